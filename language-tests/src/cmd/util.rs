@@ -170,6 +170,7 @@ pub fn core_capabilities_from_test_config(cap: &TestCapabilities) -> Capabilitie
 		.without_functions(extract_targets(&cap.deny_functions))
 		.with_network_targets(extract_targets(&cap.allow_net))
 		.without_network_targets(extract_targets(&cap.deny_net))
+		.with_function_network_targets(extract_targets(&cap.allow_function_net))
 		.with_rpc_methods(extract_targets(&cap.allow_rpc))
 		.without_rpc_methods(extract_targets(&cap.deny_rpc))
 		.with_http_routes(extract_targets(&cap.allow_http))
@@ -787,6 +787,8 @@ pub struct Capabilities {
 	pub allow_net: BoolOr<Vec<SchemaTarget<NetTarget>>>,
 	#[serde(default = "bool_or_f")]
 	pub deny_net: BoolOr<Vec<SchemaTarget<NetTarget>>>,
+	#[serde(default = "bool_or_f")]
+	pub allow_function_net: BoolOr<Vec<SchemaTarget<NetTarget>>>,
 
 	#[serde(default)]
 	pub allow_rpc: BoolOr<Vec<SchemaTarget<MethodTarget>>>,
@@ -830,6 +832,7 @@ impl Default for Capabilities {
 
 			allow_net: Default::default(),
 			deny_net: BoolOr::Bool(false),
+			allow_function_net: BoolOr::Bool(false),
 			allow_rpc: Default::default(),
 			deny_rpc: BoolOr::Bool(false),
 			allow_http: Default::default(),
@@ -19,6 +19,7 @@ pub mod format {
 	pub const CBOR: &str = "application/cbor";
 	pub const FLATBUFFERS: &str = "application/vnd.surrealdb.flatbuffers";
 	pub const NATIVE: &str = "application/vnd.surrealdb.native";
+	pub const NDJSON: &str = "application/x-ndjson";
 
 	pub const PLAIN: &str = "text/plain";
 	pub const OCTET_STREAM: &str = "application/octet-stream";
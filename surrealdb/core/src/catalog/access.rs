@@ -24,12 +24,14 @@ impl Subject {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Grant {
 	Jwt(GrantJwt),
 	Record(GrantRecord),
 	Bearer(GrantBearer),
+	#[revision(start = 2)]
+	Totp(GrantTotp),
 }
 
 impl Grant {
@@ -39,6 +41,7 @@ impl Grant {
 			Grant::Jwt(_) => "jwt",
 			Grant::Record(_) => "record",
 			Grant::Bearer(_) => "bearer",
+			Grant::Totp(_) => "totp",
 		}
 	}
 }
@@ -68,6 +71,17 @@ pub struct GrantBearer {
 	pub key: String,
 }
 
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GrantTotp {
+	// Shared secret, encoded in base32.
+	// Stored in plaintext, as it is needed to compute the expected codes.
+	// Will only be returned immediately after generation.
+	pub secret: String,
+	// Time step of the last accepted code, so that a code is only accepted once.
+	pub step: u64,
+}
+
 impl GrantBearer {
 	pub fn hashed(self) -> Self {
 		let mut hasher = Sha256::new();
@@ -116,6 +130,11 @@ impl AccessGrant {
 				gr.key = "[REDACTED]".into();
 				Grant::Bearer(gr)
 			}
+			Grant::Totp(mut gr) => {
+				// Secret is stored, but must never be displayed after enrolment.
+				gr.secret = "[REDACTED]".into();
+				Grant::Totp(gr)
+			}
 		};
 		self
 	}
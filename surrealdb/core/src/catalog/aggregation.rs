@@ -718,6 +718,9 @@ impl MutVisitor for AggregateExprCollector<'_> {
 			self.visit_mut_expr(&mut l.0)?;
 		}
 		self.visit_mut_expr(&mut s.version)?;
+		if let Some(v) = s.valid_time.as_mut() {
+			self.visit_mut_expr(v)?;
+		}
 
 		ParentRewritor.visit_mut_fields(&mut s.fields)?;
 		for o in s.omit.iter_mut() {
@@ -885,6 +888,9 @@ impl MutVisitor for ParentRewritor {
 			self.visit_mut_expr(&mut l.0)?;
 		}
 		self.visit_mut_expr(&mut s.version)?;
+		if let Some(v) = s.valid_time.as_mut() {
+			self.visit_mut_expr(v)?;
+		}
 		Ok(())
 	}
 
@@ -1000,6 +1006,25 @@ pub(crate) struct AggregationAnalysis {
 }
 
 impl AggregationAnalysis {
+	/// Returns the arguments of which a minimum or maximum is aggregated, in
+	/// order and without duplicates.
+	pub(crate) fn extremum_arguments(&self) -> Vec<usize> {
+		let mut args: Vec<usize> = self
+			.aggregations
+			.iter()
+			.filter_map(|x| match *x {
+				Aggregation::NumberMax(arg)
+				| Aggregation::NumberMin(arg)
+				| Aggregation::DatetimeMax(arg)
+				| Aggregation::DatetimeMin(arg) => Some(arg),
+				_ => None,
+			})
+			.collect();
+		args.sort_unstable();
+		args.dedup();
+		args
+	}
+
 	/// Analyze the groups and fields and produce a analysis for how to run the aggregate
 	/// expressions.
 	///
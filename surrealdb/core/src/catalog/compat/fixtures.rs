@@ -80,6 +80,7 @@ pub fn database_basic() -> DatabaseDefinition {
 		database_id: DatabaseId(1),
 		name: "test".into(),
 		strict: false,
+		capabilities: None,
 		comment: None,
 		changefeed: None,
 	}
@@ -92,6 +93,7 @@ pub fn database_with_changefeed() -> DatabaseDefinition {
 		database_id: DatabaseId(456),
 		name: "events".into(),
 		strict: false,
+		capabilities: None,
 		comment: Some("Event store".to_string()),
 		changefeed: Some(ChangeFeed {
 			expiry: Duration::from_secs(3600),
@@ -107,6 +109,7 @@ pub fn database_strict() -> DatabaseDefinition {
 		database_id: DatabaseId(2),
 		name: "strict_db".into(),
 		strict: true,
+		capabilities: None,
 		comment: Some("Strict mode database".to_string()),
 		changefeed: None,
 	}
@@ -136,6 +139,13 @@ pub fn table_basic() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		throttle: None,
+		lineage: false,
+		soft_delete: None,
+		eviction: None,
+		session: None,
+		valid_time: None,
+		session_node: None,
 	}
 }
 
@@ -170,6 +180,13 @@ pub fn table_with_view() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		throttle: None,
+		lineage: false,
+		soft_delete: None,
+		eviction: None,
+		session: None,
+		valid_time: None,
+		session_node: None,
 	}
 }
 
@@ -196,6 +213,13 @@ pub fn table_schemafull() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		throttle: None,
+		lineage: false,
+		soft_delete: None,
+		eviction: None,
+		session: None,
+		valid_time: None,
+		session_node: None,
 	}
 }
 
@@ -230,6 +254,13 @@ pub fn table_relation() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		throttle: None,
+		lineage: false,
+		soft_delete: None,
+		eviction: None,
+		session: None,
+		valid_time: None,
+		session_node: None,
 	}
 }
 
@@ -257,6 +288,13 @@ pub fn table_with_materialized_view() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		throttle: None,
+		lineage: false,
+		soft_delete: None,
+		eviction: None,
+		session: None,
+		valid_time: None,
+		session_node: None,
 	}
 }
 
@@ -280,6 +318,13 @@ pub fn table_any_type() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		throttle: None,
+		lineage: false,
+		soft_delete: None,
+		eviction: None,
+		session: None,
+		valid_time: None,
+		session_node: None,
 	}
 }
 
@@ -433,6 +478,7 @@ pub fn access_record() -> AccessDefinition {
 					issue: None,
 				},
 			}),
+			mfa: None,
 		}),
 		base: Base::Db,
 		authenticate: Some(Expr::Literal(Literal::String(
@@ -980,6 +1026,7 @@ pub fn function_basic() -> FunctionDefinition {
 		auth_limit: AuthLimit::new_no_limit(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		capabilities: None,
 	}
 }
 
@@ -995,6 +1042,7 @@ pub fn function_with_args() -> FunctionDefinition {
 		auth_limit: AuthLimit::new_no_limit(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		capabilities: None,
 	}
 }
 
@@ -6,7 +6,7 @@ use revision::{
 use serde::{Deserialize, Serialize};
 use storekey::{BorrowDecode, Encode};
 use surrealdb_strand::Strand;
-use surrealdb_types::{SqlFormat, ToSql};
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use crate::catalog::NamespaceId;
 use crate::expr::ChangeFeed;
@@ -88,7 +88,7 @@ impl From<u32> for DatabaseId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct DatabaseDefinition {
 	pub(crate) namespace_id: NamespaceId,
@@ -97,11 +97,16 @@ pub struct DatabaseDefinition {
 	pub(crate) comment: Option<String>,
 	pub(crate) changefeed: Option<ChangeFeed>,
 	pub(crate) strict: bool,
+	/// The capabilities the statements run against the database are
+	/// restricted to, declared via `CAPABILITIES { ... }`. When not set, the
+	/// statements run with the capabilities of the datastore.
+	#[revision(start = 2)]
+	pub(crate) capabilities: Option<DatabaseCapabilities>,
 }
 impl_kv_value_revisioned!(DatabaseDefinition);
 
 impl DatabaseDefinition {
-	fn to_sql_definition(&self) -> DefineDatabaseStatement {
+	pub(crate) fn to_sql_definition(&self) -> DefineDatabaseStatement {
 		DefineDatabaseStatement {
 			name: Expr::Idiom(Idiom::field(self.name.clone())),
 			comment: self
@@ -110,6 +115,7 @@ impl DatabaseDefinition {
 				.map(|v| Expr::Literal(Literal::String(v.into())))
 				.unwrap_or(Expr::Literal(Literal::None)),
 			changefeed: self.changefeed.map(|v| v.into()),
+			capabilities: self.capabilities.clone(),
 			..Default::default()
 		}
 	}
@@ -127,6 +133,56 @@ impl InfoStructure for DatabaseDefinition {
 			"name" => self.name.into(),
 			"comment", if let Some(v) = self.comment => v.into(),
 			"id" => self.database_id.0.into(),
+			"capabilities", if let Some(v) = self.capabilities => v.structure(),
 		})
 	}
 }
+
+/// The capabilities of the statements run against a database.
+///
+/// The capabilities of a database can only restrict the capabilities of the
+/// datastore, so a function which the datastore denies can not be allowed by
+/// a database.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct DatabaseCapabilities {
+	/// The functions which may be called, such as `string::*` or
+	/// `fn::charge`, or `None` to keep the functions of the datastore
+	pub(crate) functions: Option<Vec<String>>,
+	/// Whether scripting functions may be run, or `None` to keep the
+	/// scripting setting of the datastore
+	pub(crate) scripting: Option<bool>,
+}
+
+impl InfoStructure for DatabaseCapabilities {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"functions", if let Some(v) = self.functions => v.into_iter().map(Value::from).collect::<Vec<_>>().into(),
+			"scripting", if let Some(v) = self.scripting => v.into(),
+		})
+	}
+}
+
+impl ToSql for DatabaseCapabilities {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push('{');
+		if let Some(functions) = &self.functions {
+			f.push_str(" functions: [");
+			for (i, func) in functions.iter().enumerate() {
+				if i > 0 {
+					f.push_str(", ");
+				}
+				crate::fmt::QuoteStr(func).fmt_sql(f, fmt);
+			}
+			f.push(']');
+			if self.scripting.is_some() {
+				f.push(',');
+			}
+		}
+		if let Some(scripting) = self.scripting {
+			write_sql!(f, fmt, " scripting: {scripting}");
+		}
+		f.push_str(" }");
+	}
+}
@@ -12,6 +12,7 @@ mod auth;
 mod database;
 mod module;
 mod namespace;
+pub(crate) mod openapi;
 pub(crate) mod providers;
 mod record;
 mod schema;
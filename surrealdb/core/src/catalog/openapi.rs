@@ -0,0 +1,565 @@
+//! OpenAPI description of the HTTP key-value endpoints of a database.
+//!
+//! The `/key/{table}` and `/key/{table}/{id}` endpoints of the server are the
+//! same for every table, so their description is derived from the schema
+//! alone: every table gets its own paths, and a component schema built from
+//! its field definitions. Fields which are nested in other fields, such as
+//! `address.city`, are described as properties of their parent object, while
+//! fields of array elements are not described. The permissions of tables and
+//! fields are summarised in `x-surrealdb-permissions` extensions.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use surrealdb_types::ToSql;
+
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{
+	DatabaseId, DefineDefault, FieldDefinition, NamespaceId, Permission, TableDefinition,
+};
+use crate::expr::{Kind, KindLiteral, Part};
+use crate::kvs::Transaction;
+use crate::val::{Object, Value};
+
+/// The version of the OpenAPI specification of the document
+const OPENAPI_VERSION: &str = "3.1.0";
+
+/// Describes the key-value endpoints of the tables of a database
+pub(crate) async fn document(
+	txn: &Transaction,
+	(ns, db): (NamespaceId, DatabaseId),
+	(ns_name, db_name): (&str, &str),
+) -> Result<Value> {
+	let mut paths = BTreeMap::new();
+	let mut schemas = BTreeMap::new();
+	for tb in txn.all_tb(ns, db, None).await?.iter() {
+		let fields = txn.all_tb_fields(ns, db, &tb.name, None).await?;
+		let name = component_name(tb.name.as_str());
+		let (table, record) = table_paths(tb, &name);
+		let path = format!("/key/{}", encode_segment(tb.name.as_str()));
+		paths.insert(format!("{path}/{{id}}"), record);
+		paths.insert(path, table);
+		schemas.insert(name, table_schema(tb, &fields));
+	}
+	Ok(Value::from(map! {
+		"openapi" => Value::from(OPENAPI_VERSION),
+		"info" => Value::from(map! {
+			"title" => Value::from(format!("{ns_name}/{db_name}")),
+			"description" => Value::from(format!(
+				"The key-value endpoints of the tables of the {db_name} database in the {ns_name} namespace"
+			)),
+			"version" => Value::from(crate::env::VERSION),
+		}),
+		"paths" => Value::from(paths),
+		"components" => Value::from(map! {
+			"schemas" => Value::from(schemas),
+			"parameters" => parameters(ns_name, db_name),
+			"securitySchemes" => Value::from(map! {
+				"bearer" => Value::from(map! {
+					"type" => Value::from("http"),
+					"scheme" => Value::from("bearer"),
+				}),
+				"basic" => Value::from(map! {
+					"type" => Value::from("http"),
+					"scheme" => Value::from("basic"),
+				}),
+			}),
+		}),
+		"security" => Value::from(vec![
+			Value::from(map! { "bearer" => Value::from(Vec::<Value>::new()) }),
+			Value::from(map! { "basic" => Value::from(Vec::<Value>::new()) }),
+		]),
+	}))
+}
+
+/// The parameters shared by the operations of every table
+fn parameters(ns: &str, db: &str) -> Value {
+	let header = |name: &str, default: &str, description: &str| {
+		Value::from(map! {
+			"name" => Value::from(name),
+			"in" => Value::from("header"),
+			"required" => Value::Bool(true),
+			"description" => Value::from(description),
+			"schema" => Value::from(map! {
+				"type" => Value::from("string"),
+				"default" => Value::from(default),
+			}),
+		})
+	};
+	let query = |name: &str, schema: Value, description: &str| {
+		Value::from(map! {
+			"name" => Value::from(name),
+			"in" => Value::from("query"),
+			"required" => Value::Bool(false),
+			"description" => Value::from(description),
+			"schema" => schema,
+		})
+	};
+	Value::from(map! {
+		"namespace" => header("Surreal-NS", ns, "The namespace of the database"),
+		"database" => header("Surreal-DB", db, "The database of the tables"),
+		"id" => Value::from(map! {
+			"name" => Value::from("id"),
+			"in" => Value::from("path"),
+			"required" => Value::Bool(true),
+			"description" => Value::from("The id of the record, as a string or as a JSON value"),
+			"schema" => ty("string"),
+		}),
+		"limit" => query(
+			"limit",
+			Value::from(map! {
+				"type" => Value::from("integer"),
+				"default" => Value::from(100),
+			}),
+			"The maximum number of records to select",
+		),
+		"start" => query(
+			"start",
+			Value::from(map! {
+				"type" => Value::from("integer"),
+				"default" => Value::from(0),
+			}),
+			"The number of records to skip",
+		),
+		"fields" => query(
+			"fields",
+			Value::from(map! {
+				"type" => Value::from("array"),
+				"items" => ty("string"),
+			}),
+			"The fields to select, instead of every field",
+		),
+	})
+}
+
+/// Describes the paths of a table, and of its records
+fn table_paths(tb: &TableDefinition, name: &str) -> (Value, Value) {
+	let operation = |id: &str, summary: String, params: &[&str], body: bool| {
+		let mut parameters =
+			vec![reference("parameters", "namespace"), reference("parameters", "database")];
+		parameters.extend(params.iter().map(|p| reference("parameters", p)));
+		Value::from(map! {
+			"operationId" => Value::from(format!("{id}_{name}")),
+			"summary" => Value::from(summary),
+			"tags" => Value::from(vec![Value::from(tb.name.as_str())]),
+			"parameters" => Value::from(parameters),
+			"requestBody", if body => Value::from(map! {
+				"required" => Value::Bool(true),
+				"content" => json(reference("schemas", name)),
+			}),
+			"responses" => Value::from(map! {
+				"200" => Value::from(map! {
+					"description" => Value::from("The results of the statement"),
+					"content" => json(response(name)),
+				}),
+			}),
+		})
+	};
+	let table = tb.name.as_str();
+	// The records of a view can only be selected
+	let writable = tb.view.is_none();
+	let mut all = BTreeMap::new();
+	let mut one = BTreeMap::new();
+	all.insert(
+		"get".to_owned(),
+		operation(
+			"select",
+			format!("Selects the records of {table}"),
+			&["limit", "start", "fields"],
+			false,
+		),
+	);
+	one.insert(
+		"get".to_owned(),
+		operation("select_one", format!("Selects a record of {table}"), &["id", "fields"], false),
+	);
+	if writable {
+		all.insert(
+			"post".to_owned(),
+			operation("create", format!("Creates a record in {table}"), &[], true),
+		);
+		all.insert(
+			"put".to_owned(),
+			operation(
+				"update",
+				format!("Replaces the content of every record of {table}"),
+				&[],
+				true,
+			),
+		);
+		all.insert(
+			"patch".to_owned(),
+			operation("merge", format!("Merges data into every record of {table}"), &[], true),
+		);
+		all.insert(
+			"delete".to_owned(),
+			operation("delete", format!("Deletes every record of {table}"), &[], false),
+		);
+		one.insert(
+			"post".to_owned(),
+			operation("create_one", format!("Creates a record of {table}"), &["id"], true),
+		);
+		one.insert(
+			"put".to_owned(),
+			operation(
+				"upsert_one",
+				format!("Creates or replaces the content of a record of {table}"),
+				&["id"],
+				true,
+			),
+		);
+		one.insert(
+			"patch".to_owned(),
+			operation(
+				"merge_one",
+				format!("Creates or merges data into a record of {table}"),
+				&["id"],
+				true,
+			),
+		);
+		one.insert(
+			"delete".to_owned(),
+			operation("delete_one", format!("Deletes a record of {table}"), &["id"], false),
+		);
+	}
+	(Value::from(all), Value::from(one))
+}
+
+/// Describes the response of the endpoints, which holds the result of the
+/// statement they run
+fn response(name: &str) -> Value {
+	Value::from(map! {
+		"type" => Value::from("array"),
+		"items" => Value::from(map! {
+			"type" => Value::from("object"),
+			"required" => strings(["result", "status", "time"]),
+			"properties" => Value::from(map! {
+				"status" => Value::from(map! {
+					"type" => Value::from("string"),
+					"enum" => strings(["OK", "ERR"]),
+				}),
+				"time" => ty("string"),
+				"result" => Value::from(map! {
+					"anyOf" => Value::from(vec![
+						Value::from(map! {
+							"type" => Value::from("array"),
+							"items" => reference("schemas", name),
+						}),
+						Value::from(map! {
+							"type" => Value::from("string"),
+							"description" => Value::from("The error, when the status is ERR"),
+						}),
+					]),
+				}),
+			}),
+		}),
+	})
+}
+
+/// The fields of a table, arranged by their path
+#[derive(Default)]
+struct FieldTree<'a> {
+	field: Option<&'a FieldDefinition>,
+	children: BTreeMap<&'a str, FieldTree<'a>>,
+}
+
+impl<'a> FieldTree<'a> {
+	fn insert(&mut self, fd: &'a FieldDefinition) {
+		let mut node = self;
+		for part in fd.name.0.iter() {
+			// Fields of array elements, and other computed paths, are skipped
+			let Part::Field(name) = part else {
+				return;
+			};
+			node = node.children.entry(name.as_str()).or_default();
+		}
+		node.field = Some(fd);
+	}
+
+	/// Describes the nested fields as the properties of an object schema
+	fn properties(&self) -> (BTreeMap<String, Value>, Vec<Value>) {
+		let mut properties = BTreeMap::new();
+		let mut required = Vec::new();
+		for (name, node) in self.children.iter() {
+			let (schema, is_required) = node.schema();
+			if is_required {
+				required.push(Value::from(*name));
+			}
+			properties.insert((*name).to_owned(), schema);
+		}
+		(properties, required)
+	}
+
+	/// Describes a field, and whether it is always present on a record
+	fn schema(&self) -> (Value, bool) {
+		let Some(fd) = self.field else {
+			let (properties, _) = self.properties();
+			return (object_with(properties, Vec::new()), false);
+		};
+		let kind = fd.field_kind.as_ref().unwrap_or(&Kind::Any);
+		let mut schema = match kind {
+			Kind::Any | Kind::Object if !self.children.is_empty() => {
+				let (properties, required) = self.properties();
+				object_with(properties, required)
+			}
+			kind => kind_schema(kind),
+		};
+		if let Value::Object(schema) = &mut schema {
+			if let Some(comment) = &fd.comment {
+				schema.insert("description".to_owned(), Value::from(comment.as_str()));
+			}
+			if fd.readonly || fd.computed.is_some() {
+				schema.insert("readOnly".to_owned(), Value::Bool(true));
+			}
+			let permissions = [
+				("select", &fd.select_permission),
+				("create", &fd.create_permission),
+				("update", &fd.update_permission),
+			];
+			if permissions.iter().any(|(_, p)| !matches!(p, Permission::Full)) {
+				schema.insert(
+					"x-surrealdb-permissions".to_owned(),
+					permissions_summary(&permissions),
+				);
+			}
+		}
+		let required = !kind.can_be_none()
+			&& fd.computed.is_none()
+			&& fd.value.is_none()
+			&& matches!(fd.default, DefineDefault::None);
+		(schema, required)
+	}
+}
+
+/// Describes the records of a table
+fn table_schema(tb: &TableDefinition, fields: &[FieldDefinition]) -> Value {
+	let mut tree = FieldTree::default();
+	for fd in fields.iter() {
+		tree.insert(fd);
+	}
+	// The id of a record is described on its own
+	tree.children.remove("id");
+	let (mut properties, mut required) = tree.properties();
+	properties.insert(
+		"id".to_owned(),
+		Value::from(map! {
+			"type" => Value::from("string"),
+			"description" => Value::from("The id of the record, such as `table:id`"),
+			"readOnly" => Value::Bool(true),
+		}),
+	);
+	required.insert(0, Value::from("id"));
+	let mut schema = object_with(properties, required);
+	if let Value::Object(schema) = &mut schema {
+		if let Some(comment) = &tb.comment {
+			schema.insert("description".to_owned(), Value::from(comment.as_str()));
+		}
+		if tb.schemafull {
+			schema.insert("additionalProperties".to_owned(), Value::Bool(false));
+		}
+		let permissions = &tb.permissions;
+		schema.insert(
+			"x-surrealdb-permissions".to_owned(),
+			permissions_summary(&[
+				("select", &permissions.select),
+				("create", &permissions.create),
+				("update", &permissions.update),
+				("delete", &permissions.delete),
+			]),
+		);
+	}
+	schema
+}
+
+/// Describes the values of a type, as they are returned in JSON
+fn kind_schema(kind: &Kind) -> Value {
+	match kind {
+		Kind::Any | Kind::Function(..) => Value::Object(Object::default()),
+		Kind::None | Kind::Null => ty("null"),
+		Kind::Bool => ty("boolean"),
+		Kind::Bytes => Value::from(map! {
+			"type" => Value::from("array"),
+			"items" => Value::from(map! {
+				"type" => Value::from("integer"),
+				"minimum" => Value::from(0),
+				"maximum" => Value::from(255),
+			}),
+		}),
+		Kind::Datetime => formatted("date-time"),
+		Kind::Decimal | Kind::FixedDecimal(_, _) => formatted("decimal"),
+		Kind::Duration => formatted("duration"),
+		Kind::Float => ty("number"),
+		Kind::Int => ty("integer"),
+		// Decimal numbers are returned as strings
+		Kind::Number => Value::from(map! {
+			"anyOf" => Value::from(vec![ty("number"), formatted("decimal")]),
+		}),
+		Kind::Object => ty("object"),
+		Kind::String | Kind::Regex | Kind::Table(_) | Kind::Range | Kind::File(_) => ty("string"),
+		Kind::Uuid => formatted("uuid"),
+		Kind::Record(tables) => {
+			let tables = tables.iter().map(|tb| Value::from(tb.as_str())).collect::<Vec<_>>();
+			Value::from(map! {
+				"type" => Value::from("string"),
+				"format" => Value::from("record"),
+				"x-surrealdb-tables", if !tables.is_empty() => Value::from(tables),
+			})
+		}
+		Kind::Geometry(_) => Value::from(map! {
+			"type" => Value::from("object"),
+			"description" => Value::from("A GeoJSON geometry"),
+		}),
+		Kind::Either(kinds) => {
+			// A missing value is described by the field not being required
+			let kinds = kinds.iter().filter(|k| !matches!(k, Kind::None)).collect::<Vec<_>>();
+			match kinds.as_slice() {
+				[kind] => kind_schema(kind),
+				kinds => Value::from(map! {
+					"anyOf" => Value::from(kinds.iter().map(|k| kind_schema(k)).collect::<Vec<_>>()),
+				}),
+			}
+		}
+		Kind::Array(kind, max) => array(kind, *max, false),
+		Kind::Set(kind, max) => array(kind, *max, true),
+		Kind::Literal(literal) => literal_schema(literal),
+	}
+}
+
+fn array(kind: &Kind, max: Option<u64>, unique: bool) -> Value {
+	Value::from(map! {
+		"type" => Value::from("array"),
+		"items" => kind_schema(kind),
+		"maxItems", if let Some(max) = max => Value::from(max),
+		"uniqueItems", if unique => Value::Bool(true),
+	})
+}
+
+fn literal_schema(literal: &KindLiteral) -> Value {
+	let constant = |value: Value| Value::from(map! { "const" => value });
+	match literal {
+		KindLiteral::String(v) => constant(Value::from(v.as_str())),
+		KindLiteral::Integer(v) => constant(Value::from(*v)),
+		KindLiteral::Float(v) => constant(Value::from(*v)),
+		KindLiteral::Decimal(v) => constant(Value::from(v.to_string())),
+		KindLiteral::Duration(v) => constant(Value::from(v.to_string())),
+		KindLiteral::Bool(v) => constant(Value::Bool(*v)),
+		KindLiteral::Array(kinds) => Value::from(map! {
+			"type" => Value::from("array"),
+			"prefixItems" => Value::from(kinds.iter().map(kind_schema).collect::<Vec<_>>()),
+			"minItems" => Value::from(kinds.len()),
+			"items" => Value::Bool(false),
+		}),
+		KindLiteral::Object(fields) => {
+			let mut properties = BTreeMap::new();
+			let mut required = Vec::new();
+			for (name, kind) in fields.iter() {
+				if !kind.can_be_none() {
+					required.push(Value::from(name.as_str()));
+				}
+				properties.insert(name.as_str().to_owned(), kind_schema(kind));
+			}
+			let mut schema = object_with(properties, required);
+			if let Value::Object(schema) = &mut schema {
+				schema.insert("additionalProperties".to_owned(), Value::Bool(false));
+			}
+			schema
+		}
+	}
+}
+
+fn object_with(properties: BTreeMap<String, Value>, required: Vec<Value>) -> Value {
+	Value::from(map! {
+		"type" => Value::from("object"),
+		"properties" => Value::from(properties),
+		"required", if !required.is_empty() => Value::from(required),
+	})
+}
+
+/// Summarises permission clauses as `FULL`, `NONE` or their `WHERE` clause
+fn permissions_summary(permissions: &[(&str, &Permission)]) -> Value {
+	Value::from(
+		permissions
+			.iter()
+			.map(|(action, permission)| ((*action).to_owned(), Value::from(permission.to_sql())))
+			.collect::<BTreeMap<_, _>>(),
+	)
+}
+
+fn ty(name: &str) -> Value {
+	Value::from(map! { "type" => Value::from(name) })
+}
+
+fn formatted(format: &str) -> Value {
+	Value::from(map! {
+		"type" => Value::from("string"),
+		"format" => Value::from(format),
+	})
+}
+
+fn strings<const N: usize>(values: [&str; N]) -> Value {
+	Value::from(values.into_iter().map(Value::from).collect::<Vec<_>>())
+}
+
+fn reference(kind: &str, name: &str) -> Value {
+	Value::from(map! { "$ref" => Value::from(format!("#/components/{kind}/{name}")) })
+}
+
+fn json(schema: Value) -> Value {
+	Value::from(map! {
+		"application/json" => Value::from(map! { "schema" => schema }),
+	})
+}
+
+/// Names the component schema of a table, which may only contain letters,
+/// digits, `.`, `-` and `_`
+fn component_name(table: &str) -> String {
+	table
+		.chars()
+		.map(|c| match c {
+			'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+			_ => '_',
+		})
+		.collect()
+}
+
+/// Percent-encodes a table name as a path segment
+fn encode_segment(table: &str) -> String {
+	let mut out = String::with_capacity(table.len());
+	for byte in table.bytes() {
+		match byte {
+			b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+				out.push(byte as char)
+			}
+			_ => out.push_str(&format!("%{byte:02X}")),
+		}
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn describes_the_values_of_types() {
+		let option = Kind::option(Kind::Datetime);
+		assert_eq!(kind_schema(&option), formatted("date-time"));
+		let tags = Kind::Set(Box::new(Kind::String), Some(3));
+		assert_eq!(
+			kind_schema(&tags),
+			Value::from(map! {
+				"type" => Value::from("array"),
+				"items" => ty("string"),
+				"maxItems" => Value::from(3u64),
+				"uniqueItems" => Value::Bool(true),
+			})
+		);
+	}
+
+	#[test]
+	fn escapes_table_names() {
+		assert_eq!(component_name("user-events"), "user-events");
+		assert_eq!(component_name("événement"), "_v_nement");
+		assert_eq!(encode_segment("a b/c"), "a%20b%2Fc");
+	}
+}
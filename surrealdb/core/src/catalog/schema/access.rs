@@ -58,6 +58,7 @@ impl InfoStructure for AccessType {
 				"signup", if let Some(v) = v.signup => v.structure(),
 				"signin", if let Some(v) = v.signin => v.structure(),
 				"refresh", if v.bearer.is_some() => true.into(),
+				"mfa", if let Some(v) = v.mfa => v.to_string().into(),
 			}),
 			AccessType::Bearer(ac) => Value::from(map! {
 				"kind" => "BEARER".into(),
@@ -71,13 +72,31 @@ impl InfoStructure for AccessType {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
 pub(crate) struct RecordAccess {
 	pub signup: Option<Expr>,
 	pub signin: Option<Expr>,
 	pub jwt: JwtAccess,
 	pub bearer: Option<BearerAccess>,
+	#[revision(start = 2)]
+	pub mfa: Option<MfaMethod>,
+}
+
+/// The second factor required by a record access method
+#[revisioned(revision = 1)]
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
+pub enum MfaMethod {
+	/// Time-based one-time passwords, as described by RFC 6238
+	Totp,
+}
+
+impl fmt::Display for MfaMethod {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Totp => f.write_str("TOTP"),
+		}
+	}
 }
 
 #[revisioned(revision = 1)]
@@ -393,6 +412,7 @@ impl From<RecordAccess> for crate::expr::RecordAccess {
 			signin: v.signin,
 			jwt: v.jwt.into(),
 			bearer: v.bearer.map(|b| b.into()),
+			mfa: v.mfa.map(Into::into),
 		}
 	}
 }
@@ -404,6 +424,7 @@ impl From<crate::expr::RecordAccess> for RecordAccess {
 			signin: v.signin,
 			jwt: v.jwt.into(),
 			bearer: v.bearer.map(|b| b.into()),
+			mfa: v.mfa.map(Into::into),
 		}
 	}
 }
@@ -543,6 +564,22 @@ impl From<crate::expr::access_type::BearerAccessType> for BearerAccessType {
 	}
 }
 
+impl From<MfaMethod> for crate::expr::access_type::MfaMethod {
+	fn from(v: MfaMethod) -> Self {
+		match v {
+			MfaMethod::Totp => Self::Totp,
+		}
+	}
+}
+
+impl From<crate::expr::access_type::MfaMethod> for MfaMethod {
+	fn from(v: crate::expr::access_type::MfaMethod) -> Self {
+		match v {
+			crate::expr::access_type::MfaMethod::Totp => Self::Totp,
+		}
+	}
+}
+
 impl From<BearerAccessSubject> for crate::expr::access_type::BearerAccessSubject {
 	fn from(v: BearerAccessSubject) -> Self {
 		match v {
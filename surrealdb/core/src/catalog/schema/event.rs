@@ -27,7 +27,7 @@ pub struct EventDefinition {
 	pub(crate) kind: EventKind,
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum EventKind {
@@ -38,6 +38,14 @@ pub enum EventKind {
 		/// Maximum async event nesting depth for this event (0 allows top-level only).
 		max_depth: u16,
 	},
+	/// The event runs within the writing transaction, and its HTTP requests are
+	/// recorded in the outbox, to be sent once the transaction has committed.
+	#[revision(start = 2)]
+	Outbox {
+		/// Maximum retry count for each request (0 disables retries; the request is still sent
+		/// once).
+		retry: u16,
+	},
 }
 
 // This was pushed in after the first beta, so we need to add auth_limit to structs in a
@@ -85,13 +93,19 @@ impl EventDefinition {
 			EventKind::Async {
 				retry,
 				..
+			}
+			| EventKind::Outbox {
+				retry,
 			} => retry,
 		}
 	}
 
 	pub(crate) fn max_depth(&self) -> u16 {
 		match self.kind {
-			EventKind::Sync => 0,
+			EventKind::Sync
+			| EventKind::Outbox {
+				..
+			} => 0,
 			EventKind::Async {
 				max_depth,
 				..
@@ -118,6 +132,13 @@ impl InfoStructure for EventDefinition {
 			map.insert("retry", (*retry).into());
 			map.insert("maxdepth", (*max_depth).into());
 		}
+		if let EventKind::Outbox {
+			retry,
+		} = &self.kind
+		{
+			map.insert("outbox", Value::Bool(true));
+			map.insert("retry", (*retry).into());
+		}
 		Value::from(map)
 	}
 }
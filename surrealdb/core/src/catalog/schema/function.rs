@@ -1,9 +1,15 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::{Result, ensure};
 use revision::revisioned;
 use surrealdb_strand::Strand;
 use surrealdb_types::{SqlFormat, ToSql};
 
 use crate::catalog::Permission;
 use crate::catalog::auth::AuthLimit;
+use crate::dbs::capabilities::{Capabilities, NetTarget, Targets};
+use crate::err::Error;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::{Block, Kind};
 use crate::kvs::impl_kv_value_revisioned;
@@ -11,7 +17,7 @@ use crate::sql::statements::define::DefineKind;
 use crate::sql::{self, DefineFunctionStatement};
 use crate::val::Value;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct FunctionDefinition {
 	pub(crate) name: Strand,
@@ -32,6 +38,12 @@ pub struct FunctionDefinition {
 	/// auto-generated Query field for this function.
 	#[revision(start = 3)]
 	pub(crate) graphql_deprecated: Option<String>,
+
+	/// The capabilities the function runs with, declared via
+	/// `CAPABILITIES { ... }`. When not set, the function runs with the
+	/// capabilities of the calling query.
+	#[revision(start = 4)]
+	pub(crate) capabilities: Option<FunctionCapabilities>,
 }
 
 // This was pushed in after the first beta, so we need to add auth_limit to structs in a
@@ -60,6 +72,7 @@ impl FunctionDefinition {
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
 			graphql_alias: self.graphql_alias.clone(),
 			graphql_deprecated: self.graphql_deprecated.clone(),
+			capabilities: self.capabilities.clone(),
 		}
 	}
 }
@@ -79,6 +92,7 @@ impl InfoStructure for FunctionDefinition {
 			"returns", if let Some(v) = self.returns => v.to_sql().into(),
 			"graphql_alias", if let Some(v) = self.graphql_alias => v.into(),
 			"graphql_deprecated", if let Some(v) = self.graphql_deprecated => v.into(),
+			"capabilities", if let Some(v) = self.capabilities => v.structure(),
 		})
 	}
 }
@@ -88,3 +102,57 @@ impl ToSql for &FunctionDefinition {
 		self.to_sql_definition().fmt_sql(f, fmt)
 	}
 }
+
+/// The capabilities granted to a function for the duration of its execution.
+///
+/// A function with capabilities may only connect to the network targets it
+/// lists, regardless of the targets allowed for the calling query.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct FunctionCapabilities {
+	/// The network targets the function may connect to
+	pub(crate) net: Vec<String>,
+}
+
+impl FunctionCapabilities {
+	/// Returns the network targets granted to the function, ensuring that each
+	/// of them may be granted to a function under the given capabilities.
+	pub(crate) fn net_targets(&self, caps: &Capabilities) -> Result<Targets<NetTarget>> {
+		if self.net.is_empty() {
+			return Ok(Targets::None);
+		}
+		let mut targets = HashSet::new();
+		for net in self.net.iter() {
+			let target =
+				NetTarget::from_str(net).map_err(|_| Error::NetTargetNotAllowed(net.clone()))?;
+			ensure!(
+				caps.allows_function_network_target(&target),
+				Error::NetTargetNotAllowed(target.to_string())
+			);
+			targets.insert(target);
+		}
+		Ok(Targets::Some(targets))
+	}
+}
+
+impl InfoStructure for FunctionCapabilities {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"net" => self.net.into_iter().map(Value::from).collect::<Vec<_>>().into(),
+		})
+	}
+}
+
+impl ToSql for FunctionCapabilities {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push_str("{ net: [");
+		for (i, net) in self.net.iter().enumerate() {
+			if i > 0 {
+				f.push_str(", ");
+			}
+			crate::fmt::QuoteStr(net).fmt_sql(f, fmt);
+		}
+		f.push_str("] }");
+	}
+}
@@ -103,6 +103,7 @@ impl IndexDefinition {
 				.map(|x| sql::Expr::Literal(sql::Literal::String(x.into())))
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
 			concurrently: false,
+			conflicts: Default::default(),
 		}
 	}
 
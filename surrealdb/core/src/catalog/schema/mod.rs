@@ -14,6 +14,8 @@ mod index;
 mod ml;
 mod module;
 mod param;
+mod policy;
+mod query;
 mod sequence;
 mod user;
 use std::fmt::{Display, Formatter};
@@ -30,6 +32,8 @@ pub use index::*;
 pub use ml::*;
 pub use module::*;
 pub(crate) use param::*;
+pub use policy::*;
+pub use query::*;
 pub use sequence::*;
 pub use user::*;
 
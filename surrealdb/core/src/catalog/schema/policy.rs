@@ -0,0 +1,70 @@
+use revision::revisioned;
+use surrealdb_strand::Strand;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::expr::Expr;
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql::statements::define::{DefineKind, DefinePolicyStatement};
+use crate::sql::{self};
+use crate::val::{TableName, Value};
+
+/// An archival policy, which periodically moves the records of a table
+/// matching a condition into another table.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct PolicyDefinition {
+	pub(crate) name: Strand,
+	/// The table the records are moved from
+	pub(crate) target_table: TableName,
+	/// The condition a record must match to be moved
+	pub(crate) when: Expr,
+	/// The table the records are moved to
+	pub(crate) destination: TableName,
+	/// The maximum number of records moved in each transaction
+	pub(crate) batch: u32,
+	pub(crate) comment: Option<String>,
+}
+
+impl_kv_value_revisioned!(PolicyDefinition);
+
+impl PolicyDefinition {
+	/// The default number of records moved in each transaction
+	pub(crate) const DEFAULT_BATCH: u32 = 1000;
+
+	pub(crate) fn to_sql_definition(&self) -> DefinePolicyStatement {
+		DefinePolicyStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			target_table: sql::Expr::Table(self.target_table.clone()),
+			when: self.when.clone().into(),
+			destination: sql::Expr::Table(self.destination.clone()),
+			batch: sql::Expr::Literal(sql::Literal::Integer(self.batch as i64)),
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v.into())))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+}
+
+impl InfoStructure for PolicyDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name" => self.name.into(),
+			"what" => self.target_table.into(),
+			"when" => self.when.structure(),
+			"move_to" => self.destination.into(),
+			"batch" => self.batch.into(),
+			"comment", if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
+
+impl ToSql for PolicyDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
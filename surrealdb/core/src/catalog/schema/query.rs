@@ -0,0 +1,81 @@
+use revision::revisioned;
+use surrealdb_strand::Strand;
+use surrealdb_types::{SqlFormat, ToSql};
+use uuid::Uuid;
+
+use crate::catalog::Permission;
+use crate::catalog::auth::AuthLimit;
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{Expr, Kind};
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql::statements::define::{DefineKind, DefineQueryStatement};
+use crate::sql::{self};
+use crate::val::Value;
+
+/// A persisted query template, which is run by name with a set of named
+/// arguments.
+///
+/// The query is stored already parsed, so running it never parses the query
+/// again. Each definition of a template is given a new version and plan id,
+/// and the plan compiled from the query is cached under that plan id, so a
+/// redefined template is never run with the plan of its previous version.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct QueryDefinition {
+	pub(crate) name: Strand,
+	/// The arguments of the query, bound as parameters when it is run
+	pub(crate) args: Vec<(String, Kind)>,
+	/// The query which is run
+	pub(crate) query: Expr,
+	pub(crate) comment: Option<String>,
+	pub(crate) permissions: Permission,
+	/// The auth limit of the user which defined the query
+	pub(crate) auth_limit: AuthLimit,
+	/// The version of the template, incremented each time it is redefined
+	pub(crate) version: u64,
+	/// The id under which the plan of this version of the template is cached
+	pub(crate) plan_id: Uuid,
+}
+
+impl_kv_value_revisioned!(QueryDefinition);
+
+impl QueryDefinition {
+	pub(crate) fn to_sql_definition(&self) -> DefineQueryStatement {
+		DefineQueryStatement {
+			kind: DefineKind::Default,
+			name: self.name.clone(),
+			args: self.args.clone().into_iter().map(|(n, k)| (n, sql::Kind::from(k))).collect(),
+			query: self.query.clone().into(),
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v.into())))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+			permissions: self.permissions.clone().into(),
+		}
+	}
+}
+
+impl InfoStructure for QueryDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name" => self.name.into(),
+			"args" => self.args
+				.into_iter()
+				.map(|(n, k)| vec![n.into(), k.to_sql().into()].into())
+				.collect::<Vec<Value>>()
+				.into(),
+			"query" => self.query.to_sql().into(),
+			"version" => self.version.into(),
+			"permissions" => self.permissions.structure(),
+			"comment", if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
+
+impl ToSql for QueryDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
@@ -6,7 +6,8 @@ use uuid::Uuid;
 
 use crate::catalog::{DatabaseId, NamespaceId};
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{Expr, Fetchs, Fields};
+use crate::expr::visit::{Visit, Visitor};
+use crate::expr::{Expr, Fetchs, Fields, Function, Param};
 use crate::iam::Auth;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql::statements::live::LiveFields;
@@ -56,7 +57,74 @@ pub struct SubscriptionDefinition {
 
 impl_kv_value_revisioned!(SubscriptionDefinition);
 
+/// Visitor which stops at the first reference to the named parameter
+struct ParamReference<'a>(&'a str);
+
+impl Visitor for ParamReference<'_> {
+	type Error = ();
+
+	fn visit_param(&mut self, param: &Param) -> Result<(), ()> {
+		if param.as_str() == self.0 {
+			Err(())
+		} else {
+			Ok(())
+		}
+	}
+}
+
+/// Visitor which stops at the first expression which may read the session it
+/// is evaluated in: `$session`, a `session::` function, or a function whose
+/// body can not be seen from the expression
+struct SessionReference;
+
+impl Visitor for SessionReference {
+	type Error = ();
+
+	fn visit_param(&mut self, param: &Param) -> Result<(), ()> {
+		if param.as_str() == "session" {
+			Err(())
+		} else {
+			Ok(())
+		}
+	}
+
+	fn visit_function(&mut self, function: &Function) -> Result<(), ()> {
+		match function {
+			Function::Normal(name) if !name.starts_with("session::") => Ok(()),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Returns whether an expression may read the session it is evaluated in
+pub(crate) fn reads_session(expr: &Expr) -> bool {
+	expr.visit(&mut SessionReference).is_err()
+}
+
 impl SubscriptionDefinition {
+	/// Returns whether the live query refers to the named parameter
+	pub(crate) fn references_param(&self, name: &str) -> bool {
+		self.visit_parts(&mut ParamReference(name))
+	}
+
+	/// Returns whether the live query may read the session it is evaluated in
+	pub(crate) fn reads_session(&self) -> bool {
+		self.visit_parts(&mut SessionReference)
+	}
+
+	/// Visits each part of the live query, returning whether the visitor
+	/// stopped at one of them
+	fn visit_parts<V: Visitor<Error = ()>>(&self, visitor: &mut V) -> bool {
+		if let SubscriptionFields::Select(x) = &self.fields
+			&& x.visit(visitor).is_err()
+		{
+			return true;
+		}
+		self.what.visit(visitor).is_err()
+			|| self.cond.as_ref().is_some_and(|x| x.visit(visitor).is_err())
+			|| self.fetch.iter().flat_map(|x| x.iter()).any(|x| x.0.visit(visitor).is_err())
+	}
+
 	fn to_sql_definition(&self) -> crate::sql::LiveStatement {
 		let fields = match &self.fields {
 			SubscriptionFields::Diff => LiveFields::Diff,
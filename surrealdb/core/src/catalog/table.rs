@@ -6,7 +6,7 @@ use uuid::Uuid;
 
 use crate::catalog::{DatabaseId, NamespaceId, Permissions, ViewDefinition};
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{ChangeFeed, Kind};
+use crate::expr::{ChangeFeed, Eviction, Kind, SoftDelete, Throttle, ValidTime};
 use crate::fmt::EscapeKwFreeIdent;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
@@ -60,7 +60,7 @@ impl revision::WalkRevisioned for TableId {
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 9)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TableDefinition {
 	pub(crate) namespace_id: NamespaceId,
@@ -94,6 +94,41 @@ pub struct TableDefinition {
 	/// auto-generated Query/Mutation field that targets this table.
 	#[revision(start = 2)]
 	pub(crate) graphql_deprecated: Option<String>,
+
+	/// The write rate allowed on this table, enforced when records are
+	/// written, if the table was defined with a `THROTTLE` clause.
+	#[revision(start = 3)]
+	pub(crate) throttle: Option<Throttle>,
+
+	/// Whether the statement, event or field which last wrote each field of
+	/// a record is recorded, if the table was defined with `LINEAGE`.
+	#[revision(start = 4)]
+	pub(crate) lineage: bool,
+
+	/// The field marking records as deleted, and how long they are kept,
+	/// if the table was defined with a `SOFT DELETE` clause.
+	#[revision(start = 5)]
+	pub(crate) soft_delete: Option<SoftDelete>,
+
+	/// When records are evicted by the background eviction task, if the
+	/// table was defined with an `EVICT` clause.
+	#[revision(start = 6)]
+	pub(crate) eviction: Option<Eviction>,
+
+	/// The session which owns this table, if the table was defined with
+	/// `SESSION`, and is removed once the session ends.
+	#[revision(start = 7)]
+	pub(crate) session: Option<Uuid>,
+
+	/// The fields holding the valid time of each record, if the table was
+	/// defined with a `BITEMPORAL` clause.
+	#[revision(start = 8)]
+	pub(crate) valid_time: Option<ValidTime>,
+
+	/// The node on which the session owning this table is connected, so that
+	/// the table can be removed if the node leaves the cluster.
+	#[revision(start = 9)]
+	pub(crate) session_node: Option<Uuid>,
 }
 
 impl_kv_value_revisioned!(TableDefinition);
@@ -124,6 +159,13 @@ impl TableDefinition {
 			cache_indexes_ts: now,
 			graphql_alias: None,
 			graphql_deprecated: None,
+			throttle: None,
+			lineage: false,
+			soft_delete: None,
+			eviction: None,
+			session: None,
+			valid_time: None,
+			session_node: None,
 		}
 	}
 
@@ -145,6 +187,12 @@ impl TableDefinition {
 			view: self.view.clone().map(|v| v.to_sql_definition()),
 			permissions: self.permissions.clone().into(),
 			changefeed: self.changefeed.map(|v| v.into()),
+			throttle: self.throttle.map(|v| v.into()),
+			lineage: self.lineage,
+			soft_delete: self.soft_delete.clone().map(|v| v.into()),
+			eviction: self.eviction.map(|v| v.into()),
+			session: self.session.is_some(),
+			valid_time: self.valid_time.clone().map(|v| v.into()),
 			comment: self
 				.comment
 				.clone()
@@ -173,6 +221,12 @@ impl InfoStructure for TableDefinition {
 			"kind" => self.table_type.structure(),
 			"view", if let Some(v) = self.view => v.structure(),
 			"changefeed", if let Some(v) = self.changefeed => v.structure(),
+			"throttle", if let Some(v) = self.throttle => v.structure(),
+			"lineage", if self.lineage => true.into(),
+			"soft_delete", if let Some(v) = self.soft_delete => v.structure(),
+			"eviction", if let Some(v) = self.eviction => v.structure(),
+			"session", if self.session.is_some() => true.into(),
+			"valid_time", if let Some(v) = self.valid_time => v.structure(),
 			"permissions" => self.permissions.structure(),
 			"comment", if let Some(v) = self.comment => v.into(),
 			"graphql_alias", if let Some(v) = self.graphql_alias => v.into(),
@@ -31,12 +31,13 @@ use crate::val::{Datetime, TableName, Value};
 	database_id: DatabaseId(456),
 	name: "test".into(),
 	strict: false,
+	capabilities: None,
 	comment: Some("comment".to_string()),
 	changefeed: Some(ChangeFeed {
 		expiry: Duration::from_secs(123),
 		store_diff: false,
 	}),
-}, 25)]
+}, 26)]
 #[case::table(TableDefinition {
 	namespace_id: NamespaceId(123),
 	database_id: DatabaseId(456),
@@ -66,7 +67,14 @@ use crate::val::{Datetime, TableName, Value};
 	cache_indexes_ts: Uuid::default(),
 	graphql_alias: None,
 	graphql_deprecated: None,
-}, 151)]
+	throttle: None,
+	lineage: false,
+	soft_delete: None,
+	eviction: None,
+	session: None,
+	valid_time: None,
+	session_node: None,
+}, 158)]
 #[case::subscription(SubscriptionDefinition {
 	id: Uuid::default(),
 	node: Uuid::default(),
@@ -204,7 +212,8 @@ use crate::val::{Datetime, TableName, Value};
 	auth_limit: AuthLimit::default(),
 	graphql_alias: None,
 	graphql_deprecated: None,
-}, 42)]
+	capabilities: None,
+}, 43)]
 #[case::index(IndexDefinition {
 	index_id: IndexId(123),
 	name: "test".into(),
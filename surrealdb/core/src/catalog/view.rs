@@ -1,11 +1,15 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use revision::revisioned;
 use surrealdb_types::{SqlFormat, ToSql};
 
+use crate::catalog::TableDefinition;
 use crate::catalog::aggregation::AggregationAnalysis;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::{Expr, Fields, Groups};
+use crate::kvs::impl_kv_value_revisioned;
 use crate::sql::{Cond, View};
-use crate::val::{TableName, Value};
+use crate::val::{Array, Datetime, TableName, Value};
 
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -36,6 +40,24 @@ pub(crate) enum ViewDefinition {
 }
 
 impl ViewDefinition {
+	/// The tables which the view is computed from.
+	pub(crate) fn tables(&self) -> &[TableName] {
+		match self {
+			ViewDefinition::Materialized {
+				tables,
+				..
+			}
+			| ViewDefinition::Aggregated {
+				tables,
+				..
+			}
+			| ViewDefinition::Select {
+				tables,
+				..
+			} => tables,
+		}
+	}
+
 	pub(crate) fn to_sql_definition(&self) -> View {
 		match self {
 			ViewDefinition::Materialized {
@@ -43,6 +65,7 @@ impl ViewDefinition {
 				tables,
 				condition,
 			} => View {
+				materialize: true,
 				expr: fields.clone().into(),
 				what: tables.clone(),
 				cond: condition.clone().map(|x| Cond(x.into())),
@@ -55,6 +78,7 @@ impl ViewDefinition {
 				fields,
 				..
 			} => View {
+				materialize: true,
 				expr: fields.clone().into(),
 				what: tables.clone(),
 				cond: condition.clone().map(|x| Cond(x.into())),
@@ -66,6 +90,7 @@ impl ViewDefinition {
 				condition,
 				groups,
 			} => View {
+				materialize: false,
 				expr: fields.clone().into(),
 				what: tables.clone(),
 				cond: condition.clone().map(|x| Cond(x.into())),
@@ -85,3 +110,189 @@ impl InfoStructure for ViewDefinition {
 		self.to_sql().into()
 	}
 }
+
+/// The dependencies of the views of a database on the tables which they are
+/// computed from, which may themselves be views
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ViewGraph {
+	/// The tables which each view is computed from, by view
+	sources: BTreeMap<TableName, Vec<TableName>>,
+}
+
+impl ViewGraph {
+	pub(crate) fn new(tables: &[TableDefinition]) -> Self {
+		let sources = tables
+			.iter()
+			.filter_map(|tb| Some((tb.name.clone(), tb.view.as_ref()?.tables().to_vec())))
+			.collect();
+		Self {
+			sources,
+		}
+	}
+
+	pub(crate) fn is_empty(&self) -> bool {
+		self.sources.is_empty()
+	}
+
+	/// The views which are computed from a table, directly or through other
+	/// views
+	fn reachable(&self, tb: &TableName) -> BTreeSet<TableName> {
+		let mut found = BTreeSet::new();
+		let mut next = vec![tb.clone()];
+		while let Some(tb) = next.pop() {
+			for (view, sources) in self.sources.iter() {
+				if sources.contains(&tb) && found.insert(view.clone()) {
+					next.push(view.clone());
+				}
+			}
+		}
+		found
+	}
+
+	/// Orders views so that each view comes after the views it is computed
+	/// from. Views which depend on each other are ordered by name.
+	fn order(&self, mut pending: BTreeSet<TableName>) -> Vec<TableName> {
+		let mut order = Vec::with_capacity(pending.len());
+		while !pending.is_empty() {
+			let ready: Vec<TableName> = pending
+				.iter()
+				.filter(|view| {
+					self.sources
+						.get(*view)
+						.is_none_or(|sources| sources.iter().all(|s| !pending.contains(s)))
+				})
+				.cloned()
+				.collect();
+			if ready.is_empty() {
+				order.extend(pending);
+				break;
+			}
+			for view in ready {
+				pending.remove(&view);
+				order.push(view);
+			}
+		}
+		order
+	}
+
+	/// Returns the views which are computed from a table, directly or through
+	/// other views, in the order in which they are rebuilt
+	pub(crate) fn dependents(&self, tb: &TableName) -> Vec<TableName> {
+		self.order(self.reachable(tb))
+	}
+
+	/// Returns a table which a view can not be computed from, as the table is
+	/// the view itself or is computed from the view
+	pub(crate) fn cycle<'a>(
+		&self,
+		view: &TableName,
+		tables: &'a [TableName],
+	) -> Option<&'a TableName> {
+		let dependents = self.reachable(view);
+		tables.iter().find(|tb| *tb == view || dependents.contains(*tb))
+	}
+}
+
+impl InfoStructure for ViewGraph {
+	fn structure(self) -> Value {
+		let order = self.order(self.sources.keys().cloned().collect());
+		let views = order.into_iter().map(|view| {
+			let tables = self.sources[&view].iter().map(|tb| Value::from(tb.as_str())).collect();
+			Value::from(map! {
+				"name" => Value::from(view.as_str()),
+				"tables" => Value::Array(Array(tables)),
+			})
+		});
+		Value::Array(views.collect())
+	}
+}
+
+/// The state of a materialized view, kept alongside its records
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ViewState {
+	/// When the view was last computed in full from its source tables
+	pub(crate) refreshed_at: Datetime,
+	/// When a write to a source table first skipped the view, as writes do
+	/// while importing, since the view was last refreshed
+	pub(crate) stale_since: Option<Datetime>,
+}
+
+impl_kv_value_revisioned!(ViewState);
+
+impl ViewState {
+	/// The state of a view which was just computed in full
+	pub(crate) fn refreshed() -> Self {
+		Self {
+			refreshed_at: Datetime::now(),
+			stale_since: None,
+		}
+	}
+}
+
+impl InfoStructure for ViewState {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"refreshed_at" => Value::Datetime(self.refreshed_at),
+			"stale" => self.stale_since.is_some().into(),
+			"stale_since", if let Some(v) = self.stale_since => Value::Datetime(v),
+		})
+	}
+}
+
+/// The number of records of a group of an aggregated view for which an
+/// argument of a minimum or maximum has a value
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ViewValueCount {
+	/// The value, as it was first counted
+	pub(crate) value: Value,
+	pub(crate) count: u64,
+}
+
+impl_kv_value_revisioned!(ViewValueCount);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn graph(views: &[(&str, &[&str])]) -> ViewGraph {
+		let sources = views
+			.iter()
+			.map(|(view, tables)| {
+				(TableName::from(*view), tables.iter().map(|tb| TableName::from(*tb)).collect())
+			})
+			.collect();
+		ViewGraph {
+			sources,
+		}
+	}
+
+	fn names(tables: &[TableName]) -> Vec<&str> {
+		tables.iter().map(|tb| tb.as_str()).collect()
+	}
+
+	#[test]
+	fn dependents_come_after_their_sources() {
+		let graph = graph(&[
+			("a_total", &["daily"]),
+			("daily", &["reading"]),
+			("summary", &["a_total", "daily"]),
+			("other", &["person"]),
+		]);
+		assert_eq!(names(&graph.dependents(&"reading".into())), ["daily", "a_total", "summary"]);
+		assert_eq!(names(&graph.dependents(&"daily".into())), ["a_total", "summary"]);
+		assert!(graph.dependents(&"summary".into()).is_empty());
+	}
+
+	#[test]
+	fn views_can_not_depend_on_themselves() {
+		let graph = graph(&[("daily", &["reading"]), ("summary", &["daily"])]);
+		let tables = ["summary".into()];
+		assert_eq!(graph.cycle(&"daily".into(), &tables).map(|tb| tb.as_str()), Some("summary"));
+		let tables = ["daily".into()];
+		assert_eq!(graph.cycle(&"daily".into(), &tables).map(|tb| tb.as_str()), Some("daily"));
+		let tables = ["person".into(), "reading".into()];
+		assert_eq!(graph.cycle(&"daily".into(), &tables), None);
+	}
+}
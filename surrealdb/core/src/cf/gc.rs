@@ -3,67 +3,48 @@ use std::time::Duration;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 
-use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
-use crate::catalog::{DatabaseId, NamespaceId};
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{DatabaseDefinition, DatabaseId, NamespaceId};
+use crate::cf::segment::{self, segment_start};
 use crate::key::change;
 use crate::key::debug::Sprintable;
-use crate::kvs::tasklease::LeaseHandler;
 use crate::kvs::{BoxTimeStamp, BoxTimeStampImpl, KVKey, Transaction};
 
-// gc_all_at deletes all change feed entries that become stale at the given
-// current time.
-#[instrument(level = "trace", target = "surrealdb::core::cfs", skip_all)]
-pub async fn gc_all_at(lh: &LeaseHandler, tx: &Transaction) -> Result<()> {
-	// Fetch all namespaces
-	let nss = tx.all_ns(None).await?;
-
-	let ts_impl = tx.timestamp_impl();
-	// Loop over each namespace
-	for ns in nss.as_ref() {
-		// Trace for debugging
-		trace!("Performing garbage collection on {}", ns.name);
-		// Fetch all databases
-		let dbs = tx.all_db(ns.namespace_id, None).await?;
-		// Loop over each database
-		for db in dbs.as_ref() {
-			// Trace for debugging
-			trace!("Performing garbage collection on {}:{}", ns.name, db.name);
-			// Fetch all tables
-			let tbs = tx.all_tb(db.namespace_id, db.database_id, None).await?;
-			// Get the database changefeed expiration
-			let db_cf_expiry = db.changefeed.map(|v| v.expiry).unwrap_or_default();
-			// Get the maximum table changefeed expiration
-			let tb_cf_expiry = tbs
-				.as_ref()
-				.iter()
-				.filter_map(|tb| tb.changefeed.as_ref())
-				.map(|cf| cf.expiry)
-				.filter(|&dur| !dur.is_zero())
-				.max()
-				.unwrap_or(Duration::ZERO);
-			// Calculate the maximum changefeed expiration
-			let cf_expiry = db_cf_expiry.max(tb_cf_expiry);
-			// Skip if no retention policy configured
-			if cf_expiry.is_zero() {
-				continue;
-			}
-
-			let ts = tx.timestamp().await?;
-			// Calculate the changefeed watermark cutoff time
-			let watermark_ts = ts.sub_checked(cf_expiry).unwrap_or_else(|| ts_impl.earliest());
-			// Garbage collect all entries older than the watermark
-			gc_range(tx, db.namespace_id, db.database_id, &watermark_ts, &ts_impl).await?;
-			// Possibly renew the lease
-			lh.try_maintain_lease().await?;
-			// Yield execution
-			yield_now!();
-		}
-		// Possibly renew the lease
-		lh.try_maintain_lease().await?;
-		// Pause execution
-		yield_now!();
+// gc_db deletes all change feed entries of a database that become stale at
+// the current time.
+#[instrument(level = "trace", target = "surrealdb::core::cfs", skip_all, fields(ns = %db.namespace_id, db = %db.database_id))]
+pub async fn gc_db(tx: &Transaction, db: &DatabaseDefinition) -> Result<()> {
+	// Trace for debugging
+	trace!("Performing garbage collection on {}", db.name);
+	// Fetch all tables
+	let tbs = tx.all_tb(db.namespace_id, db.database_id, None).await?;
+	// Get the database changefeed expiration
+	let db_cf_expiry = db.changefeed.map(|v| v.expiry).unwrap_or_default();
+	// Get the maximum table changefeed expiration
+	let tb_cf_expiry = tbs
+		.as_ref()
+		.iter()
+		.filter_map(|tb| tb.changefeed.as_ref())
+		.map(|cf| cf.expiry)
+		.filter(|&dur| !dur.is_zero())
+		.max()
+		.unwrap_or(Duration::ZERO);
+	// Calculate the maximum changefeed expiration
+	let cf_expiry = db_cf_expiry.max(tb_cf_expiry);
+	// Skip if no retention policy configured
+	if cf_expiry.is_zero() {
+		return Ok(());
 	}
-	Ok(())
+	let ts_impl = tx.timestamp_impl();
+	let ts = tx.timestamp().await?;
+	// Calculate the changefeed watermark cutoff time
+	let watermark_ts = ts.sub_checked(cf_expiry).unwrap_or_else(|| ts_impl.earliest());
+	// Only remove the segments which have completely expired
+	let watermark_ts = segment_start(watermark_ts, &ts_impl);
+	// Garbage collect all entries older than the watermark
+	gc_range(tx, db.namespace_id, db.database_id, &watermark_ts, &ts_impl).await?;
+	// Forget the counts of the segments which were removed
+	segment::remove_before(tx, db.namespace_id, db.database_id, &watermark_ts).await
 }
 
 // gc_range deletes all change feed entries in the given database that are older
@@ -0,0 +1,232 @@
+//! Changefeed entries are grouped into fixed time segments.
+//!
+//! Changefeed keys are ordered by the timestamp of the commit which wrote
+//! them, so the entries of a segment are stored next to each other. Retention
+//! is applied a whole segment at a time, so that garbage collection removes a
+//! single contiguous range once per segment, instead of trimming the oldest
+//! entries of a busy table on every run.
+//!
+//! Truncation is not constant time. None of the storage engines offers a
+//! transactional range deletion, so removing an expired segment still costs
+//! one delete per entry within it. Segments only bound how often that work
+//! happens, and keep it to a single contiguous range.
+//!
+//! The number and size of the entries of each segment are counted when they
+//! are written, in the [`crate::key::database::cs`] keyspace, so reporting the
+//! segments of a database never requires a scan of its changefeed entries.
+//! This costs a commit which writes changefeed entries one extra key per
+//! database, holding two integers, next to the one entry per changed table
+//! which it already writes. This mirrors the table statistics deltas of
+//! [`crate::kvs::stats`], and the deltas are compacted in the same way.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use revision::revisioned;
+use uuid::Uuid;
+
+use crate::catalog::{DatabaseDefinition, DatabaseId, NamespaceId};
+use crate::cnf::CHANGEFEED_SEGMENT_SECS;
+use crate::key::database::cs;
+use crate::kvs::{
+	BoxTimeStamp, BoxTimeStampImpl, KVValue, NORMAL_BATCH_SIZE, Transaction,
+	impl_kv_value_revisioned,
+};
+use crate::val::{Datetime, Object, Value};
+
+/// A change to the entries of a segment, or the compacted total of many
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct SegmentStatsDelta {
+	/// The number of entries written to the segment
+	pub(crate) entries: u64,
+	/// The encoded size of the entries written to the segment, in bytes
+	pub(crate) bytes: u64,
+}
+
+impl_kv_value_revisioned!(SegmentStatsDelta);
+
+impl SegmentStatsDelta {
+	/// Records an entry of the given encoded size
+	pub(crate) fn record(&mut self, bytes: usize) {
+		self.entries = self.entries.saturating_add(1);
+		self.bytes = self.bytes.saturating_add(bytes as u64);
+	}
+
+	/// Adds another delta to this one
+	fn merge(&mut self, other: SegmentStatsDelta) {
+		self.entries = self.entries.saturating_add(other.entries);
+		self.bytes = self.bytes.saturating_add(other.bytes);
+	}
+}
+
+/// Returns the start of the segment which contains the given time
+fn floor(dt: DateTime<Utc>) -> DateTime<Utc> {
+	let size =
+		i64::try_from(CHANGEFEED_SEGMENT_SECS.saturating_mul(1000)).unwrap_or(i64::MAX).max(1);
+	let ms = dt.timestamp_millis();
+	DateTime::from_timestamp_millis(ms - ms.rem_euclid(size)).unwrap_or(dt)
+}
+
+/// Returns the start of the segment which contains the given time, in
+/// milliseconds since the unix epoch
+fn start_millis(dt: DateTime<Utc>) -> u64 {
+	u64::try_from(floor(dt).timestamp_millis()).unwrap_or_default()
+}
+
+/// Rounds a timestamp down to the start of its segment, so that only the
+/// segments which have completely expired are removed.
+///
+/// The timestamp is returned unchanged when it has no wall-clock time.
+pub(crate) fn segment_start(ts: BoxTimeStamp, ts_impl: &BoxTimeStampImpl) -> BoxTimeStamp {
+	match ts.as_datetime() {
+		Some(dt) => ts_impl.create_from_datetime(floor(dt)).unwrap_or(ts),
+		None => ts,
+	}
+}
+
+/// Writes the entries counted in each database by a transaction, to the
+/// segment of its commit timestamp
+pub(crate) async fn store(
+	tx: &Transaction,
+	changes: HashMap<(NamespaceId, DatabaseId), SegmentStatsDelta>,
+	ts: &BoxTimeStamp,
+) -> Result<()> {
+	// Entries without a wall-clock time do not belong to any segment
+	let Some(dt) = ts.as_datetime() else {
+		return Ok(());
+	};
+	let start = start_millis(dt);
+	for ((ns, db), delta) in changes {
+		tx.set(&cs::new(ns, db, start, Some(Uuid::now_v7())), &delta).await?;
+	}
+	Ok(())
+}
+
+/// Removes the counts of the segments which start before the given
+/// timestamp, once their entries have been removed
+pub(crate) async fn remove_before(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	ts: &BoxTimeStamp,
+) -> Result<()> {
+	if let Some(dt) = ts.as_datetime() {
+		tx.delr(cs::range_before(ns, db, start_millis(dt))?).await?;
+	}
+	Ok(())
+}
+
+/// Summarises the changefeed entries of a database by segment, from the
+/// oldest segment to the newest.
+pub(crate) async fn stats(tx: &Transaction, ns: NamespaceId, db: DatabaseId) -> Result<Vec<Value>> {
+	// The start and total of each segment
+	let mut segments: Vec<(u64, SegmentStatsDelta)> = Vec::new();
+	let mut next = Some(cs::range(ns, db)?);
+	while let Some(rng) = next {
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		next = res.next;
+		for (k, v) in res.result.iter() {
+			let start = cs::Cs::decode_key(k)?.start;
+			let delta = SegmentStatsDelta::kv_decode_value(v, ())?;
+			match segments.last_mut() {
+				Some(segment) if segment.0 == start => segment.1.merge(delta),
+				_ => segments.push((start, delta)),
+			}
+		}
+	}
+	Ok(segments
+		.into_iter()
+		.map(|(start, total)| {
+			let start = i64::try_from(start)
+				.ok()
+				.and_then(DateTime::from_timestamp_millis)
+				.unwrap_or_default();
+			Value::from(Object::from(map! {
+				"start" => Value::from(Datetime::from(start)),
+				"entries" => Value::from(total.entries),
+				"bytes" => Value::from(total.bytes),
+			}))
+		})
+		.collect())
+}
+
+/// Compacts the counts of every segment of a database into a single entry
+/// per segment
+#[instrument(level = "trace", target = "surrealdb::core::cfs", skip_all)]
+pub(crate) async fn compact_db(tx: &Transaction, db: &DatabaseDefinition) -> Result<()> {
+	// The start, total and delta keys of the segment being compacted
+	let mut segment: Option<(u64, SegmentStatsDelta, Vec<Vec<u8>>)> = None;
+	let mut next = Some(cs::range(db.namespace_id, db.database_id)?);
+	while let Some(rng) = next {
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		next = res.next;
+		for (k, v) in res.result.iter() {
+			let start = cs::Cs::decode_key(k)?.start;
+			let delta = SegmentStatsDelta::kv_decode_value(v, ())?;
+			match segment.as_mut() {
+				Some((s, total, keys)) if *s == start => {
+					total.merge(delta);
+					keys.push(k.to_vec());
+				}
+				_ => {
+					if let Some(done) = segment.replace((start, delta, vec![k.to_vec()])) {
+						compact(tx, db, done).await?;
+					}
+				}
+			}
+		}
+	}
+	if let Some(done) = segment {
+		compact(tx, db, done).await?;
+	}
+	Ok(())
+}
+
+/// Replaces the delta entries of a segment with their total
+async fn compact(
+	tx: &Transaction,
+	db: &DatabaseDefinition,
+	(start, total, keys): (u64, SegmentStatsDelta, Vec<Vec<u8>>),
+) -> Result<()> {
+	// There is nothing to compact
+	if keys.len() < 2 {
+		return Ok(());
+	}
+	for k in keys {
+		tx.clr(&k).await?;
+	}
+	tx.set(&cs::new(db.namespace_id, db.database_id, start, None), &total).await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn floors_to_segment_start() {
+		let size = *CHANGEFEED_SEGMENT_SECS as i64 * 1000;
+		let dt = DateTime::from_timestamp_millis(size * 3 + 1234).unwrap();
+		assert_eq!(floor(dt).timestamp_millis(), size * 3);
+		let dt = DateTime::from_timestamp_millis(size * 3).unwrap();
+		assert_eq!(floor(dt).timestamp_millis(), size * 3);
+	}
+
+	#[test]
+	fn deltas_are_merged() {
+		let mut total = SegmentStatsDelta::default();
+		total.record(10);
+		total.merge(SegmentStatsDelta {
+			entries: 2,
+			bytes: 30,
+		});
+		assert_eq!(
+			total,
+			SegmentStatsDelta {
+				entries: 3,
+				bytes: 40,
+			}
+		);
+	}
+}
@@ -349,6 +349,7 @@ mod tests {
 			}),
 			comment: None,
 			strict: false,
+			capabilities: None,
 		};
 		let mut tb_def = TableDefinition::new(
 			namespace_id,
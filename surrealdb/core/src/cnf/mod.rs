@@ -213,7 +213,7 @@ impl FromStr for LiveQueryEngine {
 	}
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct CommonConfig {
 	pub memory_threshold: usize,
 	/// Specifies how many concurrent jobs can be buffered in the worker channel
@@ -265,6 +265,11 @@ pub struct CommonConfig {
 	/// in-flight memory at the cost of slightly more per-batch dispatch
 	/// overhead. Per-batch unit is values, not bytes. (default: 1000)
 	pub scan_batch_size: usize,
+	/// The maximum number of linked records loaded with a single multi-get
+	/// when dereferencing record links, for `FETCH` clauses and field access
+	/// on graph traversals. Prefetches are also bounded by the transaction
+	/// cache size. (default: 500)
+	pub fetch_batch_size: usize,
 	/// The maximum size of the priority queue triggering usage of the priority
 	/// queue for the result collector.
 	pub max_order_limit_priority_queue_size: u32,
@@ -296,6 +301,10 @@ pub struct CommonConfig {
 	/// set. Errors that trip this guard name the env knob
 	/// (`SURREAL_GQL_MAX_OUTPUT_ROWS`).
 	pub gql_max_output_rows: usize,
+	/// The maximum number of rows a single statement may scan before it fails.
+	/// A `SELECT` can lower its own budget with `SCAN LIMIT`, but never raise
+	/// it beyond this limit (default: 0, unlimited)
+	pub max_scan_rows: u64,
 	/// The maximum stack size of the JavaScript function runtime (default: 256 KiB)
 	pub scripting_max_stack_size: usize,
 	/// The maximum memory limit of the JavaScript function runtime (default: 2 MiB)
@@ -377,6 +386,52 @@ pub struct CommonConfig {
 	/// rolling-upgrade windows during which a subscriber may need to replay missed
 	/// events. Independent of any user-defined `CHANGEFEED` retention (default: 1h).
 	pub live_query_retention: Duration,
+	/// How long the results of a query run with an idempotency key are kept,
+	/// so that retries of the query return them (default: 24h)
+	pub idempotency_key_ttl: Duration,
+	/// How long the background maintenance of changefeeds, live query events,
+	/// idempotency keys and table statistics spends on a namespace before its
+	/// remaining databases are deferred to the next run (default: 1 second)
+	pub tick_namespace_budget: Duration,
+	/// Whether record checksums are enabled on the datastore when this node
+	/// starts, storing a checksum alongside every record written by any node,
+	/// to be validated by the background scrubber. Once enabled, checksums
+	/// stay enabled until they are disabled with
+	/// [`Datastore::set_record_checksums`](crate::kvs::Datastore::set_record_checksums)
+	/// (default: false)
+	pub record_checksums: bool,
+	/// Whether the records which the background scrubber finds corrupt are
+	/// moved out of their tables, rather than only reported (default: false)
+	pub scrub_quarantine: bool,
+	/// The maximum amount of time that a write to a throttled table will wait
+	/// to be admitted before it is rejected (default: 1 second)
+	pub throttle_max_wait: Duration,
+	/// The maximum amount of time that a transaction will wait for a record or
+	/// table locked by another transaction before it fails (default: 5 seconds)
+	pub lock_wait_timeout: Duration,
+	/// The maximum number of access grants which can be created for a single
+	/// subject within the grant rate limit window (default: 0, unlimited)
+	pub grant_rate_limit_subject: u64,
+	/// The maximum number of access grants which can be created from a single
+	/// client IP address within the grant rate limit window (default: 0, unlimited)
+	pub grant_rate_limit_ip: u64,
+	/// The sliding window over which the creation of access grants is rate
+	/// limited (default: 1 minute)
+	pub grant_rate_limit_window: Duration,
+	/// How long async events and outbox requests which could not be processed
+	/// are kept in the dead-letter queue before they are removed, or zero to
+	/// keep them until they are replayed (default: 7 days)
+	pub event_dead_letter_retention: Duration,
+	/// The number of times a table must be scanned with an equality predicate
+	/// on the same field before a temporary in-memory index is built for it
+	/// (default: 0, disabled)
+	pub temporary_index_threshold: u64,
+	/// The minimum number of records a table must hold for a temporary index
+	/// to be kept for it (default: 1000)
+	pub temporary_index_min_rows: usize,
+	/// The maximum number of temporary indexes held at once, beyond which the
+	/// least recently used are evicted (default: 32)
+	pub temporary_index_limit: usize,
 }
 
 impl Default for CommonConfig {
@@ -400,11 +455,13 @@ impl Default for CommonConfig {
 			fts_doc_ids_batch_size: 1000,
 			operator_buffer_size: 2,
 			scan_batch_size: crate::exec::operators::scan::common::DEFAULT_SCAN_BATCH_SIZE,
+			fetch_batch_size: 500,
 			max_order_limit_priority_queue_size: 1000,
 			topk_threshold_pushdown_enabled: true,
 			gql_max_join_build_rows: 1_000_000,
 			gql_max_path_rows: 1_000_000,
 			gql_max_output_rows: 1_000_000,
+			max_scan_rows: 0,
 			scripting_max_stack_size: 256 * 1024,
 			scripting_max_memory_limit: 2 << 20,
 			scripting_max_time_limit: Duration::from_secs(5),
@@ -433,6 +490,19 @@ impl Default for CommonConfig {
 			surrealism_log_level: "debug".to_string(),
 			live_query_engine: LiveQueryEngine::Inline,
 			live_query_retention: Duration::from_secs(3600),
+			idempotency_key_ttl: Duration::from_secs(86400),
+			tick_namespace_budget: Duration::from_secs(1),
+			record_checksums: false,
+			scrub_quarantine: false,
+			throttle_max_wait: Duration::from_secs(1),
+			lock_wait_timeout: Duration::from_secs(5),
+			grant_rate_limit_subject: 0,
+			grant_rate_limit_ip: 0,
+			grant_rate_limit_window: Duration::from_secs(60),
+			event_dead_letter_retention: Duration::from_secs(7 * 86400),
+			temporary_index_threshold: 0,
+			temporary_index_min_rows: 1000,
+			temporary_index_limit: 32,
 		}
 	}
 }
@@ -454,6 +524,7 @@ impl Config for CommonConfig {
 			.parse_key("fts_doc_ids_batch_size", &mut self.fts_doc_ids_batch_size)
 			.parse_key("operator_buffer_size", &mut self.operator_buffer_size)
 			.parse_key("scan_batch_size", &mut self.scan_batch_size)
+			.parse_key("fetch_batch_size", &mut self.fetch_batch_size)
 			.parse_key(
 				"max_order_limit_priority_queue_size",
 				&mut self.max_order_limit_priority_queue_size,
@@ -462,6 +533,7 @@ impl Config for CommonConfig {
 			.parse_key("gql_max_join_build_rows", &mut self.gql_max_join_build_rows)
 			.parse_key("gql_max_path_rows", &mut self.gql_max_path_rows)
 			.parse_key("gql_max_output_rows", &mut self.gql_max_output_rows)
+			.parse_key("max_scan_rows", &mut self.max_scan_rows)
 			.parse_key("scripting_max_stack_size", &mut self.scripting_max_stack_size)
 			.parse_key("scripting_max_memory_limit", &mut self.scripting_max_memory_limit)
 			.parse_key_with("scripting_max_time_limit", &mut self.scripting_max_time_limit, |x| {
@@ -509,7 +581,34 @@ impl Config for CommonConfig {
 			.parse_key("live_query_engine", &mut self.live_query_engine)
 			.parse_key_with("live_query_retention", &mut self.live_query_retention, |x| {
 				crate::kvs::config::parse_duration(x).ok()
-			});
+			})
+			.parse_key_with("idempotency_key_ttl", &mut self.idempotency_key_ttl, |x| {
+				crate::kvs::config::parse_duration(x).ok()
+			})
+			.parse_key_with("tick_namespace_budget", &mut self.tick_namespace_budget, |x| {
+				crate::kvs::config::parse_duration(x).ok()
+			})
+			.parse_key("record_checksums", &mut self.record_checksums)
+			.parse_key("scrub_quarantine", &mut self.scrub_quarantine)
+			.parse_key_with("throttle_max_wait", &mut self.throttle_max_wait, |x| {
+				crate::kvs::config::parse_duration(x).ok()
+			})
+			.parse_key_with("lock_wait_timeout", &mut self.lock_wait_timeout, |x| {
+				crate::kvs::config::parse_duration(x).ok()
+			})
+			.parse_key("grant_rate_limit_subject", &mut self.grant_rate_limit_subject)
+			.parse_key("grant_rate_limit_ip", &mut self.grant_rate_limit_ip)
+			.parse_key_with("grant_rate_limit_window", &mut self.grant_rate_limit_window, |x| {
+				crate::kvs::config::parse_duration(x).ok()
+			})
+			.parse_key_with(
+				"event_dead_letter_retention",
+				&mut self.event_dead_letter_retention,
+				|x| crate::kvs::config::parse_duration(x).ok(),
+			)
+			.parse_key("temporary_index_threshold", &mut self.temporary_index_threshold)
+			.parse_key("temporary_index_min_rows", &mut self.temporary_index_min_rows)
+			.parse_key("temporary_index_limit", &mut self.temporary_index_limit);
 	}
 }
 
@@ -639,6 +738,32 @@ pub static REGEX_SIZE_LIMIT: LazyLock<usize> =
 pub static REGEX_CACHE_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_REGEX_CACHE_SIZE", usize, 1_000);
 
+/// The delay in milliseconds before a failed async event is retried, which
+/// doubles with every further attempt (default: 1000)
+pub static EVENT_RETRY_BACKOFF: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_EVENT_RETRY_BACKOFF", u64, 1_000);
+
+/// The maximum delay in milliseconds between the retries of a failed async
+/// event (default: 300000)
+pub static EVENT_RETRY_BACKOFF_MAX: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_EVENT_RETRY_BACKOFF_MAX", u64, 300_000);
+
+/// The maximum number of outbox requests which are sent at once (default: 16)
+pub static OUTBOX_CONCURRENCY: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_OUTBOX_CONCURRENCY", usize, 16);
+
+/// The time in seconds after which an outbox request which has not completed
+/// is failed, and retried like any other failed request (default: 30)
+pub static OUTBOX_REQUEST_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_OUTBOX_REQUEST_TIMEOUT", u64, 30);
+
+/// The length in seconds of the time segments which changefeed entries are
+/// grouped into, and removed by, once their retention has expired. Longer
+/// segments remove expired entries less often, but keep them for up to one
+/// segment longer than their retention (default: 600)
+pub static CHANGEFEED_SEGMENT_SECS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_CHANGEFEED_SEGMENT_SECS", u64, 600);
+
 /// Per-module controller pool size ceiling for Surrealism WASM modules (default: 8).
 /// Each pooled controller holds an instantiated WASM store. Effective pool size is
 /// `min(this, module_config.max_pool_size.unwrap_or(this))`.
@@ -667,6 +792,8 @@ pub static SURREALISM_MAX_POOL_SIZE: LazyLock<usize> =
 /// Explicit overrides drop the per-core CPU pinning that the default
 /// applies on >=16-core hosts — pinning only makes sense when the
 /// worker count exactly matches the core count.
+/// Set `SURREAL_KVS_THREADPOOL_PINNING=true|false` to force pinning on or
+/// off regardless of the worker count.
 ///
 /// **Minimum: 4.** Some kvs operations always run on this pool — read-only
 /// `count` with sharded fan-out, `compact`, writable scans — and below ~4
@@ -32,13 +32,12 @@ use crate::ctx::canceller::Canceller;
 use crate::ctx::reason::Reason;
 #[cfg(feature = "surrealism")]
 use crate::dbs::capabilities::ExperimentalTarget;
-#[cfg(feature = "http")]
-use crate::dbs::capabilities::NetTarget;
-#[cfg(all(feature = "http", feature = "surrealism"))]
-use crate::dbs::capabilities::Targets;
+use crate::dbs::capabilities::{NetTarget, Targets};
 use crate::dbs::{
-	Capabilities, MessageBroker, NewPlannerStrategy, Options, Session, StatementCounters, Variables,
+	Capabilities, MessageBroker, NewPlannerStrategy, Options, Priority, ScanBudget, ScanRecorder,
+	Session, StatementCounters, Variables,
 };
+use crate::doc::OutboxEvent;
 use crate::err::Error;
 use crate::exec::function::FunctionRegistry;
 use crate::expr::Base;
@@ -48,11 +47,15 @@ use crate::iam::{Action, ResourceKind};
 use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
 use crate::idx::trees::store::IndexStores;
-use crate::kvs::Transaction;
 use crate::kvs::cache::ds::DatastoreCache;
 use crate::kvs::index::IndexBuilder;
 use crate::kvs::sequences::Sequences;
+use crate::kvs::session_tables::Resolver;
 use crate::kvs::slowlog::SlowLog;
+use crate::kvs::{
+	IndexAdvisor, InteractiveGuard, Scheduler, SessionTables, Throttles, Transaction, TypeAdapters,
+	background_config,
+};
 use crate::mem::ALLOC;
 use crate::sql::expression::convert_public_value_to_internal;
 #[cfg(feature = "surrealism")]
@@ -101,6 +104,19 @@ pub struct Context {
 	index_builder: Option<IndexBuilder>,
 	// The sequences
 	sequences: Option<Sequences>,
+	// The table write throttles
+	throttles: Option<Throttles>,
+	// The tables defined by each session
+	session_tables: Option<SessionTables>,
+	// The resolver of the session tables of the session running the current
+	// statement, built once per statement.
+	session_resolver: Option<Arc<Resolver>>,
+	// The workload captured by the index advisor
+	index_advisor: Option<IndexAdvisor>,
+	// The type adapters registered by the embedder
+	type_adapters: Option<TypeAdapters>,
+	// The scheduler of interactive and background work
+	scheduler: Option<Scheduler>,
 	// Capabilities
 	capabilities: Arc<Capabilities>,
 	#[cfg(storage)]
@@ -119,13 +135,27 @@ pub struct Context {
 	function_registry: Arc<FunctionRegistry>,
 	// Strategy for the new streaming planner/executor
 	new_planner_strategy: NewPlannerStrategy,
+	// The scheduling priority of the running query
+	priority: Priority,
 	// When true, EXPLAIN ANALYZE omits elapsed durations for deterministic test output
 	redact_volatile_explain_attrs: bool,
+	// When true, the records deleted in this context are evicted from a table
+	// defined with `EVICT`, and live queries are notified of an eviction
+	eviction: bool,
 	// Per-statement counters, shared with the executor so it can read the
 	// number of rows affected by the running DML statement when emitting
 	// the corresponding `StatementEvent`. Replaced by the executor before
 	// each top-level statement; `None` outside an active statement.
 	statement_counters: Option<Arc<StatementCounters>>,
+	// The scan budget of the innermost `SELECT ... SCAN LIMIT` which this
+	// context runs within, if any.
+	scan_budget: Option<Arc<ScanBudget>>,
+	// The name of the event whose THEN clause is currently running, recorded
+	// as the writer of any fields changed when table lineage is enabled.
+	lineage_event: Option<Arc<str>>,
+	// The event defined with `OUTBOX` whose THEN clause is currently running,
+	// into whose outbox any HTTP requests with side effects are recorded.
+	outbox: Option<Arc<OutboxEvent>>,
 	// Pre-resolved tenant identity (namespace, database, user, session id,
 	// client ip) derived from the active session at `attach_session` time.
 	// Read by the executor and the transaction layer to populate the
@@ -185,6 +215,12 @@ impl Context {
 			cache: None,
 			index_builder: None,
 			sequences: None,
+			throttles: None,
+			session_tables: None,
+			session_resolver: None,
+			index_advisor: None,
+			type_adapters: None,
+			scheduler: None,
 			#[cfg(storage)]
 			temporary_directory: None,
 			transaction: None,
@@ -194,8 +230,13 @@ impl Context {
 			surrealism_cache: None,
 			function_registry: Arc::clone(&parent.function_registry),
 			new_planner_strategy: NewPlannerStrategy::default(),
+			priority: Priority::default(),
 			redact_volatile_explain_attrs: false,
+			eviction: false,
 			statement_counters: None,
+			scan_budget: None,
+			lineage_event: None,
+			outbox: None,
 			matches_context: None,
 			knn_context: None,
 			config: Arc::clone(&parent.config),
@@ -242,6 +283,12 @@ impl Context {
 			cache: parent.cache.clone(),
 			index_builder: parent.index_builder.clone(),
 			sequences: parent.sequences.clone(),
+			throttles: parent.throttles.clone(),
+			session_tables: parent.session_tables.clone(),
+			session_resolver: parent.session_resolver.clone(),
+			index_advisor: parent.index_advisor.clone(),
+			type_adapters: parent.type_adapters.clone(),
+			scheduler: parent.scheduler.clone(),
 			#[cfg(storage)]
 			temporary_directory: parent.temporary_directory.clone(),
 			transaction: parent.transaction.clone(),
@@ -252,8 +299,13 @@ impl Context {
 			surrealism_cache: parent.surrealism_cache.clone(),
 			function_registry: Arc::clone(&parent.function_registry),
 			new_planner_strategy: parent.new_planner_strategy,
+			priority: parent.priority,
 			redact_volatile_explain_attrs: parent.redact_volatile_explain_attrs,
+			eviction: parent.eviction,
 			statement_counters: parent.statement_counters.clone(),
+			scan_budget: parent.scan_budget.clone(),
+			lineage_event: parent.lineage_event.clone(),
+			outbox: parent.outbox.clone(),
 			matches_context: parent.matches_context.clone(),
 			knn_context: parent.knn_context.clone(),
 			config: Arc::clone(&parent.config),
@@ -268,6 +320,28 @@ impl Context {
 		}
 	}
 
+	/// Creates a new child context whose outbound network access is restricted
+	/// to the given targets, as granted to a function with a `CAPABILITIES`
+	/// clause. The denied network targets of the parent context still apply.
+	pub(crate) fn new_child_with_net_targets(
+		parent: &FrozenContext,
+		allow_net: Targets<NetTarget>,
+	) -> Result<Self> {
+		#[cfg(feature = "http")]
+		let http_client = Arc::new(HttpClient::new(
+			allow_net.clone(),
+			parent.capabilities.denied_network_targets_ref().clone(),
+			&parent.config,
+		)?);
+		let cap = Arc::new(parent.capabilities.with_scoped_network_targets(allow_net));
+		Ok(Self::new_child_with_capabilities(
+			parent,
+			cap,
+			#[cfg(feature = "http")]
+			http_client,
+		))
+	}
+
 	/// Create a new context from a frozen parent context.
 	/// This context is isolated, and values specified on
 	/// any parent contexts will not be accessible.
@@ -286,6 +360,12 @@ impl Context {
 			cache: parent.cache.clone(),
 			index_builder: parent.index_builder.clone(),
 			sequences: parent.sequences.clone(),
+			throttles: parent.throttles.clone(),
+			session_tables: parent.session_tables.clone(),
+			session_resolver: parent.session_resolver.clone(),
+			index_advisor: parent.index_advisor.clone(),
+			type_adapters: parent.type_adapters.clone(),
+			scheduler: parent.scheduler.clone(),
 			#[cfg(storage)]
 			temporary_directory: parent.temporary_directory.clone(),
 			transaction: parent.transaction.clone(),
@@ -296,8 +376,13 @@ impl Context {
 			surrealism_cache: parent.surrealism_cache.clone(),
 			function_registry: Arc::clone(&parent.function_registry),
 			new_planner_strategy: parent.new_planner_strategy,
+			priority: parent.priority,
 			redact_volatile_explain_attrs: parent.redact_volatile_explain_attrs,
+			eviction: parent.eviction,
 			statement_counters: parent.statement_counters.clone(),
+			scan_budget: parent.scan_budget.clone(),
+			lineage_event: parent.lineage_event.clone(),
+			outbox: parent.outbox.clone(),
 			matches_context: parent.matches_context.clone(),
 			knn_context: parent.knn_context.clone(),
 			config: Arc::clone(&parent.config),
@@ -347,6 +432,12 @@ impl Context {
 			cache: from.cache.clone(),
 			index_builder: from.index_builder.clone(),
 			sequences: from.sequences.clone(),
+			throttles: from.throttles.clone(),
+			session_tables: from.session_tables.clone(),
+			session_resolver: from.session_resolver.clone(),
+			index_advisor: from.index_advisor.clone(),
+			type_adapters: from.type_adapters.clone(),
+			scheduler: from.scheduler.clone(),
 			#[cfg(storage)]
 			temporary_directory: from.temporary_directory.clone(),
 			transaction: from.transaction.clone(),
@@ -357,8 +448,13 @@ impl Context {
 			surrealism_cache: from.surrealism_cache.clone(),
 			function_registry: Arc::clone(&from.function_registry),
 			new_planner_strategy: from.new_planner_strategy,
+			priority: from.priority,
 			redact_volatile_explain_attrs: from.redact_volatile_explain_attrs,
+			eviction: from.eviction,
 			statement_counters: from.statement_counters.clone(),
+			scan_budget: from.scan_budget.clone(),
+			lineage_event: from.lineage_event.clone(),
+			outbox: from.outbox.clone(),
 			matches_context: from.matches_context.clone(),
 			knn_context: from.knn_context.clone(),
 			config: Arc::clone(&from.config),
@@ -399,6 +495,12 @@ impl Context {
 			cache: from.cache.clone(),
 			index_builder: None,
 			sequences: from.sequences.clone(),
+			throttles: from.throttles.clone(),
+			session_tables: from.session_tables.clone(),
+			session_resolver: from.session_resolver.clone(),
+			index_advisor: from.index_advisor.clone(),
+			type_adapters: from.type_adapters.clone(),
+			scheduler: from.scheduler.clone(),
 			#[cfg(storage)]
 			temporary_directory: from.temporary_directory.clone(),
 			transaction: None,
@@ -409,8 +511,13 @@ impl Context {
 			surrealism_cache: from.surrealism_cache.clone(),
 			function_registry: Arc::clone(&from.function_registry),
 			new_planner_strategy: from.new_planner_strategy,
+			priority: from.priority,
 			redact_volatile_explain_attrs: from.redact_volatile_explain_attrs,
+			eviction: from.eviction,
 			statement_counters: from.statement_counters.clone(),
+			scan_budget: from.scan_budget.clone(),
+			lineage_event: from.lineage_event.clone(),
+			outbox: from.outbox.clone(),
 			matches_context: from.matches_context.clone(),
 			knn_context: from.knn_context.clone(),
 			config: Arc::clone(&from.config),
@@ -437,6 +544,11 @@ impl Context {
 		index_stores: IndexStores,
 		index_builder: IndexBuilder,
 		sequences: Sequences,
+		throttles: Throttles,
+		session_tables: SessionTables,
+		index_advisor: IndexAdvisor,
+		type_adapters: TypeAdapters,
+		scheduler: Scheduler,
 		cache: Arc<DatastoreCache>,
 		function_registry: Arc<FunctionRegistry>,
 		#[cfg(feature = "http")] http_client: Arc<HttpClient>,
@@ -461,6 +573,12 @@ impl Context {
 			cache: Some(cache),
 			index_builder: Some(index_builder),
 			sequences: Some(sequences),
+			throttles: Some(throttles),
+			session_tables: Some(session_tables),
+			session_resolver: None,
+			index_advisor: Some(index_advisor),
+			type_adapters: Some(type_adapters),
+			scheduler: Some(scheduler),
 			#[cfg(storage)]
 			temporary_directory,
 			transaction: None,
@@ -470,8 +588,13 @@ impl Context {
 			surrealism_cache: Some(surrealism_cache),
 			function_registry,
 			new_planner_strategy: planner_strategy,
+			priority: Priority::default(),
 			redact_volatile_explain_attrs: false,
+			eviction: false,
 			statement_counters: None,
+			scan_budget: None,
+			lineage_event: None,
+			outbox: None,
 			matches_context: None,
 			knn_context: None,
 			config,
@@ -508,6 +631,12 @@ impl Context {
 			cache: None,
 			index_builder: None,
 			sequences: None,
+			throttles: None,
+			session_tables: None,
+			session_resolver: None,
+			index_advisor: None,
+			type_adapters: None,
+			scheduler: None,
 			#[cfg(storage)]
 			temporary_directory: None,
 			transaction: None,
@@ -517,8 +646,13 @@ impl Context {
 			surrealism_cache: None,
 			function_registry: Arc::new(FunctionRegistry::with_builtins()),
 			new_planner_strategy: NewPlannerStrategy::default(),
+			priority: Priority::default(),
 			redact_volatile_explain_attrs: false,
+			eviction: false,
 			statement_counters: None,
+			scan_budget: None,
+			lineage_event: None,
+			outbox: None,
 			matches_context: None,
 			knn_context: None,
 			config: Default::default(),
@@ -808,6 +942,12 @@ impl Context {
 		self.transaction = Some(txn);
 	}
 
+	/// Replaces the capabilities of this context. The network targets of the
+	/// capabilities must match those of the context's HTTP client.
+	pub(crate) fn set_capabilities(&mut self, capabilities: Arc<Capabilities>) {
+		self.capabilities = capabilities;
+	}
+
 	/// Install the per-statement counter set on this context. Called by the
 	/// executor before each top-level statement so the iterator can record
 	/// the actual number of records affected -- including for DML
@@ -823,6 +963,43 @@ impl Context {
 		self.statement_counters.as_ref()
 	}
 
+	/// Limit the number of rows scanned within this context, along with the
+	/// limits of any enclosing `SELECT` and of the statement.
+	pub(crate) fn set_scan_limit(&mut self, limit: u64) {
+		self.scan_budget = Some(ScanBudget::new(limit, self.scan_budget.take()));
+	}
+
+	/// Records the rows read by the scan operators running within this
+	/// context, against the counters and the scan limits which apply.
+	pub(crate) fn scan_recorder(&self) -> ScanRecorder {
+		ScanRecorder {
+			counters: self.statement_counters.clone(),
+			budget: self.scan_budget.clone(),
+		}
+	}
+
+	/// Record the event whose THEN clause runs within this context, so that
+	/// any fields it writes are attributed to it in the table lineage.
+	pub(crate) fn set_lineage_event(&mut self, event: Option<Arc<str>>) {
+		self.lineage_event = event;
+	}
+
+	/// The event whose THEN clause is currently running, if any.
+	pub(crate) fn lineage_event(&self) -> Option<&str> {
+		self.lineage_event.as_deref()
+	}
+
+	/// Record the outbox event whose THEN clause runs within this context, so
+	/// that its HTTP requests are sent once the transaction has committed.
+	pub(crate) fn set_outbox(&mut self, outbox: Option<Arc<OutboxEvent>>) {
+		self.outbox = outbox;
+	}
+
+	/// The outbox event whose THEN clause is currently running, if any.
+	pub(crate) fn outbox(&self) -> Option<&OutboxEvent> {
+		self.outbox.as_deref()
+	}
+
 	pub(crate) fn tx(&self) -> Arc<Transaction> {
 		self.transaction
 			.clone()
@@ -875,6 +1052,83 @@ impl Context {
 		self.sequences.as_ref()
 	}
 
+	/// Return the table write throttles
+	pub(crate) fn get_throttles(&self) -> Option<&Throttles> {
+		self.throttles.as_ref()
+	}
+
+	/// Return the tables defined by each session
+	pub(crate) fn get_session_tables(&self) -> Option<&SessionTables> {
+		self.session_tables.as_ref()
+	}
+
+	/// Set the resolver of the session tables of the session running the
+	/// current statement
+	pub(crate) fn set_session_resolver(&mut self, resolver: Option<Arc<Resolver>>) {
+		self.session_resolver = resolver;
+	}
+
+	/// Return the resolver of the session tables of the session running the
+	/// current statement, if it defined any in the current database
+	pub(crate) fn session_resolver(&self) -> Option<&Arc<Resolver>> {
+		self.session_resolver.as_ref()
+	}
+
+	/// Return the workload captured by the index advisor
+	pub(crate) fn get_index_advisor(&self) -> Option<&IndexAdvisor> {
+		self.index_advisor.as_ref()
+	}
+
+	/// Return the type adapters registered by the embedder
+	pub(crate) fn get_type_adapters(&self) -> Option<&TypeAdapters> {
+		self.type_adapters.as_ref()
+	}
+
+	/// Register the start of a query with the scheduler, returning a guard
+	/// which keeps interactive queries counted as active until dropped
+	pub(crate) fn enter_scheduler(&self) -> Option<InteractiveGuard> {
+		self.scheduler.as_ref().and_then(|s| s.enter(self.priority))
+	}
+
+	/// Defer background work while interactive queries are running
+	pub(crate) async fn yield_to_interactive(&self) {
+		if self.priority == Priority::Background
+			&& let Some(scheduler) = &self.scheduler
+		{
+			scheduler.defer().await;
+		}
+	}
+
+	/// Waits until the writes which the session has queued on throttled
+	/// tables can proceed, unless the query is cancelled. This is only called
+	/// between statements, so that no transaction is held open while waiting.
+	pub(crate) async fn wait_for_throttles(&self) {
+		let Some(wait) = self.throttles.as_ref().and_then(|t| t.delay(self.session_id())) else {
+			return;
+		};
+		#[cfg(target_family = "wasm")]
+		let sleep_fut = wasmtimer::tokio::sleep(wait);
+		#[cfg(not(target_family = "wasm"))]
+		let sleep_fut = tokio::time::sleep(wait);
+		match self.cancel_token() {
+			Some(token) => {
+				tokio::select! {
+					_ = sleep_fut => {}
+					_ = token.cancelled() => {}
+				}
+			}
+			None => sleep_fut.await,
+		}
+	}
+
+	/// Return the id of the session running the query, if it has one
+	pub(crate) fn session_id(&self) -> Option<Uuid> {
+		match self.value("session").and_then(|v| v.as_object()).and_then(|v| v.get("id")) {
+			Some(Value::Uuid(id)) => Some(id.0),
+			_ => None,
+		}
+	}
+
 	pub(crate) fn try_get_sequences(&self) -> Result<&Sequences> {
 		if let Some(sqs) = self.get_sequences() {
 			Ok(sqs)
@@ -1047,10 +1301,16 @@ impl Context {
 		if session.new_planner_strategy != NewPlannerStrategy::default() {
 			self.new_planner_strategy = session.new_planner_strategy;
 		}
+		// Background queries read in smaller batches than interactive ones.
+		self.priority = session.priority;
+		if self.priority == Priority::Background {
+			self.config = Arc::new(background_config(&self.config));
+		}
 		// Propagate duration redaction flag from session.
 		if session.redact_volatile_explain_attrs {
 			self.redact_volatile_explain_attrs = true;
 		}
+		self.eviction = session.eviction;
 		if !session.variables.is_empty() {
 			self.attach_variables(session.variables.clone().into())?;
 		}
@@ -1137,6 +1397,17 @@ impl Context {
 		self.redact_volatile_explain_attrs
 	}
 
+	/// Set whether the records deleted in this context are evicted
+	pub(crate) fn set_eviction(&mut self, eviction: bool) {
+		self.eviction = eviction;
+	}
+
+	/// Whether the records deleted in this context are evicted from a table
+	/// defined with `EVICT`
+	pub(crate) fn is_eviction(&self) -> bool {
+		self.eviction
+	}
+
 	/// Check if scripting is allowed
 	#[cfg_attr(not(feature = "scripting"), expect(dead_code))]
 	pub(crate) fn check_allowed_scripting(&self) -> Result<()> {
@@ -4,7 +4,6 @@ use std::hash::{Hash, Hasher};
 use std::net::IpAddr;
 #[cfg(all(target_family = "wasm", feature = "http"))]
 use std::net::ToSocketAddrs;
-#[cfg(feature = "surrealism")]
 use std::str::FromStr;
 
 #[cfg(feature = "surrealism")]
@@ -16,6 +15,8 @@ use surrealism_runtime::capabilities::SurrealismCapabilities;
 use tokio::net::lookup_host;
 use url::Url;
 
+use crate::catalog::DatabaseCapabilities;
+use crate::dbs::Guardrails;
 use crate::dbs::session::NewPlannerStrategy;
 use crate::iam::{Auth, Level};
 use crate::rpc::Method;
@@ -124,6 +125,8 @@ pub enum ExperimentalTarget {
 	Files,
 	Surrealism,
 	Gql,
+	Impersonation,
+	TypeAdapters,
 }
 
 impl fmt::Display for ExperimentalTarget {
@@ -132,6 +135,8 @@ impl fmt::Display for ExperimentalTarget {
 			Self::Files => write!(f, "files"),
 			Self::Surrealism => write!(f, "surrealism"),
 			Self::Gql => write!(f, "gql"),
+			Self::Impersonation => write!(f, "impersonation"),
+			Self::TypeAdapters => write!(f, "type_adapters"),
 		}
 	}
 }
@@ -148,6 +153,8 @@ impl Target<str> for ExperimentalTarget {
 			Self::Files => elem.eq_ignore_ascii_case("files"),
 			Self::Surrealism => elem.eq_ignore_ascii_case("surrealism"),
 			Self::Gql => elem.eq_ignore_ascii_case("gql"),
+			Self::Impersonation => elem.eq_ignore_ascii_case("impersonation"),
+			Self::TypeAdapters => elem.eq_ignore_ascii_case("type_adapters"),
 		}
 	}
 }
@@ -176,6 +183,8 @@ impl std::str::FromStr for ExperimentalTarget {
 			"files" => Ok(ExperimentalTarget::Files),
 			"surrealism" => Ok(ExperimentalTarget::Surrealism),
 			"gql" => Ok(ExperimentalTarget::Gql),
+			"impersonation" => Ok(ExperimentalTarget::Impersonation),
+			"type_adapters" => Ok(ExperimentalTarget::TypeAdapters),
 			_ => Err(ParseExperimentalTargetError::InvalidName),
 		}
 	}
@@ -688,6 +697,9 @@ pub struct Capabilities {
 	deny_funcs: Targets<FuncTarget>,
 	pub(crate) allow_net: Targets<NetTarget>,
 	pub(crate) deny_net: Targets<NetTarget>,
+	/// Network targets which may be granted to a function with a
+	/// `CAPABILITIES` clause, without being allowed for other queries
+	allow_function_net: Targets<NetTarget>,
 	allow_rpc: Targets<MethodTarget>,
 	deny_rpc: Targets<MethodTarget>,
 	allow_http: Targets<RouteTarget>,
@@ -699,13 +711,14 @@ pub struct Capabilities {
 	allow_eval_query: Targets<EvalQueryTarget>,
 	deny_eval_query: Targets<EvalQueryTarget>,
 	planner_strategy: NewPlannerStrategy,
+	guardrails: Guardrails,
 }
 
 impl fmt::Display for Capabilities {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(
 			f,
-			"scripting={}, guest_access={}, live_query_notifications={}, allow_funcs={}, deny_funcs={}, allow_net={}, deny_net={}, allow_rpc={}, deny_rpc={}, allow_http={}, deny_http={}, allow_experimental={}, deny_experimental={}, allow_arbitrary_query={}, deny_arbitrary_query={}, allow_eval_query={}, deny_eval_query={}, planner_strategy={}",
+			"scripting={}, guest_access={}, live_query_notifications={}, allow_funcs={}, deny_funcs={}, allow_net={}, deny_net={}, allow_function_net={}, allow_rpc={}, deny_rpc={}, allow_http={}, deny_http={}, allow_experimental={}, deny_experimental={}, allow_arbitrary_query={}, deny_arbitrary_query={}, allow_eval_query={}, deny_eval_query={}, planner_strategy={}, guardrails={}",
 			self.scripting,
 			self.guest_access,
 			self.live_query_notifications,
@@ -713,6 +726,7 @@ impl fmt::Display for Capabilities {
 			self.deny_funcs,
 			self.allow_net,
 			self.deny_net,
+			self.allow_function_net,
 			self.allow_rpc,
 			self.deny_rpc,
 			self.allow_http,
@@ -724,6 +738,7 @@ impl fmt::Display for Capabilities {
 			self.allow_eval_query,
 			self.deny_eval_query,
 			self.planner_strategy,
+			self.guardrails,
 		)
 	}
 }
@@ -739,6 +754,7 @@ impl Default for Capabilities {
 			deny_funcs: Targets::None,
 			allow_net: Targets::None,
 			deny_net: Targets::None,
+			allow_function_net: Targets::None,
 			allow_rpc: Targets::All,
 			deny_rpc: Targets::None,
 			allow_http: Targets::All,
@@ -752,6 +768,7 @@ impl Default for Capabilities {
 			allow_eval_query: Targets::None,
 			deny_eval_query: Targets::None,
 			planner_strategy: NewPlannerStrategy::default(),
+			guardrails: Guardrails::default(),
 		}
 	}
 }
@@ -767,6 +784,7 @@ impl Capabilities {
 			deny_funcs: Targets::None,
 			allow_net: Targets::All,
 			deny_net: Targets::None,
+			allow_function_net: Targets::All,
 			allow_rpc: Targets::All,
 			deny_rpc: Targets::None,
 			allow_http: Targets::All,
@@ -780,6 +798,7 @@ impl Capabilities {
 			allow_eval_query: Targets::None,
 			deny_eval_query: Targets::None,
 			planner_strategy: NewPlannerStrategy::default(),
+			guardrails: Guardrails::default(),
 		}
 	}
 
@@ -793,6 +812,7 @@ impl Capabilities {
 			deny_funcs: Targets::None,
 			allow_net: Targets::None,
 			deny_net: Targets::None,
+			allow_function_net: Targets::None,
 			allow_rpc: Targets::None,
 			deny_rpc: Targets::None,
 			allow_http: Targets::None,
@@ -804,6 +824,7 @@ impl Capabilities {
 			allow_eval_query: Targets::None,
 			deny_eval_query: Targets::None,
 			planner_strategy: NewPlannerStrategy::default(),
+			guardrails: Guardrails::default(),
 		}
 	}
 
@@ -906,6 +927,52 @@ impl Capabilities {
 		&mut self.deny_net
 	}
 
+	pub fn with_function_network_targets(mut self, allow_function_net: Targets<NetTarget>) -> Self {
+		self.allow_function_net = allow_function_net;
+		self
+	}
+
+	pub fn allowed_function_network_targets_mut(&mut self) -> &mut Targets<NetTarget> {
+		&mut self.allow_function_net
+	}
+
+	/// Returns these capabilities with outbound network access restricted to
+	/// the given targets, keeping the denied network targets in place.
+	pub(crate) fn with_scoped_network_targets(&self, allow_net: Targets<NetTarget>) -> Self {
+		Self {
+			allow_net,
+			..self.clone()
+		}
+	}
+
+	/// Returns these capabilities restricted by the capabilities declared by a
+	/// database. A database can only narrow the allowed functions and disable
+	/// scripting, never extend them.
+	pub(crate) fn with_database_capabilities(&self, db: &DatabaseCapabilities) -> Self {
+		let mut caps = self.clone();
+		if let Some(functions) = &db.functions {
+			let declared: HashSet<FuncTarget> =
+				functions.iter().filter_map(|f| FuncTarget::from_str(f).ok()).collect();
+			caps.allow_funcs = match &self.allow_funcs {
+				Targets::None => Targets::None,
+				Targets::All => Targets::Some(declared),
+				// Keep the narrower of every pair of overlapping targets
+				Targets::Some(allowed) => Targets::Some(
+					declared
+						.iter()
+						.filter(|d| allowed.iter().any(|a| a.matches(*d)))
+						.chain(allowed.iter().filter(|a| declared.iter().any(|d| d.matches(*a))))
+						.cloned()
+						.collect(),
+				),
+			};
+		}
+		if db.scripting == Some(false) {
+			caps.scripting = false;
+		}
+		caps
+	}
+
 	pub fn with_rpc_methods(mut self, allow_rpc: Targets<MethodTarget>) -> Self {
 		self.allow_rpc = allow_rpc;
 		self
@@ -935,6 +1002,15 @@ impl Capabilities {
 		&self.planner_strategy
 	}
 
+	pub fn with_guardrails(mut self, guardrails: Guardrails) -> Self {
+		self.guardrails = guardrails;
+		self
+	}
+
+	pub fn guardrails(&self) -> &Guardrails {
+		&self.guardrails
+	}
+
 	pub fn allows_scripting(&self) -> bool {
 		self.scripting
 	}
@@ -981,6 +1057,13 @@ impl Capabilities {
 		self.allow_net.matches(target) && !self.deny_net.matches(target)
 	}
 
+	/// Checks whether a network target may be granted to a function, either
+	/// because it is allowed for all queries or for functions specifically.
+	pub fn allows_function_network_target(&self, target: &NetTarget) -> bool {
+		(self.allow_net.matches(target) || self.allow_function_net.matches(target))
+			&& !self.deny_net.matches(target)
+	}
+
 	#[cfg(feature = "http")]
 	pub(crate) fn matches_any_allow_net(&self, target: &NetTarget) -> bool {
 		self.allow_net.matches(target)
@@ -1096,6 +1179,14 @@ mod tests {
 		);
 		assert_eq!(ExperimentalTarget::from_str("gql").unwrap(), ExperimentalTarget::Gql);
 		assert_eq!(ExperimentalTarget::from_str("GQL").unwrap(), ExperimentalTarget::Gql);
+		assert_eq!(
+			ExperimentalTarget::from_str("impersonation").unwrap(),
+			ExperimentalTarget::Impersonation
+		);
+		assert_eq!(
+			ExperimentalTarget::from_str("type_adapters").unwrap(),
+			ExperimentalTarget::TypeAdapters
+		);
 		// The retired `opengql` spelling must no longer parse.
 		ExperimentalTarget::from_str("opengql").unwrap_err();
 		ExperimentalTarget::from_str("").unwrap_err();
@@ -1443,6 +1534,35 @@ mod tests {
 			assert!(!caps.allows_network_target(&NetTarget::from_str("example.com:80").unwrap()));
 		}
 
+		// When some nets are only allowed for functions, deny overrides the allow
+		// rules
+		{
+			let caps = Capabilities::default()
+				.with_function_network_targets(Targets::<NetTarget>::Some(
+					[NetTarget::from_str("example.com").unwrap()].into(),
+				))
+				.without_network_targets(Targets::<NetTarget>::Some(
+					[NetTarget::from_str("example.com:80").unwrap()].into(),
+				));
+			assert!(!caps.allows_network_target(&NetTarget::from_str("example.com").unwrap()));
+			assert!(
+				caps.allows_function_network_target(&NetTarget::from_str("example.com").unwrap())
+			);
+			assert!(
+				caps.allows_function_network_target(
+					&NetTarget::from_str("example.com:443").unwrap()
+				)
+			);
+			assert!(
+				!caps.allows_function_network_target(
+					&NetTarget::from_str("example.com:80").unwrap()
+				)
+			);
+			assert!(
+				!caps.allows_function_network_target(&NetTarget::from_str("other.com").unwrap())
+			);
+		}
+
 		// When all funcs are allowed
 		{
 			let caps = Capabilities::default()
@@ -1673,4 +1793,51 @@ mod tests {
 			assert!(!caps.allows_eval_query(&EvalQueryTarget::Record));
 		}
 	}
+
+	#[test]
+	fn test_database_capabilities() {
+		let db = DatabaseCapabilities {
+			functions: Some(vec!["string::*".to_owned(), "array::len".to_owned()]),
+			scripting: Some(false),
+		};
+
+		// The declared functions replace a datastore which allows every function
+		{
+			let caps = Capabilities::all().with_database_capabilities(&db);
+			assert!(caps.allows_function_name("string::lowercase"));
+			assert!(caps.allows_function_name("array::len"));
+			assert!(!caps.allows_function_name("array::sort"));
+			assert!(!caps.allows_function_name("http::get"));
+			assert!(!caps.allows_scripting());
+		}
+
+		// The declared functions can not extend the functions of the datastore
+		{
+			let caps = Capabilities::all()
+				.with_functions(Targets::Some(
+					[
+						FuncTarget::from_str("string::lowercase").unwrap(),
+						FuncTarget::from_str("array::*").unwrap(),
+					]
+					.into(),
+				))
+				.without_functions(Targets::from(FuncTarget::from_str("array::len").unwrap()))
+				.with_database_capabilities(&db);
+			assert!(caps.allows_function_name("string::lowercase"));
+			assert!(!caps.allows_function_name("string::uppercase"));
+			assert!(!caps.allows_function_name("array::len"));
+			assert!(!caps.allows_function_name("array::sort"));
+		}
+
+		// Scripting can only be disabled
+		{
+			let db = DatabaseCapabilities {
+				functions: None,
+				scripting: Some(true),
+			};
+			let caps = Capabilities::default().with_database_capabilities(&db);
+			assert!(!caps.allows_scripting());
+			assert!(caps.allows_function_name("array::sort"));
+		}
+	}
 }
@@ -0,0 +1,137 @@
+//! Queries which are parsed and planned ahead of time.
+//!
+//! [`Datastore::compile`](crate::kvs::Datastore::compile) turns SurrealQL into
+//! a [`CompiledPlan`], which can be executed any number of times with
+//! [`Datastore::process_compiled`](crate::kvs::Datastore::process_compiled),
+//! so that the cost of parsing is only paid once. A plan can also be encoded
+//! with [`CompiledPlan::to_bytes`] at build or deploy time, and loaded again
+//! with [`Datastore::load_compiled`](crate::kvs::Datastore::load_compiled).
+//!
+//! Every plan records the version of SurrealDB which compiled it, and a plan
+//! compiled by a different version is rejected rather than executed, as the
+//! meaning of a statement may change between versions. Tables, fields, and
+//! indexes are only resolved when the plan is executed, so a plan always runs
+//! against the current schema and remains valid when the schema changes.
+//!
+//! A plan also records the parser settings which the capabilities of the
+//! compiling datastore allowed. A plan compiled with settings which the
+//! executing datastore does not allow is checked against its capabilities
+//! again before it runs, just as a query is checked when it is parsed.
+
+use anyhow::{Result, bail};
+use revision::revisioned;
+use surrealdb_types::ToSql;
+
+use crate::env::VERSION;
+use crate::err::Error;
+use crate::expr::LogicalPlan;
+use crate::syn::ParserSettings;
+
+/// A SurrealQL query which has been parsed and planned ahead of time
+#[derive(Clone, Debug)]
+pub struct CompiledPlan {
+	pub(crate) version: String,
+	pub(crate) plan: LogicalPlan,
+	pub(crate) settings: ParserSettings,
+}
+
+/// The encoded form of a [`CompiledPlan`]
+#[revisioned(revision = 1)]
+struct EncodedPlan {
+	version: String,
+	query: String,
+}
+
+impl CompiledPlan {
+	pub(crate) fn new(plan: LogicalPlan, settings: ParserSettings) -> Self {
+		Self {
+			version: VERSION.to_owned(),
+			plan,
+			settings,
+		}
+	}
+
+	/// The version of SurrealDB which compiled this plan
+	pub fn version(&self) -> &str {
+		&self.version
+	}
+
+	/// Encodes the plan, so that it can be stored and loaded again with
+	/// [`Datastore::load_compiled`](crate::kvs::Datastore::load_compiled)
+	pub fn to_bytes(&self) -> Result<Vec<u8>> {
+		let encoded = EncodedPlan {
+			version: self.version.clone(),
+			query: self.plan.to_sql(),
+		};
+		Ok(revision::to_vec(&encoded)?)
+	}
+
+	/// Decodes an encoded plan, returning its version and the canonical
+	/// SurrealQL of its statements
+	pub(crate) fn decode(bytes: &[u8]) -> Result<(String, String)> {
+		let encoded: EncodedPlan =
+			revision::from_slice(bytes).map_err(|e| Error::InvalidCompiledPlan(e.to_string()))?;
+		Ok((encoded.version, encoded.query))
+	}
+
+	/// Ensures that the plan was compiled by this version of SurrealDB
+	pub(crate) fn check_version(version: &str) -> Result<()> {
+		if version != VERSION {
+			bail!(Error::InvalidCompiledPlan(format!(
+				"The plan was compiled by SurrealDB {version}, but this is SurrealDB {VERSION}. Compile the query again"
+			)));
+		}
+		Ok(())
+	}
+
+	/// Whether the plan was parsed with settings which allow nothing more
+	/// than the given settings
+	pub(crate) fn is_allowed_by(&self, settings: &ParserSettings) -> bool {
+		(settings.files_enabled || !self.settings.files_enabled)
+			&& (settings.surrealism_enabled || !self.settings.surrealism_enabled)
+			&& self.settings.object_recursion_limit <= settings.object_recursion_limit
+			&& self.settings.query_recursion_limit <= settings.query_recursion_limit
+			&& self.settings.expr_recursion_limit <= settings.expr_recursion_limit
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rejects_plans_from_other_versions() {
+		CompiledPlan::check_version(VERSION).unwrap();
+		let err = CompiledPlan::check_version("1.0.0").unwrap_err();
+		assert!(err.to_string().contains("compiled by SurrealDB 1.0.0"), "{err}");
+	}
+
+	#[test]
+	fn checks_the_settings_of_plans() {
+		let settings = ParserSettings::default();
+		let plan = CompiledPlan::new(
+			LogicalPlan {
+				expressions: vec![],
+			},
+			settings.clone(),
+		);
+		assert!(plan.is_allowed_by(&settings));
+		let permissive = ParserSettings {
+			files_enabled: true,
+			..settings.clone()
+		};
+		let plan = CompiledPlan::new(
+			LogicalPlan {
+				expressions: vec![],
+			},
+			permissive.clone(),
+		);
+		assert!(plan.is_allowed_by(&permissive));
+		assert!(!plan.is_allowed_by(&settings));
+	}
+
+	#[test]
+	fn rejects_invalid_bytes() {
+		CompiledPlan::decode(b"not a plan").unwrap_err();
+	}
+}
@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::pin::{Pin, pin};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -19,7 +20,8 @@ use crate::catalog::providers::{
 };
 use crate::ctx::reason::Reason;
 use crate::ctx::{Context, FrozenContext};
-use crate::dbs::response::QueryResult;
+use crate::dbs::capabilities::Capabilities;
+use crate::dbs::response::{QueryResult, QueryStats};
 use crate::dbs::{Force, MessageBroker, Options, QueryType, RoutedNotification, StatementCounters};
 use crate::doc::DefaultBroker;
 use crate::err::Error;
@@ -27,9 +29,12 @@ use crate::exec::planner::try_plan_expr;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::paths::{DB, NS};
 use crate::expr::plan::LogicalPlan;
-use crate::expr::statements::{OptionStatement, UseStatement};
-use crate::expr::{Base, ControlFlow, Expr, FlowResult, TopLevelExpr};
+use crate::expr::statements::{InsertStatement, OptionStatement, UseStatement};
+use crate::expr::visit::VisitMut;
+use crate::expr::{Base, ControlFlow, Data, Expr, FlowResult, Idiom, Literal, TopLevelExpr};
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::import::{ChunkedImport, ImportProgress, ImportTracker};
+use crate::kvs::session_tables::Resolver;
 use crate::kvs::slowlog::SlowLogVisit;
 use crate::kvs::{Datastore, LockType, Transaction, TransactionType};
 use crate::observe::{
@@ -60,6 +65,19 @@ pub struct Executor {
 	/// broker was already present (higher layer) or this statement skipped installation.
 	/// Drives conditional [`clear_broker`] so we never remove an externally supplied broker.
 	broker_owned_by_executor: bool,
+	/// Set for resumable imports, to save the progress of the import when
+	/// each chunk of statements commits
+	import: Option<Arc<ImportTracker>>,
+	/// Set for streamed queries, to send the result of each statement as soon
+	/// as it is complete, rather than returning them all at the end
+	output: Option<async_channel::Sender<QueryResult>>,
+	/// The number of results which have been sent to the output
+	flushed: usize,
+	/// The capabilities of the session, before they are restricted by the
+	/// capabilities of the selected database
+	capabilities: Arc<Capabilities>,
+	/// The number of bytes written by the last transaction which committed
+	committed_bytes: u64,
 }
 
 impl Executor {
@@ -138,15 +156,50 @@ impl Executor {
 impl Executor {
 	pub fn new(ctx: FrozenContext, opt: Options) -> Self {
 		Executor {
+			capabilities: ctx.get_capabilities(),
 			stack: TreeStack::new(),
 			results: Vec::new(),
 			opt,
 			ctx,
 			cached_session: None,
 			broker_owned_by_executor: false,
+			import: None,
+			output: None,
+			flushed: 0,
+			committed_bytes: 0,
 		}
 	}
 
+	/// Sends the results which are complete to the output of a streamed query.
+	///
+	/// Only the outcome of each sent result is kept, for the query event of the
+	/// batch. Returns `false` when the consumer of the output has gone away, in
+	/// which case the remaining statements need not run.
+	async fn flush_output(&mut self) -> bool {
+		let Some(output) = &self.output else {
+			return true;
+		};
+		for res in &mut self.results[self.flushed..] {
+			let result = match &mut res.result {
+				Ok(v) => Ok(std::mem::take(v)),
+				Err(e) => Err(e.clone()),
+			};
+			let sent = output
+				.send(QueryResult {
+					time: res.time,
+					result,
+					query_type: res.query_type,
+					stats: res.stats.clone(),
+				})
+				.await;
+			if sent.is_err() {
+				return false;
+			}
+		}
+		self.flushed = self.results.len();
+		true
+	}
+
 	/// Install a fresh [`StatementCounters`] on the executor's context for
 	/// the next statement and return a clone of the handle so the caller
 	/// can read the counts after the statement returns. The iterator
@@ -167,6 +220,7 @@ impl Executor {
 	/// without turning a row-count discrepancy into an outage.
 	fn install_statement_counters(&mut self) -> Arc<StatementCounters> {
 		let counters = StatementCounters::new();
+		counters.set_scan_limit(self.opt.max_scan_rows);
 		if let Some(ctx) = Arc::get_mut(&mut self.ctx) {
 			ctx.set_statement_counters(Some(Arc::clone(&counters)));
 		} else {
@@ -371,12 +425,13 @@ impl Executor {
 			}
 			// Fallback to the value-shape heuristic if no counter was
 			// installed (legacy / test paths).
-			return match value {
-				Some(Value::Array(arr)) => arr.len() as u64,
-				Some(Value::None) | Some(Value::Null) | None => 0,
-				Some(_) => 1,
-			};
+			return Self::returned_rows(value);
 		}
+		Self::returned_rows(value)
+	}
+
+	/// Count the rows a statement returned from the shape of its value.
+	fn returned_rows(value: Option<&Value>) -> u64 {
 		match value {
 			Some(Value::Array(arr)) => arr.len() as u64,
 			Some(Value::None) | Some(Value::Null) | None => 0,
@@ -397,6 +452,19 @@ impl Executor {
 		session
 	}
 
+	/// Rewrites the references to the session tables of the current session
+	/// in a statement to their scoped names, see [`crate::kvs::SessionTables`],
+	/// returning the resolver which maps the names in the result back, and
+	/// which resolves the values computed while the statement runs
+	fn resolve_session_tables(&mut self, plan: &mut TopLevelExpr) -> Option<Arc<Resolver>> {
+		let session = self.get_session_info().and_then(|s| s.id)?;
+		let tables = self.ctx.get_session_tables()?;
+		let (ns, db) = self.opt.ns_db().ok()?;
+		let mut resolver = tables.resolver(session, ns, db)?;
+		let _ = plan.visit_mut(&mut resolver);
+		Some(Arc::new(resolver))
+	}
+
 	/// Extract session information from the FrozenContext.
 	///
 	/// The session is stored as a Value object in the context with keys like
@@ -532,6 +600,7 @@ impl Executor {
 			current_value: None,
 			skip_fetch_perms: false,
 			version_stamp: None,
+			permission_trace: None,
 		};
 
 		// Check what level of context we need
@@ -582,6 +651,11 @@ impl Executor {
 			}
 		};
 
+		// Record the most rows which the plan of the statement can return
+		if let Some(counters) = self.ctx.statement_counters() {
+			counters.record_max_rows(plan.cardinality_hint());
+		}
+
 		// Execute the plan
 		// Handle control flow signals from execute()
 		let stream = match plan.execute(&exec_ctx) {
@@ -633,6 +707,18 @@ impl Executor {
 		}
 	}
 
+	/// Returns the capabilities of the session, restricted by the
+	/// capabilities declared by the selected database, if it declared any
+	async fn database_capabilities(&self, txn: &Transaction) -> Result<Arc<Capabilities>> {
+		if let Ok((ns, db)) = self.opt.ns_db()
+			&& let Some(db) = txn.get_db_by_name(ns, db, None).await?
+			&& let Some(caps) = &db.capabilities
+		{
+			return Ok(Arc::new(self.capabilities.with_database_capabilities(caps)));
+		}
+		Ok(Arc::clone(&self.capabilities))
+	}
+
 	/// Executes a statement which needs a transaction with the supplied
 	/// transaction.
 	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
@@ -640,8 +726,17 @@ impl Executor {
 		&mut self,
 		txn: Arc<Transaction>,
 		start: &Instant,
-		plan: TopLevelExpr,
+		mut plan: TopLevelExpr,
 	) -> FlowResult<Value> {
+		let resolver = self.resolve_session_tables(&mut plan);
+		// Refuse any statement which the guardrails of the datastore forbid
+		self.ctx.get_capabilities().guardrails().check(&plan)?;
+		// Record the shape of the statement for the index advisor
+		if let Some(advisor) = self.ctx.get_index_advisor()
+			&& let Ok((ns, db)) = self.opt.ns_db()
+		{
+			advisor.record(ns, db, &plan);
+		}
 		/// Helper method to get mutable access to the context
 		macro_rules! ctx_mut {
 			() => {
@@ -652,6 +747,15 @@ impl Executor {
 					.map_err(anyhow::Error::new)?
 			};
 		}
+		// Resolve the session tables in the values computed by this statement
+		if resolver.is_some() || self.ctx.session_resolver().is_some() {
+			ctx_mut!().set_session_resolver(resolver.clone());
+		}
+		// Restrict the capabilities to those of the selected database
+		let capabilities = self.database_capabilities(&txn).await?;
+		if !Arc::ptr_eq(&capabilities, &self.ctx.get_capabilities()) {
+			ctx_mut!().set_capabilities(capabilities);
+		}
 		let res = match plan {
 			TopLevelExpr::Use(stmt) => {
 				let opt_ref = self.opt.clone();
@@ -915,6 +1019,22 @@ impl Executor {
 					.await
 					.map_err(ControlFlow::Err)
 			}
+			TopLevelExpr::LiveSchema(s) => {
+				ctx_mut!().set_transaction(txn);
+				s.compute(&self.ctx, &self.opt).await.map_err(ControlFlow::Err)
+			}
+			TopLevelExpr::Lock(s) => {
+				ctx_mut!().set_transaction(txn);
+				s.compute(&self.ctx, &self.opt).await.map_err(ControlFlow::Err)
+			}
+			TopLevelExpr::Refresh(s) => {
+				ctx_mut!().set_transaction(txn);
+				self.stack
+					.enter(|stk| s.compute(stk, &self.ctx, &self.opt))
+					.finish()
+					.await
+					.map_err(ControlFlow::Err)
+			}
 			TopLevelExpr::Show(s) => {
 				ctx_mut!().set_transaction(txn);
 				s.compute(&self.ctx, &self.opt, None).await.map_err(ControlFlow::Err)
@@ -963,6 +1083,14 @@ impl Executor {
 			}
 		};
 
+		// Return the session tables under the names known by the session
+		let res = match resolver {
+			Some(resolver) => res.map(|mut v| {
+				resolver.unresolve_value(&mut v);
+				v
+			}),
+			None => res,
+		};
 		// Catch cancellation during running.
 		match self.ctx.done(true)? {
 			None => res,
@@ -993,7 +1121,107 @@ impl Executor {
 			}
 		}
 
-		self.execute_plan_impl(kvs, start, stmt).await
+		match stmt {
+			TopLevelExpr::Expr(Expr::Insert(stmt)) if stmt.non_atomic => {
+				self.execute_non_atomic_insert(kvs, start, *stmt).await
+			}
+			// A lock would be released as soon as it was taken
+			TopLevelExpr::Lock(_) => {
+				bail!(Error::InvalidStatement(
+					"Cannot LOCK a table outside of a transaction".to_string()
+				))
+			}
+			stmt => self.execute_plan_impl(kvs, start, stmt).await,
+		}
+	}
+
+	/// Execute an `INSERT ... NON ATOMIC` statement not wrapped in a
+	/// transaction block.
+	///
+	/// The rows are first inserted in a single transaction. When that is
+	/// larger than the storage engine accepts, they are split into batches
+	/// which each run in their own transaction, and the results of the
+	/// batches are returned together. Batches which committed before another
+	/// batch failed are not rolled back.
+	async fn execute_non_atomic_insert(
+		&mut self,
+		kvs: &Datastore,
+		start: &Instant,
+		mut stmt: InsertStatement,
+	) -> Result<Value> {
+		let rows = match std::mem::take(&mut stmt.data) {
+			Data::SingleExpression(Expr::Literal(Literal::Array(rows))) => InsertRows::Array(rows),
+			Data::SingleExpression(Expr::Param(param)) => match self.ctx.value(param.as_str()) {
+				Some(Value::Array(rows)) => InsertRows::Array(rows.clone().into_literal()),
+				_ => {
+					stmt.data = Data::SingleExpression(Expr::Param(param));
+					return self
+						.execute_plan_impl(
+							kvs,
+							start,
+							TopLevelExpr::Expr(Expr::Insert(Box::new(stmt))),
+						)
+						.await;
+				}
+			},
+			Data::ValuesExpression(rows) => InsertRows::Values(rows),
+			// Other data, such as the results of a SELECT, is computed in a
+			// transaction of its own before being split
+			Data::SingleExpression(expr) => {
+				match self.execute_plan_impl(kvs, start, TopLevelExpr::Expr(expr)).await? {
+					Value::Array(rows) => InsertRows::Array(rows.into_literal()),
+					value => {
+						stmt.data = Data::SingleExpression(value.into_literal());
+						return self
+							.execute_plan_impl(
+								kvs,
+								start,
+								TopLevelExpr::Expr(Expr::Insert(Box::new(stmt))),
+							)
+							.await;
+					}
+				}
+			}
+			data => {
+				stmt.data = data;
+				return self
+					.execute_plan_impl(kvs, start, TopLevelExpr::Expr(Expr::Insert(Box::new(stmt))))
+					.await;
+			}
+		};
+		let mut sizer = BatchSizer::new(rows.len());
+		let mut inserted = 0;
+		let mut result = Value::None;
+		while inserted < rows.len() {
+			let size = sizer.next(rows.len() - inserted);
+			let mut batch = stmt.clone();
+			batch.data = rows.data(inserted..inserted + size);
+			match self
+				.execute_plan_impl(kvs, start, TopLevelExpr::Expr(Expr::Insert(Box::new(batch))))
+				.await
+			{
+				Ok(value) => {
+					sizer.committed(size, self.committed_bytes);
+					inserted += size;
+					match (&mut result, value) {
+						(Value::Array(all), Value::Array(value)) => all.0.extend(value.0),
+						(all, value) => *all = value,
+					}
+				}
+				Err(e) => match e.downcast_ref::<Error>() {
+					Some(Error::TransactionTooLarge {
+						limit,
+						..
+					}) if size > 1 => sizer.too_large(size, *limit),
+					// The limit of the storage engine is below the size it rejected
+					Some(Error::TransactionRejectedTooLarge {
+						size: bytes,
+					}) if size > 1 => sizer.too_large(size, *bytes),
+					_ => return Err(e),
+				},
+			}
+		}
+		Ok(result)
 	}
 
 	async fn execute_plan_impl(
@@ -1059,11 +1287,22 @@ impl Executor {
 					return Ok(value);
 				}
 
+				let bytes = txn.bytes_written();
 				if let Err(e) = txn.commit().await {
+					// The storage engine rejected the transaction for its size
+					if matches!(
+						e.downcast_ref::<crate::kvs::Error>(),
+						Some(crate::kvs::Error::TransactionTooLarge)
+					) {
+						bail!(Error::TransactionRejectedTooLarge {
+							size: bytes,
+						});
+					}
 					bail!(Error::QueryNotExecuted {
 						message: e.to_string(),
 					});
 				}
+				self.committed_bytes = bytes;
 
 				// Flush buffered notifications only after the write is durable. Failed commits and
 				// cancelled transactions drop the receiver without delivery.
@@ -1133,6 +1372,7 @@ impl Executor {
 						Some(QueryError::NotExecuted),
 					)),
 					query_type: QueryType::Other,
+					stats: QueryStats::default(),
 				});
 				self.emit_statement_event_unexecuted(
 					kvs,
@@ -1269,6 +1509,7 @@ impl Executor {
 									),
 								}),
 								query_type: QueryType::Other,
+								stats: QueryStats::default(),
 							});
 							self.emit_statement_event_unexecuted(kvs, kind, cancel_class);
 							return Ok(());
@@ -1291,6 +1532,7 @@ impl Executor {
 								time: Duration::ZERO,
 								result,
 								query_type: QueryType::Other,
+								stats: QueryStats::default(),
 							});
 							self.emit_statement_event_unexecuted(kvs, kind, cancel_class);
 							if matches!(stmt, TopLevelExpr::Cancel) {
@@ -1311,7 +1553,7 @@ impl Executor {
 			trace!(target: TARGET, statement = %stmt.to_sql(), "Executing statement");
 
 			let query_type = match stmt {
-				TopLevelExpr::Live(_) => QueryType::Live,
+				TopLevelExpr::Live(_) | TopLevelExpr::LiveSchema(_) => QueryType::Live,
 				TopLevelExpr::Kill(_) => QueryType::Kill,
 				_ => QueryType::Other,
 			};
@@ -1330,6 +1572,7 @@ impl Executor {
 			// match either returns early or contributes a non-DML
 			// statement, so 0 is the right default.
 			let mut stmt_result_rows: u64 = 0;
+			let mut stmt_stats = QueryStats::default();
 			let result = match stmt {
 				TopLevelExpr::Begin => {
 					let _ = txn.cancel().await;
@@ -1353,6 +1596,7 @@ impl Executor {
 								.to_string(),
 						)),
 						query_type: QueryType::Other,
+						stats: QueryStats::default(),
 					});
 
 					self.emit_statement_event_cached(
@@ -1379,6 +1623,7 @@ impl Executor {
 										Some(QueryError::NotExecuted),
 									)),
 									query_type: QueryType::Other,
+									stats: QueryStats::default(),
 								});
 								return Ok(());
 							}
@@ -1393,6 +1638,7 @@ impl Executor {
 										Some(QueryError::NotExecuted),
 									)),
 									query_type: QueryType::Other,
+									stats: QueryStats::default(),
 								});
 								if matches!(stmt, TopLevelExpr::Cancel) {
 									return Ok(());
@@ -1421,6 +1667,7 @@ impl Executor {
 						time: before.elapsed(),
 						result: Ok(convert_value_to_public_value(Value::None)?),
 						query_type: QueryType::Other,
+						stats: QueryStats::default(),
 					});
 
 					self.emit_statement_event_cached(
@@ -1437,9 +1684,21 @@ impl Executor {
 					return Ok(());
 				}
 				TopLevelExpr::Commit => {
+					// Save the progress of a resumable import in the same
+					// transaction as the statements it records
+					let committed = match &self.import {
+						Some(import) => match import.save(&txn).await {
+							Ok(()) => txn.commit().await,
+							Err(e) => {
+								let _ = txn.cancel().await;
+								Err(e)
+							}
+						},
+						None => txn.commit().await,
+					};
 					// Commit the transaction.
 					// If error undo results.
-					let e = if let Err(e) = txn.commit().await {
+					let e = if let Err(e) = committed {
 						e
 					} else {
 						// Successfully commited. everything is fine.
@@ -1455,6 +1714,7 @@ impl Executor {
 							time: before.elapsed(),
 							result: Ok(convert_value_to_public_value(Value::None)?),
 							query_type: QueryType::Other,
+							stats: QueryStats::default(),
 						});
 
 						self.emit_statement_event_cached(
@@ -1489,6 +1749,7 @@ impl Executor {
 							Some(QueryError::NotExecuted),
 						)),
 						query_type: QueryType::Other,
+						stats: QueryStats::default(),
 					});
 
 					// `Cannot COMMIT` surfaces as a NotExecuted query error on
@@ -1515,6 +1776,7 @@ impl Executor {
 							time: before.elapsed(),
 							result: Ok(convert_value_to_public_value(Value::None)?),
 							query_type: QueryType::Other,
+							stats: QueryStats::default(),
 						});
 						self.emit_statement_event_cached(
 							kvs,
@@ -1539,10 +1801,18 @@ impl Executor {
 					// surface affected-row counts independently of the
 					// post-RETURN value shape.
 					let counters = self.install_statement_counters();
-					let r: Result<Value> = match self
-						.execute_plan_in_transaction(Arc::clone(&txn), &before, plan)
-						.await
-					{
+					let executed = match &plan {
+						// The statement could not be split from the block it runs in
+						TopLevelExpr::Expr(Expr::Insert(stmt)) if stmt.non_atomic => {
+							Err(ControlFlow::Err(anyhow!(Error::InvalidStatement(
+								"Cannot use NON ATOMIC within a transaction".to_string()
+							))))
+						}
+						_ => {
+							self.execute_plan_in_transaction(Arc::clone(&txn), &before, plan).await
+						}
+					};
+					let r: Result<Value> = match executed {
 						Ok(x) => Ok(x),
 						Err(ControlFlow::Return(value)) => {
 							skip_remaining = true;
@@ -1574,6 +1844,7 @@ impl Executor {
 								time: before.elapsed(),
 								result: Err(typed_err),
 								query_type,
+								stats: QueryStats::default(),
 							});
 
 							self.emit_statement_event_cached(
@@ -1605,6 +1876,7 @@ impl Executor {
 													Some(QueryError::NotExecuted),
 												)),
 												query_type: QueryType::Other,
+												stats: QueryStats::default(),
 											});
 										return Ok(());
 									}
@@ -1620,6 +1892,7 @@ impl Executor {
 													Some(QueryError::Cancelled),
 												)),
 												query_type: QueryType::Other,
+												stats: QueryStats::default(),
 											});
 									}
 								}
@@ -1643,6 +1916,7 @@ impl Executor {
 						r.as_ref().ok(),
 					);
 					stmt_result_rows = rows;
+					stmt_stats = counters.stats(Self::returned_rows(r.as_ref().ok()));
 
 					match r {
 						Ok(value) => Ok(convert_value_to_public_value(value)?),
@@ -1669,6 +1943,7 @@ impl Executor {
 				time: before.elapsed(),
 				result,
 				query_type,
+				stats: stmt_stats,
 			});
 		}
 
@@ -1692,7 +1967,22 @@ impl Executor {
 		plan: LogicalPlan,
 	) -> Result<Vec<QueryResult>> {
 		let stream = futures::stream::iter(plan.expressions.into_iter().map(Ok));
-		Self::execute_expr_stream(kvs, ctx, opt, false, stream).await
+		Self::execute_expr_stream(kvs, ctx, opt, false, None, None, stream).await
+	}
+
+	/// Executes a logical plan, sending the result of each statement to
+	/// `output` as soon as it is complete.
+	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
+	pub(crate) async fn execute_plan_streamed(
+		kvs: &Datastore,
+		ctx: FrozenContext,
+		opt: Options,
+		plan: LogicalPlan,
+		output: async_channel::Sender<QueryResult>,
+	) -> Result<()> {
+		let stream = futures::stream::iter(plan.expressions.into_iter().map(Ok));
+		Self::execute_expr_stream(kvs, ctx, opt, false, None, Some(output), stream).await?;
+		Ok(())
 	}
 
 	/// Execute a logical plan with an existing transaction
@@ -1707,6 +1997,7 @@ impl Executor {
 		// Execute each expression with the transaction
 		let tx = ctx.tx();
 		let batch_start = Instant::now();
+		let _interactive = ctx.enter_scheduler();
 		let mut executor = Self::new(ctx, opt);
 		let mut results = Vec::new();
 
@@ -1733,6 +2024,7 @@ impl Executor {
 			let query_result = match result {
 				Ok(value) | Err(ControlFlow::Return(value)) => QueryResult {
 					time,
+					stats: counters.stats(Self::returned_rows(Some(&value))),
 					result: crate::val::convert_value_to_public_value(value)
 						.map_err(|e| TypesError::internal(e.to_string())),
 					query_type: QueryType::Other,
@@ -1741,11 +2033,13 @@ impl Executor {
 					time,
 					result: Err(types_error_from_anyhow(e)),
 					query_type: QueryType::Other,
+					stats: QueryStats::default(),
 				},
 				Err(ControlFlow::Continue) | Err(ControlFlow::Break) => QueryResult {
 					time,
 					result: Err(TypesError::internal("Invalid control flow".to_string())),
 					query_type: QueryType::Other,
+					stats: QueryStats::default(),
 				},
 			};
 			let outcome = Outcome::from(&query_result.result);
@@ -1795,11 +2089,37 @@ impl Executor {
 			ctx,
 			opt,
 			skip_success_results,
+			None,
+			None,
 			stream.map(|x| x.map(expr::TopLevelExpr::from)),
 		)
 		.await
 	}
 
+	/// Executes a resumable import, applying the statements in chunks and
+	/// saving the progress of the import as each chunk commits.
+	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
+	pub(crate) async fn execute_import_stream<S>(
+		kvs: &Datastore,
+		ctx: FrozenContext,
+		opt: Options,
+		import: Arc<ImportTracker>,
+		resume: Option<ImportProgress>,
+		chunk_size: usize,
+		stream: S,
+	) -> Result<Vec<QueryResult>>
+	where
+		S: Stream<Item = Result<sql::TopLevelExpr>>,
+	{
+		let stream = ChunkedImport::new(
+			stream.map(|x| x.map(expr::TopLevelExpr::from)),
+			Arc::clone(&import),
+			resume,
+			chunk_size,
+		);
+		Self::execute_expr_stream(kvs, ctx, opt, true, Some(import), None, stream).await
+	}
+
 	#[instrument(
 		level = "debug",
 		name = "executor",
@@ -1827,6 +2147,8 @@ impl Executor {
 		ctx: FrozenContext,
 		opt: Options,
 		skip_success_results: bool,
+		import: Option<Arc<ImportTracker>>,
+		output: Option<async_channel::Sender<QueryResult>>,
 		stream: S,
 	) -> Result<Vec<QueryResult>>
 	where
@@ -1838,7 +2160,12 @@ impl Executor {
 		// of `QueryResult`s the executor produced is the source of
 		// truth for the per-batch counters.
 		let batch_start = Instant::now();
+		// Interactive queries are counted as active for the whole batch, so
+		// that background queries yield to them
+		let _interactive = ctx.enter_scheduler();
 		let mut this = Executor::new(ctx, opt);
+		this.import = import;
+		this.output = output;
 		let batch_results_start = this.results.len();
 		let mut stream = pin!(stream);
 
@@ -1868,6 +2195,12 @@ impl Executor {
 		}
 
 		while let Some(stmt) = stream.next().await {
+			// Background queries defer each statement to interactive ones,
+			// and sessions wait for their throttled writes. Statements within
+			// a transaction block do not wait, so that the transaction is not
+			// held open while waiting.
+			this.ctx.yield_to_interactive().await;
+			this.ctx.wait_for_throttles().await;
 			let stmt = match stmt {
 				Ok(x) => x,
 				Err(e) => {
@@ -1875,8 +2208,10 @@ impl Executor {
 						time: Duration::ZERO,
 						result: Err(TypesError::internal(e.to_string())),
 						query_type: QueryType::Other,
+						stats: QueryStats::default(),
 					});
 
+					this.flush_output().await;
 					this.emit_query_event_for_results(
 						kvs,
 						batch_start,
@@ -1931,6 +2266,7 @@ impl Executor {
 							time: Duration::ZERO,
 							result: Ok(convert_value_to_public_value(Value::None)?),
 							query_type: QueryType::Other,
+							stats: QueryStats::default(),
 						});
 					}
 				}
@@ -1940,9 +2276,11 @@ impl Executor {
 							time: Duration::ZERO,
 							result: Ok(convert_value_to_public_value(Value::None)?),
 							query_type: QueryType::Other,
+							stats: QueryStats::default(),
 						});
 					}
 
+					let chunk_start = this.results.len();
 					let begin_result = this.execute_begin_statement(kvs, stream.as_mut()).await;
 					let outcome = Outcome::from(&begin_result);
 					// `execute_begin_statement` returns `anyhow::Result`; on
@@ -1973,8 +2311,10 @@ impl Executor {
 							time: Duration::ZERO,
 							result: Err(types_error_from_anyhow(e)),
 							query_type: QueryType::Other,
+							stats: QueryStats::default(),
 						});
 
+						this.flush_output().await;
 						this.emit_query_event_for_results(
 							kvs,
 							batch_start,
@@ -1982,6 +2322,12 @@ impl Executor {
 						);
 						return Ok(this.results);
 					}
+
+					// Resumable imports only report the statements which failed
+					if this.import.is_some() {
+						let chunk = this.results.split_off(chunk_start);
+						this.results.extend(chunk.into_iter().filter(|r| r.result.is_err()));
+					}
 				}
 				stmt => {
 					let query_type: QueryType = QueryType::for_toplevel_expr(&stmt);
@@ -2018,9 +2364,11 @@ impl Executor {
 								time: start.elapsed(),
 								result: Err(types_error_from_anyhow(err)),
 								query_type,
+								stats: QueryStats::default(),
 							});
 						}
 					} else {
+						let stats = counters.stats(Self::returned_rows(result.as_ref().ok()));
 						let result = match result {
 							Ok(value) => Ok(convert_value_to_public_value(value)?),
 							Err(err) => Err(types_error_from_anyhow(err)),
@@ -2029,10 +2377,20 @@ impl Executor {
 							time: start.elapsed(),
 							result,
 							query_type,
+							stats,
 						});
 					}
 				}
 			}
+			// A resumable import stops at the first failure, so that the
+			// recorded progress never moves past a statement which failed
+			if this.import.is_some() && this.results.len() > batch_results_start {
+				break;
+			}
+			// A streamed query stops once its output is no longer read
+			if !this.flush_output().await {
+				break;
+			}
 			yield_now!();
 		}
 		this.emit_query_event_for_results(kvs, batch_start, &this.results[batch_results_start..]);
@@ -2040,8 +2398,76 @@ impl Executor {
 	}
 }
 
+/// The rows of an `INSERT ... NON ATOMIC` statement, which can be inserted
+/// in several batches
+enum InsertRows {
+	Array(Vec<Expr>),
+	Values(Vec<Vec<(Idiom, Expr)>>),
+}
+
+impl InsertRows {
+	fn len(&self) -> usize {
+		match self {
+			InsertRows::Array(rows) => rows.len(),
+			InsertRows::Values(rows) => rows.len(),
+		}
+	}
+
+	/// Returns the data of a statement inserting a range of the rows
+	fn data(&self, range: Range<usize>) -> Data {
+		match self {
+			InsertRows::Array(rows) => {
+				Data::SingleExpression(Expr::Literal(Literal::Array(rows[range].to_vec())))
+			}
+			InsertRows::Values(rows) => Data::ValuesExpression(rows[range].to_vec()),
+		}
+	}
+}
+
+/// Sizes the batches of an `INSERT ... NON ATOMIC` statement
+///
+/// The first batch holds every row. A batch which is too large for a single
+/// transaction is halved, and once the limit of the storage engine is known,
+/// each batch is sized from the bytes written by the rows of the previous
+/// batch, to fill about three quarters of the limit.
+struct BatchSizer {
+	size: usize,
+	limit: Option<u64>,
+}
+
+impl BatchSizer {
+	fn new(rows: usize) -> Self {
+		Self {
+			size: rows.max(1),
+			limit: None,
+		}
+	}
+
+	/// Returns the number of rows of the next batch
+	fn next(&self, remaining: usize) -> usize {
+		self.size.min(remaining)
+	}
+
+	/// Records that a batch of rows was too large for a single transaction
+	fn too_large(&mut self, rows: usize, limit: u64) {
+		self.limit = Some(self.limit.map_or(limit, |known| known.min(limit)));
+		self.size = (rows / 2).max(1);
+	}
+
+	/// Records the number of bytes written by a batch of rows which committed
+	fn committed(&mut self, rows: usize, bytes: u64) {
+		let Some(limit) = self.limit else {
+			return;
+		};
+		let per_row = (bytes / rows.max(1) as u64).max(1);
+		let size = (limit / 4 * 3) / per_row;
+		self.size = usize::try_from(size).unwrap_or(usize::MAX).max(1);
+	}
+}
+
 #[cfg(test)]
 mod tests {
+	use super::BatchSizer;
 	use crate::dbs::Session;
 	use crate::iam::{Level, Role};
 	use crate::kvs::Datastore;
@@ -2615,4 +3041,26 @@ mod tests {
 			assert_eq!(err, 1);
 		}
 	}
+
+	#[test]
+	fn sizes_non_atomic_insert_batches() {
+		let mut sizer = BatchSizer::new(1000);
+		// Every row is tried in a single transaction first
+		assert_eq!(sizer.next(1000), 1000);
+		// Committed batches do not change the size while the limit is unknown
+		sizer.committed(1000, 1 << 20);
+		assert_eq!(sizer.next(1000), 1000);
+		// A batch which is too large is halved
+		sizer.too_large(1000, 4000);
+		assert_eq!(sizer.next(1000), 500);
+		sizer.too_large(500, 4000);
+		assert_eq!(sizer.next(1000), 250);
+		// The next batch fills three quarters of the limit
+		sizer.committed(250, 2500);
+		assert_eq!(sizer.next(1000), 300);
+		assert_eq!(sizer.next(120), 120);
+		// A row larger than the limit is still tried on its own
+		sizer.committed(1, 10_000);
+		assert_eq!(sizer.next(1000), 1);
+	}
 }
@@ -0,0 +1,261 @@
+//! Guardrails which refuse destructive statements.
+//!
+//! Guardrails are configured with [`Capabilities::with_guardrails`]. They
+//! guard against entire classes of accidental data loss, such as an `UPDATE`
+//! or a `DELETE` which is missing its `WHERE` clause, regardless of the
+//! permissions of the user running the statement.
+//!
+//! The executor checks each top-level statement, along with every statement
+//! nested within it, before it is run. The statements are also checked as
+//! they run, so that the statements which are only planned at runtime, such as
+//! the body of a function, and the targets which are only known at runtime,
+//! such as a table built with `type::table`, are held to the same guardrails.
+//!
+//! [`Capabilities::with_guardrails`]: crate::dbs::Capabilities::with_guardrails
+
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::dbs::Statement;
+use crate::err::Error;
+use crate::expr::statements::{
+	DeleteStatement, RemoveDatabaseStatement, RemoveNamespaceStatement, RemoveTableStatement,
+	UpdateStatement, UpsertStatement,
+};
+use crate::expr::visit::{Visit, Visitor};
+use crate::expr::{Expr, TopLevelExpr};
+use crate::kvs::SessionTables;
+use crate::val::TableName;
+
+/// The classes of destructive statements which a datastore refuses to run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Guardrails {
+	/// Refuse `UPDATE`, `UPSERT` and `DELETE` statements which target a whole
+	/// table without a `WHERE` clause
+	pub forbid_unfiltered_update_delete: bool,
+	/// Refuse `REMOVE TABLE` statements
+	pub forbid_remove_table: bool,
+	/// Refuse `REMOVE DATABASE` statements
+	pub forbid_remove_database: bool,
+	/// Refuse `REMOVE NAMESPACE` statements
+	pub forbid_remove_namespace: bool,
+}
+
+impl Guardrails {
+	/// Guardrails which refuse every class of destructive statement
+	pub fn production() -> Self {
+		Self {
+			forbid_unfiltered_update_delete: true,
+			forbid_remove_table: true,
+			forbid_remove_database: true,
+			forbid_remove_namespace: true,
+		}
+	}
+
+	/// Returns true if any class of statement is refused
+	pub fn is_enabled(&self) -> bool {
+		*self != Self::default()
+	}
+
+	/// Checks a statement, and any statement nested within it, against the
+	/// guardrails
+	pub(crate) fn check(&self, plan: &TopLevelExpr) -> Result<()> {
+		if !self.is_enabled() {
+			return Ok(());
+		}
+		plan.visit(&mut Checker {
+			guardrails: self,
+		})
+		.map_err(refused)
+	}
+
+	/// Checks a whole table targeted by a running statement, including a
+	/// table which was only known once the statement ran
+	pub(crate) fn check_table_target(&self, stm: &Statement<'_>, table: &TableName) -> Result<()> {
+		let statement = match stm {
+			Statement::Update(_) => "UPDATE",
+			Statement::Upsert(_) => "UPSERT",
+			Statement::Delete(_) => "DELETE",
+			_ => return Ok(()),
+		};
+		self.unfiltered(statement, table.as_str(), stm.cond().is_some()).map_err(refused)
+	}
+
+	/// Checks the removal of a table, once its name is known
+	pub(crate) fn check_remove_table(&self, table: &TableName) -> Result<()> {
+		self.remove_table(SessionTables::is_scoped(table)).map_err(refused)
+	}
+
+	/// Checks the removal of a database
+	pub(crate) fn check_remove_database(&self) -> Result<()> {
+		self.remove_database().map_err(refused)
+	}
+
+	/// Checks the removal of a namespace
+	pub(crate) fn check_remove_namespace(&self) -> Result<()> {
+		self.remove_namespace().map_err(refused)
+	}
+
+	fn unfiltered(&self, statement: &str, table: &str, filtered: bool) -> Result<(), String> {
+		if self.forbid_unfiltered_update_delete && !filtered {
+			return Err(format!(
+				"{statement} on the whole `{table}` table requires a WHERE clause"
+			));
+		}
+		Ok(())
+	}
+
+	fn remove_table(&self, session_table: bool) -> Result<(), String> {
+		// Session tables are temporary, and removed when their session ends
+		if self.forbid_remove_table && !session_table {
+			return Err("REMOVE TABLE is not allowed".to_owned());
+		}
+		Ok(())
+	}
+
+	fn remove_database(&self) -> Result<(), String> {
+		if self.forbid_remove_database {
+			return Err("REMOVE DATABASE is not allowed".to_owned());
+		}
+		Ok(())
+	}
+
+	fn remove_namespace(&self) -> Result<(), String> {
+		if self.forbid_remove_namespace {
+			return Err("REMOVE NAMESPACE is not allowed".to_owned());
+		}
+		Ok(())
+	}
+}
+
+/// Converts the reason a statement was refused into an error
+fn refused(reason: String) -> anyhow::Error {
+	anyhow::Error::new(Error::Guardrail(reason))
+}
+
+impl fmt::Display for Guardrails {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let forbidden = [
+			(self.forbid_unfiltered_update_delete, "unfiltered_update_delete"),
+			(self.forbid_remove_table, "remove_table"),
+			(self.forbid_remove_database, "remove_database"),
+			(self.forbid_remove_namespace, "remove_namespace"),
+		];
+		let forbidden: Vec<_> =
+			forbidden.into_iter().filter_map(|(enabled, name)| enabled.then_some(name)).collect();
+		write!(f, "[{}]", forbidden.join(", "))
+	}
+}
+
+/// Returns the name of the first target which is a whole table
+fn whole_table(what: &[Expr]) -> Option<&str> {
+	what.iter().find_map(|expr| match expr {
+		Expr::Table(name) => Some(name.as_str()),
+		_ => None,
+	})
+}
+
+struct Checker<'a> {
+	guardrails: &'a Guardrails,
+}
+
+impl Checker<'_> {
+	fn check_unfiltered(
+		&self,
+		statement: &str,
+		what: &[Expr],
+		filtered: bool,
+	) -> Result<(), String> {
+		match whole_table(what) {
+			Some(table) => self.guardrails.unfiltered(statement, table, filtered),
+			None => Ok(()),
+		}
+	}
+}
+
+impl Visitor for Checker<'_> {
+	type Error = String;
+
+	fn visit_update(&mut self, u: &UpdateStatement) -> Result<(), Self::Error> {
+		self.check_unfiltered("UPDATE", &u.what, u.cond.is_some())?;
+		u.visit(self)
+	}
+
+	fn visit_upsert(&mut self, u: &UpsertStatement) -> Result<(), Self::Error> {
+		self.check_unfiltered("UPSERT", &u.what, u.cond.is_some())?;
+		u.visit(self)
+	}
+
+	fn visit_delete(&mut self, d: &DeleteStatement) -> Result<(), Self::Error> {
+		self.check_unfiltered("DELETE", &d.what, d.cond.is_some())?;
+		d.visit(self)
+	}
+
+	fn visit_remove_table(&mut self, r: &RemoveTableStatement) -> Result<(), Self::Error> {
+		let session_table = matches!(&r.name, Expr::Table(name) if SessionTables::is_scoped(name));
+		self.guardrails.remove_table(session_table)?;
+		r.visit(self)
+	}
+
+	fn visit_remove_database(&mut self, r: &RemoveDatabaseStatement) -> Result<(), Self::Error> {
+		self.guardrails.remove_database()?;
+		r.visit(self)
+	}
+
+	fn visit_remove_namespace(&mut self, r: &RemoveNamespaceStatement) -> Result<(), Self::Error> {
+		self.guardrails.remove_namespace()?;
+		r.visit(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn check(guardrails: Guardrails, sql: &str) -> Result<()> {
+		let ast = crate::syn::parse(sql).unwrap();
+		for expr in ast.expressions {
+			guardrails.check(&expr.into())?;
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn unfiltered_statements_are_refused() {
+		let guardrails = Guardrails {
+			forbid_unfiltered_update_delete: true,
+			..Default::default()
+		};
+		check(guardrails, "DELETE person").unwrap_err();
+		check(guardrails, "UPDATE person SET active = true").unwrap_err();
+		check(guardrails, "IF true { UPSERT person SET active = true }").unwrap_err();
+		check(guardrails, "DELETE person WHERE active = false").unwrap();
+		check(guardrails, "DELETE person:tobie").unwrap();
+		check(guardrails, "REMOVE TABLE person").unwrap();
+		check(Guardrails::default(), "DELETE person").unwrap();
+	}
+
+	#[test]
+	fn removals_are_refused() {
+		let guardrails = Guardrails::production();
+		check(guardrails, "REMOVE TABLE person").unwrap_err();
+		check(guardrails, "REMOVE DATABASE test").unwrap_err();
+		check(guardrails, "REMOVE NAMESPACE test").unwrap_err();
+		check(guardrails, "REMOVE FIELD name ON person").unwrap();
+	}
+
+	#[test]
+	fn runtime_targets_are_refused() {
+		let guardrails = Guardrails::production();
+		let table = TableName::new("person");
+		let delete = DeleteStatement::default();
+		guardrails.check_table_target(&Statement::Delete(&delete), &table).unwrap_err();
+		Guardrails::default().check_table_target(&Statement::Delete(&delete), &table).unwrap();
+		guardrails.check_remove_table(&table).unwrap_err();
+		let session_table = SessionTables::scoped_name(uuid::Uuid::new_v4(), &table);
+		guardrails.check_remove_table(&session_table).unwrap();
+		guardrails.check_remove_database().unwrap_err();
+		guardrails.check_remove_namespace().unwrap_err();
+	}
+}
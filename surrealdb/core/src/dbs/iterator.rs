@@ -315,6 +315,8 @@ impl Iterator {
 		doc_ctx: &NsDbCtx,
 		table: &TableName,
 	) -> Result<()> {
+		// The table may only be known now, such as one built by `type::table`
+		ctx.get_capabilities().guardrails().check_table_target(stm_ctx.stm, table)?;
 		let tb = if stm_ctx.stm.requires_table_existence() {
 			ctx.tx()
 				.get_tb(doc_ctx.ns.namespace_id, doc_ctx.db.database_id, table, opt.version)
@@ -1217,6 +1219,12 @@ impl Iterator {
 		if ctx.is_done(None).await? {
 			return Ok(());
 		}
+		// Count the records read for the statement stats
+		let count = match pro.val {
+			Operable::Count(count) => count as u64,
+			_ => 1,
+		};
+		ctx.scan_recorder().record(count, "Iterator")?;
 		// Get the record strategy
 		let rs = pro.record_strategy;
 		// Extract the value
@@ -1267,6 +1275,10 @@ impl Iterator {
 						&& let Some(counters) = ctx.statement_counters()
 					{
 						counters.record_affected();
+						// Report whether each UPSERT created or updated its record
+						if let Statement::Upsert(_) = stm {
+							counters.record_upserted(doc.created);
+						}
 					}
 					res
 				})
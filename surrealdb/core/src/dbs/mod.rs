@@ -5,9 +5,11 @@
 //! operations. This module also gives a `context` to the transaction.
 
 mod broker;
+mod compiled;
 mod distinct;
 pub mod executor;
 mod group;
+mod guardrails;
 mod iterator;
 mod options;
 mod plan;
@@ -30,13 +32,15 @@ pub use self::broker::{
 	RoutedNotification,
 };
 pub use self::capabilities::Capabilities;
+pub use self::compiled::CompiledPlan;
 pub(crate) use self::executor::Executor;
+pub use self::guardrails::Guardrails;
 pub(crate) use self::iterator::{Iterable, Iterator, Operable, Processable};
 pub(crate) use self::options::{Force, Options};
-pub use self::response::{QueryResult, QueryResultBuilder, QueryType, Status};
-pub use self::session::{NewPlannerStrategy, Session};
+pub use self::response::{QueryResult, QueryResultBuilder, QueryStats, QueryType, Status};
+pub use self::session::{NewPlannerStrategy, Priority, Session};
 pub(crate) use self::statement::Statement;
-pub(crate) use self::statement_counters::StatementCounters;
+pub(crate) use self::statement_counters::{ScanBudget, ScanRecorder, StatementCounters};
 
 #[cfg(storage)]
 mod file;
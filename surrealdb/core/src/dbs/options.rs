@@ -45,6 +45,8 @@ pub struct Options {
 	pub(crate) import: bool,
 	/// The data version as a timestamp
 	pub(crate) version: Option<u64>,
+	/// The maximum number of rows a statement may scan (0 for unlimited)
+	pub(crate) max_scan_rows: u64,
 	/// Tracks async event nesting depth for enforcing event MAXDEPTH.
 	async_event_depth: Option<u16>,
 }
@@ -68,6 +70,7 @@ impl Options {
 			import: false,
 			auth: Arc::new(Auth::default()),
 			version: None,
+			max_scan_rows: config.max_scan_rows,
 			async_event_depth: None,
 		}
 	}
@@ -92,6 +95,13 @@ impl Options {
 		self
 	}
 
+	/// Set the maximum number of rows a statement can scan, or 0 for no
+	/// limit.
+	pub fn with_max_scan_rows(mut self, rows: u64) -> Self {
+		self.max_scan_rows = rows;
+		self
+	}
+
 	/// Resume the computation-depth count `depth` levels deeper, reducing the
 	/// remaining budget by that much (saturating at 0).
 	///
@@ -701,6 +701,9 @@ pub(super) trait Collector {
 				}
 			},
 			Iterable::Table(doc_ctx, table, rs, sc) => {
+				if let Some(counters) = ctx.statement_counters() {
+					counters.record_table_scan();
+				}
 				let ctx = Self::check_query_planner_context(ctx, &table);
 				match rs {
 					RecordStrategy::Count => {
@@ -1169,6 +1172,17 @@ pub(super) trait Collector {
 				message: "No iterator has been found.".to_string(),
 			})
 		};
+		// Record the read in the index usage statistics
+		if let Some(ix) = exe.iterator_index(irf) {
+			ctx.get_index_stores().usage().record_read(
+				doc_ctx.ns().namespace_id,
+				doc_ctx.db().database_id,
+				ix,
+			);
+			if let Some(counters) = ctx.statement_counters() {
+				counters.record_index(&ix.name);
+			}
+		}
 
 		let txn = ctx.tx();
 		match rs {
@@ -53,13 +53,52 @@ impl QueryType {
 	/// Returns the query type for the given toplevel expression.
 	pub(crate) fn for_toplevel_expr(expr: &TopLevelExpr) -> Self {
 		match expr {
-			TopLevelExpr::Live(_) => QueryType::Live,
+			TopLevelExpr::Live(_) | TopLevelExpr::LiveSchema(_) => QueryType::Live,
 			TopLevelExpr::Kill(_) => QueryType::Kill,
 			_ => QueryType::Other,
 		}
 	}
 }
 
+/// Execution statistics for a single statement.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, SurrealValue)]
+#[surreal(crate = "surrealdb_types")]
+#[non_exhaustive]
+pub struct QueryStats {
+	/// The number of records read while executing the statement, before any
+	/// filtering was applied.
+	pub rows_scanned: u64,
+	/// The number of rows returned by the statement.
+	pub rows_returned: u64,
+	/// The names of the indexes read from, in the order they were first used.
+	pub indexes_used: Vec<String>,
+	/// Whether the statement read every record of a table instead of using
+	/// an index.
+	pub table_scan: bool,
+	/// The number of records an `UPSERT` statement created.
+	#[surreal(default)]
+	pub records_created: u64,
+	/// The number of records an `UPSERT` statement updated, rather than
+	/// created.
+	#[surreal(default)]
+	pub records_updated: u64,
+	/// The most rows which the plan of the statement can return, when the
+	/// planner could bound it.
+	#[surreal(default)]
+	pub max_rows: Option<u64>,
+	/// Whether the statement timed out with `RETURN PARTIAL`, so that only the
+	/// rows found before the timeout were returned.
+	#[surreal(default)]
+	pub truncated: bool,
+}
+
+impl QueryStats {
+	/// Returns true if nothing was recorded for the statement.
+	pub fn is_empty(&self) -> bool {
+		self == &Self::default()
+	}
+}
+
 /// The return value when running a query set on the database.
 #[derive(Debug, Clone)]
 pub struct QueryResult {
@@ -68,6 +107,8 @@ pub struct QueryResult {
 	// Record the query type in case processing the response is necessary (such as tracking live
 	// queries).
 	pub query_type: QueryType,
+	/// The execution statistics of the statement.
+	pub stats: QueryStats,
 }
 
 impl QueryResult {
@@ -157,6 +198,11 @@ impl SurrealValue for QueryResult {
 		match self.result {
 			Ok(v) => {
 				map.insert("result", v);
+				// Errors flatten their details into the map, so stats are only sent
+				// alongside successful results.
+				if !self.stats.is_empty() {
+					map.insert("stats", self.stats.into_value());
+				}
 			}
 			Err(e) => {
 				let err_val = into_query_result_value(&e);
@@ -201,6 +247,8 @@ impl SurrealValue for QueryResult {
 		let status = Status::from_value(status)?;
 		let query_type =
 			map.remove("type").map(QueryType::from_value).transpose()?.unwrap_or_default();
+		let stats =
+			map.remove("stats").map(QueryStats::from_value).transpose()?.unwrap_or_default();
 
 		let time = humantime::parse_duration(&time.into_string().map_err(|e| {
 			TypesError::serialization(e.to_string(), SerializationError::Deserialization)
@@ -223,6 +271,7 @@ impl SurrealValue for QueryResult {
 			time,
 			result,
 			query_type,
+			stats,
 		})
 	}
 }
@@ -239,6 +288,7 @@ impl QueryResultBuilder {
 			start_time: Instant::now(),
 			result: Ok(Value::None),
 			query_type: QueryType::Other,
+			stats: QueryStats::default(),
 		}
 	}
 
@@ -247,6 +297,7 @@ impl QueryResultBuilder {
 			time: Duration::ZERO,
 			result: Ok(Value::None),
 			query_type: QueryType::Other,
+			stats: QueryStats::default(),
 		}
 	}
 
@@ -265,6 +316,7 @@ impl QueryResultBuilder {
 			time: self.start_time.elapsed(),
 			result: self.result,
 			query_type: self.query_type,
+			stats: QueryStats::default(),
 		}
 	}
 
@@ -273,6 +325,7 @@ impl QueryResultBuilder {
 			time: self.start_time.elapsed(),
 			result,
 			query_type: self.query_type,
+			stats: QueryStats::default(),
 		}
 	}
 }
@@ -337,6 +390,7 @@ mod tests {
 			time: Duration::from_millis(42),
 			result: Err(error),
 			query_type: QueryType::Other,
+			stats: QueryStats::default(),
 		}
 	}
 
@@ -440,6 +494,7 @@ mod tests {
 			time: Duration::from_millis(10),
 			result: Ok(Value::String("hello".to_string())),
 			query_type: QueryType::Other,
+			stats: QueryStats::default(),
 		};
 		let val = qr.into_value();
 		let parsed = QueryResult::from_value(val).expect("round-trip should succeed");
@@ -447,4 +502,27 @@ mod tests {
 		let v = parsed.result.unwrap();
 		assert_eq!(v, Value::String("hello".to_string()));
 	}
+
+	#[test]
+	fn query_result_stats_round_trip() {
+		let stats = QueryStats {
+			rows_scanned: 12,
+			rows_returned: 3,
+			indexes_used: vec!["idx_email".to_string()],
+			table_scan: false,
+			records_created: 1,
+			records_updated: 0,
+			max_rows: Some(1),
+			truncated: false,
+		};
+		let qr = QueryResult {
+			time: Duration::from_millis(10),
+			result: Ok(Value::Array(Default::default())),
+			query_type: QueryType::Other,
+			stats: stats.clone(),
+		};
+		let val = qr.into_value();
+		let parsed = QueryResult::from_value(val).expect("round-trip should succeed");
+		assert_eq!(parsed.stats, stats);
+	}
 }
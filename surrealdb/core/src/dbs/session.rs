@@ -46,9 +46,18 @@ pub struct Session {
 	pub variables: PublicVariables,
 	/// Strategy for the new streaming planner/executor.
 	pub new_planner_strategy: NewPlannerStrategy,
+	/// The scheduling priority of the queries run in this session
+	pub priority: Priority,
 	/// When true, EXPLAIN ANALYZE output omits elapsed durations, making
 	/// output deterministic for testing.
 	pub redact_volatile_explain_attrs: bool,
+	/// Whether the records deleted by the queries of this session are evicted
+	/// from tables defined with `EVICT`, so that live queries are notified
+	/// with an `EVICTED` action instead of a `DELETE`
+	pub eviction: bool,
+	/// The session of the administrator impersonating the current user, see
+	/// [`crate::iam::impersonate`]
+	pub impersonator: Option<Arc<Session>>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
@@ -88,6 +97,38 @@ impl FromStr for NewPlannerStrategy {
 	}
 }
 
+/// The quality-of-service class of the queries run in a session
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Priority {
+	/// Latency sensitive work, which is run as soon as possible.
+	#[default]
+	Interactive,
+	/// Throughput oriented work, such as exports, analytics and archival,
+	/// which yields to interactive work and reads in smaller batches.
+	Background,
+}
+
+impl fmt::Display for Priority {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Interactive => f.write_str("interactive"),
+			Self::Background => f.write_str("background"),
+		}
+	}
+}
+
+impl FromStr for Priority {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"interactive" => Ok(Self::Interactive),
+			"background" => Ok(Self::Background),
+			_ => Err(format!("unknown priority: '{s}' (expected 'interactive' or 'background')")),
+		}
+	}
+}
+
 impl Session {
 	/// Set the selected namespace for the session
 	pub fn with_ns(mut self, ns: &str) -> Session {
@@ -119,6 +160,12 @@ impl Session {
 		self
 	}
 
+	/// Set the scheduling priority of the session
+	pub fn with_priority(mut self, priority: Priority) -> Session {
+		self.priority = priority;
+		self
+	}
+
 	/// Retrieves the selected namespace
 	pub(crate) fn ns(&self) -> Option<Arc<str>> {
 		self.ns.as_deref().map(Into::into)
@@ -203,7 +250,10 @@ impl Session {
 			exp: None,
 			variables: Default::default(),
 			new_planner_strategy: NewPlannerStrategy::default(),
+			priority: Priority::default(),
 			redact_volatile_explain_attrs: false,
+			eviction: false,
+			impersonator: None,
 		}
 	}
 
@@ -160,6 +160,24 @@ impl ToSql for Statement<'_> {
 }
 
 impl Statement<'_> {
+	/// The keyword which starts this statement
+	pub(crate) fn keyword(&self) -> &'static str {
+		match self {
+			Statement::Live(_) => "LIVE",
+			Statement::Show(_) => "SHOW",
+			Statement::Select {
+				..
+			} => "SELECT",
+			Statement::Create(_) => "CREATE",
+			Statement::Upsert(_) => "UPSERT",
+			Statement::Update(_) => "UPDATE",
+			Statement::Relate(_) => "RELATE",
+			Statement::Delete(_) => "DELETE",
+			Statement::Insert(_) => "INSERT",
+			Statement::Access(_) => "ACCESS",
+		}
+	}
+
 	/// Check if this is a SELECT statement
 	pub(crate) fn is_select(&self) -> bool {
 		matches!(self, Statement::Select { .. })
@@ -20,9 +20,25 @@
 //! and `set_record` no-ops suppressed by `!self.changed()` therefore never
 //! inflate the count, even when the visited row would otherwise have been
 //! counted by the iterator.
+//!
+//! The same counter set also records the rows scanned, indexes read and table
+//! scans performed by either execution engine, which the executor reports as
+//! the [`crate::dbs::QueryStats`] of each statement. As every scan operator
+//! records its rows here, the counters also enforce the scan budget of the
+//! statement, set from [`crate::dbs::Options::max_scan_rows`]. The `SCAN LIMIT`
+//! clause of a `SELECT` adds a [`ScanBudget`] of its own, which only covers
+//! the rows read within that `SELECT`, and can only lower the limit which
+//! applies to them.
 
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use anyhow::{Result, bail};
+use parking_lot::Mutex;
+
+use crate::dbs::QueryStats;
+use crate::err::Error;
+use crate::exec::CardinalityHint;
 
 /// Atomic per-statement counter set, shared between the iterator and the
 /// executor for the lifetime of a single top-level statement.
@@ -44,6 +60,24 @@ pub(crate) struct StatementCounters {
 	/// SELECT row counts are derived from the post-RETURN [`crate::val::Value`]
 	/// shape inside [`crate::dbs::executor`], not from this counter.
 	affected: AtomicU64,
+	/// Records which UPSERT statements created, rather than updated. Only
+	/// incremented alongside `affected`, so unchanged records are ignored.
+	created: AtomicU64,
+	/// Records which UPSERT statements updated, rather than created.
+	updated: AtomicU64,
+	/// Records read by the statement, before any filtering.
+	scanned: AtomicU64,
+	/// The number of records the statement may read, or 0 for no limit.
+	scan_limit: AtomicU64,
+	/// Whether the statement iterated over every record in a table.
+	table_scan: AtomicBool,
+	/// Whether the statement timed out, returning the rows found so far.
+	truncated: AtomicBool,
+	/// The names of the indexes read by the statement, in first-use order.
+	indexes: Mutex<Vec<String>>,
+	/// The most rows which the plan of the statement can return, if the
+	/// plan bounds it.
+	max_rows: Mutex<Option<u64>>,
 }
 
 impl StatementCounters {
@@ -63,4 +97,229 @@ impl StatementCounters {
 	pub(crate) fn affected(&self) -> u64 {
 		self.affected.load(Ordering::Relaxed)
 	}
+
+	/// Record whether an UPSERT created a new record or updated an existing
+	/// one.
+	pub(crate) fn record_upserted(&self, created: bool) {
+		if created {
+			self.created.fetch_add(1, Ordering::Relaxed);
+		} else {
+			self.updated.fetch_add(1, Ordering::Relaxed);
+		}
+	}
+
+	/// Set the number of records the statement may read, or 0 for no limit.
+	pub(crate) fn set_scan_limit(&self, limit: u64) {
+		self.scan_limit.store(limit, Ordering::Relaxed);
+	}
+
+	/// Bump the scanned-row counter by the number of records read by an
+	/// operator, failing once the statement has read more records than its
+	/// scan limit allows.
+	pub(crate) fn record_scanned(&self, count: u64, operator: &str) -> Result<()> {
+		let scanned = self.scanned.fetch_add(count, Ordering::Relaxed).saturating_add(count);
+		let limit = self.scan_limit.load(Ordering::Relaxed);
+		if limit > 0 && scanned > limit {
+			bail!(Error::ScanLimitExceeded {
+				operator: operator.to_owned(),
+				limit,
+			});
+		}
+		Ok(())
+	}
+
+	/// Mark the statement as having scanned a whole table.
+	pub(crate) fn record_table_scan(&self) {
+		self.table_scan.store(true, Ordering::Relaxed);
+	}
+
+	/// Mark the statement as having timed out with partial results.
+	pub(crate) fn record_truncated(&self) {
+		self.truncated.store(true, Ordering::Relaxed);
+	}
+
+	/// Record that the statement read from the named index.
+	pub(crate) fn record_index(&self, name: &str) {
+		let mut indexes = self.indexes.lock();
+		if !indexes.iter().any(|i| i == name) {
+			indexes.push(name.to_owned());
+		}
+	}
+
+	/// Record the most rows which the plan of the statement can return.
+	/// Unbounded plans leave the bound unknown.
+	pub(crate) fn record_max_rows(&self, hint: CardinalityHint) {
+		*self.max_rows.lock() = match hint {
+			CardinalityHint::AtMostOne => Some(1),
+			CardinalityHint::Bounded(n) => Some(n as u64),
+			CardinalityHint::Unbounded => None,
+		};
+	}
+
+	/// Snapshot the recorded statistics, along with the number of rows the
+	/// statement returned.
+	pub(crate) fn stats(&self, rows_returned: u64) -> QueryStats {
+		QueryStats {
+			rows_scanned: self.scanned.load(Ordering::Relaxed),
+			rows_returned,
+			indexes_used: self.indexes.lock().clone(),
+			table_scan: self.table_scan.load(Ordering::Relaxed),
+			records_created: self.created.load(Ordering::Relaxed),
+			records_updated: self.updated.load(Ordering::Relaxed),
+			max_rows: *self.max_rows.lock(),
+			truncated: self.truncated.load(Ordering::Relaxed),
+		}
+	}
+}
+
+/// The number of rows which a `SELECT` with a `SCAN LIMIT` clause, along
+/// with every statement nested within it, may read.
+///
+/// The rows also count against the budget of every enclosing `SELECT`, and
+/// against the limit of the statement in its [`StatementCounters`], so that a
+/// nested `SCAN LIMIT` can neither raise nor reset the limits around it.
+#[derive(Debug)]
+pub(crate) struct ScanBudget {
+	/// The number of rows which may be read
+	limit: u64,
+	/// The number of rows read so far
+	scanned: AtomicU64,
+	/// The budget of the enclosing `SELECT`, if any
+	parent: Option<Arc<ScanBudget>>,
+}
+
+impl ScanBudget {
+	pub(crate) fn new(limit: u64, parent: Option<Arc<ScanBudget>>) -> Arc<Self> {
+		Arc::new(Self {
+			limit,
+			scanned: AtomicU64::new(0),
+			parent,
+		})
+	}
+
+	/// Count the rows read by an operator against this budget and every
+	/// enclosing one, failing once any of them has been exceeded.
+	fn record(&self, count: u64, operator: &str) -> Result<()> {
+		let mut budget = Some(self);
+		while let Some(b) = budget {
+			let scanned = b.scanned.fetch_add(count, Ordering::Relaxed).saturating_add(count);
+			if scanned > b.limit {
+				bail!(Error::ScanLimitExceeded {
+					operator: operator.to_owned(),
+					limit: b.limit,
+				});
+			}
+			budget = b.parent.as_deref();
+		}
+		Ok(())
+	}
+}
+
+/// Records the rows read by a scan operator against the counters of the
+/// statement and the scan budgets of the context it runs in.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ScanRecorder {
+	pub(crate) counters: Option<Arc<StatementCounters>>,
+	pub(crate) budget: Option<Arc<ScanBudget>>,
+}
+
+impl ScanRecorder {
+	/// Count the rows read by an operator, failing once the statement, or a
+	/// `SELECT` within it, has read more rows than it may.
+	pub(crate) fn record(&self, count: u64, operator: &str) -> Result<()> {
+		if let Some(counters) = &self.counters {
+			counters.record_scanned(count, operator)?;
+		}
+		if let Some(budget) = &self.budget {
+			budget.record(count, operator)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn stats_deduplicate_indexes() {
+		let counters = StatementCounters::new();
+		counters.record_scanned(3, "TableScan").unwrap();
+		counters.record_scanned(2, "TableScan").unwrap();
+		counters.record_index("idx_a");
+		counters.record_index("idx_b");
+		counters.record_index("idx_a");
+		let stats = counters.stats(1);
+		assert_eq!(stats.rows_scanned, 5);
+		assert_eq!(stats.rows_returned, 1);
+		assert_eq!(stats.indexes_used, vec!["idx_a".to_string(), "idx_b".to_string()]);
+		assert!(!stats.table_scan);
+		counters.record_table_scan();
+		assert!(counters.stats(1).table_scan);
+		assert!(!counters.stats(1).truncated);
+		counters.record_truncated();
+		assert!(counters.stats(1).truncated);
+	}
+
+	#[test]
+	fn scans_fail_beyond_the_scan_limit() {
+		let counters = StatementCounters::new();
+		counters.set_scan_limit(5);
+		counters.record_scanned(5, "TableScan").unwrap();
+		let err = counters.record_scanned(1, "IndexScan").unwrap_err();
+		assert!(matches!(
+			err.downcast_ref::<Error>(),
+			Some(Error::ScanLimitExceeded { operator, limit: 5 }) if operator == "IndexScan"
+		));
+		// Lifting the limit lets the statement carry on scanning
+		counters.set_scan_limit(0);
+		counters.record_scanned(100, "TableScan").unwrap();
+		assert_eq!(counters.stats(0).rows_scanned, 106);
+	}
+
+	#[test]
+	fn scan_budgets_nest() {
+		let counters = StatementCounters::new();
+		counters.set_scan_limit(10);
+		let outer = ScanBudget::new(6, None);
+		// A nested budget can not raise the limit of the enclosing one
+		let inner = ScanRecorder {
+			counters: Some(Arc::clone(&counters)),
+			budget: Some(ScanBudget::new(100, Some(Arc::clone(&outer)))),
+		};
+		inner.record(6, "TableScan").unwrap();
+		let err = inner.record(1, "TableScan").unwrap_err();
+		assert!(matches!(
+			err.downcast_ref::<Error>(),
+			Some(Error::ScanLimitExceeded {
+				limit: 6,
+				..
+			})
+		));
+		// A sibling budget only counts its own rows, within the statement limit
+		let sibling = ScanRecorder {
+			counters: Some(Arc::clone(&counters)),
+			budget: Some(ScanBudget::new(2, None)),
+		};
+		sibling.record(2, "TableScan").unwrap();
+		let err = sibling.record(2, "TableScan").unwrap_err();
+		assert!(matches!(
+			err.downcast_ref::<Error>(),
+			Some(Error::ScanLimitExceeded {
+				limit: 10,
+				..
+			})
+		));
+	}
+
+	#[test]
+	fn stats_count_upserted_records() {
+		let counters = StatementCounters::new();
+		counters.record_upserted(true);
+		counters.record_upserted(false);
+		counters.record_upserted(false);
+		let stats = counters.stats(3);
+		assert_eq!(stats.records_created, 1);
+		assert_eq!(stats.records_updated, 2);
+	}
 }
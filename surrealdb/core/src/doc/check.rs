@@ -10,7 +10,7 @@ use crate::doc::compute::DocKind;
 use crate::doc::{CursorDoc, Document, Extras};
 use crate::err::Error;
 use crate::expr::paths::{ID, IN, OUT};
-use crate::expr::{Cond, FlowResultExt};
+use crate::expr::{Cond, FlowResultExt, With};
 use crate::iam::Action;
 use crate::val::{RecordId, Value};
 
@@ -30,6 +30,31 @@ impl Document {
 		Ok(())
 	}
 
+	/// Checks whether this record has been marked as
+	/// deleted on a table defined with `SOFT DELETE`.
+	/// Deleted records are skipped by SELECT and mutating
+	/// statements unless the statement was run `WITH DELETED`.
+	#[inline]
+	pub(super) fn check_soft_deleted(&self, with: Option<&With>) -> Result<(), IgnoreError> {
+		// Deleted records were explicitly requested
+		if matches!(with, Some(With::Deleted)) {
+			return Ok(());
+		}
+		// Only records of a table can be deleted
+		if self.id.is_none() {
+			return Ok(());
+		}
+		// Check the deletion marker of the table
+		if let Ok(tb) = self.doc_ctx.tb()
+			&& let Some(sd) = &tb.soft_delete
+			&& sd.is_deleted(self.current.doc.as_ref())
+		{
+			return Err(IgnoreError::Ignore);
+		}
+		// Carry on
+		Ok(())
+	}
+
 	/// Checks whether a CREATE statement is allowed on
 	/// the table for this document. When creating a
 	/// normal record, we check that the table type
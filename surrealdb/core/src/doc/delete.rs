@@ -1,10 +1,18 @@
+use std::sync::Arc;
+
+use anyhow::Result;
 use reblessive::tree::Stk;
+use surrealdb_types::ToSql;
 
 use super::IgnoreError;
 use crate::ctx::FrozenContext;
 use crate::dbs::{Options, Statement};
-use crate::doc::Document;
-use crate::val::Value;
+use crate::doc::alter::ComputedData;
+use crate::doc::{Action, CursorDoc, Document};
+use crate::err::Error;
+use crate::expr::SoftDelete;
+use crate::key::table::sd::Sd;
+use crate::val::{Datetime, Object, Value};
 
 impl Document {
 	pub(crate) async fn delete(
@@ -16,6 +24,8 @@ impl Document {
 	) -> Result<Value, IgnoreError> {
 		// Check if the record actually exists
 		self.check_record_exists()?;
+		// Skip records which have been soft deleted
+		self.check_soft_deleted(stm.with())?;
 		// SECURITY: evaluate the table-level update permission BEFORE any
 		// user-supplied expression in the WHERE clause or data clause.
 		// Otherwise a `WHERE THROW ...` could exfiltrate field values
@@ -25,6 +35,13 @@ impl Document {
 		self.check_table_not_view(opt)?;
 		// Check if the WHERE condition is truthy
 		self.check_where_condition(stk, ctx, opt, stm.cond()).await?;
+		// Keep the record if the table soft deletes records
+		if let Some(sd) = &self.doc_ctx.tb()?.soft_delete
+			&& !sd.is_deleted(self.current.doc.as_ref())
+		{
+			let sd = sd.clone();
+			return self.soft_delete(stk, ctx, opt, stm, &sd).await;
+		}
 		// Clean up any outgoing references this record holds
 		self.cleanup_table_references(stk, ctx, opt).await?;
 		// Empty the record data
@@ -41,4 +58,102 @@ impl Document {
 		// Process the projected output document
 		self.output_write(stk, ctx, opt, stm.output(), stm).await
 	}
+
+	/// Marks a record as deleted on a table defined with `SOFT DELETE`.
+	///
+	/// The marker field is set to the time of the deletion through the field
+	/// schema of the table, like the data of any other write, and the record
+	/// is reported as deleted to table views, events and live queries. The
+	/// record is removed from the indexes of the table while it is marked.
+	async fn soft_delete(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		stm: &Statement<'_>,
+		sd: &SoftDelete,
+	) -> Result<Value, IgnoreError> {
+		// Set the deletion marker as the record content
+		let marker = Object::from(map! { sd.field.clone() => Value::from(Datetime::now()) });
+		self.input_data = Some(ComputedData::Merge(Arc::new(marker.into())));
+		self.process_record_data(stk, ctx, opt).await?;
+		// Set the default record field values
+		self.default_record_data()?;
+		// Process the field schema for the table
+		self.process_table_fields(stk, ctx, opt, stm).await?;
+		// Clean up table fields and NONE values
+		self.cleanup_table_fields()?;
+		// Ensure the table schema kept the marker
+		if !sd.is_deleted(self.current.doc.as_ref()) {
+			return Err(IgnoreError::Error(anyhow::Error::new(Error::SoftDeleteMarker {
+				record: self.id()?.to_sql(),
+				field: sd.field.clone(),
+			})));
+		}
+		// Store the document and index data
+		self.store_record_data(ctx, stm).await?;
+		self.store_index_data(stk, ctx, opt).await?;
+		// Process additional table operations
+		self.process_table_views(stk, ctx, opt, super::Action::Delete).await?;
+		self.process_table_events(stk, ctx, opt, super::Action::Delete).await?;
+		self.process_table_lives(stk, ctx, opt, super::Action::Delete).await?;
+		self.process_changefeeds(ctx, opt).await?;
+		// Check table permissions for output
+		self.check_select_permissions(stk, ctx, opt, &self.initial).await?;
+		// Process the projected output document
+		self.output_write(stk, ctx, opt, stm.output(), stm).await
+	}
+
+	/// Records when a record of a table defined with `SOFT DELETE` was
+	/// deleted, so that the background purge finds the records whose
+	/// retention period has passed without scanning the table.
+	pub(super) async fn store_deletion_data(&self, ctx: &FrozenContext) -> Result<()> {
+		// Only records of soft deleted tables are tracked
+		let Some(sd) = &self.doc_ctx.tb()?.soft_delete else {
+			return Ok(());
+		};
+		let before = sd.deleted_at(self.initial.doc.as_ref());
+		let after = sd.deleted_at(self.current.doc.as_ref());
+		if before == after {
+			return Ok(());
+		}
+		// Get the record id
+		let rid = self.id()?;
+		// Get the namespace and database ids
+		let ns = self.doc_ctx.ns().namespace_id;
+		let db = self.doc_ctx.db().database_id;
+		// Move the record to its new time of deletion
+		let txn = ctx.tx();
+		if let Some(ts) = before {
+			txn.del(&Sd::new(ns, db, &rid.table, ts, rid.key.clone())).await?;
+		}
+		if let Some(ts) = after {
+			txn.set(&Sd::new(ns, db, &rid.table, ts, rid.key.clone()), &()).await?;
+		}
+		Ok(())
+	}
+
+	/// Returns the action which a write is reported as to table views,
+	/// events and live queries.
+	///
+	/// A soft deleted record is still stored, but is hidden like a deleted
+	/// record, so a write is reported by whether the record was visible
+	/// before and after it. Writes to records which were hidden before and
+	/// after, such as removing a soft deleted record permanently, are not
+	/// reported at all.
+	pub(super) fn visible_action(&self, action: Action) -> Result<Option<Action>> {
+		let Some(sd) = &self.doc_ctx.tb()?.soft_delete else {
+			return Ok(Some(action));
+		};
+		let visible = |doc: &CursorDoc| {
+			let doc = doc.doc.as_ref();
+			!doc.is_nullish() && !sd.is_deleted(doc)
+		};
+		Ok(match (visible(&self.initial), visible(&self.current)) {
+			(false, false) => None,
+			(false, true) => Some(Action::Create),
+			(true, false) => Some(Action::Delete),
+			(true, true) => Some(action),
+		})
+	}
 }
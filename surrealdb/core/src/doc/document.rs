@@ -53,12 +53,19 @@ pub(crate) struct Document {
 	/// calls suppressed by `!self.changed()`, so the counter never
 	/// inflates from rows that were filtered or unchanged.
 	pub(crate) mutated: bool,
+	/// Whether an `UPSERT` created this record rather than updating an
+	/// existing one. Consumed alongside [`Self::mutated`] by the iterator to
+	/// report the created and updated record counts of the statement.
+	pub(crate) created: bool,
 	/// Memoized result of [`Self::is_modified`]. Populated on first
 	/// access, after all mutation phases have run, and reused by the
 	/// post-mutation gates (`process_table_views` / `process_table_events`
 	/// / `process_table_lives` / `process_changefeeds` / `store_record_data`
 	/// / `store_index_data`) so each `Document` deep-compares at most once.
 	pub(super) modified: OnceCell<bool>,
+	/// The top-level fields which were written by the `DEFAULT` or `VALUE`
+	/// clause of a field definition, recorded in the table lineage.
+	pub(super) clause_fields: Vec<String>,
 }
 
 /// Carries additional context needed by the Document
@@ -708,7 +715,9 @@ impl Document {
 			record_strategy: pro.record_strategy,
 			input_data: None,
 			mutated: false,
+			created: false,
 			modified: OnceCell::new(),
+			clause_fields: Vec::new(),
 		}
 	}
 
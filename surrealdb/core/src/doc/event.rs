@@ -2,21 +2,28 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
 use reblessive::TreeStack;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use surrealdb_strand::Strand;
+use surrealdb_types::ToSql;
 #[cfg(not(target_family = "wasm"))]
 use tokio::spawn;
 
-use crate::catalog::providers::{DatabaseProvider, NamespaceProvider};
-use crate::catalog::{EventDefinition, Record};
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{DatabaseId, EventDefinition, EventKind, NamespaceId, Record};
+use crate::cnf::{EVENT_RETRY_BACKOFF, EVENT_RETRY_BACKOFF_MAX};
 use crate::ctx::{Context, FrozenContext};
 use crate::dbs::{Options, Session};
-use crate::doc::{Action, CursorDoc, Document, DocumentContext};
+use crate::doc::{
+	Action, CursorDoc, Document, DocumentContext, OutboxDeadLetterRecord, OutboxEvent,
+};
 use crate::err::Error;
 use crate::expr::FlowResultExt as _;
 use crate::iam::{Auth, AuthLimit};
+use crate::key::root::eb::EventBackoff;
+use crate::key::root::ed::EventDeadLetter;
 use crate::key::root::eq::EventQueue;
 use crate::kvs::TransactionType::Write;
 use crate::kvs::sequences::Sequences;
@@ -25,7 +32,7 @@ use crate::kvs::{
 	Datastore, HlcTimeStamp, KVValue, Key, LockType, NORMAL_BATCH_SIZE, Transaction,
 	TransactionFactory, TransactionType, Val, impl_kv_value_revisioned,
 };
-use crate::val::{RecordId, Value};
+use crate::val::{RecordId, TableName, Value};
 
 impl Document {
 	/// Processes any DEFINE EVENT clauses which
@@ -48,6 +55,10 @@ impl Document {
 		if !self.is_modified() {
 			return Ok(());
 		}
+		// Skip changes to records which are hidden by a soft delete
+		let Some(action) = self.visible_action(action)? else {
+			return Ok(());
+		};
 		// Don't run permissions
 		let opt = &opt.new_with_perms(false);
 
@@ -105,6 +116,24 @@ impl Document {
 			ctx.add_value("event", evt.into());
 			ctx.add_value("value", doc.doc.as_arc());
 			ctx.add_value("input", input.clone().unwrap_or_default());
+			ctx.set_lineage_event(Some(ev.name.as_str().into()));
+			// Record the HTTP requests of an outbox event in this transaction
+			ctx.set_outbox(match ev.kind {
+				EventKind::Outbox {
+					retry,
+				} => {
+					let db = self.doc_ctx.db();
+					Some(Arc::new(OutboxEvent {
+						ns: db.namespace_id,
+						db: db.database_id,
+						tb: ev.target_table.clone(),
+						ev: ev.name.to_string(),
+						rid: doc.rid.clone(),
+						retry,
+					}))
+				}
+				_ => None,
+			});
 			// Freeze the context
 			let ctx = ctx.freeze();
 			// Process conditional clause
@@ -174,6 +203,14 @@ impl Document {
 	}
 }
 
+/// Returns how long to wait, in milliseconds, before the given attempt to
+/// run a failed async event or send a failed outbox request, doubling the
+/// delay with every attempt up to the configured maximum.
+pub(super) fn retry_backoff(attempt: u16) -> u64 {
+	let shift = u32::from(attempt.saturating_sub(1)).min(63);
+	EVENT_RETRY_BACKOFF.saturating_mul(1 << shift).min(*EVENT_RETRY_BACKOFF_MAX)
+}
+
 /// Persisted payload for processing DEFINE EVENT ... ASYNC.
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug)]
@@ -208,6 +245,161 @@ pub struct AsyncEventRecord {
 
 impl_kv_value_revisioned!(AsyncEventRecord);
 
+/// An async event which failed on every attempt, kept for inspection and replay.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+pub(crate) struct DeadLetterRecord {
+	/// The error returned by the last attempt.
+	error: String,
+	/// Milliseconds since the unix epoch when the event was dead-lettered.
+	failed_at: i64,
+	/// The queued event, including the number of attempts made.
+	event: AsyncEventRecord,
+}
+
+impl_kv_value_revisioned!(DeadLetterRecord);
+
+/// An async event which could not be processed.
+///
+/// Returned by [`Datastore::event_dead_letters`]. Events end up here once every
+/// attempt allowed by the `RETRY` clause of the event has failed, or when they
+/// fail with an error which can not be retried.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DeadLetter {
+	/// The table the event is defined on
+	pub table: String,
+	/// The name of the event
+	pub event: String,
+	/// The record which triggered the event, if any
+	pub record: Option<String>,
+	/// How many times the event was run
+	pub attempts: u16,
+	/// The error returned by the last attempt
+	pub error: String,
+	/// When the event was moved to the dead-letter queue
+	pub failed_at: DateTime<Utc>,
+	/// The HTTP request which could not be sent, for events defined with
+	/// `OUTBOX`
+	pub request: Option<String>,
+}
+
+impl DeadLetterRecord {
+	/// Moves a queued event to the dead-letter queue, within the given transaction.
+	async fn capture(
+		tx: &Transaction,
+		eq: &EventQueue<'_>,
+		event: &AsyncEventRecord,
+		error: String,
+	) -> Result<()> {
+		let record = Self {
+			error,
+			failed_at: Utc::now().timestamp_millis(),
+			event: event.clone(),
+		};
+		tx.set(&EventDeadLetter::from_queue(eq), &record).await?;
+		tx.del(eq).await
+	}
+
+	/// Lists the dead-lettered events of a database.
+	pub(crate) async fn list(
+		tx: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Vec<DeadLetter>> {
+		let mut out = Vec::new();
+		for (k, v) in tx.getr(EventDeadLetter::range(ns, db)?, None).await? {
+			let key = EventDeadLetter::decode_key(&k)?;
+			let val = Self::kv_decode_value(&v, ())?;
+			out.push(DeadLetter {
+				table: key.tb.to_string(),
+				event: key.ev.to_string(),
+				record: val.event.rid.as_ref().map(|rid| rid.to_sql()),
+				// `attempt` already counts the run which failed last
+				attempts: val.event.attempt,
+				error: val.error,
+				failed_at: DateTime::from_timestamp_millis(val.failed_at).unwrap_or_default(),
+				request: None,
+			});
+		}
+		out.extend(OutboxDeadLetterRecord::list(tx, ns, db).await?);
+		Ok(out)
+	}
+
+	/// Moves the dead-lettered events of a database back onto the event queue,
+	/// optionally only those of a single table, and returns how many were moved.
+	///
+	/// Replayed events run against the current definition of the event, so that
+	/// a fixed event handler can process the events it previously failed. Events
+	/// which have since been removed are left in the dead-letter queue.
+	pub(crate) async fn replay(
+		tx: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		table: Option<&str>,
+	) -> Result<usize> {
+		let mut count = 0;
+		for (k, v) in tx.getr(EventDeadLetter::range(ns, db)?, None).await? {
+			let key = EventDeadLetter::decode_key(&k)?;
+			if table.is_some_and(|tb| tb != key.tb.as_str()) {
+				continue;
+			}
+			let Ok(definition) = tx.get_tb_event(ns, db, &key.tb, &key.ev, None).await else {
+				continue;
+			};
+			let mut event = Self::kv_decode_value(&v, ())?.event;
+			event.attempt = 0;
+			event.event_definition = definition.as_ref().clone();
+			let tb: &TableName = &key.tb;
+			let eq = EventQueue::new(ns, db, tb, &key.ev, HlcTimeStamp(key.ts), key.node_id);
+			tx.set(&eq, &event).await?;
+			tx.del(&key).await?;
+			count += 1;
+		}
+		if count > 0 {
+			tx.trigger_async_event();
+		}
+		Ok(count + OutboxDeadLetterRecord::replay(tx, ns, db, table).await?)
+	}
+
+	/// Removes the dead-lettered events of a database which failed before the
+	/// given time, in milliseconds since the unix epoch, along with the
+	/// dead-lettered requests of outbox events.
+	pub(crate) async fn expire(
+		tx: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		before: i64,
+	) -> Result<()> {
+		let mut next = Some(EventDeadLetter::range(ns, db)?);
+		while let Some(rng) = next {
+			let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				if Self::kv_decode_value(v, ())?.failed_at < before {
+					tx.clr(k).await?;
+				}
+			}
+		}
+		OutboxDeadLetterRecord::expire(tx, ns, db, before).await
+	}
+
+	/// Removes the dead-lettered events of a removed database
+	pub(crate) async fn database_removed(
+		tx: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<()> {
+		tx.delr(EventDeadLetter::range(ns, db)?).await
+	}
+
+	/// Removes the dead-lettered events of every database of a removed
+	/// namespace
+	pub(crate) async fn namespace_removed(tx: &Transaction, ns: NamespaceId) -> Result<()> {
+		tx.delr(EventDeadLetter::range_ns(ns)?).await
+	}
+}
+
 impl AsyncEventRecord {
 	/// Build a queued event payload from the current cursor document and context.
 	fn new(
@@ -245,6 +437,7 @@ impl AsyncEventRecord {
 		let mut ctx = Context::new_child(ctx);
 		ctx.add_values(self.values.clone());
 		ctx.auth_enabled = self.auth_enabled;
+		ctx.set_lineage_event(Some(self.event_definition.name.as_str().into()));
 		ctx.freeze()
 	}
 
@@ -292,6 +485,7 @@ impl AsyncEventRecord {
 
 	/// Process a single batch of queued async events.
 	/// Returns the number of events fetched (not necessarily successfully processed).
+	/// Events waiting for their retry backoff to elapse are not counted.
 	pub async fn process_next_events_batch(
 		ds: &Datastore,
 		lh: Option<&LeaseHandler>,
@@ -301,6 +495,8 @@ impl AsyncEventRecord {
 			if let Some(lh) = lh.as_ref() {
 				lh.try_maintain_lease().await?;
 			}
+			// Move the events whose retry backoff has elapsed back onto the queue
+			Self::requeue_due_events(ds).await?;
 			let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
 			let (beg, end) = EventQueue::range();
 			// Read a bounded batch without holding a write transaction.
@@ -313,6 +509,35 @@ impl AsyncEventRecord {
 		Ok(count)
 	}
 
+	/// Moves a batch of the events whose retry backoff has elapsed back onto
+	/// the event queue, keeping their original queue position.
+	async fn requeue_due_events(ds: &Datastore) -> Result<()> {
+		let now = u64::try_from(Utc::now().timestamp_millis()).unwrap_or_default();
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		let res = catch!(tx, tx.scan(EventBackoff::due(now), NORMAL_BATCH_SIZE, 0, None).await);
+		if res.is_empty() {
+			return tx.cancel().await;
+		}
+		for (k, v) in res.iter() {
+			let key = catch!(tx, EventBackoff::decode_key(k));
+			let ev = catch!(tx, Self::kv_decode_value(v, ()));
+			catch!(tx, tx.set(&key.to_queue(), &ev).await);
+			catch!(tx, tx.del(&key).await);
+		}
+		tx.commit().await
+	}
+
+	/// Returns whether any async event is waiting to be processed, including
+	/// the events which are backing off after a failure.
+	pub async fn has_pending_events(ds: &Datastore) -> Result<bool> {
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let (beg, end) = EventQueue::range();
+		let queued = catch!(tx, tx.scan(beg..end, 1, 0, None).await);
+		let backoff = catch!(tx, tx.scan(EventBackoff::range(), 1, 0, None).await);
+		tx.cancel().await?;
+		Ok(!queued.is_empty() || !backoff.is_empty())
+	}
+
 	#[cfg(not(target_family = "wasm"))]
 	async fn process_events_batch(
 		ds: &Datastore,
@@ -454,7 +679,7 @@ impl AsyncEventContext {
 				tx.cancel().await?;
 				if let Some(final_error) = Self::is_final_error(&e).await? {
 					let tx = self.new_write_tx().await?;
-					return Self::final_error(tx, &eq, final_error).await;
+					return Self::final_error(tx, &eq, &ev, final_error).await;
 				}
 				let tx = self.new_write_tx().await?;
 				Self::retry_attempt(tx, e, &eq, &mut ev).await
@@ -473,15 +698,19 @@ impl AsyncEventContext {
 		// attempt <= retry.
 		ev.attempt += 1;
 		if ev.attempt <= ev.event_definition.retry() {
-			// Requeue with the same key so the event keeps its original queue position; retries are
-			// bounded here and no backoff is applied.
-			catch!(tx, tx.set(eq, ev).await);
+			// Move the event out of the queue until its backoff has elapsed; it is then requeued
+			// with the same key so that it keeps its original queue position.
+			let now = u64::try_from(Utc::now().timestamp_millis()).unwrap_or_default();
+			let not_before = now.saturating_add(retry_backoff(ev.attempt));
+			catch!(tx, tx.del(eq).await);
+			catch!(tx, tx.set(&EventBackoff::from_queue(not_before, eq), ev).await);
 		} else {
 			warn!(
 				"Final error after processing the event `{}` on table {} {} times: {e}",
 				eq.ev, ev.event_definition.target_table, ev.attempt
 			);
-			catch!(tx, tx.del(eq).await);
+			// Keep the event for inspection and replay
+			catch!(tx, DeadLetterRecord::capture(&tx, eq, ev, e.to_string()).await);
 		}
 		catch!(tx, tx.commit().await);
 		Ok(())
@@ -502,10 +731,24 @@ impl AsyncEventContext {
 		}
 	}
 
-	async fn final_error(tx: Transaction, eq: &EventQueue<'_>, e: &Error) -> Result<()> {
+	async fn final_error(
+		tx: Transaction,
+		eq: &EventQueue<'_>,
+		ev: &AsyncEventRecord,
+		e: &Error,
+	) -> Result<()> {
 		// The error is final, we log the final error message and remove the event from the queue
 		warn!("Event processing failed: {:?}", e);
-		catch!(tx, tx.del(eq).await);
+		if let Error::EvReachMaxDepth(..) = e {
+			// The event could succeed once its definition is changed, so keep it for replay,
+			// counting the run which failed
+			let mut ev = ev.clone();
+			ev.attempt = ev.attempt.saturating_add(1);
+			catch!(tx, DeadLetterRecord::capture(&tx, eq, &ev, e.to_string()).await);
+		} else {
+			// The namespace or database of the event no longer exists
+			catch!(tx, tx.del(eq).await);
+		}
 		catch!(tx, tx.commit().await);
 		// Carry on
 		Ok(())
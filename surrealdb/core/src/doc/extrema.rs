@@ -0,0 +1,131 @@
+//! The values behind the minimums and maximums of aggregated views.
+//!
+//! The counts, sums and means of an aggregated view are kept up to date from
+//! each change alone, but a minimum or maximum is not: once the records
+//! holding it change, the next one has to be found. Every aggregated view
+//! which is defined or refreshed therefore counts, for each group and each
+//! argument of a minimum or maximum, the records of the group with each value
+//! of the argument, ordered by value. The next minimum or maximum is then the
+//! first or last of these values, rather than the result of a query over the
+//! records of the group.
+//!
+//! The counts of a view defined before they were kept, or of a view which was
+//! skipped by writes while importing, are incomplete. Such a view falls back
+//! to the query until it is computed again with `REFRESH VIEW`.
+
+use anyhow::Result;
+
+use crate::catalog::aggregation::AggregationAnalysis;
+use crate::catalog::{DatabaseId, NamespaceId, ViewValueCount};
+use crate::key::table::va::Va;
+use crate::kvs::{KVValue, Transaction};
+use crate::val::{Array, TableName, Value};
+
+/// The counted values of the arguments of a group of an aggregated view
+pub(crate) struct ViewExtrema<'a> {
+	ns: NamespaceId,
+	db: DatabaseId,
+	view: &'a TableName,
+	group: Array,
+	/// The arguments of which a minimum or maximum is aggregated
+	args: Vec<usize>,
+}
+
+impl<'a> ViewExtrema<'a> {
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		view: &'a TableName,
+		group: &[Value],
+		aggr: &AggregationAnalysis,
+	) -> Self {
+		Self {
+			ns,
+			db,
+			view,
+			group: Array(group.to_vec()),
+			args: aggr.extremum_arguments(),
+		}
+	}
+
+	/// Returns the counted values of a group, if the view aggregates a
+	/// minimum or maximum and its counts are complete
+	pub(crate) async fn load(
+		tx: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		view: &'a TableName,
+		group: &[Value],
+		aggr: &AggregationAnalysis,
+	) -> Result<Option<Self>> {
+		let extrema = Self::new(ns, db, view, group, aggr);
+		if extrema.args.is_empty() {
+			return Ok(None);
+		}
+		let key = crate::key::table::vs::new(ns, db, view);
+		match tx.get(&key, None).await? {
+			Some(state) if state.stale_since.is_none() => Ok(Some(extrema)),
+			_ => Ok(None),
+		}
+	}
+
+	/// Counts the argument values of a record added to the group
+	pub(crate) async fn add(&self, tx: &Transaction, values: &[Value]) -> Result<()> {
+		for &arg in &self.args {
+			let value = Array(vec![values[arg].clone()]);
+			let key = Va::new(self.ns, self.db, self.view, arg as u32, &self.group, &value);
+			let entry = match tx.get(&key, None).await? {
+				Some(mut entry) => {
+					entry.count += 1;
+					entry
+				}
+				None => ViewValueCount {
+					value: values[arg].clone(),
+					count: 1,
+				},
+			};
+			tx.set(&key, &entry).await?;
+		}
+		Ok(())
+	}
+
+	/// Uncounts the argument values of a record removed from the group
+	pub(crate) async fn remove(&self, tx: &Transaction, values: &[Value]) -> Result<()> {
+		for &arg in &self.args {
+			let value = Array(vec![values[arg].clone()]);
+			let key = Va::new(self.ns, self.db, self.view, arg as u32, &self.group, &value);
+			match tx.get(&key, None).await? {
+				Some(mut entry) if entry.count > 1 => {
+					entry.count -= 1;
+					tx.set(&key, &entry).await?;
+				}
+				Some(_) => tx.del(&key).await?,
+				None => {}
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the smallest or largest counted value of an argument
+	pub(crate) async fn extremum(&self, tx: &Transaction, arg: usize, max: bool) -> Result<Value> {
+		let rng = Va::group_range(self.ns, self.db, self.view, arg as u32, &self.group)?;
+		let found = if max {
+			tx.scanr(rng, 1, 0, None).await?
+		} else {
+			tx.scan(rng, 1, 0, None).await?
+		};
+		match found.first() {
+			Some((_, bytes)) => Ok(ViewValueCount::kv_decode_value(bytes, ())?.value),
+			None => Ok(Value::None),
+		}
+	}
+
+	/// Removes the counts of the group, once its last record is removed
+	pub(crate) async fn clear(&self, tx: &Transaction) -> Result<()> {
+		for &arg in &self.args {
+			let rng = Va::group_range(self.ns, self.db, self.view, arg as u32, &self.group)?;
+			tx.delr(rng).await?;
+		}
+		Ok(())
+	}
+}
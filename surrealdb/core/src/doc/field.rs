@@ -7,8 +7,9 @@ use surrealdb_types::ToSql;
 
 use crate::catalog::{self, FieldDefinition};
 use crate::ctx::{Context, FrozenContext};
+use crate::dbs::capabilities::ExperimentalTarget;
 use crate::dbs::{Options, Statement};
-use crate::doc::Document;
+use crate::doc::{Document, lineage};
 use crate::err::Error;
 use crate::expr::FlowResultExt as _;
 use crate::expr::data::Data;
@@ -215,6 +216,8 @@ impl Document {
 		let rid = self.id()?;
 		// Get the user applied input
 		let inp = self.compute_input_value(stk, ctx, opt, stm).await?.unwrap_or_default();
+		// Check if the table records field lineage
+		let track_lineage = self.doc_ctx.tb()?.lineage;
 		// When set, any matching embedded object fields
 		// which are prefixed with the specified idiom
 		// will be skipped, as the parent object is optional
@@ -295,6 +298,10 @@ impl Document {
 					// without needing to process the field in any other way.
 					continue;
 				}
+				// Keep the value before any clauses are processed, so that
+				// a change made by a DEFAULT or VALUE clause can be recorded
+				// in the table lineage
+				let given = (track_lineage && !skipped).then(|| val.clone());
 				// Generate the field context
 				let mut field = FieldEditContext {
 					context: None,
@@ -349,6 +356,16 @@ impl Document {
 					if val.is_none() && fd.field_kind.as_ref().is_some_and(Kind::can_be_none) {
 						skip = Some(&fd.name);
 					}
+					// Record a change made by a DEFAULT or VALUE clause
+					if let Some(given) = given
+						&& fd.computed.is_none()
+						&& (fd.value.is_some()
+							|| !matches!(fd.default, catalog::DefineDefault::None))
+						&& given != val && let Some(name) = lineage::top_level_field(&fd.name)
+						&& !self.clause_fields.iter().any(|f| f == name)
+					{
+						self.clause_fields.push(name.to_owned());
+					}
 					// Write the processed value back. `put` on a NONE
 					// preserves array element positions; `cut` would
 					// shrink the array, dropping nullable items the
@@ -513,6 +530,8 @@ impl FieldEditContext<'_> {
 			if self.def.name.is_id() {
 				return Ok(val);
 			}
+			// Canonicalize the value with any type adapter for the field type
+			let val = self.process_type_adapter(kind, val)?;
 			// Check the type of the field value
 			let val = val.coerce_to_kind(kind).map_err(|e| Error::FieldCoerce {
 				record: self.rid.to_sql(),
@@ -526,6 +545,26 @@ impl FieldEditContext<'_> {
 		Ok(val)
 	}
 
+	/// Process any type adapter registered for the declared field type
+	fn process_type_adapter(&self, kind: &Kind, val: Value) -> Result<Value> {
+		// Check if any type adapters are registered
+		let Some(adapters) = self.ctx.get_type_adapters().filter(|a| !a.is_empty()) else {
+			return Ok(val);
+		};
+		// Check if type adapters are enabled
+		if !self.ctx.get_capabilities().allows_experimental(&ExperimentalTarget::TypeAdapters) {
+			return Ok(val);
+		}
+		// Adapt the field value
+		adapters.adapt(kind, val).map_err(|message| {
+			anyhow::Error::new(Error::FieldAdapt {
+				record: self.rid.to_sql(),
+				field_name: self.def.name.to_sql(),
+				message,
+			})
+		})
+	}
+
 	/// Process any DEFAULT clause for the field definition
 	async fn process_default_clause(&mut self, val: Value) -> Result<Value> {
 		// This field has a value specified
@@ -50,6 +50,11 @@ impl Document {
 		}
 		// Get the record id
 		let rid = self.id()?;
+		// Soft deleted records are left out of the indexes
+		let indexed = |doc: &CursorDoc| {
+			!tb.soft_delete.as_ref().is_some_and(|sd| sd.is_deleted(doc.doc.as_ref()))
+		};
+		let (initial, current) = (indexed(&self.initial), indexed(&self.current));
 		// Loop through all index statements
 		for ix in ixs.iter() {
 			// Decommissioned indexes are ignored
@@ -57,21 +62,31 @@ impl Document {
 				continue;
 			}
 			// Calculate old values
-			let o = Self::build_opt_values(stk, ctx, opt, ix, &self.initial).await?;
+			let o = if initial {
+				Self::build_opt_values(stk, ctx, opt, ix, &self.initial).await?
+			} else {
+				None
+			};
 			// Calculate new values
-			let n = Self::build_opt_values(stk, ctx, opt, ix, &self.current).await?;
+			let n = if current {
+				Self::build_opt_values(stk, ctx, opt, ix, &self.current).await?
+			} else {
+				None
+			};
 			// For COUNT indexes with a condition, evaluate against the full document
 			let count_cond_match = if let Index::Count(Some(cond)) = &ix.index {
-				let old_matches = stk
-					.run(|stk| cond.0.compute(stk, ctx, opt, Some(&self.initial)))
-					.await
-					.catch_return()?
-					.is_truthy();
-				let new_matches = stk
-					.run(|stk| cond.0.compute(stk, ctx, opt, Some(&self.current)))
-					.await
-					.catch_return()?
-					.is_truthy();
+				let old_matches = initial
+					&& stk
+						.run(|stk| cond.0.compute(stk, ctx, opt, Some(&self.initial)))
+						.await
+						.catch_return()?
+						.is_truthy();
+				let new_matches = current
+					&& stk
+						.run(|stk| cond.0.compute(stk, ctx, opt, Some(&self.current)))
+						.await
+						.catch_return()?
+						.is_truthy();
 				Some((old_matches, new_matches))
 			} else {
 				None
@@ -143,6 +158,8 @@ impl Document {
 		let mut require_compaction = false;
 		// Execute the index operation
 		ic.compute(stk, &mut require_compaction).await?;
+		// Record the write in the index usage statistics
+		ctx.get_index_stores().usage().record_write(db.namespace_id, db.database_id, ix);
 		// Did any compaction request have to be triggered?
 		if require_compaction {
 			ic.trigger_compaction().await?;
@@ -154,6 +154,8 @@ impl Document {
 	) -> Result<Value, IgnoreError> {
 		// Ensure the record actually exists
 		self.check_record_exists()?;
+		// Skip records which have been soft deleted
+		self.check_soft_deleted(stm.with())?;
 		// Ensure we can store this type of record
 		self.check_table_type_insert()?;
 		// SECURITY: evaluate the table-level update permission BEFORE any
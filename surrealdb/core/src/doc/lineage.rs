@@ -0,0 +1,163 @@
+//! Field-level lineage for tables defined with `LINEAGE`.
+//!
+//! When a record in a lineage-enabled table is written, the top-level fields
+//! which changed are attributed to whatever wrote them: the `DEFAULT` or
+//! `VALUE` clause of a field definition, the `THEN` clause of an event, or
+//! otherwise the statement itself. The attribution is stored alongside the
+//! record, in a compact sidecar keyed by the record id, and can be queried
+//! with the `meta::lineage(record, field)` function.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use revision::revisioned;
+
+use crate::ctx::FrozenContext;
+use crate::dbs::Statement;
+use crate::doc::Document;
+use crate::expr::{Idiom, Part};
+use crate::kvs::impl_kv_value_revisioned;
+use crate::val::{Datetime, Object, Value};
+
+/// The last writer of each top-level field of a record
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Lineage {
+	fields: BTreeMap<String, LineageEntry>,
+}
+
+impl_kv_value_revisioned!(Lineage);
+
+/// The last write of a single field
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LineageEntry {
+	/// What computed the written value
+	source: LineageSource,
+	/// The kind of statement which wrote the record
+	statement: String,
+	/// When the field was written
+	at: Datetime,
+}
+
+/// What computed the value written to a field
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum LineageSource {
+	/// The value was given by the statement
+	Statement,
+	/// The value was written by the `THEN` clause of the named event
+	Event(String),
+	/// The value was computed by the `DEFAULT` or `VALUE` clause of the named
+	/// field
+	Field(String),
+}
+
+impl Lineage {
+	/// Returns the last write of the given top-level field, if any
+	pub(crate) fn get(&self, field: &str) -> Option<&LineageEntry> {
+		self.fields.get(field)
+	}
+}
+
+impl LineageEntry {
+	/// Converts the lineage entry into an object value
+	pub(crate) fn to_value(&self) -> Value {
+		let mut obj = BTreeMap::new();
+		let (source, name) = match &self.source {
+			LineageSource::Statement => ("statement", None),
+			LineageSource::Event(name) => ("event", Some(name)),
+			LineageSource::Field(name) => ("field", Some(name)),
+		};
+		obj.insert("source", Value::from(source));
+		if let Some(name) = name {
+			obj.insert("name", Value::from(name.as_str()));
+		}
+		obj.insert("statement", Value::from(self.statement.as_str()));
+		obj.insert("at", Value::Datetime(self.at.clone()));
+		Value::from(obj)
+	}
+}
+
+/// Returns the name of the top-level field of an idiom, if it starts with one
+pub(super) fn top_level_field(idiom: &Idiom) -> Option<&str> {
+	match idiom.0.first() {
+		Some(Part::Field(name)) => Some(name.as_str()),
+		_ => None,
+	}
+}
+
+impl Document {
+	/// Records which writer last changed each top-level field of the record,
+	/// if the table was defined with `LINEAGE`
+	pub(super) async fn store_lineage(
+		&self,
+		ctx: &FrozenContext,
+		stm: &Statement<'_>,
+	) -> Result<()> {
+		// Get the document table
+		let tb = self.doc_ctx.tb()?;
+		// Check if lineage is enabled
+		if !tb.lineage {
+			return Ok(());
+		}
+		// Find the top-level fields which changed
+		let empty = Object::default();
+		let initial = match self.initial.doc.as_ref() {
+			Value::Object(v) => v,
+			_ => &empty,
+		};
+		let current = match self.current.doc.as_ref() {
+			Value::Object(v) => v,
+			_ => &empty,
+		};
+		let changed: Vec<&str> = current
+			.iter()
+			.filter(|(k, v)| initial.get(k.as_str()) != Some(*v))
+			.chain(initial.iter().filter(|(k, _)| current.get(k.as_str()).is_none()))
+			.map(|(k, _)| k.as_str())
+			.filter(|k| *k != "id")
+			.collect();
+		if changed.is_empty() {
+			return Ok(());
+		}
+		// Merge the writes with the existing lineage
+		let rid = self.id()?;
+		let key = crate::key::table::ln::new(tb.namespace_id, tb.database_id, &rid.table, &rid.key);
+		let txn = ctx.tx();
+		let mut lineage = txn.get(&key, None).await?.unwrap_or_default();
+		let at = Datetime::now();
+		for field in changed {
+			let source = if self.clause_fields.iter().any(|f| f == field) {
+				LineageSource::Field(field.to_owned())
+			} else if let Some(event) = ctx.lineage_event() {
+				LineageSource::Event(event.to_owned())
+			} else {
+				LineageSource::Statement
+			};
+			lineage.fields.insert(
+				field.to_owned(),
+				LineageEntry {
+					source,
+					statement: stm.keyword().to_owned(),
+					at: at.clone(),
+				},
+			);
+		}
+		txn.set(&key, &lineage).await
+	}
+
+	/// Deletes the lineage of a deleted record, if the table was defined with
+	/// `LINEAGE`
+	pub(super) async fn purge_lineage(&self, ctx: &FrozenContext) -> Result<()> {
+		// Get the document table
+		let tb = self.doc_ctx.tb()?;
+		// Check if lineage is enabled
+		if !tb.lineage {
+			return Ok(());
+		}
+		let rid = self.id()?;
+		let key = crate::key::table::ln::new(tb.namespace_id, tb.database_id, &rid.table, &rid.key);
+		ctx.tx().del(&key).await
+	}
+}
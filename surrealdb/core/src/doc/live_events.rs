@@ -6,7 +6,7 @@ use tokio::sync::OnceCell;
 
 use super::document::Extras;
 use crate::cnf::LiveQueryEngine;
-use crate::ctx::FrozenContext;
+use crate::ctx::{Context, FrozenContext};
 use crate::dbs::Options;
 use crate::doc::{Action, CursorDoc, Document, DocumentContext};
 use crate::idx::planner::RecordStrategy;
@@ -65,6 +65,7 @@ impl Document {
 				id.as_ref(),
 				self.initial.doc.clone(),
 				self.current.doc.clone(),
+				ctx.is_eviction(),
 			);
 		}
 		Ok(())
@@ -104,7 +105,15 @@ impl Document {
 		let action = match event.action {
 			LiveAction::Create => Action::Create,
 			LiveAction::Update => Action::Update,
-			LiveAction::Delete => Action::Delete,
+			LiveAction::Delete | LiveAction::Evict => Action::Delete,
+		};
+		// Evictions are replayed in a context which notifies them as such
+		let ctx = if event.action == LiveAction::Evict {
+			let mut ctx = Context::new_child(ctx);
+			ctx.set_eviction(true);
+			ctx.freeze()
+		} else {
+			ctx.clone()
 		};
 		// Reconstruct the document with the captured before/after values. The id
 		// is shared by both cursors, mirroring how `Document::new` clones the
@@ -127,12 +136,14 @@ impl Document {
 			record_strategy: RecordStrategy::KeysAndValues,
 			input_data: None,
 			mutated: false,
+			created: false,
 			modified: OnceCell::new(),
+			clause_fields: Vec::new(),
 		};
 		// Run the identical matching/permission/projection/FETCH pipeline the
 		// inline write path uses — but via the inner entry, bypassing the
 		// write-path engine gate (this *is* the off-path Router delivery).
-		doc.process_table_lives_inner(stk, ctx, opt, action).await
+		doc.process_table_lives_inner(stk, &ctx, opt, action).await
 	}
 }
 
@@ -1,4 +1,6 @@
-use std::collections::BTreeSet;
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::pin::Pin;
 use std::sync::Arc;
 
@@ -11,14 +13,15 @@ use reblessive::tree::Stk;
 use tracing::instrument;
 
 use super::IgnoreError;
-use crate::catalog::{Permission, SubscriptionDefinition, SubscriptionFields};
+use crate::catalog::{Permission, SubscriptionDefinition, SubscriptionFields, reads_session};
 use crate::cnf::LiveQueryEngine;
 use crate::ctx::{Context, FrozenContext};
 use crate::dbs::{MessageBroker, Options, RoutedNotification};
 use crate::doc::{Action, CursorDoc, Document};
 use crate::err::Error;
-use crate::expr::FlowResultExt as _;
 use crate::expr::paths::{AC, ID, RD, TK};
+use crate::expr::{Expr, Fetchs, FlowResultExt as _};
+use crate::iam::Auth;
 use crate::kvs::Transaction;
 use crate::types::{PublicAction, PublicNotification};
 use crate::val::{Value, convert_value_to_public_value};
@@ -46,13 +49,18 @@ impl Document {
 		if ctx.config.live_query_engine == LiveQueryEngine::Router {
 			return Ok(());
 		}
+		// Skip changes to records which are hidden by a soft delete
+		let Some(action) = self.visible_action(action)? else {
+			return Ok(());
+		};
 		self.process_table_lives_inner(stk, ctx, opt, action).await
 	}
 
 	/// The live-query delivery pipeline, shared by the inline write path (via
 	/// [`Self::process_table_lives`], after its engine gate) and the off-path
 	/// router replay (via [`Document::replay_live_event`]). Loads the table's
-	/// subscriptions and computes/sends a notification per matching subscription.
+	/// subscriptions, groups those which would compute the same notification,
+	/// and computes/sends a notification once per matching group.
 	pub(super) async fn process_table_lives_inner(
 		&mut self,
 		_stk: &mut Stk,
@@ -99,9 +107,25 @@ impl Document {
 		// Move self to a shared reference
 		let doc: &Self = &*self;
 
-		let mut tasks = Vec::with_capacity(live_subscriptions.len());
-		// Loop through all index statements
+		// Group the subscriptions which compute the same notification, so that
+		// popular live queries are evaluated once per change, rather than once
+		// per subscriber
+		let table_reads_session = doc.table_reads_session()?;
+		let mut groups: Vec<Vec<&SubscriptionDefinition>> = Vec::new();
+		let mut shared = HashMap::new();
 		for live_subscription in live_subscriptions.iter() {
+			match shared.entry(SharedEvaluation::new(live_subscription, table_reads_session)) {
+				Entry::Occupied(entry) => groups[*entry.get()].push(live_subscription),
+				Entry::Vacant(entry) => {
+					entry.insert(groups.len());
+					groups.push(vec![live_subscription]);
+				}
+			}
+		}
+
+		let mut tasks = Vec::with_capacity(groups.len());
+		// Loop through all groups of subscriptions
+		for subscribers in groups {
 			// We need to create a new options which we will
 			// use for processing this LIVE query statement.
 			// This ensures that we are using the auth data
@@ -116,7 +140,7 @@ impl Document {
 						doc.lq_compute(
 							stk,
 							ctx,
-							live_subscription.clone(),
+							subscribers,
 							lqopt,
 							ctx.tx(),
 							(met, initial, current),
@@ -133,6 +157,31 @@ impl Document {
 		Ok(())
 	}
 
+	/// Checks whether the select permissions or computed fields of the table
+	/// may read the session they are evaluated in
+	fn table_reads_session(&self) -> Result<bool> {
+		let reads = |permission: &Permission| match permission {
+			Permission::Specific(expr) => reads_session(expr),
+			_ => false,
+		};
+		if reads(&self.doc_ctx.tb()?.permissions.select) {
+			return Ok(true);
+		}
+		Ok(self.doc_ctx.fd()?.iter().any(|fd| {
+			reads(&fd.select_permission) || fd.computed.as_ref().is_some_and(reads_session)
+		}))
+	}
+
+	/// Computes the notification of a group of subscriptions which share
+	/// their evaluation (see [`SharedEvaluation`]), and sends it to each
+	/// subscriber whose session is still valid.
+	///
+	/// The notification is computed with the first such subscription. The
+	/// subscriptions of a group have the same auth, live query and captured
+	/// variables, and sessions which differ only in their id and expiry, so
+	/// the WHERE clause, projection, and table and field permissions are
+	/// evaluated once for all of them.
+	///
 	/// SECURITY: this function is dispatched from `process_table_lives`
 	/// inside `try_join_all`, so any `Err(...)` propagated from here will
 	/// abort the triggering write across every concurrent subscription.
@@ -154,31 +203,45 @@ impl Document {
 		&self,
 		stk: &mut Stk,
 		ctx: &FrozenContext,
-		live_subscription: SubscriptionDefinition,
+		subscribers: Vec<&SubscriptionDefinition>,
 		opt: Options,
 		tx: Arc<Transaction>,
 		(met, initial, current): (Arc<Value>, Arc<Value>, Arc<Value>),
 		is_delete: bool,
 	) -> Result<()> {
-		// Ensure that a session exists on the LIVE query
-		let sess = match live_subscription.session.as_ref() {
-			Some(v) => v,
-			None => return Ok(()),
-		};
-		// Skip notification if the session that created this LIVE query has
-		// expired. `session["exp"]` is a unix timestamp set by
-		// `DURATION FOR SESSION`; absent means no expiry. We coerce via
-		// `Number::to_int` so a Float or Decimal value (e.g. from a JS
-		// client that serialised the timestamp as a float) still counts as
-		// an expiry. This mirrors the per-request check in
-		// `Datastore::execute` without requiring the originating Session
-		// object to remain in memory beyond the RPC connection lifetime.
-		if let Value::Object(ref session_obj) = *sess
-			&& let Some(Value::Number(exp)) = session_obj.get("exp")
-			&& Utc::now().timestamp() > (*exp).to_int()
-		{
+		let Some(sender) = ctx.broker() else {
 			return Ok(());
+		};
+		// Find the subscribers which this node should notify
+		let mut recipients = Vec::with_capacity(subscribers.len());
+		for live_subscription in subscribers {
+			// Ensure that a session exists on the LIVE query
+			let Some(sess) = live_subscription.session.as_ref() else {
+				continue;
+			};
+			// Skip notification if the session that created this LIVE query has
+			// expired. `session["exp"]` is a unix timestamp set by
+			// `DURATION FOR SESSION`; absent means no expiry. We coerce via
+			// `Number::to_int` so a Float or Decimal value (e.g. from a JS
+			// client that serialised the timestamp as a float) still counts as
+			// an expiry. This mirrors the per-request check in
+			// `Datastore::execute` without requiring the originating Session
+			// object to remain in memory beyond the RPC connection lifetime.
+			if let Value::Object(ref session_obj) = *sess
+				&& let Some(Value::Number(exp)) = session_obj.get("exp")
+				&& Utc::now().timestamp() > (*exp).to_int()
+			{
+				continue;
+			}
+			if !sender.should_emit(*ctx.node_id().as_bytes(), *live_subscription.node.as_bytes())? {
+				continue;
+			}
+			recipients.push((live_subscription, sess));
 		}
+		// The notification is computed with the first remaining subscriber
+		let Some(&(live_subscription, sess)) = recipients.first() else {
+			return Ok(());
+		};
 		// Ensure that auth info exists on the LIVE query
 		let auth = match live_subscription.auth.clone() {
 			Some(v) => v,
@@ -186,10 +249,6 @@ impl Document {
 		};
 		let opt = opt.with_auth(auth.into());
 
-		let Some(sender) = ctx.broker() else {
-			return Ok(());
-		};
-
 		// Get the record id of this document
 		let rid = self
 			.id
@@ -250,7 +309,7 @@ impl Document {
 		// First of all, let's check to see if the WHERE
 		// clause of the LIVE query is matched by this
 		// document. If it is then we can continue.
-		match self.lq_check(stk, &ctx, &opt, &live_subscription, &doc).await {
+		match self.lq_check(stk, &ctx, &opt, live_subscription, &doc).await {
 			Err(IgnoreError::Ignore) => return Ok(()),
 			Err(IgnoreError::Error(e)) => {
 				tracing::debug!(
@@ -280,13 +339,10 @@ impl Document {
 			}
 			Ok(_) => (),
 		}
-		if !sender.should_emit(*ctx.node_id().as_bytes(), *live_subscription.node.as_bytes())? {
-			return Ok(());
-		}
 		// Let's check what type of statement
 		// caused this LIVE query to run, and obtain
 		// the relevant result.
-		let (action, mut result) = match live_subscription.fields {
+		let (action, mut result) = match &live_subscription.fields {
 			SubscriptionFields::Diff => {
 				// DIFF mode: return JSON patch operations instead of full document
 				if is_delete {
@@ -367,7 +423,7 @@ impl Document {
 		// Any evaluation error (invalid function arguments, unsupported
 		// expressions, etc.) skips this notification rather than
 		// aborting the triggering write transaction.
-		if let Some(fetchs) = live_subscription.fetch {
+		if let Some(fetchs) = &live_subscription.fetch {
 			let mut idioms = BTreeSet::new();
 			for fetch in fetchs.iter() {
 				if let Err(e) = fetch.compute(stk, &ctx, &opt, &mut idioms).await {
@@ -393,13 +449,6 @@ impl Document {
 			}
 		}
 
-		// Extract the session ID from the session value
-		let session_id = match sess.pick(ID.as_ref()) {
-			Value::Uuid(uuid) => Some(uuid.into()),
-			Value::String(s) => s.parse::<crate::val::Uuid>().ok().map(|uuid| uuid.into()),
-			_ => None,
-		};
-
 		// Convert values to the public wire format. A conversion error
 		// (e.g. a closure-valued projection that cannot be serialised)
 		// skips this notification rather than aborting the triggering
@@ -429,16 +478,28 @@ impl Document {
 				return Ok(());
 			}
 		};
-		let notification = PublicNotification::new(
-			live_subscription.id.into(),
-			session_id,
-			action,
-			rid_public,
-			result_public,
-		);
-
-		// Send the notification
-		sender.send(RoutedNotification::new(live_subscription.node, notification)).await;
+		// Deletions made by the eviction of records are notified as evictions
+		let action = match action {
+			PublicAction::Delete if ctx.is_eviction() => PublicAction::Evicted,
+			action => action,
+		};
+		// Send the notification to each subscriber
+		for (live_subscription, sess) in recipients {
+			// Extract the session ID from the session value
+			let session_id = match sess.pick(ID.as_ref()) {
+				Value::Uuid(uuid) => Some(uuid.into()),
+				Value::String(s) => s.parse::<crate::val::Uuid>().ok().map(|uuid| uuid.into()),
+				_ => None,
+			};
+			let notification = PublicNotification::new(
+				live_subscription.id.into(),
+				session_id,
+				action,
+				rid_public.clone(),
+				result_public.clone(),
+			);
+			sender.send(RoutedNotification::new(live_subscription.node, notification)).await;
+		}
 
 		Ok(())
 	}
@@ -592,6 +653,49 @@ impl Document {
 	}
 }
 
+/// The parts of a live query subscription which determine its notifications
+///
+/// Subscriptions with equal parts compute the same notification for a change,
+/// so it is computed once and sent to each of them. Sessions are compared
+/// without their id and expiry, which differ between connections, unless the
+/// live query, or the select permissions and computed fields of the table,
+/// may read the session.
+#[derive(PartialEq, Eq, Hash)]
+struct SharedEvaluation<'a> {
+	fields: &'a SubscriptionFields,
+	what: &'a Expr,
+	cond: Option<&'a Expr>,
+	fetch: Option<&'a Fetchs>,
+	auth: Option<&'a Auth>,
+	vars: &'a BTreeMap<String, Value>,
+	session: Option<Cow<'a, Value>>,
+}
+
+impl<'a> SharedEvaluation<'a> {
+	fn new(live_subscription: &'a SubscriptionDefinition, table_reads_session: bool) -> Self {
+		let session = match &live_subscription.session {
+			Some(Value::Object(session))
+				if !table_reads_session && !live_subscription.reads_session() =>
+			{
+				let mut session = session.clone();
+				session.remove("id");
+				session.remove("exp");
+				Some(Cow::Owned(Value::Object(session)))
+			}
+			session => session.as_ref().map(Cow::Borrowed),
+		};
+		Self {
+			fields: &live_subscription.fields,
+			what: &live_subscription.what,
+			cond: live_subscription.cond.as_ref(),
+			fetch: live_subscription.fetch.as_ref(),
+			auth: live_subscription.auth.as_ref(),
+			vars: &live_subscription.vars,
+			session,
+		}
+	}
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct DefaultBroker {
 	sender: Sender<RoutedNotification>,
@@ -762,6 +866,136 @@ mod tests {
 		assert_eq!(notif.action, PublicAction::Create);
 	}
 
+	/// Subscribers of the same live query with the same auth share the
+	/// evaluation of each change, but each is still sent its own notification.
+	#[tokio::test]
+	async fn test_live_shared_evaluation_notifies_every_subscriber() {
+		let (recv, ds) = new_ds_with_broker().await.unwrap();
+		let (ns, db, tb) = ("test", "test", "person");
+		setup_ns_db_table(&ds, ns, db, tb).await;
+
+		let mut live_ids = Vec::new();
+		for _ in 0..3 {
+			let mut live_ses = Session::owner().with_ns(ns).with_db(db).with_rt(true);
+			live_ses.id = Some(uuid::Uuid::new_v4());
+			let mut res = ds
+				.execute(&format!("LIVE SELECT * FROM {tb} WHERE ok = true"), &live_ses, None)
+				.await
+				.unwrap();
+			let PublicValue::Uuid(id) = res.remove(0).result.unwrap() else {
+				panic!("LIVE SELECT should return the live query id");
+			};
+			live_ids.push((uuid::Uuid::from(id), live_ses.id.unwrap()));
+		}
+		while recv.try_recv().is_ok() {}
+
+		let owner_ses = Session::owner().with_ns(ns).with_db(db);
+		ds.execute(&format!("CREATE {tb}:1 SET ok = true"), &owner_ses, None).await.unwrap();
+
+		let mut notified = Vec::new();
+		for _ in 0..3 {
+			let notif = tokio::time::timeout(tokio::time::Duration::from_millis(500), recv.recv())
+				.await
+				.expect("notification should arrive within timeout")
+				.expect("channel should not be closed");
+			assert_eq!(notif.action, PublicAction::Create);
+			notified.push((uuid::Uuid::from(notif.id), uuid::Uuid::from(notif.session.unwrap())));
+		}
+		notified.sort();
+		live_ids.sort();
+		assert_eq!(notified, live_ids);
+	}
+
+	/// SECURITY: subscribers with a different auth never share an evaluation,
+	/// so table permissions are checked for each of them.
+	#[tokio::test]
+	async fn test_live_shared_evaluation_checks_permissions_per_auth() {
+		let (recv, ds) = new_ds_with_broker().await.unwrap();
+		let (ns, db) = ("test", "test");
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.ensure_ns_db(None, ns, db).await.unwrap();
+		tx.commit().await.unwrap();
+
+		let owner_ses = Session::owner().with_ns(ns).with_db(db);
+		ds.execute(
+			"DEFINE TABLE person PERMISSIONS FOR select WHERE owner = $auth.id; \
+			 DEFINE ACCESS user ON DATABASE TYPE RECORD",
+			&owner_ses,
+			None,
+		)
+		.await
+		.unwrap();
+
+		let mut res = ds
+			.execute("LIVE SELECT * FROM person", &record_user_session(ns, db, "alice"), None)
+			.await
+			.unwrap();
+		let alice = res.remove(0).result.unwrap();
+		ds.execute("LIVE SELECT * FROM person", &record_user_session(ns, db, "bob"), None)
+			.await
+			.unwrap();
+		while recv.try_recv().is_ok() {}
+
+		ds.execute("CREATE person:1 SET owner = user:alice", &owner_ses, None).await.unwrap();
+
+		let notif = tokio::time::timeout(tokio::time::Duration::from_millis(500), recv.recv())
+			.await
+			.expect("notification should arrive within timeout")
+			.expect("channel should not be closed");
+		assert_eq!(PublicValue::Uuid(notif.id), alice);
+		let spurious =
+			tokio::time::timeout(tokio::time::Duration::from_millis(200), recv.recv()).await;
+		assert!(spurious.is_err(), "bob must not be notified of a record he can not select");
+	}
+
+	/// SECURITY: subscribers with the same auth but different sessions do
+	/// not share an evaluation when the table permissions read the session,
+	/// even though the live query itself does not.
+	#[tokio::test]
+	async fn test_live_shared_evaluation_checks_session_permissions() {
+		let (recv, ds) = new_ds_with_broker().await.unwrap();
+		let (ns, db) = ("test", "test");
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.ensure_ns_db(None, ns, db).await.unwrap();
+		tx.commit().await.unwrap();
+
+		let owner_ses = Session::owner().with_ns(ns).with_db(db);
+		ds.execute(
+			"DEFINE TABLE person PERMISSIONS FOR select WHERE owner = $session.id; \
+			 DEFINE ACCESS user ON DATABASE TYPE RECORD",
+			&owner_ses,
+			None,
+		)
+		.await
+		.unwrap();
+
+		let mut subscribers = Vec::new();
+		for _ in 0..2 {
+			let mut live_ses = record_user_session(ns, db, "alice");
+			let session = uuid::Uuid::new_v4();
+			live_ses.id = Some(session);
+			let mut res = ds.execute("LIVE SELECT * FROM person", &live_ses, None).await.unwrap();
+			subscribers.push((res.remove(0).result.unwrap(), session));
+		}
+		while recv.try_recv().is_ok() {}
+
+		// Both subscribers are the same user, but only the first session owns
+		// the record
+		let (live, session) = &subscribers[0];
+		ds.execute(&format!("CREATE person:1 SET owner = <uuid> '{session}'"), &owner_ses, None)
+			.await
+			.unwrap();
+
+		let notif = tokio::time::timeout(tokio::time::Duration::from_millis(500), recv.recv())
+			.await
+			.expect("notification should arrive within timeout")
+			.expect("channel should not be closed");
+		assert_eq!(&PublicValue::Uuid(notif.id), live);
+		let spurious =
+			tokio::time::timeout(tokio::time::Duration::from_millis(200), recv.recv()).await;
+		assert!(spurious.is_err(), "a session must not be notified of a record it can not select");
+	}
+
 	/// SECURITY (#120): `reduce_to_owned` runs *before* computed-field
 	/// evaluation, so a computed field marked `PERMISSIONS FOR select NONE`
 	/// would never be touched by the table-side reduction. The CREATE
@@ -6,8 +6,13 @@
 //! - `id`: traditionally an integer but can be an object or collection such as an array
 
 pub(crate) use self::document::*;
-pub use self::event::AsyncEventRecord;
+pub(crate) use self::event::DeadLetterRecord;
+pub use self::event::{AsyncEventRecord, DeadLetter};
+pub(crate) use self::extrema::ViewExtrema;
+pub(crate) use self::lineage::Lineage;
 pub(crate) use self::lives::DefaultBroker;
+pub use self::outbox::OutboxRecord;
+pub(crate) use self::outbox::{OutboxDeadLetterRecord, OutboxEvent};
 
 mod document; // The entry point for a document to be processed
 
@@ -25,15 +30,19 @@ mod check; // Data and condition checking for this document
 pub(crate) mod compute; // Compute computed fields for this document
 mod edges; // Attempts to store the edge data for this document
 mod event; // Processes any table events relevant for this document
+mod extrema; // Counts the values behind the minimums and maximums of aggregated views
 mod field; // Processes any schema-defined fields for this document
 mod index; // Attempts to store the index data for this document
+mod lineage; // Records which writer last changed each field of this document
 mod live_events; // Captures live-query events for this document (Router engine)
 mod lives; // Processes any live queries relevant for this document
+mod outbox; // Delivers the HTTP requests of outbox events after their transaction commits
 mod output; // Builds the projected output for a document
 mod purge; // Deletes this document, and any edges or indexes
 mod reduce; // Reduces the permissioned fields in this document
 mod store; // Writes the document content to the storage engine
 mod table; // Processes any foreign tables relevant for this document'
+mod throttle; // Waits for the write throttle of the table to admit this document
 
 /// Error result used when a function can result in the value being processed
 /// being ignored.
@@ -0,0 +1,401 @@
+//! Delivers the HTTP requests of events defined with `OUTBOX`.
+//!
+//! An outbox event runs within the transaction which triggered it, like any
+//! other synchronous event, but the `http::post`, `http::put`, `http::patch`
+//! and `http::delete` requests it makes are not sent while it runs. Instead
+//! they are recorded in that transaction, in the [`crate::key::root::ob`]
+//! keyspace, so that a request is only ever sent once the transaction has
+//! committed, and is never lost once it has. `http::get` and `http::head`
+//! have no side effects, and are still sent straight away.
+//!
+//! The background event processing task then sends the recorded requests,
+//! several at once, failing each one which does not complete in time, so that
+//! a slow or unreachable endpoint does not hold up the others for long.
+//! A failed request is retried as many times as allowed by the `RETRY` clause
+//! of the event, with an exponential backoff, before it is moved to the
+//! dead-letter queue, from which it can be replayed. A request is removed
+//! from the outbox once it has been sent, so it is sent at least once, and
+//! may be sent again if the task stops in between. The requests of a removed
+//! database are never sent, and are removed along with its dead letters.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+#[cfg(not(feature = "http"))]
+use anyhow::bail;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use revision::revisioned;
+use surrealdb_types::ToSql;
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::timeout;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::timeout;
+
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider};
+use crate::catalog::{ApiMethod, DatabaseId, NamespaceId};
+use crate::cnf::{OUTBOX_CONCURRENCY, OUTBOX_REQUEST_TIMEOUT};
+use crate::ctx::FrozenContext;
+use crate::doc::DeadLetter;
+use crate::doc::event::retry_backoff;
+use crate::err::Error;
+use crate::key::root::ob::Outbox;
+use crate::key::root::od::OutboxDeadLetter;
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{
+	Datastore, HlcTimeStamp, KVValue, LockType, NORMAL_BATCH_SIZE, Transaction, TransactionType,
+	impl_kv_value_revisioned,
+};
+use crate::val::{Object, RecordId, TableName, Value};
+
+/// The event defined with `OUTBOX` whose THEN clause is running.
+#[derive(Debug)]
+pub(crate) struct OutboxEvent {
+	pub(crate) ns: NamespaceId,
+	pub(crate) db: DatabaseId,
+	pub(crate) tb: TableName,
+	pub(crate) ev: String,
+	/// Record id of the document which triggered the event, if one exists.
+	pub(crate) rid: Option<Arc<RecordId>>,
+	/// Maximum retry count for each request of the event.
+	pub(crate) retry: u16,
+}
+
+impl OutboxEvent {
+	/// Records an HTTP request in the transaction which triggered the event,
+	/// to be sent once the transaction has committed.
+	#[cfg(feature = "http")]
+	pub(crate) async fn record(
+		&self,
+		ctx: &FrozenContext,
+		method: ApiMethod,
+		uri: String,
+		body: Option<Value>,
+		opts: Option<Object>,
+	) -> Result<Value> {
+		// Requests which could never be sent fail the event straight away
+		let url = url::Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.clone()))?;
+		ctx.check_allowed_net(&url).await?;
+		let request = OutboxRecord {
+			ns: self.ns,
+			db: self.db,
+			tb: self.tb.clone(),
+			ev: self.ev.clone(),
+			rid: self.rid.clone(),
+			method,
+			uri,
+			body,
+			opts,
+			attempt: 0,
+			retry: self.retry,
+		};
+		let tx = ctx.tx();
+		tx.put(&Outbox::new(0, HlcTimeStamp::next(), ctx.node_id()), &request).await?;
+		tx.trigger_async_event();
+		Ok(Value::None)
+	}
+}
+
+/// An HTTP request recorded by an outbox event, waiting to be sent.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+pub struct OutboxRecord {
+	/// Namespace of the event which recorded the request.
+	ns: NamespaceId,
+	/// Database of the event which recorded the request.
+	db: DatabaseId,
+	/// Table of the event which recorded the request.
+	tb: TableName,
+	/// Name of the event which recorded the request.
+	ev: String,
+	/// Record id of the document which triggered the event, if one exists.
+	rid: Option<Arc<RecordId>>,
+	method: ApiMethod,
+	uri: String,
+	body: Option<Value>,
+	/// The headers sent with the request.
+	opts: Option<Object>,
+	/// Number of attempts to send the request which have failed.
+	attempt: u16,
+	/// Maximum retry count, captured from the event when the request was recorded.
+	retry: u16,
+}
+
+impl_kv_value_revisioned!(OutboxRecord);
+
+/// An outbox request which failed on every attempt, kept for inspection and replay.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug)]
+pub(crate) struct OutboxDeadLetterRecord {
+	/// The error returned by the last attempt.
+	error: String,
+	/// Milliseconds since the unix epoch when the request was dead-lettered.
+	failed_at: i64,
+	/// The recorded request, including the number of attempts made.
+	request: OutboxRecord,
+}
+
+impl_kv_value_revisioned!(OutboxDeadLetterRecord);
+
+impl OutboxRecord {
+	/// Send a single batch of the recorded requests which are due.
+	/// Returns the number of requests fetched (not necessarily successfully sent).
+	pub async fn process_next_batch(ds: &Datastore, lh: Option<&LeaseHandler>) -> Result<usize> {
+		if let Some(lh) = lh {
+			lh.try_maintain_lease().await?;
+		}
+		let now = u64::try_from(Utc::now().timestamp_millis()).unwrap_or_default();
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		// Requests which are backing off are keyed after the current time, so are not read
+		let res = catch!(tx, tx.scan(Outbox::due(now), NORMAL_BATCH_SIZE, 0, None).await);
+		let mut requests = Vec::with_capacity(res.len());
+		for (k, v) in res.iter() {
+			let key = catch!(tx, Outbox::decode_key(k));
+			let request = catch!(tx, Self::kv_decode_value(v, ()));
+			requests.push((key, request));
+		}
+		let databases = catch!(tx, Self::existing_databases(&tx, &requests).await);
+		tx.cancel().await?;
+		if requests.is_empty() {
+			return Ok(0);
+		}
+		let ctx = ds.setup_ctx()?.freeze();
+		let count = requests.len();
+		let mut deliveries = futures::stream::iter(requests)
+			.map(|(key, request)| {
+				let exists = databases.contains(&(request.ns, request.db));
+				let ctx = &ctx;
+				async move {
+					let res = Self::deliver(ds, ctx, key, request, exists).await;
+					(key, res)
+				}
+			})
+			.buffer_unordered((*OUTBOX_CONCURRENCY).max(1));
+		while let Some((key, res)) = deliveries.next().await {
+			if let Some(lh) = lh {
+				lh.try_maintain_lease().await?;
+			}
+			if let Err(e) = res {
+				error!(
+					"Unexpected error while sending an outbox request. Error: {e} - Key: {key:?}"
+				);
+			}
+		}
+		Ok(count)
+	}
+
+	/// Returns the databases of the given requests which still exist
+	async fn existing_databases(
+		tx: &Transaction,
+		requests: &[(Outbox, OutboxRecord)],
+	) -> Result<HashSet<(NamespaceId, DatabaseId)>> {
+		let mut existing = HashSet::new();
+		let namespaces: HashSet<NamespaceId> = tx
+			.all_ns(None)
+			.await?
+			.iter()
+			.map(|ns| ns.namespace_id)
+			.filter(|ns| requests.iter().any(|(_, request)| request.ns == *ns))
+			.collect();
+		for ns in namespaces {
+			for db in tx.all_db(ns, None).await?.iter() {
+				existing.insert((ns, db.database_id));
+			}
+		}
+		Ok(existing)
+	}
+
+	/// Returns whether any recorded request is waiting to be sent, including
+	/// the requests which are backing off after a failure.
+	pub async fn has_pending_requests(ds: &Datastore) -> Result<bool> {
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let res = catch!(tx, tx.scan(Outbox::range(), 1, 0, None).await);
+		tx.cancel().await?;
+		Ok(!res.is_empty())
+	}
+
+	/// Removes the requests, and the dead letters, of a removed database
+	pub(crate) async fn database_removed(
+		tx: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<()> {
+		Self::remove_pending(tx, |request| request.ns == ns && request.db == db).await?;
+		tx.delr(OutboxDeadLetter::range(ns, db)?).await
+	}
+
+	/// Removes the requests, and the dead letters, of every database of a
+	/// removed namespace
+	pub(crate) async fn namespace_removed(tx: &Transaction, ns: NamespaceId) -> Result<()> {
+		Self::remove_pending(tx, |request| request.ns == ns).await?;
+		tx.delr(OutboxDeadLetter::range_ns(ns)?).await
+	}
+
+	/// Removes the requests waiting to be sent which match a predicate
+	async fn remove_pending(tx: &Transaction, matches: impl Fn(&Self) -> bool) -> Result<()> {
+		for (k, v) in tx.getr(Outbox::range(), None).await? {
+			if matches(&Self::kv_decode_value(&v, ())?) {
+				tx.del(&Outbox::decode_key(&k)?).await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Send a recorded request, then remove it from the outbox, or requeue it
+	/// with a backoff when it failed. The request of a database which no
+	/// longer exists is removed without being sent.
+	async fn deliver(
+		ds: &Datastore,
+		ctx: &FrozenContext,
+		key: Outbox,
+		mut request: Self,
+		exists: bool,
+	) -> Result<()> {
+		if !exists {
+			debug!(
+				"Dropping the request of the event `{}` on table {}, as its database was removed",
+				request.ev, request.tb
+			);
+			let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+			catch!(tx, tx.del(&key).await);
+			return tx.commit().await;
+		}
+		let limit = Duration::from_secs(*OUTBOX_REQUEST_TIMEOUT);
+		let res = match timeout(limit, request.send(ctx)).await {
+			Ok(res) => res,
+			Err(_) => Err(anyhow::Error::new(Error::Http(format!(
+				"The request did not complete within {}s",
+				limit.as_secs()
+			)))),
+		};
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		catch!(tx, tx.del(&key).await);
+		if let Err(e) = res {
+			request.attempt = request.attempt.saturating_add(1);
+			if request.attempt <= request.retry {
+				let now = u64::try_from(Utc::now().timestamp_millis()).unwrap_or_default();
+				let not_before = now.saturating_add(retry_backoff(request.attempt));
+				let requeued = Outbox::new(not_before, HlcTimeStamp(key.ts), key.node_id);
+				catch!(tx, tx.set(&requeued, &request).await);
+			} else {
+				warn!(
+					"Final error after sending the request of the event `{}` on table {} {} times: {e}",
+					request.ev, request.tb, request.attempt
+				);
+				let dl = OutboxDeadLetter::from_outbox(request.ns, request.db, &key);
+				let record = OutboxDeadLetterRecord {
+					error: e.to_string(),
+					failed_at: Utc::now().timestamp_millis(),
+					request,
+				};
+				catch!(tx, tx.set(&dl, &record).await);
+			}
+		}
+		tx.commit().await
+	}
+
+	#[cfg(feature = "http")]
+	async fn send(&self, ctx: &FrozenContext) -> Result<()> {
+		use crate::fnc::util::http;
+		let uri = self.uri.clone();
+		let body = self.body.clone().unwrap_or(Value::Null);
+		let opts = self.opts.clone();
+		match self.method {
+			ApiMethod::Post => http::post(ctx, uri, body, opts).await?,
+			ApiMethod::Put => http::put(ctx, uri, body, opts).await?,
+			ApiMethod::Patch => http::patch(ctx, uri, body, opts).await?,
+			ApiMethod::Delete => http::delete(ctx, uri, opts).await?,
+			method => {
+				return Err(anyhow::Error::new(Error::Unreachable(format!(
+					"The {method} method is not recorded in the outbox"
+				))));
+			}
+		};
+		Ok(())
+	}
+
+	#[cfg(not(feature = "http"))]
+	async fn send(&self, _: &FrozenContext) -> Result<()> {
+		bail!(Error::HttpDisabled)
+	}
+}
+
+impl OutboxDeadLetterRecord {
+	/// Lists the dead-lettered requests of a database.
+	pub(crate) async fn list(
+		tx: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Vec<DeadLetter>> {
+		let mut out = Vec::new();
+		for (_, v) in tx.getr(OutboxDeadLetter::range(ns, db)?, None).await? {
+			let val = Self::kv_decode_value(&v, ())?;
+			let request = val.request;
+			out.push(DeadLetter {
+				table: request.tb.to_string(),
+				event: request.ev,
+				record: request.rid.as_ref().map(|rid| rid.to_sql()),
+				attempts: request.attempt,
+				error: val.error,
+				failed_at: DateTime::from_timestamp_millis(val.failed_at).unwrap_or_default(),
+				request: Some(format!(
+					"{} {}",
+					request.method.to_string().to_uppercase(),
+					request.uri
+				)),
+			});
+		}
+		Ok(out)
+	}
+
+	/// Moves the dead-lettered requests of a database back into the outbox,
+	/// optionally only those of a single table, and returns how many were moved.
+	///
+	/// Replayed requests start again with the full number of retries.
+	pub(crate) async fn replay(
+		tx: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		table: Option<&str>,
+	) -> Result<usize> {
+		let mut count = 0;
+		for (k, v) in tx.getr(OutboxDeadLetter::range(ns, db)?, None).await? {
+			let key = OutboxDeadLetter::decode_key(&k)?;
+			let mut request = Self::kv_decode_value(&v, ())?.request;
+			if table.is_some_and(|tb| tb != request.tb.as_str()) {
+				continue;
+			}
+			request.attempt = 0;
+			tx.set(&Outbox::new(0, HlcTimeStamp(key.ts), key.node_id), &request).await?;
+			tx.del(&key).await?;
+			count += 1;
+		}
+		if count > 0 {
+			tx.trigger_async_event();
+		}
+		Ok(count)
+	}
+
+	/// Removes the dead-lettered requests of a database which failed before
+	/// the given time, in milliseconds since the unix epoch.
+	pub(crate) async fn expire(
+		tx: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		before: i64,
+	) -> Result<()> {
+		let mut next = Some(OutboxDeadLetter::range(ns, db)?);
+		while let Some(rng) = next {
+			let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				if Self::kv_decode_value(v, ())?.failed_at < before {
+					tx.clr(k).await?;
+				}
+			}
+		}
+		Ok(())
+	}
+}
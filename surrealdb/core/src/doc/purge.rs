@@ -47,6 +47,8 @@ impl Document {
 		if !self.is_modified() {
 			return Ok(());
 		}
+		// Wait for the table write throttle
+		self.throttle_write(ctx).await?;
 		// Get the transaction
 		let txn = ctx.tx();
 		// Get the record id
@@ -57,6 +59,10 @@ impl Document {
 			let db = self.doc_ctx.db().database_id;
 			// Purge the record data
 			txn.del_record(ns, db, &rid.table, &rid.key).await?;
+			// Purge the record lineage
+			self.purge_lineage(ctx).await?;
+			// Forget when a soft deleted record was deleted
+			self.store_deletion_data(ctx).await?;
 			// Mark this row as having mutated the KV store so the
 			// iterator bumps the per-statement affected-row counter.
 			self.mutated = true;
@@ -90,6 +90,8 @@ impl Document {
 		}
 		// Ensure the record actually exists
 		self.check_record_exists()?;
+		// Skip records which have been soft deleted
+		self.check_soft_deleted(stm.with())?;
 		// Check if table has correct relation status
 		self.check_table_type_relate()?;
 		// SECURITY: evaluate the table-level update permission BEFORE any
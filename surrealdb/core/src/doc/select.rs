@@ -18,6 +18,8 @@ impl Document {
 	) -> Result<Value, IgnoreError> {
 		// Check if the record actually exists
 		self.check_record_exists()?;
+		// Skip records which have been soft deleted
+		self.check_soft_deleted(stm.with.as_ref())?;
 		// SECURITY: evaluate the table-level select permission BEFORE the
 		// WHERE clause so a `WHERE THROW ...` cannot leak record values.
 		self.check_select_permissions(stk, ctx, opt, &self.current).await?;
@@ -22,6 +22,8 @@ impl Document {
 		if tb.drop {
 			return Ok(());
 		}
+		// Wait for the table write throttle
+		self.throttle_write(ctx).await?;
 		// Get the record id
 		let rid = self.id()?;
 		// Get the namespace id
@@ -137,6 +139,10 @@ impl Document {
 			// Let's update the stored value for the specified key
 			_ => ctx.tx().set_record(ns, db, &rid.table, &rid.key, doc).await,
 		}?;
+		// Record which writer changed each field
+		self.store_lineage(ctx, stm).await?;
+		// Record when a soft deleted record was deleted
+		self.store_deletion_data(ctx).await?;
 		// KV write succeeded; mark the document as mutated so the
 		// per-statement affected-row counter (bumped from
 		// `Document::process`) reflects this row.
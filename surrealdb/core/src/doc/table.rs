@@ -9,7 +9,7 @@ use crate::catalog::providers::TableProvider;
 use crate::catalog::{Metadata, Record, RecordType, ViewDefinition};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
-use crate::doc::{Action, CursorDoc, Document, DocumentContext, Extras, NsDbCtx};
+use crate::doc::{Action, CursorDoc, Document, DocumentContext, Extras, NsDbCtx, ViewExtrema};
 use crate::err::Error;
 use crate::expr::field::Selector;
 use crate::expr::statements::SelectStatement;
@@ -18,11 +18,15 @@ use crate::expr::{
 };
 use crate::idx::planner::RecordStrategy;
 use crate::key;
-use crate::val::{Array, Number, RecordId, RecordIdKey, TableName, TryAdd, TryMul, TryPow, Value};
+use crate::val::{
+	Array, Datetime, Number, RecordId, RecordIdKey, TableName, TryAdd, TryMul, TryPow, Value,
+};
 struct Recalculation {
 	function: String,
 	stat: usize,
 	arg: usize,
+	/// Whether the maximum rather than the minimum is recalculated
+	max: bool,
 }
 
 impl Document {
@@ -38,17 +42,44 @@ impl Document {
 		opt: &Options,
 		action: Action,
 	) -> Result<()> {
+		if !self.is_modified() {
+			return Ok(());
+		}
 		// Check import
 		if opt.import {
-			return Ok(());
+			// The views miss this change until they are refreshed
+			return self.mark_views_stale(ctx).await;
 		}
-		if !self.is_modified() {
+		// Skip changes to records which are hidden by a soft delete
+		let Some(action) = self.visible_action(action)? else {
 			return Ok(());
-		}
+		};
 
 		self.process_views(stk, ctx, opt, action).await
 	}
 
+	/// Records that the materialized views of the table of this record are
+	/// stale, as they were not updated with a change to it
+	async fn mark_views_stale(&self, ctx: &FrozenContext) -> Result<()> {
+		let fts = self.doc_ctx.ft()?;
+		if fts.is_empty() {
+			return Ok(());
+		}
+		let db = self.doc_ctx.db();
+		let tx = ctx.tx();
+		for ft in fts.iter() {
+			let key = key::table::vs::new(db.namespace_id, db.database_id, &ft.name);
+			// Views defined before their state was kept have no state to update
+			if let Some(mut state) = tx.get(&key, None).await?
+				&& state.stale_since.is_none()
+			{
+				state.stale_since = Some(Datetime::now());
+				tx.set(&key, &state).await?;
+			}
+		}
+		Ok(())
+	}
+
 	async fn process_views(
 		&self,
 		stk: &mut Stk,
@@ -98,13 +129,17 @@ impl Document {
 				// Id of the document on the view
 				let id = &self.id()?.key;
 
-				let set = if let Some(cond) = condition {
+				// A soft deleted record is still stored, so the action
+				// rather than the document decides whether it is removed
+				let set = if action == Action::Delete {
+					false
+				} else if let Some(cond) = condition {
 					stk.run(|stk| cond.compute(stk, ctx, opt, Some(&self.current)))
 						.await
 						.catch_return()?
 						.is_truthy()
 				} else {
-					action != Action::Delete
+					true
 				};
 
 				let db = self.doc_ctx.db();
@@ -345,6 +380,13 @@ impl Document {
 
 		aggregation::add_to_aggregation_stats(&args, &mut meta.aggregation_stats)?;
 
+		let extrema =
+			ViewExtrema::load(&tx, db.namespace_id, db.database_id, view_table_name, &group, aggr)
+				.await?;
+		if let Some(extrema) = &extrema {
+			extrema.add(&tx, &args).await?;
+		}
+
 		let doc =
 			Value::Object(aggregation::create_field_document(&group, &meta.aggregation_stats))
 				.into();
@@ -443,9 +485,16 @@ impl Document {
 			fail!("Metadata for view table had no valid count")
 		};
 
+		let extrema =
+			ViewExtrema::load(&tx, db.namespace_id, db.database_id, view_table_name, &group, aggr)
+				.await?;
+
 		if count == 1 {
 			// Only one record, we can just delete the record.
-			tx.del(&k).await?;
+			tx.del_record(db.namespace_id, db.database_id, view_table_name, &key).await?;
+			if let Some(extrema) = &extrema {
+				extrema.clear(&tx).await?;
+			}
 
 			let ns = self.doc_ctx.ns();
 			let db = self.doc_ctx.db();
@@ -486,6 +535,10 @@ impl Document {
 			args.push(a.compute(stk, ctx, opt, Some(&self.initial)).await.catch_return()?)
 		}
 
+		if let Some(extrema) = &extrema {
+			extrema.remove(&tx, &args).await?;
+		}
+
 		let mut recalculations = Vec::new();
 		for (idx, a) in meta.aggregation_stats.iter_mut().enumerate() {
 			match a {
@@ -517,6 +570,7 @@ impl Document {
 							function: "math::max".to_string(),
 							stat: idx,
 							arg: *arg,
+							max: true,
 						})
 					}
 				}
@@ -533,6 +587,7 @@ impl Document {
 							function: "math::min".to_string(),
 							stat: idx,
 							arg: *arg,
+							max: false,
 						})
 					}
 				}
@@ -571,6 +626,7 @@ impl Document {
 							function: "time::max".to_string(),
 							stat: idx,
 							arg: *arg,
+							max: true,
 						});
 					}
 				}
@@ -587,6 +643,7 @@ impl Document {
 							function: "time::min".to_string(),
 							stat: idx,
 							arg: *arg,
+							max: false,
 						});
 					}
 				}
@@ -617,73 +674,9 @@ impl Document {
 		}
 
 		if !recalculations.is_empty() {
-			// Build the expression which recalculates the values
-			let exprs = recalculations
-				.iter()
-				.map(|x| {
-					Expr::FunctionCall(Box::new(FunctionCall {
-						receiver: Function::Normal(x.function.clone()),
-						arguments: vec![aggr.aggregate_arguments[x.arg].clone()],
-					}))
-				})
-				.collect();
-
-			// Build condition which filters out all values not belonging to the group.
-			let mut condition = None;
-			for (idx, g) in aggr.group_expressions.iter().enumerate() {
-				let expr = Expr::Binary {
-					left: Box::new(g.clone()),
-					op: BinaryOperator::Equal,
-					right: Box::new(group[idx].clone().into_literal()),
-				};
-				if let Some(c) = condition {
-					condition = Some(Expr::Binary {
-						left: Box::new(c),
-						op: BinaryOperator::And,
-						right: Box::new(expr),
-					})
-				} else {
-					condition = Some(expr)
-				}
-			}
-
-			let table_name = self.id()?.table.clone();
-
-			let recalc_stmt = SelectStatement {
-				// SELECT VALUE [recalc1, recalc2,..]
-				fields: Fields::Value(Box::new(Selector {
-					expr: Expr::Literal(Literal::Array(exprs)),
-					alias: None,
-				})),
-				// FROM ONLY table
-				only: true,
-				what: vec![Expr::Table(table_name.clone())],
-				// WHERE group_expr1 = group_value1 && group_expr2 = group_value2 && ..
-				cond: condition.map(Cond),
-				// GROUP ALL
-				group: Some(Groups(Vec::new())),
-				omit: vec![],
-				with: None,
-				split: None,
-				order: None,
-				limit: None,
-				start: None,
-				fetch: None,
-				version: Expr::Literal(Literal::None),
-				timeout: Expr::Literal(Literal::None),
-				explain: None,
-				tempfiles: false,
-			};
-
-			let value = stk.run(|stk| recalc_stmt.compute(stk, ctx, opt, None)).await?;
-
-			let Value::Array(Array(values)) = value else {
-				fail!("Aggregate recalculation select statement return an invalid result");
-			};
-			if values.len() != recalculations.len() {
-				fail!("Aggregate recalculation select statement return an invalid result");
-			}
-
+			let values = self
+				.recalculate(stk, ctx, opt, &group, aggr, &recalculations, extrema.as_ref())
+				.await?;
 			for (idx, v) in values.into_iter().enumerate() {
 				match &mut meta.aggregation_stats[recalculations[idx].stat] {
 					AggregationStat::TimeMin {
@@ -821,6 +814,14 @@ impl Document {
 			after_args.push(a.compute(stk, ctx, opt, Some(&self.current)).await.catch_return()?)
 		}
 
+		let extrema =
+			ViewExtrema::load(&tx, db.namespace_id, db.database_id, view_table_name, &group, aggr)
+				.await?;
+		if let Some(extrema) = &extrema {
+			extrema.remove(&tx, &before_args).await?;
+			extrema.add(&tx, &after_args).await?;
+		}
+
 		let mut recalculations = Vec::new();
 		for (idx, a) in meta.aggregation_stats.iter_mut().enumerate() {
 			match a {
@@ -865,6 +866,7 @@ impl Document {
 							function: "math::max".to_string(),
 							stat: idx,
 							arg: *arg,
+							max: true,
 						})
 					}
 				}
@@ -892,6 +894,7 @@ impl Document {
 							function: "math::min".to_string(),
 							stat: idx,
 							arg: *arg,
+							max: false,
 						})
 					}
 				}
@@ -964,6 +967,7 @@ impl Document {
 							function: "time::max".to_string(),
 							stat: idx,
 							arg: *arg,
+							max: true,
 						});
 					}
 				}
@@ -992,6 +996,7 @@ impl Document {
 							function: "time::min".to_string(),
 							stat: idx,
 							arg: *arg,
+							max: false,
 						});
 					}
 				}
@@ -1027,73 +1032,9 @@ impl Document {
 		}
 
 		if !recalculations.is_empty() {
-			// Build the expression which recalculates the values
-			let exprs = recalculations
-				.iter()
-				.map(|x| {
-					Expr::FunctionCall(Box::new(FunctionCall {
-						receiver: Function::Normal(x.function.clone()),
-						arguments: vec![aggr.aggregate_arguments[x.arg].clone()],
-					}))
-				})
-				.collect();
-
-			// Build condition which filters out all values not belonging to the group.
-			let mut condition = None;
-			for (idx, g) in aggr.group_expressions.iter().enumerate() {
-				let expr = Expr::Binary {
-					left: Box::new(g.clone()),
-					op: BinaryOperator::Equal,
-					right: Box::new(group[idx].clone().into_literal()),
-				};
-				if let Some(c) = condition {
-					condition = Some(Expr::Binary {
-						left: Box::new(c),
-						op: BinaryOperator::And,
-						right: Box::new(expr),
-					})
-				} else {
-					condition = Some(expr)
-				}
-			}
-
-			let table_name = self.id()?.table.clone();
-
-			let recalc_stmt = SelectStatement {
-				// SELECT VALUE [recalc1, recalc2,..]
-				fields: Fields::Value(Box::new(Selector {
-					expr: Expr::Literal(Literal::Array(exprs)),
-					alias: None,
-				})),
-				// FROM ONLY table
-				only: true,
-				what: vec![Expr::Table(table_name.clone())],
-				// WHERE group_expr1 = group_value1 && group_expr2 = group_value2 && ..
-				cond: condition.map(Cond),
-				// GROUP ALL
-				group: Some(Groups(Vec::new())),
-				omit: vec![],
-				with: None,
-				split: None,
-				order: None,
-				limit: None,
-				start: None,
-				fetch: None,
-				version: Expr::Literal(Literal::None),
-				timeout: Expr::Literal(Literal::None),
-				explain: None,
-				tempfiles: false,
-			};
-
-			let value = stk.run(|stk| recalc_stmt.compute(stk, ctx, opt, None)).await?;
-
-			let Value::Array(Array(values)) = value else {
-				fail!("Aggregate recalculation select statement return an invalid result");
-			};
-			if values.len() != recalculations.len() {
-				fail!("Aggregate recalculation select statement return an invalid result");
-			}
-
+			let values = self
+				.recalculate(stk, ctx, opt, &group, aggr, &recalculations, extrema.as_ref())
+				.await?;
 			for (idx, v) in values.into_iter().enumerate() {
 				match &mut meta.aggregation_stats[recalculations[idx].stat] {
 					AggregationStat::TimeMin {
@@ -1190,6 +1131,102 @@ impl Document {
 		Ok(())
 	}
 
+	/// Recalculates the minimums and maximums of a group of an aggregated view
+	/// which the records holding them no longer hold, from the counted values
+	/// of the group when they are complete, or else with a query over the
+	/// records of the group.
+	#[allow(clippy::too_many_arguments)]
+	async fn recalculate(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		group: &[Value],
+		aggr: &AggregationAnalysis,
+		recalculations: &[Recalculation],
+		extrema: Option<&ViewExtrema<'_>>,
+	) -> Result<Vec<Value>> {
+		if let Some(extrema) = extrema {
+			let tx = ctx.tx();
+			let mut values = Vec::with_capacity(recalculations.len());
+			for x in recalculations {
+				values.push(extrema.extremum(&tx, x.arg, x.max).await?);
+			}
+			return Ok(values);
+		}
+		// Build the expression which recalculates the values
+		let exprs = recalculations
+			.iter()
+			.map(|x| {
+				Expr::FunctionCall(Box::new(FunctionCall {
+					receiver: Function::Normal(x.function.clone()),
+					arguments: vec![aggr.aggregate_arguments[x.arg].clone()],
+				}))
+			})
+			.collect();
+
+		// Build condition which filters out all values not belonging to the group.
+		let mut condition = None;
+		for (idx, g) in aggr.group_expressions.iter().enumerate() {
+			let expr = Expr::Binary {
+				left: Box::new(g.clone()),
+				op: BinaryOperator::Equal,
+				right: Box::new(group[idx].clone().into_literal()),
+			};
+			if let Some(c) = condition {
+				condition = Some(Expr::Binary {
+					left: Box::new(c),
+					op: BinaryOperator::And,
+					right: Box::new(expr),
+				})
+			} else {
+				condition = Some(expr)
+			}
+		}
+
+		let table_name = self.id()?.table.clone();
+
+		let recalc_stmt = SelectStatement {
+			// SELECT VALUE [recalc1, recalc2,..]
+			fields: Fields::Value(Box::new(Selector {
+				expr: Expr::Literal(Literal::Array(exprs)),
+				alias: None,
+			})),
+			// FROM ONLY table
+			only: true,
+			what: vec![Expr::Table(table_name.clone())],
+			// WHERE group_expr1 = group_value1 && group_expr2 = group_value2 && ..
+			cond: condition.map(Cond),
+			// GROUP ALL
+			group: Some(Groups(Vec::new())),
+			omit: vec![],
+			with: None,
+			split: None,
+			order: None,
+			limit: None,
+			start: None,
+			fetch: None,
+			version: Expr::Literal(Literal::None),
+			timeout: Expr::Literal(Literal::None),
+			partial: false,
+			explain: None,
+			tempfiles: false,
+			valid_time: None,
+			scan_limit: None,
+		};
+
+		let value = stk.run(|stk| recalc_stmt.compute(stk, ctx, opt, None)).await?;
+
+		let Value::Array(Array(values)) = value else {
+			fail!("Aggregate recalculation select statement return an invalid result");
+		};
+		if values.len() != recalculations.len() {
+			fail!("Aggregate recalculation select statement return an invalid result");
+		}
+
+		Ok(values)
+	}
+
 	/// Run triggers which are defined on the view, like events and second order views.
 	#[allow(clippy::too_many_arguments)]
 	pub(crate) async fn run_triggers(
@@ -1231,7 +1268,9 @@ impl Document {
 			record_strategy: RecordStrategy::KeysAndValues,
 			input_data: None,
 			mutated: false,
+			created: false,
 			modified: tokio::sync::OnceCell::new(),
+			clause_fields: Vec::new(),
 			id: Some(id),
 		};
 
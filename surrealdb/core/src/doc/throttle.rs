@@ -0,0 +1,43 @@
+use anyhow::Result;
+
+use crate::ctx::FrozenContext;
+use crate::doc::Document;
+use crate::err::Error;
+use crate::kvs::Admission;
+
+impl Document {
+	/// Asks the write throttle of the table, if the table was defined with a
+	/// `THROTTLE` clause, to admit the write of this document.
+	///
+	/// A write which is queued proceeds within this transaction, and the
+	/// session waits for it before running its next statement, so that the
+	/// transaction is not held open while waiting.
+	pub(super) async fn throttle_write(&self, ctx: &FrozenContext) -> Result<()> {
+		// Get the document table
+		let tb = self.doc_ctx.tb()?;
+		// Check if the table is throttled
+		let Some(throttle) = tb.throttle else {
+			return Ok(());
+		};
+		let Some(throttles) = ctx.get_throttles() else {
+			return Ok(());
+		};
+		// Queue the write alongside the other writes of this session
+		let admission = throttles.admit(
+			tb.namespace_id,
+			tb.database_id,
+			&tb.name,
+			throttle,
+			ctx.session_id(),
+			ctx.config.throttle_max_wait,
+		);
+		match admission {
+			Admission::Now | Admission::After(_) => Ok(()),
+			Admission::Rejected => Err(Error::ThrottleExceeded {
+				table: tb.name.to_string(),
+				rate: throttle.rate,
+			}
+			.into()),
+		}
+	}
+}
@@ -16,6 +16,8 @@ impl Document {
 	) -> Result<Value, IgnoreError> {
 		// Ensure the record actually exists
 		self.check_record_exists()?;
+		// Skip records which have been soft deleted
+		self.check_soft_deleted(stm.with())?;
 		// SECURITY: evaluate the table-level update permission BEFORE any
 		// user-supplied expression in the WHERE clause or data clause.
 		// Otherwise a `WHERE THROW ...` / `SET x = THROW ...` could exfiltrate
@@ -30,6 +30,7 @@ impl Document {
 			// Record created successfully
 			Ok(x) => {
 				ctx.tx().release_last_save_point().await?;
+				self.created = true;
 				return Ok(x);
 			}
 			// We should ignore this record
@@ -152,6 +153,8 @@ impl Document {
 	) -> Result<Value, IgnoreError> {
 		// Ensure the record actually exists
 		self.check_record_exists()?;
+		// Skip records which have been soft deleted
+		self.check_soft_deleted(stm.with())?;
 		// Ensure we can store this type of record
 		self.check_table_type_upsert()?;
 		// SECURITY: evaluate the table-level update permission BEFORE any
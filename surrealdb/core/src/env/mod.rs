@@ -1,2 +1,5 @@
 /// The SurrealDB package version identifier
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The revision of the storage format which this version reads and writes
+pub const STORAGE_VERSION: u16 = crate::kvs::version::MajorVersion::LATEST;
@@ -80,6 +80,18 @@ pub(crate) enum Error {
 	#[error("Parse error: {0}")]
 	InvalidQuery(RenderedParserError),
 
+	/// A compiled query plan could not be executed
+	#[error("Invalid compiled plan: {0}")]
+	InvalidCompiledPlan(String),
+
+	/// A user could not be impersonated
+	#[error("Invalid impersonation: {0}")]
+	InvalidImpersonation(String),
+
+	/// A query could not be run with an idempotency key
+	#[error("Invalid idempotency key: {0}")]
+	InvalidIdempotencyKey(String),
+
 	/// There was an error with the SQL query
 	#[error("Cannot use {} in a CONTENT clause", value.to_sql())]
 	InvalidContent {
@@ -167,6 +179,13 @@ pub(crate) enum Error {
 		message: String,
 	},
 
+	/// The wrong arguments were given for the specified query template
+	#[error("Incorrect arguments for query {name}. {message}")]
+	InvalidQueryArguments {
+		name: String,
+		message: String,
+	},
+
 	/// The wrong quantity or magnitude of arguments was given for the specified
 	/// method
 	#[error("Incorrect arguments for method {name}(). {message}")]
@@ -235,6 +254,82 @@ pub(crate) enum Error {
 	#[error("The transaction was not completed because it exceeded the timeout: {0}")]
 	TransactionTimedout(Duration),
 
+	/// The datastore could not be locked for writes in time
+	#[error("The datastore could not be locked for writes within the timeout: {0}")]
+	WriteLockTimedout(Duration),
+
+	/// A client-managed transaction gave up waiting for a lock, because the
+	/// wait could never end
+	#[error(
+		"A deadlock was detected while waiting for the lock on {resource}, which is held by transaction {holder}"
+	)]
+	DeadlockDetected {
+		resource: String,
+		holder: uuid::Uuid,
+	},
+
+	/// A transaction gave up waiting for a lock which was held for longer
+	/// than the lock wait timeout
+	#[error(
+		"The lock on {resource}, which is held by transaction {holder}, was not released within the timeout: {timeout}"
+	)]
+	LockWaitTimedout {
+		resource: String,
+		holder: uuid::Uuid,
+		timeout: Duration,
+	},
+
+	/// A transaction would have written more than the storage engine accepts
+	#[error(
+		"The transaction is too large: it would write {size} bytes, which is more than the limit of {limit} bytes of the storage engine. Mark bulk statements as NON ATOMIC to split them into several transactions"
+	)]
+	TransactionTooLarge {
+		size: u64,
+		limit: u64,
+	},
+
+	/// The storage engine rejected a transaction for its size
+	#[error(
+		"The transaction is too large: the storage engine rejected it after it wrote {size} bytes. Mark bulk statements as NON ATOMIC to split them into several transactions"
+	)]
+	TransactionRejectedTooLarge {
+		size: u64,
+	},
+
+	/// A statement scanned more rows than its scan budget allows
+	#[error(
+		"The statement scanned more than {limit} rows in the {operator} operator. Add an index, or narrow the statement, to scan fewer rows"
+	)]
+	ScanLimitExceeded {
+		operator: String,
+		limit: u64,
+	},
+
+	/// A write was rejected because the table write throttle was exceeded
+	#[error(
+		"The write throttle of table '{table}' was exceeded, which allows {rate} writes per second"
+	)]
+	ThrottleExceeded {
+		table: String,
+		rate: u64,
+	},
+
+	/// A write was rejected because the tables evicted by least recent use
+	/// exceed the memory limit of the datastore
+	#[error(
+		"The evictable tables exceed the memory limit of {limit} bytes, retry once records have been evicted"
+	)]
+	EvictionLimitExceeded {
+		limit: u64,
+	},
+
+	/// An access grant was not created because the grant rate limit was exceeded
+	#[error("Too many access grants were created for {target}, retry after {retry_after}")]
+	GrantRateLimited {
+		target: String,
+		retry_after: Duration,
+	},
+
 	/// The query did not execute, because the transaction was cancelled
 	#[error("The query was not executed due to a cancelled transaction")]
 	QueryCancelled,
@@ -279,6 +374,12 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested archival policy does not exist
+	#[error("The policy '{name}' does not exist")]
+	PoNotFound {
+		name: String,
+	},
+
 	/// The requested function does not exist
 	#[error("The function '{name}' does not exist")]
 	FcNotFound {
@@ -315,6 +416,12 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested query template does not exist
+	#[error("The query '{name}' does not exist")]
+	QyNotFound {
+		name: String,
+	},
+
 	/// The requested database does not exist
 	#[error("The sequence '{name}' does not exist")]
 	SeqNotFound {
@@ -507,6 +614,12 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The permissions do not allow this query template to be run
+	#[error("You don't have permission to run the {name} query")]
+	QueryPermissions {
+		name: String,
+	},
+
 	/// The permissions do not allow this query to be run on this table
 	#[error("You don't have permission to {op} this file in the `{name}` bucket")]
 	BucketPermissions {
@@ -545,6 +658,12 @@ pub(crate) enum Error {
 		value: String,
 	},
 
+	/// A UNIQUE index can not quarantine conflicting records into its own table
+	#[error("Can not quarantine the conflicts of index '{index}' into the table it is defined on")]
+	IndexQuarantineTable {
+		index: String,
+	},
+
 	/// The specified table is not configured for the type of record being added
 	#[error("Found record: `{record}` which is {}a relation, but expected a {target_type}", if *relation { "" } else { "not " })]
 	TableCheck {
@@ -561,6 +680,30 @@ pub(crate) enum Error {
 		table: String,
 	},
 
+	/// The specified table is not a materialized view, so it can not be refreshed
+	#[error("Cannot refresh the `{table}` table, as it is not a materialized view")]
+	TableIsNotView {
+		table: String,
+	},
+
+	/// A view can not be computed from itself, or from a view computed from it
+	#[error(
+		"Cannot compute the `{view}` view from the `{table}` table, as the view would then depend on itself"
+	)]
+	ViewCycle {
+		view: String,
+		table: String,
+	},
+
+	/// The deletion marker of a soft deleted record was not kept by the schema of its table
+	#[error(
+		"Cannot soft delete `{record}`, as the `{field}` field does not accept the time of deletion"
+	)]
+	SoftDeleteMarker {
+		record: String,
+		field: String,
+	},
+
 	/// The specified field did not conform to the field ASSERT clause
 	#[error(
 		"Found {value} for field `{field}`, with record `{record}`, but field must conform to: {check}",
@@ -573,6 +716,14 @@ pub(crate) enum Error {
 		check: String,
 	},
 
+	/// The specified field value was rejected by the type adapter of the field
+	#[error("Couldn't adapt value for field `{field_name}` of `{record}`: {message}")]
+	FieldAdapt {
+		record: String,
+		field_name: String,
+		message: String,
+	},
+
 	/// The specified value did not conform to the LET type check
 	#[error("Tried to set `${name}`, but couldn't coerce value: {error}")]
 	SetCoerce {
@@ -682,7 +833,6 @@ pub(crate) enum Error {
 
 	/// There was an error processing a remote HTTP request
 	#[error("There was an error processing a remote HTTP request: {0}")]
-	#[cfg_attr(not(any(feature = "http", feature = "jwks")), expect(dead_code))]
 	Http(String),
 
 	/// There was an error processing a value in parallel
@@ -784,9 +934,12 @@ pub(crate) enum Error {
 
 	/// Network target is not allowed
 	#[error("Access to network target '{0}' is not allowed")]
-	#[cfg_attr(not(feature = "http"), expect(dead_code))]
 	NetTargetNotAllowed(String),
 
+	/// The statement was refused by the guardrails of the datastore
+	#[error("The statement was refused by the datastore guardrails: {0}")]
+	Guardrail(String),
+
 	//
 	// Authentication / Signup
 	#[error("There was an error creating the token")]
@@ -822,6 +975,13 @@ pub(crate) enum Error {
 	#[error("There was a problem with signing up")]
 	InvalidSignup,
 
+	/// The credentials were accepted, but the record has enrolled a second
+	/// factor which has to be verified before a token is issued
+	#[error("A one-time code is required to complete the signin")]
+	MfaRequired {
+		challenge: String,
+	},
+
 	// The cluster node already exists
 	#[error("The node '{id}' already exists")]
 	ClAlreadyExists {
@@ -866,6 +1026,12 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested archival policy already exists
+	#[error("The policy '{name}' already exists")]
+	PoAlreadyExists {
+		name: String,
+	},
+
 	/// The requested field already exists
 	#[error("The field '{name}' already exists")]
 	FdAlreadyExists {
@@ -908,6 +1074,12 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested query template already exists
+	#[error("The query '{name}' already exists")]
+	QyAlreadyExists {
+		name: String,
+	},
+
 	/// The requested config already exists
 	#[error("The config for {name} already exists")]
 	CgAlreadyExists {
@@ -926,6 +1098,33 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// A session table was defined outside of a session with an id
+	#[error("The session table '{name}' can only be defined within a session which has an id")]
+	TbSessionRequired {
+		name: String,
+	},
+
+	/// A session table was defined as a view
+	#[error("The session table '{name}' can not be defined as a view")]
+	TbSessionView {
+		name: String,
+	},
+
+	/// A session table was accessed by another session than the one which
+	/// defined it
+	#[error("The session table '{name}' belongs to another session")]
+	TbSessionOwned {
+		name: TableName,
+	},
+
+	/// A table without a valid time was selected `FOR VALID TIME`
+	#[error(
+		"The table '{name}' can not be selected FOR VALID TIME, as it is not defined as BITEMPORAL"
+	)]
+	TbNotBitemporal {
+		name: TableName,
+	},
+
 	/// The requested namespace token already exists
 	#[error("The namespace token '{name}' already exists")]
 	#[allow(dead_code)]
@@ -1312,6 +1511,7 @@ impl Error {
 		matches!(
 			self,
 			Error::FieldCoerce { .. }
+				| Error::FieldAdapt { .. }
 				| Error::FieldValue { .. }
 				| Error::FieldReadonly { .. }
 				| Error::FieldUndefined { .. }
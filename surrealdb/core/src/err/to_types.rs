@@ -55,11 +55,34 @@ pub fn into_types_error(error: Error) -> TypesError {
 			),
 		},
 		InvalidSignup => TypesError::not_allowed(message, AuthError::InvalidSignup),
+		MfaRequired {
+			challenge,
+		} => TypesError::not_allowed(
+			message,
+			AuthError::MfaRequired {
+				challenge,
+			},
+		),
+		InvalidImpersonation(_) => TypesError::not_allowed(message, None),
 
 		// Validation
 		NsEmpty => TypesError::validation(message, ValidationError::NamespaceEmpty),
 		DbEmpty => TypesError::validation(message, ValidationError::DatabaseEmpty),
 		InvalidQuery(_) => TypesError::validation(message, None),
+		FieldAdapt {
+			..
+		} => TypesError::validation(message, None),
+		InvalidCompiledPlan(_) => TypesError::validation(message, None),
+		InvalidIdempotencyKey(_) => TypesError::validation(message, None),
+		TbSessionRequired {
+			..
+		} => TypesError::validation(message, None),
+		TbSessionView {
+			..
+		} => TypesError::validation(message, None),
+		TbNotBitemporal {
+			..
+		} => TypesError::validation(message, None),
 		InvalidParam {
 			name,
 		} => TypesError::validation(
@@ -109,6 +132,10 @@ pub fn into_types_error(error: Error) -> TypesError {
 				name,
 			},
 		),
+		Guardrail(_) => TypesError::not_allowed(message, None),
+		TbSessionOwned {
+			..
+		} => TypesError::not_allowed(message, None),
 
 		// Configuration
 		RealtimeDisabled => {
@@ -128,6 +155,75 @@ pub fn into_types_error(error: Error) -> TypesError {
 				duration: duration.0,
 			},
 		),
+		WriteLockTimedout(duration) => TypesError::query(
+			message,
+			QueryError::TimedOut {
+				duration: duration.0,
+			},
+		),
+		LockWaitTimedout {
+			timeout,
+			..
+		} => TypesError::query(
+			message,
+			QueryError::TimedOut {
+				duration: timeout.0,
+			},
+		),
+		DeadlockDetected {
+			resource,
+			..
+		} => TypesError::query(
+			message,
+			QueryError::DeadlockDetected {
+				resource,
+			},
+		),
+		TransactionTooLarge {
+			size,
+			limit,
+		} => TypesError::query(
+			message,
+			QueryError::TooLarge {
+				size,
+				limit,
+			},
+		),
+		TransactionRejectedTooLarge {
+			..
+		} => TypesError::validation(message, ValidationError::InvalidParams),
+		ScanLimitExceeded {
+			operator,
+			limit,
+		} => TypesError::query(
+			message,
+			QueryError::ScanLimitExceeded {
+				operator,
+				limit,
+			},
+		),
+		ThrottleExceeded {
+			table,
+			rate,
+		} => TypesError::query(
+			message,
+			QueryError::ThrottleExceeded {
+				table,
+				rate,
+			},
+		),
+		EvictionLimitExceeded {
+			..
+		} => TypesError::query(message, None),
+		GrantRateLimited {
+			retry_after,
+			..
+		} => TypesError::query(
+			message,
+			QueryError::RateLimited {
+				retry_after: retry_after.0,
+			},
+		),
 		QueryCancelled => TypesError::query(message, QueryError::Cancelled),
 		QueryNotExecuted {
 			message,
@@ -226,6 +322,9 @@ pub fn into_types_error(error: Error) -> TypesError {
 		EvAlreadyExists {
 			..
 		}
+		| PoAlreadyExists {
+			..
+		}
 		| FdAlreadyExists {
 			..
 		}
@@ -244,6 +343,9 @@ pub fn into_types_error(error: Error) -> TypesError {
 		| PaAlreadyExists {
 			..
 		}
+		| QyAlreadyExists {
+			..
+		}
 		| CgAlreadyExists {
 			..
 		}
@@ -299,10 +401,14 @@ pub fn into_types_error(error: Error) -> TypesError {
 				TypesError::connection(message, ConnectionError::ConnectionFailed)
 			}
 			KvsError::TransactionKeyAlreadyExists => TypesError::already_exists(message, None),
-			KvsError::ReadAndDeleteOnly => TypesError::not_allowed(message, None),
+			KvsError::SnapshotExists(_) => TypesError::already_exists(message, None),
+			KvsError::ReadAndDeleteOnly | KvsError::DatastoreReadOnly => {
+				TypesError::not_allowed(message, None)
+			}
 			KvsError::TransactionTooLarge
 			| KvsError::TransactionKeyTooLarge
-			| KvsError::TransactionRangeTooLarge(_) => {
+			| KvsError::TransactionRangeTooLarge(_)
+			| KvsError::InvalidSnapshotName(_) => {
 				TypesError::validation(message, ValidationError::InvalidParams)
 			}
 			KvsError::TransactionFinished
@@ -313,7 +419,8 @@ pub fn into_types_error(error: Error) -> TypesError {
 			| KvsError::Transaction(_)
 			| KvsError::TimestampInvalid(_)
 			| KvsError::Internal(_)
-			| KvsError::CompactionNotSupported => TypesError::internal(message),
+			| KvsError::CompactionNotSupported
+			| KvsError::SnapshotNotSupported => TypesError::internal(message),
 		},
 
 		// Internal and everything else
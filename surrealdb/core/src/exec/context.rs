@@ -27,10 +27,11 @@ use crate::ctx::{Context, FrozenContext};
 use crate::dbs::{Capabilities, Options};
 use crate::err::Error;
 use crate::exec::function::FunctionRegistry;
+use crate::exec::permission::PermissionTrace;
 use crate::expr::Base;
 use crate::iam::{Action, Auth, ResourceKind};
 use crate::kvs::index::filter_online_indexes;
-use crate::kvs::{Datastore, Transaction};
+use crate::kvs::{Datastore, SessionTables, Transaction};
 use crate::val::{Datetime, TableName, Value};
 
 /// Parameters passed to queries (e.g., `$param` values).
@@ -151,6 +152,12 @@ pub struct RootContext {
 	/// that record dereferences and FETCH resolution honour the same version
 	/// as the source scan.
 	pub(crate) version_stamp: Option<u64>,
+	/// Collects the permission checks made during execution.
+	///
+	/// Set by the `ExplainPermissions` operator so that the scans beneath
+	/// it record which table and field permissions they evaluated, and
+	/// which rows or fields those permissions filtered.
+	pub(crate) permission_trace: Option<Arc<PermissionTrace>>,
 }
 
 impl std::fmt::Debug for RootContext {
@@ -163,6 +170,7 @@ impl std::fmt::Debug for RootContext {
 			.field("current_value", &self.current_value.as_ref().map(|_| "<Value>"))
 			.field("skip_fetch_perms", &self.skip_fetch_perms)
 			.field("version_stamp", &self.version_stamp)
+			.field("permission_trace", &self.permission_trace)
 			.field("ctx", &"<FrozenContext>")
 			.finish()
 	}
@@ -443,6 +451,14 @@ impl ExecutionContext {
 		self.root().ctx.value(key)
 	}
 
+	/// Rewrites the references to the session tables of the current session
+	/// in a value computed at runtime to their scoped names.
+	///
+	/// See [`SessionTables`] for how session tables are named.
+	pub(crate) fn resolve_session_tables(&self, value: &mut Value) {
+		SessionTables::resolve_value(self.ctx(), value);
+	}
+
 	/// Collect all parameter values from the context chain into a HashMap.
 	///
 	/// This walks the FrozenContext parent chain and collects all values,
@@ -539,6 +555,7 @@ impl ExecutionContext {
 				current_value: r.current_value.clone(),
 				skip_fetch_perms: r.skip_fetch_perms,
 				version_stamp: r.version_stamp,
+				permission_trace: r.permission_trace.clone(),
 			}),
 			Self::Namespace(n) => Self::Namespace(NamespaceContext {
 				root: RootContext {
@@ -551,6 +568,7 @@ impl ExecutionContext {
 					current_value: n.root.current_value.clone(),
 					skip_fetch_perms: n.root.skip_fetch_perms,
 					version_stamp: n.root.version_stamp,
+					permission_trace: n.root.permission_trace.clone(),
 				},
 				ns: Arc::clone(&n.ns),
 			}),
@@ -566,6 +584,7 @@ impl ExecutionContext {
 						current_value: d.ns_ctx.root.current_value.clone(),
 						skip_fetch_perms: d.ns_ctx.root.skip_fetch_perms,
 						version_stamp: d.ns_ctx.root.version_stamp,
+						permission_trace: d.ns_ctx.root.permission_trace.clone(),
 					},
 					ns: Arc::clone(&d.ns_ctx.ns),
 				},
@@ -648,6 +667,22 @@ impl ExecutionContext {
 		self.root().version_stamp
 	}
 
+	/// Collect the permission checks made while executing with the derived
+	/// context into the given trace.
+	///
+	/// Used by `EXPLAIN PERMISSIONS` to report which permissions the inner
+	/// statement evaluated.
+	pub(crate) fn with_permission_trace(self, trace: Arc<PermissionTrace>) -> Self {
+		let mut new = self;
+		let root = match &mut new {
+			Self::Root(r) => r,
+			Self::Namespace(n) => &mut n.root,
+			Self::Database(d) => &mut d.ns_ctx.root,
+		};
+		root.permission_trace = Some(trace);
+		new
+	}
+
 	/// Create a new context with an additional parameter.
 	///
 	/// This is used by LET statements to add variables to the execution context.
@@ -5,7 +5,8 @@
 //!
 //! Aggregates are organized by category:
 //! - [`count`]: Row and value counting (COUNT(), COUNT(field))
-//! - [`math`]: Mathematical aggregations (sum, mean, min, max, stddev, variance, median)
+//! - [`math`]: Mathematical aggregations (sum, mean, min, max, stddev, variance, median,
+//!   decimal_sum, decimal_mean)
 //! - [`time`]: Datetime aggregations (min, max)
 //! - [`array`]: Array collection operations (group, join, distinct)
 
@@ -17,7 +18,10 @@ mod time;
 // Re-export all aggregate functions
 pub use array::{ArrayDistinct, ArrayGroup, ArrayJoin};
 pub use count::{Count, CountField};
-pub use math::{MathMax, MathMean, MathMedian, MathMin, MathStddev, MathSum, MathVariance};
+pub use math::{
+	MathDecimalMean, MathDecimalSum, MathMax, MathMean, MathMedian, MathMin, MathStddev, MathSum,
+	MathVariance,
+};
 pub use time::{TimeMax, TimeMin};
 
 use crate::exec::function::FunctionRegistry;
@@ -36,6 +40,8 @@ pub fn register(registry: &mut FunctionRegistry) {
 	registry.register_aggregate(MathStddev);
 	registry.register_aggregate(MathVariance);
 	registry.register_aggregate(MathMedian);
+	registry.register_aggregate(MathDecimalSum);
+	registry.register_aggregate(MathDecimalMean);
 
 	// Time aggregates
 	registry.register_aggregate(TimeMin);
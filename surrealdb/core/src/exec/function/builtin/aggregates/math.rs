@@ -1,12 +1,14 @@
 //! Math aggregate functions.
 //!
 //! Provides aggregates for mathematical operations: sum, mean, min, max,
-//! stddev, variance, and median.
+//! stddev, variance, and median, along with the exact decimal sum and mean.
 
 use anyhow::Result;
+use rust_decimal::Decimal;
 
 use crate::exec::function::{Accumulator, AggregateFunction, Signature};
 use crate::expr::Kind;
+use crate::fnc::math;
 use crate::val::{Number, Value};
 
 // ============================================================================
@@ -162,6 +164,115 @@ impl Accumulator for MeanAccumulator {
 	}
 }
 
+// ============================================================================
+// Decimal sum and mean
+// ============================================================================
+
+/// math::decimal_sum - sums integers and decimals exactly, refusing floats
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MathDecimalSum;
+
+impl AggregateFunction for MathDecimalSum {
+	fn name(&self) -> &'static str {
+		"math::decimal_sum"
+	}
+
+	fn create_accumulator(&self) -> Box<dyn Accumulator> {
+		Box::new(DecimalAccumulator {
+			name: "math::decimal_sum",
+			mean: false,
+			sum: Decimal::ZERO,
+			count: 0,
+		})
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().arg("value", Kind::Number).returns(Kind::Decimal)
+	}
+}
+
+/// math::decimal_mean - averages integers and decimals exactly, refusing floats
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MathDecimalMean;
+
+impl AggregateFunction for MathDecimalMean {
+	fn name(&self) -> &'static str {
+		"math::decimal_mean"
+	}
+
+	fn create_accumulator(&self) -> Box<dyn Accumulator> {
+		Box::new(DecimalAccumulator {
+			name: "math::decimal_mean",
+			mean: true,
+			sum: Decimal::ZERO,
+			count: 0,
+		})
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().arg("value", Kind::Number).returns(Kind::Any)
+	}
+}
+
+#[derive(Debug, Clone)]
+struct DecimalAccumulator {
+	name: &'static str,
+	/// Whether the mean is returned rather than the sum
+	mean: bool,
+	sum: Decimal,
+	count: usize,
+}
+
+impl Accumulator for DecimalAccumulator {
+	fn update(&mut self, value: Value) -> Result<()> {
+		if let Value::Number(n) = value {
+			let n = math::exact_decimal(self.name, n)?;
+			self.sum = math::exact_add(self.name, self.sum, n)?;
+			self.count += 1;
+		}
+		// Skip non-numbers
+		Ok(())
+	}
+
+	fn update_batch(&mut self, values: &[Value]) -> Result<()> {
+		for value in values {
+			self.update(value.clone())?;
+		}
+		Ok(())
+	}
+
+	fn merge(&mut self, other: Box<dyn Accumulator>) -> Result<()> {
+		let other = other
+			.as_any()
+			.downcast_ref::<DecimalAccumulator>()
+			.ok_or_else(|| anyhow::anyhow!("Cannot merge incompatible accumulators"))?;
+		self.sum = math::exact_add(self.name, self.sum, other.sum)?;
+		self.count += other.count;
+		Ok(())
+	}
+
+	fn finalize(&self) -> Result<Value> {
+		if self.mean {
+			math::exact_mean(self.name, self.sum, self.count)
+		} else {
+			Ok(Value::from(self.sum))
+		}
+	}
+
+	fn reset(&mut self) {
+		self.sum = Decimal::ZERO;
+		self.count = 0;
+	}
+
+	fn clone_box(&self) -> Box<dyn Accumulator> {
+		Box::new(self.clone())
+	}
+
+	fn as_any(&self) -> &dyn std::any::Any {
+		self
+	}
+}
+
 // ============================================================================
 // Min
 // ============================================================================
@@ -675,6 +786,43 @@ mod tests {
 		(a - b).abs() < epsilon
 	}
 
+	// -------------------------------------------------------------------------
+	// Decimal sum and mean tests
+	// -------------------------------------------------------------------------
+
+	#[test]
+	fn test_decimal_sum_is_exact() {
+		let mut acc = MathDecimalSum.create_accumulator();
+		for _ in 0..10 {
+			acc.update(Value::Number(Number::Decimal(Decimal::new(1, 1)))).unwrap();
+		}
+		acc.update(Value::Number(Number::Int(2))).unwrap();
+		assert_eq!(acc.finalize().unwrap(), Value::Number(Number::Decimal(Decimal::new(3, 0))));
+	}
+
+	#[test]
+	fn test_decimal_sum_refuses_floats() {
+		let mut acc = MathDecimalSum.create_accumulator();
+		acc.update(Value::Number(Number::Decimal(Decimal::new(1, 1)))).unwrap();
+		acc.update(Value::Number(Number::Float(0.1))).unwrap_err();
+	}
+
+	#[test]
+	fn test_decimal_mean() {
+		let acc = MathDecimalMean.create_accumulator();
+		assert_eq!(acc.finalize().unwrap(), Value::None);
+		let mut acc1 = MathDecimalMean.create_accumulator();
+		acc1.update(Value::Number(Number::Decimal(Decimal::new(10, 0)))).unwrap();
+		let mut acc2 = MathDecimalMean.create_accumulator();
+		acc2.update(Value::Number(Number::Int(0))).unwrap();
+		acc2.update(Value::Number(Number::Int(0))).unwrap();
+		acc1.merge(acc2).unwrap();
+		assert_eq!(
+			acc1.finalize().unwrap(),
+			Value::Number(Number::Decimal(Decimal::new(10, 0) / Decimal::new(3, 0)))
+		);
+	}
+
 	// -------------------------------------------------------------------------
 	// Sum tests
 	// -------------------------------------------------------------------------
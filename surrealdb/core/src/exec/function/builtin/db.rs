@@ -0,0 +1,58 @@
+//! Database functions
+
+use anyhow::Result;
+
+use crate::err::Error;
+use crate::exec::function::{FunctionRegistry, ScalarFunction, Signature};
+use crate::exec::physical_expr::EvalContext;
+use crate::expr::Kind;
+use crate::val::Value;
+
+// =========================================================================
+// db::versionstamp - Get the current versionstamp of the database
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DbVersionstamp;
+
+impl ScalarFunction for DbVersionstamp {
+	fn name(&self) -> &'static str {
+		"db::versionstamp"
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().returns(Kind::Int)
+	}
+
+	fn is_pure(&self) -> bool {
+		false
+	}
+
+	fn is_async(&self) -> bool {
+		true
+	}
+
+	fn invoke(&self, _args: Vec<Value>) -> Result<Value> {
+		Err(anyhow::anyhow!("Function '{}' requires async execution", self.name()))
+	}
+
+	fn invoke_async<'a>(
+		&'a self,
+		ctx: &'a EvalContext<'_>,
+		_args: Vec<Value>,
+	) -> crate::exec::BoxFut<'a, Result<Value>> {
+		Box::pin(async move {
+			let frozen = ctx.exec_ctx.ctx();
+			let opt = ctx.exec_ctx.options().ok_or_else(|| {
+				anyhow::anyhow!(Error::Internal(
+					"No options available for versionstamp operation".to_string()
+				))
+			})?;
+			crate::fnc::db::versionstamp((frozen, opt), ()).await
+		})
+	}
+}
+
+pub fn register(registry: &mut FunctionRegistry) {
+	registry.register(DbVersionstamp);
+}
@@ -5,6 +5,8 @@
 
 use anyhow::Result;
 
+#[cfg(feature = "http")]
+use crate::catalog::ApiMethod;
 use crate::exec::function::FunctionRegistry;
 use crate::exec::physical_expr::EvalContext;
 #[cfg(feature = "http")]
@@ -113,6 +115,10 @@ async fn http_put_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value>
 	let url = url::Url::parse(&uri).map_err(|_| crate::err::Error::InvalidUrl(uri.clone()))?;
 	ctx.check_allowed_net(&url).await?;
 
+	if let Some(res) = http_outbox(ctx, ApiMethod::Put, &uri, body.clone(), &opts).await {
+		return res;
+	}
+
 	http_request(ctx, reqwest::Method::PUT, uri, body, opts).await
 }
 
@@ -135,6 +141,10 @@ async fn http_post_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value
 	let url = url::Url::parse(&uri).map_err(|_| crate::err::Error::InvalidUrl(uri.clone()))?;
 	ctx.check_allowed_net(&url).await?;
 
+	if let Some(res) = http_outbox(ctx, ApiMethod::Post, &uri, body.clone(), &opts).await {
+		return res;
+	}
+
 	http_request(ctx, reqwest::Method::POST, uri, body, opts).await
 }
 
@@ -157,6 +167,10 @@ async fn http_patch_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Valu
 	let url = url::Url::parse(&uri).map_err(|_| crate::err::Error::InvalidUrl(uri.clone()))?;
 	ctx.check_allowed_net(&url).await?;
 
+	if let Some(res) = http_outbox(ctx, ApiMethod::Patch, &uri, body.clone(), &opts).await {
+		return res;
+	}
+
 	http_request(ctx, reqwest::Method::PATCH, uri, body, opts).await
 }
 
@@ -178,6 +192,10 @@ async fn http_delete_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Val
 	let url = url::Url::parse(&uri).map_err(|_| crate::err::Error::InvalidUrl(uri.clone()))?;
 	ctx.check_allowed_net(&url).await?;
 
+	if let Some(res) = http_outbox(ctx, ApiMethod::Delete, &uri, None, &opts).await {
+		return res;
+	}
+
 	http_request(ctx, reqwest::Method::DELETE, uri, None, opts).await
 }
 
@@ -190,6 +208,21 @@ async fn http_delete_impl(_ctx: &EvalContext<'_>, _args: Vec<Value>) -> Result<V
 // HTTP Request implementation
 // =========================================================================
 
+/// Records the request in the outbox when it is made by an event defined with
+/// `OUTBOX`, so that it is only sent once the transaction has committed.
+#[cfg(feature = "http")]
+async fn http_outbox(
+	ctx: &EvalContext<'_>,
+	method: ApiMethod,
+	uri: &str,
+	body: Option<Value>,
+	opts: &Object,
+) -> Option<Result<Value>> {
+	let root = &ctx.exec_ctx.root().ctx;
+	let outbox = root.outbox()?;
+	Some(outbox.record(root, method, uri.to_owned(), body, Some(opts.clone())).await)
+}
+
 #[cfg(feature = "http")]
 #[allow(unused_variables)]
 async fn http_request(
@@ -18,7 +18,6 @@ define_pure_function!(MathLn, "math::ln", (value: Number) -> Number, crate::fnc:
 define_pure_function!(MathLog10, "math::log10", (value: Number) -> Number, crate::fnc::math::log10);
 define_pure_function!(MathLog2, "math::log2", (value: Number) -> Number, crate::fnc::math::log2);
 define_pure_function!(MathRad2deg, "math::rad2deg", (value: Number) -> Number, crate::fnc::math::rad2deg);
-define_pure_function!(MathRound, "math::round", (value: Number) -> Number, crate::fnc::math::round);
 define_pure_function!(MathSign, "math::sign", (value: Number) -> Number, crate::fnc::math::sign);
 define_pure_function!(MathSin, "math::sin", (value: Number) -> Number, crate::fnc::math::sin);
 define_pure_function!(MathSqrt, "math::sqrt", (value: Number) -> Number, crate::fnc::math::sqrt);
@@ -32,11 +31,14 @@ define_pure_function!(MathPow, "math::pow", (base: Number, exponent: Number) ->
 define_pure_function!(MathTop, "math::top", (array: Any, count: Int) -> Any, crate::fnc::math::top);
 
 // Three argument math functions
+define_pure_function!(MathRound, "math::round", (value: Number, ?precision: Int, ?mode: String) -> Number, crate::fnc::math::round);
 define_pure_function!(MathClamp, "math::clamp", (value: Number, min: Number, max: Number) -> Number, crate::fnc::math::clamp);
 define_pure_function!(MathLerp, "math::lerp", (a: Number, b: Number, t: Number) -> Number, crate::fnc::math::lerp);
 define_pure_function!(MathLerpangle, "math::lerpangle", (a: Number, b: Number, t: Number) -> Number, crate::fnc::math::lerpangle);
 
 // Array aggregate math functions (operate on array of numbers)
+define_pure_function!(MathDecimalMean, "math::decimal_mean", (array: Any) -> Any, crate::fnc::math::decimal_mean);
+define_pure_function!(MathDecimalSum, "math::decimal_sum", (array: Any) -> Decimal, crate::fnc::math::decimal_sum);
 define_pure_function!(MathInterquartile, "math::interquartile", (array: Any) -> Number, crate::fnc::math::interquartile);
 define_pure_function!(MathMax, "math::max", (array: Any) -> Number, crate::fnc::math::max);
 define_pure_function!(MathMean, "math::mean", (array: Any) -> Number, crate::fnc::math::mean);
@@ -68,6 +70,8 @@ pub fn register(registry: &mut FunctionRegistry) {
 		MathClamp,
 		MathCos,
 		MathCot,
+		MathDecimalMean,
+		MathDecimalSum,
 		MathDeg2rad,
 		MathFixed,
 		MathFloor,
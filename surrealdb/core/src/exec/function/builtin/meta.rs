@@ -1,11 +1,72 @@
 //! Meta functions (aliases for record functions)
 
-use crate::exec::function::FunctionRegistry;
+use anyhow::Result;
+use reblessive::tree::TreeStack;
+
+use crate::exec::function::{FunctionRegistry, ScalarFunction, Signature};
+use crate::exec::physical_expr::EvalContext;
+use crate::expr::Kind;
+use crate::fnc::args::FromArgs;
+use crate::val::Value;
 use crate::{define_pure_function, register_functions};
 
 define_pure_function!(MetaId, "meta::id", (record: Any) -> Any, crate::fnc::record::id);
 define_pure_function!(MetaTb, "meta::tb", (record: Any) -> String, crate::fnc::record::tb);
 
+// =========================================================================
+// meta::lineage - Get the last writer of a field of a record
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetaLineage;
+
+impl ScalarFunction for MetaLineage {
+	fn name(&self) -> &'static str {
+		"meta::lineage"
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().arg("record", Kind::Any).arg("field", Kind::String).returns(Kind::Any)
+	}
+
+	fn is_pure(&self) -> bool {
+		false
+	}
+
+	fn is_async(&self) -> bool {
+		true
+	}
+
+	fn invoke(&self, _args: Vec<Value>) -> Result<Value> {
+		Err(anyhow::anyhow!("Function '{}' requires async execution", self.name()))
+	}
+
+	fn invoke_async<'a>(
+		&'a self,
+		ctx: &'a EvalContext<'_>,
+		args: Vec<Value>,
+	) -> crate::exec::BoxFut<'a, Result<Value>> {
+		Box::pin(async move {
+			use crate::doc::CursorDoc;
+			let args = FromArgs::from_args("meta::lineage", args)?;
+			let frozen = ctx.exec_ctx.ctx();
+			let opt = ctx.exec_ctx.options();
+			let doc = ctx
+				.document_root
+				.or(ctx.current_value)
+				.map(|v| CursorDoc::new(None, None, v.clone()));
+			let mut stack = TreeStack::new();
+			stack
+				.enter(|stk| async move {
+					crate::fnc::record::lineage((stk, frozen, opt, doc.as_ref()), args).await
+				})
+				.finish()
+				.await
+		})
+	}
+}
+
 pub fn register(registry: &mut FunctionRegistry) {
 	register_functions!(registry, MetaId, MetaTb);
+	registry.register(MetaLineage);
 }
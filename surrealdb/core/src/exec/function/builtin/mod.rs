@@ -10,6 +10,7 @@ mod bytes;
 mod count;
 mod crypto;
 mod crypto_async;
+mod db;
 mod duration;
 mod encoding;
 mod eval;
@@ -21,6 +22,7 @@ mod meta;
 mod not;
 mod object;
 mod parse;
+mod query;
 mod rand;
 mod record;
 mod schema;
@@ -46,6 +48,7 @@ pub fn register_all(registry: &mut FunctionRegistry) {
 	count::register(registry);
 	crypto::register(registry);
 	crypto_async::register(registry);
+	db::register(registry);
 	duration::register(registry);
 	encoding::register(registry);
 	eval::register(registry);
@@ -57,6 +60,7 @@ pub fn register_all(registry: &mut FunctionRegistry) {
 	not::register(registry);
 	object::register(registry);
 	parse::register(registry);
+	query::register(registry);
 	rand::register(registry);
 	record::register(registry);
 	schema::register(registry);
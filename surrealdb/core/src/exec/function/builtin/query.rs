@@ -0,0 +1,163 @@
+//! Query template functions for the streaming executor.
+//!
+//! The query of a template is planned once for each version of the template,
+//! and the plan is kept in the datastore cache under the plan id of that
+//! version, so running a template neither parses nor plans its query again.
+//! The plan is compiled without a transaction, so it resolves its tables and
+//! indexes when it runs, and stays valid when the schema of those tables
+//! changes. A template which can not be planned is computed instead.
+
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use reblessive::TreeStack;
+
+use crate::catalog::QueryDefinition;
+use crate::dbs::NewPlannerStrategy;
+use crate::err::Error;
+use crate::exec::function::{FunctionRegistry, ScalarFunction, Signature};
+use crate::exec::physical_expr::EvalContext;
+use crate::exec::physical_expr::function::helpers::is_permitted;
+use crate::exec::planner::expr_to_physical_expr_at_depth;
+use crate::exec::{ContextLevel, PhysicalExpr};
+use crate::expr::{FlowResultExt as _, Kind};
+use crate::fnc::args::{FromArgs, Optional};
+use crate::iam::{Action, AuthLimit};
+use crate::kvs::cache;
+use crate::val::{Object, Value};
+
+/// Fetch the plan of a query template, planning and caching it on first use.
+///
+/// Returns `None` when the query can not be planned by the streaming executor.
+async fn plan(
+	ctx: &EvalContext<'_>,
+	val: &QueryDefinition,
+	depth: u32,
+) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+	let frozen = ctx.exec_ctx.ctx();
+	if *frozen.new_planner_strategy() == NewPlannerStrategy::ComputeOnly {
+		return Ok(None);
+	}
+	// Plans are cached per version of the template, and per planning depth, as
+	// the depth is recorded onto the planned nodes
+	let db_ctx = ctx.exec_ctx.database()?;
+	let (ns, db) = (db_ctx.ns_ctx.ns.namespace_id, db_ctx.db.database_id);
+	let key = cache::ds::Lookup::Qyp(ns, db, val.name.as_str(), val.plan_id, depth);
+	let cache = frozen.get_cache();
+	if let Some(entry) = cache.as_ref().and_then(|c| c.get(&key)) {
+		return entry.try_into_qyp();
+	}
+	let plan = match expr_to_physical_expr_at_depth(val.query.clone(), frozen, depth).await {
+		Ok(plan) => Some(plan),
+		Err(Error::PlannerUnsupported(_) | Error::PlannerUnimplemented(_)) => None,
+		Err(e) => return Err(e.into()),
+	};
+	if let Some(cache) = cache {
+		cache.insert(key, cache::ds::Entry::Qyp(plan.clone()));
+	}
+	Ok(plan)
+}
+
+// =========================================================================
+// query::run - Run a query template defined with DEFINE QUERY
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueryRun;
+
+impl ScalarFunction for QueryRun {
+	fn name(&self) -> &'static str {
+		"query::run"
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().arg("name", Kind::String).optional("args", Kind::Object).returns(Kind::Any)
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		ContextLevel::Database
+	}
+
+	fn is_pure(&self) -> bool {
+		false
+	}
+
+	fn is_async(&self) -> bool {
+		true
+	}
+
+	fn invoke(&self, _args: Vec<Value>) -> Result<Value> {
+		Err(anyhow::anyhow!("Function '{}' requires async execution", self.name()))
+	}
+
+	fn invoke_async<'a>(
+		&'a self,
+		ctx: &'a EvalContext<'_>,
+		args: Vec<Value>,
+	) -> crate::exec::BoxFut<'a, Result<Value>> {
+		Box::pin(async move {
+			let frozen = ctx.exec_ctx.ctx();
+			let opt = ctx.exec_ctx.options().ok_or_else(|| {
+				anyhow::anyhow!(Error::Internal("No options available for query::run".to_string()))
+			})?;
+
+			// Convert args using FromArgs (same conversion the legacy dispatch uses)
+			let (name, Optional(args)): (String, Optional<Object>) =
+				FromArgs::from_args("query::run", args)?;
+
+			// Get the query definition
+			let db_ctx = ctx.exec_ctx.database()?;
+			let (ns, db) = (db_ctx.ns_ctx.ns.namespace_id, db_ctx.db.database_id);
+			let val = ctx.txn().get_db_query(ns, db, &name).await?;
+
+			// The query is one re-entry deeper than this call
+			let depth = ctx.plan_depth + 1;
+			let Some(plan) = plan(ctx, &val, depth).await? else {
+				// The stored query is computed, which requires a reblessive stack
+				let mut stack = TreeStack::new();
+				return stack
+					.enter(|stk| async move {
+						crate::fnc::query::run((stk, frozen, opt), (name, Optional(args))).await
+					})
+					.finish()
+					.await;
+			};
+
+			// Limit the auth to that of the user which defined the query
+			let limit = AuthLimit::try_from(&val.auth_limit)?;
+			let limited = ctx.exec_ctx.with_limited_auth(&limit);
+			let limited_ctx = EvalContext {
+				exec_ctx: &limited,
+				..ctx.clone()
+			};
+
+			// Check permissions
+			if limited.should_check_perms(Action::View)?
+				&& !is_permitted(&val.permissions, &limited_ctx).await.catch_return()?
+			{
+				bail!(Error::QueryPermissions {
+					name,
+				});
+			}
+
+			// Bind the arguments to an isolated context, and run the plan
+			let isolated = crate::fnc::query::bind_args(limited.ctx(), &val, &name, args)?;
+			let isolated = limited.with_new_ctx(isolated);
+			let eval_ctx = EvalContext {
+				exec_ctx: &isolated,
+				current_value: None,
+				local_params: None,
+				recursion_ctx: None,
+				document_root: None,
+				skip_fetch_perms: ctx.skip_fetch_perms,
+				computing_record: ctx.computing_record.clone(),
+				plan_depth: depth,
+			};
+			plan.evaluate(eval_ctx).await.catch_return()
+		})
+	}
+}
+
+pub fn register(registry: &mut FunctionRegistry) {
+	registry.register(QueryRun);
+}
@@ -10,6 +10,7 @@ use crate::fnc::args::FromArgs;
 use crate::val::Value;
 use crate::{define_pure_function, register_functions};
 
+define_pure_function!(RecordCreatedAt, "record::created_at", (record: Any) -> Any, crate::fnc::record::created_at);
 define_pure_function!(RecordId, "record::id", (record: Any) -> Any, crate::fnc::record::id);
 define_pure_function!(RecordTb, "record::tb", (record: Any) -> String, crate::fnc::record::tb);
 define_pure_function!(RecordTable, "record::table", (record: Any) -> String, crate::fnc::record::tb);
@@ -121,7 +122,7 @@ impl ScalarFunction for RecordIsEdge {
 }
 
 pub fn register(registry: &mut FunctionRegistry) {
-	register_functions!(registry, RecordId, RecordTb, RecordTable);
+	register_functions!(registry, RecordCreatedAt, RecordId, RecordTb, RecordTable);
 	registry.register(RecordExists);
 	registry.register(RecordIsEdge);
 }
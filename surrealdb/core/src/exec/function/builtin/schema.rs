@@ -8,6 +8,47 @@ use crate::expr::Kind;
 use crate::fnc::args::FromArgs;
 use crate::val::Value;
 
+// =========================================================================
+// schema::openapi - Describe the key-value endpoints of the database
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchemaOpenapi;
+
+impl ScalarFunction for SchemaOpenapi {
+	fn name(&self) -> &'static str {
+		"schema::openapi"
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().returns(Kind::Object)
+	}
+
+	fn is_pure(&self) -> bool {
+		false
+	}
+
+	fn is_async(&self) -> bool {
+		true
+	}
+
+	fn invoke(&self, _args: Vec<Value>) -> Result<Value> {
+		Err(anyhow::anyhow!("Function '{}' requires async execution", self.name()))
+	}
+
+	fn invoke_async<'a>(
+		&'a self,
+		ctx: &'a EvalContext<'_>,
+		_args: Vec<Value>,
+	) -> crate::exec::BoxFut<'a, Result<Value>> {
+		Box::pin(async move {
+			let frozen = ctx.exec_ctx.ctx();
+			let opt = ctx.exec_ctx.options();
+			crate::fnc::schema::openapi((frozen, opt), ()).await
+		})
+	}
+}
+
 // =========================================================================
 // schema::table::exists - Check if a table exists
 // =========================================================================
@@ -50,6 +91,176 @@ impl ScalarFunction for SchemaTableExists {
 	}
 }
 
+// =========================================================================
+// schema::table::stats - Fetch the statistics of a table
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchemaTableStats;
+
+impl ScalarFunction for SchemaTableStats {
+	fn name(&self) -> &'static str {
+		"schema::table::stats"
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().arg("table", Kind::String).returns(Kind::Object)
+	}
+
+	fn is_pure(&self) -> bool {
+		false
+	}
+
+	fn is_async(&self) -> bool {
+		true
+	}
+
+	fn invoke(&self, _args: Vec<Value>) -> Result<Value> {
+		Err(anyhow::anyhow!("Function '{}' requires async execution", self.name()))
+	}
+
+	fn invoke_async<'a>(
+		&'a self,
+		ctx: &'a EvalContext<'_>,
+		args: Vec<Value>,
+	) -> crate::exec::BoxFut<'a, Result<Value>> {
+		Box::pin(async move {
+			let args = FromArgs::from_args("schema::table::stats", args)?;
+			let frozen = ctx.exec_ctx.ctx();
+			let opt = ctx.exec_ctx.options();
+			crate::fnc::schema::table::stats((frozen, opt), args).await
+		})
+	}
+}
+
+// =========================================================================
+// schema::advisor::start - Start capturing the workload of the database
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchemaAdvisorStart;
+
+impl ScalarFunction for SchemaAdvisorStart {
+	fn name(&self) -> &'static str {
+		"schema::advisor::start"
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().returns(Kind::None)
+	}
+
+	fn is_pure(&self) -> bool {
+		false
+	}
+
+	fn is_async(&self) -> bool {
+		true
+	}
+
+	fn invoke(&self, _args: Vec<Value>) -> Result<Value> {
+		Err(anyhow::anyhow!("Function '{}' requires async execution", self.name()))
+	}
+
+	fn invoke_async<'a>(
+		&'a self,
+		ctx: &'a EvalContext<'_>,
+		_args: Vec<Value>,
+	) -> crate::exec::BoxFut<'a, Result<Value>> {
+		Box::pin(async move {
+			let frozen = ctx.exec_ctx.ctx();
+			let opt = ctx.exec_ctx.options();
+			crate::fnc::schema::advisor::start((frozen, opt), ()).await
+		})
+	}
+}
+
+// =========================================================================
+// schema::advisor::stop - Stop capturing the workload of the database
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchemaAdvisorStop;
+
+impl ScalarFunction for SchemaAdvisorStop {
+	fn name(&self) -> &'static str {
+		"schema::advisor::stop"
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().returns(Kind::None)
+	}
+
+	fn is_pure(&self) -> bool {
+		false
+	}
+
+	fn is_async(&self) -> bool {
+		true
+	}
+
+	fn invoke(&self, _args: Vec<Value>) -> Result<Value> {
+		Err(anyhow::anyhow!("Function '{}' requires async execution", self.name()))
+	}
+
+	fn invoke_async<'a>(
+		&'a self,
+		ctx: &'a EvalContext<'_>,
+		_args: Vec<Value>,
+	) -> crate::exec::BoxFut<'a, Result<Value>> {
+		Box::pin(async move {
+			let frozen = ctx.exec_ctx.ctx();
+			let opt = ctx.exec_ctx.options();
+			crate::fnc::schema::advisor::stop((frozen, opt), ()).await
+		})
+	}
+}
+
+// =========================================================================
+// schema::advisor::recommendations - Recommend indexes for the captured workload
+// =========================================================================
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchemaAdvisorRecommendations;
+
+impl ScalarFunction for SchemaAdvisorRecommendations {
+	fn name(&self) -> &'static str {
+		"schema::advisor::recommendations"
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new().returns(Kind::Array(Box::new(Kind::Object), None))
+	}
+
+	fn is_pure(&self) -> bool {
+		false
+	}
+
+	fn is_async(&self) -> bool {
+		true
+	}
+
+	fn invoke(&self, _args: Vec<Value>) -> Result<Value> {
+		Err(anyhow::anyhow!("Function '{}' requires async execution", self.name()))
+	}
+
+	fn invoke_async<'a>(
+		&'a self,
+		ctx: &'a EvalContext<'_>,
+		_args: Vec<Value>,
+	) -> crate::exec::BoxFut<'a, Result<Value>> {
+		Box::pin(async move {
+			let frozen = ctx.exec_ctx.ctx();
+			let opt = ctx.exec_ctx.options();
+			crate::fnc::schema::advisor::recommendations((frozen, opt), ()).await
+		})
+	}
+}
+
 pub fn register(registry: &mut FunctionRegistry) {
+	registry.register(SchemaOpenapi);
 	registry.register(SchemaTableExists);
+	registry.register(SchemaTableStats);
+	registry.register(SchemaAdvisorStart);
+	registry.register(SchemaAdvisorStop);
+	registry.register(SchemaAdvisorRecommendations);
 }
@@ -276,6 +276,36 @@ macro_rules! define_pure_function {
 		}
 	};
 
+	// One required + two optional: (a: T1, ?b: T2, ?c: T3) -> ReturnType
+	(
+		$struct_name:ident,
+		$func_name:literal,
+		($arg1_name:ident : $arg1_type:ident, ? $arg2_name:ident : $arg2_type:ident, ? $arg3_name:ident : $arg3_type:ident) -> $ret:ident,
+		$impl_path:path
+	) => {
+		#[derive(Debug, Clone, Copy, Default)]
+		pub struct $struct_name;
+
+		impl $crate::exec::function::ScalarFunction for $struct_name {
+			fn name(&self) -> &'static str {
+				$func_name
+			}
+
+			fn signature(&self) -> $crate::exec::function::Signature {
+				$crate::exec::function::Signature::new()
+					.arg(stringify!($arg1_name), $crate::expr::Kind::$arg1_type)
+					.optional(stringify!($arg2_name), $crate::expr::Kind::$arg2_type)
+					.optional(stringify!($arg3_name), $crate::expr::Kind::$arg3_type)
+					.returns($crate::expr::Kind::$ret)
+			}
+
+			fn invoke(&self, args: Vec<$crate::val::Value>) -> anyhow::Result<$crate::val::Value> {
+				let args = $crate::fnc::args::FromArgs::from_args($func_name, args)?;
+				$impl_path(args)
+			}
+		}
+	};
+
 	// Two optional arguments: (?a: T1, ?b: T2) -> ReturnType
 	(
 		$struct_name:ident,
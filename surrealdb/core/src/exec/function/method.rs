@@ -628,6 +628,7 @@ pub fn build_method_registry(funcs: &FunctionRegistry) -> MethodRegistry {
 	// RecordId methods
 	// =====================================================================
 	m.register_typed("exists", ValueKind::Record, get(funcs, "record::exists"));
+	m.register_typed("created_at", ValueKind::Record, get(funcs, "record::created_at"));
 	m.register_typed("id", ValueKind::Record, get(funcs, "record::id"));
 	m.register_typed("tb", ValueKind::Record, get(funcs, "record::tb"));
 	m.register_typed("table", ValueKind::Record, get(funcs, "record::table"));
@@ -237,8 +237,9 @@ pub fn select_access_path(
 	with_hints: Option<&With>,
 	direction: ScanDirection,
 ) -> AccessPath {
-	// WITH NOINDEX forces table scan
-	if matches!(with_hints, Some(With::NoIndex)) {
+	// WITH NOINDEX forces table scan, as does WITH DELETED, since soft
+	// deleted records are left out of indexes
+	if matches!(with_hints, Some(With::NoIndex | With::Deleted)) {
 		return AccessPath::TableScan;
 	}
 
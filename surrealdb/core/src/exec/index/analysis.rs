@@ -91,7 +91,7 @@ impl<'a> IndexAnalyzer<'a> {
 		let cond = cond?;
 
 		// Check for WITH NOINDEX
-		if matches!(self.with_hints, Some(With::NoIndex)) {
+		if matches!(self.with_hints, Some(With::NoIndex | With::Deleted)) {
 			return None;
 		}
 
@@ -185,7 +185,7 @@ impl<'a> IndexAnalyzer<'a> {
 	) -> Option<AccessPath> {
 		let cond = cond?;
 
-		if matches!(self.with_hints, Some(With::NoIndex)) {
+		if matches!(self.with_hints, Some(With::NoIndex | With::Deleted)) {
 			return None;
 		}
 
@@ -289,7 +289,7 @@ impl<'a> IndexAnalyzer<'a> {
 	) -> Option<AccessPath> {
 		let cond = cond?;
 
-		if matches!(self.with_hints, Some(With::NoIndex)) {
+		if matches!(self.with_hints, Some(With::NoIndex | With::Deleted)) {
 			return None;
 		}
 
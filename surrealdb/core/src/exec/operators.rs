@@ -25,6 +25,7 @@ mod project_value;
 pub(crate) mod recursion;
 mod r#return;
 pub(crate) mod scan;
+mod scan_limit;
 mod sequence;
 mod sleep;
 mod sort;
@@ -50,7 +51,7 @@ pub use compute::Compute;
 pub use current_value_source::CurrentValueSource;
 #[cfg_attr(not(feature = "gql"), allow(unused_imports))]
 pub use distinct::Distinct;
-pub use explain::{AnalyzePlan, ExplainPlan};
+pub use explain::{AnalyzePlan, ExplainPlan, PermissionsPlan};
 pub use expr::ExprPlan;
 pub use fetch::Fetch;
 pub use filter::Filter;
@@ -62,7 +63,8 @@ pub use graph::{
 };
 pub use ifelse::IfElsePlan;
 pub use info::{
-	DatabaseInfoPlan, IndexInfoPlan, NamespaceInfoPlan, RootInfoPlan, TableInfoPlan, UserInfoPlan,
+	DatabaseInfoPlan, IndexInfoPlan, IndexStatsInfoPlan, LocksInfoPlan, NamespaceInfoPlan,
+	RootInfoPlan, TableInfoPlan, UserInfoPlan,
 };
 #[cfg_attr(not(feature = "gql"), allow(unused_imports))]
 pub use join::{HashJoin, JoinType};
@@ -83,6 +85,7 @@ pub use scan::{
 	DynamicScan, EdgeTableSpec, EmptyScan, FullTextScan, GraphEdgeScan, GraphScanOutput, IndexScan,
 	KnnScan, RecordIdScan, ReferenceScan, ReferenceScanOutput, TableScan, UnionIndexScan,
 };
+pub use scan_limit::ScanLimitScope;
 pub use sequence::SequencePlan;
 pub use sleep::SleepPlan;
 #[cfg(all(storage, not(target_family = "wasm")))]
@@ -93,6 +96,7 @@ pub use sort::{
 };
 pub use source_expr::SourceExpr;
 pub use split::Split;
+pub(crate) use timeout::OnExpiry;
 pub use timeout::Timeout;
 pub use union::Union;
 pub use unwrap_exactly_one::UnwrapExactlyOne;
@@ -3,6 +3,8 @@
 //! - [`ExplainPlan`] formats a query plan without executing it (read-only).
 //! - [`AnalyzePlan`] executes the plan, drains it to completion, then formats the plan tree
 //!   together with collected [`OperatorMetrics`].
+//! - [`PermissionsPlan`] executes the plan, drains it to completion, then reports the table and
+//!   field permissions which were evaluated, and the rows and fields which they filtered.
 
 use std::fmt::Write;
 use std::sync::Arc;
@@ -11,6 +13,7 @@ use futures::{StreamExt, stream};
 use surrealdb_types::ToSql;
 
 use crate::exec::context::{ContextLevel, ExecutionContext};
+use crate::exec::permission::{PermissionCheck, PermissionTrace};
 use crate::exec::{
 	AccessMode, CardinalityHint, ExecOperator, FlowResult, OperatorMetrics, ValueBatch,
 	ValueBatchStream, buffer_stream,
@@ -199,6 +202,173 @@ impl ExecOperator for AnalyzePlan {
 	}
 }
 
+// =========================================================================
+// EXPLAIN PERMISSIONS
+// =========================================================================
+
+/// EXPLAIN PERMISSIONS operator - executes the plan while tracing the
+/// permission checks made beneath it, then reports those checks.
+///
+/// Like [`AnalyzePlan`], the inner plan is drained to completion. Every table
+/// and field permission which is checked along the way is recorded in a
+/// [`PermissionTrace`], together with the number of rows or fields it
+/// filtered. The report also includes the auth parameters the permissions
+/// were evaluated with, which makes it possible to see why a record user can
+/// or can not see a row.
+#[derive(Debug)]
+pub struct PermissionsPlan {
+	/// The inner statement's planned content
+	pub plan: Arc<dyn ExecOperator>,
+	/// The output format
+	pub format: ExplainFormat,
+}
+
+impl ExecOperator for PermissionsPlan {
+	fn name(&self) -> &'static str {
+		"ExplainPermissions"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		match self.format {
+			ExplainFormat::Text => vec![("format".to_string(), "TEXT".to_string())],
+			ExplainFormat::Json => vec![("format".to_string(), "JSON".to_string())],
+		}
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		// We actually execute the inner plan, so inherit its requirements
+		self.plan.required_context()
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		// We execute the inner plan, so inherit its access mode
+		self.plan.access_mode()
+	}
+
+	fn cardinality_hint(&self) -> CardinalityHint {
+		CardinalityHint::AtMostOne
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		vec![&self.plan]
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let trace = Arc::new(PermissionTrace::default());
+		let inner_ctx = ctx.clone().with_permission_trace(Arc::clone(&trace));
+		let mut inner_stream = buffer_stream(
+			self.plan.execute(&inner_ctx)?,
+			self.plan.access_mode(),
+			self.plan.cardinality_hint(),
+			ctx.root().ctx.config.operator_buffer_size,
+		);
+		let auth = permission_auth(ctx);
+		let format = self.format;
+
+		let permissions_stream = async_stream::try_stream! {
+			// Drain all batches from the inner plan so every check is traced
+			let mut total_rows: u64 = 0;
+			while let Some(batch_result) = inner_stream.next().await {
+				match batch_result {
+					Ok(batch) => {
+						total_rows += batch.values.len() as u64;
+					}
+					Err(ControlFlow::Break | ControlFlow::Return(_)) => break,
+					Err(ControlFlow::Continue) => continue,
+					Err(e @ ControlFlow::Err(_)) => Err(e)?,
+				}
+			}
+
+			let checks = trace.checks();
+			let output = match format {
+				ExplainFormat::Text => {
+					Value::String(format_permissions_text(&auth, &checks, total_rows).into())
+				}
+				ExplainFormat::Json => {
+					let mut obj = Object::default();
+					obj.insert("auth", Value::Object(auth));
+					let checks: Vec<Value> = checks.iter().map(format_permission_check_json).collect();
+					obj.insert("permissions", Value::Array(Array::from(checks)));
+					obj.insert("total_rows", Value::from(total_rows as i64));
+					Value::Object(obj)
+				}
+			};
+
+			yield ValueBatch {
+				values: vec![output],
+			};
+		};
+
+		Ok(Box::pin(permissions_stream))
+	}
+
+	fn is_scalar(&self) -> bool {
+		true
+	}
+}
+
+/// The auth parameters which permission expressions are evaluated with.
+fn permission_auth(ctx: &ExecutionContext) -> Object {
+	let auth = ctx.auth();
+	let mut obj = Object::default();
+	obj.insert("id", Value::String(auth.id().into()));
+	obj.insert("level", Value::String(auth.level().to_string().into()));
+	if let Some(role) = auth.max_role() {
+		obj.insert("role", Value::String(role.to_string().into()));
+	}
+	for param in ["access", "auth", "token"] {
+		if let Some(value) = ctx.value(param).filter(|v| !v.is_nullish()) {
+			obj.insert(param, value.clone());
+		}
+	}
+	obj
+}
+
+/// Format the traced permission checks as human-readable text.
+fn format_permissions_text(auth: &Object, checks: &[PermissionCheck], total_rows: u64) -> String {
+	let mut output = String::new();
+	let _ = write!(output, "Auth:");
+	for (i, (key, value)) in auth.iter().enumerate() {
+		let sep = if i > 0 {
+			","
+		} else {
+			""
+		};
+		let _ = write!(output, "{sep} {key}: {}", value.to_sql());
+	}
+	let _ = writeln!(output);
+	for check in checks {
+		match &check.field {
+			Some(field) => {
+				let _ = write!(output, "Field {}.{}", check.table, field);
+			}
+			None => {
+				let _ = write!(output, "Table {}", check.table);
+			}
+		}
+		let _ = writeln!(
+			output,
+			": {} {{evaluated: {}, denied: {}}}",
+			check.expression, check.evaluated, check.denied
+		);
+	}
+	let _ = write!(output, "Total rows: {}", total_rows);
+	output
+}
+
+/// Format a traced permission check as a JSON object.
+fn format_permission_check_json(check: &PermissionCheck) -> Value {
+	let mut obj = Object::default();
+	obj.insert("table", Value::String(check.table.clone().into()));
+	if let Some(field) = &check.field {
+		obj.insert("field", Value::String(field.clone().into()));
+	}
+	obj.insert("expression", Value::String(check.expression.clone().into()));
+	obj.insert("evaluated", Value::from(check.evaluated as i64));
+	obj.insert("denied", Value::from(check.denied as i64));
+	Value::Object(obj)
+}
+
 // =========================================================================
 // Text Formatting
 // =========================================================================
@@ -1,7 +1,9 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use futures::StreamExt;
 
+use crate::catalog::Record;
 use crate::catalog::providers::TableProvider;
 use crate::exec::permission::{
 	PhysicalPermission, check_permission_for_value, convert_permission_to_physical_runtime,
@@ -22,6 +24,10 @@ use crate::val::{RecordId, Value};
 /// The FETCH clause replaces record IDs with their full record data.
 /// For example, if a field contains `author:tobie`, FETCH will replace
 /// it with the full author record `{ id: author:tobie, name: 'Tobie', ... }`.
+///
+/// The linked records of each batch are loaded ahead of time with batched
+/// multi-gets (see [`prefetch_links`]), so that the records are not read from
+/// the datastore one by one.
 #[derive(Debug, Clone)]
 pub struct Fetch {
 	pub(crate) input: Arc<dyn ExecOperator>,
@@ -98,16 +104,20 @@ impl ExecOperator for Fetch {
 			let ctx = ctx.clone();
 
 			async move {
-				let batch = batch_result?;
-				let mut fetched_values = Vec::with_capacity(batch.values.len());
-
-				for value in batch.values {
-					let fetched = fetch_fields(&ctx, value, &fields).await?;
-					fetched_values.push(fetched);
+				let mut values = batch_result?.values;
+				let mut start = 0;
+
+				while start < values.len() {
+					// Load the links of as many values as fit in one prefetch
+					let count = prefetch_links(&ctx, &values[start..], &fields).await?;
+					for value in &mut values[start..start + count] {
+						fetch_fields(&ctx, value, &fields).await?;
+					}
+					start += count;
 				}
 
 				Ok(ValueBatch {
-					values: fetched_values,
+					values,
 				})
 			}
 		});
@@ -119,13 +129,172 @@ impl ExecOperator for Fetch {
 /// Fetch all specified fields for a value.
 async fn fetch_fields(
 	ctx: &ExecutionContext,
-	mut value: Value,
+	value: &mut Value,
 	fields: &[Idiom],
-) -> crate::expr::FlowResult<Value> {
+) -> crate::expr::FlowResult<()> {
 	for field in fields {
-		fetch_field_path(ctx, &mut value, &field.0).await?;
+		fetch_field_path(ctx, value, &field.0).await?;
+	}
+	Ok(())
+}
+
+/// The maximum number of records loaded by a single prefetch.
+///
+/// Prefetched records are held in the transaction cache until they are
+/// dereferenced, so a prefetch never loads more records than the cache can
+/// hold alongside the entries already in it.
+fn prefetch_limit(ctx: &ExecutionContext) -> usize {
+	let config = &ctx.root().ctx.config;
+	config.fetch_batch_size.min(config.transaction_cache_size / 2).max(1)
+}
+
+/// Load the records linked from the fetched fields of a run of values into
+/// the transaction cache, returning the number of values which were covered.
+///
+/// The record ids which [`fetch_field_path`] would dereference are collected
+/// from as many values as fit within [`prefetch_limit`], and loaded with a
+/// single multi-get. Where a path continues through a linked record, the
+/// loaded record is walked in turn, so that links nested several levels deep
+/// are loaded in one round trip per level rather than one per record. At least
+/// one value is always covered, so that the caller makes progress.
+///
+/// Records are loaded without permission checks, but are only ever returned
+/// through [`fetch_record`], which applies them when the record is
+/// dereferenced.
+async fn prefetch_links(
+	ctx: &ExecutionContext,
+	values: &[Value],
+	fields: &[Idiom],
+) -> crate::expr::FlowResult<usize> {
+	// Versioned reads are not cached, so there is nothing to load ahead
+	if ctx.version_stamp().is_some() {
+		return Ok(values.len());
+	}
+	let limit = prefetch_limit(ctx);
+	let mut links = Vec::new();
+	let mut count = 0;
+	for value in values {
+		for field in fields {
+			collect_links(value, &field.0, &mut links);
+		}
+		count += 1;
+		if links.len() >= limit {
+			break;
+		}
 	}
-	Ok(value)
+	let mut seen = HashSet::new();
+	while !links.is_empty() && seen.len() < limit {
+		let mut rids = Vec::new();
+		let mut paths = Vec::new();
+		for (rid, path) in links.drain(..) {
+			if seen.len() >= limit {
+				break;
+			}
+			if seen.insert(rid.clone()) {
+				rids.push(rid);
+				paths.push(path);
+			}
+		}
+		let records = load_records(ctx, &rids).await?;
+		for (record, path) in records.iter().zip(paths) {
+			if !path.is_empty() {
+				collect_links(&record.data, path, &mut links);
+			}
+		}
+	}
+	Ok(count)
+}
+
+/// Collect the record ids which fetching a path through a value would
+/// dereference, along with the remainder of the path to walk through each
+/// loaded record.
+///
+/// This mirrors the traversal of [`fetch_field_path`], without modifying the
+/// value.
+fn collect_links<'a>(value: &Value, path: &'a [Part], out: &mut Vec<(RecordId, &'a [Part])>) {
+	match value {
+		Value::RecordId(rid) => out.push((rid.clone(), path)),
+		Value::Array(arr) if path.is_empty() => {
+			for v in arr.iter() {
+				if let Value::RecordId(rid) = v {
+					out.push((rid.clone(), path));
+				}
+			}
+		}
+		_ if path.is_empty() => {}
+		_ => match (&path[0], value) {
+			(Part::Field(name), Value::Object(obj)) => {
+				if let Some(child) = obj.get(name.as_str()) {
+					collect_links(child, &path[1..], out);
+				}
+			}
+			(Part::Field(_), Value::Array(arr)) => {
+				for v in arr.iter() {
+					collect_links(v, path, out);
+				}
+			}
+			(Part::All, Value::Array(arr)) => {
+				for v in arr.iter() {
+					collect_links(v, &path[1..], out);
+				}
+			}
+			(Part::All, Value::Object(obj)) => {
+				for v in obj.values() {
+					collect_links(v, &path[1..], out);
+				}
+			}
+			(Part::First, Value::Array(arr)) => {
+				if let Some(v) = arr.first() {
+					collect_links(v, &path[1..], out);
+				}
+			}
+			(Part::Last, Value::Array(arr)) => {
+				if let Some(v) = arr.last() {
+					collect_links(v, &path[1..], out);
+				}
+			}
+			_ => {}
+		},
+	}
+}
+
+/// Load records into the transaction cache with a single multi-get, returning
+/// the raw records in the same order as the ids.
+async fn load_records(
+	ctx: &ExecutionContext,
+	rids: &[RecordId],
+) -> crate::expr::FlowResult<Vec<Arc<Record>>> {
+	let db_ctx = ctx.database().context("Prefetching records requires database context")?;
+	let records = ctx
+		.txn()
+		.get_records(
+			db_ctx.ns_ctx.ns.namespace_id,
+			db_ctx.db.database_id,
+			rids,
+			None,
+			crate::kvs::CachePolicy::ReadWrite,
+		)
+		.await
+		.context("Failed to prefetch records")?;
+	Ok(records)
+}
+
+/// Load a set of records into the transaction cache ahead of dereferencing
+/// them one by one, in multi-gets of at most [`prefetch_limit`] records.
+///
+/// A single record is left to be read when it is dereferenced, as a
+/// multi-get would not save a round trip.
+pub(crate) async fn prefetch_records(
+	ctx: &ExecutionContext,
+	rids: &[RecordId],
+) -> crate::expr::FlowResult<()> {
+	if rids.len() < 2 || ctx.version_stamp().is_some() {
+		return Ok(());
+	}
+	for chunk in rids.chunks(prefetch_limit(ctx)) {
+		load_records(ctx, chunk).await?;
+	}
+	Ok(())
 }
 
 /// Traverse a field path through a value, fetching record IDs along the way.
@@ -389,6 +558,10 @@ pub(crate) async fn batch_fetch_records(
 		return Ok(Vec::new());
 	}
 
+	// Load the records with batched multi-gets, so that the fetches below
+	// are served from the transaction cache
+	prefetch_records(ctx, rids).await?;
+
 	// For small batches, sequential fetch may be more efficient
 	// due to lower overhead. Threshold chosen empirically.
 	const PARALLEL_THRESHOLD: usize = 4;
@@ -489,4 +662,33 @@ mod tests {
 
 		assert_eq!(fetch.name(), "Fetch");
 	}
+
+	#[test]
+	fn test_collect_links() {
+		let parse =
+			|v: &str| crate::val::convert_public_value_to_internal(crate::syn::value(v).unwrap());
+		let rid = |v: &str| match parse(v) {
+			Value::RecordId(rid) => rid,
+			_ => unreachable!(),
+		};
+		let value =
+			parse("{ author: person:a, tags: [tag:x, 1, tag:y], meta: { all: [post:1, post:2] } }");
+		let fields: Vec<Idiom> = ["author.company", "tags", "meta.all[*]", "missing"]
+			.into_iter()
+			.map(|f| crate::syn::idiom(f).unwrap().into())
+			.collect();
+
+		let mut links = Vec::new();
+		for field in &fields {
+			collect_links(&value, &field.0, &mut links);
+		}
+
+		// Links within the path are walked with the remainder of the path
+		assert_eq!(links.len(), 5);
+		assert_eq!(links[0], (rid("person:a"), &fields[0].0[1..]));
+		assert_eq!(links[1], (rid("tag:x"), &[][..]));
+		assert_eq!(links[2], (rid("tag:y"), &[][..]));
+		assert_eq!(links[3], (rid("post:1"), &[][..]));
+		assert_eq!(links[4], (rid("post:2"), &[][..]));
+	}
 }
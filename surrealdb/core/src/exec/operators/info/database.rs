@@ -146,6 +146,9 @@ async fn execute_database_info(
 	// Get the transaction
 	let txn = ctx.txn();
 
+	// Query templates are not versioned
+	let queries = txn.all_db_queries(ns, db).await?;
+
 	// Create the result set
 	if structured {
 		let object = map! {
@@ -157,6 +160,9 @@ async fn execute_database_info(
 			"modules" => crate::expr::statements::info::process_modules(ctx.ctx(), ns, db, txn.all_db_modules(ns, db, version).await?).await,
 			"models" => process(&txn.all_db_models(ns, db, version).await?),
 			"params" => process(&txn.all_db_params(ns, db, version).await?),
+			"queries", if !queries.is_empty() => {
+				process(&queries)
+			},
 			"tables" => process(&txn.all_tb(ns, db, version).await?),
 			"users" => process(&txn.all_db_users(ns, db, version).await?),
 			"configs" => process(&txn.all_db_configs(ns, db, version).await?),
@@ -221,6 +227,13 @@ async fn execute_database_info(
 				}
 				out.into()
 			},
+			"queries", if !queries.is_empty() => {
+				let mut out = Object::default();
+				for v in queries.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
 			"tables" => {
 				let mut out = Object::default();
 				for v in txn.all_tb(ns, db, version).await?.iter() {
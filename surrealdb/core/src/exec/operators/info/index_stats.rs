@@ -0,0 +1,109 @@
+//! Index stats INFO operator - returns index usage statistics for a table.
+//!
+//! Implements INFO FOR TABLE table INDEX STATS which returns the read and
+//! write counters recorded for every index on the table since the datastore
+//! was started.
+
+use std::sync::Arc;
+
+use futures::stream;
+use surrealdb_types::ToSql;
+
+use crate::exec::context::{ContextLevel, ExecutionContext};
+use crate::exec::physical_expr::{EvalContext, PhysicalExpr};
+use crate::exec::{
+	AccessMode, CardinalityHint, ExecOperator, FlowResult, OperatorMetrics, ValueBatch,
+	ValueBatchStream,
+};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{TableName, Value};
+
+/// Index stats INFO operator.
+///
+/// Returns the usage statistics of every index defined on a table.
+#[derive(Debug)]
+pub struct IndexStatsInfoPlan {
+	/// Table name expression
+	pub table: Arc<dyn PhysicalExpr>,
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl IndexStatsInfoPlan {
+	pub(crate) fn new(table: Arc<dyn PhysicalExpr>) -> Self {
+		Self {
+			table,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+impl ExecOperator for IndexStatsInfoPlan {
+	fn name(&self) -> &'static str {
+		"InfoIndexStats"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		vec![("table".to_string(), self.table.to_sql())]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		// Index stats need database context, combined with expression context
+		self.table.required_context().max(ContextLevel::Database)
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		// Info is inherently read-only, but the table expression could
+		// theoretically contain mutation subqueries.
+		self.table.access_mode()
+	}
+
+	fn cardinality_hint(&self) -> CardinalityHint {
+		CardinalityHint::AtMostOne
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(self.metrics.as_ref())
+	}
+
+	fn expressions(&self) -> Vec<(&str, &Arc<dyn PhysicalExpr>)> {
+		vec![("table", &self.table)]
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let table = Arc::clone(&self.table);
+		let ctx = ctx.clone();
+
+		Ok(Box::pin(stream::once(async move {
+			let value = execute_index_stats_info(&ctx, &*table).await?;
+			Ok(ValueBatch {
+				values: vec![value],
+			})
+		})))
+	}
+
+	fn is_scalar(&self) -> bool {
+		true
+	}
+}
+
+async fn execute_index_stats_info(
+	ctx: &ExecutionContext,
+	table_expr: &dyn PhysicalExpr,
+) -> crate::expr::FlowResult<Value> {
+	// Allowed to run?
+	ctx.is_allowed(Action::View, ResourceKind::Any, crate::expr::Base::Db)?;
+
+	// Get database context
+	let db_ctx = ctx.database()?;
+	let ns = db_ctx.ns_ctx.ns.namespace_id;
+	let db = db_ctx.db.database_id;
+
+	// Evaluate the table name expression
+	let eval_ctx = EvalContext::from_exec_ctx(ctx);
+	let table_value = table_expr.evaluate(eval_ctx).await?;
+	let tb = TableName::new(table_value.coerce_to::<String>().map_err(|e| anyhow::anyhow!("{e}"))?);
+
+	// Collect the usage recorded for every index on the table
+	let txn = ctx.txn();
+	Ok(ctx.ctx().get_index_stores().usage().table_info(&txn, ns, db, &tb).await?)
+}
@@ -0,0 +1,70 @@
+//! Locks INFO operator - returns the current key lock waits.
+//!
+//! Implements INFO FOR LOCKS which returns the client-managed transactions
+//! which are waiting for a key locked by another transaction.
+
+use std::sync::Arc;
+
+use futures::stream;
+
+use crate::exec::context::{ContextLevel, ExecutionContext};
+use crate::exec::{
+	AccessMode, CardinalityHint, ExecOperator, FlowResult, OperatorMetrics, ValueBatch,
+	ValueBatchStream,
+};
+use crate::iam::{Action, ResourceKind};
+
+/// Locks INFO operator.
+///
+/// Returns the transactions waiting for a key lock, with the transaction
+/// holding the lock and the locked resource.
+#[derive(Debug)]
+pub struct LocksInfoPlan {
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl LocksInfoPlan {
+	pub(crate) fn new() -> Self {
+		Self {
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+impl ExecOperator for LocksInfoPlan {
+	fn name(&self) -> &'static str {
+		"InfoLocks"
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		ContextLevel::Root
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		AccessMode::ReadOnly
+	}
+
+	fn cardinality_hint(&self) -> CardinalityHint {
+		CardinalityHint::AtMostOne
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(self.metrics.as_ref())
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let ctx = ctx.clone();
+
+		Ok(Box::pin(stream::once(async move {
+			// Allowed to run?
+			ctx.is_allowed(Action::View, ResourceKind::Any, crate::expr::Base::Root)?;
+			Ok(ValueBatch {
+				values: vec![ctx.txn().lock_waits()],
+			})
+		})))
+	}
+
+	fn is_scalar(&self) -> bool {
+		true
+	}
+}
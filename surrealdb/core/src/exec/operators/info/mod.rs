@@ -9,9 +9,13 @@
 //! - `TableInfoPlan`: INFO FOR TABLE - returns table metadata
 //! - `UserInfoPlan`: INFO FOR USER - returns user information
 //! - `IndexInfoPlan`: INFO FOR INDEX - returns index building status
+//! - `IndexStatsInfoPlan`: INFO FOR TABLE ... INDEX STATS - returns index usage statistics
+//! - `LocksInfoPlan`: INFO FOR LOCKS - returns the current key lock waits
 
 mod database;
 mod index;
+mod index_stats;
+mod locks;
 mod namespace;
 mod root;
 mod table;
@@ -19,6 +23,8 @@ mod user;
 
 pub use database::DatabaseInfoPlan;
 pub use index::IndexInfoPlan;
+pub use index_stats::IndexStatsInfoPlan;
+pub use locks::LocksInfoPlan;
 pub use namespace::NamespaceInfoPlan;
 pub use root::RootInfoPlan;
 pub use table::TableInfoPlan;
@@ -154,6 +154,12 @@ async fn execute_table_info(
 	// Get the transaction
 	let txn = ctx.txn();
 
+	// Archival policies are not versioned
+	let policies = txn.all_tb_policies(ns, db, &tb).await?;
+
+	// Temporary indexes only exist in memory
+	let temporary = txn.table_writes().indexes().describe(ns, db, &tb);
+
 	// Create the result set
 	if structured {
 		Ok(Value::from(map! {
@@ -161,7 +167,19 @@ async fn execute_table_info(
 			"fields" => process(&txn.all_tb_fields(ns, db, &tb, version).await?),
 			"indexes" => process(&txn.all_tb_indexes(ns, db, &tb, version).await?),
 			"lives" => process(&txn.all_tb_lives(ns, db, &tb, version).await?),
+			"policies", if !policies.is_empty() => {
+				Value::Array(policies.into_iter().map(InfoStructure::structure).collect())
+			},
 			"tables" => process(&txn.all_tb_views(ns, db, &tb, version).await?),
+			"temporary_indexes", if !temporary.is_empty() => {
+				let structure = |(field, mut v): (String, Value)| {
+					if let Value::Object(obj) = &mut v {
+						obj.insert("field", Value::from(field.as_str()));
+					}
+					v
+				};
+				Value::Array(temporary.into_iter().map(structure).collect())
+			},
 		}))
 	} else {
 		Ok(Value::from(map! {
@@ -193,6 +211,13 @@ async fn execute_table_info(
 				}
 				out.into()
 			},
+			"policies", if !policies.is_empty() => {
+				let mut out = Object::default();
+				for v in policies.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
 			"tables" => {
 				let mut out = Object::default();
 				for v in txn.all_tb_views(ns, db, &tb, version).await?.iter() {
@@ -200,6 +225,13 @@ async fn execute_table_info(
 				}
 				out.into()
 			},
+			"temporary_indexes", if !temporary.is_empty() => {
+				let mut out = Object::default();
+				for (field, v) in temporary {
+					out.insert(field, v);
+				}
+				out.into()
+			},
 		}))
 	}
 }
@@ -26,7 +26,7 @@
 //!
 //! All strategies are fully iterative and use no stack recursion:
 //!
-//! - **Default, Collect, Path, Shortest**: Loop-based, safe at any depth.
+//! - **Default, Collect, Path, Shortest, Tree**: Loop-based, safe at any depth.
 //! - **RepeatRecurse (`@`) tree-building**: Uses a two-phase iterative approach (forward BFS
 //!   discovery + backward bottom-up assembly). In the discovery phase, `@` writes its inputs to a
 //!   shared sink and returns immediately (or the body operator is executed directly when
@@ -60,17 +60,19 @@ mod default;
 mod path;
 mod repeat;
 mod shortest;
+mod tree;
 
 // Re-export for use by parts::recurse (RepeatRecursePart evaluation).
 pub(crate) use repeat::evaluate_repeat_recurse;
 
 /// Recursion operator -- bounded/unbounded recursive graph traversal.
 ///
-/// Implements four collection strategies:
+/// Implements five collection strategies:
 /// - Default: Follow path until bounds or dead end, return final value
 /// - Collect: Gather all unique nodes encountered during BFS traversal
 /// - Path: Return all paths as arrays of arrays
 /// - Shortest: Find shortest path to a target node using BFS
+/// - Tree: Nest all unique nodes beneath their parents using BFS
 ///
 /// The operator holds both:
 /// - An optional body operator chain (for EXPLAIN display as `children()`)
@@ -153,6 +155,7 @@ impl ExecOperator for RecursionOp {
 			PhysicalRecurseInstruction::Default => "default",
 			PhysicalRecurseInstruction::Collect => "collect",
 			PhysicalRecurseInstruction::Path => "path",
+			PhysicalRecurseInstruction::Tree => "tree",
 			PhysicalRecurseInstruction::Shortest {
 				..
 			} => "shortest",
@@ -173,7 +176,8 @@ impl ExecOperator for RecursionOp {
 		let instruction_ctx = match &self.instruction {
 			PhysicalRecurseInstruction::Default
 			| PhysicalRecurseInstruction::Collect
-			| PhysicalRecurseInstruction::Path => ContextLevel::Root,
+			| PhysicalRecurseInstruction::Path
+			| PhysicalRecurseInstruction::Tree => ContextLevel::Root,
 			PhysicalRecurseInstruction::Shortest {
 				target,
 			} => target.required_context(),
@@ -188,7 +192,8 @@ impl ExecOperator for RecursionOp {
 		let instruction_mode = match &self.instruction {
 			PhysicalRecurseInstruction::Default
 			| PhysicalRecurseInstruction::Collect
-			| PhysicalRecurseInstruction::Path => AccessMode::ReadOnly,
+			| PhysicalRecurseInstruction::Path
+			| PhysicalRecurseInstruction::Tree => AccessMode::ReadOnly,
 			PhysicalRecurseInstruction::Shortest {
 				target,
 			} => target.access_mode(),
@@ -281,6 +286,17 @@ impl ExecOperator for RecursionOp {
 						)
 						.await?
 					}
+					PhysicalRecurseInstruction::Tree => {
+						tree::evaluate_recurse_tree(
+							&value,
+							&path,
+							min_depth,
+							max_depth,
+							inclusive,
+							eval_ctx.with_value(&value),
+						)
+						.await?
+					}
 					PhysicalRecurseInstruction::Shortest {
 						target,
 					} => {
@@ -0,0 +1,105 @@
+//! Tree recursion strategy: nest every reached node beneath its parent.
+//!
+//! Uses breadth-first search to reach every node within the depth bounds,
+//! placing each node beneath the node it was first reached from. A node is
+//! only placed once, so cycles are never followed and diamonds are not
+//! duplicated. Fully iterative — frontier-based BFS loop, followed by a
+//! bottom-up assembly of the nested objects.
+//!
+//! # Example data and query
+//!
+//! Using a hierarchy of record links (e.g. planet → country → state/province → city):
+//!
+//! ```text
+//! planet:earth  (contains: [country:us, country:canada])
+//! ├── country:us     → contains: [state:california, state:texas]
+//! └── country:canada  → contains: [province:ontario, province:bc]
+//! ```
+//!
+//! Example SurrealQL:
+//!
+//! ```surql
+//! planet:earth.{..2+tree}.contains
+//! -- or: planet:earth.{..2+tree+inclusive}->contains->?
+//! ```
+//!
+//! Result: `[{ id: country:us, children: [{ id: state:california, children: [] }, ...] }, ...]`,
+//! or with `inclusive` a single `{ id: planet:earth, children: [...] }` object. Branches which
+//! end before `min_depth` are dropped from the result.
+
+use std::sync::Arc;
+
+use surrealdb_types::ToSql;
+
+use super::common::{eval_buffered, is_recursion_target};
+use crate::exec::FlowResult;
+use crate::exec::parts::{RecursionTree, evaluate_physical_path, is_final};
+use crate::exec::physical_expr::{EvalContext, PhysicalExpr};
+use crate::val::Value;
+
+/// Tree recursion: nest every reached node beneath the node it was first
+/// reached from.
+///
+/// Fully iterative -- frontier-based BFS loop.
+pub(crate) async fn evaluate_recurse_tree(
+	start: &Value,
+	path: &[Arc<dyn PhysicalExpr>],
+	min_depth: u32,
+	max_depth: u32,
+	inclusive: bool,
+	ctx: EvalContext<'_>,
+) -> FlowResult<Value> {
+	let mut tree = RecursionTree::new(start.clone());
+	let mut frontier = vec![0];
+	let mut depth = 0u32;
+
+	while depth < max_depth && !frontier.is_empty() {
+		let mut next_frontier = Vec::new();
+
+		// Phase 1: Evaluate all frontier nodes concurrently (bounded).
+		// Uses `buffered` (ordered) so results align with `frontier` for zip.
+		let futures: Vec<_> = frontier
+			.iter()
+			.map(|node| {
+				let value = tree.value(*node);
+				evaluate_physical_path(value, path, ctx.with_value(value))
+			})
+			.collect();
+		let eval_results = eval_buffered(futures).await?;
+
+		// Phase 2: Place the discovered nodes beneath their parents.
+		for (parent, result) in frontier.into_iter().zip(eval_results) {
+			let values = match result {
+				Value::Array(arr) => arr.0,
+				Value::None | Value::Null => continue,
+				other => vec![other],
+			};
+
+			for v in values {
+				// Dead ends (None, Null, empty arrays) silently terminate this branch.
+				if is_final(&v) {
+					continue;
+				}
+
+				// Non-RecordId values during recursion are an error --
+				// recursion is intended purely for record graph traversal.
+				if !is_recursion_target(&v) {
+					return Err(crate::err::Error::InvalidRecursionTarget {
+						value: v.to_sql(),
+					}
+					.into());
+				}
+
+				// Nodes already in the tree are not placed (or expanded) again.
+				if let Some(node) = tree.insert(v, parent) {
+					next_frontier.push(node);
+				}
+			}
+		}
+
+		frontier = next_frontier;
+		depth += 1;
+	}
+
+	Ok(tree.assemble(min_depth, inclusive))
+}
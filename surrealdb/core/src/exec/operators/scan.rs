@@ -33,4 +33,5 @@ pub(crate) use pipeline::determine_scan_direction;
 pub use record_id::RecordIdScan;
 pub use reference::{ReferenceScan, ReferenceScanOutput};
 pub use table::TableScan;
+pub(crate) use table::TemporaryIndexProbe;
 pub use union_index::UnionIndexScan;
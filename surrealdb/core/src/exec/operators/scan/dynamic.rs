@@ -7,15 +7,17 @@ use super::pipeline::{
 	build_field_state, determine_scan_direction, eval_limit_expr, kv_scan_stream,
 };
 use super::{FullTextScan, IndexScan, KnnScan};
-use crate::catalog::{DatabaseId, NamespaceId, Permission};
+use crate::catalog::{DatabaseId, NamespaceId, Permission, TableDefinition};
 use crate::err::Error;
 use crate::exec::index::access_path::{AccessPath, select_access_path};
 use crate::exec::index::analysis::IndexAnalyzer;
 use crate::exec::operators::scan::pipeline::ScanPipeline;
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
-	validate_record_user_access,
+	trace_denied_scan, validate_record_user_access,
 };
+use crate::exec::physical_expr::BinaryOp;
+use crate::exec::planner::expr_to_physical_expr;
 use crate::exec::planner::util::{
 	SELECT_ITERATION_PARAMS, fold_condition_expressions, index_covers_ordering,
 	resolve_condition_params, resolve_projection_field_idioms, strip_knn_from_condition,
@@ -27,7 +29,7 @@ use crate::exec::{
 };
 use crate::expr::order::Ordering;
 use crate::expr::with::With;
-use crate::expr::{Cond, ControlFlow, ControlFlowExt};
+use crate::expr::{BinaryOperator, Cond, ControlFlow, ControlFlowExt};
 use crate::iam::Action;
 use crate::idx::planner::ScanDirection;
 use crate::key::record;
@@ -79,6 +81,9 @@ pub struct DynamicScan {
 	pub(crate) knn_context: Option<Arc<crate::exec::function::KnnContext>>,
 	/// Predicate pre-decode filter status (plan-time); see [`PreDecodeFilterStatus`].
 	pub(crate) pre_decode_filter_status: PreDecodeFilterStatus,
+	/// Hide soft deleted records at runtime, when planning had no transaction
+	/// to check the table definition with.
+	pub(crate) soft_delete: bool,
 }
 
 impl DynamicScan {
@@ -108,9 +113,16 @@ impl DynamicScan {
 			metrics: Arc::new(OperatorMetrics::new()),
 			knn_context: None,
 			pre_decode_filter_status: PreDecodeFilterStatus::NotApplicable,
+			soft_delete: false,
 		}
 	}
 
+	/// Check the table definition for soft deletes at runtime.
+	pub(crate) fn with_soft_delete_check(mut self, check: bool) -> Self {
+		self.soft_delete = check;
+		self
+	}
+
 	/// Set plan-time pre-decode filter status for EXPLAIN and execution.
 	pub(crate) fn with_pre_decode_filter(mut self, status: PreDecodeFilterStatus) -> Self {
 		self.pre_decode_filter_status = status;
@@ -227,6 +239,7 @@ impl ExecOperator for DynamicScan {
 		let start_expr = self.start.clone();
 		let knn_context = self.knn_context.clone();
 		let pre_decode_filter_status = self.pre_decode_filter_status.clone();
+		let soft_delete = self.soft_delete;
 		let ctx = ctx.clone();
 
 		let stream = async_stream::try_stream! {
@@ -283,6 +296,17 @@ impl ExecOperator for DynamicScan {
 					return;
 				}
 
+				// Hide the record if it has been soft deleted
+				let predicate = if soft_delete {
+					let table_def = db_ctx
+						.get_table_def(&rid.table, version)
+						.await
+						.context("Failed to get table")?;
+					soft_delete_predicate(&ctx, table_def.as_deref(), predicate).await?
+				} else {
+					predicate
+				};
+
 				let results = super::record_id::execute_record_lookup(
 					&rid, version, check_perms, needed_fields.as_ref(), &ctx,
 					predicate.as_ref(), limit_val, start_val, None,
@@ -436,6 +460,7 @@ impl ExecOperator for DynamicScan {
 
 			// Early exit if denied
 			if matches!(select_permission, PhysicalPermission::Deny) {
+				trace_denied_scan(&ctx, &table_name);
 				return;
 			}
 
@@ -443,6 +468,13 @@ impl ExecOperator for DynamicScan {
 				return;
 			}
 
+			// Hide soft deleted records
+			let predicate = if soft_delete {
+				soft_delete_predicate(&ctx, table_def.as_deref(), predicate).await?
+			} else {
+				predicate
+			};
+
 			// Eagerly initialize field state (computed fields + field permissions)
 			let field_state = build_field_state(&ctx, &table_name, check_perms, needed_fields.as_ref()).await?;
 
@@ -515,6 +547,7 @@ impl ExecOperator for DynamicScan {
 
 			// Build the pipeline with start adjusted for any pre-skipping.
 			let mut pipeline = ScanPipeline::new(
+				"DynamicScan",
 				select_permission, predicate, field_state,
 				check_perms, limit_val, start_val.saturating_sub(applied_pre_skip),
 			);
@@ -578,6 +611,30 @@ fn order_touches_restricted_select_field(
 		.any(|o| field_permissions.iter().any(|(field, _)| o.value.starts_with(field.0.as_slice())))
 }
 
+/// Conjoin the soft delete marker check of the table, if it has one, onto the
+/// pushed-down predicate.
+///
+/// The check is compiled with [`expr_to_physical_expr`] because this is only
+/// reached when planning had no transaction to read the table definition.
+async fn soft_delete_predicate(
+	ctx: &ExecutionContext,
+	table_def: Option<&TableDefinition>,
+	predicate: Option<Arc<dyn PhysicalExpr>>,
+) -> anyhow::Result<Option<Arc<dyn PhysicalExpr>>> {
+	let Some(sd) = table_def.and_then(|tb| tb.soft_delete.as_ref()) else {
+		return Ok(predicate);
+	};
+	let check = expr_to_physical_expr(sd.check(), ctx.ctx()).await?;
+	Ok(Some(match predicate {
+		Some(pred) => Arc::new(BinaryOp {
+			left: pred,
+			op: BinaryOperator::And,
+			right: check,
+		}),
+		None => check,
+	}))
+}
+
 /// Configuration bundle for [`resolve_table_scan_stream`].
 struct TableScanConfig {
 	ns_id: NamespaceId,
@@ -663,7 +720,7 @@ async fn resolve_table_scan_stream(
 		return Ok((stream, 0));
 	}
 
-	let access_path = if matches!(&cfg.with, Some(With::NoIndex)) {
+	let access_path = if matches!(&cfg.with, Some(With::NoIndex | With::Deleted)) {
 		None
 	} else {
 		let db_ctx =
@@ -795,6 +852,9 @@ async fn resolve_table_scan_stream(
 		// Fall back to table KV scan (NOINDEX, BTree rejected by ordering
 		// check, etc.)
 		_ => {
+			if let Some(counters) = ctx.ctx().statement_counters() {
+				counters.record_table_scan();
+			}
 			let beg = record::prefix(cfg.ns_id, cfg.db_id, &cfg.table_name)?;
 			let end = record::suffix(cfg.ns_id, cfg.db_id, &cfg.table_name)?;
 			let stream = kv_scan_stream(
@@ -810,6 +870,8 @@ async fn resolve_table_scan_stream(
 				// TopK threshold pushdown is plan-time-only (TableScan);
 				// DynamicScan resolves its access path at runtime.
 				None,
+				ctx.ctx().scan_recorder(),
+				"DynamicScan",
 			);
 			Ok((stream, cfg.pre_skip))
 		}
@@ -898,7 +960,6 @@ fn create_index_operator(
 mod tests {
 	use super::*;
 	use crate::ctx::Context;
-	use crate::exec::planner::expr_to_physical_expr;
 
 	/// Helper to create a Scan with all fields for testing
 	async fn create_test_scan(table_name: &str, with_index_hints: bool) -> DynamicScan {
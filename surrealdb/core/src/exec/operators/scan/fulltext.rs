@@ -16,7 +16,7 @@ use crate::err::Error;
 use crate::exec::index::access_path::IndexRef;
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
-	validate_record_user_access,
+	trace_denied_scan, validate_record_user_access,
 };
 use crate::exec::{
 	AccessMode, ContextLevel, ExecOperator, ExecutionContext, FlowResult, OperatorMetrics,
@@ -184,6 +184,7 @@ impl ExecOperator for FullTextScan {
 
 			// Early exit if denied
 			if matches!(select_permission, PhysicalPermission::Deny) {
+				trace_denied_scan(&ctx, &table_name);
 				return;
 			}
 
@@ -206,6 +207,7 @@ impl ExecOperator for FullTextScan {
 			// Table-level permissions are handled by fetch_and_filter_records_batch.
 			// The pipeline handles computed fields and field-level permissions.
 			let mut pipeline = ScanPipeline::new(
+				"FullTextScan",
 				PhysicalPermission::Allow,
 				None,
 				field_state,
@@ -216,6 +218,10 @@ impl ExecOperator for FullTextScan {
 
 			// Get the FullText index parameters from the index definition
 			let index_def = index_ref.definition();
+			ctx.ctx().get_index_stores().usage().record_read(ns.namespace_id, db.database_id, index_def);
+			if let Some(counters) = ctx.ctx().statement_counters() {
+				counters.record_index(&index_def.name);
+			}
 			let ft_params = match &index_def.index {
 				Index::FullText(params) => params,
 				_ => {
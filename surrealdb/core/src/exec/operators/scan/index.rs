@@ -19,7 +19,7 @@ use crate::exec::index::iterator::{
 };
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
-	validate_record_user_access,
+	trace_denied_scan, validate_record_user_access,
 };
 use crate::exec::{
 	AccessMode, ContextLevel, ControlFlowExt, ExecOperator, ExecutionContext, FlowResult,
@@ -404,6 +404,7 @@ impl ExecOperator for IndexScan {
 
 			// Early exit if denied
 			if matches!(select_permission, PhysicalPermission::Deny) {
+				trace_denied_scan(&ctx, &table_name);
 				return;
 			}
 
@@ -431,6 +432,7 @@ impl ExecOperator for IndexScan {
 			// fetch_and_filter_records_batch, so the pipeline uses Allow
 			// to avoid double-checking.
 			let mut pipeline = super::pipeline::ScanPipeline::new(
+				"IndexScan",
 				PhysicalPermission::Allow,
 				where_predicate,
 				field_state,
@@ -442,6 +444,10 @@ impl ExecOperator for IndexScan {
 			// Create the appropriate iterator based on access type and index uniqueness
 			let is_unique = index_ref.is_unique();
 			let ix = index_ref.definition();
+			ctx.ctx().get_index_stores().usage().record_read(ns_id, db_id, ix);
+			if let Some(counters) = ctx.ctx().statement_counters() {
+				counters.record_index(&ix.name);
+			}
 
 			// Collect record IDs from index and batch-fetch full records
 			match (&access, is_unique) {
@@ -21,6 +21,13 @@
 //! 3. Scans `IndexCountKey` deltas to compute the total count.
 //! 4. Falls back to a full scan + filter + count if no matching COUNT index is found or permissions
 //!    are conditional.
+//!
+//! When the WHERE clause reads a field with a restricted SELECT permission, the
+//! planner only emits this operator when the B-tree access pins that field to a
+//! single value, or bounds it from below, and the permission reads nothing but
+//! pinned columns (see [`PinnedFieldPermissions`]). This covers equality, range
+//! and compound accesses. The permission is then checked once against the
+//! pinned values rather than against each record, so the count stays index-only.
 
 use std::sync::Arc;
 
@@ -30,21 +37,38 @@ use crate::catalog::{DatabaseId, Index, NamespaceId, Permission};
 use crate::err::Error;
 use crate::exec::index::access_path::{BTreeAccess, IndexRef};
 use crate::exec::permission::{
-	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
-	validate_record_user_access,
+	PhysicalPermission, check_permission_for_value, convert_permission_to_physical_runtime,
+	should_check_perms, validate_record_user_access,
 };
 use crate::exec::{
 	AccessMode, CardinalityHint, ContextLevel, EvalContext, ExecOperator, ExecutionContext,
 	FlowResult, OperatorMetrics, PhysicalExpr, ValueBatch, ValueBatchStream, monitor_stream,
 };
 use crate::expr::cond::Cond;
-use crate::expr::{ControlFlow, ControlFlowExt};
+use crate::expr::{ControlFlow, ControlFlowExt, Idiom};
 use crate::iam::Action;
 use crate::key::index::iu::IndexCountKey;
 use crate::key::record;
 use crate::kvs::KVValue;
 use crate::val::{Number, Object, TableName, Value};
 
+/// The field-level SELECT permissions of the restricted fields read by the
+/// WHERE clause of an indexed count, checked against the values which the
+/// B-tree access pins the index columns to.
+///
+/// Each restricted field is pinned to a single value other than NONE or NULL,
+/// or bounded from below by such a value. When its permission denies access,
+/// the field reads as NONE in every matching record, so no record can match
+/// the access.
+#[derive(Debug, Clone)]
+pub(crate) struct PinnedFieldPermissions {
+	/// The document made of the pinned values, keyed by column name.
+	pub(crate) document: Value,
+	/// The restricted fields, with their pinned value (`None` for a bounded
+	/// field) and SELECT permission.
+	pub(crate) fields: Vec<(Idiom, Option<Value>, Permission)>,
+}
+
 /// Optimized operator for `SELECT count() FROM <table> WHERE <cond> GROUP ALL`
 /// when a matching COUNT index exists.
 ///
@@ -69,6 +93,9 @@ pub struct IndexCountScan {
 	/// matching COUNT index exists.  The planner resolves this from the
 	/// same index analysis it performs for regular queries.
 	pub(crate) btree_access: Option<(IndexRef, BTreeAccess)>,
+	/// The restricted field permissions to check against the values pinned
+	/// by the B-tree access, when the WHERE clause reads restricted fields.
+	pub(crate) pinned_permissions: Option<PinnedFieldPermissions>,
 	/// Per-operator runtime metrics for EXPLAIN ANALYZE.
 	pub(crate) metrics: Arc<OperatorMetrics>,
 }
@@ -89,6 +116,7 @@ impl IndexCountScan {
 			version,
 			field_names,
 			btree_access: None,
+			pinned_permissions: None,
 			metrics: Arc::new(OperatorMetrics::new()),
 		}
 	}
@@ -98,6 +126,16 @@ impl IndexCountScan {
 		self.btree_access = access;
 		self
 	}
+
+	/// Set the restricted field permissions to check against the values
+	/// pinned by the B-tree access.
+	pub(crate) fn with_pinned_permissions(
+		mut self,
+		permissions: Option<PinnedFieldPermissions>,
+	) -> Self {
+		self.pinned_permissions = permissions;
+		self
+	}
 }
 impl ExecOperator for IndexCountScan {
 	fn name(&self) -> &'static str {
@@ -147,6 +185,7 @@ impl ExecOperator for IndexCountScan {
 		let version = self.version.clone();
 		let field_names = self.field_names.clone();
 		let btree_access = self.btree_access.clone();
+		let pinned_permissions = self.pinned_permissions.clone();
 		let ctx = ctx.clone();
 
 		let stream = async_stream::try_stream! {
@@ -239,7 +278,12 @@ impl ExecOperator for IndexCountScan {
 				.await
 				.context("Failed to fetch table indexes")?;
 
+			// Restricted fields are only checked against the pinned values of
+			// a B-tree access, so a COUNT index can not be used for them.
 			let matching_index = indexes.iter().find(|ix| {
+				if pinned_permissions.is_some() {
+					return false;
+				}
 				if let Index::Count(ref idx_cond) = ix.index {
 					// The COUNT index condition must exactly match the WHERE clause.
 					idx_cond.as_ref() == Some(&condition)
@@ -261,6 +305,16 @@ impl ExecOperator for IndexCountScan {
 				.await?;
 				yield make_count_batch(count, &field_names);
 			} else if let Some((ref ix_ref, ref access)) = btree_access {
+				// The restricted fields read by the WHERE clause hide the
+				// pinned or bounded value from every record when their
+				// permission denies.
+				if check_perms
+					&& let Some(ref pinned) = pinned_permissions
+					&& !pinned_permissions_allow(&ctx, pinned).await?
+				{
+					// No record matches, as when the documents are reduced.
+					return;
+				}
 				// Medium path: count entries by iterating B-tree index
 				// keys only — no record value deserialization.
 				let count = count_btree_index_keys(
@@ -356,6 +410,27 @@ pub(crate) async fn sum_index_count_deltas(
 	Ok(count.max(0) as usize)
 }
 
+/// Check the permissions of the restricted fields read by the WHERE clause
+/// against the values pinned by the B-tree access.
+async fn pinned_permissions_allow(
+	ctx: &ExecutionContext,
+	pinned: &PinnedFieldPermissions,
+) -> Result<bool, ControlFlow> {
+	for (field, value, permission) in &pinned.fields {
+		let permission = convert_permission_to_physical_runtime(permission, ctx.ctx())
+			.await
+			.context("Failed to convert permission")?;
+		let field = value.as_ref().map(|v| (field, v));
+		let allowed = check_permission_for_value(&permission, &pinned.document, field, ctx)
+			.await
+			.context("Failed to check permission")?;
+		if !allowed {
+			return Ok(false);
+		}
+	}
+	Ok(true)
+}
+
 /// Fallback: scan all records, apply the predicate, and count matches.
 ///
 /// Used when no matching COUNT index exists or when per-record permissions
@@ -455,6 +530,10 @@ async fn count_btree_index_keys(
 	let ix = index_ref.definition();
 	let is_unique = index_ref.is_unique();
 	let mut count = 0usize;
+	ctx.ctx().get_index_stores().usage().record_read(ns_id, db_id, ix);
+	if let Some(counters) = ctx.ctx().statement_counters() {
+		counters.record_index(&ix.name);
+	}
 
 	match (access, is_unique) {
 		(BTreeAccess::Equality(value), true) => {
@@ -18,7 +18,7 @@ use crate::err::Error;
 use crate::exec::index::access_path::IndexRef;
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
-	validate_record_user_access,
+	trace_denied_scan, validate_record_user_access,
 };
 use crate::exec::{
 	AccessMode, CardinalityHint, ContextLevel, ExecOperator, ExecutionContext, FlowResult,
@@ -230,6 +230,7 @@ impl ExecOperator for KnnScan {
 
 			// Early exit if denied
 			if matches!(select_permission, PhysicalPermission::Deny) {
+				trace_denied_scan(&ctx, &table_name);
 				return;
 			}
 
@@ -251,6 +252,10 @@ impl ExecOperator for KnnScan {
 
 			// Get the ANN parameters from the index definition
 			let index_def = index_ref.definition();
+			frozen_ctx.get_index_stores().usage().record_read(ns.namespace_id, db.database_id, index_def);
+			if let Some(counters) = frozen_ctx.statement_counters() {
+				counters.record_index(&index_def.name);
+			}
 			let knn_results = match &index_def.index {
 				Index::Hnsw(hnsw_params) => {
 					// Obtain the shared HNSW index
@@ -377,6 +382,7 @@ impl ExecOperator for KnnScan {
 			// Table-level permissions are handled by fetch_and_filter_records_batch.
 			// The pipeline handles computed fields and field-level permissions.
 			let mut pipeline = ScanPipeline::new(
+				"KnnScan",
 				PhysicalPermission::Allow,
 				None,
 				field_state,
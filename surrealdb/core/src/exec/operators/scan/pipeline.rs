@@ -18,8 +18,10 @@ use std::sync::Arc;
 
 use crate::catalog::providers::TableProvider;
 use crate::catalog::{DatabaseId, NamespaceId};
+use crate::dbs::ScanRecorder;
 use crate::exec::permission::{
 	PhysicalPermission, check_permission_for_value, convert_permission_to_physical,
+	trace_permission,
 };
 use crate::exec::pre_decode_filter::{PreDecodeFilter, PreDecodeFilterOutcome};
 use crate::exec::topk_pushdown::TopKThresholdProbe;
@@ -57,6 +59,8 @@ pub(crate) struct ScanPipeline {
 	skipped: usize,
 	/// How many rows have been emitted so far.
 	emitted: usize,
+	/// The name of the scan operator, reported when the scan limit is exceeded.
+	operator: &'static str,
 }
 
 impl ScanPipeline {
@@ -94,6 +98,7 @@ impl ScanPipeline {
 	}
 
 	pub(crate) fn new(
+		operator: &'static str,
 		permission: PhysicalPermission,
 		predicate: Option<Arc<dyn PhysicalExpr>>,
 		field_state: FieldState,
@@ -117,6 +122,7 @@ impl ScanPipeline {
 			start,
 			skipped: 0,
 			emitted: 0,
+			operator,
 		}
 	}
 
@@ -133,6 +139,8 @@ impl ScanPipeline {
 		batch: &mut Vec<Value>,
 		ctx: &ExecutionContext,
 	) -> Result<bool, ControlFlow> {
+		// Count the records read for the statement stats
+		ctx.ctx().scan_recorder().record(batch.len() as u64, self.operator)?;
 		// Phase 1: filter + process (parallel per-record via try_join_all_buffered)
 		if self.needs_processing {
 			filter_and_process_batch(
@@ -211,6 +219,10 @@ pub(crate) fn determine_scan_direction(
 /// small-limit queries (e.g. `LIMIT 10`) don't fetch a full batch from
 /// storage. Subsequent batches use [`crate::kvs::NORMAL_BATCH_SIZE`].
 ///
+/// Rows which are dropped before decoding, by the pre-decode filter or the
+/// TopK probe, never reach [`ScanPipeline::process_batch`], so they are
+/// counted as scanned on the supplied `scans` recorder here instead.
+///
 /// Iterates the cursor's borrowed `&[u8]` slices directly — record decode
 /// happens inline, no intermediate owned `Vec<u8>` allocation per row.
 #[allow(clippy::too_many_arguments)]
@@ -225,6 +237,8 @@ pub(crate) fn kv_scan_stream(
 	limit_hint: Option<u32>,
 	pre_decode_filter: Option<Arc<PreDecodeFilter>>,
 	topk_probe: Option<Arc<TopKThresholdProbe>>,
+	scans: ScanRecorder,
+	operator: &'static str,
 ) -> ValueBatchStream {
 	let skip = pre_skip.min(u32::MAX as usize) as u32;
 	let stream = async_stream::try_stream! {
@@ -317,6 +331,7 @@ pub(crate) fn kv_scan_stream(
 				Err(cf)?;
 			}
 			first = false;
+			scans.record(stats.rows.saturating_sub(decoded.len() as u64), operator)?;
 			// `stats.rows` counts every row the cursor advanced over (including
 			// pre-decode-filter rejects), matching the previous `batch.len()`.
 			yielded += stats.rows as usize;
@@ -422,8 +437,12 @@ pub(crate) async fn filter_and_process_batch(
 	let mut write_idx = 0;
 	for read_idx in 0..batch.len() {
 		// Table-level permission (skip if Allow)
-		if needs_perm_filter && !check_perm!(permission, &batch[read_idx], ctx)? {
-			continue;
+		if needs_perm_filter {
+			let allowed = check_perm!(permission, &batch[read_idx], ctx)?;
+			trace_permission(ctx, permission, &batch[read_idx], None, allowed);
+			if !allowed {
+				continue;
+			}
 		}
 		// Move to write position
 		if write_idx != read_idx {
@@ -978,14 +997,16 @@ pub(crate) async fn filter_fields_by_permission(
 				// so evaluation order is irrelevant.
 				for path in original.each(&idiom.0).into_iter().rev() {
 					let field_value = original.pick(&path.0);
-					let allowed =
-						check_permission_for_value(perm, original, Some(&field_value), ctx)
-							.await
-							.map_err(|e| {
-							ControlFlow::Err(anyhow::anyhow!(
-								"Failed to check field permission: {e}"
-							))
-						})?;
+					let allowed = check_permission_for_value(
+						perm,
+						original,
+						Some((idiom, &field_value)),
+						ctx,
+					)
+					.await
+					.map_err(|e| {
+						ControlFlow::Err(anyhow::anyhow!("Failed to check field permission: {e}"))
+					})?;
 					if !allowed {
 						value.cut(&path.0);
 					}
@@ -18,7 +18,7 @@ use super::pipeline::{
 use super::resolved::ResolvedTableContext;
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
-	validate_record_user_access,
+	trace_denied_scan, validate_record_user_access,
 };
 use crate::exec::pre_decode_filter::{PreDecodeFilterStatus, pre_decode_filter_for_execute};
 use crate::exec::{
@@ -290,6 +290,7 @@ pub(crate) async fn execute_record_lookup(
 
 	// Early exit if denied
 	if matches!(select_permission, PhysicalPermission::Deny) {
+		trace_denied_scan(ctx, &rid.table);
 		return Ok(vec![]);
 	}
 
@@ -344,9 +345,12 @@ pub(crate) async fn execute_record_lookup(
 				pre_decode_filter,
 				// TopK threshold pushdown targets full table scans only.
 				None,
+				ctx.ctx().scan_recorder(),
+				"RecordIdScan",
 			);
 
 			let mut pipeline = ScanPipeline::new(
+				"RecordIdScan",
 				select_permission,
 				predicate.cloned(),
 				field_state,
@@ -378,6 +382,7 @@ pub(crate) async fn execute_record_lookup(
 			else {
 				return Ok(vec![]);
 			};
+			ctx.ctx().scan_recorder().record(1, "RecordIdScan")?;
 
 			let mut batch = vec![value];
 			if needs_processing {
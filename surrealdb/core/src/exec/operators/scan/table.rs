@@ -2,7 +2,11 @@
 //!
 //! Created by the planner when the access path is resolved to a table scan
 //! at plan time. Skips runtime index analysis and source expression evaluation,
-//! going straight to `kv_scan_stream` + `ScanPipeline`.
+//! going straight to `kv_scan_stream` + `ScanPipeline`. An equality predicate
+//! on a top-level field which is scanned repeatedly can instead be served by a
+//! temporary in-memory index (see [`crate::kvs::TemporaryIndexes`]). A bound
+//! on the creation time of the records limits the scan to the matching ranges
+//! of record ids (see [`crate::idx::planner::id_range`]).
 
 use std::sync::Arc;
 
@@ -10,23 +14,40 @@ use futures::StreamExt;
 use tracing::instrument;
 
 use super::common::resolve_version_stamp;
-use super::pipeline::{ScanPipeline, build_field_state, eval_limit_expr, kv_scan_stream};
+use super::pipeline::{
+	ScanPipeline, build_field_state, eval_limit_expr, kv_scan_stream, range_end_key,
+	range_start_key,
+};
 use super::resolved::ResolvedTableContext;
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::exec::operators::fetch::fetch_raw_record;
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
-	validate_record_user_access,
+	trace_denied_scan, validate_record_user_access,
 };
 use crate::exec::pre_decode_filter::{PreDecodeFilterStatus, pre_decode_filter_for_execute};
 use crate::exec::topk_pushdown::{TopKPushdownStatus, topk_probe_for_execute};
 use crate::exec::{
-	AccessMode, ContextLevel, ExecOperator, ExecutionContext, FlowResult, OperatorMetrics,
-	OutputOrdering, PhysicalExpr, ValueBatch, ValueBatchStream, monitor_stream,
+	AccessMode, ContextLevel, EvalContext, ExecOperator, ExecutionContext, FlowResult,
+	OperatorMetrics, OutputOrdering, PhysicalExpr, ValueBatch, ValueBatchStream, monitor_stream,
 };
-use crate::expr::{ControlFlow, ControlFlowExt};
+use crate::expr::{ControlFlow, ControlFlowExt, Part};
 use crate::iam::Action;
 use crate::idx::planner::ScanDirection;
 use crate::key::record;
-use crate::val::TableName;
+use crate::kvs::{Lookup, Predicate, TemporaryIndex};
+use crate::val::{RecordId, RecordIdKey, RecordIdKeyRange, TableName, Value};
+
+/// An equality predicate on a top-level field, which a temporary index can
+/// look the matching records up with.
+#[derive(Debug, Clone)]
+pub(crate) struct TemporaryIndexProbe {
+	/// The top-level field compared against
+	pub(crate) field: String,
+	/// The value the field is compared against
+	pub(crate) value: Arc<dyn PhysicalExpr>,
+}
 
 /// Direct KV range scan over a known table.
 ///
@@ -53,6 +74,11 @@ pub struct TableScan {
 	pub(crate) pre_decode_filter_status: PreDecodeFilterStatus,
 	/// TopK threshold pushdown status (plan-time); see [`TopKPushdownStatus`].
 	pub(crate) topk_pushdown_status: TopKPushdownStatus,
+	/// Equality predicate which a temporary index may serve at runtime.
+	pub(crate) temporary_index: Option<TemporaryIndexProbe>,
+	/// Ranges of record ids which contain every matching record, scanned in
+	/// order instead of the whole table.
+	pub(crate) id_ranges: Option<Vec<RecordIdKeyRange>>,
 	pub(crate) metrics: Arc<OperatorMetrics>,
 }
 
@@ -77,6 +103,8 @@ impl TableScan {
 			resolved: None,
 			pre_decode_filter_status: PreDecodeFilterStatus::NotApplicable,
 			topk_pushdown_status: TopKPushdownStatus::NotApplicable,
+			temporary_index: None,
+			id_ranges: None,
 			metrics: Arc::new(OperatorMetrics::new()),
 		}
 	}
@@ -98,6 +126,18 @@ impl TableScan {
 		self.topk_pushdown_status = status;
 		self
 	}
+
+	/// Set the equality predicate which a temporary index may serve.
+	pub(crate) fn with_temporary_index(mut self, probe: TemporaryIndexProbe) -> Self {
+		self.temporary_index = Some(probe);
+		self
+	}
+
+	/// Set the ranges of record ids to scan instead of the whole table.
+	pub(crate) fn with_id_ranges(mut self, ranges: Vec<RecordIdKeyRange>) -> Self {
+		self.id_ranges = Some(ranges);
+		self
+	}
 }
 impl ExecOperator for TableScan {
 	fn name(&self) -> &'static str {
@@ -122,6 +162,17 @@ impl ExecOperator for TableScan {
 		if let Some(s) = self.topk_pushdown_status.explain_text() {
 			attrs.push(("topk_pushdown".to_string(), s.to_string()));
 		}
+		if let Some(ref probe) = self.temporary_index {
+			attrs.push(("temporary_index".to_string(), probe.field.clone()));
+		}
+		if let Some(ref ranges) = self.id_ranges {
+			use surrealdb_types::ToSql;
+			let ranges = ranges
+				.iter()
+				.map(|r| Value::Range(Box::new(r.clone().into_value_range())).to_sql())
+				.collect::<Vec<_>>();
+			attrs.push(("id_ranges".to_string(), ranges.join(", ")));
+		}
 		attrs
 	}
 
@@ -179,6 +230,8 @@ impl ExecOperator for TableScan {
 		let needed_fields = self.needed_fields.clone();
 		let pre_decode_filter_status = self.pre_decode_filter_status.clone();
 		let topk_pushdown_status = self.topk_pushdown_status.clone();
+		let temporary_index = self.temporary_index.clone();
+		let id_ranges = self.id_ranges.clone();
 		let metrics = Arc::clone(&self.metrics);
 		let ctx = ctx.clone();
 
@@ -243,6 +296,7 @@ impl ExecOperator for TableScan {
 			};
 
 			if matches!(select_permission, PhysicalPermission::Deny) {
+				trace_denied_scan(&ctx, &table_name);
 				return;
 			}
 
@@ -250,17 +304,77 @@ impl ExecOperator for TableScan {
 				return;
 			}
 
+			// Look the records up in a temporary index, when one applies
+			if let Some(mut ids) = temporary_index_lookup(
+				&ctx, temporary_index.as_ref(), ns.namespace_id, db.database_id, &table_name,
+				version,
+			).await? {
+				if matches!(direction, ScanDirection::Backward) {
+					ids.reverse();
+				}
+				let mut pipeline = ScanPipeline::new(
+					"TableScan",
+					select_permission, predicate, field_state,
+					check_perms, limit_val, start_val,
+				);
+				for chunk in ids.chunks(crate::kvs::NORMAL_BATCH_SIZE as usize) {
+					if ctx.cancellation().is_cancelled() {
+						Err(ControlFlow::Err(
+							anyhow::anyhow!(crate::err::Error::QueryCancelled),
+						))?;
+					}
+					let mut values = Vec::with_capacity(chunk.len());
+					for key in chunk {
+						let rid = RecordId {
+							table: table_name.clone(),
+							key: key.clone(),
+						};
+						if let Some(v) = fetch_raw_record(&ctx, &rid, None).await? {
+							values.push(v);
+						}
+					}
+					ctx.ctx().scan_recorder().record(values.len() as u64, "TableScan")?;
+					let cont = pipeline.process_batch(&mut values, &ctx).await?;
+					if !values.is_empty() {
+						yield ValueBatch { values };
+					}
+					if !cont {
+						break;
+					}
+				}
+				return;
+			}
+
+			if let Some(counters) = ctx.ctx().statement_counters() {
+				counters.record_table_scan();
+			}
+
 			// Row-filtering (permissions, WHERE) prevents positional pushdown;
 			// row-modifying ops (computed fields, field perms) do not.
 			let needs_row_filtering = ScanPipeline::compute_needs_row_filtering(
 				&select_permission, predicate.as_ref(),
 			);
 
-			let pre_skip = if !needs_row_filtering { start_val } else { 0 };
-			let effective_storage_limit = if !needs_row_filtering { limit_val } else { None };
-
-			let beg = record::prefix(ns.namespace_id, db.database_id, &table_name)?;
-			let end = record::suffix(ns.namespace_id, db.database_id, &table_name)?;
+			// Positional pushdown only applies to a single scanned range
+			let pushdown = !needs_row_filtering && id_ranges.is_none();
+			let pre_skip = if pushdown { start_val } else { 0 };
+			let effective_storage_limit = if pushdown { limit_val } else { None };
+
+			// Scan the whole table, or only the ranges of record ids
+			let keys = match &id_ranges {
+				Some(ranges) => ranges
+					.iter()
+					.map(|r| {
+						let beg = range_start_key(ns.namespace_id, db.database_id, &table_name, &r.start)?;
+						let end = range_end_key(ns.namespace_id, db.database_id, &table_name, &r.end)?;
+						Ok((beg, end))
+					})
+					.collect::<Result<Vec<_>, ControlFlow>>()?,
+				None => vec![(
+					record::prefix(ns.namespace_id, db.database_id, &table_name)?,
+					record::suffix(ns.namespace_id, db.database_id, &table_name)?,
+				)],
+			};
 			let limit_hint = limit_val.map(|l| (l + start_val).try_into().unwrap_or(u32::MAX));
 			let pre_decode_filter = pre_decode_filter_for_execute(
 				&pre_decode_filter_status,
@@ -274,30 +388,35 @@ impl ExecOperator for TableScan {
 				check_perms,
 				&metrics,
 			);
-			let mut source = kv_scan_stream(
-				Arc::clone(&txn), beg, end, version,
-				effective_storage_limit, direction, pre_skip, limit_hint,
-				pre_decode_filter, topk_probe,
-			);
-
 			let mut pipeline = ScanPipeline::new(
+				"TableScan",
 				select_permission, predicate, field_state,
 				check_perms, limit_val, start_val.saturating_sub(pre_skip),
 			);
 
-			while let Some(batch_result) = source.next().await {
-				if ctx.cancellation().is_cancelled() {
-					Err(ControlFlow::Err(
-						anyhow::anyhow!(crate::err::Error::QueryCancelled),
-					))?;
-				}
-				let mut batch = batch_result?;
-				let cont = pipeline.process_batch(&mut batch.values, &ctx).await?;
-				if !batch.values.is_empty() {
-					yield ValueBatch { values: batch.values };
-				}
-				if !cont {
-					break;
+			'ranges: for (beg, end) in keys {
+				let mut source = kv_scan_stream(
+					Arc::clone(&txn), beg, end, version,
+					effective_storage_limit, direction, pre_skip, limit_hint,
+					pre_decode_filter.clone(), topk_probe.clone(),
+					ctx.ctx().scan_recorder(),
+					"TableScan",
+				);
+
+				while let Some(batch_result) = source.next().await {
+					if ctx.cancellation().is_cancelled() {
+						Err(ControlFlow::Err(
+							anyhow::anyhow!(crate::err::Error::QueryCancelled),
+						))?;
+					}
+					let mut batch = batch_result?;
+					let cont = pipeline.process_batch(&mut batch.values, &ctx).await?;
+					if !batch.values.is_empty() {
+						yield ValueBatch { values: batch.values };
+					}
+					if !cont {
+						break 'ranges;
+					}
 				}
 			}
 		};
@@ -305,3 +424,91 @@ impl ExecOperator for TableScan {
 		Ok(monitor_stream(Box::pin(stream), "TableScan", &self.metrics))
 	}
 }
+
+/// Looks the records which might match the equality predicate of a scan up in
+/// a temporary index, building the index from the table first once the
+/// predicate has been scanned often enough. Returns `None` when the table has
+/// to be scanned instead.
+async fn temporary_index_lookup(
+	ctx: &ExecutionContext,
+	probe: Option<&TemporaryIndexProbe>,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	version: Option<u64>,
+) -> Result<Option<Vec<RecordIdKey>>, ControlFlow> {
+	// Temporary indexes only reflect the latest version of the table
+	let Some(probe) = probe.filter(|_| version.is_none()) else {
+		return Ok(None);
+	};
+	let value = probe.value.evaluate(EvalContext::from_exec_ctx(ctx)).await?;
+	if !TemporaryIndex::supports(&value) {
+		return Ok(None);
+	}
+	let config = &ctx.ctx().config;
+	let txn = ctx.txn();
+	let writes = txn.table_writes();
+	let predicate = Predicate {
+		ns,
+		db,
+		tb: tb.clone(),
+		field: probe.field.clone(),
+	};
+	let threshold = config.temporary_index_threshold;
+	let index = match writes.indexes().lookup(&predicate, writes.epoch(), threshold) {
+		Lookup::Scan => return Ok(None),
+		Lookup::Use(index) => index,
+		Lookup::Build(mut builder) => {
+			// Computed fields are not stored, so can not be indexed
+			let fields = txn.all_tb_fields(ns, db, tb, None).await?;
+			let computed = fields.iter().filter(|f| f.computed.is_some()).any(
+				|f| matches!(f.name.0.first(), Some(Part::Field(n)) if n.as_str() == probe.field),
+			);
+			if computed {
+				writes.indexes().discard(&predicate);
+				return Ok(None);
+			}
+			// Index the field of every record of the table
+			let beg = record::prefix(ns, db, tb)?;
+			let end = record::suffix(ns, db, tb)?;
+			let mut source = kv_scan_stream(
+				Arc::clone(&txn),
+				beg,
+				end,
+				None,
+				None,
+				ScanDirection::Forward,
+				0,
+				None,
+				None,
+				None,
+				ctx.ctx().scan_recorder(),
+				"TableScan",
+			);
+			while let Some(batch) = source.next().await {
+				for doc in batch?.values {
+					let Value::Object(obj) = &doc else {
+						continue;
+					};
+					let Some(Value::RecordId(rid)) = obj.get("id") else {
+						continue;
+					};
+					let field = obj.get(probe.field.as_str()).unwrap_or(&Value::None);
+					if !builder.push(rid.key.clone(), field) {
+						writes.indexes().discard(&predicate);
+						return Ok(None);
+					}
+				}
+			}
+			let index = builder.finish();
+			writes.indexes().install(
+				&predicate,
+				Arc::clone(&index),
+				config.temporary_index_min_rows,
+				config.temporary_index_limit,
+			);
+			index
+		}
+	};
+	Ok(index.get(&value).map(<[RecordIdKey]>::to_vec))
+}
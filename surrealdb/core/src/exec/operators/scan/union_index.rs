@@ -23,7 +23,7 @@ use crate::exec::operators::SortDirection;
 use crate::exec::ordering::{OutputOrdering, SortProperty};
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
-	validate_record_user_access,
+	trace_denied_scan, validate_record_user_access,
 };
 use crate::exec::{
 	AccessMode, CombineAccessModes, ContextLevel, ExecOperator, ExecutionContext, FlowResult,
@@ -433,11 +433,13 @@ impl ExecOperator for UnionIndexScan {
 
 			// Early exit if denied
 			if matches!(select_permission, PhysicalPermission::Deny) {
+				trace_denied_scan(&ctx, &table_name);
 				return;
 			}
 
 			// Build the pipeline (no predicate/limit/start — outer operators handle those)
 			let mut pipeline = ScanPipeline::new(
+				"UnionIndexScan",
 				select_permission, None, field_state,
 				check_perms, None, 0,
 			);
@@ -0,0 +1,79 @@
+//! ScanLimitScope operator — limits the number of rows scanned by the
+//! operator tree of a `SELECT` with a `SCAN LIMIT` clause.
+
+use std::sync::Arc;
+
+use crate::exec::{
+	AccessMode, ContextLevel, ExecOperator, ExecutionContext, FlowResult, OperatorMetrics,
+	ValueBatchStream, monitor_stream,
+};
+
+/// Runs the inner operator tree in a context whose scans may read at most
+/// `limit` rows.
+///
+/// Inserted by the planner at the top of the SELECT pipeline when a
+/// `SCAN LIMIT` clause is present. The limit only covers the scans of this
+/// SELECT, including the subqueries within it, and applies along with the
+/// limits of any enclosing SELECT and of the statement, so it can lower the
+/// configured maximum but never raise it.
+#[derive(Debug, Clone)]
+pub struct ScanLimitScope {
+	pub(crate) inner: Arc<dyn ExecOperator>,
+	pub(crate) limit: u64,
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl ScanLimitScope {
+	pub(crate) fn new(inner: Arc<dyn ExecOperator>, limit: u64) -> Self {
+		Self {
+			inner,
+			limit,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+impl ExecOperator for ScanLimitScope {
+	fn name(&self) -> &'static str {
+		"ScanLimitScope"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		vec![("limit".to_string(), self.limit.to_string())]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		self.inner.required_context()
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		self.inner.access_mode()
+	}
+
+	fn cardinality_hint(&self) -> crate::exec::CardinalityHint {
+		self.inner.cardinality_hint()
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		vec![&self.inner]
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn is_scalar(&self) -> bool {
+		self.inner.is_scalar()
+	}
+
+	fn output_ordering(&self) -> crate::exec::OutputOrdering {
+		self.inner.output_ordering()
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let mut child = crate::ctx::Context::new_child(ctx.ctx());
+		child.set_scan_limit(self.limit);
+		let scoped = ctx.with_new_ctx(child.freeze());
+		Ok(monitor_stream(self.inner.execute(&scoped)?, "ScanLimitScope", &self.metrics))
+	}
+}
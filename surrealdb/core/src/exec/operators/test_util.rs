@@ -93,6 +93,7 @@ pub(crate) fn root_ctx() -> ExecutionContext {
 		current_value: None,
 		skip_fetch_perms: false,
 		version_stamp: None,
+		permission_trace: None,
 	})
 }
 
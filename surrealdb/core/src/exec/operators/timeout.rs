@@ -2,7 +2,10 @@
 //!
 //! Wraps an input operator stream and enforces a maximum execution
 //! duration. If the timeout expires before the input completes, a
-//! `QueryTimedout` error is returned.
+//! `QueryTimedout` error is returned, unless the statement asked for
+//! `RETURN PARTIAL`, in which case the stream ends with the batches produced
+//! so far. When the SELECT is the statement itself, rather than a subquery
+//! within it, the statement is also marked as truncated in its stats.
 
 use std::sync::Arc;
 
@@ -16,15 +19,31 @@ use crate::exec::{
 use crate::expr::{ControlFlow, ControlFlowExt};
 use crate::val::Duration;
 
+/// What a [`Timeout`] does once its duration expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OnExpiry {
+	/// Fail with a `QueryTimedout` error.
+	Fail,
+	/// End the stream with the batches produced so far.
+	Partial,
+	/// End the stream with the batches produced so far, and mark the
+	/// statement as truncated in its stats. Only the statement's own SELECT
+	/// uses this, so that a subquery does not mark the statement around it.
+	PartialStatement,
+}
+
 /// Applies a timeout to the execution of its input operator.
 ///
-/// If the timeout expires before the input stream completes, an error is returned.
+/// If the timeout expires before the input stream completes, an error is returned,
+/// or the stream ends early when partial results were asked for.
 /// This is typically applied as the outermost operator for a query.
 #[derive(Debug, Clone)]
 pub struct Timeout {
 	pub(crate) input: Arc<dyn ExecOperator>,
 	/// The timeout duration. If None, no timeout is applied.
 	pub(crate) timeout: Option<Arc<dyn PhysicalExpr>>,
+	/// What to do once the timeout expires.
+	pub(crate) on_expiry: OnExpiry,
 	pub(crate) metrics: Arc<OperatorMetrics>,
 }
 
@@ -32,10 +51,12 @@ impl Timeout {
 	pub(crate) fn new(
 		input: Arc<dyn ExecOperator>,
 		timeout: Option<Arc<dyn PhysicalExpr>>,
+		on_expiry: OnExpiry,
 	) -> Self {
 		Self {
 			input,
 			timeout,
+			on_expiry,
 			metrics: Arc::new(OperatorMetrics::new()),
 		}
 	}
@@ -46,11 +67,14 @@ impl ExecOperator for Timeout {
 	}
 
 	fn attrs(&self) -> Vec<(String, String)> {
+		let mut attrs = Vec::new();
 		if let Some(timeout) = &self.timeout {
-			vec![("duration".to_string(), timeout.to_sql())]
-		} else {
-			vec![]
+			attrs.push(("duration".to_string(), timeout.to_sql()));
+		}
+		if self.on_expiry != OnExpiry::Fail {
+			attrs.push(("partial".to_string(), "true".to_string()));
 		}
+		attrs
 	}
 
 	fn required_context(&self) -> ContextLevel {
@@ -104,6 +128,7 @@ impl ExecOperator for Timeout {
 
 		// Evaluate the timeout expression to get the duration
 		let timeout_expr = Arc::clone(timeout_expr);
+		let on_expiry = self.on_expiry;
 		let ctx = ctx.clone();
 
 		let timeout_stream = async_stream::try_stream! {
@@ -130,6 +155,10 @@ impl ExecOperator for Timeout {
 				// Check if we've exceeded the timeout
 				let remaining = timeout_instant.saturating_duration_since(tokio::time::Instant::now());
 				if remaining.is_zero() {
+					if on_expiry != OnExpiry::Fail {
+						mark_truncated(&ctx, on_expiry);
+						break;
+					}
 					Err(ControlFlow::Err(anyhow::anyhow!(Error::QueryTimedout(duration))))?;
 				}
 
@@ -149,6 +178,10 @@ impl ExecOperator for Timeout {
 					}
 					Err(_) => {
 						// Timeout expired
+						if on_expiry != OnExpiry::Fail {
+							mark_truncated(&ctx, on_expiry);
+							break;
+						}
 						Err(ControlFlow::Err(anyhow::anyhow!(Error::QueryTimedout(duration))))?;
 					}
 				}
@@ -158,3 +191,14 @@ impl ExecOperator for Timeout {
 		Ok(monitor_stream(Box::pin(timeout_stream), "Timeout", &self.metrics))
 	}
 }
+
+/// Marks the statement as having returned partial results, if the timeout
+/// belongs to the statement itself
+fn mark_truncated(ctx: &ExecutionContext, on_expiry: OnExpiry) {
+	if on_expiry != OnExpiry::PartialStatement {
+		return;
+	}
+	if let Some(counters) = ctx.ctx().statement_counters() {
+		counters.record_truncated();
+	}
+}
@@ -137,6 +137,16 @@ pub(crate) async fn evaluate_field(
 		}
 
 		Value::Array(arr) => {
+			// Load linked records, such as the results of a graph traversal,
+			// with batched multi-gets rather than one lookup per element
+			let rids: Vec<_> = arr
+				.iter()
+				.filter_map(|v| match v {
+					Value::RecordId(rid) => Some(rid.clone()),
+					_ => None,
+				})
+				.collect();
+			crate::exec::operators::fetch::prefetch_records(ctx.exec_ctx, &rids).await?;
 			// Apply field access to each element (may involve fetches)
 			let mut results = Vec::with_capacity(arr.len());
 			for v in arr.iter() {
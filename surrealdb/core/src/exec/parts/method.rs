@@ -11,6 +11,7 @@ use crate::exec::physical_expr::function::validate_return;
 use crate::exec::physical_expr::{BlockPhysicalExpr, EvalContext, PhysicalExpr};
 use crate::exec::{AccessMode, BoxFut, CombineAccessModes, ContextLevel};
 use crate::expr::FlowResult;
+use crate::kvs::SessionTables;
 use crate::val::{Closure, Value};
 
 // ============================================================================
@@ -68,7 +69,13 @@ impl PhysicalExpr for MethodPart {
 			// Rewrite error names for method calls: when invoked as `.extend()`,
 			// the error should say "function extend()" not "function object::extend()".
 			match result {
-				Ok(v) => Ok(v),
+				Ok(mut v) => {
+					// A record built from its table name may be in a session table
+					if SessionTables::is_table_constructor(func.name()) {
+						ctx.exec_ctx.resolve_session_tables(&mut v);
+					}
+					Ok(v)
+				}
 				Err(e) => {
 					if let Some(crate::err::Error::InvalidFunctionArguments {
 						message,
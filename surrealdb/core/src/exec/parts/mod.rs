@@ -13,7 +13,9 @@ use crate::exec::physical_expr::{EvalContext, PhysicalExpr};
 use crate::expr::FlowResult;
 // Re-export recursion utilities from the canonical definitions in `expr::idiom::recursion`.
 // These are shared between the legacy compute path and the streaming execution engine.
-pub(crate) use crate::expr::idiom::recursion::{clean_iteration, get_final, is_final};
+pub(crate) use crate::expr::idiom::recursion::{
+	RecursionTree, clean_iteration, get_final, is_final,
+};
 use crate::val::Value;
 
 pub(crate) mod array_ops;
@@ -34,6 +34,9 @@ pub enum PhysicalRecurseInstruction {
 		/// Expression that evaluates to the target RecordId
 		target: Arc<dyn PhysicalExpr>,
 	},
+
+	/// Nest every node encountered beneath its parent
+	Tree,
 }
 
 // ============================================================================
@@ -142,6 +145,7 @@ impl ToSql for RecursePart {
 					"default" => {}
 					"collect" => f.push_str("+collect"),
 					"path" => f.push_str("+path"),
+					"tree" => f.push_str("+tree"),
 					"shortest" => f.push_str("+shortest=..."),
 					_ => {}
 				}
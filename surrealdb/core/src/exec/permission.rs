@@ -7,7 +7,9 @@
 
 use std::sync::Arc;
 
+use parking_lot::Mutex;
 use reblessive::tree::Stk;
+use surrealdb_types::ToSql;
 
 use crate::catalog::{Permission, TableDefinition};
 use crate::ctx::FrozenContext;
@@ -16,9 +18,9 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::exec::planner::Planner;
 use crate::exec::{DatabaseContext, EvalContext, ExecutionContext, PhysicalExpr};
-use crate::expr::FlowResultExt as _;
+use crate::expr::{FlowResultExt as _, Idiom};
 use crate::iam::Action;
-use crate::val::Value;
+use crate::val::{TableName, Value};
 
 /// Result of a permission check.
 #[derive(Debug, Clone)]
@@ -31,6 +33,115 @@ pub enum PhysicalPermission {
 	Conditional(Arc<dyn PhysicalExpr>),
 }
 
+/// The permission checks made while executing a statement under
+/// `EXPLAIN PERMISSIONS`, grouped by the table, field and expression which
+/// was checked.
+#[derive(Debug, Default)]
+pub(crate) struct PermissionTrace {
+	checks: Mutex<Vec<PermissionCheck>>,
+}
+
+/// The outcome of the checks of a single permission expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PermissionCheck {
+	/// The table whose permission was checked
+	pub table: String,
+	/// The field whose permission was checked, if it was a field permission
+	pub field: Option<String>,
+	/// The permission expression, as it was defined
+	pub expression: String,
+	/// The number of times the permission was checked
+	pub evaluated: u64,
+	/// The number of rows or fields which were filtered by the permission
+	pub denied: u64,
+}
+
+impl PermissionTrace {
+	fn record(&self, table: String, field: Option<String>, expression: String, allowed: bool) {
+		let mut checks = self.checks.lock();
+		let pos = checks
+			.iter()
+			.position(|c| c.table == table && c.field == field && c.expression == expression);
+		let check = match pos {
+			Some(pos) => &mut checks[pos],
+			None => {
+				checks.push(PermissionCheck {
+					table,
+					field,
+					expression,
+					evaluated: 0,
+					denied: 0,
+				});
+				checks.last_mut().expect("a check was just pushed")
+			}
+		};
+		check.evaluated += 1;
+		if !allowed {
+			check.denied += 1;
+		}
+	}
+
+	/// The checks which were made, in the order they were first made.
+	pub(crate) fn checks(&self) -> Vec<PermissionCheck> {
+		self.checks.lock().clone()
+	}
+}
+
+/// Formats a permission the way it was defined.
+fn permission_expression(permission: &PhysicalPermission) -> String {
+	match permission {
+		PhysicalPermission::Allow => "FULL".to_string(),
+		PhysicalPermission::Deny => "NONE".to_string(),
+		PhysicalPermission::Conditional(expr) => format!("WHERE {}", expr.to_sql()),
+	}
+}
+
+/// Record a row or field permission check against a record, when the
+/// statement is executed under `EXPLAIN PERMISSIONS`.
+///
+/// Checks which are bypassed (unconditional `FULL` permissions, or checks
+/// inside a permission predicate) are not recorded.
+pub(crate) fn trace_permission(
+	ctx: &ExecutionContext,
+	permission: &PhysicalPermission,
+	record: &Value,
+	field: Option<&Idiom>,
+	allowed: bool,
+) {
+	let root = ctx.root();
+	let Some(trace) = &root.permission_trace else {
+		return;
+	};
+	if root.skip_fetch_perms || matches!(permission, PhysicalPermission::Allow) {
+		return;
+	}
+	let table = match record {
+		Value::Object(obj) => match obj.get("id") {
+			Some(Value::RecordId(rid)) => rid.table.to_string(),
+			_ => String::new(),
+		},
+		_ => String::new(),
+	};
+	trace.record(table, field.map(|f| f.to_sql()), permission_expression(permission), allowed);
+}
+
+/// Record that a scan of a table was skipped entirely because its SELECT
+/// permission denies access, when the statement is executed under
+/// `EXPLAIN PERMISSIONS`.
+pub(crate) fn trace_denied_scan(ctx: &ExecutionContext, table: &TableName) {
+	let root = ctx.root();
+	if let Some(trace) = &root.permission_trace
+		&& !root.skip_fetch_perms
+	{
+		trace.record(
+			table.to_string(),
+			None,
+			permission_expression(&PhysicalPermission::Deny),
+			false,
+		);
+	}
+}
+
 /// Convert a catalog Permission to a PhysicalPermission via the given
 /// planner. Inner subqueries inherit the planner's `CycleGuard`, so a
 /// self-referential permission (`WHERE (SELECT FROM same_table) != NONE`)
@@ -161,13 +272,25 @@ pub(crate) fn validate_record_user_access(db_ctx: &DatabaseContext) -> Result<()
 ///
 /// Returns `true` if access is allowed, `false` if denied.
 ///
-/// `value_param` lets field-level callers bind the `$value` parameter to
-/// the field's picked value (matching legacy `pluck.rs` semantics). Pass
-/// `None` for table-level checks, where `$value` has no meaning.
+/// `field` lets field-level callers name the field being checked, and bind
+/// the `$value` parameter to the field's picked value (matching legacy
+/// `pluck.rs` semantics). Pass `None` for table-level checks, where `$value`
+/// has no meaning.
 pub(crate) async fn check_permission_for_value(
 	permission: &PhysicalPermission,
 	value: &Value,
-	value_param: Option<&Value>,
+	field: Option<(&Idiom, &Value)>,
+	ctx: &ExecutionContext,
+) -> Result<bool, Error> {
+	let allowed = evaluate_permission_for_value(permission, value, field, ctx).await?;
+	trace_permission(ctx, permission, value, field.map(|(idiom, _)| idiom), allowed);
+	Ok(allowed)
+}
+
+async fn evaluate_permission_for_value(
+	permission: &PhysicalPermission,
+	value: &Value,
+	field: Option<(&Idiom, &Value)>,
 	ctx: &ExecutionContext,
 ) -> Result<bool, Error> {
 	match permission {
@@ -182,8 +305,8 @@ pub(crate) async fn check_permission_for_value(
 			}
 
 			let bound_ctx;
-			let exec_ctx = match value_param {
-				Some(v) => {
+			let exec_ctx = match field {
+				Some((_, v)) => {
 					bound_ctx = ctx.with_param("value", v.clone());
 					&bound_ctx
 				}
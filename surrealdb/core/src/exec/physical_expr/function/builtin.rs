@@ -8,6 +8,7 @@ use super::helpers::{args_access_mode, args_required_context, evaluate_args};
 use crate::exec::physical_expr::{EvalContext, PhysicalExpr};
 use crate::exec::{AccessMode, BoxFut};
 use crate::expr::FlowResult;
+use crate::kvs::SessionTables;
 use crate::val::Value;
 
 /// Built-in function expression - math::abs(), string::len(), etc.
@@ -55,24 +56,31 @@ impl PhysicalExpr for BuiltinFunctionExec {
 			let args = evaluate_args(&self.arguments, ctx.clone()).await?;
 
 			// Invoke the function based on whether it's pure or needs context
-			if func.is_pure() && !func.is_async() {
-				Ok(func.invoke(args)?)
+			let mut res = if func.is_pure() && !func.is_async() {
+				func.invoke(args)?
 			} else {
 				// Surface the plan-time nesting depth so `eval::*` can continue
 				// counting toward `max_computation_depth` when it re-plans its
 				// query string. Harmless for every other builtin (they ignore it).
-				let mut ctx = ctx;
+				let mut ctx = ctx.clone();
 				ctx.plan_depth = self.plan_depth;
-				Ok(func.invoke_async(&ctx, args).await?)
+				func.invoke_async(&ctx, args).await?
+			};
+			// A table built from its name may be a session table
+			if SessionTables::is_table_constructor(&self.name) {
+				ctx.exec_ctx.resolve_session_tables(&mut res);
 			}
+			Ok(res)
 		})
 	}
 
 	fn access_mode(&self) -> AccessMode {
-		// `api::invoke` and the `eval::*` functions can run nested writes, so they
-		// are read-write; everything else is read-only.
-		let func_mode = if matches!(self.name.as_str(), "api::invoke" | "eval::surql" | "eval::gql")
-		{
+		// `api::invoke`, `query::run` and the `eval::*` functions can run nested
+		// writes, so they are read-write; everything else is read-only.
+		let func_mode = if matches!(
+			self.name.as_str(),
+			"api::invoke" | "eval::surql" | "eval::gql" | "query::run"
+		) {
 			AccessMode::ReadWrite
 		} else {
 			AccessMode::ReadOnly
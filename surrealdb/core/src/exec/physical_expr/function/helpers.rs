@@ -28,33 +28,31 @@ pub(crate) async fn check_permission(
 	func_name: &str,
 	ctx: &EvalContext<'_>,
 ) -> FlowResult<()> {
-	match permission {
-		Permission::Full => Ok(()),
-		Permission::None => Err(Error::FunctionPermissions {
+	if is_permitted(permission, ctx).await? {
+		Ok(())
+	} else {
+		Err(Error::FunctionPermissions {
 			name: func_name.to_string(),
 		}
-		.into()),
+		.into())
+	}
+}
+
+/// Evaluate the PERMISSIONS clause of a stored definition, such as a function
+/// or a query template, returning whether it is allowed.
+pub(crate) async fn is_permitted(
+	permission: &Permission,
+	ctx: &EvalContext<'_>,
+) -> FlowResult<bool> {
+	match permission {
+		Permission::Full => Ok(true),
+		Permission::None => Ok(false),
 		Permission::Specific(expr) => {
 			// Plan and evaluate the permission expression
 			match expr_to_physical_expr(expr.clone(), ctx.exec_ctx.ctx()).await {
-				Ok(phys_expr) => {
-					let result = phys_expr.evaluate(ctx.clone()).await?;
-					if !result.is_truthy() {
-						Err(Error::FunctionPermissions {
-							name: func_name.to_string(),
-						}
-						.into())
-					} else {
-						Ok(())
-					}
-				}
-				Err(_) => {
-					// If we can't plan the expression, deny by default
-					Err(Error::FunctionPermissions {
-						name: func_name.to_string(),
-					}
-					.into())
-				}
+				Ok(phys_expr) => Ok(phys_expr.evaluate(ctx.clone()).await?.is_truthy()),
+				// If we can't plan the expression, deny by default
+				Err(_) => Ok(false),
 			}
 		}
 	}
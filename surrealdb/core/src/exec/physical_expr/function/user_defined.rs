@@ -11,6 +11,7 @@ use super::helpers::{
 	validate_return,
 };
 use crate::catalog::providers::DatabaseProvider;
+use crate::ctx::Context;
 use crate::err::Error;
 use crate::exec::physical_expr::{BlockPhysicalExpr, EvalContext, PhysicalExpr};
 use crate::exec::{AccessMode, BoxFut};
@@ -74,6 +75,15 @@ impl PhysicalExpr for UserDefinedFunctionExec {
 					anyhow::anyhow!("Invalid auth limit on function '{}': {}", func_name, e)
 				})?;
 			let limited_ctx = ctx.exec_ctx.with_limited_auth(&auth_limit);
+			// Restrict network access to the targets granted by a CAPABILITIES clause
+			let limited_ctx = match &func_def.capabilities {
+				Some(caps) => {
+					let net = caps.net_targets(&limited_ctx.capabilities())?;
+					let scoped = Context::new_child_with_net_targets(limited_ctx.ctx(), net)?;
+					limited_ctx.with_new_ctx(scoped.freeze())
+				}
+				None => limited_ctx,
+			};
 			let ctx = EvalContext {
 				exec_ctx: &limited_ctx,
 				current_value: ctx.current_value,
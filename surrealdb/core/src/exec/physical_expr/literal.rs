@@ -179,7 +179,10 @@ impl PhysicalExpr for Param {
 			// FrozenContext handles scoped parameter lookup via parent-chain,
 			// including protected params ($auth, $access, $token, $session)
 			if let Some(v) = ctx.exec_ctx.value(self.0.as_str()) {
-				return Ok(v.clone());
+				// The param may refer to a session table by its name
+				let mut v = v.clone();
+				ctx.exec_ctx.resolve_session_tables(&mut v);
+				return Ok(v);
 			}
 
 			// $parent falls back to document_root when not explicitly bound.
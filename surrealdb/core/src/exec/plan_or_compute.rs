@@ -347,6 +347,7 @@ pub(crate) fn expr_required_context(expr: &Expr) -> ContextLevel {
 
 		// DML statements need a database
 		Expr::Select(_)
+		| Expr::With(_)
 		| Expr::Create(_)
 		| Expr::Update(_)
 		| Expr::Upsert(_)
@@ -390,11 +391,12 @@ pub(crate) fn block_required_context(block: &Block) -> ContextLevel {
 /// Determine the minimum [`ContextLevel`] required by an [`InfoStatement`].
 fn info_stmt_required_context(info: &InfoStatement) -> ContextLevel {
 	match info {
-		InfoStatement::Root(_, _) => ContextLevel::Root,
+		InfoStatement::Root(_, _) | InfoStatement::Locks => ContextLevel::Root,
 		InfoStatement::Ns(_, _) => ContextLevel::Namespace,
-		InfoStatement::Db(_, _) | InfoStatement::Tb(_, _, _) | InfoStatement::Index(_, _, _) => {
-			ContextLevel::Database
-		}
+		InfoStatement::Db(_, _)
+		| InfoStatement::Tb(_, _, _)
+		| InfoStatement::Index(_, _, _)
+		| InfoStatement::IndexStats(_) => ContextLevel::Database,
 		InfoStatement::User(user_expr, base, _) => {
 			let base_ctx = match base {
 				Some(Base::Root) | None => ContextLevel::Root,
@@ -84,8 +84,8 @@ use crate::exec::ExecOperator;
 use crate::exec::function::FunctionRegistry;
 use crate::exec::operators::{
 	AnalyzePlan, DatabaseInfoPlan, ExplainPlan, ExprPlan, Fetch, ForeachPlan, IfElsePlan,
-	IndexInfoPlan, NamespaceInfoPlan, ReturnPlan, RootInfoPlan, SequencePlan, SleepPlan,
-	TableInfoPlan, UserInfoPlan,
+	IndexInfoPlan, IndexStatsInfoPlan, LocksInfoPlan, NamespaceInfoPlan, PermissionsPlan,
+	ReturnPlan, RootInfoPlan, SequencePlan, SleepPlan, TableInfoPlan, UserInfoPlan,
 };
 use crate::exec::physical_expr::{
 	ArrayLiteral, BinaryOp, BlockPhysicalExpr, BuiltinFunctionExec, ClosureCallExec, ClosureExec,
@@ -526,7 +526,14 @@ impl<'ctx> Planner<'ctx> {
 	/// method only adds the [`require_planned`] strategy translation on
 	/// top.
 	pub async fn plan(&self, expr: &Expr) -> Result<Arc<dyn ExecOperator>, Error> {
-		let result = self.plan_expr(expr.clone()).await;
+		let result = match expr {
+			// A top-level SELECT is the statement itself, rather than a
+			// subquery, so it records partial results in the statement stats
+			Expr::Select(select) if self.depth == 0 => {
+				self.plan_select((**select).clone(), true).await
+			}
+			_ => self.plan_expr(expr.clone()).await,
+		};
 		self.require_planned(result)
 	}
 
@@ -586,6 +593,9 @@ impl<'ctx> Planner<'ctx> {
 			Expr::Block(b) => Ok(Arc::new(BlockPhysicalExpr {
 				block: *b,
 			})),
+			Expr::With(w) => Ok(Arc::new(BlockPhysicalExpr {
+				block: w.into_block(),
+			})),
 
 			// Control flow
 			Expr::Break => Ok(Arc::new(ControlFlowExpr {
@@ -1023,10 +1033,16 @@ impl<'ctx> Planner<'ctx> {
 			Expr::Explain {
 				format,
 				analyze,
+				permissions,
 				statement,
 			} => {
 				let inner_plan = self.plan_expr(*statement).await?;
-				if analyze {
+				if permissions {
+					Arc::new(PermissionsPlan {
+						plan: inner_plan,
+						format,
+					})
+				} else if analyze {
 					Arc::new(AnalyzePlan {
 						plan: inner_plan,
 						format,
@@ -1193,13 +1209,15 @@ impl<'ctx> Planner<'ctx> {
 			match expr {
 				Expr::Select(select) => self.plan_select_statement(*select).await,
 				Expr::Block(block) => self.plan_block(*block).await,
+				Expr::With(with) => self.plan_block(with.into_block()).await,
 				Expr::Return(output_stmt) => self.plan_return_statement(*output_stmt).await,
 				Expr::Let(let_stmt) => self.plan_let_statement(*let_stmt).await,
 				Expr::Explain {
 					format,
 					analyze,
+					permissions,
 					statement,
-				} => self.plan_explain_statement(format, analyze, *statement).await,
+				} => self.plan_explain_statement(format, analyze, permissions, *statement).await,
 				Expr::Info(info) => self.plan_info_statement(*info).await,
 				Expr::Foreach(stmt) => self.plan_foreach_statement(*stmt),
 				Expr::IfElse(stmt) => self.plan_if_else_statement(*stmt),
@@ -1292,10 +1310,16 @@ impl<'ctx> Planner<'ctx> {
 		&self,
 		format: crate::expr::ExplainFormat,
 		analyze: bool,
+		permissions: bool,
 		statement: Expr,
 	) -> Result<Arc<dyn ExecOperator>, Error> {
 		let inner_plan = self.plan_expr(statement).await?;
-		if analyze {
+		if permissions {
+			Ok(Arc::new(PermissionsPlan {
+				plan: inner_plan,
+				format,
+			}))
+		} else if analyze {
 			Ok(Arc::new(AnalyzePlan {
 				plan: inner_plan,
 				format,
@@ -1415,6 +1439,11 @@ impl<'ctx> Planner<'ctx> {
 				let table = self.physical_expr_as_name(table).await?;
 				Ok(Arc::new(IndexInfoPlan::new(index, table, structured)) as Arc<dyn ExecOperator>)
 			}
+			InfoStatement::IndexStats(table) => {
+				let table = self.physical_expr_as_name(table).await?;
+				Ok(Arc::new(IndexStatsInfoPlan::new(table)) as Arc<dyn ExecOperator>)
+			}
+			InfoStatement::Locks => Ok(Arc::new(LocksInfoPlan::new()) as Arc<dyn ExecOperator>),
 		}
 	}
 
@@ -462,6 +462,9 @@ impl<'ctx> Planner<'ctx> {
 			Some(RecurseInstruction::Path {
 				..
 			}) => Ok(PhysicalRecurseInstruction::Path),
+			Some(RecurseInstruction::Tree {
+				..
+			}) => Ok(PhysicalRecurseInstruction::Tree),
 			Some(RecurseInstruction::Shortest {
 				expects,
 				..
@@ -508,6 +511,8 @@ fn is_inclusive_recurse(instruction: &Option<RecurseInstruction>) -> bool {
 		}) | Some(RecurseInstruction::Shortest {
 			inclusive: true,
 			..
+		}) | Some(RecurseInstruction::Tree {
+			inclusive: true,
 		})
 	)
 }
@@ -26,32 +26,35 @@ pub(crate) use pipeline::{
 use super::Planner;
 use super::util::{
 	SELECT_ITERATION_PARAMS, all_value_sources, derive_field_name, extract_bruteforce_knn,
-	extract_count_field_names, extract_matches_context, extract_record_id_point_lookup,
-	extract_version, fold_condition_expressions, has_knn_k_operator, has_knn_ktree_operator,
-	has_knn_operator, has_top_level_or, idiom_to_field_name, index_covers_ordering,
-	is_bounded_topk_downstream, is_count_all_eligible, is_indexed_count_eligible,
-	order_is_scan_compatible, resolve_condition_params, resolve_param_value,
-	resolve_projection_field_idioms, strip_fts_condition, strip_index_conditions,
-	strip_knn_from_condition, strip_union_index_conditions,
+	extract_count_field_names, extract_id_ranges, extract_matches_context,
+	extract_record_id_point_lookup, extract_temporary_index_predicate, extract_version,
+	fold_condition_expressions, has_knn_k_operator, has_knn_ktree_operator, has_knn_operator,
+	has_top_level_or, idiom_to_field_name, index_covers_ordering, is_bounded_topk_downstream,
+	is_count_all_eligible, is_indexed_count_eligible, order_is_scan_compatible,
+	resolve_condition_params, resolve_param_value, resolve_projection_field_idioms,
+	strip_fts_condition, strip_index_conditions, strip_knn_from_condition,
+	strip_union_index_conditions,
 };
 use crate::catalog::Index;
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
 use crate::err::Error;
 use crate::exec::index::access_path::{AccessPath, BTreeAccess, IndexRef, select_access_path};
 use crate::exec::index::analysis::IndexAnalyzer;
+use crate::exec::operators::scan::TemporaryIndexProbe;
 use crate::exec::operators::scan::determine_scan_direction;
+use crate::exec::operators::scan::index_count::PinnedFieldPermissions;
 use crate::exec::operators::scan::resolved::{ResolvedTableContext, resolve_table_context};
 use crate::exec::operators::{
-	AnalyzePlan, DynamicScan, ExplainPlan, Fetch, Filter, KnnTopK, Limit, RecordIdScan,
-	SortDirection, SourceExpr, TableScan, Timeout, Union, UnionIndexScan, UnwrapExactlyOne,
-	VersionScope,
+	AnalyzePlan, DynamicScan, ExplainPlan, Fetch, Filter, KnnTopK, Limit, OnExpiry, RecordIdScan,
+	ScanLimitScope, SortDirection, SourceExpr, TableScan, Timeout, Union, UnionIndexScan,
+	UnwrapExactlyOne, VersionScope,
 };
 use crate::exec::pre_decode_filter::pre_decode_filter_status_at_plan_time;
 use crate::exec::{ExecOperator, OperatorMetrics};
 use crate::expr::field::{Field, Fields};
 use crate::expr::order::Ordering as OrderClause;
 use crate::expr::with::With;
-use crate::expr::{Cond, Expr, Idiom, Literal};
+use crate::expr::{BinaryOperator, Cond, Expr, Idiom, Literal};
 use crate::idx::planner::ScanDirection;
 use crate::kvs::Transaction;
 use crate::kvs::index::filter_online_indexes;
@@ -580,7 +583,8 @@ impl<'ctx> Planner<'ctx> {
 	}
 
 	/// Wrap a planned SELECT operator with the standard tail layers:
-	/// `Timeout` (if a non-`NONE` TIMEOUT clause), `VersionScope` (if a
+	/// `Timeout` (if a non-`NONE` TIMEOUT clause, acting on expiry as
+	/// `on_expiry` says), `VersionScope` (if a
 	/// VERSION expression), and `UnwrapExactlyOne` (if `FROM ONLY`).
 	///
 	/// `only_none_on_empty` controls `UnwrapExactlyOne::new`'s
@@ -594,6 +598,7 @@ impl<'ctx> Planner<'ctx> {
 		&self,
 		op: Arc<dyn ExecOperator>,
 		timeout: Expr,
+		on_expiry: OnExpiry,
 		version: Option<Arc<dyn crate::exec::PhysicalExpr>>,
 		only: bool,
 		only_none_on_empty: bool,
@@ -602,7 +607,7 @@ impl<'ctx> Planner<'ctx> {
 			Expr::Literal(Literal::None) => op,
 			te => {
 				let tp = self.physical_expr(te).await?;
-				Arc::new(Timeout::new(op, Some(tp))) as Arc<dyn ExecOperator>
+				Arc::new(Timeout::new(op, Some(tp), on_expiry)) as Arc<dyn ExecOperator>
 			}
 		};
 		let versioned: Arc<dyn ExecOperator> = match version {
@@ -625,11 +630,28 @@ impl<'ctx> Planner<'ctx> {
 	/// Performs plan-time index resolution when a transaction is available,
 	/// enabling sort elimination and concrete scan operators.
 	pub(crate) async fn plan_select_statement(
+		&self,
+		select: crate::expr::statements::SelectStatement,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		self.plan_select(select, false).await
+	}
+
+	/// Plan a SELECT which is either the statement itself (`statement`), or
+	/// a subquery within it. Only the former records in the stats of the
+	/// statement that it returned partial results.
+	pub(crate) async fn plan_select(
 		&self,
 		mut select: crate::expr::statements::SelectStatement,
+		statement: bool,
 	) -> Result<Arc<dyn ExecOperator>, Error> {
 		let explain = select.explain.take();
-		let plan = Box::pin(self.plan_select_core(select)).await?;
+		let scan_limit = select.scan_limit.take();
+		let plan = Box::pin(self.plan_select_core(select, statement)).await?;
+		// Limit how many rows the scans of this statement may read
+		let plan: Arc<dyn ExecOperator> = match scan_limit {
+			Some(limit) => Arc::new(ScanLimitScope::new(plan, limit)),
+			None => plan,
+		};
 		match explain {
 			Some(crate::expr::explain::Explain(full)) => {
 				if full {
@@ -657,7 +679,14 @@ impl<'ctx> Planner<'ctx> {
 	async fn plan_select_core(
 		&self,
 		select: crate::expr::statements::SelectStatement,
+		statement: bool,
 	) -> Result<Arc<dyn ExecOperator>, Error> {
+		// Partial results are lost within blocking operators like Sort or Aggregate
+		select.check_partial()?;
+		// Compute the views among the sources from their queries
+		let select = self.expand_views(select).await?;
+		// Filter the records by their valid time
+		let select = self.expand_valid_time(select).await?;
 		let crate::expr::statements::SelectStatement {
 			fields,
 			omit,
@@ -673,12 +702,25 @@ impl<'ctx> Planner<'ctx> {
 			fetch,
 			version,
 			timeout,
+			partial,
 			explain: _,
 			tempfiles,
+			valid_time: _,
+			scan_limit: _,
 		} = select;
 
+		// Only the statement's own SELECT marks its stats as truncated
+		let on_expiry = match (partial, statement) {
+			(false, _) => OnExpiry::Fail,
+			(true, false) => OnExpiry::Partial,
+			(true, true) => OnExpiry::PartialStatement,
+		};
+
 		let version = extract_version(version, self).await?;
 
+		// Hide soft deleted records unless they were requested
+		let cond = self.soft_delete_condition(&what, cond, with.as_ref()).await?;
+
 		// COUNT fast-path
 		if is_count_all_eligible(&fields, &group, &cond, &split, &order, &fetch, &omit, &what) {
 			use crate::exec::operators::CountScan;
@@ -688,33 +730,54 @@ impl<'ctx> Planner<'ctx> {
 			let field_names = extract_count_field_names(&fields);
 			let count_scan: Arc<dyn ExecOperator> =
 				Arc::new(CountScan::new(table_expr, version.clone(), field_names));
-			return self.wrap_select_tail(count_scan, timeout, version, only, true).await;
+			return self
+				.wrap_select_tail(count_scan, timeout, on_expiry, version, only, true)
+				.await;
 		}
 
 		// Indexed COUNT fast-path (COUNT with WHERE + matching COUNT index)
 		// Skip when WITH NOINDEX is specified — the user explicitly forbids
 		// index-assisted execution.
 		//
-		// SECURITY: also skip when the WHERE clause references a field whose
-		// SELECT permission is not `Full`. The indexed-count fast paths
-		// (`IndexCountScan` with either a dedicated `Index::Count` or a
-		// covering B-tree access) count index entries directly, bypassing
-		// the document-level field reduction that hides restricted values.
-		// Without this guard, a record user could learn the cardinality of
-		// field values they are not permitted to SELECT.
+		// SECURITY: when the WHERE clause references a field whose SELECT
+		// permission is not `Full`, only a B-tree access which pins that
+		// field to a single value, or bounds it from below, can be used, and
+		// only when the field's permission reads nothing but pinned columns.
+		// The indexed-count fast paths (`IndexCountScan` with either a
+		// dedicated `Index::Count` or a covering B-tree access) count index
+		// entries directly, bypassing the document-level field reduction
+		// that hides restricted values, so the permission is instead checked
+		// once against the pinned values. Without this guard, a record user
+		// could learn the cardinality of field values they are not
+		// permitted to SELECT.
 		if is_indexed_count_eligible(&fields, &group, &cond, &split, &order, &fetch, &omit, &what)
-			&& !matches!(with, Some(crate::expr::with::With::NoIndex))
-			&& !self.cond_touches_restricted_select_field(&what, &cond).await
+			&& !matches!(with, Some(With::NoIndex | With::Deleted))
 		{
+			let restricted = self.cond_touches_restricted_select_field(&what, &cond).await;
 			// Try COUNT index first, then B-tree index for key-only counting.
-			let has_count_idx = self.has_matching_count_index(&what, &cond).await;
+			let has_count_idx = !restricted && self.has_matching_count_index(&what, &cond).await;
 			let btree_access = if !has_count_idx {
 				self.resolve_count_btree_access(&what, &cond, with.as_ref()).await
 			} else {
 				None
 			};
+			// Resolve the restricted field permissions to check against
+			// the values pinned by the B-tree access.
+			let pinned_permissions = match (&btree_access, restricted) {
+				(Some((index_ref, access)), true) => {
+					self.resolve_pinned_field_permissions(&what, &cond, index_ref, access).await
+				}
+				_ => None,
+			};
 
-			if has_count_idx || btree_access.is_some() {
+			// A WHERE clause reading restricted fields can only be counted
+			// when their permissions could be pinned.
+			let usable = match restricted {
+				true => pinned_permissions.is_some(),
+				false => has_count_idx || btree_access.is_some(),
+			};
+
+			if usable {
 				use crate::exec::operators::scan::index_count::IndexCountScan;
 				// `is_indexed_count_eligible` proves that `what` is non-empty
 				// and `cond` is `Some`. Either invariant breaking would be a
@@ -743,9 +806,12 @@ impl<'ctx> Planner<'ctx> {
 						version.clone(),
 						field_names,
 					)
-					.with_btree_access(btree_access),
+					.with_btree_access(btree_access)
+					.with_pinned_permissions(pinned_permissions),
 				);
-				return self.wrap_select_tail(index_count_scan, timeout, version, only, true).await;
+				return self
+					.wrap_select_tail(index_count_scan, timeout, on_expiry, version, only, true)
+					.await;
 			}
 		}
 
@@ -828,7 +894,7 @@ impl<'ctx> Planner<'ctx> {
 				scan
 			};
 			let projected = self.plan_projections(fields, omit, limited).await?;
-			return self.wrap_select_tail(projected, timeout, version, only, true).await;
+			return self.wrap_select_tail(projected, timeout, on_expiry, version, only, true).await;
 		}
 
 		// Capture literal Expr::Table nodes BEFORE resolve_source_exprs so
@@ -1168,7 +1234,7 @@ impl<'ctx> Planner<'ctx> {
 
 		let projected = pp.plan_pipeline(source, Some(fields), config).await?;
 		let fetched = pp.plan_fetch(fetch, projected).await?;
-		pp.wrap_select_tail(fetched, timeout, version, only, !is_value_source).await
+		pp.wrap_select_tail(fetched, timeout, on_expiry, version, only, !is_value_source).await
 	}
 
 	/// Plan FROM sources with plan-time index resolution.
@@ -1417,6 +1483,7 @@ impl<'ctx> Planner<'ctx> {
 							.plan_table_scan_source(
 								table,
 								direction,
+								cond,
 								order,
 								scan_predicate,
 								scan_limit,
@@ -1459,8 +1526,12 @@ impl<'ctx> Planner<'ctx> {
 		// Fallback: create the appropriate operator (index resolved at runtime)
 		let knn_ctx = self.ctx.get_knn_context().cloned();
 
+		// Without a transaction, the table of a record id can not be checked
+		// for soft deletes at plan time, so the lookup is left to DynamicScan
+		let soft_delete_check = self.txn.is_none() && !matches!(with, Some(With::Deleted));
+
 		match expr {
-			Expr::Literal(crate::expr::literal::Literal::RecordId(rid)) => {
+			Expr::Literal(crate::expr::literal::Literal::RecordId(rid)) if !soft_delete_check => {
 				let record_id_expr = self
 					.physical_expr(Expr::Literal(crate::expr::literal::Literal::RecordId(rid)))
 					.await?;
@@ -1504,6 +1575,7 @@ impl<'ctx> Planner<'ctx> {
 				})
 			}
 			Expr::Table(_)
+			| Expr::Literal(crate::expr::literal::Literal::RecordId(_))
 			| Expr::FunctionCall(_)
 			| Expr::Postfix {
 				..
@@ -1734,6 +1806,7 @@ impl<'ctx> Planner<'ctx> {
 		&self,
 		table: crate::val::TableName,
 		direction: crate::idx::planner::ScanDirection,
+		cond: Option<&Cond>,
 		order: Option<&crate::expr::order::Ordering>,
 		scan_predicate: Option<Arc<dyn crate::exec::PhysicalExpr>>,
 		scan_limit: Option<Arc<dyn crate::exec::PhysicalExpr>>,
@@ -1806,6 +1879,19 @@ impl<'ctx> Planner<'ctx> {
 				}
 			}
 		};
+		// An equality predicate on a top-level field can be served by a
+		// temporary index once it has been scanned often enough. The scan
+		// re-applies its own predicate to the records found by the index.
+		let temporary_index = match cond.filter(|_| scan_predicate.is_some()) {
+			Some(cond) => match extract_temporary_index_predicate(cond) {
+				Some((field, value)) => Some(TemporaryIndexProbe {
+					field,
+					value: self.physical_expr(value).await?,
+				}),
+				None => None,
+			},
+			None => None,
+		};
 		let mut scan = TableScan::new(
 			table,
 			direction,
@@ -1820,6 +1906,14 @@ impl<'ctx> Planner<'ctx> {
 		}
 		scan = scan.with_pre_decode_filter(pdf);
 		scan = scan.with_topk_pushdown(topk_status);
+		if let Some(probe) = temporary_index {
+			scan = scan.with_temporary_index(probe);
+		}
+		// A bound on the creation time of the records limits the scan to the
+		// matching ranges of record ids
+		if let Some(ranges) = cond.and_then(|c| extract_id_ranges(c, direction)) {
+			scan = scan.with_id_ranges(ranges);
+		}
 		Ok(PlannedSource {
 			operator: Arc::new(scan) as Arc<dyn ExecOperator>,
 			filter_action,
@@ -2169,7 +2263,8 @@ impl<'ctx> Planner<'ctx> {
 					dyn_start,
 				)
 				.with_knn_context(knn_ctx)
-				.with_pre_decode_filter(pdf),
+				.with_pre_decode_filter(pdf)
+				.with_soft_delete_check(self.txn.is_none() && !matches!(with, Some(With::Deleted))),
 			) as Arc<dyn ExecOperator>,
 			filter_action,
 			limit_pushed,
@@ -2248,6 +2343,136 @@ impl<'ctx> Planner<'ctx> {
 		}
 	}
 
+	/// Replace the views among the sources with the queries defining them.
+	///
+	/// Views defined with `DEFINE VIEW` hold no records of their own, so they
+	/// are expanded before planning, which lets the condition of a view over
+	/// a single table take part in index selection and predicate pushdown.
+	/// See [`crate::expr::statements::SelectStatement::expand_views`].
+	async fn expand_views(
+		&self,
+		select: crate::expr::statements::SelectStatement,
+	) -> Result<crate::expr::statements::SelectStatement, Error> {
+		let Some(txn) = self.txn.as_ref() else {
+			return Ok(select);
+		};
+		let Some((ns_id, db_id)) = self.ns_db_ids().await else {
+			return Ok(select);
+		};
+		match select.expand_views(txn, ns_id, db_id).await {
+			Ok(Some(expanded)) => Ok(expanded),
+			Ok(None) => Ok(select),
+			Err(e) => Err(e.downcast::<Error>().unwrap_or_else(|e| Error::Internal(e.to_string()))),
+		}
+	}
+
+	/// Replace the `FOR VALID TIME AS OF` clause with a condition on the
+	/// valid time fields of the selected tables. Without catalog access at
+	/// plan time, the statement falls back to the compute executor, which
+	/// replaces the clause once the tables are known.
+	async fn expand_valid_time(
+		&self,
+		select: crate::expr::statements::SelectStatement,
+	) -> Result<crate::expr::statements::SelectStatement, Error> {
+		if select.valid_time.is_none() {
+			return Ok(select);
+		}
+		let (Some(txn), Some((ns_id, db_id))) = (self.txn.as_ref(), self.ns_db_ids().await) else {
+			return Err(Error::PlannerUnsupported(
+				"SELECT FOR VALID TIME without catalog access".to_string(),
+			));
+		};
+		match select.expand_valid_time(txn, ns_id, db_id).await {
+			Ok(Some(expanded)) => Ok(expanded),
+			Ok(None) => Ok(select),
+			Err(e) => Err(e.downcast::<Error>().unwrap_or_else(|e| Error::Internal(e.to_string()))),
+		}
+	}
+
+	/// Add the check hiding soft deleted records to the WHERE condition.
+	///
+	/// Tables defined with `SOFT DELETE` only return records without a
+	/// deletion marker, unless the SELECT is run `WITH DELETED`. The marker
+	/// check is added to the condition before planning, so index selection
+	/// and predicate pushdown apply to it like any other filter. SELECTs
+	/// which mix soft deleted tables with other sources, or read from
+	/// sources only known at runtime, fall back to the compute executor,
+	/// which checks each record instead.
+	async fn soft_delete_condition(
+		&self,
+		what: &[Expr],
+		cond: Option<Cond>,
+		with: Option<&With>,
+	) -> Result<Option<Cond>, Error> {
+		if matches!(with, Some(With::Deleted)) {
+			return Ok(cond);
+		}
+		let Some(txn) = self.txn.as_ref() else {
+			return Ok(cond);
+		};
+		let Some((ns_id, db_id)) = self.ns_db_ids().await else {
+			return Ok(cond);
+		};
+		let mut soft_deletes = Vec::with_capacity(what.len());
+		let mut others = false;
+		let mut dynamic = false;
+		for expr in what {
+			let table = match expr {
+				Expr::Table(tb) => tb,
+				Expr::Literal(Literal::RecordId(rid)) => &rid.table,
+				Expr::Literal(Literal::Array(_)) => {
+					dynamic = true;
+					continue;
+				}
+				Expr::Literal(_) | Expr::Select(_) => {
+					others = true;
+					continue;
+				}
+				_ => {
+					dynamic = true;
+					continue;
+				}
+			};
+			let tb = txn
+				.get_tb(ns_id, db_id, table, None)
+				.await
+				.map_err(|e| Error::Internal(e.to_string()))?;
+			match tb.and_then(|tb| tb.soft_delete.clone()) {
+				Some(sd) => soft_deletes.push(sd),
+				None => others = true,
+			}
+		}
+		// Sources only known at runtime may read from soft deleted tables
+		if dynamic {
+			let tables =
+				txn.all_tb(ns_id, db_id, None).await.map_err(|e| Error::Internal(e.to_string()))?;
+			if tables.iter().any(|tb| tb.soft_delete.is_some()) {
+				return Err(Error::PlannerUnsupported(
+					"SELECT from runtime sources in a database with soft deleted tables"
+						.to_string(),
+				));
+			}
+		}
+		let Some(sd) = soft_deletes.first() else {
+			return Ok(cond);
+		};
+		// A single marker check must apply to every source
+		if others || soft_deletes.iter().any(|x| x.field != sd.field) {
+			return Err(Error::PlannerUnsupported(
+				"SELECT mixing soft deleted tables with other sources".to_string(),
+			));
+		}
+		let check = sd.check();
+		Ok(Some(match cond {
+			Some(Cond(c)) => Cond(Expr::Binary {
+				left: Box::new(c),
+				op: BinaryOperator::And,
+				right: Box::new(check),
+			}),
+			None => Cond(check),
+		}))
+	}
+
 	/// Check at plan time whether a matching COUNT index exists for the query.
 	///
 	/// Returns `true` when:
@@ -2393,6 +2618,100 @@ impl<'ctx> Planner<'ctx> {
 		}
 	}
 
+	/// Resolve the field-level SELECT permissions which the indexed COUNT
+	/// fast path can check against the values pinned by a B-tree access,
+	/// instead of against each record.
+	///
+	/// Every restricted field read by the WHERE clause must be an index
+	/// column which the access either pins to a single value other than NONE
+	/// or NULL, or bounds from below by such a value (a range, or the range
+	/// on the column after the prefix of a compound access). In both cases a
+	/// denied field reads as NONE, which falls outside the access, so no
+	/// record matches. The permission may only read pinned columns of the
+	/// document, and the permission of a bounded field may not read `$value`,
+	/// as its value differs between records. Returns `None` when any
+	/// restricted field can not be checked this way, in which case the fast
+	/// path must not be used.
+	async fn resolve_pinned_field_permissions(
+		&self,
+		what: &[Expr],
+		cond: &Option<Cond>,
+		index_ref: &IndexRef,
+		access: &BTreeAccess,
+	) -> Option<PinnedFieldPermissions> {
+		use crate::expr::Part;
+
+		let cond = cond.as_ref()?;
+		let Some(Expr::Table(table_name)) = what.first() else {
+			return None;
+		};
+		let txn = self.txn.as_ref()?;
+		let (ns_id, db_id) = self.ns_db_ids().await?;
+		// The index columns which the access pins to a single value, and the
+		// column which it bounds, with its lower bound if it has one
+		let cols = &index_ref.definition().cols;
+		let (pinned, bounded): (Vec<(&Idiom, &crate::val::Value)>, _) = match access {
+			BTreeAccess::Equality(value) if cols.len() == 1 => (vec![(&cols[0], value)], None),
+			BTreeAccess::Range {
+				from,
+				..
+			} => (Vec::new(), Some((&cols[0], from.as_ref().map(|b| &b.value)))),
+			BTreeAccess::Compound {
+				prefix,
+				range,
+			} => {
+				let bounded = cols.get(prefix.len()).zip(range.as_ref()).map(|(col, (op, v))| {
+					let lower =
+						matches!(op, BinaryOperator::MoreThan | BinaryOperator::MoreThanEqual);
+					(col, lower.then_some(v))
+				});
+				(cols.iter().zip(prefix.iter()).collect(), bounded)
+			}
+			_ => return None,
+		};
+		// The document made of the pinned top-level columns
+		let mut document = crate::val::Object::default();
+		for (col, value) in pinned.iter() {
+			if let [Part::Field(name)] = col.0.as_slice() {
+				document.insert(name.as_str().to_owned(), (*value).clone());
+			}
+		}
+		let fields = txn.all_tb_fields(ns_id, db_id, table_name, None).await.ok()?;
+		let mut restricted = Vec::new();
+		for field in fields.iter() {
+			if matches!(field.select_permission, crate::catalog::Permission::Full) {
+				continue;
+			}
+			if !RestrictedPrefixes::Some(vec![field.name.clone()]).cond_touches(cond) {
+				continue;
+			}
+			// The field must be a pinned or bounded top-level column
+			if !matches!(field.name.0.as_slice(), [Part::Field(_)]) {
+				return None;
+			}
+			let value = match pinned.iter().find(|(col, _)| **col == field.name) {
+				Some((_, value)) if !value.is_nullish() => Some(*value),
+				Some(_) => return None,
+				// The lower bound of a bounded field must exclude NONE
+				None => match bounded {
+					Some((col, Some(lower))) if *col == field.name && !lower.is_nullish() => None,
+					_ => return None,
+				},
+			};
+			// The permission must only read pinned columns
+			if let crate::catalog::Permission::Specific(expr) = &field.select_permission
+				&& !reads_only_fields(expr, &document, value.is_some())
+			{
+				return None;
+			}
+			restricted.push((field.name.clone(), value.cloned(), field.select_permission.clone()));
+		}
+		Some(PinnedFieldPermissions {
+			document: crate::val::Value::Object(document),
+			fields: restricted,
+		})
+	}
+
 	/// Resolve a B-tree index access path covering the WHERE condition for
 	/// key-only counting.  Returns `Some((IndexRef, BTreeAccess))` when the
 	/// index analysis finds a B-tree index that fully covers the predicate
@@ -2481,7 +2800,8 @@ impl<'ctx> Planner<'ctx> {
 			return Ok(Some((AccessPath::EmptyScan, direction)));
 		}
 
-		if matches!(with, Some(With::NoIndex)) {
+		// Soft deleted records are only found by scanning the table
+		if matches!(with, Some(With::NoIndex | With::Deleted)) {
 			return Ok(Some((AccessPath::TableScan, direction)));
 		}
 
@@ -2713,6 +3033,75 @@ impl crate::expr::visit::Visitor for RestrictedIdiomChecker<'_> {
 	}
 }
 
+/// Returns `true` when `expr` reads no field of the current document other
+/// than the keys of `document`, and only reads `$value` when `value` is set.
+fn reads_only_fields(expr: &Expr, document: &crate::val::Object, value: bool) -> bool {
+	use crate::expr::visit::Visitor;
+	let mut collector = DocumentFieldCollector::default();
+	let _ = collector.visit_expr(expr);
+	!collector.opaque
+		&& (value || !collector.value)
+		&& collector.fields.iter().all(|name| document.contains_key(name))
+}
+
+/// Visitor collecting the top-level fields of the current document which an
+/// expression reads. `opaque` is set when the expression can read the
+/// document in a way which can not be analysed statically: through a bare
+/// row parameter, a `$parent` reference, or a subquery. `value` is set when
+/// the expression reads the `$value` parameter.
+#[derive(Default)]
+struct DocumentFieldCollector {
+	fields: std::collections::HashSet<String>,
+	opaque: bool,
+	value: bool,
+}
+
+impl crate::expr::visit::Visitor for DocumentFieldCollector {
+	type Error = std::convert::Infallible;
+
+	fn visit_idiom(&mut self, idiom: &Idiom) -> Result<(), Self::Error> {
+		use super::row_scope::{IdiomRoot, classify_idiom_root};
+		use crate::expr::Part;
+
+		match classify_idiom_root(idiom) {
+			IdiomRoot::ThisRow => {
+				let mut parts = idiom.0.iter().filter(|p| !matches!(p, Part::Start(_)));
+				match parts.next() {
+					Some(Part::Field(name)) => {
+						self.fields.insert(name.as_str().to_owned());
+					}
+					_ => self.opaque = true,
+				}
+				for part in parts {
+					self.visit_part(part)?;
+				}
+			}
+			IdiomRoot::OuterRow => self.opaque = true,
+			IdiomRoot::Opaque => {
+				for part in idiom.0.iter() {
+					self.visit_part(part)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn visit_expr(&mut self, expr: &Expr) -> Result<(), Self::Error> {
+		use super::row_scope::RowScopeKind;
+		use crate::expr::visit::Visit;
+
+		match expr {
+			Expr::Param(p) if RowScopeKind::from_param_name(p.as_str()).is_some() => {
+				self.opaque = true;
+			}
+			Expr::Param(p) if p.as_str() == "value" => self.value = true,
+			Expr::Select(_) | Expr::With(_) => self.opaque = true,
+			_ => expr.visit(self)?,
+		}
+		Ok(())
+	}
+}
+
 /// Adjust the scan direction and access path when the chosen index covers
 /// the ORDER BY clause.
 ///
@@ -3,7 +3,8 @@
 //! Predicate inspection (top-level OR, KNN/FTS detection), brute-force KNN
 //! parameter extraction, condition stripping after a scan operator has
 //! consumed parts of the predicate, MATCHES context collection for index
-//! functions, and the record-id point-lookup detector.
+//! functions, the record-id point-lookup detector, and the record-id range
+//! detector for bounds on the creation time of records.
 //!
 //! The visitors here all stop at SELECT subquery boundaries: subqueries
 //! have their own planning pass and their predicates do not contribute to
@@ -11,13 +12,15 @@
 
 use std::collections::HashSet;
 
-use super::literals::try_literal_to_value;
+use super::literals::{try_expr_to_value, try_literal_to_value};
 use crate::catalog::Distance;
 use crate::exec::index::analysis::idiom_matches_containment;
 use crate::expr::operator::NearestNeighbor;
 use crate::expr::visit::{MutVisitor, Visit, VisitMut, Visitor};
 use crate::expr::{BinaryOperator, Cond, Expr, Idiom, Literal};
-use crate::val::Number;
+use crate::idx::planner::ScanDirection;
+use crate::idx::planner::id_range::{self, TimeBounds};
+use crate::val::{Number, RecordIdKeyRange, Value};
 
 // ============================================================================
 // Predicate / Validation Helpers
@@ -720,6 +723,78 @@ fn check_id_recordid_pair(
 	}
 }
 
+/// Extract an equality predicate on a top-level field from the top-level AND
+/// chain of a condition, for a temporary index to look the matching records
+/// up with.
+///
+/// Returns the field name and the expression compared against, which must be
+/// a literal which a temporary index can look up, or a parameter.
+pub(crate) fn extract_temporary_index_predicate(cond: &Cond) -> Option<(String, Expr)> {
+	find_field_equality_in_and_chain(&cond.0)
+}
+
+/// Extract the ranges of record ids which contain every record created within
+/// the bounds which the top-level AND chain of a condition puts on
+/// `record::created_at(id)`, so that only those ranges have to be scanned.
+///
+/// Must be called on a condition whose parameters have been resolved and
+/// whose constant expressions have been folded to literals.
+pub(crate) fn extract_id_ranges(
+	cond: &Cond,
+	direction: ScanDirection,
+) -> Option<Vec<RecordIdKeyRange>> {
+	let mut bounds = TimeBounds::default();
+	for (op, value) in id_range::comparisons(cond) {
+		if let Some(Value::Datetime(value)) = try_expr_to_value(value) {
+			bounds.narrow(&op, value);
+		}
+	}
+	bounds.ranges(direction)
+}
+
+/// Walk the top-level AND chain looking for `<field> = <literal or param>`.
+fn find_field_equality_in_and_chain(expr: &Expr) -> Option<(String, Expr)> {
+	match expr {
+		Expr::Binary {
+			left,
+			op: BinaryOperator::And,
+			right,
+		} => find_field_equality_in_and_chain(left)
+			.or_else(|| find_field_equality_in_and_chain(right)),
+		Expr::Binary {
+			left,
+			op: BinaryOperator::Equal | BinaryOperator::ExactEqual,
+			right,
+		} => check_field_value_pair(left, right).or_else(|| check_field_value_pair(right, left)),
+		_ => None,
+	}
+}
+
+/// Check if `idiom_side` is a plain top-level field other than `id`, and
+/// `value_side` is a literal which a temporary index can look up, or a param.
+fn check_field_value_pair(idiom_side: &Expr, value_side: &Expr) -> Option<(String, Expr)> {
+	let Expr::Idiom(idiom) = idiom_side else {
+		return None;
+	};
+	let [crate::expr::Part::Field(field)] = idiom.0.as_slice() else {
+		return None;
+	};
+	if idiom.is_id() {
+		return None;
+	}
+	match value_side {
+		Expr::Param(_)
+		| Expr::Literal(
+			Literal::String(_)
+			| Literal::Bool(_)
+			| Literal::Uuid(_)
+			| Literal::Datetime(_)
+			| Literal::Duration(_),
+		) => Some((field.as_str().to_owned(), value_side.clone())),
+		_ => None,
+	}
+}
+
 /// Check if a source expression represents a "value source" (array, primitive).
 pub(crate) fn is_value_source_expr(expr: &Expr) -> bool {
 	match expr {
@@ -21,10 +21,11 @@ mod optimization;
 mod params;
 
 pub(crate) use conditions::{
-	all_value_sources, extract_bruteforce_knn, extract_matches_context,
-	extract_record_id_point_lookup, extract_table_from_context, has_knn_k_operator,
-	has_knn_ktree_operator, has_knn_operator, has_top_level_or, strip_fts_condition,
-	strip_index_conditions, strip_knn_from_condition, strip_union_index_conditions,
+	all_value_sources, extract_bruteforce_knn, extract_id_ranges, extract_matches_context,
+	extract_record_id_point_lookup, extract_table_from_context, extract_temporary_index_predicate,
+	has_knn_k_operator, has_knn_ktree_operator, has_knn_operator, has_top_level_or,
+	strip_fts_condition, strip_index_conditions, strip_knn_from_condition,
+	strip_union_index_conditions,
 };
 pub(crate) use fields::{
 	check_forbidden_group_by_params, derive_field_name, idiom_to_field_name, idiom_to_field_path,
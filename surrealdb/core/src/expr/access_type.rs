@@ -136,6 +136,7 @@ pub(crate) struct RecordAccess {
 	pub signin: Option<Expr>,
 	pub jwt: JwtAccess,
 	pub bearer: Option<BearerAccess>,
+	pub mfa: Option<MfaMethod>,
 }
 
 impl Default for RecordAccess {
@@ -147,10 +148,16 @@ impl Default for RecordAccess {
 				..Default::default()
 			},
 			bearer: None,
+			mfa: None,
 		}
 	}
 }
 
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum MfaMethod {
+	Totp,
+}
+
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
 pub(crate) struct BearerAccess {
 	pub kind: BearerAccessType,
@@ -147,6 +147,7 @@ impl Visitor for FieldDependencyExtractor {
 			// === Opaque (flip + walk) ===
 			// Subqueries can access arbitrary fields/tables at runtime.
 			Expr::Select(_)
+			| Expr::With(_)
 			| Expr::Create(_)
 			| Expr::Update(_)
 			| Expr::Upsert(_)
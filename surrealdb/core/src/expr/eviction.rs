@@ -0,0 +1,30 @@
+use std::time;
+
+use revision::revisioned;
+
+use crate::expr::statements::info::InfoStructure;
+use crate::val::{Duration, Value};
+
+/// The eviction behaviour of a table, declared with `EVICT`
+///
+/// Records of the table are removed by the background eviction task when
+/// they have not been written for the time to live, or, when the memory
+/// limit of the datastore is exceeded, in order of least recent access.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct Eviction {
+	/// Whether records are evicted when the memory limit is exceeded
+	pub lru: bool,
+	/// How long records are kept after they were last written
+	pub ttl: Option<time::Duration>,
+}
+
+impl InfoStructure for Eviction {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"lru" => self.lru.into(),
+			"ttl", if let Some(v) = self.ttl => Duration(v).into(),
+		})
+	}
+}
@@ -15,13 +15,14 @@ use crate::expr::statements::{
 	AlterStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
 	IfelseStatement, InfoStatement, InsertStatement, OutputStatement, RebuildStatement,
 	RelateStatement, RemoveStatement, SelectStatement, SetStatement, UpdateStatement,
-	UpsertStatement,
+	UpsertStatement, WithStatement,
 };
 use crate::expr::{
 	BinaryOperator, Block, Constant, ControlFlow, FlowResult, FunctionCall, Idiom, Literal, Mock,
 	ObjectEntry, Param, PostfixOperator, PrefixOperator, RecordIdKeyLit, RecordIdLit,
 };
 use crate::fnc;
+use crate::kvs::SessionTables;
 use crate::types::PublicValue;
 use crate::val::{Array, Range, TableName, Value};
 
@@ -68,6 +69,7 @@ pub(crate) enum Expr {
 
 	IfElse(Box<IfelseStatement>),
 	Select(Box<SelectStatement>),
+	With(Box<WithStatement>),
 	Create(Box<CreateStatement>),
 	Update(Box<UpdateStatement>),
 	Upsert(Box<UpsertStatement>),
@@ -85,6 +87,9 @@ pub(crate) enum Expr {
 	Explain {
 		format: ExplainFormat,
 		analyze: bool,
+		/// Whether to trace the permissions which were evaluated while
+		/// executing the statement, rather than to explain its plan
+		permissions: bool,
 		statement: Box<Expr>,
 	},
 	/// An GQL `MATCH` query, lowered to its declarative binding-table plan.
@@ -132,6 +137,7 @@ impl Expr {
 			Expr::Throw(expr) => expr.read_only(),
 			Expr::IfElse(s) => s.read_only(),
 			Expr::Select(s) => s.read_only(),
+			Expr::With(s) => s.read_only(),
 			Expr::Let(s) => s.read_only(),
 			Expr::Foreach(s) => s.read_only(),
 			Expr::Explain {
@@ -201,6 +207,7 @@ impl Expr {
 			Expr::Block(block) => block.has_direct_write(),
 			Expr::IfElse(s) => s.has_direct_write(),
 			Expr::Foreach(s) => s.has_direct_write(),
+			Expr::With(s) => s.has_direct_write(),
 			Expr::Explain {
 				statement,
 				..
@@ -376,6 +383,7 @@ impl Expr {
 			| Expr::Throw(_)
 			| Expr::IfElse(_)
 			| Expr::Select(_)
+			| Expr::With(_)
 			| Expr::Create(_)
 			| Expr::Update(_)
 			| Expr::Delete(_)
@@ -505,6 +513,10 @@ impl Expr {
 			Expr::Select(select_statement) => {
 				select_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
 			}
+			Expr::With(with_statement) => {
+				let block = with_statement.as_ref().clone().into_block();
+				block.compute(stk, ctx, &opt, doc).await
+			}
 			Expr::Create(create_statement) => {
 				create_statement.compute(stk, ctx, &opt, doc).await.map_err(ControlFlow::Err)
 			}
@@ -623,7 +635,14 @@ impl Expr {
 				{
 					return x.invoke(stk, ctx, opt, doc, args).await.map_err(ControlFlow::Err);
 				};
-				fnc::idiom(stk, ctx, opt, doc, res, name, args).await.map_err(ControlFlow::Err)
+				let mut res = fnc::idiom(stk, ctx, opt, doc, res, name, args)
+					.await
+					.map_err(ControlFlow::Err)?;
+				// A record built from its table name may be in a session table
+				if name.as_str() == "to_record" {
+					SessionTables::resolve_value(ctx, &mut res);
+				}
+				Ok(res)
 			}
 			PostfixOperator::Call(exprs) => {
 				let mut args = Vec::new();
@@ -850,6 +869,7 @@ impl Expr {
 			| Expr::Return(_)
 			| Expr::IfElse(_)
 			| Expr::Select(_)
+			| Expr::With(_)
 			| Expr::Create(_)
 			| Expr::Update(_)
 			| Expr::Delete(_)
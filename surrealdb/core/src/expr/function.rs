@@ -12,6 +12,7 @@ use crate::err::Error;
 use crate::expr::{Expr, Idiom, Kind, Model, ModuleExecutable, Script, Value};
 use crate::fnc;
 use crate::iam::{Action, AuthLimit};
+use crate::kvs::SessionTables;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) enum Function {
@@ -69,8 +70,11 @@ impl Function {
 				..
 			} => false,
 			// `eval::*` can evaluate arbitrary nested queries (including writes),
-			// so they must open a write transaction like `api::invoke`.
-			Self::Normal(f) => f != "api::invoke" && f != "eval::surql" && f != "eval::gql",
+			// so they must open a write transaction like `api::invoke`, as must
+			// `query::run` for the query templates it runs.
+			Self::Normal(f) => {
+				f != "api::invoke" && f != "eval::surql" && f != "eval::gql" && f != "query::run"
+			}
 			Self::Model(_) => true,
 		}
 	}
@@ -89,7 +93,12 @@ impl Function {
 				// Check this function is allowed
 				ctx.check_allowed_function(s)?;
 				// Run the normal function
-				Ok(fnc::run(stk, ctx, opt, doc, s, args).await?)
+				let mut res = fnc::run(stk, ctx, opt, doc, s, args).await?;
+				// A table built from its name may be a session table
+				if SessionTables::is_table_constructor(s) {
+					SessionTables::resolve_value(ctx, &mut res);
+				}
+				Ok(res)
 			}
 			#[cfg_attr(not(feature = "scripting"), expect(unused_variables))]
 			Function::Script(s) => {
@@ -128,9 +137,17 @@ impl Function {
 					&args,
 					&val.args.iter().map(|(_, k)| k.clone()).collect::<Vec<Kind>>(),
 				)?;
+				// Restrict network access to the targets granted to the function
+				let ctx = match &val.capabilities {
+					Some(caps) => {
+						let net = caps.net_targets(&ctx.get_capabilities())?;
+						Context::new_child_with_net_targets(ctx, net)?.freeze()
+					}
+					None => ctx.clone(),
+				};
 				// Compute the function arguments
 				// Duplicate context
-				let mut ctx = Context::new_isolated(ctx);
+				let mut ctx = Context::new_isolated(&ctx);
 				// Process the function arguments
 				for (val, (param_name, kind)) in args.into_iter().zip(&val.args) {
 					ctx.add_value(
@@ -273,24 +290,32 @@ async fn check_perms(
 	name: &str,
 	permissions: &Permission,
 ) -> FlowResult<()> {
+	if is_permitted(stk, ctx, opt, doc, permissions).await? {
+		Ok(())
+	} else {
+		Err(ControlFlow::from(anyhow::Error::new(Error::FunctionPermissions {
+			name: name.to_string(),
+		})))
+	}
+}
+
+/// Evaluates the PERMISSIONS clause of a stored definition, such as a function
+/// or a query template, returning whether it is allowed.
+pub(crate) async fn is_permitted(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	doc: Option<&CursorDoc>,
+	permissions: &Permission,
+) -> FlowResult<bool> {
 	match permissions {
-		Permission::Full => Ok(()),
-		Permission::None => {
-			Err(ControlFlow::from(anyhow::Error::new(Error::FunctionPermissions {
-				name: name.to_string(),
-			})))
-		}
+		Permission::Full => Ok(true),
+		Permission::None => Ok(false),
 		Permission::Specific(e) => {
 			// Disable permission recursion and block side effects
 			let opt = &opt.new_for_permission_predicate();
 			// Process the PERMISSION clause
-			if !stk.run(|stk| e.compute(stk, ctx, opt, doc)).await?.is_truthy() {
-				Err(ControlFlow::from(anyhow::Error::new(Error::FunctionPermissions {
-					name: name.to_string(),
-				})))
-			} else {
-				Ok(())
-			}
+			Ok(stk.run(|stk| e.compute(stk, ctx, opt, doc)).await?.is_truthy())
 		}
 	}
 }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use anyhow::{Result, bail};
 use reblessive::tree::Stk;
 
@@ -63,6 +65,138 @@ pub(crate) fn clean_iteration(v: Value) -> Value {
 	}
 }
 
+/// The values reached by a `+tree` recursion, each placed beneath the value
+/// it was first reached from
+///
+/// A value is only added to the tree once, so cycles in the recursed graph
+/// are never followed.
+pub(crate) struct RecursionTree {
+	/// The value, parent and depth of every node, in the order reached
+	nodes: Vec<(Value, usize, u32)>,
+	/// The values which are already in the tree
+	seen: HashSet<Value>,
+}
+
+impl RecursionTree {
+	/// Creates a tree rooted at the starting point of the recursion
+	pub(crate) fn new(root: Value) -> Self {
+		let mut seen = HashSet::new();
+		seen.insert(root.clone());
+		Self {
+			nodes: vec![(root, 0, 0)],
+			seen,
+		}
+	}
+
+	/// Returns the value of a node
+	pub(crate) fn value(&self, node: usize) -> &Value {
+		&self.nodes[node].0
+	}
+
+	/// Adds a value reached from a node, returning the new node, or `None`
+	/// if the value is already in the tree
+	pub(crate) fn insert(&mut self, value: Value, parent: usize) -> Option<usize> {
+		if !self.seen.insert(value.clone()) {
+			return None;
+		}
+		let depth = self.nodes[parent].2 + 1;
+		self.nodes.push((value, parent, depth));
+		Some(self.nodes.len() - 1)
+	}
+
+	/// Assembles the nodes into nested `{ id, children }` objects, dropping
+	/// the branches which end before the minimum depth
+	///
+	/// An inclusive tree returns the object of the starting point, otherwise
+	/// the trees beneath the starting point are returned as an array.
+	pub(crate) fn assemble(self, min: u32, inclusive: bool) -> Value {
+		let mut nodes = self.nodes;
+		// Nodes are always reached after their parent, so walking them in
+		// reverse finds the deepest descendant of every node in one pass
+		let mut deepest: Vec<u32> = nodes.iter().map(|(_, _, depth)| *depth).collect();
+		for (node, (_, parent, _)) in nodes.iter().enumerate().skip(1).rev() {
+			deepest[*parent] = deepest[*parent].max(deepest[node]);
+		}
+		// Build the objects bottom-up, so no recursion is needed
+		let mut children: Vec<Vec<Value>> = vec![Vec::new(); nodes.len()];
+		let root = std::mem::take(&mut nodes[0].0);
+		for (node, (value, parent, _)) in nodes.into_iter().enumerate().skip(1).rev() {
+			if deepest[node] < min {
+				continue;
+			}
+			let branch = std::mem::take(&mut children[node]);
+			children[parent].push(Self::node(value, branch));
+		}
+		let branch = std::mem::take(&mut children[0]);
+		match inclusive {
+			true if deepest[0] < min => Value::None,
+			true => Self::node(root, branch),
+			false => Value::from(branch.into_iter().rev().collect::<Vec<_>>()),
+		}
+	}
+
+	fn node(id: Value, mut children: Vec<Value>) -> Value {
+		// Children were pushed in reverse order
+		children.reverse();
+		Value::from(map! {
+			"id" => id,
+			"children" => Value::from(children),
+		})
+	}
+}
+
+/// Walks the recursed path breadth-first from the current value, assembling
+/// the values reached into a [`RecursionTree`]
+pub(crate) async fn compute_tree(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	doc: Option<&CursorDoc>,
+	rec: Recursion<'_>,
+	inclusive: bool,
+) -> Result<Value> {
+	// Find the recursion limit
+	let limit = ctx.config.idiom_recursion_limit;
+	let mut tree = RecursionTree::new(rec.current.to_owned());
+	let mut frontier = vec![0];
+	let mut depth = 0;
+
+	while !frontier.is_empty() {
+		// Stop at the maximum depth, or fail when
+		// an unbounded recursion reaches the limit
+		if let Some(max) = rec.max {
+			if depth >= max {
+				break;
+			}
+		} else if depth >= limit {
+			bail!(Error::IdiomRecursionLimitExceeded {
+				limit,
+			});
+		}
+		depth += 1;
+
+		let mut next = vec![];
+		for parent in frontier {
+			let value = tree.value(parent).to_owned();
+			let res =
+				stk.run(|stk| value.get(stk, ctx, opt, doc, rec.path)).await.catch_return()?;
+			let steps = match clean_iteration(res) {
+				Value::Array(v) => v.0,
+				v if is_final(&v) => continue,
+				v => vec![v],
+			};
+			for step in steps {
+				if let Some(node) = tree.insert(step, parent) {
+					next.push(node);
+				}
+			}
+		}
+		frontier = next;
+	}
+
+	Ok(tree.assemble(rec.min, inclusive))
+}
+
 pub(crate) async fn compute_idiom_recursion(
 	stk: &mut Stk,
 	ctx: &FrozenContext,
@@ -81,6 +215,14 @@ pub(crate) async fn compute_idiom_recursion(
 		return Ok(get_final(rec.current));
 	}
 
+	// Trees are walked in one pass, tracking the parent of every value
+	if let Some(RecurseInstruction::Tree {
+		inclusive,
+	}) = rec.instruction
+	{
+		return compute_tree(stk, ctx, opt, doc, rec, *inclusive).await;
+	}
+
 	// Counter for the local loop and current value
 	let mut i = rec.iterated.to_owned();
 	let mut current = rec.current.to_owned();
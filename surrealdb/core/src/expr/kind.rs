@@ -89,7 +89,7 @@ impl From<crate::types::PublicGeometryKind> for GeometryKind {
 }
 
 /// The kind, or data type, of a value or field.
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub enum Kind {
 	/// The most generic type, can be anything.
@@ -155,9 +155,17 @@ pub enum Kind {
 	/// If the kind was specified without a bucket the vec will be empty.
 	/// So `<file>` is just `Kind::File(Vec::new())`
 	File(Vec<String>),
+	/// A decimal type with a precision and scale, like `decimal(20, 4)`.
+	/// The first number is the maximum number of significant digits, and the
+	/// second is the number of those digits after the decimal point.
+	#[revision(start = 2)]
+	FixedDecimal(u32, u32),
 }
 
 impl Kind {
+	/// The most significant digits which a decimal can always hold.
+	pub(crate) const MAX_DECIMAL_PRECISION: u32 = 28;
+
 	/// Returns the kind of a type.
 	pub(crate) fn of<T: HasKind>() -> Kind {
 		T::kind()
@@ -291,6 +299,7 @@ impl Kind {
 			| Kind::Bytes
 			| Kind::Datetime
 			| Kind::Decimal
+			| Kind::FixedDecimal(_, _)
 			| Kind::Duration
 			| Kind::Float
 			| Kind::Int
@@ -608,7 +617,8 @@ impl From<Kind> for crate::types::PublicKind {
 			Kind::Bool => crate::types::PublicKind::Bool,
 			Kind::Bytes => crate::types::PublicKind::Bytes,
 			Kind::Datetime => crate::types::PublicKind::Datetime,
-			Kind::Decimal => crate::types::PublicKind::Decimal,
+			// The precision and scale are not part of the public kind
+			Kind::Decimal | Kind::FixedDecimal(_, _) => crate::types::PublicKind::Decimal,
 			Kind::Duration => crate::types::PublicKind::Duration,
 			Kind::Float => crate::types::PublicKind::Float,
 			Kind::Int => crate::types::PublicKind::Int,
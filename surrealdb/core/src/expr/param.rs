@@ -16,6 +16,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::fmt::EscapeKwFreeIdent;
 use crate::iam::Action;
+use crate::kvs::SessionTables;
 use crate::val::Value;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
@@ -119,7 +120,12 @@ impl Param {
 			// This is a normal param
 			v => match ctx.value(v) {
 				// The param has been set locally
-				Some(v) => Ok(v.clone()),
+				Some(v) => {
+					let mut v = v.clone();
+					// The param may refer to a session table by its name
+					SessionTables::resolve_value(ctx, &mut v);
+					Ok(v)
+				}
 				// The param has not been set locally
 				None => {
 					// Ensure a database is set
@@ -489,6 +489,10 @@ pub(crate) enum RecurseInstruction {
 		// Do we include the starting point in the collection?
 		inclusive: bool,
 	},
+	Tree {
+		// Do we root the tree at the starting point?
+		inclusive: bool,
+	},
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -644,6 +648,9 @@ impl RecurseInstruction {
 				// Continue
 				Ok(res)
 			}
+			Self::Tree {
+				inclusive,
+			} => recursion::compute_tree(stk, ctx, opt, doc, rec, *inclusive).await,
 		}
 	}
 }
@@ -1,6 +1,7 @@
 use crate::expr::Expr;
 use crate::expr::statements::{
-	AccessStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement, UseStatement,
+	AccessStatement, KillStatement, LiveSchemaStatement, LiveStatement, LockStatement,
+	OptionStatement, RefreshStatement, ShowStatement, UseStatement,
 };
 
 #[derive(Clone, Debug)]
@@ -16,7 +17,10 @@ pub(crate) enum TopLevelExpr {
 	Access(Box<AccessStatement>),
 	Kill(KillStatement),
 	Live(Box<LiveStatement>),
+	LiveSchema(LiveSchemaStatement),
+	Lock(LockStatement),
 	Option(OptionStatement),
+	Refresh(RefreshStatement),
 	Use(UseStatement),
 	Show(ShowStatement),
 	Expr(Expr),
@@ -32,7 +36,10 @@ impl TopLevelExpr {
 			| TopLevelExpr::Show(_) => true,
 			TopLevelExpr::Kill(_)
 			| TopLevelExpr::Live(_)
+			| TopLevelExpr::LiveSchema(_)
+			| TopLevelExpr::Lock(_)
 			| TopLevelExpr::Option(_)
+			| TopLevelExpr::Refresh(_)
 			| TopLevelExpr::Use(_)
 			| TopLevelExpr::Access(_) => false,
 			TopLevelExpr::Expr(expr) => expr.read_only(),
@@ -0,0 +1,66 @@
+use std::time;
+
+use revision::revisioned;
+
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{BinaryOperator, Expr, Idiom, Literal};
+use crate::val::{Duration, Value};
+
+/// The soft delete behaviour of a table, declared with `SOFT DELETE FIELD`
+///
+/// Deleting a record of the table sets the marker field to the time of
+/// deletion instead of removing the record. Marked records are hidden from
+/// SELECT and mutating statements unless they are run `WITH DELETED`, and are
+/// removed permanently when deleted `WITH DELETED`, or by the background purge
+/// task once the retention period has passed.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct SoftDelete {
+	/// The field which records when a record was deleted
+	pub field: String,
+	/// How long deleted records are kept before being purged
+	pub retention: Option<time::Duration>,
+}
+
+impl SoftDelete {
+	/// Checks whether a record has been marked as deleted
+	pub(crate) fn is_deleted(&self, doc: &Value) -> bool {
+		match doc {
+			Value::Object(o) => o.get(self.field.as_str()).is_some_and(Value::is_some),
+			_ => false,
+		}
+	}
+
+	/// The time at which a record was deleted, in milliseconds since the
+	/// unix epoch, if it is marked with a datetime
+	pub(crate) fn deleted_at(&self, doc: &Value) -> Option<u64> {
+		match doc {
+			Value::Object(o) => match o.get(self.field.as_str()) {
+				Some(Value::Datetime(v)) => {
+					Some(u64::try_from(v.timestamp_millis()).unwrap_or_default())
+				}
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	/// The condition matching records which have not been deleted
+	pub(crate) fn check(&self) -> Expr {
+		Expr::Binary {
+			left: Box::new(Expr::Idiom(Idiom::field(self.field.clone()))),
+			op: BinaryOperator::Equal,
+			right: Box::new(Expr::Literal(Literal::None)),
+		}
+	}
+}
+
+impl InfoStructure for SoftDelete {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"field" => self.field.into(),
+			"retention", if let Some(v) = self.retention => Duration(v).into(),
+		})
+	}
+}
@@ -1,18 +1,25 @@
+use std::sync::Arc;
+
 use anyhow::{Result, bail, ensure};
+use chrono::Utc;
 use rand::Rng;
 use reblessive::tree::Stk;
 use surrealdb_strand::Strand;
 use surrealdb_types::{SqlFormat, ToSql};
 
 use crate::catalog::providers::{
-	AuthorisationProvider, CatalogProvider, NamespaceProvider, UserProvider,
+	AuthorisationProvider, CatalogProvider, DatabaseProvider, NamespaceProvider, UserProvider,
 };
+use crate::catalog::{DatabaseId, NamespaceId};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::{Base, Cond, ControlFlow, FlowResult, FlowResultExt as _, RecordIdLit};
-use crate::iam::{Action, ResourceKind};
+use crate::iam::{Action, ResourceKind, totp};
+use crate::key::root::gl::{GrantLimitKey, GrantLimitKind};
+use crate::kvs::Transaction;
+use crate::kvs::ratelimit::GrantWindow;
 use crate::val::{Array, Datetime, Duration, Object, Value};
 use crate::{catalog, val};
 
@@ -30,10 +37,12 @@ pub static GRANT_BEARER_KEY_LENGTH: usize = 24;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) enum AccessStatement {
-	Grant(AccessStatementGrant),   // Create access grant.
-	Show(AccessStatementShow),     // Show access grants.
-	Revoke(AccessStatementRevoke), // Revoke access grant.
-	Purge(AccessStatementPurge),   // Purge access grants.
+	Grant(AccessStatementGrant),             // Create access grant.
+	GrantMany(AccessStatementGrantMany),     // Create access grants for many subjects.
+	Show(AccessStatementShow),               // Show access grants.
+	ShowSubject(AccessStatementShowSubject), // Show access grants of a subject.
+	Revoke(AccessStatementRevoke),           // Revoke access grant.
+	Purge(AccessStatementPurge),             // Purge access grants.
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -41,6 +50,14 @@ pub(crate) struct AccessStatementGrant {
 	pub ac: Strand,
 	pub base: Option<Base>,
 	pub subject: Subject,
+	pub totp: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct AccessStatementGrantMany {
+	pub ac: Strand,
+	pub base: Option<Base>,
+	pub subjects: Vec<Subject>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
@@ -51,6 +68,11 @@ pub(crate) struct AccessStatementShow {
 	pub cond: Option<Cond>,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct AccessStatementShowSubject {
+	pub subject: Subject,
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub(crate) struct AccessStatementRevoke {
 	pub ac: Strand,
@@ -111,13 +133,17 @@ fn random_string(length: usize, pool: &[u8]) -> String {
 	string
 }
 
-pub fn new_grant_bearer(ty: catalog::BearerAccessType) -> catalog::GrantBearer {
-	let id = format!(
+fn new_grant_id() -> String {
+	format!(
 		"{}{}",
 		// The pool for the first character of the key identifier excludes digits.
 		random_string(1, &GRANT_BEARER_CHARACTER_POOL[10..]),
 		random_string(GRANT_BEARER_ID_LENGTH - 1, GRANT_BEARER_CHARACTER_POOL)
-	);
+	)
+}
+
+pub fn new_grant_bearer(ty: catalog::BearerAccessType) -> catalog::GrantBearer {
+	let id = new_grant_id();
 	let secret = random_string(GRANT_BEARER_KEY_LENGTH, GRANT_BEARER_CHARACTER_POOL);
 	let prefix = match ty {
 		catalog::BearerAccessType::Bearer => "surreal-bearer",
@@ -141,12 +167,7 @@ pub fn access_object_from_grant(grant: &catalog::AccessGrant) -> Object {
 	res.insert("creation".to_owned(), Value::from(grant.creation));
 	res.insert("expiration".to_owned(), grant.expiration.map(Value::from).unwrap_or(Value::None));
 	res.insert("revocation".to_owned(), grant.revocation.map(Value::from).unwrap_or(Value::None));
-	let mut sub = Object::default();
-	match &grant.subject {
-		catalog::Subject::Record(id) => sub.insert("record".to_owned(), Value::from(id.clone())),
-		catalog::Subject::User(name) => sub.insert("user".to_owned(), Value::from(name.clone())),
-	};
-	res.insert("subject".to_owned(), Value::from(sub));
+	res.insert("subject".to_owned(), Value::from(subject_object(&grant.subject)));
 
 	let mut gr = Object::default();
 	match &grant.grant {
@@ -167,12 +188,25 @@ pub fn access_object_from_grant(grant: &catalog::AccessGrant) -> Object {
 			gr.insert("id".to_owned(), Value::from(bg.id.clone()));
 			gr.insert("key".to_owned(), Value::from(bg.key.clone()));
 		}
+		catalog::Grant::Totp(tg) => {
+			gr.insert("secret".to_owned(), Value::from(tg.secret.clone()));
+		}
 	};
 	res.insert("grant".to_owned(), Value::from(gr));
 
 	res
 }
 
+/// Returns the surrealql object representation of the subject of a grant
+fn subject_object(subject: &catalog::Subject) -> Object {
+	let mut sub = Object::default();
+	match subject {
+		catalog::Subject::Record(id) => sub.insert("record".to_owned(), Value::from(id.clone())),
+		catalog::Subject::User(name) => sub.insert("user".to_owned(), Value::from(name.clone())),
+	};
+	sub
+}
+
 pub async fn create_grant(
 	access: String,
 	base: Option<Base>,
@@ -192,19 +226,22 @@ pub async fn create_grant(
 	txn.clear_cache();
 
 	// Read the access definition.
-	let ac = match base {
-		Base::Root => txn.expect_root_access(&access).await?,
+	let (ac, scope) = match base {
+		Base::Root => (txn.expect_root_access(&access).await?, (None, None)),
 		Base::Ns => {
 			let ns = ctx.expect_ns_id(opt).await?;
-			txn.get_ns_access(ns, &access, None).await?.ok_or_else(|| Error::AccessNsNotFound {
-				ac: access.clone(),
-				// The namespace is expected above
-				ns: opt.ns.as_deref().expect("namespace validated by expect_ns_id").to_owned(),
-			})?
+			let ac = txn.get_ns_access(ns, &access, None).await?.ok_or_else(|| {
+				Error::AccessNsNotFound {
+					ac: access.clone(),
+					// The namespace is expected above
+					ns: opt.ns.as_deref().expect("namespace validated by expect_ns_id").to_owned(),
+				}
+			})?;
+			(ac, (Some(ns), None))
 		}
 		Base::Db => {
 			let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
-			txn.get_db_access(ns, db, &access, None).await?.ok_or_else(|| {
+			let ac = txn.get_db_access(ns, db, &access, None).await?.ok_or_else(|| {
 				Error::AccessDbNotFound {
 					ac: access.clone(),
 					// The namespace and database is expected above
@@ -219,7 +256,8 @@ pub async fn create_grant(
 						.expect("database validated by expect_ns_db_ids")
 						.to_owned(),
 				}
-			})?
+			})?;
+			(ac, (Some(ns), Some(db)))
 		}
 	};
 
@@ -243,6 +281,8 @@ pub async fn create_grant(
 				Some(bearer) => bearer,
 				None => bail!(Error::AccessMethodMismatch),
 			};
+			// Check the grant rate limits.
+			limit_grant_rate(ctx, scope, &access, &subject).await?;
 			// Create a new bearer key.
 			let grant = new_grant_bearer(atb.kind);
 
@@ -298,6 +338,9 @@ pub async fn create_grant(
 				}
 			}
 
+			// Store the grant under its subject.
+			put_subject_grant(ctx, opt, base, &gr).await?;
+
 			info!(
 				"Access method '{}' was used to create grant '{}' of type '{}' for '{}' by '{}'",
 				gr.ac,
@@ -366,6 +409,8 @@ pub async fn create_grant(
 					// A grant can be created for a record that does not exist yet.
 				}
 			};
+			// Check the grant rate limits.
+			limit_grant_rate(ctx, scope, &access, &subject).await?;
 			// Create a new bearer key.
 			let grant = new_grant_bearer(at.kind);
 			let gr = catalog::AccessGrant {
@@ -433,6 +478,9 @@ pub async fn create_grant(
 				}
 			}
 
+			// Store the grant under its subject.
+			put_subject_grant(ctx, opt, base, &gr).await?;
+
 			info!(
 				"Access method '{}' was used to create grant '{}' of type '{}' for '{}' by '{}'",
 				gr.ac,
@@ -449,6 +497,266 @@ pub async fn create_grant(
 	}
 }
 
+/// Enrols a record in the second factor of a record access method, returning
+/// the grant holding the secret of the enrolment. Any previous enrolment of
+/// the record is revoked, so that only the latest secret is accepted.
+pub async fn create_grant_totp(
+	access: String,
+	base: Option<Base>,
+	subject: catalog::Subject,
+	ctx: &FrozenContext,
+	opt: &Options,
+) -> Result<catalog::AccessGrant> {
+	let base = match &base {
+		Some(base) => *base,
+		None => opt.selected_base()?,
+	};
+	// Allowed to run?
+	ctx.is_allowed(opt, Action::Edit, ResourceKind::Access, base)?;
+	// Only records of database access methods can be enrolled.
+	ensure!(matches!(base, Base::Db), Error::AccessLevelMismatch);
+	ensure!(matches!(subject, catalog::Subject::Record(_)), Error::AccessGrantInvalidSubject);
+	// Get the transaction.
+	let txn = ctx.tx();
+	// Clear the cache.
+	txn.clear_cache();
+	// Read the access definition.
+	let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+	let ac = txn.get_db_access(ns, db, &access, None).await?.ok_or_else(|| {
+		Error::AccessDbNotFound {
+			ac: access.clone(),
+			// The namespace and database is expected above
+			ns: opt.ns.as_deref().expect("namespace validated by expect_ns_db_ids").to_owned(),
+			db: opt.db.as_deref().expect("database validated by expect_ns_db_ids").to_owned(),
+		}
+	})?;
+	// The record access method must require a second factor.
+	match &ac.access_type {
+		catalog::AccessType::Record(at) if at.mfa.is_some() => {}
+		_ => bail!(Error::AccessMethodMismatch),
+	}
+	// Check the grant rate limits.
+	limit_grant_rate(ctx, (Some(ns), Some(db)), &access, &subject).await?;
+	// Revoke the previous enrolments of the record.
+	for gr in get_totp_enrolments(&txn, ns, db, &ac.name, &subject).await? {
+		let mut gr = (*gr).clone();
+		gr.revocation = Some(Datetime::now());
+		let key = crate::key::database::access::gr::new(ns, db, &gr.ac, &gr.id);
+		txn.set(&key, &gr).await?;
+	}
+
+	let gr = catalog::AccessGrant {
+		ac: ac.name.to_string(),
+		// Unique grant identifier, which is named by the challenges of the enrolment.
+		id: new_grant_id(),
+		// Current time.
+		creation: Datetime::now(),
+		// An enrolment does not expire, as the record would lose its second factor.
+		expiration: None,
+		// The grant is initially not revoked.
+		revocation: None,
+		// Subject associated with the grant.
+		subject,
+		// The contents of the grant.
+		grant: catalog::Grant::Totp(totp::new_grant_totp()),
+	};
+
+	// Create the grant.
+	// The secret is needed to verify the codes, so it is stored as is.
+	let key = crate::key::database::access::gr::new(ns, db, &gr.ac, &gr.id);
+	txn.put(&key, &gr).await?;
+
+	// Store the grant under its subject.
+	put_subject_grant(ctx, opt, base, &gr).await?;
+
+	info!(
+		"Access method '{}' was used to create grant '{}' of type '{}' for '{}' by '{}'",
+		gr.ac,
+		gr.id,
+		gr.grant.variant(),
+		gr.subject.id(),
+		opt.auth.id()
+	);
+
+	Ok(gr)
+}
+
+/// Returns the active second factor enrolments of a subject for a database
+/// access method.
+pub(crate) async fn get_totp_enrolments(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	ac: &str,
+	subject: &catalog::Subject,
+) -> Result<Vec<Arc<catalog::AccessGrant>>> {
+	let rng = crate::key::database::sg::SubjectGrant::range(ns, db, subject)?;
+	let mut res = Vec::new();
+	for key in txn.keys(rng, u32::MAX, 0, None).await?.iter() {
+		let key = crate::key::database::sg::SubjectGrant::decode_key(key)?;
+		if key.ac != ac {
+			continue;
+		}
+		let Some(gr) = txn.get_db_access_grant(ns, db, ac, &key.gr, None).await? else {
+			continue;
+		};
+		let active = gr.revocation.is_none()
+			&& gr.expiration.as_ref().is_none_or(|exp| exp > &Datetime::now());
+		if active && matches!(gr.grant, catalog::Grant::Totp(_)) {
+			res.push(gr);
+		}
+	}
+	Ok(res)
+}
+
+/// Stores a grant under its subject, so that the grants of a subject can be
+/// listed across every access method at the base of the grant.
+async fn put_subject_grant(
+	ctx: &FrozenContext,
+	opt: &Options,
+	base: Base,
+	gr: &catalog::AccessGrant,
+) -> Result<()> {
+	let txn = ctx.tx();
+	match base {
+		Base::Root => {
+			let key = crate::key::root::sg::SubjectGrant::new(&gr.subject, &gr.ac, &gr.id);
+			txn.set(&key, &()).await
+		}
+		Base::Ns => {
+			let ns = ctx.get_ns_id(opt).await?;
+			let key = crate::key::namespace::sg::SubjectGrant::new(ns, &gr.subject, &gr.ac, &gr.id);
+			txn.set(&key, &()).await
+		}
+		Base::Db => {
+			let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+			let key =
+				crate::key::database::sg::SubjectGrant::new(ns, db, &gr.subject, &gr.ac, &gr.id);
+			txn.set(&key, &()).await
+		}
+	}
+}
+
+/// Stores the grants which were created before grants were stored under
+/// their subjects, across every access method of the datastore. This only
+/// runs once, after which a marker is stored.
+pub(crate) async fn index_subject_grants(txn: &Transaction) -> Result<()> {
+	let marker = crate::key::root::si::Si::new();
+	if txn.exists(&marker, None).await? {
+		return Ok(());
+	}
+	for ac in txn.all_root_accesses(None).await?.iter() {
+		for gr in txn.all_root_access_grants(&ac.name, None).await?.iter() {
+			let key = crate::key::root::sg::SubjectGrant::new(&gr.subject, &gr.ac, &gr.id);
+			txn.set(&key, &()).await?;
+		}
+	}
+	for ns in txn.all_ns(None).await?.iter() {
+		let ns = ns.namespace_id;
+		for ac in txn.all_ns_accesses(ns, None).await?.iter() {
+			for gr in txn.all_ns_access_grants(ns, &ac.name, None).await?.iter() {
+				let key =
+					crate::key::namespace::sg::SubjectGrant::new(ns, &gr.subject, &gr.ac, &gr.id);
+				txn.set(&key, &()).await?;
+			}
+		}
+		for db in txn.all_db(ns, None).await?.iter() {
+			let db = db.database_id;
+			for ac in txn.all_db_accesses(ns, db, None).await?.iter() {
+				for gr in txn.all_db_access_grants(ns, db, &ac.name, None).await?.iter() {
+					let key = crate::key::database::sg::SubjectGrant::new(
+						ns,
+						db,
+						&gr.subject,
+						&gr.ac,
+						&gr.id,
+					);
+					txn.set(&key, &()).await?;
+				}
+			}
+		}
+	}
+	txn.set(&marker, &()).await
+}
+
+/// Removes a purged grant from the grants of its subject.
+async fn del_subject_grant(
+	ctx: &FrozenContext,
+	opt: &Options,
+	base: Base,
+	gr: &catalog::AccessGrant,
+) -> Result<()> {
+	let txn = ctx.tx();
+	match base {
+		Base::Root => {
+			let key = crate::key::root::sg::SubjectGrant::new(&gr.subject, &gr.ac, &gr.id);
+			txn.del(&key).await
+		}
+		Base::Ns => {
+			let ns = ctx.get_ns_id(opt).await?;
+			let key = crate::key::namespace::sg::SubjectGrant::new(ns, &gr.subject, &gr.ac, &gr.id);
+			txn.del(&key).await
+		}
+		Base::Db => {
+			let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+			let key =
+				crate::key::database::sg::SubjectGrant::new(ns, db, &gr.subject, &gr.ac, &gr.id);
+			txn.del(&key).await
+		}
+	}
+}
+
+/// Enforces the configured rate limits on the creation of access grants, both
+/// for the subject of the grant and for the IP address of the client creating
+/// it. The number of recently created grants is stored in the datastore, so
+/// that the limits apply across every node in a cluster.
+async fn limit_grant_rate(
+	ctx: &FrozenContext,
+	(ns, db): (Option<NamespaceId>, Option<DatabaseId>),
+	ac: &str,
+	subject: &catalog::Subject,
+) -> Result<()> {
+	let cnf = &ctx.config;
+	let mut limits = Vec::with_capacity(2);
+	if cnf.grant_rate_limit_subject > 0 {
+		let kind = match subject {
+			catalog::Subject::Record(_) => GrantLimitKind::Record,
+			catalog::Subject::User(_) => GrantLimitKind::User,
+		};
+		let id = subject.id();
+		let target = format!("subject '{id}'");
+		limits.push((kind, id, target, cnf.grant_rate_limit_subject));
+	}
+	if cnf.grant_rate_limit_ip > 0 {
+		let ip = ctx.value("session").and_then(|v| v.as_object()).and_then(|v| v.get("ip"));
+		if let Some(Value::String(ip)) = ip {
+			let target = format!("IP address '{ip}'");
+			limits.push((GrantLimitKind::Ip, ip.to_string(), target, cnf.grant_rate_limit_ip));
+		}
+	}
+	if limits.is_empty() {
+		return Ok(());
+	}
+	// Use the wall clock, which is shared between nodes
+	let now = Utc::now().timestamp_millis();
+	let txn = ctx.tx();
+	for (kind, id, target, limit) in limits {
+		let key = GrantLimitKey::new(kind, ns, db, ac, &id);
+		let window = txn.get(&key, None).await?;
+		match GrantWindow::record(window, now, cnf.grant_rate_limit_window, limit) {
+			Ok(window) => txn.set(&key, &window).await?,
+			Err(retry_after) => {
+				warn!("The creation of access grants was rate limited for {target}");
+				bail!(Error::GrantRateLimited {
+					target,
+					retry_after: retry_after.into(),
+				});
+			}
+		}
+	}
+	Ok(())
+}
+
 async fn compute_grant(
 	stmt: &AccessStatementGrant,
 	stk: &mut Stk,
@@ -458,11 +766,75 @@ async fn compute_grant(
 ) -> FlowResult<Value> {
 	let subject = stmt.subject.compute(stk, ctx, opt, doc).await?;
 
+	if stmt.totp {
+		let grant = create_grant_totp(stmt.ac.to_string(), stmt.base, subject, ctx, opt).await?;
+		let mut res = access_object_from_grant(&grant);
+		// Return the URI of the enrolment along with its secret, to be shown as a QR code.
+		// This is the only time the secret is returned.
+		if let (catalog::Grant::Totp(tg), Some(Value::Object(gr))) =
+			(&grant.grant, res.get_mut("grant"))
+		{
+			let uri = totp::uri(&grant.ac, &grant.subject.id(), tg);
+			gr.insert("uri".to_owned(), Value::from(uri));
+		}
+		return Ok(Value::Object(res));
+	}
+
 	let grant = create_grant(stmt.ac.to_string(), stmt.base, subject, ctx, opt).await?;
 
 	Ok(Value::Object(access_object_from_grant(&grant)))
 }
 
+/// Returns whether the error of a grant only concerns its subject, in which
+/// case the grants of the other subjects of a list are still issued.
+fn is_subject_grant_error(e: &anyhow::Error) -> bool {
+	matches!(
+		e.downcast_ref(),
+		Some(
+			Error::AccessGrantInvalidSubject
+				| Error::UserRootNotFound { .. }
+				| Error::UserNsNotFound { .. }
+				| Error::UserDbNotFound { .. }
+				| Error::GrantRateLimited { .. }
+				| Error::Kvs(crate::kvs::Error::TransactionKeyAlreadyExists)
+		)
+	)
+}
+
+async fn compute_grant_many(
+	stmt: &AccessStatementGrantMany,
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	doc: Option<&CursorDoc>,
+) -> FlowResult<Value> {
+	let base = match &stmt.base {
+		Some(base) => *base,
+		None => opt.selected_base()?,
+	};
+	// Allowed to run?
+	ctx.is_allowed(opt, Action::Edit, ResourceKind::Access, base)?;
+	// Issue a grant for each subject, within the current transaction.
+	let mut grants = Vec::with_capacity(stmt.subjects.len());
+	for subject in stmt.subjects.iter() {
+		let subject = subject.compute(stk, ctx, opt, doc).await?;
+		match create_grant(stmt.ac.to_string(), Some(base), subject.clone(), ctx, opt).await {
+			// This is the only time the plaintext keys are returned.
+			Ok(grant) => grants.push(Value::Object(access_object_from_grant(&grant))),
+			// Report the subjects which could not be granted access.
+			Err(e) if is_subject_grant_error(&e) => {
+				let mut res = Object::default();
+				res.insert("ac".to_owned(), Value::from(stmt.ac.to_string()));
+				res.insert("subject".to_owned(), Value::from(subject_object(&subject)));
+				res.insert("error".to_owned(), Value::from(e.to_string()));
+				grants.push(Value::Object(res));
+			}
+			Err(e) => return Err(ControlFlow::Err(e)),
+		}
+	}
+	Ok(Value::Array(grants.into()))
+}
+
 async fn compute_show(
 	stmt: &AccessStatementShow,
 	stk: &mut Stk,
@@ -614,6 +986,65 @@ async fn compute_show(
 	}
 }
 
+async fn compute_show_subject(
+	stmt: &AccessStatementShowSubject,
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	doc: Option<&CursorDoc>,
+) -> FlowResult<Value> {
+	let base = opt.selected_base()?;
+	// Allowed to run?
+	ctx.is_allowed(opt, Action::View, ResourceKind::Access, base)?;
+	// Compute the subject.
+	let subject = stmt.subject.compute(stk, ctx, opt, doc).await?;
+	// Get the transaction.
+	let txn = ctx.tx();
+	// Clear the cache.
+	txn.clear_cache();
+	// Get the grants of the subject, across all access methods.
+	let keys = match base {
+		Base::Root => {
+			let rng = crate::key::root::sg::SubjectGrant::range(&subject)?;
+			txn.keys(rng, u32::MAX, 0, None).await?
+		}
+		Base::Ns => {
+			let ns = ctx.expect_ns_id(opt).await?;
+			let rng = crate::key::namespace::sg::SubjectGrant::range(ns, &subject)?;
+			txn.keys(rng, u32::MAX, 0, None).await?
+		}
+		Base::Db => {
+			let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+			let rng = crate::key::database::sg::SubjectGrant::range(ns, db, &subject)?;
+			txn.keys(rng, u32::MAX, 0, None).await?
+		}
+	};
+	let mut show = Vec::new();
+	for key in keys.iter() {
+		let grant = match base {
+			Base::Root => {
+				let key = crate::key::root::sg::SubjectGrant::decode_key(key)?;
+				txn.get_root_access_grant(&key.ac, &key.gr, None).await?
+			}
+			Base::Ns => {
+				let key = crate::key::namespace::sg::SubjectGrant::decode_key(key)?;
+				txn.get_ns_access_grant(key.ns, &key.ac, &key.gr, None).await?
+			}
+			Base::Db => {
+				let key = crate::key::database::sg::SubjectGrant::decode_key(key)?;
+				txn.get_db_access_grant(key.ns, key.db, &key.ac, &key.gr, None).await?
+			}
+		};
+		// Grants are removed along with their access method, so skip
+		// the grants which no longer exist.
+		if let Some(grant) = grant {
+			show.push(Value::Object(access_object_from_grant(&(*grant).clone().redacted())));
+		}
+	}
+
+	Ok(Value::Array(show.into()))
+}
+
 pub async fn revoke_grant(
 	stmt: &AccessStatementRevoke,
 	stk: &mut Stk,
@@ -926,6 +1357,7 @@ async fn compute_purge(
 					.await?
 				}
 			};
+			del_subject_grant(ctx, opt, base, gr).await?;
 
 			info!(
 				"Access method '{}' was used to purge grant '{}' of type '{}' for '{}' by '{}'",
@@ -954,9 +1386,13 @@ impl AccessStatement {
 	) -> FlowResult<Value> {
 		match self {
 			AccessStatement::Grant(stmt) => compute_grant(stmt, stk, ctx, opt, doc).await,
+			AccessStatement::GrantMany(stmt) => compute_grant_many(stmt, stk, ctx, opt, doc).await,
 			AccessStatement::Show(stmt) => {
 				compute_show(stmt, stk, ctx, opt, doc).await.map_err(ControlFlow::Err)
 			}
+			AccessStatement::ShowSubject(stmt) => {
+				compute_show_subject(stmt, stk, ctx, opt, doc).await
+			}
 			AccessStatement::Revoke(stmt) => {
 				compute_revoke(stmt, stk, ctx, opt, doc).await.map_err(ControlFlow::Err)
 			}
@@ -973,3 +1409,121 @@ impl ToSql for AccessStatement {
 		sql_stmt.fmt_sql(f, fmt);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_types::QueryError;
+
+	use crate::cnf::ConfigMap;
+	use crate::dbs::{Capabilities, Session};
+	use crate::kvs::{Datastore, LockType, TransactionType};
+
+	async fn new_ds(key: &str, limit: &str) -> Datastore {
+		new_ds_with_window(key, limit, "1h").await
+	}
+
+	async fn new_ds_with_window(key: &str, limit: &str, window: &str) -> Datastore {
+		let config = ConfigMap::empty()
+			.with_key_value(key, limit)
+			.with_key_value("grant_rate_limit_window", window);
+		let ds = Datastore::builder()
+			.with_capabilities(Capabilities::all())
+			.with_config(config)
+			.build_with_path("memory")
+			.await
+			.unwrap();
+		let sess = Session::owner();
+		let sql = "
+			DEFINE ACCESS api ON ROOT TYPE BEARER FOR USER;
+			DEFINE USER tobie ON ROOT ROLES EDITOR;
+			DEFINE USER jaime ON ROOT ROLES EDITOR;
+		";
+		for res in ds.execute(sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		ds
+	}
+
+	async fn grant(ds: &Datastore, user: &str, ip: &str) -> Result<(), surrealdb_types::Error> {
+		let mut sess = Session::owner();
+		sess.ip = Some(ip.to_owned());
+		let sql = format!("ACCESS api ON ROOT GRANT FOR USER {user}");
+		let mut res = ds.execute(&sql, &sess, None).await.unwrap();
+		res.remove(0).result.map(|_| ())
+	}
+
+	#[tokio::test]
+	async fn grant_rate_limit_subject() {
+		let ds = new_ds("grant_rate_limit_subject", "2").await;
+		grant(&ds, "tobie", "127.0.0.1").await.unwrap();
+		grant(&ds, "tobie", "127.0.0.2").await.unwrap();
+		let err = grant(&ds, "tobie", "127.0.0.3").await.unwrap_err();
+		let Some(QueryError::RateLimited {
+			retry_after,
+		}) = err.query_details()
+		else {
+			panic!("Expected a rate limited error: {err:?}");
+		};
+		assert!(!retry_after.is_zero());
+		// Other subjects are limited separately
+		grant(&ds, "jaime", "127.0.0.1").await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn grant_rate_limit_ip() {
+		let ds = new_ds("grant_rate_limit_ip", "1").await;
+		grant(&ds, "tobie", "127.0.0.1").await.unwrap();
+		let err = grant(&ds, "jaime", "127.0.0.1").await.unwrap_err();
+		assert!(matches!(err.query_details(), Some(QueryError::RateLimited { .. })), "{err:?}");
+		// Other clients are limited separately
+		grant(&ds, "jaime", "127.0.0.2").await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn grant_rate_limit_windows_are_garbage_collected() {
+		use crate::key::root::gl;
+
+		async fn windows(ds: &Datastore) -> usize {
+			let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+			let keys = tx.keys(gl::prefix()..gl::suffix(), 100, 0, None).await.unwrap();
+			tx.cancel().await.unwrap();
+			keys.len()
+		}
+
+		let ds = new_ds_with_window("grant_rate_limit_ip", "10", "200ms").await;
+		grant(&ds, "tobie", "127.0.0.1").await.unwrap();
+		grant(&ds, "jaime", "127.0.0.2").await.unwrap();
+		assert_eq!(windows(&ds).await, 2);
+		// The windows are kept while their grants are still limited
+		ds.garbage_collect().await.unwrap();
+		assert_eq!(windows(&ds).await, 2);
+		// The windows are removed once both fixed windows have passed
+		tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+		ds.garbage_collect().await.unwrap();
+		assert_eq!(windows(&ds).await, 0);
+	}
+
+	#[tokio::test]
+	async fn grant_many_reports_failures_per_subject() {
+		use surrealdb_types::Value;
+
+		let ds = new_ds("grant_rate_limit_subject", "1").await;
+		let sess = Session::owner();
+		let sql = "ACCESS api ON ROOT GRANT FOR USER IN [tobie, unknown, tobie, jaime]";
+		let mut res = ds.execute(sql, &sess, None).await.unwrap();
+		let grants = res.remove(0).result.unwrap();
+		// The plaintext keys of the issued grants are returned
+		assert!(matches!(grants.get(0).get("grant").get("key"), Value::String(_)), "{grants:?}");
+		assert!(matches!(grants.get(3).get("grant").get("key"), Value::String(_)), "{grants:?}");
+		// Unknown and rate limited subjects are reported without failing the others
+		assert_eq!(grants.get(1).get("subject").get("user"), &Value::String("unknown".into()));
+		assert!(matches!(grants.get(1).get("error"), Value::String(_)), "{grants:?}");
+		assert!(matches!(grants.get(2).get("error"), Value::String(_)), "{grants:?}");
+		// Only the issued grants are stored
+		let mut res = ds.execute("ACCESS api ON ROOT SHOW ALL", &sess, None).await.unwrap();
+		let Value::Array(stored) = res.remove(0).result.unwrap() else {
+			panic!("Expected the stored grants");
+		};
+		assert_eq!(stored.len(), 2);
+	}
+}
@@ -16,6 +16,7 @@ use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, Literal};
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::Value;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -165,6 +166,7 @@ impl AlterAccessStatement {
 		let key = crate::key::database::ac::new(ns, db, name);
 		txn.set(&key, &ac).await?;
 		txn.clear_cache();
+		notify_schema_change(ctx, opt, SchemaAction::Alter, SchemaChange::Access(name)).await?;
 		Ok(Value::None)
 	}
 }
@@ -15,8 +15,10 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::parameterize::{expr_to_ident, expr_to_idiom};
 use crate::expr::reference::Reference;
+use crate::expr::statements::refresh::mark_dependents_stale;
 use crate::expr::{Base, Expr, Kind, Literal};
 use crate::iam::{Action, AuthLimit, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::{TableName, Value};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
@@ -210,6 +212,14 @@ impl AlterFieldStatement {
 		.await?;
 		// Clear the cache
 		txn.clear_cache();
+		// The views computed from the table are stale until refreshed
+		mark_dependents_stale(ctx, opt, &what).await?;
+		// Notify the schema subscriptions
+		let change = SchemaChange::Field {
+			table: what.as_str(),
+			name: &name,
+		};
+		notify_schema_change(ctx, opt, SchemaAction::Alter, change).await?;
 		// Ok all good
 		Ok(Value::None)
 	}
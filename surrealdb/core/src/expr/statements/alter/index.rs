@@ -16,6 +16,7 @@ use crate::expr::parameterize::expr_to_ident;
 use crate::expr::statements::alter::AlterKind;
 use crate::expr::{Base, Expr, Literal, Value};
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::TableName;
 
 /// Represents an `ALTER INDEX` statement.
@@ -109,6 +110,12 @@ impl AlterIndexStatement {
 		.await?;
 		// Clear the cache
 		txn.clear_cache();
+		// Notify the schema subscriptions
+		let change = SchemaChange::Index {
+			table: table.as_str(),
+			name: &name,
+		};
+		notify_schema_change(ctx, opt, SchemaAction::Alter, change).await?;
 		// Ok all good
 		Ok(Value::None)
 	}
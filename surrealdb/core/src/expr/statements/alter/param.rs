@@ -13,6 +13,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::expr::{Base, Expr, FlowResultExt};
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::Value;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
@@ -65,6 +66,12 @@ impl AlterParamStatement {
 		let key = crate::key::database::pa::new(ns, db, &self.name);
 		txn.set(&key, &pa).await?;
 		txn.clear_cache();
+		// Notify the schema subscriptions
+		let change = SchemaChange::Param {
+			name: pa.name.as_str(),
+			value: Some(&pa.value),
+		};
+		notify_schema_change(ctx, opt, SchemaAction::Alter, change).await?;
 		Ok(Value::None)
 	}
 }
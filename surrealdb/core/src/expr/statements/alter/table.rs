@@ -16,6 +16,7 @@ use crate::expr::parameterize::expr_to_ident;
 use crate::expr::statements::DefineTableStatement;
 use crate::expr::{Base, ChangeFeed, Expr, Literal};
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::{TableName, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -160,6 +161,9 @@ impl AlterTableStatement {
 
 		// Clear the cache
 		txn.clear_cache();
+		// Notify the schema subscriptions
+		notify_schema_change(ctx, opt, SchemaAction::Alter, SchemaChange::Table(name.as_str()))
+			.await?;
 		// Ok all good
 		Ok(Value::None)
 	}
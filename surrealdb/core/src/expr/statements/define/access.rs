@@ -21,6 +21,7 @@ use crate::expr::{
 	AccessType, Algorithm, Base, Expr, FlowResultExt, Idiom, JwtAccess, Literal, RecordAccess,
 };
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::{self, Duration, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -140,6 +141,7 @@ impl DefineAccessStatement {
 						signin: record_access.signin.clone(),
 						jwt: convert_jwt_access(&record_access.jwt),
 						bearer: record_access.bearer.as_ref().map(convert_bearer_access),
+						mfa: record_access.mfa.map(Into::into),
 					}))
 				}
 				catalog::AccessType::Jwt(jwt_access) => {
@@ -297,6 +299,7 @@ impl DefineAccessStatement {
 						signin: record_access.signin.clone(),
 						jwt: convert_jwt_access(stk, ctx, opt, doc, &record_access.jwt).await?,
 						bearer: map_opt!(x as &record_access.bearer => convert_bearer_access(stk, ctx, opt, doc, x).await?),
+						mfa: record_access.mfa.map(Into::into),
 					})
 				}
 				AccessType::Jwt(jwt_access) => catalog::AccessType::Jwt(
@@ -435,9 +438,8 @@ impl DefineAccessStatement {
 				// Check if the definition exists
 				let (ns, db) = ctx.get_ns_db_ids(opt).await?;
 				let mut existing_uses_es512 = false;
-				if let Some(access) =
-					txn.get_db_access(ns, db, definition.name.as_str(), None).await?
-				{
+				let existing = txn.get_db_access(ns, db, definition.name.as_str(), None).await?;
+				if let Some(access) = &existing {
 					existing_uses_es512 = Self::uses_es512(&access);
 					match self.kind {
 						DefineKind::Default => {
@@ -463,6 +465,14 @@ impl DefineAccessStatement {
 				txn.set(&key, &definition).await?;
 				// Clear the cache
 				txn.clear_cache();
+				// Notify the schema subscriptions
+				let action = if existing.is_some() {
+					SchemaAction::Alter
+				} else {
+					SchemaAction::Define
+				};
+				let change = SchemaChange::Access(definition.name.as_str());
+				notify_schema_change(ctx, opt, action, change).await?;
 				// Ok all good
 				Ok(Value::None)
 			}
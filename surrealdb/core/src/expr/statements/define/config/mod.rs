@@ -41,6 +41,15 @@ impl ConfigInner {
 		}
 	}
 
+	/// The name under which the config is stored
+	pub(crate) fn name(&self) -> &'static str {
+		match self {
+			ConfigInner::GraphQL(_) => "graphql",
+			ConfigInner::Api(_) => "api",
+			ConfigInner::Default(_) => "default",
+		}
+	}
+
 	pub(crate) async fn compute(
 		&self,
 		stk: &mut Stk,
@@ -74,11 +83,7 @@ impl DefineConfigStatement {
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Get the config kind
-		let cg = match &self.inner {
-			ConfigInner::GraphQL(_) => "graphql",
-			ConfigInner::Api(_) => "api",
-			ConfigInner::Default(_) => "default",
-		};
+		let cg = self.inner.name();
 
 		match base {
 			Base::Root => {
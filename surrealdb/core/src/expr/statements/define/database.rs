@@ -2,8 +2,8 @@ use anyhow::{Result, bail};
 use reblessive::tree::Stk;
 
 use super::DefineKind;
-use crate::catalog::DatabaseDefinition;
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider};
+use crate::catalog::{DatabaseCapabilities, DatabaseDefinition};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -23,6 +23,7 @@ pub(crate) struct DefineDatabaseStatement {
 	pub strict: bool,
 	pub comment: Expr,
 	pub changefeed: Option<ChangeFeed>,
+	pub capabilities: Option<DatabaseCapabilities>,
 }
 
 impl Default for DefineDatabaseStatement {
@@ -34,6 +35,7 @@ impl Default for DefineDatabaseStatement {
 			comment: Expr::Literal(Literal::None),
 			changefeed: None,
 			strict: false,
+			capabilities: None,
 		}
 	}
 }
@@ -96,6 +98,7 @@ impl DefineDatabaseStatement {
 			comment,
 			changefeed: self.changefeed,
 			strict: self.strict,
+			capabilities: self.capabilities.clone(),
 		};
 		txn.put_db(nsv.name.as_str(), db_def).await?;
 
@@ -0,0 +1,296 @@
+//! Previews of DEFINE statements.
+//!
+//! A DEFINE statement followed by `DIFF` is not applied. Instead it returns
+//! the current definition of the resource, if there is one, along with the
+//! definition which the statement would store, both formatted as SurrealQL.
+//! This allows a schema to be managed declaratively, by comparing the desired
+//! definitions with the stored ones before applying them.
+//!
+//! A statement with `IF NOT EXISTS` leaves an existing definition unchanged,
+//! while any other statement is compared as if it overwrote the existing
+//! definition.
+//!
+//! Secrets, such as password hashes and signing keys, are redacted from both
+//! definitions before they are compared, as they are by `INFO`. A statement
+//! which only changes a secret is therefore not reported as a change.
+
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+use surrealdb_types::ToSql;
+
+use super::{DefineKind, DefineStatement};
+use crate::catalog::providers::{
+	AuthorisationProvider, DatabaseProvider, NamespaceProvider, RootProvider, TableProvider,
+	UserProvider,
+};
+use crate::catalog::{ModuleName, UserDefinition};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::Base;
+use crate::expr::parameterize::{expr_to_ident, expr_to_idiom};
+use crate::iam::{Action, ResourceKind};
+use crate::key::database::sq::Sq;
+use crate::val::{TableName, Value};
+
+/// What secrets are replaced with in a previewed definition
+const REDACTED: &str = "[REDACTED]";
+
+impl DefineStatement {
+	/// Returns the existing and the resulting definition of the resource
+	/// defined by this statement, without applying it
+	#[instrument(level = "trace", name = "DefineStatement::diff", skip_all)]
+	pub(crate) async fn diff(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Previewing a definition requires the same permissions as applying it
+		let (resource, base) = self.resource();
+		ctx.is_allowed(opt, Action::Edit, resource, base)?;
+		// Fetch the existing definition
+		let before = self.existing(stk, ctx, opt, doc).await?;
+		// Format the definition which would be stored
+		let mut stmt = self.clone();
+		let kind = std::mem::take(stmt.kind_mut());
+		let after = match (&before, kind) {
+			(Some(before), DefineKind::IfNotExists) => before.clone(),
+			_ => crate::sql::statements::define::DefineStatement::from(stmt.redact()).to_sql(),
+		};
+		let changed = before.as_deref() != Some(after.as_str());
+		Ok(Value::from(map! {
+			"before" => before.map(Value::from).unwrap_or_default(),
+			"after" => Value::from(after),
+			"changed" => Value::Bool(changed),
+		}))
+	}
+
+	/// The kind of resource defined by this statement, and the level at which
+	/// it is defined
+	fn resource(&self) -> (ResourceKind, Base) {
+		match self {
+			Self::Namespace(_) => (ResourceKind::Namespace, Base::Root),
+			Self::Database(_) => (ResourceKind::Database, Base::Ns),
+			Self::Function(_) | Self::Query(_) => (ResourceKind::Function, Base::Db),
+			Self::Analyzer(_) => (ResourceKind::Analyzer, Base::Db),
+			Self::Param(_) => (ResourceKind::Parameter, Base::Db),
+			Self::Table(_) | Self::Policy(_) => (ResourceKind::Table, Base::Db),
+			Self::Event(_) => (ResourceKind::Event, Base::Db),
+			Self::Field(_) => (ResourceKind::Field, Base::Db),
+			Self::Index(_) => (ResourceKind::Index, Base::Db),
+			Self::User(v) => (ResourceKind::Actor, v.base),
+			Self::Model(_) => (ResourceKind::Model, Base::Db),
+			Self::Access(v) => (ResourceKind::Actor, v.base),
+			Self::Config(v) => {
+				let kind = v.inner.kind();
+				let base = kind.base().into();
+				(ResourceKind::Config(kind), base)
+			}
+			Self::Api(_) => (ResourceKind::Api, Base::Db),
+			Self::Bucket(_) => (ResourceKind::Bucket, Base::Db),
+			Self::Sequence(_) => (ResourceKind::Sequence, Base::Db),
+			Self::Module(_) => (ResourceKind::Module, Base::Db),
+			Self::Diff(v) => v.resource(),
+		}
+	}
+
+	/// Removes the secrets from the definition, as they are removed from the
+	/// definitions shown by `INFO`
+	fn redact(self) -> Self {
+		match self {
+			Self::User(mut v) => {
+				v.hash = REDACTED.to_owned();
+				Self::User(v)
+			}
+			Self::Access(v) => Self::Access(v.redact()),
+			Self::Diff(v) => Self::Diff(Box::new(v.redact())),
+			v => v,
+		}
+	}
+
+	/// How this statement treats an existing definition
+	fn kind_mut(&mut self) -> &mut DefineKind {
+		match self {
+			Self::Namespace(v) => &mut v.kind,
+			Self::Database(v) => &mut v.kind,
+			Self::Function(v) => &mut v.kind,
+			Self::Analyzer(v) => &mut v.kind,
+			Self::Param(v) => &mut v.kind,
+			Self::Query(v) => &mut v.kind,
+			Self::Table(v) => &mut v.kind,
+			Self::Event(v) => &mut v.kind,
+			Self::Policy(v) => &mut v.kind,
+			Self::Field(v) => &mut v.kind,
+			Self::Index(v) => &mut v.kind,
+			Self::User(v) => &mut v.kind,
+			Self::Model(v) => &mut v.kind,
+			Self::Access(v) => &mut v.kind,
+			Self::Config(v) => &mut v.kind,
+			Self::Api(v) => &mut v.kind,
+			Self::Bucket(v) => &mut v.kind,
+			Self::Sequence(v) => &mut v.kind,
+			Self::Module(v) => &mut v.kind,
+			Self::Diff(v) => v.kind_mut(),
+		}
+	}
+
+	/// Fetches the existing definition of the resource, formatted as
+	/// SurrealQL with its secrets redacted. Nothing is created while looking
+	/// it up, not even the namespace or database.
+	async fn existing(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Option<String>> {
+		let txn = ctx.tx();
+		// Resources at the root level
+		match self {
+			Self::Namespace(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "namespace name").await?;
+				return Ok(txn.get_ns_by_name(&name, None).await?.map(|x| x.to_sql()));
+			}
+			Self::User(v) if v.base == Base::Root => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "user name").await?;
+				return Ok(txn.get_root_user(&name, None).await?.map(|x| redacted_user(&x)));
+			}
+			Self::Access(v) if v.base == Base::Root => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "access name").await?;
+				return Ok(txn.get_root_access(&name, None).await?.map(|x| x.to_sql()));
+			}
+			Self::Config(v) if Base::from(v.inner.kind().base()) == Base::Root => {
+				return Ok(txn.get_root_config(v.inner.name()).await?.map(|x| x.to_sql()));
+			}
+			_ => {}
+		}
+		// Resources at the namespace level
+		let Some(ns) = txn.get_ns_by_name(opt.ns()?, None).await? else {
+			return Ok(None);
+		};
+		let ns = ns.namespace_id;
+		match self {
+			Self::Database(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "database name").await?;
+				return Ok(txn.get_db_by_name(opt.ns()?, &name, None).await?.map(|x| x.to_sql()));
+			}
+			Self::User(v) if v.base == Base::Ns => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "user name").await?;
+				return Ok(txn.get_ns_user(ns, &name, None).await?.map(|x| redacted_user(&x)));
+			}
+			Self::Access(v) if v.base == Base::Ns => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "access name").await?;
+				return Ok(txn.get_ns_access(ns, &name, None).await?.map(|x| x.to_sql()));
+			}
+			_ => {}
+		}
+		// Resources at the database level
+		let Some((ns, db)) = ctx.try_ns_db_ids(opt).await? else {
+			return Ok(None);
+		};
+		let sql = match self {
+			Self::Function(v) => {
+				let key = crate::key::database::fc::new(ns, db, &v.name);
+				txn.get(&key, None).await?.map(|x| x.to_sql())
+			}
+			Self::Analyzer(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "analyzer name").await?;
+				txn.get(&crate::key::database::az::new(ns, db, &name), None)
+					.await?
+					.map(|x| x.to_sql())
+			}
+			Self::Param(v) => {
+				let key = crate::key::database::pa::new(ns, db, &v.name);
+				txn.get(&key, None).await?.map(|x| x.to_sql())
+			}
+			Self::Query(v) => {
+				let key = crate::key::database::qy::new(ns, db, &v.name);
+				txn.get(&key, None).await?.map(|x| x.to_sql())
+			}
+			Self::Table(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "table name").await?;
+				txn.get_tb(ns, db, &TableName::new(name), None).await?.map(|x| x.to_sql())
+			}
+			Self::Event(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "event name").await?;
+				let tb = expr_to_ident(stk, ctx, opt, doc, &v.target_table, "target table").await?;
+				let tb = TableName::new(tb);
+				txn.get(&crate::key::table::ev::new(ns, db, &tb, &name), None)
+					.await?
+					.map(|x| x.to_sql())
+			}
+			Self::Policy(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "policy name").await?;
+				let tb = expr_to_ident(stk, ctx, opt, doc, &v.target_table, "target table").await?;
+				let tb = TableName::new(tb);
+				txn.get(&crate::key::table::po::new(ns, db, &tb, &name), None)
+					.await?
+					.map(|x| x.to_sql())
+			}
+			Self::Field(v) => {
+				let name = expr_to_idiom(stk, ctx, opt, doc, &v.name, "field name").await?;
+				let tb = expr_to_ident(stk, ctx, opt, doc, &v.what, "table name").await?;
+				let name = name.to_raw_string();
+				txn.get_tb_field(ns, db, &TableName::new(tb), &name, None)
+					.await?
+					.map(|x| x.to_sql())
+			}
+			Self::Index(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "index name").await?;
+				let tb = expr_to_ident(stk, ctx, opt, doc, &v.what, "index table").await?;
+				txn.get_tb_index(ns, db, &TableName::new(tb), &name, None)
+					.await?
+					.map(|x| x.to_sql())
+			}
+			Self::User(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "user name").await?;
+				txn.get_db_user(ns, db, &name, None).await?.map(|x| redacted_user(&x))
+			}
+			Self::Model(v) => {
+				txn.get_db_model(ns, db, &v.name, &v.version, None).await?.map(|x| x.to_sql())
+			}
+			Self::Access(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "access name").await?;
+				txn.get_db_access(ns, db, &name, None).await?.map(|x| x.to_sql())
+			}
+			Self::Config(v) => {
+				txn.get_db_config(ns, db, v.inner.name(), None).await?.map(|x| x.to_sql())
+			}
+			Self::Api(v) => {
+				let path = expr_to_ident(stk, ctx, opt, doc, &v.path, "api path").await?;
+				txn.get_db_api(ns, db, &path, None).await?.map(|x| x.to_sql())
+			}
+			Self::Bucket(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "bucket name").await?;
+				txn.get_db_bucket(ns, db, &name, None).await?.map(|x| x.to_sql())
+			}
+			Self::Sequence(v) => {
+				let name = expr_to_ident(stk, ctx, opt, doc, &v.name, "sequence name").await?;
+				txn.get(&Sq::new(ns, db, &name), None).await?.map(|x| x.to_sql())
+			}
+			Self::Module(v) => {
+				let name = ModuleName::try_from(v)?.get_storage_name();
+				txn.get(&crate::key::database::md::new(ns, db, &name), None)
+					.await?
+					.map(|x| x.to_sql())
+			}
+			Self::Namespace(_) | Self::Database(_) => {
+				bail!(Error::unreachable("handled above"))
+			}
+			Self::Diff(_) => bail!(Error::unreachable("a DIFF statement can not be nested")),
+		};
+		Ok(sql)
+	}
+}
+
+/// Formats a user definition with its password hash redacted
+fn redacted_user(user: &UserDefinition) -> String {
+	UserDefinition {
+		hash: REDACTED.to_owned(),
+		..user.clone()
+	}
+	.to_sql()
+}
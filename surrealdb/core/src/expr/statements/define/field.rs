@@ -17,12 +17,14 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::parameterize::{expr_to_ident, expr_to_idiom};
 use crate::expr::reference::Reference;
+use crate::expr::statements::refresh::mark_dependents_stale;
 use crate::expr::{
 	Base, Expr, FlowResultExt, Idiom, Kind, KindLiteral, Literal, Part, RecordIdKeyLit,
 };
 use crate::iam::{Action, AuthLimit, ResourceKind};
 use crate::idx::planner::ScanDirection;
 use crate::kvs::{NORMAL_BATCH_SIZE, Transaction};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::{TableName, Value};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
@@ -228,6 +230,18 @@ impl DefineFieldStatement {
 		// Process the statement
 		txn.put_tb_field(ns, db, &tb.name, &definition).await?;
 
+		// Notify the schema subscriptions
+		let action = if existing.is_some() {
+			SchemaAction::Alter
+		} else {
+			SchemaAction::Define
+		};
+		let change = SchemaChange::Field {
+			table: tb.name.as_str(),
+			name: &fd,
+		};
+		notify_schema_change(ctx, opt, action, change).await?;
+
 		// Overwriting an existing reference field can drop target tables it used
 		// to reference (the REFERENCE clause removed, or the record kind narrowed
 		// or changed); purge the now-stranded reference keys so the DELETE
@@ -266,6 +280,8 @@ impl DefineFieldStatement {
 						txn.put_tb(ns_name, db_name, &tb).await?;
 						// Clear the cache
 						txn.clear_cache();
+						// The views computed from the table are stale until refreshed
+						mark_dependents_stale(ctx, opt, &tb.name).await?;
 						// Ok all good
 						return Ok(Value::None);
 					}
@@ -293,6 +309,8 @@ impl DefineFieldStatement {
 						txn.put_tb(ns_name, db_name, &tb).await?;
 						// Clear the cache
 						txn.clear_cache();
+						// The views computed from the table are stale until refreshed
+						mark_dependents_stale(ctx, opt, &tb.name).await?;
 						// Ok all good
 						return Ok(Value::None);
 					}
@@ -307,6 +325,8 @@ impl DefineFieldStatement {
 
 		// Clear the cache
 		txn.clear_cache();
+		// The views computed from the table are stale until refreshed
+		mark_dependents_stale(ctx, opt, &tb.name).await?;
 		// Ok all good
 		Ok(Value::None)
 	}
@@ -5,7 +5,7 @@ use surrealdb_types::{SqlFormat, ToSql};
 
 use super::DefineKind;
 use crate::catalog::providers::{CatalogProvider, DatabaseProvider};
-use crate::catalog::{FunctionDefinition, Permission};
+use crate::catalog::{FunctionCapabilities, FunctionDefinition, Permission};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -25,6 +25,7 @@ pub(crate) struct DefineFunctionStatement {
 	pub returns: Option<Kind>,
 	pub graphql_alias: Option<String>,
 	pub graphql_deprecated: Option<String>,
+	pub capabilities: Option<FunctionCapabilities>,
 }
 
 impl DefineFunctionStatement {
@@ -49,6 +50,10 @@ impl DefineFunctionStatement {
 				name: format!("fn::{}", self.name),
 			});
 		}
+		// Functions can only be granted network targets allowed by the server
+		if let Some(caps) = &self.capabilities {
+			caps.net_targets(&ctx.get_capabilities())?;
+		}
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
@@ -92,6 +97,7 @@ impl DefineFunctionStatement {
 				auth_limit: AuthLimit::new_from_auth(&opt.auth).into(),
 				graphql_alias: self.graphql_alias.clone(),
 				graphql_deprecated: self.graphql_deprecated.clone(),
+				capabilities: self.capabilities.clone(),
 			},
 		)
 		.await?;
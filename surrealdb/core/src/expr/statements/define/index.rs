@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use anyhow::{Result, bail};
+use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
 use uuid::Uuid;
@@ -15,8 +15,10 @@ use crate::err::Error;
 use crate::expr::parameterize::{expr_to_ident, exprs_to_fields};
 use crate::expr::{Base, Expr, FlowResultExt, Idiom, Literal, Part};
 use crate::iam::{Action, ResourceKind};
+use crate::idx::conflicts::ConflictReport;
 use crate::kvs::Transaction;
 use crate::kvs::index::{IndexBuilder, retire_durable_index};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::{TableName, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -28,6 +30,20 @@ pub(crate) struct DefineIndexStatement {
 	pub index: Index,
 	pub comment: Expr,
 	pub concurrently: bool,
+	pub conflicts: UniqueConflicts,
+}
+
+/// How a `UNIQUE` index handles records which already conflict when defined
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) enum UniqueConflicts {
+	/// The index fails to build on the first conflicting record
+	#[default]
+	Fail,
+	/// The conflicting records are reported, and the index is not defined
+	Validate,
+	/// The conflicting records are moved into the given table, a batch at a
+	/// time in transactions of their own, before the index is defined
+	Quarantine(Expr),
 }
 
 impl Default for DefineIndexStatement {
@@ -40,6 +56,7 @@ impl Default for DefineIndexStatement {
 			index: Index::Idx,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
 		}
 	}
 }
@@ -143,6 +160,31 @@ impl DefineIndexStatement {
 			.catch_return()?
 			.cast_to()?;
 
+		// Scan for the records which conflict with a UNIQUE index. When
+		// quarantining, they are moved aside in committed transactions of
+		// their own, so that the index builder no longer encounters them, and
+		// running the statement again after a failed build moves any which
+		// remain
+		let report = match &self.conflicts {
+			UniqueConflicts::Fail => None,
+			UniqueConflicts::Validate => {
+				let report = ConflictReport::scan(stk, ctx, opt, &tb, &cols).await?;
+				return Ok(report.into_value(false));
+			}
+			UniqueConflicts::Quarantine(into) => {
+				let into = TableName::new(
+					expr_to_ident(stk, ctx, opt, doc, into, "quarantine table").await?,
+				);
+				ensure!(
+					into != table_name,
+					Error::IndexQuarantineTable {
+						index: name
+					}
+				);
+				Some(ConflictReport::quarantine(stk, ctx, opt, &tb, &cols, &into).await?)
+			}
+		};
+
 		if let Some(ix) = existing.as_ref()
 			&& self.kind == DefineKind::Default
 			&& opt.import
@@ -213,6 +255,18 @@ impl DefineIndexStatement {
 		};
 		txn.put_tb_index(tb.namespace_id, tb.database_id, &tb.name, &index_def).await?;
 
+		// Notify the schema subscriptions
+		let action = if existing.is_some() {
+			SchemaAction::Alter
+		} else {
+			SchemaAction::Define
+		};
+		let change = SchemaChange::Index {
+			table: tb.name.as_str(),
+			name: &name,
+		};
+		notify_schema_change(ctx, opt, action, change).await?;
+
 		refresh_table_index_cache(ctx, &txn, ns, db, &tb).await?;
 		let index_builder =
 			ctx.get_index_builder().ok_or_else(|| Error::unreachable("No Index Builder"))?;
@@ -237,7 +291,7 @@ impl DefineIndexStatement {
 		.await?;
 
 		// Ok all good
-		Ok(Value::None)
+		Ok(report.map(|r| r.into_value(true)).unwrap_or_default())
 	}
 }
 
@@ -4,6 +4,7 @@ mod api;
 mod bucket;
 pub mod config;
 mod database;
+mod diff;
 mod event;
 mod field;
 mod function;
@@ -12,6 +13,8 @@ mod model;
 mod module;
 mod namespace;
 mod param;
+mod policy;
+mod query;
 mod sequence;
 mod table;
 mod user;
@@ -29,12 +32,14 @@ pub(crate) use field::{
 	validate_id_field_restrictions,
 };
 pub(crate) use function::DefineFunctionStatement;
-pub(crate) use index::DefineIndexStatement;
 pub(in crate::expr::statements) use index::run_indexing;
+pub(crate) use index::{DefineIndexStatement, UniqueConflicts};
 pub(crate) use model::DefineModelStatement;
 pub(crate) use module::DefineModuleStatement;
 pub(crate) use namespace::DefineNamespaceStatement;
 pub(crate) use param::DefineParamStatement;
+pub(crate) use policy::DefinePolicyStatement;
+pub(crate) use query::DefineQueryStatement;
 use reblessive::tree::Stk;
 pub(crate) use sequence::DefineSequenceStatement;
 pub(crate) use table::DefineTableStatement;
@@ -85,8 +90,10 @@ pub(crate) enum DefineStatement {
 	Function(DefineFunctionStatement),
 	Analyzer(DefineAnalyzerStatement),
 	Param(DefineParamStatement),
+	Query(DefineQueryStatement),
 	Table(DefineTableStatement),
 	Event(DefineEventStatement),
+	Policy(DefinePolicyStatement),
 	Field(DefineFieldStatement),
 	Index(DefineIndexStatement),
 	User(DefineUserStatement),
@@ -97,6 +104,8 @@ pub(crate) enum DefineStatement {
 	Bucket(DefineBucketStatement),
 	Sequence(DefineSequenceStatement),
 	Module(DefineModuleStatement),
+	/// A definition which is previewed rather than applied
+	Diff(Box<DefineStatement>),
 }
 
 impl DefineStatement {
@@ -114,8 +123,10 @@ impl DefineStatement {
 			Self::Database(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Function(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Param(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Query(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Table(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Event(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Policy(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Field(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Index(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Analyzer(v) => v.compute(stk, ctx, opt, doc).await,
@@ -127,6 +138,7 @@ impl DefineStatement {
 			Self::Bucket(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Sequence(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Module(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Diff(v) => v.diff(stk, ctx, opt, doc).await,
 		}
 	}
 }
@@ -11,6 +11,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::{Base, Expr, FlowResultExt as _};
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::Value;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -50,7 +51,8 @@ impl DefineParamStatement {
 
 		// Check if the definition exists
 		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
-		if txn.get_db_param(ns, db, &self.name, None).await.is_ok() {
+		let exists = txn.get_db_param(ns, db, &self.name, None).await.is_ok();
+		if exists {
 			match self.kind {
 				DefineKind::Default => {
 					if !opt.import {
@@ -75,19 +77,26 @@ impl DefineParamStatement {
 			.catch_return()?
 			.cast_to()?;
 		// Process the statement
-		txn.put_db_param(
-			db.namespace_id,
-			db.database_id,
-			&ParamDefinition {
-				value,
-				name: self.name.clone(),
-				comment,
-				permissions: self.permissions.clone(),
-			},
-		)
-		.await?;
+		let definition = ParamDefinition {
+			value,
+			name: self.name.clone(),
+			comment,
+			permissions: self.permissions.clone(),
+		};
+		txn.put_db_param(db.namespace_id, db.database_id, &definition).await?;
 		// Clear the cache
 		txn.clear_cache();
+		// Notify the schema subscriptions
+		let action = if exists {
+			SchemaAction::Alter
+		} else {
+			SchemaAction::Define
+		};
+		let change = SchemaChange::Param {
+			name: definition.name.as_str(),
+			value: Some(&definition.value),
+		};
+		notify_schema_change(ctx, opt, action, change).await?;
 		// Ok all good
 		Ok(Value::None)
 	}
@@ -0,0 +1,125 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use super::DefineKind;
+use crate::catalog::PolicyDefinition;
+use crate::catalog::providers::TableProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{TableName, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefinePolicyStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub when: Expr,
+	pub destination: Expr,
+	pub batch: Expr,
+	pub comment: Expr,
+}
+
+impl DefinePolicyStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "DefinePolicyStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "policy name").await?;
+		let target_table = TableName::new(
+			expr_to_ident(stk, ctx, opt, doc, &self.target_table, "target table").await?,
+		);
+		let destination = TableName::new(
+			expr_to_ident(stk, ctx, opt, doc, &self.destination, "destination table").await?,
+		);
+
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Table, Base::Db)?;
+		// Records can not be moved into the table they are moved from
+		if target_table == destination {
+			bail!(Error::Query {
+				message: format!(
+					"The policy '{name}' can not move records from '{target_table}' into the same table"
+				),
+			});
+		}
+		// Get the NS and DB
+		let (ns_name, db_name) = opt.ns_db()?;
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		if txn.get_tb_policy(ns, db, &target_table, &name).await.is_ok() {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::PoAlreadyExists {
+							name: name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => return Ok(Value::None),
+			}
+		}
+
+		// Ensure both tables exist
+		txn.get_or_add_tb(Some(ctx), ns_name, db_name, &target_table, None).await?;
+		txn.get_or_add_tb(Some(ctx), ns_name, db_name, &destination, None).await?;
+
+		let batch = match &self.batch {
+			Expr::Literal(crate::expr::Literal::None) => PolicyDefinition::DEFAULT_BATCH,
+			batch => {
+				let batch = stk
+					.run(|stk| batch.compute(stk, ctx, opt, doc))
+					.await
+					.catch_return()?
+					.cast_to::<i64>()?;
+				match u32::try_from(batch) {
+					Ok(batch) if batch > 0 => batch,
+					_ => bail!(Error::Query {
+						message: format!(
+							"`{batch}` is not a valid batch size for a policy definition. A batch size must be within 1..={}",
+							u32::MAX
+						),
+					}),
+				}
+			}
+		};
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		// Process the statement
+		let key = crate::key::table::po::new(ns, db, &target_table, &name);
+		txn.set(
+			&key,
+			&PolicyDefinition {
+				name: name.clone().into(),
+				target_table: target_table.clone(),
+				when: self.when.clone(),
+				destination,
+				batch,
+				comment,
+			},
+		)
+		.await?;
+		// Reset the progress of any previous definition
+		let key = crate::key::table::pp::new(ns, db, &target_table, &name);
+		txn.del(&key).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
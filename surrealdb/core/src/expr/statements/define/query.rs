@@ -0,0 +1,104 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+use surrealdb_strand::Strand;
+use surrealdb_types::{SqlFormat, ToSql};
+use uuid::Uuid;
+
+use super::DefineKind;
+use crate::catalog::providers::CatalogProvider;
+use crate::catalog::{Permission, QueryDefinition};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::{Base, Expr, FlowResultExt, Kind};
+use crate::iam::{Action, AuthLimit, ResourceKind};
+use crate::val::Value;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineQueryStatement {
+	pub kind: DefineKind,
+	pub name: Strand,
+	pub args: Vec<(String, Kind)>,
+	pub query: Expr,
+	pub comment: Expr,
+	pub permissions: Permission,
+}
+
+impl DefineQueryStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "DefineQueryStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Function, Base::Db)?;
+		// A PERMISSIONS clause must not perform writes (GHSA-66r2-5gwj-gxm2).
+		if self.permissions.has_direct_write() {
+			bail!(Error::PermissionClauseNotReadonly {
+				kind: "query",
+				name: self.name.to_string(),
+			});
+		}
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		let previous = txn.get_db_query(ns, db, &self.name).await.ok();
+		if previous.is_some() {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::QyAlreadyExists {
+							name: self.name.to_string(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => return Ok(Value::None),
+			}
+		}
+
+		// Process the statement
+		let (ns_name, db_name) = opt.ns_db()?;
+		txn.get_or_add_db(Some(ctx), ns_name, db_name).await?;
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		// The query is stored parsed, so that it is never parsed again when run
+		let key = crate::key::database::qy::new(ns, db, &self.name);
+		txn.set(
+			&key,
+			&QueryDefinition {
+				name: self.name.clone(),
+				args: self.args.clone(),
+				query: self.query.clone(),
+				comment,
+				permissions: self.permissions.clone(),
+				auth_limit: AuthLimit::new_from_auth(&opt.auth).into(),
+				version: previous.map(|v| v.version + 1).unwrap_or(1),
+				plan_id: Uuid::now_v7(),
+			},
+		)
+		.await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl ToSql for DefineQueryStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::define::DefineQueryStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
@@ -13,23 +13,26 @@ use crate::catalog::aggregation::{
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
 use crate::catalog::{
 	DatabaseId, FieldDefinition, Metadata, NamespaceId, Permissions, Record, RecordType,
-	TableDefinition, TableType, ViewDefinition,
+	TableDefinition, TableType, ViewDefinition, ViewGraph, ViewState,
 };
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
-use crate::doc::{self, CursorDoc, Document, DocumentContext, NsDbCtx};
+use crate::doc::{self, CursorDoc, Document, DocumentContext, NsDbCtx, ViewExtrema};
 use crate::err::Error;
 use crate::expr::changefeed::ChangeFeed;
 use crate::expr::field::Selector;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::paths::{ID, IN, OUT};
+use crate::expr::statements::refresh::{mark_dependents_stale, refresh_dependents};
 use crate::expr::{
-	Base, BinaryOperator, Cond, Expr, Field, Fields, FlowResultExt, Function, FunctionCall, Group,
-	Groups, Idiom, Kind, Literal, SelectStatement, View,
+	Base, BinaryOperator, Cond, Eviction, Expr, Field, Fields, FlowResultExt, Function,
+	FunctionCall, Group, Groups, Idiom, Kind, Literal, SelectStatement, SoftDelete, Throttle,
+	ValidTime, View,
 };
 use crate::iam::{Action, ResourceKind};
 use crate::key;
-use crate::kvs::Transaction;
+use crate::kvs::{SessionTable, SessionTables, Transaction};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::{Array, Number, RecordId, RecordIdKey, TableName, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -42,6 +45,12 @@ pub(crate) struct DefineTableStatement {
 	pub view: Option<View>,
 	pub permissions: Permissions,
 	pub changefeed: Option<ChangeFeed>,
+	pub throttle: Option<Throttle>,
+	pub lineage: bool,
+	pub soft_delete: Option<SoftDelete>,
+	pub eviction: Option<Eviction>,
+	pub session: bool,
+	pub valid_time: Option<ValidTime>,
 	pub comment: Expr,
 	pub table_type: TableType,
 	pub graphql_alias: Option<String>,
@@ -59,6 +68,12 @@ impl Default for DefineTableStatement {
 			view: None,
 			permissions: Permissions::default(),
 			changefeed: None,
+			throttle: None,
+			lineage: false,
+			soft_delete: None,
+			eviction: None,
+			session: false,
+			valid_time: None,
 			comment: Expr::Literal(Literal::None),
 			table_type: TableType::default(),
 			graphql_alias: None,
@@ -84,9 +99,26 @@ impl DefineTableStatement {
 		super::validate_graphql_alias(&self.graphql_alias, "table")?;
 
 		// Process the name
-		let name =
+		let mut name =
 			TableName::new(expr_to_ident(stk, ctx, opt, doc, &self.name, "table name").await?);
 
+		// A session table is stored under a name scoped to its session
+		let session = SessionTables::session_id(ctx);
+		if self.session {
+			let Some(session) = session else {
+				bail!(Error::TbSessionRequired {
+					name: name.as_str().to_string(),
+				});
+			};
+			// A view is updated by the writes of every session
+			if self.view.is_some() {
+				bail!(Error::TbSessionView {
+					name: name.as_str().to_string(),
+				});
+			}
+			name = SessionTables::scoped_name(session, &name);
+		}
+
 		// A PERMISSIONS clause must not perform writes (GHSA-66r2-5gwj-gxm2).
 		if self.permissions.has_direct_write() {
 			bail!(Error::PermissionClauseNotReadonly {
@@ -105,24 +137,24 @@ impl DefineTableStatement {
 		let db = txn.expect_db_by_name(ns_name, db_name).await?;
 
 		// Check if the definition exists
-		let table_id =
-			if let Some(tb) = txn.get_tb(ns.namespace_id, db.database_id, &name, None).await? {
-				match self.kind {
-					DefineKind::Default => {
-						if !opt.import {
-							bail!(Error::TbAlreadyExists {
-								name: name.as_str().to_string(),
-							});
-						}
+		let existing = txn.get_tb(ns.namespace_id, db.database_id, &name, None).await?;
+		let table_id = if let Some(tb) = &existing {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::TbAlreadyExists {
+							name: name.as_str().to_string(),
+						});
 					}
-					DefineKind::Overwrite => {}
-					DefineKind::IfNotExists => return Ok(Value::None),
 				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => return Ok(Value::None),
+			}
 
-				tb.table_id
-			} else {
-				txn.get_next_tb_id(Some(ctx), ns.namespace_id, db.database_id).await?
-			};
+			tb.table_id
+		} else {
+			txn.get_next_tb_id(Some(ctx), ns.namespace_id, db.database_id).await?
+		};
 
 		let comment = stk
 			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
@@ -144,6 +176,19 @@ impl DefineTableStatement {
 			permissions: self.permissions.clone(),
 			comment,
 			changefeed: self.changefeed,
+			throttle: self.throttle,
+			lineage: self.lineage,
+			soft_delete: self.soft_delete.clone(),
+			eviction: self.eviction,
+			session: match self.session {
+				true => session,
+				false => existing.as_ref().and_then(|tb| tb.session),
+			},
+			valid_time: self.valid_time.clone(),
+			session_node: match self.session {
+				true => Some(ctx.node_id()),
+				false => existing.as_ref().and_then(|tb| tb.session_node),
+			},
 
 			cache_fields_ts: cache_ts,
 			cache_events_ts: cache_ts,
@@ -153,6 +198,17 @@ impl DefineTableStatement {
 			graphql_deprecated: self.graphql_deprecated.clone(),
 		};
 
+		// A view can not be computed from itself, even through other views
+		if let Some(view) = &tb_def.view {
+			let graph = ViewGraph::new(&txn.all_tb(ns.namespace_id, db.database_id, None).await?);
+			if let Some(table) = graph.cycle(&name, view.tables()) {
+				bail!(Error::ViewCycle {
+					view: name.to_string(),
+					table: table.to_string(),
+				});
+			}
+		}
+
 		// Add table relational fields
 		Self::add_in_out_fields(&txn, ns.namespace_id, db.database_id, &mut tb_def).await?;
 
@@ -164,6 +220,34 @@ impl DefineTableStatement {
 		// Update the catalog
 		let tb = txn.put_tb(ns_name, db_name, &tb_def).await?;
 
+		// Resolve the references to a session table in its session
+		if let (true, Some(session), Some(tables)) =
+			(self.session, session, ctx.get_session_tables())
+		{
+			tables.register(
+				session,
+				SessionTable {
+					ns: ns_name.to_string(),
+					db: db_name.to_string(),
+					name: SessionTables::unscoped_name(session, &name),
+				},
+			);
+		}
+
+		// Record when the records of the table were written once it has a time to live
+		let ttl = |tb: Option<&TableDefinition>| {
+			tb.and_then(|tb| tb.eviction).is_some_and(|e| e.ttl.is_some())
+		};
+		crate::kvs::evict::define_ttl(
+			&txn,
+			ns.namespace_id,
+			db.database_id,
+			&name,
+			ttl(existing.as_deref()),
+			ttl(Some(&tb_def)),
+		)
+		.await?;
+
 		// Clear the cache
 		txn.clear_cache();
 
@@ -174,11 +258,51 @@ impl DefineTableStatement {
 		let doc_ctx =
 			DocumentContext::initialise(ctx, &parent, tb, &name, opt.version, true).await?;
 
+		// Forget the tables which an overwritten view is no longer computed from
+		if let Some(old) = existing.as_ref().and_then(|tb| tb.view.as_ref()) {
+			let tables = tb_def.view.as_ref().map(|v| v.tables()).unwrap_or_default();
+			for ft in old.tables().iter().filter(|ft| !tables.contains(ft)) {
+				let key = crate::key::table::ft::new(ns.namespace_id, db.database_id, ft, &name);
+				txn.del(&key).await?;
+				// Refresh the table cache
+				if let Some(foreign_tb) =
+					txn.get_tb(ns.namespace_id, db.database_id, ft, None).await?
+				{
+					txn.put_tb(
+						ns_name,
+						db_name,
+						&TableDefinition {
+							cache_tables_ts: Uuid::now_v7(),
+							..foreign_tb.as_ref().clone()
+						},
+					)
+					.await?;
+				}
+				// Clear the cache
+				txn.clear_cache();
+			}
+		}
+
 		// Check if table is a view
 		if let Some(view) = &tb_def.view {
+			// The views computed from this view are kept alongside its data
+			let dependents = txn.all_tb_views(ns.namespace_id, db.database_id, &name, None).await?;
 			// Remove the table data
 			let key = crate::key::table::all::new(ns.namespace_id, db.database_id, &name);
 			txn.delp(&key).await?;
+			txn.table_writes().write(ns.namespace_id, db.database_id, &name);
+			// The statistics were removed with the data, and are counted again
+			// as the view is populated
+			txn.table_stats().forget(ns.namespace_id, db.database_id, &name);
+			for dependent in dependents.iter() {
+				let key = crate::key::table::ft::new(
+					ns.namespace_id,
+					db.database_id,
+					&name,
+					&dependent.name,
+				);
+				txn.set(&key, dependent).await?;
+			}
 
 			let (ViewDefinition::Materialized {
 				tables,
@@ -225,11 +349,29 @@ impl DefineTableStatement {
 		}
 		// Clear the cache
 		txn.clear_cache();
+		// The views computed from an overwritten view no longer follow its
+		// records, so they are rebuilt in order. The records of any other
+		// table are left as they are, so its views are only stale.
+		if existing.is_some() {
+			if tb_def.view.is_some() {
+				refresh_dependents(stk, ctx, opt, &name).await?;
+			} else {
+				mark_dependents_stale(ctx, opt, &name).await?;
+			}
+		}
+		// Notify the schema subscriptions
+		let action = if existing.is_some() {
+			SchemaAction::Alter
+		} else {
+			SchemaAction::Define
+		};
+		notify_schema_change(ctx, opt, action, SchemaChange::Table(name.as_str())).await?;
 		// Ok all good
 		Ok(Value::None)
 	}
 
-	async fn initialize_view(
+	/// Computes the records of a view in full from its source tables
+	pub(crate) async fn initialize_view(
 		stk: &mut Stk,
 		ctx: &FrozenContext,
 		opt: &Options,
@@ -240,7 +382,7 @@ impl DefineTableStatement {
 		match view {
 			ViewDefinition::Select {
 				..
-			} => {}
+			} => return Ok(()),
 			ViewDefinition::Materialized {
 				fields,
 				tables,
@@ -275,8 +417,92 @@ impl DefineTableStatement {
 					tables,
 				)
 				.await?;
+				Self::initialize_view_extrema(
+					stk,
+					ctx,
+					opt,
+					view_table_name,
+					analysis,
+					condition.as_ref(),
+					tables,
+				)
+				.await?;
 			}
 		}
+		// Record when the view was computed
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		let key = key::table::vs::new(ns, db, view_table_name);
+		ctx.tx().set(&key, &ViewState::refreshed()).await?;
+		Ok(())
+	}
+
+	/// Counts the values behind the minimums and maximums of an aggregated
+	/// view, so that they can be found again once the records holding them
+	/// change
+	async fn initialize_view_extrema(
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		view_table_name: &TableName,
+		analysis: &AggregationAnalysis,
+		condition: Option<&Expr>,
+		tables: &[TableName],
+	) -> Result<()> {
+		if analysis.extremum_arguments().is_empty() {
+			return Ok(());
+		}
+
+		let row = Expr::Literal(Literal::Array(vec![
+			Expr::Literal(Literal::Array(analysis.group_expressions.clone())),
+			Expr::Literal(Literal::Array(analysis.aggregate_arguments.clone())),
+		]));
+		let stmt = SelectStatement {
+			// SELECT VALUE [[group_expr1, ..], [aggregate_arg1, ..]]
+			fields: Fields::Value(Box::new(Selector {
+				expr: row,
+				alias: None,
+			})),
+			what: tables.iter().map(|x| Expr::Table(x.clone())).collect(),
+			// WHERE cond
+			cond: condition.cloned().map(Cond),
+			omit: vec![],
+			only: false,
+			with: None,
+			split: None,
+			group: None,
+			order: None,
+			limit: None,
+			start: None,
+			fetch: None,
+			version: Expr::Literal(Literal::None),
+			timeout: Expr::Literal(Literal::None),
+			partial: false,
+			explain: None,
+			tempfiles: false,
+			valid_time: None,
+			scan_limit: None,
+		};
+		let Value::Array(Array(rows)) = stmt.compute(stk, ctx, opt, None).await? else {
+			fail!("initial select for view did not return an array");
+		};
+
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		let tx = ctx.tx();
+
+		for row in rows {
+			let Value::Array(Array(mut row)) = row else {
+				fail!("initial select for view did not return an array of arrays");
+			};
+			let (Some(Value::Array(Array(args))), Some(Value::Array(Array(group)))) =
+				(row.pop(), row.pop())
+			else {
+				fail!("initial select for view did not return the group and arguments");
+			};
+			ViewExtrema::new(ns, db, view_table_name, &group, analysis).add(&tx, &args).await?;
+
+			yield_now!();
+		}
+
 		Ok(())
 	}
 
@@ -321,8 +547,11 @@ impl DefineTableStatement {
 			fetch: None,
 			version: Expr::Literal(Literal::None),
 			timeout: Expr::Literal(Literal::None),
+			partial: false,
 			explain: None,
 			tempfiles: false,
+			valid_time: None,
+			scan_limit: None,
 		};
 
 		let Value::Array(Array(v)) = select.compute(stk, ctx, opt, None).await? else {
@@ -341,9 +570,10 @@ impl DefineTableStatement {
 				fail!("select results did not contain a record id");
 			};
 
-			let key = key::record::new(ns, db, view_table_name, &id.key);
+			// Write through the record path, so that the record is checksummed,
+			// counted and tracked like any other record of the view
 			let record = Arc::new(Record::new(Value::Object(o)));
-			tx.put(&key, &record).await?;
+			tx.put_record(ns, db, view_table_name, &id.key, Arc::clone(&record)).await?;
 
 			let ns = doc_ctx.ns();
 			let db = doc_ctx.db();
@@ -568,8 +798,11 @@ impl DefineTableStatement {
 			fetch: None,
 			version: Expr::Literal(Literal::None),
 			timeout: Expr::Literal(Literal::None),
+			partial: false,
 			explain: None,
 			tempfiles: false,
+			valid_time: None,
+			scan_limit: None,
 		};
 		let res = stmt.compute(stk, ctx, opt, None).await?;
 		let Value::Array(res) = res else {
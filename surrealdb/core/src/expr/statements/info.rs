@@ -8,6 +8,7 @@ use crate::catalog::providers::{
 	ApiProvider, AuthorisationProvider, BucketProvider, DatabaseProvider, NamespaceProvider,
 	NodeProvider, RootProvider, TableProvider, UserProvider,
 };
+use crate::catalog::{TableDefinition, ViewGraph};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -16,6 +17,7 @@ use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, FlowResultExt};
 use crate::iam::{Action, ResourceKind};
 use crate::kvs::index::index_building_info;
+use crate::kvs::{Transaction, scrub};
 use crate::sys::INFORMATION;
 use crate::val::{Datetime, Object, TableName, Value};
 
@@ -33,6 +35,10 @@ pub(crate) enum InfoStatement {
 	User(Expr, Option<Base>, bool),
 	/// Index information
 	Index(Expr, Expr, bool),
+	/// Index usage statistics for a table
+	IndexStats(Expr),
+	/// The client-managed transactions waiting for a key lock
+	Locks,
 }
 
 impl InfoStatement {
@@ -66,6 +72,7 @@ impl InfoStatement {
 				if *structured {
 					let object = map! {
 						"accesses" => process(&txn.all_root_accesses(version).await?),
+						"corrupt_records" => corrupt_records(&txn).await?,
 						"defaults" => txn.get_default_config().await?
 							.map(|x| x.as_ref().clone().structure())
 							.unwrap_or_else(|| Value::Object(Default::default())),
@@ -85,6 +92,7 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"corrupt_records" => corrupt_records(&txn).await?,
 						"defaults" => txn.get_default_config().await?
 							.map(|x| x.as_ref().clone().structure())
 							.unwrap_or_else(|| Value::Object(Default::default())),
@@ -188,6 +196,12 @@ impl InfoStatement {
 					),
 					_ => None,
 				};
+				// Summarise the segments of the changefeed entries
+				let changefeed = crate::cf::segment::stats(&txn, ns, db).await?;
+				// Query templates are not versioned
+				let queries = txn.all_db_queries(ns, db).await?;
+				// The dependencies of the views, in the order they are rebuilt
+				let views = ViewGraph::new(&txn.all_tb(ns, db, version).await?);
 				// Create the result set
 				let res = if *structured {
 					let object = map! {
@@ -199,10 +213,15 @@ impl InfoStatement {
 						"modules" => process_modules(ctx, ns, db, txn.all_db_modules(ns, db, version).await?).await,
 						"models" => process(&txn.all_db_models(ns, db, version).await?),
 						"params" => process(&txn.all_db_params(ns, db, version).await?),
-						"tables" => process(&txn.all_tb(ns, db, version).await?),
+						"queries", if !queries.is_empty() => {
+							process(&queries)
+						},
+						"tables" => process(&shared_tables(txn.all_tb(ns, db, version).await?)),
 						"users" => process(&txn.all_db_users(ns, db, version).await?),
 						"configs" => process(&txn.all_db_configs(ns, db, version).await?),
 						"sequences" => process(&txn.all_db_sequences(ns, db, version).await?),
+						"views", if !views.is_empty() => views.structure(),
+						"changefeed", if !changefeed.is_empty() => changefeed.into(),
 					};
 					Value::Object(Object::from(object))
 				} else {
@@ -263,9 +282,16 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"queries", if !queries.is_empty() => {
+							let mut out = Object::default();
+							for v in queries.iter() {
+								out.insert(v.name.clone(), v.to_sql().into());
+							}
+							out.into()
+						},
 						"tables" => {
 							let mut out = Object::default();
-							for v in txn.all_tb(ns, db, version).await?.iter() {
+							for v in shared_tables(txn.all_tb(ns, db, version).await?).iter() {
 								out.insert(v.name.clone(), v.to_sql().into());
 							}
 							out.into()
@@ -291,6 +317,7 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"changefeed", if !changefeed.is_empty() => changefeed.into(),
 					};
 					Value::Object(Object::from(object))
 				};
@@ -316,14 +343,39 @@ impl InfoStatement {
 					),
 					_ => None,
 				};
+				// Archival policies are not versioned
+				let policies = txn.all_tb_policies(ns, db, &tb).await?;
+				// Temporary indexes only exist in memory
+				let temporary = txn.table_writes().indexes().describe(ns, db, &tb);
+				// When a materialized view was last computed in full
+				let view = txn.get(&crate::key::table::vs::new(ns, db, &tb), None).await?;
+				// The views computed from the table, in the order they are rebuilt
+				let dependents =
+					ViewGraph::new(&txn.all_tb(ns, db, version).await?).dependents(&tb);
 				// Create the result set
 				Ok(if *structured {
 					Value::from(map! {
+						"dependents", if !dependents.is_empty() => {
+							Value::Array(dependents.iter().map(|v| Value::from(v.as_str())).collect())
+						},
 						"events" => process(&txn.all_tb_events(ns, db, &tb, version).await?),
 						"fields" => process(&txn.all_tb_fields(ns, db, &tb, version).await?),
 						"indexes" => process(&txn.all_tb_indexes(ns, db, &tb, version).await?),
 						"lives" => process(&txn.all_tb_lives(ns, db, &tb, version).await?),
+						"policies", if !policies.is_empty() => {
+							Value::Array(policies.into_iter().map(InfoStructure::structure).collect())
+						},
 						"tables" => process(&txn.all_tb_views(ns, db, &tb, version).await?),
+						"temporary_indexes", if !temporary.is_empty() => {
+							let structure = |(field, mut v): (String, Value)| {
+								if let Value::Object(obj) = &mut v {
+									obj.insert("field", Value::from(field.as_str()));
+								}
+								v
+							};
+							Value::Array(temporary.into_iter().map(structure).collect())
+						},
+						"view", if let Some(v) = view => v.structure(),
 					})
 				} else {
 					Value::from(map! {
@@ -355,6 +407,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"policies", if !policies.is_empty() => {
+							let mut out = Object::default();
+							for v in policies.iter() {
+								out.insert(v.name.clone(), v.to_sql().into());
+							}
+							out.into()
+						},
 						"tables" => {
 							let mut out = Object::default();
 							for v in txn.all_tb_views(ns, db, &tb, version).await?.iter() {
@@ -362,6 +421,14 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"temporary_indexes", if !temporary.is_empty() => {
+							let mut out = Object::default();
+							for (field, v) in temporary {
+								out.insert(field, v);
+							}
+							out.into()
+						},
+						"view", if let Some(v) = view => v.structure(),
 					})
 				})
 			}
@@ -431,6 +498,24 @@ impl InfoStatement {
 				let ix = txn.expect_tb_index(ns, db, &table, &index).await?;
 				index_building_info(&txn, ns, db, &ix).await
 			}
+			InfoStatement::IndexStats(tb) => {
+				// Allowed to run?
+				ctx.is_allowed(opt, Action::View, ResourceKind::Any, Base::Db)?;
+				// Get the NS and DB
+				let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+				// Compute table name
+				let tb = TableName::new(expr_to_ident(stk, ctx, opt, doc, tb, "table name").await?);
+				// Get the transaction
+				let txn = ctx.tx();
+				// Collect the usage recorded for every index on the table
+				ctx.get_index_stores().usage().table_info(&txn, ns, db, &tb).await
+			}
+			InfoStatement::Locks => {
+				// Allowed to run?
+				ctx.is_allowed(opt, Action::View, ResourceKind::Any, Base::Root)?;
+				// List the current waits for key locks
+				Ok(ctx.tx().lock_waits())
+			}
 		}
 	}
 }
@@ -438,6 +523,15 @@ pub(crate) trait InfoStructure {
 	fn structure(self) -> Value;
 }
 
+/// Leaves out the session tables, which are private to the session which
+/// defined them
+fn shared_tables(tables: Arc<[TableDefinition]>) -> Arc<[TableDefinition]> {
+	if tables.iter().all(|tb| tb.session.is_none()) {
+		return tables;
+	}
+	tables.iter().filter(|tb| tb.session.is_none()).cloned().collect()
+}
+
 fn process<T>(a: &Arc<[T]>) -> Value
 where
 	T: InfoStructure + Clone,
@@ -445,6 +539,13 @@ where
 	Value::Array(a.iter().cloned().map(InfoStructure::structure).collect())
 }
 
+/// The records which the background scrubber has found not to match their
+/// checksums
+async fn corrupt_records(txn: &Transaction) -> Result<Value> {
+	let records = scrub::corrupt_records(txn).await?;
+	Ok(Value::Array(records.into_iter().map(InfoStructure::structure).collect()))
+}
+
 async fn system() -> Value {
 	let info = INFORMATION.lock().await;
 	Value::from(map! {
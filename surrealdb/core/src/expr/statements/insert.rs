@@ -25,6 +25,12 @@ pub(crate) struct InsertStatement {
 	pub output: Option<Output>,
 	pub timeout: Expr,
 	pub relation: bool,
+	/// Does the statement have the `NON ATOMIC` clause.
+	///
+	/// A statement with this clause which runs in its own transaction is
+	/// split into several transactions by the executor, when its data is too
+	/// large for a single transaction of the storage engine.
+	pub non_atomic: bool,
 }
 
 impl InsertStatement {
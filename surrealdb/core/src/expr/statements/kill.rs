@@ -63,8 +63,13 @@ impl KillStatement {
 				// kill live queries they themselves created.
 				if ctx.auth_enabled() && !opt.auth.is_root() {
 					let table_key = crate::key::table::lq::new(live.ns, live.db, &live.tb, lid);
-					let subscription: Option<SubscriptionDefinition> =
+					let mut subscription: Option<SubscriptionDefinition> =
 						txn.get(&table_key, None).await?;
+					// Schema subscriptions are stored on the database
+					if subscription.is_none() {
+						let key = crate::key::database::lq::new(live.ns, live.db, lid);
+						subscription = txn.get(&key, None).await?;
+					}
 					if let Some(sub) = subscription {
 						// For live queries created before auth tracking was introduced
 						// (sub.auth is None), we have no ownership information and
@@ -101,6 +106,9 @@ impl KillStatement {
 				// Delete the table live query
 				let key = crate::key::table::lq::new(live.ns, live.db, &live.tb, lid);
 				txn.clr(&key).await?;
+				// Delete the database live query
+				let key = crate::key::database::lq::new(live.ns, live.db, lid);
+				txn.clr(&key).await?;
 				// Refresh the table cache for lives
 				if let Some(cache) = ctx.get_cache() {
 					cache.set_live_queries_version(live.ns, live.db, &live.tb);
@@ -123,7 +131,8 @@ impl KillStatement {
 						None,
 						PublicAction::Killed,
 						PublicValue::None,
-						PublicValue::None,
+						// The reason the live query was killed
+						PublicValue::String("The live query was killed with KILL".to_owned()),
 					),
 				))
 				.await;
@@ -10,8 +10,9 @@ use crate::dbs::{Options, ParameterCapturePass, Variables};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::visit::{Visit, Visitor};
-use crate::expr::{Cond, Expr, Fetchs, Fields, FlowResultExt as _, Idiom, Param};
-use crate::val::Value;
+use crate::expr::{Base, Cond, Expr, Fetchs, Fields, FlowResultExt as _, Idiom, Literal, Param};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{TableName, Value};
 
 /// The set of parameter names that carry per-event document data and are therefore
 /// only meaningful at notification time, not at LIVE query registration time.
@@ -198,6 +199,61 @@ impl LiveStatement {
 	}
 }
 
+/// Subscribes to the changes made to the schema of the current database
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct LiveSchemaStatement {
+	pub id: Uuid,
+}
+
+impl LiveSchemaStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "LiveSchemaStatement::compute", skip_all)]
+	pub(crate) async fn compute(&self, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
+		// Is realtime enabled?
+		ctx.realtime()?;
+		// Valid options?
+		opt.valid_for_db()?;
+		// Subscribers see the name of everything defined in the database
+		ctx.is_allowed(opt, Action::View, ResourceKind::Any, Base::Db)?;
+		// Get the Node ID
+		let nid = ctx.node_id();
+		// Get the NS and DB
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		// Get the transaction
+		let txn = ctx.tx();
+		let subscription_definition = SubscriptionDefinition {
+			id: self.id,
+			node: nid,
+			fields: SubscriptionFields::Diff,
+			what: Expr::Literal(Literal::None),
+			cond: None,
+			fetch: None,
+			// Use the current session authentication
+			// for when we send notifications
+			auth: Some(opt.auth.as_ref().clone()),
+			session: ctx.value("session").cloned(),
+			vars: Default::default(),
+		};
+		// Insert the node live query. Schema subscriptions
+		// are not attached to a table, so the table is empty.
+		let key = crate::key::node::lq::new(nid, self.id);
+		txn.replace(
+			&key,
+			&NodeLiveQuery {
+				ns,
+				db,
+				tb: TableName::new(""),
+			},
+		)
+		.await?;
+		// Insert the database live query
+		let key = crate::key::database::lq::new(ns, db, self.id);
+		txn.replace(&key, &subscription_definition).await?;
+		// Return the query id
+		Ok(crate::val::Uuid(self.id).into())
+	}
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -0,0 +1,35 @@
+use anyhow::Result;
+
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::expr::Base;
+use crate::iam::{Action, ResourceKind};
+use crate::val::{Duration, TableName, Value};
+
+/// Locks a table until the end of the current transaction, so that other
+/// transactions wait before writing to it
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct LockStatement {
+	pub(crate) what: TableName,
+	/// How long to wait for the lock, instead of the lock wait timeout of
+	/// the datastore
+	pub(crate) timeout: Option<Duration>,
+}
+
+impl LockStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "LockStatement::compute", skip_all)]
+	pub(crate) async fn compute(&self, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
+		// Valid options?
+		opt.valid_for_db()?;
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Table, Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Wait for the lock
+		let timeout = self.timeout.map(|timeout| timeout.0);
+		ctx.tx().lock_table(ns, db, &self.what, timeout).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
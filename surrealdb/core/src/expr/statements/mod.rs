@@ -11,9 +11,11 @@ pub(crate) mod info;
 pub(crate) mod insert;
 pub(crate) mod kill;
 pub(crate) mod live;
+pub(crate) mod lock;
 pub(crate) mod option;
 pub(crate) mod output;
 pub(crate) mod rebuild;
+pub(crate) mod refresh;
 pub(crate) mod relate;
 pub(crate) mod remove;
 pub(crate) mod select;
@@ -23,6 +25,7 @@ pub(crate) mod sleep;
 pub(crate) mod update;
 pub(crate) mod upsert;
 pub(crate) mod r#use;
+pub(crate) mod with;
 
 pub(crate) use self::access::AccessStatement;
 pub(crate) use self::alter::AlterStatement;
@@ -39,10 +42,12 @@ pub(crate) use self::ifelse::IfelseStatement;
 pub(crate) use self::info::InfoStatement;
 pub(crate) use self::insert::InsertStatement;
 pub(crate) use self::kill::KillStatement;
-pub(crate) use self::live::{LiveFields, LiveStatement};
+pub(crate) use self::live::{LiveFields, LiveSchemaStatement, LiveStatement};
+pub(crate) use self::lock::LockStatement;
 pub(crate) use self::option::OptionStatement;
 pub(crate) use self::output::OutputStatement;
 pub(crate) use self::rebuild::RebuildStatement;
+pub(crate) use self::refresh::RefreshStatement;
 pub(crate) use self::relate::RelateStatement;
 pub(crate) use self::remove::{
 	RemoveAccessStatement, RemoveAnalyzerStatement, RemoveConfigStatement, RemoveDatabaseStatement,
@@ -57,3 +62,4 @@ pub(crate) use self::sleep::SleepStatement;
 pub(crate) use self::update::UpdateStatement;
 pub(crate) use self::upsert::UpsertStatement;
 pub(crate) use self::r#use::UseStatement;
+pub(crate) use self::with::WithStatement;
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{
+	DatabaseDefinition, NamespaceDefinition, Record, TableDefinition, ViewDefinition, ViewGraph,
+};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::{self, Document, DocumentContext, NsDbCtx};
+use crate::err::Error;
+use crate::expr::Base;
+use crate::expr::statements::DefineTableStatement;
+use crate::iam::{Action, ResourceKind};
+use crate::key;
+use crate::key::record::RecordKey;
+use crate::key::table::va::Va;
+use crate::val::{Datetime, RecordId, TableName, Value};
+
+/// The number of view records removed at once
+const BATCH_SIZE: u32 = 1000;
+
+/// Computes a materialized view again in full from its source tables
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RefreshStatement {
+	pub(crate) what: TableName,
+}
+
+impl RefreshStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "RefreshStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+	) -> Result<Value> {
+		// Valid options?
+		opt.valid_for_db()?;
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Table, Base::Db)?;
+		// Get the NS and DB
+		let (ns_name, db_name) = opt.ns_db()?;
+		let txn = ctx.tx();
+		let ns = txn.expect_ns_by_name(ns_name).await?;
+		let db = txn.expect_db_by_name(ns_name, db_name).await?;
+		let (ns_id, db_id) = (ns.namespace_id, db.database_id);
+		// Get the view
+		let Some(tb) = txn.get_tb(ns_id, db_id, &self.what, None).await? else {
+			bail!(Error::TbNotFound {
+				name: self.what.clone(),
+			});
+		};
+		let view = match &tb.view {
+			Some(
+				view @ (ViewDefinition::Materialized {
+					..
+				}
+				| ViewDefinition::Aggregated {
+					..
+				}),
+			) => view.clone(),
+			_ => bail!(Error::TableIsNotView {
+				table: self.what.to_string(),
+			}),
+		};
+		refresh_view(stk, ctx, opt, ns, db, tb, &view).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+/// Computes a materialized view again in full from its source tables
+async fn refresh_view(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	ns: Arc<NamespaceDefinition>,
+	db: Arc<DatabaseDefinition>,
+	tb: Arc<TableDefinition>,
+	view: &ViewDefinition,
+) -> Result<()> {
+	let txn = ctx.tx();
+	let (ns_id, db_id) = (ns.namespace_id, db.database_id);
+	let view_name = tb.name.clone();
+	let parent = NsDbCtx {
+		ns,
+		db,
+	};
+	let doc_ctx =
+		DocumentContext::initialise(ctx, &parent, tb, &view_name, opt.version, true).await?;
+	// Remove the records of the view, along with what was derived from them
+	let beg = key::record::prefix(ns_id, db_id, &view_name)?;
+	let end = key::record::suffix(ns_id, db_id, &view_name)?;
+	loop {
+		let batch = txn.scan(beg.clone()..end.clone(), BATCH_SIZE, 0, None).await?;
+		if batch.is_empty() {
+			break;
+		}
+		for (k, v) in batch {
+			let record = Arc::new(revision::from_slice::<Record>(&v)?);
+			let id = Arc::new(RecordId {
+				table: view_name.clone(),
+				key: RecordKey::decode_key(&k)?.id,
+			});
+			txn.del_record(ns_id, db_id, &view_name, &id.key).await?;
+			Document::run_triggers(
+				stk,
+				ctx,
+				opt,
+				doc_ctx.clone(),
+				id,
+				doc::Action::Delete,
+				Some(record),
+				None,
+			)
+			.await?;
+
+			yield_now!();
+		}
+	}
+	// Remove the counted values of the minimums and maximums
+	txn.delr(Va::range(ns_id, db_id, &view_name)?).await?;
+	// Compute the view again
+	DefineTableStatement::initialize_view(stk, ctx, opt, &doc_ctx, &view_name, view).await?;
+	Ok(())
+}
+
+/// Returns the materialized views which are computed from a table, directly
+/// or through other views, each after the views it is computed from
+async fn materialized_dependents(
+	ctx: &FrozenContext,
+	opt: &Options,
+	tb: &TableName,
+) -> Result<Vec<(Arc<TableDefinition>, ViewDefinition)>> {
+	let txn = ctx.tx();
+	let (ns_id, db_id) = ctx.expect_ns_db_ids(opt).await?;
+	// Most tables have no views
+	if txn.all_tb_views(ns_id, db_id, tb, None).await?.is_empty() {
+		return Ok(Vec::new());
+	}
+	let graph = ViewGraph::new(&txn.all_tb(ns_id, db_id, None).await?);
+	let mut views = Vec::new();
+	for name in graph.dependents(tb) {
+		let Some(view_tb) = txn.get_tb(ns_id, db_id, &name, None).await? else {
+			continue;
+		};
+		// Views which are not materialized are computed when selected
+		let view = match &view_tb.view {
+			Some(
+				view @ (ViewDefinition::Materialized {
+					..
+				}
+				| ViewDefinition::Aggregated {
+					..
+				}),
+			) => view.clone(),
+			_ => continue,
+		};
+		views.push((view_tb, view));
+	}
+	Ok(views)
+}
+
+/// Rebuilds the materialized views which are computed from a table, directly
+/// or through other views, each after the views it is computed from
+///
+/// This is needed once the records of the table were replaced without its
+/// views following along. While importing, the views are only marked as
+/// stale instead.
+pub(crate) async fn refresh_dependents(
+	stk: &mut Stk,
+	ctx: &FrozenContext,
+	opt: &Options,
+	tb: &TableName,
+) -> Result<()> {
+	if opt.import {
+		return mark_dependents_stale(ctx, opt, tb).await;
+	}
+	let views = materialized_dependents(ctx, opt, tb).await?;
+	if views.is_empty() {
+		return Ok(());
+	}
+	let (ns_name, db_name) = opt.ns_db()?;
+	let txn = ctx.tx();
+	let ns = txn.expect_ns_by_name(ns_name).await?;
+	let db = txn.expect_db_by_name(ns_name, db_name).await?;
+	for (view_tb, view) in views {
+		refresh_view(stk, ctx, opt, Arc::clone(&ns), Arc::clone(&db), view_tb, &view).await?;
+	}
+	Ok(())
+}
+
+/// Marks the materialized views which are computed from a table, directly or
+/// through other views, as stale, until they are refreshed
+///
+/// Changes to the schema of a table leave its records as they are, so its
+/// views are not rebuilt within the statement which changed the schema,
+/// which could take arbitrarily long. They are rebuilt with `REFRESH VIEW`.
+pub(crate) async fn mark_dependents_stale(
+	ctx: &FrozenContext,
+	opt: &Options,
+	tb: &TableName,
+) -> Result<()> {
+	let txn = ctx.tx();
+	let (ns_id, db_id) = ctx.expect_ns_db_ids(opt).await?;
+	for (view_tb, _) in materialized_dependents(ctx, opt, tb).await? {
+		let key = key::table::vs::new(ns_id, db_id, &view_tb.name);
+		// Views defined before their state was kept have no state to update
+		if let Some(mut state) = txn.get(&key, None).await?
+			&& state.stale_since.is_none()
+		{
+			state.stale_since = Some(Datetime::now());
+			txn.set(&key, &state).await?;
+		}
+	}
+	Ok(())
+}
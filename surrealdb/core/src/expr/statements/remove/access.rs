@@ -9,6 +9,7 @@ use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, Literal, Value};
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct RemoveAccessStatement {
@@ -108,6 +109,9 @@ impl RemoveAccessStatement {
 				txn.del_db_access(ns, db, &ac.name).await?;
 				// Clear the cache
 				txn.clear_cache();
+				// Notify the schema subscriptions
+				let change = SchemaChange::Access(ac.name.as_str());
+				notify_schema_change(ctx, opt, SchemaAction::Remove, change).await?;
 				// Ok all good
 				Ok(Value::None)
 			}
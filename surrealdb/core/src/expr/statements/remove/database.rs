@@ -5,7 +5,7 @@ use super::retire_database_indexes;
 use crate::catalog::providers::DatabaseProvider;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
-use crate::doc::CursorDoc;
+use crate::doc::{CursorDoc, DeadLetterRecord, OutboxRecord};
 use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, Literal, Value};
@@ -40,6 +40,8 @@ impl RemoveDatabaseStatement {
 	) -> Result<Value> {
 		// Allowed to run?
 		ctx.is_allowed(opt, Action::Edit, ResourceKind::Database, Base::Ns)?;
+		// Refused by the guardrails of the datastore?
+		ctx.get_capabilities().guardrails().check_remove_database()?;
 		// Get the transaction
 		let txn = ctx.tx();
 
@@ -67,6 +69,10 @@ impl RemoveDatabaseStatement {
 		if let Some(seq) = ctx.get_sequences() {
 			seq.database_removed(&txn, db.namespace_id, db.database_id).await?;
 		}
+		// Remove the outbox requests which are waiting to be sent
+		OutboxRecord::database_removed(&txn, db.namespace_id, db.database_id).await?;
+		// Remove the async events which could not be processed
+		DeadLetterRecord::database_removed(&txn, db.namespace_id, db.database_id).await?;
 
 		// Delete the catalog definition and enqueue the data for background
 		// reclaim. Only the small catalog entry is removed in this transaction
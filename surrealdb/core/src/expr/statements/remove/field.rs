@@ -9,8 +9,10 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::parameterize::{expr_to_ident, expr_to_idiom};
+use crate::expr::statements::refresh::mark_dependents_stale;
 use crate::expr::{Base, Expr, Literal, Value};
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::TableName;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -113,6 +115,14 @@ impl RemoveFieldStatement {
 		.await?;
 		// Clear the cache
 		txn.clear_cache();
+		// The views computed from the table are stale until refreshed
+		mark_dependents_stale(ctx, opt, &table_name).await?;
+		// Notify the schema subscriptions
+		let change = SchemaChange::Field {
+			table: table_name.as_str(),
+			name: &name,
+		};
+		notify_schema_change(ctx, opt, SchemaAction::Remove, change).await?;
 		// Ok all good
 		Ok(Value::None)
 	}
@@ -12,6 +12,7 @@ use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, Literal, Value};
 use crate::iam::{Action, ResourceKind};
 use crate::kvs::index::retire_durable_index;
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::val::TableName;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -101,6 +102,12 @@ impl RemoveIndexStatement {
 		.await?;
 		// Clear the cache
 		txn.clear_cache();
+		// Notify the schema subscriptions
+		let change = SchemaChange::Index {
+			table: table_name.as_str(),
+			name: &name,
+		};
+		notify_schema_change(ctx, opt, SchemaAction::Remove, change).await?;
 		// Ok all good
 		Ok(Value::None)
 	}
@@ -12,6 +12,8 @@ mod model;
 mod module;
 mod namespace;
 mod param;
+mod policy;
+mod query;
 mod sequence;
 mod table;
 mod user;
@@ -31,6 +33,8 @@ pub(crate) use model::RemoveModelStatement;
 pub(crate) use module::RemoveModuleStatement;
 pub(crate) use namespace::RemoveNamespaceStatement;
 pub(crate) use param::RemoveParamStatement;
+pub(crate) use policy::RemovePolicyStatement;
+pub(crate) use query::RemoveQueryStatement;
 use reblessive::tree::Stk;
 pub(crate) use sequence::RemoveSequenceStatement;
 pub(crate) use table::RemoveTableStatement;
@@ -53,8 +57,10 @@ pub(crate) enum RemoveStatement {
 	Analyzer(RemoveAnalyzerStatement),
 	Access(RemoveAccessStatement),
 	Param(RemoveParamStatement),
+	Query(RemoveQueryStatement),
 	Table(RemoveTableStatement),
 	Event(RemoveEventStatement),
+	Policy(RemovePolicyStatement),
 	Field(RemoveFieldStatement),
 	Index(RemoveIndexStatement),
 	User(RemoveUserStatement),
@@ -81,8 +87,10 @@ impl RemoveStatement {
 			Self::Function(v) => v.compute(ctx, opt).await,
 			Self::Access(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Param(v) => v.compute(ctx, opt).await,
+			Self::Query(v) => v.compute(ctx, opt).await,
 			Self::Table(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Event(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Policy(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Field(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Index(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Analyzer(v) => v.compute(stk, ctx, opt, doc).await,
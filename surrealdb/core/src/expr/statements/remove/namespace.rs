@@ -5,7 +5,7 @@ use super::retire_namespace_indexes;
 use crate::catalog::providers::NamespaceProvider;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
-use crate::doc::CursorDoc;
+use crate::doc::{CursorDoc, DeadLetterRecord, OutboxRecord};
 use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, Literal, Value};
@@ -39,6 +39,8 @@ impl RemoveNamespaceStatement {
 	) -> Result<Value> {
 		// Allowed to run?
 		ctx.is_allowed(opt, Action::Edit, ResourceKind::Namespace, Base::Root)?;
+		// Refused by the guardrails of the datastore?
+		ctx.get_capabilities().guardrails().check_remove_namespace()?;
 		// Get the transaction
 		let txn = ctx.tx();
 		// Compute the name
@@ -64,6 +66,10 @@ impl RemoveNamespaceStatement {
 		if let Some(seq) = ctx.get_sequences() {
 			seq.namespace_removed(&txn, ns.namespace_id).await?;
 		}
+		// Remove the outbox requests which are waiting to be sent
+		OutboxRecord::namespace_removed(&txn, ns.namespace_id).await?;
+		// Remove the async events which could not be processed
+		DeadLetterRecord::namespace_removed(&txn, ns.namespace_id).await?;
 
 		// Delete the catalog definition and enqueue the data for background
 		// reclaim. Only the small catalog entry is removed in this transaction
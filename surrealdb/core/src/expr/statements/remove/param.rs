@@ -7,6 +7,7 @@ use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::{Base, Value};
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub(crate) struct RemoveParamStatement {
@@ -38,6 +39,12 @@ impl RemoveParamStatement {
 		txn.del(&key).await?;
 		// Clear the cache
 		txn.clear_cache();
+		// Notify the schema subscriptions
+		let change = SchemaChange::Param {
+			name: pa.name.as_str(),
+			value: None,
+		};
+		notify_schema_change(ctx, opt, SchemaAction::Remove, change).await?;
 		// Ok all good
 		Ok(Value::None)
 	}
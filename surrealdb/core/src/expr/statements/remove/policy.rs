@@ -0,0 +1,78 @@
+use anyhow::Result;
+use reblessive::tree::Stk;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RemovePolicyStatement {
+	pub name: Expr,
+	pub table_name: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemovePolicyStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			table_name: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl RemovePolicyStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Table, Base::Db)?;
+		// Compute the table name
+		let table_name = TableName::new(
+			expr_to_ident(stk, ctx, opt, doc, &self.table_name, "table name").await?,
+		);
+		// Compute the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "policy name").await?;
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let po = match txn.get_tb_policy(ns, db, &table_name, &name).await {
+			Ok(x) => x,
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::PoNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Delete the definition and its progress
+		let key = crate::key::table::po::new(ns, db, &po.target_table, &po.name);
+		txn.del(&key).await?;
+		let key = crate::key::table::pp::new(ns, db, &po.target_table, &po.name);
+		txn.del(&key).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl ToSql for RemovePolicyStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::remove::RemovePolicyStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+use surrealdb_strand::Strand;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::{Base, Value};
+use crate::iam::{Action, ResourceKind};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct RemoveQueryStatement {
+	pub name: Strand,
+	pub if_exists: bool,
+}
+
+impl RemoveQueryStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(&self, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Function, Base::Db)?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let qy = match txn.get_db_query(ns, db, &self.name).await {
+			Ok(x) => x,
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::QyNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Delete the definition
+		let key = crate::key::database::qy::new(ns, db, &qy.name);
+		txn.del(&key).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl ToSql for RemoveQueryStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::remove::RemoveQueryStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
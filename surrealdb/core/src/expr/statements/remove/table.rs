@@ -12,6 +12,7 @@ use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, Literal, Value};
 use crate::iam::{Action, ResourceKind};
+use crate::lq::schema::{SchemaAction, SchemaChange, notify_schema_change};
 use crate::types::{PublicAction, PublicNotification, PublicValue};
 use crate::val::TableName;
 
@@ -46,6 +47,8 @@ impl RemoveTableStatement {
 		// Compute the name
 		let name =
 			TableName::new(expr_to_ident(stk, ctx, opt, doc, &self.name, "table name").await?);
+		// Refused by the guardrails of the datastore?
+		ctx.get_capabilities().guardrails().check_remove_table(&name)?;
 		// Get the NS and DB
 		let (ns_name, db_name) = opt.ns_db()?;
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
@@ -97,6 +100,8 @@ impl RemoveTableStatement {
 
 		// Remove the resource data
 		let key = crate::key::table::all::new(ns, db, &name);
+		txn.table_writes().write(ns, db, &name);
+		txn.table_stats().forget(ns, db, &name);
 		if self.expunge {
 			txn.clrp(&key).await?
 		} else {
@@ -145,7 +150,8 @@ impl RemoveTableStatement {
 							None,
 							PublicAction::Killed,
 							PublicValue::None,
-							PublicValue::None,
+							// The reason the live query was killed
+							PublicValue::String("The table was removed".to_owned()),
 						),
 					))
 					.await;
@@ -157,6 +163,9 @@ impl RemoveTableStatement {
 		}
 		// Clear the transaction cache
 		txn.clear_cache();
+		// Notify the schema subscriptions
+		notify_schema_change(ctx, opt, SchemaAction::Remove, SchemaChange::Table(name.as_str()))
+			.await?;
 		// Ok all good
 		Ok(Value::None)
 	}
@@ -1,20 +1,24 @@
 use std::borrow::Cow;
 use std::sync::Arc;
 
-use anyhow::{Result, ensure};
+use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
+use surrealdb_types::ToSql;
 
-use crate::catalog::providers::{DatabaseProvider, NamespaceProvider};
-use crate::ctx::FrozenContext;
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{DatabaseId, NamespaceId, ViewDefinition};
+use crate::ctx::{Context, FrozenContext};
 use crate::dbs::{Iterator, Options, Statement};
 use crate::doc::{CursorDoc, NsDbCtx};
 use crate::err::Error;
 use crate::expr::order::Ordering;
 use crate::expr::{
-	Cond, Explain, Expr, Fetchs, Fields, FlowResultExt as _, Groups, Limit, Splits, Start, With,
+	BinaryOperator, Cond, Explain, Expr, Fetchs, Fields, FlowResultExt as _, Groups, Limit,
+	Literal, Splits, Start, With,
 };
 use crate::idx::planner::{QueryPlanner, RecordStrategy, StatementContext};
-use crate::val::{Datetime, Value};
+use crate::kvs::Transaction;
+use crate::val::{Datetime, TableName, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct SelectStatement {
@@ -49,8 +53,26 @@ pub(crate) struct SelectStatement {
 	pub fetch: Option<Fetchs>,
 	pub version: Expr,
 	pub timeout: Expr,
+	/// The `RETURN PARTIAL` clause of the timeout.
+	///
+	/// Once the timeout expires, this returns the rows found so far, marking
+	/// the statement as truncated in its stats, rather than failing.
+	pub partial: bool,
 	pub explain: Option<Explain>,
 	pub tempfiles: bool,
+	/// The `FOR VALID TIME AS OF` clause.
+	///
+	/// This only returns the records of bitemporal tables which were valid
+	/// at the given time, and is replaced by a condition on the valid time
+	/// fields of the tables before the statement runs.
+	pub valid_time: Option<Expr>,
+	/// The `SCAN LIMIT` clause.
+	///
+	/// This sets how many rows the statement, and the subqueries within it,
+	/// may scan. It applies along with the `max_scan_rows` limit of the
+	/// options it runs with, and with the limits of any enclosing statement,
+	/// so it can lower the limit which applies but never raise it.
+	pub scan_limit: Option<u64>,
 }
 
 impl SelectStatement {
@@ -61,6 +83,189 @@ impl SelectStatement {
 			&& self.cond.as_ref().map(|x| x.0.read_only()).unwrap_or(true)
 	}
 
+	/// Checks that `RETURN PARTIAL` is only used when the rows are returned as
+	/// they are found. The rows held back by an `ORDER BY` or `GROUP BY` clause
+	/// when the timeout expires would otherwise be lost.
+	pub(crate) fn check_partial(&self) -> Result<(), Error> {
+		if self.partial && (self.order.is_some() || self.group.is_some()) {
+			return Err(Error::Query {
+				message: "RETURN PARTIAL can not be used with ORDER BY or GROUP BY".to_owned(),
+			});
+		}
+		Ok(())
+	}
+
+	/// Replaces the views among the sources of the statement, which are
+	/// computed when they are selected from, with the queries defining them.
+	///
+	/// A view which selects every field of a single table, without grouping,
+	/// is replaced by its table when it is the only source of the statement,
+	/// and its condition is added to the WHERE clause, so the condition of the
+	/// statement is pushed down into the table scan. Any other view is replaced
+	/// by a subquery. As the records are read from the tables of the view, the
+	/// permissions of those tables apply.
+	///
+	/// Returns `None` if no source of the statement is a view.
+	pub(crate) async fn expand_views(
+		&self,
+		txn: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Option<SelectStatement>> {
+		for w in self.what.iter() {
+			if let Expr::Table(tb) = w
+				&& let Some(tb) = txn.get_tb(ns, db, tb, None).await?
+				&& matches!(tb.view, Some(ViewDefinition::Select { .. }))
+			{
+				let mut stm = self.clone();
+				stm.expand_views_within(txn, ns, db, &mut Vec::new()).await?;
+				return Ok(Some(stm));
+			}
+		}
+		Ok(None)
+	}
+
+	async fn expand_views_within(
+		&mut self,
+		txn: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		path: &mut Vec<TableName>,
+	) -> Result<()> {
+		let depth = path.len();
+		let mut i = 0;
+		while let Some(w) = self.what.get(i) {
+			let Expr::Table(tb) = w else {
+				i += 1;
+				continue;
+			};
+			let Some(ViewDefinition::Select {
+				fields,
+				tables,
+				condition,
+				groups,
+			}) = txn.get_tb(ns, db, tb, None).await?.and_then(|x| x.view.clone())
+			else {
+				i += 1;
+				continue;
+			};
+			ensure!(
+				!path.contains(tb),
+				Error::Query {
+					message: format!("The view '{tb}' is defined in terms of itself"),
+				}
+			);
+			path.push(tb.clone());
+			// Push the condition of the view down into its table
+			if self.what.len() == 1
+				&& tables.len() == 1
+				&& fields == Fields::all()
+				&& groups.is_none()
+			{
+				self.what = tables.into_iter().map(Expr::Table).collect();
+				self.cond = match (condition, self.cond.take()) {
+					(Some(l), Some(r)) => Some(Cond(Expr::Binary {
+						left: Box::new(l),
+						op: BinaryOperator::And,
+						right: Box::new(r.0),
+					})),
+					(l, r) => l.map(Cond).or(r),
+				};
+				// The table may itself be a view
+				continue;
+			}
+			let mut sub = SelectStatement {
+				fields,
+				omit: Vec::new(),
+				only: false,
+				what: tables.into_iter().map(Expr::Table).collect(),
+				with: None,
+				cond: condition.map(Cond),
+				split: None,
+				group: groups,
+				order: None,
+				limit: None,
+				start: None,
+				fetch: None,
+				version: Expr::Literal(Literal::None),
+				timeout: Expr::Literal(Literal::None),
+				partial: false,
+				explain: None,
+				tempfiles: false,
+				valid_time: None,
+				scan_limit: None,
+			};
+			Box::pin(sub.expand_views_within(txn, ns, db, path)).await?;
+			path.pop();
+			self.what[i] = Expr::Select(Box::new(sub));
+			i += 1;
+		}
+		path.truncate(depth);
+		Ok(())
+	}
+
+	/// Replaces the `FOR VALID TIME AS OF` clause of the statement with a
+	/// condition on the valid time fields of its tables.
+	///
+	/// Every source of the statement must be a table, or a record of a table,
+	/// defined as `BITEMPORAL`, with the same valid time fields. The condition
+	/// is added to the WHERE clause, so that index selection and predicate
+	/// pushdown apply to it like to any other filter.
+	///
+	/// Returns `None` if the statement has no such clause.
+	pub(crate) async fn expand_valid_time(
+		&self,
+		txn: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Option<SelectStatement>> {
+		let Some(at) = &self.valid_time else {
+			return Ok(None);
+		};
+		let mut valid_time = None;
+		for w in self.what.iter() {
+			let tb = match w {
+				Expr::Table(tb) => tb,
+				Expr::Literal(Literal::RecordId(rid)) => &rid.table,
+				w => bail!(Error::Query {
+					message: format!(
+						"Only tables and records can be selected FOR VALID TIME, found {}",
+						w.to_sql()
+					),
+				}),
+			};
+			let Some(vt) = txn.get_tb(ns, db, tb, None).await?.and_then(|x| x.valid_time.clone())
+			else {
+				bail!(Error::TbNotBitemporal {
+					name: tb.clone(),
+				});
+			};
+			match &valid_time {
+				Some(x) if *x != vt => bail!(Error::Query {
+					message:
+						"The tables selected FOR VALID TIME must use the same valid time fields"
+							.to_string(),
+				}),
+				_ => valid_time = Some(vt),
+			}
+		}
+		let Some(valid_time) = valid_time else {
+			return Ok(None);
+		};
+		let mut stm = self.clone();
+		let check = valid_time.check(at.clone());
+		stm.valid_time = None;
+		stm.cond = Some(match stm.cond.take() {
+			Some(Cond(c)) => Cond(Expr::Binary {
+				left: Box::new(c),
+				op: BinaryOperator::And,
+				right: Box::new(check),
+			}),
+			None => Cond(check),
+		});
+		Ok(Some(stm))
+	}
+
 	/// Process this type returning a computed simple Value
 	#[instrument(level = "trace", name = "SelectStatement::compute", skip_all)]
 	pub(crate) async fn compute(
@@ -72,6 +277,31 @@ impl SelectStatement {
 	) -> Result<Value> {
 		// Valid options?
 		opt.valid_for_db()?;
+		// Check the partial results can be returned
+		self.check_partial()?;
+		// Limit how many rows the scans of this statement may read
+		if let Some(limit) = self.scan_limit {
+			let mut scoped = Context::new_child(ctx);
+			scoped.set_scan_limit(limit);
+			let scoped = scoped.freeze();
+			let stm = SelectStatement {
+				scan_limit: None,
+				..self.clone()
+			};
+			return stk.run(|stk| stm.compute(stk, &scoped, opt, parent_doc)).await;
+		}
+		// Compute the views among the sources from their queries
+		if let Some((ns, db)) = ctx.try_ns_db_ids(opt).await?
+			&& let Some(stm) = self.expand_views(&ctx.tx(), ns, db).await?
+		{
+			return stk.run(|stk| stm.compute(stk, ctx, opt, parent_doc)).await;
+		}
+		// Filter the records by their valid time
+		if let Some((ns, db)) = ctx.try_ns_db_ids(opt).await?
+			&& let Some(stm) = self.expand_valid_time(&ctx.tx(), ns, db).await?
+		{
+			return stk.run(|stk| stm.compute(stk, ctx, opt, parent_doc)).await;
+		}
 		// Assign the statement
 		let stm = Statement::from_select(stk, ctx, opt, parent_doc, self).await?;
 		// Create a new iterator
@@ -156,8 +386,14 @@ impl SelectStatement {
 			let res = iterator
 				.output(stk, ctx.as_ref(), &opt, &stm, RecordStrategy::KeysAndValues)
 				.await?;
-			// Catch statement timeout
-			ctx.expect_not_timedout().await?;
+			// Catch statement timeout, keeping the rows found so far when asked to
+			if self.partial && ctx.is_timedout().await?.is_some() {
+				if let Some(counters) = ctx.statement_counters() {
+					counters.record_truncated();
+				}
+			} else {
+				ctx.expect_not_timedout().await?;
+			}
 
 			if self.only {
 				match res {
@@ -0,0 +1,101 @@
+use std::convert::Infallible;
+
+use crate::expr::statements::{SelectStatement, SetStatement};
+use crate::expr::visit::{MutVisitor, VisitMut};
+use crate::expr::{Block, Expr, Param};
+
+/// A SELECT statement preceded by common table expressions, like
+/// `WITH recent AS (SELECT ...) SELECT * FROM recent`.
+///
+/// The statement runs as a block which computes each expression once,
+/// binding the result to a parameter, before running the SELECT statement.
+/// Tables named after an expression within the statement, or within the
+/// later expressions, refer to that parameter.
+///
+/// The parameters are named within their own `cte::` namespace, so that
+/// expressions can be named after protected parameters like `$auth`, and
+/// do not replace the parameters of the same name used by the rest of the
+/// query.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct WithStatement {
+	/// The common table expressions, in the order they are computed
+	pub exprs: Vec<CommonTableExpr>,
+	pub stmt: SelectStatement,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct CommonTableExpr {
+	pub name: String,
+	pub what: Expr,
+}
+
+impl WithStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn read_only(&self) -> bool {
+		self.exprs.iter().all(|x| x.what.read_only()) && self.stmt.read_only()
+	}
+
+	/// Check if the expressions directly contain a data-modifying statement.
+	pub(crate) fn has_direct_write(&self) -> bool {
+		self.exprs.iter().any(|x| x.what.has_direct_write())
+	}
+
+	/// Returns the parameter bound to the result of a common table expression.
+	pub(crate) fn param(name: &str) -> Param {
+		Param::from(format!("cte::{name}"))
+	}
+
+	/// Lowers the statement into the block it runs as.
+	pub(crate) fn into_block(self) -> Block {
+		let mut names = Vec::with_capacity(self.exprs.len());
+		let mut exprs = Vec::with_capacity(self.exprs.len() + 1);
+		for CommonTableExpr {
+			name,
+			mut what,
+		} in self.exprs
+		{
+			// Each expression can refer to the expressions before it
+			bind_tables(&names, &mut what);
+			exprs.push(Expr::Let(Box::new(SetStatement {
+				name: Self::param(&name).into_strand(),
+				what,
+				kind: None,
+			})));
+			names.push(name);
+		}
+		let mut stmt = Expr::Select(Box::new(self.stmt));
+		bind_tables(&names, &mut stmt);
+		exprs.push(stmt);
+		Block(exprs)
+	}
+}
+
+/// Replaces the tables named after the given expressions with the parameters
+/// bound to their results.
+fn bind_tables(names: &[String], expr: &mut Expr) {
+	if names.is_empty() {
+		return;
+	}
+	let mut binder = TableBinder {
+		names,
+	};
+	let _ = binder.visit_mut_expr(expr);
+}
+
+struct TableBinder<'a> {
+	names: &'a [String],
+}
+
+impl MutVisitor for TableBinder<'_> {
+	type Error = Infallible;
+
+	fn visit_mut_expr(&mut self, expr: &mut Expr) -> Result<(), Self::Error> {
+		if let Expr::Table(table) = expr
+			&& self.names.iter().any(|name| table.as_str() == name)
+		{
+			*expr = Expr::Param(WithStatement::param(table.as_str()));
+			return Ok(());
+		}
+		expr.visit_mut(self)
+	}
+}
@@ -0,0 +1,24 @@
+use revision::revisioned;
+
+use crate::expr::statements::info::InfoStructure;
+use crate::val::Value;
+
+/// The write rate allowed on a table, declared with `THROTTLE`
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct Throttle {
+	/// The number of writes allowed per second
+	pub rate: u64,
+	/// The number of writes which can be made at once before throttling
+	pub burst: u64,
+}
+
+impl InfoStructure for Throttle {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"rate" => self.rate.into(),
+			"burst" => self.burst.into(),
+		})
+	}
+}
@@ -0,0 +1,67 @@
+use revision::revisioned;
+
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{BinaryOperator, Expr, Idiom, Literal};
+use crate::val::Value;
+
+/// The valid time of the records of a table, declared with
+/// `BITEMPORAL VALID FROM ... TO ...`
+///
+/// Each record holds the period during which the fact it describes was true,
+/// next to the time at which it was written, which versioned storage keeps.
+/// The period starts at the valid from field, and ends before the valid to
+/// field, which is left unset while the fact is still true. SELECT statements
+/// run `FOR VALID TIME AS OF` only return the records which were valid at
+/// that time.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct ValidTime {
+	/// The field holding the start of the valid time of a record
+	pub from: String,
+	/// The field holding the end of the valid time of a record
+	pub to: String,
+}
+
+impl ValidTime {
+	/// The condition matching records which were valid at a point in time
+	///
+	/// The condition only compares the fields with the point in time, so that
+	/// an index on the valid from field can be used to scan the records.
+	pub(crate) fn check(&self, at: Expr) -> Expr {
+		let to = || Box::new(Expr::Idiom(Idiom::field(self.to.clone())));
+		let started = Expr::Binary {
+			left: Box::new(Expr::Idiom(Idiom::field(self.from.clone()))),
+			op: BinaryOperator::LessThanEqual,
+			right: Box::new(at.clone()),
+		};
+		let current = Expr::Binary {
+			left: to(),
+			op: BinaryOperator::Equal,
+			right: Box::new(Expr::Literal(Literal::None)),
+		};
+		let ended = Expr::Binary {
+			left: to(),
+			op: BinaryOperator::MoreThan,
+			right: Box::new(at),
+		};
+		Expr::Binary {
+			left: Box::new(started),
+			op: BinaryOperator::And,
+			right: Box::new(Expr::Binary {
+				left: Box::new(current),
+				op: BinaryOperator::Or,
+				right: Box::new(ended),
+			}),
+		}
+	}
+}
+
+impl InfoStructure for ValidTime {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"from" => self.from.into(),
+			"to" => self.to.into(),
+		})
+	}
+}
@@ -9,7 +9,8 @@ use crate::expr::order::Ordering;
 use crate::expr::part::{DestructurePart, Recurse, RecurseInstruction};
 use crate::expr::reference::{Reference, ReferenceDeleteStrategy};
 use crate::expr::statements::access::{
-	AccessStatementGrant, AccessStatementPurge, AccessStatementRevoke, AccessStatementShow, Subject,
+	AccessStatementGrant, AccessStatementGrantMany, AccessStatementPurge, AccessStatementRevoke,
+	AccessStatementShow, AccessStatementShowSubject, Subject,
 };
 use crate::expr::statements::alter::{
 	AlterAccessStatement, AlterAnalyzerStatement, AlterApiClause, AlterApiStatement,
@@ -22,11 +23,13 @@ use crate::expr::statements::define::config::ConfigInner;
 use crate::expr::statements::define::config::api::ApiConfig;
 use crate::expr::statements::define::config::defaults::DefaultConfig;
 use crate::expr::statements::define::{
-	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineDefault, DefineSequenceStatement,
+	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineDefault, DefinePolicyStatement,
+	DefineQueryStatement, DefineSequenceStatement, UniqueConflicts,
 };
 use crate::expr::statements::rebuild::RebuildStatement;
 use crate::expr::statements::remove::{
-	RemoveApiStatement, RemoveBucketStatement, RemoveSequenceStatement,
+	RemoveApiStatement, RemoveBucketStatement, RemovePolicyStatement, RemoveQueryStatement,
+	RemoveSequenceStatement,
 };
 use crate::expr::statements::{
 	AccessStatement, AlterStatement, CreateStatement, DefineAccessStatement,
@@ -40,7 +43,7 @@ use crate::expr::statements::{
 	RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement, RemoveModuleStatement,
 	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
 	RemoveUserStatement, SelectStatement, SetStatement, ShowStatement, SleepStatement,
-	UpdateStatement, UpsertStatement, UseStatement,
+	UpdateStatement, UpsertStatement, UseStatement, WithStatement,
 };
 use crate::expr::{
 	AccessType, Block, ClosureExpr, Data, Expr, Field, Fields, Function, FunctionCall, Idiom,
@@ -137,6 +140,9 @@ implement_visitor! {
 			TopLevelExpr::Access(s) => {this.visit_access(s)? },
 			TopLevelExpr::Kill(s) => {this.visit_kill(s)?; },
 			TopLevelExpr::Live(s) => {this.visit_live(s)?; },
+			TopLevelExpr::LiveSchema(_) => {},
+			TopLevelExpr::Lock(_) => {},
+			TopLevelExpr::Refresh(_) => {},
 		TopLevelExpr::Option(s) =>{ this.visit_option(s)?; },
 		TopLevelExpr::Use(s) => {this.visit_use(s)?; },
 		TopLevelExpr::Show(s) => {this.visit_show(s)?; },
@@ -150,9 +156,15 @@ implement_visitor! {
 			AccessStatement::Grant(a) => {
 				this.visit_access_grant(a)?;
 			},
+			AccessStatement::GrantMany(a) => {
+				this.visit_access_grant_many(a)?;
+			},
 			AccessStatement::Show(a) => {
 				this.visit_access_show(a)?;
 			},
+			AccessStatement::ShowSubject(a) => {
+				this.visit_access_show_subject(a)?;
+			},
 			AccessStatement::Revoke(a) => {
 				this.visit_access_revoke(a)?;
 			},
@@ -168,6 +180,13 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_access_grant_many(this, a: &AccessStatementGrantMany){
+		for s in a.subjects.iter(){
+			this.visit_access_subject(s)?;
+		}
+		Ok(())
+	}
+
 	fn visit_access_subject(this, s: &Subject){
 		match s{
 			Subject::Record(r) => {
@@ -184,6 +203,10 @@ implement_visitor! {
 		}
 		Ok(())
 	}
+	fn visit_access_show_subject(this, a: &AccessStatementShowSubject){
+		this.visit_access_subject(&a.subject)?;
+		Ok(())
+	}
 	fn visit_access_revoke(this, a: &AccessStatementRevoke){
 		if let Some(c) = a.cond.as_ref(){
 			this.visit_expr(&c.0)?;
@@ -273,6 +296,7 @@ implement_visitor! {
 				this.visit_if_else(s)?;
 			},
 			Expr::Select(s) => { this.visit_select(s)?; },
+			Expr::With(s) => { this.visit_with(s)?; },
 			Expr::Create(s) => { this.visit_create(s)?; },
 			Expr::Update(s) => { this.visit_update(s)?; },
 			Expr::Upsert(s) => { this.visit_upsert(s)?; },
@@ -668,6 +692,9 @@ implement_visitor! {
 			}
 		}
 		this.visit_expr(&s.version)?;
+		if let Some(v) = s.valid_time.as_ref(){
+			this.visit_expr(v)?;
+		}
 
 		Ok(())
 	}
@@ -692,12 +719,18 @@ implement_visitor! {
 			RemoveStatement::Param(r) => {
 				this.visit_remove_param(r)?;
 			},
+			RemoveStatement::Query(r) => {
+				this.visit_remove_query(r)?;
+			},
 			RemoveStatement::Table(r) => {
 				this.visit_remove_table(r)?;
 			},
 			RemoveStatement::Event(r) => {
 				this.visit_remove_event(r)?;
 			},
+			RemoveStatement::Policy(r) => {
+				this.visit_remove_policy(r)?;
+			},
 			RemoveStatement::Field(r) => {
 				this.visit_remove_field(r)?;
 			},
@@ -765,6 +798,10 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_remove_query(this, r: &RemoveQueryStatement){
+		Ok(())
+	}
+
 	fn visit_remove_table(this, r: &RemoveTableStatement){
 		this.visit_expr(&r.name)?;
 		Ok(())
@@ -775,6 +812,12 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_remove_policy(this, r: &RemovePolicyStatement){
+		this.visit_expr(&r.name)?;
+		this.visit_expr(&r.table_name)?;
+		Ok(())
+	}
+
 	fn visit_remove_field(this, r: &RemoveFieldStatement){
 		this.visit_expr(&r.name)?;
 		Ok(())
@@ -872,6 +915,10 @@ implement_visitor! {
 				this.visit_expr(expr)?;
 				this.visit_expr(expr1)?;
 			},
+			InfoStatement::IndexStats(expr) => {
+				this.visit_expr(expr)?;
+			},
+			InfoStatement::Locks => {},
 		}
 		Ok(())
 	}
@@ -894,6 +941,14 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_with(this, w: &WithStatement){
+		for e in w.exprs.iter(){
+			this.visit_expr(&e.what)?;
+		}
+		this.visit_select(&w.stmt)?;
+		Ok(())
+	}
+
 	fn visit_delete(this, d: &DeleteStatement){
 		for v in d.what.iter(){
 			this.visit_expr(v)?;
@@ -925,12 +980,18 @@ implement_visitor! {
 			DefineStatement::Param(d) => {
 				this.visit_define_param(d)?;
 			},
+			DefineStatement::Query(d) => {
+				this.visit_define_query(d)?;
+			},
 			DefineStatement::Table(d) => {
 				this.visit_define_table(d)?;
 			},
 			DefineStatement::Event(d) => {
 				this.visit_define_event(d)?;
 			},
+			DefineStatement::Policy(d) => {
+				this.visit_define_policy(d)?;
+			},
 			DefineStatement::Field(d) => {
 				this.visit_define_field(d)?;
 			},
@@ -961,6 +1022,9 @@ implement_visitor! {
 			DefineStatement::Module(d) => {
 				this.visit_define_module(d)?;
 			},
+			DefineStatement::Diff(d) => {
+				this.visit_define(d)?;
+			},
 		}
 		Ok(())
 	}
@@ -1132,6 +1196,9 @@ implement_visitor! {
 			this.visit_expr(c)?;
 		}
 		this.visit_expr(&d.comment)?;
+		if let UniqueConflicts::Quarantine(tb) = &d.conflicts {
+			this.visit_expr(tb)?;
+		}
 		Ok(())
 	}
 
@@ -1194,6 +1261,16 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_define_policy(this, d: &DefinePolicyStatement){
+		this.visit_expr(&d.name)?;
+		this.visit_expr(&d.target_table)?;
+		this.visit_expr(&d.when)?;
+		this.visit_expr(&d.destination)?;
+		this.visit_expr(&d.batch)?;
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
 	fn visit_define_table(this, d: &DefineTableStatement){
 		this.visit_expr(&d.name)?;
 		if let Some(v) = d.view.as_ref(){
@@ -1252,6 +1329,16 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_define_query(this, d: &DefineQueryStatement){
+		for (_, k) in d.args.iter(){
+			this.visit_kind(k)?;
+		}
+		this.visit_expr(&d.query)?;
+		this.visit_expr(&d.comment)?;
+		this.visit_permission(&d.permissions)?;
+		Ok(())
+	}
+
 	fn visit_define_analyzer(this, d: &DefineAnalyzerStatement){
 		this.visit_expr(&d.name)?;
 		this.visit_expr(&d.comment)?;
@@ -1409,6 +1496,7 @@ implement_visitor! {
 				Kind::Bytes |
 				Kind::Datetime |
 				Kind::Decimal |
+				Kind::FixedDecimal(_, _) |
 				Kind::Duration |
 				Kind::Float |
 				Kind::Int |
@@ -1613,7 +1701,8 @@ implement_visitor! {
 	fn visit_recurse_instruction(this, r: &RecurseInstruction){
 		match r {
 			RecurseInstruction::Path { ..} |
-				RecurseInstruction::Collect { ..} => {}
+				RecurseInstruction::Collect { ..} |
+				RecurseInstruction::Tree { ..} => {}
 			RecurseInstruction::Shortest { expects, .. } => {
 				this.visit_expr(expects)?;
 			},
@@ -1708,6 +1797,9 @@ implement_visitor_mut! {
 			TopLevelExpr::Access(s) => {this.visit_mut_access(s)? },
 			TopLevelExpr::Kill(s) => {this.visit_mut_kill(s)?; },
 			TopLevelExpr::Live(s) => {this.visit_mut_live(s)?; },
+			TopLevelExpr::LiveSchema(_) => {},
+			TopLevelExpr::Lock(_) => {},
+			TopLevelExpr::Refresh(_) => {},
 		TopLevelExpr::Option(s) =>{ this.visit_mut_option(s)?; },
 		TopLevelExpr::Use(s) => {this.visit_mut_use(s)?; },
 		TopLevelExpr::Show(s) => {this.visit_mut_show(s)?; },
@@ -1721,9 +1813,15 @@ implement_visitor_mut! {
 			AccessStatement::Grant(a) => {
 				this.visit_mut_access_grant(a)?;
 			},
+			AccessStatement::GrantMany(a) => {
+				this.visit_mut_access_grant_many(a)?;
+			},
 			AccessStatement::Show(a) => {
 				this.visit_mut_access_show(a)?;
 			},
+			AccessStatement::ShowSubject(a) => {
+				this.visit_mut_access_show_subject(a)?;
+			},
 			AccessStatement::Revoke(a) => {
 				this.visit_mut_access_revoke(a)?;
 			},
@@ -1739,6 +1837,13 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_access_grant_many(this, a: &mut AccessStatementGrantMany){
+		for s in a.subjects.iter_mut(){
+			this.visit_mut_access_subject(s)?;
+		}
+		Ok(())
+	}
+
 	fn visit_mut_access_subject(this, s: &mut Subject){
 		match s{
 			Subject::Record(r) => {
@@ -1755,6 +1860,10 @@ implement_visitor_mut! {
 		}
 		Ok(())
 	}
+	fn visit_mut_access_show_subject(this, a: &mut AccessStatementShowSubject){
+		this.visit_mut_access_subject(&mut a.subject)?;
+		Ok(())
+	}
 	fn visit_mut_access_revoke(this, a: &mut AccessStatementRevoke){
 		if let Some(c) = a.cond.as_mut(){
 			this.visit_mut_expr(&mut c.0)?;
@@ -1844,6 +1953,7 @@ implement_visitor_mut! {
 				this.visit_mut_if_else(s)?;
 			},
 			Expr::Select(s) => { this.visit_mut_select(s)?; },
+			Expr::With(s) => { this.visit_mut_with(s)?; },
 			Expr::Create(s) => { this.visit_mut_create(s)?; },
 			Expr::Update(s) => { this.visit_mut_update(s)?; },
 			Expr::Upsert(s) => { this.visit_mut_upsert(s)?; },
@@ -2239,6 +2349,9 @@ implement_visitor_mut! {
 			}
 		}
 		this.visit_mut_expr(&mut s.version)?;
+		if let Some(v) = s.valid_time.as_mut(){
+			this.visit_mut_expr(v)?;
+		}
 
 		Ok(())
 	}
@@ -2263,12 +2376,18 @@ implement_visitor_mut! {
 			RemoveStatement::Param(r) => {
 				this.visit_mut_remove_param(r)?;
 			},
+			RemoveStatement::Query(r) => {
+				this.visit_mut_remove_query(r)?;
+			},
 			RemoveStatement::Table(r) => {
 				this.visit_mut_remove_table(r)?;
 			},
 			RemoveStatement::Event(r) => {
 				this.visit_mut_remove_event(r)?;
 			},
+			RemoveStatement::Policy(r) => {
+				this.visit_mut_remove_policy(r)?;
+			},
 			RemoveStatement::Field(r) => {
 				this.visit_mut_remove_field(r)?;
 			},
@@ -2336,6 +2455,10 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_remove_query(this, r: &mut RemoveQueryStatement){
+		Ok(())
+	}
+
 	fn visit_mut_remove_table(this, r: &mut RemoveTableStatement){
 		this.visit_mut_expr(&mut r.name)?;
 		Ok(())
@@ -2346,6 +2469,12 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_remove_policy(this, r: &mut RemovePolicyStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		this.visit_mut_expr(&mut r.table_name)?;
+		Ok(())
+	}
+
 	fn visit_mut_remove_field(this, r: &mut RemoveFieldStatement){
 		this.visit_mut_expr(&mut r.name)?;
 		Ok(())
@@ -2443,6 +2572,10 @@ implement_visitor_mut! {
 				this.visit_mut_expr(expr)?;
 				this.visit_mut_expr(expr1)?;
 			},
+			InfoStatement::IndexStats(expr) => {
+				this.visit_mut_expr(expr)?;
+			},
+			InfoStatement::Locks => {},
 		}
 		Ok(())
 	}
@@ -2465,6 +2598,14 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_with(this, w: &mut WithStatement){
+		for e in w.exprs.iter_mut(){
+			this.visit_mut_expr(&mut e.what)?;
+		}
+		this.visit_mut_select(&mut w.stmt)?;
+		Ok(())
+	}
+
 	fn visit_mut_delete(this, d: &mut DeleteStatement){
 		for v in d.what.iter_mut(){
 			this.visit_mut_expr(v)?;
@@ -2496,12 +2637,18 @@ implement_visitor_mut! {
 			DefineStatement::Param(d) => {
 				this.visit_mut_define_param(d)?;
 			},
+			DefineStatement::Query(d) => {
+				this.visit_mut_define_query(d)?;
+			},
 			DefineStatement::Table(d) => {
 				this.visit_mut_define_table(d)?;
 			},
 			DefineStatement::Event(d) => {
 				this.visit_mut_define_event(d)?;
 			},
+			DefineStatement::Policy(d) => {
+				this.visit_mut_define_policy(d)?;
+			},
 			DefineStatement::Field(d) => {
 				this.visit_mut_define_field(d)?;
 			},
@@ -2532,6 +2679,9 @@ implement_visitor_mut! {
 			DefineStatement::Module(d) => {
 				this.visit_mut_define_module(d)?;
 			},
+			DefineStatement::Diff(d) => {
+				this.visit_mut_define(d)?;
+			},
 		}
 		Ok(())
 	}
@@ -2702,6 +2852,9 @@ implement_visitor_mut! {
 			this.visit_mut_expr(c)?;
 		}
 		this.visit_mut_expr(&mut d.comment)?;
+		if let UniqueConflicts::Quarantine(tb) = &mut d.conflicts {
+			this.visit_mut_expr(tb)?;
+		}
 		Ok(())
 	}
 
@@ -2764,6 +2917,16 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_define_policy(this, d: &mut DefinePolicyStatement){
+		this.visit_mut_expr(&mut d.name)?;
+		this.visit_mut_expr(&mut d.target_table)?;
+		this.visit_mut_expr(&mut d.when)?;
+		this.visit_mut_expr(&mut d.destination)?;
+		this.visit_mut_expr(&mut d.batch)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
 	fn visit_mut_define_table(this, d: &mut DefineTableStatement){
 		this.visit_mut_expr(&mut d.name)?;
 		if let Some(v) = d.view.as_mut(){
@@ -2823,6 +2986,16 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_define_query(this, d: &mut DefineQueryStatement){
+		for (_, k) in d.args.iter_mut(){
+			this.visit_mut_kind(k)?;
+		}
+		this.visit_mut_expr(&mut d.query)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		this.visit_mut_permission(&mut d.permissions)?;
+		Ok(())
+	}
+
 	fn visit_mut_define_analyzer(this, d: &mut DefineAnalyzerStatement){
 		this.visit_mut_expr(&mut d.name)?;
 		this.visit_mut_expr(&mut d.comment)?;
@@ -2991,6 +3164,7 @@ implement_visitor_mut! {
 				Kind::Bytes |
 				Kind::Datetime |
 				Kind::Decimal |
+				Kind::FixedDecimal(_, _) |
 				Kind::Duration |
 				Kind::Float |
 				Kind::Int |
@@ -3195,7 +3369,8 @@ implement_visitor_mut! {
 	fn visit_mut_recurse_instruction(this, r: &mut RecurseInstruction){
 		match r {
 			RecurseInstruction::Path { ..} |
-				RecurseInstruction::Collect { ..} => {}
+				RecurseInstruction::Collect { ..} |
+				RecurseInstruction::Tree { ..} => {}
 			RecurseInstruction::Shortest { expects, .. } => {
 				this.visit_mut_expr(expects)?;
 			},
@@ -6,6 +6,8 @@ use crate::fmt::{EscapeKwFreeIdent, Fmt};
 pub enum With {
 	NoIndex,
 	Index(Vec<String>),
+	/// Include soft deleted records
+	Deleted,
 }
 
 impl ToSql for With {
@@ -13,6 +15,7 @@ impl ToSql for With {
 		f.push_str("WITH");
 		match self {
 			With::NoIndex => f.push_str(" NOINDEX"),
+			With::Deleted => f.push_str(" DELETED"),
 			With::Index(i) => {
 				f.push_str(" INDEX ");
 				Fmt::comma_separated(i.iter().map(|x| EscapeKwFreeIdent(x.as_str())))
@@ -170,6 +170,7 @@ impl ToSql for CoverStmts<'_> {
 
 			sql::Expr::IfElse(_)
 			| sql::Expr::Select(_)
+			| sql::Expr::With(_)
 			| sql::Expr::Create(_)
 			| sql::Expr::Update(_)
 			| sql::Expr::Upsert(_)
@@ -0,0 +1,18 @@
+use anyhow::Result;
+
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::val::Value;
+
+/// Return the current versionstamp of the selected database.
+///
+/// This is the safe watermark of the underlying datastore: every transaction
+/// committed at or below it is final and visible. It never decreases, so it
+/// can be used as a consistent cut point when consuming changefeeds or
+/// running incremental exports.
+pub async fn versionstamp((ctx, opt): (&FrozenContext, &Options), _: ()) -> Result<Value> {
+	// A versionstamp is always relative to a database
+	ctx.expect_ns_db_ids(opt).await?;
+	let vs = ctx.tx().safe_timestamp().await?.as_versionstamp();
+	Ok(Value::try_from(vs)?)
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
 
 use super::args::Optional;
+#[cfg(feature = "http")]
+use crate::catalog::ApiMethod;
 use crate::ctx::FrozenContext;
 use crate::err::Error;
 use crate::val::Value;
@@ -102,6 +104,9 @@ pub async fn put(
 ) -> Result<Value> {
 	let uri = try_as_uri("http::put", uri)?;
 	let opts = try_as_opts("http::put", "The third argument should be an object.", opts)?;
+	if let Some(outbox) = ctx.outbox() {
+		return outbox.record(ctx, ApiMethod::Put, uri, body, opts).await;
+	}
 	crate::fnc::util::http::put(ctx, uri, body.unwrap_or(Value::Null), opts).await
 }
 
@@ -112,6 +117,9 @@ pub async fn post(
 ) -> Result<Value> {
 	let uri = try_as_uri("http::post", uri)?;
 	let opts = try_as_opts("http::post", "The third argument should be an object.", opts)?;
+	if let Some(outbox) = ctx.outbox() {
+		return outbox.record(ctx, ApiMethod::Post, uri, body, opts).await;
+	}
 	crate::fnc::util::http::post(ctx, uri, body.unwrap_or(Value::Null), opts).await
 }
 
@@ -122,6 +130,9 @@ pub async fn patch(
 ) -> Result<Value> {
 	let uri = try_as_uri("http::patch", uri)?;
 	let opts = try_as_opts("http::patch", "The third argument should be an object.", opts)?;
+	if let Some(outbox) = ctx.outbox() {
+		return outbox.record(ctx, ApiMethod::Patch, uri, body, opts).await;
+	}
 	crate::fnc::util::http::patch(ctx, uri, body.unwrap_or(Value::Null), opts).await
 }
 
@@ -132,5 +143,8 @@ pub async fn delete(
 ) -> Result<Value> {
 	let uri = try_as_uri("http::delete", uri)?;
 	let opts = try_as_opts("http::delete", "The second argument should be an object.", opts)?;
+	if let Some(outbox) = ctx.outbox() {
+		return outbox.record(ctx, ApiMethod::Delete, uri, None, opts).await;
+	}
 	crate::fnc::util::http::delete(ctx, uri, opts).await
 }
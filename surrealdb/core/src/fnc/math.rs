@@ -1,10 +1,13 @@
 use core::f64;
 
 use anyhow::{Result, bail, ensure};
+use rust_decimal::{Decimal, RoundingStrategy};
 use surrealdb_types::ToSql;
 
 use crate::cnf::GENERATION_ALLOCATION_LIMIT;
 use crate::err::Error;
+use crate::expr::Kind;
+use crate::fnc::args::Optional;
 use crate::fnc::util;
 use crate::fnc::util::math::bottom::Bottom;
 use crate::fnc::util::math::interquartile::Interquartile;
@@ -75,6 +78,25 @@ pub fn cot((arg,): (Number,)) -> Result<Value> {
 	Ok(arg.cot().into())
 }
 
+pub fn decimal_mean((array,): (Vec<Number>,)) -> Result<Value> {
+	let name = "math::decimal_mean";
+	let count = array.len();
+	let mut sum = Decimal::ZERO;
+	for v in array {
+		sum = exact_add(name, sum, exact_decimal(name, v)?)?;
+	}
+	exact_mean(name, sum, count)
+}
+
+pub fn decimal_sum((array,): (Vec<Number>,)) -> Result<Value> {
+	let name = "math::decimal_sum";
+	let mut sum = Decimal::ZERO;
+	for v in array {
+		sum = exact_add(name, sum, exact_decimal(name, v)?)?;
+	}
+	Ok(sum.into())
+}
+
 pub fn deg2rad((arg,): (Number,)) -> Result<Value> {
 	Ok(arg.deg2rad().into())
 }
@@ -187,8 +209,42 @@ pub fn rad2deg((arg,): (Number,)) -> Result<Value> {
 	Ok(arg.rad2deg().into())
 }
 
-pub fn round((arg,): (Number,)) -> Result<Value> {
-	Ok(arg.round().into())
+pub fn round(
+	(arg, Optional(precision), Optional(mode)): (Number, Optional<i64>, Optional<String>),
+) -> Result<Value> {
+	if precision.is_none() && mode.is_none() {
+		return Ok(arg.round().into());
+	}
+	let max = Kind::MAX_DECIMAL_PRECISION;
+	let precision = precision.unwrap_or(0);
+	ensure!(
+		(0..=max as i64).contains(&precision),
+		Error::InvalidFunctionArguments {
+			name: String::from("math::round"),
+			message: format!("The second argument must be an integer between 0 and {max}."),
+		}
+	);
+	let strategy = mode.map(|mode| rounding_strategy(&mode)).transpose()?;
+	Ok(arg.round_dp(precision as u32, strategy).into())
+}
+
+/// Parses the rounding mode of `math::round`
+fn rounding_strategy(mode: &str) -> Result<RoundingStrategy> {
+	Ok(match mode.to_ascii_uppercase().as_str() {
+		"HALF_EVEN" => RoundingStrategy::MidpointNearestEven,
+		"HALF_UP" => RoundingStrategy::MidpointAwayFromZero,
+		"HALF_DOWN" => RoundingStrategy::MidpointTowardZero,
+		"UP" => RoundingStrategy::AwayFromZero,
+		"DOWN" => RoundingStrategy::ToZero,
+		"CEILING" => RoundingStrategy::ToPositiveInfinity,
+		"FLOOR" => RoundingStrategy::ToNegativeInfinity,
+		_ => bail!(Error::InvalidFunctionArguments {
+			name: String::from("math::round"),
+			message: String::from(
+				"The third argument must be one of 'HALF_EVEN', 'HALF_UP', 'HALF_DOWN', 'UP', 'DOWN', 'CEILING' or 'FLOOR'."
+			),
+		}),
+	})
 }
 
 pub fn sign((arg,): (Number,)) -> Result<Value> {
@@ -218,6 +274,7 @@ pub fn stddev((array,): (Vec<Number>,)) -> Result<Value> {
 pub fn sum((array,): (Vec<Number>,)) -> Result<Value> {
 	Ok(array.into_iter().sum::<Number>().into())
 }
+
 pub fn tan((arg,): (Number,)) -> Result<Value> {
 	Ok(arg.tan().into())
 }
@@ -240,3 +297,37 @@ pub fn trimean((mut array,): (Vec<Number>,)) -> Result<Value> {
 pub fn variance((array,): (Vec<Number>,)) -> Result<Value> {
 	util::math::variance(&array).map(Value::Number)
 }
+
+/// Converts a number of an exact aggregation to a decimal, refusing floats
+pub(crate) fn exact_decimal(name: &str, number: Number) -> Result<Decimal> {
+	match number {
+		Number::Int(v) => Ok(Decimal::from(v)),
+		Number::Decimal(v) => Ok(v),
+		Number::Float(_) => bail!(Error::InvalidFunctionArguments {
+			name: name.to_owned(),
+			message: format!(
+				"Expected only integers and decimals, but found the float `{}`. Convert it explicitly with `<decimal>` to include it.",
+				number.to_sql()
+			),
+		}),
+	}
+}
+
+/// Adds decimals exactly, failing rather than losing precision on overflow
+pub(crate) fn exact_add(name: &str, a: Decimal, b: Decimal) -> Result<Decimal> {
+	match a.checked_add(b) {
+		Some(v) => Ok(v),
+		None => bail!(Error::ArithmeticOverflow(format!("{name}(..)"))),
+	}
+}
+
+/// Divides the exact sum of a number of decimals by their count
+pub(crate) fn exact_mean(name: &str, sum: Decimal, count: usize) -> Result<Value> {
+	if count == 0 {
+		return Ok(Value::None);
+	}
+	match sum.checked_div(Decimal::from(count)) {
+		Some(mean) => Ok(mean.normalize().into()),
+		None => bail!(Error::ArithmeticOverflow(format!("{name}(..)"))),
+	}
+}
@@ -16,6 +16,7 @@ pub mod array;
 pub mod bytes;
 pub mod count;
 pub mod crypto;
+pub mod db;
 pub mod duration;
 pub mod encoding;
 pub mod eval;
@@ -27,6 +28,7 @@ pub mod not;
 pub mod object;
 pub mod operate;
 pub mod parse;
+pub mod query;
 pub mod rand;
 pub mod record;
 pub mod schema;
@@ -77,6 +79,7 @@ pub async fn run(
 		|| name.eq("file::rename")
 		|| name.eq("file::rename_if_not_exists")
 		|| name.eq("file::list")
+		|| name.eq("meta::lineage")
 		|| name.eq("record::exists")
 		|| name.eq("record::is_edge")
 		|| name.eq("set::all")
@@ -92,6 +95,8 @@ pub async fn run(
 		|| name.eq("value::expect")
 		|| name.eq("value::patch")
 		|| name.eq("sequence::nextval")
+		|| name.eq("db::versionstamp")
+		|| name.eq("query::run")
 		|| name.starts_with("eval::")
 		|| name.starts_with("api")
 		|| name.starts_with("http")
@@ -100,7 +105,10 @@ pub async fn run(
 		|| name.starts_with("crypto::bcrypt")
 		|| name.starts_with("crypto::pbkdf2")
 		|| name.starts_with("crypto::scrypt")
+		|| name.eq("schema::openapi")
 		|| name.eq("schema::table::exists")
+		|| name.eq("schema::table::stats")
+		|| name.starts_with("schema::advisor::")
 	{
 		stk.run(|stk| asynchronous(stk, ctx, opt, doc, name, args)).await
 	} else {
@@ -267,6 +275,8 @@ pub fn synchronous(
 		"math::clamp" => math::clamp,
 		"math::cos" => math::cos,
 		"math::cot" => math::cot,
+		"math::decimal_mean" => math::decimal_mean,
+		"math::decimal_sum" => math::decimal_sum,
 		"math::deg2rad" => math::deg2rad,
 		"math::fixed" => math::fixed,
 		"math::floor" => math::floor,
@@ -338,6 +348,7 @@ pub fn synchronous(
 		"rand::uuid::v7" => rand::uuid::v7,
 		"rand::uuid" => rand::uuid,
 		//
+		"record::created_at" => record::created_at,
 		"record::id" => record::id,
 		"record::table" => record::tb,
 		"record::tb" => record::tb,
@@ -626,6 +637,8 @@ pub async fn asynchronous(
 		"http::patch" => http::patch(ctx).await,
 		"http::delete" => http::delete(ctx).await,
 		//
+		"meta::lineage" => record::lineage((stk, ctx, Some(opt), doc)).await,
+		//
 		"record::exists" => record::exists((stk, ctx, Some(opt), doc)).await,
 		"record::is_edge" => record::is::edge((stk, ctx, Some(opt), doc)).await,
 		//
@@ -648,13 +661,22 @@ pub async fn asynchronous(
 		//
 		"sequence::nextval" => sequence::nextval((ctx, opt)).await,
 		//
+		"db::versionstamp" => db::versionstamp((ctx, opt)).await,
+		//
 		"type::field" => r#type::field((stk, ctx, Some(opt), doc)).await,
 		"type::fields" => r#type::fields((stk, ctx, Some(opt), doc)).await,
 		//
 		"value::diff" => value::diff.await,
 		"value::expect" => value::expect((stk, ctx, Some(opt), doc)).await,
 		"value::patch" => value::patch.await,
+		"schema::openapi" => schema::openapi((ctx, Some(opt))).await,
 		"schema::table::exists" => schema::table::exists((ctx, Some(opt))).await,
+		"schema::table::stats" => schema::table::stats((ctx, Some(opt))).await,
+		"schema::advisor::start" => schema::advisor::start((ctx, Some(opt))).await,
+		"schema::advisor::stop" => schema::advisor::stop((ctx, Some(opt))).await,
+		"schema::advisor::recommendations" => schema::advisor::recommendations((ctx, Some(opt))).await,
+		//
+		"query::run" => query::run((stk, ctx, opt)).await,
 	)
 }
 
@@ -1114,6 +1136,7 @@ pub async fn idiom(
 				//
 				"exists" => record::exists((stk, ctx, Some(opt), doc)).await,
 				"is_edge" => record::is::edge((stk, ctx, Some(opt), doc)).await,
+				"created_at" => record::created_at,
 				"id" => record::id,
 				"table" => record::tb,
 				"tb" => record::tb,
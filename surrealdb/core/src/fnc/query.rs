@@ -0,0 +1,84 @@
+//! `query::run` — run a query template defined with `DEFINE QUERY`.
+//!
+//! The template is run with the arguments passed by name in an object. Each
+//! argument is coerced to the kind it was declared with, and is bound as a
+//! parameter of an isolated context, so the template never sees the call
+//! site's parameters. Like a custom function, the template runs with the auth
+//! of the user which defined it, limited to that of the caller.
+//!
+//! The template is stored already parsed, so running it never parses it again.
+//! The streaming executor also caches the plan of each version of a template,
+//! and only computes the template here when it can not be planned.
+
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use crate::catalog::QueryDefinition;
+use crate::ctx::{Context, FrozenContext};
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::FlowResultExt as _;
+use crate::expr::function::is_permitted;
+use crate::fnc::args::Optional;
+use crate::iam::{Action, AuthLimit};
+use crate::val::{Object, Value};
+
+/// Run the query template with the given name.
+///
+/// ```surql
+/// query::run("recent_orders", { user: user:tobie });
+/// ```
+pub async fn run(
+	(stk, ctx, opt): (&mut Stk, &FrozenContext, &Options),
+	(name, Optional(args)): (String, Optional<Object>),
+) -> Result<Value> {
+	// Get the query definition
+	let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+	let val = ctx.tx().get_db_query(ns, db, &name).await?;
+	let opt = AuthLimit::try_from(&val.auth_limit)?.limit_opt(opt);
+	// Check permissions
+	if ctx.check_perms(&opt, Action::View)?
+		&& !is_permitted(stk, ctx, &opt, None, &val.permissions).await.catch_return()?
+	{
+		bail!(Error::QueryPermissions {
+			name,
+		});
+	}
+	// Bind the arguments to an isolated context
+	let ctx = bind_args(ctx, &val, &name, args)?;
+	// Run the query
+	stk.run(|stk| val.query.compute(stk, &ctx, &opt, None)).await.catch_return()
+}
+
+/// Creates a context which is isolated from the call site, with the arguments
+/// of the query template bound as parameters.
+///
+/// Each argument is coerced to the kind it was declared with, and an argument
+/// which the template does not declare is an error.
+pub(crate) fn bind_args(
+	ctx: &FrozenContext,
+	val: &QueryDefinition,
+	name: &str,
+	args: Option<Object>,
+) -> Result<FrozenContext> {
+	let mut args = args.unwrap_or_default();
+	let mut ctx = Context::new_isolated(ctx);
+	for (param, kind) in val.args.iter() {
+		let value = args.remove(param).unwrap_or_default().coerce_to_kind(kind).map_err(|e| {
+			Error::InvalidQueryArguments {
+				name: name.to_owned(),
+				message: format!("Failed to coerce argument `${param}`: {e}"),
+			}
+		})?;
+		ctx.add_value(param.clone(), Arc::new(value));
+	}
+	if let Some(param) = args.keys().next() {
+		bail!(Error::InvalidQueryArguments {
+			name: name.to_owned(),
+			message: format!("The query has no argument `${param}`"),
+		});
+	}
+	Ok(ctx.freeze())
+}
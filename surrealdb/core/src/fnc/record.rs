@@ -29,10 +29,46 @@ pub async fn exists(
 	}
 }
 
+pub async fn lineage(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, Option<&Options>, Option<&CursorDoc>),
+	(arg, field): (RecordId, String),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	// Only reveal the lineage of records which can be selected
+	let v = match Value::RecordId(arg.clone())
+		.get(stk, ctx, opt, doc, ID.as_ref())
+		.await
+		.catch_return()
+	{
+		Ok(v) => v,
+		// An undefined table means the record cannot exist.
+		Err(e) if matches!(e.downcast_ref(), Some(Error::TbNotFound { .. })) => {
+			return Ok(Value::None);
+		}
+		Err(e) => return Err(e),
+	};
+	if v.is_none() {
+		return Ok(Value::None);
+	}
+	// Fetch the lineage sidecar of the record
+	let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+	let key = crate::key::table::ln::new(ns, db, &arg.table, &arg.key);
+	let lineage = ctx.tx().get(&key, None).await?;
+	Ok(lineage.and_then(|l| l.get(&field).map(|e| e.to_value())).unwrap_or_default())
+}
+
 pub fn id((arg,): (RecordId,)) -> Result<Value> {
 	Ok(arg.key.into_value())
 }
 
+/// Returns the time at which a record was created, for records whose id is a
+/// V7 UUID, or an array starting with a datetime
+pub fn created_at((arg,): (RecordId,)) -> Result<Value> {
+	Ok(arg.key.created_at().map(Value::Datetime).unwrap_or_default())
+}
+
 pub fn tb((arg,): (RecordId,)) -> Result<Value> {
 	Ok(Value::String(arg.table.into()))
 }
@@ -1,3 +1,25 @@
+use anyhow::Result;
+
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::expr::Base;
+use crate::iam::{Action, ResourceKind};
+use crate::val::Value;
+
+/// Describes the HTTP key-value endpoints of the tables of the current
+/// database as an OpenAPI document
+pub async fn openapi((ctx, opt): (&FrozenContext, Option<&Options>), _: ()) -> Result<Value> {
+	if let Some(opt) = opt {
+		opt.valid_for_db()?;
+		ctx.is_allowed(opt, Action::View, ResourceKind::Table, Base::Db)?;
+		let ids = ctx.expect_ns_db_ids(opt).await?;
+		let txn = ctx.tx();
+		crate::catalog::openapi::document(&txn, ids, opt.ns_db()?).await
+	} else {
+		Ok(Value::None)
+	}
+}
+
 pub mod table {
 	use anyhow::Result;
 
@@ -24,4 +46,74 @@ pub mod table {
 			Ok(Value::None)
 		}
 	}
+
+	pub async fn stats(
+		(ctx, opt): (&FrozenContext, Option<&Options>),
+		(arg,): (String,),
+	) -> Result<Value> {
+		if let Some(opt) = opt {
+			opt.valid_for_db()?;
+			ctx.is_allowed(opt, Action::View, ResourceKind::Table, Base::Db)?;
+			let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+			let txn = ctx.tx();
+			let tb: TableName = arg.into();
+			txn.expect_tb(ns, db, &tb).await?;
+			crate::kvs::stats::fetch(&txn, ns, db, &tb).await
+		} else {
+			Ok(Value::None)
+		}
+	}
+}
+
+pub mod advisor {
+	use anyhow::Result;
+
+	use crate::ctx::FrozenContext;
+	use crate::dbs::Options;
+	use crate::err::Error;
+	use crate::expr::Base;
+	use crate::iam::{Action, ResourceKind};
+	use crate::kvs::IndexAdvisor;
+	use crate::val::Value;
+
+	fn advisor(ctx: &FrozenContext) -> Result<&IndexAdvisor> {
+		ctx.get_index_advisor().ok_or_else(|| {
+			anyhow::Error::new(Error::unreachable("The index advisor is not available"))
+		})
+	}
+
+	pub async fn start((ctx, opt): (&FrozenContext, Option<&Options>), _: ()) -> Result<Value> {
+		if let Some(opt) = opt {
+			opt.valid_for_db()?;
+			ctx.is_allowed(opt, Action::Edit, ResourceKind::Index, Base::Db)?;
+			let (ns, db) = opt.ns_db()?;
+			advisor(ctx)?.start(ns, db);
+		}
+		Ok(Value::None)
+	}
+
+	pub async fn stop((ctx, opt): (&FrozenContext, Option<&Options>), _: ()) -> Result<Value> {
+		if let Some(opt) = opt {
+			opt.valid_for_db()?;
+			ctx.is_allowed(opt, Action::Edit, ResourceKind::Index, Base::Db)?;
+			let (ns, db) = opt.ns_db()?;
+			advisor(ctx)?.stop(ns, db);
+		}
+		Ok(Value::None)
+	}
+
+	pub async fn recommendations(
+		(ctx, opt): (&FrozenContext, Option<&Options>),
+		_: (),
+	) -> Result<Value> {
+		if let Some(opt) = opt {
+			opt.valid_for_db()?;
+			ctx.is_allowed(opt, Action::View, ResourceKind::Index, Base::Db)?;
+			let ids = ctx.expect_ns_db_ids(opt).await?;
+			let txn = ctx.tx();
+			advisor(ctx)?.recommendations(&txn, opt.ns_db()?, ids).await
+		} else {
+			Ok(Value::None)
+		}
+	}
 }
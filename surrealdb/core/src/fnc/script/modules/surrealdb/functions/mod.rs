@@ -21,6 +21,7 @@ mod math;
 mod meta;
 mod object;
 mod parse;
+mod query;
 mod rand;
 mod record;
 mod schema;
@@ -55,6 +56,7 @@ impl_module_def!(
 	"not" => run,
 	"object" => (object::Package),
 	"parse" => (parse::Package),
+	"query" => (query::Package),
 	"rand" => (rand::Package),
 	"record" => (record::Package),
 	"search" => (search::Package),
@@ -1,11 +1,17 @@
+use js::prelude::Async;
+
+use super::fut;
 use crate::fnc::script::modules::impl_module_def;
 
+mod advisor;
 mod table;
 
 pub struct Package;
 
 impl_module_def!(
 	Package,
-	"string",
+	"schema",
+	"advisor" => (advisor::Package),
+	"openapi" => fut Async,
 	"table" => (table::Package)
 );
@@ -0,0 +1,14 @@
+use js::prelude::Async;
+
+use super::super::fut;
+use crate::fnc::script::modules::impl_module_def;
+
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"schema::advisor",
+	"recommendations" => fut Async,
+	"start" => fut Async,
+	"stop" => fut Async
+);
@@ -8,5 +8,6 @@ pub struct Package;
 impl_module_def!(
 	Package,
 	"schema::table",
-	"exists" => fut Async
+	"exists" => fut Async,
+	"stats" => fut Async
 );
@@ -557,7 +557,7 @@ fn kind_to_type_inner(
 		Kind::Bool => TypeRef::named(TypeRef::BOOLEAN),
 		Kind::Bytes => TypeRef::named("bytes"),
 		Kind::Datetime => TypeRef::named("datetime"),
-		Kind::Decimal => TypeRef::named("decimal"),
+		Kind::Decimal | Kind::FixedDecimal(_, _) => TypeRef::named("decimal"),
 		Kind::Duration => TypeRef::named("duration"),
 		Kind::Float => TypeRef::named(TypeRef::FLOAT),
 		Kind::Int => TypeRef::named(TypeRef::INT),
@@ -1004,7 +1004,7 @@ pub(crate) fn graphql_to_sql_kind_with_scope(
 			},
 			_ => Err(type_error(kind, val)),
 		},
-		Kind::Decimal => match val {
+		Kind::Decimal | Kind::FixedDecimal(_, _) => match val {
 			GraphqlValue::Number(n) => {
 				if let Some(int) = n.as_i64() {
 					Ok(SurValue::Number(SurNumber::Decimal(int.into())))
@@ -356,6 +356,7 @@ fn select_all_from_record(rid: &RecordId, version: &Option<Datetime>) -> SelectS
 		only: true,
 		version: version_to_expr(version),
 		timeout: Expr::Literal(Literal::None),
+		partial: false,
 		omit: vec![],
 		with: None,
 		cond: None,
@@ -367,6 +368,8 @@ fn select_all_from_record(rid: &RecordId, version: &Option<Datetime>) -> SelectS
 		fetch: None,
 		explain: None,
 		tempfiles: false,
+		valid_time: None,
+		scan_limit: None,
 	}
 }
 
@@ -389,6 +392,7 @@ fn select_field_from_record(
 		only: true,
 		version: version_to_expr(version),
 		timeout: Expr::Literal(Literal::None),
+		partial: false,
 		omit: vec![],
 		with: None,
 		cond: None,
@@ -400,6 +404,8 @@ fn select_field_from_record(
 		fetch: None,
 		explain: None,
 		tempfiles: false,
+		valid_time: None,
+		scan_limit: None,
 	}
 }
 
@@ -424,6 +430,7 @@ fn select_all_from_table(
 		start,
 		version: version_to_expr(version),
 		timeout: Expr::Literal(Literal::None),
+		partial: false,
 		omit: vec![],
 		only: false,
 		with: None,
@@ -432,6 +439,8 @@ fn select_all_from_table(
 		fetch: None,
 		explain: None,
 		tempfiles: false,
+		valid_time: None,
+		scan_limit: None,
 	}
 }
 
@@ -1953,7 +1962,7 @@ fn filter_from_type(
 			let list_ty = TypeRef::named_nn_list("number");
 			filter_impl!(filter, list_ty, "in");
 		}
-		Kind::Decimal => {
+		Kind::Decimal | Kind::FixedDecimal(_, _) => {
 			let num_ty = TypeRef::named("decimal");
 			filter_impl!(filter, num_ty, "gt");
 			filter_impl!(filter, num_ty, "gte");
@@ -2683,13 +2692,19 @@ fn strip_option(kind: &Kind) -> Kind {
 fn numeric_array_inner(kind: &Kind) -> Option<Kind> {
 	match kind {
 		Kind::Array(inner, _) => match inner.as_ref() {
-			Kind::Float | Kind::Int | Kind::Number | Kind::Decimal => Some(*inner.clone()),
+			Kind::Float | Kind::Int | Kind::Number | Kind::Decimal | Kind::FixedDecimal(_, _) => {
+				Some(*inner.clone())
+			}
 			Kind::Either(ks) => {
 				let non_none: Vec<&Kind> =
 					ks.iter().filter(|k| !matches!(k, Kind::None | Kind::Null)).collect();
 				if non_none.len() == 1
-					&& matches!(non_none[0], Kind::Float | Kind::Int | Kind::Number | Kind::Decimal)
-				{
+					&& matches!(
+						non_none[0],
+						Kind::Float
+							| Kind::Int | Kind::Number
+							| Kind::Decimal | Kind::FixedDecimal(_, _)
+					) {
 					Some(non_none[0].clone())
 				} else {
 					None
@@ -2913,12 +2928,17 @@ struct AggregateRow(SurObject);
 /// through `Either`/`Option` once.
 fn is_numeric_kind(kind: &Kind) -> bool {
 	match kind {
-		Kind::Float | Kind::Int | Kind::Number | Kind::Decimal => true,
+		Kind::Float | Kind::Int | Kind::Number | Kind::Decimal | Kind::FixedDecimal(_, _) => true,
 		Kind::Either(ks) => {
 			let non_none: Vec<&Kind> =
 				ks.iter().filter(|k| !matches!(k, Kind::None | Kind::Null)).collect();
 			non_none.len() == 1
-				&& matches!(non_none[0], Kind::Float | Kind::Int | Kind::Number | Kind::Decimal)
+				&& matches!(
+					non_none[0],
+					Kind::Float
+						| Kind::Int | Kind::Number
+						| Kind::Decimal | Kind::FixedDecimal(_, _)
+				)
 		}
 		_ => false,
 	}
@@ -2928,7 +2948,9 @@ fn is_numeric_kind(kind: &Kind) -> bool {
 /// `Kind::Number` as a fallback.
 fn numeric_kind(kind: &Kind) -> Kind {
 	match kind {
-		Kind::Float | Kind::Int | Kind::Number | Kind::Decimal => kind.clone(),
+		Kind::Float | Kind::Int | Kind::Number | Kind::Decimal | Kind::FixedDecimal(_, _) => {
+			kind.clone()
+		}
 		Kind::Either(ks) => {
 			let non_none: Vec<Kind> =
 				ks.iter().filter(|k| !matches!(k, Kind::None | Kind::Null)).cloned().collect();
@@ -3175,6 +3197,7 @@ fn make_table_aggregate_field(
 				start: None,
 				version: version_to_expr(&None),
 				timeout: Expr::Literal(Literal::None),
+				partial: false,
 				omit: vec![],
 				only: false,
 				with: None,
@@ -3182,6 +3205,8 @@ fn make_table_aggregate_field(
 				fetch: None,
 				explain: None,
 				tempfiles: false,
+				valid_time: None,
+				scan_limit: None,
 			};
 			let res = execute_select(&kvs, sess, stmt).await?;
 
@@ -3371,6 +3396,7 @@ async fn run_connection_total_count(
 		group: Some(Groups(Vec::new())),
 		version: version_to_expr(&q.version),
 		timeout: Expr::Literal(Literal::None),
+		partial: false,
 		omit: vec![],
 		only: false,
 		with: None,
@@ -3381,6 +3407,8 @@ async fn run_connection_total_count(
 		fetch: None,
 		explain: None,
 		tempfiles: false,
+		valid_time: None,
+		scan_limit: None,
 	};
 	let res = execute_select(&q.kvs, sess, stmt).await?;
 	let arr = match res {
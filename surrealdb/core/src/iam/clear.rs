@@ -10,5 +10,6 @@ pub fn clear(session: &mut Session) -> Result<()> {
 	session.tk = None;
 	session.ac = None;
 	session.rd = None;
+	session.impersonator = None;
 	Ok(())
 }
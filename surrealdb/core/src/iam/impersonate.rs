@@ -0,0 +1,316 @@
+//! Impersonation of record and system users by administrators.
+//!
+//! An owner or editor at the root or namespace level may run the commands of a
+//! session under the authentication of another user, in order to see exactly
+//! what that user sees when supporting or debugging an application. The
+//! administrator's own session is kept on [`Session::impersonator`] while
+//! impersonating, and is restored when impersonation stops. The impersonator
+//! is attached to the audit events of every statement run in the meantime.
+//!
+//! Impersonation is an experimental capability, which must be enabled with
+//! [`ExperimentalTarget::Impersonation`].
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use surrealdb_types::ToSql;
+
+use super::{Actor, Auth, Level, Role};
+use crate::catalog;
+use crate::catalog::providers::{
+	AuthorisationProvider, DatabaseProvider, NamespaceProvider, UserProvider,
+};
+use crate::dbs::Session;
+use crate::dbs::capabilities::ExperimentalTarget;
+use crate::err::Error;
+use crate::iam::Error as IamError;
+use crate::kvs::Datastore;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::types::{PublicValue, PublicVariables};
+
+/// Starts impersonating the user described by `vars`.
+///
+/// A record user is described by `ns`, `db`, `ac`, and the record `id`, and a
+/// system user by `user`, along with `ns` and `db` for namespace and database
+/// users. The session is only changed once the subject has been resolved.
+pub async fn impersonate(
+	kvs: &Datastore,
+	session: &mut Session,
+	vars: PublicVariables,
+) -> Result<()> {
+	// Check that impersonation is enabled
+	if !kvs.get_capabilities().allows_experimental(&ExperimentalTarget::Impersonation) {
+		bail!(Error::InvalidImpersonation(
+			"the experimental capability `impersonation` is not enabled".to_owned()
+		));
+	}
+	// Impersonation can not be nested
+	ensure_not_impersonating(session)?;
+	// Parse the specified variables
+	let ns = string(&vars, "ns")?;
+	let db = string(&vars, "db")?;
+	let ac = string(&vars, "ac")?;
+	let subject = match (ns, db, ac) {
+		// A record user of a database access method
+		(Some(ns), Some(db), Some(ac)) => {
+			let Some(rid @ PublicValue::RecordId(_)) = vars.get("id").cloned() else {
+				bail!(Error::InvalidImpersonation(
+					"a record user requires a record `id`".to_owned()
+				));
+			};
+			check_allowed(&session.au, &Level::Record(ns.clone(), db.clone(), ac.clone()), None)?;
+			// Ensure that the record access method exists
+			record_access(kvs, &ns, &db, &ac).await?;
+			Subject::Record {
+				ns,
+				db,
+				ac,
+				rid,
+			}
+		}
+		// A system user at the root, a namespace, or a database
+		(ns, db, None) => {
+			let Some(user) = string(&vars, "user")? else {
+				bail!(Error::InvalidImpersonation("a system user requires a `user`".to_owned()));
+			};
+			let level = match (ns, db) {
+				(None, None) => Level::Root,
+				(Some(ns), None) => Level::Namespace(ns),
+				(Some(ns), Some(db)) => Level::Database(ns, db),
+				(None, Some(_)) => bail!(Error::NsEmpty),
+			};
+			// Check the scope before revealing whether the user exists
+			check_allowed(&session.au, &level, None)?;
+			let roles = system_user(kvs, &level, &user)
+				.await?
+				.roles
+				.iter()
+				.map(|x| Role::from_str(x))
+				.collect::<Result<Vec<_>, _>>()
+				.map_err(Error::from)?;
+			check_allowed(&session.au, &level, roles.iter().max())?;
+			Subject::System(Auth::new(Actor::new(user, roles, level)))
+		}
+		_ => bail!(Error::NoSigninTarget),
+	};
+	// Keep the session of the administrator
+	let impersonator = Arc::new(session.clone());
+	// Switch the session to the subject
+	match subject {
+		Subject::Record {
+			ns,
+			db,
+			ac,
+			rid,
+		} => {
+			session.au = Arc::new(Auth::for_record(rid.to_sql(), &ns, &db, &ac));
+			session.ns = Some(ns);
+			session.db = Some(db);
+			session.ac = Some(ac);
+			session.rd = Some(rid);
+		}
+		Subject::System(au) => {
+			match au.level() {
+				Level::Namespace(ns) => {
+					session.ns = Some(ns.clone());
+				}
+				Level::Database(ns, db) => {
+					session.ns = Some(ns.clone());
+					session.db = Some(db.clone());
+				}
+				_ => {}
+			}
+			session.au = Arc::new(au);
+			session.ac = None;
+			session.rd = None;
+		}
+	}
+	// The subject has no token, and the session expires with the administrator's
+	session.tk = None;
+	session.impersonator = Some(impersonator);
+	Ok(())
+}
+
+/// Stops impersonating, restoring the authentication of the administrator.
+///
+/// Does nothing when the session is not impersonating a user.
+pub fn stop(session: &mut Session) -> Result<()> {
+	if let Some(impersonator) = session.impersonator.take() {
+		session.au = impersonator.au.clone();
+		session.ns.clone_from(&impersonator.ns);
+		session.db.clone_from(&impersonator.db);
+		session.ac.clone_from(&impersonator.ac);
+		session.tk.clone_from(&impersonator.tk);
+		session.rd.clone_from(&impersonator.rd);
+		session.exp = impersonator.exp;
+	}
+	Ok(())
+}
+
+/// Ensures that the session is not impersonating a user.
+///
+/// The authentication of a session can not be changed while impersonating, as
+/// stopping would otherwise restore the administrator over the new user.
+pub fn ensure_not_impersonating(session: &Session) -> Result<()> {
+	if session.impersonator.is_some() {
+		bail!(Error::InvalidImpersonation(
+			"stop impersonating before changing the authentication of the session".to_owned()
+		));
+	}
+	Ok(())
+}
+
+/// Fetches an optional string variable
+fn string(vars: &PublicVariables, key: &str) -> Result<Option<String>> {
+	match vars.get(key) {
+		None | Some(PublicValue::None | PublicValue::Null) => Ok(None),
+		Some(v) => Ok(Some(v.clone().into_string()?)),
+	}
+}
+
+/// The user being impersonated
+enum Subject {
+	Record {
+		ns: String,
+		db: String,
+		ac: String,
+		rid: PublicValue,
+	},
+	System(Auth),
+}
+
+/// Checks that `au` may impersonate a user at `level` with the role `role`.
+///
+/// Only owners and editors at the root or namespace level may impersonate,
+/// only users within their own level, and only users whose role is no higher
+/// than their own.
+fn check_allowed(au: &Auth, level: &Level, role: Option<&Role>) -> Result<()> {
+	let in_scope = match au.level() {
+		Level::Root => true,
+		Level::Namespace(n) => match level {
+			Level::Namespace(ns) | Level::Database(ns, _) | Level::Record(ns, _, _) => ns == n,
+			_ => false,
+		},
+		_ => false,
+	};
+	let role_allowed = match role {
+		Some(role) => au.max_role().is_some_and(|max| *role <= max),
+		None => true,
+	};
+	if !in_scope || !role_allowed || !au.has_editor_role() {
+		bail!(Error::IamError(IamError::NotAllowed {
+			actor: au.id().to_owned(),
+			action: "Impersonate".to_owned(),
+			resource: level.to_string(),
+		}));
+	}
+	Ok(())
+}
+
+/// Fetches the definition of a system user
+async fn system_user(
+	kvs: &Datastore,
+	level: &Level,
+	user: &str,
+) -> Result<Arc<catalog::UserDefinition>> {
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	let found = match level {
+		Level::Root => catch!(tx, tx.get_root_user(user, None).await),
+		Level::Namespace(ns) => {
+			let ns_def = catch!(tx, tx.expect_ns_by_name(ns).await);
+			catch!(tx, tx.get_ns_user(ns_def.namespace_id, user, None).await)
+		}
+		Level::Database(ns, db) => {
+			let db_def = catch!(tx, tx.expect_db_by_name(ns, db).await);
+			catch!(tx, tx.get_db_user(db_def.namespace_id, db_def.database_id, user, None).await)
+		}
+		_ => None,
+	};
+	tx.cancel().await?;
+	match (found, level) {
+		(Some(found), _) => Ok(found),
+		(None, Level::Namespace(ns)) => Err(Error::UserNsNotFound {
+			name: user.to_owned(),
+			ns: ns.clone(),
+		}
+		.into()),
+		(None, Level::Database(ns, db)) => Err(Error::UserDbNotFound {
+			name: user.to_owned(),
+			ns: ns.clone(),
+			db: db.clone(),
+		}
+		.into()),
+		(None, _) => Err(Error::UserRootNotFound {
+			name: user.to_owned(),
+		}
+		.into()),
+	}
+}
+
+/// Ensures that a record access method exists on a database
+async fn record_access(kvs: &Datastore, ns: &str, db: &str, ac: &str) -> Result<()> {
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	let db_def = catch!(tx, tx.expect_db_by_name(ns, db).await);
+	let access =
+		catch!(tx, tx.get_db_access(db_def.namespace_id, db_def.database_id, ac, None).await);
+	tx.cancel().await?;
+	match access {
+		Some(access) if matches!(access.access_type, catalog::AccessType::Record(_)) => Ok(()),
+		Some(_) => Err(Error::AccessMethodMismatch.into()),
+		None => Err(Error::AccessDbNotFound {
+			ac: ac.to_owned(),
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+		}
+		.into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn only_admins_within_their_level_may_impersonate() {
+		let record = Level::Record("a".into(), "b".into(), "user".into());
+		// Root owners may impersonate anyone
+		let owner = Auth::for_root(Role::Owner);
+		check_allowed(&owner, &record, None).unwrap();
+		check_allowed(&owner, &Level::Root, Some(&Role::Owner)).unwrap();
+		// Root editors may not impersonate owners
+		let editor = Auth::for_root(Role::Editor);
+		check_allowed(&editor, &record, None).unwrap();
+		check_allowed(&editor, &Level::Root, Some(&Role::Editor)).unwrap();
+		check_allowed(&editor, &Level::Root, Some(&Role::Owner)).unwrap_err();
+		// Viewers may not impersonate
+		let viewer = Auth::for_root(Role::Viewer);
+		check_allowed(&viewer, &record, None).unwrap_err();
+		// Namespace admins may only impersonate users within their namespace
+		let ns = Auth::for_ns(Role::Owner, "a");
+		check_allowed(&ns, &record, None).unwrap();
+		check_allowed(&ns, &Level::Database("a".into(), "c".into()), Some(&Role::Owner)).unwrap();
+		check_allowed(&ns, &Level::Root, Some(&Role::Viewer)).unwrap_err();
+		check_allowed(&ns, &Level::Record("x".into(), "b".into(), "user".into()), None)
+			.unwrap_err();
+		// Database admins may not impersonate
+		let db = Auth::for_db(Role::Owner, "a", "b");
+		check_allowed(&db, &record, None).unwrap_err();
+	}
+
+	#[test]
+	fn stop_restores_the_impersonator() {
+		let mut session = Session::owner().with_ns("a").with_db("b");
+		let original = session.clone();
+		// Stopping without impersonating does nothing
+		stop(&mut session).unwrap();
+		assert_eq!(session, original);
+		// Stopping restores the administrator
+		session.impersonator = Some(Arc::new(original.clone()));
+		session.au = Arc::new(Auth::for_record("user:one".into(), "a", "b", "user"));
+		session.ac = Some("user".into());
+		stop(&mut session).unwrap();
+		assert_eq!(session, original);
+	}
+}
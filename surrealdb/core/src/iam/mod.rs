@@ -11,6 +11,7 @@ pub mod check;
 pub mod clear;
 pub mod entities;
 pub(crate) mod file;
+pub mod impersonate;
 pub mod issue;
 #[cfg(feature = "jwks")]
 pub mod jwks;
@@ -18,6 +19,7 @@ pub mod reset;
 pub mod signin;
 pub mod signup;
 pub mod token;
+pub(crate) mod totp;
 pub mod verify;
 
 pub use self::auth::*;
@@ -12,4 +12,5 @@ pub fn reset(session: &mut Session) {
 	session.ns = None;
 	session.db = None;
 	session.variables = PublicVariables::default();
+	session.impersonator = None;
 }
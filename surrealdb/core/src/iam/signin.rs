@@ -28,11 +28,11 @@ use crate::expr::access_type;
 use crate::expr::statements::access;
 use crate::iam::issue::{config, expiration};
 use crate::iam::token::{Claims, HEADER, Token};
-use crate::iam::{self, Auth, algorithm_to_jwt_algorithm};
+use crate::iam::{self, Auth, algorithm_to_jwt_algorithm, totp};
 use crate::kvs::Datastore;
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
-use crate::types::{PublicValue, PublicVariables};
+use crate::types::{PublicRecordId, PublicValue, PublicVariables};
 use crate::val::{Datetime, Value};
 
 /// Authenticates a user and returns an authentication token.
@@ -273,10 +273,7 @@ pub async fn db_access(
 	match av.access_type.clone() {
 		catalog::AccessType::Record(at) => {
 			// Check if the record access method supports issuing tokens
-			let iss = match &at.jwt.issue {
-				Some(iss) => iss.clone(),
-				_ => bail!(Error::AccessMethodMismatch),
-			};
+			ensure!(at.jwt.issue.is_some(), Error::AccessMethodMismatch);
 			// Check if a refresh token is defined
 			if let Some(bearer) = &at.bearer {
 				// Check if a refresh token is being used to authenticate
@@ -294,6 +291,17 @@ pub async fn db_access(
 					.await;
 				}
 			};
+			// Check if a second factor is being used to complete a signin
+			if let Some(challenge) = vars.get("challenge") {
+				ensure!(at.mfa.is_some(), Error::InvalidAuth);
+				let challenge = challenge.as_string().ok_or_else(|| Error::InvalidAuth)?;
+				let code = match vars.get("code") {
+					Some(code) => code.as_string().ok_or_else(|| Error::InvalidAuth)?,
+					None => bail!(Error::InvalidAuth),
+				};
+				let rid = verify_mfa(kvs, &db_def, &av, challenge, code).await?;
+				return signin_record(kvs, session, ns, db, &av, &at, rid).await;
+			}
 			match &at.signin {
 				// This record access allows signin
 				Some(val) => {
@@ -308,103 +316,12 @@ pub async fn db_access(
 						Ok(val) => {
 							match val.into_record() {
 								// There is a record returned
-								Ok(mut rid) => {
-									// Create the authentication key
-									let key = iam::issue::config(iss.alg, &iss.key)?;
-									// Create the authentication claim
-									let claims = Claims {
-										iss: Some(SERVER_NAME.to_owned()),
-										iat: Some(Utc::now().timestamp()),
-										nbf: Some(Utc::now().timestamp()),
-										exp: iam::issue::expiration(av.token_duration)?,
-										jti: Some(Uuid::new_v4().to_string()),
-										ns: Some(ns.clone()),
-										db: Some(db.clone()),
-										ac: Some(ac.clone()),
-										id: Some(rid.to_sql()),
-										..Claims::default()
-									};
-									// AUTHENTICATE clause
-									if let Some(au) = &av.authenticate {
-										// Setup the system session for finding the signin
-										// record
-										let mut sess = Session::for_level(
-											Level::Database(ns.clone(), db.clone()),
-											Role::Editor,
-										);
-										sess.rd = Some(
-											crate::val::convert_value_to_public_value(
-												Value::RecordId(rid.clone().into()),
-											)
-											.expect("record id conversion should succeed"),
-										);
-										sess.tk = Some(
-											crate::val::convert_value_to_public_value(
-												claims.clone().into_claims_object().into(),
-											)
-											.expect("claims conversion should succeed"),
-										);
-										sess.ip.clone_from(&session.ip);
-										sess.or.clone_from(&session.or);
-										rid = authenticate_record(kvs, &sess, au).await?;
-									}
-									// Create refresh token if defined for the record access
-									// method
-									let refresh = match &at.bearer {
-										Some(_) => Some(
-											create_refresh_token_record(
-												kvs,
-												av.name.to_string(),
-												&ns,
-												&db,
-												rid.clone().into(),
-											)
-											.await?,
-										),
-										None => None,
-									};
-									// Log the authenticated access method info
-									trace!("Signing in to database with access method `{}`", ac);
-									// Create the authentication token
-									let enc = encode(
-										&Header::new(algorithm_to_jwt_algorithm(iss.alg)),
-										&claims,
-										&key,
-									);
-									// Set the authentication on the session
-									session.tk = Some(
-										crate::val::convert_value_to_public_value(
-											claims.into_claims_object().into(),
-										)
-										.expect("claims conversion should succeed"),
-									);
-									session.ns = Some(ns.clone());
-									session.db = Some(db.clone());
-									session.ac = Some(ac.clone());
-									session.rd = Some(
-										crate::val::convert_value_to_public_value(Value::RecordId(
-											rid.clone().into(),
-										))
-										.expect("record id conversion should succeed"),
-									);
-									session.exp = iam::issue::expiration(av.session_duration)?;
-									session.au = Arc::new(Auth::new(Actor::new(
-										rid.to_sql(),
-										Default::default(),
-										Level::Record(ns, db, rid.to_sql()),
-									)));
-									// Check the authentication token
-									match enc {
-										// The auth token was created successfully
-										Ok(token) => Ok(match refresh {
-											Some(refresh) => Token::WithRefresh {
-												access: token,
-												refresh,
-											},
-											None => Token::Access(token),
-										}),
-										_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
+								Ok(rid) => {
+									// The record may have to confirm the signin with a second factor
+									if at.mfa.is_some() {
+										require_mfa(kvs, &db_def, &av, &rid).await?;
 									}
+									signin_record(kvs, session, ns, db, &av, &at, rid).await
 								}
 								_ => Err(anyhow::Error::new(Error::NoRecordFound)),
 							}
@@ -451,6 +368,201 @@ pub async fn db_access(
 	}
 }
 
+/// Issues the token of a record which signed in to a record access method.
+async fn signin_record(
+	kvs: &Datastore,
+	session: &mut Session,
+	ns: String,
+	db: String,
+	av: &catalog::AccessDefinition,
+	at: &catalog::RecordAccess,
+	mut rid: PublicRecordId,
+) -> Result<Token> {
+	// Check if the record access method supports issuing tokens
+	let Some(iss) = &at.jwt.issue else {
+		bail!(Error::AccessMethodMismatch);
+	};
+	// Create the authentication key
+	let key = iam::issue::config(iss.alg, &iss.key)?;
+	// Create the authentication claim
+	let claims = Claims {
+		iss: Some(SERVER_NAME.to_owned()),
+		iat: Some(Utc::now().timestamp()),
+		nbf: Some(Utc::now().timestamp()),
+		exp: iam::issue::expiration(av.token_duration)?,
+		jti: Some(Uuid::new_v4().to_string()),
+		ns: Some(ns.clone()),
+		db: Some(db.clone()),
+		ac: Some(av.name.to_string()),
+		id: Some(rid.to_sql()),
+		..Claims::default()
+	};
+	// AUTHENTICATE clause
+	if let Some(au) = &av.authenticate {
+		// Setup the system session for finding the signin
+		// record
+		let mut sess = Session::for_level(Level::Database(ns.clone(), db.clone()), Role::Editor);
+		sess.rd = Some(
+			crate::val::convert_value_to_public_value(Value::RecordId(rid.clone().into()))
+				.expect("record id conversion should succeed"),
+		);
+		sess.tk = Some(
+			crate::val::convert_value_to_public_value(claims.clone().into_claims_object().into())
+				.expect("claims conversion should succeed"),
+		);
+		sess.ip.clone_from(&session.ip);
+		sess.or.clone_from(&session.or);
+		rid = authenticate_record(kvs, &sess, au).await?;
+	}
+	// Create refresh token if defined for the record access
+	// method
+	let refresh = match &at.bearer {
+		Some(_) => Some(
+			create_refresh_token_record(kvs, av.name.to_string(), &ns, &db, rid.clone().into())
+				.await?,
+		),
+		None => None,
+	};
+	// Log the authenticated access method info
+	trace!("Signing in to database with access method `{}`", av.name);
+	// Create the authentication token
+	let enc = encode(&Header::new(algorithm_to_jwt_algorithm(iss.alg)), &claims, &key);
+	// Set the authentication on the session
+	session.tk = Some(
+		crate::val::convert_value_to_public_value(claims.into_claims_object().into())
+			.expect("claims conversion should succeed"),
+	);
+	session.ns = Some(ns.clone());
+	session.db = Some(db.clone());
+	session.ac = Some(av.name.to_string());
+	session.rd = Some(
+		crate::val::convert_value_to_public_value(Value::RecordId(rid.clone().into()))
+			.expect("record id conversion should succeed"),
+	);
+	session.exp = iam::issue::expiration(av.session_duration)?;
+	session.au = Arc::new(Auth::new(Actor::new(
+		rid.to_sql(),
+		Default::default(),
+		Level::Record(ns, db, rid.to_sql()),
+	)));
+	// Check the authentication token
+	match enc {
+		// The auth token was created successfully
+		Ok(token) => Ok(match refresh {
+			Some(refresh) => Token::WithRefresh {
+				access: token,
+				refresh,
+			},
+			None => Token::Access(token),
+		}),
+		_ => Err(anyhow::Error::new(Error::TokenMakingFailed)),
+	}
+}
+
+/// Fails with a challenge if a record which signed in to a record access
+/// method is enrolled in its second factor.
+async fn require_mfa(
+	kvs: &Datastore,
+	db: &DatabaseDefinition,
+	av: &catalog::AccessDefinition,
+	rid: &PublicRecordId,
+) -> Result<()> {
+	let subject = catalog::Subject::Record(rid.clone().into());
+	let tx = kvs.transaction(Write, Optimistic).await?;
+	let enrolments = catch!(
+		tx,
+		access::get_totp_enrolments(&tx, db.namespace_id, db.database_id, &av.name, &subject).await
+	);
+	// Records which are not enrolled sign in with their credentials only
+	let Some(gr) = enrolments.last() else {
+		tx.cancel().await?;
+		return Ok(());
+	};
+	if !matches!(gr.grant, catalog::Grant::Totp(_)) {
+		tx.cancel().await?;
+		bail!(Error::InvalidAuth);
+	}
+	// Store the challenge, replacing any earlier challenge of the grant
+	let (challenge, state) = totp::challenge(&gr.id, Utc::now().timestamp());
+	let key =
+		crate::key::database::access::mc::new(db.namespace_id, db.database_id, &av.name, &gr.id);
+	catch!(tx, tx.set(&key, &state).await);
+	tx.commit().await?;
+	trace!("Signing in to database with access method `{}` requires a second factor", av.name);
+	bail!(Error::MfaRequired {
+		challenge
+	})
+}
+
+/// Completes the signin of a record to a record access method with the code
+/// of its second factor, returning the record.
+///
+/// The challenge is removed along with the verification, and the grant of the
+/// enrolment is updated, so that neither the challenge nor the code is ever
+/// accepted again. Every rejected code counts against the attempts of the
+/// challenge, which is removed once they run out.
+async fn verify_mfa(
+	kvs: &Datastore,
+	db: &DatabaseDefinition,
+	av: &catalog::AccessDefinition,
+	challenge: &str,
+	code: &str,
+) -> Result<PublicRecordId> {
+	// Return opaque errors to avoid leaking why the second factor is invalid
+	let Some((id, token)) = totp::parse_challenge(challenge) else {
+		bail!(Error::InvalidAuth);
+	};
+	let tx = kvs.transaction(Write, Optimistic).await?;
+	let key = crate::key::database::access::mc::new(db.namespace_id, db.database_id, &av.name, id);
+	let now = Utc::now().timestamp();
+	// Only the challenge which was last issued for the grant is accepted
+	let state = catch!(tx, tx.get(&key, None).await);
+	let Some(mut state) = state.filter(|state| state.matches(token, now)) else {
+		let _ = tx.cancel().await;
+		debug!("Second factor challenge for access method `{}` is invalid", av.name);
+		bail!(Error::InvalidAuth);
+	};
+	let gr = catch!(
+		tx,
+		tx.get_db_access_grant(db.namespace_id, db.database_id, &av.name, id, None).await
+	);
+	let verified = gr.as_ref().and_then(|gr| {
+		let active = gr.revocation.is_none()
+			&& gr.expiration.as_ref().is_none_or(|exp| exp > &Datetime::now());
+		match (&gr.grant, &gr.subject) {
+			(catalog::Grant::Totp(totp), catalog::Subject::Record(rid)) if active => {
+				let step = totp::verify(totp, code, now as u64)?;
+				Some((rid.clone(), step))
+			}
+			_ => None,
+		}
+	});
+	let (Some(gr), Some((rid, step))) = (gr, verified) else {
+		// Count the rejected code, removing the challenge once its attempts run out
+		state.attempts = state.attempts.saturating_add(1);
+		if state.attempts >= totp::CHALLENGE_ATTEMPTS {
+			catch!(tx, tx.del(&key).await);
+		} else {
+			catch!(tx, tx.set(&key, &state).await);
+		}
+		tx.commit().await?;
+		debug!("Second factor for access method `{}` is invalid", av.name);
+		bail!(Error::InvalidAuth);
+	};
+	// The challenge is only ever used once
+	catch!(tx, tx.del(&key).await);
+	// Store the step of the code, so that it is not accepted again
+	let mut gr = (*gr).clone();
+	if let catalog::Grant::Totp(totp) = &mut gr.grant {
+		totp.step = step;
+	}
+	let key =
+		crate::key::database::access::gr::new(db.namespace_id, db.database_id, &gr.ac, &gr.id);
+	catch!(tx, tx.set(&key, &gr).await);
+	tx.commit().await?;
+	PublicRecordId::try_from(rid)
+}
+
 fn auth_from_level_user(level: Level, user: &catalog::UserDefinition) -> Result<Auth> {
 	let roles = user
 		.roles
@@ -1610,6 +1722,210 @@ mod tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn test_signin_record_with_mfa() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let res = ds
+			.execute(
+				r#"
+				DEFINE ACCESS user ON DATABASE TYPE RECORD
+					SIGNIN (
+						SELECT * FROM user WHERE name = $user AND crypto::argon2::compare(pass, $pass)
+					)
+					WITH MFA TOTP
+					DURATION FOR SESSION 2h
+				;
+
+				CREATE user:test CONTENT {
+					name: 'user',
+					pass: crypto::argon2::generate('pass')
+				};
+				CREATE user:other CONTENT {
+					name: 'other',
+					pass: crypto::argon2::generate('pass')
+				};
+
+				ACCESS user GRANT TOTP FOR RECORD user:test;
+				"#,
+				&sess,
+				None,
+			)
+			.await
+			.unwrap();
+		// Get the secret from the enrolment
+		let result = if let Ok(res) = &res.last().unwrap().result {
+			res.clone()
+		} else {
+			panic!("Unable to retrieve the enrolment");
+		};
+		let grant = result.get("grant").clone().into_object().unwrap();
+		let secret = grant.get("secret").unwrap().as_string().unwrap().clone();
+		let uri = grant.get("uri").unwrap().as_string().unwrap().clone();
+		assert!(uri.starts_with("otpauth://totp/user:user:test?secret="), "{uri}");
+		let totp = catalog::GrantTotp {
+			secret,
+			step: 0,
+		};
+
+		let signin = |user: &str, challenge: Option<(&str, &str)>| {
+			let mut vars = PublicVariables::new();
+			vars.insert("user", user.to_string());
+			vars.insert("pass", "pass");
+			if let Some((challenge, code)) = challenge {
+				vars.insert("challenge", challenge.to_string());
+				vars.insert("code", code.to_string());
+			}
+			vars
+		};
+		let mut sess = Session {
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			..Default::default()
+		};
+
+		// Records which are not enrolled sign in with their credentials only
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			signin("other", None),
+		)
+		.await;
+		assert!(res.is_ok(), "Failed to signin without enrolment: {res:?}");
+
+		// Enrolled records are challenged
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			signin("user", None),
+		)
+		.await;
+		let challenge = match res.unwrap_err().downcast().expect("Unexpected error kind") {
+			Error::MfaRequired {
+				challenge,
+			} => challenge,
+			e => panic!("Unexpected error, expected MfaRequired found {e}"),
+		};
+
+		// A wrong code is rejected
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			signin("user", Some((&challenge, "000000x"))),
+		)
+		.await;
+		match res.unwrap_err().downcast().expect("Unexpected error kind") {
+			Error::InvalidAuth => {}
+			e => panic!("Unexpected error, expected InvalidAuth found {e}"),
+		}
+
+		// The challenge is removed once its attempts run out
+		for _ in 1..totp::CHALLENGE_ATTEMPTS {
+			let res = db_access(
+				&ds,
+				&mut sess,
+				"test".to_string(),
+				"test".to_string(),
+				"user".to_string(),
+				signin("user", Some((&challenge, "000000x"))),
+			)
+			.await;
+			assert!(res.is_err(), "Unexpected signin with a wrong code: {res:?}");
+		}
+		let code = totp::code(&totp, Utc::now().timestamp() as u64);
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			signin("user", Some((&challenge, &code))),
+		)
+		.await;
+		match res.unwrap_err().downcast().expect("Unexpected error kind") {
+			Error::InvalidAuth => {}
+			e => panic!("Unexpected error, expected InvalidAuth found {e}"),
+		}
+
+		// A new challenge is issued by signing in again
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			signin("user", None),
+		)
+		.await;
+		let challenge = match res.unwrap_err().downcast().expect("Unexpected error kind") {
+			Error::MfaRequired {
+				challenge,
+			} => challenge,
+			e => panic!("Unexpected error, expected MfaRequired found {e}"),
+		};
+
+		// A challenge which was not issued is rejected, even with the right code
+		let (id, _) = totp::parse_challenge(&challenge).unwrap();
+		let forged = format!("surreal-mfa-{id}-{}", "0".repeat(64));
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			signin("user", Some((&forged, &code))),
+		)
+		.await;
+		match res.unwrap_err().downcast().expect("Unexpected error kind") {
+			Error::InvalidAuth => {}
+			e => panic!("Unexpected error, expected InvalidAuth found {e}"),
+		}
+
+		// The code completes the signin
+		let mut sess = Session {
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			..Default::default()
+		};
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			signin("user", Some((&challenge, &code))),
+		)
+		.await;
+		assert!(res.is_ok(), "Failed to signin with the second factor: {res:?}");
+		assert_eq!(sess.ac, Some("user".to_string()));
+		assert!(sess.au.is_record());
+		assert_eq!(sess.au.id(), "user:test");
+
+		// The same code is not accepted again
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			signin("user", Some((&challenge, &code))),
+		)
+		.await;
+		match res.unwrap_err().downcast().expect("Unexpected error kind") {
+			Error::InvalidAuth => {}
+			e => panic!("Unexpected error, expected InvalidAuth found {e}"),
+		}
+	}
+
 	#[tokio::test]
 	async fn test_signin_record_with_jwt_issuer() {
 		// Test with correct credentials
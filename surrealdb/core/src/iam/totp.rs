@@ -0,0 +1,285 @@
+//! Time-based one-time passwords, the second factor of record access methods.
+//!
+//! A record access method defined `WITH MFA TOTP` requires the records which
+//! are enrolled with `ACCESS ... GRANT TOTP FOR RECORD ...` to confirm every
+//! signin with a code from an authenticator app, as described by RFC 6238.
+//! Records which are not enrolled keep signing in with their credentials only.
+//!
+//! Once the SIGNIN clause has found an enrolled record, the signin fails with
+//! [`Error::MfaRequired`], carrying a challenge which names the grant along
+//! with a random token. The challenge is stored in the datastore, replacing
+//! any earlier challenge of the grant, so that it can neither be forged nor
+//! extended by the client. The signin is completed by signing in again with
+//! the `challenge` and the `code`, within [`CHALLENGE_DURATION`]. A challenge
+//! is removed once it has been verified, or once [`CHALLENGE_ATTEMPTS`] codes
+//! were rejected, and a code is only ever accepted once.
+//!
+//! [`Error::MfaRequired`]: crate::err::Error::MfaRequired
+
+use rand::Rng;
+use revision::revisioned;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use url::Url;
+
+use crate::catalog::GrantTotp;
+use crate::kvs::impl_kv_value_revisioned;
+
+/// The number of random bytes of a secret
+const SECRET_LENGTH: usize = 20;
+/// The number of seconds for which a code is valid
+const PERIOD: u64 = 30;
+/// The number of digits of a code
+const DIGITS: usize = 6;
+/// The number of periods by which the clock of an authenticator may drift
+const DRIFT: u64 = 1;
+/// The block size of both SHA-1 and SHA-256
+const BLOCK_SIZE: usize = 64;
+/// The alphabet of base32, as described by RFC 4648
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+/// The prefix of a challenge
+const CHALLENGE_PREFIX: &str = "surreal-mfa-";
+/// The number of seconds within which a challenge has to be verified
+pub(crate) const CHALLENGE_DURATION: i64 = 300;
+/// The number of codes which can be tried against a single challenge
+pub(crate) const CHALLENGE_ATTEMPTS: u8 = 5;
+/// The number of random bytes of the token of a challenge
+const CHALLENGE_TOKEN_LENGTH: usize = 32;
+
+/// A challenge issued to an enrolled record, stored until it is verified, it
+/// runs out of attempts, or the next challenge of the grant replaces it
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct MfaChallenge {
+	/// The SHA-256 hash of the token of the challenge
+	token: Vec<u8>,
+	/// When the challenge expires, in seconds since the Unix epoch
+	expiry: i64,
+	/// The number of codes which were rejected
+	pub(crate) attempts: u8,
+}
+
+impl_kv_value_revisioned!(MfaChallenge);
+
+impl MfaChallenge {
+	/// Checks the token of a challenge, and that it has not expired
+	pub(crate) fn matches(&self, token: &str, now: i64) -> bool {
+		let hash = Sha256::digest(token.as_bytes());
+		let ok: bool = hash.as_slice().ct_eq(&self.token).into();
+		ok && now < self.expiry
+	}
+}
+
+/// Generates the secret of a new enrolment
+pub(crate) fn new_grant_totp() -> GrantTotp {
+	let mut secret = [0u8; SECRET_LENGTH];
+	rand::rng().fill(&mut secret);
+	GrantTotp {
+		secret: base32_encode(&secret),
+		step: 0,
+	}
+}
+
+/// Returns the `otpauth` URI of an enrolment, which authenticator apps
+/// import from a QR code
+pub(crate) fn uri(issuer: &str, account: &str, grant: &GrantTotp) -> String {
+	let mut uri = Url::parse("otpauth://totp/").expect("the base URI is valid");
+	uri.set_path(&format!("{issuer}:{account}"));
+	uri.query_pairs_mut()
+		.append_pair("secret", &grant.secret)
+		.append_pair("issuer", issuer)
+		.append_pair("algorithm", "SHA1")
+		.append_pair("digits", &DIGITS.to_string())
+		.append_pair("period", &PERIOD.to_string());
+	uri.to_string()
+}
+
+/// Checks a code against an enrolment at a point in time, given in seconds
+/// since the Unix epoch, and returns the time step of the code.
+///
+/// The codes of the steps next to the current one are accepted to allow for
+/// clock drift, but never the code of a step which was already used.
+pub(crate) fn verify(grant: &GrantTotp, code: &str, now: u64) -> Option<u64> {
+	let secret = base32_decode(&grant.secret)?;
+	let current = now / PERIOD;
+	let mut found = None;
+	// Every step is checked, so that the time taken does not tell which one matched
+	for step in current.saturating_sub(DRIFT)..=current + DRIFT {
+		let expected = format!("{:0width$}", hotp(&secret, step), width = DIGITS);
+		let ok: bool = expected.as_bytes().ct_eq(code.as_bytes()).into();
+		if ok && step > grant.step && found.is_none() {
+			found = Some(step);
+		}
+	}
+	found
+}
+
+/// Returns the code of an enrolment at a point in time, as an authenticator
+/// app would
+#[cfg(test)]
+pub(crate) fn code(grant: &GrantTotp, now: u64) -> String {
+	let secret = base32_decode(&grant.secret).expect("the secret is valid base32");
+	format!("{:0width$}", hotp(&secret, now / PERIOD), width = DIGITS)
+}
+
+/// Returns a new challenge for the grant of an enrolment, at a point in time
+/// given in seconds since the Unix epoch, along with the state to store for it
+///
+/// Only the hash of the random token is stored, so that the challenge can
+/// not be completed by someone who can read the datastore.
+pub(crate) fn challenge(id: &str, now: i64) -> (String, MfaChallenge) {
+	let mut token = [0u8; CHALLENGE_TOKEN_LENGTH];
+	rand::rng().fill(&mut token);
+	let token = hex::encode(token);
+	let state = MfaChallenge {
+		token: Sha256::digest(token.as_bytes()).to_vec(),
+		expiry: now + CHALLENGE_DURATION,
+		attempts: 0,
+	};
+	(format!("{CHALLENGE_PREFIX}{id}-{token}"), state)
+}
+
+/// Returns the grant identifier and the token of a challenge
+pub(crate) fn parse_challenge(challenge: &str) -> Option<(&str, &str)> {
+	challenge.strip_prefix(CHALLENGE_PREFIX)?.rsplit_once('-')
+}
+
+/// Returns the code of a secret for a counter, as described by RFC 4226
+fn hotp(secret: &[u8], counter: u64) -> u32 {
+	let mac = hmac::<Sha1>(secret, &counter.to_be_bytes());
+	let offset = (mac[mac.len() - 1] & 0x0f) as usize;
+	let value =
+		u32::from_be_bytes([mac[offset] & 0x7f, mac[offset + 1], mac[offset + 2], mac[offset + 3]]);
+	value % 10u32.pow(DIGITS as u32)
+}
+
+/// Computes the HMAC of a message, as described by RFC 2104
+fn hmac<D: Digest>(key: &[u8], message: &[u8]) -> Vec<u8> {
+	let mut block = [0u8; BLOCK_SIZE];
+	if key.len() > BLOCK_SIZE {
+		let hashed = D::digest(key);
+		block[..hashed.len()].copy_from_slice(&hashed);
+	} else {
+		block[..key.len()].copy_from_slice(key);
+	}
+	let mut inner = D::new();
+	inner.update(block.map(|b| b ^ 0x36));
+	inner.update(message);
+	let mut outer = D::new();
+	outer.update(block.map(|b| b ^ 0x5c));
+	outer.update(inner.finalize());
+	outer.finalize().to_vec()
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+	let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+	let (mut buffer, mut bits) = (0u32, 0u32);
+	for &byte in bytes {
+		buffer = (buffer << 8) | byte as u32;
+		bits += 8;
+		while bits >= 5 {
+			bits -= 5;
+			out.push(BASE32[((buffer >> bits) & 0x1f) as usize] as char);
+		}
+	}
+	if bits > 0 {
+		out.push(BASE32[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+	}
+	out
+}
+
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+	let mut out = Vec::with_capacity(text.len() * 5 / 8);
+	let (mut buffer, mut bits) = (0u32, 0u32);
+	for c in text.bytes().take_while(|c| *c != b'=') {
+		let value = BASE32.iter().position(|a| *a == c.to_ascii_uppercase())? as u32;
+		buffer = (buffer << 5) | value;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			out.push((buffer >> bits) as u8);
+		}
+	}
+	Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn grant(secret: &[u8]) -> GrantTotp {
+		GrantTotp {
+			secret: base32_encode(secret),
+			step: 0,
+		}
+	}
+
+	#[test]
+	fn base32_round_trips() {
+		assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+		assert_eq!(base32_decode("MZXW6YTBOI").unwrap(), b"foobar");
+		assert_eq!(base32_decode("mzxw6ytboi======").unwrap(), b"foobar");
+		assert!(base32_decode("MZXW1").is_none());
+		let secret = new_grant_totp().secret;
+		assert_eq!(base32_decode(&secret).unwrap().len(), SECRET_LENGTH);
+	}
+
+	#[test]
+	fn hmac_matches_rfc_2202() {
+		let mac = hmac::<Sha1>(&[0x0b; 20], b"Hi There");
+		assert_eq!(hex::encode(mac), "b617318655057264e28bc0b6fb378c8ef146be00");
+		// Keys longer than a block are hashed first
+		let mac =
+			hmac::<Sha1>(&[0xaa; 80], b"Test Using Larger Than Block-Size Key - Hash Key First");
+		assert_eq!(hex::encode(mac), "aa4ae5e15272d00e95705637ce8a3b55ed402112");
+	}
+
+	#[test]
+	fn codes_match_rfc_6238() {
+		let secret = b"12345678901234567890";
+		// The reference codes have eight digits, of which the last six are used
+		assert_eq!(hotp(secret, 59 / PERIOD), 287082);
+		assert_eq!(hotp(secret, 1111111109 / PERIOD), 81804);
+		assert_eq!(hotp(secret, 1234567890 / PERIOD), 5924);
+		assert_eq!(hotp(secret, 2000000000 / PERIOD), 279037);
+	}
+
+	#[test]
+	fn codes_are_accepted_once() {
+		let mut grant = grant(b"12345678901234567890");
+		assert_eq!(verify(&grant, "005924", 1234567890), Some(1234567890 / PERIOD));
+		// A code from the previous period is still accepted
+		assert_eq!(verify(&grant, "005924", 1234567890 + PERIOD), Some(1234567890 / PERIOD));
+		assert_eq!(verify(&grant, "005924", 1234567890 + 2 * PERIOD), None);
+		assert_eq!(verify(&grant, "5924", 1234567890), None);
+		assert_eq!(verify(&grant, "000000", 1234567890), None);
+		// A code which was used can not be used again
+		grant.step = 1234567890 / PERIOD;
+		assert_eq!(verify(&grant, "005924", 1234567890), None);
+	}
+
+	#[test]
+	fn challenges_match_their_token() {
+		let (challenge, state) = challenge("abc", 1000);
+		let (id, token) = parse_challenge(&challenge).unwrap();
+		assert_eq!(id, "abc");
+		assert_eq!(token.len(), CHALLENGE_TOKEN_LENGTH * 2);
+		assert!(state.matches(token, 1000));
+		// Expired
+		assert!(!state.matches(token, 1000 + CHALLENGE_DURATION));
+		// Another token
+		let (other, _) = self::challenge("abc", 1000);
+		assert!(!state.matches(parse_challenge(&other).unwrap().1, 1000));
+		assert!(parse_challenge("surreal-mfa-abc").is_none());
+	}
+
+	#[test]
+	fn uris_describe_the_enrolment() {
+		let grant = grant(b"foobar");
+		assert_eq!(
+			uri("my app", "user:tobie", &grant),
+			"otpauth://totp/my%20app:user:tobie?secret=MZXW6YTBOI&issuer=my+app&algorithm=SHA1&digits=6&period=30"
+		);
+	}
+}
@@ -0,0 +1,256 @@
+//! Detection of the records which conflict with a `UNIQUE` index.
+//!
+//! Building a `UNIQUE` index over a table which already holds duplicate values
+//! fails on the first conflict the index builder encounters. The scan here
+//! reads the whole table instead, a batch at a time, collecting every value
+//! which is shared by more than one record, so that conflicts can be reviewed,
+//! or moved aside into another table, before uniqueness is enforced.
+//!
+//! Quarantined records are moved a batch at a time, each batch in a committed
+//! transaction of its own, as the index builder only reads committed records.
+//! The step is resumable: if the index then fails to build, for example
+//! because a conflicting record was written in the meantime, running the
+//! statement again moves aside the conflicts which remain.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use reblessive::tree::Stk;
+
+use crate::catalog::{Record, TableDefinition};
+use crate::ctx::{Context, FrozenContext};
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::statements::{CreateStatement, DeleteStatement};
+use crate::expr::{Data, FlowResultExt, Idiom, Output};
+use crate::idx::index::unique_tuples;
+use crate::key::record;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::Write;
+use crate::kvs::{INDEXING_BATCH_SIZE, Key, Val};
+use crate::val::{Array, RecordId, TableName, Value};
+
+/// The records of a table which conflict with a `UNIQUE` index
+#[derive(Debug, Default)]
+pub(crate) struct ConflictReport {
+	/// The number of records which were scanned
+	pub scanned: usize,
+	/// Every indexed value held by more than one record, along with the
+	/// records holding it, ordered by value
+	pub conflicts: Vec<(Array, Vec<RecordId>)>,
+	/// The records which have to be removed before the index can be built.
+	/// The first record holding a value is kept, and every later record which
+	/// holds a value of a kept record is listed here.
+	pub duplicates: Vec<RecordId>,
+}
+
+/// The state of a conflict scan, carried from one batch to the next
+#[derive(Default)]
+struct ConflictScan {
+	/// The number of records which were scanned
+	scanned: usize,
+	/// The kept record holding each value seen so far
+	claimed: BTreeMap<Array, RecordId>,
+	/// The values held by more than one record, along with their records
+	conflicts: BTreeMap<Array, Vec<RecordId>>,
+	/// The records which conflict with a kept record
+	duplicates: Vec<RecordId>,
+}
+
+impl ConflictScan {
+	/// Scans a batch of records, returning those which conflict with a record
+	/// which is kept
+	async fn batch(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		cols: &[Idiom],
+		batch: &[(Key, Val)],
+	) -> Result<Vec<RecordId>> {
+		let mut duplicates = Vec::new();
+		for (k, v) in batch.iter() {
+			let key = record::RecordKey::decode_key(k)?;
+			let val: Record = revision::from_slice(v.as_slice())?;
+			let rid: Arc<RecordId> = RecordId {
+				table: key.tb.into_owned(),
+				key: key.id,
+			}
+			.into();
+			let doc = CursorDoc::new(Some(Arc::clone(&rid)), None, val);
+			if doc.doc.as_ref().is_nullish() {
+				continue;
+			}
+			let mut values = Vec::with_capacity(cols.len());
+			for col in cols.iter() {
+				values.push(col.compute(stk, ctx, opt, Some(&doc)).await.catch_return()?);
+			}
+			let tuples = unique_tuples(values, cols);
+			self.scanned += 1;
+			// Keep the record unless one of its values is held by a kept record
+			let conflicting = tuples.iter().any(|t| self.claimed.contains_key(t));
+			for t in tuples {
+				match self.claimed.get(&t) {
+					Some(first) => self
+						.conflicts
+						.entry(t)
+						.or_insert_with(|| vec![first.clone()])
+						.push(rid.as_ref().clone()),
+					None if !conflicting => {
+						self.claimed.insert(t, rid.as_ref().clone());
+					}
+					None => {}
+				}
+			}
+			if conflicting {
+				duplicates.push(rid.as_ref().clone());
+			}
+		}
+		self.duplicates.extend(duplicates.iter().cloned());
+		Ok(duplicates)
+	}
+
+	/// Completes the scan
+	fn finish(self) -> ConflictReport {
+		ConflictReport {
+			scanned: self.scanned,
+			conflicts: self.conflicts.into_iter().collect(),
+			duplicates: self.duplicates,
+		}
+	}
+}
+
+impl ConflictReport {
+	/// Scans every record of a table, computing the values of the given index
+	/// columns and collecting those which conflict
+	pub(crate) async fn scan(
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		tb: &TableDefinition,
+		cols: &[Idiom],
+	) -> Result<Self> {
+		let txn = ctx.tx();
+		let mut scan = ConflictScan::default();
+		let beg = record::prefix(tb.namespace_id, tb.database_id, &tb.name)?;
+		let end = record::suffix(tb.namespace_id, tb.database_id, &tb.name)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			// Stop if the statement was cancelled or timed out
+			if let Some(reason) = ctx.done(true)? {
+				return Err(Error::from(reason).into());
+			}
+			let batch = txn.batch_keys_vals(rng, INDEXING_BATCH_SIZE, None).await?;
+			next = batch.next;
+			scan.batch(stk, ctx, opt, cols, &batch.result).await?;
+		}
+		Ok(scan.finish())
+	}
+
+	/// Scans every record of a table like [`Self::scan`], moving the records
+	/// which conflict into another table, keeping their ids, so that the
+	/// remaining records no longer conflict.
+	///
+	/// Each batch is scanned and moved in a transaction of its own, which is
+	/// committed before the next batch is read.
+	pub(crate) async fn quarantine(
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		tb: &TableDefinition,
+		cols: &[Idiom],
+		into: &TableName,
+	) -> Result<Self> {
+		let index_builder =
+			ctx.get_index_builder().ok_or_else(|| Error::unreachable("No Index Builder"))?;
+		let mut scan = ConflictScan::default();
+		let beg = record::prefix(tb.namespace_id, tb.database_id, &tb.name)?;
+		let end = record::suffix(tb.namespace_id, tb.database_id, &tb.name)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			// Stop if the statement was cancelled or timed out
+			if let Some(reason) = ctx.done(true)? {
+				return Err(Error::from(reason).into());
+			}
+			let tx = index_builder
+				.transaction_factory()
+				.transaction(Write, Optimistic, ctx.try_get_sequences()?.clone())
+				.await?;
+			let mut qctx = Context::new_child(ctx);
+			qctx.set_transaction(Arc::new(tx));
+			let qctx = qctx.freeze();
+			let tx = qctx.tx();
+			let batch = catch!(tx, tx.batch_keys_vals(rng, INDEXING_BATCH_SIZE, None).await);
+			next = batch.next;
+			let duplicates = catch!(tx, scan.batch(stk, &qctx, opt, cols, &batch.result).await);
+			for rid in duplicates {
+				catch!(tx, Self::move_record(stk, &qctx, opt, tb, rid, into).await);
+			}
+			tx.commit().await?;
+		}
+		Ok(scan.finish())
+	}
+
+	/// Moves a record into another table, keeping its id
+	async fn move_record(
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		tb: &TableDefinition,
+		rid: RecordId,
+		into: &TableName,
+	) -> Result<()> {
+		let record = ctx
+			.tx()
+			.get_record(tb.namespace_id, tb.database_id, &rid.table, &rid.key, None)
+			.await?;
+		let mut data = record.data.clone();
+		if let Value::Object(obj) = &mut data {
+			obj.remove("id");
+		}
+		let create = CreateStatement {
+			what: vec![
+				Value::RecordId(RecordId {
+					table: into.clone(),
+					key: rid.key.clone(),
+				})
+				.into_literal(),
+			],
+			data: Some(Data::ContentExpression(data.into_literal())),
+			output: Some(Output::None),
+			..Default::default()
+		};
+		stk.run(|stk| create.compute(stk, ctx, opt, None)).await?;
+		let delete = DeleteStatement {
+			what: vec![Value::RecordId(rid).into_literal()],
+			output: Some(Output::None),
+			..Default::default()
+		};
+		stk.run(|stk| delete.compute(stk, ctx, opt, None)).await?;
+		Ok(())
+	}
+
+	/// Converts the report into the value returned by `DEFINE INDEX`
+	pub(crate) fn into_value(self, quarantined: bool) -> Value {
+		let conflicts = self
+			.conflicts
+			.into_iter()
+			.map(|(value, records)| {
+				Value::from(map! {
+					"value" => Value::from(value),
+					"records" => Value::from(records.into_iter().map(Value::from).collect::<Vec<_>>()),
+				})
+			})
+			.collect::<Vec<_>>();
+		let duplicates =
+			Value::from(self.duplicates.into_iter().map(Value::from).collect::<Vec<_>>());
+		Value::from(map! {
+			"scanned" => Value::from(self.scanned),
+			"conflicts" => Value::from(conflicts),
+			"quarantined", if quarantined => duplicates.clone(),
+			"duplicates", if !quarantined => duplicates,
+		})
+	}
+}
@@ -13,6 +13,7 @@
 //! - Numeric predicates need a single probe/range in the index; per-variant fan-out is no longer
 //!   required.
 
+use std::collections::BTreeSet;
 use std::path::PathBuf;
 
 use anyhow::{Result, bail};
@@ -28,7 +29,7 @@ use crate::catalog::{
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::err::Error;
-use crate::expr::{Cond, Part};
+use crate::expr::{Cond, Idiom, Part};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::fulltext::{FullTextCompactionPlan, FullTextIndex};
 use crate::idx::planner::iterators::{IndexCountCompactionPlan, IndexCountThingIterator};
@@ -155,6 +156,7 @@ impl<'a> IndexOperation<'a> {
 
 	async fn index_unique(&mut self) -> Result<()> {
 		let txn = self.ctx.tx();
+		let mut entries = 0;
 		// Delete the old index data
 		if let Some(o) = self.o.take() {
 			let i = Indexable::new(o, self.ix);
@@ -170,7 +172,7 @@ impl<'a> IndexOperation<'a> {
 								Some(Error::Kvs(crate::kvs::Error::TransactionConditionNotMet))
 							) => {}
 						Err(e) => return Err(e),
-						Ok(()) => {}
+						Ok(()) => entries -= 1,
 					}
 				} else {
 					let key = self.get_unique_index_key(&o)?;
@@ -181,7 +183,7 @@ impl<'a> IndexOperation<'a> {
 								Some(Error::Kvs(crate::kvs::Error::TransactionConditionNotMet))
 							) => {}
 						Err(e) => return Err(e),
-						Ok(()) => {}
+						Ok(()) => entries -= 1,
 					}
 				}
 			}
@@ -196,6 +198,7 @@ impl<'a> IndexOperation<'a> {
 					// uniqueness check — NULL != NULL per SQL convention.
 					let key = self.get_non_unique_index_key(&n)?;
 					txn.set(&key, self.rid).await?;
+					entries += 1;
 				} else {
 					let key = self.get_unique_index_key(&n)?;
 					if txn.putc(&key, self.rid, None).await.is_err() {
@@ -204,15 +207,18 @@ impl<'a> IndexOperation<'a> {
 							txn.get(&key, None).await?.expect("record should exist");
 						return self.err_index_exists(rid, n);
 					}
+					entries += 1;
 				}
 			}
 		}
+		self.record_entries(&txn, entries);
 		Ok(())
 	}
 
 	async fn index_non_unique(&mut self) -> Result<()> {
 		// Lock the transaction
 		let txn = self.ctx.tx();
+		let mut entries = 0;
 		// Delete the old index data
 		if let Some(o) = self.o.take() {
 			let i = Indexable::new(o, self.ix);
@@ -229,7 +235,10 @@ impl<'a> IndexOperation<'a> {
 							Err(e)
 						}
 					}
-					Ok(v) => Ok(v),
+					Ok(v) => {
+						entries -= 1;
+						Ok(v)
+					}
 				}?
 			}
 		}
@@ -239,11 +248,22 @@ impl<'a> IndexOperation<'a> {
 			for n in i {
 				let key = self.get_non_unique_index_key(&n)?;
 				txn.set(&key, self.rid).await?;
+				entries += 1;
 			}
 		}
+		self.record_entries(&txn, entries);
 		Ok(())
 	}
 
+	/// Records the change in the number of entries of the index in the
+	/// statistics of the table
+	fn record_entries(&self, txn: &Transaction, entries: i64) {
+		if entries != 0 {
+			let tb = &self.ix.table_name;
+			txn.table_stats().index(self.ns, self.db, tb, self.ix.index_id, entries);
+		}
+	}
+
 	async fn index_count(
 		&mut self,
 		_stk: &mut Stk,
@@ -521,6 +541,13 @@ impl<'a> IndexOperation<'a> {
 	}
 }
 
+/// Returns the distinct value tuples which a `UNIQUE` index on the given
+/// columns would enforce uniqueness for. Tuples containing NONE or NULL are
+/// skipped, as they never conflict.
+pub(crate) fn unique_tuples(vals: Vec<Value>, cols: &[Idiom]) -> BTreeSet<Array> {
+	Indexable::with_cols(vals, cols).into_iter().filter(|t| !t.is_any_none_or_null()).collect()
+}
+
 /// Extract from the given document, the values required by the index and put
 /// then in an array. Eg. IF the index is composed of the columns `name` and
 /// `instrument` Given this doc: { "id": 1, "instrument":"piano", "name":"Tobie"
@@ -529,8 +556,12 @@ struct Indexable(Vec<(Value, bool)>);
 
 impl Indexable {
 	fn new(vals: Vec<Value>, ix: &IndexDefinition) -> Self {
+		Self::with_cols(vals, &ix.cols)
+	}
+
+	fn with_cols(vals: Vec<Value>, cols: &[Idiom]) -> Self {
 		let mut source = Vec::with_capacity(vals.len());
-		for (v, i) in vals.into_iter().zip(ix.cols.iter()) {
+		for (v, i) in vals.into_iter().zip(cols.iter()) {
 			let f = matches!(i.0.last(), Some(&Part::Flatten));
 			source.push((v, f));
 		}
@@ -1,8 +1,10 @@
+pub(crate) mod conflicts;
 pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
 pub(super) mod seqdocids;
 pub mod trees;
+pub mod usage;
 
 use std::borrow::Cow;
 use std::fmt::{Debug, Display};
@@ -48,6 +48,7 @@ impl MutVisitor for CountLimitRewriter {
 			| Expr::Throw(_)
 			| Expr::IfElse(_)
 			| Expr::Select(_)
+			| Expr::With(_)
 			| Expr::Create(_)
 			| Expr::Update(_)
 			| Expr::Delete(_)
@@ -413,6 +413,14 @@ impl QueryExecutor {
 		}
 	}
 
+	/// Returns the index read by the given iterator.
+	pub(crate) fn iterator_index(&self, ir: IteratorRef) -> Option<&IndexDefinition> {
+		match self.0.it_entries.get(ir)? {
+			IteratorEntry::Single(_, io) => Some(&**io.index_reference()),
+			IteratorEntry::Range(_, r, ..) => Some(&**r),
+		}
+	}
+
 	pub(crate) fn explain(&self, ir: IteratorRef) -> Value {
 		match self.0.it_entries.get(ir) {
 			Some(ie) => ie.explain(),
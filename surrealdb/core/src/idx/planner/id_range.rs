@@ -0,0 +1,256 @@
+//! Rewrites conditions on the creation time of records into record id ranges.
+//!
+//! Records whose ids are V7 UUIDs, or arrays starting with a datetime, are
+//! stored in the order in which they were created. A condition which bounds
+//! `record::created_at(id)` (or `id.created_at()`) from below can therefore be
+//! answered by scanning a range of record ids rather than the whole table.
+//!
+//! The ranges cover every record which could match, and the condition is still
+//! evaluated for each scanned record. Only conditions with a lower bound are
+//! rewritten, as records whose ids are not time-based have no creation time,
+//! and `NONE` is lower than any datetime.
+
+use std::ops::Bound;
+
+use anyhow::Result;
+use chrono::TimeDelta;
+use reblessive::tree::Stk;
+
+use crate::expr::paths::ID;
+use crate::expr::{BinaryOperator, Cond, Expr, FlowResultExt as _, Function, Part};
+use crate::idx::planner::{ScanDirection, StatementContext};
+use crate::val::{Array, Datetime, RecordIdKey, RecordIdKeyRange, Uuid, Value};
+
+/// The bounds of the creation time of the matching records
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct TimeBounds {
+	from: Option<Datetime>,
+	to: Option<Datetime>,
+}
+
+impl TimeBounds {
+	/// Narrows the bounds with the condition `created_at OP value`
+	pub(crate) fn narrow(&mut self, op: &BinaryOperator, value: Datetime) {
+		match op {
+			BinaryOperator::MoreThan | BinaryOperator::MoreThanEqual => {
+				self.from = self.from.max(Some(value));
+			}
+			BinaryOperator::LessThan | BinaryOperator::LessThanEqual => {
+				self.to = Some(self.to.map_or(value, |to| to.min(value)));
+			}
+			_ => {}
+		}
+	}
+
+	/// Returns the record id ranges which contain every record created within
+	/// the bounds, in the order in which they should be scanned
+	pub(crate) fn ranges(&self, sc: ScanDirection) -> Option<Vec<RecordIdKeyRange>> {
+		let from = self.from?;
+		// No record can have been created within the bounds
+		if self.to.is_some_and(|to| to < from) {
+			return Some(Vec::new());
+		}
+		// V7 UUIDs are ordered by the millisecond in which they were generated
+		let uuids = RecordIdKeyRange {
+			start: Bound::Included(RecordIdKey::Uuid(Uuid::v7_bounds(from).0)),
+			end: Bound::Included(RecordIdKey::Uuid(match self.to {
+				Some(to) => Uuid::v7_bounds(to).1,
+				None => Uuid::max(),
+			})),
+		};
+		// Arrays are ordered by their first element, and then by the rest
+		let next = self.to.and_then(|to| to.0.checked_add_signed(TimeDelta::nanoseconds(1)));
+		let arrays = RecordIdKeyRange {
+			start: Bound::Included(RecordIdKey::Array(Array(vec![Value::Datetime(from)]))),
+			end: match next {
+				Some(next) => Bound::Excluded(RecordIdKey::Array(Array(vec![Value::Datetime(
+					Datetime(next),
+				)]))),
+				None => Bound::Unbounded,
+			},
+		};
+		Some(match sc {
+			ScanDirection::Forward => vec![uuids, arrays],
+			ScanDirection::Backward => vec![arrays, uuids],
+		})
+	}
+}
+
+/// Returns the record id ranges to scan instead of the whole table, if the
+/// condition of the statement bounds the creation time of the records
+pub(super) async fn ranges(
+	stk: &mut Stk,
+	stm_ctx: &StatementContext<'_>,
+	sc: ScanDirection,
+) -> Result<Option<Vec<RecordIdKeyRange>>> {
+	let Some(cond) = stm_ctx.cond else {
+		return Ok(None);
+	};
+	let mut bounds = TimeBounds::default();
+	for (op, value) in comparisons(cond) {
+		let value = stk
+			.run(|stk| value.compute(stk, stm_ctx.ctx, stm_ctx.opt, None))
+			.await
+			.catch_return()?;
+		if let Value::Datetime(value) = value {
+			bounds.narrow(&op, value);
+		}
+	}
+	Ok(bounds.ranges(sc))
+}
+
+/// Finds the comparisons of the creation time of the records with a value
+/// which is the same for every record, within the top-level AND chain of a
+/// condition. The operators are normalised so that the creation time is on
+/// the left.
+pub(crate) fn comparisons(cond: &Cond) -> Vec<(BinaryOperator, &Expr)> {
+	let mut out = Vec::new();
+	let mut conjuncts = vec![&cond.0];
+	while let Some(e) = conjuncts.pop() {
+		let Expr::Binary {
+			left,
+			op,
+			right,
+		} = e
+		else {
+			continue;
+		};
+		// Every branch of an AND must match
+		if *op == BinaryOperator::And {
+			conjuncts.push(left);
+			conjuncts.push(right);
+			continue;
+		}
+		if !matches!(
+			op,
+			BinaryOperator::LessThan
+				| BinaryOperator::LessThanEqual
+				| BinaryOperator::MoreThan
+				| BinaryOperator::MoreThanEqual
+		) {
+			continue;
+		}
+		// created_at OP value, or value OP created_at
+		if is_created_at(left) && is_constant(right) {
+			out.push((op.clone(), &**right));
+		} else if is_created_at(right) && is_constant(left) {
+			let op = match op {
+				BinaryOperator::LessThan => BinaryOperator::MoreThan,
+				BinaryOperator::LessThanEqual => BinaryOperator::MoreThanEqual,
+				BinaryOperator::MoreThan => BinaryOperator::LessThan,
+				_ => BinaryOperator::LessThanEqual,
+			};
+			out.push((op, &**left));
+		}
+	}
+	out
+}
+
+/// Checks if an expression is `record::created_at(id)` or `id.created_at()`
+fn is_created_at(e: &Expr) -> bool {
+	match e {
+		Expr::FunctionCall(fc) => {
+			matches!(&fc.receiver, Function::Normal(name) if name == "record::created_at")
+				&& matches!(fc.arguments.as_slice(), [Expr::Idiom(i)] if i.is_id())
+		}
+		Expr::Idiom(i) => match i.0.as_slice() {
+			[id, Part::Method(name, args)] => {
+				*id == ID[0] && name.as_str() == "created_at" && args.is_empty()
+			}
+			_ => false,
+		},
+		_ => false,
+	}
+}
+
+/// Checks if an expression evaluates to the same value for every record
+fn is_constant(e: &Expr) -> bool {
+	match e {
+		Expr::Param(p) => !matches!(p.as_str(), "this" | "self"),
+		Expr::Binary {
+			left,
+			right,
+			..
+		} => is_constant(left) && is_constant(right),
+		Expr::FunctionCall(fc) => {
+			is_deterministic(&fc.receiver) && fc.arguments.iter().all(is_constant)
+		}
+		e => e.is_static(),
+	}
+}
+
+/// Checks if a function returns the same value each time it is called with
+/// the same arguments. Only builtin functions are known to, other than those
+/// which read the clock or generate random values.
+fn is_deterministic(f: &Function) -> bool {
+	match f {
+		Function::Normal(name) => {
+			!(name.starts_with("rand") || name.starts_with("time::now") || name == "sleep")
+		}
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::ops::RangeBounds;
+	use std::str::FromStr;
+
+	use super::*;
+
+	fn time(s: &str) -> Datetime {
+		Datetime::from_str(s).unwrap()
+	}
+
+	#[test]
+	fn bounds_are_narrowed() {
+		let mut bounds = TimeBounds::default();
+		bounds.narrow(&BinaryOperator::LessThan, time("2025-01-02T00:00:00Z"));
+		assert_eq!(bounds.ranges(ScanDirection::Forward), None);
+		bounds.narrow(&BinaryOperator::MoreThan, time("2025-01-01T00:00:00Z"));
+		bounds.narrow(&BinaryOperator::MoreThanEqual, time("2024-01-01T00:00:00Z"));
+		bounds.narrow(&BinaryOperator::LessThanEqual, time("2025-01-03T00:00:00Z"));
+		assert_eq!(
+			bounds,
+			TimeBounds {
+				from: Some(time("2025-01-01T00:00:00Z")),
+				to: Some(time("2025-01-02T00:00:00Z")),
+			}
+		);
+	}
+
+	#[test]
+	fn comparisons_need_constant_values() {
+		let cond = |s: &str| Cond(crate::syn::expr(s).unwrap().into());
+		let found = |s: &str| comparisons(&cond(s)).len();
+		assert_eq!(found("record::created_at(id) > d'2025-01-01T00:00:00Z'"), 1);
+		assert_eq!(found("id.created_at() >= $from AND id.created_at() < $to"), 2);
+		assert_eq!(found("id.created_at() > time::now() - 1h"), 0);
+		assert_eq!(found("record::created_at(id) > rand::time()"), 0);
+		assert_eq!(found("record::created_at(id) > fn::since()"), 0);
+	}
+
+	#[test]
+	fn ranges_contain_created_records() {
+		let bounds = TimeBounds {
+			from: Some(time("2025-01-01T00:00:00Z")),
+			to: Some(time("2025-01-02T00:00:00Z")),
+		};
+		let ranges = bounds.ranges(ScanDirection::Forward).unwrap();
+		let contains = |key: &RecordIdKey| {
+			ranges.iter().any(|r| (r.start.as_ref(), r.end.as_ref()).contains(key))
+		};
+		for (t, inside) in [
+			("2024-12-31T23:59:59.999Z", false),
+			("2025-01-01T00:00:00Z", true),
+			("2025-01-02T00:00:00Z", true),
+			("2025-01-02T00:00:00.001Z", false),
+		] {
+			let uuid = RecordIdKey::Uuid(Uuid::new_v7_from_datetime(time(t)));
+			assert_eq!(contains(&uuid), inside, "{t}");
+			let array =
+				RecordIdKey::Array(Array(vec![Value::Datetime(time(t)), Value::from("tobie")]));
+			assert_eq!(contains(&array), inside, "{t}");
+		}
+	}
+}
@@ -1,5 +1,6 @@
 pub(crate) mod count_exists_rewriter;
 pub(crate) mod executor;
+pub(crate) mod id_range;
 pub(crate) mod iterators;
 pub(in crate::idx) mod knn;
 pub(crate) mod plan;
@@ -97,12 +98,22 @@ impl<'a> StatementContext<'a> {
 	/// - `Full` — no permission check applies (root/owner session) or the table grants the action
 	///   unconditionally; also returned when the table is absent here, since callers verify table
 	///   existence before invoking this method.
-	/// - `Specific` — the table defines a per-record permission expression for this statement type;
-	///   callers must fetch record values so the expression can be evaluated.
+	/// - `Specific` — the table defines a per-record permission expression for this statement type,
+	///   or hides soft deleted records from this SELECT; callers must fetch record values so the
+	///   expression or deletion marker can be evaluated.
 	/// - `None` — the statement is denied on this table; iterator preparation in
 	///   [`crate::dbs::iterator`] short-circuits on this result and skips ingesting any iterable
 	///   for the table.
 	pub(crate) async fn check_table_permission(&self, tb: &TableName) -> Result<GrantedPermission> {
+		// Soft deleted records are hidden based on their values
+		if matches!(self.stm, Statement::Select { .. })
+			&& !matches!(self.with, Some(With::Deleted))
+			&& let Ok((ns, db)) = self.ctx.get_ns_db_ids(self.opt).await
+			&& let Some(table) = self.ctx.tx().get_tb(ns, db, tb, None).await?
+			&& table.soft_delete.is_some()
+		{
+			return Ok(GrantedPermission::Specific);
+		}
 		if !self.is_perm {
 			return Ok(GrantedPermission::Full);
 		}
@@ -344,12 +355,25 @@ impl QueryPlanner {
 				self.add(doc_ctx.clone(), t.clone(), Some(ir), exe, it, keys_only);
 			}
 			Plan::TableIterator(reason, rs, sc) => {
-				if let Some(reason) = reason {
-					self.fallbacks.push(reason);
+				// Scan the records created within the bounds of the condition
+				let ranges = if is_knn {
+					None
+				} else {
+					id_range::ranges(stk, stm_ctx, sc).await?
+				};
+				if let Some(ranges) = ranges {
+					self.add(doc_ctx.clone(), t.clone(), None, exe, it, rs);
+					for r in ranges {
+						it.ingest(Iterable::Range(doc_ctx.clone(), t.clone(), r, rs, sc));
+					}
+				} else {
+					if let Some(reason) = reason {
+						self.fallbacks.push(reason);
+					}
+					self.add(doc_ctx.clone(), t.clone(), None, exe, it, rs);
+					it.ingest(Iterable::Table(doc_ctx.clone(), t.clone(), rs, sc));
+					is_table_iterator = true;
 				}
-				self.add(doc_ctx.clone(), t.clone(), None, exe, it, rs);
-				it.ingest(Iterable::Table(doc_ctx.clone(), t.clone(), rs, sc));
-				is_table_iterator = true;
 			}
 		}
 		if is_knn && is_table_iterator {
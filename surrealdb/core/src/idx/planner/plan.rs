@@ -74,6 +74,11 @@ impl PlanBuilder {
 			return Self::table_iterator(ctx, Some("WITH NOINDEX"), p.gp).await;
 		}
 
+		// Soft deleted records are left out of indexes
+		if let Some(With::Deleted) = ctx.with {
+			return Self::table_iterator(ctx, Some("WITH DELETED"), p.gp).await;
+		}
+
 		if let Some(io) = p.index_count {
 			return Ok(Plan::SingleIndex(None, io, RecordStrategy::Count));
 		}
@@ -30,6 +30,7 @@ impl<'a> MutVisitor for KnnConditionRewriter<'a> {
 			| Expr::Throw(_)
 			| Expr::IfElse(_)
 			| Expr::Select(_)
+			| Expr::With(_)
 			| Expr::Create(_)
 			| Expr::Update(_)
 			| Expr::Delete(_)
@@ -21,6 +21,7 @@ use crate::idx::trees::hnsw::cache::VectorCache;
 use crate::idx::trees::store::diskann::{DiskAnnIndexes, SharedDiskAnnIndex};
 use crate::idx::trees::store::hnsw::{HnswIndexes, SharedHnswIndex};
 use crate::idx::trees::store::mapper::Mappers;
+use crate::idx::usage::IndexUsage;
 
 #[derive(Clone)]
 pub struct IndexStores(Arc<Inner>);
@@ -34,6 +35,7 @@ struct Inner {
 	hnsw_indexes: HnswIndexes,
 	mappers: Mappers,
 	vector_cache: VectorCache,
+	usage: IndexUsage,
 }
 
 impl IndexStores {
@@ -48,6 +50,7 @@ impl IndexStores {
 			hnsw_indexes: HnswIndexes::default(),
 			mappers: Mappers::default(),
 			vector_cache: VectorCache::new(hnsw_cache_size),
+			usage: IndexUsage::default(),
 		}))
 	}
 
@@ -100,6 +103,7 @@ impl IndexStores {
 		tb: TableId,
 		ix: &IndexDefinition,
 	) -> Result<()> {
+		self.0.usage.remove(ns, db, ix);
 		if matches!(ix.index, Index::Hnsw(_)) {
 			let ikb = IndexKeyBase::new(ns, db, ix.table_name.clone(), ix.index_id);
 			self.remove_hnsw_index(tb, ikb).await?;
@@ -134,4 +138,8 @@ impl IndexStores {
 	pub(crate) fn vector_cache(&self) -> &VectorCache {
 		&self.0.vector_cache
 	}
+
+	pub(crate) fn usage(&self) -> &IndexUsage {
+		&self.0.usage
+	}
 }
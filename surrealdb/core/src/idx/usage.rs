@@ -0,0 +1,237 @@
+//! Per-index usage statistics.
+//!
+//! Every datastore keeps an in-memory record of how often each index is read
+//! by a query plan and written by a document mutation. The counters are
+//! process-local and start from zero whenever the datastore is opened, so
+//! they describe usage since the datastore started rather than the whole
+//! lifetime of an index.
+//!
+//! Writes are counted when the index entries for a document are computed,
+//! which happens before the surrounding transaction commits. A transaction
+//! which is later cancelled therefore still counts as a write.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
+
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{DatabaseId, IndexDefinition, IndexId, NamespaceId};
+use crate::kvs::Transaction;
+use crate::val::{Object, TableName, Value};
+
+type UsageKey = (NamespaceId, DatabaseId, TableName, IndexId);
+
+/// Read and write counters for a single index.
+#[derive(Default)]
+struct Counters {
+	reads: AtomicU64,
+	writes: AtomicU64,
+	/// Milliseconds since the unix epoch, or `0` if never read
+	last_read: AtomicI64,
+	/// Milliseconds since the unix epoch, or `0` if never written
+	last_write: AtomicI64,
+}
+
+/// A point-in-time copy of the usage counters for one index.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IndexUsageStats {
+	/// How many times a query plan has read from the index
+	pub reads: u64,
+	/// How many document mutations have updated the index
+	pub writes: u64,
+	/// When the index was last read by a query plan
+	pub last_read: Option<DateTime<Utc>>,
+	/// When the index was last updated by a document mutation
+	pub last_write: Option<DateTime<Utc>>,
+}
+
+/// An index which has not been read for at least the requested duration.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnusedIndex {
+	/// The table the index is defined on
+	pub table: String,
+	/// The name of the index
+	pub index: String,
+	/// The usage recorded for the index since tracking started
+	pub stats: IndexUsageStats,
+}
+
+pub(crate) struct IndexUsage {
+	since: DateTime<Utc>,
+	entries: DashMap<UsageKey, Arc<Counters>>,
+}
+
+impl Default for IndexUsage {
+	fn default() -> Self {
+		Self {
+			since: Utc::now(),
+			entries: DashMap::new(),
+		}
+	}
+}
+
+impl IndexUsage {
+	/// Records that a query plan read from the given index
+	pub(crate) fn record_read(&self, ns: NamespaceId, db: DatabaseId, ix: &IndexDefinition) {
+		let c = self.counters(ns, db, ix);
+		c.reads.fetch_add(1, Ordering::Relaxed);
+		c.last_read.store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+	}
+
+	/// Records that a document mutation updated the given index
+	pub(crate) fn record_write(&self, ns: NamespaceId, db: DatabaseId, ix: &IndexDefinition) {
+		let c = self.counters(ns, db, ix);
+		c.writes.fetch_add(1, Ordering::Relaxed);
+		c.last_write.store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+	}
+
+	/// Returns the usage recorded for the given index
+	pub(crate) fn stats(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		ix: &IndexDefinition,
+	) -> IndexUsageStats {
+		let key = (ns, db, ix.table_name.clone(), ix.index_id);
+		let Some(c) = self.entries.get(&key) else {
+			return IndexUsageStats::default();
+		};
+		IndexUsageStats {
+			reads: c.reads.load(Ordering::Relaxed),
+			writes: c.writes.load(Ordering::Relaxed),
+			last_read: from_millis(c.last_read.load(Ordering::Relaxed)),
+			last_write: from_millis(c.last_write.load(Ordering::Relaxed)),
+		}
+	}
+
+	/// Forgets the usage recorded for an index which has been removed
+	pub(crate) fn remove(&self, ns: NamespaceId, db: DatabaseId, ix: &IndexDefinition) {
+		self.entries.remove(&(ns, db, ix.table_name.clone(), ix.index_id));
+	}
+
+	/// Returns every index in the database which has not been read for at
+	/// least the given duration.
+	///
+	/// An index which has never been read is only reported once usage has
+	/// been tracked for longer than `unused_for`, as there is otherwise no
+	/// evidence that it is unused.
+	pub(crate) async fn unused(
+		&self,
+		txn: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		unused_for: Duration,
+	) -> Result<Vec<UnusedIndex>> {
+		let cutoff = TimeDelta::from_std(unused_for)
+			.ok()
+			.and_then(|d| Utc::now().checked_sub_signed(d))
+			.unwrap_or(DateTime::<Utc>::MIN_UTC);
+		let mut out = Vec::new();
+		for tb in txn.all_tb(ns, db, None).await?.iter() {
+			for ix in txn.all_tb_indexes(ns, db, &tb.name, None).await?.iter() {
+				let stats = self.stats(ns, db, ix);
+				let unused = match stats.last_read {
+					Some(last) => last <= cutoff,
+					None => self.since <= cutoff,
+				};
+				if unused {
+					out.push(UnusedIndex {
+						table: tb.name.to_string(),
+						index: ix.name.to_string(),
+						stats,
+					});
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	/// Builds the output of `INFO FOR TABLE ... INDEX STATS`
+	pub(crate) async fn table_info(
+		&self,
+		txn: &Transaction,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+	) -> Result<Value> {
+		let mut indexes = Object::default();
+		for ix in txn.all_tb_indexes(ns, db, tb, None).await?.iter() {
+			let stats = self.stats(ns, db, ix);
+			indexes.insert(
+				ix.name.to_string(),
+				Value::from(map! {
+					"reads" => Value::from(stats.reads as i64),
+					"writes" => Value::from(stats.writes as i64),
+					"last_read" => stats.last_read.map(Value::from).unwrap_or_default(),
+					"last_write" => stats.last_write.map(Value::from).unwrap_or_default(),
+				}),
+			);
+		}
+		Ok(Value::from(map! {
+			"since" => Value::from(self.since),
+			"indexes" => Value::from(indexes),
+		}))
+	}
+
+	fn counters(&self, ns: NamespaceId, db: DatabaseId, ix: &IndexDefinition) -> Arc<Counters> {
+		let key = (ns, db, ix.table_name.clone(), ix.index_id);
+		if let Some(c) = self.entries.get(&key) {
+			return Arc::clone(&c);
+		}
+		Arc::clone(&self.entries.entry(key).or_default())
+	}
+}
+
+fn from_millis(ms: i64) -> Option<DateTime<Utc>> {
+	if ms == 0 {
+		None
+	} else {
+		DateTime::from_timestamp_millis(ms)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::catalog::Index;
+
+	fn index(table: &str, id: u32) -> IndexDefinition {
+		IndexDefinition {
+			index_id: IndexId(id),
+			name: format!("ix{id}").into(),
+			table_name: TableName::from(table),
+			cols: vec![],
+			index: Index::Idx,
+			comment: None,
+			prepare_remove: false,
+		}
+	}
+
+	#[test]
+	fn records_reads_and_writes() {
+		let usage = IndexUsage::default();
+		let ns = NamespaceId(1);
+		let db = DatabaseId(2);
+		let a = index("person", 1);
+		let b = index("person", 2);
+		usage.record_read(ns, db, &a);
+		usage.record_read(ns, db, &a);
+		usage.record_write(ns, db, &a);
+		let stats = usage.stats(ns, db, &a);
+		assert_eq!(stats.reads, 2);
+		assert_eq!(stats.writes, 1);
+		assert!(stats.last_read.is_some());
+		assert!(stats.last_write.is_some());
+		// Other indexes are unaffected
+		assert_eq!(usage.stats(ns, db, &b), IndexUsageStats::default());
+		// The same index id on another table is tracked separately
+		assert_eq!(usage.stats(ns, db, &index("other", 1)), IndexUsageStats::default());
+		// Removing an index forgets its usage
+		usage.remove(ns, db, &a);
+		assert_eq!(usage.stats(ns, db, &a), IndexUsageStats::default());
+	}
+}
@@ -19,6 +19,8 @@ pub enum Category {
 	AccessRoot,
 	/// crate::key::root::access::gr         /*{ac}!gr{gr}
 	AccessGrant,
+	/// crate::key::root::sg                 /!sg{kind}{sub}{ac}{gr}
+	SubjectGrant,
 	/// crate::key::root::nd                 /!nd{nd}
 	Node,
 	/// crate::key::root::nb                 /!nb
@@ -39,11 +41,31 @@ pub enum Category {
 	Reclaim,
 	/// crate::key::root::eq                 /!eq{ns}{db}{tb}{ev}{ts}{nid}
 	EventQueue,
+	/// crate::key::root::ed                 /!ed{ns}{db}{tb}{ev}{ts}{nid}
+	EventDeadLetter,
+	/// crate::key::root::eb                 /!eb{time}{ns}{db}{tb}{ev}{ts}{nid}
+	EventBackoff,
+	/// crate::key::root::ob                 /!ob{time}{ts}{nid}
+	Outbox,
+	/// crate::key::root::od                 /!od{ns}{db}{ts}{nid}
+	OutboxDeadLetter,
+	/// crate::key::root::im                 /!im{id}
+	ImportProgress,
+	/// crate::key::root::gl                 /!gl{kind}{scope}{id}
+	GrantRateLimit,
+	/// crate::key::root::cr                 /!cr{ns}{db}{tb}{id}
+	CorruptRecord,
+	/// crate::key::root::cs                 /!cs
+	RecordChecksumsEnabled,
+	/// crate::key::root::si                 /!si
+	SubjectGrantsIndexed,
 	///
 	/// ------------------------------
 	///
 	/// crate::key::node::all                /${nd}
 	NodeRoot,
+	/// crate::key::node::hc                 /${nd}!hc
+	NodeHealthCheck,
 	/// crate::key::node::lq                 /${nd}!lq{lq}{ns}{db}
 	NodeLiveQuery,
 	///
@@ -74,6 +96,8 @@ pub enum Category {
 	NamespaceAccessRoot,
 	/// crate::key::namespace::access::gr    /*{ns}*{ac}!gr{gr}
 	NamespaceAccessGrant,
+	/// crate::key::namespace::sg            /*{ns}!sg{kind}{sub}{ac}{gr}
+	NamespaceSubjectGrant,
 	/// crate::key::namespace::us            /*{ns}!us{us}
 	NamespaceUser,
 	///
@@ -87,6 +111,10 @@ pub enum Category {
 	DatabaseAccessRoot,
 	/// crate::key::database::access::gr     /*{ns}*{db}*ac!gr{gr}
 	DatabaseAccessGrant,
+	/// crate::key::database::access::mc     /*{ns}*{db}*ac!mc{gr}
+	DatabaseMfaChallenge,
+	/// crate::key::database::sg             /*{ns}*{db}!sg{kind}{sub}{ac}{gr}
+	DatabaseSubjectGrant,
 	/// crate::key::database::ap             /*{ns}*{db}!ap{ap}
 	DatabaseApi,
 	/// crate::key::database::az             /*{ns}*{db}!az{az}
@@ -99,6 +127,8 @@ pub enum Category {
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::qy             /*{ns}*{db}!qy{qy}
+	DatabaseQuery,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 	DatabaseTable,
 	/// crate::key::database::ts             /*{ns}*{db}!ts{ts}
@@ -111,21 +141,45 @@ pub enum Category {
 	DatabaseConfig,
 	/// crate::key::database::sq             /*{ns}*{db}*sq{sq}
 	DatabaseSequence,
+	/// crate::key::database::lq             /*{ns}*{db}!lq{lq}
+	DatabaseLiveQuery,
+	/// crate::key::database::ik             /*{ns}*{db}!ik{ik}
+	DatabaseIdempotencyKey,
+	/// crate::key::database::cs             /*{ns}*{db}!cs{start}{uid}
+	DatabaseChangeFeedSegment,
 	///
 	/// ------------------------------
 	///
 	/// crate::key::table::all               /*{ns}*{db}*{tb}
 	TableRoot,
+	/// crate::key::table::ck                /*{ns}*{db}*{tb}!ck{id}
+	TableRecordChecksum,
 	/// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 	TableEvent,
+	/// crate::key::table::ew                /*{ns}*{db}*{tb}!ew{id}
+	TableRecordWritten,
 	/// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 	TableField,
 	/// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
 	TableView, // (ft = foreign table = view)
 	/// crate::key::table::ix                /*{ns}*{db}*{tb}!ix{ix}
 	IndexDefinition,
+	/// crate::key::table::ln                /*{ns}*{db}*{tb}!ln{id}
+	TableLineage,
 	/// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
 	TableLiveQuery,
+	/// crate::key::table::po                /*{ns}*{db}*{tb}!po{po}
+	TablePolicy,
+	/// crate::key::table::pp                /*{ns}*{db}*{tb}!pp{po}
+	TablePolicyProgress,
+	/// crate::key::table::sd                /*{ns}*{db}*{tb}!sd{ts}{id}
+	TableRecordDeleted,
+	/// crate::key::table::st                /*{ns}*{db}*{tb}!st{uid}
+	TableStats,
+	/// crate::key::table::va                /*{ns}*{db}*{tb}!va{arg}{group}{value}
+	TableViewValue,
+	/// crate::key::table::vs                /*{ns}*{db}*{tb}!vs
+	TableViewState,
 	///
 	/// ------------------------------
 	///
@@ -226,12 +280,14 @@ impl Display for Category {
 			Self::Access => "Access",
 			Self::AccessRoot => "AccessRoot",
 			Self::AccessGrant => "AccessGrant",
+			Self::SubjectGrant => "SubjectGrant",
 			Self::Node => "Node",
 			Self::NamespaceIdentifierBatch => "NamespaceIdentifierBatch",
 			Self::NamespaceIdentifierState => "NamespaceIdentifierState",
 			Self::Namespace => "Namespace",
 			Self::User => "User",
 			Self::NodeRoot => "NodeRoot",
+			Self::NodeHealthCheck => "NodeHealthCheck",
 			Self::NodeLiveQuery => "NodeLiveQuery",
 			Self::NamespaceRoot => "NamespaceRoot",
 			Self::DatabaseAlias => "DatabaseAlias",
@@ -240,17 +296,21 @@ impl Display for Category {
 			Self::NamespaceAccess => "NamespaceAccess",
 			Self::NamespaceAccessRoot => "NamespaceAccessRoot",
 			Self::NamespaceAccessGrant => "NamespaceAccessGrant",
+			Self::NamespaceSubjectGrant => "NamespaceSubjectGrant",
 			Self::NamespaceUser => "NamespaceUser",
 			Self::DatabaseRoot => "DatabaseRoot",
 			Self::DatabaseAccess => "DatabaseAccess",
 			Self::DatabaseAccessRoot => "DatabaseAccessRoot",
 			Self::DatabaseAccessGrant => "DatabaseAccessGrant",
+			Self::DatabaseMfaChallenge => "DatabaseMfaChallenge",
+			Self::DatabaseSubjectGrant => "DatabaseSubjectGrant",
 			Self::DatabaseApi => "DatabaseApi",
 			Self::DatabaseAnalyzer => "DatabaseAnalyzer",
 			Self::DatabaseBucket => "DatabaseBucket",
 			Self::DatabaseFunction => "DatabaseFunction",
 			Self::DatabaseModel => "DatabaseModel",
 			Self::DatabaseParameter => "DatabaseParameter",
+			Self::DatabaseQuery => "DatabaseQuery",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifierBatch => "DatabaseTableIdentifierBatch",
 			Self::DatabaseTableIdentifierState => "DatabaseTableIdentifierState",
@@ -259,12 +319,24 @@ impl Display for Category {
 			Self::DatabaseVersionstamp => "DatabaseVersionstamp",
 			Self::DatabaseSequence => "DatabaseSequence",
 			Self::DatabaseConfig => "DatabaseConfig",
+			Self::DatabaseLiveQuery => "DatabaseLiveQuery",
+			Self::DatabaseIdempotencyKey => "DatabaseIdempotencyKey",
+			Self::DatabaseChangeFeedSegment => "DatabaseChangeFeedSegment",
 			Self::TableRoot => "TableRoot",
+			Self::TableRecordChecksum => "TableRecordChecksum",
 			Self::TableEvent => "TableEvent",
+			Self::TableRecordWritten => "TableRecordWritten",
 			Self::TableField => "TableField",
 			Self::TableView => "TableView",
 			Self::IndexDefinition => "IndexDefinition",
+			Self::TableLineage => "TableLineage",
 			Self::TableLiveQuery => "TableLiveQuery",
+			Self::TablePolicy => "TablePolicy",
+			Self::TablePolicyProgress => "TablePolicyProgress",
+			Self::TableRecordDeleted => "TableRecordDeleted",
+			Self::TableStats => "TableStats",
+			Self::TableViewValue => "TableViewValue",
+			Self::TableViewState => "TableViewState",
 			Self::IndexRoot => "IndexRoot",
 			Self::IndexTermDocList => "IndexTermDocList",
 			Self::IndexBTreeNode => "IndexBTreeNode",
@@ -305,6 +377,15 @@ impl Display for Category {
 			Self::IndexBuildAppending => "IndexBuildAppending",
 			Self::IndexBuildPrimaryAppending => "IndexBuildPrimaryAppending",
 			Self::EventQueue => "EventQueue",
+			Self::EventDeadLetter => "EventDeadLetter",
+			Self::EventBackoff => "EventBackoff",
+			Self::Outbox => "Outbox",
+			Self::OutboxDeadLetter => "OutboxDeadLetter",
+			Self::ImportProgress => "ImportProgress",
+			Self::GrantRateLimit => "GrantRateLimit",
+			Self::CorruptRecord => "CorruptRecord",
+			Self::RecordChecksumsEnabled => "RecordChecksumsEnabled",
+			Self::SubjectGrantsIndexed => "SubjectGrantsIndexed",
 			Self::TableIndexIdentifierBatch => "TableIndexIdentifierBatch",
 			Self::TableIndexIdentifierState => "TableIndexIdentifierState",
 		};
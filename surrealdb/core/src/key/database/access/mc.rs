@@ -0,0 +1,67 @@
+//! Stores the pending second factor challenge of a grant
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::iam::totp::MfaChallenge;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct MfaChallengeKey<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub ac: Cow<'a, str>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub gr: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(MfaChallengeKey<'_> => MfaChallenge);
+
+pub fn new<'a>(ns: NamespaceId, db: DatabaseId, ac: &'a str, gr: &'a str) -> MfaChallengeKey<'a> {
+	MfaChallengeKey::new(ns, db, ac, gr)
+}
+
+impl Categorise for MfaChallengeKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseMfaChallenge
+	}
+}
+
+impl<'a> MfaChallengeKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, ac: &'a str, gr: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'&',
+			ac: Cow::Borrowed(ac),
+			_d: b'!',
+			_e: b'm',
+			_f: b'c',
+			gr: Cow::Borrowed(gr),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = MfaChallengeKey::new(NamespaceId(1), DatabaseId(2), "testac", "testgr");
+		let enc = MfaChallengeKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02&testac\0!mctestgr\0");
+	}
+}
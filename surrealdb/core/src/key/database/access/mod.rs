@@ -1,2 +1,3 @@
 pub mod all;
 pub mod gr;
+pub mod mc;
@@ -0,0 +1,115 @@
+//! Stores the number and size of the changefeed entries of a time segment
+//!
+//! Like the statistics of a table, each transaction which writes changefeed
+//! entries appends a delta entry with a unique `uid`, rather than updating a
+//! single entry in place. The deltas of a segment are periodically compacted
+//! into a single entry with a `uid` of `None`.
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::cf::segment::SegmentStatsDelta;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Cs {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	/// The start of the segment, in milliseconds since the unix epoch
+	pub start: u64,
+	pub uid: Option<Uuid>,
+}
+
+impl_kv_key_storekey!(Cs => SegmentStatsDelta);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, start: u64, uid: Option<Uuid>) -> Cs {
+	Cs::new(ns, db, start, uid)
+}
+
+/// The range covering the entries of every segment
+pub fn range(ns: NamespaceId, db: DatabaseId) -> Result<Range<Vec<u8>>> {
+	let mut beg = super::all::new(ns, db).encode_key()?;
+	beg.extend_from_slice(b"!cs");
+	let mut end = beg.clone();
+	beg.push(0x00);
+	end.push(0xff);
+	Ok(beg..end)
+}
+
+/// The range covering the entries of every segment which starts before the
+/// given time
+pub fn range_before(ns: NamespaceId, db: DatabaseId, start: u64) -> Result<Range<Vec<u8>>> {
+	let mut rng = range(ns, db)?;
+	rng.end = Cs::new(ns, db, start, None).encode_key()?;
+	Ok(rng)
+}
+
+impl Categorise for Cs {
+	fn categorise(&self) -> Category {
+		Category::DatabaseChangeFeedSegment
+	}
+}
+
+impl Cs {
+	pub fn new(ns: NamespaceId, db: DatabaseId, start: u64, uid: Option<Uuid>) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'c',
+			_e: b's',
+			start,
+			uid,
+		}
+	}
+
+	pub fn decode_key(k: &[u8]) -> Result<Cs> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let val = Cs::new(NamespaceId(1), DatabaseId(2), 600_000, None);
+		let enc = Cs::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cs\x00\x00\x00\x00\x00\x09\x27\xc0\x02"
+		);
+	}
+
+	#[test]
+	fn range() {
+		let r = super::range(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(r.start, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cs\x00");
+		assert_eq!(r.end, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cs\xff");
+	}
+
+	#[test]
+	fn range_before() {
+		let r = super::range_before(NamespaceId(1), DatabaseId(2), 600_000).unwrap();
+		assert_eq!(r.start, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cs\x00");
+		assert_eq!(
+			r.end,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!cs\x00\x00\x00\x00\x00\x09\x27\xc0\x02"
+		);
+	}
+}
@@ -0,0 +1,87 @@
+//! Stores the result of a query which was run with an idempotency key
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::idempotency::IdempotencyRecord;
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Ik<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub ik: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Ik<'_> => IdempotencyRecord);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, ik: &str) -> Ik<'_> {
+	Ik::new(ns, db, ik)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!ik\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!ik\xff");
+	Ok(k)
+}
+
+impl Categorise for Ik<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseIdempotencyKey
+	}
+}
+
+impl<'a> Ik<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, ik: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'i',
+			_e: b'k',
+			ik: Cow::Borrowed(ik),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let val = Ik::new(NamespaceId(1), DatabaseId(2), "testik");
+		let enc = Ik::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!iktestik\0");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!ik\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!ik\xff");
+	}
+}
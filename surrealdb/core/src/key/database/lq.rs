@@ -0,0 +1,87 @@
+//! Stores a LIVE SCHEMA query definition on the database
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::catalog::{DatabaseId, NamespaceId, SubscriptionDefinition};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+/// Tracks a subscription to the schema changes of a database. Like a table
+/// live query, the stored definition includes the node id, and the node keeps
+/// a matching [`crate::key::node::lq`] entry.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Lq {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub lq: Uuid,
+}
+
+impl_kv_key_storekey!(Lq => SubscriptionDefinition);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, lq: Uuid) -> Lq {
+	Lq::new(ns, db, lq)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!lq\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!lq\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00");
+	Ok(k)
+}
+
+impl Categorise for Lq {
+	fn categorise(&self) -> Category {
+		Category::DatabaseLiveQuery
+	}
+}
+
+impl Lq {
+	pub fn new(ns: NamespaceId, db: DatabaseId, lq: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'l',
+			_e: b'q',
+			lq,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let live_query_id =
+			Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let val = Lq::new(NamespaceId(1), DatabaseId(2), live_query_id);
+		let enc = Lq::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!lq\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!lq\x00")
+	}
+}
@@ -5,10 +5,15 @@ pub mod ap;
 pub mod az;
 pub mod bu;
 pub mod cg;
+pub mod cs;
 pub mod fc;
+pub mod ik;
+pub mod lq;
 pub mod md;
 pub mod ml;
 pub mod pa;
+pub mod qy;
+pub mod sg;
 pub mod sq;
 pub mod tb;
 pub mod th;
@@ -0,0 +1,86 @@
+//! Stores a DEFINE QUERY template definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId, QueryDefinition};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Qy<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub qy: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Qy<'_> => QueryDefinition);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, qy: &str) -> Qy<'_> {
+	Qy::new(ns, db, qy)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!qy\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode_key()?;
+	k.extend_from_slice(b"!qy\xff");
+	Ok(k)
+}
+
+impl Categorise for Qy<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseQuery
+	}
+}
+
+impl<'a> Qy<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, qy: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'q',
+			_e: b'y',
+			qy: Cow::Borrowed(qy),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let val = Qy::new(NamespaceId(1), DatabaseId(2), "testqy");
+		let enc = Qy::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!qytestqy\0");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!qy\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!qy\xff");
+	}
+}
@@ -28,8 +28,15 @@
 //! crate::key::root::us                 /!us{us}
 //! crate::key::root::tl                 /!tl{tl}
 //! crate::key::root::cg                 /!cg{ty}
+//! crate::key::root::im                 /!im{ns}{db}{id} -> ImportProgress
+//! crate::key::root::eb                 /!eb{time}{ns}{db}{tb}{ev}{ts}{nid} -> AsyncEventRecord
+//! crate::key::root::ed                 /!ed{ns}{db}{tb}{ev}{ts}{nid} -> DeadLetterRecord
+//! crate::key::root::ob                 /!ob{time}{ts}{nid} -> OutboxRecord
+//! crate::key::root::od                 /!od{ns}{db}{ts}{nid} -> OutboxDeadLetterRecord
+//! crate::key::root::gl                 /!gl{kind}{scope}{id} -> GrantWindow
 //!
 //! crate::key::node::all                /${nd}
+//! crate::key::node::hc                 /${nd}!hc
 //! crate::key::node::lq                 /${nd}!lq{lq}{ns}{db}
 //!
 //! crate::key::root::access::all        /&{ac}
@@ -50,9 +57,12 @@
 //! crate::key::database::az             /*{ns}*{db}!az{az_name}
 //! crate::key::database::bu             /*{ns}*{db}!bu{bu_name}
 //! crate::key::database::fc             /*{ns}*{db}!fn{fc_name}
+//! crate::key::database::ik             /*{ns}*{db}!ik{ik}
+//! crate::key::database::lq             /*{ns}*{db}!lq{lq}
 //! crate::key::database::md             /*{ns}*{db}!md{md_name} -> ModuleDefinition
 //! crate::key::database::ml             /*{ns}*{db}!ml{ml_name}{vn}
 //! crate::key::database::pa             /*{ns}*{db}!pa{pa_name}
+//! crate::key::database::qy             /*{ns}*{db}!qy{qy_name} -> QueryDefinition
 //! crate::key::database::sq             /*{ns}*{db}!sq{sq_name}
 //! crate::key::database::tb             /*{ns}*{db}!tb{tb_name} -> TableDefinition
 //! crate::key::database::ti             /+{ns}*{db}!ti
@@ -60,17 +70,27 @@
 //! crate::key::database::us             /*{ns}*{db}!us{us_name}
 //! crate::key::database::vs             /*{ns}*{db}!vs
 //! crate::key::database::cg             /*{ns}*{db}!cg{ty}
+//! crate::key::database::cs             /*{ns}*{db}!cs{start}{uid} -> SegmentStatsDelta
 //!
 //! crate::key::database::access::all    /*{ns}*{db}&{ac}
 //! crate::key::database::access::gr     /*{ns}*{db}&{ac}!gr{gr}
+//! crate::key::database::access::mc     /*{ns}*{db}&{ac}!mc{gr}
 //!
 //! crate::key::table::all               /*{ns}*{db}*{tb_name}
 //! crate::key::table::ev                /*{ns}*{db}*{tb_name}!ev{ev}
+//! crate::key::table::ew                /*{ns}*{db}*{tb_name}!ew{id} -> u64
 //! crate::key::table::fd                /*{ns}*{db}*{tb_name}!fd{fd}
 //! crate::key::table::ft                /*{ns}*{db}*{tb_name}!ft{ft}
 //! crate::key::table::ix                /*{ns}*{db}*{tb_name}!il{ix} -> ix_name
 //! crate::key::table::ix                /*{ns}*{db}*{tb_name}!ix{ix_name} -> IndexDefinition
+//! crate::key::table::ln                /*{ns}*{db}*{tb_name}!ln{id}
 //! crate::key::table::lq                /*{ns}*{db}*{tb_name}!lq{lq}
+//! crate::key::table::po                /*{ns}*{db}*{tb_name}!po{po}
+//! crate::key::table::pp                /*{ns}*{db}*{tb_name}!pp{po}
+//! crate::key::table::sd                /*{ns}*{db}*{tb_name}!sd{ts}{id}
+//! crate::key::table::st                /*{ns}*{db}*{tb_name}!st{uid} -> TableStatsDelta
+//! crate::key::table::va                /*{ns}*{db}*{tb_name}!va{arg}{group}{value} -> ViewValueCount
+//! crate::key::table::vs                /*{ns}*{db}*{tb_name}!vs -> ViewState
 //!
 //! crate::key::index::all               /*{ns}*{db}*{tb_name}+{ix}
 //! crate::key::index::bc                /*{ns}*{db}*{tb_name}+{ix}!bc{id}
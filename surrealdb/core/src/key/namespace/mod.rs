@@ -4,4 +4,5 @@ pub mod all;
 pub mod db;
 pub mod dh;
 pub mod di;
+pub mod sg;
 pub mod us;
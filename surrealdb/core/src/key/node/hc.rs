@@ -0,0 +1,60 @@
+//! Stores the scratch value written by the health check of a node
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+/// The Hc key holds a random value, which is written and then read back by
+/// the health check of a node, in order to verify that the storage engine can
+/// be written to and read from.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Hc {
+	__: u8,
+	_a: u8,
+	pub nd: Uuid,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+}
+
+impl_kv_key_storekey!(Hc => u64);
+
+pub fn new(nd: Uuid) -> Hc {
+	Hc::new(nd)
+}
+
+impl Categorise for Hc {
+	fn categorise(&self) -> Category {
+		Category::NodeHealthCheck
+	}
+}
+
+impl Hc {
+	pub fn new(nd: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'$',
+			nd,
+			_b: b'!',
+			_c: b'h',
+			_d: b'c',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = Hc::new(Uuid::from_bytes([
+			0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+			0x0f, 0x10,
+		]));
+		let enc = Hc::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/$\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10!hc");
+	}
+}
@@ -0,0 +1,66 @@
+//! Stores a record found corrupt by the background scrubber
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::kvs::scrub::CorruptRecord;
+use crate::val::{RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Cr<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ns: NamespaceId,
+	pub db: DatabaseId,
+	pub tb: Cow<'a, TableName>,
+	pub id: RecordIdKey,
+}
+
+impl_kv_key_storekey!(Cr<'_> => CorruptRecord);
+
+impl Categorise for Cr<'_> {
+	fn categorise(&self) -> Category {
+		Category::CorruptRecord
+	}
+}
+
+impl<'a> Cr<'a> {
+	pub(crate) fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, id: RecordIdKey) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'c',
+			_c: b'r',
+			ns,
+			db,
+			tb: Cow::Borrowed(tb),
+			id,
+		}
+	}
+
+	pub(crate) fn range() -> (Vec<u8>, Vec<u8>) {
+		(b"/!cr\x00".to_vec(), b"/!cr\xff".to_vec())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Cr::new(NamespaceId(1), DatabaseId(2), &tb, RecordIdKey::Number(3));
+		let enc = Cr::encode_key(&val).unwrap();
+		let (beg, end) = Cr::range();
+		assert!(enc.starts_with(b"/!cr\x00\x00\x00\x01\x00\x00\x00\x02testtb\0"));
+		assert!(beg < enc && enc < end);
+	}
+}
@@ -0,0 +1,48 @@
+//! Stores when record checksums were last enabled
+//!
+//! The checksums written before then may be stale, as the records could have
+//! been changed while checksums were disabled, so the background scrubber
+//! writes them again rather than reporting the records as corrupt.
+use storekey::{BorrowDecode, Encode};
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Cs {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+}
+
+impl_kv_key_storekey!(Cs => u64);
+
+impl Categorise for Cs {
+	fn categorise(&self) -> Category {
+		Category::RecordChecksumsEnabled
+	}
+}
+
+impl Cs {
+	pub(crate) fn new() -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'c',
+			_c: b's',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let enc = Cs::new().encode_key().unwrap();
+		assert_eq!(enc, b"/!cs");
+	}
+}
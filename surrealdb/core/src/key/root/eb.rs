@@ -0,0 +1,111 @@
+//! Stores async events which are waiting to be retried
+//!
+//! A failed async event is moved here from the event queue until its retry
+//! backoff has elapsed. The keys are ordered by the time at which the event is
+//! due again, so that the events which can be retried are read without
+//! scanning the events which are still backing off.
+use std::borrow::Cow;
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::doc::AsyncEventRecord;
+use crate::key::category::{Categorise, Category};
+use crate::key::root::eq::EventQueue;
+use crate::kvs::{HlcTimeStamp, Key, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct EventBackoff<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	/// Milliseconds since the unix epoch at which the event is retried.
+	pub not_before: u64,
+	pub ns: NamespaceId,
+	pub db: DatabaseId,
+	pub tb: Cow<'a, TableName>,
+	pub ev: Cow<'a, str>,
+	/// Timestamp when the event was generated, copied from the queue key.
+	pub ts: u64,
+	/// The ID of the node that generated the event, copied from the queue key.
+	pub node_id: Uuid,
+}
+
+impl_kv_key_storekey!(EventBackoff<'_> => AsyncEventRecord);
+
+impl Categorise for EventBackoff<'_> {
+	fn categorise(&self) -> Category {
+		Category::EventBackoff
+	}
+}
+
+impl<'a> EventBackoff<'a> {
+	/// Creates the backoff key for an event taken from the queue
+	pub(crate) fn from_queue(not_before: u64, eq: &'a EventQueue<'_>) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'e',
+			_c: b'b',
+			not_before,
+			ns: eq.ns,
+			db: eq.db,
+			tb: Cow::Borrowed(eq.tb.as_ref()),
+			ev: Cow::Borrowed(eq.ev.as_ref()),
+			ts: eq.ts,
+			node_id: eq.node_id,
+		}
+	}
+
+	/// Returns the queue key the event is moved back to once it is due
+	pub(crate) fn to_queue(&self) -> EventQueue<'_> {
+		EventQueue::new(self.ns, self.db, &self.tb, &self.ev, HlcTimeStamp(self.ts), self.node_id)
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<EventBackoff<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Returns the range of every event waiting to be retried
+	pub(crate) fn range() -> Range<Key> {
+		b"/!eb".to_vec()..b"/!eb\xff".to_vec()
+	}
+
+	/// Returns the range of the events which are due at the given time, in
+	/// milliseconds since the unix epoch
+	pub(crate) fn due(now: u64) -> Range<Key> {
+		let beg = b"/!eb".to_vec();
+		let mut end = beg.clone();
+		end.extend_from_slice(&now.saturating_add(1).to_be_bytes());
+		beg..end
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let tb = TableName::from("testtb");
+		let eq = EventQueue::new(NamespaceId(1), DatabaseId(2), &tb, "testev", HlcTimeStamp(1), id);
+		let val = EventBackoff::from_queue(5, &eq);
+		let enc = EventBackoff::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/!eb\0\0\0\0\0\0\0\x05\x00\x00\x00\x01\x00\x00\x00\x02testtb\0testev\0\0\0\0\0\0\0\0\x01\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+		assert_eq!(val.to_queue(), eq);
+		assert!(EventBackoff::range().contains(&enc));
+		assert!(EventBackoff::due(5).contains(&enc));
+		assert!(!EventBackoff::due(4).contains(&enc));
+	}
+}
@@ -0,0 +1,144 @@
+//! Stores async events which could not be processed
+use std::borrow::Cow;
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::doc::DeadLetterRecord;
+use crate::key::category::{Categorise, Category};
+use crate::key::root::eq::EventQueue;
+use crate::kvs::{KVKey, Key, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct EventDeadLetter<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ns: NamespaceId,
+	pub db: DatabaseId,
+	pub tb: Cow<'a, TableName>,
+	pub ev: Cow<'a, str>,
+	/// Timestamp when the event was generated, copied from the queue key.
+	pub ts: u64,
+	/// The ID of the node that generated the event, copied from the queue key.
+	pub node_id: Uuid,
+}
+
+impl_kv_key_storekey!(EventDeadLetter<'_> => DeadLetterRecord);
+
+impl Categorise for EventDeadLetter<'_> {
+	fn categorise(&self) -> Category {
+		Category::EventDeadLetter
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode)]
+#[storekey(format = "()")]
+struct EventDeadLetterPrefix {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	ns: NamespaceId,
+	db: DatabaseId,
+}
+
+impl_kv_key_storekey!(EventDeadLetterPrefix => ());
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode)]
+#[storekey(format = "()")]
+struct EventDeadLetterNsPrefix {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	ns: NamespaceId,
+}
+
+impl_kv_key_storekey!(EventDeadLetterNsPrefix => ());
+
+impl<'a> EventDeadLetter<'a> {
+	/// Creates the dead-letter key for an event taken from the queue
+	pub(crate) fn from_queue(eq: &'a EventQueue<'_>) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'e',
+			_c: b'd',
+			ns: eq.ns,
+			db: eq.db,
+			tb: Cow::Borrowed(eq.tb.as_ref()),
+			ev: Cow::Borrowed(eq.ev.as_ref()),
+			ts: eq.ts,
+			node_id: eq.node_id,
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<EventDeadLetter<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Returns the range of dead-letter keys in a database
+	pub(crate) fn range(ns: NamespaceId, db: DatabaseId) -> Result<Range<Key>> {
+		let mut beg = EventDeadLetterPrefix {
+			__: b'/',
+			_a: b'!',
+			_b: b'e',
+			_c: b'd',
+			ns,
+			db,
+		}
+		.encode_key()?;
+		let mut end = beg.clone();
+		beg.push(0);
+		end.push(0xff);
+		Ok(beg..end)
+	}
+
+	/// Returns the range of dead-letter keys in every database of a namespace
+	pub(crate) fn range_ns(ns: NamespaceId) -> Result<Range<Key>> {
+		let mut beg = EventDeadLetterNsPrefix {
+			__: b'/',
+			_a: b'!',
+			_b: b'e',
+			_c: b'd',
+			ns,
+		}
+		.encode_key()?;
+		let mut end = beg.clone();
+		beg.push(0);
+		end.push(0xff);
+		Ok(beg..end)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::HlcTimeStamp;
+
+	#[test]
+	fn key() {
+		let id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let tb = TableName::from("testtb");
+		let eq = EventQueue::new(NamespaceId(1), DatabaseId(2), &tb, "testev", HlcTimeStamp(1), id);
+		let val = EventDeadLetter::from_queue(&eq);
+		let enc = EventDeadLetter::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/!ed\x00\x00\x00\x01\x00\x00\x00\x02testtb\0testev\0\0\0\0\0\0\0\0\x01\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+		let range = EventDeadLetter::range(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert!(range.contains(&enc));
+		let range = EventDeadLetter::range_ns(NamespaceId(1)).unwrap();
+		assert!(range.contains(&enc));
+		let range = EventDeadLetter::range_ns(NamespaceId(2)).unwrap();
+		assert!(!range.contains(&enc));
+	}
+}
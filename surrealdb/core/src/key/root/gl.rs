@@ -0,0 +1,106 @@
+//! Stores the number of access grants recently created for a subject or client
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::kvs::ratelimit::GrantWindow;
+
+/// What an access grant rate limit is counted against
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum GrantLimitKind {
+	/// Grants created for a record subject
+	Record,
+	/// Grants created for a system user subject
+	User,
+	/// Grants created from a client IP address
+	Ip,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct GrantLimitKey<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub kind: u8,
+	/// The namespace of the access method, if it is not a root access method
+	pub ns: Option<NamespaceId>,
+	/// The database of the access method, if it is a database access method
+	pub db: Option<DatabaseId>,
+	/// The name of the access method
+	pub ac: Cow<'a, str>,
+	pub id: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(GrantLimitKey<'_> => GrantWindow);
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = crate::key::root::all::kv();
+	k.extend_from_slice(b"!gl\x00");
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = crate::key::root::all::kv();
+	k.extend_from_slice(b"!gl\xff");
+	k
+}
+
+impl Categorise for GrantLimitKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::GrantRateLimit
+	}
+}
+
+impl<'a> GrantLimitKey<'a> {
+	pub fn new(
+		kind: GrantLimitKind,
+		ns: Option<NamespaceId>,
+		db: Option<DatabaseId>,
+		ac: &'a str,
+		id: &'a str,
+	) -> Self {
+		let kind = match kind {
+			GrantLimitKind::Record => b'r',
+			GrantLimitKind::User => b'u',
+			GrantLimitKind::Ip => b'i',
+		};
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'g',
+			_c: b'l',
+			kind,
+			ns,
+			db,
+			ac: Cow::Borrowed(ac),
+			id: Cow::Borrowed(id),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = GrantLimitKey::new(
+			GrantLimitKind::Ip,
+			Some(NamespaceId(1)),
+			Some(DatabaseId(2)),
+			"signup",
+			"127.0.0.1",
+		);
+		let enc = GrantLimitKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!gli\x03\x00\x00\x00\x01\x03\x00\x00\x00\x02signup\x00127.0.0.1\x00");
+		// A root access method can not collide with those of a namespace
+		let val = GrantLimitKey::new(GrantLimitKind::Ip, None, None, "1/2/signup", "127.0.0.1");
+		let enc = GrantLimitKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!gli\x02\x021/2/signup\x00127.0.0.1\x00");
+	}
+}
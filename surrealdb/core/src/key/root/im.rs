@@ -0,0 +1,59 @@
+//! Stores the progress of a resumable import
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::kvs::import::ImportProgress;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct ImportProgressKey<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	/// The namespace selected when the import started, if any
+	pub ns: Option<Cow<'a, str>>,
+	/// The database selected when the import started, if any
+	pub db: Option<Cow<'a, str>>,
+	pub id: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(ImportProgressKey<'_> => ImportProgress);
+
+impl Categorise for ImportProgressKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::ImportProgress
+	}
+}
+
+impl<'a> ImportProgressKey<'a> {
+	pub fn new(ns: Option<&'a str>, db: Option<&'a str>, id: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'i',
+			_c: b'm',
+			ns: ns.map(Cow::Borrowed),
+			db: db.map(Cow::Borrowed),
+			id: Cow::Borrowed(id),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = ImportProgressKey::new(Some("testns"), Some("testdb"), "/backups/test.surql");
+		let enc = ImportProgressKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!im\x03testns\x00\x03testdb\x00/backups/test.surql\x00");
+		let val = ImportProgressKey::new(None, None, "/backups/test.surql");
+		let enc = ImportProgressKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!im\x02\x02/backups/test.surql\x00");
+	}
+}
@@ -1,13 +1,23 @@
 pub mod ac;
 pub mod access;
 pub mod all;
+pub mod cr;
+pub mod cs;
+pub mod eb;
+pub mod ed;
 pub mod eq;
+pub mod gl;
 pub mod ic;
+pub mod im;
 pub mod nd;
 pub mod nh;
 pub mod ni;
 pub mod ns;
+pub mod ob;
+pub mod od;
 pub mod rc;
 pub mod root_config;
+pub mod sg;
+pub mod si;
 pub mod tl;
 pub mod us;
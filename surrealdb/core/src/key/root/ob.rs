@@ -0,0 +1,91 @@
+//! Stores the HTTP requests of outbox events which are waiting to be sent
+//!
+//! The requests are recorded by the transaction which triggered the event, so
+//! they only exist once it has committed. The keys are ordered by the time at
+//! which the request is due, so that the requests which are backing off after
+//! a failure are not read until they can be sent again.
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::doc::OutboxRecord;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{HlcTimeStamp, Key, impl_kv_key_storekey};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Outbox {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	/// Milliseconds since the unix epoch at which the request is sent.
+	pub not_before: u64,
+	/// Timestamp when this request was recorded (component 1 of the composite unique ID).
+	pub ts: u64,
+	/// The ID of the node that recorded the request (component 2 of the composite unique ID).
+	pub node_id: Uuid,
+}
+
+impl_kv_key_storekey!(Outbox => OutboxRecord);
+
+impl Categorise for Outbox {
+	fn categorise(&self) -> Category {
+		Category::Outbox
+	}
+}
+
+impl Outbox {
+	pub(crate) fn new(not_before: u64, ts: HlcTimeStamp, node_id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'o',
+			_c: b'b',
+			not_before,
+			ts: ts.0,
+			node_id,
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<Outbox> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Returns the range of every request waiting to be sent
+	pub(crate) fn range() -> Range<Key> {
+		b"/!ob".to_vec()..b"/!ob\xff".to_vec()
+	}
+
+	/// Returns the range of the requests which are due at the given time, in
+	/// milliseconds since the unix epoch
+	pub(crate) fn due(now: u64) -> Range<Key> {
+		let beg = b"/!ob".to_vec();
+		let mut end = beg.clone();
+		end.extend_from_slice(&now.saturating_add(1).to_be_bytes());
+		beg..end
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let val = Outbox::new(5, HlcTimeStamp(1), id);
+		let enc = Outbox::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/!ob\0\0\0\0\0\0\0\x05\0\0\0\0\0\0\0\x01\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+		assert_eq!(Outbox::decode_key(&enc).unwrap(), val);
+		assert!(Outbox::range().contains(&enc));
+		assert!(Outbox::due(5).contains(&enc));
+		assert!(!Outbox::due(4).contains(&enc));
+	}
+}
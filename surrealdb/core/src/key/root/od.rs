@@ -0,0 +1,138 @@
+//! Stores the HTTP requests of outbox events which could not be sent
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::doc::OutboxDeadLetterRecord;
+use crate::key::category::{Categorise, Category};
+use crate::key::root::ob::Outbox;
+use crate::kvs::{KVKey, Key, impl_kv_key_storekey};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct OutboxDeadLetter {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ns: NamespaceId,
+	pub db: DatabaseId,
+	/// Timestamp when the request was recorded, copied from the outbox key.
+	pub ts: u64,
+	/// The ID of the node that recorded the request, copied from the outbox key.
+	pub node_id: Uuid,
+}
+
+impl_kv_key_storekey!(OutboxDeadLetter => OutboxDeadLetterRecord);
+
+impl Categorise for OutboxDeadLetter {
+	fn categorise(&self) -> Category {
+		Category::OutboxDeadLetter
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode)]
+#[storekey(format = "()")]
+struct OutboxDeadLetterPrefix {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	ns: NamespaceId,
+	db: DatabaseId,
+}
+
+impl_kv_key_storekey!(OutboxDeadLetterPrefix => ());
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode)]
+#[storekey(format = "()")]
+struct OutboxDeadLetterNsPrefix {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	ns: NamespaceId,
+}
+
+impl_kv_key_storekey!(OutboxDeadLetterNsPrefix => ());
+
+impl OutboxDeadLetter {
+	/// Creates the dead-letter key for a request taken from the outbox
+	pub(crate) fn from_outbox(ns: NamespaceId, db: DatabaseId, ob: &Outbox) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'o',
+			_c: b'd',
+			ns,
+			db,
+			ts: ob.ts,
+			node_id: ob.node_id,
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<OutboxDeadLetter> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Returns the range of dead-letter keys in a database
+	pub(crate) fn range(ns: NamespaceId, db: DatabaseId) -> Result<Range<Key>> {
+		let mut beg = OutboxDeadLetterPrefix {
+			__: b'/',
+			_a: b'!',
+			_b: b'o',
+			_c: b'd',
+			ns,
+			db,
+		}
+		.encode_key()?;
+		let mut end = beg.clone();
+		beg.push(0);
+		end.push(0xff);
+		Ok(beg..end)
+	}
+
+	/// Returns the range of dead-letter keys in every database of a namespace
+	pub(crate) fn range_ns(ns: NamespaceId) -> Result<Range<Key>> {
+		let mut beg = OutboxDeadLetterNsPrefix {
+			__: b'/',
+			_a: b'!',
+			_b: b'o',
+			_c: b'd',
+			ns,
+		}
+		.encode_key()?;
+		let mut end = beg.clone();
+		beg.push(0);
+		end.push(0xff);
+		Ok(beg..end)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::HlcTimeStamp;
+
+	#[test]
+	fn key() {
+		let id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let ob = Outbox::new(5, HlcTimeStamp(1), id);
+		let val = OutboxDeadLetter::from_outbox(NamespaceId(1), DatabaseId(2), &ob);
+		let enc = OutboxDeadLetter::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/!od\x00\x00\x00\x01\x00\x00\x00\x02\0\0\0\0\0\0\0\x01\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+		assert_eq!(OutboxDeadLetter::decode_key(&enc).unwrap(), val);
+		let range = OutboxDeadLetter::range(NamespaceId(1), DatabaseId(2)).unwrap();
+		assert!(range.contains(&enc));
+		let range = OutboxDeadLetter::range_ns(NamespaceId(1)).unwrap();
+		assert!(range.contains(&enc));
+		let range = OutboxDeadLetter::range_ns(NamespaceId(2)).unwrap();
+		assert!(!range.contains(&enc));
+	}
+}
@@ -0,0 +1,103 @@
+//! Stores the access grants of a subject, across the root access methods
+use std::borrow::Cow;
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, Key, impl_kv_key_storekey};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct SubjectGrant<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub kind: u8,
+	pub sub: Cow<'a, str>,
+	pub ac: Cow<'a, str>,
+	pub gr: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(SubjectGrant<'_> => ());
+
+impl Categorise for SubjectGrant<'_> {
+	fn categorise(&self) -> Category {
+		Category::SubjectGrant
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode)]
+struct SubjectGrantPrefix {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	kind: u8,
+	sub: String,
+}
+
+impl_kv_key_storekey!(SubjectGrantPrefix => ());
+
+impl<'a> SubjectGrant<'a> {
+	pub(crate) fn new(subject: &catalog::Subject, ac: &'a str, gr: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b's',
+			_c: b'g',
+			kind: subject_kind(subject),
+			sub: Cow::Owned(subject.id()),
+			ac: Cow::Borrowed(ac),
+			gr: Cow::Borrowed(gr),
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<SubjectGrant<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+
+	/// Returns the range of the grant keys of a subject
+	pub(crate) fn range(subject: &catalog::Subject) -> Result<Range<Key>> {
+		let mut beg = SubjectGrantPrefix {
+			__: b'/',
+			_a: b'!',
+			_b: b's',
+			_c: b'g',
+			kind: subject_kind(subject),
+			sub: subject.id(),
+		}
+		.encode_key()?;
+		let mut end = beg.clone();
+		beg.push(0);
+		end.push(0xff);
+		Ok(beg..end)
+	}
+}
+
+/// Distinguishes a record subject from a system user with the same name
+pub(crate) fn subject_kind(subject: &catalog::Subject) -> u8 {
+	match subject {
+		catalog::Subject::Record(_) => b'r',
+		catalog::Subject::User(_) => b'u',
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let subject = catalog::Subject::User("tobie".to_owned());
+		let val = SubjectGrant::new(&subject, "testac", "testgr");
+		let enc = SubjectGrant::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!sgutobie\0testac\0testgr\0");
+		let range = SubjectGrant::range(&subject).unwrap();
+		assert!(range.contains(&enc));
+		let dec = SubjectGrant::decode_key(&enc).unwrap();
+		assert_eq!(dec, val);
+	}
+}
@@ -0,0 +1,49 @@
+//! Stores that the access grants of the datastore are stored under their
+//! subjects
+//!
+//! Grants created before the grants of a subject could be listed are not
+//! stored under their subjects, so they are backfilled once, when the
+//! datastore is bootstrapped, after which this key is set.
+use storekey::{BorrowDecode, Encode};
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Si {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+}
+
+impl_kv_key_storekey!(Si => ());
+
+impl Categorise for Si {
+	fn categorise(&self) -> Category {
+		Category::SubjectGrantsIndexed
+	}
+}
+
+impl Si {
+	pub(crate) fn new() -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b's',
+			_c: b'i',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let enc = Si::new().encode_key().unwrap();
+		assert_eq!(enc, b"/!si");
+	}
+}
@@ -31,6 +31,10 @@ impl Tl {
 			TaskLeaseType::EventProcessing => 3,
 			TaskLeaseType::ReclaimTombstones => 4,
 			TaskLeaseType::IndexBuildResume => 5,
+			TaskLeaseType::Archival => 6,
+			TaskLeaseType::SoftDeletePurge => 7,
+			TaskLeaseType::Eviction => 8,
+			TaskLeaseType::Scrub => 9,
 		};
 		Self {
 			__: b'/',
@@ -0,0 +1,98 @@
+//! Stores when a record of an evictable table was last written
+//!
+//! `!ew` is written alongside every record of a table defined with
+//! `EVICT AFTER`, so that the records which have not been written for the
+//! time to live are found by every node, and across restarts. The value is
+//! the time of the write, in milliseconds since the unix epoch.
+use std::borrow::Cow;
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::{RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Ew<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub id: RecordIdKey,
+}
+
+impl_kv_key_storekey!(Ew<'_> => u64);
+
+pub fn new<'a>(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, id: &RecordIdKey) -> Ew<'a> {
+	Ew::new(ns, db, tb, id.to_owned())
+}
+
+/// The range covering the write time of every record of a table
+pub fn range(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Range<Vec<u8>>> {
+	let mut beg = super::all::new(ns, db, tb).encode_key()?;
+	beg.extend_from_slice(b"!ew");
+	let mut end = beg.clone();
+	beg.push(0x00);
+	end.push(0xff);
+	Ok(beg..end)
+}
+
+impl Categorise for Ew<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableRecordWritten
+	}
+}
+
+impl<'a> Ew<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, id: RecordIdKey) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'e',
+			_f: b'w',
+			id,
+		}
+	}
+
+	pub fn decode_key(k: &[u8]) -> Result<Ew<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_strand::Strand;
+
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Ew::new(
+			NamespaceId(1),
+			DatabaseId(2),
+			&tb,
+			RecordIdKey::String(Strand::new_static("testid")),
+		);
+		let enc = Ew::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!ew\x03testid\0");
+		let rng = range(NamespaceId(1), DatabaseId(2), &tb).unwrap();
+		assert!(rng.start < enc && enc < rng.end);
+	}
+}
@@ -0,0 +1,77 @@
+//! Stores the field lineage of a record in a table defined with LINEAGE
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::doc::Lineage;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::val::{RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Ln<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub id: RecordIdKey,
+}
+
+impl_kv_key_storekey!(Ln<'_> => Lineage);
+
+pub fn new<'a>(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, id: &RecordIdKey) -> Ln<'a> {
+	Ln::new(ns, db, tb, id.to_owned())
+}
+
+impl Categorise for Ln<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableLineage
+	}
+}
+
+impl<'a> Ln<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, id: RecordIdKey) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'l',
+			_f: b'n',
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_strand::Strand;
+
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Ln::new(
+			NamespaceId(1),
+			DatabaseId(2),
+			&tb,
+			RecordIdKey::String(Strand::new_static("testid")),
+		);
+		let enc = Ln::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!ln\x03testid\0");
+	}
+}
@@ -3,10 +3,19 @@ pub mod bg;
 pub mod bp;
 pub mod br;
 pub mod bs;
+pub mod ck;
 pub mod ev;
+pub mod ew;
 pub mod fd;
 pub mod ft;
 pub mod ih;
 pub mod is;
 pub mod ix;
+pub mod ln;
 pub mod lq;
+pub mod po;
+pub mod pp;
+pub mod sd;
+pub mod st;
+pub mod va;
+pub mod vs;
@@ -0,0 +1,95 @@
+//! Stores a DEFINE POLICY archival policy definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId, PolicyDefinition};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Po<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub po: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Po<'_> => PolicyDefinition);
+
+pub fn new<'a>(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, po: &'a str) -> Po<'a> {
+	Po::new(ns, db, tb, po)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(b"!po\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(b"!po\xff");
+	Ok(k)
+}
+
+impl Categorise for Po<'_> {
+	fn categorise(&self) -> Category {
+		Category::TablePolicy
+	}
+}
+
+impl<'a> Po<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, po: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'p',
+			_f: b'o',
+			po: Cow::Borrowed(po),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Po::new(NamespaceId(1), DatabaseId(2), &tb, "testpo");
+		let enc = Po::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!potestpo\0");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let tb = TableName::from("testtb");
+		let val = super::prefix(NamespaceId(1), DatabaseId(2), &tb).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!po\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let tb = TableName::from("testtb");
+		let val = super::suffix(NamespaceId(1), DatabaseId(2), &tb).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!po\xff");
+	}
+}
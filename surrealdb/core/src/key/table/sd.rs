@@ -0,0 +1,118 @@
+//! Stores when a record of a soft deleted table was deleted
+//!
+//! `!sd` is written alongside every record of a table defined with
+//! `SOFT DELETE` which carries a deletion marker. The keys are ordered by the
+//! time of deletion, in milliseconds since the unix epoch, so that the records
+//! whose retention period has passed are found without scanning the table.
+use std::borrow::Cow;
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::{RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Sd<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	/// Milliseconds since the unix epoch at which the record was deleted
+	pub ts: u64,
+	pub id: RecordIdKey,
+}
+
+impl_kv_key_storekey!(Sd<'_> => ());
+
+pub fn new<'a>(
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &'a TableName,
+	ts: u64,
+	id: &RecordIdKey,
+) -> Sd<'a> {
+	Sd::new(ns, db, tb, ts, id.to_owned())
+}
+
+/// The range covering the records of a table which were deleted before the
+/// given time, in milliseconds since the unix epoch
+pub fn before(ns: NamespaceId, db: DatabaseId, tb: &TableName, ts: u64) -> Result<Range<Vec<u8>>> {
+	let mut beg = super::all::new(ns, db, tb).encode_key()?;
+	beg.extend_from_slice(b"!sd");
+	let mut end = beg.clone();
+	end.extend_from_slice(&ts.to_be_bytes());
+	Ok(beg..end)
+}
+
+impl Categorise for Sd<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableRecordDeleted
+	}
+}
+
+impl<'a> Sd<'a> {
+	pub fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ts: u64,
+		id: RecordIdKey,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b's',
+			_f: b'd',
+			ts,
+			id,
+		}
+	}
+
+	pub fn decode_key(k: &[u8]) -> Result<Sd<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_strand::Strand;
+
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Sd::new(
+			NamespaceId(1),
+			DatabaseId(2),
+			&tb,
+			5,
+			RecordIdKey::String(Strand::new_static("testid")),
+		);
+		let enc = Sd::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!sd\0\0\0\0\0\0\0\x05\x03testid\0"
+		);
+		assert_eq!(Sd::decode_key(&enc).unwrap(), val);
+		assert!(before(NamespaceId(1), DatabaseId(2), &tb, 6).unwrap().contains(&enc));
+		assert!(!before(NamespaceId(1), DatabaseId(2), &tb, 5).unwrap().contains(&enc));
+	}
+}
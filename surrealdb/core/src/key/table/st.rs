@@ -0,0 +1,109 @@
+//! Stores the statistics of a table
+//!
+//! Rather than updating a single entry in place, which would cause every
+//! concurrent write to the table to conflict, each transaction which writes to
+//! the table appends a delta entry with a unique `uid`. The deltas are
+//! periodically compacted into a single entry with a `uid` of `None`, and the
+//! statistics of the table are the sum of all entries.
+use std::borrow::Cow;
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+use uuid::Uuid;
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::stats::TableStatsDelta;
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct St<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub uid: Option<Uuid>,
+}
+
+impl_kv_key_storekey!(St<'_> => TableStatsDelta);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, tb: &TableName, uid: Option<Uuid>) -> St<'_> {
+	St::new(ns, db, tb, uid)
+}
+
+/// The range covering every statistics entry of a table, including the
+/// compacted entry
+pub fn range(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Range<Vec<u8>>> {
+	let mut beg = super::all::new(ns, db, tb).encode_key()?;
+	beg.extend_from_slice(b"!st");
+	let mut end = beg.clone();
+	beg.push(0x00);
+	end.push(0xff);
+	Ok(beg..end)
+}
+
+impl Categorise for St<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableStats
+	}
+}
+
+impl<'a> St<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, uid: Option<Uuid>) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b's',
+			_f: b't',
+			uid,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let uid = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let tb = TableName::from("testtb");
+		let val = St::new(NamespaceId(1), DatabaseId(2), &tb, Some(uid));
+		let enc = St::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!st\x03\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+	}
+
+	#[test]
+	fn compacted_key() {
+		let tb = TableName::from("testtb");
+		let val = St::new(NamespaceId(1), DatabaseId(2), &tb, None);
+		let enc = St::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!st\x02");
+	}
+
+	#[test]
+	fn range() {
+		let tb = TableName::from("testtb");
+		let r = super::range(NamespaceId(1), DatabaseId(2), &tb).unwrap();
+		assert_eq!(r.start, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!st\0");
+		assert_eq!(r.end, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!st\xff");
+	}
+}
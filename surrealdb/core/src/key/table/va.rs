@@ -0,0 +1,169 @@
+//! Stores the values behind the minimums and maximums of an aggregated view
+//!
+//! `!va{arg}{group}{value}` counts the records of a group of the view for
+//! which an argument of a minimum or maximum has a value. The values are
+//! encoded like index values, so that the entries of a group are ordered by
+//! value, and its minimum and maximum are the first and last of them.
+use std::borrow::Cow;
+use std::ops::Range;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId, ViewValueCount};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, Key};
+use crate::val::{Array, IndexFormat, TableName};
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "IndexFormat")]
+pub(crate) struct Va<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub arg: u32,
+	pub group: Cow<'a, Array>,
+	pub value: Cow<'a, Array>,
+}
+
+impl KVKey for Va<'_> {
+	type ValueType = ViewValueCount;
+
+	fn encode_key(&self) -> Result<Vec<u8>> {
+		Ok(storekey::encode_vec_format::<IndexFormat, _>(self)
+			.map_err(|_| crate::err::Error::Unencodable)?)
+	}
+
+	fn value_context(&self) {}
+}
+
+impl Categorise for Va<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableViewValue
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Encode)]
+#[storekey(format = "IndexFormat")]
+struct VaGroupPrefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub arg: u32,
+	pub group: Cow<'a, Array>,
+}
+
+impl<'a> Va<'a> {
+	/// The key counting the records of a group with a value of an argument
+	///
+	/// The value is wrapped in an array, so that numbers are normalised as
+	/// they are in index keys.
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		arg: u32,
+		group: &'a Array,
+		value: &'a Array,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'v',
+			_f: b'a',
+			arg,
+			group: Cow::Borrowed(group),
+			value: Cow::Borrowed(value),
+		}
+	}
+
+	/// The range of the values of an argument within a group
+	pub(crate) fn group_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		arg: u32,
+		group: &Array,
+	) -> Result<Range<Key>> {
+		let mut beg = storekey::encode_vec_format::<IndexFormat, _>(&VaGroupPrefix {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'v',
+			_f: b'a',
+			arg,
+			group: Cow::Borrowed(group),
+		})
+		.map_err(|_| crate::err::Error::Unencodable)?;
+		let mut end = beg.clone();
+		beg.push(0x00);
+		end.push(0xff);
+		Ok(beg..end)
+	}
+
+	/// The range of every value of every group of a view
+	pub(crate) fn range(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Range<Key>> {
+		let mut beg = super::all::new(ns, db, tb).encode_key()?;
+		beg.extend_from_slice(b"!va");
+		let mut end = beg.clone();
+		beg.push(0x00);
+		end.push(0xff);
+		Ok(beg..end)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::val::Value;
+
+	#[test]
+	fn values_are_ordered() {
+		let tb = TableName::from("testtb");
+		let group = Array(vec![Value::from("a")]);
+		let key = |v: Value| {
+			let value = Array(vec![v]);
+			Va::new(NamespaceId(1), DatabaseId(2), &tb, 0, &group, &value).encode_key().unwrap()
+		};
+		let rng = Va::group_range(NamespaceId(1), DatabaseId(2), &tb, 0, &group).unwrap();
+		let (low, mid, high) = (key(Value::from(-1.5)), key(Value::from(2)), key(Value::from(10)));
+		assert!(rng.start < low && low < mid && mid < high && high < rng.end);
+		// Equal numbers share an entry
+		assert_eq!(key(Value::from(2)), key(Value::from(2.0)));
+		// Other groups and arguments are outside of the range
+		let other = Array(vec![Value::from("b")]);
+		let value = Array(vec![Value::from(2)]);
+		let k =
+			Va::new(NamespaceId(1), DatabaseId(2), &tb, 0, &other, &value).encode_key().unwrap();
+		assert!(!rng.contains(&k));
+		let k =
+			Va::new(NamespaceId(1), DatabaseId(2), &tb, 1, &group, &value).encode_key().unwrap();
+		assert!(!rng.contains(&k));
+		assert!(Va::range(NamespaceId(1), DatabaseId(2), &tb).unwrap().contains(&k));
+	}
+}
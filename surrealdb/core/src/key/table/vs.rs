@@ -0,0 +1,70 @@
+//! Stores the state of a materialized view
+//!
+//! `!vs` records when the view was last computed in full from its source
+//! tables, and whether writes to them have skipped the view since.
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId, ViewState};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Vs<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+}
+
+impl_kv_key_storekey!(Vs<'_> => ViewState);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Vs<'_> {
+	Vs::new(ns, db, tb)
+}
+
+impl Categorise for Vs<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableViewState
+	}
+}
+
+impl<'a> Vs<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'v',
+			_f: b's',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Vs::new(NamespaceId(1), DatabaseId(2), &tb);
+		let enc = Vs::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!vs");
+	}
+}
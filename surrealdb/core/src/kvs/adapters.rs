@@ -0,0 +1,129 @@
+//! Rust-side type adapters for declared field types.
+//!
+//! An embedder can register a [`TypeAdapter`] with the datastore builder, to
+//! take part in writing fields which are declared with a specific type. For
+//! example an adapter for fields of type `{ amount: decimal, currency:
+//! string }` could accept `"12.50 EUR"` as a value, and turn it into the
+//! canonical `{ amount: 12.50dec, currency: 'EUR' }` object. The adapter is
+//! invoked before the value is coerced to the declared type, so the adapted
+//! value is still checked against the `TYPE` and `ASSERT` clauses of the field.
+//!
+//! Type adapters are an experimental capability, which must be enabled with
+//! [`ExperimentalTarget::TypeAdapters`]. While the capability is not enabled,
+//! registered adapters are ignored.
+//!
+//! [`ExperimentalTarget::TypeAdapters`]: crate::dbs::capabilities::ExperimentalTarget::TypeAdapters
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::expr::Kind;
+use crate::sql::expression::convert_public_value_to_internal;
+use crate::types::{PublicKind, PublicValue};
+use crate::val::{Value, convert_value_to_public_value};
+
+/// Validates and canonicalizes the values written to the fields which are
+/// declared with a specific type
+pub trait TypeAdapter: Send + Sync + 'static {
+	/// The declared field type which this adapter handles
+	///
+	/// The adapter is also used for fields declared with the optional form of
+	/// this type, in which case `NONE` values are not passed to the adapter.
+	fn kind(&self) -> PublicKind;
+
+	/// Validates a value written to a field, returning its canonical form
+	///
+	/// Returning an error rejects the write, with the error as the reason.
+	fn adapt(&self, value: PublicValue) -> Result<PublicValue, String>;
+}
+
+/// A registered adapter, along with the declared types which it handles
+#[derive(Clone)]
+struct Registered {
+	kind: Kind,
+	optional: Kind,
+	adapter: Arc<dyn TypeAdapter>,
+}
+
+/// The type adapters registered with the datastore
+#[derive(Clone, Default)]
+pub(crate) struct TypeAdapters {
+	adapters: Arc<Vec<Registered>>,
+}
+
+impl fmt::Debug for TypeAdapters {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_list().entries(self.adapters.iter().map(|r| &r.kind)).finish()
+	}
+}
+
+impl TypeAdapters {
+	/// Registers an adapter, replacing any adapter for the same type
+	pub(crate) fn register(&mut self, adapter: Arc<dyn TypeAdapter>) {
+		let kind = Kind::from(adapter.kind());
+		let adapters = Arc::make_mut(&mut self.adapters);
+		adapters.retain(|r| r.kind != kind);
+		adapters.push(Registered {
+			optional: Kind::option(kind.clone()),
+			kind,
+			adapter,
+		});
+	}
+
+	/// Returns true if no adapter has been registered
+	pub(crate) fn is_empty(&self) -> bool {
+		self.adapters.is_empty()
+	}
+
+	/// Adapts a value written to a field of the declared type, returning the
+	/// value unchanged if no adapter handles the type
+	pub(crate) fn adapt(&self, kind: &Kind, value: Value) -> Result<Value, String> {
+		let Some(registered) =
+			self.adapters.iter().find(|r| r.kind == *kind || r.optional == *kind)
+		else {
+			return Ok(value);
+		};
+		// The optional form of a type accepts NONE as is
+		if value.is_none() && registered.kind != *kind {
+			return Ok(value);
+		}
+		let value = convert_value_to_public_value(value).map_err(|e| e.to_string())?;
+		let value = registered.adapter.adapt(value)?;
+		Ok(convert_public_value_to_internal(value))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct Upper;
+
+	impl TypeAdapter for Upper {
+		fn kind(&self) -> PublicKind {
+			PublicKind::String
+		}
+
+		fn adapt(&self, value: PublicValue) -> Result<PublicValue, String> {
+			match value {
+				PublicValue::String(s) => Ok(PublicValue::String(s.to_uppercase())),
+				_ => Err("expected a string".to_owned()),
+			}
+		}
+	}
+
+	#[test]
+	fn adapters_handle_their_declared_type() {
+		let mut adapters = TypeAdapters::default();
+		adapters.register(Arc::new(Upper));
+		let value = Value::from("eur");
+		assert_eq!(adapters.adapt(&Kind::String, value.clone()), Ok(Value::from("EUR")));
+		assert_eq!(
+			adapters.adapt(&Kind::option(Kind::String), value.clone()),
+			Ok(Value::from("EUR"))
+		);
+		assert_eq!(adapters.adapt(&Kind::option(Kind::String), Value::None), Ok(Value::None));
+		assert_eq!(adapters.adapt(&Kind::Int, value.clone()), Ok(value));
+		adapters.adapt(&Kind::String, Value::from(1)).unwrap_err();
+	}
+}
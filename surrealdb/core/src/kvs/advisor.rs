@@ -0,0 +1,399 @@
+//! Index advisor based on a captured workload.
+//!
+//! While a capture is running for a database, the executor records the shape
+//! of every statement which reads or writes the records of a table with a
+//! condition or an ordering: the table, the fields compared for equality, the
+//! fields compared with a range, and the fields ordered by. Statements with the
+//! same shape are counted together, whatever values they compare against.
+//!
+//! Each captured shape suggests an index on its equality fields, followed by
+//! its first range field, or else by its ordered fields. Suggestions which are
+//! already served by a standard or unique index are discarded, and the rest are
+//! ranked using the statistics of their table: the estimated benefit is the
+//! number of records which the captured statements scan without the index, and
+//! the estimated cost is the number of entries which the index would hold.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use surrealdb_types::ToSql;
+
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{DatabaseId, Index, NamespaceId};
+use crate::expr::order::Ordering as OrderBy;
+use crate::expr::statements::{DeleteStatement, SelectStatement, UpdateStatement, UpsertStatement};
+use crate::expr::visit::{Visit, Visitor};
+use crate::expr::{BinaryOperator, Cond, Expr, Part, TopLevelExpr};
+use crate::kvs::{SessionTables, Transaction};
+use crate::val::{TableName, Value};
+
+/// The workload captured for each database, keyed by namespace and database
+#[derive(Clone, Default)]
+pub(crate) struct IndexAdvisor {
+	inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+	/// The number of databases with a running capture
+	active: AtomicUsize,
+	workloads: Mutex<HashMap<(String, String), Workload>>,
+}
+
+#[derive(Default)]
+struct Workload {
+	capturing: bool,
+	shapes: HashMap<Shape, u64>,
+}
+
+/// The normalized shape of a statement which reads or writes a table
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+struct Shape {
+	table: TableName,
+	/// The fields compared for equality, sorted
+	equality: Vec<String>,
+	/// The fields compared with a range, in the order of the condition
+	range: Vec<String>,
+	/// The fields ordered by
+	order: Vec<String>,
+}
+
+impl Shape {
+	/// Returns the fields of the index which would serve this shape
+	fn index_fields(&self) -> Vec<String> {
+		let mut fields = self.equality.clone();
+		match self.range.first() {
+			Some(field) => fields.push(field.clone()),
+			None => fields.extend(self.order.iter().cloned()),
+		}
+		let mut seen = Vec::with_capacity(fields.len());
+		fields.retain(|field| {
+			let first = !seen.contains(field);
+			seen.push(field.clone());
+			first
+		});
+		fields
+	}
+}
+
+impl IndexAdvisor {
+	/// Starts a capture for a database, discarding any previous capture
+	pub(crate) fn start(&self, ns: &str, db: &str) {
+		let mut workloads = self.inner.workloads.lock();
+		let workload = workloads.entry((ns.to_owned(), db.to_owned())).or_default();
+		if !workload.capturing {
+			workload.capturing = true;
+			self.inner.active.fetch_add(1, Ordering::Relaxed);
+		}
+		workload.shapes.clear();
+	}
+
+	/// Stops the capture for a database, keeping the captured workload
+	pub(crate) fn stop(&self, ns: &str, db: &str) {
+		let mut workloads = self.inner.workloads.lock();
+		if let Some(workload) = workloads.get_mut(&(ns.to_owned(), db.to_owned()))
+			&& workload.capturing
+		{
+			workload.capturing = false;
+			self.inner.active.fetch_sub(1, Ordering::Relaxed);
+		}
+	}
+
+	/// Records the shapes of a statement, if a capture is running for the
+	/// database
+	pub(crate) fn record(&self, ns: &str, db: &str, plan: &TopLevelExpr) {
+		if self.inner.active.load(Ordering::Relaxed) == 0 {
+			return;
+		}
+		let mut collector = ShapeCollector::default();
+		let _ = plan.visit(&mut collector);
+		if collector.shapes.is_empty() {
+			return;
+		}
+		let mut workloads = self.inner.workloads.lock();
+		if let Some(workload) = workloads.get_mut(&(ns.to_owned(), db.to_owned()))
+			&& workload.capturing
+		{
+			for shape in collector.shapes {
+				*workload.shapes.entry(shape).or_default() += 1;
+			}
+		}
+	}
+
+	/// Returns the indexes recommended for the workload captured for a
+	/// database, ranked by their estimated benefit
+	pub(crate) async fn recommendations(
+		&self,
+		tx: &Transaction,
+		(ns, db): (&str, &str),
+		(ns_id, db_id): (NamespaceId, DatabaseId),
+	) -> Result<Value> {
+		// Combine the shapes which are served by the same index
+		let mut candidates: BTreeMap<(TableName, Vec<String>), (usize, u64)> = BTreeMap::new();
+		if let Some(workload) = self.inner.workloads.lock().get(&(ns.to_owned(), db.to_owned())) {
+			for (shape, count) in workload.shapes.iter() {
+				let fields = shape.index_fields();
+				if fields.is_empty() {
+					continue;
+				}
+				let candidate = candidates
+					.entry((shape.table.clone(), fields))
+					.or_insert((shape.equality.len(), 0));
+				candidate.0 = candidate.0.min(shape.equality.len());
+				candidate.1 += count;
+			}
+		}
+		let mut recommendations = Vec::new();
+		for ((table, fields), (equality, queries)) in candidates {
+			if tx.get_tb(ns_id, db_id, &table, None).await?.is_none() {
+				continue;
+			}
+			let indexes = tx.all_tb_indexes(ns_id, db_id, &table, None).await?;
+			let served = indexes.iter().any(|ix| {
+				let cols: Vec<String> = ix.cols.iter().map(|col| col.to_sql()).collect();
+				matches!(ix.index, Index::Idx | Index::Uniq)
+					&& !ix.prepare_remove
+					&& serves(&cols, &fields, equality)
+			});
+			if served {
+				continue;
+			}
+			let rows = crate::kvs::stats::total(tx, ns_id, db_id, &table).await?.rows.max(0) as u64;
+			recommendations.push(Recommendation {
+				table,
+				fields,
+				queries,
+				rows,
+			});
+		}
+		recommendations.sort_by(|a, b| b.benefit().cmp(&a.benefit()));
+		Ok(recommendations.into_iter().map(Recommendation::into_value).collect::<Vec<_>>().into())
+	}
+}
+
+/// Returns true if an index on the given columns serves the given fields, the
+/// first of which are compared for equality in any order
+fn serves(cols: &[String], fields: &[String], equality: usize) -> bool {
+	if cols.len() < fields.len() {
+		return false;
+	}
+	let (eq, rest) = fields.split_at(equality.min(fields.len()));
+	cols[..eq.len()].iter().all(|col| eq.contains(col)) && cols[eq.len()..fields.len()] == *rest
+}
+
+/// An index recommended for a captured workload
+struct Recommendation {
+	table: TableName,
+	fields: Vec<String>,
+	/// The number of captured statements which the index serves
+	queries: u64,
+	/// The estimated number of records in the table
+	rows: u64,
+}
+
+impl Recommendation {
+	/// The estimated number of records which the captured statements scan
+	/// without the index
+	fn benefit(&self) -> u64 {
+		self.queries.saturating_mul(self.rows)
+	}
+
+	fn into_value(self) -> Value {
+		let benefit = self.benefit();
+		let name = format!("{}_{}", self.table.as_str(), self.fields.join("_"))
+			.chars()
+			.map(|c| {
+				if c.is_ascii_alphanumeric() {
+					c
+				} else {
+					'_'
+				}
+			})
+			.collect::<String>();
+		let statement = format!(
+			"DEFINE INDEX {name} ON {} FIELDS {}",
+			self.table.to_sql(),
+			self.fields.join(", ")
+		);
+		Value::from(map! {
+			"table" => Value::from(self.table.as_str().to_owned()),
+			"fields" => Value::from(self.fields.into_iter().map(Value::from).collect::<Vec<_>>()),
+			"queries" => Value::from(self.queries),
+			"benefit" => Value::from(benefit),
+			"cost" => Value::from(self.rows),
+			"statement" => Value::from(statement),
+		})
+	}
+}
+
+/// Collects the shapes of the statements which read or write a table
+#[derive(Default)]
+struct ShapeCollector {
+	shapes: Vec<Shape>,
+}
+
+impl ShapeCollector {
+	fn collect(&mut self, what: &[Expr], cond: Option<&Cond>, order: Option<&OrderBy>) {
+		let mut equality = Vec::new();
+		let mut range = Vec::new();
+		if let Some(cond) = cond {
+			conditions(&cond.0, &mut equality, &mut range);
+		}
+		equality.sort();
+		equality.dedup();
+		range.retain(|field| !equality.contains(field));
+		let mut ordered = Vec::new();
+		if let Some(OrderBy::Order(list)) = order {
+			for order in list.iter() {
+				match field(&Expr::Idiom(order.value.clone())) {
+					Some(field) => ordered.push(field),
+					None => break,
+				}
+			}
+		}
+		if equality.is_empty() && range.is_empty() && ordered.is_empty() {
+			return;
+		}
+		for expr in what {
+			// Session tables are temporary, so are not worth an index
+			if let Expr::Table(table) = expr
+				&& !SessionTables::is_scoped(table)
+			{
+				self.shapes.push(Shape {
+					table: table.clone(),
+					equality: equality.clone(),
+					range: range.clone(),
+					order: ordered.clone(),
+				});
+			}
+		}
+	}
+}
+
+impl Visitor for ShapeCollector {
+	type Error = std::convert::Infallible;
+
+	fn visit_select(&mut self, s: &SelectStatement) -> Result<(), Self::Error> {
+		self.collect(&s.what, s.cond.as_ref(), s.order.as_ref());
+		s.visit(self)
+	}
+
+	fn visit_update(&mut self, u: &UpdateStatement) -> Result<(), Self::Error> {
+		self.collect(&u.what, u.cond.as_ref(), None);
+		u.visit(self)
+	}
+
+	fn visit_upsert(&mut self, u: &UpsertStatement) -> Result<(), Self::Error> {
+		self.collect(&u.what, u.cond.as_ref(), None);
+		u.visit(self)
+	}
+
+	fn visit_delete(&mut self, d: &DeleteStatement) -> Result<(), Self::Error> {
+		self.collect(&d.what, d.cond.as_ref(), None);
+		d.visit(self)
+	}
+}
+
+/// Collects the fields compared in the conjunctions of a condition
+fn conditions(expr: &Expr, equality: &mut Vec<String>, range: &mut Vec<String>) {
+	let Expr::Binary {
+		left,
+		op,
+		right,
+	} = expr
+	else {
+		return;
+	};
+	if let BinaryOperator::And = op {
+		conditions(left, equality, range);
+		conditions(right, equality, range);
+		return;
+	}
+	// Only a field compared with something other than a field can be indexed
+	let compared = match (field(left), field(right)) {
+		(Some(field), None) if !matches!(**right, Expr::Idiom(_)) => field,
+		(None, Some(field)) if !matches!(**left, Expr::Idiom(_)) => field,
+		_ => return,
+	};
+	match op {
+		BinaryOperator::Equal | BinaryOperator::ExactEqual => equality.push(compared),
+		BinaryOperator::LessThan
+		| BinaryOperator::LessThanEqual
+		| BinaryOperator::MoreThan
+		| BinaryOperator::MoreThanEqual => range.push(compared),
+		_ => {}
+	}
+}
+
+/// Returns the name of a field of the record, other than its id
+fn field(expr: &Expr) -> Option<String> {
+	match expr {
+		Expr::Idiom(idiom)
+			if !idiom.is_empty()
+				&& !idiom.is_id()
+				&& idiom.iter().all(|part| matches!(part, Part::Field(_))) =>
+		{
+			Some(idiom.to_sql())
+		}
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn record(advisor: &IndexAdvisor, sql: &str) {
+		let ast = crate::syn::parse(sql).unwrap();
+		for expr in ast.expressions {
+			advisor.record("test", "test", &expr.into());
+		}
+	}
+
+	fn shapes(advisor: &IndexAdvisor) -> Vec<(Vec<String>, u64)> {
+		let workloads = advisor.inner.workloads.lock();
+		let mut shapes: Vec<_> = workloads[&("test".to_owned(), "test".to_owned())]
+			.shapes
+			.iter()
+			.map(|(shape, count)| (shape.index_fields(), *count))
+			.collect();
+		shapes.sort();
+		shapes
+	}
+
+	#[test]
+	fn shapes_are_only_captured_while_running() {
+		let advisor = IndexAdvisor::default();
+		record(&advisor, "SELECT * FROM person WHERE name = 'Tobie'");
+		advisor.start("test", "test");
+		record(&advisor, "SELECT * FROM person WHERE name = 'Tobie' AND age > 18");
+		record(&advisor, "SELECT * FROM person WHERE age > 21 AND name = $name");
+		record(&advisor, "SELECT * FROM person ORDER BY created_at");
+		record(&advisor, "DELETE person WHERE email = 'tobie@surrealdb.com'");
+		record(&advisor, "SELECT * FROM person WHERE a = b OR name = 'Tobie'");
+		record(&advisor, "SELECT * FROM person:tobie");
+		advisor.stop("test", "test");
+		record(&advisor, "SELECT * FROM person WHERE name = 'Jaime'");
+		assert_eq!(
+			shapes(&advisor),
+			vec![
+				(vec!["created_at".to_owned()], 1),
+				(vec!["email".to_owned()], 1),
+				(vec!["name".to_owned(), "age".to_owned()], 2),
+			]
+		);
+	}
+
+	#[test]
+	fn existing_indexes_serve_their_prefix() {
+		let cols = ["name".to_owned(), "age".to_owned()];
+		assert!(serves(&cols, &["name".to_owned()], 1));
+		assert!(serves(&cols, &["name".to_owned(), "age".to_owned()], 1));
+		assert!(serves(&cols, &["age".to_owned(), "name".to_owned()], 2));
+		assert!(!serves(&cols, &["age".to_owned()], 1));
+		assert!(!serves(&cols, &["age".to_owned(), "name".to_owned()], 1));
+	}
+}
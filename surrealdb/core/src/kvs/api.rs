@@ -27,11 +27,11 @@ use crate::kvs::{
 /// (`Send + Sync` natively, empty on WASM), so a `BoxFut` returned from a
 /// trait method satisfies whatever the caller expects.
 #[cfg(target_family = "wasm")]
-pub(crate) type BoxFut<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+pub type BoxFut<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
 /// A boxed future returned by `Transactable` / `ScanCursorKeys` /
 /// `ScanCursorVals` trait methods.
 #[cfg(not(target_family = "wasm"))]
-pub(crate) type BoxFut<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+pub type BoxFut<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 /// The result of a [`Transactable::keys`] or [`Transactable::keysr`] operation.
 ///
@@ -516,6 +516,15 @@ pub trait Transactable: requirements::TransactionRequirements {
 	/// will return a [`crate::kvs::Error::TransactionReadonly`] error.
 	fn writeable(&self) -> bool;
 
+	/// The largest number of bytes which a transaction can write.
+	///
+	/// Storage engines which limit the size of their transactions return the
+	/// limit, so that an oversized transaction fails before it is rejected
+	/// with an opaque error at commit.
+	fn size_limit(&self) -> Option<u64> {
+		None
+	}
+
 	/// Cancel a transaction.
 	///
 	/// This reverses all changes made within the transaction.
@@ -0,0 +1,198 @@
+//! Background archival of records matching `DEFINE POLICY` definitions.
+//!
+//! Each run moves at most `BATCH` matching records from the table a policy is
+//! defined on into its destination table, keeping the record ids, within a
+//! single transaction. Batches are repeated until no more records match, and
+//! the progress of every policy is stored alongside its definition. Both the
+//! current and the archived records remain queryable together, for example
+//! with `SELECT * FROM event, event_archive`.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use revision::revisioned;
+use surrealdb_types::{SurrealValue, ToSql};
+use tracing::warn;
+
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{DatabaseDefinition, PolicyDefinition};
+use crate::dbs::{Priority, Session};
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{Datastore, Transaction, impl_kv_value_revisioned};
+use crate::sql;
+
+/// The statements which move a single batch of records
+///
+/// The final statement evaluates to the number of records which were moved.
+const MOVE_BATCH: &str = r"
+	BEGIN;
+	LET $archived = SELECT * FROM type::table($source) WHERE {when} LIMIT $batch;
+	FOR $row IN $archived {
+		UPSERT type::record($destination, record::id($row.id))
+			CONTENT object::remove($row, 'id') RETURN NONE;
+	};
+	DELETE $archived.id RETURN NONE;
+	array::len($archived);
+	COMMIT;
+";
+
+/// The stored progress of an archival policy, updated after each run
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct PolicyProgress {
+	/// The total number of records moved by the policy
+	moved: u64,
+	/// The number of records moved by the last run
+	last_moved: u64,
+	/// When the policy last ran, in milliseconds since the Unix epoch
+	last_run: i64,
+	/// The error returned by the last run, if it failed
+	last_error: Option<String>,
+}
+
+impl_kv_value_revisioned!(PolicyProgress);
+
+/// The progress of an archival policy
+///
+/// Returned by [`Datastore::archival_progress`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ArchivalProgress {
+	/// The table the policy moves records from
+	pub table: String,
+	/// The name of the policy
+	pub policy: String,
+	/// The table the policy moves records to
+	pub destination: String,
+	/// The total number of records moved by the policy
+	pub moved: u64,
+	/// The number of records moved by the last run
+	pub last_moved: u64,
+	/// When the policy last ran, if it has run since it was defined
+	pub last_run: Option<DateTime<Utc>>,
+	/// The error returned by the last run, if it failed
+	pub last_error: Option<String>,
+}
+
+/// Lists every archival policy in the datastore, along with its database
+async fn all_policies(
+	tx: &Transaction,
+) -> Result<Vec<(String, DatabaseDefinition, PolicyDefinition)>> {
+	let mut out = Vec::new();
+	for ns in tx.all_ns(None).await?.iter() {
+		for db in tx.all_db(ns.namespace_id, None).await?.iter() {
+			for tb in tx.all_tb(db.namespace_id, db.database_id, None).await?.iter() {
+				for po in tx.all_tb_policies(db.namespace_id, db.database_id, &tb.name).await? {
+					out.push((ns.name.to_string(), db.clone(), po));
+				}
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// Runs every archival policy in the datastore until no more records match,
+/// or the task lease is lost.
+pub(crate) async fn run(ds: &Datastore, lh: &LeaseHandler) -> Result<()> {
+	let tx = ds.transaction(Read, Optimistic).await?;
+	let res = all_policies(&tx).await;
+	tx.cancel().await?;
+	for (ns, db, po) in res? {
+		// Stop if another node has taken over the task
+		if !lh.try_maintain_lease().await? {
+			return Ok(());
+		}
+		let mut moved = 0;
+		let res = loop {
+			match move_batch(ds, &ns, &db, &po).await {
+				// A partial batch means no more records currently match
+				Ok(n) if n < po.batch as u64 => {
+					moved += n;
+					break Ok(());
+				}
+				Ok(n) => moved += n,
+				Err(e) => break Err(e),
+			}
+			if !lh.try_maintain_lease().await? {
+				break Ok(());
+			}
+		};
+		if let Err(e) = &res {
+			warn!("Archival policy '{}' on table '{}' failed: {e}", po.name, po.target_table);
+		}
+		// Record the progress of the policy
+		let key =
+			crate::key::table::pp::new(db.namespace_id, db.database_id, &po.target_table, &po.name);
+		let tx = ds.transaction(Write, Optimistic).await?;
+		let mut progress = catch!(tx, tx.get(&key, None).await).unwrap_or_default();
+		progress.moved += moved;
+		progress.last_moved = moved;
+		progress.last_run = Utc::now().timestamp_millis();
+		progress.last_error = res.err().map(|e| e.to_string());
+		catch!(tx, tx.set(&key, &progress).await);
+		catch!(tx, tx.commit().await);
+	}
+	Ok(())
+}
+
+/// Moves a single batch of records for a policy, returning how many were moved
+async fn move_batch(
+	ds: &Datastore,
+	ns: &str,
+	db: &DatabaseDefinition,
+	po: &PolicyDefinition,
+) -> Result<u64> {
+	let when = sql::Expr::from(po.when.clone()).to_sql();
+	let sql = MOVE_BATCH.replace("{when}", &when);
+	let vars = map! {
+		"source".to_string() => po.target_table.to_string().into_value(),
+		"destination".to_string() => po.destination.to_string().into_value(),
+		"batch".to_string() => (po.batch as i64).into_value(),
+	};
+	let sess = Session::owner().with_ns(ns).with_db(&db.name).with_priority(Priority::Background);
+	let mut res = ds
+		.execute(
+			&sql,
+			&sess,
+			Some(vars.into_iter().collect::<std::collections::BTreeMap<_, _>>().into()),
+		)
+		.await
+		.map_err(|e| anyhow::anyhow!(e))?;
+	// The batch is moved in one transaction, so every statement fails together
+	let Some(last) = res.pop() else {
+		fail!("The archival batch returned no results");
+	};
+	let moved = last.result.map_err(|e| anyhow::anyhow!(e))?.into_t::<i64>()?;
+	Ok(moved.max(0) as u64)
+}
+
+/// Lists the progress of every archival policy in a database
+pub(crate) async fn progress(
+	tx: &Transaction,
+	ns: &str,
+	db: &str,
+) -> Result<Vec<ArchivalProgress>> {
+	let db = tx.expect_db_by_name(ns, db).await?;
+	let mut out = Vec::new();
+	for tb in tx.all_tb(db.namespace_id, db.database_id, None).await?.iter() {
+		for po in tx.all_tb_policies(db.namespace_id, db.database_id, &tb.name).await? {
+			let key =
+				crate::key::table::pp::new(db.namespace_id, db.database_id, &tb.name, &po.name);
+			let progress: Option<PolicyProgress> = tx.get(&key, None).await?;
+			let progress = progress.unwrap_or_default();
+			out.push(ArchivalProgress {
+				table: po.target_table.to_string(),
+				policy: po.name.to_string(),
+				destination: po.destination.to_string(),
+				moved: progress.moved,
+				last_moved: progress.last_moved,
+				last_run: (progress.last_run > 0)
+					.then(|| DateTime::from_timestamp_millis(progress.last_run))
+					.flatten(),
+				last_error: progress.last_error,
+			});
+		}
+	}
+	Ok(out)
+}
@@ -8,6 +8,7 @@ use jsonwebtoken::jwk::JwkSet;
 use uuid::Uuid;
 
 use crate::catalog::{self};
+use crate::exec::PhysicalExpr;
 
 /// A cached JWKS document together with the time it was stored.
 #[cfg(feature = "jwks")]
@@ -34,6 +35,9 @@ pub(crate) enum Entry {
 	Lvs(Arc<[catalog::SubscriptionDefinition]>),
 	/// An Uuid.
 	Lvv(Uuid),
+	/// The plan of a query template, or `None` if the template can not be
+	/// planned, and is computed instead.
+	Qyp(Option<Arc<dyn PhysicalExpr>>),
 }
 
 impl Entry {
@@ -97,4 +101,13 @@ impl Entry {
 			_ => fail!("Unable to convert type into Entry::Lvv"),
 		}
 	}
+
+	/// Converts this cache entry into the plan of a query template.
+	/// This panics if called on a cache entry that is not an [`Entry::Qyp`].
+	pub(crate) fn try_into_qyp(self) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+		match self {
+			Entry::Qyp(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Qyp"),
+		}
+	}
 }
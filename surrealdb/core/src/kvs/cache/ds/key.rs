@@ -21,6 +21,8 @@ pub(crate) enum Key {
 	Lvs(NamespaceId, DatabaseId, String, Uuid),
 	/// A cache key for live queries version (on a table)
 	Lvv(NamespaceId, DatabaseId, TableName),
+	/// A cache key for the plan of a query template, planned at a depth
+	Qyp(NamespaceId, DatabaseId, String, Uuid, u32),
 }
 
 impl<'a> From<Lookup<'a>> for Key {
@@ -34,6 +36,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Ixs(a, b, c, d) => Key::Ixs(a, b, c.to_string(), d),
 			Lookup::Lvs(a, b, c, d) => Key::Lvs(a, b, c.to_string(), d),
 			Lookup::Lvv(a, b, c) => Key::Lvv(a, b, c.clone()),
+			Lookup::Qyp(a, b, c, d, e) => Key::Qyp(a, b, c.to_string(), d, e),
 		}
 	}
 }
@@ -22,6 +22,8 @@ pub(crate) enum Lookup<'a> {
 	Lvs(NamespaceId, DatabaseId, &'a str, Uuid),
 	/// A cache key for live queries version (on a table)
 	Lvv(NamespaceId, DatabaseId, &'a TableName),
+	/// A cache key for the plan of a query template, planned at a depth
+	Qyp(NamespaceId, DatabaseId, &'a str, Uuid, u32),
 }
 
 impl Equivalent<Key> for Lookup<'_> {
@@ -36,6 +38,7 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Ixs(la, lb, lc, ld), Key::Ixs(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Lvs(la, lb, lc, ld), Key::Lvs(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Lvv(la, lb, lc), Key::Lvv(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Qyp(la, lb, lc, ld, le), Key::Qyp(ka, kb, kc, kd, ke)) => la == ka && lb == kb && lc == kc && ld == kd && le == ke,
 			_ => false,
 		}
 	}
@@ -44,6 +44,8 @@ pub(crate) enum Entry {
 	Dus(Arc<[catalog::UserDefinition]>),
 	/// A slice of DefineFunctionStatement specified on a database.
 	Fcs(Arc<[catalog::FunctionDefinition]>),
+	/// A slice of DefineQueryStatement specified on a database.
+	Qys(Arc<[catalog::QueryDefinition]>),
 	/// A slice of DefineModuleStatement specified on a database.
 	Mds(Arc<[catalog::ModuleDefinition]>),
 	/// A slice of TableDefinition specified on a database.
@@ -220,6 +222,15 @@ impl Entry {
 		}
 	}
 
+	/// Converts this cache entry into a slice of [`catalog::QueryDefinition`].
+	/// This panics if called on a cache entry that is not an [`Entry::Qys`].
+	pub(crate) fn try_into_qys(self) -> Result<Arc<[catalog::QueryDefinition]>> {
+		match self {
+			Entry::Qys(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Qys"),
+		}
+	}
+
 	/// Converts this cache entry into a slice of [`catalog::ModuleDefinition`].
 	/// This panics if called on a cache entry that is not an [`Entry::Mds`].
 	pub(crate) fn try_into_mds(self) -> Result<Arc<[catalog::ModuleDefinition]>> {
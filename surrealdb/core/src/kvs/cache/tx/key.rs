@@ -38,6 +38,8 @@ pub(crate) enum Key {
 	Bus(NamespaceId, DatabaseId),
 	/// A cache key for functions (on a database)
 	Fcs(NamespaceId, DatabaseId),
+	/// A cache key for query templates (on a database)
+	Qys(NamespaceId, DatabaseId),
 	/// A cache key for modules (on a database)
 	Mds(NamespaceId, DatabaseId),
 	/// A cache key for models (on a database)
@@ -97,6 +99,8 @@ pub(crate) enum Key {
 	Bu(NamespaceId, DatabaseId, String),
 	/// A cache key for a function (on a database)
 	Fc(NamespaceId, DatabaseId, String),
+	/// A cache key for a query template (on a database)
+	Qy(NamespaceId, DatabaseId, String),
 	/// A cache key for a module (on a database)
 	Md(NamespaceId, DatabaseId, String),
 	/// A cache key for a model (on a database)
@@ -142,6 +146,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Azs(a, b) => Key::Azs(a, b),
 			Lookup::Bus(a, b) => Key::Bus(a, b),
 			Lookup::Fcs(a, b) => Key::Fcs(a, b),
+			Lookup::Qys(a, b) => Key::Qys(a, b),
 			Lookup::Mds(a, b) => Key::Mds(a, b),
 			Lookup::Mls(a, b) => Key::Mls(a, b),
 			Lookup::Cgs(a, b) => Key::Cgs(a, b),
@@ -172,6 +177,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Az(a, b, c) => Key::Az(a, b, c.to_string()),
 			Lookup::Bu(a, b, c) => Key::Bu(a, b, c.to_string()),
 			Lookup::Fc(a, b, c) => Key::Fc(a, b, c.to_string()),
+			Lookup::Qy(a, b, c) => Key::Qy(a, b, c.to_string()),
 			Lookup::Md(a, b, c) => Key::Md(a, b, c.to_string()),
 			Lookup::Ml(a, b, c, d) => Key::Ml(a, b, c.to_string(), d.to_string()),
 			Lookup::Cg(a, b, c) => Key::Cg(a, b, c.to_string()),
@@ -39,6 +39,8 @@ pub(crate) enum Lookup<'a> {
 	Bus(NamespaceId, DatabaseId),
 	/// A cache key for functions (on a database)
 	Fcs(NamespaceId, DatabaseId),
+	/// A cache key for query templates (on a database)
+	Qys(NamespaceId, DatabaseId),
 	/// A cache key for modules (on a database)
 	Mds(NamespaceId, DatabaseId),
 	/// A cache key for models (on a database)
@@ -98,6 +100,8 @@ pub(crate) enum Lookup<'a> {
 	Bu(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a function (on a database)
 	Fc(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for a query template (on a database)
+	Qy(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a module (on a database)
 	Md(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a model (on a database)
@@ -143,6 +147,7 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Azs(la, lb), Key::Azs(ka, kb)) => la == ka && lb == kb,
 			(Self::Bus(la, lb), Key::Bus(ka, kb)) => la == ka && lb == kb,
 			(Self::Fcs(la, lb), Key::Fcs(ka, kb)) => la == ka && lb == kb,
+			(Self::Qys(la, lb), Key::Qys(ka, kb)) => la == ka && lb == kb,
 			(Self::Mds(la, lb), Key::Mds(ka, kb)) => la == ka && lb == kb,
 			(Self::Mls(la, lb), Key::Mls(ka, kb)) => la == ka && lb == kb,
 			(Self::Cgs(la, lb), Key::Cgs(ka, kb)) => la == ka && lb == kb,
@@ -173,6 +178,7 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Az(la, lb, lc), Key::Az(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Bu(la, lb, lc), Key::Bu(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Fc(la, lb, lc), Key::Fc(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Qy(la, lb, lc), Key::Qy(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Md(la, lb, lc), Key::Md(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Ml(la, lb, lc, ld), Key::Ml(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Cg(la, lb, lc), Key::Cg(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
@@ -237,6 +243,11 @@ mod tests {
 		Key::Fcs(NamespaceId(1), DatabaseId(1)),
 		true
 	)]
+	#[case(
+		Lookup::Qys(NamespaceId(1), DatabaseId(1)),
+		Key::Qys(NamespaceId(1), DatabaseId(1)),
+		true
+	)]
 	#[case(
 		Lookup::Mls(NamespaceId(1), DatabaseId(1)),
 		Key::Mls(NamespaceId(1), DatabaseId(1)),
@@ -283,6 +294,7 @@ mod tests {
 	#[case(Lookup::Az(NamespaceId(1), DatabaseId(1), "test"), Key::Az(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Bu(NamespaceId(1), DatabaseId(1), "test"), Key::Bu(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Fc(NamespaceId(1), DatabaseId(1), "test"), Key::Fc(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(Lookup::Qy(NamespaceId(1), DatabaseId(1), "test"), Key::Qy(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Ml(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Ml(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::Cg(NamespaceId(1), DatabaseId(1), "test"), Key::Cg(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Pa(NamespaceId(1), DatabaseId(1), "test"), Key::Pa(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
@@ -0,0 +1,287 @@
+//! A conformance suite for storage engines.
+//!
+//! [`run`] checks that the transactions of a storage engine behave as the rest
+//! of the datastore expects. Engines are expected to run it in their own test
+//! suites, against an empty datastore:
+//!
+//! ```rust,ignore
+//! #[tokio::test]
+//! async fn conforms() {
+//! 	let engine = MyEngine::open_temporary().await.unwrap();
+//! 	surrealdb_core::kvs::custom::conformance::run(&engine).await.unwrap();
+//! }
+//! ```
+//!
+//! The suite only writes keys starting with [`PREFIX`], and removes them again
+//! once every check has passed.
+
+use std::fmt::Debug;
+
+use anyhow::{Result, bail, ensure};
+
+use crate::kvs::{Error, Key, Transactable, TransactionBuilder, Val};
+
+/// The prefix of the keys written by the suite
+pub const PREFIX: &[u8] = b"/!conformance/";
+
+/// Runs every check of the suite against a storage engine.
+///
+/// Returns the first check which failed, describing what was expected.
+pub async fn run(engine: &dyn TransactionBuilder) -> Result<()> {
+	clear(engine).await?;
+	reads_its_own_writes(engine).await?;
+	commits_are_visible(engine).await?;
+	cancels_are_discarded(engine).await?;
+	conditional_writes(engine).await?;
+	ranges_are_ordered(engine).await?;
+	read_only_transactions(engine).await?;
+	finished_transactions(engine).await?;
+	save_points(engine).await?;
+	clear(engine).await
+}
+
+/// Returns a key of the suite
+fn key(name: &str) -> Key {
+	[PREFIX, name.as_bytes()].concat()
+}
+
+/// Returns the range of the keys of the suite starting with a name
+fn range(name: &str) -> std::ops::Range<Key> {
+	let start = key(name);
+	let mut end = start.clone();
+	end.push(0xff);
+	start..end
+}
+
+/// Starts a transaction
+async fn begin(engine: &dyn TransactionBuilder, write: bool) -> Result<Box<dyn Transactable>> {
+	let (tx, _) = engine.new_transaction(write, false).await?;
+	ensure!(tx.writeable() == write, "a new transaction must report if it is writeable");
+	ensure!(!tx.closed(), "a new transaction must not be closed");
+	Ok(tx)
+}
+
+/// Checks that an operation failed with a specific error
+fn failed_with<T: Debug>(
+	check: &str,
+	result: std::result::Result<T, Error>,
+	expected: fn(&Error) -> bool,
+) -> Result<()> {
+	match result {
+		Err(e) if expected(&e) => Ok(()),
+		Err(e) => bail!("{check}: failed with the wrong error: {e}"),
+		Ok(v) => bail!("{check}: succeeded with {v:?}"),
+	}
+}
+
+/// Removes the keys of the suite
+async fn clear(engine: &dyn TransactionBuilder) -> Result<()> {
+	let tx = begin(engine, true).await?;
+	let keys = tx.keys(range(""), u32::MAX, 0, None).await?;
+	for key in keys.keys {
+		tx.del(key).await?;
+	}
+	tx.commit().await?;
+	Ok(())
+}
+
+async fn reads_its_own_writes(engine: &dyn TransactionBuilder) -> Result<()> {
+	let tx = begin(engine, true).await?;
+	ensure!(tx.get(key("own"), None).await?.is_none(), "a missing key must not have a value");
+	ensure!(!tx.exists(key("own"), None).await?, "a missing key must not exist");
+	tx.set(key("own"), b"one".to_vec()).await?;
+	ensure!(
+		tx.get(key("own"), None).await? == Some(b"one".to_vec()),
+		"a transaction must read the keys it set"
+	);
+	ensure!(tx.exists(key("own"), None).await?, "a key which was set must exist");
+	tx.del(key("own")).await?;
+	ensure!(
+		tx.get(key("own"), None).await?.is_none(),
+		"a transaction must not read the keys it deleted"
+	);
+	tx.cancel().await?;
+	Ok(())
+}
+
+async fn commits_are_visible(engine: &dyn TransactionBuilder) -> Result<()> {
+	let tx = begin(engine, true).await?;
+	tx.set(key("commit"), b"one".to_vec()).await?;
+	tx.commit().await?;
+	ensure!(tx.closed(), "a committed transaction must be closed");
+	let tx = begin(engine, false).await?;
+	ensure!(
+		tx.get(key("commit"), None).await? == Some(b"one".to_vec()),
+		"a committed key must be read by later transactions"
+	);
+	tx.cancel().await?;
+	Ok(())
+}
+
+async fn cancels_are_discarded(engine: &dyn TransactionBuilder) -> Result<()> {
+	let tx = begin(engine, true).await?;
+	tx.set(key("cancel"), b"one".to_vec()).await?;
+	tx.cancel().await?;
+	ensure!(tx.closed(), "a cancelled transaction must be closed");
+	let tx = begin(engine, false).await?;
+	ensure!(
+		tx.get(key("cancel"), None).await?.is_none(),
+		"a key set by a cancelled transaction must not be read by later transactions"
+	);
+	tx.cancel().await?;
+	Ok(())
+}
+
+async fn conditional_writes(engine: &dyn TransactionBuilder) -> Result<()> {
+	let tx = begin(engine, true).await?;
+	tx.put(key("cond"), b"one".to_vec()).await?;
+	failed_with("putting an existing key", tx.put(key("cond"), b"two".to_vec()).await, |e| {
+		matches!(e, Error::TransactionKeyAlreadyExists)
+	})?;
+	failed_with(
+		"putting a key with a wrong check",
+		tx.putc(key("cond"), b"two".to_vec(), Some(b"zero".to_vec())).await,
+		|e| matches!(e, Error::TransactionConditionNotMet),
+	)?;
+	tx.putc(key("cond"), b"two".to_vec(), Some(b"one".to_vec())).await?;
+	failed_with(
+		"deleting a key with a wrong check",
+		tx.delc(key("cond"), Some(b"one".to_vec())).await,
+		|e| matches!(e, Error::TransactionConditionNotMet),
+	)?;
+	tx.delc(key("cond"), Some(b"two".to_vec())).await?;
+	ensure!(tx.get(key("cond"), None).await?.is_none(), "a key deleted with a check must be gone");
+	tx.putc(key("cond"), b"three".to_vec(), None).await?;
+	ensure!(
+		tx.get(key("cond"), None).await? == Some(b"three".to_vec()),
+		"putting a missing key with an empty check must set it"
+	);
+	tx.cancel().await?;
+	Ok(())
+}
+
+async fn ranges_are_ordered(engine: &dyn TransactionBuilder) -> Result<()> {
+	let tx = begin(engine, true).await?;
+	// Set the keys out of order, with another key right after the range
+	for name in ["range/c", "range/a", "range/e", "range/b", "range/d", "rangez"] {
+		tx.set(key(name), name.as_bytes().to_vec()).await?;
+	}
+	tx.commit().await?;
+	let names = |keys: Vec<Key>| -> Vec<String> {
+		keys.iter().map(|k| String::from_utf8_lossy(&k[PREFIX.len()..]).into_owned()).collect()
+	};
+	let tx = begin(engine, false).await?;
+	let keys = tx.keys(range("range/"), 10, 0, None).await?;
+	ensure!(
+		names(keys.keys) == ["range/a", "range/b", "range/c", "range/d", "range/e"],
+		"keys must be returned in ascending order, and only from within the range"
+	);
+	let keys = tx.keys(range("range/"), 2, 1, None).await?;
+	ensure!(
+		names(keys.keys) == ["range/b", "range/c"],
+		"keys must skip the first keys of the range, then stop at the limit"
+	);
+	let keys = tx.keysr(range("range/"), 2, 1, None).await?;
+	ensure!(
+		names(keys.keys) == ["range/d", "range/c"],
+		"reverse keys must skip the last keys of the range, then stop at the limit"
+	);
+	let scan = tx.scan(range("range/"), 3, 0, None).await?;
+	let values: Vec<Val> = scan.values.iter().map(|(_, v)| v.clone()).collect();
+	ensure!(
+		values == [b"range/a".to_vec(), b"range/b".to_vec(), b"range/c".to_vec()],
+		"scans must return the values of the keys in ascending order"
+	);
+	let scan = tx.scanr(range("range/"), 2, 0, None).await?;
+	let (keys, _): (Vec<Key>, Vec<Val>) = scan.values.into_iter().unzip();
+	ensure!(
+		names(keys) == ["range/e", "range/d"],
+		"reverse scans must return the keys in descending order"
+	);
+	let start = key("range/b");
+	let end = key("range/d");
+	let keys = tx.keys(start..end, 10, 0, None).await?;
+	ensure!(
+		names(keys.keys) == ["range/b", "range/c"],
+		"ranges must include their start and exclude their end"
+	);
+	tx.cancel().await?;
+	Ok(())
+}
+
+async fn read_only_transactions(engine: &dyn TransactionBuilder) -> Result<()> {
+	let tx = begin(engine, false).await?;
+	failed_with(
+		"setting a key in a read-only transaction",
+		tx.set(key("ro"), Vec::new()).await,
+		|e| matches!(e, Error::TransactionReadonly),
+	)?;
+	failed_with("deleting a key in a read-only transaction", tx.del(key("ro")).await, |e| {
+		matches!(e, Error::TransactionReadonly)
+	})?;
+	tx.cancel().await?;
+	Ok(())
+}
+
+async fn finished_transactions(engine: &dyn TransactionBuilder) -> Result<()> {
+	let tx = begin(engine, true).await?;
+	tx.commit().await?;
+	failed_with("reading from a committed transaction", tx.get(key("done"), None).await, |e| {
+		matches!(e, Error::TransactionFinished)
+	})?;
+	failed_with("committing a committed transaction", tx.commit().await, |e| {
+		matches!(e, Error::TransactionFinished)
+	})?;
+	let tx = begin(engine, true).await?;
+	tx.cancel().await?;
+	failed_with(
+		"writing to a cancelled transaction",
+		tx.set(key("done"), Vec::new()).await,
+		|e| matches!(e, Error::TransactionFinished),
+	)?;
+	Ok(())
+}
+
+async fn save_points(engine: &dyn TransactionBuilder) -> Result<()> {
+	let tx = begin(engine, true).await?;
+	tx.set(key("save/a"), b"one".to_vec()).await?;
+	tx.new_save_point().await?;
+	tx.set(key("save/a"), b"two".to_vec()).await?;
+	tx.set(key("save/b"), b"two".to_vec()).await?;
+	tx.rollback_to_save_point().await?;
+	ensure!(
+		tx.get(key("save/a"), None).await? == Some(b"one".to_vec()),
+		"rolling back to a save point must restore the keys changed since"
+	);
+	ensure!(
+		tx.get(key("save/b"), None).await?.is_none(),
+		"rolling back to a save point must remove the keys set since"
+	);
+	tx.new_save_point().await?;
+	tx.set(key("save/b"), b"three".to_vec()).await?;
+	tx.release_last_save_point().await?;
+	ensure!(
+		tx.get(key("save/b"), None).await? == Some(b"three".to_vec()),
+		"releasing a save point must keep the keys set since"
+	);
+	tx.cancel().await?;
+	Ok(())
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use tokio_util::sync::CancellationToken;
+
+	use crate::CommunityComposer;
+	use crate::cnf::ConfigMap;
+	use crate::kvs::TransactionBuilderFactory;
+
+	#[tokio::test]
+	async fn memory_engine_conforms() {
+		let parts = CommunityComposer()
+			.new_transaction_builder("memory", CancellationToken::new(), ConfigMap::default())
+			.await
+			.unwrap();
+		super::run(parts.builder.as_ref()).await.unwrap();
+	}
+}
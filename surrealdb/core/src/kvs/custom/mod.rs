@@ -0,0 +1,98 @@
+#![cfg(feature = "kv-custom")]
+//! Storage engines provided by other crates.
+//!
+//! A storage engine implements [`TransactionBuilder`] for its datastore and
+//! [`Transactable`] for its transactions, and is registered under a path
+//! scheme with [`register`]. A datastore opened with a path using that scheme,
+//! such as `Datastore::new("mycloud://bucket/prefix")`, is then backed by the
+//! engine, without the need to provide a composer of its own.
+//!
+//! Every method of [`Transactable`] without a default implementation has to be
+//! implemented, and has to return the [`Error`] variants which the built-in
+//! engines return in the same situations. The [`conformance`] suite checks
+//! this against a running engine.
+//!
+//! [`Transactable`]: crate::kvs::Transactable
+
+pub mod conformance;
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+
+use parking_lot::RwLock;
+
+use super::api::BoxFut;
+use super::ds::requirements::TransactionBuilderFactoryRequirements;
+use super::err::{Error, Result};
+use crate::cnf::ConfigMap;
+use crate::kvs::TransactionBuilder;
+
+/// The schemes of the built-in storage engines, which can not be registered
+const BUILTIN_SCHEMES: [&str; 7] =
+	["memory", "mem", "file", "rocksdb", "surrealkv", "indxdb", "tikv"];
+
+/// The storage engines registered by their scheme
+static ENGINES: LazyLock<RwLock<HashMap<String, Arc<dyn StorageEngine>>>> =
+	LazyLock::new(Default::default);
+
+/// A storage engine which can be registered under a path scheme.
+pub trait StorageEngine: TransactionBuilderFactoryRequirements {
+	/// Opens the datastore found at a path.
+	///
+	/// - `path`: the path of the datastore, without the scheme and query parameters
+	/// - `config`: the configuration of the datastore, including the query parameters of the
+	///   path prefixed with `datastore_`
+	fn open(
+		&self,
+		path: &str,
+		config: ConfigMap,
+	) -> BoxFut<'_, anyhow::Result<Box<dyn TransactionBuilder>>>;
+}
+
+/// Registers a storage engine under a path scheme.
+///
+/// Fails if the scheme is used by a built-in storage engine, is already
+/// registered, or contains characters other than lowercase ASCII letters,
+/// digits, `+`, `-` and `.`.
+pub fn register(scheme: &str, engine: Arc<dyn StorageEngine>) -> Result<()> {
+	let valid = scheme.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+		&& scheme
+			.chars()
+			.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '+' | '-' | '.'));
+	if !valid {
+		return Err(Error::Datastore(format!("The storage engine scheme `{scheme}` is not valid")));
+	}
+	if BUILTIN_SCHEMES.contains(&scheme) {
+		return Err(Error::Datastore(format!(
+			"The storage engine scheme `{scheme}` is used by a built-in storage engine"
+		)));
+	}
+	let mut engines = ENGINES.write();
+	if engines.contains_key(scheme) {
+		return Err(Error::Datastore(format!(
+			"A storage engine is already registered for the scheme `{scheme}`"
+		)));
+	}
+	engines.insert(scheme.to_owned(), engine);
+	Ok(())
+}
+
+/// Removes the storage engine registered under a path scheme.
+///
+/// Datastores which are already open are not affected. Returns whether an
+/// engine was registered under the scheme.
+pub fn unregister(scheme: &str) -> bool {
+	ENGINES.write().remove(scheme).is_some()
+}
+
+/// Returns the storage engine registered under a path scheme
+pub(crate) fn engine(scheme: &str) -> Option<Arc<dyn StorageEngine>> {
+	ENGINES.read().get(scheme).cloned()
+}
+
+/// Checks if the scheme of a datastore path has a registered storage engine
+pub(crate) fn registered(path: &str) -> bool {
+	path.split_once("://")
+		.or_else(|| path.split_once(':'))
+		.is_some_and(|(scheme, _)| ENGINES.read().contains_key(scheme))
+}
@@ -1,4 +1,5 @@
 use std::any::{Any, TypeId};
+use std::collections::BTreeMap;
 #[cfg(not(target_family = "wasm"))]
 use std::collections::HashMap;
 #[cfg(target_family = "wasm")]
@@ -8,8 +9,8 @@ use std::collections::hash_map::Entry;
 use std::fmt::{self, Display};
 #[cfg(storage)]
 use std::path::PathBuf;
-use std::pin::pin;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::task::{Poll, ready};
 use std::time::Duration;
 
@@ -18,10 +19,11 @@ use anyhow::bail;
 use anyhow::{Context as _, Result, ensure};
 use async_channel::Sender;
 use bytes::{Bytes, BytesMut};
+use chrono::Utc;
 use futures::{Future, Stream};
 use rand::Rng;
 use reblessive::TreeStack;
-use surrealdb_types::{AuthError, Error as TypesError, SurrealValue, object};
+use surrealdb_types::{AuthError, Error as TypesError, SurrealValue, ToSql, object};
 #[cfg(not(target_family = "wasm"))]
 use tokio::spawn;
 use tokio::sync::Notify;
@@ -34,7 +36,12 @@ use super::api::{BoxFut, Transactable};
 use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::MajorVersion;
-use super::{Key, Val, export};
+use super::{
+	ArchivalProgress, CorruptRecord, Evictions, HealthReport, IndexAdvisor, Key, KeyLocks,
+	NamespaceTick, NamespaceTickMetrics, Predicate, Scheduler, SessionTables, TemporaryIndexes,
+	Throttles, TickScheduler, TypeAdapters, Val, WriteGate, WriteLock, archival,
+	background_batch_size, evict, export, import, purge, scrub,
+};
 use crate::api::err::ApiError;
 use crate::api::invocation::process_api_request;
 use crate::api::request::ApiRequest;
@@ -44,10 +51,13 @@ use crate::catalog::providers::{
 	ApiProvider, CatalogProvider, DatabaseProvider, NamespaceProvider, NodeProvider, TableProvider,
 	UserProvider,
 };
-use crate::catalog::{ApiDefinition, Index, NodeLiveQuery, SubscriptionDefinition};
+use crate::catalog::{
+	ApiDefinition, DatabaseDefinition, Index, NamespaceDefinition, NodeLiveQuery,
+	SubscriptionDefinition,
+};
 use crate::cnf::dynamic::DynamicConfiguration;
 use crate::cnf::{CommonConfig, ConfigMap, LiveQueryEngine};
-use crate::ctx::{CancelHandle, Context};
+use crate::ctx::{CancelHandle, Context, FrozenContext};
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
 use crate::dbs::capabilities::{
@@ -55,13 +65,17 @@ use crate::dbs::capabilities::{
 };
 use crate::dbs::node::{Node, Timestamp};
 use crate::dbs::{
-	Capabilities, Executor, MessageBroker, Options, QueryResult, QueryResultBuilder, Session,
+	Capabilities, CompiledPlan, Executor, MessageBroker, Options, Priority, QueryResult,
+	QueryResultBuilder, Session,
 };
-use crate::doc::AsyncEventRecord;
+use crate::doc::{AsyncEventRecord, DeadLetter, DeadLetterRecord, OutboxRecord};
 use crate::err::Error;
 use crate::exec::function::FunctionRegistry;
 use crate::expr::model::get_model_path;
-use crate::expr::statements::{DefineModelStatement, DefineStatement, DefineUserStatement};
+use crate::expr::statements::{
+	DefineModelStatement, DefineStatement, DefineUserStatement, RemoveStatement,
+	RemoveTableStatement,
+};
 use crate::expr::{Base, Expr, FlowResultExt as _, Literal, LogicalPlan, TopLevelExpr};
 #[cfg(feature = "gql")]
 use crate::gql::PreparedGqlQuery;
@@ -71,6 +85,7 @@ use crate::iam::{Action, Auth, Error as IamError, Resource, ResourceKind, Role};
 use crate::idx::IndexKeyBase;
 use crate::idx::index::IndexOperation;
 use crate::idx::trees::store::IndexStores;
+use crate::idx::usage::UnusedIndex;
 use crate::key::root::ic::IndexCompactionKey;
 use crate::key::root::rc::{
 	RECLAIM_DATABASE, RECLAIM_INDEX, RECLAIM_NAMESPACE, ReclaimKey, ReclaimState,
@@ -83,6 +98,7 @@ use crate::kvs::ds::requirements::{
 	TransactionBuilderFactoryRequirements, TransactionBuilderRequirements,
 };
 use crate::kvs::index::IndexBuilder;
+use crate::kvs::ratelimit::GrantWindow;
 use crate::kvs::sequences::Sequences;
 use crate::kvs::slowlog::SlowLog;
 use crate::kvs::tasklease::{LeaseHandler, TaskLeaseType};
@@ -98,7 +114,7 @@ use crate::sql::Ast;
 use crate::surrealism::cache::SurrealismCache;
 use crate::syn::parser::{ParserSettings, StatementStream};
 use crate::types::{PublicNotification, PublicValue, PublicVariables};
-use crate::val::convert_value_to_public_value;
+use crate::val::{TableName, convert_value_to_public_value};
 use crate::{CommunityComposer, syn};
 
 mod builder;
@@ -262,6 +278,18 @@ pub struct Datastore {
 	buckets: BucketsManager,
 	// The sequences
 	sequences: Sequences,
+	// The table write throttles
+	throttles: Throttles,
+	// The tables defined by each session
+	session_tables: SessionTables,
+	// The workload captured by the index advisor
+	index_advisor: IndexAdvisor,
+	// The type adapters registered by the embedder
+	type_adapters: TypeAdapters,
+	// The scheduler of interactive and background work
+	scheduler: Scheduler,
+	// The per-namespace scheduling of background maintenance
+	ticks: TickScheduler,
 	// The surrealism cache
 	#[cfg(feature = "surrealism")]
 	surrealism_cache: Arc<SurrealismCache>,
@@ -309,6 +337,19 @@ pub(crate) struct TransactionFactory {
 	/// configured.
 	observer: Arc<dyn ExecutionObserver>,
 	config: Arc<CommonConfig>,
+	/// Holds back new write transactions while the datastore is locked for
+	/// writes
+	write_gate: WriteGate,
+	/// Whether write transactions are rejected, for example when the
+	/// datastore was opened from a snapshot
+	read_only: Arc<AtomicBool>,
+	/// The temporary in-memory indexes built for repeated table scans
+	temporary_indexes: TemporaryIndexes,
+	/// The records of tables defined with `EVICT`, and when they were last
+	/// used
+	evictions: Evictions,
+	/// The key locks of client-managed transactions
+	key_locks: KeyLocks,
 }
 
 impl TransactionFactory {
@@ -317,11 +358,17 @@ impl TransactionFactory {
 		builder: Box<dyn TransactionBuilder>,
 		config: Arc<CommonConfig>,
 	) -> Self {
+		let evictions = Evictions::new(builder.memory_limit());
 		Self {
 			builder: Arc::new(builder),
 			async_event_trigger,
 			observer: Arc::new(NoopObserver),
 			config,
+			write_gate: WriteGate::default(),
+			read_only: Arc::default(),
+			temporary_indexes: TemporaryIndexes::default(),
+			evictions,
+			key_locks: KeyLocks::default(),
 		}
 	}
 
@@ -361,6 +408,17 @@ impl TransactionFactory {
 			Pessimistic => true,
 			Optimistic => false,
 		};
+		// Reject writes when the datastore is read-only
+		if write && self.read_only.load(Ordering::Acquire) {
+			bail!(Error::Kvs(crate::kvs::Error::DatastoreReadOnly));
+		}
+		// Wait while the datastore is locked for writes
+		let permit = match write {
+			true => Some(self.write_gate.enter().await),
+			false => None,
+		};
+		// Record the clock of the temporary indexes before the snapshot is taken
+		let epoch = self.temporary_indexes.epoch();
 		// Create a new transaction on the datastore
 		let (inner, local) = self.builder.new_transaction(write, lock).await?;
 		Ok(Transaction::new(
@@ -372,7 +430,42 @@ impl TransactionFactory {
 				inner,
 			},
 			&self.config,
-		))
+		)
+		.with_write_permit(permit)
+		.with_temporary_indexes(self.temporary_indexes.clone(), epoch)
+		.with_evictions(self.evictions.clone())
+		.with_key_locks(self.key_locks.clone()))
+	}
+
+	/// Locks the datastore for writes, see [`Datastore::lock_writes`]
+	pub(crate) async fn lock_writes(
+		&self,
+		wait: Duration,
+		max_hold: Duration,
+	) -> Result<WriteLock> {
+		self.write_gate.lock(wait, max_hold).await
+	}
+
+	/// Checks whether the datastore is locked for writes, see
+	/// [`Datastore::lock_writes`]
+	pub(crate) fn writes_locked(&self) -> bool {
+		self.write_gate.is_locked()
+	}
+
+	/// Sets whether write transactions are rejected, see
+	/// [`Datastore::with_read_only`]
+	pub(crate) fn set_read_only(&self, read_only: bool) {
+		self.read_only.store(read_only, Ordering::Release);
+	}
+
+	/// Checks whether write transactions are rejected
+	pub(crate) fn is_read_only(&self) -> bool {
+		self.read_only.load(Ordering::Acquire)
+	}
+
+	/// Creates a named snapshot, see [`Datastore::snapshot`]
+	pub(crate) async fn snapshot(&self, name: &str) -> Result<()> {
+		self.builder.snapshot(name).await
 	}
 
 	/// Registers metrics for the current datastore flavor if supported.
@@ -425,6 +518,16 @@ pub trait TransactionBuilder: TransactionBuilderRequirements {
 	/// - `metric`: The name of the metric to collect.
 	fn collect_u64_metric(&self, metric: &str) -> Option<u64>;
 
+	/// Returns the maximum size of the stored records, in bytes, if the
+	/// datastore is bounded.
+	///
+	/// When exceeded, the background eviction task evicts the least recently
+	/// used records of tables defined with `EVICT LRU`. The default
+	/// implementation is unbounded.
+	fn memory_limit(&self) -> Option<u64> {
+		None
+	}
+
 	/// Returns an immutable backend-specific extension handle.
 	///
 	/// Backends expose only stable, shareable handles through this hook. The
@@ -440,6 +543,16 @@ pub trait TransactionBuilder: TransactionBuilderRequirements {
 	fn extension(&self, _: TypeId) -> Option<Arc<dyn Any + Send + Sync>> {
 		None
 	}
+
+	/// Creates a named storage-level snapshot of the datastore.
+	///
+	/// Backends which store their data on disk can create a snapshot in the
+	/// directory returned by [`crate::kvs::snapshot_path`], which can then be
+	/// opened as a datastore of its own. The default implementation returns
+	/// [`crate::kvs::Error::SnapshotNotSupported`].
+	fn snapshot<'a>(&'a self, _name: &'a str) -> BoxFut<'a, Result<()>> {
+		Box::pin(async move { bail!(Error::Kvs(crate::kvs::Error::SnapshotNotSupported)) })
+	}
 }
 
 /// Transaction-builder construction result with router startup state.
@@ -623,7 +736,7 @@ impl TransactionBuilderFactory for CommunityComposer {
 				#[cfg(feature = "kv-mem")]
 				{
 					// Create a new blocking threadpool
-					super::threadpool::initialise();
+					super::threadpool::initialise(&config);
 
 					// Persist path comes from the URL path; do not inject an empty
 					// string or `parse_key_with` logs a spurious DATASTORE_PERSIST warning.
@@ -658,7 +771,7 @@ impl TransactionBuilderFactory for CommunityComposer {
 				#[cfg(feature = "kv-rocksdb")]
 				{
 					// Create a new blocking threadpool
-					super::threadpool::initialise();
+					super::threadpool::initialise(&config);
 					// Parse RocksDB-specific configuration from query parameters
 					let config = config.load();
 					// Initialise the storage engine
@@ -678,7 +791,7 @@ impl TransactionBuilderFactory for CommunityComposer {
 				#[cfg(feature = "kv-surrealkv")]
 				{
 					// Create a new blocking threadpool
-					super::threadpool::initialise();
+					super::threadpool::initialise(&config);
 					// Parse SurrealKV-specific configuration from query parameters
 					let config = config.load();
 					// Initialise the storage engine
@@ -727,6 +840,13 @@ impl TransactionBuilderFactory for CommunityComposer {
 			}
 			// The datastore path is not valid
 			(flavour, path) => {
+				// Initiate a storage engine registered by another crate
+				#[cfg(feature = "kv-custom")]
+				if let Some(engine) = super::custom::engine(flavour) {
+					let v = engine.open(&path, config).await?;
+					info!(target: TARGET, "Started {flavour} kvs store");
+					return Ok(TransactionBuilderParts::without_router_state(v));
+				}
 				info!(target: TARGET, "Unable to load the specified datastore {flavour}{path}");
 				bail!(Error::Kvs(crate::kvs::Error::Datastore(
 					"Unable to load the specified datastore".into()
@@ -746,6 +866,8 @@ impl TransactionBuilderFactory for CommunityComposer {
 			v_s if v_s.starts_with("surrealkv:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("mem:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("tikv:") => Ok(v.to_string()),
+			#[cfg(feature = "kv-custom")]
+			v_s if super::custom::registered(v_s) => Ok(v.to_string()),
 			_ => bail!("Provide a valid database path parameter"),
 		}
 	}
@@ -819,6 +941,15 @@ impl TransactionBuilder for DatastoreFlavor {
 		}
 	}
 
+	fn memory_limit(&self) -> Option<u64> {
+		match self {
+			#[cfg(feature = "kv-mem")]
+			Self::Mem(v) => v.max_memory(),
+			#[allow(unreachable_patterns)]
+			_ => None,
+		}
+	}
+
 	fn shutdown(&self) -> BoxFut<'_, Result<()>> {
 		Box::pin(async move {
 			match self {
@@ -850,6 +981,19 @@ impl TransactionBuilder for DatastoreFlavor {
 			_ => None,
 		}
 	}
+
+	fn snapshot<'a>(&'a self, name: &'a str) -> BoxFut<'a, Result<()>> {
+		Box::pin(async move {
+			match self {
+				#[cfg(feature = "kv-rocksdb")]
+				Self::RocksDB(v) => Ok(v.snapshot(name).await?),
+				#[cfg(feature = "kv-surrealkv")]
+				Self::SurrealKV(v) => Ok(v.snapshot(name).await?),
+				#[allow(unreachable_patterns)]
+				_ => bail!(Error::Kvs(crate::kvs::Error::SnapshotNotSupported)),
+			}
+		})
+	}
 }
 
 impl Display for DatastoreFlavor {
@@ -1003,6 +1147,12 @@ impl Datastore {
 			function_registry: Arc::new(FunctionRegistry::with_builtins()),
 			buckets: self.buckets,
 			sequences: Sequences::new(self.transaction_factory.clone(), self.id),
+			throttles: Throttles::default(),
+			session_tables: SessionTables::default(),
+			index_advisor: IndexAdvisor::default(),
+			type_adapters: self.type_adapters,
+			scheduler: Scheduler::default(),
+			ticks: TickScheduler::default(),
 			transaction_factory: self.transaction_factory,
 			async_event_trigger: self.async_event_trigger,
 			#[cfg(feature = "surrealism")]
@@ -1053,6 +1203,12 @@ impl Datastore {
 			function_registry: Arc::new(FunctionRegistry::with_builtins()),
 			buckets: self.buckets.clone(),
 			sequences: Sequences::new(transaction_factory.clone(), id),
+			throttles: Throttles::default(),
+			session_tables: SessionTables::default(),
+			index_advisor: IndexAdvisor::default(),
+			type_adapters: self.type_adapters.clone(),
+			scheduler: Scheduler::default(),
+			ticks: TickScheduler::default(),
 			transaction_factory,
 			async_event_trigger: Arc::clone(&self.async_event_trigger),
 			#[cfg(feature = "surrealism")]
@@ -1078,6 +1234,17 @@ impl Datastore {
 		self
 	}
 
+	/// Set whether this Datastore rejects write transactions.
+	///
+	/// This is used when opening a snapshot, so that the snapshot can be
+	/// queried without being modified. Write transactions, including those
+	/// created by background tasks, fail with
+	/// [`crate::kvs::Error::DatastoreReadOnly`].
+	pub fn with_read_only(self, read_only: bool) -> Self {
+		self.transaction_factory.set_read_only(read_only);
+		self
+	}
+
 	/// Get the configured transaction timeout, if any
 	pub(crate) fn transaction_timeout(&self) -> Option<Duration> {
 		self.transaction_timeout
@@ -1206,10 +1373,20 @@ impl Datastore {
 	// Returns the current version and a flag indicating if this is a new datastore
 	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn get_version(&self) -> Result<(MajorVersion, bool)> {
-		// Start a new writeable transaction
-		let txn = self.transaction(Write, Optimistic).await?.enclose();
 		// Create the key where the version is stored
 		let key = crate::key::version::new();
+		// A read-only datastore can be checked, but never initialised
+		if self.transaction_factory.is_read_only() {
+			let txn = self.transaction(Read, Optimistic).await?.enclose();
+			let val = catch!(txn, txn.get(&key, None).await);
+			catch!(txn, txn.cancel().await);
+			return match val {
+				Some(val) => Ok((val, false)),
+				None => Err(Error::Kvs(crate::kvs::Error::DatastoreReadOnly).into()),
+			};
+		}
+		// Start a new writeable transaction
+		let txn = self.transaction(Write, Optimistic).await?.enclose();
 		// Check if a version is already set in storage
 		let val = match catch!(txn, txn.get(&key, None).await) {
 			// There is a version set in the storage
@@ -1252,6 +1429,17 @@ impl Datastore {
 		Ok(val)
 	}
 
+	/// Returns the revision of the storage format stored in the datastore,
+	/// or `None` if the datastore has not been initialised yet
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn storage_version(&self) -> Result<Option<u16>> {
+		let key = crate::key::version::new();
+		let txn = self.transaction(Read, Optimistic).await?.enclose();
+		let val = catch!(txn, txn.get(&key, None).await);
+		catch!(txn, txn.cancel().await);
+		Ok(val.map(u16::from))
+	}
+
 	// --------------------------------------------------
 	// Initialisation functions
 	// --------------------------------------------------
@@ -1609,10 +1797,38 @@ impl Datastore {
 		Self::retry("Expire nodes", || self.expire_nodes()).await?;
 		// Remove archived nodes
 		Self::retry("Remove nodes", || self.remove_nodes()).await?;
+		// Enable record checksums on the datastore, if configured
+		if self.config.record_checksums {
+			Self::retry("Record checksums", || self.set_record_checksums(true)).await?;
+		}
+		// Store the access grants under their subjects, if not done already
+		Self::retry("Index subject grants", || self.index_subject_grants()).await?;
 		// Everything ok
 		Ok(())
 	}
 
+	/// Stores the access grants which were created before grants were stored
+	/// under their subjects, so that they are listed by `ACCESS ... SHOW`
+	/// for a subject
+	async fn index_subject_grants(&self) -> Result<()> {
+		let tx = self.transaction(Write, Optimistic).await?;
+		catch!(tx, crate::expr::statements::access::index_subject_grants(&tx).await);
+		tx.commit().await
+	}
+
+	/// Enables or disables record checksums for every node of the datastore
+	///
+	/// The setting is stored in the datastore, along with when checksums were
+	/// enabled, so that the background scrubber writes the checksums which may
+	/// have become stale while they were disabled, rather than reporting their
+	/// records as corrupt. Enabling checksums which are already enabled has no
+	/// effect.
+	pub async fn set_record_checksums(&self, enabled: bool) -> Result<()> {
+		let tx = self.transaction(Write, Optimistic).await?;
+		catch!(tx, scrub::init(&tx, enabled).await);
+		tx.commit().await
+	}
+
 	/// Retries an async operation until it succeeds or the global timeout elapses.
 	///
 	/// Only [`TransactionConflict`](crate::kvs::Error::TransactionConflict)
@@ -1898,6 +2114,9 @@ impl Datastore {
 							let tlq = crate::key::table::lq::new(val.ns, val.db, &val.tb, nlq.lq);
 							// Delete the table live query
 							catch!(txn, txn.clr(&tlq).await);
+							// Delete the database live query
+							let dlq = crate::key::database::lq::new(val.ns, val.db, nlq.lq);
+							catch!(txn, txn.clr(&dlq).await);
 							// Delete the node live query
 							catch!(txn, txn.clr(&nlq).await);
 						}
@@ -1913,6 +2132,9 @@ impl Datastore {
 				let key = crate::key::root::nd::new(*id);
 				// Delete the cluster node entry
 				catch!(txn, txn.clr(&key).await);
+				// Delete the health check scratch value of the node
+				let key = crate::key::node::hc::new(*id);
+				catch!(txn, txn.clr(&key).await);
 			}
 			// Commit the changes
 			catch!(txn, txn.commit().await);
@@ -1928,20 +2150,25 @@ impl Datastore {
 	/// This function clears up all data which might have been missed from
 	/// previous cleanup runs, or when previous runs failed. This function
 	/// currently deletes all live queries, for nodes which no longer exist
-	/// in the cluster, from all namespaces, databases, and tables. It uses
-	/// a number of transactions in order to prevent failure of large or
-	/// long-running transactions on distributed storage engines.
+	/// in the cluster, from all namespaces, databases, and tables, as well
+	/// as the session tables whose session has ended without removing them,
+	/// the dead-lettered events whose retention has passed, and the access
+	/// grant rate limit windows which have expired.
+	/// It uses a number of transactions in order to prevent failure of large
+	/// or long-running transactions on distributed storage engines.
 	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
 	pub async fn garbage_collect(&self) -> Result<()> {
 		// Log the node deletion
 		trace!(target: TARGET, "Garbage collecting all miscellaneous data");
-		// Fetch archived nodes
-		let archived = {
+		// Fetch archived and active nodes
+		let (archived, active) = {
 			let txn = self.transaction(Read, Optimistic).await?;
 			let nds = catch!(txn, txn.all_nodes().await);
 			txn.cancel().await?;
-			// Filter the archived nodes
-			nds.iter().filter_map(Node::archived).collect::<Vec<_>>()
+			// Filter the archived and the active nodes
+			let archived = nds.iter().filter_map(Node::archived).collect::<Vec<_>>();
+			let active = nds.iter().filter(|nd| nd.is_active()).map(Node::id).collect::<Vec<_>>();
+			(archived, active)
 		};
 		// Fetch all namespaces
 		let nss = {
@@ -1965,6 +2192,38 @@ impl Datastore {
 			for db in dbs.iter() {
 				// Log the namespace
 				trace!(target: TARGET, "Garbage collecting data in database {}/{}", ns.name, db.name);
+				// Iterate over the database live queries
+				let beg = crate::key::database::lq::prefix(db.namespace_id, db.database_id)?;
+				let end = crate::key::database::lq::suffix(db.namespace_id, db.database_id)?;
+				let txn = self.transaction(Write, Optimistic).await?;
+				let res = catch!(txn, txn.getr(beg..end, None).await);
+				for (k, v) in res.iter() {
+					// Decode the LIVE SCHEMA query statement
+					let stm: SubscriptionDefinition = KVValue::kv_decode_value(v, ())?;
+					// Check that the node for this query is archived
+					if archived.contains(&stm.node) {
+						// Delete the node live query
+						let nlq = crate::key::node::lq::new(stm.node, stm.id);
+						catch!(txn, txn.clr(&nlq).await);
+						// Delete the database live query
+						catch!(txn, txn.clr(k).await);
+					}
+				}
+				// Commit the changes
+				catch!(txn, txn.commit().await);
+				// Remove the dead-lettered events whose retention has passed
+				let retention = self.config.event_dead_letter_retention;
+				if !retention.is_zero() {
+					let retention = i64::try_from(retention.as_millis()).unwrap_or(i64::MAX);
+					let before = Utc::now().timestamp_millis().saturating_sub(retention);
+					let txn = self.transaction(Write, Optimistic).await?;
+					catch!(
+						txn,
+						DeadLetterRecord::expire(&txn, db.namespace_id, db.database_id, before)
+							.await
+					);
+					catch!(txn, txn.commit().await);
+				}
 				// Fetch all tables
 				let tbs = {
 					let txn = self.transaction(Read, Optimistic).await?;
@@ -1974,6 +2233,22 @@ impl Datastore {
 				};
 				// Loop over all tables
 				for tb in tbs.iter() {
+					// Remove the session tables whose session has ended
+					if let Some(session) = tb.session {
+						// The session of a table defined on this node is known
+						// for as long as it runs, while the sessions of other
+						// nodes end at the latest with their node
+						let ended = match tb.session_node {
+							Some(nd) if nd != self.id() => !active.contains(&nd),
+							_ => !self.session_tables.contains(session),
+						};
+						if ended {
+							trace!(target: TARGET, "Removing session table {}/{}/{} of ended session {session}", ns.name, db.name, tb.name);
+							self.remove_session_table(session, &ns.name, &db.name, tb.name.clone())
+								.await?;
+							continue;
+						}
+					}
 					// Log the namespace
 					trace!(target: TARGET, "Garbage collecting data in table {}/{}/{}", ns.name, db.name, tb.name);
 					// Iterate over the table live queries
@@ -2013,6 +2288,29 @@ impl Datastore {
 				}
 			}
 		}
+		// Remove the access grant rate limit windows which have expired
+		trace!(target: TARGET, "Garbage collecting expired access grant rate limit windows");
+		let now = Utc::now().timestamp_millis();
+		let length = self.config.grant_rate_limit_window;
+		let mut next = Some(crate::key::root::gl::prefix()..crate::key::root::gl::suffix());
+		let txn = self.transaction(Write, Optimistic).await?;
+		while let Some(rng) = next {
+			// Fetch the next batch of keys and values
+			let res = catch!(txn, txn.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await);
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				// Decode the rate limit window
+				let window: GrantWindow = catch!(txn, KVValue::kv_decode_value(v, ()));
+				// Delete the window once no grant it counts is limited
+				if window.is_expired(now, length) {
+					catch!(txn, txn.clr(k).await);
+				}
+			}
+			// Pause and yield execution
+			yield_now!();
+		}
+		// Commit the changes
+		catch!(txn, txn.commit().await);
 		// All ok
 		Ok(())
 	}
@@ -2047,6 +2345,9 @@ impl Datastore {
 				let tlq = crate::key::table::lq::new(lq.ns, lq.db, &lq.tb, id);
 				// Delete the table live query
 				catch!(txn, txn.clr(&tlq).await);
+				// Delete the database live query
+				let dlq = crate::key::database::lq::new(lq.ns, lq.db, id);
+				catch!(txn, txn.clr(&dlq).await);
 				// Delete the node live query
 				catch!(txn, txn.clr(&nlq).await);
 			}
@@ -2057,6 +2358,113 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Remove the tables defined by a session.
+	///
+	/// This function should be run when a session ends.
+	///
+	/// Tables defined with `DEFINE TABLE ... SESSION` are only visible to
+	/// the session which defined them, and are removed along with their
+	/// records once the session has ended.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn remove_session_tables(&self, session: Uuid) -> Result<()> {
+		for table in self.session_tables.take(session) {
+			// Log the table removal
+			trace!(target: TARGET, "Removing session table {} of session {session}", table.name);
+			// Remove the table under its scoped name
+			let name = SessionTables::scoped_name(session, &table.name);
+			self.remove_session_table(session, &table.ns, &table.db, name).await?;
+		}
+		// All ok
+		Ok(())
+	}
+
+	/// Remove a session table, along with its records, as the session which
+	/// owns the table.
+	async fn remove_session_table(
+		&self,
+		session: Uuid,
+		ns: &str,
+		db: &str,
+		name: TableName,
+	) -> Result<()> {
+		let stm = RemoveTableStatement {
+			name: Expr::Table(name),
+			if_exists: true,
+			expunge: true,
+		};
+		let plan = LogicalPlan {
+			expressions: vec![TopLevelExpr::Expr(Expr::Remove(Box::new(RemoveStatement::Table(
+				stm,
+			))))],
+		};
+		let mut sess = Session::owner().with_ns(ns).with_db(db);
+		sess.id = Some(session);
+		for res in self.process_plan(plan, &sess, None).await? {
+			res.result?;
+		}
+		Ok(())
+	}
+
+	/// Update a parameter of the live queries started by a session.
+	///
+	/// This function should be run when a session sets or unsets a parameter.
+	///
+	/// Live queries capture the parameters which they refer to when they are
+	/// started. This function replaces the captured value in the specified
+	/// live queries on the current node which refer to the parameter, so that
+	/// notifications are filtered and projected with the current value of the
+	/// session parameter. A value of `NONE` removes the parameter.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self, value))]
+	pub async fn update_live_query_param(
+		&self,
+		ids: Vec<uuid::Uuid>,
+		name: &str,
+		value: PublicValue,
+	) -> Result<()> {
+		// There is nothing to do without live queries
+		if ids.is_empty() {
+			return Ok(());
+		}
+		// Log the parameter update
+		trace!(target: TARGET, "Updating parameter ${name} for live queries");
+		// Convert the value for storage
+		let value = crate::sql::expression::convert_public_value_to_internal(value);
+		// Create a new transaction
+		let txn = self.transaction(Write, Optimistic).await?;
+		// Loop over the live query unique ids
+		for id in ids {
+			// Get the key for this node live query
+			let nlq = crate::key::node::lq::new(self.id(), id);
+			// Fetch the LIVE meta data node entry
+			let Some(lq) = catch!(txn, txn.get(&nlq, None).await) else {
+				continue;
+			};
+			// Get the key for this table live query
+			let tlq = crate::key::table::lq::new(lq.ns, lq.db, &lq.tb, id);
+			// Fetch the table live query
+			let Some(mut sub) = catch!(txn, txn.get(&tlq, None).await) else {
+				continue;
+			};
+			// Ignore live queries which don't use the parameter
+			if !sub.references_param(name) {
+				continue;
+			}
+			// Replace the captured parameter value
+			if value.is_none() {
+				sub.vars.remove(name);
+			} else {
+				sub.vars.insert(name.to_owned(), value.clone());
+			}
+			catch!(txn, txn.replace(&tlq, &sub).await);
+			// Refresh the table cache for lives
+			self.cache.set_live_queries_version(lq.ns, lq.db, &lq.tb);
+		}
+		// Commit the changes
+		catch!(txn, txn.commit().await);
+		// All ok
+		Ok(())
+	}
+
 	// --------------------------------------------------
 	// Changefeed functions
 	// --------------------------------------------------
@@ -2064,14 +2472,22 @@ impl Datastore {
 	/// Performs changefeed garbage collection as a background task.
 	///
 	/// This method is responsible for cleaning up old changefeed data across
-	/// all databases. It uses a distributed task lease mechanism to coordinate
-	/// which node performs this maintenance operation. Once a batch starts it
-	/// runs to completion even if the lease expires, so brief overlap is
-	/// possible.
+	/// all databases, along with the expired live query events and
+	/// idempotency keys, and for compacting the statistics of tables. It uses
+	/// a distributed task lease mechanism to coordinate which node performs
+	/// this maintenance operation. Once a namespace starts it runs to
+	/// completion even if the lease expires, so brief overlap is possible.
 	///
 	/// The process involves:
 	/// 1. Acquiring a lease for the ChangeFeedCleanup task
-	/// 2. Cleaning up old changefeed data from all databases
+	/// 2. Maintaining each namespace in its own transaction, starting from a
+	///    different namespace on every run, within the configured
+	///    `tick_namespace_budget` per namespace
+	///
+	/// A namespace which runs out of budget resumes from its first remaining
+	/// database on the next run, and a namespace which fails is logged and
+	/// skipped, so that no namespace can hold back the others. The outcome of
+	/// every namespace is available from [`Datastore::tick_metrics`].
 	///
 	/// # Arguments
 	/// * `interval` - The interval between compaction runs, to calculate the lease duration
@@ -2093,26 +2509,110 @@ impl Datastore {
 		}
 		// Output function invocation details to logs
 		trace!(target: TARGET, "Running changefeed garbage collection");
+		// Fetch all namespaces
+		let nss = {
+			let txn = self.transaction(Read, Optimistic).await?;
+			let res = txn.all_ns(None).await;
+			let _ = txn.cancel().await;
+			res?
+		};
+		// Maintain each namespace in turn
+		for ns in self.ticks.start(&nss) {
+			let start = web_time::Instant::now();
+			let res = self.changefeed_process_ns(&lh, ns, start).await;
+			if let Err(e) = &res {
+				warn!(target: TARGET, "Failed background maintenance of namespace '{}': {e}", ns.name);
+			}
+			self.ticks.record(&ns.name, start.elapsed(), res.map_err(|e| e.to_string()));
+			// Stop if another node has taken over the lease
+			if !lh.try_maintain_lease().await? {
+				break;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Maintains the databases of a namespace, until the maintenance budget of
+	/// the namespace is spent
+	async fn changefeed_process_ns(
+		&self,
+		lh: &LeaseHandler,
+		ns: &NamespaceDefinition,
+		start: web_time::Instant,
+	) -> Result<NamespaceTick> {
 		// Create a new transaction
 		let txn = self.transaction(Write, Optimistic).await?;
+		// Fetch all databases
+		let dbs = catch!(txn, txn.all_db(ns.namespace_id, None).await);
+		let mut tick = NamespaceTick {
+			databases: 0,
+			deferred: false,
+		};
+		let mut order = self.ticks.databases(ns.namespace_id, &dbs).peekable();
+		while let Some(db) = order.next() {
+			if let Err(e) = self.changefeed_process_db(&txn, db).await {
+				let _ = txn.cancel().await;
+				// Start with the next database, so a failing one can not
+				// prevent the others from being maintained
+				self.ticks.resume_at(ns.namespace_id, order.next().map(|db| db.database_id));
+				return Err(e);
+			}
+			tick.databases += 1;
+			// Possibly renew the lease
+			catch!(txn, lh.try_maintain_lease().await);
+			// Defer the remaining databases once the budget is spent
+			if let Some(next) = order.peek()
+				&& start.elapsed() >= self.config.tick_namespace_budget
+			{
+				tick.deferred = true;
+				catch!(txn, txn.commit().await);
+				self.ticks.resume_at(ns.namespace_id, Some(next.database_id));
+				return Ok(tick);
+			}
+		}
+		// Commit the changes
+		catch!(txn, txn.commit().await);
+		self.ticks.resume_at(ns.namespace_id, None);
+		Ok(tick)
+	}
+
+	/// Maintains a single database, within the transaction of its namespace
+	async fn changefeed_process_db(
+		&self,
+		txn: &Transaction,
+		db: &DatabaseDefinition,
+	) -> Result<()> {
 		// Perform the changefeed garbage collection
-		catch!(txn, crate::cf::gc_all_at(&lh, &txn).await);
+		crate::cf::gc_db(txn, db).await?;
 		// When the Router engine is active, also garbage-collect the dedicated
 		// live-query event keyspace, retaining entries for the configured window so
 		// reconnecting/lagging subscribers can still replay. This rides the same
 		// lease and transaction as the changefeed GC above.
 		if self.config.live_query_engine == LiveQueryEngine::Router {
-			catch!(
-				txn,
-				crate::lq::gc::gc_all_at(&lh, &txn, self.config.live_query_retention).await
-			);
+			crate::lq::gc::gc_db(txn, db, self.config.live_query_retention).await?;
 		}
-		// Commit the changes
-		catch!(txn, txn.commit().await);
-		// Everything ok
+		// Garbage-collect the expired idempotency keys, on the same lease
+		crate::kvs::idempotency::gc_db(txn, db).await?;
+		// Compact the statistics of every table, on the same lease
+		crate::kvs::stats::compact_db(txn, db).await?;
+		// Compact the counts of the changefeed segments, on the same lease
+		crate::cf::segment::compact_db(txn, db).await?;
+		// Possibly yield to other tasks
+		yield_now!();
 		Ok(())
 	}
 
+	/// Returns the background maintenance metrics of every namespace, by name.
+	///
+	/// The metrics cover the runs of [`Datastore::changefeed_process`] on this
+	/// node since the datastore was started, for the namespaces which exist.
+	pub fn tick_metrics(&self) -> BTreeMap<String, NamespaceTickMetrics> {
+		self.ticks.metrics()
+	}
+
 	/// Run one live-query router pass.
 	///
 	/// Under the [`LiveQueryEngine::Router`] engine this tails the dedicated
@@ -2128,7 +2628,9 @@ impl Datastore {
 		if self.config.live_query_engine != LiveQueryEngine::Router {
 			return Ok(());
 		}
-		crate::lq::router::process(self, &self.live_query_router).await
+		crate::lq::router::process(self, &self.live_query_router).await?;
+		self.live_query_router.record_pass();
+		Ok(())
 	}
 
 	// --------------------------------------------------
@@ -3300,7 +3802,8 @@ impl Datastore {
 		}
 	}
 
-	/// Process queued async events using a distributed lease to coordinate batches.
+	/// Process queued async events, and send the HTTP requests recorded by
+	/// outbox events, using a distributed lease to coordinate batches.
 	/// Once a batch starts it runs to completion even if the lease expires, so
 	/// brief overlap is possible.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
@@ -3324,54 +3827,216 @@ impl Datastore {
 			}
 			// Output function invocation details to logs
 			trace!(target: TARGET, "Running event processing process");
-			if AsyncEventRecord::process_next_events_batch(self, Some(&lh)).await? == 0 {
-				// The last batch didn't have any events to process,
-				// we can sleep until the next wake-up call
+			let events = AsyncEventRecord::process_next_events_batch(self, Some(&lh)).await?;
+			let requests = OutboxRecord::process_next_batch(self, Some(&lh)).await?;
+			if events == 0 && requests == 0 {
+				// The last batch didn't have any events or outbox requests
+				// to process, we can sleep until the next wake-up call
 				return Ok(());
 			}
 		}
 	}
 
-	// --------------------------------------------------
-	// Other functions
-	// --------------------------------------------------
-
-	/// Create a new transaction on this datastore
-	///
-	/// ```rust,no_run
-	/// use surrealdb_core::kvs::{Datastore, TransactionType::*, LockType::*};
-	/// use anyhow::Error;
+	/// Move the records matching archival policies into their destination
+	/// tables, using a distributed lease so that only one node archives at a
+	/// time.
 	///
-	/// #[tokio::main]
-	/// async fn main() -> Result<(),Error> {
-	///     let ds = Datastore::new("rocksdb://database.db").await?;
-	///     let mut tx = ds.transaction(Write, Optimistic).await?;
-	///     tx.cancel().await?;
-	///     Ok(())
-	/// }
-	/// ```
-	pub async fn transaction(&self, write: TransactionType, lock: LockType) -> Result<Transaction> {
-		self.transaction_factory.transaction(write, lock, self.sequences.clone()).await
-	}
-
-	pub(crate) fn sequences(&self) -> &Sequences {
-		&self.sequences
+	/// # Arguments
+	/// * `interval` - The interval between archival runs, to calculate the lease duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn archival_process(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting archival process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::Archival,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running archival process");
+		archival::run(self, &lh).await
 	}
 
-	pub(crate) fn transaction_factory(&self) -> &TransactionFactory {
-		&self.transaction_factory
+	/// Permanently remove the soft deleted records of tables whose retention
+	/// period has passed, using a distributed lease so that only one node
+	/// purges at a time.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between purge runs, to calculate the lease duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn soft_delete_purge_process(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting soft delete purge process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::SoftDeletePurge,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running soft delete purge process");
+		purge::run(self, &lh).await
 	}
 
-	#[cfg(test)]
-	#[cfg_attr(not(feature = "kv-mem"), allow(dead_code))]
-	pub(crate) fn index_builder(&self) -> &IndexBuilder {
-		&self.index_builder
-	}
-	pub fn async_event_trigger(&self) -> &Arc<Notify> {
-		&self.async_event_trigger
+	/// Evict the expired and least recently used records of tables defined
+	/// with `EVICT`, using a distributed lease so that only one node evicts
+	/// at a time.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between eviction runs, to calculate the lease duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn eviction_process(&self, interval: Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting eviction process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::Eviction,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running eviction process");
+		evict::run(self, &lh).await
 	}
 
-	pub async fn health_check(&self) -> Result<()> {
+	/// Validate the stored records against their checksums, when record
+	/// checksums are enabled, using a distributed lease so that only one node
+	/// scrubs at a time.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between scrub runs, to calculate the lease duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn scrub_process(&self, interval: Duration) -> Result<()> {
+		// Record checksums are not enabled on this datastore
+		let tx = self.transaction(Read, Optimistic).await?;
+		let enabled = scrub::enabled(&tx).await;
+		tx.cancel().await?;
+		if !enabled? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting scrub process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::Scrub,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running scrub process");
+		scrub::run(self, &lh, self.config.scrub_quarantine).await
+	}
+
+	/// Returns the records which the background scrubber has found not to
+	/// match their checksums
+	///
+	/// The records stay reported until they are cleared with
+	/// [`Datastore::clear_corrupt_records`], even once they have been written
+	/// again.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn corrupt_records(&self) -> Result<Vec<CorruptRecord>> {
+		let tx = self.transaction(Read, Optimistic).await?;
+		let res = scrub::corrupt_records(&tx).await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Counts the records reported by [`Datastore::corrupt_records`], without
+	/// reading the reports
+	async fn corrupt_record_count(&self) -> Result<usize> {
+		let tx = self.transaction(Read, Optimistic).await?;
+		let res = scrub::count_corrupt_records(&tx).await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Clears the records reported by [`Datastore::corrupt_records`],
+	/// discarding the stored values of any records which were quarantined
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn clear_corrupt_records(&self) -> Result<()> {
+		let (beg, end) = crate::key::root::cr::Cr::range();
+		let tx = self.transaction(Write, Optimistic).await?;
+		catch!(tx, tx.delr(beg..end).await);
+		tx.commit().await
+	}
+
+	/// The records of tables defined with `EVICT`, and when they were last
+	/// used
+	pub(crate) fn evictions(&self) -> &Evictions {
+		&self.transaction_factory.evictions
+	}
+
+	/// Notified once the records of the tables evicted by least recent use
+	/// exceed the memory limit of the datastore
+	pub fn eviction_trigger(&self) -> &Notify {
+		self.evictions().trigger()
+	}
+
+	// --------------------------------------------------
+	// Other functions
+	// --------------------------------------------------
+
+	/// Create a new transaction on this datastore
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::{Datastore, TransactionType::*, LockType::*};
+	/// use anyhow::Error;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(),Error> {
+	///     let ds = Datastore::new("rocksdb://database.db").await?;
+	///     let mut tx = ds.transaction(Write, Optimistic).await?;
+	///     tx.cancel().await?;
+	///     Ok(())
+	/// }
+	/// ```
+	pub async fn transaction(&self, write: TransactionType, lock: LockType) -> Result<Transaction> {
+		self.transaction_factory.transaction(write, lock, self.sequences.clone()).await
+	}
+
+	pub(crate) fn sequences(&self) -> &Sequences {
+		&self.sequences
+	}
+
+	pub(crate) fn transaction_factory(&self) -> &TransactionFactory {
+		&self.transaction_factory
+	}
+
+	#[cfg(test)]
+	#[cfg_attr(not(feature = "kv-mem"), allow(dead_code))]
+	pub(crate) fn index_builder(&self) -> &IndexBuilder {
+		&self.index_builder
+	}
+	pub fn async_event_trigger(&self) -> &Arc<Notify> {
+		&self.async_event_trigger
+	}
+
+	pub async fn health_check(&self) -> Result<()> {
 		let tx = self.transaction(Read, Optimistic).await?;
 
 		// Cancel the transaction
@@ -3393,6 +4058,76 @@ impl Datastore {
 		}
 	}
 
+	/// Probes the health of the datastore
+	///
+	/// Writes a random value to a scratch key of the current node and reads it
+	/// back in a fresh transaction, to verify that the storage engine can be
+	/// written to and read from. The write is skipped while the datastore is
+	/// locked for writes, as it would otherwise wait for the lock. The report
+	/// also includes how recently the node refreshed its cluster heartbeat and,
+	/// under the [`LiveQueryEngine::Router`] engine, how recently the live
+	/// query router delivered notifications, along with the number of records
+	/// which the background scrubber has found corrupt.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn health_report(&self) -> HealthReport {
+		let mut report = HealthReport::default();
+		let key = crate::key::node::hc::new(self.id);
+		let probe = rand::random::<u64>();
+		let start = web_time::Instant::now();
+		// Write the probe value to storage
+		let written = if self.transaction_factory.writes_locked() {
+			None
+		} else {
+			let res = async {
+				let tx = self.transaction(Write, Optimistic).await?;
+				catch!(tx, tx.set(&key, &probe).await);
+				tx.commit().await
+			}
+			.await;
+			if let Err(e) = &res {
+				report.errors.push(format!("Failed to write to storage: {e}"));
+			}
+			report.writable = Some(res.is_ok());
+			Some(res.is_ok())
+		};
+		// Read the probe value back from storage
+		let res = async {
+			let tx = self.transaction(Read, Optimistic).await?;
+			let res = tx.get(&key, None).await;
+			let _ = tx.cancel().await;
+			res
+		}
+		.await;
+		match res {
+			Ok(Some(val)) if written == Some(true) && val != probe => {
+				report.errors.push("Read a stale value back from storage".to_string());
+			}
+			Ok(None) if written == Some(true) => {
+				report
+					.errors
+					.push("The value written to storage could not be read back".to_string());
+			}
+			Ok(_) => report.readable = true,
+			Err(e) => report.errors.push(format!("Failed to read from storage: {e}")),
+		}
+		report.latency = start.elapsed();
+		// Check that the node heartbeat is being refreshed
+		match self.node_heartbeat_age().await {
+			Ok(age) => report.heartbeat_age = Some(age),
+			Err(e) => report.errors.push(format!("Failed to read the node heartbeat: {e}")),
+		}
+		// Check that live query notifications are being delivered
+		if self.config.live_query_engine == LiveQueryEngine::Router {
+			report.live_query_lag = self.live_query_router.lag();
+		}
+		// Count the records which the scrubber has found corrupt
+		match self.corrupt_record_count().await {
+			Ok(count) => report.corrupt_records = count as u64,
+			Err(e) => report.errors.push(format!("Failed to read the corrupt records: {e}")),
+		}
+		report
+	}
+
 	/// Returns how long ago the current node last refreshed its cluster
 	/// heartbeat.
 	///
@@ -3447,6 +4182,92 @@ impl Datastore {
 		self.process(ast, sess, vars).await
 	}
 
+	/// Parse and execute an SQL query with an idempotency key
+	///
+	/// The query is run in a single transaction, and its results are recorded
+	/// under the key in the current database. If the same user runs the same
+	/// query with the same key again, before the key expires, the recorded
+	/// results are returned and the query is not run again, so that a client
+	/// can safely retry a mutation when it does not know whether it succeeded.
+	/// Results are only recorded when every statement succeeds. Reusing a key
+	/// for a different query is an error.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn execute_idempotent(
+		&self,
+		txt: &str,
+		sess: &Session,
+		vars: Option<PublicVariables>,
+		key: &str,
+	) -> std::result::Result<Vec<QueryResult>, TypesError> {
+		// Parse the SQL query text
+		let ast = syn::parse_with_capabilities(txt, &self.capabilities, &self.config)
+			.map_err(|e| TypesError::validation(e.to_string(), None))?;
+		// Process the AST, recording its results
+		self.process_idempotent(ast, txt, sess, vars, key).await.map_err(|e| {
+			match e.downcast::<TypesError>() {
+				Ok(e) => e,
+				Err(e) => e
+					.downcast::<Error>()
+					.map(crate::err::into_types_error)
+					.unwrap_or_else(|e| TypesError::internal(e.to_string())),
+			}
+		})
+	}
+
+	/// Parse and execute an SQL query, streaming the result of each statement
+	///
+	/// Rather than waiting for the whole query to complete, the result of each
+	/// statement is yielded as soon as the statement is complete. Statements in
+	/// a transaction block are yielded once the block commits or is cancelled.
+	/// The query is driven by polling the stream, and stops running statements
+	/// once the stream is dropped.
+	///
+	/// ```rust,no_run
+	/// use anyhow::Error;
+	/// use futures::StreamExt;
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::dbs::Session;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(),Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner().with_ns("test").with_db("test");
+	///     let mut stream = ds.execute_stream("SELECT * FROM person", &ses, None)?;
+	///     while let Some(res) = stream.next().await {
+	///         let res = res?;
+	///     }
+	///     Ok(())
+	/// }
+	/// ```
+	pub fn execute_stream<'a>(
+		&'a self,
+		txt: &str,
+		sess: &'a Session,
+		vars: Option<PublicVariables>,
+	) -> std::result::Result<
+		impl Stream<Item = std::result::Result<QueryResult, TypesError>> + 'a,
+		TypesError,
+	> {
+		use futures::StreamExt;
+		// Parse the SQL query text
+		let ast = syn::parse_with_capabilities(txt, &self.capabilities, &self.config)
+			.map_err(|e| TypesError::validation(e.to_string(), None))?;
+		// Results are sent one at a time, so that the executor waits for the
+		// consumer rather than buffering the whole query
+		let (snd, rcv) = async_channel::bounded(1);
+		let run = futures::stream::once(async move {
+			let (ctx, opt) = self.setup_query(sess, vars, None)?;
+			Executor::execute_plan_streamed(self, ctx, opt, ast.into(), snd).await.map_err(|e| {
+				e.downcast::<Error>()
+					.map(crate::err::into_types_error)
+					.unwrap_or_else(|e| TypesError::internal(e.to_string()))
+			})
+		})
+		// Only a failure of the query as a whole is yielded by the executor
+		.filter_map(|res| std::future::ready(res.err().map(Err)));
+		Ok(futures::stream::select(rcv.map(Ok), run))
+	}
+
 	/// Parse and lower a GQL query into a [`PreparedGqlQuery`], checking that
 	/// the `gql` experimental capability is enabled.
 	#[cfg(feature = "gql")]
@@ -3762,7 +4583,86 @@ impl Datastore {
 			ctx.attach_variables(vars.into())?;
 		}
 		// Process all statements
+		let stream = Self::import_statements(&ctx, query);
+		Executor::execute_stream(self, Arc::new(ctx), opt, true, stream).await
+	}
+
+	/// Executes a resumable import.
+	///
+	/// The statements are applied in transactions of
+	/// [`chunk_size`](import::Config::chunk_size) statements, and the progress
+	/// of the import is recorded as each transaction commits. If an import
+	/// with the same [`id`](import::Config::id) was interrupted, the
+	/// statements it already applied are skipped, after checking that they
+	/// are unchanged. The import stops at the first statement which fails,
+	/// and its progress is removed once it completes.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn execute_resumable_import<S>(
+		&self,
+		sess: &Session,
+		vars: Option<PublicVariables>,
+		query: S,
+		cfg: import::Config,
+	) -> Result<Vec<QueryResult>>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Resuming an import requires the same access as starting it
+		self.check_anon(sess).map_err(|_| {
+			Error::from(IamError::NotAllowed {
+				actor: "anonymous".to_string(),
+				action: "process".to_string(),
+				resource: "query".to_string(),
+			})
+		})?;
+		// Create a new query options
+		let opt = self.setup_options(sess);
+		// Create a default context
+		let mut ctx = self.setup_ctx()?;
+		// Start an execution context
+		ctx.attach_session(sess)?;
+		// Store the query variables
+		if let Some(vars) = vars {
+			ctx.attach_variables(vars.into())?;
+		}
+		// Load the progress of any previous run of this import into the same
+		// namespace and database
+		let tracker =
+			Arc::new(import::ImportTracker::new(sess.ns.clone(), sess.db.clone(), cfg.id));
+		let txn = self.transaction(Read, Optimistic).await?;
+		let resume = catch!(txn, tracker.load(&txn).await);
+		txn.cancel().await?;
+		// Process all statements
+		let stream = Self::import_statements(&ctx, query);
+		let results = Executor::execute_import_stream(
+			self,
+			Arc::new(ctx),
+			opt,
+			Arc::clone(&tracker),
+			resume,
+			cfg.chunk_size,
+			stream,
+		)
+		.await?;
+		// Remove the progress once every statement has been applied
+		if results.iter().all(|r| r.result.is_ok()) {
+			let txn = self.transaction(Write, Optimistic).await?;
+			catch!(txn, tracker.clear(&txn).await);
+			txn.commit().await?;
+		}
+		Ok(results)
+	}
 
+	/// Parses the statements of an import as they are streamed in
+	fn import_statements<S>(
+		ctx: &Context,
+		query: S,
+	) -> impl Stream<Item = Result<crate::sql::TopLevelExpr>> + use<S>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
 		let parser_settings = ParserSettings {
 			files_enabled: ctx.get_capabilities().allows_experimental(&ExperimentalTarget::Files),
 			surrealism_enabled: ctx
@@ -3773,11 +4673,11 @@ impl Datastore {
 		let mut statements_stream = StatementStream::new_with_settings(parser_settings);
 		let mut buffer = BytesMut::new();
 		let mut parse_size = 4096;
-		let mut bytes_stream = pin!(query);
+		let mut bytes_stream = Box::pin(query);
 		let mut complete = false;
 		let mut filling = true;
 
-		let stream = futures::stream::poll_fn(move |cx| {
+		futures::stream::poll_fn(move |cx| {
 			loop {
 				// fill the buffer to at least parse_size when filling is required.
 				while filling {
@@ -3827,9 +4727,7 @@ impl Datastore {
 					}
 				}
 			}
-		});
-
-		Executor::execute_stream(self, Arc::new(ctx), opt, true, stream).await
+		})
 	}
 
 	/// Execute a pre-parsed SQL query
@@ -3858,6 +4756,95 @@ impl Datastore {
 		self.process_plan_inner(ast.into(), sess, vars, Some(cancel)).await
 	}
 
+	/// Parse and plan an SQL query ahead of time
+	///
+	/// The returned [`CompiledPlan`] can be executed any number of times with
+	/// [`Self::process_compiled`], and encoded with
+	/// [`CompiledPlan::to_bytes`] to be loaded again with
+	/// [`Self::load_compiled`].
+	///
+	/// ```rust,no_run
+	/// use anyhow::Error;
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::dbs::Session;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(),Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner().with_ns("test").with_db("test");
+	///     let plan = ds.compile("SELECT * FROM person WHERE age > $age")?;
+	///     for age in [18, 21] {
+	///         let vars = surrealdb_types::vars! { age: age };
+	///         let res = ds.process_compiled(&plan, &ses, Some(vars)).await?;
+	///     }
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub fn compile(&self, txt: &str) -> std::result::Result<CompiledPlan, TypesError> {
+		// Parse the SQL query text
+		let ast = syn::parse_with_capabilities(txt, &self.capabilities, &self.config)
+			.map_err(|e| TypesError::validation(e.to_string(), None))?;
+		let settings = syn::settings_from_capabilities_config(&self.capabilities, &self.config);
+		Ok(CompiledPlan::new(ast.into(), settings))
+	}
+
+	/// Load a plan which was encoded with [`CompiledPlan::to_bytes`]
+	///
+	/// Fails if the plan was compiled by a different version of SurrealDB, or
+	/// uses features which the capabilities of this datastore do not allow.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub fn load_compiled(&self, bytes: &[u8]) -> std::result::Result<CompiledPlan, TypesError> {
+		let (version, query) = CompiledPlan::decode(bytes)
+			.and_then(|(version, query)| {
+				CompiledPlan::check_version(&version)?;
+				Ok((version, query))
+			})
+			.map_err(|e| {
+				e.downcast::<Error>()
+					.map(crate::err::into_types_error)
+					.unwrap_or_else(|e| TypesError::internal(e.to_string()))
+			})?;
+		let ast = syn::parse_with_capabilities(&query, &self.capabilities, &self.config)
+			.map_err(|e| TypesError::validation(e.to_string(), None))?;
+		Ok(CompiledPlan {
+			version,
+			plan: ast.into(),
+			settings: syn::settings_from_capabilities_config(&self.capabilities, &self.config),
+		})
+	}
+
+	/// Execute a plan which was compiled with [`Self::compile`]
+	///
+	/// Fails without executing any statement if the plan was compiled by a
+	/// different version of SurrealDB, or uses features which the
+	/// capabilities of this datastore do not allow.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn process_compiled(
+		&self,
+		plan: &CompiledPlan,
+		sess: &Session,
+		vars: Option<PublicVariables>,
+	) -> std::result::Result<Vec<QueryResult>, TypesError> {
+		CompiledPlan::check_version(&plan.version).map_err(|e| {
+			e.downcast::<Error>()
+				.map(crate::err::into_types_error)
+				.unwrap_or_else(|e| TypesError::internal(e.to_string()))
+		})?;
+		// A plan compiled by a datastore which allows more than this one is
+		// checked against the capabilities of this datastore, as it would be
+		// when the query is parsed
+		let settings = syn::settings_from_capabilities_config(&self.capabilities, &self.config);
+		let plan = if plan.is_allowed_by(&settings) {
+			plan.plan.clone()
+		} else {
+			syn::parse_with_capabilities(&plan.plan.to_sql(), &self.capabilities, &self.config)
+				.map_err(|e| TypesError::validation(e.to_string(), None))?
+				.into()
+		};
+		self.process_plan_inner(plan, sess, vars, None).await
+	}
+
 	pub(crate) async fn process_plan(
 		&self,
 		plan: LogicalPlan,
@@ -3874,6 +4861,23 @@ impl Datastore {
 		vars: Option<PublicVariables>,
 		cancel: Option<CancelHandle>,
 	) -> Result<Vec<QueryResult>, TypesError> {
+		let (ctx, opt) = self.setup_query(sess, vars, cancel)?;
+		// Process all statements
+		Executor::execute_plan(self, ctx, opt, plan).await.map_err(|e| {
+			e.downcast::<Error>()
+				.map(crate::err::into_types_error)
+				.unwrap_or_else(|e| TypesError::internal(e.to_string()))
+		})
+	}
+
+	/// Checks that the session may run queries, and sets up the context and
+	/// options to run them with
+	fn setup_query(
+		&self,
+		sess: &Session,
+		vars: Option<PublicVariables>,
+		cancel: Option<CancelHandle>,
+	) -> Result<(FrozenContext, Options), TypesError> {
 		// Check if the session has expired
 		if sess.expired() {
 			return Err(TypesError::not_allowed(
@@ -3919,12 +4923,7 @@ impl Datastore {
 			ctx.attach_variables(vars.into()).map_err(crate::err::into_types_error)?;
 		}
 
-		// Process all statements
-		Executor::execute_plan(self, ctx.freeze(), opt, plan).await.map_err(|e| {
-			e.downcast::<Error>()
-				.map(crate::err::into_types_error)
-				.unwrap_or_else(|e| TypesError::internal(e.to_string()))
-		})
+		Ok((ctx.freeze(), opt))
 	}
 
 	/// Evaluates a SQL [`Value`] without checking authenticating config
@@ -4015,6 +5014,21 @@ impl Datastore {
 		self.execute_import(sess, None, stream).await
 	}
 
+	/// Returns the batch size of an export with the given priority.
+	///
+	/// A background export first waits, for a bounded time, for interactive
+	/// queries to finish, as it reads from a single transaction which it can
+	/// not give up while it is deferred.
+	async fn export_batch_size(&self, priority: Priority) -> u32 {
+		match priority {
+			Priority::Interactive => self.config.export_batch_size,
+			Priority::Background => {
+				self.scheduler.defer().await;
+				background_batch_size(self.config.export_batch_size)
+			}
+		}
+	}
+
 	/// Performs a full database export as SQL
 	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn export(
@@ -4039,9 +5053,11 @@ impl Datastore {
 		ensure!(!sess.expired(), Error::ExpiredSession);
 		// Retrieve the provided NS and DB
 		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Background exports yield to interactive queries before they start,
+		// and read in smaller batches
+		let batch_size = self.export_batch_size(sess.priority).await;
 		// Create a new readonly transaction
 		let txn = self.transaction(Read, Optimistic).await?;
-		let batch_size = self.config.export_batch_size;
 		// Return an async export job
 		Ok(async move {
 			// Process the export
@@ -4051,6 +5067,71 @@ impl Datastore {
 		})
 	}
 
+	/// Performs a full namespace export as SQL
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn export_namespace(
+		&self,
+		sess: &Session,
+		chn: Sender<Vec<u8>>,
+	) -> Result<impl Future<Output = Result<()>> + 'static> {
+		// Create a default export config
+		let cfg = export::Config::default();
+		self.export_namespace_with_config(sess, chn, cfg).await
+	}
+
+	/// Performs a full namespace export as SQL.
+	///
+	/// Every database in the namespace of the session is read from a single
+	/// transaction, so the databases are exported from the same snapshot of
+	/// the datastore, where the storage engine supports it.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn export_namespace_with_config(
+		&self,
+		sess: &Session,
+		chn: Sender<Vec<u8>>,
+		cfg: export::Config,
+	) -> Result<impl Future<Output = Result<()>> + 'static> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Retrieve the provided NS
+		let ns = sess.ns.clone().ok_or(Error::NsEmpty)?;
+		// Exporting a namespace reads every database within it
+		self.check(sess, Action::View, ResourceKind::Any.on_ns(&ns))?;
+		// Background exports yield to interactive queries before they start,
+		// and read in smaller batches
+		let batch_size = self.export_batch_size(sess.priority).await;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Return an async export job
+		Ok(async move {
+			// Process the export
+			let res = txn.export_namespace(&ns, cfg, batch_size, chn).await;
+			txn.cancel().await?;
+			res
+		})
+	}
+
+	/// Restores some of the databases from a namespace export
+	///
+	/// Only the sections of the export which hold the given databases are
+	/// imported, into the namespace of the session. The import fails if any
+	/// of the databases is missing from the export.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn import_databases_stream<S>(
+		&self,
+		sess: &Session,
+		stream: S,
+		databases: Vec<String>,
+	) -> Result<Vec<QueryResult>>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Execute the SQL import
+		self.execute_import(sess, None, export::select_databases(stream, databases)).await
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	#[allow(clippy::needless_pass_by_value)] // Public API: ergonomic for callers passing `ResourceKind::X.on_db(ns, db)` inline.
@@ -4084,6 +5165,11 @@ impl Datastore {
 			self.index_stores.clone(),
 			self.index_builder.clone(),
 			self.sequences.clone(),
+			self.throttles.clone(),
+			self.session_tables.clone(),
+			self.index_advisor.clone(),
+			self.type_adapters.clone(),
+			self.scheduler.clone(),
 			Arc::clone(&self.cache),
 			Arc::clone(&self.function_registry),
 			#[cfg(feature = "http")]
@@ -4293,6 +5379,171 @@ impl Datastore {
 		Ok(model)
 	}
 
+	/// Lists the indexes in a database which have not been read for at least
+	/// the given duration.
+	///
+	/// Usage is only tracked in memory since this datastore was started, so
+	/// an index which has never been read is only reported once the datastore
+	/// has been running for longer than `unused_for`. Indexes reported here
+	/// still cost a write for every mutation of their table, and are good
+	/// candidates for removal.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn unused_indexes(
+		&self,
+		ns: &str,
+		db: &str,
+		unused_for: Duration,
+	) -> Result<Vec<UnusedIndex>> {
+		let tx = self.transaction(Read, Optimistic).await?;
+		let db = tx.expect_db_by_name(ns, db).await?;
+		let res = self
+			.index_stores
+			.usage()
+			.unused(&tx, db.namespace_id, db.database_id, unused_for)
+			.await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Lists the async events in a database which could not be processed, and
+	/// the HTTP requests of outbox events which could not be sent.
+	///
+	/// An event or request is moved to the dead-letter queue once every attempt
+	/// allowed by the `RETRY` clause of its `DEFINE EVENT` statement has failed,
+	/// along with the error returned by the last attempt.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn event_dead_letters(&self, ns: &str, db: &str) -> Result<Vec<DeadLetter>> {
+		let tx = self.transaction(Read, Optimistic).await?;
+		let db = tx.expect_db_by_name(ns, db).await?;
+		let res = DeadLetterRecord::list(&tx, db.namespace_id, db.database_id).await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Requeues the dead-lettered async events in a database, optionally only
+	/// those of a single table, returning how many events were requeued.
+	///
+	/// Requeued events run against the current definition of their event and
+	/// start again with the full number of retries. Events whose definition
+	/// has been removed stay in the dead-letter queue. Dead-lettered outbox
+	/// requests are moved back into the outbox, and are sent again as they
+	/// were recorded.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn replay_event_dead_letters(
+		&self,
+		ns: &str,
+		db: &str,
+		table: Option<&str>,
+	) -> Result<usize> {
+		let tx = self.transaction(Write, Optimistic).await?;
+		let db = catch!(tx, tx.expect_db_by_name(ns, db).await);
+		let count =
+			catch!(tx, DeadLetterRecord::replay(&tx, db.namespace_id, db.database_id, table).await);
+		tx.commit().await?;
+		Ok(count)
+	}
+
+	/// Lists the progress of every archival policy in a database.
+	///
+	/// Archival policies are created with `DEFINE POLICY`, and are run by the
+	/// background archival task every `archival_interval`.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn archival_progress(&self, ns: &str, db: &str) -> Result<Vec<ArchivalProgress>> {
+		let tx = self.transaction(Read, Optimistic).await?;
+		let res = archival::progress(&tx, ns, db).await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Locks the datastore for writes, for example while taking a backup.
+	///
+	/// Waits at most `wait` for the write transactions which are already open
+	/// to commit or cancel, and returns an error if they do not. While the
+	/// returned [`WriteLock`] is held, new write transactions wait for it to be
+	/// released, and read transactions continue as normal. The lock is
+	/// released when it is dropped, or forcibly once it has been held for
+	/// `max_hold`, so that a lost lock can not block writes indefinitely.
+	///
+	/// The caller must not hold a write transaction of its own while locking,
+	/// as the lock would wait for that transaction to finish.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn lock_writes(&self, wait: Duration, max_hold: Duration) -> Result<WriteLock> {
+		self.transaction_factory.lock_writes(wait, max_hold).await
+	}
+
+	/// Creates a named storage-level snapshot of the datastore.
+	///
+	/// The snapshot is created by the storage engine as a checkpoint, which
+	/// hard-links the data files of the datastore rather than copying them,
+	/// so it is nearly instantaneous. It is stored in the directory returned
+	/// by [`crate::kvs::snapshot_path`], and can be opened as a datastore of
+	/// its own, for example with [`Datastore::with_read_only`]. Only the
+	/// RocksDB and SurrealKV storage engines support snapshots.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn snapshot(&self, name: &str) -> Result<()> {
+		self.transaction_factory.snapshot(name).await
+	}
+
+	/// Pins the temporary index of a top-level field of a table.
+	///
+	/// A pinned temporary index is built the next time the table is scanned
+	/// with an equality predicate on the field, even when temporary indexes
+	/// are not enabled by the `temporary_index_threshold` configuration, and
+	/// is never evicted. Like every temporary index, it is held in memory for
+	/// the lifetime of this datastore, and is rebuilt on the next scan after
+	/// the table is written to.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn pin_temporary_index(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		field: &str,
+	) -> Result<()> {
+		let predicate = self.temporary_index_predicate(ns, db, tb, field).await?;
+		self.transaction_factory.temporary_indexes.pin(predicate);
+		Ok(())
+	}
+
+	/// Unpins the temporary index of a top-level field of a table, returning
+	/// whether it was pinned. The index can then be evicted, and is only
+	/// rebuilt once the table has been scanned often enough.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn unpin_temporary_index(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		field: &str,
+	) -> Result<bool> {
+		let predicate = self.temporary_index_predicate(ns, db, tb, field).await?;
+		Ok(self.transaction_factory.temporary_indexes.unpin(&predicate))
+	}
+
+	async fn temporary_index_predicate(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		field: &str,
+	) -> Result<Predicate> {
+		let tb = TableName::from(tb);
+		let txn = self.transaction(TransactionType::Read, LockType::Optimistic).await?;
+		let def = txn.get_tb_by_name(ns, db, &tb, None).await;
+		txn.cancel().await?;
+		let Some(def) = def? else {
+			bail!(Error::TbNotFound {
+				name: tb,
+			});
+		};
+		Ok(Predicate {
+			ns: def.namespace_id,
+			db: def.database_id,
+			tb,
+			field: field.to_owned(),
+		})
+	}
+
 	/// Invoke an API handler.
 	///
 	/// TODO: This should not need to be public, but it is used by the server's
@@ -4495,6 +5746,7 @@ mod test {
 		CatalogProvider, DatabaseProvider, NamespaceProvider, TableProvider,
 	};
 	use crate::iam::verify::verify_root_creds;
+	use crate::key::root::im::ImportProgressKey;
 	use crate::kvs::testing::{
 		RetryableConflictSite, inject_retryable_conflict, retryable_conflict_count,
 	};
@@ -5582,4 +6834,157 @@ mod test {
 		//
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn resumable_import_skips_applied_statements() -> Result<()> {
+		let (ds, session) = new_index_compaction_test_ds().await?;
+		let import_into = |session: &Session, sql: &'static str| {
+			let mut cfg = import::Config::new("test.surql");
+			cfg.chunk_size = 2;
+			let stream =
+				futures::stream::once(async move { Ok(Bytes::from_static(sql.as_bytes())) });
+			let session = session.clone();
+			let ds = &ds;
+			async move { ds.execute_resumable_import(&session, None, stream, cfg).await }
+		};
+		let import = |sql: &'static str| import_into(&session, sql);
+		let key = ImportProgressKey::new(Some("test"), Some("test"), "test.surql");
+		// The first chunk commits, and the second fails
+		let res = import("OPTION IMPORT; CREATE a:1; CREATE a:2; THROW 'boom';").await?;
+		assert!(res.iter().any(|r| r.result.is_err()), "{res:?}");
+		let txn = ds.transaction(Read, Optimistic).await?;
+		let progress = txn.get(&key, None).await?;
+		txn.cancel().await?;
+		assert_eq!(progress.map(|p| p.offset), Some(2));
+		// The same file imported into another database keeps its own progress
+		let other = session.clone().with_db("other");
+		let res = import_into(&other, "OPTION IMPORT; CREATE a:1; CREATE a:2; CREATE a:3;").await?;
+		assert!(res.iter().all(|r| r.result.is_ok()), "{res:?}");
+		let txn = ds.transaction(Read, Optimistic).await?;
+		let progress = txn.get(&key, None).await?;
+		txn.cancel().await?;
+		assert_eq!(progress.map(|p| p.offset), Some(2));
+		// Resuming skips the first chunk, and removes the progress once complete
+		let res = import("OPTION IMPORT; CREATE a:1; CREATE a:2; CREATE a:3;").await?;
+		assert!(res.is_empty(), "{res:?}");
+		let txn = ds.transaction(Read, Optimistic).await?;
+		let progress = txn.get(&key, None).await?;
+		txn.cancel().await?;
+		assert!(progress.is_none());
+		let res = &mut ds.execute("RETURN count(SELECT * FROM a)", &session, None).await?;
+		assert_eq!(res.remove(0).result.unwrap(), PublicValue::from_t(3));
+		// Resuming an import which changed since it was interrupted fails
+		import("OPTION IMPORT; CREATE b:1; CREATE b:2; THROW 'boom';").await?;
+		let res = import("OPTION IMPORT; CREATE c:1; CREATE c:2; CREATE c:3;").await?;
+		let err = res[0].result.as_ref().unwrap_err();
+		assert!(err.to_string().contains("does not match"), "{err}");
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn namespace_export_restores_selected_databases() -> Result<()> {
+		use futures::StreamExt;
+
+		let ds = Datastore::new("memory").await?;
+		let session = Session::owner().with_ns("test");
+		execute_all(
+			&ds,
+			&session.clone().with_db("a"),
+			"CREATE person:tobie; USE DB b; CREATE person:jaime;",
+		)
+		.await?;
+		// Export both databases into a single archive
+		let (snd, rcv) = async_channel::bounded(10);
+		let export = ds.export_namespace(&session, snd).await?;
+		let (res, chunks) = futures::join!(export, rcv.collect::<Vec<_>>());
+		res?;
+		let archive = Bytes::from(chunks.concat());
+		// Restore only one of the databases
+		let restored = Datastore::new("memory").await?;
+		let stream = futures::stream::once(async { Ok(archive) });
+		let res = restored.import_databases_stream(&session, stream, vec!["b".into()]).await?;
+		assert!(res.is_empty(), "{res:?}");
+		let res = &mut restored
+			.execute("RETURN object::keys((INFO FOR NS).databases)", &session, None)
+			.await?;
+		assert_eq!(res.remove(0).result.unwrap(), PublicValue::Array(surrealdb_types::array!["b"]));
+		let res = &mut restored
+			.execute("RETURN count(SELECT * FROM person)", &session.clone().with_db("b"), None)
+			.await?;
+		assert_eq!(res.remove(0).result.unwrap(), PublicValue::from_t(1));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn pinned_temporary_index() -> Result<()> {
+		use surrealdb_types::ToSql;
+
+		let (ds, session) = new_index_compaction_test_ds().await?;
+		let query = async |sql: &str| -> Result<String> {
+			let res = &mut ds.execute(sql, &session, None).await?;
+			Ok(res.remove(0).result?.to_sql())
+		};
+		execute_all(
+			&ds,
+			&session,
+			"CREATE person:1 SET name = 'a'; CREATE person:2 SET name = 'b'; \
+			 CREATE person:3 SET name = 'a';",
+		)
+		.await?;
+		ds.pin_temporary_index("test", "test", "person", "name").await?;
+		// The first scan builds the index, and the second uses it
+		for _ in 0..2 {
+			let res = query("SELECT VALUE id FROM person WHERE name = 'a'").await?;
+			assert_eq!(res, "[person:1, person:3]");
+		}
+		let res = query("(INFO FOR TABLE person).temporary_indexes").await?;
+		assert_eq!(res, "{ name: { built: true, hits: 1, pinned: true, rows: 3, values: 2 } }");
+		// A write invalidates the index, which is rebuilt on the next scan
+		execute_all(&ds, &session, "UPDATE person:2 SET name = 'a'").await?;
+		let res = query("(INFO FOR TABLE person).temporary_indexes.name.built").await?;
+		assert_eq!(res, "false");
+		let res = query("SELECT VALUE id FROM person WHERE name = 'a'").await?;
+		assert_eq!(res, "[person:1, person:2, person:3]");
+		assert!(ds.unpin_temporary_index("test", "test", "person", "name").await?);
+		assert!(!ds.unpin_temporary_index("test", "test", "person", "name").await?);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn compiled_plans() -> Result<()> {
+		use surrealdb_types::ToSql;
+
+		let (ds, session) = new_index_compaction_test_ds().await?;
+		execute_all(&ds, &session, "CREATE person:1 SET age = 17; CREATE person:2 SET age = 30;")
+			.await?;
+		let run = async |plan: &CompiledPlan, age: i64| -> Result<String> {
+			let vars = surrealdb_types::vars! { age: age };
+			let res = &mut ds.process_compiled(plan, &session, Some(vars)).await?;
+			Ok(res.remove(0).result?.to_sql())
+		};
+		// A compiled plan can be executed many times
+		let plan = ds.compile("SELECT VALUE id FROM person WHERE age > $age")?;
+		assert_eq!(plan.version(), crate::env::VERSION);
+		assert_eq!(run(&plan, 10).await?, "[person:1, person:2]");
+		assert_eq!(run(&plan, 20).await?, "[person:2]");
+		// An encoded plan can be loaded again
+		let loaded = ds.load_compiled(&plan.to_bytes()?)?;
+		assert_eq!(run(&loaded, 20).await?, "[person:2]");
+		// Plans compiled by another version are rejected
+		let mut stale = plan.clone();
+		stale.version = "1.0.0".to_owned();
+		assert!(ds.process_compiled(&stale, &session, None).await.is_err());
+		assert!(ds.load_compiled(&stale.to_bytes()?).is_err());
+		// Plans using features which this datastore does not allow are rejected
+		let permissive = Datastore::builder()
+			.with_capabilities(
+				Capabilities::all().with_experimental(crate::dbs::capabilities::Targets::All),
+			)
+			.build_with_path("memory")
+			.await?;
+		let plan = permissive.compile("RETURN f\"test:/a.txt\"")?;
+		let err = ds.process_compiled(&plan, &session, None).await.unwrap_err();
+		assert!(err.to_string().contains("experimental files feature"), "{err}");
+		Ok(())
+	}
 }
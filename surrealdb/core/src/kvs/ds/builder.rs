@@ -25,8 +25,9 @@ use crate::kvs::index::IndexBuilder;
 use crate::kvs::sequences::Sequences;
 use crate::kvs::slowlog::SlowLog;
 use crate::kvs::{
-	Datastore, TransactionBuilder, TransactionBuilderFactory, TransactionBuilderParts,
-	TransactionFactory,
+	Datastore, IndexAdvisor, SessionTables, Throttles, TransactionBuilder,
+	TransactionBuilderFactory, TransactionBuilderParts, TransactionFactory, TypeAdapter,
+	TypeAdapters,
 };
 use crate::lq::LiveQueryRouter;
 use crate::observe::{ExecutionObserver, NoopObserver};
@@ -57,6 +58,7 @@ pub struct Builder {
 	#[cfg(feature = "surrealism")]
 	lazy_surrealism: bool,
 	observer: Arc<dyn ExecutionObserver>,
+	type_adapters: TypeAdapters,
 }
 
 impl Default for Builder {
@@ -83,6 +85,7 @@ impl Builder {
 			#[cfg(feature = "surrealism")]
 			lazy_surrealism: false,
 			observer: Arc::new(NoopObserver),
+			type_adapters: TypeAdapters::default(),
 		}
 	}
 
@@ -108,6 +111,33 @@ impl Builder {
 		self
 	}
 
+	/// Set the number of worker threads in the shared blocking threadpool
+	/// which runs synchronous storage IO for the `memory`, `rocksdb` and
+	/// `surrealkv` engines.
+	///
+	/// The threadpool is shared by every datastore in the process, so only
+	/// the configuration of the first datastore which is started takes
+	/// effect. Values below 4 are ignored. When unset, the pool is sized
+	/// from `SURREAL_KVS_THREADPOOL_SIZE`, or from the core count.
+	pub fn with_blocking_threads(mut self, count: usize) -> Self {
+		self.config = std::mem::take(&mut self.config)
+			.with_key_value("kvs_threadpool_size", count.to_string());
+		self
+	}
+
+	/// Set whether the workers of the shared blocking threadpool are pinned
+	/// to dedicated cores.
+	///
+	/// When enabled, one pinned worker is created per logical core and the
+	/// configured size is ignored. When disabled, the workers are never
+	/// pinned. When unset, the workers are pinned only if the pool has one
+	/// worker per core on a host with at least 16 cores.
+	pub fn with_core_pinning(mut self, enabled: bool) -> Self {
+		self.config = std::mem::take(&mut self.config)
+			.with_key_value("kvs_threadpool_pinning", enabled.to_string());
+		self
+	}
+
 	/// Sets the capabilities for the datastore.
 	pub fn with_capabilities(mut self, cap: Capabilities) -> Self {
 		self.capabilities = cap;
@@ -192,6 +222,17 @@ impl Builder {
 		self
 	}
 
+	/// Register a [`TypeAdapter`] for the fields declared with its type,
+	/// replacing any adapter registered for the same type.
+	///
+	/// Adapters are only invoked when the
+	/// [`ExperimentalTarget::TypeAdapters`](crate::dbs::capabilities::ExperimentalTarget::TypeAdapters)
+	/// capability is enabled.
+	pub fn with_type_adapter(mut self, adapter: Arc<dyn TypeAdapter>) -> Self {
+		self.type_adapters.register(adapter);
+		self
+	}
+
 	pub async fn build_with_path(self, path: &str) -> Result<Datastore> {
 		self.build_with_factory_path(path, CommunityComposer()).await
 	}
@@ -306,6 +347,10 @@ impl Builder {
 			function_registry: Arc::new(FunctionRegistry::with_builtins()),
 			buckets,
 			sequences: Sequences::new(tf, id),
+			throttles: Throttles::default(),
+			session_tables: SessionTables::default(),
+			index_advisor: IndexAdvisor::default(),
+			type_adapters: self.type_adapters,
 			async_event_trigger,
 			#[cfg(feature = "surrealism")]
 			surrealism_cache: Arc::new(SurrealismCache::new(config.surrealism_cache_size)),
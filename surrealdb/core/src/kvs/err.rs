@@ -78,6 +78,24 @@ pub enum Error {
 
 	#[error("The storage layer does not support compaction requests.")]
 	CompactionNotSupported,
+
+	/// The storage engine can not create storage-level snapshots
+	#[error("The storage layer does not support snapshots.")]
+	SnapshotNotSupported,
+
+	/// The snapshot name is not a valid single directory name
+	#[error(
+		"Invalid snapshot name '{0}'. Snapshot names may only contain letters, digits, '-' and '_'"
+	)]
+	InvalidSnapshotName(String),
+
+	/// A snapshot with the same name already exists
+	#[error("A snapshot named '{0}' already exists")]
+	SnapshotExists(String),
+
+	/// The datastore was opened read-only, for example from a snapshot
+	#[error("The datastore was opened read-only")]
+	DatastoreReadOnly,
 }
 
 impl Error {
@@ -0,0 +1,633 @@
+//! Background eviction of records from tables defined with `EVICT`.
+//!
+//! Tables defined with `EVICT AFTER <duration>` have their records evicted
+//! once they have not been written for the duration. The time at which each
+//! record was last written is stored alongside it, in the
+//! [`crate::key::table::ew`] keyspace, so that records written on any node
+//! are evicted, and their time to live is kept across restarts. The records
+//! which already exist when a time to live is defined are recorded as if they
+//! had just been written.
+//!
+//! Tables defined with `EVICT LRU` have their least recently used records
+//! evicted whenever their records exceed the memory limit of the datastore,
+//! which is set with the `max_memory` option of the in-memory engine. The
+//! in-memory engine is only ever used by a single node, so the size of each
+//! record, and the time at which it was last read by id or written, are
+//! tracked in memory, within the datastore instance. Writes are tracked once
+//! their transaction commits, and the records which already exist when a table
+//! becomes evictable are tracked as if they had just been used. A commit which
+//! takes the records over the limit wakes the eviction task straight away,
+//! and writes to those tables are rejected until enough records are evicted,
+//! so the limit is only ever exceeded by the last transaction to commit.
+//!
+//! Records are evicted by deleting them, so table events are run as usual,
+//! and live queries on the table receive an `EVICTED` notification for every
+//! evicted record. Expired records are evicted in the transaction which reads
+//! their write times, so a record written in the meantime is not evicted.
+
+use std::collections::{BTreeMap, HashMap, HashSet, hash_map};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use anyhow::{Result, bail};
+use chrono::Utc;
+use parking_lot::{Mutex, RwLock};
+use tokio::sync::Notify;
+use tracing::warn;
+
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{DatabaseDefinition, DatabaseId, NamespaceId, TableDefinition};
+use crate::dbs::{Priority, Session};
+use crate::err::Error;
+use crate::key::record;
+use crate::key::table::ew;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{Datastore, KVValue, NORMAL_BATCH_SIZE, Transaction};
+use crate::val::{RecordId, RecordIdKey, TableName, Value, convert_value_to_public_value};
+
+/// The statement which evicts records from a table
+const EVICT: &str = "DELETE $ids RETURN NONE";
+
+type TableKey = (NamespaceId, DatabaseId, TableName);
+
+/// The records of an evictable table, and when they were last used
+type Records = HashMap<RecordIdKey, Entry>;
+
+/// The records of tables evicted by least recent use, and when they were
+/// last used
+#[derive(Clone, Default)]
+pub(crate) struct Evictions {
+	inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+	/// The maximum total size of the tracked records, if the datastore is
+	/// bounded
+	limit: Option<u64>,
+	/// Whether any table is tracked, so that other tables are not slowed down
+	active: AtomicBool,
+	/// The tracked tables, each locked on its own, so that the records of one
+	/// table are used without waiting for those of another
+	tables: RwLock<HashMap<TableKey, Arc<Mutex<Records>>>>,
+	/// Ticks every time a record is used, to find the least recently used
+	/// records
+	uses: AtomicU64,
+	/// The total size of the tracked records
+	bytes: AtomicU64,
+	/// Wakes the eviction task once the tracked records exceed the limit
+	trigger: Notify,
+}
+
+struct Entry {
+	/// The tick at which the record was last read or written
+	used: u64,
+	/// The encoded size of the record
+	bytes: u64,
+}
+
+impl Evictions {
+	pub(crate) fn new(limit: Option<u64>) -> Self {
+		Self {
+			inner: Arc::new(Inner {
+				limit,
+				..Default::default()
+			}),
+		}
+	}
+
+	/// The maximum total size of the tracked records, if the datastore is
+	/// bounded
+	pub(crate) fn limit(&self) -> Option<u64> {
+		self.inner.limit
+	}
+
+	/// The total size of the tracked records
+	fn bytes(&self) -> u64 {
+		self.inner.bytes.load(Ordering::Relaxed)
+	}
+
+	/// Whether the tracked records exceed the limit
+	fn exceeded(&self) -> bool {
+		self.inner.limit.is_some_and(|limit| self.bytes() > limit)
+	}
+
+	/// Notified once the tracked records exceed the limit, so that they are
+	/// evicted without waiting for the next run of the eviction task
+	pub(crate) fn trigger(&self) -> &Notify {
+		&self.inner.trigger
+	}
+
+	/// Returns the records of a tracked table
+	fn table(&self, tb: &TableKey) -> Option<Arc<Mutex<Records>>> {
+		self.inner.tables.read().get(tb).cloned()
+	}
+
+	/// Records that a record was read
+	pub(crate) fn read(&self, ns: NamespaceId, db: DatabaseId, tb: &TableName, id: &RecordIdKey) {
+		if !self.inner.active.load(Ordering::Relaxed) {
+			return;
+		}
+		let Some(records) = self.table(&(ns, db, tb.clone())) else {
+			return;
+		};
+		let used = self.inner.uses.fetch_add(1, Ordering::Relaxed) + 1;
+		if let Some(entry) = records.lock().get_mut(id) {
+			entry.used = used;
+		}
+	}
+
+	/// Records that a record was written
+	fn write(&self, tb: &TableKey, id: RecordIdKey, bytes: u64) {
+		let Some(records) = self.table(tb) else {
+			return;
+		};
+		let used = self.inner.uses.fetch_add(1, Ordering::Relaxed) + 1;
+		let entry = Entry {
+			used,
+			bytes,
+		};
+		self.inner.bytes.fetch_add(bytes, Ordering::Relaxed);
+		if let Some(old) = records.lock().insert(id, entry) {
+			self.inner.bytes.fetch_sub(old.bytes, Ordering::Relaxed);
+		}
+	}
+
+	/// Records that a record was deleted
+	fn delete(&self, tb: &TableKey, id: &RecordIdKey) {
+		self.forget(tb, std::slice::from_ref(id));
+	}
+
+	/// Applies the changes committed by a transaction, waking the eviction
+	/// task if the tracked records now exceed the limit
+	fn apply(&self, changes: Vec<Change>) {
+		for change in changes {
+			match change {
+				Change::Write(tb, id, bytes) => self.write(&tb, id, bytes),
+				Change::Delete(tb, id) => self.delete(&tb, &id),
+			}
+		}
+		if self.exceeded() {
+			self.inner.trigger.notify_one();
+		}
+	}
+
+	/// Tracks the given tables, and stops tracking any other table, returning
+	/// the tables which were not tracked before
+	fn retain(&self, tables: HashSet<TableKey>) -> Vec<TableKey> {
+		let mut tracked = self.inner.tables.write();
+		tracked.retain(|k, records| {
+			let keep = tables.contains(k);
+			if !keep {
+				let bytes = records.lock().values().map(|e| e.bytes).sum();
+				self.inner.bytes.fetch_sub(bytes, Ordering::Relaxed);
+			}
+			keep
+		});
+		let added = tables.into_iter().filter(|k| !tracked.contains_key(k)).collect::<Vec<_>>();
+		for k in added.iter() {
+			tracked.insert(k.clone(), Arc::default());
+		}
+		self.inner.active.store(!tracked.is_empty(), Ordering::Relaxed);
+		added
+	}
+
+	/// Tracks the records which existed when a table became evictable, as if
+	/// they had just been used
+	fn seed(&self, tb: &TableKey, existing: Vec<(RecordIdKey, u64)>) {
+		let Some(records) = self.table(tb) else {
+			return;
+		};
+		let used = self.inner.uses.load(Ordering::Relaxed);
+		let mut records = records.lock();
+		for (id, bytes) in existing {
+			if let hash_map::Entry::Vacant(v) = records.entry(id) {
+				self.inner.bytes.fetch_add(bytes, Ordering::Relaxed);
+				v.insert(Entry {
+					used,
+					bytes,
+				});
+			}
+		}
+	}
+
+	/// Returns the least recently used records of the given tables, whose
+	/// total size is at least the given number of bytes
+	fn least_recently_used(&self, tables: &[TableKey], bytes: u64) -> Vec<(TableKey, RecordIdKey)> {
+		let mut candidates = Vec::new();
+		for tb in tables {
+			let Some(records) = self.table(tb) else {
+				continue;
+			};
+			let records = records.lock();
+			candidates.extend(records.iter().map(|(id, e)| (e.used, e.bytes, tb, id.clone())));
+		}
+		candidates.sort_unstable_by_key(|(used, ..)| *used);
+		let mut total = 0;
+		candidates
+			.into_iter()
+			.take_while(|(_, size, ..)| {
+				let more = total < bytes;
+				total += size;
+				more
+			})
+			.map(|(_, _, tb, id)| (tb.clone(), id))
+			.collect()
+	}
+
+	/// Stops tracking records which have been evicted
+	fn forget(&self, tb: &TableKey, ids: &[RecordIdKey]) {
+		let Some(records) = self.table(tb) else {
+			return;
+		};
+		let mut records = records.lock();
+		for id in ids {
+			if let Some(old) = records.remove(id) {
+				self.inner.bytes.fetch_sub(old.bytes, Ordering::Relaxed);
+			}
+		}
+	}
+}
+
+/// A change made by a transaction to the records of the evictable tables
+enum Change {
+	Write(TableKey, RecordIdKey, u64),
+	Delete(TableKey, RecordIdKey),
+}
+
+/// The changes a transaction makes to the records of the evictable tables,
+/// which are only tracked once the transaction commits
+#[derive(Default)]
+pub(crate) struct EvictionBuffer {
+	evictions: Evictions,
+	changes: Mutex<Vec<Change>>,
+}
+
+impl EvictionBuffer {
+	pub(crate) fn new(evictions: Evictions) -> Self {
+		Self {
+			evictions,
+			changes: Mutex::default(),
+		}
+	}
+
+	/// Records that a record was read
+	pub(crate) fn read(&self, ns: NamespaceId, db: DatabaseId, tb: &TableName, id: &RecordIdKey) {
+		self.evictions.read(ns, db, tb, id);
+	}
+
+	/// Records that a record was written, rejecting the write while the
+	/// tracked records of a bounded datastore exceed its limit
+	pub(crate) fn write(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		id: &RecordIdKey,
+		bytes: u64,
+	) -> Result<()> {
+		if !self.evictions.inner.active.load(Ordering::Relaxed) {
+			return Ok(());
+		}
+		let tb = (ns, db, tb.clone());
+		if self.evictions.table(&tb).is_none() {
+			return Ok(());
+		}
+		if let Some(limit) = self.evictions.limit()
+			&& self.evictions.exceeded()
+		{
+			self.evictions.inner.trigger.notify_one();
+			bail!(Error::EvictionLimitExceeded {
+				limit
+			});
+		}
+		self.changes.lock().push(Change::Write(tb, id.clone(), bytes));
+		Ok(())
+	}
+
+	/// Records that a record was deleted
+	pub(crate) fn delete(&self, ns: NamespaceId, db: DatabaseId, tb: &TableName, id: &RecordIdKey) {
+		if !self.evictions.inner.active.load(Ordering::Relaxed) {
+			return;
+		}
+		self.changes.lock().push(Change::Delete((ns, db, tb.clone()), id.clone()));
+	}
+
+	/// Tracks the changes once the transaction has committed
+	pub(crate) fn commit(&self) {
+		let changes = std::mem::take(&mut *self.changes.lock());
+		if !changes.is_empty() {
+			self.evictions.apply(changes);
+		}
+	}
+
+	/// Discards the changes of a transaction which was cancelled
+	pub(crate) fn clear(&self) {
+		self.changes.lock().clear();
+	}
+}
+
+/// An evictable table, along with the name of its namespace and its database
+type Evictable = (String, DatabaseDefinition, TableDefinition);
+
+/// Lists every evictable table
+async fn all_tables(tx: &Transaction) -> Result<Vec<Evictable>> {
+	let mut out = Vec::new();
+	for ns in tx.all_ns(None).await?.iter() {
+		for db in tx.all_db(ns.namespace_id, None).await?.iter() {
+			for tb in tx.all_tb(db.namespace_id, db.database_id, None).await?.iter() {
+				if tb.eviction.is_some() {
+					out.push((ns.name.to_string(), db.clone(), tb.clone()));
+				}
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// Lists the records of a table, along with their size
+async fn all_records(tx: &Transaction, tb: &TableKey) -> Result<Vec<(RecordIdKey, u64)>> {
+	let (ns, db, tb) = tb;
+	let mut out = Vec::new();
+	let mut next = Some(record::prefix(*ns, *db, tb)?..record::suffix(*ns, *db, tb)?);
+	while let Some(rng) = next {
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		next = res.next;
+		for (k, v) in res.result.iter() {
+			out.push((record::RecordKey::decode_key(k)?.id, v.len() as u64));
+		}
+	}
+	Ok(out)
+}
+
+/// Starts recording when the records of a table were written once a time to
+/// live is defined on it, as if the existing records had just been written,
+/// and stops once the time to live is removed
+pub(crate) async fn define_ttl(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	before: bool,
+	after: bool,
+) -> Result<()> {
+	match (before, after) {
+		(false, true) => {
+			let now = u64::try_from(Utc::now().timestamp_millis()).unwrap_or_default();
+			let mut next = Some(record::prefix(ns, db, tb)?..record::suffix(ns, db, tb)?);
+			while let Some(rng) = next {
+				let res = tx.batch_keys(rng, NORMAL_BATCH_SIZE, None).await?;
+				next = res.next;
+				for k in res.result.iter() {
+					let id = record::RecordKey::decode_key(k)?.id;
+					tx.set(&ew::new(ns, db, tb, &id), &now).await?;
+				}
+			}
+			Ok(())
+		}
+		(true, false) => tx.delr(ew::range(ns, db, tb)?).await,
+		_ => Ok(()),
+	}
+}
+
+/// Lists the records of a table which were last written before the given
+/// time, in milliseconds since the unix epoch
+async fn expired(tx: &Transaction, tb: &TableKey, before: u64) -> Result<Vec<RecordIdKey>> {
+	let (ns, db, tb) = tb;
+	let mut out = Vec::new();
+	let mut next = Some(ew::range(*ns, *db, tb)?);
+	while let Some(rng) = next {
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		next = res.next;
+		for (k, v) in res.result.iter() {
+			if u64::kv_decode_value(v, ())? <= before {
+				out.push(ew::Ew::decode_key(k)?.id);
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// Evicts the records of a table which were last written before the given
+/// time, in milliseconds since the unix epoch
+async fn evict_expired(
+	ds: &Datastore,
+	ns: &str,
+	db: &DatabaseDefinition,
+	tb: &TableDefinition,
+	before: u64,
+) -> Result<()> {
+	let (ns_id, db_id, name) = key(db, tb);
+	let tx = Arc::new(ds.transaction(Write, Optimistic).await?);
+	// Read the write times in the transaction which evicts the records, so
+	// that a record written in the meantime conflicts instead of being evicted
+	let ids = catch!(tx, expired(&tx, &(ns_id, db_id, name.clone()), before).await);
+	if ids.is_empty() {
+		return tx.cancel().await;
+	}
+	catch!(tx, delete(ds, ns, db, tb, &ids, Arc::clone(&tx)).await);
+	// Remove the write times which are left behind for records that no
+	// longer exist, such as the records of a table which was emptied in a
+	// single range
+	for id in ids.iter() {
+		catch!(tx, tx.del(&ew::new(ns_id, db_id, &name, id)).await);
+	}
+	tx.commit().await
+}
+
+/// Evicts the expired and least recently used records of every evictable
+/// table, until all tables have been evicted from or the task lease is lost.
+pub(crate) async fn run(ds: &Datastore, lh: &LeaseHandler) -> Result<()> {
+	let evictions = ds.evictions();
+	// Find the evictable tables
+	let tx = ds.transaction(Read, Optimistic).await?;
+	let res = all_tables(&tx).await;
+	tx.cancel().await?;
+	let tables = res?;
+	let lru = tables
+		.iter()
+		.filter(|(_, _, tb)| tb.eviction.is_some_and(|e| e.lru))
+		.map(|(_, db, tb)| key(db, tb))
+		.collect::<Vec<_>>();
+	// Track the existing records of tables which have become evictable
+	let added = evictions.retain(lru.iter().cloned().collect::<HashSet<_>>());
+	if !added.is_empty() {
+		let tx = ds.transaction(Read, Optimistic).await?;
+		for tb in added.iter() {
+			match all_records(&tx, tb).await {
+				Ok(records) => evictions.seed(tb, records),
+				Err(e) => warn!("Tracking the records of evictable table '{}' failed: {e}", tb.2),
+			}
+		}
+		tx.cancel().await?;
+	}
+	// Evict the records which have not been written for the time to live
+	let now = Utc::now().timestamp_millis();
+	for (ns, db, tb) in tables.iter() {
+		let Some(ttl) = tb.eviction.and_then(|e| e.ttl) else {
+			continue;
+		};
+		// Stop if another node has taken over the task
+		if !lh.try_maintain_lease().await? {
+			return Ok(());
+		}
+		let ttl = i64::try_from(ttl.as_millis()).unwrap_or(i64::MAX);
+		let Ok(before) = u64::try_from(now.saturating_sub(ttl)) else {
+			continue;
+		};
+		if let Err(e) = evict_expired(ds, ns, db, tb, before).await {
+			warn!("Evicting expired records from table '{}' failed: {e}", tb.name);
+		}
+	}
+	// Evict the least recently used records while over the memory limit
+	let Some(limit) = evictions.limit() else {
+		return Ok(());
+	};
+	// The size is measured once the expired records have been evicted
+	let used = evictions.bytes();
+	if used <= limit {
+		return Ok(());
+	}
+	let mut victims = HashMap::<TableKey, Vec<RecordIdKey>>::new();
+	for (tb, id) in evictions.least_recently_used(&lru, used - limit) {
+		victims.entry(tb).or_default().push(id);
+	}
+	for (ns, db, tb) in tables.iter() {
+		let Some(ids) = victims.remove(&key(db, tb)) else {
+			continue;
+		};
+		// Stop if another node has taken over the task
+		if !lh.try_maintain_lease().await? {
+			return Ok(());
+		}
+		if let Err(e) = evict(ds, ns, db, tb, ids).await {
+			warn!("Evicting least recently used records from table '{}' failed: {e}", tb.name);
+		}
+	}
+	Ok(())
+}
+
+fn key(db: &DatabaseDefinition, tb: &TableDefinition) -> TableKey {
+	(db.namespace_id, db.database_id, tb.name.clone())
+}
+
+/// Evicts the given records of a table
+async fn evict(
+	ds: &Datastore,
+	ns: &str,
+	db: &DatabaseDefinition,
+	tb: &TableDefinition,
+	ids: Vec<RecordIdKey>,
+) -> Result<()> {
+	if ids.is_empty() {
+		return Ok(());
+	}
+	let tx = Arc::new(ds.transaction(Write, Optimistic).await?);
+	catch!(tx, delete(ds, ns, db, tb, &ids, Arc::clone(&tx)).await);
+	tx.commit().await?;
+	// Records which no longer exist are not deleted, so forget them here
+	ds.evictions().forget(&key(db, tb), &ids);
+	Ok(())
+}
+
+/// Deletes the given records of a table within a transaction, in a session
+/// which notifies live queries that the records were evicted
+async fn delete(
+	ds: &Datastore,
+	ns: &str,
+	db: &DatabaseDefinition,
+	tb: &TableDefinition,
+	ids: &[RecordIdKey],
+	tx: Arc<Transaction>,
+) -> Result<()> {
+	let rids = ids
+		.iter()
+		.map(|id| {
+			Value::RecordId(RecordId {
+				table: tb.name.clone(),
+				key: id.clone(),
+			})
+		})
+		.collect::<Vec<_>>();
+	let vars = BTreeMap::from([(
+		"ids".to_string(),
+		convert_value_to_public_value(Value::Array(rids.into()))?,
+	)]);
+	let sess = Session {
+		eviction: true,
+		..Session::owner().with_ns(ns).with_db(&db.name).with_priority(Priority::Background)
+	};
+	let mut res = ds
+		.execute_with_transaction(EVICT, &sess, Some(vars.into()), tx)
+		.await
+		.map_err(|e| anyhow::anyhow!(e))?;
+	let Some(last) = res.pop() else {
+		fail!("The eviction returned no results");
+	};
+	last.result.map_err(|e| anyhow::anyhow!(e))?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn tb() -> TableKey {
+		(NamespaceId(1), DatabaseId(2), TableName::from("cache"))
+	}
+
+	#[test]
+	fn untracked_tables_are_ignored() {
+		let evictions = Evictions::default();
+		evictions.write(&tb(), RecordIdKey::Number(1), 10);
+		assert!(evictions.least_recently_used(&[tb()], 1).is_empty());
+		assert_eq!(evictions.retain(HashSet::from([tb()])), vec![tb()]);
+		assert!(evictions.retain(HashSet::from([tb()])).is_empty());
+		assert!(evictions.least_recently_used(&[tb()], 1).is_empty());
+	}
+
+	#[test]
+	fn least_recently_used_records_are_evicted_first() {
+		let evictions = Evictions::default();
+		let (ns, db, name) = tb();
+		evictions.retain(HashSet::from([tb()]));
+		evictions.seed(&tb(), vec![(RecordIdKey::Number(1), 10)]);
+		evictions.write(&tb(), RecordIdKey::Number(2), 10);
+		evictions.write(&tb(), RecordIdKey::Number(3), 10);
+		evictions.read(ns, db, &name, &RecordIdKey::Number(1));
+		let victims = evictions.least_recently_used(&[tb()], 15);
+		assert_eq!(victims, vec![(tb(), RecordIdKey::Number(2)), (tb(), RecordIdKey::Number(3))]);
+		evictions.delete(&tb(), &RecordIdKey::Number(2));
+		evictions.forget(&tb(), &[RecordIdKey::Number(3)]);
+		assert_eq!(
+			evictions.least_recently_used(&[tb()], 15),
+			vec![(tb(), RecordIdKey::Number(1))]
+		);
+		assert_eq!(evictions.bytes(), 10);
+	}
+
+	#[test]
+	fn writes_are_tracked_once_committed() {
+		let evictions = Evictions::new(Some(15));
+		let (ns, db, name) = tb();
+		evictions.retain(HashSet::from([tb()]));
+		let cancelled = EvictionBuffer::new(evictions.clone());
+		cancelled.write(ns, db, &name, &RecordIdKey::Number(1), 10).unwrap();
+		cancelled.clear();
+		cancelled.commit();
+		assert_eq!(evictions.bytes(), 0);
+		let committed = EvictionBuffer::new(evictions.clone());
+		committed.write(ns, db, &name, &RecordIdKey::Number(1), 10).unwrap();
+		committed.write(ns, db, &name, &RecordIdKey::Number(2), 10).unwrap();
+		assert_eq!(evictions.bytes(), 0);
+		committed.commit();
+		assert_eq!(evictions.bytes(), 20);
+		// Writes are rejected until the records are back within the limit
+		let rejected = EvictionBuffer::new(evictions.clone());
+		assert!(rejected.write(ns, db, &name, &RecordIdKey::Number(3), 10).is_err());
+		evictions.forget(&tb(), &[RecordIdKey::Number(1)]);
+		assert!(rejected.write(ns, db, &name, &RecordIdKey::Number(3), 10).is_ok());
+	}
+}
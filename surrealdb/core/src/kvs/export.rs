@@ -1,26 +1,43 @@
+use std::collections::{BTreeMap, HashSet};
 use std::fmt;
+use std::task::{Poll, ready};
 
 use anyhow::Result;
 use async_channel::Sender;
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
 use surrealdb_types::{SurrealValue, ToSql};
 
 use super::{KVValue, Transaction};
 use crate::catalog::providers::{
-	ApiProvider, AuthorisationProvider, BucketProvider, DatabaseProvider, TableProvider,
-	UserProvider,
+	ApiProvider, AuthorisationProvider, BucketProvider, DatabaseProvider, NamespaceProvider,
+	TableProvider, UserProvider,
 };
-use crate::catalog::{DatabaseId, NamespaceId, Record, TableDefinition};
+use crate::catalog::{DatabaseDefinition, DatabaseId, NamespaceId, Record, TableDefinition};
 use crate::err::Error;
 use crate::expr::paths::{IN, OUT};
 use crate::expr::statements::define::{DefineAccessStatement, DefineUserStatement};
 use crate::expr::{Base, DefineAnalyzerStatement};
 use crate::key::record;
-use crate::sql::statements::OptionStatement;
+use crate::sql::statements::define::DefineKind;
+use crate::sql::statements::{OptionStatement, UseStatement};
+use crate::sql::{Expr, Idiom};
+
+/// The comment which starts the section of a namespace export holding the
+/// contents of a single database
+const DATABASE_MARKER: &str = "-- DATABASE: ";
+
+/// The comment which starts the last line of an export, holding the number of
+/// records exported from each table, and a checksum of the export before it
+const TRAILER_MARKER: &str = "-- TRAILER: ";
 
 #[derive(Clone, Debug, SurrealValue)]
 #[surreal(crate = "surrealdb_types")]
 #[surreal(default)]
 pub struct Config {
+	pub schema: bool,
 	pub users: bool,
 	pub accesses: bool,
 	pub params: bool,
@@ -34,11 +51,16 @@ pub struct Config {
 	pub versions: bool,
 	pub records: bool,
 	pub sequences: bool,
+	/// Whether to end the export with a trailer which holds the number of
+	/// records exported from each table and a checksum of the export
+	#[surreal(default)]
+	pub checksum: bool,
 }
 
 impl Default for Config {
 	fn default() -> Config {
 		Config {
+			schema: true,
 			users: true,
 			accesses: true,
 			params: true,
@@ -52,6 +74,27 @@ impl Default for Config {
 			versions: false,
 			records: true,
 			sequences: true,
+			checksum: true,
+		}
+	}
+}
+
+impl Config {
+	/// Exports only the definitions of the database, without any records
+	pub fn schema_only(self) -> Config {
+		Config {
+			schema: true,
+			records: false,
+			..self
+		}
+	}
+
+	/// Exports only the records of the database, without any definitions
+	pub fn data_only(self) -> Config {
+		Config {
+			schema: false,
+			records: true,
+			..self
 		}
 	}
 }
@@ -107,8 +150,8 @@ impl TableConfig {
 	pub(crate) fn is_any(&self) -> bool {
 		matches!(self, Self::All | Self::Some(_) | Self::Exclude(_))
 	}
-	// Check if we should export a specific table
-	pub(crate) fn includes(&self, table: &str) -> bool {
+	/// Check if we should export a specific table
+	pub fn includes(&self, table: &str) -> bool {
 		match self {
 			Self::All => true,
 			Self::None => false,
@@ -126,6 +169,36 @@ impl TableConfig {
 	}
 }
 
+/// Orders table definitions so that every view comes after the tables which
+/// it is computed from, keeping the original order otherwise.
+fn dependency_order(tables: &[TableDefinition]) -> Vec<&TableDefinition> {
+	fn visit<'a>(
+		table: &'a TableDefinition,
+		tables: &'a [TableDefinition],
+		visited: &mut HashSet<&'a str>,
+		ordered: &mut Vec<&'a TableDefinition>,
+	) {
+		// Skip tables which were already ordered, or views with cycles
+		if !visited.insert(table.name.as_str()) {
+			return;
+		}
+		if let Some(view) = &table.view {
+			for source in view.tables() {
+				if let Some(source) = tables.iter().find(|tb| tb.name == *source) {
+					visit(source, tables, visited, ordered);
+				}
+			}
+		}
+		ordered.push(table);
+	}
+	let mut visited = HashSet::new();
+	let mut ordered = Vec::with_capacity(tables.len());
+	for table in tables {
+		visit(table, tables, &mut visited, &mut ordered);
+	}
+	ordered
+}
+
 struct InlineCommentWriter<'a, F>(&'a mut F);
 impl<F: fmt::Write> fmt::Write for InlineCommentWriter<'_, F> {
 	fn write_str(&mut self, s: &str) -> fmt::Result {
@@ -157,6 +230,83 @@ impl<F: fmt::Display> fmt::Display for InlineCommentDisplay<F> {
 	}
 }
 
+/// Sends the lines of an export, keeping the checksum and record counts which
+/// are written to its trailer
+struct Output<'a> {
+	chn: &'a Sender<Vec<u8>>,
+	state: Mutex<OutputState>,
+}
+
+#[derive(Default)]
+struct OutputState {
+	/// The checksum of every line sent so far
+	hasher: Sha256,
+	/// The number of bytes sent so far
+	bytes: u64,
+	/// The database whose contents are being sent, in a namespace export
+	database: Option<String>,
+	/// The number of records exported from each table, by database
+	records: BTreeMap<Option<String>, BTreeMap<String, u64>>,
+}
+
+impl<'a> Output<'a> {
+	fn new(chn: &'a Sender<Vec<u8>>) -> Self {
+		Self {
+			chn,
+			state: Mutex::new(OutputState::default()),
+		}
+	}
+
+	async fn send(&self, line: Vec<u8>) -> Result<()> {
+		{
+			let mut state = self.state.lock();
+			state.hasher.update(&line);
+			state.bytes += line.len() as u64;
+		}
+		self.chn.send(line).await?;
+		Ok(())
+	}
+
+	/// Sets the database whose contents are sent next
+	fn database(&self, name: &str) {
+		self.state.lock().database = Some(name.to_owned());
+	}
+
+	/// Adds to the number of records exported from a table
+	fn count(&self, table: &str, records: u64) {
+		let mut state = self.state.lock();
+		let database = state.database.clone();
+		*state.records.entry(database).or_default().entry(table.to_owned()).or_default() += records;
+	}
+
+	/// Writes the trailer, which must be the last line of the export
+	async fn finish(self) -> Result<()> {
+		let state = self.state.into_inner();
+		// Namespace exports count the records of each database separately
+		let mut records = serde_json::Map::new();
+		for (database, tables) in state.records {
+			let tables = serde_json::json!(tables);
+			match database {
+				Some(database) => {
+					records.insert(database, tables);
+				}
+				None => {
+					if let serde_json::Value::Object(tables) = tables {
+						records.extend(tables);
+					}
+				}
+			}
+		}
+		let trailer = serde_json::json!({
+			"bytes": state.bytes,
+			"checksum": hex::encode(state.hasher.finalize()),
+			"records": records,
+		});
+		self.chn.send(bytes!(format!("{TRAILER_MARKER}{}", InlineCommentDisplay(trailer)))).await?;
+		Ok(())
+	}
+}
+
 impl Transaction {
 	/// Writes the full database contents as binary SQL.
 	pub async fn export(
@@ -172,24 +322,140 @@ impl Transaction {
 				name: db.to_owned(),
 			})
 		})?;
+		let out = Output::new(&chn);
+		// Output the MANIFEST
+		self.export_manifest(&cfg, &out, db.namespace_id, db.database_id).await?;
+		// Output OPTIONS
+		self.export_section("OPTION", [OptionStatement::import()].into_iter(), &out).await?;
+		// Output the database contents
+		self.export_database(&db, &cfg, batch_size, &out).await?;
+		// Output the TRAILER
+		if cfg.checksum {
+			out.finish().await?;
+		}
+		Ok(())
+	}
 
+	/// Writes the contents of every database in a namespace as binary SQL.
+	///
+	/// Every database is read within this transaction, so the export is a
+	/// consistent snapshot of the namespace on storage engines which provide
+	/// snapshot isolation. Each database is written in its own section, which
+	/// defines the database and switches to it, so the export can be imported
+	/// as a whole, or passed through [`select_databases`] to restore only some
+	/// of its databases.
+	pub async fn export_namespace(
+		&self,
+		ns: &str,
+		cfg: Config,
+		batch_size: u32,
+		chn: Sender<Vec<u8>>,
+	) -> Result<()> {
+		let ns_def = self.get_ns_by_name(ns, None).await?.ok_or_else(|| {
+			anyhow::Error::new(Error::NsNotFound {
+				name: ns.to_owned(),
+			})
+		})?;
+		let dbs = self.all_db(ns_def.namespace_id, None).await?;
+		let chn = Output::new(&chn);
+		// Output the MANIFEST, listing the databases in the export
+		let manifest = serde_json::json!({
+			"version": crate::env::VERSION,
+			"namespace": ns,
+			"databases": dbs.iter().map(|db| db.name.to_string()).collect::<Vec<_>>(),
+		});
+		chn.send(bytes!("-- ------------------------------")).await?;
+		chn.send(bytes!("-- MANIFEST")).await?;
+		chn.send(bytes!("-- ------------------------------")).await?;
+		chn.send(bytes!("")).await?;
+		chn.send(bytes!(format!("-- {}", InlineCommentDisplay(manifest)))).await?;
+		chn.send(bytes!("")).await?;
+		// Import mode is enabled once, for every database
+		self.export_section("OPTION", [OptionStatement::import()].into_iter(), &chn).await?;
+		// Output each DATABASE
+		for db in dbs.iter() {
+			let mut define = db.to_sql_definition();
+			define.kind = DefineKind::IfNotExists;
+			let name = Expr::Idiom(Idiom::field(db.name.clone()));
+			chn.send(bytes!("-- ------------------------------")).await?;
+			chn.send(bytes!(format!("{DATABASE_MARKER}{}", InlineCommentDisplay(&db.name))))
+				.await?;
+			chn.send(bytes!("-- ------------------------------")).await?;
+			chn.send(bytes!("")).await?;
+			chn.send(bytes!(format!("{};", define.to_sql()))).await?;
+			chn.send(bytes!(format!("{};", UseStatement::Db(name).to_sql()))).await?;
+			chn.send(bytes!("")).await?;
+			chn.database(&db.name);
+			self.export_database(db, &cfg, batch_size, &chn).await?;
+		}
+		// Output the TRAILER
+		if cfg.checksum {
+			chn.finish().await?;
+		}
+		Ok(())
+	}
+
+	async fn export_database(
+		&self,
+		db: &DatabaseDefinition,
+		cfg: &Config,
+		batch_size: u32,
+		chn: &Output<'_>,
+	) -> Result<()> {
 		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
-		self.export_metadata(&cfg, &chn, db.namespace_id, db.database_id).await?;
+		if cfg.schema {
+			self.export_metadata(cfg, chn, db.namespace_id, db.database_id).await?;
+		}
 		// Output TABLES
-		self.export_tables(&cfg, &chn, db.namespace_id, db.database_id, batch_size).await?;
+		self.export_tables(cfg, chn, db.namespace_id, db.database_id, batch_size).await?;
 		Ok(())
 	}
 
-	async fn export_metadata(
+	/// Writes a header describing the export, so that tooling can inspect a
+	/// dump without parsing it. The header is a comment holding a JSON object,
+	/// so it is ignored when the dump is imported.
+	async fn export_manifest(
 		&self,
 		cfg: &Config,
-		chn: &Sender<Vec<u8>>,
+		chn: &Output<'_>,
 		ns: NamespaceId,
 		db: DatabaseId,
 	) -> Result<()> {
-		// Output OPTIONS
-		self.export_section("OPTION", [OptionStatement::import()].into_iter(), chn).await?;
+		let mut contents = Vec::new();
+		if cfg.schema {
+			contents.push("schema");
+		}
+		if cfg.records && cfg.tables.is_any() {
+			contents.push("data");
+		}
+		let tables = self
+			.all_tb(ns, db, None)
+			.await?
+			.iter()
+			.filter(|tb| tb.session.is_none() && cfg.tables.includes(&tb.name))
+			.map(|tb| tb.name.to_string())
+			.collect::<Vec<_>>();
+		let manifest = serde_json::json!({
+			"version": crate::env::VERSION,
+			"contents": contents,
+			"tables": tables,
+		});
+		chn.send(bytes!("-- ------------------------------")).await?;
+		chn.send(bytes!("-- MANIFEST")).await?;
+		chn.send(bytes!("-- ------------------------------")).await?;
+		chn.send(bytes!("")).await?;
+		chn.send(bytes!(format!("-- {}", InlineCommentDisplay(manifest)))).await?;
+		chn.send(bytes!("")).await?;
+		Ok(())
+	}
 
+	async fn export_metadata(
+		&self,
+		cfg: &Config,
+		chn: &Output<'_>,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<()> {
 		// Output USERS
 		if cfg.users {
 			let users = self.all_db_users(ns, db, None).await?;
@@ -226,6 +492,12 @@ impl Transaction {
 			self.export_section("FUNCTIONS", functions.iter(), chn).await?;
 		}
 
+		// Output QUERIES, which are exported along with the functions
+		if cfg.functions {
+			let queries = self.all_db_queries(ns, db).await?;
+			self.export_section("QUERIES", queries.iter(), chn).await?;
+		}
+
 		// Output ANALYZERS
 		if cfg.analyzers {
 			let analyzers = self.all_db_analyzers(ns, db, None).await?;
@@ -274,7 +546,7 @@ impl Transaction {
 		&self,
 		title: &str,
 		items: impl ExactSizeIterator<Item = T>,
-		chn: &Sender<Vec<u8>>,
+		chn: &Output<'_>,
 	) -> Result<()>
 	where
 		T: ToSql,
@@ -299,7 +571,7 @@ impl Transaction {
 	async fn export_tables(
 		&self,
 		cfg: &Config,
-		chn: &Sender<Vec<u8>>,
+		chn: &Output<'_>,
 		ns: NamespaceId,
 		db: DatabaseId,
 		batch_size: u32,
@@ -319,16 +591,22 @@ impl Transaction {
 				}
 			}
 		}
-		// Loop over all of the tables in order
-		for table in tables.iter() {
-			// Check if this table is included in the export config
-			if !cfg.tables.includes(&table.name) {
-				continue;
+		// Define the tables before the views which are computed from them
+		// Session tables only exist for as long as their session
+		let tables = dependency_order(&tables)
+			.into_iter()
+			.filter(|table| table.session.is_none() && cfg.tables.includes(&table.name))
+			.collect::<Vec<_>>();
+		// Export the table definition structures first, so that every table
+		// is fully defined before any records are imported
+		if cfg.schema {
+			for table in tables.iter() {
+				self.export_table_structure(ns, db, table, chn).await?;
 			}
-			// Export the table definition structure first
-			self.export_table_structure(ns, db, table, chn).await?;
-			// Then export the table data if its desired
-			if cfg.records {
+		}
+		// Then export the table data if its desired
+		if cfg.records {
+			for table in tables.iter() {
 				self.export_table_data(ns, db, table, chn, batch_size).await?;
 			}
 		}
@@ -341,7 +619,7 @@ impl Transaction {
 		ns: NamespaceId,
 		db: DatabaseId,
 		table: &TableDefinition,
-		chn: &Sender<Vec<u8>>,
+		chn: &Output<'_>,
 	) -> Result<()> {
 		chn.send(bytes!("-- ------------------------------")).await?;
 		chn.send(bytes!(format!("-- TABLE: {}", InlineCommentDisplay(&table.name)))).await?;
@@ -371,6 +649,14 @@ impl Transaction {
 			chn.send(bytes!(format!("{};", event.to_sql()))).await?;
 		}
 		chn.send(bytes!("")).await?;
+		// Export all archival policy definitions for this table
+		let policies = self.all_tb_policies(ns, db, &table.name).await?;
+		if !policies.is_empty() {
+			for policy in policies.iter() {
+				chn.send(bytes!(format!("{};", policy.to_sql()))).await?;
+			}
+			chn.send(bytes!("")).await?;
+		}
 		// Everything ok
 		Ok(())
 	}
@@ -380,7 +666,7 @@ impl Transaction {
 		ns: NamespaceId,
 		db: DatabaseId,
 		table: &TableDefinition,
-		chn: &Sender<Vec<u8>>,
+		chn: &Output<'_>,
 		batch_size: u32,
 	) -> Result<()> {
 		chn.send(bytes!("-- ------------------------------")).await?;
@@ -399,6 +685,7 @@ impl Transaction {
 			if batch.result.is_empty() {
 				break;
 			}
+			chn.count(&table.name, batch.result.len() as u64);
 			self.export_regular_data(batch.result, chn).await?;
 		}
 
@@ -462,7 +749,7 @@ impl Transaction {
 	async fn export_regular_data(
 		&self,
 		regular_values: Vec<(Vec<u8>, Vec<u8>)>,
-		chn: &Sender<Vec<u8>>,
+		chn: &Output<'_>,
 	) -> Result<()> {
 		// Initialize strings to hold normal records and graph edge records.
 		// Write directly to strings to avoid unnecessary allocations.
@@ -497,3 +784,226 @@ impl Transaction {
 		Ok(())
 	}
 }
+
+/// Filters a namespace export down to the sections of the given databases.
+///
+/// The lines before the first database section, which start the import, are
+/// always kept. The export is split into lines as it is streamed, so chunks
+/// may end part way through a line. The stream fails once it ends if any of
+/// the databases was not found in the export.
+pub fn select_databases<S>(stream: S, databases: Vec<String>) -> impl Stream<Item = Result<Bytes>>
+where
+	S: Stream<Item = Result<Bytes>>,
+{
+	let mut filter = DatabaseFilter::new(databases);
+	let mut stream = Box::pin(stream);
+	let mut complete = false;
+	futures::stream::poll_fn(move |cx| {
+		loop {
+			if complete {
+				return Poll::Ready(None);
+			}
+			let lines = match ready!(stream.poll_next_unpin(cx)) {
+				Some(Ok(chunk)) => filter.push(&chunk),
+				Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+				None => {
+					complete = true;
+					match filter.finish() {
+						Ok(lines) => lines,
+						Err(e) => return Poll::Ready(Some(Err(e))),
+					}
+				}
+			};
+			if !lines.is_empty() {
+				return Poll::Ready(Some(Ok(Bytes::from(lines))));
+			}
+		}
+	})
+}
+
+/// Checks an export against its trailer before passing it on to be imported.
+///
+/// The whole export is read first, so that an export which was truncated or
+/// modified is rejected before any of its statements are applied. The stream
+/// fails if the export has no trailer, or if its length or checksum do not
+/// match the trailer.
+pub fn verify_checksum<S>(stream: S) -> impl Stream<Item = Result<Bytes>>
+where
+	S: Stream<Item = Result<Bytes>>,
+{
+	futures::stream::once(async move {
+		let mut stream = Box::pin(stream);
+		let mut export = Vec::new();
+		while let Some(chunk) = stream.next().await {
+			export.extend_from_slice(&chunk?);
+		}
+		verify_trailer(&export)?;
+		Ok(Bytes::from(export))
+	})
+}
+
+/// Checks an export against its trailer, which is its last line
+pub fn verify_trailer(export: &[u8]) -> Result<()> {
+	let invalid = |reason: &str| {
+		anyhow::Error::new(Error::InvalidStatement(format!(
+			"The export failed verification, as {reason}"
+		)))
+	};
+	let body = export.strip_suffix(b"\n").unwrap_or(export);
+	let start = body.iter().rposition(|b| *b == b'\n').map(|i| i + 1).unwrap_or(0);
+	let Some(trailer) = body[start..].strip_prefix(TRAILER_MARKER.as_bytes()) else {
+		return Err(invalid("it has no trailer"));
+	};
+	let trailer: serde_json::Value =
+		serde_json::from_slice(trailer).map_err(|_| invalid("its trailer is not valid"))?;
+	if trailer.get("bytes").and_then(|v| v.as_u64()) != Some(start as u64) {
+		return Err(invalid("its length does not match its trailer"));
+	}
+	let checksum = hex::encode(Sha256::digest(&export[..start]));
+	if trailer.get("checksum").and_then(|v| v.as_str()) != Some(checksum.as_str()) {
+		return Err(invalid("its checksum does not match its trailer"));
+	}
+	Ok(())
+}
+
+/// Keeps the lines of a namespace export which belong to a set of databases
+struct DatabaseFilter {
+	/// The databases to keep, and whether their section has been seen
+	databases: Vec<(String, bool)>,
+	/// The end of the export which has not yet been split into lines
+	buffer: Vec<u8>,
+	/// Whether the lines of the current section are kept
+	keep: bool,
+}
+
+impl DatabaseFilter {
+	fn new(databases: Vec<String>) -> Self {
+		Self {
+			databases: databases.into_iter().map(|db| (db, false)).collect(),
+			buffer: Vec::new(),
+			keep: true,
+		}
+	}
+
+	/// Returns the complete lines of the export so far which are kept
+	fn push(&mut self, chunk: &[u8]) -> Vec<u8> {
+		self.buffer.extend_from_slice(chunk);
+		let Some(end) = self.buffer.iter().rposition(|b| *b == b'\n') else {
+			return Vec::new();
+		};
+		let rest = self.buffer.split_off(end + 1);
+		let lines = std::mem::replace(&mut self.buffer, rest);
+		self.filter(&lines)
+	}
+
+	/// Returns the last line of the export if it is kept
+	fn finish(&mut self) -> Result<Vec<u8>> {
+		let lines = std::mem::take(&mut self.buffer);
+		let lines = self.filter(&lines);
+		if let Some((db, _)) = self.databases.iter().find(|(_, seen)| !seen) {
+			return Err(anyhow::Error::new(Error::DbNotFound {
+				name: db.clone(),
+			}));
+		}
+		Ok(lines)
+	}
+
+	fn filter(&mut self, lines: &[u8]) -> Vec<u8> {
+		let mut out = Vec::with_capacity(lines.len());
+		for line in lines.split_inclusive(|b| *b == b'\n') {
+			// A marker starts the section of another database
+			if let Some(name) = line.strip_prefix(DATABASE_MARKER.as_bytes()) {
+				let name = name.strip_suffix(b"\n").unwrap_or(name);
+				self.keep = false;
+				for (db, seen) in self.databases.iter_mut() {
+					if InlineCommentDisplay(db.as_str()).to_string().as_bytes() == name {
+						self.keep = true;
+						*seen = true;
+					}
+				}
+			}
+			if self.keep {
+				out.extend_from_slice(line);
+			}
+		}
+		out
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EXPORT: &str = "-- MANIFEST\nOPTION IMPORT;\n\
+		-- DATABASE: a\nUSE DB a;\nCREATE person:one;\n\
+		-- DATABASE: b\nUSE DB b;\nCREATE person:two;\n";
+
+	async fn select(chunk_size: usize, databases: &[&str]) -> Result<String> {
+		let chunks = EXPORT
+			.as_bytes()
+			.chunks(chunk_size)
+			.map(|c| Ok(Bytes::copy_from_slice(c)))
+			.collect::<Vec<_>>();
+		let databases = databases.iter().map(|db| db.to_string()).collect();
+		let mut stream = Box::pin(select_databases(futures::stream::iter(chunks), databases));
+		let mut out = Vec::new();
+		while let Some(chunk) = stream.next().await {
+			out.extend_from_slice(&chunk?);
+		}
+		Ok(String::from_utf8(out).unwrap())
+	}
+
+	#[tokio::test]
+	async fn select_databases_keeps_selected_sections() {
+		for chunk_size in [1, 7, EXPORT.len()] {
+			assert_eq!(
+				select(chunk_size, &["b"]).await.unwrap(),
+				"-- MANIFEST\nOPTION IMPORT;\n-- DATABASE: b\nUSE DB b;\nCREATE person:two;\n",
+				"{chunk_size}"
+			);
+			assert_eq!(select(chunk_size, &["a", "b"]).await.unwrap(), EXPORT, "{chunk_size}");
+		}
+	}
+
+	async fn export_with_trailer(lines: &[&str]) -> Vec<u8> {
+		let (snd, rcv) = async_channel::unbounded();
+		let out = Output::new(&snd);
+		out.count("person", 2);
+		for line in lines {
+			out.send(bytes!(line)).await.unwrap();
+		}
+		out.finish().await.unwrap();
+		drop(snd);
+		let mut export = Vec::new();
+		while let Ok(chunk) = rcv.recv().await {
+			export.extend_from_slice(&chunk);
+		}
+		export
+	}
+
+	#[tokio::test]
+	async fn trailer_verifies_export() {
+		let export =
+			export_with_trailer(&["OPTION IMPORT;", "INSERT [ { id: person:one } ];"]).await;
+		let trailer = String::from_utf8(export.clone()).unwrap();
+		assert!(trailer.contains(r#""records":{"person":2}"#), "{trailer}");
+		verify_trailer(&export).unwrap();
+		// A modified export is rejected
+		let mut modified = export.clone();
+		modified[0] = b'o';
+		assert!(verify_trailer(&modified).is_err());
+		// A truncated export is rejected
+		let lines = export.split_inclusive(|b| *b == b'\n').collect::<Vec<_>>();
+		assert!(verify_trailer(&lines[..lines.len() - 1].concat()).is_err());
+		assert!(verify_trailer(&[lines[0], lines[2]].concat()).is_err());
+	}
+
+	#[tokio::test]
+	async fn select_databases_requires_every_database() {
+		let err = select(EXPORT.len(), &["a", "c"]).await.unwrap_err();
+		assert!(
+			matches!(err.downcast_ref(), Some(Error::DbNotFound { name }) if name == "c"),
+			"{err}"
+		);
+	}
+}
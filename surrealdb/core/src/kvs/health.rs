@@ -0,0 +1,38 @@
+//! Datastore health reporting.
+
+use std::time::Duration;
+
+/// A report on the health of a datastore
+///
+/// Returned by [`Datastore::health_report`](crate::kvs::Datastore::health_report),
+/// which probes the storage engine by writing a value and reading it back, and
+/// checks how recently the background tasks of the node last ran.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HealthReport {
+	/// Whether the value written by the probe could be read back
+	pub readable: bool,
+	/// Whether the probe could write to the storage engine, or `None` if the
+	/// write was skipped because the datastore is locked for writes
+	pub writable: Option<bool>,
+	/// How long the storage probe took to complete
+	pub latency: Duration,
+	/// How long ago this node last refreshed its cluster heartbeat
+	pub heartbeat_age: Option<Duration>,
+	/// How long ago the live query router last completed a pass, when live
+	/// queries are delivered by the router
+	pub live_query_lag: Option<Duration>,
+	/// The number of records which the background scrubber has found not to
+	/// match their checksums, as returned by
+	/// [`Datastore::corrupt_records`](crate::kvs::Datastore::corrupt_records)
+	pub corrupt_records: u64,
+	/// The errors which were encountered while probing
+	pub errors: Vec<String>,
+}
+
+impl HealthReport {
+	/// Checks whether the storage engine could be read from and written to
+	pub fn is_healthy(&self) -> bool {
+		self.readable && self.writable != Some(false) && self.errors.is_empty()
+	}
+}
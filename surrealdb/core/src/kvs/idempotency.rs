@@ -0,0 +1,310 @@
+//! Idempotency keys for retry-safe mutations.
+//!
+//! A query run with [`Datastore::execute_idempotent`] is executed in a single
+//! transaction, and its results are recorded under the idempotency key in the
+//! same transaction, in the [`crate::key::database::ik`] keyspace of the
+//! current database. When a client retries the query with the same key, for
+//! instance after a network timeout, the recorded results are returned rather
+//! than running the query again. A key is only recorded when every statement
+//! succeeds, so a failed query can be retried.
+//!
+//! The key is reserved in the transaction before the query runs. When two
+//! runs of a query with the same key overlap, both reserve the key, so the
+//! transaction which commits last conflicts and none of its writes apply.
+//!
+//! A key can only be reused by the user who first used it, and only for the
+//! same query and variables. Keys expire once the idempotency key TTL of the
+//! datastore has elapsed, and expired keys are garbage collected alongside the
+//! changefeeds.
+
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use chrono::Utc;
+use revision::revisioned;
+use surrealdb_types::{SurrealValue, ToSql};
+
+use crate::catalog::providers::DatabaseProvider;
+use crate::catalog::{DatabaseDefinition, DatabaseId, NamespaceId};
+use crate::dbs::{QueryResult, Session};
+use crate::err::Error;
+use crate::key::database::ik;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::kvs::{Datastore, KVValue, NORMAL_BATCH_SIZE, Transaction, impl_kv_value_revisioned};
+use crate::sql::Ast;
+use crate::sql::expression::convert_public_value_to_internal;
+use crate::types::PublicVariables;
+use crate::val::{Value, convert_value_to_public_value};
+
+/// The maximum length of an idempotency key
+const MAX_KEY_LENGTH: usize = 256;
+
+/// The results of a query which was run with an idempotency key
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct IdempotencyRecord {
+	/// The user who ran the query
+	pub(crate) actor: String,
+	/// A hash of the query and its variables
+	pub(crate) fingerprint: String,
+	/// When the key expires, in seconds since the unix epoch
+	pub(crate) expires_at: i64,
+	/// The results of the query
+	pub(crate) results: Vec<Value>,
+}
+
+impl_kv_value_revisioned!(IdempotencyRecord);
+
+impl IdempotencyRecord {
+	fn is_expired(&self, now: i64) -> bool {
+		now >= self.expires_at
+	}
+}
+
+/// Checks that an idempotency key is usable
+pub(crate) fn check_key(key: &str) -> Result<()> {
+	if key.is_empty() {
+		bail!(Error::InvalidIdempotencyKey("the key can not be empty".to_owned()));
+	}
+	if key.len() > MAX_KEY_LENGTH {
+		bail!(Error::InvalidIdempotencyKey(format!(
+			"the key can not be longer than {MAX_KEY_LENGTH} bytes"
+		)));
+	}
+	Ok(())
+}
+
+/// Identifies the user who ran a query
+pub(crate) fn actor(sess: &Session) -> String {
+	format!("{}@{}", sess.au.id(), sess.au.level())
+}
+
+/// Hashes a query along with its variables
+pub(crate) fn fingerprint(txt: &str, vars: Option<&PublicVariables>) -> String {
+	let mut hasher = blake3::Hasher::new();
+	hasher.update(txt.as_bytes());
+	if let Some(vars) = vars {
+		for (key, value) in vars.iter() {
+			hasher.update(b"\0");
+			hasher.update(key.as_bytes());
+			hasher.update(b"=");
+			hasher.update(value.to_sql().as_bytes());
+		}
+	}
+	hasher.finalize().to_hex().to_string()
+}
+
+/// Fetches the results recorded under an idempotency key, if it has been used
+/// and has not expired.
+///
+/// Fails if the key was used by another user, or for a different query.
+pub(crate) async fn lookup(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	key: &str,
+	actor: &str,
+	fingerprint: &str,
+) -> Result<Option<Vec<QueryResult>>> {
+	let Some(record) = tx.get(&ik::new(ns, db, key), None).await? else {
+		return Ok(None);
+	};
+	if record.is_expired(Utc::now().timestamp()) {
+		return Ok(None);
+	}
+	if record.actor != actor || record.fingerprint != fingerprint {
+		bail!(Error::InvalidIdempotencyKey(
+			"the key has already been used for a different query".to_owned()
+		));
+	}
+	let results = record
+		.results
+		.into_iter()
+		.map(|v| {
+			let v = convert_value_to_public_value(v)?;
+			QueryResult::from_value(v).map_err(anyhow::Error::new)
+		})
+		.collect::<Result<Vec<_>>>()?;
+	Ok(Some(results))
+}
+
+/// Reserves an idempotency key which is unused, or has expired, before its
+/// query runs. Overlapping runs which reserve the same key conflict when the
+/// later of them commits.
+pub(crate) async fn reserve(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	key: &str,
+	record: &IdempotencyRecord,
+) -> Result<()> {
+	let key = ik::new(ns, db, key);
+	// An expired key is replaced as though it was unused
+	let expired = tx.get(&key, None).await?;
+	tx.putc(&key, record, expired.as_ref()).await
+}
+
+/// Records the results of a query under an idempotency key
+pub(crate) async fn record(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	key: &str,
+	record: IdempotencyRecord,
+) -> Result<()> {
+	tx.set(&ik::new(ns, db, key), &record).await
+}
+
+/// Converts the results of a query so that they can be recorded
+pub(crate) fn encode_results(results: &[QueryResult]) -> Vec<Value> {
+	results.iter().map(|r| convert_public_value_to_internal(r.clone().into_value())).collect()
+}
+
+/// Marks the successful results of a query as not executed, as its
+/// transaction was cancelled because another statement failed
+pub(crate) fn not_executed(results: Vec<QueryResult>) -> Vec<QueryResult> {
+	results
+		.into_iter()
+		.map(|mut r| {
+			if r.result.is_ok() {
+				r.result = Err(crate::err::into_types_error(Error::QueryNotExecuted {
+					message: "The query was run with an idempotency key".to_owned(),
+				}));
+			}
+			r
+		})
+		.collect()
+}
+
+/// Garbage-collects the expired idempotency keys of a database
+#[instrument(level = "trace", target = "surrealdb::core::kvs::idempotency", skip_all)]
+pub(crate) async fn gc_db(tx: &Transaction, db: &DatabaseDefinition) -> Result<()> {
+	let now = Utc::now().timestamp();
+	let beg = ik::prefix(db.namespace_id, db.database_id)?;
+	let end = ik::suffix(db.namespace_id, db.database_id)?;
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		next = res.next;
+		for (k, v) in res.result.iter() {
+			let record = IdempotencyRecord::kv_decode_value(v, ())?;
+			if record.is_expired(now) {
+				tx.clr(k).await?;
+			}
+		}
+		yield_now!();
+	}
+	Ok(())
+}
+
+impl Datastore {
+	/// Runs a parsed query with an idempotency key, see
+	/// [`Datastore::execute_idempotent`]
+	pub(crate) async fn process_idempotent(
+		&self,
+		ast: Ast,
+		txt: &str,
+		sess: &Session,
+		vars: Option<PublicVariables>,
+		key: &str,
+	) -> Result<Vec<QueryResult>> {
+		check_key(key)?;
+		let (Some(ns), Some(db)) = (sess.ns.as_deref(), sess.db.as_deref()) else {
+			bail!(Error::DbEmpty);
+		};
+		let mut entry = IdempotencyRecord {
+			actor: actor(sess),
+			fingerprint: fingerprint(txt, vars.as_ref()),
+			expires_at: Utc::now()
+				.timestamp()
+				.saturating_add(self.config.idempotency_key_ttl.as_secs() as i64),
+			results: Vec::new(),
+		};
+		let tx = Arc::new(self.transaction(Write, Optimistic).await?);
+		let mut reserved = false;
+		if let Some(db_def) = catch!(tx, tx.get_db_by_name(ns, db, None).await) {
+			let (ns, db) = (db_def.namespace_id, db_def.database_id);
+			// Return the recorded results of a previous run
+			let found = lookup(&tx, ns, db, key, &entry.actor, &entry.fingerprint);
+			if let Some(results) = catch!(tx, found.await) {
+				tx.cancel().await?;
+				return Ok(results);
+			}
+			// Reserve the key before running the query
+			catch!(tx, reserve(&tx, ns, db, key, &entry).await);
+			reserved = true;
+		}
+		// Run the query within the transaction
+		let results = match self.process_with_transaction(ast, sess, vars, Arc::clone(&tx)).await {
+			Ok(results) => results,
+			Err(e) => {
+				let _ = tx.cancel().await;
+				return Err(anyhow::Error::new(e));
+			}
+		};
+		// Failed queries are not recorded, so that they can be retried
+		if results.iter().any(|r| r.result.is_err()) {
+			tx.cancel().await?;
+			return Ok(not_executed(results));
+		}
+		// The database may have been created by the query, in which case the
+		// key is reserved as it is recorded
+		if let Some(db_def) = catch!(tx, tx.get_db_by_name(ns, db, None).await) {
+			let (ns, db) = (db_def.namespace_id, db_def.database_id);
+			entry.results = encode_results(&results);
+			if reserved {
+				catch!(tx, record(&tx, ns, db, key, entry).await);
+			} else {
+				catch!(tx, reserve(&tx, ns, db, key, &entry).await);
+			}
+		}
+		tx.commit().await?;
+		Ok(results)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn keys_are_checked() {
+		check_key("5b0e3f1e-6f5c-4a6e-9f3b-0c6d2f3a9e41").unwrap();
+		check_key("").unwrap_err();
+		check_key(&"a".repeat(MAX_KEY_LENGTH + 1)).unwrap_err();
+	}
+
+	#[test]
+	fn fingerprints_include_variables() {
+		let mut vars = PublicVariables::new();
+		let plain = fingerprint("CREATE person", None);
+		assert_eq!(plain, fingerprint("CREATE person", Some(&vars)));
+		vars.insert("name".to_owned(), "Tobie".to_owned().into_value());
+		assert_ne!(plain, fingerprint("CREATE person", Some(&vars)));
+		assert_ne!(plain, fingerprint("UPDATE person", None));
+	}
+
+	#[tokio::test]
+	async fn overlapping_reservations_conflict() -> Result<()> {
+		let ds = Datastore::new("memory").await?;
+		let (ns, db) = (NamespaceId(1), DatabaseId(2));
+		let entry = IdempotencyRecord {
+			actor: "root@root".to_owned(),
+			fingerprint: fingerprint("CREATE person", None),
+			expires_at: i64::MAX,
+			results: Vec::new(),
+		};
+		let a = ds.transaction(Write, Optimistic).await?;
+		let b = ds.transaction(Write, Optimistic).await?;
+		reserve(&a, ns, db, "key", &entry).await?;
+		reserve(&b, ns, db, "key", &entry).await?;
+		a.commit().await?;
+		b.commit().await.unwrap_err();
+		// A key which is in use can not be reserved again
+		let c = ds.transaction(Write, Optimistic).await?;
+		reserve(&c, ns, db, "key", &entry).await.unwrap_err();
+		c.cancel().await?;
+		Ok(())
+	}
+}
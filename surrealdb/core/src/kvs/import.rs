@@ -0,0 +1,300 @@
+//! Resumable imports.
+//!
+//! A resumable import groups the statements of an import into transactions of
+//! [`Config::chunk_size`] statements. When each transaction commits, a progress
+//! marker is written in the same transaction, recording how many statements
+//! have been applied along with a rolling hash of those statements. If the
+//! import is interrupted, running it again skips the statements which were
+//! already applied, after checking that they hash to the recorded value. The
+//! marker is removed once the import completes.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use anyhow::Result;
+use futures::Stream;
+use parking_lot::Mutex;
+use revision::revisioned;
+use sha2::{Digest, Sha256};
+use surrealdb_types::ToSql;
+
+use super::{Transaction, impl_kv_value_revisioned};
+use crate::err::Error;
+use crate::expr::TopLevelExpr;
+use crate::key::root::im::ImportProgressKey;
+
+/// The default number of statements applied in each transaction
+const DEFAULT_CHUNK_SIZE: usize = 1000;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+	/// Identifies the import within the namespace and database selected by
+	/// the session, so that an interrupted import can be resumed
+	pub id: String,
+	/// The number of statements applied in each transaction
+	pub chunk_size: usize,
+}
+
+impl Config {
+	pub fn new(id: impl Into<String>) -> Self {
+		Self {
+			id: id.into(),
+			chunk_size: DEFAULT_CHUNK_SIZE,
+		}
+	}
+}
+
+/// The progress marker of a resumable import
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ImportProgress {
+	/// The number of statements which have been applied
+	pub(crate) offset: u64,
+	/// The rolling hash of the applied statements
+	pub(crate) hash: Vec<u8>,
+}
+
+impl_kv_value_revisioned!(ImportProgress);
+
+/// Shares the progress of an import between the statement stream and the
+/// executor, which saves it when each chunk commits.
+pub(crate) struct ImportTracker {
+	ns: Option<String>,
+	db: Option<String>,
+	id: String,
+	pending: Mutex<ImportProgress>,
+}
+
+impl ImportTracker {
+	pub(crate) fn new(ns: Option<String>, db: Option<String>, id: String) -> Self {
+		Self {
+			ns,
+			db,
+			id,
+			pending: Mutex::default(),
+		}
+	}
+
+	fn key(&self) -> ImportProgressKey<'_> {
+		ImportProgressKey::new(self.ns.as_deref(), self.db.as_deref(), &self.id)
+	}
+
+	/// Loads the progress of a previous run of this import
+	pub(crate) async fn load(&self, txn: &Transaction) -> Result<Option<ImportProgress>> {
+		txn.get(&self.key(), None).await
+	}
+
+	/// Saves the progress of the statements emitted so far
+	pub(crate) async fn save(&self, txn: &Transaction) -> Result<()> {
+		let progress = self.pending.lock().clone();
+		txn.set(&self.key(), &progress).await
+	}
+
+	/// Removes the progress marker once the import has completed
+	pub(crate) async fn clear(&self, txn: &Transaction) -> Result<()> {
+		txn.del(&self.key()).await
+	}
+}
+
+/// Wraps the statements of an import, skipping statements which a previous
+/// run already applied, and surrounding the remaining statements with
+/// `BEGIN` and `COMMIT` in chunks.
+pub(crate) struct ChunkedImport<S> {
+	inner: Pin<Box<S>>,
+	tracker: Arc<ImportTracker>,
+	/// The progress recorded by a previous run
+	resume: ImportProgress,
+	chunk_size: usize,
+	hasher: Sha256,
+	/// The number of statements seen so far
+	offset: u64,
+	/// The number of statements in the currently open chunk
+	in_chunk: usize,
+	/// Whether the leading `OPTION IMPORT` statement has been seen
+	started: bool,
+	/// A statement to emit after the `BEGIN` of a new chunk
+	buffered: Option<TopLevelExpr>,
+	/// Whether the open chunk should be committed next
+	commit: bool,
+}
+
+impl<S> ChunkedImport<S>
+where
+	S: Stream<Item = Result<TopLevelExpr>>,
+{
+	pub(crate) fn new(
+		inner: S,
+		tracker: Arc<ImportTracker>,
+		resume: Option<ImportProgress>,
+		chunk_size: usize,
+	) -> Self {
+		Self {
+			inner: Box::pin(inner),
+			tracker,
+			resume: resume.unwrap_or_default(),
+			chunk_size: chunk_size.max(1),
+			hasher: Sha256::new(),
+			offset: 0,
+			in_chunk: 0,
+			started: false,
+			buffered: None,
+			commit: false,
+		}
+	}
+}
+
+impl<S> Stream for ChunkedImport<S>
+where
+	S: Stream<Item = Result<TopLevelExpr>>,
+{
+	type Item = Result<TopLevelExpr>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+		if let Some(stmt) = this.buffered.take() {
+			return Poll::Ready(Some(Ok(stmt)));
+		}
+		if this.commit {
+			this.commit = false;
+			return Poll::Ready(Some(Ok(TopLevelExpr::Commit)));
+		}
+		loop {
+			let stmt = match ready!(this.inner.as_mut().poll_next(cx)) {
+				Some(Ok(stmt)) => stmt,
+				Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+				None if this.offset < this.resume.offset => {
+					return Poll::Ready(Some(Err(anyhow::Error::new(Error::InvalidStatement(
+						"The import is shorter than the progress recorded by a previous run"
+							.to_string(),
+					)))));
+				}
+				None if this.in_chunk > 0 => {
+					this.in_chunk = 0;
+					return Poll::Ready(Some(Ok(TopLevelExpr::Commit)));
+				}
+				None => return Poll::Ready(None),
+			};
+			// The leading OPTION IMPORT is handled by the executor before the
+			// chunked statements
+			if !this.started {
+				this.started = true;
+				if let TopLevelExpr::Option(_) = stmt {
+					return Poll::Ready(Some(Ok(stmt)));
+				}
+			}
+			if let TopLevelExpr::Begin | TopLevelExpr::Cancel | TopLevelExpr::Commit = stmt {
+				return Poll::Ready(Some(Err(anyhow::Error::new(Error::InvalidStatement(
+					"A resumable import can not contain transaction statements".to_string(),
+				)))));
+			}
+			this.hasher.update(stmt.to_sql().as_bytes());
+			this.offset += 1;
+			// Skip statements which were applied by a previous run
+			if this.offset <= this.resume.offset {
+				if this.offset == this.resume.offset
+					&& this.hasher.clone().finalize().as_slice() != this.resume.hash
+				{
+					return Poll::Ready(Some(Err(anyhow::Error::new(Error::InvalidStatement(
+						"The import does not match the progress recorded by a previous run"
+							.to_string(),
+					)))));
+				}
+				// Statements which select the namespace or database, or set
+				// options, are replayed so that later statements apply to the
+				// same place
+				match stmt {
+					TopLevelExpr::Use(_) | TopLevelExpr::Option(_) => {
+						return Poll::Ready(Some(Ok(stmt)));
+					}
+					_ => continue,
+				}
+			}
+			*this.tracker.pending.lock() = ImportProgress {
+				offset: this.offset,
+				hash: this.hasher.clone().finalize().to_vec(),
+			};
+			let first = this.in_chunk == 0;
+			this.in_chunk += 1;
+			if this.in_chunk == this.chunk_size {
+				this.in_chunk = 0;
+				this.commit = true;
+			}
+			if first {
+				this.buffered = Some(stmt);
+				return Poll::Ready(Some(Ok(TopLevelExpr::Begin)));
+			}
+			return Poll::Ready(Some(Ok(stmt)));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::StreamExt;
+
+	use super::*;
+	use crate::expr::statements::{OptionStatement, UseStatement};
+	use crate::expr::{Expr, Literal};
+
+	fn stmt(n: i64) -> TopLevelExpr {
+		TopLevelExpr::Expr(Expr::Literal(Literal::Integer(n)))
+	}
+
+	fn import(n: i64) -> Vec<Result<TopLevelExpr>> {
+		let mut out = vec![Ok(TopLevelExpr::Option(OptionStatement {
+			name: "IMPORT".into(),
+			what: true,
+		}))];
+		out.push(Ok(TopLevelExpr::Use(UseStatement::Default)));
+		out.extend((0..n).map(|n| Ok(stmt(n))));
+		out
+	}
+
+	async fn collect(
+		input: Vec<Result<TopLevelExpr>>,
+		resume: Option<ImportProgress>,
+	) -> (Vec<TopLevelExpr>, ImportProgress) {
+		let tracker = Arc::new(ImportTracker::new("test".to_string()));
+		let stream =
+			ChunkedImport::new(futures::stream::iter(input), Arc::clone(&tracker), resume, 2);
+		let out = stream.map(|x| x.unwrap()).collect().await;
+		let progress = tracker.pending.lock().clone();
+		(out, progress)
+	}
+
+	#[tokio::test]
+	async fn chunks_statements() {
+		let (out, progress) = collect(import(3), None).await;
+		assert!(matches!(out[0], TopLevelExpr::Option(_)));
+		assert_eq!(out[1], TopLevelExpr::Begin);
+		assert!(matches!(out[2], TopLevelExpr::Use(_)));
+		assert_eq!(out[3], stmt(0));
+		assert_eq!(out[4], TopLevelExpr::Commit);
+		assert_eq!(out[5..], [TopLevelExpr::Begin, stmt(1), stmt(2), TopLevelExpr::Commit]);
+		assert_eq!(progress.offset, 4);
+	}
+
+	#[tokio::test]
+	async fn resumes_from_progress() {
+		// Record the progress after the first chunk
+		let (_, progress) = collect(import(1), None).await;
+		assert_eq!(progress.offset, 2);
+		let (out, _) = collect(import(3), Some(progress)).await;
+		assert!(matches!(out[0], TopLevelExpr::Option(_)));
+		// The USE statement is replayed outside of a chunk
+		assert!(matches!(out[1], TopLevelExpr::Use(_)));
+		assert_eq!(out[2..], [TopLevelExpr::Begin, stmt(1), stmt(2), TopLevelExpr::Commit]);
+	}
+
+	#[tokio::test]
+	async fn rejects_changed_imports() {
+		let (_, progress) = collect(import(1), None).await;
+		let tracker = Arc::new(ImportTracker::new("test".to_string()));
+		let mut input = import(3);
+		input[2] = Ok(stmt(42));
+		let stream = ChunkedImport::new(futures::stream::iter(input), tracker, Some(progress), 2);
+		let out: Vec<_> = stream.collect().await;
+		assert!(out.iter().any(|x| x.is_err()));
+	}
+}
@@ -17,7 +17,7 @@ use super::{
 	BuildTicketMutationSeq, ExistingPrimaryAppending, IndexBuildPhase, IndexBuildReportStatus,
 	LEGACY_BATCH_ID,
 };
-use crate::catalog::providers::NodeProvider;
+use crate::catalog::providers::{NodeProvider, TableProvider};
 use crate::catalog::{Index, Record};
 use crate::ctx::FrozenContext;
 use crate::doc::{CursorDoc, Document};
@@ -521,6 +521,11 @@ impl Building {
 				.await?;
 		let lookup_tx = self.new_read_tx().await?;
 		let result = async {
+			// Soft deleted records are left out of the index
+			let soft_delete = tx
+				.get_tb(self.ix_key.ns, self.ix_key.db, &self.ix.table_name, None)
+				.await?
+				.and_then(|tb| tb.soft_delete.clone());
 			// Index the records.
 			for (k, v) in values {
 				if self.is_aborted().await {
@@ -549,6 +554,10 @@ impl Building {
 				} else {
 					// Otherwise, proceed with normal indexing.
 					let doc = CursorDoc::new(Some(Arc::clone(&rid)), None, val);
+					if soft_delete.as_ref().is_some_and(|sd| sd.is_deleted(doc.doc.as_ref())) {
+						count += 1;
+						continue;
+					}
 					let opt_values = stack
 						.enter(|stk| {
 							Document::build_opt_values(stk, ctx, &self.opt, &self.ix, &doc)
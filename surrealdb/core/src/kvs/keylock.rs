@@ -0,0 +1,753 @@
+//! Key locks of client-managed transactions.
+//!
+//! A transaction started with `BEGIN` over an RPC connection stays open across
+//! requests, and locks the records it writes until it is committed or
+//! cancelled. Only record keys are locked: the other keys written along with a
+//! record, such as index entries and table statistics, are shared by the
+//! writes of many records, and are left to the conflict detection of the
+//! storage engine. Another client-managed transaction writing a locked record
+//! waits for the lock to be released. Two transactions which wait for each
+//! other, directly or through other transactions, can never proceed, so a wait
+//! which would close such a cycle fails immediately with
+//! [`Error::DeadlockDetected`]. Cycles spanning other kinds of waits can not be
+//! seen, so a wait otherwise fails with [`Error::LockWaitTimedout`] once it has
+//! lasted longer than the lock wait timeout.
+//!
+//! A transaction running `LOCK TABLE ... IN EXCLUSIVE MODE` locks the key
+//! range of a whole table in the same way, taking key locks from then on even
+//! when it is not client-managed. Transactions managed by the datastore itself
+//! never take key locks, but record the tables whose records they write, and
+//! wait before writing to a locked table. A table lock is only granted once
+//! the transactions which wrote to the table before it was requested have been
+//! committed or cancelled. The waits of every transaction are part of the same
+//! wait graph, so a cycle through these waits fails with
+//! [`Error::DeadlockDetected`] too, and are listed by `INFO FOR LOCKS`.
+//!
+//! The locks are held in the memory of the node running the transaction, so
+//! they only order the transactions of that node. Transactions on different
+//! nodes of a cluster are only ordered by the conflict detection of the
+//! storage engine, as they are without key locks.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use surrealdb_types::ToSql;
+use tokio::sync::Notify;
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::timeout;
+use uuid::Uuid;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::timeout;
+use web_time::Instant;
+
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::key::record::RecordKey;
+use crate::kvs::{KVKey, Key};
+use crate::val::{RecordId, Value};
+
+/// The key locks of the client-managed transactions of a datastore
+#[derive(Clone, Default)]
+pub(crate) struct KeyLocks {
+	inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+	state: Mutex<LockState>,
+	/// The number of transactions holding a table lock, so that writes can
+	/// skip the state while no table is locked
+	tables: AtomicUsize,
+}
+
+#[derive(Default)]
+struct LockState {
+	/// The single keys locked by transactions, ordered so that the keys
+	/// inside a key range can be found without visiting the others
+	keys: BTreeMap<Key, Uuid>,
+	/// The key ranges locked by transactions, of which there are few
+	ranges: Vec<(Range<Key>, Uuid)>,
+	/// The locks held by each transaction
+	held: HashMap<Uuid, Held>,
+	/// The lock which each waiting transaction is waiting for, each of which
+	/// waits for a single holder
+	waits: HashMap<Uuid, LockWait>,
+	/// The table key ranges locked by each transaction, which are also held
+	tables: HashMap<Uuid, Vec<Range<Key>>>,
+	/// The table key ranges written by each transaction which takes no key
+	/// locks, which a table lock waits for
+	writers: HashMap<Uuid, Vec<Range<Key>>>,
+}
+
+#[derive(Default)]
+struct Held {
+	/// The single keys locked by the transaction
+	keys: Vec<Key>,
+	/// Notified once the transaction releases its locks, waking only the
+	/// transactions waiting for it
+	released: Arc<Notify>,
+}
+
+struct LockWait {
+	/// The transaction holding the lock
+	holder: Uuid,
+	/// The key range which is waited for
+	range: Range<Key>,
+	/// When the wait started
+	since: DateTime<Utc>,
+}
+
+fn overlaps(a: &Range<Key>, b: &Range<Key>) -> bool {
+	a.start < b.end && b.start < a.end
+}
+
+/// Checks whether a key range holds the single key at its start
+fn is_single(range: &Range<Key>) -> bool {
+	range.end.len() == range.start.len() + 1
+		&& range.end.starts_with(&range.start)
+		&& range.end.last() == Some(&0x00)
+}
+
+impl LockState {
+	/// Returns another transaction holding a lock which overlaps a key range
+	fn holder_of(&self, owner: Uuid, range: &Range<Key>) -> Option<Uuid> {
+		if let Some((_, id)) = self.ranges.iter().find(|(r, id)| *id != owner && overlaps(r, range))
+		{
+			return Some(*id);
+		}
+		self.keys.range(range.clone()).find_map(|(_, id)| (*id != owner).then_some(*id))
+	}
+
+	/// Returns another transaction holding a table lock which overlaps a key
+	/// range
+	fn table_holder_of(&self, owner: Uuid, range: &Range<Key>) -> Option<Uuid> {
+		self.tables.iter().find_map(|(id, ranges)| {
+			let overlaps = ranges.iter().any(|r| overlaps(r, range));
+			(*id != owner && overlaps).then_some(*id)
+		})
+	}
+
+	/// Returns another transaction taking no key locks which has written to
+	/// a table overlapping a key range
+	fn writer_of(&self, owner: Uuid, range: &Range<Key>) -> Option<Uuid> {
+		self.writers.iter().find_map(|(id, ranges)| {
+			let overlaps = ranges.iter().any(|r| overlaps(r, range));
+			(*id != owner && overlaps).then_some(*id)
+		})
+	}
+
+	/// Locks a key range for a transaction, unless it already holds a lock
+	/// covering it
+	fn hold(&mut self, owner: Uuid, range: Range<Key>) {
+		let covered = self
+			.ranges
+			.iter()
+			.any(|(r, id)| *id == owner && r.start <= range.start && range.end <= r.end);
+		if covered {
+			return;
+		}
+		if is_single(&range) {
+			if self.keys.insert(range.start.clone(), owner).is_none() {
+				self.held.entry(owner).or_default().keys.push(range.start);
+			}
+		} else {
+			self.held.entry(owner).or_default();
+			self.ranges.push((range, owner));
+		}
+	}
+
+	/// Returns the notification of the release of the locks of a transaction
+	fn released(&mut self, holder: Uuid) -> Arc<Notify> {
+		Arc::clone(&self.held.entry(holder).or_default().released)
+	}
+
+	/// Checks whether a transaction waits, directly or through the holders
+	/// it waits for, for another transaction
+	fn waits_for(&self, mut waiter: Uuid, holder: Uuid) -> bool {
+		// Every transaction waits for one lock at most, so the chain of waits
+		// can not be longer than the number of waits
+		for _ in 0..=self.waits.len() {
+			if waiter == holder {
+				return true;
+			}
+			match self.waits.get(&waiter) {
+				Some(wait) => waiter = wait.holder,
+				None => return false,
+			}
+		}
+		false
+	}
+}
+
+impl KeyLocks {
+	/// Waits for at most until `deadline` while `holder_of` returns another
+	/// transaction which a transaction has to wait for, then runs `attempt`
+	/// with the state still locked. The wait is listed while it lasts, and
+	/// fails when it would close a cycle of waits.
+	async fn wait<T>(
+		&self,
+		owner: Uuid,
+		range: &Range<Key>,
+		deadline: Instant,
+		max_wait: Duration,
+		holder_of: impl Fn(&LockState) -> Option<Uuid>,
+		attempt: impl FnOnce(&mut LockState) -> T,
+	) -> Result<T> {
+		loop {
+			let (holder, notified) = {
+				let mut state = self.inner.state.lock();
+				let Some(holder) = holder_of(&state) else {
+					state.waits.remove(&owner);
+					return Ok(attempt(&mut state));
+				};
+				// Waiting for the holder would close a cycle of waits
+				if state.waits_for(holder, owner) {
+					state.waits.remove(&owner);
+					return Err(deadlock(range, holder));
+				}
+				let since = match state.waits.get(&owner) {
+					Some(wait) => wait.since,
+					None => Utc::now(),
+				};
+				state.waits.insert(
+					owner,
+					LockWait {
+						holder,
+						range: range.clone(),
+						since,
+					},
+				);
+				// Register for the release before the state is unlocked, so
+				// that a release in between is not missed
+				let mut notified = Box::pin(state.released(holder).notified_owned());
+				notified.as_mut().enable();
+				(holder, notified)
+			};
+			let remaining = deadline.saturating_duration_since(Instant::now());
+			if timeout(remaining, notified).await.is_err() {
+				self.inner.state.lock().waits.remove(&owner);
+				return Err(timed_out(range, holder, max_wait));
+			}
+		}
+	}
+
+	/// Locks a key range for a transaction, waiting for at most `max_wait`
+	/// while another transaction holds an overlapping lock
+	pub(crate) async fn acquire(
+		&self,
+		owner: Uuid,
+		range: Range<Key>,
+		max_wait: Duration,
+	) -> Result<()> {
+		let deadline = Instant::now() + max_wait;
+		self.acquire_until(owner, range, deadline, max_wait).await
+	}
+
+	async fn acquire_until(
+		&self,
+		owner: Uuid,
+		range: Range<Key>,
+		deadline: Instant,
+		max_wait: Duration,
+	) -> Result<()> {
+		self.wait(
+			owner,
+			&range,
+			deadline,
+			max_wait,
+			|state| state.holder_of(owner, &range),
+			|state| state.hold(owner, range.clone()),
+		)
+		.await
+	}
+
+	/// Locks the key range of a table for a transaction, as with
+	/// [`KeyLocks::acquire`], so that the transactions which take no locks
+	/// also wait before writing to it. The lock is then only granted once the
+	/// transactions taking no locks which wrote to the table have been
+	/// committed or cancelled, all within `max_wait`.
+	pub(crate) async fn acquire_table(
+		&self,
+		owner: Uuid,
+		range: Range<Key>,
+		max_wait: Duration,
+	) -> Result<()> {
+		let deadline = Instant::now() + max_wait;
+		let held = {
+			let state = self.inner.state.lock();
+			if state.tables.get(&owner).is_some_and(|t| t.contains(&range)) {
+				return Ok(());
+			}
+			state.ranges.iter().any(|(r, id)| *id == owner && *r == range)
+		};
+		self.acquire_until(owner, range.clone(), deadline, max_wait).await?;
+		// Hold back the writes of the transactions which take no locks
+		{
+			let mut state = self.inner.state.lock();
+			state.tables.entry(owner).or_default().push(range.clone());
+			self.inner.tables.store(state.tables.len(), Ordering::Release);
+		}
+		// Wait for the writes which were made before the lock was taken
+		let drained = self
+			.wait(owner, &range, deadline, max_wait, |state| state.writer_of(owner, &range), |_| ())
+			.await;
+		if drained.is_err() {
+			// The table is not locked after all
+			let mut state = self.inner.state.lock();
+			if let Some(tables) = state.tables.get_mut(&owner) {
+				tables.retain(|r| *r != range);
+				if tables.is_empty() {
+					state.tables.remove(&owner);
+				}
+			}
+			if !held {
+				state.ranges.retain(|(r, id)| !(*id == owner && *r == range));
+			}
+			self.inner.tables.store(state.tables.len(), Ordering::Release);
+			// Wake the transactions held back by the lock
+			state.released(owner).notify_waiters();
+		}
+		drained
+	}
+
+	/// Records that a transaction taking no key locks writes to the key range
+	/// of a table, waiting for at most `max_wait` while another transaction
+	/// holds a table lock overlapping it
+	pub(crate) async fn write_table(
+		&self,
+		owner: Uuid,
+		range: Range<Key>,
+		max_wait: Duration,
+	) -> Result<()> {
+		let deadline = Instant::now() + max_wait;
+		self.wait(
+			owner,
+			&range,
+			deadline,
+			max_wait,
+			|state| state.table_holder_of(owner, &range),
+			|state| {
+				// A table lock waiting for the write is notified on release
+				state.held.entry(owner).or_default();
+				let writes = state.writers.entry(owner).or_default();
+				if !writes.contains(&range) {
+					writes.push(range.clone());
+				}
+			},
+		)
+		.await
+	}
+
+	/// Waits for at most `max_wait` while another transaction holds a table
+	/// lock overlapping a key range, without locking the key range
+	pub(crate) async fn wait_for_tables(
+		&self,
+		owner: Uuid,
+		range: &Range<Key>,
+		max_wait: Duration,
+	) -> Result<()> {
+		// Most of the time no table is locked
+		if self.inner.tables.load(Ordering::Acquire) == 0 {
+			return Ok(());
+		}
+		let deadline = Instant::now() + max_wait;
+		self.wait(
+			owner,
+			range,
+			deadline,
+			max_wait,
+			|state| state.table_holder_of(owner, range),
+			|_| (),
+		)
+		.await
+	}
+
+	/// Releases every lock held by a transaction
+	pub(crate) fn release(&self, owner: Uuid) {
+		let mut state = self.inner.state.lock();
+		state.waits.remove(&owner);
+		state.writers.remove(&owner);
+		let Some(held) = state.held.remove(&owner) else {
+			return;
+		};
+		for key in held.keys.iter() {
+			state.keys.remove(key);
+		}
+		state.ranges.retain(|(_, id)| *id != owner);
+		if state.tables.remove(&owner).is_some() {
+			self.inner.tables.store(state.tables.len(), Ordering::Release);
+		}
+		drop(state);
+		held.released.notify_waiters();
+	}
+
+	/// Describes the transactions which are currently waiting for a lock
+	pub(crate) fn waits(&self) -> Value {
+		let state = self.inner.state.lock();
+		let mut waits: Vec<_> = state.waits.iter().collect();
+		waits.sort_by_key(|(_, wait)| wait.since);
+		waits
+			.into_iter()
+			.map(|(waiter, wait)| {
+				Value::from(map! {
+					"transaction" => Value::from(*waiter),
+					"holder" => Value::from(wait.holder),
+					"resource" => Value::from(describe(&wait.range)),
+					"since" => Value::from(wait.since),
+				})
+			})
+			.collect::<Vec<_>>()
+			.into()
+	}
+}
+
+/// The locks held by a client-managed transaction, which are released when
+/// this is dropped
+pub(crate) struct KeyLockOwner {
+	locks: KeyLocks,
+	id: Uuid,
+	max_wait: Duration,
+}
+
+impl KeyLockOwner {
+	pub(crate) fn new(locks: KeyLocks, id: Uuid, max_wait: Duration) -> Self {
+		Self {
+			locks,
+			id,
+			max_wait,
+		}
+	}
+
+	/// Locks a key about to be written if it holds a record, or otherwise
+	/// waits while its table is locked by another transaction
+	pub(crate) async fn lock_key(&self, key: &[u8]) -> Result<()> {
+		let range = single(key);
+		if record_of(key).is_some() {
+			self.locks.acquire(self.id, range, self.max_wait).await
+		} else {
+			self.locks.wait_for_tables(self.id, &range, self.max_wait).await
+		}
+	}
+
+	/// Locks a range of keys
+	pub(crate) async fn lock_range(&self, range: Range<Key>) -> Result<()> {
+		self.locks.acquire(self.id, range, self.max_wait).await
+	}
+
+	/// Locks the key range of a table, waiting for at most `max_wait` when
+	/// given instead of the lock wait timeout
+	pub(crate) async fn lock_table(
+		&self,
+		range: Range<Key>,
+		max_wait: Option<Duration>,
+	) -> Result<()> {
+		let max_wait = max_wait.unwrap_or(self.max_wait);
+		self.locks.acquire_table(self.id, range, max_wait).await
+	}
+}
+
+impl Drop for KeyLockOwner {
+	fn drop(&mut self) {
+		self.locks.release(self.id);
+	}
+}
+
+/// The tables written by a transaction which takes no key locks, which a
+/// table lock waits for until [`KeyLockWriter::finish`] is called or this is
+/// dropped
+pub(crate) struct KeyLockWriter {
+	locks: KeyLocks,
+	id: Uuid,
+	max_wait: Duration,
+	/// The table key ranges already recorded, so that the shared state is
+	/// only locked for the first write to each table
+	tables: Mutex<Vec<Range<Key>>>,
+}
+
+impl KeyLockWriter {
+	pub(crate) fn new(locks: KeyLocks, id: Uuid, max_wait: Duration) -> Self {
+		Self {
+			locks,
+			id,
+			max_wait,
+			tables: Mutex::new(Vec::new()),
+		}
+	}
+
+	/// The id under which the transaction waits and writes
+	pub(crate) fn id(&self) -> Uuid {
+		self.id
+	}
+
+	/// Records a write to the key range of a table, waiting while the table
+	/// is locked by another transaction
+	pub(crate) async fn write_table(&self, range: Range<Key>) -> Result<()> {
+		if self.tables.lock().contains(&range) {
+			return Ok(());
+		}
+		self.locks.write_table(self.id, range.clone(), self.max_wait).await?;
+		self.tables.lock().push(range);
+		Ok(())
+	}
+
+	/// Waits while a table holding a key range is locked by another
+	/// transaction, without recording a write
+	pub(crate) async fn wait_for_tables(&self, range: &Range<Key>) -> Result<()> {
+		self.locks.wait_for_tables(self.id, range, self.max_wait).await
+	}
+
+	/// Lets the table locks waiting for the writes of the transaction go
+	pub(crate) fn finish(&self) {
+		if !std::mem::take(&mut *self.tables.lock()).is_empty() {
+			self.locks.release(self.id);
+		}
+	}
+}
+
+impl Drop for KeyLockWriter {
+	fn drop(&mut self) {
+		self.finish();
+	}
+}
+
+/// The key range holding a single key
+pub(crate) fn single(key: &[u8]) -> Range<Key> {
+	let mut end = key.to_vec();
+	end.push(0x00);
+	key.to_vec()..end
+}
+
+/// The key range holding every key which starts with a prefix
+pub(crate) fn prefix_range(prefix: &[u8]) -> Range<Key> {
+	let mut end = prefix.to_vec();
+	// Increment the last byte which can be incremented, so that keys whose
+	// next byte is 0xff are included
+	while let Some(last) = end.pop() {
+		if last < 0xff {
+			end.push(last + 1);
+			return prefix.to_vec()..end;
+		}
+	}
+	// A prefix of only 0xff bytes has no successor, but no key starts with it
+	let mut end = prefix.to_vec();
+	end.resize(prefix.len() + 1, 0xff);
+	prefix.to_vec()..end
+}
+
+fn deadlock(range: &Range<Key>, holder: Uuid) -> anyhow::Error {
+	anyhow::Error::new(Error::DeadlockDetected {
+		resource: describe(range),
+		holder,
+	})
+}
+
+fn timed_out(range: &Range<Key>, holder: Uuid, timeout: Duration) -> anyhow::Error {
+	anyhow::Error::new(Error::LockWaitTimedout {
+		resource: describe(range),
+		holder,
+		timeout: timeout.into(),
+	})
+}
+
+/// Returns the record held by a key, if it is the key of a record
+fn record_of(key: &[u8]) -> Option<RecordId> {
+	let record = RecordKey::decode_key(key).ok()?;
+	// Other keys of a table can be decoded as a record key too, so the key
+	// is encoded again with the markers of a record key
+	let tb = record.tb.into_owned();
+	let encoded = crate::key::record::new(record.ns, record.db, &tb, &record.id).encode_key();
+	if !encoded.is_ok_and(|encoded| encoded == key) {
+		return None;
+	}
+	Some(RecordId {
+		table: tb,
+		key: record.id,
+	})
+}
+
+/// Describes a locked key range, naming the record it holds when possible
+fn describe(range: &Range<Key>) -> String {
+	if !is_single(range) {
+		return format!("keys {}", range.sprint());
+	}
+	let key = range.start.as_slice();
+	match record_of(key) {
+		Some(id) => format!("record {}", id.to_sql()),
+		None => format!("key {}", key.sprint()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const WAIT: Duration = Duration::from_secs(5);
+
+	fn key(k: &[u8]) -> Range<Key> {
+		single(k)
+	}
+
+	#[tokio::test]
+	async fn waits_until_the_lock_is_released() {
+		let locks = KeyLocks::default();
+		let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+		locks.acquire(a, key(b"k"), WAIT).await.unwrap();
+		// The holder can lock its keys again
+		locks.acquire(a, key(b"k"), WAIT).await.unwrap();
+		let waiter = {
+			let locks = locks.clone();
+			tokio::spawn(async move { locks.acquire(b, key(b"k"), WAIT).await })
+		};
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		assert!(!waiter.is_finished());
+		locks.release(a);
+		waiter.await.unwrap().unwrap();
+	}
+
+	#[tokio::test]
+	async fn detects_a_cycle_of_waits() {
+		let locks = KeyLocks::default();
+		let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+		locks.acquire(a, key(b"x"), WAIT).await.unwrap();
+		locks.acquire(b, key(b"y"), WAIT).await.unwrap();
+		let waiter = {
+			let locks = locks.clone();
+			tokio::spawn(async move { locks.acquire(a, key(b"y"), WAIT).await })
+		};
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		// The waits of the datastore are listed
+		let Value::Array(waits) = locks.waits() else {
+			panic!("expected an array of waits");
+		};
+		assert_eq!(waits.len(), 1);
+		// Waiting for `x` would make both transactions wait for each other
+		let err = locks.acquire(b, key(b"x"), WAIT).await.unwrap_err();
+		assert!(matches!(
+			err.downcast_ref::<Error>(),
+			Some(Error::DeadlockDetected { holder, .. }) if *holder == a
+		));
+		// Cancelling the failed transaction lets the other one proceed
+		locks.release(b);
+		waiter.await.unwrap().unwrap();
+	}
+
+	#[tokio::test]
+	async fn times_out_waiting_for_a_lock() {
+		let locks = KeyLocks::default();
+		let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+		locks.acquire(a, b"a".to_vec()..b"c".to_vec(), WAIT).await.unwrap();
+		let err = locks.acquire(b, key(b"b"), Duration::from_millis(20)).await.unwrap_err();
+		assert!(matches!(
+			err.downcast_ref::<Error>(),
+			Some(Error::LockWaitTimedout { holder, .. }) if *holder == a
+		));
+		// The wait is no longer listed once it has failed
+		assert_eq!(locks.waits(), Value::from(Vec::<Value>::new()));
+	}
+
+	#[tokio::test]
+	async fn table_locks_hold_back_unlocked_writes() {
+		let locks = KeyLocks::default();
+		let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+		// Writes proceed while no table is locked
+		locks.wait_for_tables(b, &key(b"t1"), WAIT).await.unwrap();
+		locks.acquire_table(a, prefix_range(b"t"), WAIT).await.unwrap();
+		// Writes to other tables are not held back
+		locks.wait_for_tables(b, &key(b"u1"), WAIT).await.unwrap();
+		// The holder of the table lock is not held back by its own lock
+		locks.wait_for_tables(a, &key(b"t1"), WAIT).await.unwrap();
+		let err =
+			locks.wait_for_tables(b, &key(b"t1"), Duration::from_millis(20)).await.unwrap_err();
+		assert!(matches!(
+			err.downcast_ref::<Error>(),
+			Some(Error::LockWaitTimedout { holder, .. }) if *holder == a
+		));
+		let writer = {
+			let locks = locks.clone();
+			tokio::spawn(async move { locks.write_table(b, prefix_range(b"t"), WAIT).await })
+		};
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		assert!(!writer.is_finished());
+		// Waits for table locks are listed too
+		let Value::Array(waits) = locks.waits() else {
+			panic!("expected an array of waits");
+		};
+		assert_eq!(waits.len(), 1);
+		locks.release(a);
+		writer.await.unwrap().unwrap();
+	}
+
+	#[tokio::test]
+	async fn table_locks_wait_for_writes_in_flight() {
+		let locks = KeyLocks::default();
+		let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+		locks.write_table(b, prefix_range(b"t"), WAIT).await.unwrap();
+		let err = locks
+			.acquire_table(a, prefix_range(b"t"), Duration::from_millis(20))
+			.await
+			.unwrap_err();
+		assert!(matches!(
+			err.downcast_ref::<Error>(),
+			Some(Error::LockWaitTimedout { holder, .. }) if *holder == b
+		));
+		// The table is not left locked once the lock has failed
+		locks.wait_for_tables(b, &key(b"t1"), WAIT).await.unwrap();
+		let table = {
+			let locks = locks.clone();
+			tokio::spawn(async move { locks.acquire_table(a, prefix_range(b"t"), WAIT).await })
+		};
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		assert!(!table.is_finished());
+		// The writer waiting for the table it already wrote to closes a cycle
+		let err = locks.wait_for_tables(b, &key(b"t1"), WAIT).await.unwrap_err();
+		assert!(matches!(
+			err.downcast_ref::<Error>(),
+			Some(Error::DeadlockDetected { holder, .. }) if *holder == a
+		));
+		locks.release(b);
+		table.await.unwrap().unwrap();
+	}
+
+	#[tokio::test]
+	async fn ranges_hold_back_the_keys_inside_them() {
+		let locks = KeyLocks::default();
+		let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+		locks.acquire(a, key(b"t\xff1"), WAIT).await.unwrap();
+		let err =
+			locks.acquire(b, prefix_range(b"t"), Duration::from_millis(20)).await.unwrap_err();
+		assert!(matches!(err.downcast_ref::<Error>(), Some(Error::LockWaitTimedout { .. })));
+		locks.release(a);
+		locks.acquire(b, prefix_range(b"t"), WAIT).await.unwrap();
+		let err = locks.acquire(a, key(b"t\xff1"), Duration::from_millis(20)).await.unwrap_err();
+		assert!(matches!(err.downcast_ref::<Error>(), Some(Error::LockWaitTimedout { .. })));
+	}
+
+	#[test]
+	fn prefix_ranges_hold_every_key_with_the_prefix() {
+		let rng = prefix_range(b"ab");
+		assert!(rng.contains(&b"ab".to_vec()));
+		assert!(rng.contains(&b"ab\xff\xff".to_vec()));
+		assert!(!rng.contains(&b"ac".to_vec()));
+		assert_eq!(prefix_range(b"a\xff"), b"a\xff".to_vec()..b"b".to_vec());
+	}
+
+	#[test]
+	fn only_record_keys_name_records() {
+		use crate::catalog::{DatabaseId, NamespaceId};
+		use crate::val::{RecordIdKey, TableName};
+		let tb = TableName::from("thing");
+		let (ns, db) = (NamespaceId(1), DatabaseId(2));
+		let rec =
+			crate::key::record::new(ns, db, &tb, &RecordIdKey::Number(1)).encode_key().unwrap();
+		assert!(record_of(&rec).is_some());
+		let ck =
+			crate::key::table::ck::new(ns, db, &tb, &RecordIdKey::Number(1)).encode_key().unwrap();
+		assert!(record_of(&ck).is_none());
+	}
+}
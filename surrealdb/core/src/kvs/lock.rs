@@ -0,0 +1,139 @@
+//! Datastore-wide write locks.
+//!
+//! Every write transaction holds a shared permit on the datastore's
+//! [`WriteGate`] for as long as it is open. [`Datastore::lock_writes`] takes
+//! the gate exclusively, which waits for open write transactions to finish and
+//! then holds back new ones until the returned [`WriteLock`] is released. Read
+//! transactions never touch the gate, so they continue as normal.
+//!
+//! [`Datastore::lock_writes`]: crate::kvs::Datastore::lock_writes
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use parking_lot::Mutex;
+#[cfg(not(target_family = "wasm"))]
+use tokio::spawn;
+use tokio::sync::{OwnedRwLockReadGuard, OwnedRwLockWriteGuard, RwLock};
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::{sleep, timeout};
+use tracing::warn;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_futures::spawn_local as spawn;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::{sleep, timeout};
+
+use crate::err::Error;
+
+/// Admits write transactions, unless the datastore is locked for writes
+#[derive(Clone, Default)]
+pub(crate) struct WriteGate {
+	lock: Arc<RwLock<()>>,
+}
+
+/// Held by an open write transaction, preventing the datastore from being
+/// locked for writes until the transaction is committed or cancelled
+pub(crate) struct WritePermit(#[allow(dead_code)] OwnedRwLockReadGuard<()>);
+
+impl WriteGate {
+	/// Waits until write transactions are allowed, and returns a permit
+	pub(crate) async fn enter(&self) -> WritePermit {
+		WritePermit(Arc::clone(&self.lock).read_owned().await)
+	}
+
+	/// Checks whether the datastore is locked for writes, or is being locked
+	pub(crate) fn is_locked(&self) -> bool {
+		self.lock.try_read().is_err()
+	}
+
+	/// Locks the datastore for writes
+	///
+	/// Waits at most `wait` for the open write transactions to finish. The
+	/// lock is released when the returned [`WriteLock`] is dropped, or
+	/// forcibly once it has been held for `max_hold`.
+	pub(crate) async fn lock(&self, wait: Duration, max_hold: Duration) -> Result<WriteLock> {
+		let guard = timeout(wait, Arc::clone(&self.lock).write_owned())
+			.await
+			.map_err(|_| Error::WriteLockTimedout(wait.into()))?;
+		let guard = Arc::new(Mutex::new(Some(guard)));
+		// Release the lock if the holder fails to do so in time
+		let expiry = Arc::downgrade(&guard);
+		spawn(async move {
+			sleep(max_hold).await;
+			if let Some(guard) = expiry.upgrade()
+				&& guard.lock().take().is_some()
+			{
+				warn!("A datastore write lock was forcibly released after {max_hold:?}");
+			}
+		});
+		Ok(WriteLock {
+			guard,
+			max_hold,
+		})
+	}
+}
+
+/// A lock preventing new write transactions on a datastore
+///
+/// Returned by [`Datastore::lock_writes`](crate::kvs::Datastore::lock_writes).
+/// While the lock is held, new write transactions wait until it is released,
+/// and read transactions are unaffected. The lock is released when this value
+/// is dropped, or forcibly once its maximum hold duration has passed.
+pub struct WriteLock {
+	guard: Arc<Mutex<Option<OwnedRwLockWriteGuard<()>>>>,
+	max_hold: Duration,
+}
+
+impl WriteLock {
+	/// Releases the lock, allowing write transactions to continue
+	pub fn release(self) {
+		drop(self)
+	}
+
+	/// Checks whether the lock is still held
+	///
+	/// Returns `false` once the lock has been forcibly released.
+	pub fn is_held(&self) -> bool {
+		self.guard.lock().is_some()
+	}
+
+	/// The maximum duration for which the lock is held
+	pub fn max_hold(&self) -> Duration {
+		self.max_hold
+	}
+}
+
+impl Drop for WriteLock {
+	fn drop(&mut self) {
+		self.guard.lock().take();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn waits_for_open_writes() {
+		let gate = WriteGate::default();
+		let permit = gate.enter().await;
+		let wait = Duration::from_millis(20);
+		assert!(gate.lock(wait, Duration::from_secs(10)).await.is_err());
+		drop(permit);
+		let lock = gate.lock(wait, Duration::from_secs(10)).await.unwrap();
+		assert!(lock.is_held());
+		// New writes are held back until the lock is released
+		assert!(timeout(wait, gate.enter()).await.is_err());
+		lock.release();
+		timeout(wait, gate.enter()).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn releases_after_max_hold() {
+		let gate = WriteGate::default();
+		let lock = gate.lock(Duration::from_millis(20), Duration::from_millis(20)).await.unwrap();
+		timeout(Duration::from_secs(5), gate.enter()).await.unwrap();
+		assert!(!lock.is_held());
+	}
+}
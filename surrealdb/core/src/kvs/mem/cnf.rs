@@ -1,4 +1,5 @@
 use crate::cnf::Config;
+use crate::expr::Bytesize;
 use crate::kvs::config::{AolMode, SnapshotMode, SyncMode, parse_duration};
 
 /// Configuration for the in-memory storage engine, parsed from query parameters.
@@ -17,6 +18,10 @@ pub struct MemoryConfig {
 	pub aol_mode: AolMode,
 	/// Snapshot interval. Requires `persist_path`.
 	pub snapshot_mode: SnapshotMode,
+	/// The maximum size of the records of tables defined with `EVICT LRU`, in
+	/// bytes. When exceeded, their least recently used records are evicted,
+	/// and writes to them are rejected until they are back within the limit.
+	pub max_memory: Option<u64>,
 }
 
 impl Default for MemoryConfig {
@@ -28,6 +33,7 @@ impl Default for MemoryConfig {
 			sync_mode: SyncMode::Never,
 			aol_mode: AolMode::Never,
 			snapshot_mode: SnapshotMode::Never,
+			max_memory: None,
 		}
 	}
 }
@@ -47,7 +53,10 @@ impl Config for MemoryConfig {
 			parse_duration(x).map(|x| x.as_nanos() as u64).ok()
 		})
 		.parse_key("datastore_aol", &mut self.aol_mode)
-		.parse_key("datastore_snapshot", &mut self.snapshot_mode);
+		.parse_key("datastore_snapshot", &mut self.snapshot_mode)
+		.parse_key_with("datastore_max_memory", &mut self.max_memory, |x| {
+			Bytesize::parse(x).ok().map(|x| Some(x.0))
+		});
 
 		if map.has_key("datastore_sync") {
 			map.parse_key("datastore_sync", &mut self.sync_mode);
@@ -75,6 +84,7 @@ mod test {
 		assert_eq!(config.aol_mode, AolMode::Never);
 		assert_eq!(config.snapshot_mode, SnapshotMode::Never);
 		assert_eq!(config.sync_mode, SyncMode::Never);
+		assert!(config.max_memory.is_none());
 	}
 
 	#[test]
@@ -90,4 +100,12 @@ mod test {
 		assert_eq!(config.snapshot_mode, SnapshotMode::Interval(Duration::from_secs(60)));
 		assert_eq!(config.sync_mode, SyncMode::Interval(Duration::from_secs(5)));
 	}
+
+	#[test]
+	fn test_memory_config_with_max_memory() {
+		let map = ConfigMap::from_config_string("max_memory=256mb")
+			.map_keys(|x| format!("datastore_{x}"));
+		let config = map.load::<MemoryConfig>();
+		assert_eq!(config.max_memory, Some(256 * 1024 * 1024));
+	}
 }
@@ -35,6 +35,8 @@ pub struct Datastore {
 	db: Database,
 	/// Whether user-defined timestamps (versioning) are enabled
 	versioned: bool,
+	/// The maximum size of the stored records, in bytes
+	max_memory: Option<u64>,
 }
 
 pub struct Transaction {
@@ -76,6 +78,9 @@ impl Datastore {
 			}
 			None => info!(target: TARGET, "Storage mode: in-memory only (no persist path)"),
 		}
+		if let Some(max_memory) = config.max_memory {
+			info!(target: TARGET, "Memory limit: {max_memory} bytes");
+		}
 		// Create new configuration options
 		let opts = DatabaseOptions {
 			enable_gc: config.retention_ns > 0,
@@ -123,9 +128,15 @@ impl Datastore {
 		Ok(Datastore {
 			db,
 			versioned: config.versioned,
+			max_memory: config.max_memory,
 		})
 	}
 
+	/// The maximum size of the stored records, in bytes
+	pub(crate) fn max_memory(&self) -> Option<u64> {
+		self.max_memory
+	}
+
 	/// Shutdown the database
 	pub(crate) async fn shutdown(&self) -> Result<()> {
 		// Nothing to do here
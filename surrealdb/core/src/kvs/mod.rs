@@ -13,11 +13,17 @@
 //! - `tikv`: [TiKV](https://github.com/tikv/tikv) a distributed, and transactional key-value
 //!   database
 //! - `mem`: in-memory database
+//! - `custom`: storage engines provided by other crates, registered under a path scheme (with the
+//!   `kv-custom` feature)
 
 pub mod config;
 pub mod export;
+pub mod import;
 
+mod adapters;
+mod advisor;
 mod api;
+mod archival;
 mod batch;
 mod clock;
 mod consts;
@@ -25,14 +31,25 @@ mod cursor;
 mod direction;
 mod ds;
 mod err;
+mod health;
 mod into;
 mod key;
+mod keylock;
+mod lock;
+mod priority;
+mod purge;
+pub(crate) mod session_tables;
+mod snapshot;
+mod tempindex;
 mod threadpool;
+mod throttle;
+mod tick;
 mod timestamp;
 mod tr;
 mod tx;
 mod util;
 
+pub mod custom;
 mod indxdb;
 mod mem;
 mod rocksdb;
@@ -43,15 +60,27 @@ mod tikv;
 mod tests;
 
 pub(crate) mod cache;
+pub(crate) mod evict;
+pub(crate) mod idempotency;
 pub(crate) mod index;
+pub(crate) mod ratelimit;
+pub(crate) mod scrub;
 pub(crate) mod sequences;
 pub(crate) mod slowlog;
+pub(crate) mod stats;
 pub(crate) mod tasklease;
 pub(crate) mod version;
 
+pub use adapters::TypeAdapter;
+pub(crate) use adapters::TypeAdapters;
+pub(crate) use advisor::IndexAdvisor;
+#[cfg(feature = "kv-custom")]
+pub use api::BoxFut;
 pub use api::{
 	GetMultiResult, KeysResult, ScanCursorKeys, ScanCursorVals, ScanResult, Transactable,
 };
+pub use archival::ArchivalProgress;
+pub(crate) use archival::PolicyProgress;
 pub use consts::{
 	COUNT_BATCH_SIZE, ESTIMATED_BYTES_PER_KEY, ESTIMATED_BYTES_PER_KV, INDEXING_BATCH_SIZE,
 	NORMAL_BATCH_SIZE,
@@ -64,8 +93,21 @@ pub use ds::{
 	TransactionBuilderFactory, TransactionBuilderParts,
 };
 pub use err::{Error, Result};
+pub(crate) use evict::{EvictionBuffer, Evictions};
+pub use health::HealthReport;
 pub use into::IntoBytes;
 pub(crate) use key::{KVKey, KVValue, impl_kv_key_storekey, impl_kv_value_revisioned};
+pub(crate) use keylock::{KeyLockOwner, KeyLockWriter, KeyLocks};
+pub use lock::WriteLock;
+pub(crate) use lock::{WriteGate, WritePermit};
+pub(crate) use priority::{InteractiveGuard, Scheduler, background_batch_size, background_config};
+pub use scrub::CorruptRecord;
+pub(crate) use session_tables::{SessionTable, SessionTables};
+pub use snapshot::{copy_snapshot, snapshot_path};
+pub(crate) use tempindex::{Lookup, Predicate, TableWrites, TemporaryIndex, TemporaryIndexes};
+pub(crate) use throttle::{Admission, Throttles};
+pub use tick::NamespaceTickMetrics;
+pub(crate) use tick::{NamespaceTick, TickScheduler};
 pub use timestamp::{
 	BoxTimeStamp, BoxTimeStampImpl, HlcTimeStamp, HlcTimeStampImpl, IncTimeStampImpl,
 	MAX_TIMESTAMP_BYTES, TimeStamp, TimeStampImpl,
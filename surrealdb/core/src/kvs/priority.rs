@@ -0,0 +1,163 @@
+//! Scheduling of background work around interactive traffic.
+//!
+//! Every query runs with a [`Priority`] taken from its session. While an
+//! interactive query is executing, the datastore's [`Scheduler`] counts it as
+//! active, and background queries (exports, analytics, archival and purge
+//! jobs) defer the start of each statement for a short while so that the
+//! interactive query gets the storage engine to itself. A background query is
+//! never deferred for longer than [`MAX_DEFERRAL`] at a time, so that it keeps
+//! making progress under a constant interactive load. Background queries also
+//! scan with smaller batches, which keeps each read short.
+//!
+//! Background work is only deferred before it opens a transaction, so that
+//! it never holds a transaction open while it waits. Deferred work is woken
+//! as soon as the last interactive query finishes, rather than polling.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::sync::Notify;
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::timeout;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::timeout;
+
+use crate::cnf::CommonConfig;
+use crate::dbs::Priority;
+
+/// The longest time for which background work is deferred at a time
+const MAX_DEFERRAL: Duration = Duration::from_millis(50);
+
+/// The factor by which the batch sizes of background work are reduced
+const BACKGROUND_BATCH_DIVISOR: usize = 4;
+
+/// Tracks the interactive work running on the datastore
+#[derive(Clone, Default)]
+pub(crate) struct Scheduler {
+	interactive: Arc<AtomicUsize>,
+	/// Notified when the last running interactive query finishes
+	idle: Arc<Notify>,
+}
+
+impl Scheduler {
+	/// Registers the start of a query with the given priority.
+	///
+	/// Interactive queries are counted as active until the returned guard is
+	/// dropped. Background queries are not counted.
+	pub(crate) fn enter(&self, priority: Priority) -> Option<InteractiveGuard> {
+		match priority {
+			Priority::Interactive => {
+				self.interactive.fetch_add(1, Ordering::AcqRel);
+				Some(InteractiveGuard {
+					interactive: Arc::clone(&self.interactive),
+					idle: Arc::clone(&self.idle),
+				})
+			}
+			Priority::Background => None,
+		}
+	}
+
+	/// The number of interactive queries which are currently running
+	pub(crate) fn interactive(&self) -> usize {
+		self.interactive.load(Ordering::Acquire)
+	}
+
+	/// Waits while interactive queries are running, for at most
+	/// [`MAX_DEFERRAL`], before letting background work continue
+	pub(crate) async fn defer(&self) {
+		if self.interactive() == 0 {
+			return;
+		}
+		// Register for the notification before checking again, so that a
+		// query which finishes in between is not missed
+		let mut idle = std::pin::pin!(self.idle.notified());
+		idle.as_mut().enable();
+		if self.interactive() == 0 {
+			return;
+		}
+		let _ = timeout(MAX_DEFERRAL, idle).await;
+	}
+}
+
+/// Marks an interactive query as running until dropped
+pub(crate) struct InteractiveGuard {
+	interactive: Arc<AtomicUsize>,
+	idle: Arc<Notify>,
+}
+
+impl Drop for InteractiveGuard {
+	fn drop(&mut self) {
+		if self.interactive.fetch_sub(1, Ordering::AcqRel) == 1 {
+			self.idle.notify_waiters();
+		}
+	}
+}
+
+/// Returns the configuration used by background queries, with reduced batch
+/// sizes so that each individual read holds the storage engine for less time
+pub(crate) fn background_config(config: &CommonConfig) -> CommonConfig {
+	CommonConfig {
+		scan_batch_size: (config.scan_batch_size / BACKGROUND_BATCH_DIVISOR).max(1),
+		fetch_batch_size: (config.fetch_batch_size / BACKGROUND_BATCH_DIVISOR).max(1),
+		export_batch_size: background_batch_size(config.export_batch_size),
+		..config.clone()
+	}
+}
+
+/// Returns the reduced batch size used by background work
+pub(crate) fn background_batch_size(batch_size: u32) -> u32 {
+	(batch_size / BACKGROUND_BATCH_DIVISOR as u32).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+	use web_time::Instant;
+
+	use super::*;
+
+	#[test]
+	fn counts_interactive_queries() {
+		let scheduler = Scheduler::default();
+		let first = scheduler.enter(Priority::Interactive);
+		let second = scheduler.enter(Priority::Interactive);
+		assert!(scheduler.enter(Priority::Background).is_none());
+		assert_eq!(scheduler.interactive(), 2);
+		drop(first);
+		assert_eq!(scheduler.interactive(), 1);
+		drop(second);
+		assert_eq!(scheduler.interactive(), 0);
+	}
+
+	#[tokio::test]
+	async fn defers_background_work_for_a_bounded_time() {
+		let scheduler = Scheduler::default();
+		// Nothing is deferred without interactive work
+		let start = Instant::now();
+		scheduler.defer().await;
+		assert!(start.elapsed() < MAX_DEFERRAL);
+		// Background work continues even if interactive work never finishes
+		let guard = scheduler.enter(Priority::Interactive);
+		let start = Instant::now();
+		scheduler.defer().await;
+		assert!(start.elapsed() >= MAX_DEFERRAL);
+		// Background work continues as soon as interactive work finishes
+		let deferred = scheduler.clone();
+		let waiting = tokio::spawn(async move {
+			let start = Instant::now();
+			deferred.defer().await;
+			start.elapsed()
+		});
+		tokio::task::yield_now().await;
+		drop(guard);
+		assert!(waiting.await.unwrap() < MAX_DEFERRAL);
+	}
+
+	#[test]
+	fn reduces_background_batch_sizes() {
+		assert_eq!(background_batch_size(1000), 250);
+		assert_eq!(background_batch_size(2), 1);
+		let config = background_config(&CommonConfig::default());
+		assert_eq!(config.scan_batch_size, CommonConfig::default().scan_batch_size / 4);
+	}
+}
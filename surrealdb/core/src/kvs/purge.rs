@@ -0,0 +1,118 @@
+//! Background purging of soft deleted records.
+//!
+//! Tables defined with `SOFT DELETE FIELD ... PURGE AFTER ...` keep deleted
+//! records, marked with the time of deletion, for the retention period. The
+//! time of deletion of every marked record is also kept in the
+//! [`crate::key::table::sd`] keyspace, ordered by time, so each run reads only
+//! the records which were deleted before the retention period, and removes
+//! them permanently by deleting them `WITH DELETED`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+use tracing::warn;
+
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{DatabaseDefinition, TableDefinition};
+use crate::dbs::{Priority, Session};
+use crate::key::table::sd::{self, Sd};
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{Datastore, NORMAL_BATCH_SIZE, Transaction};
+use crate::val::{RecordId, Value, convert_value_to_public_value};
+
+/// The statement which permanently removes a batch of expired records
+const PURGE: &str = "DELETE $ids WITH DELETED RETURN NONE";
+
+/// Lists every table with a soft delete retention period, along with its
+/// database
+async fn all_tables(
+	tx: &Transaction,
+) -> Result<Vec<(String, DatabaseDefinition, TableDefinition)>> {
+	let mut out = Vec::new();
+	for ns in tx.all_ns(None).await?.iter() {
+		for db in tx.all_db(ns.namespace_id, None).await?.iter() {
+			for tb in tx.all_tb(db.namespace_id, db.database_id, None).await?.iter() {
+				if tb.soft_delete.as_ref().is_some_and(|sd| sd.retention.is_some()) {
+					out.push((ns.name.to_string(), db.clone(), tb.clone()));
+				}
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// Purges the expired records of every soft delete table in the datastore,
+/// until all tables have been purged or the task lease is lost.
+pub(crate) async fn run(ds: &Datastore, lh: &LeaseHandler) -> Result<()> {
+	let tx = ds.transaction(Read, Optimistic).await?;
+	let res = all_tables(&tx).await;
+	tx.cancel().await?;
+	for (ns, db, tb) in res? {
+		if let Err(e) = purge_table(ds, lh, &ns, &db, &tb).await {
+			warn!("Purging soft deleted records from table '{}' failed: {e}", tb.name);
+		}
+	}
+	Ok(())
+}
+
+/// Removes the records of a table which were deleted before its retention
+/// period, a batch at a time, until none are left or the task lease is lost
+async fn purge_table(
+	ds: &Datastore,
+	lh: &LeaseHandler,
+	ns: &str,
+	db: &DatabaseDefinition,
+	tb: &TableDefinition,
+) -> Result<()> {
+	let Some(retention) = tb.soft_delete.as_ref().and_then(|sd| sd.retention) else {
+		return Ok(());
+	};
+	let retention = u64::try_from(retention.as_millis()).unwrap_or(u64::MAX);
+	let now = u64::try_from(Utc::now().timestamp_millis()).unwrap_or_default();
+	let rng = sd::before(db.namespace_id, db.database_id, &tb.name, now.saturating_sub(retention))?;
+	loop {
+		// Stop if another node has taken over the task
+		if !lh.try_maintain_lease().await? {
+			return Ok(());
+		}
+		let tx = Arc::new(ds.transaction(Write, Optimistic).await?);
+		let keys = catch!(tx, tx.keys(rng.clone(), NORMAL_BATCH_SIZE, 0, None).await);
+		if keys.is_empty() {
+			return tx.cancel().await;
+		}
+		let mut ids = Vec::with_capacity(keys.len());
+		for key in keys.iter() {
+			let id = catch!(tx, Sd::decode_key(key)).id;
+			ids.push(Value::RecordId(RecordId {
+				table: tb.name.clone(),
+				key: id,
+			}));
+			// Remove the entry even if the record no longer exists
+			catch!(tx, tx.del(key).await);
+		}
+		let vars = BTreeMap::from([(
+			"ids".to_string(),
+			catch!(tx, convert_value_to_public_value(Value::from(ids))),
+		)]);
+		let sess =
+			Session::owner().with_ns(ns).with_db(&db.name).with_priority(Priority::Background);
+		let res = ds
+			.execute_with_transaction(PURGE, &sess, Some(vars.into()), Arc::clone(&tx))
+			.await
+			.map_err(|e| anyhow::anyhow!(e))
+			.and_then(|mut res| match res.pop() {
+				Some(last) => last.result.map(|_| ()).map_err(|e| anyhow::anyhow!(e)),
+				None => Err(anyhow::anyhow!("The soft delete purge returned no results")),
+			});
+		catch!(tx, res);
+		tx.commit().await?;
+		// The last batch was not full, so no records are left
+		if keys.len() < NORMAL_BATCH_SIZE as usize {
+			return Ok(());
+		}
+	}
+}
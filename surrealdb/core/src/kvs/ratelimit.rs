@@ -0,0 +1,149 @@
+//! Sliding window rate limits for access grant creation.
+//!
+//! The number of grants created for a subject, or from a client IP address, is
+//! stored in the datastore as a [`GrantWindow`], so that the limits are shared
+//! by every node in a cluster. Each window counts the grants created within the
+//! current fixed window, and within the window before it. The number of grants
+//! created within the sliding window ending now is then estimated by weighting
+//! the previous window by the share of it which still overlaps the sliding
+//! window.
+
+use std::time::Duration;
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+
+use crate::kvs::impl_kv_value_revisioned;
+
+/// The number of grants recently created for a single subject or client
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub(crate) struct GrantWindow {
+	/// The start of the current fixed window, in milliseconds since the epoch
+	start: i64,
+	/// The number of grants created within the current fixed window
+	current: u64,
+	/// The number of grants created within the previous fixed window
+	previous: u64,
+}
+
+impl_kv_value_revisioned!(GrantWindow);
+
+impl GrantWindow {
+	/// Records the creation of a grant at `now`, both in milliseconds since
+	/// the epoch, if fewer than `limit` grants were created within the sliding
+	/// window ending at `now`. Otherwise returns how long to wait until a
+	/// grant can be created again.
+	pub(crate) fn record(
+		window: Option<Self>,
+		now: i64,
+		length: Duration,
+		limit: u64,
+	) -> Result<Self, Duration> {
+		let length = (length.as_millis() as i64).max(1);
+		let start = now - now.rem_euclid(length);
+		// Move the counts along if one or more windows have passed
+		let (previous, current) = match window {
+			Some(w) if w.start == start => (w.previous, w.current),
+			Some(w) if w.start == start - length => (w.current, 0),
+			_ => (0, 0),
+		};
+		// The share of the current window which has elapsed
+		let elapsed = (now - start) as f64 / length as f64;
+		let estimate = previous as f64 * (1.0 - elapsed) + current as f64;
+		let budget = limit.saturating_sub(1) as f64;
+		if estimate <= budget {
+			return Ok(Self {
+				start,
+				current: current + 1,
+				previous,
+			});
+		}
+		// Find how long until the estimate has dropped within the budget
+		let wait = if (current as f64) <= budget {
+			// The previous window only has to slide further out of view
+			1.0 - elapsed - (budget - current as f64) / previous as f64
+		} else {
+			// The current window also has to slide partly out of view
+			(1.0 - elapsed) + (1.0 - budget / current as f64)
+		};
+		let wait = (wait * length as f64).ceil().max(1.0);
+		Err(Duration::from_millis(wait as u64))
+	}
+
+	/// Returns whether none of the grants counted by this window fall within
+	/// the sliding window ending at `now`, so that the window can be removed.
+	pub(crate) fn is_expired(&self, now: i64, length: Duration) -> bool {
+		let length = (length.as_millis() as i64).max(1);
+		// Both the current and the previous fixed window have passed
+		now >= self.start.saturating_add(length.saturating_mul(2))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const MINUTE: Duration = Duration::from_secs(60);
+
+	#[test]
+	fn allows_grants_within_limit() {
+		let mut window = None;
+		for _ in 0..3 {
+			window = Some(GrantWindow::record(window, 60_000, MINUTE, 3).unwrap());
+		}
+		assert_eq!(GrantWindow::record(window, 60_000, MINUTE, 3), Err(Duration::from_secs(80)));
+	}
+
+	#[test]
+	fn weights_previous_window() {
+		let window = GrantWindow {
+			start: 0,
+			current: 4,
+			previous: 0,
+		};
+		// Halfway through the next window, half of the previous grants count
+		let window = GrantWindow::record(Some(window), 90_000, MINUTE, 3).unwrap();
+		assert_eq!(
+			window,
+			GrantWindow {
+				start: 60_000,
+				current: 1,
+				previous: 4,
+			}
+		);
+		// The estimate is now three, so wait for a quarter of the window
+		assert_eq!(
+			GrantWindow::record(Some(window), 90_000, MINUTE, 3),
+			Err(Duration::from_secs(15))
+		);
+	}
+
+	#[test]
+	fn expires_after_two_windows() {
+		let window = GrantWindow::record(None, 90_000, MINUTE, 1).unwrap();
+		// The grant still counts towards the sliding window
+		assert!(!window.is_expired(90_000, MINUTE));
+		assert!(!window.is_expired(179_999, MINUTE));
+		// The grant no longer counts once its window is not the previous one
+		assert!(window.is_expired(180_000, MINUTE));
+	}
+
+	#[test]
+	fn resets_after_idle_windows() {
+		let window = GrantWindow {
+			start: 0,
+			current: 10,
+			previous: 10,
+		};
+		let window = GrantWindow::record(Some(window), 180_000, MINUTE, 1).unwrap();
+		assert_eq!(
+			window,
+			GrantWindow {
+				start: 180_000,
+				current: 1,
+				previous: 0,
+			}
+		);
+	}
+}
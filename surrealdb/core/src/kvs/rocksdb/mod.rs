@@ -32,6 +32,7 @@ use garbage_collector::GarbageCollector;
 use inline_guard::InlineGuard;
 use memory_manager::MemoryManager;
 use range_shard::{COUNT_PARALLEL_MAX_SHARDS, shard_range};
+use rocksdb::checkpoint::Checkpoint;
 use rocksdb::{
 	BottommostLevelCompaction, ColumnFamilyDescriptor, CompactOptions, DBCompactionStyle,
 	DBCompressionType, DBRawIteratorWithThreadMode, FlushOptions, LogLevel,
@@ -63,6 +64,8 @@ const TARGET: &str = "surrealdb::core::kvs::rocksdb";
 pub struct Datastore {
 	/// The underlying RocksDB optimistic transaction database
 	db: Pin<Arc<OptimisticTransactionDB>>,
+	/// The directory the database is stored in
+	path: String,
 	/// Whether user-defined timestamps (versioning) are enabled
 	versioned: bool,
 	/// Memory manager for managing memory usage
@@ -512,6 +515,7 @@ impl Datastore {
 		// Return the datastore
 		Ok(Datastore {
 			db,
+			path: path.to_owned(),
 			versioned: config.versioned,
 			memory_manager,
 			disk_space_manager,
@@ -751,6 +755,21 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Create a named snapshot of the database as a RocksDB checkpoint
+	pub(crate) async fn snapshot(&self, name: &str) -> Result<()> {
+		// Check the name, and the snapshot does not exist yet
+		let target = super::snapshot::prepare_snapshot(&self.path, name)?;
+		info!(target: TARGET, "Creating snapshot at {}", target.display());
+		// The checkpoint flushes the memtables and hard-links the SST
+		// files, so run it on the affinity pool rather than the executor
+		affinitypool::spawn_local(move || {
+			let checkpoint = Checkpoint::new(&*self.db)?;
+			checkpoint.create_checkpoint(&target)?;
+			Ok(())
+		})
+		.await
+	}
+
 	/// Start a new transaction
 	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Box<dyn Transactable>> {
 		// Set the transaction options
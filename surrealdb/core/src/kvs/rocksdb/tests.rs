@@ -981,7 +981,7 @@ async fn open_cursor_cancellation_releases_cursors_alive_slot() {
 	// Without an actual worker thread, `count()` never yields while
 	// holding `inner`, so we can't drive `open_keys_cursor` into the
 	// parked state that exposes the leak. Init is idempotent.
-	crate::kvs::threadpool::initialise();
+	crate::kvs::threadpool::initialise(&crate::cnf::ConfigMap::empty());
 
 	let path = TempDir::new().unwrap().path().to_string_lossy().to_string();
 	let ds = RocksDbDatastore::new(&path, RocksDbConfig::default()).await.unwrap();
@@ -0,0 +1,378 @@
+//! Record checksums and background scrubbing.
+//!
+//! While record checksums are enabled on the datastore, every record which is
+//! written has a checksum of its key and value stored alongside it. Checksums
+//! are enabled by any node started with the `record_checksums` option, or with
+//! [`Datastore::set_record_checksums`], and apply to the writes of every node.
+//! The scrubber periodically reads every record back from storage and
+//! validates it against its checksum, to find records which were corrupted at
+//! rest without ever being read, such as in long-lived embedded deployments.
+//!
+//! A record whose checksum does not match is reported in the corrupt records
+//! of the datastore, which are returned by [`Datastore::corrupt_records`] and
+//! by `INFO FOR ROOT`, and counted by [`Datastore::health_report`]. With the
+//! `scrub_quarantine` option the record is also deleted from its table, like
+//! any other deleted record, and its stored value is kept in the report.
+//!
+//! The checksums written before checksums were last enabled may be stale, as
+//! the records could have been changed while checksums were disabled. The
+//! scrubber writes them again, along with the checksums of records which have
+//! none, and removes the checksums of records which no longer exist.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use surrealdb_types::ToSql;
+use tracing::warn;
+
+use crate::catalog::DatabaseDefinition;
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::dbs::{Priority, Session};
+use crate::expr::statements::info::InfoStructure;
+use crate::key::record::{self, RecordKey};
+use crate::key::root::cr::Cr;
+use crate::key::root::cs::Cs;
+use crate::key::table::ck::{self, Ck};
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{
+	Datastore, KVKey, KVValue, NORMAL_BATCH_SIZE, Transaction, impl_kv_value_revisioned,
+};
+use crate::val::{RecordId, RecordIdKey, TableName, Value, convert_value_to_public_value};
+
+/// Deletes a corrupt record through the document delete path, so that its
+/// index entries, edges, references and table statistics are removed with it
+const QUARANTINE: &str = "DELETE $id RETURN NONE";
+
+/// The checksum of a stored record
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub(crate) struct RecordChecksum {
+	/// The checksum of the key and value of the record
+	pub(crate) sum: u64,
+	/// When the checksum was written, in milliseconds since the epoch
+	pub(crate) at: u64,
+}
+
+impl_kv_value_revisioned!(RecordChecksum);
+
+impl RecordChecksum {
+	/// Computes the checksum of the encoded key and value of a record
+	pub(crate) fn new(key: &[u8], val: &[u8]) -> Self {
+		let mut hasher = blake3::Hasher::new();
+		hasher.update(&(key.len() as u64).to_be_bytes());
+		hasher.update(key);
+		hasher.update(val);
+		let hash = hasher.finalize();
+		let mut sum = [0u8; 8];
+		sum.copy_from_slice(&hash.as_bytes()[..8]);
+		Self {
+			sum: u64::from_be_bytes(sum),
+			at: Utc::now().timestamp_millis().max(0) as u64,
+		}
+	}
+}
+
+/// A record which did not match its checksum, as returned by
+/// [`Datastore::corrupt_records`]
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CorruptRecord {
+	/// The namespace of the record
+	pub namespace: String,
+	/// The database of the record
+	pub database: String,
+	/// The id of the record
+	pub record: String,
+	/// When the scrubber found the record to be corrupt
+	pub detected_at: DateTime<Utc>,
+	/// The stored value of the record, if it was moved out of its table
+	pub quarantined: Option<Vec<u8>>,
+}
+
+impl_kv_value_revisioned!(CorruptRecord);
+
+impl InfoStructure for CorruptRecord {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"namespace" => self.namespace.into(),
+			"database" => self.database.into(),
+			"record" => self.record.into(),
+			"detected_at" => crate::val::Datetime::from(self.detected_at).into(),
+			"quarantined" => self.quarantined.is_some().into(),
+		})
+	}
+}
+
+/// Whether record checksums are enabled on the datastore
+pub(crate) async fn enabled(tx: &Transaction) -> Result<bool> {
+	tx.exists(&Cs::new(), None).await
+}
+
+/// Records when checksums were enabled, or forgets it once they are disabled
+pub(crate) async fn init(tx: &Transaction, enabled: bool) -> Result<()> {
+	let key = Cs::new();
+	match (enabled, tx.exists(&key, None).await?) {
+		(true, false) => {
+			let now = Utc::now().timestamp_millis().max(0) as u64;
+			tx.set(&key, &now).await
+		}
+		(false, true) => tx.del(&key).await,
+		_ => Ok(()),
+	}
+}
+
+/// Lists every table in the datastore, along with its namespace and database
+async fn all_tables(tx: &Transaction) -> Result<Vec<(String, DatabaseDefinition, TableName)>> {
+	let mut out = Vec::new();
+	for ns in tx.all_ns(None).await?.iter() {
+		for db in tx.all_db(ns.namespace_id, None).await?.iter() {
+			for tb in tx.all_tb(db.namespace_id, db.database_id, None).await?.iter() {
+				out.push((ns.name.to_string(), db.clone(), tb.name.clone()));
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// Validates every record in the datastore against its checksum, until all
+/// tables have been scrubbed or the task lease is lost.
+pub(crate) async fn run(ds: &Datastore, lh: &LeaseHandler, quarantine: bool) -> Result<()> {
+	let tx = ds.transaction(Read, Optimistic).await?;
+	let res = async {
+		match tx.get(&Cs::new(), None).await? {
+			Some(since) => Ok(Some((since, all_tables(&tx).await?))),
+			None => Ok(None),
+		}
+	}
+	.await;
+	tx.cancel().await?;
+	// Checksums have not been enabled on this datastore
+	let Some((since, tables)) = res? else {
+		return Ok(());
+	};
+	let scrubber = Scrubber {
+		ds,
+		since,
+		quarantine,
+	};
+	for (ns, db, tb) in tables {
+		// Stop if another node has taken over the task
+		if !lh.try_maintain_lease().await? {
+			return Ok(());
+		}
+		if let Err(e) = scrubber.table(&ns, &db, &tb).await {
+			warn!("Scrubbing the records of table '{tb}' failed: {e}");
+		}
+	}
+	Ok(())
+}
+
+struct Scrubber<'a> {
+	ds: &'a Datastore,
+	/// When checksums were last enabled
+	since: u64,
+	/// Whether corrupt records are moved out of their tables
+	quarantine: bool,
+}
+
+impl Scrubber<'_> {
+	/// Validates the records of a table, then removes the checksums of the
+	/// records which no longer exist
+	async fn table(&self, ns: &str, db: &DatabaseDefinition, tb: &TableName) -> Result<()> {
+		let (nsid, dbid) = (db.namespace_id, db.database_id);
+		let mut next = Some(record::prefix(nsid, dbid, tb)?..record::suffix(nsid, dbid, tb)?);
+		while let Some(rng) = next {
+			let tx = self.ds.transaction(Write, Optimistic).await?;
+			let (rest, corrupt) = catch!(tx, self.records(&tx, ns, db, tb, rng).await);
+			next = rest;
+			tx.commit().await?;
+			for (id, val) in corrupt {
+				if let Err(e) = self.quarantine(ns, db, tb, id, val).await {
+					warn!("Quarantining a corrupt record of table '{tb}' failed: {e}");
+				}
+			}
+			yield_now!();
+		}
+		let mut next = Some(ck::range(nsid, dbid, tb)?);
+		while let Some(rng) = next {
+			let tx = self.ds.transaction(Write, Optimistic).await?;
+			next = catch!(tx, self.orphans(&tx, rng).await);
+			tx.commit().await?;
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	/// Validates a batch of records, returning the range of the next batch,
+	/// along with the corrupt records which are to be quarantined
+	async fn records(
+		&self,
+		tx: &Transaction,
+		ns: &str,
+		db: &DatabaseDefinition,
+		tb: &TableName,
+		rng: Range<Vec<u8>>,
+	) -> Result<(Option<Range<Vec<u8>>>, Vec<(RecordIdKey, Vec<u8>)>)> {
+		let mut corrupt = Vec::new();
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		let ids = res
+			.result
+			.iter()
+			.map(|(k, _)| RecordKey::decode_key(k).map(|k| k.id))
+			.collect::<Result<Vec<_>>>()?;
+		let keys = ids.iter().map(|id| ck::new(db.namespace_id, db.database_id, tb, id)).collect();
+		let sums = tx.getm(keys, None).await?;
+		for (((key, val), id), stored) in res.result.iter().zip(ids).zip(sums) {
+			let actual = RecordChecksum::new(key, val);
+			match stored {
+				// The record matches its checksum
+				Some(stored) if stored.sum == actual.sum => {}
+				// The record does not match a checksum written since checksums
+				// were last enabled
+				Some(stored) if stored.at >= self.since => {
+					let rid = RecordId {
+						table: tb.clone(),
+						key: id.clone(),
+					};
+					warn!(
+						"Record {} in {ns}/{} does not match its checksum",
+						rid.to_sql(),
+						db.name
+					);
+					let report = CorruptRecord {
+						namespace: ns.to_owned(),
+						database: db.name.to_string(),
+						record: rid.to_sql(),
+						detected_at: Utc::now(),
+						quarantined: None,
+					};
+					tx.set(&Cr::new(db.namespace_id, db.database_id, tb, id.clone()), &report)
+						.await?;
+					if self.quarantine {
+						corrupt.push((id, val.clone()));
+					}
+				}
+				// The checksum is missing, or may have been written before the
+				// record was last changed while checksums were disabled
+				_ => tx.set(&ck::new(db.namespace_id, db.database_id, tb, &id), &actual).await?,
+			}
+		}
+		Ok((res.next, corrupt))
+	}
+
+	/// Deletes a corrupt record from its table, like any other deleted record,
+	/// and keeps its stored value in its report. The record is left in place
+	/// if it has been written since it was found corrupt, or if its value can
+	/// not be decoded to remove it from the indexes of the table.
+	async fn quarantine(
+		&self,
+		ns: &str,
+		db: &DatabaseDefinition,
+		tb: &TableName,
+		id: RecordIdKey,
+		val: Vec<u8>,
+	) -> Result<()> {
+		let (nsid, dbid) = (db.namespace_id, db.database_id);
+		let key = record::new(nsid, dbid, tb, &id).encode_key()?;
+		let tx = Arc::new(self.ds.transaction(Write, Optimistic).await?);
+		if catch!(tx, tx.get(&key, None).await).as_deref() != Some(val.as_slice()) {
+			return tx.cancel().await;
+		}
+		let rid = RecordId {
+			table: tb.clone(),
+			key: id.clone(),
+		};
+		let vars = BTreeMap::from([(
+			"id".to_string(),
+			catch!(tx, convert_value_to_public_value(Value::RecordId(rid))),
+		)]);
+		let sess =
+			Session::owner().with_ns(ns).with_db(&db.name).with_priority(Priority::Background);
+		let res = self
+			.ds
+			.execute_with_transaction(QUARANTINE, &sess, Some(vars.into()), Arc::clone(&tx))
+			.await
+			.map_err(|e| anyhow::anyhow!(e))
+			.and_then(|mut res| match res.pop() {
+				Some(last) => last.result.map(|_| ()).map_err(|e| anyhow::anyhow!(e)),
+				None => Err(anyhow::anyhow!("The quarantine returned no results")),
+			});
+		catch!(tx, res);
+		// Keep the stored value of the record in its report
+		let cr = Cr::new(nsid, dbid, tb, id);
+		if let Some(mut report) = catch!(tx, tx.get(&cr, None).await) {
+			report.quarantined = Some(val);
+			catch!(tx, tx.set(&cr, &report).await);
+		}
+		tx.commit().await
+	}
+
+	/// Removes the checksums of a batch of records which no longer exist,
+	/// returning the range of the next batch
+	async fn orphans(
+		&self,
+		tx: &Transaction,
+		rng: Range<Vec<u8>>,
+	) -> Result<Option<Range<Vec<u8>>>> {
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		let keys = res
+			.result
+			.iter()
+			.map(|(k, _)| {
+				let k = Ck::decode_key(k)?;
+				Ok(record::new(k.ns, k.db, &k.tb, &k.id).encode_key()?)
+			})
+			.collect::<Result<Vec<_>>>()?;
+		let found = tx.getm(keys, None).await?;
+		for ((key, _), found) in res.result.iter().zip(found) {
+			if found.is_none() {
+				tx.del(key).await?;
+			}
+		}
+		Ok(res.next)
+	}
+}
+
+/// Counts the records which the scrubber has found corrupt
+pub(crate) async fn count_corrupt_records(tx: &Transaction) -> Result<usize> {
+	let (beg, end) = Cr::range();
+	tx.count(beg..end, None).await
+}
+
+/// Fetches every record which the scrubber has found corrupt
+pub(crate) async fn corrupt_records(tx: &Transaction) -> Result<Vec<CorruptRecord>> {
+	let (beg, end) = Cr::range();
+	let mut out = Vec::new();
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		next = res.next;
+		for (_, v) in res.result.iter() {
+			out.push(CorruptRecord::kv_decode_value(v, ())?);
+		}
+	}
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn checksums_cover_the_key_and_value() {
+		let sum = RecordChecksum::new(b"key", b"value").sum;
+		assert_eq!(RecordChecksum::new(b"key", b"value").sum, sum);
+		assert_ne!(RecordChecksum::new(b"key", b"valuf").sum, sum);
+		assert_ne!(RecordChecksum::new(b"kez", b"value").sum, sum);
+		// Moving bytes from the key to the value changes the checksum
+		assert_ne!(RecordChecksum::new(b"keyv", b"alue").sum, sum);
+	}
+}
@@ -711,6 +711,7 @@ mod tests {
 				comment: None,
 				changefeed: None,
 				strict: false,
+				capabilities: None,
 			},
 		)
 		.await
@@ -0,0 +1,301 @@
+//! Tables which are only visible to the session which defined them.
+//!
+//! A table defined with `SESSION` is stored under a name which is scoped to
+//! the session, so that its definition and records are kept under their own
+//! key prefix, and several sessions can each define a table with the same
+//! name. The datastore's [`SessionTables`] records the tables defined by each
+//! session, and references to those tables in the statements run by the
+//! session are rewritten to the scoped names before the statements are run.
+//! Tables and record ids which are only known once a statement runs, such as
+//! those built by `type::table` or bound to parameters, are resolved in the
+//! same way, and the record ids returned to the session use the names which
+//! the session knows.
+//!
+//! The owning session and node are stored in the definition of the table.
+//! Transactions run for another session can not access the table, and the
+//! tables of sessions which ended without removing them, for instance when
+//! their node crashed, are removed by the garbage collection of the datastore.
+
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use uuid::Uuid;
+
+use crate::ctx::Context;
+use crate::expr::statements::DefineTableStatement;
+use crate::expr::visit::{MutVisitor, VisitMut};
+use crate::expr::{Expr, Literal, RecordIdLit};
+use crate::val::{TableName, Value};
+
+/// A table defined by a session, in the namespace and database it was defined
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct SessionTable {
+	pub(crate) ns: String,
+	pub(crate) db: String,
+	/// The name of the table, as used by the session
+	pub(crate) name: TableName,
+}
+
+/// The session tables of every session in the datastore
+#[derive(Clone, Default)]
+pub(crate) struct SessionTables {
+	tables: Arc<RwLock<HashMap<Uuid, HashSet<SessionTable>>>>,
+}
+
+impl SessionTables {
+	/// Returns the id of the session which runs a statement, if it has one
+	pub(crate) fn session_id(ctx: &Context) -> Option<Uuid> {
+		match ctx.value("session").and_then(|v| v.as_object()).and_then(|v| v.get("id")) {
+			Some(Value::Uuid(id)) => Some(id.0),
+			_ => None,
+		}
+	}
+
+	/// Resolves the references to the session tables of the session running a
+	/// statement in a value computed while the statement runs
+	///
+	/// This uses the resolver which the executor built once for the
+	/// statement, and only tables, record ids, and arrays of them are
+	/// resolved, so that this remains cheap for the values of every parameter.
+	pub(crate) fn resolve_value(ctx: &Context, value: &mut Value) {
+		if let Some(resolver) = ctx.session_resolver() {
+			resolver.resolve_value(value);
+		}
+	}
+
+	/// Returns true if a function builds tables or record ids from values
+	/// which may name a session table
+	pub(crate) fn is_table_constructor(function: &str) -> bool {
+		matches!(function, "type::table" | "type::record")
+	}
+
+	/// Returns the name under which a session stores a table
+	///
+	/// A name which is already scoped to the session is returned unchanged.
+	pub(crate) fn scoped_name(session: Uuid, name: &TableName) -> TableName {
+		let suffix = format!("@{}", session.simple());
+		if name.as_str().ends_with(&suffix) {
+			return name.clone();
+		}
+		TableName::new(format!("{}{suffix}", name.as_str()))
+	}
+
+	/// Returns the name of a table as used by the session, from its scoped name
+	pub(crate) fn unscoped_name(session: Uuid, name: &TableName) -> TableName {
+		let suffix = format!("@{}", session.simple());
+		match name.as_str().strip_suffix(&suffix) {
+			Some(name) => TableName::new(name),
+			None => name.clone(),
+		}
+	}
+
+	/// Returns true if a name is scoped to a session
+	pub(crate) fn is_scoped(name: &TableName) -> bool {
+		name.as_str().rsplit_once('@').is_some_and(|(_, id)| Uuid::try_parse(id).is_ok())
+	}
+
+	/// Returns true if a session has defined a table on this node
+	pub(crate) fn contains(&self, session: Uuid) -> bool {
+		self.tables.read().contains_key(&session)
+	}
+
+	/// Records a table defined by a session
+	pub(crate) fn register(&self, session: Uuid, table: SessionTable) {
+		self.tables.write().entry(session).or_default().insert(table);
+	}
+
+	/// Returns the resolver for the tables which a session defined in a
+	/// database, if the session defined any
+	pub(crate) fn resolver(&self, session: Uuid, ns: &str, db: &str) -> Option<Resolver> {
+		let tables = self.tables.read();
+		let names: HashSet<TableName> = tables
+			.get(&session)?
+			.iter()
+			.filter(|t| t.ns == ns && t.db == db)
+			.map(|t| t.name.clone())
+			.collect();
+		if names.is_empty() {
+			return None;
+		}
+		Some(Resolver {
+			session,
+			names,
+		})
+	}
+
+	/// Removes and returns the tables defined by a session
+	pub(crate) fn take(&self, session: Uuid) -> Vec<SessionTable> {
+		self.tables.write().remove(&session).map(|t| t.into_iter().collect()).unwrap_or_default()
+	}
+}
+
+/// Rewrites the references to the session tables of a session in a statement
+/// to their scoped names
+pub(crate) struct Resolver {
+	session: Uuid,
+	names: HashSet<TableName>,
+}
+
+impl Resolver {
+	fn resolve(&self, name: &mut TableName) {
+		if self.names.contains(name) {
+			*name = SessionTables::scoped_name(self.session, name);
+		}
+	}
+
+	/// Rewrites the session tables referenced by a value to their scoped names
+	pub(crate) fn resolve_value(&self, value: &mut Value) {
+		match value {
+			Value::Table(name) => self.resolve(name),
+			Value::RecordId(rid) => self.resolve(&mut rid.table),
+			Value::Array(values) => values.iter_mut().for_each(|v| self.resolve_value(v)),
+			_ => {}
+		}
+	}
+
+	/// Rewrites the scoped names of the session tables in a value returned to
+	/// the session to the names which the session knows
+	pub(crate) fn unresolve_value(&self, value: &mut Value) {
+		match value {
+			Value::Table(name) => *name = SessionTables::unscoped_name(self.session, name),
+			Value::RecordId(rid) => {
+				rid.table = SessionTables::unscoped_name(self.session, &rid.table);
+			}
+			Value::Array(values) => values.iter_mut().for_each(|v| self.unresolve_value(v)),
+			Value::Object(fields) => fields.values_mut().for_each(|v| self.unresolve_value(v)),
+			_ => {}
+		}
+	}
+}
+
+impl MutVisitor for Resolver {
+	type Error = Infallible;
+
+	fn visit_mut_expr(&mut self, expr: &mut Expr) -> Result<(), Self::Error> {
+		if let Expr::Table(name) = expr {
+			self.resolve(name);
+			return Ok(());
+		}
+		expr.visit_mut(self)
+	}
+
+	fn visit_mut_record_id(&mut self, t: &mut RecordIdLit) -> Result<(), Self::Error> {
+		self.resolve(&mut t.table);
+		t.visit_mut(self)
+	}
+
+	fn visit_mut_define_table(&mut self, d: &mut DefineTableStatement) -> Result<(), Self::Error> {
+		// A session table is scoped when it is defined
+		if d.session {
+			let name = std::mem::replace(&mut d.name, Expr::Literal(Literal::None));
+			let res = d.visit_mut(self);
+			d.name = name;
+			return res;
+		}
+		d.visit_mut(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::expr::TopLevelExpr;
+	use crate::val::RecordId;
+
+	#[test]
+	fn names_are_scoped_to_the_session() {
+		let session = Uuid::new_v4();
+		let name = TableName::new("results");
+		let scoped = SessionTables::scoped_name(session, &name);
+		assert_ne!(scoped, name);
+		assert_eq!(SessionTables::scoped_name(session, &scoped), scoped);
+		assert_eq!(SessionTables::unscoped_name(session, &scoped), name);
+		assert_ne!(scoped, SessionTables::scoped_name(Uuid::new_v4(), &name));
+	}
+
+	#[test]
+	fn references_are_resolved_in_the_defining_database() {
+		let session = Uuid::new_v4();
+		let tables = SessionTables::default();
+		tables.register(
+			session,
+			SessionTable {
+				ns: "test".to_string(),
+				db: "test".to_string(),
+				name: TableName::new("results"),
+			},
+		);
+		assert!(tables.resolver(session, "test", "other").is_none());
+		assert!(tables.resolver(Uuid::new_v4(), "test", "test").is_none());
+		let mut resolver = tables.resolver(session, "test", "test").unwrap();
+		let mut plan = TopLevelExpr::Expr(Expr::Table(TableName::new("results")));
+		let _ = plan.visit_mut(&mut resolver);
+		let scoped = SessionTables::scoped_name(session, &TableName::new("results"));
+		assert_eq!(plan, TopLevelExpr::Expr(Expr::Table(scoped)));
+		let mut plan = TopLevelExpr::Expr(Expr::Table(TableName::new("person")));
+		let _ = plan.visit_mut(&mut resolver);
+		assert_eq!(plan, TopLevelExpr::Expr(Expr::Table(TableName::new("person"))));
+		assert_eq!(tables.take(session).len(), 1);
+		assert!(tables.resolver(session, "test", "test").is_none());
+	}
+
+	#[test]
+	fn values_are_resolved_and_returned_unscoped() {
+		let session = Uuid::new_v4();
+		let tables = SessionTables::default();
+		tables.register(
+			session,
+			SessionTable {
+				ns: "test".to_string(),
+				db: "test".to_string(),
+				name: TableName::new("results"),
+			},
+		);
+		assert!(tables.contains(session));
+		let resolver = tables.resolver(session, "test", "test").unwrap();
+		let scoped = SessionTables::scoped_name(session, &TableName::new("results"));
+		let mut value = Value::Array(
+			vec![
+				Value::Table(TableName::new("results")),
+				Value::RecordId(RecordId::new(TableName::new("results"), 1_i64)),
+				Value::Table(TableName::new("person")),
+			]
+			.into(),
+		);
+		resolver.resolve_value(&mut value);
+		assert_eq!(
+			value,
+			Value::Array(
+				vec![
+					Value::Table(scoped.clone()),
+					Value::RecordId(RecordId::new(scoped.clone(), 1_i64)),
+					Value::Table(TableName::new("person")),
+				]
+				.into()
+			)
+		);
+		// Resolving is idempotent
+		let mut again = value.clone();
+		resolver.resolve_value(&mut again);
+		assert_eq!(again, value);
+		let mut output = Value::Object(
+			[("id".to_string(), Value::RecordId(RecordId::new(scoped, 1_i64)))]
+				.into_iter()
+				.collect(),
+		);
+		resolver.unresolve_value(&mut output);
+		assert_eq!(
+			output,
+			Value::Object(
+				[(
+					"id".to_string(),
+					Value::RecordId(RecordId::new(TableName::new("results"), 1_i64))
+				)]
+				.into_iter()
+				.collect()
+			)
+		);
+	}
+}
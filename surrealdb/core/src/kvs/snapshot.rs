@@ -0,0 +1,218 @@
+//! Named storage-level snapshots of on-disk datastores.
+//!
+//! A snapshot is created by the storage engine itself, as a RocksDB or
+//! SurrealKV checkpoint, which hard-links the immutable data files of the
+//! datastore rather than copying them. Creating a snapshot is therefore nearly
+//! instantaneous, regardless of the size of the datastore. The snapshots of a
+//! datastore at `path` are stored next to it, in the `{path}.snapshots`
+//! directory, and each snapshot can be opened as a datastore of its own.
+
+use std::path::{Path, PathBuf};
+
+use crate::kvs::{Error, Result};
+
+/// The maximum length of a snapshot name
+const MAX_NAME_LENGTH: usize = 128;
+
+/// Returns the directory in which the named snapshot of the datastore at
+/// `path` is stored.
+///
+/// Snapshot names may only contain ASCII letters, digits, `-` and `_`, so that
+/// a snapshot can never be created or opened outside of the snapshots
+/// directory of the datastore.
+pub fn snapshot_path(path: impl AsRef<Path>, name: &str) -> Result<PathBuf> {
+	let valid = !name.is_empty()
+		&& name.len() <= MAX_NAME_LENGTH
+		&& name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+	if !valid {
+		return Err(Error::InvalidSnapshotName(name.to_owned()));
+	}
+	// Ignore any trailing separator, so that the snapshots
+	// directory is never placed inside the datastore directory
+	let mut dir = path.as_ref().components().as_path().as_os_str().to_owned();
+	dir.push(".snapshots");
+	Ok(PathBuf::from(dir).join(name))
+}
+
+/// Returns the directory in which a new snapshot is to be created, checking
+/// that no snapshot with the same name exists, and creating the snapshots
+/// directory if necessary.
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+pub(crate) fn prepare_snapshot(path: impl AsRef<Path>, name: &str) -> Result<PathBuf> {
+	let target = snapshot_path(path, name)?;
+	if target.exists() {
+		return Err(Error::SnapshotExists(name.to_owned()));
+	}
+	if let Some(parent) = target.parent() {
+		std::fs::create_dir_all(parent).map_err(|e| Error::Datastore(e.to_string()))?;
+	}
+	Ok(target)
+}
+
+/// How a file of a snapshot is transferred into a copy of the snapshot
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Transfer {
+	/// The file is never modified once written, so is hard-linked
+	Link,
+	/// The file may be written to once the copy is opened, so is copied
+	Copy,
+}
+
+/// Returns how a file of a snapshot is transferred into a copy of the
+/// snapshot, or `None` if the file is not part of the data of the datastore.
+///
+/// Only the files which the storage engines need to open a datastore are
+/// listed. Files which belong to the process which created the snapshot, such
+/// as the `LOCK` file and the `LOG` files of RocksDB, are left behind.
+fn transfer(name: &str) -> Option<Transfer> {
+	let ext = Path::new(name).extension().and_then(|ext| ext.to_str());
+	match (name, ext) {
+		// RocksDB and SurrealKV table files, and RocksDB blob files
+		(_, Some("sst" | "blob")) => Some(Transfer::Link),
+		// Write-ahead logs, value logs, and manifests
+		(_, Some("log" | "wal" | "vlog" | "manifest")) => Some(Transfer::Copy),
+		// The RocksDB files describing the datastore
+		("CURRENT" | "IDENTITY", _) => Some(Transfer::Copy),
+		_ if name.starts_with("MANIFEST-") || name.starts_with("OPTIONS-") => Some(Transfer::Copy),
+		_ => None,
+	}
+}
+
+/// Removes a partially copied snapshot, unless the copy completed
+struct CopyGuard<'a> {
+	target: &'a Path,
+	complete: bool,
+}
+
+impl Drop for CopyGuard<'_> {
+	fn drop(&mut self) {
+		if !self.complete {
+			std::fs::remove_dir_all(self.target).ok();
+		}
+	}
+}
+
+/// Copies a snapshot into a directory from which it can be opened, so that
+/// the snapshot itself is left untouched.
+///
+/// The storage engines write to the directory of a datastore whenever it is
+/// opened, even if no transaction ever writes to it, so a snapshot is never
+/// opened in place. Table files are never modified once written, so they are
+/// hard-linked rather than copied, as RocksDB does itself when creating a
+/// checkpoint. Every other file of the datastore is copied. The directory of
+/// the copy must not exist yet, and is removed if the copy fails.
+pub fn copy_snapshot(source: impl AsRef<Path>, target: impl AsRef<Path>) -> Result<()> {
+	let (source, target) = (source.as_ref(), target.as_ref());
+	if let Some(parent) = target.parent() {
+		std::fs::create_dir_all(parent).map_err(|e| Error::Datastore(e.to_string()))?;
+	}
+	std::fs::create_dir(target).map_err(|e| Error::Datastore(e.to_string()))?;
+	let mut guard = CopyGuard {
+		target,
+		complete: false,
+	};
+	copy_dir(source, target)?;
+	guard.complete = true;
+	Ok(())
+}
+
+fn copy_dir(source: &Path, target: &Path) -> Result<()> {
+	let entries = std::fs::read_dir(source).map_err(|e| Error::Datastore(e.to_string()))?;
+	for entry in entries {
+		let entry = entry.map_err(|e| Error::Datastore(e.to_string()))?;
+		let from = entry.path();
+		let to = target.join(entry.file_name());
+		let kind = entry.file_type().map_err(|e| Error::Datastore(e.to_string()))?;
+		if kind.is_dir() {
+			std::fs::create_dir(&to).map_err(|e| Error::Datastore(e.to_string()))?;
+			copy_dir(&from, &to)?;
+			continue;
+		}
+		match entry.file_name().to_str().and_then(transfer) {
+			Some(Transfer::Link) => {
+				// Fall back to a copy across file systems
+				if std::fs::hard_link(&from, &to).is_err() {
+					std::fs::copy(&from, &to).map_err(|e| Error::Datastore(e.to_string()))?;
+				}
+			}
+			Some(Transfer::Copy) => {
+				std::fs::copy(&from, &to).map_err(|e| Error::Datastore(e.to_string()))?;
+			}
+			None => {}
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn snapshot_path_is_next_to_datastore() {
+		let path = snapshot_path("/data/app", "fixture-1").unwrap();
+		assert_eq!(path, PathBuf::from("/data/app.snapshots/fixture-1"));
+		let path = snapshot_path("/data/app/", "fixture_1").unwrap();
+		assert_eq!(path, PathBuf::from("/data/app.snapshots/fixture_1"));
+	}
+
+	#[test]
+	fn snapshot_path_rejects_unsafe_names() {
+		for name in ["", ".", "..", "../app", "a/b", "a\\b", "snap shot", "ünïcode"] {
+			assert!(
+				matches!(snapshot_path("/data/app", name), Err(Error::InvalidSnapshotName(_))),
+				"{name:?} should be rejected"
+			);
+		}
+		assert!(snapshot_path("/data/app", &"a".repeat(MAX_NAME_LENGTH + 1)).is_err());
+	}
+
+	#[test]
+	fn snapshot_is_copied() {
+		let dir = temp_dir::TempDir::new().unwrap();
+		let source = dir.path().join("snapshot");
+		std::fs::create_dir_all(source.join("nested")).unwrap();
+		std::fs::write(source.join("000001.sst"), b"table").unwrap();
+		std::fs::write(source.join("CURRENT"), b"MANIFEST-000002").unwrap();
+		std::fs::write(source.join("LOCK"), b"").unwrap();
+		std::fs::write(source.join("LOG"), b"info").unwrap();
+		std::fs::write(source.join("nested").join("MANIFEST-000002"), b"manifest").unwrap();
+		let target = dir.path().join("copy");
+		copy_snapshot(&source, &target).unwrap();
+		assert_eq!(std::fs::read(target.join("000001.sst")).unwrap(), b"table");
+		assert_eq!(std::fs::read(target.join("CURRENT")).unwrap(), b"MANIFEST-000002");
+		let manifest = target.join("nested").join("MANIFEST-000002");
+		assert_eq!(std::fs::read(&manifest).unwrap(), b"manifest");
+		// The files of the process which created the snapshot are left behind
+		assert!(!target.join("LOCK").exists());
+		assert!(!target.join("LOG").exists());
+		// Writing to the copy leaves the snapshot untouched
+		std::fs::write(&manifest, b"changed").unwrap();
+		let manifest = source.join("nested").join("MANIFEST-000002");
+		assert_eq!(std::fs::read(&manifest).unwrap(), b"manifest");
+	}
+
+	#[test]
+	fn failed_copy_is_removed() {
+		let dir = temp_dir::TempDir::new().unwrap();
+		let target = dir.path().join("copy");
+		copy_snapshot(dir.path().join("missing"), &target).unwrap_err();
+		assert!(!target.exists());
+		// An existing directory is never copied into, nor removed
+		std::fs::create_dir(&target).unwrap();
+		copy_snapshot(dir.path(), &target).unwrap_err();
+		assert!(target.exists());
+	}
+
+	#[test]
+	fn only_datastore_files_are_transferred() {
+		assert_eq!(transfer("000012.sst"), Some(Transfer::Link));
+		assert_eq!(transfer("000013.blob"), Some(Transfer::Link));
+		assert_eq!(transfer("000014.log"), Some(Transfer::Copy));
+		assert_eq!(transfer("OPTIONS-000007"), Some(Transfer::Copy));
+		assert_eq!(transfer("IDENTITY"), Some(Transfer::Copy));
+		for name in ["LOCK", "LOG", "LOG.old.1700000000000000", "000015.sst.tmp"] {
+			assert_eq!(transfer(name), None, "{name:?} should be left behind");
+		}
+	}
+}
@@ -0,0 +1,278 @@
+//! Incrementally maintained statistics of tables.
+//!
+//! Every transaction buffers the changes it makes to the number of records in
+//! each table, to their approximate encoded size, and to the number of entries
+//! in each of their standard and unique indexes. When the transaction commits,
+//! the changes are written as a delta entry in the [`crate::key::table::st`]
+//! keyspace, stamped with the versionstamp of the transaction. Deltas are
+//! compacted into a single entry in the background, alongside the changefeed
+//! garbage collection, so reading the statistics of a table never requires a
+//! scan of its records.
+//!
+//! The statistics are estimates: statements which are rolled back within a
+//! transaction which then commits are still counted, and other kinds of index
+//! are not counted at all.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use revision::revisioned;
+use uuid::Uuid;
+
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{DatabaseDefinition, DatabaseId, IndexId, NamespaceId};
+use crate::key::table::st;
+use crate::kvs::{KVValue, NORMAL_BATCH_SIZE, Transaction, impl_kv_value_revisioned};
+use crate::val::{Object, TableName, Value};
+
+/// A change to the statistics of a table, or the compacted total of many
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct TableStatsDelta {
+	/// The change in the number of records
+	pub(crate) rows: i64,
+	/// The change in the encoded size of the records, in bytes
+	pub(crate) bytes: i64,
+	/// The change in the number of entries of each index
+	pub(crate) indexes: BTreeMap<u32, i64>,
+	/// The versionstamp of the latest transaction which wrote to the table
+	pub(crate) versionstamp: Option<u128>,
+}
+
+impl_kv_value_revisioned!(TableStatsDelta);
+
+impl TableStatsDelta {
+	fn is_empty(&self) -> bool {
+		self.rows == 0 && self.bytes == 0 && self.indexes.values().all(|v| *v == 0)
+	}
+
+	/// Adds another delta to this one
+	pub(crate) fn merge(&mut self, other: TableStatsDelta) {
+		self.rows = self.rows.saturating_add(other.rows);
+		self.bytes = self.bytes.saturating_add(other.bytes);
+		for (ix, entries) in other.indexes {
+			let total = self.indexes.entry(ix).or_default();
+			*total = total.saturating_add(entries);
+		}
+		self.indexes.retain(|_, v| *v != 0);
+		self.versionstamp = self.versionstamp.max(other.versionstamp);
+	}
+}
+
+/// The changes made to the statistics of tables by a transaction
+#[derive(Default)]
+pub(crate) struct TableStatsBuffer {
+	changes: Mutex<HashMap<(NamespaceId, DatabaseId, TableName), TableStatsDelta>>,
+}
+
+impl TableStatsBuffer {
+	/// Records a change to the records of a table
+	pub(crate) fn records(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		rows: i64,
+		bytes: i64,
+	) {
+		let mut changes = self.changes.lock();
+		let delta = changes.entry((ns, db, tb.clone())).or_default();
+		delta.rows += rows;
+		delta.bytes += bytes;
+	}
+
+	/// Records a change to the entries of an index
+	pub(crate) fn index(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		ix: IndexId,
+		entries: i64,
+	) {
+		let mut changes = self.changes.lock();
+		let delta = changes.entry((ns, db, tb.clone())).or_default();
+		*delta.indexes.entry(ix.0).or_default() += entries;
+	}
+
+	/// Takes the buffered changes which have an effect
+	pub(crate) fn take(&self) -> Vec<(NamespaceId, DatabaseId, TableName, TableStatsDelta)> {
+		self.changes
+			.lock()
+			.drain()
+			.filter(|(_, delta)| !delta.is_empty())
+			.map(|((ns, db, tb), delta)| (ns, db, tb, delta))
+			.collect()
+	}
+
+	/// Discards the buffered changes to a table whose records and statistics
+	/// have been removed, so that the records written to it afterwards are
+	/// counted from zero
+	pub(crate) fn forget(&self, ns: NamespaceId, db: DatabaseId, tb: &TableName) {
+		self.changes.lock().remove(&(ns, db, tb.clone()));
+	}
+
+	/// Discards the buffered changes
+	pub(crate) fn clear(&self) {
+		self.changes.lock().clear();
+	}
+}
+
+/// Writes the changes made to the statistics of tables by a transaction
+pub(crate) async fn store(
+	tx: &Transaction,
+	changes: Vec<(NamespaceId, DatabaseId, TableName, TableStatsDelta)>,
+	versionstamp: u128,
+) -> Result<()> {
+	for (ns, db, tb, mut delta) in changes {
+		delta.versionstamp = Some(versionstamp);
+		tx.set(&st::new(ns, db, &tb, Some(Uuid::now_v7())), &delta).await?;
+	}
+	Ok(())
+}
+
+/// Fetches the statistics of a table, as the sum of the compacted entry and
+/// every delta since
+pub(crate) async fn total(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+) -> Result<TableStatsDelta> {
+	let mut total = TableStatsDelta::default();
+	let mut next = Some(st::range(ns, db, tb)?);
+	while let Some(rng) = next {
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		next = res.next;
+		for (_, v) in res.result.iter() {
+			total.merge(TableStatsDelta::kv_decode_value(v, ())?);
+		}
+	}
+	Ok(total)
+}
+
+/// Fetches the statistics of a table, returning them as an object
+pub(crate) async fn fetch(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+) -> Result<Value> {
+	let total = total(tx, ns, db, tb).await?;
+	// Report the entries of every index by name
+	let mut indexes = Object::default();
+	for ix in tx.all_tb_indexes(ns, db, tb, None).await?.iter() {
+		if let Some(entries) = total.indexes.get(&ix.index_id.0) {
+			indexes.insert(ix.name.clone(), Value::from((*entries).max(0)));
+		}
+	}
+	let versionstamp = match total.versionstamp {
+		Some(vs) => Value::try_from(vs)?,
+		None => Value::None,
+	};
+	Ok(Value::from(map! {
+		"rows" => Value::from(total.rows.max(0)),
+		"bytes" => Value::from(total.bytes.max(0)),
+		"indexes" => Value::from(indexes),
+		"versionstamp" => versionstamp,
+	}))
+}
+
+/// Compacts the statistics of every table of a database into a single entry
+/// per table
+#[instrument(level = "trace", target = "surrealdb::core::kvs::stats", skip_all)]
+pub(crate) async fn compact_db(tx: &Transaction, db: &DatabaseDefinition) -> Result<()> {
+	// Fetch all tables
+	let tbs = tx.all_tb(db.namespace_id, db.database_id, None).await?;
+	for tb in tbs.as_ref() {
+		compact(tx, db.namespace_id, db.database_id, &tb.name).await?;
+		yield_now!();
+	}
+	Ok(())
+}
+
+/// Compacts the statistics of a table into a single entry
+async fn compact(tx: &Transaction, ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<()> {
+	let mut total = TableStatsDelta::default();
+	let mut deltas = Vec::new();
+	let mut next = Some(st::range(ns, db, tb)?);
+	while let Some(rng) = next {
+		let res = tx.batch_keys_vals(rng, NORMAL_BATCH_SIZE, None).await?;
+		next = res.next;
+		for (k, v) in res.result.iter() {
+			total.merge(TableStatsDelta::kv_decode_value(v, ())?);
+			deltas.push(k.to_vec());
+		}
+	}
+	// There is nothing to compact
+	if deltas.len() < 2 {
+		return Ok(());
+	}
+	// Forget the entries of indexes which have been removed
+	let indexes = tx.all_tb_indexes(ns, db, tb, None).await?;
+	total.indexes.retain(|ix, _| indexes.iter().any(|i| i.index_id.0 == *ix));
+	for k in deltas {
+		tx.clr(&k).await?;
+	}
+	tx.set(&st::new(ns, db, tb, None), &total).await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn deltas_are_merged() {
+		let mut total = TableStatsDelta::default();
+		total.merge(TableStatsDelta {
+			rows: 2,
+			bytes: 100,
+			indexes: BTreeMap::from([(1, 2), (2, 1)]),
+			versionstamp: Some(5),
+		});
+		total.merge(TableStatsDelta {
+			rows: -1,
+			bytes: -40,
+			indexes: BTreeMap::from([(2, -1)]),
+			versionstamp: Some(3),
+		});
+		assert_eq!(
+			total,
+			TableStatsDelta {
+				rows: 1,
+				bytes: 60,
+				indexes: BTreeMap::from([(1, 2)]),
+				versionstamp: Some(5),
+			}
+		);
+	}
+
+	#[test]
+	fn buffer_skips_empty_changes() {
+		let buffer = TableStatsBuffer::default();
+		let tb = TableName::from("person");
+		buffer.records(NamespaceId(1), DatabaseId(2), &tb, 1, 10);
+		buffer.records(NamespaceId(1), DatabaseId(2), &tb, -1, -10);
+		assert!(buffer.take().is_empty());
+		buffer.index(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), 1);
+		assert_eq!(buffer.take().len(), 1);
+		assert!(buffer.take().is_empty());
+	}
+
+	#[test]
+	fn buffer_forgets_removed_tables() {
+		let buffer = TableStatsBuffer::default();
+		let person = TableName::from("person");
+		let other = TableName::from("other");
+		buffer.records(NamespaceId(1), DatabaseId(2), &person, 3, 30);
+		buffer.records(NamespaceId(1), DatabaseId(2), &other, 1, 10);
+		buffer.forget(NamespaceId(1), DatabaseId(2), &person);
+		buffer.records(NamespaceId(1), DatabaseId(2), &person, 1, 10);
+		let mut changes = buffer.take();
+		changes.sort_by(|a, b| a.2.cmp(&b.2));
+		assert_eq!(changes.len(), 2);
+		assert_eq!((changes[0].2.as_str(), changes[0].3.rows), ("other", 1));
+		assert_eq!((changes[1].2.as_str(), changes[1].3.rows), ("person", 1));
+	}
+}
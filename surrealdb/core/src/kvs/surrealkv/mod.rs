@@ -33,6 +33,8 @@ const TARGET: &str = "surrealdb::core::kvs::surrealkv";
 
 pub struct Datastore {
 	db: Tree,
+	/// The directory the database is stored in
+	path: String,
 	/// Whether the datastore supports transaction versioning
 	versioned: bool,
 	/// Commit coordinator for batching transaction commits when sync=every
@@ -129,6 +131,7 @@ impl Datastore {
 		// Create and return the datastore
 		Ok(Datastore {
 			db,
+			path: path.to_owned(),
 			versioned: config.versioned,
 			commit_coordinator,
 			background_flusher,
@@ -157,6 +160,17 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Create a named snapshot of the database as a SurrealKV checkpoint
+	pub(crate) async fn snapshot(&self, name: &str) -> Result<()> {
+		// Check the name, and the snapshot does not exist yet
+		let target = super::snapshot::prepare_snapshot(&self.path, name)?;
+		info!(target: TARGET, "Creating snapshot at {}", target.display());
+		// Flush the WAL so the checkpoint includes every commit
+		self.db.flush_wal(true)?;
+		self.db.create_checkpoint(&target)?;
+		Ok(())
+	}
+
 	/// Start a new transaction
 	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Box<dyn Transactable>> {
 		// Create a new transaction
@@ -32,6 +32,14 @@ pub(crate) enum TaskLeaseType {
 	EventProcessing,
 	/// Background reclaim of tombstoned namespace/database/index data
 	ReclaimTombstones,
+	/// Moving records matching archival policies into their destination tables
+	Archival,
+	/// Purging soft deleted records once their retention period has passed
+	SoftDeletePurge,
+	/// Evicting records from tables defined with `EVICT`
+	Eviction,
+	/// Validating stored records against their checksums
+	Scrub,
 }
 
 /// Represents a distributed task lease stored in the datastore.
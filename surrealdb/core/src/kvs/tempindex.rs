@@ -0,0 +1,499 @@
+//! Temporary in-memory indexes for repeated table scans.
+//!
+//! When a table is scanned with an equality predicate on a top-level field,
+//! and no defined index applies, the scan is recorded against the shape of the
+//! predicate, which is the table and the field, irrespective of the value
+//! compared against. Scans are tracked by this normalised predicate rather
+//! than by a hash of the whole query plan, so that queries which differ only
+//! in their other clauses, or in the value compared against, share their
+//! statistics and their index. Once the same shape has been scanned often enough, the
+//! next scan builds a temporary index mapping each value of the field to the
+//! records holding it, and later scans look the records up in that index
+//! instead of scanning the whole table. The predicate is always re-applied to
+//! the records which are looked up, so an index only has to find every record
+//! which might match.
+//!
+//! Temporary indexes are held in memory for the lifetime of the datastore, and
+//! are never persisted. They are invalidated as soon as a transaction writes
+//! to their table, and are only ever used by transactions which started after
+//! every write to the table had finished, so that they always reflect the
+//! snapshot of the transaction using them. Writes are only tracked within the
+//! datastore instance, so temporary indexes must not be enabled when several
+//! nodes write to the same storage.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::val::{Object, RecordIdKey, TableName, Value};
+
+/// The maximum number of predicates whose statistics are tracked
+const MAX_TRACKED_PREDICATES: usize = 1024;
+
+type TableKey = (NamespaceId, DatabaseId, TableName);
+
+/// The shape of an equality predicate on a top-level field of a table
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct Predicate {
+	pub ns: NamespaceId,
+	pub db: DatabaseId,
+	pub tb: TableName,
+	pub field: String,
+}
+
+/// What a table scan should do with a predicate
+pub(crate) enum Lookup {
+	/// Look the records up in the temporary index
+	Use(Arc<TemporaryIndex>),
+	/// Build the temporary index, then look the records up in it
+	Build(TemporaryIndexBuilder),
+	/// Scan the table
+	Scan,
+}
+
+/// The temporary indexes of every table in the datastore
+#[derive(Clone, Default)]
+pub(crate) struct TemporaryIndexes {
+	inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+	/// Ticks every time a transaction starts or finishes writing to a table
+	clock: AtomicU64,
+	state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+	tables: HashMap<TableKey, TableState>,
+	predicates: HashMap<Predicate, Entry>,
+	/// Ticks every time a predicate is looked up, to find the least recently
+	/// used index
+	uses: u64,
+}
+
+#[derive(Default)]
+struct TableState {
+	/// The number of transactions currently writing to the table
+	writers: u32,
+	/// The clock tick at which a transaction last started or finished writing
+	/// to the table
+	changed: u64,
+}
+
+#[derive(Default)]
+struct Entry {
+	/// The number of scans since the index was last built or invalidated
+	hits: u64,
+	/// Whether the index was pinned, and is built on the next scan and never
+	/// evicted
+	pinned: bool,
+	/// When the predicate was last looked up
+	used: u64,
+	index: Option<Arc<TemporaryIndex>>,
+}
+
+impl TemporaryIndexes {
+	/// Returns the current tick of the clock, which a transaction records
+	/// before its snapshot is taken
+	pub(crate) fn epoch(&self) -> u64 {
+		self.inner.clock.load(Ordering::SeqCst)
+	}
+
+	fn tick(&self) -> u64 {
+		self.inner.clock.fetch_add(1, Ordering::SeqCst) + 1
+	}
+
+	fn begin_write(&self, table: &TableKey) {
+		let changed = self.tick();
+		let mut state = self.inner.state.lock();
+		let table = state.tables.entry(table.clone()).or_default();
+		table.writers += 1;
+		table.changed = changed;
+	}
+
+	fn end_write(&self, table: &TableKey) {
+		let changed = self.tick();
+		let mut state = self.inner.state.lock();
+		if let Some(table) = state.tables.get_mut(table) {
+			table.writers = table.writers.saturating_sub(1);
+			table.changed = changed;
+		}
+	}
+
+	/// Records a scan of a table with the given predicate, by a transaction
+	/// which started at the given epoch, and decides how the scan should find
+	/// its records
+	pub(crate) fn lookup(&self, predicate: &Predicate, epoch: u64, threshold: u64) -> Lookup {
+		let mut guard = self.inner.state.lock();
+		let state = &mut *guard;
+		// Without a threshold, only pinned indexes are built
+		if threshold == 0 && !state.predicates.get(predicate).is_some_and(|e| e.pinned) {
+			return Lookup::Scan;
+		}
+		// Stop tracking new predicates once too many are tracked
+		if !state.predicates.contains_key(predicate)
+			&& state.predicates.len() >= MAX_TRACKED_PREDICATES
+		{
+			state.predicates.retain(|_, e| e.pinned || e.index.is_some());
+			if state.predicates.len() >= MAX_TRACKED_PREDICATES {
+				return Lookup::Scan;
+			}
+		}
+		let table = state
+			.tables
+			.get(&(predicate.ns, predicate.db, predicate.tb.clone()))
+			.map(|t| (t.writers, t.changed))
+			.unwrap_or_default();
+		// The transaction sees every write to the table, and no write is ongoing
+		let stable = table.0 == 0 && table.1 <= epoch;
+		state.uses += 1;
+		let entry = state.predicates.entry(predicate.clone()).or_default();
+		entry.used = state.uses;
+		if let Some(index) = &entry.index {
+			if index.changed == table.1 {
+				return match stable {
+					true => Lookup::Use(Arc::clone(index)),
+					false => Lookup::Scan,
+				};
+			}
+			// The table was written to since the index was built
+			entry.index = None;
+			entry.hits = 0;
+		}
+		entry.hits += 1;
+		if stable && (entry.pinned || (threshold > 0 && entry.hits >= threshold)) {
+			return Lookup::Build(TemporaryIndexBuilder::new(table.1));
+		}
+		Lookup::Scan
+	}
+
+	/// Installs a built index, unless the table was written to while it was
+	/// being built, or it covers fewer than `min_rows` records and was not
+	/// pinned. Evicts the least recently used unpinned indexes beyond `limit`.
+	pub(crate) fn install(
+		&self,
+		predicate: &Predicate,
+		index: Arc<TemporaryIndex>,
+		min_rows: usize,
+		limit: usize,
+	) -> bool {
+		let mut guard = self.inner.state.lock();
+		let state = &mut *guard;
+		let changed = state
+			.tables
+			.get(&(predicate.ns, predicate.db, predicate.tb.clone()))
+			.map(|t| (t.writers, t.changed))
+			.unwrap_or_default();
+		let Some(entry) = state.predicates.get_mut(predicate) else {
+			return false;
+		};
+		// Scanning the table is cheap enough without an index
+		if !entry.pinned && index.rows < min_rows {
+			entry.hits = 0;
+			return false;
+		}
+		if changed != (0, index.changed) {
+			return false;
+		}
+		entry.index = Some(index);
+		// Evict the least recently used indexes
+		loop {
+			let built = state.predicates.values().filter(|e| e.index.is_some()).count();
+			if built <= limit {
+				break;
+			}
+			let Some(lru) = state
+				.predicates
+				.values_mut()
+				.filter(|e| e.index.is_some() && !e.pinned)
+				.min_by_key(|e| e.used)
+			else {
+				break;
+			};
+			lru.index = None;
+			lru.hits = 0;
+		}
+		true
+	}
+
+	/// Records that an index could not be built for a predicate, so that it is
+	/// not attempted again until the threshold has been reached once more
+	pub(crate) fn discard(&self, predicate: &Predicate) {
+		if let Some(entry) = self.inner.state.lock().predicates.get_mut(predicate) {
+			entry.hits = 0;
+		}
+	}
+
+	/// Pins the index of a predicate, so that it is built on the next scan
+	/// and never evicted
+	pub(crate) fn pin(&self, predicate: Predicate) {
+		self.inner.state.lock().predicates.entry(predicate).or_default().pinned = true;
+	}
+
+	/// Unpins the index of a predicate, returning whether it was pinned
+	pub(crate) fn unpin(&self, predicate: &Predicate) -> bool {
+		let mut state = self.inner.state.lock();
+		match state.predicates.get_mut(predicate) {
+			Some(entry) => std::mem::replace(&mut entry.pinned, false),
+			None => false,
+		}
+	}
+
+	/// Describes the tracked predicates of a table, keyed by field
+	pub(crate) fn describe(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+	) -> Vec<(String, Value)> {
+		let state = self.inner.state.lock();
+		let changed =
+			state.tables.get(&(ns, db, tb.clone())).map(|t| t.changed).unwrap_or_default();
+		let mut out: Vec<(String, Value)> = state
+			.predicates
+			.iter()
+			.filter(|(p, _)| p.ns == ns && p.db == db && &p.tb == tb)
+			.map(|(p, e)| {
+				// An index is stale once the table has been written to
+				let index = e.index.as_ref().filter(|i| i.changed == changed);
+				let mut obj = Object::default();
+				obj.insert("hits", Value::from(e.hits as i64));
+				obj.insert("pinned", Value::from(e.pinned));
+				obj.insert("built", Value::from(index.is_some()));
+				if let Some(index) = index {
+					obj.insert("rows", Value::from(index.rows as i64));
+					obj.insert("values", Value::from(index.entries.len() as i64));
+				}
+				(p.field.clone(), Value::from(obj))
+			})
+			.collect();
+		out.sort_by(|a, b| a.0.cmp(&b.0));
+		out
+	}
+}
+
+/// The tables written to by a transaction, whose temporary indexes can not be
+/// used until the transaction has finished
+pub(crate) struct TableWrites {
+	indexes: TemporaryIndexes,
+	/// The tick of the clock when the transaction started
+	epoch: u64,
+	tables: Mutex<Vec<TableKey>>,
+}
+
+impl TableWrites {
+	pub(crate) fn new(indexes: TemporaryIndexes, epoch: u64) -> Self {
+		Self {
+			indexes,
+			epoch,
+			tables: Mutex::new(Vec::new()),
+		}
+	}
+
+	pub(crate) fn indexes(&self) -> &TemporaryIndexes {
+		&self.indexes
+	}
+
+	pub(crate) fn epoch(&self) -> u64 {
+		self.epoch
+	}
+
+	/// Records a write to a table
+	pub(crate) fn write(&self, ns: NamespaceId, db: DatabaseId, tb: &TableName) {
+		let mut tables = self.tables.lock();
+		if !tables.iter().any(|(n, d, t)| *n == ns && *d == db && t == tb) {
+			let table = (ns, db, tb.clone());
+			self.indexes.begin_write(&table);
+			tables.push(table);
+		}
+	}
+
+	/// Records that the transaction has committed or cancelled its writes
+	pub(crate) fn finish(&self) {
+		for table in self.tables.lock().drain(..) {
+			self.indexes.end_write(&table);
+		}
+	}
+}
+
+impl Drop for TableWrites {
+	fn drop(&mut self) {
+		self.finish();
+	}
+}
+
+/// A value which can be looked up in a temporary index.
+///
+/// Only values whose equality is structural are indexed, so that a lookup
+/// finds every record which `=` would match.
+#[derive(Clone, Debug, Hash, PartialEq)]
+struct IndexKey(Value);
+
+// Equality is reflexive for every value which can be an index key
+impl Eq for IndexKey {}
+
+impl IndexKey {
+	fn new(value: &Value) -> Option<Self> {
+		match value {
+			Value::String(_)
+			| Value::Bool(_)
+			| Value::Uuid(_)
+			| Value::Datetime(_)
+			| Value::Duration(_) => Some(Self(value.clone())),
+			Value::RecordId(rid)
+				if matches!(
+					rid.key,
+					RecordIdKey::Number(_) | RecordIdKey::String(_) | RecordIdKey::Uuid(_)
+				) =>
+			{
+				Some(Self(value.clone()))
+			}
+			_ => None,
+		}
+	}
+}
+
+/// An in-memory index of the values of a top-level field of a table
+pub(crate) struct TemporaryIndex {
+	/// The tick at which the table was last changed when the index was built
+	changed: u64,
+	/// The number of records in the table
+	rows: usize,
+	/// The ids of the records holding each value, in key order
+	entries: HashMap<IndexKey, Vec<RecordIdKey>>,
+}
+
+impl TemporaryIndex {
+	/// Returns whether the index can look up records by the given value
+	pub(crate) fn supports(value: &Value) -> bool {
+		IndexKey::new(value).is_some()
+	}
+
+	/// Returns the ids of the records which might hold the given value, in key
+	/// order, or `None` if the value can not be looked up
+	pub(crate) fn get(&self, value: &Value) -> Option<&[RecordIdKey]> {
+		let key = IndexKey::new(value)?;
+		Some(self.entries.get(&key).map(Vec::as_slice).unwrap_or_default())
+	}
+}
+
+/// Builds a temporary index from a scan of its table in key order
+pub(crate) struct TemporaryIndexBuilder {
+	index: TemporaryIndex,
+}
+
+impl TemporaryIndexBuilder {
+	fn new(changed: u64) -> Self {
+		Self {
+			index: TemporaryIndex {
+				changed,
+				rows: 0,
+				entries: HashMap::new(),
+			},
+		}
+	}
+
+	/// Adds the value of the field of a record. Returns `false` if the value
+	/// could match a lookup without being equal to it, in which case the
+	/// index can not be built.
+	pub(crate) fn push(&mut self, id: RecordIdKey, value: &Value) -> bool {
+		self.index.rows += 1;
+		if let Some(key) = IndexKey::new(value) {
+			self.index.entries.entry(key).or_default().push(id);
+		}
+		// A regex is equal to every string which it matches
+		!matches!(value, Value::Regex(_))
+	}
+
+	pub(crate) fn finish(self) -> Arc<TemporaryIndex> {
+		Arc::new(self.index)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn predicate(field: &str) -> Predicate {
+		Predicate {
+			ns: NamespaceId(1),
+			db: DatabaseId(2),
+			tb: TableName::from("person"),
+			field: field.to_owned(),
+		}
+	}
+
+	fn build(lookup: Lookup) -> Arc<TemporaryIndex> {
+		let Lookup::Build(mut builder) = lookup else {
+			panic!("expected the index to be built");
+		};
+		assert!(builder.push(RecordIdKey::Number(1), &Value::from("a")));
+		assert!(builder.push(RecordIdKey::Number(2), &Value::from("b")));
+		assert!(builder.push(RecordIdKey::Number(3), &Value::from("a")));
+		builder.finish()
+	}
+
+	#[test]
+	fn builds_after_threshold() {
+		let ti = TemporaryIndexes::default();
+		let p = predicate("name");
+		assert!(matches!(ti.lookup(&p, ti.epoch(), 2), Lookup::Scan));
+		let index = build(ti.lookup(&p, ti.epoch(), 2));
+		assert!(ti.install(&p, index, 0, 8));
+		let Lookup::Use(index) = ti.lookup(&p, ti.epoch(), 2) else {
+			panic!("expected the index to be used");
+		};
+		let ids = index.get(&Value::from("a")).unwrap();
+		assert_eq!(ids, &[RecordIdKey::Number(1), RecordIdKey::Number(3)]);
+		assert!(index.get(&Value::from("c")).unwrap().is_empty());
+		assert!(index.get(&Value::from(1i64)).is_none());
+	}
+
+	#[test]
+	fn writes_invalidate_index() {
+		let ti = TemporaryIndexes::default();
+		let p = predicate("name");
+		let index = build(ti.lookup(&p, ti.epoch(), 1));
+		assert!(ti.install(&p, index, 0, 8));
+		// A transaction started before the write finished can not use it
+		let before = ti.epoch();
+		let writes = TableWrites::new(ti.clone(), before);
+		writes.write(p.ns, p.db, &p.tb);
+		assert!(matches!(ti.lookup(&p, ti.epoch(), 1), Lookup::Scan));
+		writes.finish();
+		assert!(matches!(ti.lookup(&p, before, 1), Lookup::Scan));
+		// A transaction started afterwards rebuilds it
+		assert!(matches!(ti.lookup(&p, ti.epoch(), 1), Lookup::Build(_)));
+	}
+
+	#[test]
+	fn rejects_index_built_during_write() {
+		let ti = TemporaryIndexes::default();
+		let p = predicate("name");
+		let lookup = ti.lookup(&p, ti.epoch(), 1);
+		let writes = TableWrites::new(ti.clone(), ti.epoch());
+		writes.write(p.ns, p.db, &p.tb);
+		drop(writes);
+		assert!(!ti.install(&p, build(lookup), 0, 8));
+	}
+
+	#[test]
+	fn pinning() {
+		let ti = TemporaryIndexes::default();
+		let (a, b) = (predicate("a"), predicate("b"));
+		// Pinned indexes are built even when disabled, and never evicted
+		ti.pin(a.clone());
+		assert!(ti.install(&a, build(ti.lookup(&a, ti.epoch(), 0)), 10, 0));
+		assert!(matches!(ti.lookup(&b, ti.epoch(), 0), Lookup::Scan));
+		assert!(ti.install(&b, build(ti.lookup(&b, ti.epoch(), 1)), 0, 1));
+		assert!(matches!(ti.lookup(&a, ti.epoch(), 1), Lookup::Use(_)));
+		assert!(matches!(ti.lookup(&b, ti.epoch(), 1), Lookup::Build(_)));
+		assert!(ti.unpin(&a));
+		assert!(!ti.unpin(&a));
+	}
+}
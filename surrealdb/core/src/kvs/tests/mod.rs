@@ -30,6 +30,8 @@ mod multiwriter_same_keys_conflict;
 mod raw;
 #[cfg(feature = "kv-mem")]
 mod reclaim_test;
+#[cfg(feature = "kv-mem")]
+mod scrub_test;
 mod snapshot;
 #[cfg(feature = "kv-mem")]
 mod tx_cache_test;
@@ -0,0 +1,186 @@
+//! Tests for record checksums and the background scrubber.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use surrealdb_types::ToSql;
+
+use crate::catalog::Record;
+use crate::catalog::providers::DatabaseProvider;
+use crate::cnf::ConfigMap;
+use crate::dbs::{Capabilities, Session};
+use crate::key::record;
+use crate::key::table::ck;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+use crate::kvs::{Datastore, KVKey, KVValue};
+use crate::val::{Object, RecordIdKey, TableName, Value};
+
+async fn checksummed_ds(quarantine: bool) -> Datastore {
+	test_ds(true, quarantine).await
+}
+
+async fn test_ds(checksums: bool, quarantine: bool) -> Datastore {
+	let config = ConfigMap::empty()
+		.with_key_value("record_checksums", checksums.to_string())
+		.with_key_value("scrub_quarantine", quarantine.to_string());
+	let ds = Datastore::builder()
+		.with_config(config)
+		.with_capabilities(Capabilities::all())
+		.build_with_path("memory")
+		.await
+		.unwrap();
+	ds.bootstrap().await.unwrap();
+	ds.execute(
+		"DEFINE NAMESPACE test; DEFINE DATABASE test; CREATE thing:1 SET v = 1; CREATE thing:2 SET v = 2;",
+		&Session::owner().with_ns("test").with_db("test"),
+		None,
+	)
+	.await
+	.unwrap();
+	ds
+}
+
+/// Overwrites the stored value of a record, bypassing its checksum
+async fn corrupt(ds: &Datastore, id: i64) {
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	let db = tx.get_db_by_name("test", "test", None).await.unwrap().unwrap();
+	let tb = TableName::from("thing");
+	let key = record::new(db.namespace_id, db.database_id, &tb, &RecordIdKey::Number(id))
+		.encode_key()
+		.unwrap();
+	let mut val = tx.get(&key, None).await.unwrap().unwrap();
+	let last = val.len() - 1;
+	val[last] ^= 0xff;
+	tx.set(&key, &val).await.unwrap();
+	tx.commit().await.unwrap();
+}
+
+/// Overwrites the stored value of a record with a valid record, bypassing
+/// its checksum
+async fn overwrite(ds: &Datastore, id: i64, data: Object) {
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	let db = tx.get_db_by_name("test", "test", None).await.unwrap().unwrap();
+	let tb = TableName::from("thing");
+	let key = record::new(db.namespace_id, db.database_id, &tb, &RecordIdKey::Number(id))
+		.encode_key()
+		.unwrap();
+	let val = Record::new(Value::Object(data)).kv_encode_value().unwrap();
+	tx.set(&key, &val).await.unwrap();
+	tx.commit().await.unwrap();
+}
+
+/// Counts the stored checksums of the records of the test table
+async fn checksums(ds: &Datastore) -> usize {
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let db = tx.get_db_by_name("test", "test", None).await.unwrap().unwrap();
+	let tb = TableName::from("thing");
+	let rng = ck::range(db.namespace_id, db.database_id, &tb).unwrap();
+	let count = tx.getr(rng, None).await.unwrap().len();
+	tx.cancel().await.unwrap();
+	count
+}
+
+#[tokio::test]
+async fn records_are_stored_with_checksums() {
+	let ds = checksummed_ds(false).await;
+	assert_eq!(checksums(&ds).await, 2);
+	// Unchanged records are not reported
+	ds.scrub_process(Duration::from_secs(60)).await.unwrap();
+	assert!(ds.corrupt_records().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn corrupt_records_are_reported() {
+	let ds = checksummed_ds(false).await;
+	corrupt(&ds, 1).await;
+	ds.scrub_process(Duration::from_secs(60)).await.unwrap();
+	let found = ds.corrupt_records().await.unwrap();
+	assert_eq!(found.len(), 1);
+	assert_eq!(found[0].namespace, "test");
+	assert_eq!(found[0].record, "thing:1");
+	assert_eq!(found[0].quarantined, None);
+	assert_eq!(ds.health_report().await.corrupt_records, 1);
+	// The record is left in place
+	let mut res = ds
+		.execute(
+			"SELECT VALUE id FROM thing",
+			&Session::owner().with_ns("test").with_db("test"),
+			None,
+		)
+		.await
+		.unwrap();
+	assert_eq!(res.remove(0).result.unwrap().to_sql(), "[thing:1, thing:2]");
+	// The record is reported by INFO FOR ROOT
+	let mut res = ds
+		.execute(
+			"(INFO FOR ROOT).corrupt_records.map(|$r| [$r.record, $r.quarantined])",
+			&Session::owner(),
+			None,
+		)
+		.await
+		.unwrap();
+	assert_eq!(res.remove(0).result.unwrap().to_sql(), "[['thing:1', false]]");
+	ds.clear_corrupt_records().await.unwrap();
+	assert!(ds.corrupt_records().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn corrupt_records_are_quarantined() {
+	let ds = checksummed_ds(true).await;
+	let sess = Session::owner().with_ns("test").with_db("test");
+	ds.execute("DEFINE INDEX v ON thing FIELDS v UNIQUE", &sess, None).await.unwrap();
+	// Corrupt the record without changing its indexed field
+	let data = BTreeMap::from([("v", Value::from(2)), ("w", Value::from(3))]);
+	overwrite(&ds, 2, data.into()).await;
+	ds.scrub_process(Duration::from_secs(60)).await.unwrap();
+	let found = ds.corrupt_records().await.unwrap();
+	assert_eq!(found.len(), 1);
+	assert_eq!(found[0].record, "thing:2");
+	assert!(found[0].quarantined.is_some());
+	// The record is moved out of its table
+	let mut res = ds.execute("SELECT VALUE id FROM thing", &sess, None).await.unwrap();
+	let ids = res.remove(0).result.unwrap();
+	assert_eq!(ids.to_sql(), "[thing:1]");
+	// The record is deleted along with its index entries
+	let mut res = ds.execute("CREATE thing:3 SET v = 2", &sess, None).await.unwrap();
+	res.remove(0).result.unwrap();
+}
+
+#[tokio::test]
+async fn undecodable_records_are_not_quarantined() {
+	let ds = checksummed_ds(true).await;
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	let db = tx.get_db_by_name("test", "test", None).await.unwrap().unwrap();
+	let tb = TableName::from("thing");
+	let key = record::new(db.namespace_id, db.database_id, &tb, &RecordIdKey::Number(2))
+		.encode_key()
+		.unwrap();
+	tx.set(&key, &vec![0xff; 4]).await.unwrap();
+	tx.commit().await.unwrap();
+	ds.scrub_process(Duration::from_secs(60)).await.unwrap();
+	// The record is reported, but left in place
+	let found = ds.corrupt_records().await.unwrap();
+	assert_eq!(found.len(), 1);
+	assert_eq!(found[0].quarantined, None);
+	assert_eq!(checksums(&ds).await, 2);
+}
+
+#[tokio::test]
+async fn checksums_are_a_datastore_setting() {
+	let ds = test_ds(false, false).await;
+	let sess = Session::owner().with_ns("test").with_db("test");
+	assert_eq!(checksums(&ds).await, 0);
+	// Checksums enabled by another node apply to the writes of this node
+	ds.set_record_checksums(true).await.unwrap();
+	ds.execute("CREATE thing:3 SET v = 3", &sess, None).await.unwrap();
+	assert_eq!(checksums(&ds).await, 1);
+	// The scrubber writes the missing checksums
+	ds.scrub_process(Duration::from_secs(60)).await.unwrap();
+	assert_eq!(checksums(&ds).await, 3);
+	assert!(ds.corrupt_records().await.unwrap().is_empty());
+	// Once disabled, records are written without checksums
+	ds.set_record_checksums(false).await.unwrap();
+	ds.execute("CREATE thing:4 SET v = 4", &sess, None).await.unwrap();
+	assert_eq!(checksums(&ds).await, 3);
+}
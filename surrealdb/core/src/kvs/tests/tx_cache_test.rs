@@ -34,6 +34,7 @@ async fn setup_tx_with_ns_db() -> (Datastore, crate::kvs::Transaction, Namespace
 		database_id: DatabaseId(1),
 		name: "test".into(),
 		strict: false,
+		capabilities: None,
 		comment: None,
 		changefeed: None,
 	};
@@ -293,6 +294,7 @@ async fn test_single_tx_cache_invalidation_on_db_put_and_del() {
 		database_id: DatabaseId(1),
 		name: "testdb".into(),
 		strict: false,
+		capabilities: None,
 		comment: None,
 		changefeed: None,
 	};
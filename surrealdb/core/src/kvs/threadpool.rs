@@ -1,8 +1,51 @@
 #![cfg(any(feature = "kv-mem", feature = "kv-rocksdb", feature = "kv-surrealkv"))]
 
+use crate::cnf::{Config, ConfigMap};
+
+/// Configuration for the shared KVS blocking threadpool.
+///
+/// Parsed from the `kvs_threadpool_size` and `kvs_threadpool_pinning` keys.
+/// The server populates these from the `SURREAL_KVS_THREADPOOL_SIZE` and
+/// `SURREAL_KVS_THREADPOOL_PINNING` environment variables through
+/// `ConfigMap::from_env()`, and embedders can set them with
+/// `Datastore::builder().with_blocking_threads(...)` and
+/// `with_core_pinning(...)`.
+#[derive(Debug, Clone)]
+pub(super) struct ThreadPoolConfig {
+	/// The number of worker threads in the pool
+	pub size: usize,
+	/// Whether to pin one worker to each core, or `None` to decide based
+	/// on the pool size and the core count
+	pub pinning: Option<bool>,
+}
+
+impl Default for ThreadPoolConfig {
+	fn default() -> Self {
+		Self {
+			#[cfg(not(target_family = "wasm"))]
+			size: *crate::cnf::KVS_THREADPOOL_SIZE,
+			#[cfg(target_family = "wasm")]
+			size: 1,
+			pinning: None,
+		}
+	}
+}
+
+impl Config for ThreadPoolConfig {
+	fn parse(&mut self, map: &ConfigMap) {
+		// Values below the minimum are ignored, as for the environment variable
+		map.parse_key_with("kvs_threadpool_size", &mut self.size, |x| {
+			x.parse::<usize>().ok().filter(|n| *n >= 4)
+		})
+		.parse_key_option("kvs_threadpool_pinning", &mut self.pinning);
+	}
+}
+
 /// Create the shared KVS blocking threadpool.
 ///
-/// Size and pinning behaviour are driven by [`crate::cnf::KVS_THREADPOOL_SIZE`]:
+/// The pool is process-wide, so only the configuration of the first datastore
+/// which is started takes effect. Size and pinning behaviour are driven by
+/// [`ThreadPoolConfig`], defaulting to [`crate::cnf::KVS_THREADPOOL_SIZE`]:
 ///
 /// * When the resolved size matches the host's logical core count *and* that count is at least 16,
 ///   the pool uses `affinitypool::thread_per_core` so each worker is pinned to a dedicated core.
@@ -10,16 +53,20 @@
 /// * When the size is below 16 on a small-core host (the computed default floor), the pool is sized
 ///   to 16 unpinned workers — enough slack to absorb short bursts of blocking I/O without occupying
 ///   every core.
-/// * When `SURREAL_KVS_THREADPOOL_SIZE` is set to an explicit value that does not equal the core
-///   count (oversubscription or undersubscription), the pool drops pinning and uses that exact
-///   worker count.
-pub(super) fn initialise() {
+/// * When the size is set to an explicit value that does not equal the core count
+///   (oversubscription or undersubscription), the pool drops pinning and uses that exact worker
+///   count.
+/// * When pinning is explicitly enabled, one pinned worker is created per core regardless of the
+///   size, and when explicitly disabled the workers are never pinned.
+pub(super) fn initialise(config: &ConfigMap) {
 	// Create the threadpool and ignore errors
 	#[cfg(not(target_family = "wasm"))]
 	{
-		// Resolve the configured pool size (env-overridable; default
-		// computed from `num_cpus::get()` with a 16-thread floor).
-		let threads = *crate::cnf::KVS_THREADPOOL_SIZE;
+		// Resolve the configured pool size and pinning
+		let ThreadPoolConfig {
+			size: threads,
+			pinning,
+		} = config.load();
 		// Cache the host's logical core count once so the pinning
 		// decision is consistent with the size resolution above.
 		let cores = num_cpus::get();
@@ -30,7 +77,8 @@ pub(super) fn initialise() {
 		// over/under-subscription drops pinning, since pinning a count
 		// other than `num_cpus` is either impossible (too many) or
 		// leaves cores unused (too few).
-		let builder = if threads == cores && cores >= 16 {
+		let pinned = pinning.unwrap_or(threads == cores && cores >= 16);
+		let builder = if pinned {
 			builder.thread_per_core(true)
 		} else {
 			builder.worker_threads(threads)
@@ -38,4 +86,29 @@ pub(super) fn initialise() {
 		// Create the threadpool and ignore errors
 		let _ = builder.build().build_global();
 	}
+	#[cfg(target_family = "wasm")]
+	let _ = config;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_threadpool_config() {
+		let map = ConfigMap::empty()
+			.with_key_value("kvs_threadpool_size", "8")
+			.with_key_value("kvs_threadpool_pinning", "false");
+		let config: ThreadPoolConfig = map.load();
+		assert_eq!(config.size, 8);
+		assert_eq!(config.pinning, Some(false));
+	}
+
+	#[test]
+	fn ignores_threadpool_size_below_minimum() {
+		let map = ConfigMap::empty().with_key_value("kvs_threadpool_size", "2");
+		let config: ThreadPoolConfig = map.load();
+		assert_eq!(config.size, ThreadPoolConfig::default().size);
+		assert_eq!(config.pinning, None);
+	}
 }
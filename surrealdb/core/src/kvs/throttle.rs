@@ -0,0 +1,222 @@
+//! Per-table write throttling.
+//!
+//! Every table defined with a `THROTTLE` clause is given a token bucket in the
+//! datastore's [`Throttles`], which refills at the defined rate and holds at
+//! most the defined burst. Writes take a token from the bucket while one is
+//! available. Once the bucket is empty, writes are queued, and the rate of the
+//! table is shared equally between the sessions which are waiting, so that a
+//! single busy session can not hold back the writes of every other session. A
+//! write which would have to wait longer than the configured maximum is
+//! rejected instead.
+//!
+//! A queued write is not held back within its transaction, which would keep
+//! the transaction open while waiting. Instead, the session waits until its
+//! queued writes can proceed before it runs its next statement. Buckets which
+//! have drained their queue and refilled are forgotten, as they would be
+//! created again in the same state.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use uuid::Uuid;
+use web_time::Instant;
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::expr::Throttle;
+use crate::val::TableName;
+
+/// Whether a write to a throttled table can proceed
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Admission {
+	/// The write can proceed immediately
+	Now,
+	/// The write can proceed once the duration has elapsed
+	After(Duration),
+	/// The write would have to wait for too long, and is rejected
+	Rejected,
+}
+
+/// The write throttles of every throttled table in the datastore
+#[derive(Clone, Default)]
+pub(crate) struct Throttles {
+	buckets: Arc<Mutex<HashMap<(NamespaceId, DatabaseId, TableName), Bucket>>>,
+}
+
+impl Throttles {
+	/// Asks the throttle of a table to admit a write from a session
+	pub(crate) fn admit(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		throttle: Throttle,
+		session: Option<Uuid>,
+		max_wait: Duration,
+	) -> Admission {
+		let now = Instant::now();
+		let mut buckets = self.buckets.lock();
+		let bucket =
+			buckets.entry((ns, db, tb.clone())).or_insert_with(|| Bucket::new(throttle, now));
+		// Start afresh if the table was redefined with other limits
+		if bucket.throttle != throttle {
+			*bucket = Bucket::new(throttle, now);
+		}
+		bucket.admit(now, session, max_wait)
+	}
+
+	/// Returns how long a session has to wait until the writes which it has
+	/// queued on every throttled table can proceed
+	pub(crate) fn delay(&self, session: Option<Uuid>) -> Option<Duration> {
+		let now = Instant::now();
+		let mut buckets = self.buckets.lock();
+		buckets.retain(|_, bucket| !bucket.is_idle(now));
+		buckets
+			.values()
+			.filter_map(|bucket| bucket.queued.get(&session))
+			.max()
+			.and_then(|slot| slot.checked_duration_since(now))
+			.filter(|wait| !wait.is_zero())
+	}
+}
+
+struct Bucket {
+	/// The limits of the table
+	throttle: Throttle,
+	/// The number of writes which can proceed immediately
+	tokens: f64,
+	/// The time from which the bucket has yet to be refilled
+	refilled: Instant,
+	/// The time at which the last queued write of each session can proceed
+	queued: HashMap<Option<Uuid>, Instant>,
+}
+
+impl Bucket {
+	fn new(throttle: Throttle, now: Instant) -> Self {
+		Self {
+			throttle,
+			tokens: throttle.burst as f64,
+			refilled: now,
+			queued: HashMap::new(),
+		}
+	}
+
+	/// Whether the bucket is in the state in which it would be created
+	fn is_idle(&self, now: Instant) -> bool {
+		let Some(elapsed) = now.checked_duration_since(self.refilled) else {
+			return false;
+		};
+		let tokens = self.tokens + elapsed.as_secs_f64() * self.throttle.rate as f64;
+		tokens >= self.throttle.burst as f64 && self.queued.values().all(|slot| *slot <= now)
+	}
+
+	fn admit(&mut self, now: Instant, session: Option<Uuid>, max_wait: Duration) -> Admission {
+		// Refill the bucket for the time which has passed
+		if let Some(elapsed) = now.checked_duration_since(self.refilled) {
+			let tokens = self.tokens + elapsed.as_secs_f64() * self.throttle.rate as f64;
+			self.tokens = tokens.min(self.throttle.burst as f64);
+			self.refilled = now;
+		}
+		// Forget the sessions whose queued writes have all proceeded
+		self.queued.retain(|_, slot| *slot > now);
+		// Proceed immediately if nothing is queued
+		if self.queued.is_empty() && self.tokens >= 1.0 {
+			self.tokens -= 1.0;
+			return Admission::Now;
+		}
+		// Share the rate equally between the waiting sessions
+		let sessions = self.queued.len() + usize::from(!self.queued.contains_key(&session));
+		let interval = Duration::from_secs_f64(sessions as f64 / self.throttle.rate as f64);
+		let slot = self.queued.get(&session).map_or(now, |slot| (*slot).max(now)) + interval;
+		let wait = slot.duration_since(now);
+		if wait > max_wait {
+			return Admission::Rejected;
+		}
+		self.queued.insert(session, slot);
+		// The bucket refills once the queue has drained
+		self.tokens = 0.0;
+		self.refilled = self.refilled.max(slot);
+		Admission::After(wait)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const MAX_WAIT: Duration = Duration::from_secs(1);
+
+	fn bucket(rate: u64, burst: u64, now: Instant) -> Bucket {
+		Bucket::new(
+			Throttle {
+				rate,
+				burst,
+			},
+			now,
+		)
+	}
+
+	#[test]
+	fn admits_burst_then_queues() {
+		let now = Instant::now();
+		let mut b = bucket(10, 2, now);
+		assert_eq!(b.admit(now, None, MAX_WAIT), Admission::Now);
+		assert_eq!(b.admit(now, None, MAX_WAIT), Admission::Now);
+		assert_eq!(b.admit(now, None, MAX_WAIT), Admission::After(Duration::from_millis(100)));
+		assert_eq!(b.admit(now, None, MAX_WAIT), Admission::After(Duration::from_millis(200)));
+	}
+
+	#[test]
+	fn shares_rate_between_sessions() {
+		let now = Instant::now();
+		let (a, b) = (Some(Uuid::new_v4()), Some(Uuid::new_v4()));
+		let mut bk = bucket(10, 1, now);
+		assert_eq!(bk.admit(now, a, MAX_WAIT), Admission::Now);
+		assert_eq!(bk.admit(now, a, MAX_WAIT), Admission::After(Duration::from_millis(100)));
+		assert_eq!(bk.admit(now, a, MAX_WAIT), Admission::After(Duration::from_millis(200)));
+		// A second session is not queued behind every write of the first
+		assert_eq!(bk.admit(now, b, MAX_WAIT), Admission::After(Duration::from_millis(200)));
+	}
+
+	#[test]
+	fn rejects_beyond_max_wait() {
+		let now = Instant::now();
+		let mut b = bucket(1, 1, now);
+		assert_eq!(b.admit(now, None, MAX_WAIT), Admission::Now);
+		assert_eq!(b.admit(now, None, MAX_WAIT), Admission::After(MAX_WAIT));
+		assert_eq!(b.admit(now, None, MAX_WAIT), Admission::Rejected);
+		// The bucket refills once the queue has drained
+		let later = now + Duration::from_secs(2);
+		assert_eq!(b.admit(later, None, MAX_WAIT), Admission::Now);
+	}
+
+	#[test]
+	fn forgets_idle_buckets() {
+		let now = Instant::now();
+		let mut b = bucket(10, 1, now);
+		assert!(b.is_idle(now));
+		assert_eq!(b.admit(now, None, MAX_WAIT), Admission::Now);
+		assert!(!b.is_idle(now));
+		assert_eq!(b.admit(now, None, MAX_WAIT), Admission::After(Duration::from_millis(100)));
+		assert!(!b.is_idle(now + Duration::from_millis(150)));
+		assert!(b.is_idle(now + Duration::from_millis(200)));
+	}
+
+	#[test]
+	fn delays_sessions_with_queued_writes() {
+		let throttles = Throttles::default();
+		let (ns, db, tb) = (NamespaceId(1), DatabaseId(2), TableName::from("person"));
+		let throttle = Throttle {
+			rate: 1,
+			burst: 1,
+		};
+		let (a, b) = (Some(Uuid::new_v4()), Some(Uuid::new_v4()));
+		let admit = |session| throttles.admit(ns, db, &tb, throttle, session, MAX_WAIT);
+		assert_eq!(admit(a), Admission::Now);
+		assert_eq!(throttles.delay(a), None);
+		assert!(matches!(admit(a), Admission::After(_)));
+		assert!(throttles.delay(a).is_some());
+		assert_eq!(throttles.delay(b), None);
+	}
+}
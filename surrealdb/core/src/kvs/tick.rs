@@ -0,0 +1,221 @@
+//! Per-namespace scheduling of background maintenance.
+//!
+//! The maintenance run by [`Datastore::changefeed_process`] garbage collects
+//! changefeeds, live query events and idempotency keys, and compacts the
+//! statistics of tables. It works through one namespace at a time, each in a
+//! transaction of its own, so that a failure in one namespace is recorded and
+//! logged without holding back the maintenance of the others.
+//!
+//! Each pass starts from the namespace after the one which the previous pass
+//! started from, and every namespace is given the same time budget. Once the
+//! budget of a namespace is spent, its remaining databases are deferred to the
+//! next pass, which resumes from the first of them. One tenant with a large
+//! backlog of changes to clean up therefore can not starve the others.
+//!
+//! [`Datastore::changefeed_process`]: crate::kvs::Datastore::changefeed_process
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::catalog::{DatabaseDefinition, DatabaseId, NamespaceDefinition, NamespaceId};
+
+/// The background maintenance of a namespace, as returned by
+/// [`Datastore::tick_metrics`](crate::kvs::Datastore::tick_metrics)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NamespaceTickMetrics {
+	/// The number of passes which have maintained the namespace
+	pub runs: u64,
+	/// The number of passes which failed to maintain the namespace
+	pub errors: u64,
+	/// The number of databases which have been maintained
+	pub databases: u64,
+	/// The number of passes which ran out of budget, and deferred some of the
+	/// databases of the namespace to the next pass
+	pub deferred: u64,
+	/// How long the last pass spent on the namespace
+	pub last_duration: Duration,
+	/// How long every pass together spent on the namespace
+	pub total_duration: Duration,
+	/// The error of the last pass, if it failed
+	pub last_error: Option<String>,
+}
+
+/// The outcome of the maintenance of a namespace in a single pass
+pub(crate) struct NamespaceTick {
+	/// The number of databases which were maintained
+	pub(crate) databases: u64,
+	/// Whether some of the databases were deferred to the next pass
+	pub(crate) deferred: bool,
+}
+
+/// Schedules the background maintenance of the namespaces of a datastore
+#[derive(Default)]
+pub(crate) struct TickScheduler {
+	/// The number of passes which have started
+	passes: AtomicUsize,
+	/// The database from which the next pass resumes, in each namespace
+	/// which ran out of budget or failed
+	resume: Mutex<HashMap<NamespaceId, DatabaseId>>,
+	/// The metrics of each namespace, by name
+	metrics: Mutex<BTreeMap<String, NamespaceTickMetrics>>,
+}
+
+impl TickScheduler {
+	/// Orders the namespaces of a new pass, starting from the namespace after
+	/// the one which the previous pass started from
+	///
+	/// The state of namespaces which have been removed is forgotten.
+	pub(crate) fn start<'a>(
+		&self,
+		nss: &'a [NamespaceDefinition],
+	) -> impl Iterator<Item = &'a NamespaceDefinition> {
+		self.resume.lock().retain(|ns, _| nss.iter().any(|n| n.namespace_id == *ns));
+		self.metrics.lock().retain(|name, _| nss.iter().any(|n| n.name.as_str() == name.as_str()));
+		let first = match nss.len() {
+			0 => 0,
+			len => self.passes.fetch_add(1, Ordering::Relaxed) % len,
+		};
+		nss[first..].iter().chain(nss[..first].iter())
+	}
+
+	/// Orders the databases of a namespace, starting from the database which
+	/// the namespace was deferred at, if any
+	pub(crate) fn databases<'a>(
+		&self,
+		ns: NamespaceId,
+		dbs: &'a [DatabaseDefinition],
+	) -> impl Iterator<Item = &'a DatabaseDefinition> {
+		let first = self
+			.resume
+			.lock()
+			.get(&ns)
+			.and_then(|db| dbs.iter().position(|d| d.database_id == *db))
+			.unwrap_or(0);
+		dbs[first..].iter().chain(dbs[..first].iter())
+	}
+
+	/// Resumes the next pass over a namespace from a database, or from the
+	/// first database when `None`
+	pub(crate) fn resume_at(&self, ns: NamespaceId, db: Option<DatabaseId>) {
+		let mut resume = self.resume.lock();
+		match db {
+			Some(db) => resume.insert(ns, db),
+			None => resume.remove(&ns),
+		};
+	}
+
+	/// Records the maintenance of a namespace in a pass
+	pub(crate) fn record(
+		&self,
+		ns: &str,
+		duration: Duration,
+		outcome: Result<NamespaceTick, String>,
+	) {
+		let mut metrics = self.metrics.lock();
+		let entry = metrics.entry(ns.to_owned()).or_default();
+		entry.runs += 1;
+		entry.last_duration = duration;
+		entry.total_duration += duration;
+		match outcome {
+			Ok(tick) => {
+				entry.databases += tick.databases;
+				entry.deferred += tick.deferred as u64;
+				entry.last_error = None;
+			}
+			Err(e) => {
+				entry.errors += 1;
+				entry.last_error = Some(e);
+			}
+		}
+	}
+
+	/// Returns the metrics of every namespace which has been maintained
+	pub(crate) fn metrics(&self) -> BTreeMap<String, NamespaceTickMetrics> {
+		self.metrics.lock().clone()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn ns(id: u32, name: &str) -> NamespaceDefinition {
+		NamespaceDefinition {
+			namespace_id: NamespaceId(id),
+			name: name.into(),
+			comment: None,
+		}
+	}
+
+	fn db(id: u32) -> DatabaseDefinition {
+		DatabaseDefinition {
+			namespace_id: NamespaceId(0),
+			database_id: DatabaseId(id),
+			name: format!("db{id}").into(),
+			comment: None,
+			changefeed: None,
+			strict: false,
+			capabilities: None,
+		}
+	}
+
+	#[test]
+	fn passes_rotate_the_first_namespace() {
+		let scheduler = TickScheduler::default();
+		let nss = [ns(0, "a"), ns(1, "b"), ns(2, "c")];
+		let order =
+			|s: &TickScheduler| s.start(&nss).map(|n| n.name.to_string()).collect::<Vec<_>>();
+		assert_eq!(order(&scheduler), ["a", "b", "c"]);
+		assert_eq!(order(&scheduler), ["b", "c", "a"]);
+		assert_eq!(order(&scheduler), ["c", "a", "b"]);
+		assert_eq!(order(&scheduler), ["a", "b", "c"]);
+		assert_eq!(scheduler.start(&[]).count(), 0);
+	}
+
+	#[test]
+	fn deferred_namespaces_resume_where_they_stopped() {
+		let scheduler = TickScheduler::default();
+		let dbs = [db(0), db(1), db(2)];
+		let order = |s: &TickScheduler| {
+			s.databases(NamespaceId(0), &dbs).map(|d| d.database_id.0).collect::<Vec<_>>()
+		};
+		assert_eq!(order(&scheduler), [0, 1, 2]);
+		scheduler.resume_at(NamespaceId(0), Some(DatabaseId(2)));
+		assert_eq!(order(&scheduler), [2, 0, 1]);
+		// A removed database starts the namespace over
+		scheduler.resume_at(NamespaceId(0), Some(DatabaseId(9)));
+		assert_eq!(order(&scheduler), [0, 1, 2]);
+		scheduler.resume_at(NamespaceId(0), None);
+		assert_eq!(order(&scheduler), [0, 1, 2]);
+	}
+
+	#[test]
+	fn metrics_are_kept_per_namespace() {
+		let scheduler = TickScheduler::default();
+		let tick = |databases, deferred| {
+			Ok(NamespaceTick {
+				databases,
+				deferred,
+			})
+		};
+		scheduler.record("a", Duration::from_millis(10), tick(3, true));
+		scheduler.record("a", Duration::from_millis(5), Err("failed".to_owned()));
+		scheduler.record("b", Duration::from_millis(1), tick(1, false));
+		let metrics = scheduler.metrics();
+		assert_eq!(metrics["a"].runs, 2);
+		assert_eq!(metrics["a"].errors, 1);
+		assert_eq!(metrics["a"].databases, 3);
+		assert_eq!(metrics["a"].deferred, 1);
+		assert_eq!(metrics["a"].last_duration, Duration::from_millis(5));
+		assert_eq!(metrics["a"].total_duration, Duration::from_millis(15));
+		assert_eq!(metrics["a"].last_error.as_deref(), Some("failed"));
+		assert_eq!(metrics["b"].databases, 1);
+		// The metrics of removed namespaces are forgotten
+		scheduler.start(&[ns(0, "a")]).count();
+		assert_eq!(scheduler.metrics().keys().collect::<Vec<_>>(), ["a"]);
+	}
+}
@@ -70,6 +70,15 @@ pub struct TikvConfig {
 	/// gRPC payload-too-large or runaway commit.
 	pub delr_max_keys: u32,
 
+	/// Maximum number of bytes a single transaction is allowed to write.
+	/// Default 100 MiB, matching the default `txn-total-size-limit` of TiDB.
+	///
+	/// A transaction which would write more fails with a
+	/// `TransactionTooLarge` error reporting its size, instead of an opaque
+	/// error at commit. Bulk statements marked `NON ATOMIC` are split into
+	/// several transactions which each stay within this limit.
+	pub transaction_size_limit: u64,
+
 	/// PEM-encoded root CA the client trusts when verifying the TiKV/PD
 	/// server certificate.
 	///
@@ -133,6 +142,7 @@ impl Default for TikvConfig {
 			grpc_max_decoding_message_size: 4 * 1024 * 1024,
 			grpc_max_encoding_message_size: 4 * 1024 * 1024,
 			delr_max_keys: 1_000_000,
+			transaction_size_limit: 100 * 1024 * 1024,
 			tls_ca_path: None,
 			tls_cert_path: None,
 			tls_key_path: None,
@@ -161,6 +171,7 @@ impl Config for TikvConfig {
 				&mut self.grpc_max_encoding_message_size,
 			)
 			.parse_key("tikv_delr_max_keys", &mut self.delr_max_keys)
+			.parse_key("tikv_transaction_size_limit", &mut self.transaction_size_limit)
 			.parse_key_option("tikv_tls_ca_path", &mut self.tls_ca_path)
 			.parse_key_option("tikv_tls_cert_path", &mut self.tls_cert_path)
 			.parse_key_option("tikv_tls_key_path", &mut self.tls_key_path)
@@ -188,6 +199,7 @@ mod test {
 		assert_eq!(config.grpc_max_decoding_message_size, 4 * 1024 * 1024);
 		assert_eq!(config.grpc_max_encoding_message_size, 4 * 1024 * 1024);
 		assert_eq!(config.delr_max_keys, 1_000_000);
+		assert_eq!(config.transaction_size_limit, 100 * 1024 * 1024);
 		assert!(config.tls_ca_path.is_none());
 		assert!(config.gc_enabled);
 		assert_eq!(config.gc_lifetime_secs, 600);
@@ -207,6 +219,7 @@ mod test {
 			.with_key_value("tikv_grpc_max_decoding_message_size", "8388608")
 			.with_key_value("tikv_grpc_max_encoding_message_size", "16777216")
 			.with_key_value("tikv_delr_max_keys", "50000")
+			.with_key_value("tikv_transaction_size_limit", "10485760")
 			.with_key_value("tikv_tls_ca_path", "/etc/tikv/ca.pem")
 			.with_key_value("tikv_tls_cert_path", "/etc/tikv/cert.pem")
 			.with_key_value("tikv_tls_key_path", "/etc/tikv/key.pem")
@@ -224,6 +237,7 @@ mod test {
 		assert_eq!(config.grpc_max_decoding_message_size, 8 * 1024 * 1024);
 		assert_eq!(config.grpc_max_encoding_message_size, 16 * 1024 * 1024);
 		assert_eq!(config.delr_max_keys, 50_000);
+		assert_eq!(config.transaction_size_limit, 10 * 1024 * 1024);
 		assert_eq!(config.tls_ca_path.as_deref(), Some("/etc/tikv/ca.pem"));
 		assert_eq!(config.tls_cert_path.as_deref(), Some("/etc/tikv/cert.pem"));
 		assert_eq!(config.tls_key_path.as_deref(), Some("/etc/tikv/key.pem"));
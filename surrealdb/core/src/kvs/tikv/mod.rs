@@ -612,6 +612,11 @@ impl Transactable for Transaction {
 		self.write
 	}
 
+	/// The largest number of bytes a transaction can write
+	fn size_limit(&self) -> Option<u64> {
+		Some(self.handle.config.transaction_size_limit)
+	}
+
 	/// Cancel a transaction
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
 	fn cancel(&self) -> BoxFut<'_, Result<()>> {
@@ -88,6 +88,12 @@ impl Transactor {
 		self.inner.writeable()
 	}
 
+	/// The largest number of bytes which the transaction can write, if the
+	/// storage engine limits the size of transactions.
+	pub fn size_limit(&self) -> Option<u64> {
+		self.inner.size_limit()
+	}
+
 	/// Cancel a transaction.
 	///
 	/// This reverses all changes made within the transaction.
@@ -40,6 +40,7 @@ use crate::catalog::{
 	NamespaceDefinition, NamespaceId, Record, TableDefinition, TableId,
 };
 use crate::cf::Changefeed;
+use crate::cf::segment::SegmentStatsDelta;
 use crate::cnf::CommonConfig;
 use crate::ctx::Context;
 use crate::dbs::node::Node;
@@ -58,15 +59,18 @@ use crate::kvs::index::{
 	BuildGeneration, BuildTicket, BuildTicketMutationSeq, IndexBuildPhase, IndexBuildReportStatus,
 	IndexBuildState, IndexBuilder,
 };
+use crate::kvs::scrub::RecordChecksum;
 use crate::kvs::sequences::Sequences;
+use crate::kvs::stats::TableStatsBuffer;
 #[cfg(test)]
 use crate::kvs::testing::{
 	NonRetryableErrorSite, RetryableConflictSite, maybe_inject_non_retryable_error,
 	maybe_inject_retryable_conflict,
 };
 use crate::kvs::{
-	BoxTimeStamp, BoxTimeStampImpl, Direction, Error as KvsError, KVKey, KVValue, Transactor,
-	cache, is_retryable_transaction_conflict,
+	BoxTimeStamp, BoxTimeStampImpl, Direction, Error as KvsError, EvictionBuffer, Evictions, KVKey,
+	KVValue, KeyLockOwner, KeyLockWriter, KeyLocks, TableWrites, TemporaryIndexes, Transactor,
+	WritePermit, cache, is_retryable_transaction_conflict, keylock,
 };
 use crate::lq::writer::LiveEventBuffer;
 use crate::observe::{
@@ -164,6 +168,30 @@ pub struct Transaction {
 	/// state and index data from separate transactions. These cleanups remove that
 	/// provisional state only when the schema transaction does not commit.
 	pending_uncommitted_index_builds: Mutex<Vec<PendingUncommittedIndexBuild>>,
+	/// Prevents the datastore from being locked for writes while this write
+	/// transaction is open. Released as soon as the transaction finishes.
+	write_permit: parking_lot::Mutex<Option<WritePermit>>,
+	/// The tables written to by this transaction, whose temporary indexes can
+	/// not be used until the transaction has finished
+	table_writes: TableWrites,
+	/// The changes made to the statistics of tables by this transaction
+	table_stats: TableStatsBuffer,
+	/// The changes made by this transaction to the records of evictable
+	/// tables, which are tracked once it commits
+	evictions: EvictionBuffer,
+	/// The key locks of the client-managed transactions of the datastore
+	key_locks: KeyLocks,
+	/// How long a write waits for a key locked by another transaction
+	lock_wait_timeout: Duration,
+	/// Whether a checksum is stored alongside every record which is written,
+	/// read from the datastore the first time that a record is written
+	record_checksums: OnceLock<bool>,
+	/// The locks held by this transaction, when it is client-managed.
+	/// Released as soon as the transaction finishes.
+	lock_owner: parking_lot::Mutex<Option<Arc<KeyLockOwner>>>,
+	/// The tables written by this transaction while it takes no key locks,
+	/// which a table lock waits for until it is committed or cancelled
+	lock_writer: KeyLockWriter,
 }
 
 const INDEX_BUILD_RESERVATION_RELEASE_RETRY_SLEEP: Duration = Duration::from_millis(100);
@@ -789,9 +817,248 @@ impl Transaction {
 			cached_index_build_reservations: Mutex::new(HashMap::new()),
 			pending_index_builder_aborts: Mutex::new(Vec::new()),
 			pending_uncommitted_index_builds: Mutex::new(Vec::new()),
+			write_permit: parking_lot::Mutex::new(None),
+			table_writes: TableWrites::new(TemporaryIndexes::default(), 0),
+			table_stats: TableStatsBuffer::default(),
+			evictions: EvictionBuffer::default(),
+			key_locks: KeyLocks::default(),
+			lock_wait_timeout: config.lock_wait_timeout,
+			record_checksums: OnceLock::new(),
+			lock_owner: parking_lot::Mutex::new(None),
+			lock_writer: KeyLockWriter::new(
+				KeyLocks::default(),
+				Uuid::nil(),
+				config.lock_wait_timeout,
+			),
 		}
 	}
 
+	/// Attaches the permit admitting this write transaction while the
+	/// datastore is not locked for writes
+	pub(crate) fn with_write_permit(mut self, permit: Option<WritePermit>) -> Transaction {
+		self.write_permit = parking_lot::Mutex::new(permit);
+		self
+	}
+
+	/// Attaches the temporary indexes of the datastore, with the tick of their
+	/// clock recorded before the snapshot of this transaction was taken
+	pub(crate) fn with_temporary_indexes(
+		mut self,
+		indexes: TemporaryIndexes,
+		epoch: u64,
+	) -> Transaction {
+		self.table_writes = TableWrites::new(indexes, epoch);
+		self
+	}
+
+	/// Attaches the records of evictable tables in the datastore, so that
+	/// their use by this transaction is tracked
+	pub(crate) fn with_evictions(mut self, evictions: Evictions) -> Transaction {
+		self.evictions = EvictionBuffer::new(evictions);
+		self
+	}
+
+	/// Attaches the key locks of the client-managed transactions of the
+	/// datastore
+	pub(crate) fn with_key_locks(mut self, key_locks: KeyLocks) -> Transaction {
+		self.lock_writer =
+			KeyLockWriter::new(key_locks.clone(), Uuid::now_v7(), self.lock_wait_timeout);
+		self.key_locks = key_locks;
+		self
+	}
+
+	/// Marks this transaction as managed by a client, with `BEGIN` over an RPC
+	/// connection, under the given transaction id.
+	///
+	/// The records written by the transaction are then locked on this node
+	/// until it is committed or cancelled, and a write waiting for the lock of
+	/// another client-managed transaction fails once it would wait forever, or
+	/// once it has waited for longer than the lock wait timeout.
+	pub fn enable_key_locks(&self, id: Uuid) {
+		let owner = KeyLockOwner::new(self.key_locks.clone(), id, self.lock_wait_timeout);
+		*self.lock_owner.lock() = Some(Arc::new(owner));
+	}
+
+	/// Describes the transactions of the datastore which are waiting for a
+	/// key lock or a table lock
+	pub(crate) fn lock_waits(&self) -> crate::val::Value {
+		self.key_locks.waits()
+	}
+
+	/// Locks every record of a table until this transaction is committed or
+	/// cancelled, waiting for at most `max_wait` when given instead of the
+	/// lock wait timeout.
+	///
+	/// The transaction takes key locks from then on, even when it is not
+	/// client-managed. The lock is only granted once the other transactions
+	/// which wrote to the table without taking key locks have finished.
+	pub(crate) async fn lock_table(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		max_wait: Option<Duration>,
+	) -> Result<()> {
+		let owner = self
+			.lock_owner
+			.lock()
+			.get_or_insert_with(|| {
+				// The lock is held under the id of the writes made so far, so
+				// that it does not wait for them
+				let owner = KeyLockOwner::new(
+					self.key_locks.clone(),
+					self.lock_writer.id(),
+					self.lock_wait_timeout,
+				);
+				Arc::new(owner)
+			})
+			.clone();
+		let key = crate::key::table::all::new(ns, db, tb).encode_key()?;
+		owner.lock_table(keylock::prefix_range(&key), max_wait).await
+	}
+
+	/// Locks a record about to be written, if this transaction is
+	/// client-managed, or otherwise waits while its table is locked
+	async fn lock_key(&self, key: &[u8]) -> Result<()> {
+		let owner = self.lock_owner.lock().clone();
+		match owner {
+			Some(owner) => owner.lock_key(key).await,
+			None => {
+				let rng = keylock::single(key);
+				self.lock_writer.wait_for_tables(&rng).await
+			}
+		}
+	}
+
+	/// Locks a record about to be written, if this transaction takes key
+	/// locks, or otherwise records the write to its table, waiting while the
+	/// table is locked
+	async fn lock_record(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		key: &[u8],
+	) -> Result<()> {
+		let owner = self.lock_owner.lock().clone();
+		match owner {
+			Some(owner) => owner.lock_key(key).await,
+			None => {
+				let table = crate::key::table::all::new(ns, db, tb).encode_key()?;
+				self.lock_writer.write_table(keylock::prefix_range(&table)).await
+			}
+		}
+	}
+
+	/// Locks a range of keys about to be written, if this transaction is
+	/// client-managed, or otherwise waits while their table is locked
+	async fn lock_range(&self, rng: &Range<Key>) -> Result<()> {
+		let owner = self.lock_owner.lock().clone();
+		match owner {
+			Some(owner) => owner.lock_range(rng.clone()).await,
+			None => self.lock_writer.wait_for_tables(rng).await,
+		}
+	}
+
+	/// Whether record checksums are enabled on the datastore. The setting is
+	/// stored rather than configured per node, so that the checksums of the
+	/// records written by every node are kept up to date.
+	async fn record_checksums(&self) -> Result<bool> {
+		if let Some(enabled) = self.record_checksums.get() {
+			return Ok(*enabled);
+		}
+		let enabled = self.exists(&crate::key::root::cs::Cs::new(), None).await?;
+		Ok(*self.record_checksums.get_or_init(|| enabled))
+	}
+
+	/// Stores the checksum of a record about to be written, if record
+	/// checksums are enabled
+	async fn set_record_checksum(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		id: &RecordIdKey,
+		key: &[u8],
+		val: &[u8],
+	) -> Result<()> {
+		if !self.record_checksums().await? {
+			return Ok(());
+		}
+		let sum = RecordChecksum::new(key, val);
+		self.set(&crate::key::table::ck::new(ns, db, tb, id), &sum).await
+	}
+
+	/// Records when a record of a table defined with `EVICT AFTER` was
+	/// written, so that any node can evict it once its time to live has passed
+	async fn set_record_written(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		id: &RecordIdKey,
+	) -> Result<()> {
+		if !self.evicts_after(ns, db, tb).await? {
+			return Ok(());
+		}
+		let now = u64::try_from(Utc::now().timestamp_millis()).unwrap_or_default();
+		self.set(&crate::key::table::ew::new(ns, db, tb, id), &now).await
+	}
+
+	/// Forgets when a deleted record of a table defined with `EVICT AFTER`
+	/// was written
+	async fn del_record_written(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		id: &RecordIdKey,
+	) -> Result<()> {
+		if !self.evicts_after(ns, db, tb).await? {
+			return Ok(());
+		}
+		self.del(&crate::key::table::ew::new(ns, db, tb, id)).await
+	}
+
+	/// Checks whether the records of a table are evicted after a time to live
+	async fn evicts_after(&self, ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<bool> {
+		let tb = self.get_tb(ns, db, tb, None).await?;
+		Ok(tb.is_some_and(|tb| tb.eviction.is_some_and(|e| e.ttl.is_some())))
+	}
+
+	/// Returns the number of key and value bytes written by this transaction
+	pub(crate) fn bytes_written(&self) -> u64 {
+		self.metrics.bytes_written()
+	}
+
+	/// Fails before a write which would make this transaction larger than
+	/// the storage engine accepts
+	fn check_size(&self, bytes: u64) -> Result<()> {
+		if let Some(limit) = self.tr.size_limit() {
+			let size = self.metrics.bytes_written().saturating_add(bytes);
+			if size > limit {
+				return Err(Error::TransactionTooLarge {
+					size,
+					limit,
+				}
+				.into());
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the tables written to by this transaction, and through them
+	/// the temporary indexes of the datastore
+	pub(crate) fn table_writes(&self) -> &TableWrites {
+		&self.table_writes
+	}
+
+	/// Returns the changes made to the statistics of tables by this
+	/// transaction
+	pub(crate) fn table_stats(&self) -> &TableStatsBuffer {
+		&self.table_stats
+	}
+
 	/// Attach pre-resolved tenant identity so the emitted
 	/// [`TransactionEvent`] carries the active session's namespace,
 	/// database, user, session id, and client IP. Typically called by the
@@ -812,6 +1079,23 @@ impl Transaction {
 		let _ = self.tenant_identity.set(identity);
 	}
 
+	/// Ensures that a table defined with `SESSION` is only accessed by the
+	/// session which defined it.
+	///
+	/// The session is taken from the tenant identity of the transaction, so
+	/// that transactions which are not run for a session, such as those of
+	/// the background tasks of the datastore, can access every table.
+	fn check_session_table(&self, tb: &TableDefinition) -> Result<()> {
+		if let (Some(owner), Some(identity)) = (tb.session, self.tenant_identity.get())
+			&& identity.session_id != Some(owner)
+		{
+			return Err(anyhow::anyhow!(Error::TbSessionOwned {
+				name: tb.name.clone(),
+			}));
+		}
+		Ok(())
+	}
+
 	#[cfg(test)]
 	pub(crate) fn metrics_snapshot_for_test(&self) -> crate::observe::TransactionMetricsSnapshot {
 		self.metrics.snapshot()
@@ -1058,10 +1342,20 @@ impl Transaction {
 		if let Some(live_events) = self.live_events.get() {
 			live_events.clear();
 		}
+		// Clear any buffered table statistics
+		self.table_stats.clear();
+		// Discard the changes to the records of evictable tables
+		self.evictions.clear();
 		// Cancel the underlying transactor. Emit a transaction event on
 		// either outcome so counters and durations are always reported
 		// even when cancel itself reports a driver-level error.
 		let result = self.tr.cancel().await.map_err(Error::from);
+		// Let a pending write lock proceed before the follow-up cleanup
+		// transactions below are opened
+		self.write_permit.lock().take();
+		self.lock_owner.lock().take();
+		self.lock_writer.finish();
+		self.table_writes.finish();
 		let cleanup_result = self.cleanup_uncommitted_index_builds().await;
 		let release_result = self.release_index_build_reservations().await;
 		self.discard_index_builder_aborts().await;
@@ -1093,7 +1387,13 @@ impl Transaction {
 			return Err(e);
 		}
 		// Commit the transaction
-		if let Err(e) = self.tr.commit().await {
+		let committed = self.tr.commit().await;
+		self.write_permit.lock().take();
+		self.lock_owner.lock().take();
+		self.lock_writer.finish();
+		self.table_writes.finish();
+		if let Err(e) = committed {
+			self.evictions.clear();
 			let cleanup_result = self.cleanup_uncommitted_index_builds().await;
 			let release_result = self.release_index_build_reservations().await;
 			self.discard_index_builder_aborts().await;
@@ -1133,6 +1433,7 @@ impl Transaction {
 				"durable index-build reservation cleanup failed after transaction commit; committed appendings remain recoverable: {err}"
 			);
 		}
+		self.evictions.commit();
 		self.discard_uncommitted_index_builds().await;
 		self.run_index_builder_aborts().await;
 		if self.trigger_async_event.load(Ordering::Relaxed) {
@@ -1253,6 +1554,7 @@ impl Transaction {
 	{
 		let key = key.encode_key()?;
 		let key_bytes = key.len() as u64;
+		self.lock_key(&key).await?;
 		self.tr.del(key).await.map_err(Error::from)?;
 		self.metrics.record_del(1, key_bytes);
 		Ok(())
@@ -1268,6 +1570,7 @@ impl Transaction {
 		let key = key.encode_key()?;
 		let key_bytes = key.len() as u64;
 		let chk = chk.map(|v| v.kv_encode_value()).transpose()?;
+		self.lock_key(&key).await?;
 		self.tr.delc(key, chk).await.map_err(Error::from)?;
 		self.metrics.record_del(1, key_bytes);
 		Ok(())
@@ -1284,7 +1587,9 @@ impl Transaction {
 	{
 		let beg = rng.start.encode_key()?;
 		let end = rng.end.encode_key()?;
-		self.tr.delr(beg..end).await.map_err(Error::from)?;
+		let rng = beg..end;
+		self.lock_range(&rng).await?;
+		self.tr.delr(rng).await.map_err(Error::from)?;
 		// Range/prefix deletes don't report the number of affected keys or
 		// their byte size.
 		self.metrics.record_del(0, 0);
@@ -1301,6 +1606,7 @@ impl Transaction {
 		K: KVKey + Debug,
 	{
 		let key = key.encode_key()?;
+		self.lock_range(&keylock::prefix_range(&key)).await?;
 		self.tr.delp(key).await.map_err(Error::from)?;
 		self.metrics.record_del(0, 0);
 		Ok(())
@@ -1314,6 +1620,7 @@ impl Transaction {
 	{
 		let key = key.encode_key()?;
 		let key_bytes = key.len() as u64;
+		self.lock_key(&key).await?;
 		self.tr.clr(key).await.map_err(Error::from)?;
 		self.metrics.record_del(1, key_bytes);
 		Ok(())
@@ -1329,6 +1636,7 @@ impl Transaction {
 		let key = key.encode_key()?;
 		let key_bytes = key.len() as u64;
 		let chk = chk.map(|v| v.kv_encode_value()).transpose()?;
+		self.lock_key(&key).await?;
 		self.tr.clrc(key, chk).await.map_err(Error::from)?;
 		self.metrics.record_del(1, key_bytes);
 		Ok(())
@@ -1345,7 +1653,9 @@ impl Transaction {
 	{
 		let beg = rng.start.encode_key()?;
 		let end = rng.end.encode_key()?;
-		self.tr.clrr(beg..end).await.map_err(Error::from)?;
+		let rng = beg..end;
+		self.lock_range(&rng).await?;
+		self.tr.clrr(rng).await.map_err(Error::from)?;
 		self.metrics.record_del(0, 0);
 		Ok(())
 	}
@@ -1360,6 +1670,7 @@ impl Transaction {
 		K: KVKey + Debug,
 	{
 		let key = key.encode_key()?;
+		self.lock_range(&keylock::prefix_range(&key)).await?;
 		self.tr.clrp(key).await.map_err(Error::from)?;
 		self.metrics.record_del(0, 0);
 		Ok(())
@@ -1516,6 +1827,8 @@ impl Transaction {
 		let val = val.kv_encode_value()?;
 		let key_bytes = key.len() as u64;
 		let value_bytes = val.len() as u64;
+		self.check_size(key_bytes + value_bytes)?;
+		self.lock_key(&key).await?;
 		self.tr.set(key, val).await.map_err(Error::from)?;
 		self.metrics.record_set(key_bytes, value_bytes);
 		Ok(())
@@ -1531,6 +1844,8 @@ impl Transaction {
 		let val = val.kv_encode_value()?;
 		let key_bytes = key.len() as u64;
 		let value_bytes = val.len() as u64;
+		self.check_size(key_bytes + value_bytes)?;
+		self.lock_key(&key).await?;
 		self.tr.put(key, val).await.map_err(Error::from)?;
 		self.metrics.record_put(key_bytes, value_bytes);
 		Ok(())
@@ -1552,6 +1867,8 @@ impl Transaction {
 		let chk = chk.map(|v| v.kv_encode_value()).transpose()?;
 		let key_bytes = key.len() as u64;
 		let value_bytes = val.len() as u64;
+		self.check_size(key_bytes + value_bytes)?;
+		self.lock_key(&key).await?;
 		self.tr.putc(key, val, chk).await.map_err(Error::from)?;
 		self.metrics.record_put(key_bytes, value_bytes);
 		Ok(())
@@ -1567,6 +1884,8 @@ impl Transaction {
 		let val = val.kv_encode_value()?;
 		let key_bytes = key.len() as u64;
 		let value_bytes = val.len() as u64;
+		self.check_size(key_bytes + value_bytes)?;
+		self.lock_key(&key).await?;
 		self.tr.replace(key, val).await.map_err(Error::from)?;
 		self.metrics.record_put(key_bytes, value_bytes);
 		Ok(())
@@ -1884,6 +2203,82 @@ impl Transaction {
 		Ok(!self.keys(beg..end, 1, 0, None).await?.is_empty())
 	}
 
+	/// Retrieve all archival policy definitions for a specific table.
+	///
+	/// Policies are only read by DDL statements and the archival task, so they
+	/// are fetched directly rather than through the transaction cache.
+	pub(crate) async fn all_tb_policies(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+	) -> Result<Vec<catalog::PolicyDefinition>> {
+		let beg = crate::key::table::po::prefix(ns, db, tb)?;
+		let end = crate::key::table::po::suffix(ns, db, tb)?;
+		Ok(self.getr(beg..end, None).await?.into_iter().map(|(_, v)| v).collect())
+	}
+
+	/// Retrieve a specific archival policy definition for a table.
+	pub(crate) async fn get_tb_policy(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &TableName,
+		po: &str,
+	) -> Result<catalog::PolicyDefinition> {
+		let key = crate::key::table::po::new(ns, db, tb, po);
+		self.get(&key, None).await?.ok_or_else(|| {
+			Error::PoNotFound {
+				name: po.to_owned(),
+			}
+			.into()
+		})
+	}
+
+	/// Retrieve all query template definitions for a specific database.
+	pub(crate) async fn all_db_queries(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+	) -> Result<Arc<[catalog::QueryDefinition]>> {
+		let qey = cache::tx::Lookup::Qys(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_qys(),
+			None => {
+				let beg = crate::key::database::qy::prefix(ns, db)?;
+				let end = crate::key::database::qy::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Qys(Arc::clone(&val));
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve a specific query template definition for a database.
+	pub(crate) async fn get_db_query(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		qy: &str,
+	) -> Result<Arc<catalog::QueryDefinition>> {
+		let qey = cache::tx::Lookup::Qy(ns, db, qy);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::qy::new(ns, db, qy);
+				let val = self.get(&key, None).await?.ok_or_else(|| Error::QyNotFound {
+					name: qy.to_owned(),
+				})?;
+				let val = Arc::new(val);
+				let entry = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Returns the implementation of timestamp that this transaction uses.
 	pub fn timestamp_impl(&self) -> BoxTimeStampImpl {
 		self.tr.timestamp_impl()
@@ -1942,6 +2337,7 @@ impl Transaction {
 		id: &RecordId,
 		previous: CursorRecord,
 		current: CursorRecord,
+		eviction: bool,
 	) {
 		self.live_events.get_or_init(LiveEventBuffer::new).buffer_record_change(
 			ns,
@@ -1950,6 +2346,7 @@ impl Transaction {
 			id.clone(),
 			previous.into_owned(),
 			current.into_owned(),
+			eviction,
 		)
 	}
 
@@ -1983,20 +2380,36 @@ impl Transaction {
 			Some(live_events) => live_events.changes()?,
 			None => Vec::new(),
 		};
-		// Nothing buffered in either keyspace -> nothing to do.
-		if cf_changes.is_empty() && lqe_changes.is_empty() {
+		// Gather buffered table statistics (if any).
+		let stats_changes = self.table_stats.take();
+		// Nothing buffered in any keyspace -> nothing to do.
+		if cf_changes.is_empty() && lqe_changes.is_empty() && stats_changes.is_empty() {
 			return Ok(());
 		}
-		// Both keyspaces share this commit's versionstamp.
+		// All keyspaces share this commit's versionstamp.
+		let timestamp = self.timestamp().await?;
 		let buf = &mut [0u8; _];
-		let ts = self.timestamp().await?.encode(buf);
-		// Write the changefeed entries.
-		let cf_futures = cf_changes.into_iter().map(|(ns, db, tb, value)| async move {
+		let ts = timestamp.encode(buf);
+		// Write the table statistics deltas.
+		if !stats_changes.is_empty() {
+			crate::kvs::stats::store(self, stats_changes, timestamp.as_versionstamp()).await?;
+		}
+		// Write the changefeed entries, counting them in each database.
+		let mut segments = HashMap::<(NamespaceId, DatabaseId), SegmentStatsDelta>::new();
+		let mut cf_writes = Vec::with_capacity(cf_changes.len());
+		for (ns, db, tb, value) in cf_changes {
 			let key = crate::key::change::new(ns, db, ts, &tb).encode_key()?;
+			segments.entry((ns, db)).or_default().record(key.len() + value.len());
+			cf_writes.push((key, value));
+		}
+		let cf_futures = cf_writes.into_iter().map(|(key, value)| async move {
 			self.tr.set(key, value).await.map_err(Error::from)?;
 			Ok::<(), anyhow::Error>(())
 		});
 		try_join_all(cf_futures).await?;
+		if !segments.is_empty() {
+			crate::cf::segment::store(self, segments, &timestamp).await?;
+		}
 		// Write the live-query event entries to the dedicated keyspace.
 		let lqe_futures = lqe_changes.into_iter().map(|(ns, db, tb, value)| async move {
 			let key = crate::key::lqe::new(ns, db, ts, &tb).encode_key()?;
@@ -2509,6 +2922,7 @@ impl DatabaseProvider for Transaction {
 							comment: None,
 							changefeed: None,
 							strict: false,
+							capabilities: None,
 						};
 
 						return self.put_db(ns_def.name.as_str(), db_def).await;
@@ -3202,6 +3616,7 @@ impl TableProvider for Transaction {
 					let table_key =
 						crate::key::database::tb::new(db_def.namespace_id, db_def.database_id, tb);
 					if let Some(tb_def) = self.get(&table_key, version).await? {
+						self.check_session_table(&tb_def)?;
 						return Ok(Arc::new(tb_def));
 					}
 					return Err(Error::TbNotFound {
@@ -3212,7 +3627,11 @@ impl TableProvider for Transaction {
 				let qey = cache::tx::Lookup::TbByName(ns, db, tb);
 				match self.cache.get(&qey) {
 					// The entry is in the cache
-					Some(val) => val.try_into_type(),
+					Some(val) => {
+						let tb_def: Arc<TableDefinition> = val.try_into_type()?;
+						self.check_session_table(&tb_def)?;
+						Ok(tb_def)
+					}
 					// The entry is not in the cache
 					None => {
 						let Some(db_def) = self.get_db_by_name(ns, db, None).await? else {
@@ -3227,6 +3646,7 @@ impl TableProvider for Transaction {
 							tb,
 						);
 						if let Some(tb_def) = self.get(&table_key, None).await? {
+							self.check_session_table(&tb_def)?;
 							let cached_tb = Arc::new(tb_def);
 							let cached_entry = cache::tx::Entry::Any(
 								Arc::clone(&cached_tb) as Arc<dyn Any + Send + Sync>
@@ -3273,11 +3693,16 @@ impl TableProvider for Transaction {
 				let Some(tb) = self.get(&key, version).await? else {
 					return Ok(None);
 				};
+				self.check_session_table(&tb)?;
 				return Ok(Some(Arc::new(tb)));
 			}
 			let qey = cache::tx::Lookup::TbByName(ns, db, tb);
 			match self.cache.get(&qey) {
-				Some(val) => val.try_into_type().map(Some),
+				Some(val) => {
+					let tb: Arc<TableDefinition> = val.try_into_type()?;
+					self.check_session_table(&tb)?;
+					Ok(Some(tb))
+				}
 				None => {
 					let Some(db) = self.get_db_by_name(ns, db, None).await? else {
 						return Ok(None);
@@ -3287,6 +3712,7 @@ impl TableProvider for Transaction {
 					let Some(tb) = self.get(&key, None).await? else {
 						return Ok(None);
 					};
+					self.check_session_table(&tb)?;
 
 					let tb = Arc::new(tb);
 					let entr = cache::tx::Entry::Any(tb.clone());
@@ -3553,16 +3979,22 @@ impl TableProvider for Transaction {
 					let Some(val) = self.get(&key, version).await? else {
 						return Ok(None);
 					};
+					self.check_session_table(&val)?;
 					return Ok(Some(Arc::new(val)));
 				}
 				let qey = cache::tx::Lookup::Tb(ns, db, tb);
 				match self.cache.get(&qey) {
-					Some(val) => val.try_into_type().map(Some),
+					Some(val) => {
+						let val: Arc<TableDefinition> = val.try_into_type()?;
+						self.check_session_table(&val)?;
+						Ok(Some(val))
+					}
 					None => {
 						let key = crate::key::database::tb::new(ns, db, tb);
 						let Some(val) = self.get(&key, None).await? else {
 							return Ok(None);
 						};
+						self.check_session_table(&val)?;
 						let val = Arc::new(val);
 						let entry = cache::tx::Entry::Any(val.clone());
 						self.cache.insert(qey, entry);
@@ -3822,6 +4254,7 @@ impl TableProvider for Transaction {
 						None => Ok(Arc::new(Default::default())),
 					}
 				} else {
+					self.evictions.read(ns, db, tb, id);
 					let qey = cache::tx::Lookup::Record(ns, db, tb, id);
 					match self.cache.get(&qey) {
 						// The entry is in the cache
@@ -3961,8 +4394,18 @@ impl TableProvider for Transaction {
 	) -> BoxProviderFut<'a, Result<()>> {
 		Box::pin(
 			async move {
-				let key = crate::key::record::new(ns, db, tb, id);
-				self.put(&key, record.as_ref()).await?;
+				let key = crate::key::record::new(ns, db, tb, id).encode_key()?;
+				let val = record.as_ref().kv_encode_value()?;
+				let (key_bytes, value_bytes) = (key.len() as u64, val.len() as u64);
+				self.check_size(key_bytes + value_bytes)?;
+				self.evictions.write(ns, db, tb, id, value_bytes)?;
+				self.lock_record(ns, db, tb, &key).await?;
+				self.set_record_checksum(ns, db, tb, id, &key, &val).await?;
+				self.tr.put(key, val).await.map_err(Error::from)?;
+				self.metrics.record_put(key_bytes, value_bytes);
+				self.table_writes.write(ns, db, tb);
+				self.table_stats.records(ns, db, tb, 1, value_bytes as i64);
+				self.set_record_written(ns, db, tb, id).await?;
 				// Set the value in the cache
 				let qey = cache::tx::Lookup::Record(ns, db, tb, id);
 				self.cache.insert(qey, cache::tx::Entry::Val(record));
@@ -3984,8 +4427,29 @@ impl TableProvider for Transaction {
 		Box::pin(
 			async move {
 				// Set the value in the datastore
-				let key = crate::key::record::new(ns, db, tb, id);
-				self.set(&key, record.as_ref()).await?;
+				let key = crate::key::record::new(ns, db, tb, id).encode_key()?;
+				let val = record.as_ref().kv_encode_value()?;
+				let (key_bytes, value_bytes) = (key.len() as u64, val.len() as u64);
+				// Fetch the size of any previous value for the table statistics
+				let old = self.tr.get(key.clone(), None).await.map_err(Error::from)?;
+				self.check_size(key_bytes + value_bytes)?;
+				self.evictions.write(ns, db, tb, id, value_bytes)?;
+				self.lock_record(ns, db, tb, &key).await?;
+				self.set_record_checksum(ns, db, tb, id, &key, &val).await?;
+				self.tr.set(key, val).await.map_err(Error::from)?;
+				self.metrics.record_set(key_bytes, value_bytes);
+				self.table_writes.write(ns, db, tb);
+				match old {
+					Some(old) => self.table_stats.records(
+						ns,
+						db,
+						tb,
+						0,
+						value_bytes as i64 - old.len() as i64,
+					),
+					None => self.table_stats.records(ns, db, tb, 1, value_bytes as i64),
+				}
+				self.set_record_written(ns, db, tb, id).await?;
 				// Clear the value from the cache
 				let qey = cache::tx::Lookup::Record(ns, db, tb, id);
 				self.cache.remove(&qey);
@@ -4006,8 +4470,22 @@ impl TableProvider for Transaction {
 		Box::pin(
 			async move {
 				// Delete the value in the datastore
-				let key = crate::key::record::new(ns, db, tb, id);
-				self.del(&key).await?;
+				let key = crate::key::record::new(ns, db, tb, id).encode_key()?;
+				let key_bytes = key.len() as u64;
+				// Fetch the size of any previous value for the table statistics
+				let old = self.tr.get(key.clone(), None).await.map_err(Error::from)?;
+				self.lock_record(ns, db, tb, &key).await?;
+				self.tr.del(key).await.map_err(Error::from)?;
+				if self.record_checksums().await? {
+					self.del(&crate::key::table::ck::new(ns, db, tb, id)).await?;
+				}
+				self.metrics.record_del(1, key_bytes);
+				self.table_writes.write(ns, db, tb);
+				if let Some(old) = old {
+					self.table_stats.records(ns, db, tb, -1, -(old.len() as i64));
+				}
+				self.evictions.delete(ns, db, tb, id);
+				self.del_record_written(ns, db, tb, id).await?;
 				// Clear the value from the cache
 				let qey = cache::tx::Lookup::Record(ns, db, tb, id);
 				self.cache.remove(&qey);
@@ -4721,6 +5199,10 @@ impl AuthorisationProvider for Transaction {
 			// Delete the definition
 			let key = crate::key::root::ac::new(ra);
 			self.del(&key).await?;
+			// Delete the access grants from the grants of their subjects.
+			for gr in self.all_root_access_grants(ra, None).await?.iter() {
+				self.del(&crate::key::root::sg::SubjectGrant::new(&gr.subject, ra, &gr.id)).await?;
+			}
 			// Delete any associated data including access grants.
 			let key = crate::key::root::access::all::new(ra);
 			self.delp(&key).await?;
@@ -4744,6 +5226,11 @@ impl AuthorisationProvider for Transaction {
 			// Delete the definition
 			let key = crate::key::namespace::ac::new(ns, na);
 			self.del(&key).await?;
+			// Delete the access grants from the grants of their subjects.
+			for gr in self.all_ns_access_grants(ns, na, None).await?.iter() {
+				let key = crate::key::namespace::sg::SubjectGrant::new(ns, &gr.subject, na, &gr.id);
+				self.del(&key).await?;
+			}
 			// Delete any associated data including access grants.
 			let key = crate::key::namespace::access::all::new(ns, na);
 			self.delp(&key).await?;
@@ -4772,6 +5259,12 @@ impl AuthorisationProvider for Transaction {
 			// Delete the definition
 			let key = crate::key::database::ac::new(ns, db, da);
 			self.del(&key).await?;
+			// Delete the access grants from the grants of their subjects.
+			for gr in self.all_db_access_grants(ns, db, da, None).await?.iter() {
+				let key =
+					crate::key::database::sg::SubjectGrant::new(ns, db, &gr.subject, da, &gr.id);
+				self.del(&key).await?;
+			}
 			// Delete any associated data including access grants.
 			let key = crate::key::database::access::all::new(ns, db, da);
 			self.delp(&key).await?;
@@ -4,12 +4,15 @@ use crate::kvs::impl_kv_value_revisioned;
 use crate::val::{RecordId, Value};
 
 /// The kind of mutation that produced a [`LiveEvent`].
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub(crate) enum LiveAction {
 	Create,
 	Update,
 	Delete,
+	/// A deletion made by the eviction of the record
+	#[revision(start = 2)]
+	Evict,
 }
 
 /// A single record change captured for live-query routing.
@@ -53,9 +56,18 @@ impl LiveEvents {
 	/// Append a record change, deriving the action from the before/after values.
 	///
 	/// Matches the changefeed's classification: a nullish `after` is a delete, an
-	/// absent `before` is a create, otherwise it is an update.
-	pub(crate) fn push_record_change(&mut self, id: RecordId, before: Value, after: Value) {
-		let action = if after.is_nullish() {
+	/// absent `before` is a create, otherwise it is an update. Deletes made by
+	/// an eviction are kept apart, so that they are notified as evictions.
+	pub(crate) fn push_record_change(
+		&mut self,
+		id: RecordId,
+		before: Value,
+		after: Value,
+		eviction: bool,
+	) {
+		let action = if after.is_nullish() && eviction {
+			LiveAction::Evict
+		} else if after.is_nullish() {
 			LiveAction::Delete
 		} else if before.is_none() {
 			LiveAction::Create
@@ -2,15 +2,13 @@ use std::time::Duration;
 
 use anyhow::Result;
 
-use crate::catalog::providers::{DatabaseProvider, NamespaceProvider};
-use crate::catalog::{DatabaseId, NamespaceId};
+use crate::catalog::{DatabaseDefinition, DatabaseId, NamespaceId};
 use crate::key::lqe;
-use crate::kvs::tasklease::LeaseHandler;
 use crate::kvs::{BoxTimeStamp, BoxTimeStampImpl, KVKey, Transaction};
 
-/// Garbage-collect the dedicated live-query event keyspace.
+/// Garbage-collect the dedicated live-query event keyspace of a database.
 ///
-/// For every database, deletes live-query events older than `retention`. This
+/// Deletes the live-query events of the database older than `retention`. This
 /// runs regardless of whether the database *currently* has subscribers: events
 /// written while a table had subscribers must still be collected once they age
 /// out, even after every subscriber has disconnected or been killed (and after a
@@ -23,29 +21,16 @@ use crate::kvs::{BoxTimeStamp, BoxTimeStampImpl, KVKey, Transaction};
 /// changefeed entries or `SHOW CHANGES`. The caller gates invocation on the
 /// Router engine being active.
 #[instrument(level = "trace", target = "surrealdb::core::lq", skip_all)]
-pub async fn gc_all_at(lh: &LeaseHandler, tx: &Transaction, retention: Duration) -> Result<()> {
+pub async fn gc_db(tx: &Transaction, db: &DatabaseDefinition, retention: Duration) -> Result<()> {
 	// A zero retention would delete everything up to "now"; treat it as disabled.
 	if retention.is_zero() {
 		return Ok(());
 	}
 	let ts_impl = tx.timestamp_impl();
-	// Fetch all namespaces
-	let nss = tx.all_ns(None).await?;
-	for ns in nss.as_ref() {
-		// Fetch all databases
-		let dbs = tx.all_db(ns.namespace_id, None).await?;
-		for db in dbs.as_ref() {
-			let ts = tx.timestamp().await?;
-			// Watermark cutoff = now - retention
-			let watermark_ts = ts.sub_checked(retention).unwrap_or_else(|| ts_impl.earliest());
-			gc_range(tx, db.namespace_id, db.database_id, &watermark_ts, &ts_impl).await?;
-			lh.try_maintain_lease().await?;
-			yield_now!();
-		}
-		lh.try_maintain_lease().await?;
-		yield_now!();
-	}
-	Ok(())
+	let ts = tx.timestamp().await?;
+	// Watermark cutoff = now - retention
+	let watermark_ts = ts.sub_checked(retention).unwrap_or_else(|| ts_impl.earliest());
+	gc_range(tx, db.namespace_id, db.database_id, &watermark_ts, &ts_impl).await
 }
 
 /// Delete live-query events for a database older than the given watermark.
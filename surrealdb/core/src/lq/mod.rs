@@ -11,10 +11,14 @@
 //! rows so the decision is consistent and cluster-wide. The per-node [`router`]
 //! tails that keyspace off the write path and replays each event through the
 //! [`subscriber`]-side compute; [`gc`] reclaims old events by retention.
+//!
+//! Subscriptions to the schema of a database are notified directly by the
+//! statements which change it ([`schema`]).
 
 pub(crate) mod event;
 pub(crate) mod gc;
 pub(crate) mod router;
+pub(crate) mod schema;
 pub(crate) mod subscriber;
 pub(crate) mod writer;
 
@@ -16,9 +16,11 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use parking_lot::Mutex;
+use web_time::Instant;
 
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider};
 use crate::key::lqe;
@@ -43,6 +45,8 @@ pub(crate) struct LiveQueryRouter {
 	/// Highest versionstamp already delivered; the next pass scans strictly
 	/// after it. `None` until the first pass establishes the baseline.
 	cursor: Mutex<Option<u128>>,
+	/// When the last pass completed. `None` until the first pass completes.
+	completed: Mutex<Option<Instant>>,
 }
 
 impl LiveQueryRouter {
@@ -84,6 +88,17 @@ impl LiveQueryRouter {
 	pub(crate) fn set_baseline(&self, safe_vs: u128) {
 		*self.cursor.lock() = Some(safe_vs);
 	}
+
+	/// Record that a pass completed successfully.
+	pub(crate) fn record_pass(&self) {
+		*self.completed.lock() = Some(Instant::now());
+	}
+
+	/// How long ago the last pass completed, or `None` if no pass has completed
+	/// yet.
+	pub(crate) fn lag(&self) -> Option<Duration> {
+		self.completed.lock().map(|completed| completed.elapsed())
+	}
 }
 
 /// Run one tail-and-deliver pass for the Router engine.
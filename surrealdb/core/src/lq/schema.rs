@@ -0,0 +1,198 @@
+//! Live notifications of schema changes.
+//!
+//! A `LIVE SCHEMA` query subscribes to the schema of the current database. The
+//! subscription is stored under [`crate::key::database::lq`], and whenever a
+//! table, field, index, access method, or param of the database is defined,
+//! altered, or removed, the statement sends a notification to every subscriber
+//! through the context's broker. Like record notifications, these are buffered
+//! by the executor and only delivered once the transaction commits.
+//!
+//! The notifications of params carry their new value, so that params used as
+//! feature flags can be followed without reading them again.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::catalog::SubscriptionDefinition;
+use crate::ctx::FrozenContext;
+use crate::dbs::{Options, RoutedNotification};
+use crate::expr::Base;
+use crate::expr::paths::ID;
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::KVValue;
+use crate::types::{PublicAction, PublicNotification};
+use crate::val::{Value, convert_value_to_public_value};
+
+/// How a schema resource was changed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum SchemaAction {
+	Define,
+	Alter,
+	Remove,
+}
+
+impl SchemaAction {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Define => "define",
+			Self::Alter => "alter",
+			Self::Remove => "remove",
+		}
+	}
+
+	fn notification_action(&self) -> PublicAction {
+		match self {
+			Self::Define => PublicAction::Create,
+			Self::Alter => PublicAction::Update,
+			Self::Remove => PublicAction::Delete,
+		}
+	}
+}
+
+/// The schema resource which was changed
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum SchemaChange<'a> {
+	Table(&'a str),
+	Field {
+		table: &'a str,
+		name: &'a str,
+	},
+	Index {
+		table: &'a str,
+		name: &'a str,
+	},
+	Access(&'a str),
+	Param {
+		name: &'a str,
+		/// The value of the param, unless it was removed
+		value: Option<&'a Value>,
+	},
+}
+
+impl SchemaChange<'_> {
+	fn kind(&self) -> &'static str {
+		match self {
+			Self::Table(_) => "table",
+			Self::Field {
+				..
+			} => "field",
+			Self::Index {
+				..
+			} => "index",
+			Self::Access(_) => "access",
+			Self::Param {
+				..
+			} => "param",
+		}
+	}
+
+	fn name(&self) -> &str {
+		match self {
+			Self::Table(name)
+			| Self::Access(name)
+			| Self::Field {
+				name,
+				..
+			}
+			| Self::Index {
+				name,
+				..
+			}
+			| Self::Param {
+				name,
+				..
+			} => name,
+		}
+	}
+
+	fn table(&self) -> Option<&str> {
+		match self {
+			Self::Field {
+				table,
+				..
+			}
+			| Self::Index {
+				table,
+				..
+			} => Some(table),
+			Self::Table(_)
+			| Self::Access(_)
+			| Self::Param {
+				..
+			} => None,
+		}
+	}
+
+	fn structure(&self, action: SchemaAction) -> Value {
+		Value::from(map! {
+			"action" => Value::from(action.as_str()),
+			"kind" => Value::from(self.kind()),
+			"name" => Value::from(self.name()),
+			"table", if let Some(table) = self.table() => Value::from(table),
+			"value", if let Self::Param { value: Some(value), .. } = self => (*value).clone(),
+		})
+	}
+}
+
+/// Notifies the schema subscriptions of the current database of a change.
+///
+/// Subscribers whose session has expired, or who are no longer allowed to view
+/// the database, are skipped.
+pub(crate) async fn notify_schema_change(
+	ctx: &FrozenContext,
+	opt: &Options,
+	action: SchemaAction,
+	change: SchemaChange<'_>,
+) -> Result<()> {
+	// Notifications are only sent when a broker is installed
+	let Some(sender) = ctx.broker() else {
+		return Ok(());
+	};
+	let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+	let beg = crate::key::database::lq::prefix(ns, db)?;
+	let end = crate::key::database::lq::suffix(ns, db)?;
+	let subscriptions = ctx.tx().getr(beg..end, None).await?;
+	if subscriptions.is_empty() {
+		return Ok(());
+	}
+	let record = convert_value_to_public_value(Value::from(change.name()))?;
+	let result = convert_value_to_public_value(change.structure(action))?;
+	for (_, v) in subscriptions.iter() {
+		let subscription = SubscriptionDefinition::kv_decode_value(v, ())?;
+		// Ensure that a session and auth exist on the subscription
+		let (Some(sess), Some(auth)) = (&subscription.session, &subscription.auth) else {
+			continue;
+		};
+		// Skip subscriptions whose session has expired
+		if let Value::Object(session) = sess
+			&& let Some(Value::Number(exp)) = session.get("exp")
+			&& Utc::now().timestamp() > (*exp).to_int()
+		{
+			continue;
+		}
+		// Skip subscribers which can no longer view the database
+		let opt = opt.with_auth(Arc::new(auth.clone()));
+		if ctx.is_allowed(&opt, Action::View, ResourceKind::Any, Base::Db).is_err() {
+			continue;
+		}
+		if !sender.should_emit(*ctx.node_id().as_bytes(), *subscription.node.as_bytes())? {
+			continue;
+		}
+		let session_id = match sess.pick(ID.as_ref()) {
+			Value::Uuid(uuid) => Some(uuid.into()),
+			Value::String(s) => s.parse::<crate::val::Uuid>().ok().map(|uuid| uuid.into()),
+			_ => None,
+		};
+		let notification = PublicNotification::new(
+			subscription.id.into(),
+			session_id,
+			action.notification_action(),
+			record.clone(),
+			result.clone(),
+		);
+		sender.send(RoutedNotification::new(subscription.node, notification)).await;
+	}
+	Ok(())
+}
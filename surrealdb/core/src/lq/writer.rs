@@ -48,6 +48,7 @@ impl LiveEventBuffer {
 		id: RecordId,
 		before: Value,
 		after: Value,
+		eviction: bool,
 	) {
 		let mut buffer = self.buffer.lock();
 		buffer
@@ -57,7 +58,7 @@ impl LiveEventBuffer {
 				tb: tb.clone(),
 			})
 			.or_insert_with(LiveEvents::new)
-			.push_record_change(id, before, after);
+			.push_record_change(id, before, after, eviction);
 	}
 
 	/// Returns all buffered events as prepared writes. The commit versionstamp is
@@ -47,7 +47,8 @@ pub const INTERNAL: &str = "internal";
 /// - `Configuration` → [`CLIENT`] (caller asked for an unsupported feature).
 /// - `Query(QueryError::TimedOut)` → [`TIMEOUT`].
 /// - `Query(QueryError::Cancelled)` → [`CTX_CANCELLED`].
-/// - `Query(QueryError::TransactionConflict)` → [`TXN_CONFLICT`].
+/// - `Query(QueryError::TransactionConflict)` and `Query(QueryError::DeadlockDetected)` →
+///   [`TXN_CONFLICT`].
 /// - `Query(_)` (incl. `NotExecuted` and `None`) → [`CLIENT`].
 /// - `Serialization` / `NotFound` / `AlreadyExists` / `Connection` / `Thrown` → [`CLIENT`].
 /// - `NotAllowed` → [`PERMISSION`].
@@ -63,7 +64,10 @@ pub fn classify_types_error(err: &surrealdb_types::Error) -> &'static str {
 				..
 			}) => TIMEOUT,
 			Some(QueryError::Cancelled) => CTX_CANCELLED,
-			Some(QueryError::TransactionConflict) => TXN_CONFLICT,
+			Some(QueryError::TransactionConflict)
+			| Some(QueryError::DeadlockDetected {
+				..
+			}) => TXN_CONFLICT,
 			// `NotExecuted` and the wire-form `None` collapse to the
 			// generic client bucket: the executor's unexecuted-statement
 			// emit path already records the more specific `txn_*` /
@@ -63,10 +63,12 @@ pub enum StatementType {
 	Define,
 	Remove,
 	Rebuild,
+	Refresh,
 	Alter,
 	Info,
 	Live,
 	Kill,
+	Lock,
 	Let,
 	Return,
 	Foreach,
@@ -101,10 +103,12 @@ impl StatementType {
 			Self::Define => "define",
 			Self::Remove => "remove",
 			Self::Rebuild => "rebuild",
+			Self::Refresh => "refresh",
 			Self::Alter => "alter",
 			Self::Info => "info",
 			Self::Live => "live",
 			Self::Kill => "kill",
+			Self::Lock => "lock",
 			Self::Let => "let",
 			Self::Return => "return",
 			Self::Foreach => "foreach",
@@ -134,8 +138,10 @@ impl StatementType {
 			TopLevelExpr::Commit => Self::Commit,
 			TopLevelExpr::Access(_) => Self::Access,
 			TopLevelExpr::Kill(_) => Self::Kill,
-			TopLevelExpr::Live(_) => Self::Live,
+			TopLevelExpr::Lock(_) => Self::Lock,
+			TopLevelExpr::Live(_) | TopLevelExpr::LiveSchema(_) => Self::Live,
 			TopLevelExpr::Option(_) => Self::Option,
+			TopLevelExpr::Refresh(_) => Self::Refresh,
 			TopLevelExpr::Use(_) => Self::Use,
 			TopLevelExpr::Show(_) => Self::Show,
 			TopLevelExpr::Expr(expr) => Self::from_expr(expr),
@@ -145,7 +151,7 @@ impl StatementType {
 	/// Classify a bare [`Expr`] into its bounded statement category.
 	pub(crate) fn from_expr(expr: &Expr) -> Self {
 		match expr {
-			Expr::Select(_) => Self::Select,
+			Expr::Select(_) | Expr::With(_) => Self::Select,
 			Expr::Create(_) => Self::Create,
 			Expr::Update(_) => Self::Update,
 			Expr::Upsert(_) => Self::Upsert,
@@ -248,6 +254,8 @@ pub enum AuthAction {
 	Refresh,
 	Invalidate,
 	Revoke,
+	Impersonate,
+	StopImpersonating,
 }
 
 impl AuthAction {
@@ -259,6 +267,8 @@ impl AuthAction {
 			Self::Refresh => "refresh",
 			Self::Invalidate => "invalidate",
 			Self::Revoke => "revoke",
+			Self::Impersonate => "impersonate",
+			Self::StopImpersonating => "stop_impersonating",
 		}
 	}
 }
@@ -449,6 +459,10 @@ pub struct StatementEventSafe {
 /// [`NetworkBytesEventCtx::from_session`]: anonymous sessions map to `None`,
 /// record-access principals to a fixed `<record>` sentinel, and everything
 /// else to the actor id.
+///
+/// `impersonator` is the actor id of the administrator impersonating the
+/// session's user, if any, so that audit sinks can attribute the statements
+/// run while impersonating to the administrator who ran them.
 #[derive(Clone, Debug, Default)]
 pub struct TenantIdentity {
 	pub namespace: Option<String>,
@@ -456,6 +470,7 @@ pub struct TenantIdentity {
 	pub user: Option<String>,
 	pub session_id: Option<Uuid>,
 	pub client_ip: Option<IpAddr>,
+	pub impersonator: Option<String>,
 }
 
 impl TenantIdentity {
@@ -475,6 +490,7 @@ impl TenantIdentity {
 			user,
 			session_id: sess.id,
 			client_ip,
+			impersonator: sess.impersonator.as_ref().map(|i| i.au.id().to_owned()),
 		}
 	}
 
@@ -487,6 +503,7 @@ impl TenantIdentity {
 			user: self.user.clone(),
 			session_id: self.session_id,
 			client_ip: self.client_ip,
+			impersonator: self.impersonator.clone(),
 		}
 	}
 
@@ -531,6 +548,7 @@ impl TenantIdentity {
 			user: self.user.clone(),
 			session_id: self.session_id,
 			client_ip: self.client_ip,
+			impersonator: self.impersonator.clone(),
 		}
 	}
 }
@@ -547,6 +565,8 @@ pub struct StatementEventCtx {
 	pub user: Option<String>,
 	pub session_id: Option<Uuid>,
 	pub client_ip: Option<IpAddr>,
+	/// The administrator impersonating `user`, if any.
+	pub impersonator: Option<String>,
 }
 
 /// Emitted once per top-level statement completion.
@@ -722,6 +742,12 @@ impl TransactionMetrics {
 		}
 	}
 
+	/// The number of key and value bytes written so far.
+	pub fn bytes_written(&self) -> u64 {
+		let key_bytes = self.key_bytes_written.load(Ordering::Relaxed);
+		key_bytes.saturating_add(self.value_bytes_written.load(Ordering::Relaxed))
+	}
+
 	/// Freeze the current counter values into a `Copy` snapshot suitable for
 	/// attaching to a [`TransactionEvent`]. Derived totals (`total_bytes_*`,
 	/// `ops_total`) are computed here so consumers do not pay extra atomic
@@ -840,6 +866,8 @@ pub struct AuthEventCtx {
 	pub user: Option<String>,
 	pub session_id: Option<Uuid>,
 	pub client_ip: Option<IpAddr>,
+	/// The administrator impersonating `user`, if any.
+	pub impersonator: Option<String>,
 }
 
 /// Emitted once per authentication attempt (both successful and failed).
@@ -992,6 +1020,7 @@ impl HttpRequestEventCtx {
 			user,
 			session_id: sess.id,
 			client_ip,
+			impersonator: sess.impersonator.as_ref().map(|i| i.au.id().to_owned()),
 		}
 	}
 
@@ -1192,10 +1221,12 @@ mod tests {
 			StatementType::Define.as_label(),
 			StatementType::Remove.as_label(),
 			StatementType::Rebuild.as_label(),
+			StatementType::Refresh.as_label(),
 			StatementType::Alter.as_label(),
 			StatementType::Info.as_label(),
 			StatementType::Live.as_label(),
 			StatementType::Kill.as_label(),
+			StatementType::Lock.as_label(),
 			StatementType::Let.as_label(),
 			StatementType::Return.as_label(),
 			StatementType::Foreach.as_label(),
@@ -40,6 +40,25 @@ pub struct EngineOptions {
 	///
 	/// Default: 5 seconds
 	pub event_processing_interval: Duration,
+	/// Interval for moving records matching `DEFINE POLICY` archival policies
+	/// into their destination tables.
+	///
+	/// Default: 60 seconds
+	pub archival_interval: Duration,
+	/// Interval for purging soft deleted records once the retention period
+	/// set with `SOFT DELETE ... PURGE AFTER` has passed.
+	///
+	/// Default: 60 seconds
+	pub soft_delete_purge_interval: Duration,
+	/// Interval for evicting the records of tables defined with `EVICT`.
+	///
+	/// Default: 10 seconds
+	pub eviction_interval: Duration,
+	/// Interval for validating the stored records against their checksums,
+	/// when record checksums are enabled.
+	///
+	/// Default: 1 hour
+	pub scrub_interval: Duration,
 	/// Interval at which the per-node live-query router tails the dedicated
 	/// `lqe` keyspace and delivers notifications off the write path.
 	///
@@ -117,6 +136,10 @@ impl Default for EngineOptions {
 			index_compaction_interval: Duration::from_secs(5),
 			index_build_resume_interval: Duration::from_secs(30),
 			event_processing_interval: Duration::from_secs(5),
+			archival_interval: Duration::from_secs(60),
+			soft_delete_purge_interval: Duration::from_secs(60),
+			eviction_interval: Duration::from_secs(10),
+			scrub_interval: Duration::from_secs(3600),
 			live_query_router_interval: Duration::from_millis(100),
 			reclaim_interval: Duration::from_secs(60),
 			reclaim_grace: Duration::from_secs(600),
@@ -160,6 +183,26 @@ impl EngineOptions {
 		self
 	}
 
+	pub fn with_archival_interval(mut self, interval: Duration) -> Self {
+		self.archival_interval = interval;
+		self
+	}
+
+	pub fn with_soft_delete_purge_interval(mut self, interval: Duration) -> Self {
+		self.soft_delete_purge_interval = interval;
+		self
+	}
+
+	pub fn with_eviction_interval(mut self, interval: Duration) -> Self {
+		self.eviction_interval = interval;
+		self
+	}
+
+	pub fn with_scrub_interval(mut self, interval: Duration) -> Self {
+		self.scrub_interval = interval;
+		self
+	}
+
 	pub fn with_live_query_router_interval(mut self, interval: Duration) -> Self {
 		self.live_query_router_interval = interval;
 		self
@@ -1,6 +1,7 @@
 pub mod cbor;
 pub mod flatbuffers;
 pub mod json;
+pub mod ndjson;
 
 pub const PROTOCOLS: [&str; 3] = [
 	"json",        // For basic JSON serialisation
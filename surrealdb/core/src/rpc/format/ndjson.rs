@@ -0,0 +1,78 @@
+//! Newline-delimited JSON output of query results.
+//!
+//! Each row of a statement result is written as a JSON document on its own
+//! line, so that results can be piped into data tools as they are produced.
+//! A statement which returns an array writes one line for each element, a
+//! statement which returns no value writes nothing, and a statement which
+//! returns any other value writes that value as a single line. A failed
+//! statement writes its result in the usual query result shape, with an `ERR`
+//! status, so that failures are not silently dropped from the output.
+
+use surrealdb_types::SurrealValue;
+
+use super::json;
+use crate::dbs::QueryResult;
+use crate::types::PublicValue;
+
+/// Encodes the rows of a statement result as newline-delimited JSON, one
+/// line at a time, so that each row can be streamed as soon as it is encoded
+pub fn lines(result: QueryResult) -> impl Iterator<Item = anyhow::Result<Vec<u8>>> {
+	let rows = match result.result {
+		Ok(PublicValue::None) => Vec::new(),
+		Ok(PublicValue::Array(rows)) => rows.into_iter().collect(),
+		Ok(row) => vec![row],
+		Err(_) => vec![result.into_value()],
+	};
+	rows.into_iter().map(line)
+}
+
+fn line(value: PublicValue) -> anyhow::Result<Vec<u8>> {
+	let mut out = json::encode(value)?;
+	out.push(b'\n');
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use surrealdb_types::Error as TypesError;
+
+	use super::*;
+	use crate::dbs::{QueryStats, QueryType};
+	use crate::types::PublicNumber;
+
+	fn result(result: Result<PublicValue, TypesError>) -> QueryResult {
+		QueryResult {
+			time: Duration::ZERO,
+			result,
+			query_type: QueryType::Other,
+			stats: QueryStats::default(),
+		}
+	}
+
+	fn encode(result: QueryResult) -> Vec<Vec<u8>> {
+		lines(result).collect::<anyhow::Result<_>>().unwrap()
+	}
+
+	#[test]
+	fn writes_one_line_per_row() {
+		let rows = PublicValue::Array(
+			vec![PublicValue::Number(PublicNumber::Int(1)), PublicValue::String("a".to_owned())]
+				.into(),
+		);
+		assert_eq!(encode(result(Ok(rows))), vec![b"1\n".to_vec(), b"\"a\"\n".to_vec()]);
+		assert_eq!(encode(result(Ok(PublicValue::Bool(true)))), vec![b"true\n".to_vec()]);
+		assert!(encode(result(Ok(PublicValue::None))).is_empty());
+	}
+
+	#[test]
+	fn writes_failures_as_error_results() {
+		let mut out = encode(result(Err(TypesError::internal("boom".to_owned()))));
+		assert_eq!(out.len(), 1);
+		let line = String::from_utf8(out.remove(0)).unwrap();
+		assert!(line.ends_with('\n'));
+		assert!(line.contains(r#""status":"ERR""#), "{line}");
+		assert!(line.contains(r#""result":"boom""#), "{line}");
+	}
+}
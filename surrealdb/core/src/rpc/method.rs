@@ -11,6 +11,8 @@ pub enum Method {
 	Refresh,
 	Invalidate,
 	Revoke,
+	Impersonate,
+	StopImpersonating,
 	Reset,
 	Kill,
 	Live,
@@ -25,6 +27,7 @@ pub enum Method {
 	Patch,
 	Delete,
 	Version,
+	Compatibility,
 	Query,
 	Gql,
 	Graphql,
@@ -34,6 +37,8 @@ pub enum Method {
 	Attach,
 	Sessions,
 	Detach,
+	ResumeToken,
+	Resume,
 	Begin,
 	Commit,
 	Cancel,
@@ -71,6 +76,8 @@ impl Method {
 			"refresh" => Self::Refresh,
 			"invalidate" => Self::Invalidate,
 			"revoke" => Self::Revoke,
+			"impersonate" => Self::Impersonate,
+			"stop_impersonating" => Self::StopImpersonating,
 			"reset" => Self::Reset,
 			"kill" => Self::Kill,
 			"live" => Self::Live,
@@ -85,6 +92,7 @@ impl Method {
 			"patch" => Self::Patch,
 			"delete" => Self::Delete,
 			"version" => Self::Version,
+			"compatibility" => Self::Compatibility,
 			"query" => Self::Query,
 			"gql" => Self::Gql,
 			"graphql" => Self::Graphql,
@@ -94,6 +102,8 @@ impl Method {
 			"attach" => Self::Attach,
 			"sessions" => Self::Sessions,
 			"detach" => Self::Detach,
+			"resume_token" => Self::ResumeToken,
+			"resume" => Self::Resume,
 			"begin" => Self::Begin,
 			"commit" => Self::Commit,
 			"cancel" => Self::Cancel,
@@ -118,6 +128,8 @@ impl Method {
 			Self::Refresh => "refresh",
 			Self::Invalidate => "invalidate",
 			Self::Revoke => "revoke",
+			Self::Impersonate => "impersonate",
+			Self::StopImpersonating => "stop_impersonating",
 			Self::Reset => "reset",
 			Self::Kill => "kill",
 			Self::Live => "live",
@@ -132,6 +144,7 @@ impl Method {
 			Self::Patch => "patch",
 			Self::Delete => "delete",
 			Self::Version => "version",
+			Self::Compatibility => "compatibility",
 			Self::Query => "query",
 			Self::Gql => "gql",
 			Self::Graphql => "graphql",
@@ -141,6 +154,8 @@ impl Method {
 			Self::Attach => "attach",
 			Self::Sessions => "sessions",
 			Self::Detach => "detach",
+			Self::ResumeToken => "resume_token",
+			Self::Resume => "resume",
 			Self::Begin => "begin",
 			Self::Commit => "commit",
 			Self::Cancel => "cancel",
@@ -87,6 +87,8 @@ const fn method_to_auth_action(method: Method) -> Option<AuthAction> {
 		Method::Refresh => Some(AuthAction::Refresh),
 		Method::Invalidate => Some(AuthAction::Invalidate),
 		Method::Revoke => Some(AuthAction::Revoke),
+		Method::Impersonate => Some(AuthAction::Impersonate),
+		Method::StopImpersonating => Some(AuthAction::StopImpersonating),
 		_ => None,
 	}
 }
@@ -162,6 +164,9 @@ pub trait RpcProtocol {
 	async fn del_session(&self, id: &Uuid) {
 		self.session_map().remove(id);
 		self.cleanup_lqs(id).await;
+		if let Err(err) = self.kvs().remove_session_tables(*id).await {
+			warn!("Failed to remove the session tables of session {id}: {err}");
+		}
 	}
 
 	/// Lists all sessions
@@ -175,6 +180,18 @@ pub trait RpcProtocol {
 		Ok(DbResult::Other(PublicValue::Array(array)))
 	}
 
+	/// Issues a token with which the sessions of this connection can be
+	/// resumed on a new connection. Resumption is not supported by default
+	async fn resume_token(&self) -> Result<DbResult, surrealdb_types::Error> {
+		Err(method_not_found(Method::ResumeToken.to_string()))
+	}
+
+	/// Restores the sessions of a previous connection from a resumption
+	/// token. Resumption is not supported by default
+	async fn resume(&self, _params: PublicArray) -> Result<DbResult, surrealdb_types::Error> {
+		Err(method_not_found(Method::Resume.to_string()))
+	}
+
 	// ------------------------------
 	// Transactions
 	// ------------------------------
@@ -227,6 +244,17 @@ pub trait RpcProtocol {
 		async { unimplemented!("handle_kill function must be implemented if LQ_SUPPORT = true") }
 	}
 
+	/// Handles a change to a session parameter, so that the live queries
+	/// started by the session use the current value of the parameter
+	fn handle_param(
+		&self,
+		_session_id: Uuid,
+		_name: &str,
+		_value: PublicValue,
+	) -> impl std::future::Future<Output = ()> + Send {
+		async {}
+	}
+
 	/// Handles the cleanup of live queries
 	fn cleanup_lqs(&self, session_id: &Uuid) -> impl std::future::Future<Output = ()> + Send;
 
@@ -276,6 +304,15 @@ pub trait RpcProtocol {
 				warn!("Capabilities denied RPC method call attempt, target: '{method}'");
 				return Err(method_not_allowed(method.to_string()));
 			}
+			// The authentication can not change while impersonating a user
+			if matches!(
+				method,
+				Method::Signup | Method::Signin | Method::Authenticate | Method::Refresh
+			) {
+				let session_lock = self.get_session(&session)?;
+				crate::iam::impersonate::ensure_not_impersonating(&*session_lock.read().await)
+					.map_err(types_error_from_anyhow)?;
+			}
 			// Execute the desired method
 			match method {
 				Method::Ping => Ok(DbResult::Other(PublicValue::None)),
@@ -287,6 +324,8 @@ pub trait RpcProtocol {
 				Method::Refresh => self.refresh(session, params).await,
 				Method::Invalidate => self.invalidate(session).await,
 				Method::Revoke => self.revoke(params).await,
+				Method::Impersonate => self.impersonate(session, params).await,
+				Method::StopImpersonating => self.stop_impersonating(session).await,
 				Method::Reset => self.reset(session).await,
 				Method::Kill => self.kill(txn, session, params).await,
 				Method::Live => self.live(txn, session, params).await,
@@ -296,6 +335,7 @@ pub trait RpcProtocol {
 				Method::Gql => self.gql(txn, session, params).await,
 				Method::Graphql => self.graphql(txn, session, params).await,
 				Method::Version => self.version(txn, params).await,
+				Method::Compatibility => self.compatibility(params).await,
 				Method::Begin => self.begin(txn, session).await,
 				Method::Commit => self.commit(txn, session, params).await,
 				Method::Cancel => self.cancel(txn, session, params).await,
@@ -308,6 +348,8 @@ pub trait RpcProtocol {
 					Some(id) => self.detach(id).await,
 					None => Err(invalid_params("Expected a session ID")),
 				},
+				Method::ResumeToken => self.resume_token().await,
+				Method::Resume => self.resume(params).await,
 				// Deprecated methods
 				Method::Select => self.select(txn, session, params).await,
 				Method::Insert => self.insert(txn, session, params).await,
@@ -791,6 +833,68 @@ pub trait RpcProtocol {
 		Ok(DbResult::Other(PublicValue::None))
 	}
 
+	/// Starts impersonating a record or system user.
+	///
+	/// Only owners and editors at the root or namespace level may impersonate
+	/// a user, and only when the experimental `impersonation` capability is
+	/// enabled. Until impersonation is stopped, every method runs under the
+	/// authentication of the impersonated user, and the audit events of the
+	/// session record the administrator as the impersonator. See
+	/// [`crate::iam::impersonate`].
+	#[tracing::instrument(
+		level = "debug",
+		target = "surrealdb::core::rpc",
+		name = "rpc.impersonate",
+		skip_all,
+		fields(rpc.session = %session_id)
+	)]
+	async fn impersonate(
+		&self,
+		session_id: Uuid,
+		params: PublicArray,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		// Process the method arguments
+		let Some(PublicValue::Object(params)) = extract_args(params.into_vec()) else {
+			return Err(invalid_params("Expected (subject:object)".to_string()));
+		};
+		// Get a write lock on the session
+		let session_lock = self.get_session(&session_id)?;
+		let mut session = session_lock.write().await;
+		let snapshot = AuthPrincipalSnapshot::capture(&session);
+		// Attempt impersonation, mutating the session
+		let out = crate::iam::impersonate::impersonate(self.kvs(), &mut session, params.into())
+			.await
+			.map(|_| PublicValue::None);
+		let principal_changed = snapshot.differs_from(&session);
+		drop(session);
+		if principal_changed {
+			self.cleanup_lqs(&session_id).await;
+		}
+		// Return nothing on success
+		out.map(DbResult::Other).map_err(types_error_from_anyhow)
+	}
+
+	/// Stops impersonating, restoring the authentication of the administrator
+	async fn stop_impersonating(
+		&self,
+		session_id: Uuid,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		// Get a write lock on the session
+		let session_lock = self.get_session(&session_id)?;
+		let mut session = session_lock.write().await;
+		let snapshot = AuthPrincipalSnapshot::capture(&session);
+		// Restore the impersonating session
+		crate::iam::impersonate::stop(&mut session).map_err(types_error_from_anyhow)?;
+		let principal_changed = snapshot.differs_from(&session);
+		drop(session);
+		// Live queries started while impersonating must not outlive it
+		if principal_changed {
+			self.cleanup_lqs(&session_id).await;
+		}
+		// Return nothing on success
+		Ok(DbResult::Other(PublicValue::None))
+	}
+
 	async fn reset(&self, session_id: Uuid) -> Result<DbResult, surrealdb_types::Error> {
 		// Get a write lock on the session
 		let session_lock = self.get_session(&session_id)?;
@@ -857,13 +961,20 @@ pub trait RpcProtocol {
 			return Err(session_expired());
 		}
 
-		match val {
-			None | Some(PublicValue::None) => session.variables.remove(key.as_str()),
+		let val = match val {
+			None | Some(PublicValue::None) => {
+				session.variables.remove(key.as_str());
+				PublicValue::None
+			}
 			Some(val) => {
 				crate::rpc::check_protected_param(&key)?;
-				session.variables.insert(key, val)
+				session.variables.insert(key.clone(), val.clone());
+				val
 			}
-		}
+		};
+		// Release the session before updating the live queries
+		drop(session);
+		self.handle_param(session_id, &key, val).await;
 
 		// Return nothing
 		Ok(DbResult::Other(PublicValue::Null))
@@ -893,6 +1004,9 @@ pub trait RpcProtocol {
 		// Get a write lock on the session
 		let mut session = session_lock.write().await;
 		session.variables.remove(key.as_str());
+		// Release the session before updating the live queries
+		drop(session);
+		self.handle_param(session_id, &key, PublicValue::None).await;
 
 		Ok(DbResult::Other(PublicValue::Null))
 	}
@@ -1032,8 +1146,11 @@ pub trait RpcProtocol {
 			fetch: None,
 			version: Expr::Literal(Literal::None),
 			timeout: Expr::Literal(Literal::None),
+			partial: false,
 			explain: None,
 			tempfiles: false,
+			valid_time: None,
+			scan_limit: None,
 		};
 		let ast = Ast::single_expr(Expr::Select(Box::new(sql)));
 
@@ -1084,6 +1201,7 @@ pub trait RpcProtocol {
 			update: None,
 			timeout: Expr::Literal(Literal::None),
 			relation: false,
+			non_atomic: false,
 		};
 		let ast = Ast::single_expr(Expr::Insert(Box::new(sql)));
 		// Specify the query parameters
@@ -1131,6 +1249,7 @@ pub trait RpcProtocol {
 			ignore: false,
 			update: None,
 			timeout: Expr::Literal(Literal::None),
+			non_atomic: false,
 		};
 		let ast = Ast::single_expr(Expr::Insert(Box::new(sql)));
 		// Specify the query parameters
@@ -1544,6 +1663,24 @@ pub trait RpcProtocol {
 		}
 	}
 
+	/// Reports the version of the server along with the revision of the
+	/// storage format of its datastore, so that clients can check that they
+	/// are compatible with the server when connecting
+	async fn compatibility(&self, params: PublicArray) -> Result<DbResult, surrealdb_types::Error> {
+		if !params.is_empty() {
+			return Err(invalid_params("Expected 0 arguments".to_string()));
+		}
+		let version = match self.version_data() {
+			DbResult::Other(version) => version,
+			_ => PublicValue::None,
+		};
+		let storage = self.kvs().storage_version().await.map_err(types_error_from_anyhow)?;
+		Ok(DbResult::Other(PublicValue::from_t(object! {
+			version: version,
+			storage: storage.map(i64::from),
+		})))
+	}
+
 	// ------------------------------
 	// Methods for querying
 	// ------------------------------
@@ -1561,9 +1698,13 @@ pub trait RpcProtocol {
 			return Err(method_not_allowed(Method::Query.to_string()));
 		}
 		// Process the method arguments
-		let (query, vars) =
-			extract_args::<(PublicValue, Option<PublicValue>)>(params.into_vec())
-				.ok_or(invalid_params("Expected (query:string, vars:object)".to_string()))?;
+		let (query, vars, options) =
+			extract_args::<(PublicValue, Option<PublicValue>, Option<PublicValue>)>(
+				params.into_vec(),
+			)
+			.ok_or(invalid_params(
+				"Expected (query:string, vars:object, options:object)".to_string(),
+			))?;
 
 		let PublicValue::String(query) = query else {
 			return Err(invalid_params("Expected query to be string".to_string()));
@@ -1584,10 +1725,32 @@ pub trait RpcProtocol {
 			}
 		};
 
+		// Check for an idempotency key
+		let key = match options {
+			Some(PublicValue::Object(mut o)) => match o.remove("idempotency_key") {
+				Some(PublicValue::String(key)) => Some(key),
+				None | Some(PublicValue::None | PublicValue::Null) => None,
+				unexpected => {
+					return Err(invalid_params(format!(
+						"Expected idempotency_key to be string, got {unexpected:?}"
+					)));
+				}
+			},
+			None | Some(PublicValue::None | PublicValue::Null) => None,
+			unexpected => {
+				return Err(invalid_params(format!(
+					"Expected options to be object, got {unexpected:?}"
+				)));
+			}
+		};
+
+		let query = match key.as_deref() {
+			Some(key) => QueryForm::Idempotent(&query, key),
+			None => QueryForm::Text(&query),
+		};
+
 		Ok(DbResult::Query(
-			run_query(self, txn, session_id, QueryForm::Text(&query), vars)
-				.await
-				.map_err(types_error_from_anyhow)?,
+			run_query(self, txn, session_id, query, vars).await.map_err(types_error_from_anyhow)?,
 		))
 	}
 
@@ -1925,6 +2088,8 @@ pub trait RpcProtocol {
 
 enum QueryForm<'a> {
 	Text(&'a str),
+	/// A query along with its idempotency key
+	Idempotent(&'a str, &'a str),
 	Parsed(Ast),
 	/// A pre-lowered GQL query. Only constructed by the `gql` handler, which
 	/// is itself gated behind the `gql` feature.
@@ -1963,6 +2128,12 @@ where
 			(QueryForm::Text(query), None) => {
 				this.kvs().execute_with_transaction(query, &session, vars, tx).await?
 			}
+			(QueryForm::Idempotent(..), _) => {
+				return Err(crate::err::Error::InvalidIdempotencyKey(
+					"idempotency keys can not be used within a transaction".to_owned(),
+				)
+				.into());
+			}
 			(QueryForm::Parsed(ast), Some(cancel)) => {
 				this.kvs()
 					.process_with_transaction_and_cancel(ast, &session, vars, tx, cancel)
@@ -1989,6 +2160,9 @@ where
 				this.kvs().execute_with_cancel(query, &session, vars, cancel).await?
 			}
 			(QueryForm::Text(query), None) => this.kvs().execute(query, &session, vars).await?,
+			(QueryForm::Idempotent(query, key), _) => {
+				this.kvs().execute_idempotent(query, &session, vars, key).await?
+			}
 			(QueryForm::Parsed(ast), Some(cancel)) => {
 				this.kvs().process_with_cancel(ast, &session, vars, cancel).await?
 			}
@@ -13,6 +13,7 @@ pub static VERSION: &str = "version";
 pub static TXN: &str = "txn";
 pub static SESSION_ID: &str = "session";
 pub static TRACE_CONTEXT: &str = "trace_context";
+pub static TRACE_ID: &str = "trace_id";
 
 #[derive(Debug)]
 pub struct Request {
@@ -32,9 +33,55 @@ pub struct Request {
 	/// spec title ("W3C Trace Context") and to make clear the field
 	/// carries the full propagation context, not just a trace id.
 	pub trace_context: Option<HashMap<String, String>>,
+	/// Optional client-supplied identifier used to correlate the request
+	/// with the server logs. When present, the server layer records it on
+	/// the per-request span, so every span and log line emitted while the
+	/// request is processed carries it.
+	pub trace_id: Option<String>,
 }
 
 impl Request {
+	/// Create a request for the given method, without any parameters.
+	pub fn new(method: Method) -> Self {
+		Request {
+			id: None,
+			version: None,
+			session_id: None,
+			txn: None,
+			method,
+			params: PublicArray::new(),
+			trace_context: None,
+			trace_id: None,
+		}
+	}
+
+	/// Convert the request into the surrealql object which is sent over the
+	/// wire. This is the inverse of [`Request::from_object`].
+	pub fn into_object(self) -> PublicObject {
+		let mut obj = PublicObject::new();
+		if let Some(id) = self.id {
+			obj.insert(ID, id);
+		}
+		if let Some(version) = self.version {
+			obj.insert(VERSION, i64::from(version));
+		}
+		if let Some(session_id) = self.session_id {
+			obj.insert(SESSION_ID, session_id);
+		}
+		if let Some(txn) = self.txn {
+			obj.insert(TXN, txn);
+		}
+		obj.insert(METHOD, self.method.to_str());
+		obj.insert(PARAMS, self.params);
+		if let Some(trace_context) = self.trace_context {
+			obj.insert(TRACE_CONTEXT, PublicObject::from(trace_context));
+		}
+		if let Some(trace_id) = self.trace_id {
+			obj.insert(TRACE_ID, trace_id);
+		}
+		obj
+	}
+
 	/// Create a request by extracting the request fields from an surealql
 	/// object.
 	pub fn from_object(mut obj: PublicObject) -> Result<Self, TypesError> {
@@ -124,6 +171,12 @@ impl Request {
 			}
 			_ => return Err(invalid_request()),
 		};
+		// Fetch the optional 'trace_id' argument
+		let trace_id = match obj.remove(TRACE_ID) {
+			None | Some(PublicValue::None | PublicValue::Null) => None,
+			Some(PublicValue::String(v)) => Some(v),
+			_ => return Err(invalid_request()),
+		};
 		// Parse the specified method
 		let method = Method::parse_case_sensitive(method);
 		// Return the parsed request
@@ -135,6 +188,7 @@ impl Request {
 			txn,
 			session_id,
 			trace_context,
+			trace_id,
 		})
 	}
 }
@@ -153,6 +207,22 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn request_round_trips_through_object() {
+		let mut req = Request::new(Method::Query);
+		req.id = Some(PublicValue::from_t(7_i64));
+		req.txn = Some(PublicUuid::new_v7());
+		req.params = PublicArray::from(vec![PublicValue::from_t("RETURN 1")]);
+		req.trace_id = Some("4bf92f3577b34da6a3ce929d0e0e4736".to_string());
+		let parsed = Request::from_object(req.into_object()).unwrap();
+		assert_eq!(parsed.id, Some(PublicValue::from_t(7_i64)));
+		assert_eq!(parsed.method, Method::Query);
+		assert!(parsed.txn.is_some());
+		assert!(parsed.session_id.is_none());
+		assert_eq!(parsed.params, PublicArray::from(vec![PublicValue::from_t("RETURN 1")]));
+		assert_eq!(parsed.trace_id.as_deref(), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+	}
+
 	#[test]
 	fn trace_context_absent_is_none() {
 		let obj = object! { id: 1, method: "ping" };
@@ -231,6 +301,17 @@ mod tests {
 		assert!(req.trace_context.is_none());
 	}
 
+	#[test]
+	fn trace_id_is_parsed() {
+		let obj = object! { id: 1, method: "ping", trace_id: "4bf92f3577b34da6a3ce929d0e0e4736" };
+		let req = Request::from_object(obj).unwrap();
+		assert_eq!(req.trace_id.as_deref(), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+		let obj = object! { id: 1, method: "ping" };
+		assert!(Request::from_object(obj).unwrap().trace_id.is_none());
+		let obj = object! { id: 1, method: "ping", trace_id: 42_i64 };
+		assert!(Request::from_object(obj).is_err());
+	}
+
 	#[test]
 	fn trace_context_with_unsupported_kind_is_invalid_request() {
 		let obj = object! {
@@ -5,14 +5,14 @@ use surrealdb_types::{Error as TypesError, kind, object};
 use uuid::Uuid;
 
 use crate::dbs;
-use crate::dbs::{QueryResult, QueryType};
+use crate::dbs::{QueryResult, QueryStats, QueryType};
 use crate::rpc::request::SESSION_ID;
 use crate::types::{
 	PublicArray, PublicKind, PublicNotification, PublicObject, PublicValue, SurrealValue,
 };
 
 /// Query statistics.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub struct DbResultStats {
 	/// The time taken to execute the query.
@@ -20,6 +20,24 @@ pub struct DbResultStats {
 	/// Note: This comes from the `time` field of the [`crate::dbs::QueryResult`] struct.
 	pub execution_time: Option<Duration>,
 	pub query_type: Option<QueryType>,
+	/// The number of records read while executing the query.
+	pub rows_scanned: u64,
+	/// The number of rows returned by the query.
+	pub rows_returned: u64,
+	/// The names of the indexes read by the query.
+	pub indexes_used: Vec<String>,
+	/// Whether the query read every record of a table instead of using an index.
+	pub table_scan: bool,
+	/// The number of records an `UPSERT` query created.
+	pub records_created: u64,
+	/// The number of records an `UPSERT` query updated, rather than created.
+	pub records_updated: u64,
+	/// The most rows the query plan can return, if the planner could bound
+	/// it.
+	pub max_rows: Option<u64>,
+	/// Whether the query timed out, returning only the rows found before the
+	/// timeout.
+	pub truncated: bool,
 }
 
 impl DbResultStats {
@@ -32,6 +50,18 @@ impl DbResultStats {
 		self.query_type = Some(query_type);
 		self
 	}
+
+	pub fn with_query_stats(mut self, stats: QueryStats) -> Self {
+		self.rows_scanned = stats.rows_scanned;
+		self.rows_returned = stats.rows_returned;
+		self.indexes_used = stats.indexes_used;
+		self.table_scan = stats.table_scan;
+		self.records_created = stats.records_created;
+		self.records_updated = stats.records_updated;
+		self.max_rows = stats.max_rows;
+		self.truncated = stats.truncated;
+		self
+	}
 }
 
 /// The data returned by the database
@@ -122,6 +152,7 @@ impl SurrealValue for DbResult {
 						"CREATE" => crate::types::PublicAction::Create,
 						"UPDATE" => crate::types::PublicAction::Update,
 						"DELETE" => crate::types::PublicAction::Delete,
+						"EVICTED" => crate::types::PublicAction::Evicted,
 						_ => {
 							return Err(TypesError::internal(format!(
 								"Invalid action: {}",
@@ -68,6 +68,9 @@ impl ToSql for AccessType {
 				if ac.bearer.is_some() {
 					write_sql!(f, sql_fmt, " WITH REFRESH")
 				}
+				if let Some(mfa) = &ac.mfa {
+					write_sql!(f, sql_fmt, " WITH MFA {mfa}")
+				}
 				write_sql!(f, sql_fmt, " WITH JWT {}", ac.jwt);
 			}
 			AccessType::Bearer(ac) => {
@@ -286,6 +289,7 @@ pub(crate) struct RecordAccess {
 	pub signin: Option<Expr>,
 	pub jwt: JwtAccess,
 	pub bearer: Option<BearerAccess>,
+	pub mfa: Option<MfaMethod>,
 }
 
 impl From<RecordAccess> for crate::expr::RecordAccess {
@@ -295,6 +299,7 @@ impl From<RecordAccess> for crate::expr::RecordAccess {
 			signin: v.signin.map(Into::into),
 			jwt: v.jwt.into(),
 			bearer: v.bearer.map(Into::into),
+			mfa: v.mfa.map(Into::into),
 		}
 	}
 }
@@ -306,6 +311,37 @@ impl From<crate::expr::RecordAccess> for RecordAccess {
 			signin: v.signin.map(Into::into),
 			jwt: v.jwt.into(),
 			bearer: v.bearer.map(Into::into),
+			mfa: v.mfa.map(Into::into),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) enum MfaMethod {
+	Totp,
+}
+
+impl ToSql for MfaMethod {
+	fn fmt_sql(&self, f: &mut String, _fmt: SqlFormat) {
+		match self {
+			Self::Totp => f.push_str("TOTP"),
+		}
+	}
+}
+
+impl From<MfaMethod> for crate::expr::access_type::MfaMethod {
+	fn from(v: MfaMethod) -> Self {
+		match v {
+			MfaMethod::Totp => Self::Totp,
+		}
+	}
+}
+
+impl From<crate::expr::access_type::MfaMethod> for MfaMethod {
+	fn from(v: crate::expr::access_type::MfaMethod) -> Self {
+		match v {
+			crate::expr::access_type::MfaMethod::Totp => Self::Totp,
 		}
 	}
 }
@@ -120,7 +120,7 @@ pub fn continued_idiom<'a>(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Re
 
 impl<'a> Arbitrary<'a> for RecurseInstruction {
 	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-		let r = match u.int_in_range(0u8..=2)? {
+		let r = match u.int_in_range(0u8..=3)? {
 			0 => RecurseInstruction::Path {
 				inclusive: u.arbitrary()?,
 			},
@@ -141,6 +141,9 @@ impl<'a> Arbitrary<'a> for RecurseInstruction {
 					inclusive: u.arbitrary()?,
 				}
 			}
+			3 => RecurseInstruction::Tree {
+				inclusive: u.arbitrary()?,
+			},
 			_ => unreachable!(),
 		};
 		Ok(r)
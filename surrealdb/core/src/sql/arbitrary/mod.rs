@@ -12,7 +12,9 @@ use surrealdb_types::Duration;
 pub(crate) use utils::*;
 
 use crate::sql::changefeed::ChangeFeed;
+use crate::sql::eviction::Eviction;
 use crate::sql::statements::SleepStatement;
+use crate::sql::throttle::Throttle;
 use crate::val::Bytes;
 
 impl<'a> Arbitrary<'a> for ChangeFeed {
@@ -24,6 +26,28 @@ impl<'a> Arbitrary<'a> for ChangeFeed {
 	}
 }
 
+impl<'a> Arbitrary<'a> for Throttle {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		// A throttle always allows at least one write per second
+		Ok(Self {
+			rate: u.int_in_range(1..=u64::MAX)?,
+			burst: u.int_in_range(1..=u64::MAX)?,
+		})
+	}
+}
+
+impl<'a> Arbitrary<'a> for Eviction {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		// An eviction clause always has a least recently used policy or a
+		// time to live
+		let ttl: Option<Duration> = u.arbitrary()?;
+		Ok(Self {
+			lru: ttl.is_none() || bool::arbitrary(u)?,
+			ttl,
+		})
+	}
+}
+
 impl<'a> Arbitrary<'a> for SleepStatement {
 	fn arbitrary(_u: &mut Unstructured<'a>) -> Result<Self> {
 		Ok(Self {
@@ -9,7 +9,7 @@ use crate::sql::arbitrary::idiom::plain_idiom;
 use crate::sql::arbitrary::{arb_vec1, arb_vec2, atleast_one, basic_idiom};
 use crate::sql::field::Selector;
 use crate::sql::order::{OrderList, Ordering};
-use crate::sql::statements::access::Subject;
+use crate::sql::statements::access::{AccessStatementGrantMany, Subject};
 use crate::sql::statements::define::config::api::Middleware;
 use crate::sql::{
 	Closure, Data, Expr, Fetch, Field, Fields, Function, FunctionCall, Group, Groups, Idiom, Kind,
@@ -209,6 +209,22 @@ impl<'a> Arbitrary<'a> for Subject {
 	}
 }
 
+impl<'a> Arbitrary<'a> for AccessStatementGrantMany {
+	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+		// The subjects of a list are all users or all records
+		let subjects = match u.int_in_range(0u8..=1)? {
+			0 => arb_vec1(u, |u| Ok(Subject::User(u.arbitrary()?)))?,
+			1 => arb_vec1(u, |u| Ok(Subject::Record(record_id_no_range(u)?)))?,
+			_ => unreachable!(),
+		};
+		Ok(AccessStatementGrantMany {
+			ac: u.arbitrary()?,
+			base: u.arbitrary()?,
+			subjects,
+		})
+	}
+}
+
 impl<'a> Arbitrary<'a> for Selector {
 	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
 		let alias = if u.arbitrary()? {
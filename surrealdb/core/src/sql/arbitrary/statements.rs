@@ -11,13 +11,15 @@ use crate::sql::statements::alter::{
 	AlterSystemStatement,
 };
 use crate::sql::statements::define::{
-	DefineAccessStatement, DefineAnalyzerStatement, DefineUserStatement,
+	DefineAccessStatement, DefineAnalyzerStatement, DefineUserStatement, UniqueConflicts,
 };
+use crate::sql::statements::with::{CommonTableExpr, WithStatement};
 use crate::sql::{
 	AccessType, Ast, Base, BinaryOperator, Data, DefineFieldStatement, DefineIndexStatement, Expr,
 	Index, InsertStatement, KillStatement, Kind, Literal, Permission, Permissions, SelectStatement,
 	TopLevelExpr, View,
 };
+use crate::val::TableName;
 
 impl<'a> Arbitrary<'a> for KillStatement {
 	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
@@ -130,6 +132,16 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineIndexStatement {
 			Index::Count(_) => Vec::new(),
 		};
 
+		let conflicts = match index {
+			Index::Uniq => match u.int_in_range(0u8..=2)? {
+				0 => UniqueConflicts::Fail,
+				1 => UniqueConflicts::Validate,
+				2 => UniqueConflicts::Quarantine(u.arbitrary()?),
+				_ => unreachable!(),
+			},
+			_ => UniqueConflicts::Fail,
+		};
+
 		Ok(DefineIndexStatement {
 			kind,
 			name,
@@ -138,6 +150,7 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineIndexStatement {
 			index,
 			comment,
 			concurrently,
+			conflicts,
 		})
 	}
 }
@@ -165,6 +178,7 @@ impl<'a> arbitrary::Arbitrary<'a> for InsertStatement {
 			output: u.arbitrary()?,
 			timeout: u.arbitrary()?,
 			relation: u.arbitrary()?,
+			non_atomic: u.arbitrary()?,
 		})
 	}
 }
@@ -191,6 +205,9 @@ impl<'a> arbitrary::Arbitrary<'a> for SelectStatement {
 			None
 		};
 
+		let timeout: Expr = u.arbitrary()?;
+		let partial = !matches!(timeout, Expr::Literal(Literal::None)) && u.arbitrary()?;
+
 		Ok(SelectStatement {
 			fields,
 			omit: u.arbitrary()?,
@@ -205,9 +222,34 @@ impl<'a> arbitrary::Arbitrary<'a> for SelectStatement {
 			start: u.arbitrary()?,
 			fetch: u.arbitrary()?,
 			version: u.arbitrary()?,
-			timeout: u.arbitrary()?,
+			timeout,
+			partial,
 			explain: u.arbitrary()?,
 			tempfiles: u.arbitrary()?,
+			valid_time: u.arbitrary()?,
+			scan_limit: u.arbitrary::<Option<u64>>()?.map(|x| x.max(1)),
+		})
+	}
+}
+
+impl<'a> arbitrary::Arbitrary<'a> for WithStatement {
+	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+		// Each expression in a WITH clause must have a unique name
+		let names: Vec<TableName> = atleast_one(u)?;
+		let mut exprs: Vec<CommonTableExpr> = Vec::with_capacity(names.len());
+		for name in names {
+			let name = name.into_string();
+			if exprs.iter().any(|x| x.name == name) {
+				continue;
+			}
+			exprs.push(CommonTableExpr {
+				name,
+				what: u.arbitrary()?,
+			});
+		}
+		Ok(WithStatement {
+			exprs,
+			stmt: u.arbitrary()?,
 		})
 	}
 }
@@ -223,6 +265,7 @@ impl<'a> arbitrary::Arbitrary<'a> for View {
 		};
 
 		Ok(View {
+			materialize: true,
 			expr,
 			what: arb_vec1(u, |u| u.arbitrary())?,
 			cond: u.arbitrary()?,
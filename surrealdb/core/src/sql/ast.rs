@@ -5,7 +5,8 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use crate::expr;
 use crate::fmt::Fmt;
 use crate::sql::statements::{
-	AccessStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement, UseStatement,
+	AccessStatement, KillStatement, LiveSchemaStatement, LiveStatement, LockStatement,
+	OptionStatement, RefreshStatement, ShowStatement, UseStatement,
 };
 use crate::sql::{Expr, Literal, Param};
 
@@ -158,7 +159,10 @@ pub(crate) enum TopLevelExpr {
 	Access(Box<AccessStatement>),
 	Kill(KillStatement),
 	Live(Box<LiveStatement>),
+	LiveSchema(LiveSchemaStatement),
+	Lock(LockStatement),
 	Option(OptionStatement),
+	Refresh(RefreshStatement),
 	Use(UseStatement),
 	Show(ShowStatement),
 	Expr(Expr),
@@ -179,9 +183,18 @@ impl From<TopLevelExpr> for crate::expr::TopLevelExpr {
 			TopLevelExpr::Live(live_statement) => {
 				crate::expr::TopLevelExpr::Live(Box::new((*live_statement).into()))
 			}
+			TopLevelExpr::LiveSchema(live_statement) => {
+				crate::expr::TopLevelExpr::LiveSchema(live_statement.into())
+			}
+			TopLevelExpr::Lock(lock_statement) => {
+				crate::expr::TopLevelExpr::Lock(lock_statement.into())
+			}
 			TopLevelExpr::Option(option_statement) => {
 				crate::expr::TopLevelExpr::Option(option_statement.into())
 			}
+			TopLevelExpr::Refresh(refresh_statement) => {
+				crate::expr::TopLevelExpr::Refresh(refresh_statement.into())
+			}
 			TopLevelExpr::Use(use_statement) => {
 				crate::expr::TopLevelExpr::Use(use_statement.into())
 			}
@@ -208,9 +221,18 @@ impl From<crate::expr::TopLevelExpr> for TopLevelExpr {
 			crate::expr::TopLevelExpr::Live(live_statement) => {
 				TopLevelExpr::Live(Box::new((*live_statement).into()))
 			}
+			crate::expr::TopLevelExpr::LiveSchema(live_statement) => {
+				TopLevelExpr::LiveSchema(live_statement.into())
+			}
+			crate::expr::TopLevelExpr::Lock(lock_statement) => {
+				TopLevelExpr::Lock(lock_statement.into())
+			}
 			crate::expr::TopLevelExpr::Option(option_statement) => {
 				TopLevelExpr::Option(option_statement.into())
 			}
+			crate::expr::TopLevelExpr::Refresh(refresh_statement) => {
+				TopLevelExpr::Refresh(refresh_statement.into())
+			}
 			crate::expr::TopLevelExpr::Use(use_statement) => {
 				TopLevelExpr::Use(use_statement.into())
 			}
@@ -241,7 +263,10 @@ impl ToSql for TopLevelExpr {
 			TopLevelExpr::Access(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Kill(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Live(s) => s.fmt_sql(f, fmt),
+			TopLevelExpr::LiveSchema(s) => s.fmt_sql(f, fmt),
+			TopLevelExpr::Lock(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Option(s) => s.fmt_sql(f, fmt),
+			TopLevelExpr::Refresh(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Use(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Show(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Expr(e) => e.fmt_sql(f, fmt),
@@ -0,0 +1,38 @@
+use crate::types::PublicDuration;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Eviction {
+	pub lru: bool,
+	pub ttl: Option<PublicDuration>,
+}
+
+impl surrealdb_types::ToSql for Eviction {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: surrealdb_types::SqlFormat) {
+		use surrealdb_types::write_sql;
+		f.push_str("EVICT");
+		if self.lru {
+			f.push_str(" LRU");
+		}
+		if let Some(ref v) = self.ttl {
+			write_sql!(f, sql_fmt, " AFTER {}", v);
+		}
+	}
+}
+
+impl From<Eviction> for crate::expr::Eviction {
+	fn from(v: Eviction) -> Self {
+		crate::expr::Eviction {
+			lru: v.lru,
+			ttl: v.ttl.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::Eviction> for Eviction {
+	fn from(v: crate::expr::Eviction) -> Self {
+		Eviction {
+			lru: v.lru,
+			ttl: v.ttl.map(Into::into),
+		}
+	}
+}
@@ -11,7 +11,7 @@ use crate::sql::statements::{
 	AlterStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
 	IfelseStatement, InfoStatement, InsertStatement, OutputStatement, RebuildStatement,
 	RelateStatement, RemoveStatement, SelectStatement, SetStatement, SleepStatement,
-	UpdateStatement, UpsertStatement,
+	UpdateStatement, UpsertStatement, WithStatement,
 };
 use crate::sql::{
 	BinaryOperator, Block, Closure, Constant, Dir, FunctionCall, Idiom, Literal, Mock, Param, Part,
@@ -55,6 +55,7 @@ pub(crate) enum Expr {
 	Return(Box<OutputStatement>),
 	IfElse(Box<IfelseStatement>),
 	Select(Box<SelectStatement>),
+	With(Box<WithStatement>),
 	Create(Box<CreateStatement>),
 	Update(Box<UpdateStatement>),
 	Delete(Box<DeleteStatement>),
@@ -72,6 +73,7 @@ pub(crate) enum Expr {
 	Explain {
 		format: ExplainFormat,
 		analyze: bool,
+		permissions: bool,
 		statement: Box<Expr>,
 	},
 }
@@ -146,6 +148,7 @@ impl Expr {
 			| Expr::Return(_)
 			| Expr::IfElse(_)
 			| Expr::Select(_)
+			| Expr::With(_)
 			| Expr::Create(_)
 			| Expr::Update(_)
 			| Expr::Delete(_)
@@ -534,6 +537,7 @@ impl ToSql for Expr {
 			Expr::Throw(expr) => write_sql!(f, fmt, "THROW {}", CoverStmts(expr.as_ref())),
 			Expr::IfElse(s) => s.fmt_sql(f, fmt),
 			Expr::Select(s) => s.fmt_sql(f, fmt),
+			Expr::With(s) => s.fmt_sql(f, fmt),
 			Expr::Create(s) => s.fmt_sql(f, fmt),
 			Expr::Update(s) => s.fmt_sql(f, fmt),
 			Expr::Delete(s) => s.fmt_sql(f, fmt),
@@ -551,12 +555,16 @@ impl ToSql for Expr {
 			Expr::Explain {
 				format: explain_format,
 				analyze,
+				permissions,
 				statement,
 			} => {
 				f.push_str("EXPLAIN");
 				if *analyze {
 					f.push_str(" ANALYZE");
 				}
+				if *permissions {
+					f.push_str(" PERMISSIONS");
+				}
 				match explain_format {
 					ExplainFormat::Text => f.push_str(" FORMAT TEXT"),
 					ExplainFormat::Json => f.push_str(" FORMAT JSON"),
@@ -610,6 +618,7 @@ impl From<Expr> for crate::expr::Expr {
 			Expr::Throw(e) => crate::expr::Expr::Throw(Box::new((*e).into())),
 			Expr::IfElse(s) => crate::expr::Expr::IfElse(Box::new((*s).into())),
 			Expr::Select(s) => crate::expr::Expr::Select(Box::new((*s).into())),
+			Expr::With(s) => crate::expr::Expr::With(Box::new((*s).into())),
 			Expr::Create(s) => crate::expr::Expr::Create(Box::new((*s).into())),
 			Expr::Update(s) => crate::expr::Expr::Update(Box::new((*s).into())),
 			Expr::Delete(s) => crate::expr::Expr::Delete(Box::new((*s).into())),
@@ -627,10 +636,12 @@ impl From<Expr> for crate::expr::Expr {
 			Expr::Explain {
 				format,
 				analyze,
+				permissions,
 				statement,
 			} => crate::expr::Expr::Explain {
 				format: format.into(),
 				analyze,
+				permissions,
 				statement: Box::new((*statement).into()),
 			},
 		}
@@ -679,6 +690,7 @@ impl From<crate::expr::Expr> for Expr {
 			crate::expr::Expr::Throw(e) => Expr::Throw(Box::new((*e).into())),
 			crate::expr::Expr::IfElse(s) => Expr::IfElse(Box::new((*s).into())),
 			crate::expr::Expr::Select(s) => Expr::Select(Box::new((*s).into())),
+			crate::expr::Expr::With(s) => Expr::With(Box::new((*s).into())),
 			crate::expr::Expr::Create(s) => Expr::Create(Box::new((*s).into())),
 			crate::expr::Expr::Update(s) => Expr::Update(Box::new((*s).into())),
 			crate::expr::Expr::Delete(s) => Expr::Delete(Box::new((*s).into())),
@@ -696,10 +708,12 @@ impl From<crate::expr::Expr> for Expr {
 			crate::expr::Expr::Explain {
 				format,
 				analyze,
+				permissions,
 				statement,
 			} => Expr::Explain {
 				format: format.into(),
 				analyze,
+				permissions,
 				statement: Box::new((*statement).into()),
 			},
 			// `Expr::Match` is only constructed by the GQL lowering at top level and
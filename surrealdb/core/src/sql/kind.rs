@@ -160,6 +160,8 @@ pub enum Kind {
 	/// If the kind was specified without a bucket the vec will be empty.
 	/// So `<file>` is just `Kind::File(Vec::new())`
 	File(Vec<String>),
+	/// A decimal type with a precision and scale, like `decimal(20, 4)`.
+	FixedDecimal(u32, u32),
 }
 
 impl Kind {
@@ -220,6 +222,7 @@ impl From<Kind> for crate::expr::Kind {
 			Kind::Range => crate::expr::Kind::Range,
 			Kind::Literal(l) => crate::expr::Kind::Literal(l.into()),
 			Kind::File(k) => crate::expr::Kind::File(k),
+			Kind::FixedDecimal(p, s) => crate::expr::Kind::FixedDecimal(p, s),
 		}
 	}
 }
@@ -263,6 +266,7 @@ impl From<crate::expr::Kind> for Kind {
 			crate::expr::Kind::Range => Self::Range,
 			crate::expr::Kind::Literal(l) => Self::Literal(l.into()),
 			crate::expr::Kind::File(k) => Kind::File(k),
+			crate::expr::Kind::FixedDecimal(p, s) => Kind::FixedDecimal(p, s),
 		}
 	}
 }
@@ -276,7 +280,8 @@ impl From<Kind> for crate::types::PublicKind {
 			Kind::Bool => crate::types::PublicKind::Bool,
 			Kind::Bytes => crate::types::PublicKind::Bytes,
 			Kind::Datetime => crate::types::PublicKind::Datetime,
-			Kind::Decimal => crate::types::PublicKind::Decimal,
+			// The precision and scale are not part of the public kind
+			Kind::Decimal | Kind::FixedDecimal(_, _) => crate::types::PublicKind::Decimal,
 			Kind::Duration => crate::types::PublicKind::Duration,
 			Kind::Float => crate::types::PublicKind::Float,
 			Kind::Int => crate::types::PublicKind::Int,
@@ -363,6 +368,7 @@ impl ToSql for Kind {
 			Kind::Bytes => f.push_str("bytes"),
 			Kind::Datetime => f.push_str("datetime"),
 			Kind::Decimal => f.push_str("decimal"),
+			Kind::FixedDecimal(p, s) => write_sql!(f, fmt, "decimal({p}, {s})"),
 			Kind::Duration => f.push_str("duration"),
 			Kind::Float => f.push_str("float"),
 			Kind::Int => f.push_str("int"),
@@ -668,6 +674,7 @@ mod tests {
 	#[case::bytes(Kind::Bytes, "bytes")]
 	#[case::datetime(Kind::Datetime, "datetime")]
 	#[case::decimal(Kind::Decimal, "decimal")]
+	#[case::fixed_decimal(Kind::FixedDecimal(20, 4), "decimal(20, 4)")]
 	#[case::duration(Kind::Duration, "duration")]
 	#[case::float(Kind::Float, "float")]
 	#[case::int(Kind::Int, "int")]
@@ -721,6 +728,7 @@ mod tests {
 	#[case::array(Kind::Array(Box::new(Kind::String), None))]
 	#[case::either(Kind::Either(vec![Kind::String, Kind::Int]))]
 	#[case::file(Kind::File(vec!["bucket".to_string()]))]
+	#[case::fixed_decimal(Kind::FixedDecimal(20, 4))]
 	fn test_kind_conversions_expr(#[case] sql_kind: Kind) {
 		let expr_kind: crate::expr::Kind = sql_kind.clone().into();
 		let back_to_sql: Kind = expr_kind.into();
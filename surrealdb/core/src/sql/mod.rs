@@ -13,6 +13,7 @@ pub(crate) mod cond;
 pub(crate) mod constant;
 pub(crate) mod data;
 pub(crate) mod dir;
+pub(crate) mod eviction;
 pub(crate) mod explain;
 pub(crate) mod expression;
 pub(crate) mod fetch;
@@ -40,13 +41,16 @@ pub(crate) mod record_id;
 pub(crate) mod reference;
 pub(crate) mod scoring;
 pub(crate) mod script;
+pub(crate) mod soft_delete;
 pub(crate) mod split;
 pub(crate) mod start;
 pub(crate) mod table_type;
 #[cfg(test)]
 mod test_to_sql;
+pub(crate) mod throttle;
 pub(crate) mod tokenizer;
 pub(crate) mod user;
+pub(crate) mod valid_time;
 pub(crate) mod view;
 pub(crate) mod with;
 
@@ -71,6 +75,7 @@ pub(crate) use self::cond::Cond;
 pub(crate) use self::constant::Constant;
 pub(crate) use self::data::Data;
 pub(crate) use self::dir::Dir;
+pub(crate) use self::eviction::Eviction;
 pub(crate) use self::explain::Explain;
 pub(crate) use self::expression::Expr;
 pub(crate) use self::fetch::{Fetch, Fetchs};
@@ -98,6 +103,7 @@ pub(crate) use self::record_id::{
 };
 pub(crate) use self::scoring::Scoring;
 pub(crate) use self::script::Script;
+pub(crate) use self::soft_delete::SoftDelete;
 pub(crate) use self::split::{Split, Splits};
 pub(crate) use self::start::Start;
 pub(crate) use self::statements::{
@@ -107,5 +113,7 @@ pub(crate) use self::statements::{
 	UpdateStatement, UpsertStatement,
 };
 pub(crate) use self::table_type::TableType;
+pub(crate) use self::throttle::Throttle;
+pub(crate) use self::valid_time::ValidTime;
 pub(crate) use self::view::View;
 pub(crate) use self::with::With;
@@ -267,6 +267,10 @@ pub enum RecurseInstruction {
 		// Do we include the starting point in the collection?
 		inclusive: bool,
 	},
+	Tree {
+		// Do we root the tree at the starting point?
+		inclusive: bool,
+	},
 }
 
 impl ToSql for RecurseInstruction {
@@ -296,6 +300,15 @@ impl ToSql for RecurseInstruction {
 			} => {
 				write_sql!(f, fmt, "shortest={expects}");
 
+				if *inclusive {
+					f.push_str("+inclusive");
+				}
+			}
+			Self::Tree {
+				inclusive,
+			} => {
+				f.push_str("tree");
+
 				if *inclusive {
 					f.push_str("+inclusive");
 				}
@@ -324,6 +337,11 @@ impl From<RecurseInstruction> for crate::expr::part::RecurseInstruction {
 				expects: expects.into(),
 				inclusive,
 			},
+			RecurseInstruction::Tree {
+				inclusive,
+			} => Self::Tree {
+				inclusive,
+			},
 		}
 	}
 }
@@ -348,6 +366,11 @@ impl From<crate::expr::part::RecurseInstruction> for RecurseInstruction {
 				expects: expects.into(),
 				inclusive,
 			},
+			crate::expr::part::RecurseInstruction::Tree {
+				inclusive,
+			} => Self::Tree {
+				inclusive,
+			},
 		}
 	}
 }
@@ -0,0 +1,37 @@
+use crate::fmt::EscapeKwFreeIdent;
+use crate::types::PublicDuration;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SoftDelete {
+	pub field: String,
+	pub retention: Option<PublicDuration>,
+}
+
+impl surrealdb_types::ToSql for SoftDelete {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: surrealdb_types::SqlFormat) {
+		use surrealdb_types::write_sql;
+		write_sql!(f, sql_fmt, "SOFT DELETE FIELD {}", EscapeKwFreeIdent(&self.field));
+		if let Some(ref v) = self.retention {
+			write_sql!(f, sql_fmt, " PURGE AFTER {}", v);
+		}
+	}
+}
+
+impl From<SoftDelete> for crate::expr::SoftDelete {
+	fn from(v: SoftDelete) -> Self {
+		crate::expr::SoftDelete {
+			field: v.field,
+			retention: v.retention.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::SoftDelete> for SoftDelete {
+	fn from(v: crate::expr::SoftDelete) -> Self {
+		SoftDelete {
+			field: v.field,
+			retention: v.retention.map(Into::into),
+		}
+	}
+}
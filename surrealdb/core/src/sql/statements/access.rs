@@ -8,17 +8,21 @@ use crate::types::PublicDuration;
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum AccessStatement {
-	Grant(AccessStatementGrant),   // Create access grant.
-	Show(AccessStatementShow),     // Show access grants.
-	Revoke(AccessStatementRevoke), // Revoke access grant.
-	Purge(AccessStatementPurge),   // Purge access grants.
+	Grant(AccessStatementGrant),             // Create access grant.
+	GrantMany(AccessStatementGrantMany),     // Create access grants for many subjects.
+	Show(AccessStatementShow),               // Show access grants.
+	ShowSubject(AccessStatementShowSubject), // Show access grants of a subject.
+	Revoke(AccessStatementRevoke),           // Revoke access grant.
+	Purge(AccessStatementPurge),             // Purge access grants.
 }
 
 impl From<AccessStatement> for crate::expr::statements::access::AccessStatement {
 	fn from(v: AccessStatement) -> Self {
 		match v {
 			AccessStatement::Grant(v) => Self::Grant(v.into()),
+			AccessStatement::GrantMany(v) => Self::GrantMany(v.into()),
 			AccessStatement::Show(v) => Self::Show(v.into()),
+			AccessStatement::ShowSubject(v) => Self::ShowSubject(v.into()),
 			AccessStatement::Revoke(v) => Self::Revoke(v.into()),
 			AccessStatement::Purge(v) => Self::Purge(v.into()),
 		}
@@ -29,7 +33,13 @@ impl From<crate::expr::statements::access::AccessStatement> for AccessStatement
 	fn from(v: crate::expr::statements::access::AccessStatement) -> Self {
 		match v {
 			crate::expr::statements::access::AccessStatement::Grant(v) => Self::Grant(v.into()),
+			crate::expr::statements::access::AccessStatement::GrantMany(v) => {
+				Self::GrantMany(v.into())
+			}
 			crate::expr::statements::access::AccessStatement::Show(v) => Self::Show(v.into()),
+			crate::expr::statements::access::AccessStatement::ShowSubject(v) => {
+				Self::ShowSubject(v.into())
+			}
 			crate::expr::statements::access::AccessStatement::Revoke(v) => Self::Revoke(v.into()),
 			crate::expr::statements::access::AccessStatement::Purge(v) => Self::Purge(v.into()),
 		}
@@ -42,6 +52,8 @@ pub struct AccessStatementGrant {
 	pub ac: Strand,
 	pub base: Option<Base>,
 	pub subject: Subject,
+	/// Whether the grant enrols the subject in TOTP, instead of issuing a key
+	pub totp: bool,
 }
 
 impl From<AccessStatementGrant> for crate::expr::statements::access::AccessStatementGrant {
@@ -50,6 +62,7 @@ impl From<AccessStatementGrant> for crate::expr::statements::access::AccessState
 			ac: v.ac,
 			base: v.base.map(Into::into),
 			subject: v.subject.into(),
+			totp: v.totp,
 		}
 	}
 }
@@ -60,6 +73,36 @@ impl From<crate::expr::statements::access::AccessStatementGrant> for AccessState
 			ac: v.ac,
 			base: v.base.map(Into::into),
 			subject: v.subject.into(),
+			totp: v.totp,
+		}
+	}
+}
+
+/// Creates an access grant for each of a list of subjects, which are either
+/// all users or all records.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessStatementGrantMany {
+	pub ac: Strand,
+	pub base: Option<Base>,
+	pub subjects: Vec<Subject>,
+}
+
+impl From<AccessStatementGrantMany> for crate::expr::statements::access::AccessStatementGrantMany {
+	fn from(v: AccessStatementGrantMany) -> Self {
+		Self {
+			ac: v.ac,
+			base: v.base.map(Into::into),
+			subjects: v.subjects.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::access::AccessStatementGrantMany> for AccessStatementGrantMany {
+	fn from(v: crate::expr::statements::access::AccessStatementGrantMany) -> Self {
+		Self {
+			ac: v.ac,
+			base: v.base.map(Into::into),
+			subjects: v.subjects.into_iter().map(Into::into).collect(),
 		}
 	}
 }
@@ -95,6 +138,32 @@ impl From<crate::expr::statements::access::AccessStatementShow> for AccessStatem
 	}
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AccessStatementShowSubject {
+	pub subject: Subject,
+}
+
+impl From<AccessStatementShowSubject>
+	for crate::expr::statements::access::AccessStatementShowSubject
+{
+	fn from(v: AccessStatementShowSubject) -> Self {
+		Self {
+			subject: v.subject.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::access::AccessStatementShowSubject>
+	for AccessStatementShowSubject
+{
+	fn from(v: crate::expr::statements::access::AccessStatementShowSubject) -> Self {
+		Self {
+			subject: v.subject.into(),
+		}
+	}
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AccessStatementRevoke {
@@ -219,11 +288,36 @@ impl ToSql for AccessStatement {
 					write_sql!(f, fmt, " ON {v}");
 				}
 				write_sql!(f, fmt, " GRANT");
+				if stmt.totp {
+					f.push_str(" TOTP");
+				}
 				match &stmt.subject {
 					Subject::User(x) => write_sql!(f, fmt, " FOR USER {}", EscapeIdent(x.as_str())),
 					Subject::Record(x) => write_sql!(f, fmt, " FOR RECORD {}", x),
 				}
 			}
+			Self::GrantMany(stmt) => {
+				write_sql!(f, fmt, "ACCESS {}", EscapeKwFreeIdent(stmt.ac.as_str()));
+				if let Some(ref v) = stmt.base {
+					write_sql!(f, fmt, " ON {v}");
+				}
+				f.push_str(" GRANT FOR");
+				// The subjects of a list are all users or all records
+				match stmt.subjects.first() {
+					Some(Subject::Record(_)) => f.push_str(" RECORD IN ["),
+					_ => f.push_str(" USER IN ["),
+				}
+				for (i, subject) in stmt.subjects.iter().enumerate() {
+					if i > 0 {
+						f.push_str(", ");
+					}
+					match subject {
+						Subject::User(x) => write_sql!(f, fmt, "{}", EscapeIdent(x.as_str())),
+						Subject::Record(x) => write_sql!(f, fmt, "{}", x),
+					}
+				}
+				f.push(']');
+			}
 			Self::Show(stmt) => {
 				write_sql!(f, fmt, "ACCESS {}", EscapeKwFreeIdent(stmt.ac.as_str()));
 				if let Some(ref v) = stmt.base {
@@ -238,6 +332,13 @@ impl ToSql for AccessStatement {
 					},
 				};
 			}
+			Self::ShowSubject(stmt) => {
+				f.push_str("ACCESS SHOW ALL");
+				match &stmt.subject {
+					Subject::User(x) => write_sql!(f, fmt, " FOR USER {}", EscapeIdent(x.as_str())),
+					Subject::Record(x) => write_sql!(f, fmt, " FOR RECORD {}", x),
+				}
+			}
 			Self::Revoke(stmt) => {
 				write_sql!(f, fmt, "ACCESS {}", EscapeKwFreeIdent(stmt.ac.as_str()));
 				if let Some(ref v) = stmt.base {
@@ -49,6 +49,11 @@ impl ToSql for AlterEventStatement {
 				} => {
 					write_sql!(f, fmt, " ASYNC RETRY {} MAXDEPTH {}", retry, max_depth);
 				}
+				EventKind::Outbox {
+					retry,
+				} => {
+					write_sql!(f, fmt, " OUTBOX RETRY {}", retry);
+				}
 			},
 			AlterKind::Drop => f.push_str(" DROP ASYNC"),
 			AlterKind::None => {}
@@ -1,6 +1,7 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
+use crate::catalog::DatabaseCapabilities;
 use crate::fmt::CoverStmts;
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::{Expr, Literal};
@@ -14,6 +15,8 @@ pub(crate) struct DefineDatabaseStatement {
 	pub strict: bool,
 	pub comment: Expr,
 	pub changefeed: Option<ChangeFeed>,
+	/// Optional capabilities declared via `CAPABILITIES { ... }`.
+	pub capabilities: Option<DatabaseCapabilities>,
 }
 
 impl Default for DefineDatabaseStatement {
@@ -25,6 +28,7 @@ impl Default for DefineDatabaseStatement {
 			comment: Expr::Literal(Literal::None),
 			changefeed: None,
 			strict: false,
+			capabilities: None,
 		}
 	}
 }
@@ -47,6 +51,9 @@ impl ToSql for DefineDatabaseStatement {
 		if let Some(ref v) = self.changefeed {
 			write_sql!(f, sql_fmt, " {v}");
 		}
+		if let Some(ref caps) = self.capabilities {
+			write_sql!(f, sql_fmt, " CAPABILITIES {caps}");
+		}
 	}
 }
 
@@ -59,6 +66,7 @@ impl From<DefineDatabaseStatement> for crate::expr::statements::DefineDatabaseSt
 			comment: v.comment.into(),
 			changefeed: v.changefeed.map(Into::into),
 			strict: v.strict,
+			capabilities: v.capabilities,
 		}
 	}
 }
@@ -73,6 +81,7 @@ impl From<crate::expr::statements::DefineDatabaseStatement> for DefineDatabaseSt
 			strict: v.strict,
 			comment: v.comment.into(),
 			changefeed: v.changefeed.map(Into::into),
+			capabilities: v.capabilities,
 		}
 	}
 }
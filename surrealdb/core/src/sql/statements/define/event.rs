@@ -27,12 +27,15 @@ impl ToSql for DefineEventStatement {
 			DefineKind::IfNotExists => f.push_str(" IF NOT EXISTS"),
 		}
 		write_sql!(f, fmt, " {} ON {}", CoverStmts(&self.name), CoverStmts(&self.target_table),);
-		if let EventKind::Async {
-			retry,
-			max_depth,
-		} = self.event_kind
-		{
-			write_sql!(f, fmt, " ASYNC RETRY {} MAXDEPTH {}", retry, max_depth);
+		match self.event_kind {
+			EventKind::Sync => {}
+			EventKind::Async {
+				retry,
+				max_depth,
+			} => write_sql!(f, fmt, " ASYNC RETRY {} MAXDEPTH {}", retry, max_depth),
+			EventKind::Outbox {
+				retry,
+			} => write_sql!(f, fmt, " OUTBOX RETRY {}", retry),
 		}
 		write_sql!(f, fmt, " WHEN {}", CoverStmts(&self.when),);
 		if !self.then.is_empty() {
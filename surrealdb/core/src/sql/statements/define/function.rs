@@ -2,6 +2,7 @@ use surrealdb_strand::Strand;
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
+use crate::catalog::FunctionCapabilities;
 use crate::fmt::{CoverStmts, EscapeKwFreeIdent};
 use crate::sql::{Block, Expr, Kind, Literal, Permission};
 
@@ -20,6 +21,8 @@ pub(crate) struct DefineFunctionStatement {
 	/// Optional GraphQL deprecation reason declared via
 	/// `GRAPHQL_DEPRECATED "..."`.
 	pub graphql_deprecated: Option<String>,
+	/// Optional capabilities declared via `CAPABILITIES { ... }`.
+	pub capabilities: Option<FunctionCapabilities>,
 }
 
 impl ToSql for DefineFunctionStatement {
@@ -56,6 +59,9 @@ impl ToSql for DefineFunctionStatement {
 		if let Some(ref reason) = self.graphql_deprecated {
 			write_sql!(f, fmt, " GRAPHQL_DEPRECATED {}", crate::fmt::QuoteStr(reason));
 		}
+		if let Some(ref caps) = self.capabilities {
+			write_sql!(f, fmt, " CAPABILITIES {caps}");
+		}
 		let fmt = fmt.increment();
 		write_sql!(f, fmt, " PERMISSIONS {}", self.permissions);
 	}
@@ -73,6 +79,7 @@ impl From<DefineFunctionStatement> for crate::expr::statements::DefineFunctionSt
 			returns: v.returns.map(Into::into),
 			graphql_alias: v.graphql_alias,
 			graphql_deprecated: v.graphql_deprecated,
+			capabilities: v.capabilities,
 		}
 	}
 }
@@ -89,6 +96,7 @@ impl From<crate::expr::statements::DefineFunctionStatement> for DefineFunctionSt
 			returns: v.returns.map(Into::into),
 			graphql_alias: v.graphql_alias,
 			graphql_deprecated: v.graphql_deprecated,
+			capabilities: v.capabilities,
 		}
 	}
 }
@@ -13,6 +13,25 @@ pub(crate) struct DefineIndexStatement {
 	pub index: Index,
 	pub comment: Expr,
 	pub concurrently: bool,
+	pub conflicts: UniqueConflicts,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) enum UniqueConflicts {
+	#[default]
+	Fail,
+	Validate,
+	Quarantine(Expr),
+}
+
+impl ToSql for UniqueConflicts {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		match self {
+			Self::Fail => {}
+			Self::Validate => write_sql!(f, sql_fmt, " VALIDATE"),
+			Self::Quarantine(tb) => write_sql!(f, sql_fmt, " QUARANTINE {}", CoverStmts(tb)),
+		}
+	}
 }
 
 impl ToSql for DefineIndexStatement {
@@ -35,6 +54,7 @@ impl ToSql for DefineIndexStatement {
 		if Index::Idx != self.index {
 			write_sql!(f, sql_fmt, " {}", self.index);
 		}
+		self.conflicts.fmt_sql(f, sql_fmt);
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
 			write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
 		}
@@ -54,6 +74,7 @@ impl From<DefineIndexStatement> for crate::expr::statements::DefineIndexStatemen
 			index: v.index.into(),
 			comment: v.comment.into(),
 			concurrently: v.concurrently,
+			conflicts: v.conflicts.into(),
 		}
 	}
 }
@@ -68,6 +89,29 @@ impl From<crate::expr::statements::DefineIndexStatement> for DefineIndexStatemen
 			index: v.index.into(),
 			comment: v.comment.into(),
 			concurrently: v.concurrently,
+			conflicts: v.conflicts.into(),
+		}
+	}
+}
+
+impl From<UniqueConflicts> for crate::expr::statements::define::UniqueConflicts {
+	fn from(v: UniqueConflicts) -> Self {
+		match v {
+			UniqueConflicts::Fail => Self::Fail,
+			UniqueConflicts::Validate => Self::Validate,
+			UniqueConflicts::Quarantine(tb) => Self::Quarantine(tb.into()),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::UniqueConflicts> for UniqueConflicts {
+	fn from(v: crate::expr::statements::define::UniqueConflicts) -> Self {
+		match v {
+			crate::expr::statements::define::UniqueConflicts::Fail => Self::Fail,
+			crate::expr::statements::define::UniqueConflicts::Validate => Self::Validate,
+			crate::expr::statements::define::UniqueConflicts::Quarantine(tb) => {
+				Self::Quarantine(tb.into())
+			}
 		}
 	}
 }
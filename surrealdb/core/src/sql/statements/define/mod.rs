@@ -12,6 +12,8 @@ mod model;
 mod module;
 mod namespace;
 mod param;
+mod policy;
+mod query;
 mod sequence;
 mod table;
 pub mod user;
@@ -25,11 +27,13 @@ pub(crate) use database::DefineDatabaseStatement;
 pub(crate) use event::DefineEventStatement;
 pub(crate) use field::{DefineDefault, DefineFieldStatement};
 pub(crate) use function::DefineFunctionStatement;
-pub(crate) use index::DefineIndexStatement;
+pub(crate) use index::{DefineIndexStatement, UniqueConflicts};
 pub(crate) use model::DefineModelStatement;
 pub(crate) use module::DefineModuleStatement;
 pub(crate) use namespace::DefineNamespaceStatement;
 pub(crate) use param::DefineParamStatement;
+pub(crate) use policy::DefinePolicyStatement;
+pub(crate) use query::DefineQueryStatement;
 pub(crate) use sequence::DefineSequenceStatement;
 use surrealdb_types::{SqlFormat, ToSql};
 pub(crate) use table::DefineTableStatement;
@@ -72,8 +76,10 @@ pub(crate) enum DefineStatement {
 	Function(DefineFunctionStatement),
 	Analyzer(DefineAnalyzerStatement),
 	Param(DefineParamStatement),
+	Query(DefineQueryStatement),
 	Table(DefineTableStatement),
 	Event(DefineEventStatement),
+	Policy(DefinePolicyStatement),
 	Field(Box<DefineFieldStatement>),
 	Index(DefineIndexStatement),
 	User(DefineUserStatement),
@@ -86,6 +92,8 @@ pub(crate) enum DefineStatement {
 	Sequence(DefineSequenceStatement),
 	#[cfg_attr(feature = "arbitrary", arbitrary(skip))]
 	Module(DefineModuleStatement),
+	#[cfg_attr(feature = "arbitrary", arbitrary(skip))]
+	Diff(Box<DefineStatement>),
 }
 
 impl ToSql for DefineStatement {
@@ -96,8 +104,10 @@ impl ToSql for DefineStatement {
 			Self::Function(v) => v.fmt_sql(f, fmt),
 			Self::User(v) => v.fmt_sql(f, fmt),
 			Self::Param(v) => v.fmt_sql(f, fmt),
+			Self::Query(v) => v.fmt_sql(f, fmt),
 			Self::Table(v) => v.fmt_sql(f, fmt),
 			Self::Event(v) => v.fmt_sql(f, fmt),
+			Self::Policy(v) => v.fmt_sql(f, fmt),
 			Self::Field(v) => v.fmt_sql(f, fmt),
 			Self::Index(v) => v.fmt_sql(f, fmt),
 			Self::Analyzer(v) => v.fmt_sql(f, fmt),
@@ -108,6 +118,10 @@ impl ToSql for DefineStatement {
 			Self::Bucket(v) => v.fmt_sql(f, fmt),
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
 			Self::Module(v) => v.fmt_sql(f, fmt),
+			Self::Diff(v) => {
+				v.fmt_sql(f, fmt);
+				f.push_str(" DIFF");
+			}
 		}
 	}
 }
@@ -120,8 +134,10 @@ impl From<DefineStatement> for crate::expr::statements::DefineStatement {
 			DefineStatement::Function(v) => Self::Function(v.into()),
 			DefineStatement::Analyzer(v) => Self::Analyzer(v.into()),
 			DefineStatement::Param(v) => Self::Param(v.into()),
+			DefineStatement::Query(v) => Self::Query(v.into()),
 			DefineStatement::Table(v) => Self::Table(v.into()),
 			DefineStatement::Event(v) => Self::Event(v.into()),
+			DefineStatement::Policy(v) => Self::Policy(v.into()),
 			DefineStatement::Field(v) => Self::Field((*v).into()),
 			DefineStatement::Index(v) => Self::Index(v.into()),
 			DefineStatement::User(v) => Self::User(v.into()),
@@ -132,6 +148,7 @@ impl From<DefineStatement> for crate::expr::statements::DefineStatement {
 			DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			DefineStatement::Sequence(v) => Self::Sequence(v.into()),
 			DefineStatement::Module(v) => Self::Module(v.into()),
+			DefineStatement::Diff(v) => Self::Diff(Box::new((*v).into())),
 		}
 	}
 }
@@ -144,8 +161,10 @@ impl From<crate::expr::statements::DefineStatement> for DefineStatement {
 			crate::expr::statements::DefineStatement::Function(v) => Self::Function(v.into()),
 			crate::expr::statements::DefineStatement::Analyzer(v) => Self::Analyzer(v.into()),
 			crate::expr::statements::DefineStatement::Param(v) => Self::Param(v.into()),
+			crate::expr::statements::DefineStatement::Query(v) => Self::Query(v.into()),
 			crate::expr::statements::DefineStatement::Table(v) => Self::Table(v.into()),
 			crate::expr::statements::DefineStatement::Event(v) => Self::Event(v.into()),
+			crate::expr::statements::DefineStatement::Policy(v) => Self::Policy(v.into()),
 			crate::expr::statements::DefineStatement::Field(v) => Self::Field(Box::new(v.into())),
 			crate::expr::statements::DefineStatement::Index(v) => Self::Index(v.into()),
 			crate::expr::statements::DefineStatement::User(v) => Self::User(v.into()),
@@ -156,6 +175,7 @@ impl From<crate::expr::statements::DefineStatement> for DefineStatement {
 			crate::expr::statements::DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			crate::expr::statements::DefineStatement::Sequence(v) => Self::Sequence(v.into()),
 			crate::expr::statements::DefineStatement::Module(v) => Self::Module(v.into()),
+			crate::expr::statements::DefineStatement::Diff(v) => Self::Diff(Box::new((*v).into())),
 		}
 	}
 }
@@ -0,0 +1,71 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefinePolicyStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub when: Expr,
+	pub destination: Expr,
+	pub batch: Expr,
+	pub comment: Expr,
+}
+
+impl ToSql for DefinePolicyStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push_str("DEFINE POLICY");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => f.push_str(" OVERWRITE"),
+			DefineKind::IfNotExists => f.push_str(" IF NOT EXISTS"),
+		}
+		write_sql!(
+			f,
+			fmt,
+			" {} ON {} WHEN {} MOVE TO {}",
+			CoverStmts(&self.name),
+			CoverStmts(&self.target_table),
+			CoverStmts(&self.when),
+			CoverStmts(&self.destination),
+		);
+		if !matches!(self.batch, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " BATCH {}", CoverStmts(&self.batch));
+		}
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefinePolicyStatement> for crate::expr::statements::define::DefinePolicyStatement {
+	fn from(v: DefinePolicyStatement) -> Self {
+		crate::expr::statements::define::DefinePolicyStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			when: v.when.into(),
+			destination: v.destination.into(),
+			batch: v.batch.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefinePolicyStatement> for DefinePolicyStatement {
+	fn from(v: crate::expr::statements::define::DefinePolicyStatement) -> Self {
+		DefinePolicyStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			when: v.when.into(),
+			destination: v.destination.into(),
+			batch: v.batch.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
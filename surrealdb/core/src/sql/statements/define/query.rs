@@ -0,0 +1,67 @@
+use surrealdb_strand::Strand;
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::{CoverStmts, EscapeKwFreeIdent};
+use crate::sql::{Expr, Kind, Literal, Permission};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineQueryStatement {
+	pub kind: DefineKind,
+	pub name: Strand,
+	pub args: Vec<(String, Kind)>,
+	pub query: Expr,
+	pub comment: Expr,
+	pub permissions: Permission,
+}
+
+impl ToSql for DefineQueryStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "DEFINE QUERY");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => write_sql!(f, fmt, " OVERWRITE"),
+			DefineKind::IfNotExists => write_sql!(f, fmt, " IF NOT EXISTS"),
+		}
+		write_sql!(f, fmt, " {}(", EscapeKwFreeIdent(self.name.as_str()));
+		for (i, (name, kind)) in self.args.iter().enumerate() {
+			if i > 0 {
+				f.push_str(", ");
+			}
+			write_sql!(f, fmt, "${}: {kind}", EscapeKwFreeIdent(name));
+		}
+		write_sql!(f, fmt, ") AS {}", CoverStmts(&self.query));
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+		let fmt = fmt.increment();
+		write_sql!(f, fmt, " PERMISSIONS {}", self.permissions);
+	}
+}
+
+impl From<DefineQueryStatement> for crate::expr::statements::define::DefineQueryStatement {
+	fn from(v: DefineQueryStatement) -> Self {
+		Self {
+			kind: v.kind.into(),
+			name: v.name,
+			args: v.args.into_iter().map(|(i, k)| (i, k.into())).collect(),
+			query: v.query.into(),
+			comment: v.comment.into(),
+			permissions: v.permissions.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineQueryStatement> for DefineQueryStatement {
+	fn from(v: crate::expr::statements::define::DefineQueryStatement) -> Self {
+		Self {
+			kind: v.kind.into(),
+			name: v.name,
+			args: v.args.into_iter().map(|(i, k)| (i, k.into())).collect(),
+			query: v.query.into(),
+			comment: v.comment.into(),
+			permissions: v.permissions.into(),
+		}
+	}
+}
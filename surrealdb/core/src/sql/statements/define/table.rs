@@ -3,7 +3,9 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use super::DefineKind;
 use crate::fmt::{CoverStmts, EscapeKwFreeIdent};
 use crate::sql::changefeed::ChangeFeed;
-use crate::sql::{Expr, Literal, Permissions, TableType, View};
+use crate::sql::{
+	Eviction, Expr, Literal, Permissions, SoftDelete, TableType, Throttle, ValidTime, View,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -16,6 +18,12 @@ pub(crate) struct DefineTableStatement {
 	pub view: Option<View>,
 	pub permissions: Permissions,
 	pub changefeed: Option<ChangeFeed>,
+	pub throttle: Option<Throttle>,
+	pub lineage: bool,
+	pub soft_delete: Option<SoftDelete>,
+	pub eviction: Option<Eviction>,
+	pub session: bool,
+	pub valid_time: Option<ValidTime>,
 	pub comment: Expr,
 	pub table_type: TableType,
 	/// Optional GraphQL alias declared via `GRAPHQL_ALIAS "..."`.
@@ -36,6 +44,12 @@ impl Default for DefineTableStatement {
 			view: None,
 			permissions: Permissions::none(),
 			changefeed: None,
+			throttle: None,
+			lineage: false,
+			soft_delete: None,
+			eviction: None,
+			session: false,
+			valid_time: None,
 			comment: Expr::Literal(Literal::None),
 			table_type: TableType::default(),
 			graphql_alias: None,
@@ -46,6 +60,19 @@ impl Default for DefineTableStatement {
 
 impl ToSql for DefineTableStatement {
 	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		if let Some(v) = self.view.as_ref().filter(|v| !v.materialize) {
+			f.push_str("DEFINE VIEW");
+			match self.kind {
+				DefineKind::Default => {}
+				DefineKind::Overwrite => f.push_str(" OVERWRITE"),
+				DefineKind::IfNotExists => f.push_str(" IF NOT EXISTS"),
+			}
+			write_sql!(f, sql_fmt, " {} {}", CoverStmts(&self.name), v);
+			if !matches!(self.comment, Expr::Literal(Literal::None)) {
+				write_sql!(f, sql_fmt, " COMMENT {}", CoverStmts(&self.comment));
+			}
+			return;
+		}
 		f.push_str("DEFINE TABLE");
 		match self.kind {
 			DefineKind::Default => {}
@@ -105,6 +132,24 @@ impl ToSql for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write_sql!(f, sql_fmt, " {}", v);
 		}
+		if let Some(ref v) = self.throttle {
+			write_sql!(f, sql_fmt, " {}", v);
+		}
+		if self.lineage {
+			f.push_str(" LINEAGE");
+		}
+		if let Some(ref v) = self.soft_delete {
+			write_sql!(f, sql_fmt, " {}", v);
+		}
+		if let Some(ref v) = self.eviction {
+			write_sql!(f, sql_fmt, " {}", v);
+		}
+		if self.session {
+			f.push_str(" SESSION");
+		}
+		if let Some(ref v) = self.valid_time {
+			write_sql!(f, sql_fmt, " {}", v);
+		}
 		if sql_fmt.is_pretty() {
 			f.push('\n');
 			let inner_fmt = sql_fmt.increment();
@@ -127,6 +172,12 @@ impl From<DefineTableStatement> for crate::expr::statements::DefineTableStatemen
 			view: v.view.map(Into::into),
 			permissions: v.permissions.into(),
 			changefeed: v.changefeed.map(Into::into),
+			throttle: v.throttle.map(Into::into),
+			lineage: v.lineage,
+			soft_delete: v.soft_delete.map(Into::into),
+			eviction: v.eviction.map(Into::into),
+			session: v.session,
+			valid_time: v.valid_time.map(Into::into),
 			comment: v.comment.into(),
 			table_type: v.table_type.into(),
 			graphql_alias: v.graphql_alias,
@@ -147,6 +198,12 @@ impl From<crate::expr::statements::DefineTableStatement> for DefineTableStatemen
 			view: v.view.map(Into::into),
 			permissions: v.permissions.into(),
 			changefeed: v.changefeed.map(Into::into),
+			throttle: v.throttle.map(Into::into),
+			lineage: v.lineage,
+			soft_delete: v.soft_delete.map(Into::into),
+			eviction: v.eviction.map(Into::into),
+			session: v.session,
+			valid_time: v.valid_time.map(Into::into),
 			comment: v.comment.into(),
 			table_type: v.table_type.into(),
 			graphql_alias: v.graphql_alias,
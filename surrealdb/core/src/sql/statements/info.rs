@@ -14,6 +14,8 @@ pub enum InfoStatement {
 	Tb(Expr, bool, Option<Expr>),
 	User(Expr, Option<Base>, bool),
 	Index(Expr, Expr, bool),
+	IndexStats(Expr),
+	Locks,
 }
 
 impl ToSql for InfoStatement {
@@ -95,6 +97,10 @@ impl ToSql for InfoStatement {
 					CoverStmts(t)
 				)
 			}
+			Self::IndexStats(t) => {
+				write_sql!(f, sql_fmt, "INFO FOR TABLE {} INDEX STATS", CoverStmts(t))
+			}
+			Self::Locks => f.push_str("INFO FOR LOCKS"),
 		}
 	}
 }
@@ -108,6 +114,8 @@ impl From<InfoStatement> for crate::expr::statements::InfoStatement {
 			InfoStatement::Tb(t, v, ver) => Self::Tb(t.into(), v, ver.map(From::from)),
 			InfoStatement::User(u, b, v) => Self::User(u.into(), b.map(Into::into), v),
 			InfoStatement::Index(i, t, v) => Self::Index(i.into(), t.into(), v),
+			InfoStatement::IndexStats(t) => Self::IndexStats(t.into()),
+			InfoStatement::Locks => Self::Locks,
 		}
 	}
 }
@@ -129,6 +137,8 @@ impl From<crate::expr::statements::InfoStatement> for InfoStatement {
 			crate::expr::statements::InfoStatement::Index(i, t, v) => {
 				Self::Index(i.into(), t.into(), v)
 			}
+			crate::expr::statements::InfoStatement::IndexStats(t) => Self::IndexStats(t.into()),
+			crate::expr::statements::InfoStatement::Locks => Self::Locks,
 		}
 	}
 }
@@ -13,6 +13,8 @@ pub struct InsertStatement {
 	pub output: Option<Output>,
 	pub timeout: Expr,
 	pub relation: bool,
+	/// Does the statement have the `NON ATOMIC` clause.
+	pub non_atomic: bool,
 }
 
 impl ToSql for InsertStatement {
@@ -31,6 +33,9 @@ impl ToSql for InsertStatement {
 		if let Some(ref v) = self.update {
 			write_sql!(f, fmt, " {v}");
 		}
+		if self.non_atomic {
+			f.push_str(" NON ATOMIC");
+		}
 		if let Some(ref v) = self.output {
 			write_sql!(f, fmt, " {v}");
 		}
@@ -50,6 +55,7 @@ impl From<InsertStatement> for crate::expr::statements::InsertStatement {
 			output: v.output.map(Into::into),
 			timeout: v.timeout.into(),
 			relation: v.relation,
+			non_atomic: v.non_atomic,
 		}
 	}
 }
@@ -64,6 +70,7 @@ impl From<crate::expr::statements::InsertStatement> for InsertStatement {
 			output: v.output.map(Into::into),
 			timeout: v.timeout.into(),
 			relation: v.relation,
+			non_atomic: v.non_atomic,
 		}
 	}
 }
@@ -80,3 +80,26 @@ impl From<crate::expr::statements::LiveStatement> for LiveStatement {
 		}
 	}
 }
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct LiveSchemaStatement;
+
+impl ToSql for LiveSchemaStatement {
+	fn fmt_sql(&self, f: &mut String, _fmt: SqlFormat) {
+		f.push_str("LIVE SCHEMA");
+	}
+}
+
+impl From<LiveSchemaStatement> for crate::expr::statements::LiveSchemaStatement {
+	fn from(_: LiveSchemaStatement) -> Self {
+		crate::expr::statements::LiveSchemaStatement {
+			id: Uuid::new_v4(),
+		}
+	}
+}
+impl From<crate::expr::statements::LiveSchemaStatement> for LiveSchemaStatement {
+	fn from(_: crate::expr::statements::LiveSchemaStatement) -> Self {
+		LiveSchemaStatement
+	}
+}
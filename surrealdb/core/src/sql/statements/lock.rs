@@ -0,0 +1,44 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::EscapeKwFreeIdent;
+use crate::types::PublicDuration;
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct LockStatement {
+	pub what: TableName,
+	pub timeout: Option<PublicDuration>,
+}
+
+impl ToSql for LockStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(
+			f,
+			fmt,
+			"LOCK TABLE {} IN EXCLUSIVE MODE",
+			EscapeKwFreeIdent(self.what.as_str())
+		);
+		if let Some(ref v) = self.timeout {
+			write_sql!(f, fmt, " TIMEOUT {}", v);
+		}
+	}
+}
+
+impl From<LockStatement> for crate::expr::statements::LockStatement {
+	fn from(v: LockStatement) -> Self {
+		crate::expr::statements::LockStatement {
+			what: v.what,
+			timeout: v.timeout.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::statements::LockStatement> for LockStatement {
+	fn from(v: crate::expr::statements::LockStatement) -> Self {
+		LockStatement {
+			what: v.what,
+			timeout: v.timeout.map(Into::into),
+		}
+	}
+}
@@ -0,0 +1,32 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::EscapeKwFreeIdent;
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RefreshStatement {
+	pub what: TableName,
+}
+
+impl ToSql for RefreshStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "REFRESH VIEW {}", EscapeKwFreeIdent(self.what.as_str()));
+	}
+}
+
+impl From<RefreshStatement> for crate::expr::statements::RefreshStatement {
+	fn from(v: RefreshStatement) -> Self {
+		crate::expr::statements::RefreshStatement {
+			what: v.what,
+		}
+	}
+}
+
+impl From<crate::expr::statements::RefreshStatement> for RefreshStatement {
+	fn from(v: crate::expr::statements::RefreshStatement) -> Self {
+		RefreshStatement {
+			what: v.what,
+		}
+	}
+}
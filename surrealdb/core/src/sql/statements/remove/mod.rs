@@ -13,6 +13,8 @@ mod model;
 mod module;
 mod namespace;
 mod param;
+mod policy;
+mod query;
 mod sequence;
 mod table;
 mod user;
@@ -31,6 +33,8 @@ pub(crate) use model::RemoveModelStatement;
 pub(crate) use module::RemoveModuleStatement;
 pub(crate) use namespace::RemoveNamespaceStatement;
 pub(crate) use param::RemoveParamStatement;
+pub(crate) use policy::RemovePolicyStatement;
+pub(crate) use query::RemoveQueryStatement;
 pub(crate) use sequence::RemoveSequenceStatement;
 pub(crate) use table::RemoveTableStatement;
 pub(crate) use user::RemoveUserStatement;
@@ -44,8 +48,10 @@ pub(crate) enum RemoveStatement {
 	Analyzer(RemoveAnalyzerStatement),
 	Access(RemoveAccessStatement),
 	Param(RemoveParamStatement),
+	Query(RemoveQueryStatement),
 	Table(RemoveTableStatement),
 	Event(RemoveEventStatement),
+	Policy(RemovePolicyStatement),
 	Field(RemoveFieldStatement),
 	Index(RemoveIndexStatement),
 	User(RemoveUserStatement),
@@ -66,8 +72,10 @@ impl ToSql for RemoveStatement {
 			Self::Function(v) => v.fmt_sql(f, fmt),
 			Self::Access(v) => v.fmt_sql(f, fmt),
 			Self::Param(v) => v.fmt_sql(f, fmt),
+			Self::Query(v) => v.fmt_sql(f, fmt),
 			Self::Table(v) => v.fmt_sql(f, fmt),
 			Self::Event(v) => v.fmt_sql(f, fmt),
+			Self::Policy(v) => v.fmt_sql(f, fmt),
 			Self::Field(v) => v.fmt_sql(f, fmt),
 			Self::Index(v) => v.fmt_sql(f, fmt),
 			Self::Analyzer(v) => v.fmt_sql(f, fmt),
@@ -91,8 +99,10 @@ impl From<RemoveStatement> for crate::expr::statements::RemoveStatement {
 			RemoveStatement::Analyzer(v) => Self::Analyzer(v.into()),
 			RemoveStatement::Access(v) => Self::Access(v.into()),
 			RemoveStatement::Param(v) => Self::Param(v.into()),
+			RemoveStatement::Query(v) => Self::Query(v.into()),
 			RemoveStatement::Table(v) => Self::Table(v.into()),
 			RemoveStatement::Event(v) => Self::Event(v.into()),
+			RemoveStatement::Policy(v) => Self::Policy(v.into()),
 			RemoveStatement::Field(v) => Self::Field(v.into()),
 			RemoveStatement::Index(v) => Self::Index(v.into()),
 			RemoveStatement::User(v) => Self::User(v.into()),
@@ -115,8 +125,10 @@ impl From<crate::expr::statements::RemoveStatement> for RemoveStatement {
 			crate::expr::statements::RemoveStatement::Analyzer(v) => Self::Analyzer(v.into()),
 			crate::expr::statements::RemoveStatement::Access(v) => Self::Access(v.into()),
 			crate::expr::statements::RemoveStatement::Param(v) => Self::Param(v.into()),
+			crate::expr::statements::RemoveStatement::Query(v) => Self::Query(v.into()),
 			crate::expr::statements::RemoveStatement::Table(v) => Self::Table(v.into()),
 			crate::expr::statements::RemoveStatement::Event(v) => Self::Event(v.into()),
+			crate::expr::statements::RemoveStatement::Policy(v) => Self::Policy(v.into()),
 			crate::expr::statements::RemoveStatement::Field(v) => Self::Field(v.into()),
 			crate::expr::statements::RemoveStatement::Index(v) => Self::Index(v.into()),
 			crate::expr::statements::RemoveStatement::User(v) => Self::User(v.into()),
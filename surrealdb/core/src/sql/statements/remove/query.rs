@@ -0,0 +1,39 @@
+use surrealdb_strand::Strand;
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::EscapeKwFreeIdent;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct RemoveQueryStatement {
+	pub name: Strand,
+	pub if_exists: bool,
+}
+
+impl ToSql for RemoveQueryStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "REMOVE QUERY");
+		if self.if_exists {
+			write_sql!(f, fmt, " IF EXISTS");
+		}
+		write_sql!(f, fmt, " {}", EscapeKwFreeIdent(self.name.as_str()));
+	}
+}
+
+impl From<RemoveQueryStatement> for crate::expr::statements::remove::RemoveQueryStatement {
+	fn from(v: RemoveQueryStatement) -> Self {
+		crate::expr::statements::remove::RemoveQueryStatement {
+			name: v.name,
+			if_exists: v.if_exists,
+		}
+	}
+}
+
+impl From<crate::expr::statements::remove::RemoveQueryStatement> for RemoveQueryStatement {
+	fn from(v: crate::expr::statements::remove::RemoveQueryStatement) -> Self {
+		RemoveQueryStatement {
+			name: v.name,
+			if_exists: v.if_exists,
+		}
+	}
+}
@@ -24,8 +24,14 @@ pub struct SelectStatement {
 	pub fetch: Option<Fetchs>,
 	pub version: Expr,
 	pub timeout: Expr,
+	/// The RETURN PARTIAL part in SELECT * FROM baz TIMEOUT 2s RETURN PARTIAL.
+	pub partial: bool,
 	pub explain: Option<Explain>,
 	pub tempfiles: bool,
+	/// The d'...' part in SELECT * FROM baz FOR VALID TIME AS OF d'...'.
+	pub valid_time: Option<Expr>,
+	/// The 1000 part in SELECT * FROM baz SCAN LIMIT 1000.
+	pub scan_limit: Option<u64>,
 }
 
 impl ToSql for SelectStatement {
@@ -39,6 +45,9 @@ impl ToSql for SelectStatement {
 			write_sql!(f, fmt, " ONLY");
 		}
 		write_sql!(f, fmt, " {}", Fmt::comma_separated(self.what.iter().map(CoverStmts)));
+		if let Some(ref v) = self.valid_time {
+			write_sql!(f, fmt, " FOR VALID TIME AS OF {}", CoverStmts(v));
+		}
 		if let Some(ref v) = self.with {
 			write_sql!(f, fmt, " {v}");
 		}
@@ -68,6 +77,12 @@ impl ToSql for SelectStatement {
 		}
 		if !matches!(self.timeout, Expr::Literal(Literal::None)) {
 			write_sql!(f, fmt, " TIMEOUT {}", CoverStmts(&self.timeout));
+			if self.partial {
+				write_sql!(f, fmt, " RETURN PARTIAL");
+			}
+		}
+		if let Some(v) = self.scan_limit {
+			write_sql!(f, fmt, " SCAN LIMIT {v}");
 		}
 		if let Some(ref v) = self.explain {
 			write_sql!(f, fmt, " {v}");
@@ -92,8 +107,11 @@ impl From<SelectStatement> for crate::expr::statements::SelectStatement {
 			fetch: v.fetch.map(Into::into),
 			version: v.version.into(),
 			timeout: v.timeout.into(),
+			partial: v.partial,
 			explain: v.explain.map(Into::into),
 			tempfiles: v.tempfiles,
+			valid_time: v.valid_time.map(Into::into),
+			scan_limit: v.scan_limit,
 		}
 	}
 }
@@ -115,8 +133,11 @@ impl From<crate::expr::statements::SelectStatement> for SelectStatement {
 			fetch: v.fetch.map(Into::into),
 			version: v.version.into(),
 			timeout: v.timeout.into(),
+			partial: v.partial,
 			explain: v.explain.map(Into::into),
 			tempfiles: v.tempfiles,
+			valid_time: v.valid_time.map(Into::into),
+			scan_limit: v.scan_limit,
 		}
 	}
 }
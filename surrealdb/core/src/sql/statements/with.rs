@@ -0,0 +1,70 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::{EscapeIdent, Fmt};
+use crate::sql::Expr;
+use crate::sql::statements::SelectStatement;
+
+/// A SELECT statement preceded by common table expressions, like
+/// `WITH recent AS (SELECT ...) SELECT * FROM recent`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithStatement {
+	/// The common table expressions, in the order they are computed
+	pub exprs: Vec<CommonTableExpr>,
+	pub stmt: SelectStatement,
+}
+
+/// The `recent AS (SELECT ...)` part in
+/// `WITH recent AS (SELECT ...) SELECT * FROM recent`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommonTableExpr {
+	pub name: String,
+	pub what: Expr,
+}
+
+impl ToSql for WithStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "WITH {} {}", Fmt::comma_separated(self.exprs.iter()), self.stmt)
+	}
+}
+
+impl ToSql for CommonTableExpr {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "{} AS ({})", EscapeIdent(self.name.as_str()), self.what)
+	}
+}
+
+impl From<WithStatement> for crate::expr::statements::WithStatement {
+	fn from(v: WithStatement) -> Self {
+		Self {
+			exprs: v.exprs.into_iter().map(Into::into).collect(),
+			stmt: v.stmt.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::WithStatement> for WithStatement {
+	fn from(v: crate::expr::statements::WithStatement) -> Self {
+		Self {
+			exprs: v.exprs.into_iter().map(Into::into).collect(),
+			stmt: v.stmt.into(),
+		}
+	}
+}
+
+impl From<CommonTableExpr> for crate::expr::statements::with::CommonTableExpr {
+	fn from(v: CommonTableExpr) -> Self {
+		Self {
+			name: v.name,
+			what: v.what.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::with::CommonTableExpr> for CommonTableExpr {
+	fn from(v: crate::expr::statements::with::CommonTableExpr) -> Self {
+		Self {
+			name: v.name,
+			what: v.what.into(),
+		}
+	}
+}
@@ -4,11 +4,12 @@ use surrealdb_strand::Strand;
 use surrealdb_types::ToSql;
 
 use crate::sql::literal::ObjectEntry;
-use crate::sql::statements::access::{AccessStatementGrant, Subject};
+use crate::sql::statements::access::{AccessStatementGrant, AccessStatementGrantMany, Subject};
 use crate::sql::statements::alter::AlterKind;
 use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::rebuild::RebuildIndexStatement;
 use crate::sql::statements::show::ShowSince;
+use crate::sql::statements::with::{CommonTableExpr, WithStatement};
 use crate::sql::statements::{
 	AccessStatement, AlterStatement, AlterTableStatement, CreateStatement, DefineStatement,
 	DefineTableStatement, DeleteStatement, ForeachStatement, IfelseStatement, InfoStatement,
@@ -17,8 +18,8 @@ use crate::sql::statements::{
 	SleepStatement, UpdateStatement, UpsertStatement, UseStatement,
 };
 use crate::sql::{
-	BinaryOperator, Block, Closure, Constant, Data, Expr, Fields, Function, FunctionCall, Idiom,
-	KillStatement, Literal, LiveStatement, Mock, Param, PostfixOperator, PrefixOperator,
+	Base, BinaryOperator, Block, Closure, Constant, Data, Expr, Fields, Function, FunctionCall,
+	Idiom, KillStatement, Literal, LiveStatement, Mock, Param, PostfixOperator, PrefixOperator,
 	RecordIdKeyLit, RecordIdLit, TopLevelExpr,
 };
 use crate::types::{PublicBytes, PublicDuration, PublicFile, PublicGeometry};
@@ -142,7 +143,9 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
             Expr::Literal(Literal::Integer(3)),
         ]))))], close: None })), "IF true {\n\t1;\n\t2;\n} ELSE IF false { 3 }", "IF true {\n\n\t1;\n\t2;\n} ELSE IF false { 3 }")]
 // Expression: Select
-#[case::expr_select(Expr::Select(Box::new(SelectStatement { fields: Fields::all(), omit: vec![], only: false, what: vec![Expr::Table("user".into())], with: None, cond: None, split: None, group: None, order: None, limit: None, start: None, fetch: None, version: Expr::Literal(Literal::None), timeout: Expr::Literal(Literal::None), explain: None, tempfiles: false })), "SELECT * FROM user", "SELECT * FROM user")]
+#[case::expr_select(Expr::Select(Box::new(SelectStatement { fields: Fields::all(), omit: vec![], only: false, what: vec![Expr::Table("user".into())], with: None, cond: None, split: None, group: None, order: None, limit: None, start: None, fetch: None, version: Expr::Literal(Literal::None), timeout: Expr::Literal(Literal::None), partial: false, explain: None, tempfiles: false, valid_time: None, scan_limit: None })), "SELECT * FROM user", "SELECT * FROM user")]
+// Expression: With
+#[case::expr_with(Expr::With(Box::new(WithStatement { exprs: vec![CommonTableExpr { name: "adults".to_string(), what: Expr::Select(Box::new(SelectStatement { fields: Fields::all(), omit: vec![], only: false, what: vec![Expr::Table("person".into())], with: None, cond: None, split: None, group: None, order: None, limit: None, start: None, fetch: None, version: Expr::Literal(Literal::None), timeout: Expr::Literal(Literal::None), partial: false, explain: None, tempfiles: false, valid_time: None, scan_limit: None })) }], stmt: SelectStatement { fields: Fields::all(), omit: vec![], only: false, what: vec![Expr::Table("adults".into())], with: None, cond: None, split: None, group: None, order: None, limit: None, start: None, fetch: None, version: Expr::Literal(Literal::None), timeout: Expr::Literal(Literal::None), partial: false, explain: None, tempfiles: false, valid_time: None, scan_limit: None } })), "WITH adults AS (SELECT * FROM person) SELECT * FROM adults", "WITH adults AS (SELECT * FROM person) SELECT * FROM adults")]
 // Expression: Create
 #[case::expr_create(Expr::Create(Box::new(CreateStatement { only: false, what: vec![Expr::Table("user".into())], data: None, output: None, timeout: Expr::Literal(Literal::None) })), "CREATE user", "CREATE user")]
 // Expression: Update
@@ -154,7 +157,7 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
 // Expression: Relate OR UPDATE
 #[case::expr_relate_or_update(Expr::Relate(Box::new(RelateStatement { only: false, or_update: true, through: Expr::Table("likes".into()), from: Expr::Param(Param::new("from".to_string())), to: Expr::Param(Param::new("to".to_string())), data: None, output: None, timeout: Expr::Literal(Literal::None) })), "RELATE OR UPDATE $from -> likes -> $to", "RELATE OR UPDATE $from -> likes -> $to")]
 // Expression: Insert
-#[case::expr_insert(Expr::Insert(Box::new(InsertStatement { into: Some(Expr::Table("user".into())), data: Data::SingleExpression(Expr::Literal(Literal::Object(vec![ObjectEntry { key: "name".into(), value: Expr::Literal(Literal::String(Strand::new_static("test"))) }]))), ignore: false, update: None, output: None, timeout: Expr::Literal(Literal::None), relation: false})), "INSERT INTO user { name: 'test' }", "INSERT INTO user {\n\tname: 'test'\n}")]
+#[case::expr_insert(Expr::Insert(Box::new(InsertStatement { into: Some(Expr::Table("user".into())), data: Data::SingleExpression(Expr::Literal(Literal::Object(vec![ObjectEntry { key: "name".into(), value: Expr::Literal(Literal::String(Strand::new_static("test"))) }]))), ignore: false, update: None, output: None, timeout: Expr::Literal(Literal::None), relation: false, non_atomic: false})), "INSERT INTO user { name: 'test' }", "INSERT INTO user {\n\tname: 'test'\n}")]
 // Expression: Define
 #[case::expr_define(
 	Expr::Define(Box::new(DefineStatement::Table(DefineTableStatement::default()))),
@@ -179,6 +182,16 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
 	"INFO FOR ROOT",
 	"INFO FOR ROOT"
 )]
+#[case::expr_info_index_stats(
+	Expr::Info(Box::new(InfoStatement::IndexStats(Expr::Table("user".into())))),
+	"INFO FOR TABLE user INDEX STATS",
+	"INFO FOR TABLE user INDEX STATS"
+)]
+#[case::expr_info_locks(
+	Expr::Info(Box::new(InfoStatement::Locks)),
+	"INFO FOR LOCKS",
+	"INFO FOR LOCKS"
+)]
 // Expression: Foreach
 #[case::expr_foreach(Expr::Foreach(Box::new(ForeachStatement { param: Param::new("item".to_string()), range: Expr::Literal(Literal::Array(vec![Expr::Literal(Literal::Integer(1)), Expr::Literal(Literal::Integer(2))])), block: Block(vec![Expr::Literal(Literal::Integer(1))]) })), "FOR $item IN [1, 2] { 1 }", "FOR $item IN [\n\t1,\n\t2\n] {\n\n\t1\n}")]
 // Expression: Let
@@ -249,8 +262,11 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
             fetch: None,
             version: Expr::Literal(Literal::None),
             timeout: Expr::Literal(Literal::None),
+            partial: false,
             explain: None,
-            tempfiles: false
+            tempfiles: false,
+            valid_time: None,
+            scan_limit: None
         })),
         block: Block(vec![
             Expr::IfElse(Box::new(IfelseStatement {
@@ -332,7 +348,21 @@ use crate::val::{Bytes, Duration, File, Geometry, Number, Object, RecordId, Set,
 		ac: "user".into(),
         base: None,
         subject: Subject::Record(RecordIdLit { table: "user".into(), key: RecordIdKeyLit::Number(123) }),
+        totp: false,
     }))), "ACCESS user GRANT FOR RECORD user:123", "ACCESS user GRANT FOR RECORD user:123")]
+#[case::top_level_access_totp(TopLevelExpr::Access(Box::new(AccessStatement::Grant(
+    AccessStatementGrant {
+		ac: "user".into(),
+        base: None,
+        subject: Subject::Record(RecordIdLit { table: "user".into(), key: RecordIdKeyLit::Number(123) }),
+        totp: true,
+    }))), "ACCESS user GRANT TOTP FOR RECORD user:123", "ACCESS user GRANT TOTP FOR RECORD user:123")]
+#[case::top_level_access_many(TopLevelExpr::Access(Box::new(AccessStatement::GrantMany(
+    AccessStatementGrantMany {
+		ac: "api".into(),
+        base: Some(Base::Db),
+        subjects: vec![Subject::User("tobie".into()), Subject::User("jaime".into())],
+    }))), "ACCESS api ON DATABASE GRANT FOR USER IN [tobie, jaime]", "ACCESS api ON DATABASE GRANT FOR USER IN [tobie, jaime]")]
 #[case::top_level_kill(TopLevelExpr::Kill(KillStatement { id: Expr::Param(Param::new("id".to_string())) }), "KILL $id", "KILL $id")]
 #[case::top_level_live(TopLevelExpr::Live(Box::new(LiveStatement { fields: LiveFields::Select(Fields::all()), what: Expr::Table("user".into()), cond: None, fetch: None })), "LIVE SELECT * FROM user", "LIVE SELECT * FROM user")]
 #[case::top_level_live_diff(TopLevelExpr::Live(Box::new(LiveStatement { fields: LiveFields::Diff, what: Expr::Table("user".into()), cond: None, fetch: None })), "LIVE SELECT DIFF FROM user", "LIVE SELECT DIFF FROM user")]
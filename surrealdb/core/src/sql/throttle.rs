@@ -0,0 +1,33 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Throttle {
+	pub rate: u64,
+	pub burst: u64,
+}
+
+impl surrealdb_types::ToSql for Throttle {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: surrealdb_types::SqlFormat) {
+		use surrealdb_types::write_sql;
+		write_sql!(f, sql_fmt, "THROTTLE {} writes/s", self.rate);
+		if self.burst != self.rate {
+			write_sql!(f, sql_fmt, " BURST {}", self.burst);
+		}
+	}
+}
+
+impl From<Throttle> for crate::expr::Throttle {
+	fn from(v: Throttle) -> Self {
+		crate::expr::Throttle {
+			rate: v.rate,
+			burst: v.burst,
+		}
+	}
+}
+
+impl From<crate::expr::Throttle> for Throttle {
+	fn from(v: crate::expr::Throttle) -> Self {
+		Throttle {
+			rate: v.rate,
+			burst: v.burst,
+		}
+	}
+}
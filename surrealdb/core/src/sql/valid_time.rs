@@ -0,0 +1,39 @@
+use crate::fmt::EscapeKwFreeIdent;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ValidTime {
+	pub from: String,
+	pub to: String,
+}
+
+impl surrealdb_types::ToSql for ValidTime {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: surrealdb_types::SqlFormat) {
+		use surrealdb_types::write_sql;
+		write_sql!(
+			f,
+			sql_fmt,
+			"BITEMPORAL VALID FROM {} TO {}",
+			EscapeKwFreeIdent(&self.from),
+			EscapeKwFreeIdent(&self.to)
+		);
+	}
+}
+
+impl From<ValidTime> for crate::expr::ValidTime {
+	fn from(v: ValidTime) -> Self {
+		crate::expr::ValidTime {
+			from: v.from,
+			to: v.to,
+		}
+	}
+}
+
+impl From<crate::expr::ValidTime> for ValidTime {
+	fn from(v: crate::expr::ValidTime) -> Self {
+		ValidTime {
+			from: v.from,
+			to: v.to,
+		}
+	}
+}
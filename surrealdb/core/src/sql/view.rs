@@ -6,6 +6,8 @@ use crate::val::TableName;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct View {
+	/// Whether the view is stored, or computed when it is selected from
+	pub materialize: bool,
 	pub expr: Fields,
 	pub what: Vec<TableName>,
 	pub cond: Option<Cond>,
@@ -33,7 +35,7 @@ impl ToSql for View {
 impl From<View> for crate::expr::View {
 	fn from(v: View) -> Self {
 		crate::expr::View {
-			materialize: true,
+			materialize: v.materialize,
 			expr: v.expr.into(),
 			what: v.what,
 			cond: v.cond.map(Into::into),
@@ -45,6 +47,7 @@ impl From<View> for crate::expr::View {
 impl From<crate::expr::View> for View {
 	fn from(v: crate::expr::View) -> Self {
 		View {
+			materialize: v.materialize,
 			expr: v.expr.into(),
 			what: v.what,
 			cond: v.cond.map(Into::into),
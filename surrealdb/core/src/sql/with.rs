@@ -10,6 +10,7 @@ pub enum With {
 		#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::sql::arbitrary::atleast_one))]
 		Vec<String>,
 	),
+	Deleted,
 }
 
 impl ToSql for With {
@@ -17,6 +18,7 @@ impl ToSql for With {
 		f.push_str("WITH");
 		match self {
 			With::NoIndex => f.push_str(" NOINDEX"),
+			With::Deleted => f.push_str(" DELETED"),
 			With::Index(i) => {
 				f.push_str(" INDEX ");
 				write_sql!(
@@ -35,6 +37,7 @@ impl From<With> for crate::expr::With {
 		match v {
 			With::NoIndex => Self::NoIndex,
 			With::Index(i) => Self::Index(i),
+			With::Deleted => Self::Deleted,
 		}
 	}
 }
@@ -43,6 +46,7 @@ impl From<crate::expr::With> for With {
 		match v {
 			crate::expr::With::NoIndex => Self::NoIndex,
 			crate::expr::With::Index(i) => Self::Index(i),
+			crate::expr::With::Deleted => Self::Deleted,
 		}
 	}
 }
@@ -0,0 +1,207 @@
+//! Formatting of SurrealQL queries in the canonical style of the project.
+//!
+//! Queries are parsed, and printed again from their syntax tree, so the output
+//! only depends on what a query means and not on how it was written. Comments
+//! are not kept.
+
+use anyhow::Result;
+use surrealdb_types::ToSql;
+use unicase::UniCase;
+
+use super::lexer::Lexer;
+use super::lexer::keywords::KEYWORDS;
+use super::token::TokenKind;
+use crate::sql::TopLevelExpr;
+
+/// The indentation of the lines of a formatted query
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Indent {
+	/// One tab per level
+	#[default]
+	Tabs,
+	/// The given number of spaces per level
+	Spaces(u8),
+}
+
+/// The casing of the keywords of a formatted query
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum KeywordCase {
+	#[default]
+	Upper,
+	Lower,
+}
+
+/// The options of [`format`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct FormatOptions {
+	pub indent: Indent,
+	pub keyword_case: KeywordCase,
+	/// The number of characters from which a statement is broken over several
+	/// lines, or `None` to keep every statement on a single line
+	pub max_width: Option<usize>,
+}
+
+impl Default for FormatOptions {
+	fn default() -> Self {
+		Self {
+			indent: Indent::Tabs,
+			keyword_case: KeywordCase::Upper,
+			max_width: Some(100),
+		}
+	}
+}
+
+/// Formats a SurrealQL query in the canonical style of the project.
+///
+/// Every statement is written on its own line, and ends with a semicolon.
+/// Statements which do not fit within the maximum width are broken over
+/// several lines and indented. Where the indentation or the casing of the
+/// keywords would change the meaning of a statement, such as with a table
+/// named after a keyword or with an embedded script, the statement is written
+/// with tabs and uppercase keywords instead.
+#[instrument(level = "trace", target = "surrealdb::core::syn", skip_all, fields(length = input.len()))]
+pub fn format(input: &str, options: FormatOptions) -> Result<String> {
+	let ast = super::parse(input)?;
+	let mut out = String::with_capacity(input.len());
+	for (i, expr) in ast.expressions.iter().enumerate() {
+		if i > 0 {
+			out.push('\n');
+		}
+		out.push_str(&format_statement(expr, options));
+		out.push(';');
+	}
+	Ok(out)
+}
+
+fn format_statement(expr: &TopLevelExpr, options: FormatOptions) -> String {
+	let line = expr.to_sql();
+	let canonical = match options.max_width {
+		// The statement is followed by a semicolon
+		Some(width) if line.chars().count() >= width => expr.to_sql_pretty(),
+		_ => line,
+	};
+	if options.indent == Indent::Tabs && options.keyword_case == KeywordCase::Upper {
+		return canonical;
+	}
+	let Some(styled) = restyle(&canonical, options) else {
+		return canonical;
+	};
+	match (super::parse(&canonical), super::parse(&styled)) {
+		(Ok(expected), Ok(actual)) if expected == actual => styled,
+		_ => canonical,
+	}
+}
+
+/// Rewrites the indentation and the keywords of a canonically formatted
+/// statement
+fn restyle(text: &str, options: FormatOptions) -> Option<String> {
+	let indent = match options.indent {
+		Indent::Tabs => "\t".to_string(),
+		Indent::Spaces(n) => " ".repeat(n as usize),
+	};
+	let mut out = String::with_capacity(text.len());
+	let mut lexer = Lexer::new(text.as_bytes());
+	let mut last = 0;
+	loop {
+		let token = lexer.next_token();
+		match token.kind {
+			TokenKind::Eof => break,
+			TokenKind::Invalid => return None,
+			_ => {}
+		}
+		let start = token.span.offset as usize;
+		let end = start + token.span.len as usize;
+		restyle_whitespace(&text[last..start], &indent, &mut out);
+		let word = &text[start..end];
+		if options.keyword_case == KeywordCase::Lower && is_keyword(word) {
+			out.push_str(&word.to_ascii_lowercase());
+		} else {
+			out.push_str(word);
+		}
+		last = end;
+	}
+	restyle_whitespace(&text[last..], &indent, &mut out);
+	Some(out)
+}
+
+/// Replaces the tabs at the start of the lines within the whitespace between
+/// two tokens
+fn restyle_whitespace(whitespace: &str, indent: &str, out: &mut String) {
+	for (i, line) in whitespace.split('\n').enumerate() {
+		if i > 0 {
+			out.push('\n');
+			let tabs = line.len() - line.trim_start_matches('\t').len();
+			for _ in 0..tabs {
+				out.push_str(indent);
+			}
+			out.push_str(&line[tabs..]);
+		} else {
+			out.push_str(line);
+		}
+	}
+}
+
+fn is_keyword(word: &str) -> bool {
+	word.bytes().any(|b| b.is_ascii_uppercase())
+		&& KEYWORDS.get(&UniCase::ascii(word)).is_some_and(|kind| *kind != TokenKind::Identifier)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn format_with(input: &str, options: FormatOptions) -> String {
+		format(input, options).unwrap()
+	}
+
+	#[test]
+	fn formats_canonically() {
+		let options = FormatOptions::default();
+		let formatted = format_with("select   * from person where age>18;create person", options);
+		assert_eq!(formatted, "SELECT * FROM person WHERE age > 18;\nCREATE person;");
+		// Formatting is stable
+		assert_eq!(format_with(&formatted, options), formatted);
+	}
+
+	#[test]
+	fn formats_keywords_in_lowercase() {
+		let options = FormatOptions {
+			keyword_case: KeywordCase::Lower,
+			..Default::default()
+		};
+		let formatted = format_with("SELECT name FROM person WHERE age > 18", options);
+		assert_eq!(formatted, "select name from person where age > 18;");
+		// Identifiers named after keywords are left as they are
+		let query = "DEFINE TABLE `SELECT`";
+		let formatted = format_with(query, options);
+		assert!(formatted.contains("SELECT"));
+		assert_eq!(super::super::parse(&formatted).unwrap(), super::super::parse(query).unwrap());
+	}
+
+	#[test]
+	fn wraps_long_statements() {
+		let query =
+			"SELECT name, age, email, address, phone FROM person WHERE age > 18 AND active = true";
+		let options = FormatOptions {
+			max_width: Some(40),
+			indent: Indent::Spaces(2),
+			..Default::default()
+		};
+		let formatted = format_with(query, options);
+		assert!(formatted.lines().count() > 1);
+		assert!(!formatted.contains('\t'));
+		// The formatted query means the same
+		assert_eq!(super::super::parse(&formatted).unwrap(), super::super::parse(query).unwrap());
+		// Statements are kept on one line without a maximum width
+		let options = FormatOptions {
+			max_width: None,
+			..Default::default()
+		};
+		assert_eq!(format_with(query, options).lines().count(), 1);
+	}
+
+	#[test]
+	fn rejects_invalid_queries() {
+		format("SELECT * FROM", FormatOptions::default()).unwrap_err();
+	}
+}
@@ -80,6 +80,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ASSERT") => TokenKind::Keyword(Keyword::Assert),
 	UniCase::ascii("ASYNC") => TokenKind::Keyword(Keyword::Async),
 	UniCase::ascii("AT") => TokenKind::Keyword(Keyword::At),
+	UniCase::ascii("ATOMIC") => TokenKind::Keyword(Keyword::Atomic),
 	UniCase::ascii("AUTHENTICATE") => TokenKind::Keyword(Keyword::Authenticate),
 	UniCase::ascii("AUTO") => TokenKind::Keyword(Keyword::Auto),
 	UniCase::ascii("BACKEND") => TokenKind::Keyword(Keyword::Backend),
@@ -91,9 +92,11 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("BM25") => TokenKind::Keyword(Keyword::Bm25),
 	UniCase::ascii("BREAK") => TokenKind::Keyword(Keyword::Break),
 	UniCase::ascii("BUCKET") => TokenKind::Keyword(Keyword::Bucket),
+	UniCase::ascii("BURST") => TokenKind::Keyword(Keyword::Burst),
 	UniCase::ascii("BY") => TokenKind::Keyword(Keyword::By),
 	UniCase::ascii("CAMEL") => TokenKind::Keyword(Keyword::Camel),
 	UniCase::ascii("CANCEL") => TokenKind::Keyword(Keyword::Cancel),
+	UniCase::ascii("CAPABILITIES") => TokenKind::Keyword(Keyword::Capabilities),
 	UniCase::ascii("CAPACITY") => TokenKind::Keyword(Keyword::Capacity),
 	UniCase::ascii("CASCADE") => TokenKind::Keyword(Keyword::Cascade),
 	UniCase::ascii("CHANGEFEED") => TokenKind::Keyword(Keyword::ChangeFeed),
@@ -121,6 +124,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("DEFAULT") => TokenKind::Keyword(Keyword::Default),
 	UniCase::ascii("DEFINE") => TokenKind::Keyword(Keyword::Define),
 	UniCase::ascii("DELETE") => TokenKind::Keyword(Keyword::Delete),
+	UniCase::ascii("DELETED") => TokenKind::Keyword(Keyword::Deleted),
 	UniCase::ascii("DESC") => TokenKind::Keyword(Keyword::Descending),
 	UniCase::ascii("DESCENDING") => TokenKind::Keyword(Keyword::Descending),
 	UniCase::ascii("DIFF") => TokenKind::Keyword(Keyword::Diff),
@@ -135,6 +139,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("END") => TokenKind::Keyword(Keyword::End),
 	UniCase::ascii("ENFORCED") => TokenKind::Keyword(Keyword::Enforced),
 	UniCase::ascii("EVENT") => TokenKind::Keyword(Keyword::Event),
+	UniCase::ascii("EVICT") => TokenKind::Keyword(Keyword::Evict),
 	UniCase::ascii("EXCLUDE") => TokenKind::Keyword(Keyword::Exclude),
 	UniCase::ascii("EXISTS") => TokenKind::Keyword(Keyword::Exists),
 	UniCase::ascii("EXPIRED") => TokenKind::Keyword(Keyword::Expired),
@@ -184,8 +189,10 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("KV") => TokenKind::Keyword(Keyword::Root),
 	UniCase::ascii("LET") => TokenKind::Keyword(Keyword::Let),
 	UniCase::ascii("LIMIT") => TokenKind::Keyword(Keyword::Limit),
+	UniCase::ascii("LINEAGE") => TokenKind::Keyword(Keyword::Lineage),
 	UniCase::ascii("LIVE") => TokenKind::Keyword(Keyword::Live),
 	UniCase::ascii("LM") => TokenKind::Keyword(Keyword::Lm),
+	UniCase::ascii("LOCKS") => TokenKind::Keyword(Keyword::Locks),
 	UniCase::ascii("LOWERCASE") => TokenKind::Keyword(Keyword::Lowercase),
 	UniCase::ascii("M") => TokenKind::Keyword(Keyword::M),
 	UniCase::ascii("M0") => TokenKind::Keyword(Keyword::M0),
@@ -196,10 +203,12 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ML") => TokenKind::Keyword(Keyword::ML),
 	UniCase::ascii("MODEL") => TokenKind::Keyword(Keyword::Model),
 	UniCase::ascii("MODULE") => TokenKind::Keyword(Keyword::Module),
+	UniCase::ascii("MOVE") => TokenKind::Keyword(Keyword::Move),
 	UniCase::ascii("NAMESPACE") => TokenKind::Keyword(Keyword::Namespace),
 	UniCase::ascii("NGRAM") => TokenKind::Keyword(Keyword::Ngram),
 	UniCase::ascii("NO") => TokenKind::Keyword(Keyword::No),
 	UniCase::ascii("NOINDEX") => TokenKind::Keyword(Keyword::NoIndex),
+	UniCase::ascii("NON") => TokenKind::Keyword(Keyword::Non),
 	UniCase::ascii("NONE") => TokenKind::Keyword(Keyword::None),
 	UniCase::ascii("NONEINSIDE") => TokenKind::Keyword(Keyword::NoneInside),
 	UniCase::ascii("NORMAL") => TokenKind::Keyword(Keyword::Normal),
@@ -216,6 +225,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ORDER") => TokenKind::Keyword(Keyword::Order),
 	UniCase::ascii("ORIGINAL") => TokenKind::Keyword(Keyword::Original),
 	UniCase::ascii("OUT") => TokenKind::Keyword(Keyword::Out),
+	UniCase::ascii("OUTBOX") => TokenKind::Keyword(Keyword::Outbox),
 	UniCase::ascii("OUTSIDE") => TokenKind::Keyword(Keyword::Outside),
 	UniCase::ascii("OVERWRITE") => TokenKind::Keyword(Keyword::Overwrite),
 	UniCase::ascii("PARALLEL") => TokenKind::Keyword(Keyword::Parallel),
@@ -224,11 +234,14 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("PASSWORD") => TokenKind::Keyword(Keyword::Password),
 	UniCase::ascii("PATCH") => TokenKind::Keyword(Keyword::Patch),
 	UniCase::ascii("PERMISSIONS") => TokenKind::Keyword(Keyword::Permissions),
+	UniCase::ascii("POLICY") => TokenKind::Keyword(Keyword::Policy),
 	UniCase::ascii("POSTINGS_CACHE") => TokenKind::Keyword(Keyword::PostingsCache),
 	UniCase::ascii("POSTINGS_ORDER") => TokenKind::Keyword(Keyword::PostingsOrder),
 	UniCase::ascii("PREPARE") => TokenKind::Keyword(Keyword::Prepare),
 	UniCase::ascii("PUNCT") => TokenKind::Keyword(Keyword::Punct),
 	UniCase::ascii("PURGE") => TokenKind::Keyword(Keyword::Purge),
+	UniCase::ascii("QUARANTINE") => TokenKind::Keyword(Keyword::Quarantine),
+	UniCase::ascii("QUERY") => TokenKind::Keyword(Keyword::Query),
 	UniCase::ascii("RANGE") => TokenKind::Keyword(Keyword::Range),
 	UniCase::ascii("READONLY") => TokenKind::Keyword(Keyword::Readonly),
 	UniCase::ascii("REBUILD") => TokenKind::Keyword(Keyword::Rebuild),
@@ -259,8 +272,10 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("SINCE") => TokenKind::Keyword(Keyword::Since),
 	UniCase::ascii("SLEEP") => TokenKind::Keyword(Keyword::Sleep),
 	UniCase::ascii("SNOWBALL") => TokenKind::Keyword(Keyword::Snowball),
+	UniCase::ascii("SOFT") => TokenKind::Keyword(Keyword::Soft),
 	UniCase::ascii("SPLIT") => TokenKind::Keyword(Keyword::Split),
 	UniCase::ascii("START") => TokenKind::Keyword(Keyword::Start),
+	UniCase::ascii("STATS") => TokenKind::Keyword(Keyword::Stats),
 	UniCase::ascii("STRICT") => TokenKind::Keyword(Keyword::Strict),
 	UniCase::ascii("STRUCTURE") => TokenKind::Keyword(Keyword::Structure),
 	UniCase::ascii("SYSTEM") => TokenKind::Keyword(Keyword::System),
@@ -271,6 +286,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("TERMS_CACHE") => TokenKind::Keyword(Keyword::TermsCache),
 	UniCase::ascii("TERMS_ORDER") => TokenKind::Keyword(Keyword::TermsOrder),
 	UniCase::ascii("THEN") => TokenKind::Keyword(Keyword::Then),
+	UniCase::ascii("THROTTLE") => TokenKind::Keyword(Keyword::Throttle),
 	UniCase::ascii("THROW") => TokenKind::Keyword(Keyword::Throw),
 	UniCase::ascii("TIMEOUT") => TokenKind::Keyword(Keyword::Timeout),
 	UniCase::ascii("TO") => TokenKind::Keyword(Keyword::To),
@@ -287,9 +303,11 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("URL") => TokenKind::Keyword(Keyword::Url),
 	UniCase::ascii("USE") => TokenKind::Keyword(Keyword::Use),
 	UniCase::ascii("USER") => TokenKind::Keyword(Keyword::User),
+	UniCase::ascii("VALIDATE") => TokenKind::Keyword(Keyword::Validate),
 	UniCase::ascii("VALUE") => TokenKind::Keyword(Keyword::Value),
 	UniCase::ascii("VALUES") => TokenKind::Keyword(Keyword::Values),
 	UniCase::ascii("VERSION") => TokenKind::Keyword(Keyword::Version),
+	UniCase::ascii("VIEW") => TokenKind::Keyword(Keyword::View),
 	UniCase::ascii("VS") => TokenKind::Keyword(Keyword::Vs),
 	UniCase::ascii("WHEN") => TokenKind::Keyword(Keyword::When),
 	UniCase::ascii("WHERE") => TokenKind::Keyword(Keyword::Where),
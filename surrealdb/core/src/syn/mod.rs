@@ -12,6 +12,7 @@ use crate::sql::{Ast, Block, Expr, Function, Idiom, Kind};
 use crate::types::{PublicDatetime, PublicDuration, PublicRecordId, PublicValue};
 
 pub mod error;
+mod format;
 pub mod lexer;
 pub mod parser;
 pub mod token;
@@ -25,10 +26,12 @@ pub trait Parse<T> {
 mod test;
 
 use anyhow::{Result, bail, ensure};
+pub use format::{FormatOptions, Indent, KeywordCase, format};
 use lexer::Lexer;
 pub use parser::ParserSettings;
 use parser::{ParseResult, Parser};
 use reblessive::{Stack, Stk};
+use surrealdb_types::ToSql;
 use token::t;
 
 const TARGET: &str = "surrealdb::core::syn";
@@ -143,6 +146,27 @@ pub(crate) fn expr_with_capabilities(
 	)
 }
 
+/// Parses a SurrealQL condition, like that of a `WHERE` clause, returning
+/// its canonical SurrealQL text.
+///
+/// The whole input must be a single expression, so the returned text can be
+/// placed within another query without changing the statements around it.
+#[instrument(level = "trace", target = "surrealdb::core::syn", fields(length = input.len()))]
+pub fn condition(input: &str) -> Result<String> {
+	trace!(target: TARGET, "Parsing SurrealQL condition");
+
+	let expr = parse_with_settings(
+		input.as_bytes(),
+		settings_from_capabilities_config(&Capabilities::all(), &CommonConfig::default()),
+		async |parser, stk| {
+			let expr = parser.parse_expr_field(stk).await?;
+			parser.assert_finished()?;
+			Ok(expr)
+		},
+	)?;
+	Ok(expr.to_sql())
+}
+
 /// Parses a SurrealQL function name.
 #[instrument(level = "trace", target = "surrealdb::core::syn", fields(length = input.len()))]
 pub fn function_with_capabilities(
@@ -371,6 +395,7 @@ fn extract_tables_from_kind_impl(kind: &Kind, tables: &mut HashSet<String>) {
 		| Kind::Bytes
 		| Kind::Datetime
 		| Kind::Decimal
+		| Kind::FixedDecimal(_, _)
 		| Kind::Duration
 		| Kind::Float
 		| Kind::Int
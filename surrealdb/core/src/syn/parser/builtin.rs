@@ -113,6 +113,8 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("crypto::scrypt::compare") => (PathKind::Function, None),
 		UniCase::ascii("crypto::scrypt::generate") => (PathKind::Function, None),
 		//
+		UniCase::ascii("db::versionstamp") => (PathKind::Function, None),
+		//
 		UniCase::ascii("duration::days") => (PathKind::Function, None),
 		UniCase::ascii("duration::hours") => (PathKind::Function, None),
 		UniCase::ascii("duration::micros") => (PathKind::Function, None),
@@ -187,6 +189,8 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("math::clamp") => (PathKind::Function, None),
 		UniCase::ascii("math::cos") => (PathKind::Function, None),
 		UniCase::ascii("math::cot") => (PathKind::Function, None),
+		UniCase::ascii("math::decimal_mean") => (PathKind::Function, None),
+		UniCase::ascii("math::decimal_sum") => (PathKind::Function, None),
 		UniCase::ascii("math::deg2rad") => (PathKind::Function, None),
 		UniCase::ascii("math::fixed") => (PathKind::Function, None),
 		UniCase::ascii("math::floor") => (PathKind::Function, None),
@@ -221,6 +225,7 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("math::variance") => (PathKind::Function, None),
 		//
 		UniCase::ascii("meta::id") => (PathKind::Function, None),
+		UniCase::ascii("meta::lineage") => (PathKind::Function, None),
 		UniCase::ascii("meta::tb") => (PathKind::Function, None),
 		//
 		UniCase::ascii("not") => (PathKind::Function, None),
@@ -245,6 +250,8 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("parse::url::query") => (PathKind::Function, None),
 		UniCase::ascii("parse::url::scheme") => (PathKind::Function, None),
 		//
+		UniCase::ascii("query::run") => (PathKind::Function, None),
+		//
 		UniCase::ascii("rand") => (PathKind::Function, None),
 		UniCase::ascii("rand::bool") => (PathKind::Function, None),
 		UniCase::ascii("rand::duration") => (PathKind::Function, None),
@@ -259,6 +266,7 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("rand::uuid::v7") => (PathKind::Function, None),
 		UniCase::ascii("rand::uuid") => (PathKind::Function, None),
 		//
+		UniCase::ascii("record::created_at") => (PathKind::Function, None),
 		UniCase::ascii("record::exists") => (PathKind::Function, None),
 		UniCase::ascii("record::id") => (PathKind::Function, None),
 		UniCase::ascii("record::is_edge") => (PathKind::Function, None),
@@ -517,7 +525,12 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("time::MAXIMUM") => (PathKind::Constant(Constant::TimeMax), None),
 		UniCase::ascii("duration::MAX") => (PathKind::Constant(Constant::DurationMax), None),
 		//
+		UniCase::ascii("schema::openapi") => (PathKind::Function, None),
 		UniCase::ascii("schema::table::exists") => (PathKind::Function, None),
+		UniCase::ascii("schema::table::stats") => (PathKind::Function, None),
+		UniCase::ascii("schema::advisor::start") => (PathKind::Function, None),
+		UniCase::ascii("schema::advisor::stop") => (PathKind::Function, None),
+		UniCase::ascii("schema::advisor::recommendations") => (PathKind::Function, None),
 };
 
 const MAX_LEVENSTHEIN_CUT_OFF: u8 = 4;
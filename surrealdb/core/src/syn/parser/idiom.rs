@@ -416,6 +416,23 @@ impl Parser<'_> {
 				Some(RecurseInstruction::Collect {
 					inclusive,
 				})
+			} else if kind.eq_ignore_ascii_case("tree") {
+				let mut inclusive = false;
+				loop {
+					if self.eat(t!("+")) {
+						let kind = self.parse_ident()?.into_string();
+						if kind.eq_ignore_ascii_case("inclusive") {
+							inclusive = true
+						} else {
+							bail!("Unexpected option `{}` expected `inclusive`",kind, @self.last_span());
+						}
+					} else {
+						break;
+					};
+				}
+				Some(RecurseInstruction::Tree {
+					inclusive,
+				})
 			} else if kind.eq_ignore_ascii_case("shortest") {
 				expected!(self, t!("="));
 				let token = self.peek();
@@ -446,7 +463,7 @@ impl Parser<'_> {
 					inclusive,
 				})
 			} else {
-				bail!("Unexpected instruction `{}` expected `path`, `collect`, or `shortest`",kind, @self.last_span());
+				bail!("Unexpected instruction `{}` expected `path`, `collect`, `tree`, or `shortest`",kind, @self.last_span());
 			}
 		} else {
 			None
@@ -6,6 +6,7 @@ use super::mac::unexpected;
 use super::{ParseResult, Parser};
 use crate::sql::Kind;
 use crate::sql::kind::{GeometryKind, KindLiteral};
+use crate::syn::error::bail;
 use crate::syn::lexer::compound;
 use crate::syn::parser::enter_object_recursion;
 use crate::syn::parser::mac::expected;
@@ -132,7 +133,27 @@ impl Parser<'_> {
 			t!("NULL") => Ok(Kind::Null),
 			t!("BYTES") => Ok(Kind::Bytes),
 			t!("DATETIME") => Ok(Kind::Datetime),
-			t!("DECIMAL") => Ok(Kind::Decimal),
+			t!("DECIMAL") => {
+				let span = this.peek().span;
+				if this.eat(t!("(")) {
+					let precision: u32 = this.next_token_value()?;
+					expected!(this, t!(","));
+					let scale: u32 = this.next_token_value()?;
+					this.expect_closing_delimiter(t!(")"), span)?;
+					let max = crate::expr::Kind::MAX_DECIMAL_PRECISION;
+					if precision == 0 || precision > max {
+						bail!("The precision of a decimal must be between 1 and {max}",
+							@span.covers(this.last_span()));
+					}
+					if scale > precision {
+						bail!("The scale of a decimal can not be larger than its precision",
+							@span.covers(this.last_span()));
+					}
+					Ok(Kind::FixedDecimal(precision, scale))
+				} else {
+					Ok(Kind::Decimal)
+				}
+			}
 			t!("DURATION") => Ok(Kind::Duration),
 			t!("FLOAT") => Ok(Kind::Float),
 			t!("INT") => Ok(Kind::Int),
@@ -264,6 +285,7 @@ mod tests {
 	#[case::bytes("bytes", "bytes", Kind::Bytes)]
 	#[case::datetime("datetime", "datetime", Kind::Datetime)]
 	#[case::decimal("decimal", "decimal", Kind::Decimal)]
+	#[case::fixed_decimal("decimal(20,4)", "decimal(20, 4)", Kind::FixedDecimal(20, 4))]
 	#[case::duration("duration", "duration", Kind::Duration)]
 	#[case::float("float", "float", Kind::Float)]
 	#[case::number("number", "number", Kind::Number)]
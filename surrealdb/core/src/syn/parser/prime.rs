@@ -211,6 +211,13 @@ impl Parser<'_> {
 				let stmt = self.parse_select_stmt(stk).await?;
 				Expr::Select(Box::new(stmt))
 			}
+			t!("WITH")
+				if Self::kind_is_identifier(self.peek1().kind) && self.peek2().kind == t!("AS") =>
+			{
+				self.pop_peek();
+				let stmt = self.parse_with_stmt(stk).await?;
+				Expr::With(Box::new(stmt))
+			}
 			t!("CREATE") => {
 				self.pop_peek();
 				let stmt = self.parse_create_stmt(stk).await?;
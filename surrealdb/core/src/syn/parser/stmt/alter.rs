@@ -240,12 +240,16 @@ impl Parser<'_> {
 							self.pop_peek();
 							res.comment = AlterKind::Drop;
 						}
-						t!("ASYNC") => {
+						t!("ASYNC") | t!("OUTBOX") => {
 							self.pop_peek();
 							res.kind = AlterKind::Drop;
 						}
 						_ => {
-							unexpected!(self, peek, "`WHEN`, `THEN`, `COMMENT`, or `ASYNC`")
+							unexpected!(
+								self,
+								peek,
+								"`WHEN`, `THEN`, `COMMENT`, `ASYNC`, or `OUTBOX`"
+							)
 						}
 					}
 				}
@@ -272,16 +276,27 @@ impl Parser<'_> {
 						max_depth: EventDefinition::DEFAULT_MAX_DEPTH,
 					});
 				}
+				t!("OUTBOX") => {
+					self.pop_peek();
+					res.kind = AlterKind::Set(EventKind::Outbox {
+						retry: EventDefinition::DEFAULT_RETRY,
+					});
+				}
 				t!("RETRY") => {
 					let token = self.pop_peek();
-					if let AlterKind::Set(EventKind::Async {
-						ref mut retry,
-						..
-					}) = res.kind
+					if let AlterKind::Set(
+						EventKind::Async {
+							ref mut retry,
+							..
+						}
+						| EventKind::Outbox {
+							ref mut retry,
+						},
+					) = res.kind
 					{
 						*retry = self.next_token_value()?;
 					} else {
-						bail!("Unexpected token `RETRY`", @token.span => "RETRY must be set after ASYNC");
+						bail!("Unexpected token `RETRY`", @token.span => "RETRY must be set after ASYNC or OUTBOX");
 					}
 				}
 				t!("MAXDEPTH") => {
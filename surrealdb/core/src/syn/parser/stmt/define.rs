@@ -1,7 +1,12 @@
+use std::str::FromStr;
+
 use reblessive::Stk;
 use surrealdb_strand::Strand;
 
-use crate::catalog::{ApiMethod, EventDefinition, EventKind};
+use crate::catalog::{
+	ApiMethod, DatabaseCapabilities, EventDefinition, EventKind, FunctionCapabilities,
+};
+use crate::dbs::capabilities::{FuncTarget, NetTarget};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::base::Base;
@@ -17,8 +22,9 @@ use crate::sql::statements::define::{
 	ApiAction, DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement,
 	DefineBucketStatement, DefineConfigStatement, DefineDatabaseStatement, DefineDefault,
 	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineSequenceStatement,
-	DefineStatement, DefineTableStatement, DefineUserStatement,
+	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefinePolicyStatement,
+	DefineQueryStatement, DefineSequenceStatement, DefineStatement, DefineTableStatement,
+	DefineUserStatement, UniqueConflicts,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
@@ -48,7 +54,7 @@ impl Parser<'_> {
 		stk: &mut Stk,
 	) -> ParseResult<DefineStatement> {
 		let next = self.next();
-		match next.kind {
+		let stmt = match next.kind {
 			t!("NAMESPACE") => {
 				self.parse_define_namespace(stk).await.map(DefineStatement::Namespace)
 			}
@@ -56,11 +62,16 @@ impl Parser<'_> {
 			t!("FUNCTION") => self.parse_define_function(stk).await.map(DefineStatement::Function),
 			t!("USER") => self.parse_define_user(stk).await.map(DefineStatement::User),
 			t!("PARAM") => self.parse_define_param(stk).await.map(DefineStatement::Param),
+			t!("QUERY") => self.parse_define_query(stk).await.map(DefineStatement::Query),
 			t!("TABLE") => self.parse_define_table(stk).await.map(DefineStatement::Table),
+			t!("VIEW") => self.parse_define_view(stk).await.map(DefineStatement::Table),
 			t!("API") => self.parse_define_api(stk).await.map(DefineStatement::Api),
 			t!("EVENT") => {
 				stk.run(|stk| self.parse_define_event(stk)).await.map(DefineStatement::Event)
 			}
+			t!("POLICY") => {
+				stk.run(|stk| self.parse_define_policy(stk)).await.map(DefineStatement::Policy)
+			}
 			t!("FIELD") => stk
 				.run(|stk| self.parse_define_field(stk))
 				.await
@@ -75,7 +86,12 @@ impl Parser<'_> {
 			t!("SEQUENCE") => self.parse_define_sequence(stk).await.map(DefineStatement::Sequence),
 			t!("MODULE") => self.parse_define_module(stk).await.map(DefineStatement::Module),
 			_ => unexpected!(self, next, "a define statement keyword"),
+		}?;
+		// Preview the definition rather than applying it
+		if self.eat(t!("DIFF")) {
+			return Ok(DefineStatement::Diff(Box::new(stmt)));
 		}
+		Ok(stmt)
 	}
 
 	pub(crate) async fn parse_define_namespace(
@@ -140,6 +156,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.strict = true;
 				}
+				t!("CAPABILITIES") => {
+					self.pop_peek();
+					res.capabilities = Some(self.parse_database_capabilities()?);
+				}
 				_ => break,
 			}
 		}
@@ -147,6 +167,60 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses the `{ functions: [...], scripting: ... }` body of a database
+	/// `CAPABILITIES` clause.
+	fn parse_database_capabilities(&mut self) -> ParseResult<DatabaseCapabilities> {
+		let start = expected!(self, t!("{")).span;
+		let mut res = DatabaseCapabilities::default();
+		loop {
+			if self.eat(t!("}")) {
+				break;
+			}
+			let token = self.peek();
+			match self.parse_ident()?.into_string().as_str() {
+				"functions" => {
+					expected!(self, t!(":"));
+					let open = expected!(self, t!("[")).span;
+					let mut functions = Vec::new();
+					loop {
+						if self.eat(t!("]")) {
+							break;
+						}
+						let token = self.peek();
+						let func = self.parse_string_lit()?;
+						if FuncTarget::from_str(&func).is_err() {
+							bail!("Invalid function target `{func}`",
+								@token.span => "Expected a function target in the form of <family>::* or <family>::<name>");
+						}
+						functions.push(func);
+						if !self.eat(t!(",")) {
+							self.expect_closing_delimiter(t!("]"), open)?;
+							break;
+						}
+					}
+					res.functions = Some(functions);
+				}
+				"scripting" => {
+					expected!(self, t!(":"));
+					let next = self.next();
+					res.scripting = Some(match next.kind {
+						t!("true") => true,
+						t!("false") => false,
+						_ => unexpected!(self, next, "either 'true' or 'false'"),
+					});
+				}
+				_ => {
+					bail!("Unexpected database capability", @token.span => "Expected `functions` or `scripting`");
+				}
+			}
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("}"), start)?;
+				break;
+			}
+		}
+		Ok(res)
+	}
+
 	pub(crate) async fn parse_define_function(
 		&mut self,
 		stk: &mut Stk,
@@ -198,6 +272,7 @@ impl Parser<'_> {
 			permissions: Permission::default(),
 			graphql_alias: None,
 			graphql_deprecated: None,
+			capabilities: None,
 		};
 
 		loop {
@@ -218,6 +293,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.graphql_deprecated = Some(self.parse_string_lit()?);
 				}
+				t!("CAPABILITIES") => {
+					self.pop_peek();
+					res.capabilities = Some(self.parse_function_capabilities()?);
+				}
 				_ => break,
 			}
 		}
@@ -225,6 +304,105 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub(crate) async fn parse_define_query(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineQueryStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+		let name = self.parse_ident()?;
+		let token = expected!(self, t!("(")).span;
+		let mut args = Vec::new();
+		loop {
+			if self.eat(t!(")")) {
+				break;
+			}
+
+			let param = self.next_token_value::<Param>()?.into_string();
+			expected!(self, t!(":"));
+			let kind = stk.run(|ctx| self.parse_inner_kind(ctx)).await?;
+
+			args.push((param, kind));
+
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!(")"), token)?;
+				break;
+			}
+		}
+		expected!(self, t!("AS"));
+		let query = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+
+		let mut res = DefineQueryStatement {
+			kind,
+			name,
+			args,
+			query,
+			comment: Expr::Literal(Literal::None),
+			permissions: Permission::default(),
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				t!("PERMISSIONS") => {
+					self.pop_peek();
+					res.permissions = stk.run(|ctx| self.parse_permission_value(ctx)).await?;
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	/// Parses the `{ net: [...] }` body of a function `CAPABILITIES` clause.
+	fn parse_function_capabilities(&mut self) -> ParseResult<FunctionCapabilities> {
+		let start = expected!(self, t!("{")).span;
+		let mut res = FunctionCapabilities::default();
+		loop {
+			if self.eat(t!("}")) {
+				break;
+			}
+			let token = self.peek();
+			if self.parse_ident_str()? != "net" {
+				bail!("Unexpected function capability", @token.span => "Expected `net`");
+			}
+			expected!(self, t!(":"));
+			let open = expected!(self, t!("[")).span;
+			loop {
+				if self.eat(t!("]")) {
+					break;
+				}
+				let token = self.peek();
+				let net = self.parse_string_lit()?;
+				if NetTarget::from_str(&net).is_err() {
+					bail!("Invalid network target `{net}`",
+						@token.span => "Expected a network target in the form of <host>[:<port>] or <ipv4|ipv6>[/<mask>]");
+				}
+				res.net.push(net);
+				if !self.eat(t!(",")) {
+					self.expect_closing_delimiter(t!("]"), open)?;
+					break;
+				}
+			}
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("}"), start)?;
+				break;
+			}
+		}
+		Ok(res)
+	}
+
 	#[cfg(not(feature = "surrealism"))]
 	pub(crate) async fn parse_define_module(
 		&mut self,
@@ -513,8 +691,13 @@ impl Parser<'_> {
 											jwt: ac.jwt.clone(),
 										});
 									}
+									TokenKind::Identifier if self.peek_ident_keyword("MFA") => {
+										self.pop_peek();
+										self.expect_ident_keyword("TOTP")?;
+										ac.mfa = Some(access_type::MfaMethod::Totp);
+									}
 									_ => {
-										unexpected!(self, token, "JWT or REFRESH")
+										unexpected!(self, token, "JWT, REFRESH or MFA")
 									}
 								}
 								self.eat(t!(","));
@@ -708,6 +891,30 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("THROTTLE") => {
+					self.pop_peek();
+					res.throttle = Some(self.parse_throttle()?);
+				}
+				t!("LINEAGE") => {
+					self.pop_peek();
+					res.lineage = true;
+				}
+				t!("SOFT") => {
+					self.pop_peek();
+					res.soft_delete = Some(self.parse_soft_delete()?);
+				}
+				t!("EVICT") => {
+					self.pop_peek();
+					res.eviction = Some(self.parse_eviction()?);
+				}
+				t!("SESSION") => {
+					self.pop_peek();
+					res.session = true;
+				}
+				TokenKind::Identifier if self.peek_ident_keyword("BITEMPORAL") => {
+					self.pop_peek();
+					res.valid_time = Some(self.parse_valid_time()?);
+				}
 				t!("AS") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -738,6 +945,53 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses a view which is computed when it is selected from, which is
+	/// stored as a table without any records of its own.
+	pub(crate) async fn parse_define_view(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineTableStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+		let name = stk.run(|ctx| self.parse_expr_table(ctx)).await?;
+		expected!(self, t!("AS"));
+		let peek = self.peek();
+		let mut view = match peek.kind {
+			t!("(") => {
+				let open = self.pop_peek().span;
+				let view = self.parse_view(stk).await?;
+				self.expect_closing_delimiter(t!(")"), open)?;
+				view
+			}
+			t!("SELECT") => self.parse_view(stk).await?,
+			_ => unexpected!(self, peek, "`SELECT`"),
+		};
+		view.materialize = false;
+		let mut res = DefineTableStatement {
+			name,
+			kind,
+			view: Some(view),
+			// The records are read from the tables of the view, which check
+			// their own permissions
+			permissions: Permissions::full(),
+			..Default::default()
+		};
+
+		while let t!("COMMENT") = self.peek_kind() {
+			self.pop_peek();
+			res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		}
+
+		Ok(res)
+	}
+
 	pub(crate) async fn parse_define_api(
 		&mut self,
 		stk: &mut Stk,
@@ -891,16 +1145,25 @@ impl Parser<'_> {
 						max_depth: EventDefinition::DEFAULT_MAX_DEPTH,
 					};
 				}
+				t!("OUTBOX") => {
+					self.pop_peek();
+					res.event_kind = EventKind::Outbox {
+						retry: EventDefinition::DEFAULT_RETRY,
+					};
+				}
 				t!("RETRY") => {
 					let token = self.pop_peek();
 					if let EventKind::Async {
 						retry,
 						..
+					}
+					| EventKind::Outbox {
+						retry,
 					} = &mut res.event_kind
 					{
 						*retry = self.next_token_value()?;
 					} else {
-						bail!("Unexpected token `RETRY`", @token.span => "RETRY must be set after ASYNC");
+						bail!("Unexpected token `RETRY`", @token.span => "RETRY must be set after ASYNC or OUTBOX");
 					}
 				}
 				t!("MAXDEPTH") => {
@@ -923,6 +1186,57 @@ impl Parser<'_> {
 		}
 		Ok(res)
 	}
+
+	pub(crate) async fn parse_define_policy(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefinePolicyStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let target_table = stk.run(|ctx| self.parse_expr_table(ctx)).await?;
+		expected!(self, t!("WHEN"));
+		let when = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		expected!(self, t!("MOVE"));
+		expected!(self, t!("TO"));
+		self.eat(t!("TABLE"));
+		let destination = stk.run(|ctx| self.parse_expr_table(ctx)).await?;
+
+		let mut res = DefinePolicyStatement {
+			kind,
+			name,
+			target_table,
+			when,
+			destination,
+			batch: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("BATCH") => {
+					self.pop_peek();
+					res.batch = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
 	pub(crate) async fn parse_define_field(
 		&mut self,
 		stk: &mut Stk,
@@ -1066,6 +1380,7 @@ impl Parser<'_> {
 			index: Index::Idx,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
 		};
 
 		let mut field_span = None;
@@ -1084,6 +1399,12 @@ impl Parser<'_> {
 				t!("UNIQUE") => {
 					self.pop_peek();
 					res.index = Index::Uniq;
+					if self.eat(t!("VALIDATE")) {
+						res.conflicts = UniqueConflicts::Validate;
+					} else if self.eat(t!("QUARANTINE")) {
+						let tb = stk.run(|ctx| self.parse_expr_table(ctx)).await?;
+						res.conflicts = UniqueConflicts::Quarantine(tb);
+					}
 				}
 				t!("COUNT") => {
 					self.pop_peek();
@@ -37,6 +37,12 @@ impl Parser<'_> {
 		} else {
 			None
 		};
+		let non_atomic = if self.eat(t!("NON")) {
+			expected!(self, t!("ATOMIC"));
+			true
+		} else {
+			false
+		};
 		let output = self.try_parse_output(stk).await?;
 
 		// VERSION is no longer supported in INSERT statements, it is left here for backwards
@@ -54,6 +60,7 @@ impl Parser<'_> {
 			output,
 			timeout,
 			relation,
+			non_atomic,
 		})
 	}
 
@@ -4,17 +4,19 @@ use super::mac::expected;
 use super::{ParseResult, Parser};
 use crate::sql::data::Assignment;
 use crate::sql::statements::access::{
-	AccessStatement, AccessStatementGrant, AccessStatementPurge, AccessStatementRevoke,
-	AccessStatementShow, PurgeKind, Subject,
+	AccessStatement, AccessStatementGrant, AccessStatementGrantMany, AccessStatementPurge,
+	AccessStatementRevoke, AccessStatementShow, AccessStatementShowSubject, PurgeKind, Subject,
 };
 use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::rebuild::RebuildIndexStatement;
 use crate::sql::statements::show::ShowSince;
 use crate::sql::statements::{
-	ForeachStatement, InfoStatement, KillStatement, LiveStatement, OptionStatement,
-	OutputStatement, RebuildStatement, SetStatement, ShowStatement, SleepStatement, UseStatement,
+	ForeachStatement, InfoStatement, KillStatement, LiveSchemaStatement, LiveStatement,
+	LockStatement, OptionStatement, OutputStatement, RebuildStatement, RefreshStatement,
+	SetStatement, ShowStatement, SleepStatement, UseStatement,
 };
 use crate::sql::{AssignOperator, ExplainFormat, Expr, Literal, Param, TopLevelExpr};
+use crate::syn::error::bail;
 use crate::syn::lexer::compound;
 use crate::syn::parser::mac::unexpected;
 use crate::syn::token::{TokenKind, t};
@@ -95,6 +97,14 @@ impl Parser<'_> {
 			}
 			t!("LIVE") => {
 				self.pop_peek();
+				// SCHEMA is not a reserved keyword, so it is parsed as an identifier
+				let peek = self.peek();
+				if peek.kind == TokenKind::Identifier
+					&& self.lexer.span_str(peek.span).eq_ignore_ascii_case("SCHEMA")
+				{
+					self.pop_peek();
+					return Ok(TopLevelExpr::LiveSchema(LiveSchemaStatement));
+				}
 				self.parse_live_stmt(stk).await.map(|x| TopLevelExpr::Live(Box::new(x)))
 			}
 			t!("OPTION") => {
@@ -113,6 +123,21 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_show_stmt().map(TopLevelExpr::Show)
 			}
+			t!("REFRESH") if self.peek1().kind == t!("VIEW") => {
+				self.pop_peek();
+				self.pop_peek();
+				let what = self.parse_ident_str()?.into();
+				Ok(TopLevelExpr::Refresh(RefreshStatement {
+					what,
+				}))
+			}
+			// LOCK is not a reserved keyword, so it is parsed as an identifier
+			TokenKind::Identifier
+				if self.peek_ident_keyword("LOCK") && self.peek1().kind == t!("TABLE") =>
+			{
+				self.pop_peek();
+				self.parse_lock_stmt().map(TopLevelExpr::Lock)
+			}
 			_ => {
 				let covered = self.peek_kind() == t!("(");
 				let expr = self.parse_expr_start(stk).await?;
@@ -127,34 +152,48 @@ impl Parser<'_> {
 
 	/// Parsers an access statement.
 	async fn parse_access(&mut self, stk: &mut Stk) -> ParseResult<AccessStatement> {
+		// `ACCESS SHOW ALL FOR ...` shows the grants of a subject across all access methods.
+		if self.peek_kind() == t!("SHOW") && self.peek1().kind == t!("ALL") {
+			self.pop_peek();
+			self.pop_peek();
+			expected!(self, t!("FOR"));
+			let subject = self.parse_access_subject(stk).await?;
+			return Ok(AccessStatement::ShowSubject(AccessStatementShowSubject {
+				subject,
+			}));
+		}
 		let ac = self.parse_ident()?;
 		let base = self.eat(t!("ON")).then(|| self.parse_base()).transpose()?;
 		let peek = self.peek();
 		match peek.kind {
 			t!("GRANT") => {
 				self.pop_peek();
+				// `GRANT TOTP` enrols a single record in the second factor of the access method.
+				let totp = self.peek_ident_keyword("TOTP");
+				if totp {
+					self.pop_peek();
+				}
 				expected!(self, t!("FOR"));
-				match self.peek_kind() {
-					t!("USER") => {
-						self.pop_peek();
-						let user = self.parse_ident()?;
-						Ok(AccessStatement::Grant(AccessStatementGrant {
-							ac,
-							base,
-							subject: Subject::User(user),
-						}))
-					}
-					t!("RECORD") => {
-						self.pop_peek();
-						let rid = stk.run(|ctx| self.parse_record_id(ctx)).await?;
-						Ok(AccessStatement::Grant(AccessStatementGrant {
-							ac,
-							base,
-							subject: Subject::Record(rid),
-						}))
-					}
-					_ => unexpected!(self, peek, "either USER or RECORD"),
+				// `FOR USER IN [...]` issues a grant for each subject of the list.
+				if !totp
+					&& matches!(self.peek_kind(), t!("USER") | t!("RECORD"))
+					&& self.peek1().kind == t!("IN")
+					&& self.peek2().kind == t!("[")
+				{
+					let subjects = self.parse_access_subjects(stk).await?;
+					return Ok(AccessStatement::GrantMany(AccessStatementGrantMany {
+						ac,
+						base,
+						subjects,
+					}));
 				}
+				let subject = self.parse_access_subject(stk).await?;
+				Ok(AccessStatement::Grant(AccessStatementGrant {
+					ac,
+					base,
+					subject,
+					totp,
+				}))
 			}
 			t!("SHOW") => {
 				self.pop_peek();
@@ -270,6 +309,50 @@ impl Parser<'_> {
 		}
 	}
 
+	/// Parsers the subject of an access grant.
+	async fn parse_access_subject(&mut self, stk: &mut Stk) -> ParseResult<Subject> {
+		let peek = self.peek();
+		match peek.kind {
+			t!("USER") => {
+				self.pop_peek();
+				Ok(Subject::User(self.parse_ident()?))
+			}
+			t!("RECORD") => {
+				self.pop_peek();
+				let rid = stk.run(|ctx| self.parse_record_id(ctx)).await?;
+				Ok(Subject::Record(rid))
+			}
+			_ => unexpected!(self, peek, "either USER or RECORD"),
+		}
+	}
+
+	/// Parses a list of access subjects of the same kind, like `USER IN [tobie, jaime]`.
+	async fn parse_access_subjects(&mut self, stk: &mut Stk) -> ParseResult<Vec<Subject>> {
+		let peek = self.next();
+		expected!(self, t!("IN"));
+		let open = expected!(self, t!("[")).span;
+		let mut subjects = Vec::new();
+		loop {
+			if self.eat(t!("]")) {
+				break;
+			}
+			let subject = match peek.kind {
+				t!("USER") => Subject::User(self.parse_ident()?),
+				t!("RECORD") => Subject::Record(stk.run(|ctx| self.parse_record_id(ctx)).await?),
+				_ => unexpected!(self, peek, "either USER or RECORD"),
+			};
+			subjects.push(subject);
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("]"), open)?;
+				break;
+			}
+		}
+		if subjects.is_empty() {
+			bail!("Expected at least one subject to grant access to", @self.last_span);
+		}
+		Ok(subjects)
+	}
+
 	/// Parsers a begin statement.
 	///
 	/// # Parser State
@@ -319,6 +402,9 @@ impl Parser<'_> {
 			}
 		};
 
+		// Check for optional PERMISSIONS keyword
+		let permissions = !analyze && self.eat(t!("PERMISSIONS"));
+
 		// Check for optional FORMAT keyword
 		let format = {
 			let peek = self.peek();
@@ -356,6 +442,7 @@ impl Parser<'_> {
 		Ok(Expr::Explain {
 			format,
 			analyze,
+			permissions,
 			statement: Box::new(statement),
 		})
 	}
@@ -447,6 +534,10 @@ impl Parser<'_> {
 			}
 			t!("TABLE") => {
 				let ident = stk.run(|stk| self.parse_expr_table(stk)).await?;
+				if self.eat(t!("INDEX")) {
+					expected!(self, t!("STATS"));
+					return Ok(InfoStatement::IndexStats(ident));
+				}
 				let version = if self.eat(t!("VERSION")) {
 					Some(stk.run(|stk| self.parse_expr_inherit(stk)).await?)
 				} else {
@@ -469,6 +560,7 @@ impl Parser<'_> {
 				let structure = self.eat(t!("STRUCTURE"));
 				InfoStatement::Index(index, table, structure)
 			}
+			t!("LOCKS") => InfoStatement::Locks,
 			_ => unexpected!(self, next, "an info target"),
 		};
 
@@ -493,6 +585,27 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parsers a LOCK statement.
+	///
+	/// # Parser State
+	/// Expects `LOCK` to already be consumed.
+	pub(super) fn parse_lock_stmt(&mut self) -> ParseResult<LockStatement> {
+		expected!(self, t!("TABLE"));
+		let what = self.parse_ident_str()?.into();
+		expected!(self, t!("IN"));
+		self.expect_ident_keyword("EXCLUSIVE")?;
+		self.expect_ident_keyword("MODE")?;
+		let timeout = if self.eat(t!("TIMEOUT")) {
+			Some(self.next_token_value::<PublicDuration>()?)
+		} else {
+			None
+		};
+		Ok(LockStatement {
+			what,
+			timeout,
+		})
+	}
+
 	/// Parsers a LIVE statement.
 	///
 	/// # Parser State
@@ -5,8 +5,12 @@ use surrealdb_strand::Strand;
 use surrealdb_types::ToSql;
 
 use crate::sql::changefeed::ChangeFeed;
+use crate::sql::eviction::Eviction;
 use crate::sql::index::{Distance, VectorType};
 use crate::sql::reference::{Reference, ReferenceDeleteStrategy};
+use crate::sql::soft_delete::SoftDelete;
+use crate::sql::throttle::Throttle;
+use crate::sql::valid_time::ValidTime;
 use crate::sql::{
 	Base, Cond, Data, Explain, Expr, Fetch, Fetchs, Field, Fields, Group, Groups, Idiom, Literal,
 	Output, Permission, Permissions, View, With,
@@ -113,6 +117,33 @@ impl Parser<'_> {
 		Ok(duration)
 	}
 
+	/// Parses the `RETURN PARTIAL` clause of a timeout, if there is one
+	pub(crate) fn try_parse_partial(&mut self, timeout: &Expr) -> ParseResult<bool> {
+		if matches!(timeout, Expr::Literal(Literal::None)) || !self.eat(t!("RETURN")) {
+			return Ok(false);
+		}
+		self.expect_ident_keyword("PARTIAL")?;
+		Ok(true)
+	}
+
+	/// Parses a `SCAN LIMIT` clause, if there is one
+	pub(crate) fn try_parse_scan_limit(&mut self) -> ParseResult<Option<u64>> {
+		if !self.peek_ident_keyword("SCAN") {
+			return Ok(None);
+		}
+		self.pop_peek();
+		expected!(self, t!("LIMIT"));
+		let span = self.recent_span();
+		let limit = self.next_token_value::<u64>()?;
+		if limit == 0 {
+			bail!(
+				"Scan limits must be greater than zero",
+				@span => "at least one row must be allowed to be scanned"
+			);
+		}
+		Ok(Some(limit))
+	}
+
 	pub(crate) async fn try_parse_fetch(&mut self, stk: &mut Stk) -> ParseResult<Option<Fetchs>> {
 		if !self.eat(t!("FETCH")) {
 			return Ok(None);
@@ -472,6 +503,157 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses a table write throttle
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `THROTTLE` keyword
+	pub fn parse_throttle(&mut self) -> ParseResult<Throttle> {
+		let rate = self.parse_throttle_limit()?;
+		let unit = self.recent_span();
+		if !self.parse_ident_str()?.eq_ignore_ascii_case("writes") {
+			bail!(
+				"Expected a throttle rate in writes per second",
+				@unit => "expected `writes/s` here"
+			);
+		}
+		expected!(self, t!("/"));
+		let unit = self.recent_span();
+		if !self.parse_ident_str()?.eq_ignore_ascii_case("s") {
+			bail!(
+				"Expected a throttle rate in writes per second",
+				@unit => "expected `writes/s` here"
+			);
+		}
+		let burst = if self.eat(t!("BURST")) {
+			self.parse_throttle_limit()?
+		} else {
+			rate
+		};
+		Ok(Throttle {
+			rate,
+			burst,
+		})
+	}
+
+	fn parse_throttle_limit(&mut self) -> ParseResult<u64> {
+		let span = self.recent_span();
+		let limit = self.next_token_value::<u64>()?;
+		if limit == 0 {
+			bail!(
+				"Throttle limits must be greater than zero",
+				@span => "at least one write must be allowed"
+			);
+		}
+		Ok(limit)
+	}
+
+	/// Parses a table soft delete clause
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `SOFT` keyword
+	pub fn parse_soft_delete(&mut self) -> ParseResult<SoftDelete> {
+		expected!(self, t!("DELETE"));
+		expected!(self, t!("FIELD"));
+		let field = self.parse_ident()?.into_string();
+		let retention = if self.eat(t!("PURGE")) {
+			expected!(self, t!("AFTER"));
+			Some(self.next_token_value::<PublicDuration>()?)
+		} else {
+			None
+		};
+		Ok(SoftDelete {
+			field,
+			retention,
+		})
+	}
+
+	/// Parses a table eviction clause
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `EVICT` keyword
+	pub fn parse_eviction(&mut self) -> ParseResult<Eviction> {
+		let peek = self.peek();
+		let lru = peek.kind == TokenKind::Identifier
+			&& self.span_str(peek.span).eq_ignore_ascii_case("LRU");
+		if lru {
+			self.pop_peek();
+		}
+		let ttl = if self.eat(t!("AFTER")) {
+			Some(self.next_token_value::<PublicDuration>()?)
+		} else {
+			None
+		};
+		if !lru && ttl.is_none() {
+			unexpected!(self, self.peek(), "`LRU` or `AFTER`");
+		}
+		Ok(Eviction {
+			lru,
+			ttl,
+		})
+	}
+
+	/// Checks whether the next token is an identifier which is only a
+	/// keyword within a single clause, like the `VALID` of
+	/// `BITEMPORAL VALID FROM`
+	pub(crate) fn peek_ident_keyword(&mut self, keyword: &str) -> bool {
+		let peek = self.peek();
+		peek.kind == TokenKind::Identifier && self.span_str(peek.span).eq_ignore_ascii_case(keyword)
+	}
+
+	/// Eats an identifier which is only a keyword within a single clause
+	pub(crate) fn expect_ident_keyword(&mut self, keyword: &'static str) -> ParseResult<()> {
+		if !self.peek_ident_keyword(keyword) {
+			unexpected!(self, self.peek(), format!("`{keyword}`"));
+		}
+		self.pop_peek();
+		Ok(())
+	}
+
+	/// Parses a table valid time clause
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `BITEMPORAL` identifier
+	pub fn parse_valid_time(&mut self) -> ParseResult<ValidTime> {
+		self.expect_ident_keyword("VALID")?;
+		expected!(self, t!("FROM"));
+		let from = self.parse_ident()?.into_string();
+		expected!(self, t!("TO"));
+		let to = self.parse_ident()?.into_string();
+		if from == to {
+			bail!(
+				"The valid time of a table must start and end in different fields",
+				@self.last_span() => "this field also holds the start of the valid time"
+			);
+		}
+		Ok(ValidTime {
+			from,
+			to,
+		})
+	}
+
+	/// Parses a `FOR VALID TIME AS OF` clause, if there is one
+	pub(crate) async fn try_parse_valid_time(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<Option<Expr>> {
+		if self.peek_kind() != t!("FOR") {
+			return Ok(None);
+		}
+		let peek = self.peek1();
+		if peek.kind != TokenKind::Identifier
+			|| !self.span_str(peek.span).eq_ignore_ascii_case("VALID")
+		{
+			return Ok(None);
+		}
+		self.pop_peek();
+		self.pop_peek();
+		self.expect_ident_keyword("TIME")?;
+		expected!(self, t!("AS"));
+		self.expect_ident_keyword("OF")?;
+		let at = stk.run(|stk| self.parse_expr_field(stk)).await?;
+		Ok(Some(at))
+	}
+
 	/// Parses a reference
 	///
 	/// # Parser State
@@ -521,6 +703,7 @@ impl Parser<'_> {
 		let group = self.try_parse_group(&fields, fields_span, None)?;
 
 		Ok(View {
+			materialize: true,
 			expr: fields,
 			what: from,
 			cond,
@@ -660,7 +843,8 @@ impl Parser<'_> {
 				}
 				With::Index(index)
 			}
-			_ => unexpected!(self, next, "`NO`, `NOINDEX` or `INDEX`"),
+			t!("DELETED") => With::Deleted,
+			_ => unexpected!(self, next, "`NO`, `NOINDEX`, `INDEX` or `DELETED`"),
 		};
 		Ok(Some(with))
 	}
@@ -2,7 +2,8 @@ use reblessive::Stk;
 
 use crate::sql::statements::remove::{
 	RemoveAnalyzerStatement, RemoveApiStatement, RemoveBucketStatement, RemoveConfigKind,
-	RemoveConfigStatement, RemoveModuleStatement, RemoveSequenceStatement,
+	RemoveConfigStatement, RemoveModuleStatement, RemovePolicyStatement, RemoveQueryStatement,
+	RemoveSequenceStatement,
 };
 use crate::sql::statements::{
 	RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
@@ -168,6 +169,20 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("QUERY") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.parse_ident()?;
+
+				RemoveStatement::Query(RemoveQueryStatement {
+					name,
+					if_exists,
+				})
+			}
 			t!("TABLE") => {
 				let expunge = if self.eat(t!("AND")) {
 					expected!(self, t!("EXPUNGE"));
@@ -209,6 +224,24 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("POLICY") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let table = stk.run(|stk| self.parse_expr_field(stk)).await?;
+
+				RemoveStatement::Policy(RemovePolicyStatement {
+					name,
+					what: table,
+					if_exists,
+				})
+			}
 			t!("FIELD") => {
 				let if_exists = if self.eat(t!("IF")) {
 					expected!(self, t!("EXISTS"));
@@ -3,7 +3,9 @@ use reblessive::Stk;
 use super::parts::MissingKind;
 use crate::sql::order::{OrderList, Ordering};
 use crate::sql::statements::SelectStatement;
+use crate::sql::statements::with::{CommonTableExpr, WithStatement};
 use crate::sql::{Expr, Fields, Limit, Literal, Order, Split, Splits, Start};
+use crate::syn::error::bail;
 use crate::syn::parser::mac::expected;
 use crate::syn::parser::{ParseResult, Parser};
 use crate::syn::token::{Span, t};
@@ -41,6 +43,7 @@ impl Parser<'_> {
 			what.push(stk.run(|ctx| self.parse_expr_table(ctx)).await?);
 		}
 
+		let valid_time = self.try_parse_valid_time(stk).await?;
 		let with = self.try_parse_with()?;
 		let cond = self.try_parse_condition(stk).await?;
 
@@ -65,6 +68,8 @@ impl Parser<'_> {
 			Expr::Literal(Literal::None)
 		};
 		let timeout = self.try_parse_timeout(stk).await?;
+		let partial = self.try_parse_partial(&timeout)?;
+		let scan_limit = self.try_parse_scan_limit()?;
 		let tempfiles = self.eat(t!("TEMPFILES"));
 		let explain = self.try_parse_explain()?;
 
@@ -83,8 +88,46 @@ impl Parser<'_> {
 			fetch,
 			version,
 			timeout,
+			partial,
 			tempfiles,
 			explain,
+			valid_time,
+			scan_limit,
+		})
+	}
+
+	/// Parses a SELECT statement preceded by common table expressions, like
+	/// `WITH recent AS (SELECT ...) SELECT * FROM recent`.
+	///
+	/// # Parser State
+	/// Expects `WITH` to already be consumed.
+	pub(crate) async fn parse_with_stmt(&mut self, stk: &mut Stk) -> ParseResult<WithStatement> {
+		let mut exprs: Vec<CommonTableExpr> = Vec::new();
+		loop {
+			let name = self.parse_ident_str()?.to_owned();
+			if exprs.iter().any(|x| x.name == name) {
+				bail!(
+					"Duplicate common table expression `{name}`",
+					@self.last_span() => "Each expression in a WITH clause must have a unique name"
+				);
+			}
+			expected!(self, t!("AS"));
+			let start = expected!(self, t!("(")).span;
+			let what = stk.run(|stk| self.parse_expr_inherit(stk)).await?;
+			self.expect_closing_delimiter(t!(")"), start)?;
+			exprs.push(CommonTableExpr {
+				name,
+				what,
+			});
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
+		expected!(self, t!("SELECT"));
+		let stmt = self.parse_select_stmt(stk).await?;
+		Ok(WithStatement {
+			exprs,
+			stmt,
 		})
 	}
 
@@ -2,7 +2,7 @@ use chrono::offset::TimeZone;
 use chrono::{NaiveDate, Offset, Utc};
 use surrealdb_strand::Strand;
 
-use crate::catalog::EventKind;
+use crate::catalog::{DatabaseCapabilities, EventKind, FunctionCapabilities};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::{
 	AccessType, BearerAccess, BearerAccessSubject, BearerAccessType, JwtAccess, JwtAccessIssue,
@@ -17,36 +17,38 @@ use crate::sql::language::Language;
 use crate::sql::literal::ObjectEntry;
 use crate::sql::lookup::{LookupKind, LookupSubject};
 use crate::sql::statements::access::{
-	self, AccessStatementGrant, AccessStatementPurge, AccessStatementRevoke, AccessStatementShow,
-	PurgeKind,
+	self, AccessStatementGrant, AccessStatementGrantMany, AccessStatementPurge,
+	AccessStatementRevoke, AccessStatementShow, AccessStatementShowSubject, PurgeKind,
 };
 use crate::sql::statements::define::user::PassType;
 use crate::sql::statements::define::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement, DefineDefault,
 	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement,
+	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefinePolicyStatement,
+	DefineQueryStatement, DefineStatement, DefineTableStatement, UniqueConflicts,
 };
 use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::remove::{
-	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement,
+	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement, RemovePolicyStatement,
+	RemoveQueryStatement,
 };
 use crate::sql::statements::show::{ShowSince, ShowStatement};
 use crate::sql::statements::sleep::SleepStatement;
 use crate::sql::statements::{
 	AccessStatement, CreateStatement, DeleteStatement, ForeachStatement, IfelseStatement,
-	InfoStatement, InsertStatement, KillStatement, OptionStatement, OutputStatement,
-	RelateStatement, RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement,
-	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
-	RemoveParamStatement, RemoveStatement, RemoveTableStatement, RemoveUserStatement,
-	SelectStatement, UpdateStatement, UpsertStatement, UseStatement,
+	InfoStatement, InsertStatement, KillStatement, LiveSchemaStatement, LockStatement,
+	OptionStatement, OutputStatement, RefreshStatement, RelateStatement, RemoveAccessStatement,
+	RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
+	RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement,
+	RemoveTableStatement, RemoveUserStatement, SelectStatement, UpdateStatement, UpsertStatement,
+	UseStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
-	Algorithm, AssignOperator, Base, BinaryOperator, Block, Cond, Data, Dir, Explain, Expr, Fetch,
-	Fetchs, Field, Fields, Group, Groups, Idiom, Index, Kind, Literal, Lookup, Mock, Output, Param,
-	Part, Permission, Permissions, RecordIdKeyLit, RecordIdLit, Scoring, TableType, TopLevelExpr,
-	With,
+	Algorithm, AssignOperator, Base, BinaryOperator, Block, Cond, Data, Dir, Eviction, Explain,
+	Expr, Fetch, Fetchs, Field, Fields, Group, Groups, Idiom, Index, Kind, Literal, Lookup, Mock,
+	Output, Param, Part, Permission, Permissions, RecordIdKeyLit, RecordIdLit, Scoring, SoftDelete,
+	TableType, Throttle, TopLevelExpr, ValidTime, With,
 };
 use crate::syn;
 use crate::syn::parser::ParserSettings;
@@ -207,6 +209,7 @@ fn parse_define_database() {
 				expiry: PublicDuration::from_secs(60 * 10),
 				store_diff: true,
 			}),
+			capabilities: None,
 		})))
 	);
 
@@ -223,10 +226,66 @@ fn parse_define_database() {
 			strict: false,
 			comment: Expr::Literal(Literal::None),
 			changefeed: None,
+			capabilities: None,
 		})))
 	)
 }
 
+#[test]
+fn parse_define_database_capabilities() {
+	let res = syn::parse_with(
+		r#"DEFINE DATABASE app CAPABILITIES { functions: ['string::*', "array::len"], scripting: false }"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	let Expr::Define(stmt) = res else {
+		panic!("expected a define statement")
+	};
+	let DefineStatement::Database(stmt) = *stmt else {
+		panic!("expected a define database statement")
+	};
+	assert_eq!(
+		stmt.capabilities,
+		Some(DatabaseCapabilities {
+			functions: Some(vec!["string::*".to_owned(), "array::len".to_owned()]),
+			scripting: Some(false),
+		})
+	);
+
+	syn::parse_with(
+		r#"DEFINE DATABASE app CAPABILITIES { functions: ["string::is::*"] }"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+	syn::parse_with(
+		r#"DEFINE DATABASE app CAPABILITIES { net: [] }"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_define_diff() {
+	let res =
+		syn::parse_with("DEFINE NAMESPACE OVERWRITE a DIFF".as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Diff(Box::new(DefineStatement::Namespace(
+			DefineNamespaceStatement {
+				kind: DefineKind::Overwrite,
+				id: None,
+				name: Expr::Idiom(Idiom::field("a".to_string())),
+				comment: Expr::Literal(Literal::None),
+			}
+		)))))
+	)
+}
+
 #[test]
 fn parse_define_function() {
 	let res = syn::parse_with(
@@ -257,10 +316,49 @@ fn parse_define_function() {
 			returns: None,
 			graphql_alias: None,
 			graphql_deprecated: None,
+			capabilities: None,
 		})))
 	)
 }
 
+#[test]
+fn parse_define_function_capabilities() {
+	let res = syn::parse_with(
+		r#"DEFINE FUNCTION fn::charge() {
+			RETURN 1
+		} CAPABILITIES { net: ["api.stripe.com:443", '10.0.0.0/8'] }
+		"#
+		.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	let Expr::Define(stmt) = res else {
+		panic!("expected a define statement")
+	};
+	let DefineStatement::Function(stmt) = *stmt else {
+		panic!("expected a define function statement")
+	};
+	assert_eq!(
+		stmt.capabilities,
+		Some(FunctionCapabilities {
+			net: vec!["api.stripe.com:443".to_owned(), "10.0.0.0/8".to_owned()],
+		})
+	);
+
+	syn::parse_with(
+		r#"DEFINE FUNCTION fn::charge() { RETURN 1 } CAPABILITIES { net: ["not a target"] }"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+	syn::parse_with(
+		r#"DEFINE FUNCTION fn::charge() { RETURN 1 } CAPABILITIES { files: [] }"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
 #[test]
 fn parse_define_user() {
 	// Password.
@@ -1056,6 +1154,7 @@ fn parse_define_access_record() {
 						}),
 					},
 					bearer: None,
+					mfa: None,
 				})),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1093,6 +1192,7 @@ fn parse_define_access_record() {
 						}),
 					},
 					bearer: None,
+					mfa: None,
 				})),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1148,6 +1248,7 @@ fn parse_define_access_record() {
 							}),
 						},
 					}),
+					mfa: None,
 				})),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1202,6 +1303,7 @@ fn parse_define_access_record() {
 							}),
 						},
 					}),
+					mfa: None,
 				})),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1239,6 +1341,7 @@ fn parse_define_access_record() {
 						}),
 					},
 					bearer: None,
+					mfa: None,
 				})),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1554,6 +1657,37 @@ fn parse_define_param() {
 	);
 }
 
+#[test]
+fn parse_define_query() {
+	let res = syn::parse_with(
+		r#"DEFINE QUERY OVERWRITE recent_orders($user: record<user>, $limit: option<int>) AS $user COMMENT "orders" PERMISSIONS FULL"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Query(DefineQueryStatement {
+			kind: DefineKind::Overwrite,
+			name: Strand::new_static("recent_orders"),
+			args: vec![
+				("user".to_owned(), Kind::Record(vec!["user".into()])),
+				("limit".to_owned(), Kind::Either(vec![Kind::None, Kind::Int])),
+			],
+			query: Expr::Param(Param::new("user".to_owned())),
+			comment: Expr::Literal(Literal::String(Strand::new_static("orders"))),
+			permissions: Permission::Full,
+		})))
+	);
+
+	syn::parse_with(
+		r#"DEFINE QUERY recent_orders($user: record<user>) SELECT * FROM order"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
 #[test]
 fn parse_define_table() {
 	let res =
@@ -1568,6 +1702,7 @@ fn parse_define_table() {
 			drop: true,
 			full: true,
 			view: Some(crate::sql::View {
+				materialize: true,
 				expr: Fields::Select(vec![Field::Single(Selector {
 					expr: ident_field("foo"),
 					alias: None,
@@ -1592,6 +1727,12 @@ fn parse_define_table() {
 				expiry: PublicDuration::from_secs(1),
 				store_diff: true,
 			}),
+			throttle: None,
+			lineage: false,
+			soft_delete: None,
+			eviction: None,
+			session: false,
+			valid_time: None,
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
@@ -1602,158 +1743,571 @@ fn parse_define_table() {
 }
 
 #[test]
-fn parse_define_event() {
+fn parse_define_table_throttle() {
 	let res = syn::parse_with(
-		r#"DEFINE EVENT event ON TABLE table WHEN null THEN null,none ASYNC RETRY 5 MAXDEPTH 64"#
-			.as_bytes(),
+		r#"DEFINE TABLE logs SCHEMALESS THROTTLE 1000 writes/s BURST 5000"#.as_bytes(),
 		async |parser, stk| parser.parse_expr_inherit(stk).await,
 	)
 	.unwrap();
-
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a DEFINE TABLE statement");
+	};
 	assert_eq!(
-		res,
-		Expr::Define(Box::new(DefineStatement::Event(DefineEventStatement {
-			kind: DefineKind::Default,
-			name: Expr::Idiom(Idiom::field("event".to_string())),
-			target_table: Expr::Table("table".into()),
-			when: Expr::Literal(Literal::Null),
-			then: vec![Expr::Literal(Literal::Null), Expr::Literal(Literal::None)],
-			comment: Expr::Literal(Literal::None),
-			event_kind: EventKind::Async {
-				retry: 5,
-				max_depth: 64,
-			}
-		})))
+		stmt.throttle,
+		Some(Throttle {
+			rate: 1000,
+			burst: 5000,
+		})
+	);
+	// The burst defaults to the rate
+	let res = syn::parse_with(
+		r#"DEFINE TABLE logs THROTTLE 10 writes/s"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
 	)
+	.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a DEFINE TABLE statement");
+	};
+	assert_eq!(
+		stmt.throttle,
+		Some(Throttle {
+			rate: 10,
+			burst: 10,
+		})
+	);
+	// A throttle must allow writes
+	syn::parse_with(r#"DEFINE TABLE logs THROTTLE 0 writes/s"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+	syn::parse_with(r#"DEFINE TABLE logs THROTTLE 10 writes/m"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
 }
 
 #[test]
-fn parse_define_field() {
-	// General
-	{
-		let res = syn::parse_with(r#"DEFINE FIELD foo.*[*]... ON TABLE bar TYPE option<number | array<record<foo>,10>> VALUE null ASSERT true DEFAULT false PERMISSIONS FOR UPDATE NONE, FOR CREATE WHERE true"#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
-
-		assert_eq!(
-			res,
-			Expr::Define(Box::new(DefineStatement::Field(Box::new(DefineFieldStatement {
-				kind: DefineKind::Default,
-				name: Expr::Idiom(Idiom(vec![
-					Part::Field(Strand::new_static("foo")),
-					Part::All,
-					Part::All,
-					Part::Flatten,
-				])),
-				what: Expr::Table("bar".into()),
-				field_kind: Some(Kind::Either(vec![
-					Kind::None,
-					Kind::Number,
-					Kind::Array(Box::new(Kind::Record(vec!["foo".into()])), Some(10))
-				])),
-				flexible: false,
-				readonly: false,
-				value: Some(Expr::Literal(Literal::Null)),
-				assert: Some(Expr::Literal(Literal::Bool(true))),
-				default: DefineDefault::Set(Expr::Literal(Literal::Bool(false))),
-				permissions: Permissions {
-					delete: Permission::Full,
-					update: Permission::None,
-					create: Permission::Specific(Expr::Literal(Literal::Bool(true))),
-					select: Permission::Full,
-				},
-				comment: Expr::Literal(Literal::None),
-				reference: None,
-				computed: None,
-				graphql_alias: None,
-				graphql_deprecated: None,
-			}))))
-		)
-	}
-
-	// Invalid DELETE permission
-	{
-		syn::parse_with(
-			r#"DEFINE FIELD foo ON TABLE bar PERMISSIONS FOR DELETE NONE"#.as_bytes(),
-			async |parser, stk| parser.parse_expr_inherit(stk).await,
-		)
-		.unwrap_err();
-	}
-}
-
-#[test]
-fn parse_define_index() {
+fn parse_define_table_eviction() {
 	let res = syn::parse_with(
-		"DEFINE INDEX index ON TABLE table FIELDS a FULLTEXT ANALYZER ana BM25 (0.1,0.2) HIGHLIGHTS"
-		.as_bytes(),
+		r#"DEFINE TABLE cache SCHEMALESS EVICT LRU AFTER 1h"#.as_bytes(),
 		async |parser, stk| parser.parse_expr_inherit(stk).await,
 	)
 	.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a DEFINE TABLE statement");
+	};
 	assert_eq!(
-		res,
-		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
-			kind: DefineKind::Default,
-			name: Expr::Idiom(Idiom::field("index".to_string())),
-			what: Expr::Table("table".into()),
-			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))])),],
-			index: Index::FullText(FullTextParams {
-				az: "ana".into(),
-				hl: true,
-				sc: Scoring::Bm {
-					k1: 0.1,
-					b: 0.2
-				},
-			}),
-			comment: Expr::Literal(Literal::None),
-			concurrently: false
-		})))
+		stmt.eviction,
+		Some(Eviction {
+			lru: true,
+			ttl: Some(PublicDuration::from_secs(3600)),
+		})
+	);
+	// Records can expire without the table being evicted from under memory
+	// pressure
+	let res =
+		syn::parse_with(r#"DEFINE TABLE cache EVICT AFTER 10m"#.as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a DEFINE TABLE statement");
+	};
+	assert_eq!(
+		stmt.eviction,
+		Some(Eviction {
+			lru: false,
+			ttl: Some(PublicDuration::from_secs(600)),
+		})
 	);
+	// An eviction policy must evict something
+	syn::parse_with(r#"DEFINE TABLE cache EVICT"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+}
+
+#[test]
+fn parse_define_view() {
+	use surrealdb_types::ToSql;
 
 	let res = syn::parse_with(
-		r#"DEFINE INDEX index ON TABLE table FIELDS a UNIQUE"#.as_bytes(),
+		r#"DEFINE VIEW IF NOT EXISTS active AS SELECT * FROM user WHERE active COMMENT 'a'"#
+			.as_bytes(),
 		async |parser, stk| parser.parse_expr_inherit(stk).await,
 	)
 	.unwrap();
-
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a DEFINE TABLE statement");
+	};
+	assert_eq!(stmt.kind, DefineKind::IfNotExists);
+	assert_eq!(stmt.name, Expr::Table("active".into()));
 	assert_eq!(
-		res,
-		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
-			kind: DefineKind::Default,
-			name: Expr::Idiom(Idiom::field("index".to_string())),
-			what: Expr::Table("table".into()),
-			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
-			index: Index::Uniq,
-			comment: Expr::Literal(Literal::None),
-			concurrently: false
-		})))
+		stmt.view,
+		Some(crate::sql::View {
+			materialize: false,
+			expr: Fields::all(),
+			what: vec!["user".into()],
+			cond: Some(Cond(ident_field("active"))),
+			group: None,
+		})
 	);
-
-	let res =
-		syn::parse_with( r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 128 EFC 250 TYPE F32 DISTANCE MANHATTAN M 6 M0 12 LM 0.5 EXTEND_CANDIDATES KEEP_PRUNED_CONNECTIONS HASHED_VECTOR"#.as_bytes(),async |parser,stk| parser.parse_expr_inherit(stk).await).unwrap();
+	assert_eq!(stmt.permissions, Permissions::full());
+	assert_eq!(stmt.comment, Expr::Literal(Literal::String(Strand::new_static("a"))));
 	assert_eq!(
-		res,
-		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
-			kind: DefineKind::Default,
-			name: Expr::Idiom(Idiom::field("index".to_string())),
-			what: Expr::Table("table".into()),
-			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
-			index: Index::Hnsw(HnswParams {
-				dimension: 128,
-				distance: Distance::Manhattan,
-				vector_type: VectorType::F32,
-				m: 6,
-				m0: 12,
-				ef_construction: 250,
-				extend_candidates: true,
-				keep_pruned_connections: true,
-				ml: 0.5.into(),
-				use_hashed_vector: true,
-			}),
-			comment: Expr::Literal(Literal::None),
-			concurrently: false
-		})))
+		stmt.to_sql(),
+		"DEFINE VIEW IF NOT EXISTS active AS SELECT * FROM user WHERE active COMMENT 'a'"
 	);
+	// A view is defined by a query
+	syn::parse_with(r#"DEFINE VIEW active"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+}
 
+#[test]
+fn parse_define_table_lineage() {
 	let res = syn::parse_with(
-		r#"DEFINE INDEX index ON TABLE table FIELDS a DISKANN DIMENSION 128"#.as_bytes(),
+		r#"DEFINE TABLE account SCHEMAFULL LINEAGE"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a DEFINE TABLE statement");
+	};
+	assert!(stmt.lineage);
+	assert!(stmt.full);
+}
+
+#[test]
+fn parse_define_table_soft_delete() {
+	let res = syn::parse_with(
+		r#"DEFINE TABLE person SOFT DELETE FIELD deleted_at PURGE AFTER 30d"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a DEFINE TABLE statement");
+	};
+	assert_eq!(
+		stmt.soft_delete,
+		Some(SoftDelete {
+			field: "deleted_at".to_string(),
+			retention: Some(PublicDuration::from_days(30).unwrap()),
+		})
+	);
+	// Deleted records are kept forever without a retention period
+	let res = syn::parse_with(
+		r#"DEFINE TABLE person SOFT DELETE FIELD deleted_at"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a DEFINE TABLE statement");
+	};
+	assert_eq!(
+		stmt.soft_delete,
+		Some(SoftDelete {
+			field: "deleted_at".to_string(),
+			retention: None,
+		})
+	);
+	// Soft deleted records are included with WITH DELETED
+	let res =
+		syn::parse_with(r#"SELECT * FROM person WITH DELETED"#.as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	let Expr::Select(stmt) = res else {
+		panic!("expected a SELECT statement");
+	};
+	assert_eq!(stmt.with, Some(With::Deleted));
+	let res = syn::parse_with(r#"DELETE person WITH DELETED"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	let Expr::Delete(stmt) = res else {
+		panic!("expected a DELETE statement");
+	};
+	assert_eq!(stmt.with, Some(With::Deleted));
+}
+
+#[test]
+fn parse_define_table_valid_time() {
+	let res = syn::parse_with(
+		r#"DEFINE TABLE policy BITEMPORAL VALID FROM valid_from TO valid_to"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a DEFINE statement");
+	};
+	let DefineStatement::Table(stmt) = *stmt else {
+		panic!("expected a DEFINE TABLE statement");
+	};
+	assert_eq!(
+		stmt.valid_time,
+		Some(ValidTime {
+			from: "valid_from".to_string(),
+			to: "valid_to".to_string(),
+		})
+	);
+	// The valid time must start and end in different fields
+	syn::parse_with(
+		r#"DEFINE TABLE policy BITEMPORAL VALID FROM valid TO valid"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+	let res = syn::parse_with(
+		r#"SELECT * FROM policy FOR VALID TIME AS OF d'2024-01-01T00:00:00Z' WHERE active"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::Select(stmt) = res else {
+		panic!("expected a SELECT statement");
+	};
+	assert!(matches!(stmt.valid_time, Some(Expr::Literal(Literal::Datetime(_)))));
+	assert!(stmt.cond.is_some());
+}
+
+#[test]
+fn parse_select_scan_limit() {
+	use surrealdb_types::ToSql;
+
+	let res = syn::parse_with(
+		r#"SELECT * FROM person WHERE age > 18 TIMEOUT 5s SCAN LIMIT 100000"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::Select(stmt) = res else {
+		panic!("expected a SELECT statement");
+	};
+	assert_eq!(stmt.scan_limit, Some(100_000));
+	assert_eq!(stmt.to_sql(), "SELECT * FROM person WHERE age > 18 TIMEOUT 5s SCAN LIMIT 100000");
+	// A scan limit must allow at least one row
+	syn::parse_with(r#"SELECT * FROM person SCAN LIMIT 0"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap_err();
+}
+
+#[test]
+fn parse_select_timeout_partial() {
+	use surrealdb_types::ToSql;
+
+	let res = syn::parse_with(
+		r#"SELECT * FROM event TIMEOUT 2s RETURN PARTIAL SCAN LIMIT 10"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::Select(stmt) = res else {
+		panic!("expected a SELECT statement");
+	};
+	assert!(stmt.partial);
+	assert_eq!(stmt.to_sql(), "SELECT * FROM event TIMEOUT 2s RETURN PARTIAL SCAN LIMIT 10");
+	// Partial results are only returned once a timeout expires
+	syn::parse("SELECT * FROM event RETURN PARTIAL").unwrap_err();
+}
+
+#[test]
+fn parse_define_event() {
+	let res = syn::parse_with(
+		r#"DEFINE EVENT event ON TABLE table WHEN null THEN null,none ASYNC RETRY 5 MAXDEPTH 64"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Event(DefineEventStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("event".to_string())),
+			target_table: Expr::Table("table".into()),
+			when: Expr::Literal(Literal::Null),
+			then: vec![Expr::Literal(Literal::Null), Expr::Literal(Literal::None)],
+			comment: Expr::Literal(Literal::None),
+			event_kind: EventKind::Async {
+				retry: 5,
+				max_depth: 64,
+			}
+		})))
+	)
+}
+
+#[test]
+fn parse_define_event_outbox() {
+	let res = syn::parse_with(
+		r#"DEFINE EVENT event ON TABLE table OUTBOX RETRY 3 THEN http::post('https://example.com')"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	let Expr::Define(define) = res else {
+		panic!("Expected a DEFINE statement");
+	};
+	let DefineStatement::Event(event) = *define else {
+		panic!("Expected a DEFINE EVENT statement");
+	};
+	assert_eq!(
+		event.event_kind,
+		EventKind::Outbox {
+			retry: 3,
+		}
+	);
+
+	syn::parse_with(
+		r#"DEFINE EVENT event ON TABLE table RETRY 3 THEN null"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_define_policy() {
+	let res = syn::parse_with(
+		r#"DEFINE POLICY IF NOT EXISTS archive ON TABLE event WHEN true MOVE TO event_archive BATCH 500 COMMENT "cold""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Policy(DefinePolicyStatement {
+			kind: DefineKind::IfNotExists,
+			name: Expr::Idiom(Idiom::field("archive".to_string())),
+			target_table: Expr::Table("event".into()),
+			when: Expr::Literal(Literal::Bool(true)),
+			destination: Expr::Table("event_archive".into()),
+			batch: Expr::Literal(Literal::Integer(500)),
+			comment: Expr::Literal(Literal::String(Strand::new_static("cold"))),
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE POLICY archive ON event WHEN true MOVE TO TABLE event_archive"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Policy(DefinePolicyStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("archive".to_string())),
+			target_table: Expr::Table("event".into()),
+			when: Expr::Literal(Literal::Bool(true)),
+			destination: Expr::Table("event_archive".into()),
+			batch: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		})))
+	);
+
+	syn::parse_with(
+		r#"DEFINE POLICY archive ON event MOVE TO event_archive"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_define_field() {
+	// General
+	{
+		let res = syn::parse_with(r#"DEFINE FIELD foo.*[*]... ON TABLE bar TYPE option<number | array<record<foo>,10>> VALUE null ASSERT true DEFAULT false PERMISSIONS FOR UPDATE NONE, FOR CREATE WHERE true"#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
+
+		assert_eq!(
+			res,
+			Expr::Define(Box::new(DefineStatement::Field(Box::new(DefineFieldStatement {
+				kind: DefineKind::Default,
+				name: Expr::Idiom(Idiom(vec![
+					Part::Field(Strand::new_static("foo")),
+					Part::All,
+					Part::All,
+					Part::Flatten,
+				])),
+				what: Expr::Table("bar".into()),
+				field_kind: Some(Kind::Either(vec![
+					Kind::None,
+					Kind::Number,
+					Kind::Array(Box::new(Kind::Record(vec!["foo".into()])), Some(10))
+				])),
+				flexible: false,
+				readonly: false,
+				value: Some(Expr::Literal(Literal::Null)),
+				assert: Some(Expr::Literal(Literal::Bool(true))),
+				default: DefineDefault::Set(Expr::Literal(Literal::Bool(false))),
+				permissions: Permissions {
+					delete: Permission::Full,
+					update: Permission::None,
+					create: Permission::Specific(Expr::Literal(Literal::Bool(true))),
+					select: Permission::Full,
+				},
+				comment: Expr::Literal(Literal::None),
+				reference: None,
+				computed: None,
+				graphql_alias: None,
+				graphql_deprecated: None,
+			}))))
+		)
+	}
+
+	// Invalid DELETE permission
+	{
+		syn::parse_with(
+			r#"DEFINE FIELD foo ON TABLE bar PERMISSIONS FOR DELETE NONE"#.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap_err();
+	}
+}
+
+#[test]
+fn parse_define_index() {
+	let res = syn::parse_with(
+		"DEFINE INDEX index ON TABLE table FIELDS a FULLTEXT ANALYZER ana BM25 (0.1,0.2) HIGHLIGHTS"
+		.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".into()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))])),],
+			index: Index::FullText(FullTextParams {
+				az: "ana".into(),
+				hl: true,
+				sc: Scoring::Bm {
+					k1: 0.1,
+					b: 0.2
+				},
+			}),
+			comment: Expr::Literal(Literal::None),
+			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a UNIQUE"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".into()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
+			index: Index::Uniq,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a UNIQUE VALIDATE"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".into()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
+			index: Index::Uniq,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false,
+			conflicts: UniqueConflicts::Validate,
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a UNIQUE QUARANTINE table_conflicts"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".into()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
+			index: Index::Uniq,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false,
+			conflicts: UniqueConflicts::Quarantine(Expr::Table("table_conflicts".into())),
+		})))
+	);
+
+	let res =
+		syn::parse_with( r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 128 EFC 250 TYPE F32 DISTANCE MANHATTAN M 6 M0 12 LM 0.5 EXTEND_CANDIDATES KEEP_PRUNED_CONNECTIONS HASHED_VECTOR"#.as_bytes(),async |parser,stk| parser.parse_expr_inherit(stk).await).unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".into()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
+			index: Index::Hnsw(HnswParams {
+				dimension: 128,
+				distance: Distance::Manhattan,
+				vector_type: VectorType::F32,
+				m: 6,
+				m0: 12,
+				ef_construction: 250,
+				extend_candidates: true,
+				keep_pruned_connections: true,
+				ml: 0.5.into(),
+				use_hashed_vector: true,
+			}),
+			comment: Expr::Literal(Literal::None),
+			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a DISKANN DIMENSION 128"#.as_bytes(),
 		async |parser, stk| parser.parse_expr_inherit(stk).await,
 	)
 	.unwrap();
@@ -1774,7 +2328,8 @@ fn parse_define_index() {
 				use_hashed_vector: false,
 			}),
 			comment: Expr::Literal(Literal::None),
-			concurrently: false
+			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
 		})))
 	);
 
@@ -1800,7 +2355,8 @@ fn parse_define_index() {
 				use_hashed_vector: true,
 			}),
 			comment: Expr::Literal(Literal::None),
-			concurrently: false
+			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
 		})))
 	);
 
@@ -1826,7 +2382,8 @@ fn parse_define_index() {
 				use_hashed_vector: false,
 			}),
 			comment: Expr::Literal(Literal::None),
-			concurrently: false
+			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
 		})))
 	);
 
@@ -1852,7 +2409,8 @@ fn parse_define_index() {
 				use_hashed_vector: false,
 			}),
 			comment: Expr::Literal(Literal::None),
-			concurrently: false
+			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
 		})))
 	);
 
@@ -2054,8 +2612,11 @@ pub fn parse_for() {
 					fetch: None,
 					version: Expr::Literal(Literal::None),
 					timeout: Expr::Literal(Literal::None),
+					partial: false,
 					explain: None,
-					tempfiles: false
+					tempfiles: false,
+					valid_time: None,
+					scan_limit: None
 				}))),
 				op: BinaryOperator::Multiply,
 				right: Box::new(Expr::Literal(Literal::Integer(2)))
@@ -2156,6 +2717,19 @@ fn parse_info() {
 		Expr::Info(Box::new(InfoStatement::Tb(Expr::Table("table".into()), false, None)))
 	);
 
+	let res =
+		syn::parse_with("INFO FOR TABLE table INDEX STATS".as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	assert_eq!(res, Expr::Info(Box::new(InfoStatement::IndexStats(Expr::Table("table".into())))));
+
+	let res = syn::parse_with("INFO FOR LOCKS".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, Expr::Info(Box::new(InfoStatement::Locks)));
+
 	let res = syn::parse_with("INFO FOR USER user".as_bytes(), async |parser, stk| {
 		parser.parse_expr_inherit(stk).await
 	})
@@ -2183,6 +2757,102 @@ fn parse_info() {
 	);
 }
 
+#[test]
+fn parse_select_with_cte() {
+	let res = syn::parse_with(
+		r#"WITH recent AS (SELECT * FROM person), top AS (SELECT * FROM recent) SELECT * FROM top, person"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let Expr::With(with) = res else {
+		panic!("expected a WITH statement");
+	};
+	let [recent, top] = with.exprs.as_slice() else {
+		panic!("expected two common table expressions");
+	};
+	assert_eq!(recent.name, "recent");
+	assert_eq!(top.name, "top");
+	let Expr::Select(ref inner) = top.what else {
+		panic!("expected a SELECT statement");
+	};
+	assert_eq!(inner.what, vec![Expr::Table("recent".into())]);
+	assert_eq!(with.stmt.what, vec![Expr::Table("top".into()), Expr::Table("person".into())]);
+	// Each expression must have a unique name
+	syn::parse_with(
+		r#"WITH a AS (1), a AS (2) SELECT * FROM a"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_select_with_cte_round_trips() {
+	use surrealdb_types::ToSql;
+
+	let sql = "WITH recent AS (SELECT * FROM person), top AS (SELECT * FROM recent LIMIT 1) SELECT * FROM top";
+	let res =
+		syn::parse_with(sql.as_bytes(), async |parser, stk| parser.parse_expr_inherit(stk).await)
+			.unwrap();
+	assert_eq!(res.to_sql(), sql);
+	// The statement is printed back as written once it is stored
+	let stored: crate::expr::Expr = res.into();
+	assert_eq!(stored.to_sql(), sql);
+}
+
+#[test]
+fn parse_select_with_cte_named_after_protected_param() {
+	// Expressions named after protected parameters do not bind those parameters
+	let res = syn::parse_with(
+		r#"WITH auth AS (SELECT * FROM person) SELECT * FROM auth WHERE owner = $auth"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	let crate::expr::Expr::With(with) = crate::expr::Expr::from(res) else {
+		panic!("expected a WITH statement");
+	};
+	let block = with.into_block();
+	let [crate::expr::Expr::Let(auth), crate::expr::Expr::Select(select)] = block.0.as_slice()
+	else {
+		panic!("expected a LET statement followed by a SELECT statement");
+	};
+	assert_eq!(auth.name.as_str(), "cte::auth");
+	assert_eq!(select.what, vec![crate::expr::Expr::Param("cte::auth".to_owned().into())]);
+	// The parameter of the same name still refers to the session
+	let Some(crate::expr::Expr::Binary {
+		right,
+		..
+	}) = select.cond.as_ref().map(|cond| &cond.0)
+	else {
+		panic!("expected a binary condition");
+	};
+	assert_eq!(**right, crate::expr::Expr::Param("auth".to_owned().into()));
+}
+
+#[test]
+fn parse_select_with_cte_does_not_bind_param() {
+	// The expressions are only in scope within their statement, and never
+	// replace the parameter of the same name used by the rest of the query
+	let res = syn::parse_with(
+		r#"LET $recent = 1; WITH recent AS (SELECT * FROM person) SELECT * FROM recent; RETURN $recent;"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_query(stk).await,
+	)
+	.unwrap();
+	let [_, TopLevelExpr::Expr(Expr::With(with)), TopLevelExpr::Expr(Expr::Return(output))] =
+		res.expressions.as_slice()
+	else {
+		panic!("expected a LET, a WITH and a RETURN statement");
+	};
+	let block = crate::expr::statements::WithStatement::from((**with).clone()).into_block();
+	assert!(
+		block.0.iter().all(
+			|expr| !matches!(expr, crate::expr::Expr::Let(set) if set.name.as_str() == "recent")
+		)
+	);
+	assert_eq!(output.what, Expr::Param(Param::new("recent")));
+}
+
 #[test]
 fn parse_show() {
 	let res = syn::parse_with(
@@ -2383,6 +3053,7 @@ fn parse_insert() {
 			output: Some(Output::After),
 			timeout: Expr::Literal(Literal::None),
 			relation: false,
+			non_atomic: false,
 		})),
 	)
 }
@@ -2416,14 +3087,45 @@ fn parse_insert_select() {
 				fetch: None,
 				version: Expr::Literal(Literal::None),
 				timeout: Expr::Literal(Literal::None),
+				partial: false,
 				explain: None,
-				tempfiles: false
+				tempfiles: false,
+				valid_time: None,
+				scan_limit: None
 			}))),
 			ignore: true,
 			update: None,
 			output: None,
 			timeout: Expr::Literal(Literal::None),
 			relation: false,
+			non_atomic: false,
+		})),
+	)
+}
+
+#[test]
+fn parse_insert_non_atomic() {
+	let res = syn::parse_with(
+		r#"INSERT INTO foo [{ a: 1 }] NON ATOMIC RETURN NONE"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Insert(Box::new(InsertStatement {
+			into: Some(Expr::Table("foo".into())),
+			data: Data::SingleExpression(Expr::Literal(Literal::Array(vec![Expr::Literal(
+				Literal::Object(vec![ObjectEntry {
+					key: "a".into(),
+					value: Expr::Literal(Literal::Integer(1)),
+				}])
+			)]))),
+			ignore: false,
+			update: None,
+			output: Some(Output::None),
+			timeout: Expr::Literal(Literal::None),
+			relation: false,
+			non_atomic: true,
 		})),
 	)
 }
@@ -2503,6 +3205,68 @@ fn parse_live() {
 	)
 }
 
+#[test]
+fn parse_live_schema() {
+	let res = syn::parse_with(r#"LIVE SCHEMA"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, TopLevelExpr::LiveSchema(LiveSchemaStatement));
+}
+
+#[test]
+fn parse_lock() {
+	let res = syn::parse_with(
+		r#"LOCK TABLE inventory IN EXCLUSIVE MODE TIMEOUT 5s"#.as_bytes(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Lock(LockStatement {
+			what: "inventory".into(),
+			timeout: Some(PublicDuration::from_secs(5)),
+		})
+	);
+
+	let res = syn::parse_with(
+		r#"lock table inventory in exclusive mode"#.as_bytes(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Lock(LockStatement {
+			what: "inventory".into(),
+			timeout: None,
+		})
+	);
+
+	syn::parse_with(r#"LOCK TABLE inventory IN SHARED MODE"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap_err();
+}
+
+#[test]
+fn parse_refresh() {
+	let res = syn::parse_with(r#"REFRESH VIEW person_by_age"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Refresh(RefreshStatement {
+			what: "person_by_age".into(),
+		})
+	);
+
+	syn::parse_with(r#"REFRESH VIEW"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap_err();
+}
+
 #[test]
 fn parse_option() {
 	let res = syn::parse_with(r#"OPTION value = true"#.as_bytes(), async |parser, stk| {
@@ -2679,6 +3443,18 @@ fn parse_remove() {
 		})))
 	);
 
+	let res = syn::parse_with(r#"REMOVE QUERY IF EXISTS foo"#.as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Query(RemoveQueryStatement {
+			name: Strand::new_static("foo"),
+			if_exists: true,
+		})))
+	);
+
 	let res = syn::parse_with(r#"REMOVE TABLE foo"#.as_bytes(), async |parser, stk| {
 		parser.parse_expr_inherit(stk).await
 	})
@@ -2706,6 +3482,20 @@ fn parse_remove() {
 		})))
 	);
 
+	let res = syn::parse_with(
+		r#"REMOVE POLICY IF EXISTS foo ON TABLE bar"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Policy(RemovePolicyStatement {
+			name: Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("foo"))])),
+			what: Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("bar"))])),
+			if_exists: true,
+		})))
+	);
+
 	let res =
 		syn::parse_with(r#"REMOVE FIELD foo.bar[10] ON bar"#.as_bytes(), async |parser, stk| {
 			parser.parse_expr_inherit(stk).await
@@ -2912,6 +3702,7 @@ fn parse_access_grant() {
 				ac: "a".into(),
 				base: Some(Base::Ns),
 				subject: access::Subject::User("b".into()),
+				totp: false,
 			})))
 		);
 	}
@@ -2932,9 +3723,108 @@ fn parse_access_grant() {
 					table: "b".into(),
 					key: RecordIdKeyLit::String("c".into()),
 				}),
+				totp: false,
+			})))
+		);
+	}
+}
+
+#[test]
+fn parse_access_grant_totp() {
+	let res = syn::parse_with_settings(
+		r#"ACCESS a GRANT TOTP FOR RECORD b:c"#.as_bytes(),
+		ParserSettings::default(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Access(Box::new(AccessStatement::Grant(AccessStatementGrant {
+			ac: "a".into(),
+			base: None,
+			subject: access::Subject::Record(RecordIdLit {
+				table: "b".into(),
+				key: RecordIdKeyLit::String("c".into()),
+			}),
+			totp: true,
+		})))
+	);
+	// Records are enrolled one at a time
+	syn::parse_with_settings(
+		r#"ACCESS a GRANT TOTP FOR RECORD IN [b:c]"#.as_bytes(),
+		ParserSettings::default(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_access_grant_many() {
+	// Users
+	{
+		let res = syn::parse_with_settings(
+			r#"ACCESS a ON DATABASE GRANT FOR USER IN [b, c]"#.as_bytes(),
+			ParserSettings::default(),
+			async |parser, stk| parser.parse_top_level_expr(stk).await,
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			TopLevelExpr::Access(Box::new(AccessStatement::GrantMany(AccessStatementGrantMany {
+				ac: "a".into(),
+				base: Some(Base::Db),
+				subjects: vec![
+					access::Subject::User("b".into()),
+					access::Subject::User("c".into())
+				],
+			})))
+		);
+	}
+	// Records
+	{
+		let res = syn::parse_with_settings(
+			r#"ACCESS a GRANT FOR RECORD IN [b:c,]"#.as_bytes(),
+			ParserSettings::default(),
+			async |parser, stk| parser.parse_top_level_expr(stk).await,
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			TopLevelExpr::Access(Box::new(AccessStatement::GrantMany(AccessStatementGrantMany {
+				ac: "a".into(),
+				base: None,
+				subjects: vec![access::Subject::Record(RecordIdLit {
+					table: "b".into(),
+					key: RecordIdKeyLit::String("c".into()),
+				})],
+			})))
+		);
+	}
+	// A user named `in` is still granted access on its own
+	{
+		let res = syn::parse_with_settings(
+			r#"ACCESS a GRANT FOR USER in"#.as_bytes(),
+			ParserSettings::default(),
+			async |parser, stk| parser.parse_top_level_expr(stk).await,
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			TopLevelExpr::Access(Box::new(AccessStatement::Grant(AccessStatementGrant {
+				ac: "a".into(),
+				base: None,
+				subject: access::Subject::User("in".into()),
+				totp: false,
 			})))
 		);
 	}
+	// The list can not be empty
+	syn::parse_with_settings(
+		r#"ACCESS a GRANT FOR USER IN []"#.as_bytes(),
+		ParserSettings::default(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
+	)
+	.unwrap_err();
 }
 
 #[test]
@@ -2995,6 +3885,45 @@ fn parse_access_show() {
 	}
 }
 
+#[test]
+fn parse_access_show_subject() {
+	// User
+	{
+		let res = syn::parse_with_settings(
+			r#"ACCESS SHOW ALL FOR USER tobie"#.as_bytes(),
+			ParserSettings::default(),
+			async |parser, stk| parser.parse_top_level_expr(stk).await,
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			TopLevelExpr::Access(Box::new(AccessStatement::ShowSubject(
+				AccessStatementShowSubject {
+					subject: access::Subject::User("tobie".into()),
+				}
+			)))
+		);
+	}
+	// An access method named `show`
+	{
+		let res = syn::parse_with_settings(
+			r#"ACCESS show SHOW ALL"#.as_bytes(),
+			ParserSettings::default(),
+			async |parser, stk| parser.parse_top_level_expr(stk).await,
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			TopLevelExpr::Access(Box::new(AccessStatement::Show(AccessStatementShow {
+				ac: "show".into(),
+				base: None,
+				gr: None,
+				cond: None,
+			})))
+		);
+	}
+}
+
 #[test]
 fn parse_access_revoke() {
 	// All
@@ -21,7 +21,7 @@ use crate::sql::statements::define::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement, DefineDefault,
 	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
 	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement,
+	DefineTableStatement, UniqueConflicts,
 };
 use crate::sql::statements::show::{ShowSince, ShowStatement};
 use crate::sql::statements::sleep::SleepStatement;
@@ -183,6 +183,7 @@ fn statements() -> Vec<TopLevelExpr> {
 					expiry: PublicDuration::from_secs(60 * 10),
 					store_diff: false,
 				}),
+				capabilities: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Database(
@@ -193,6 +194,7 @@ fn statements() -> Vec<TopLevelExpr> {
 				strict: false,
 				comment: Expr::Literal(Literal::None),
 				changefeed: None,
+				capabilities: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Function(
@@ -212,6 +214,7 @@ fn statements() -> Vec<TopLevelExpr> {
 				returns: None,
 				graphql_alias: None,
 				graphql_deprecated: None,
+				capabilities: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Access(
@@ -230,6 +233,7 @@ fn statements() -> Vec<TopLevelExpr> {
 						issue: None,
 					},
 					bearer: None,
+					mfa: None,
 				})),
 				authenticate: None,
 				// Default durations.
@@ -264,6 +268,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			drop: true,
 			full: true,
 			view: Some(crate::sql::View {
+				materialize: true,
 				expr: Fields::Select(vec![Field::Single(Selector {
 					expr: ident_field("foo"),
 					alias: None,
@@ -288,6 +293,12 @@ fn statements() -> Vec<TopLevelExpr> {
 				expiry: PublicDuration::from_secs(1),
 				store_diff: false,
 			}),
+			throttle: None,
+			lineage: false,
+			soft_delete: None,
+			eviction: None,
+			session: false,
+			valid_time: None,
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
@@ -354,6 +365,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			}),
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
 		})))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
 			kind: DefineKind::Default,
@@ -363,6 +375,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			index: Index::Uniq,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
+			conflicts: UniqueConflicts::Fail,
 		})))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Analyzer(
 			DefineAnalyzerStatement {
@@ -436,8 +449,11 @@ fn statements() -> Vec<TopLevelExpr> {
 					fetch: None,
 					version: Expr::Literal(Literal::None),
 					timeout: Expr::Literal(Literal::None),
+					partial: false,
 					explain: None,
 					tempfiles: false,
+					valid_time: None,
+					scan_limit: None,
 				}))),
 				op: BinaryOperator::Multiply,
 				right: Box::new(Expr::Literal(Literal::Integer(2))),
@@ -510,7 +526,10 @@ fn statements() -> Vec<TopLevelExpr> {
 			fetch: Some(Fetchs(vec![Fetch(ident_field("foo"))])),
 			version: Expr::Literal(Literal::Datetime(PublicDatetime::from(expected_datetime))),
 			timeout: Expr::Literal(Literal::None),
+			partial: false,
 			tempfiles: false,
+			valid_time: None,
+			scan_limit: None,
 			explain: Some(Explain(true)),
 		}))),
 		TopLevelExpr::Expr(Expr::Select(Box::new(SelectStatement {
@@ -540,7 +559,10 @@ fn statements() -> Vec<TopLevelExpr> {
 			fetch: None,
 			version: Expr::Literal(Literal::None),
 			timeout: Expr::Literal(Literal::None),
+			partial: false,
 			tempfiles: false,
+			valid_time: None,
+			scan_limit: None,
 			explain: None,
 		}))),
 		TopLevelExpr::Expr(Expr::Let(Box::new(SetStatement {
@@ -618,6 +640,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			output: Some(Output::After),
 			timeout: Expr::Literal(Literal::None),
 			relation: false,
+			non_atomic: false,
 		}))),
 		TopLevelExpr::Kill(KillStatement {
 			id: Expr::Literal(Literal::Uuid(PublicUuid::from(uuid::uuid!(
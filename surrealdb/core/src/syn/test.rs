@@ -61,3 +61,11 @@ fn empty_expr() {
 fn empty_json() {
 	super::json("").unwrap_err();
 }
+
+#[test]
+fn conditions_are_single_expressions() {
+	assert_eq!(super::condition("room = $room").unwrap(), "room = $room");
+	super::condition("").unwrap_err();
+	super::condition("true) OR (true").unwrap_err();
+	super::condition("true; REMOVE TABLE person").unwrap_err();
+}
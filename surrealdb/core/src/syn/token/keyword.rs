@@ -38,6 +38,7 @@ keyword! {
 	Assert => "ASSERT",
 	Async => "ASYNC",
 	At => "AT",
+	Atomic => "ATOMIC",
 	Authenticate => "AUTHENTICATE",
 	Auto => "AUTO",
 	Backend => "BACKEND",
@@ -47,6 +48,7 @@ keyword! {
 	Begin => "BEGIN",
 	Blank => "BLANK",
 	Bucket => "BUCKET",
+	Burst => "BURST",
 	Reject => "REJECT",
 	Bm25 => "BM25",
 	Break => "BREAK",
@@ -56,6 +58,7 @@ keyword! {
 	Cascade => "CASCADE",
 	ChangeFeed => "CHANGEFEED",
 	Changes => "CHANGES",
+	Capabilities => "CAPABILITIES",
 	Capacity => "CAPACITY",
 	Class => "CLASS",
 	Comment => "COMMENT",
@@ -72,6 +75,7 @@ keyword! {
 	Default => "DEFAULT",
 	Define => "DEFINE",
 	Delete => "DELETE",
+	Deleted => "DELETED",
 	Descending => "DESCENDING",
 	Diff => "DIFF",
 	Dimension => "DIMENSION",
@@ -81,6 +85,7 @@ keyword! {
 	Efc => "EFC",
 	Edgengram => "EDGENGRAM",
 	Event => "EVENT",
+	Evict => "EVICT",
 	Else => "ELSE",
 	End => "END",
 	Enforced => "ENFORCED",
@@ -127,7 +132,9 @@ keyword! {
 	Kill => "KILL",
 	Let => "LET",
 	Limit => "LIMIT",
+	Lineage => "LINEAGE",
 	Live => "LIVE",
+	Locks => "LOCKS",
 	Lowercase => "LOWERCASE",
 	Lm => "LM",
 	M => "M",
@@ -138,10 +145,12 @@ keyword! {
 	Merge => "MERGE",
 	Model => "MODEL",
 	Module => "MODULE",
+	Move => "MOVE",
 	Namespace => "NAMESPACE",
 	Ngram => "NGRAM",
 	No => "NO",
 	NoIndex => "NOINDEX",
+	Non => "NON",
 	None => "NONE",
 	Null => "NULL",
 	Numeric => "NUMERIC",
@@ -151,6 +160,7 @@ keyword! {
 	Option => "OPTION",
 	Order => "ORDER",
 	Original => "ORIGINAL",
+	Outbox => "OUTBOX",
 	Overwrite => "OVERWRITE",
 	Parallel => "PARALLEL",
 	Param => "PARAM",
@@ -158,11 +168,14 @@ keyword! {
 	Password => "PASSWORD",
 	Patch => "PATCH",
 	Permissions => "PERMISSIONS",
+	Policy => "POLICY",
 	PostingsCache => "POSTINGS_CACHE",
 	PostingsOrder => "POSTINGS_ORDER",
 	Prepare => "PREPARE",
 	Punct => "PUNCT",
 	Purge => "PURGE",
+	Quarantine => "QUARANTINE",
+	Query => "QUERY",
 	Range => "RANGE",
 	Readonly => "READONLY",
 	Rebuild => "REBUILD",
@@ -192,8 +205,10 @@ keyword! {
 	Since => "SINCE",
 	Sleep => "SLEEP",
 	Snowball => "SNOWBALL",
+	Soft => "SOFT",
 	Split => "SPLIT",
 	Start => "START",
+	Stats => "STATS",
 	Strict => "STRICT",
 	Structure => "STRUCTURE",
 	System => "SYSTEM",
@@ -203,6 +218,7 @@ keyword! {
 	TermsCache => "TERMS_CACHE",
 	TermsOrder => "TERMS_ORDER",
 	Then => "THEN",
+	Throttle => "THROTTLE",
 	Throw => "THROW",
 	Timeout => "TIMEOUT",
 	Tokenizers => "TOKENIZERS",
@@ -219,9 +235,11 @@ keyword! {
 	Url => "URL",
 	Use => "USE",
 	User => "USER",
+	Validate => "VALIDATE",
 	Value => "VALUE",
 	Values => "VALUES",
 	Version => "VERSION",
+	View => "VIEW",
 	Vs => "VS",
 	When => "WHEN",
 	Where => "WHERE",
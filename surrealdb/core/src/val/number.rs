@@ -698,6 +698,32 @@ impl Number {
 		}
 	}
 
+	/// Rounds to a number of digits after the decimal point
+	///
+	/// Without a strategy, floats are rounded half away from zero and
+	/// decimals half to even, as with [`Number::round`]. Floats are rounded
+	/// as written, so that `2.675` rounds to `2.68` rather than rounding its
+	/// binary approximation down.
+	pub fn round_dp(self, dp: u32, strategy: Option<RoundingStrategy>) -> Number {
+		match self {
+			Number::Int(v) => v.into(),
+			// The shortest decimal which converts back to the same float
+			Number::Float(v) => match Decimal::from_str_normalized(&v.to_string()) {
+				Ok(d) => {
+					let strategy = strategy.unwrap_or(RoundingStrategy::MidpointAwayFromZero);
+					d.round_dp_with_strategy(dp, strategy).to_f64().unwrap_or(v).into()
+				}
+				// Non-finite floats, and floats too large to have any digits
+				// after the decimal point
+				Err(_) => v.into(),
+			},
+			Number::Decimal(v) => {
+				let strategy = strategy.unwrap_or(RoundingStrategy::MidpointNearestEven);
+				v.round_dp_with_strategy(dp, strategy).into()
+			}
+		}
+	}
+
 	pub fn sign(self) -> Self {
 		match self {
 			Number::Int(n) => n.signum().into(),
@@ -1273,6 +1299,15 @@ pub trait DecimalExt {
 	fn from_str_normalized(s: &str) -> Result<Self, rust_decimal::Error>
 	where
 		Self: Sized;
+
+	/// Returns the decimal with exactly `scale` digits after the decimal
+	/// point, if it fits in a `decimal(precision, scale)` without rounding.
+	///
+	/// A decimal fits if, without its trailing zeros, it has at most `scale`
+	/// digits after the decimal point and at most `precision` digits in all.
+	fn with_precision(self, precision: u32, scale: u32) -> Option<Self>
+	where
+		Self: Sized;
 }
 
 impl DecimalExt for Decimal {
@@ -1280,6 +1315,20 @@ impl DecimalExt for Decimal {
 		#[allow(clippy::disallowed_methods)]
 		Ok(Decimal::from_str(s)?.normalize())
 	}
+
+	fn with_precision(self, precision: u32, scale: u32) -> Option<Decimal> {
+		let mut v = self.normalize();
+		if v.scale() > scale {
+			return None;
+		}
+		v.rescale(scale);
+		// Rescaling leaves the scale lower when the digits would not fit
+		if v.scale() != scale {
+			return None;
+		}
+		let digits = v.mantissa().unsigned_abs().checked_ilog10().map_or(0, |d| d + 1);
+		(digits <= precision).then_some(v)
+	}
 }
 
 #[cfg(test)]
@@ -1568,6 +1617,47 @@ mod tests {
 		assert_eq!(Number::Float(f64::NEG_INFINITY).fixed(2), Number::Float(f64::NEG_INFINITY));
 	}
 
+	#[test]
+	fn round_dp_applies_the_strategy() {
+		let dec = |s: &str| Number::Decimal(Decimal::from_str_normalized(s).unwrap());
+		assert_eq!(dec("2.345").round_dp(2, None), dec("2.34"));
+		assert_eq!(
+			dec("2.345").round_dp(2, Some(RoundingStrategy::MidpointAwayFromZero)),
+			dec("2.35")
+		);
+		assert_eq!(
+			dec("-2.341").round_dp(2, Some(RoundingStrategy::ToNegativeInfinity)),
+			dec("-2.35")
+		);
+		// Floats are rounded as written
+		assert_eq!(Number::Float(2.675).round_dp(2, None), Number::Float(2.68));
+		assert_eq!(
+			Number::Float(2.675).round_dp(2, Some(RoundingStrategy::MidpointNearestEven)),
+			Number::Float(2.68)
+		);
+		assert_eq!(
+			Number::Float(2.665).round_dp(2, Some(RoundingStrategy::MidpointNearestEven)),
+			Number::Float(2.66)
+		);
+		assert_eq!(Number::Float(f64::INFINITY).round_dp(2, None), Number::Float(f64::INFINITY));
+		assert_eq!(Number::Int(7).round_dp(2, Some(RoundingStrategy::ToZero)), Number::Int(7));
+	}
+
+	#[test]
+	fn decimals_fit_a_precision_and_scale() {
+		let dec = |s: &str| Decimal::from_str_normalized(s).unwrap();
+		let fitted = dec("123.4").with_precision(5, 2).unwrap();
+		assert_eq!(fitted, dec("123.40"));
+		assert_eq!(fitted.scale(), 2);
+		assert_eq!(dec("-999.99").with_precision(5, 2), Some(dec("-999.99")));
+		assert_eq!(dec("1.5000").with_precision(2, 1), Some(dec("1.5")));
+		assert_eq!(dec("0").with_precision(1, 0), Some(dec("0")));
+		// Too many digits after the decimal point
+		assert_eq!(dec("1.234").with_precision(5, 2), None);
+		// Too many digits before the decimal point
+		assert_eq!(dec("1000").with_precision(5, 2), None);
+	}
+
 	#[test]
 	fn fixed_decimal_uses_round_dp() {
 		let d = Decimal::from_str_normalized("1.2345").unwrap();
@@ -15,7 +15,9 @@ use crate::doc::CursorDoc;
 use crate::expr::{self, Expr, Field, Fields, Literal, SelectStatement};
 use crate::fmt::EscapeRidKey;
 use crate::kvs::impl_kv_value_revisioned;
-use crate::val::{Array, IndexFormat, Number, Object, Range, Strand, TableName, Uuid, Value};
+use crate::val::{
+	Array, Datetime, IndexFormat, Number, Object, Range, Strand, TableName, Uuid, Value,
+};
 
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Encode, BorrowDecode)]
@@ -215,6 +217,22 @@ impl RecordIdKey {
 		matches!(self, RecordIdKey::Range(_))
 	}
 
+	/// Returns the time at which a record with this key was created, if the
+	/// key is a V7 UUID or an array starting with a datetime.
+	///
+	/// Other time-based UUIDs are not ordered by their timestamp, so they are
+	/// not treated as carrying a creation time.
+	pub(crate) fn created_at(&self) -> Option<Datetime> {
+		match self {
+			RecordIdKey::Uuid(u) if u.0.get_version_num() == 7 => u.to_datetime(),
+			RecordIdKey::Array(a) => match a.first() {
+				Some(Value::Datetime(d)) => Some(*d),
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
 	/// Returns surrealql value of this key.
 	pub(crate) fn into_value(self) -> Value {
 		match self {
@@ -450,8 +468,11 @@ impl RecordId {
 			fetch: None,
 			version: Expr::Literal(Literal::None),
 			timeout: Expr::Literal(Literal::None),
+			partial: false,
 			explain: None,
 			tempfiles: false,
+			valid_time: None,
+			scan_limit: None,
 		};
 
 		Ok(stk.run(|stk| stm.compute(stk, ctx, opt, doc)).await?.first().into_object())
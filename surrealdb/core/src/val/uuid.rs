@@ -3,6 +3,7 @@ use std::ops::Deref;
 use std::str;
 use std::str::FromStr;
 
+use chrono::DateTime;
 use revision::revisioned;
 use storekey::{BorrowDecode, Encode};
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
@@ -60,6 +61,24 @@ impl Uuid {
 	pub fn from_slice(slice: &[u8]) -> Result<Self, uuid::Error> {
 		Ok(Self(uuid::Uuid::from_slice(slice)?))
 	}
+
+	/// The time at which a time-based UUID was generated
+	pub fn to_datetime(&self) -> Option<Datetime> {
+		let (secs, nanos) = self.0.get_timestamp()?.to_unix();
+		DateTime::from_timestamp(secs as i64, nanos).map(Datetime)
+	}
+
+	/// The lowest and the highest V7 UUID which can be generated within the
+	/// millisecond of the given time
+	pub fn v7_bounds(timestamp: Datetime) -> (Self, Self) {
+		let millis = timestamp.0.timestamp_millis().clamp(0, 0xffff_ffff_ffff) as u64;
+		let prefix = &millis.to_be_bytes()[2..];
+		let mut min = [0x00; 16];
+		let mut max = [0xff; 16];
+		min[..6].copy_from_slice(prefix);
+		max[..6].copy_from_slice(prefix);
+		(Self(uuid::Uuid::from_bytes(min)), Self(uuid::Uuid::from_bytes(max)))
+	}
 }
 
 impl From<uuid::Uuid> for Uuid {
@@ -126,4 +145,18 @@ mod tests {
 
 		assert_eq!(output, format!("u'{}'", uuid_str));
 	}
+
+	#[test]
+	fn test_uuid_v7_bounds() {
+		let time = Datetime::from_str("2025-01-01T00:00:00.123456Z").unwrap();
+		let uuid = Uuid::new_v7_from_datetime(time);
+		assert_eq!(
+			uuid.to_datetime(),
+			Some(Datetime::from_str("2025-01-01T00:00:00.123Z").unwrap())
+		);
+		let (min, max) = Uuid::v7_bounds(time);
+		assert!(min <= uuid && uuid <= max);
+		assert!(Uuid::v7_bounds(Datetime::from_str("2025-01-01T00:00:00.124Z").unwrap()).0 > max);
+		assert_eq!(Uuid::new_v4().to_datetime(), None);
+	}
 }
@@ -786,6 +786,7 @@ impl Value {
 			Kind::Int => self.can_cast_to::<i64>(),
 			Kind::Float => self.can_cast_to::<f64>(),
 			Kind::Decimal => self.can_cast_to::<Decimal>(),
+			Kind::FixedDecimal(p, s) => self.fixed_decimal(*p, *s).is_some(),
 			Kind::Number => self.can_cast_to::<Number>(),
 			Kind::String => self.can_cast_to::<String>(),
 			Kind::Datetime => self.can_cast_to::<Datetime>(),
@@ -892,6 +893,16 @@ impl Value {
 		matches!(self, Value::File(f) if f.is_bucket_type(buckets))
 	}
 
+	/// Returns the value as a decimal rounded to fit a `decimal(precision,
+	/// scale)`, if it has few enough digits before the decimal point
+	fn fixed_decimal(&self, precision: u32, scale: u32) -> Option<Decimal> {
+		if !self.can_cast_to::<Decimal>() {
+			return None;
+		}
+		let v = self.clone().cast_to::<Decimal>().ok()?;
+		v.round_dp(scale).with_precision(precision, scale)
+	}
+
 	pub fn cast_to<T: Cast>(self) -> Result<T, CastError> {
 		T::cast(self)
 	}
@@ -907,6 +918,7 @@ impl Value {
 			Kind::Int => self.cast_to::<i64>().map(Value::from),
 			Kind::Float => self.cast_to::<f64>().map(Value::from),
 			Kind::Decimal => self.cast_to::<Decimal>().map(Value::from),
+			Kind::FixedDecimal(p, s) => self.cast_to_fixed_decimal(*p, *s).map(Value::from),
 			Kind::Number => self.cast_to::<Number>().map(Value::from),
 			Kind::String => self.cast_to::<String>().map(Value::from),
 			Kind::Datetime => self.cast_to::<Datetime>().map(Value::from),
@@ -1158,6 +1170,24 @@ impl Value {
 		Ok(set)
 	}
 
+	/// Try to convert this value to a decimal with a precision and scale
+	///
+	/// Unlike coercion, casting rounds the decimal to the scale, half to
+	/// even.
+	pub(crate) fn cast_to_fixed_decimal(
+		self,
+		precision: u32,
+		scale: u32,
+	) -> Result<Decimal, CastError> {
+		match self.fixed_decimal(precision, scale) {
+			Some(v) => Ok(v),
+			None => Err(CastError::InvalidKind {
+				from: self,
+				into: Kind::FixedDecimal(precision, scale).to_sql(),
+			}),
+		}
+	}
+
 	pub(crate) fn cast_to_file_buckets(self, buckets: &[String]) -> Result<File, CastError> {
 		let v = self.cast_to::<File>()?;
 
@@ -9,8 +9,8 @@ use surrealdb_types::ToSql;
 use crate::expr::Kind;
 use crate::expr::kind::{GeometryKind, HasKind, KindLiteral};
 use crate::val::{
-	Array, Bytes, Closure, Datetime, Duration, File, Geometry, Null, Number, Object, Range,
-	RecordId, Regex, Set, SqlNone, Strand, TableName, Uuid, Value,
+	Array, Bytes, Closure, Datetime, DecimalExt, Duration, File, Geometry, Null, Number, Object,
+	Range, RecordId, Regex, Set, SqlNone, Strand, TableName, Uuid, Value,
 };
 
 /// Identifies which element of a collection caused a coercion failure.
@@ -528,6 +528,7 @@ impl Value {
 			Kind::Int => self.can_coerce_to::<i64>(),
 			Kind::Float => self.can_coerce_to::<f64>(),
 			Kind::Decimal => self.can_coerce_to::<Decimal>(),
+			Kind::FixedDecimal(p, s) => self.fixed_decimal(*p, *s).is_some(),
 			Kind::Number => self.can_coerce_to::<Number>(),
 			Kind::String => self.can_coerce_to::<String>(),
 			Kind::Datetime => self.can_coerce_to::<Datetime>(),
@@ -644,6 +645,22 @@ impl Value {
 		matches!(self, Value::File(f) if f.is_bucket_type(buckets))
 	}
 
+	/// Returns the number as a decimal which fits a `decimal(precision, scale)`
+	fn fixed_decimal(&self, precision: u32, scale: u32) -> Option<Decimal> {
+		let Value::Number(n) = self else {
+			return None;
+		};
+		match n {
+			Number::Int(v) => Decimal::from(*v).with_precision(precision, scale),
+			Number::Decimal(v) => v.with_precision(precision, scale),
+			// Floats are taken as written, rather than as their binary
+			// approximation, which rarely fits any scale
+			Number::Float(v) => Decimal::from_str_normalized(&v.to_string())
+				.ok()
+				.and_then(|d| d.with_precision(precision, scale)),
+		}
+	}
+
 	/// Convert the value using coercion rules.
 	///
 	/// Coercion rules are more strict then coverting rules.
@@ -666,6 +683,7 @@ impl Value {
 			Kind::Int => self.coerce_to::<i64>().map(Value::from),
 			Kind::Float => self.coerce_to::<f64>().map(Value::from),
 			Kind::Decimal => self.coerce_to::<Decimal>().map(Value::from),
+			Kind::FixedDecimal(p, s) => self.coerce_to_fixed_decimal(*p, *s).map(Value::from),
 			Kind::Number => self.coerce_to::<Number>().map(Value::from),
 			Kind::String => self.coerce_to::<String>().map(Value::from),
 			Kind::Datetime => self.coerce_to::<Datetime>().map(Value::from),
@@ -908,6 +926,24 @@ impl Value {
 		Ok(set)
 	}
 
+	/// Try to coerce this value to a decimal with a precision and scale
+	///
+	/// Decimals are never rounded to fit the scale, so that no precision is
+	/// lost without being asked for.
+	pub(crate) fn coerce_to_fixed_decimal(
+		self,
+		precision: u32,
+		scale: u32,
+	) -> Result<Decimal, CoerceError> {
+		match self.fixed_decimal(precision, scale) {
+			Some(v) => Ok(v),
+			None => Err(CoerceError::InvalidKind {
+				from: self,
+				into: Kind::FixedDecimal(precision, scale).to_sql(),
+			}),
+		}
+	}
+
 	pub(crate) fn coerce_to_file_buckets(self, buckets: &[String]) -> Result<File, CoerceError> {
 		let v = self.coerce_to::<File>()?;
 
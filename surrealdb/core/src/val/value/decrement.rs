@@ -17,6 +17,13 @@ impl Value {
 		path: &[Part],
 		val: Value,
 	) -> Result<()> {
+		// Apply the decrement to each array element matched by a `WHERE` filter
+		if let Some(paths) = self.matched_paths(stk, ctx, opt, path).await? {
+			for path in paths {
+				stk.run(|stk| self.decrement(stk, ctx, opt, &path, val.clone())).await?;
+			}
+			return Ok(());
+		}
 		match self.get(stk, ctx, opt, None, path).await.catch_return()? {
 			Value::Array(v) => match val {
 				Value::Array(x) => {
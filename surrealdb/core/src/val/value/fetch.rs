@@ -65,8 +65,11 @@ impl Value {
 							fetch: None,
 							version: Expr::Literal(Literal::None),
 							timeout: Expr::Literal(Literal::None),
+							partial: false,
 							explain: None,
 							tempfiles: false,
+							valid_time: None,
+							scan_limit: None,
 						};
 						*this = stm
 							.compute(stk, ctx, opt, None)
@@ -268,8 +271,11 @@ impl Value {
 					fetch: None,
 					version: Expr::Literal(Literal::None),
 					timeout: Expr::Literal(Literal::None),
+					partial: false,
 					explain: None,
 					tempfiles: false,
+					valid_time: None,
+					scan_limit: None,
 				};
 				*this = stm.compute(stk, ctx, opt, None).await?.first();
 				Ok(())
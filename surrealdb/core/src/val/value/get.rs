@@ -601,8 +601,11 @@ impl Value {
 								fetch: None,
 								version: Expr::Literal(Literal::None),
 								timeout: Expr::Literal(Literal::None),
+								partial: false,
 								explain: None,
 								tempfiles: false,
+								valid_time: None,
+								scan_limit: None,
 							};
 
 							let res = stk.run(|stk| stm.compute(stk, ctx, opt, doc)).await?.all();
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use reblessive::tree::Stk;
+
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::expr::FlowResultExt as _;
+use crate::expr::part::Part;
+use crate::val::Value;
+
+impl Value {
+	/// Resolves the first `[WHERE ...]` filter in a path into the paths of
+	/// the array elements which it matches.
+	///
+	/// This lets compound assignments such as `items[WHERE sku = 'x'].qty += 1`
+	/// update each matched element in place, rather than operating on the
+	/// array of matched values. Returns `None` when the path has no filter.
+	pub(crate) async fn matched_paths(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		path: &[Part],
+	) -> Result<Option<Vec<Vec<Part>>>> {
+		let Some((pos, cond)) = path.iter().enumerate().find_map(|(i, p)| match p {
+			Part::Where(w) => Some((i, w)),
+			_ => None,
+		}) else {
+			return Ok(None);
+		};
+		let (head, tail) = (&path[..pos], &path[pos + 1..]);
+		let mut paths = Vec::new();
+		if let Value::Array(arr) = self.get(stk, ctx, opt, None, head).await.catch_return()? {
+			for (i, v) in arr.iter().enumerate() {
+				let cur = v.clone().into();
+				if stk
+					.run(|stk| cond.compute(stk, ctx, opt, Some(&cur)))
+					.await
+					.catch_return()?
+					.is_truthy()
+				{
+					let mut p = head.to_vec();
+					p.push(Part::index_int(i as i64));
+					p.extend_from_slice(tail);
+					paths.push(p);
+				}
+			}
+		}
+		Ok(Some(paths))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::dbs::test::mock;
+	use crate::expr::idiom::Idiom;
+	use crate::syn;
+
+	macro_rules! parse_val {
+		($input:expr) => {
+			crate::val::convert_public_value_to_internal(syn::value($input).unwrap())
+		};
+	}
+
+	#[tokio::test]
+	async fn matched_paths_none() {
+		let (ctx, opt) = mock().await;
+		let idi: Idiom = syn::idiom("items[0].qty").unwrap().into();
+		let val = parse_val!("{ items: [{ sku: 'a', qty: 1 }] }");
+		let mut stack = reblessive::TreeStack::new();
+		let res =
+			stack.enter(|stk| val.matched_paths(stk, &ctx, &opt, &idi)).finish().await.unwrap();
+		assert_eq!(res, None);
+	}
+
+	#[tokio::test]
+	async fn matched_paths_where() {
+		let (ctx, opt) = mock().await;
+		let idi: Idiom = syn::idiom("items[WHERE sku = 'b'].qty").unwrap().into();
+		let val = parse_val!("{ items: [{ sku: 'a' }, { sku: 'b' }, { sku: 'c' }, { sku: 'b' }] }");
+		let mut stack = reblessive::TreeStack::new();
+		let res =
+			stack.enter(|stk| val.matched_paths(stk, &ctx, &opt, &idi)).finish().await.unwrap();
+		let first: Idiom = syn::idiom("items[1].qty").unwrap().into();
+		let second: Idiom = syn::idiom("items[3].qty").unwrap().into();
+		assert_eq!(res, Some(vec![first.0, second.0]));
+	}
+}
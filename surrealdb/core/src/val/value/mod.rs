@@ -15,6 +15,7 @@ mod generate;
 mod get;
 mod inc;
 mod increment;
+mod matches;
 mod merge;
 mod patch;
 mod pick;
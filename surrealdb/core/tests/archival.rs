@@ -0,0 +1,64 @@
+#![recursion_limit = "256"]
+
+mod helpers;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use helpers::Test;
+
+#[tokio::test]
+#[test_log::test]
+async fn test_archival_policy_moves_records() -> Result<()> {
+	let sql = r#"
+		DEFINE POLICY archive ON event WHEN created < d'2024-01-01' MOVE TO event_archive BATCH 2;
+		CREATE event:1 SET created = d'2023-01-01', kind = 'old';
+		CREATE event:2 SET created = d'2023-06-01', kind = 'old';
+		CREATE event:3 SET created = d'2023-12-01', kind = 'old';
+		CREATE event:4 SET created = d'2025-01-01', kind = 'new';
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(5)?;
+	t.skip_ok(5)?;
+
+	// Nothing has been moved before the policy has run
+	let progress = t.ds.archival_progress("test", "test").await?;
+	assert_eq!(progress.len(), 1);
+	assert_eq!(progress[0].table, "event");
+	assert_eq!(progress[0].policy, "archive");
+	assert_eq!(progress[0].destination, "event_archive");
+	assert_eq!(progress[0].moved, 0);
+	assert!(progress[0].last_run.is_none());
+
+	// The matching records are moved over two batches
+	t.ds.archival_process(Duration::from_secs(60)).await?;
+	let progress = t.ds.archival_progress("test", "test").await?;
+	assert_eq!(progress[0].moved, 3);
+	assert_eq!(progress[0].last_moved, 3);
+	assert!(progress[0].last_run.is_some());
+	assert!(progress[0].last_error.is_none());
+
+	let sql = r#"
+		SELECT id, kind FROM event;
+		SELECT id, kind FROM event_archive;
+		SELECT VALUE kind FROM event, event_archive;
+	"#;
+	let mut t = t.new_sql(sql).await?;
+	t.expect_size(3)?;
+	t.expect_val("[{ id: event:4, kind: 'new' }]")?;
+	t.expect_val(
+		"[
+			{ id: event_archive:1, kind: 'old' },
+			{ id: event_archive:2, kind: 'old' },
+			{ id: event_archive:3, kind: 'old' }
+		]",
+	)?;
+	t.expect_val("['new', 'old', 'old', 'old']")?;
+
+	// Running again moves nothing, but keeps the running total
+	t.ds.archival_process(Duration::from_secs(60)).await?;
+	let progress = t.ds.archival_progress("test", "test").await?;
+	assert_eq!(progress[0].moved, 3);
+	assert_eq!(progress[0].last_moved, 0);
+	Ok(())
+}
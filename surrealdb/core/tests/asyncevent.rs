@@ -5,17 +5,28 @@ mod helpers;
 use std::time::Duration;
 
 use anyhow::Result;
-use helpers::Test;
-use surrealdb_core::doc::AsyncEventRecord;
+use helpers::{Test, new_ns_db};
+use surrealdb_core::cnf::ConfigMap;
+use surrealdb_core::dbs::{Capabilities, Session};
+use surrealdb_core::doc::{AsyncEventRecord, OutboxRecord};
 use surrealdb_core::kvs::Datastore;
 use tokio::time::{sleep, timeout};
 
 async fn wait_for_events_processing(ds: &Datastore) -> Result<()> {
-	timeout(Duration::from_secs(10), async {
-		while AsyncEventRecord::process_next_events_batch(ds, None).await? != 0 {
+	timeout(Duration::from_secs(30), async {
+		loop {
+			let events = AsyncEventRecord::process_next_events_batch(ds, None).await?;
+			let requests = OutboxRecord::process_next_batch(ds, None).await?;
+			// Failed events and requests are only retried once their backoff has elapsed
+			if events == 0
+				&& requests == 0
+				&& !AsyncEventRecord::has_pending_events(ds).await?
+				&& !OutboxRecord::has_pending_requests(ds).await?
+			{
+				return Ok::<_, anyhow::Error>(());
+			}
 			sleep(Duration::from_millis(100)).await;
 		}
-		Ok::<_, anyhow::Error>(())
 	})
 	.await?
 }
@@ -127,3 +138,168 @@ async fn test_async_event_retry() -> Result<()> {
 	t.expect_val("20")?;
 	Ok(())
 }
+
+#[tokio::test]
+#[test_log::test]
+async fn test_async_event_dead_letters() -> Result<()> {
+	let sql = r#"
+		DEFINE EVENT throw_it ON person ASYNC RETRY 1 THEN {
+			CREATE blah;
+			THROW "See you in the dead-letter queue!";
+		};
+		CREATE |person:3| RETURN NONE;
+	"#;
+
+	let mut t = Test::new(sql).await?;
+	t.expect_size(2)?;
+	t.expect_vals(&["NONE", "[]"])?;
+
+	// Every attempt fails, so the events are dead-lettered
+	wait_for_events_processing(&t.ds).await?;
+
+	let dead = t.ds.event_dead_letters("test", "test").await?;
+	assert_eq!(dead.len(), 3);
+	for dl in &dead {
+		assert_eq!(dl.table, "person");
+		assert_eq!(dl.event, "throw_it");
+		assert_eq!(dl.attempts, 2);
+		assert!(dl.error.contains("See you in the dead-letter queue!"), "{}", dl.error);
+		assert!(dl.record.as_deref().is_some_and(|r| r.starts_with("person:")));
+	}
+
+	// Fix the event, then replay the dead-lettered events
+	let sql = r#"
+		DEFINE EVENT OVERWRITE throw_it ON person ASYNC RETRY 1 THEN {
+			CREATE blah;
+		};
+	"#;
+	let mut t = t.new_sql(sql).await?;
+	t.expect_size(1)?;
+	t.expect_val("NONE")?;
+
+	assert_eq!(t.ds.replay_event_dead_letters("test", "test", Some("other")).await?, 0);
+	assert_eq!(t.ds.replay_event_dead_letters("test", "test", Some("person")).await?, 3);
+	wait_for_events_processing(&t.ds).await?;
+	assert!(t.ds.event_dead_letters("test", "test").await?.is_empty());
+
+	let mut t = t.new_sql("count(SELECT * FROM blah);").await?;
+	t.expect_size(1)?;
+	t.expect_val("3")?;
+	Ok(())
+}
+
+#[tokio::test]
+#[test_log::test]
+async fn test_async_event_dead_letters_expire() -> Result<()> {
+	let config = ConfigMap::empty().with_key_value("event_dead_letter_retention", "500ms");
+	let ds = Datastore::builder()
+		.with_capabilities(Capabilities::all())
+		.with_config(config)
+		.build_with_path("memory")
+		.await?;
+	new_ns_db(&ds, "test", "test").await?;
+	let sql = r#"
+		DEFINE EVENT throw_it ON person ASYNC RETRY 1 THEN {
+			THROW "See you in the dead-letter queue!";
+		};
+		CREATE person:1 RETURN NONE;
+	"#;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	for res in ds.execute(sql, &ses, None).await? {
+		res.result.unwrap();
+	}
+	wait_for_events_processing(&ds).await?;
+	assert_eq!(ds.event_dead_letters("test", "test").await?.len(), 1);
+
+	// Dead letters are kept until their retention has passed
+	ds.garbage_collect().await?;
+	assert_eq!(ds.event_dead_letters("test", "test").await?.len(), 1);
+	sleep(Duration::from_millis(600)).await;
+	ds.garbage_collect().await?;
+	assert!(ds.event_dead_letters("test", "test").await?.is_empty());
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+#[test_log::test]
+async fn test_outbox_event() -> Result<()> {
+	use wiremock::matchers::{method, path};
+	use wiremock::{Mock, ResponseTemplate};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("POST"))
+		.and(path("/hook"))
+		.respond_with(ResponseTemplate::new(200))
+		.expect(1)
+		.mount(&server)
+		.await;
+
+	let sql = format!(
+		r#"
+		DEFINE EVENT hook ON person OUTBOX THEN http::post('{}/hook', {{ name: $after.name }});
+		CREATE person:1 SET name = 'Tobie' RETURN NONE;
+		BEGIN;
+		CREATE person:2 SET name = 'Jaime' RETURN NONE;
+		CANCEL;
+		"#,
+		server.uri()
+	);
+	let mut t = Test::new(&sql).await?;
+	t.expect_size(3)?;
+	t.expect_vals(&["NONE", "[]"])?;
+
+	// The request is recorded by the transaction, and only sent afterwards
+	assert!(server.received_requests().await.unwrap_or_default().is_empty());
+	wait_for_events_processing(&t.ds).await?;
+
+	// The request of the cancelled transaction is never sent
+	server.verify().await;
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+#[test_log::test]
+async fn test_outbox_event_dead_letters() -> Result<()> {
+	use wiremock::matchers::{method, path};
+	use wiremock::{Mock, ResponseTemplate};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("POST"))
+		.and(path("/hook"))
+		.respond_with(ResponseTemplate::new(500))
+		.expect(2)
+		.mount(&server)
+		.await;
+
+	let sql = format!(
+		r#"
+		DEFINE EVENT hook ON person OUTBOX RETRY 1 THEN http::post('{}/hook');
+		CREATE person:1 RETURN NONE;
+		"#,
+		server.uri()
+	);
+	let mut t = Test::new(&sql).await?;
+	t.expect_size(2)?;
+	t.expect_vals(&["NONE", "[]"])?;
+
+	// Every attempt fails, so the request is dead-lettered
+	wait_for_events_processing(&t.ds).await?;
+	server.verify().await;
+
+	let dead = t.ds.event_dead_letters("test", "test").await?;
+	assert_eq!(dead.len(), 1);
+	assert_eq!(dead[0].table, "person");
+	assert_eq!(dead[0].event, "hook");
+	assert_eq!(dead[0].record.as_deref(), Some("person:1"));
+	assert_eq!(dead[0].attempts, 2);
+	assert_eq!(dead[0].request, Some(format!("POST {}/hook", server.uri())));
+	assert!(dead[0].error.contains("500"), "{}", dead[0].error);
+
+	// The replayed request is moved back into the outbox
+	assert_eq!(t.ds.replay_event_dead_letters("test", "test", Some("person")).await?, 1);
+	assert!(t.ds.event_dead_letters("test", "test").await?.is_empty());
+	assert!(OutboxRecord::has_pending_requests(&t.ds).await?);
+	Ok(())
+}
@@ -688,3 +688,32 @@ async fn test_changefeed_gc_cycle_kept() -> Result<()> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn test_changefeed_gc_maintains_each_namespace() -> Result<()> {
+	let (_, db) = new_ds("test-cf-a", "test-cf-a", false).await?;
+	for ns in ["test-cf-a", "test-cf-b"] {
+		let ses = Session::owner().with_ns(ns).with_db("test");
+		let src = r#"
+			DEFINE TABLE t CHANGEFEED 1ns;
+			CREATE t:1;
+		"#;
+		for res in db.execute(src, &ses, None).await? {
+			res.result.unwrap();
+		}
+	}
+
+	db.changefeed_process(&std::time::Duration::from_secs(1)).await.unwrap();
+	db.changefeed_process(&std::time::Duration::from_secs(1)).await.unwrap();
+
+	let metrics = db.tick_metrics();
+	for ns in ["test-cf-a", "test-cf-b"] {
+		let ns = &metrics[ns];
+		assert_eq!(ns.runs, 2);
+		assert_eq!(ns.errors, 0);
+		assert_eq!(ns.last_error, None);
+		assert!(ns.databases >= 2);
+		assert!(ns.total_duration >= ns.last_duration);
+	}
+	Ok(())
+}
@@ -0,0 +1,127 @@
+#![recursion_limit = "256"]
+
+mod helpers;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use helpers::{Test, new_ds, skip_ok};
+use surrealdb_core::dbs::Session;
+use surrealdb_types::Action;
+
+#[tokio::test]
+#[test_log::test]
+async fn test_eviction_removes_expired_records() -> Result<()> {
+	let sql = r#"
+		DEFINE TABLE cache EVICT AFTER 500ms;
+		DEFINE TABLE person;
+		CREATE cache:1;
+		CREATE person:1;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(4)?;
+	t.skip_ok(4)?;
+
+	// Records are kept until they have not been written for the time to live
+	t.ds.eviction_process(Duration::from_secs(60)).await?;
+	let sql = "SELECT VALUE id FROM cache";
+	let mut t = t.new_sql(sql).await?;
+	t.expect_size(1)?;
+	t.expect_val("[cache:1]")?;
+
+	// Records which have not been written for the time to live are evicted
+	tokio::time::sleep(Duration::from_millis(600)).await;
+	t.ds.eviction_process(Duration::from_secs(60)).await?;
+	let sql = r#"
+		SELECT VALUE id FROM cache;
+		SELECT VALUE id FROM person;
+	"#;
+	let mut t = t.new_sql(sql).await?;
+	t.expect_size(2)?;
+	t.expect_val("[]")?;
+	t.expect_val("[person:1]")?;
+	Ok(())
+}
+
+#[tokio::test]
+#[test_log::test]
+async fn test_eviction_time_to_live_is_kept_across_restarts() -> Result<()> {
+	let sql = r#"
+		DEFINE TABLE cache EVICT AFTER 500ms;
+		CREATE cache:1;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(2)?;
+	t.skip_ok(2)?;
+
+	// The write times are kept in the datastore, so a restart does not reset them
+	tokio::time::sleep(Duration::from_millis(300)).await;
+	let mut t = t.restart("CREATE cache:2").await?;
+	t.expect_size(1)?;
+	t.skip_ok(1)?;
+	tokio::time::sleep(Duration::from_millis(300)).await;
+	t.ds.eviction_process(Duration::from_secs(60)).await?;
+	let sql = "SELECT VALUE id FROM cache";
+	let mut t = t.new_sql(sql).await?;
+	t.expect_size(1)?;
+	t.expect_val("[cache:2]")?;
+	Ok(())
+}
+
+#[tokio::test]
+#[test_log::test]
+async fn test_eviction_existing_records_are_written_when_defined() -> Result<()> {
+	let sql = r#"
+		DEFINE TABLE cache;
+		CREATE cache:1;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(2)?;
+	t.skip_ok(2)?;
+
+	// The existing records are recorded as if they had just been written
+	tokio::time::sleep(Duration::from_millis(600)).await;
+	let mut t = t.new_sql("DEFINE TABLE OVERWRITE cache EVICT AFTER 500ms").await?;
+	t.expect_size(1)?;
+	t.skip_ok(1)?;
+	t.ds.eviction_process(Duration::from_secs(60)).await?;
+	let mut t = t.new_sql("SELECT VALUE id FROM cache").await?;
+	t.expect_size(1)?;
+	t.expect_val("[cache:1]")?;
+
+	tokio::time::sleep(Duration::from_millis(600)).await;
+	t.ds.eviction_process(Duration::from_secs(60)).await?;
+	let mut t = t.new_sql("SELECT VALUE id FROM cache").await?;
+	t.expect_size(1)?;
+	t.expect_val("[]")?;
+	Ok(())
+}
+
+#[tokio::test]
+#[test_log::test]
+async fn test_eviction_notifies_live_queries() -> Result<()> {
+	let (channel, dbs) = new_ds("test", "test", false).await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let sql = "
+		DEFINE TABLE cache EVICT AFTER 500ms;
+		CREATE cache:1;
+		CREATE cache:2;
+		LIVE SELECT * FROM cache;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	skip_ok(res, 4)?;
+
+	// Records which are deleted are notified as usual
+	let res = &mut dbs.execute("DELETE cache:2", &ses, None).await?;
+	skip_ok(res, 1)?;
+	assert_eq!(channel.recv().await?.action, Action::Delete);
+
+	// Records which are evicted are notified as evictions
+	tokio::time::sleep(Duration::from_millis(600)).await;
+	dbs.eviction_process(Duration::from_secs(60)).await?;
+	let notification = channel.recv().await?;
+	assert_eq!(notification.action, Action::Evicted);
+	assert_eq!(notification.record, surrealdb_core::syn::value("cache:1")?);
+	Ok(())
+}
@@ -212,6 +212,7 @@ async fn live_document_reduction() -> Result<()> {
 	// Receive the notification
 	let tmp = channel.recv().await?;
 	assert_eq!(tmp.action, Action::Killed);
+	assert_eq!(tmp.result, syn::value("'The live query was killed with KILL'")?);
 
 	// Create a live query with a WHERE clause
 	let sql = "LIVE SELECT * FROM test WHERE hidden = 123;";
@@ -337,6 +338,7 @@ async fn test_remove_table_invalidates_live_cache() -> Result<()> {
 	skip_ok(res, 1)?;
 	let tmp = channel.recv().await?;
 	assert_eq!(tmp.action, Action::Killed);
+	assert_eq!(tmp.result, syn::value("'The table was removed'")?);
 
 	// ...and must also bump the datastore live-query cache version, so a write
 	// to a recreated same-name table does not see the stale subscription.
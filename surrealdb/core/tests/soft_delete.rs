@@ -0,0 +1,43 @@
+#![recursion_limit = "256"]
+
+mod helpers;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use helpers::Test;
+
+#[tokio::test]
+#[test_log::test]
+async fn test_soft_delete_purge_removes_expired_records() -> Result<()> {
+	let sql = r#"
+		DEFINE TABLE person SOFT DELETE FIELD deleted_at PURGE AFTER 1d;
+		CREATE person:1;
+		CREATE person:2;
+		CREATE person:3 SET deleted_at = d'2020-01-01';
+		DELETE person:1;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(5)?;
+	t.skip_ok(5)?;
+
+	// Only the record deleted before the retention period is purged
+	t.ds.soft_delete_purge_process(Duration::from_secs(60)).await?;
+
+	let sql = r#"
+		SELECT id FROM person;
+		SELECT id FROM person WITH DELETED;
+	"#;
+	let mut t = t.new_sql(sql).await?;
+	t.expect_size(2)?;
+	t.expect_val("[{ id: person:2 }]")?;
+	t.expect_val("[{ id: person:1 }, { id: person:2 }]")?;
+
+	// Running again keeps the records within the retention period
+	t.ds.soft_delete_purge_process(Duration::from_secs(60)).await?;
+	let sql = "SELECT VALUE id FROM person WITH DELETED";
+	let mut t = t.new_sql(sql).await?;
+	t.expect_size(1)?;
+	t.expect_val("[person:1, person:2]")?;
+	Ok(())
+}
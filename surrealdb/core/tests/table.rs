@@ -2,8 +2,10 @@
 
 mod helpers;
 use anyhow::Result;
-use helpers::new_ds;
+use helpers::{Test, new_ds};
 use surrealdb_core::dbs::Session;
+use surrealdb_types::Table;
+use uuid::Uuid;
 
 use crate::helpers::skip_ok;
 
@@ -125,3 +127,102 @@ async fn define_foreign_table_with_no_cond_and_group_max() -> Result<()> {
 async fn define_foreign_table_with_no_cond_and_group_sum() -> Result<()> {
 	define_foreign_table_group(false, "math::sum(value)").await
 }
+
+fn session_with_id(id: Uuid) -> Session {
+	let mut session = Session::owner().with_ns("test").with_db("test");
+	session.id = Some(id);
+	session
+}
+
+#[tokio::test]
+async fn session_table_references_are_resolved_at_runtime() -> Result<()> {
+	let mut session = session_with_id(Uuid::new_v4());
+	session.variables.insert("tb", Table::new("results"));
+	let (notify, ds) = new_ds("test", "test", false).await?;
+	let sql = "
+		DEFINE TABLE results SESSION;
+		CREATE results:1 SET v = 1 RETURN NONE;
+		SELECT * FROM results;
+		SELECT * FROM type::table('results');
+		SELECT * FROM type::record('results', 1);
+		SELECT * FROM 'results'.to_record(1);
+		LET $name = 'results';
+		SELECT * FROM type::table($name);
+		SELECT id FROM $tb;
+	";
+	let mut t = Test::new_ds_session(ds, notify, session, sql).await?;
+	t.skip_ok(2)?;
+	// Every reference is resolved, and the ids are returned unscoped
+	for _ in 0..4 {
+		t.expect_val("[{ id: results:1, v: 1 }]")?;
+	}
+	t.skip_ok(1)?;
+	t.expect_val("[{ id: results:1, v: 1 }]")?;
+	t.expect_val("[{ id: results:1 }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn session_table_can_not_be_a_view() -> Result<()> {
+	let (notify, ds) = new_ds("test", "test", false).await?;
+	let sql = "DEFINE TABLE results SESSION AS SELECT * FROM person;";
+	let mut t = Test::new_ds_session(ds, notify, session_with_id(Uuid::new_v4()), sql).await?;
+	t.expect_error("The session table 'results' can not be defined as a view")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn session_table_can_not_be_accessed_by_another_session() -> Result<()> {
+	let owner = Uuid::new_v4();
+	let (notify, ds) = new_ds("test", "test", false).await?;
+	let sql = "DEFINE TABLE results SESSION; CREATE results:1 SET v = 1 RETURN NONE;";
+	let mut t = Test::new_ds_session(ds, notify, session_with_id(owner), sql).await?;
+	t.skip_ok(2)?;
+	// The scoped name of the table does not give access to it, neither from
+	// another session, nor from a session without an id
+	let scoped = format!("results@{}", owner.simple());
+	let sql = format!(
+		"
+		SELECT * FROM type::table('{scoped}');
+		SELECT * FROM type::record('{scoped}', 1);
+		UPDATE type::record('{scoped}', 1) SET v = 2;
+		REMOVE TABLE `{scoped}`;
+		"
+	);
+	let denied = |e: &surrealdb_types::Error| e.to_string().contains("belongs to another session");
+	let mut t =
+		Test::new_ds_session(t.ds, t.notifications, session_with_id(Uuid::new_v4()), &sql).await?;
+	for _ in 0..4 {
+		t.expect_error_func(denied)?;
+	}
+	let session = Session::owner().with_ns("test").with_db("test");
+	let mut t = Test::new_ds_session(t.ds, t.notifications, session, &sql).await?;
+	for _ in 0..4 {
+		t.expect_error_func(denied)?;
+	}
+	// The table is left untouched
+	let sql = "SELECT * FROM results";
+	let mut t = Test::new_ds_session(t.ds, t.notifications, session_with_id(owner), sql).await?;
+	t.expect_val("[{ id: results:1, v: 1 }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn session_tables_of_ended_sessions_are_garbage_collected() -> Result<()> {
+	let owner = Uuid::new_v4();
+	let (notify, ds) = new_ds("test", "test", false).await?;
+	let sql = "DEFINE TABLE results SESSION; CREATE results:1 SET v = 1 RETURN NONE;";
+	let mut t = Test::new_ds_session(ds, notify, session_with_id(owner), sql).await?;
+	t.skip_ok(2)?;
+	// The session is still running, so its table is kept
+	t.ds.garbage_collect().await?;
+	let sql = format!("SELECT * FROM type::table('results@{}')", owner.simple());
+	let mut t = Test::new_ds_session(t.ds, t.notifications, session_with_id(owner), &sql).await?;
+	t.expect_val("[{ id: results:1, v: 1 }]")?;
+	// The session ended without removing its table when the node restarted
+	let ds = t.ds.restart();
+	ds.garbage_collect().await?;
+	let mut t = Test::new_ds_session(ds, t.notifications, session_with_id(owner), &sql).await?;
+	t.expect_val("[]")?;
+	Ok(())
+}
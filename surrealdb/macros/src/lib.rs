@@ -0,0 +1,204 @@
+//! Procedural macros for the `surrealdb` crate.
+//!
+//! - `surql!()` - Checks a SurrealQL query at compile time, binding interpolated Rust expressions
+//!   as query parameters
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{LitStr, parse_macro_input};
+
+/// The prefix of the parameters holding interpolated values
+const PARAM_PREFIX: &str = "_surql_";
+
+/// Builds a SurrealQL query which is checked for syntax errors at compile time.
+///
+/// The query is written as a string literal. Rust expressions inside `{}` are
+/// not spliced into the query text: each one is replaced with a query
+/// parameter, and its value is bound to that parameter when the query runs.
+/// Literal braces, for example in objects or blocks, are written as `{{` and
+/// `}}`, as with [`format!`]. Braces inside quoted strings and identifiers are
+/// taken literally.
+///
+/// Every interpolated expression must implement `SurrealValue`, and is moved
+/// into the query. Each evaluation of the macro binds its values to parameters
+/// of its own, so queries built by the macro can be chained on one request.
+///
+/// The macro evaluates to a `surrealdb::opt::Surql`, which can be passed to
+/// `Surreal::query`.
+///
+/// ```ignore
+/// let min_age = 18;
+/// let name = String::from("Tobie");
+/// let response = db
+///     .query(surql!("SELECT * FROM person WHERE age >= {min_age} AND name = {name}"))
+///     .await?;
+///
+/// // Objects use escaped braces
+/// db.query(surql!("CREATE person CONTENT {{ name: {name}, age: {min_age + 1} }}")).await?;
+/// ```
+#[proc_macro]
+pub fn surql(input: TokenStream) -> TokenStream {
+	let lit = parse_macro_input!(input as LitStr);
+	let Template {
+		query,
+		parts,
+		exprs,
+	} = match split_template(&lit.value()) {
+		Ok(v) => v,
+		Err(e) => return syn::Error::new(lit.span(), e).to_compile_error().into(),
+	};
+	// Check the query with the parameters in place of the interpolations
+	if let Err(e) = surrealdb_core::syn::parse(&query) {
+		return syn::Error::new(lit.span(), format!("Invalid SurrealQL query: {e}"))
+			.to_compile_error()
+			.into();
+	}
+	let mut values = Vec::with_capacity(exprs.len());
+	for expr in exprs.iter() {
+		let expr = match syn::parse_str::<syn::Expr>(expr) {
+			Ok(expr) => expr,
+			Err(e) => {
+				return syn::Error::new(
+					lit.span(),
+					format!("Invalid interpolated expression `{expr}`: {e}"),
+				)
+				.to_compile_error()
+				.into();
+			}
+		};
+		values.push(expr);
+	}
+	quote! {
+		::surrealdb::opt::Surql::__from_parts(
+			&[#(#parts),*],
+			::std::vec![#(::surrealdb::types::SurrealValue::into_value(#values)),*],
+		)
+	}
+	.into()
+}
+
+/// A query template with its interpolations taken out
+struct Template {
+	/// The query with numbered parameters in place of the interpolations, to
+	/// be checked for syntax errors
+	query: String,
+	/// The text between the interpolations, one more than the expressions
+	parts: Vec<String>,
+	/// The interpolated expressions, in order
+	exprs: Vec<String>,
+}
+
+/// Takes the `{expr}` interpolations out of a template
+fn split_template(input: &str) -> Result<Template, String> {
+	let mut query = String::with_capacity(input.len());
+	let mut part = String::new();
+	let mut parts = Vec::new();
+	let mut exprs = Vec::new();
+	let mut chars = input.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			// Copy quoted strings and identifiers as they are written
+			'\'' | '"' | '`' | '⟨' => {
+				let close = if c == '⟨' {
+					'⟩'
+				} else {
+					c
+				};
+				part.push(c);
+				let mut closed = false;
+				while let Some(c) = chars.next() {
+					part.push(c);
+					if c == '\\' {
+						if let Some(c) = chars.next() {
+							part.push(c);
+						}
+					} else if c == close {
+						closed = true;
+						break;
+					}
+				}
+				if !closed {
+					return Err(format!("Unterminated quote, expected a closing `{close}`"));
+				}
+			}
+			'{' if chars.peek() == Some(&'{') => {
+				chars.next();
+				part.push('{');
+			}
+			'}' if chars.peek() == Some(&'}') => {
+				chars.next();
+				part.push('}');
+			}
+			'{' => {
+				// Find the matching brace, allowing blocks inside the expression
+				let mut depth = 1;
+				let mut expr = String::new();
+				for c in chars.by_ref() {
+					match c {
+						'{' => depth += 1,
+						'}' => {
+							depth -= 1;
+							if depth == 0 {
+								break;
+							}
+						}
+						_ => {}
+					}
+					expr.push(c);
+				}
+				if depth != 0 {
+					return Err(
+						"Unterminated interpolation, use `{{` for a literal brace".to_owned()
+					);
+				}
+				if expr.trim().is_empty() {
+					return Err("Empty interpolation, use `{{}}` for literal braces".to_owned());
+				}
+				query.push_str(&part);
+				query.push_str(&format!("${PARAM_PREFIX}{}", exprs.len()));
+				parts.push(std::mem::take(&mut part));
+				exprs.push(expr);
+			}
+			'}' => {
+				return Err("Unmatched `}`, use `}}` for a literal brace".to_owned());
+			}
+			c => part.push(c),
+		}
+	}
+	query.push_str(&part);
+	parts.push(part);
+	Ok(Template {
+		query,
+		parts,
+		exprs,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn splits_templates() {
+		let tpl =
+			split_template("CREATE person CONTENT {{ name: {name}, age: {age + 1} }}").unwrap();
+		assert_eq!(tpl.query, "CREATE person CONTENT { name: $_surql_0, age: $_surql_1 }");
+		assert_eq!(tpl.parts, ["CREATE person CONTENT { name: ", ", age: ", " }"]);
+		assert_eq!(tpl.exprs, ["name", "age + 1"]);
+	}
+
+	#[test]
+	fn skips_quoted_braces() {
+		let tpl = split_template(r#"SELECT "{a}", '{{\'}', `{b}`, ⟨{c}⟩ FROM {tb}"#).unwrap();
+		assert_eq!(tpl.query, r#"SELECT "{a}", '{{\'}', `{b}`, ⟨{c}⟩ FROM $_surql_0"#);
+		assert_eq!(tpl.exprs, ["tb"]);
+		assert!(split_template("SELECT '{a} FROM {tb}").is_err());
+	}
+
+	#[test]
+	fn rejects_unbalanced_braces() {
+		assert!(split_template("SELECT * FROM {table").is_err());
+		assert!(split_template("SELECT * FROM table }").is_err());
+		assert!(split_template("SELECT * FROM {}").is_err());
+	}
+}
@@ -64,6 +64,13 @@ struct ExportConfigArguments {
 	/// Whether configs should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	configs: Option<bool>,
+	/// Whether only the definitions should be exported, without any records
+	#[arg(long)]
+	#[arg(conflicts_with = "data_only")]
+	schema_only: bool,
+	/// Whether only the records should be exported, without any definitions
+	#[arg(long)]
+	data_only: bool,
 }
 
 #[derive(Args, Debug)]
@@ -176,6 +183,14 @@ fn apply_config<C: Connection, R>(
 			.records(false);
 	}
 
+	if config.schema_only {
+		export = export.schema_only();
+	}
+
+	if config.data_only {
+		export = export.data_only();
+	}
+
 	if let Some(value) = config.users {
 		export = export.users(value);
 	}
@@ -21,6 +21,9 @@ pub struct ImportCommandArguments {
 	#[arg(help = "Path to the SurrealQL file to import")]
 	#[arg(index = 1)]
 	file: String,
+	#[arg(help = "Verify the export against its trailer before importing anything")]
+	#[arg(long)]
+	verify: bool,
 	#[command(flatten)]
 	conn: DatabaseConnectionArguments,
 	#[command(flatten)]
@@ -32,6 +35,7 @@ pub struct ImportCommandArguments {
 pub async fn init(
 	ImportCommandArguments {
 		file,
+		verify,
 		conn: DatabaseConnectionArguments {
 			endpoint,
 		},
@@ -87,7 +91,13 @@ pub async fn init(
 	// Use the specified namespace / database
 	client.use_ns(namespace).use_db(database).await?;
 	// Import the data into the database
-	client.import(file).await.inspect_err(|_| {
+	let import = client.import(file);
+	let import = if verify {
+		import.verify()
+	} else {
+		import
+	};
+	import.await.inspect_err(|_| {
 		error!(
 			"Surreal import failed, import might only be partially completed or have failed entirely."
 		)
@@ -82,6 +82,34 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_ASYNC_EVENT_PROCESSING_INTERVAL", long = "async-event-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "5s")]
 	event_processing_interval: Duration,
+	#[arg(
+		help = "The interval at which to move records matching archival policies into their destination tables",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_ARCHIVAL_INTERVAL", long = "archival-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	archival_interval: Duration,
+	#[arg(
+		help = "The interval at which to purge soft deleted records whose retention period has passed",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_SOFT_DELETE_PURGE_INTERVAL", long = "soft-delete-purge-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	soft_delete_purge_interval: Duration,
+	#[arg(
+		help = "The interval at which to evict records from tables defined with EVICT",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_EVICTION_INTERVAL", long = "eviction-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "10s")]
+	eviction_interval: Duration,
+	#[arg(
+		help = "The interval at which to validate stored records against their checksums, when record checksums are enabled",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_SCRUB_INTERVAL", long = "scrub-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1h")]
+	scrub_interval: Duration,
 	#[arg(env = "SURREAL_RECLAIM_INTERVAL", long = "reclaim-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "60s")]
 	reclaim_interval: Duration,
@@ -232,6 +260,10 @@ pub async fn init<
 		index_compaction_interval,
 		index_build_resume_interval,
 		event_processing_interval,
+		archival_interval,
+		soft_delete_purge_interval,
+		eviction_interval,
+		scrub_interval,
 		reclaim_interval,
 		reclaim_grace,
 		tikv_gc_interval,
@@ -272,6 +304,10 @@ pub async fn init<
 		.with_index_compaction_interval(index_compaction_interval)
 		.with_index_build_resume_interval(index_build_resume_interval)
 		.with_event_processing_interval(event_processing_interval)
+		.with_archival_interval(archival_interval)
+		.with_soft_delete_purge_interval(soft_delete_purge_interval)
+		.with_eviction_interval(eviction_interval)
+		.with_scrub_interval(scrub_interval)
 		.with_reclaim_interval(reclaim_interval)
 		.with_reclaim_grace(reclaim_grace)
 		.with_tikv_gc_interval(tikv_gc_interval)
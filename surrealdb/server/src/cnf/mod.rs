@@ -78,6 +78,15 @@ pub static HTTP_MAX_ATTACHED_SESSIONS: LazyLock<usize> =
 pub static WEBSOCKET_MAX_ATTACHED_SESSIONS: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_ATTACHED_SESSIONS", usize, 256);
 
+/// How long in seconds the sessions of a disconnected WebSocket connection
+/// can be resumed with a resumption token (default: 30).
+///
+/// The sessions are held in memory on the node the client was connected to,
+/// so a client can only resume them when reconnecting to the same node. A
+/// value of 0 disables session resumption.
+pub static WEBSOCKET_RESUMPTION_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_WEBSOCKET_RESUMPTION_TIMEOUT", u64, 30);
+
 /// The maximum HTTP body size of the HTTP /key endpoints (default: 16 KiB)
 pub static HTTP_MAX_KEY_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_KEY_BODY_SIZE", usize, 16 << 10);
@@ -181,6 +181,20 @@ Targets must be in the form of <host>[:<port>], <ipv4|ipv6>[/<mask>]. For exampl
 	#[arg(value_parser = super::cli::validator::net_targets)]
 	allow_net: Option<Targets<NetTarget>>,
 
+	#[arg(
+		help = "Allow functions to be granted outbound network access to these targets with a CAPABILITIES clause, without allowing them for other queries",
+		long_help = r#"Allow functions to be granted outbound network access to these targets with a CAPABILITIES clause, without allowing them for other queries.
+A function defined with `CAPABILITIES { net: [...] }` may only connect to the targets it lists, each of which must be allowed here or by --allow-net.
+Specifically denied network targets prevail over any targets allowed for functions.
+Targets must be in the form of <host>[:<port>], <ipv4|ipv6>[/<mask>]. For example:
+ - 'api.stripe.com' -> Allow functions to be granted connections to this host on any port
+ - 'api.stripe.com:443' -> Allow functions to be granted connections to this host on port 443
+"#
+	)]
+	#[arg(env = "SURREAL_CAPS_ALLOW_FUNCTION_NET", long)]
+	#[arg(value_parser = super::cli::validator::net_targets)]
+	allow_function_net: Option<Targets<NetTarget>>,
+
 	#[arg(
 		help = "Allow all RPC methods to be called except for routes that are specifically denied. Alternatively, you can provide a comma-separated list of RPC methods to allow."
 	)]
@@ -416,6 +430,12 @@ impl DbsCapabilities {
 		self.allow_net.clone().unwrap_or(Targets::None)
 	}
 
+	fn get_allow_function_net(&self) -> Targets<NetTarget> {
+		// Network targets for functions are only ever allowed explicitly, and are
+		// still subject to the denied network targets
+		self.allow_function_net.clone().unwrap_or(Targets::None)
+	}
+
 	fn get_allow_rpc(&self) -> Targets<MethodTarget> {
 		// If there was a global deny, we allow if there is a general allow or some
 		// specific allows for RPC
@@ -649,6 +669,7 @@ fn merge_capabilities(initial: Capabilities, caps: &DbsCapabilities) -> Capabili
 		.without_functions(caps.get_deny_funcs())
 		.with_network_targets(caps.get_allow_net())
 		.without_network_targets(caps.get_deny_net())
+		.with_function_network_targets(caps.get_allow_function_net())
 		.with_rpc_methods(caps.get_allow_rpc())
 		.without_rpc_methods(caps.get_deny_rpc())
 		.with_http_routes(caps.get_allow_http())
@@ -1612,6 +1633,7 @@ mod tests {
 			allow_arbitrary_query: Some(Targets::All),
 			allow_eval_query: None,
 			allow_net: None,
+			allow_function_net: None,
 			allow_rpc: None,
 			allow_http: None,
 			deny_all: false,
@@ -1645,6 +1667,7 @@ mod tests {
 			allow_arbitrary_query: None,
 			allow_eval_query: None,
 			allow_net: None,
+			allow_function_net: None,
 			allow_rpc: None,
 			allow_http: None,
 			deny_all: false,
@@ -1682,6 +1705,7 @@ mod tests {
 			allow_arbitrary_query: None,
 			allow_eval_query: None,
 			allow_net: None,
+			allow_function_net: None,
 			allow_rpc: None,
 			allow_http: None,
 			deny_all: false,
@@ -1715,6 +1739,7 @@ mod tests {
 			allow_arbitrary_query: None,
 			allow_eval_query: None,
 			allow_net: None,
+			allow_function_net: None,
 			allow_rpc: None,
 			allow_http: Some(Targets::All),
 			deny_all: false,
@@ -1748,6 +1773,7 @@ mod tests {
 			allow_arbitrary_query: None,
 			allow_eval_query: None,
 			allow_net: None,
+			allow_function_net: None,
 			allow_rpc: None,
 			allow_http: None,
 			deny_all: false,
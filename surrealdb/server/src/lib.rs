@@ -44,6 +44,11 @@ use std::future::Future;
 use std::process::ExitCode;
 
 pub use cli::{Config, ConfigCheck, ConfigCheckRequirements};
+/// Re-export `Embedded` for convenience so embedders can `use
+/// surrealdb_server::Embedded`.
+#[cfg(feature = "embed")]
+#[doc(inline)]
+pub use ntw::embed::Embedded;
 /// Re-export `RouterFactory` for convenience so embedders can `use
 /// surrealdb_server::RouterFactory`.
 #[doc(inline)]
@@ -0,0 +1,232 @@
+//! Embed the SurrealDB wire protocols inside another Axum application.
+//!
+//! [`Embedded`] serves a single [`Datastore`] both over the network, through
+//! the standard RPC and HTTP endpoints, and in-process, through an SDK client.
+//! The endpoints are exposed as an Axum [`Router`], which can be merged or
+//! nested alongside the routes of the embedding application, or as a plain
+//! tower service. The client talks to the same datastore directly, without
+//! going through the network.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use axum::{Router, routing::get};
+//! use surrealdb_server::ntw::RouterOptions;
+//! use surrealdb_server::ntw::embed::Embedded;
+//! use surrealdb_server::core::{CommunityComposer, kvs::Datastore};
+//! use tokio_util::sync::CancellationToken;
+//!
+//! let (send, recv) = surrealdb_server::core::channel::bounded(100);
+//! let ds = Arc::new(Datastore::builder().with_notify(send).build_with_path("memory").await?);
+//! let ct = CancellationToken::new();
+//!
+//! let embedded =
+//!     Embedded::build::<CommunityComposer>(RouterOptions::default(), ds, Some(recv), ct, ())
+//!         .await?;
+//!
+//! // Query the datastore in-process
+//! let db = embedded.client().clone();
+//! db.use_ns("app").use_db("app").await?;
+//!
+//! // Serve the wire protocols alongside the application routes
+//! let app = Router::new()
+//!     .route("/hello", get(|| async { "hello" }))
+//!     .nest("/db", embedded.into_router());
+//! ```
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::Router;
+use axum::body::Body;
+use axum::routing::RouterIntoService;
+use surrealdb::Surreal;
+use surrealdb::engine::local::Db;
+use surrealdb_core::channel::{self, Receiver};
+use surrealdb_core::cnf::NOTIFICATIONS_CHANNEL_SIZE;
+use surrealdb_core::kvs::Datastore;
+use surrealdb_core::options::EngineOptions;
+use surrealdb_types::Notification;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use super::{RouterFactory, RouterOptions, SurrealRouter};
+use crate::rpc::RpcState;
+
+/// A datastore served both over the SurrealDB wire protocols and in-process.
+///
+/// Built with [`Embedded::build`]. The RPC and HTTP endpoints are available
+/// through [`into_router`](Embedded::into_router) or
+/// [`into_service`](Embedded::into_service), and the in-process SDK client
+/// through [`client`](Embedded::client).
+///
+/// Live query notifications from the datastore are delivered to whichever side
+/// started the live query: WebSocket clients of the endpoints, or the SDK
+/// client. Notification delivery stops when the [`CancellationToken`] passed to
+/// [`build`](Embedded::build) is cancelled, which also shuts the datastore down
+/// once the client has stopped.
+pub struct Embedded {
+	router: SurrealRouter,
+	client: Surreal<Db>,
+	notifications: Option<JoinHandle<()>>,
+}
+
+impl Embedded {
+	/// Serve the given datastore over the wire protocols and in-process.
+	///
+	/// # Parameters
+	/// - `opt`: Router-specific configuration (see [`RouterOptions`])
+	/// - `ds`: The [`Datastore`] instance to serve
+	/// - `notifications`: The receiving end of the notification channel of the datastore, if live
+	///   queries are enabled
+	/// - `ct`: A [`CancellationToken`] for cooperative shutdown
+	///
+	/// # Generic parameters
+	/// - `F`: A [`RouterFactory`] that determines which routes are included
+	pub async fn build<F: RouterFactory>(
+		opt: impl Into<RouterOptions>,
+		ds: Arc<Datastore>,
+		notifications: Option<Receiver<Notification>>,
+		ct: CancellationToken,
+		router_state: F::RouterState,
+	) -> Result<Self> {
+		// Split the notifications between the endpoints and the client
+		let (rpc_send, rpc_recv) = channel::bounded(NOTIFICATIONS_CHANNEL_SIZE);
+		let (sdk_send, sdk_recv) = channel::bounded(NOTIFICATIONS_CHANNEL_SIZE);
+		// Build the router serving the endpoints
+		let router =
+			SurrealRouter::build::<F>(opt, Arc::clone(&ds), rpc_recv, ct.clone(), router_state)
+				.await?;
+		// Build the client using the same datastore
+		let client = Surreal::<Db>::unstable_from_datastore(
+			ct.clone(),
+			ds,
+			notifications.is_some().then_some(sdk_recv),
+			EngineOptions::default(),
+		)
+		.await
+		.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+		// Deliver the notifications to the side which owns each live query
+		let notifications = notifications.map(|recv| {
+			router.spawn_notifications();
+			let state = Arc::clone(router.rpc_state());
+			tokio::spawn(fan_out(recv, state, rpc_send, sdk_send, ct))
+		});
+		Ok(Self {
+			router,
+			client,
+			notifications,
+		})
+	}
+
+	/// Return the in-process SDK client for the datastore.
+	pub fn client(&self) -> &Surreal<Db> {
+		&self.client
+	}
+
+	/// Return a reference to the [`RpcState`] backing the endpoints.
+	pub fn rpc_state(&self) -> &Arc<RpcState> {
+		self.router.rpc_state()
+	}
+
+	/// Return a reference to the shared [`Datastore`].
+	pub fn datastore(&self) -> &Arc<Datastore> {
+		self.router.datastore()
+	}
+
+	/// Consume this [`Embedded`] and return the Axum [`Router`] serving the
+	/// endpoints, ready to be merged or nested into another application.
+	///
+	/// The client remains usable through any clone taken beforehand with
+	/// [`client`](Embedded::client).
+	pub fn into_router(self) -> Router {
+		self.router.into_router()
+	}
+
+	/// Consume this [`Embedded`] and return the endpoints as a tower service,
+	/// for applications which do not use Axum routing.
+	pub fn into_service(self) -> RouterIntoService<Body> {
+		self.router.into_router().into_service()
+	}
+
+	/// Return the handle of the notification delivery task, if live queries
+	/// are enabled.
+	pub fn notifications(&mut self) -> Option<JoinHandle<()>> {
+		self.notifications.take()
+	}
+}
+
+/// Forwards each notification to the endpoints if the live query was started by
+/// a WebSocket client, and to the in-process client otherwise.
+async fn fan_out(
+	recv: Receiver<Notification>,
+	state: Arc<RpcState>,
+	rpc: channel::Sender<Notification>,
+	sdk: channel::Sender<Notification>,
+	ct: CancellationToken,
+) {
+	loop {
+		tokio::select! {
+			biased;
+			_ = ct.cancelled() => break,
+			Ok(notification) = recv.recv() => {
+				let remote = state.live_queries.read().await.contains_key(&notification.id);
+				// A closed side simply drops its notifications
+				if remote {
+					rpc.send(notification).await.ok();
+				} else {
+					sdk.send(notification).await.ok();
+				}
+			},
+			else => break,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use axum::body::Body;
+	use http::{Request, StatusCode};
+	use surrealdb_core::CommunityComposer;
+	use surrealdb_core::dbs::Session;
+	use surrealdb_types::ToSql;
+	use tower::ServiceExt;
+
+	use super::*;
+	use crate::ntw::client_ip::ClientIp;
+
+	#[tokio::test]
+	async fn client_and_endpoints_share_datastore() {
+		let ds = Arc::new(Datastore::new("memory").await.unwrap());
+		let opts = RouterOptions {
+			client_ip: ClientIp::None,
+			..Default::default()
+		};
+		let embedded = Embedded::build::<CommunityComposer>(
+			opts,
+			Arc::clone(&ds),
+			None,
+			CancellationToken::new(),
+			(),
+		)
+		.await
+		.unwrap();
+		// Write through the in-process client
+		let db = embedded.client().clone();
+		db.use_ns("test").use_db("test").await.unwrap();
+		db.query("CREATE person:tobie").await.unwrap().check().unwrap();
+		// The record is visible in the shared datastore
+		let session = Session::owner().with_ns("test").with_db("test");
+		let mut res = ds.execute("SELECT * FROM person:tobie", &session, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		assert_eq!(val.to_sql(), "[{ id: person:tobie }]");
+		// The endpoints are served by the same datastore
+		let res = embedded
+			.into_service()
+			.oneshot(Request::builder().uri("/health").body(Body::empty()).unwrap())
+			.await
+			.unwrap();
+		assert_eq!(res.status(), StatusCode::OK);
+	}
+}
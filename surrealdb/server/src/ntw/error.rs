@@ -187,6 +187,14 @@ fn types_error_into_response(e: &surrealdb_types::Error) -> Response {
 				Some("Your authentication details are invalid. Reauthenticate using valid authentication parameters.".to_string()),
 				Some("There was a problem with authentication".to_string()),
 			),
+			Some(NotAllowedError::Auth(AuthError::MfaRequired {
+				challenge,
+			})) => (
+				StatusCode::UNAUTHORIZED,
+				Some("Second factor required".to_string()),
+				Some("Sign in again with this challenge and a one-time code.".to_string()),
+				Some(challenge.clone()),
+			),
 			_ => (
 				StatusCode::FORBIDDEN,
 				Some("Forbidden".to_string()),
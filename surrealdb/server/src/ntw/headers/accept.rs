@@ -3,7 +3,7 @@ use axum_extra::headers::Header;
 use http::{HeaderName, HeaderValue};
 
 use crate::ntw::headers::content_type::{
-	HEADER_VALUE_APPLICATION_CBOR, HEADER_VALUE_APPLICATION_JSON,
+	HEADER_VALUE_APPLICATION_CBOR, HEADER_VALUE_APPLICATION_JSON, HEADER_VALUE_APPLICATION_NDJSON,
 	HEADER_VALUE_APPLICATION_OCTET_STREAM, HEADER_VALUE_APPLICATION_SURREAL_DB_FLATBUFFERS,
 	HEADER_VALUE_TEXT_PLAIN,
 };
@@ -16,6 +16,7 @@ pub enum Accept {
 	ApplicationCbor,
 	ApplicationOctetStream,
 	ApplicationFlatbuffers,
+	ApplicationNdjson,
 }
 
 impl std::fmt::Display for Accept {
@@ -28,6 +29,7 @@ impl std::fmt::Display for Accept {
 				f.write_str(surrealdb_core::api::format::OCTET_STREAM)
 			}
 			Accept::ApplicationFlatbuffers => f.write_str(surrealdb_core::api::format::FLATBUFFERS),
+			Accept::ApplicationNdjson => f.write_str(surrealdb_core::api::format::NDJSON),
 		}
 	}
 }
@@ -52,6 +54,7 @@ impl Header for Accept {
 			surrealdb_core::api::format::CBOR => Ok(Accept::ApplicationCbor),
 			surrealdb_core::api::format::OCTET_STREAM => Ok(Accept::ApplicationOctetStream),
 			surrealdb_core::api::format::FLATBUFFERS => Ok(Accept::ApplicationFlatbuffers),
+			surrealdb_core::api::format::NDJSON => Ok(Accept::ApplicationNdjson),
 			_ => Err(headers::Error::invalid()),
 		}
 	}
@@ -80,6 +83,7 @@ impl From<&Accept> for HeaderValue {
 			Accept::ApplicationFlatbuffers => {
 				HEADER_VALUE_APPLICATION_SURREAL_DB_FLATBUFFERS.clone()
 			}
+			Accept::ApplicationNdjson => HEADER_VALUE_APPLICATION_NDJSON.clone(),
 		}
 	}
 }
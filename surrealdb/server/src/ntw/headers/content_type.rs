@@ -33,6 +33,9 @@ pub(super) static HEADER_VALUE_APPLICATION_CBOR: HeaderValue =
 /// Pre-allocated static header value for `application/octet-stream` content type
 pub(super) static HEADER_VALUE_APPLICATION_OCTET_STREAM: HeaderValue =
 	HeaderValue::from_static(surrealdb_core::api::format::OCTET_STREAM);
+/// Pre-allocated static header value for `application/x-ndjson` content type
+pub(super) static HEADER_VALUE_APPLICATION_NDJSON: HeaderValue =
+	HeaderValue::from_static(surrealdb_core::api::format::NDJSON);
 /// Pre-allocated static header value for `application/surrealdb+flatbuffers` content type
 pub(super) static HEADER_VALUE_APPLICATION_SURREAL_DB_FLATBUFFERS: HeaderValue =
 	HeaderValue::from_static(surrealdb_core::api::format::FLATBUFFERS);
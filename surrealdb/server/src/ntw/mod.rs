@@ -1,6 +1,8 @@
 pub mod api;
 mod auth;
 pub mod client_ip;
+#[cfg(feature = "embed")]
+pub mod embed;
 pub mod error;
 pub mod export;
 #[cfg(feature = "gql")]
@@ -21,6 +21,7 @@ use surrealdb_core::rpc::{DbResponse, Method, RpcProtocol};
 use tokio::sync::RwLock;
 use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::request_id::RequestId;
+use tracing::Instrument;
 use uuid::Uuid;
 
 use super::AppState;
@@ -32,6 +33,7 @@ use crate::ntw::error::Error as NetError;
 use crate::rpc::RpcState;
 use crate::rpc::format::HttpFormat;
 use crate::rpc::websocket::Websocket;
+use crate::telemetry::traces::rpc::span_for_request;
 
 pub fn router() -> Router<Arc<RpcState>> {
 	Router::new()
@@ -206,6 +208,14 @@ async fn post_handler(
 			// (if any) so HTTP responses match the WebSocket convention.
 			let req_id = req.id;
 			let method = req.method;
+			// Record the request on a span, along with the client-supplied
+			// trace id, so its processing can be correlated with the client
+			let span = span_for_request(&request_session_id);
+			span.record("rpc.method", method.to_str());
+			span.record("otel.name", format!("surrealdb.rpc/{method}"));
+			if let Some(trace_id) = req.trace_id.as_deref() {
+				span.record("rpc.trace_id", trace_id);
+			}
 			// Ownership gate: if the client supplied a session id that targets an existing attached
 			// session, the caller's request-level auth principal must match the
 			// session's stored principal. `Method::Attach` is the only
@@ -238,6 +248,7 @@ async fn post_handler(
 						method,
 						req.params,
 					)
+					.instrument(span)
 					.await
 				}
 				Err(err) => Err(err),
@@ -1,16 +1,21 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use anyhow::Context;
+use axum::body::Body;
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{DefaultBodyLimit, Query, WebSocketUpgrade};
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
 use axum::routing::options;
 use axum::{Extension, Router};
 use axum_extra::TypedHeader;
 use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
+use http::StatusCode;
+use http::header::{CONTENT_TYPE, HeaderValue};
 use surrealdb_core::dbs::Session;
 use surrealdb_core::dbs::capabilities::RouteTarget;
+use surrealdb_core::kvs::Datastore;
 use surrealdb_types::{Array, SurrealValue, Value, Variables};
 use tower_http::limit::RequestBodyLimitLayer;
 
@@ -41,7 +46,7 @@ async fn post_handler(
 	output: Option<TypedHeader<Accept>>,
 	Query(params): Query<BTreeMap<String, String>>,
 	sql: Bytes,
-) -> Result<Output, ResponseError> {
+) -> Result<Response, ResponseError> {
 	let vars = Variables::from(params);
 	// Get a database reference
 	let db = &state.datastore;
@@ -56,8 +61,12 @@ async fn post_handler(
 	}
 	// Convert the received sql query
 	let sql = bytes_to_utf8(&sql).context("Non UTF-8 request body").map_err(ResponseError)?;
+	// Stream the results as newline-delimited JSON
+	if let Some(Accept::ApplicationNdjson) = output.as_deref() {
+		return stream_ndjson(Arc::clone(db), session, sql.to_owned(), vars).await;
+	}
 	// Execute the received sql query
-	match db.execute(sql, &session, Some(vars)).await {
+	let response = match db.execute(sql, &session, Some(vars)).await {
 		Ok(res) => match output.as_deref() {
 			// Simple serialization
 			None | Some(Accept::ApplicationJson) => {
@@ -82,6 +91,59 @@ async fn post_handler(
 		},
 		// There was an error when executing the query
 		Err(err) => Err(ResponseError(err.into())),
+	};
+	response.map(IntoResponse::into_response)
+}
+
+/// Executes a query, streaming the rows of each statement as newline-delimited
+/// JSON as soon as the statement is complete, with each row in its own chunk.
+///
+/// A query which fails as a whole, for instance because it can not be parsed,
+/// is reported with an error response before any output is streamed.
+async fn stream_ndjson(
+	db: Arc<Datastore>,
+	session: Session,
+	sql: String,
+	vars: Variables,
+) -> Result<Response, ResponseError> {
+	let (chn, body_stream) = surrealdb::channel::bounded::<anyhow::Result<Bytes>>(1);
+	let (ready, started) = tokio::sync::oneshot::channel();
+	tokio::spawn(async move {
+		let mut results = match db.execute_stream(&sql, &session, Some(vars)) {
+			Ok(results) => {
+				let _ = ready.send(Ok(()));
+				results
+			}
+			Err(err) => {
+				let _ = ready.send(Err(err));
+				return;
+			}
+		};
+		'results: while let Some(res) = results.next().await {
+			let res = match res {
+				Ok(res) => res,
+				// A failure of the query as a whole ends the output
+				Err(err) => {
+					let _ = chn.send(Err(err.into())).await;
+					break;
+				}
+			};
+			// Each row is sent as its own chunk, as soon as it is encoded
+			for line in surrealdb_core::rpc::format::ndjson::lines(res) {
+				// Dropping the results stops the query when the client disconnects
+				if chn.send(line.map(Bytes::from)).await.is_err() {
+					break 'results;
+				}
+			}
+		}
+	});
+	match started.await {
+		Ok(Ok(())) => Ok(Response::builder()
+			.status(StatusCode::OK)
+			.header(CONTENT_TYPE, HeaderValue::from(Accept::ApplicationNdjson))
+			.body(Body::from_stream(body_stream))?),
+		Ok(Err(err)) => Err(ResponseError(err.into())),
+		Err(_) => Err(ResponseError(anyhow::anyhow!("The query was aborted"))),
 	}
 }
 
@@ -212,6 +212,7 @@ surrealdb_process_uptime_seconds 99
 				user: Some(CANARY.into()),
 				session_id: None,
 				client_ip: None,
+				impersonator: Some(CANARY.into()),
 			},
 		};
 		metrics_obs.on_statement_complete(&stmt);
@@ -280,6 +281,7 @@ surrealdb_process_uptime_seconds 99
 				user: Some(CANARY.into()),
 				session_id: None,
 				client_ip: None,
+				impersonator: Some(CANARY.into()),
 			},
 		});
 
@@ -17,6 +17,7 @@ impl From<&Accept> for Format {
 			Accept::ApplicationCbor => Format::Cbor,
 			Accept::ApplicationOctetStream => Format::Unsupported,
 			Accept::ApplicationFlatbuffers => Format::Flatbuffers,
+			Accept::ApplicationNdjson => Format::Unsupported,
 		}
 	}
 }
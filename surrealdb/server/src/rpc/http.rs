@@ -131,9 +131,14 @@ impl Http {
 		}
 		// Fetch the stored session (returns session_not_found if absent).
 		let session_lock = self.get_session(session_id)?;
-		// Read the principal fingerprint under a short read lock.
+		// Read the principal fingerprint under a short read lock. While an
+		// administrator is impersonating a user, the session remains bound to
+		// the administrator, who keeps authenticating each request.
 		let session_guard = session_lock.read().await;
-		let session_au = session_guard.au.as_ref();
+		let session_au = match &session_guard.impersonator {
+			Some(impersonator) => impersonator.au.as_ref(),
+			None => session_guard.au.as_ref(),
+		};
 		if caller_may_use_session(session_au, caller_au) {
 			Ok(())
 		} else {
@@ -5,10 +5,11 @@ pub mod websocket;
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use futures::stream::FuturesUnordered;
 use surrealdb_core::channel::Receiver;
+use surrealdb_core::dbs::Session;
 #[cfg(feature = "graphql")]
 use surrealdb_core::graphql::NotificationRouter;
 use surrealdb_core::rpc::{DbResponse, DbResult};
@@ -45,12 +46,29 @@ pub struct LiveQueryEntry {
 
 /// Mapping of LIVE Query ID to its registered entry.
 type LiveQueries = RwLock<HashMap<Uuid, LiveQueryEntry>>;
+/// The sessions of a disconnected WebSocket, held so that the client can
+/// resume them on a new connection with its resumption token. Live queries
+/// and transactions are not held, as they end when the connection closes.
+#[derive(Clone, Debug)]
+pub struct SuspendedSessions {
+	/// The secret part of the resumption token
+	pub secret: Uuid,
+	/// The state of each session of the WebSocket, keyed by session id
+	pub sessions: Vec<(Uuid, Session)>,
+	/// When the sessions can no longer be resumed
+	pub expires: Instant,
+}
+
+/// Mapping of disconnected WebSocket ID to its suspended sessions.
+type Resumable = RwLock<HashMap<Uuid, SuspendedSessions>>;
 
 pub struct RpcState {
 	/// Stores the currently connected WebSockets
 	pub web_sockets: WebSockets,
 	/// Stores the currently initiated LIVE queries
 	pub live_queries: LiveQueries,
+	/// Stores the sessions of disconnected WebSockets which can be resumed
+	pub resumable: Resumable,
 	/// HTTP RPC handler with persistent sessions
 	pub http: Arc<crate::rpc::http::Http>,
 	/// Prometheus observer for per-protocol network byte counters. `None`
@@ -73,6 +91,7 @@ impl RpcState {
 		Self {
 			web_sockets: RwLock::new(HashMap::new()),
 			live_queries: RwLock::new(HashMap::new()),
+			resumable: RwLock::new(HashMap::new()),
 			http: Arc::new(crate::rpc::http::Http::new(datastore)),
 			metrics_observer,
 			#[cfg(feature = "graphql")]
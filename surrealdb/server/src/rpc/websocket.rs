@@ -1,6 +1,6 @@
 use core::fmt;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use axum::extract::ws::close_code::AGAIN;
 use axum::extract::ws::{CloseFrame, Message, WebSocket};
@@ -19,7 +19,7 @@ use surrealdb_core::observe::{
 };
 use surrealdb_core::rpc::format::Format;
 use surrealdb_core::rpc::{DbResponse, DbResult, Method, RpcProtocol};
-use surrealdb_types::{Array, Error as TypesError, HashMap, ToSql, Value};
+use surrealdb_types::{Array, AuthError, Error as TypesError, HashMap, ToSql, Value};
 use tokio::sync::RwLock;
 use tokio::sync::mpsc::{Receiver, Sender, channel};
 use tokio::task::JoinSet;
@@ -28,11 +28,11 @@ use tracing::Instrument;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use uuid::Uuid;
 
-use super::RpcState;
+use super::{RpcState, SuspendedSessions};
 use crate::cnf::{
 	PKG_NAME, PKG_VERSION, WEBSOCKET_MAX_ATTACHED_SESSIONS, WEBSOCKET_PING_FREQUENCY,
 	WEBSOCKET_RESPONSE_BUFFER_SIZE, WEBSOCKET_RESPONSE_CHANNEL_SIZE,
-	WEBSOCKET_RESPONSE_FLUSH_PERIOD,
+	WEBSOCKET_RESPONSE_FLUSH_PERIOD, WEBSOCKET_RESUMPTION_TIMEOUT,
 };
 use crate::rpc::CONN_CLOSED_ERR;
 use crate::rpc::format::WsFormat;
@@ -52,6 +52,26 @@ const SERVER_SHUTTING_DOWN: &str = "The server is gracefully shutting down";
 /// executor and goes straight to `kvs().transaction(...)`.
 const REQUEST_CANCELLED: &str = "The request was cancelled because the WebSocket is closing";
 
+/// An error string sent when a resumption token can not be used
+const INVALID_RESUMPTION_TOKEN: &str = "The resumption token is invalid or has expired";
+
+/// Splits a resumption token into the id of the WebSocket which issued it,
+/// and the secret which proves that the token was issued to the client.
+fn parse_resumption_token(token: &str) -> Option<(Uuid, Uuid)> {
+	if token.len() != 64 {
+		return None;
+	}
+	let id = Uuid::try_parse(token.get(..32)?).ok()?;
+	let secret = Uuid::try_parse(token.get(32..)?).ok()?;
+	Some((id, secret))
+}
+
+/// Compares two resumption secrets in constant time, so that the time taken
+/// to reject a token reveals nothing about the expected secret.
+fn secrets_match(a: &Uuid, b: &Uuid) -> bool {
+	a.as_bytes().iter().zip(b.as_bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 /// Build an OTel parent `Context` from W3C Trace Context propagation
 /// headers carried in the RPC envelope's `trace_context` field. Reuses
 /// the `HeaderMap`-based `HeaderExtractor` so the same propagator path
@@ -99,6 +119,9 @@ pub struct Websocket {
 	pub(crate) sessions: HashMap<Uuid, Arc<RwLock<Session>>>,
 	/// The active transactions for this WebSocket connection
 	pub(crate) transactions: DashMap<Uuid, Arc<Transaction>>,
+	/// The secret of the latest resumption token issued on this connection.
+	/// The sessions are only held after a disconnect when a token was issued.
+	pub(crate) resumption: RwLock<Option<Uuid>>,
 	/// A cancellation token called when shutting down the server
 	pub(crate) shutdown: CancellationToken,
 	/// Connection-level cancellation handle. Bundles a hot-path
@@ -148,6 +171,7 @@ impl Websocket {
 			cancel: surrealdb_core::ctx::CancelHandle::new(),
 			sessions: HashMap::new(),
 			transactions: DashMap::new(),
+			resumption: RwLock::new(None),
 			channel: sender.clone(),
 			datastore,
 		});
@@ -217,6 +241,16 @@ impl Websocket {
 		// the per-session limits, counter map, and `(session_id, tx)`
 		// value-type rework from 6907 are intentionally out of scope.
 		rpc.cleanup_all_txns().await;
+		// Remove the session tables of every session on this WebSocket. This
+		// happens before the sessions are held for resumption, so that a
+		// resumed session never races with the removal of its tables.
+		for (session_id, _) in rpc.session_map().to_vec() {
+			if let Err(err) = rpc.kvs().remove_session_tables(session_id).await {
+				warn!("Failed to remove the session tables of session {session_id}: {err}");
+			}
+		}
+		// Hold the sessions so that the client can resume them
+		rpc.suspend_sessions().await;
 		// Remove this WebSocket from the list
 		state.web_sockets.write().await.remove(&id);
 		// Emit a session disconnect event including the full session
@@ -238,6 +272,45 @@ impl Websocket {
 		});
 	}
 
+	/// Issues a new resumption token for this connection, which replaces
+	/// any token issued before it
+	async fn issue_resumption_token(&self) -> String {
+		let secret = Uuid::new_v4();
+		*self.resumption.write().await = Some(secret);
+		format!("{}{}", self.id.simple(), secret.simple())
+	}
+
+	/// Holds the sessions of this connection after a disconnect, so that the
+	/// client can resume them with its resumption token until they expire.
+	///
+	/// Nothing is held when no token was issued, when resumption is disabled,
+	/// or when the server is shutting down.
+	async fn suspend_sessions(&self) {
+		let timeout = *WEBSOCKET_RESUMPTION_TIMEOUT;
+		let Some(secret) = *self.resumption.read().await else {
+			return;
+		};
+		if timeout == 0 || self.shutdown.is_cancelled() {
+			return;
+		}
+		let mut sessions = Vec::new();
+		for (id, session) in self.session_map().to_vec() {
+			sessions.push((id, session.read().await.clone()));
+		}
+		let now = Instant::now();
+		let mut resumable = self.state.resumable.write().await;
+		// Drop the sessions which can no longer be resumed
+		resumable.retain(|_, suspended| suspended.expires > now);
+		resumable.insert(
+			self.id,
+			SuspendedSessions {
+				secret,
+				sessions,
+				expires: now + Duration::from_secs(timeout),
+			},
+		);
+	}
+
 	/// Send Ping messages to the client
 	async fn ping(rpc: Arc<Websocket>, internal_sender: Sender<Message>) {
 		// Create the interval ticker
@@ -533,6 +606,11 @@ impl Websocket {
 				"rpc.request_id",
 				req.id.as_ref().map(|id| id.to_sql()).unwrap_or_default(),
 			);
+			// Record the client-supplied trace id, so that every span and
+			// log line for this request can be correlated with the client
+			if let Some(trace_id) = req.trace_id.as_deref() {
+				span.record("rpc.trace_id", trace_id);
+			}
 			// If the client included W3C Trace Context propagation
 			// headers in the RPC envelope, use them as the OTel parent
 			// of the per-message span. WebSocket has no per-message
@@ -797,6 +875,76 @@ impl RpcProtocol for Websocket {
 		Ok(DbResult::Other(Value::None))
 	}
 
+	/// Issues a token with which the sessions of this connection can be
+	/// resumed on a new connection after a disconnect, within
+	/// [`WEBSOCKET_RESUMPTION_TIMEOUT`] seconds.
+	///
+	/// Only the latest token issued on a connection can be used.
+	async fn resume_token(&self) -> Result<DbResult, TypesError> {
+		if *WEBSOCKET_RESUMPTION_TIMEOUT == 0 {
+			return Err(surrealdb_core::rpc::method_not_allowed(Method::ResumeToken.to_string()));
+		}
+		Ok(DbResult::Other(Value::String(self.issue_resumption_token().await)))
+	}
+
+	/// Restores the sessions of a disconnected connection from its
+	/// resumption token, and returns a new token for this connection.
+	///
+	/// A token can only be used once, and only on a connection which has no
+	/// attached sessions yet. The namespace, database, variables and
+	/// authentication of each session are restored, while its live queries,
+	/// transactions and session tables ended with the previous connection.
+	async fn resume(&self, params: Array) -> Result<DbResult, TypesError> {
+		// Process the method arguments
+		let token = match params.into_vec().as_slice() {
+			[Value::String(token)] => token.clone(),
+			_ => return Err(surrealdb_core::rpc::invalid_params("Expected (token:string)")),
+		};
+		// Only a new connection can resume the sessions of another
+		if self.session_map().len() > 1 {
+			return Err(surrealdb_core::rpc::method_not_allowed(Method::Resume.to_string()));
+		}
+		let invalid = || {
+			TypesError::not_allowed(INVALID_RESUMPTION_TOKEN.to_string(), AuthError::InvalidAuth)
+		};
+		let (connection_id, secret) = parse_resumption_token(&token).ok_or_else(invalid)?;
+		// Take the suspended sessions, which can only be resumed once
+		let suspended = {
+			let mut resumable = self.state.resumable.write().await;
+			match resumable.get(&connection_id) {
+				Some(suspended) if secrets_match(&suspended.secret, &secret) => {
+					resumable.remove(&connection_id)
+				}
+				_ => None,
+			}
+		};
+		let suspended =
+			suspended.filter(|suspended| suspended.expires > Instant::now()).ok_or_else(invalid)?;
+		// The client address and origin are those of this connection
+		let (ip, or) = {
+			let current = self.get_session(&self.id)?;
+			let current = current.read().await;
+			(current.ip.clone(), current.or.clone())
+		};
+		// Restore the sessions, the default session taking the id of this connection
+		for (id, mut session) in
+			suspended.sessions.into_iter().take(*WEBSOCKET_MAX_ATTACHED_SESSIONS)
+		{
+			let id = if id == connection_id {
+				self.id
+			} else {
+				id
+			};
+			if session.id == Some(connection_id) {
+				session.id = Some(self.id);
+			}
+			session.ip = ip.clone();
+			session.or = or.clone();
+			self.set_session(id, Arc::new(RwLock::new(session)));
+		}
+		Ok(DbResult::Other(Value::String(self.issue_resumption_token().await)))
+	}
+
 	// ------------------------------
 	// Transactions
 	// ------------------------------
@@ -947,7 +1095,26 @@ impl RpcProtocol for Websocket {
 		}
 	}
 
-	/// Handles the cleanup of live queries for a given session.
+	/// Handles a change to a session parameter.
+	///
+	/// Updates the parameter in the live queries which the session started
+	/// on this connection, so that their notifications are filtered with the
+	/// current value of the session parameter.
+	async fn handle_param(&self, session_id: Uuid, name: &str, value: Value) {
+		let ids: Vec<Uuid> = self
+			.state
+			.live_queries
+			.read()
+			.await
+			.iter()
+			.filter(|(_, entry)| entry.websocket_id == self.id && entry.session_id == session_id)
+			.map(|(key, _)| *key)
+			.collect();
+		if let Err(err) = self.kvs().update_live_query_param(ids, name, value).await {
+			error!("Error updating live query parameter ${name}: {err}");
+		}
+	}
+
 	///
 	/// Drops the gauge per-entry using the namespace/database recorded at
 	/// registration time so the gauge stays balanced even when entries on
@@ -986,6 +1153,8 @@ impl RpcProtocol for Websocket {
 		// Generate a unique transaction ID
 		let id = Uuid::now_v7();
 		debug!("WebSocket begin: created transaction {id}");
+		// Lock the keys written by the transaction until it finishes
+		tx.enable_key_locks(id);
 		// Store the transaction in the map
 		self.transactions.insert(id, Arc::new(tx));
 		debug!(
@@ -1224,6 +1393,7 @@ mod tests {
 			datastore: ds,
 			sessions: HashMap::new(),
 			transactions: DashMap::new(),
+			resumption: RwLock::new(None),
 			shutdown: CancellationToken::new(),
 			cancel: surrealdb_core::ctx::CancelHandle::new(),
 			channel: tx,
@@ -1287,6 +1457,49 @@ mod tests {
 		assert!(ctx.user.is_none());
 	}
 
+	#[tokio::test]
+	async fn resumption_token_restores_the_sessions_once() {
+		// Disconnect a connection which was issued a token, then resume
+		// its sessions on a new connection sharing the same RPC state.
+		let old = ws_with_observer(None).await;
+		let sess = Session::owner().with_ns("acme").with_db("prod");
+		old.set_session(old.id, Arc::new(RwLock::new(sess)));
+		let attached = Uuid::new_v4();
+		old.attach(attached).await.unwrap();
+		let DbResult::Other(Value::String(token)) = old.resume_token().await.unwrap() else {
+			panic!("expected a resumption token");
+		};
+		old.suspend_sessions().await;
+
+		let (tx, _rx) = channel::<Message>(8);
+		let new = Arc::new(Websocket {
+			id: Uuid::new_v4(),
+			format: Format::Json,
+			state: Arc::clone(&old.state),
+			datastore: Arc::clone(&old.datastore),
+			sessions: HashMap::new(),
+			transactions: DashMap::new(),
+			resumption: RwLock::new(None),
+			shutdown: CancellationToken::new(),
+			cancel: surrealdb_core::ctx::CancelHandle::new(),
+			channel: tx,
+		});
+		new.set_session(new.id, Arc::new(RwLock::new(Session::default())));
+		let params = Array::from(vec![Value::String(token.clone())]);
+		let DbResult::Other(Value::String(next)) = new.resume(params).await.unwrap() else {
+			panic!("expected a new resumption token");
+		};
+		assert_ne!(next, token);
+		let session = new.get_session(&new.id).unwrap();
+		let session = session.read().await;
+		assert_eq!(session.ns.as_deref(), Some("acme"));
+		assert_eq!(session.db.as_deref(), Some("prod"));
+		assert_eq!(session.id, Some(new.id));
+		assert!(new.get_session(&attached).is_ok());
+		// A token can only be used once
+		assert!(new.state.resumable.read().await.is_empty());
+	}
+
 	/// Install the W3C trace-context propagator once per process. The
 	/// `extract_trace_context_*` tests share it because
 	/// `set_text_map_propagator` is process-global; running them with no
@@ -1555,6 +1768,7 @@ mod tests {
 				datastore: ds,
 				sessions: HashMap::new(),
 				transactions: DashMap::new(),
+				resumption: RwLock::new(None),
 				shutdown: CancellationToken::new(),
 				cancel: surrealdb_core::ctx::CancelHandle::new(),
 				channel: chn_internal,
@@ -1676,6 +1890,7 @@ mod tests {
 				datastore: ds,
 				sessions: HashMap::new(),
 				transactions: DashMap::new(),
+				resumption: RwLock::new(None),
 				shutdown: CancellationToken::new(),
 				cancel: surrealdb_core::ctx::CancelHandle::new(),
 				channel: chn_internal,
@@ -1785,6 +2000,7 @@ mod tests {
 				datastore: ds,
 				sessions: HashMap::new(),
 				transactions: DashMap::new(),
+				resumption: RwLock::new(None),
 				shutdown: CancellationToken::new(),
 				cancel: surrealdb_core::ctx::CancelHandle::new(),
 				channel: chn_internal,
@@ -1857,6 +2073,7 @@ mod tests {
 				datastore: ds,
 				sessions: HashMap::new(),
 				transactions: DashMap::new(),
+				resumption: RwLock::new(None),
 				shutdown: CancellationToken::new(),
 				cancel: surrealdb_core::ctx::CancelHandle::new(),
 				channel: chn_internal,
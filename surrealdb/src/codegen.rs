@@ -0,0 +1,534 @@
+//! Generation of Rust types from the schema of a database.
+//!
+//! [`Codegen`] reads the tables and fields defined in a database, either
+//! through a connection or from a SurrealQL export, and generates a Rust
+//! struct for each table. Fields are typed from their `TYPE` clause, record
+//! links are typed as [`RecordId`](crate::types::RecordId), and fields which
+//! hold one of a set of strings are typed as a generated enum. Nested fields,
+//! and fields typed as object literals, are generated as structs of their own.
+//!
+//! The generated types derive both `serde` and
+//! [`SurrealValue`](crate::types::SurrealValue), so the crate which includes
+//! them must depend on `serde` with the `derive` feature. Generating them from
+//! a build script keeps the types of an application in sync with its schema.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use crate::types::{Kind, KindLiteral, SurrealValue};
+use crate::{Connection, Error, Result, Surreal};
+
+/// The path of the types which the generated code refers to
+const TYPES: &str = "surrealdb::types";
+
+/// The derives of the generated structs
+const STRUCT_DERIVES: &str = "#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, surrealdb::types::SurrealValue)]";
+
+/// The derives of the generated enums
+const ENUM_DERIVES: &str = "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, surrealdb::types::SurrealValue)]";
+
+/// Generates Rust types from the schema of a database
+///
+/// A struct is generated for each table of the database, named after the
+/// table in `PascalCase`. Its `id` field holds the id of the record, and
+/// relation tables also get `in` and `out` fields. Fields without a `TYPE`
+/// clause, and fields of kinds which have no Rust equivalent, are typed as
+/// [`Value`](crate::types::Value). Optional fields are typed as [`Option`],
+/// and are left out when they are `NONE`.
+///
+/// # Examples
+///
+/// Generating the types of an application from its exported schema in a
+/// build script
+///
+#[cfg_attr(feature = "kv-mem", doc = "```no_run")]
+#[cfg_attr(not(feature = "kv-mem"), doc = "```ignore")]
+/// use std::path::PathBuf;
+///
+/// use surrealdb::codegen::Codegen;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     println!("cargo::rerun-if-changed=schema.surql");
+///     let export = std::fs::read_to_string("schema.surql")?;
+///     let runtime = tokio::runtime::Runtime::new()?;
+///     let code = runtime.block_on(Codegen::from_export(&export))?.generate();
+///     let path = PathBuf::from(std::env::var("OUT_DIR")?).join("schema.rs");
+///     std::fs::write(path, code)?;
+///     Ok(())
+/// }
+/// ```
+///
+/// The types are then included in the application with
+/// `include!(concat!(env!("OUT_DIR"), "/schema.rs"))`.
+#[derive(Clone, Debug)]
+pub struct Codegen {
+	tables: Vec<TableSchema>,
+}
+
+/// The parts of the structure of a database which types are generated from
+#[derive(Debug, Default, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct DbInfo {
+	tables: Vec<TableEntry>,
+}
+
+#[derive(Debug, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct TableEntry {
+	name: String,
+	kind: Option<TableKind>,
+	comment: Option<String>,
+}
+
+#[derive(Debug, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct TableKind {
+	kind: String,
+}
+
+/// The parts of the structure of a table which types are generated from
+#[derive(Debug, Default, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct TableInfo {
+	fields: Vec<FieldInfo>,
+}
+
+#[derive(Debug, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct FieldInfo {
+	name: String,
+	kind: Option<String>,
+	comment: Option<String>,
+}
+
+/// A table, and the fields defined on it
+#[derive(Clone, Debug)]
+struct TableSchema {
+	name: String,
+	relation: bool,
+	comment: Option<String>,
+	fields: Vec<FieldSchema>,
+}
+
+#[derive(Clone, Debug)]
+struct FieldSchema {
+	name: String,
+	kind: Option<Kind>,
+	comment: Option<String>,
+}
+
+/// A field, and the fields nested within it
+#[derive(Default)]
+struct Node {
+	kind: Option<Kind>,
+	comment: Option<String>,
+	children: BTreeMap<String, Node>,
+}
+
+impl Codegen {
+	/// Reads the schema of the database selected on a connection
+	pub async fn from_connection<C>(db: &Surreal<C>) -> Result<Self>
+	where
+		C: Connection,
+	{
+		let mut response = db.query("INFO FOR DB STRUCTURE").await?;
+		let info = response.take::<Option<DbInfo>>(0)?.unwrap_or_default();
+		let mut tables = Vec::with_capacity(info.tables.len());
+		for table in info.tables {
+			let mut response = db
+				.query("INFO FOR TABLE type::table($table) STRUCTURE")
+				.bind(("table", table.name.clone()))
+				.await?;
+			let info = response.take::<Option<TableInfo>>(0)?.unwrap_or_default();
+			let mut fields = Vec::with_capacity(info.fields.len());
+			for field in info.fields {
+				let kind = match &field.kind {
+					Some(kind) => Some(
+						surrealdb_core::syn::kind(kind)
+							.map_err(|e| {
+								Error::internal(format!(
+									"Invalid kind for field `{}` of table `{}`: {e}",
+									field.name, table.name
+								))
+							})?
+							.into(),
+					),
+					None => None,
+				};
+				fields.push(FieldSchema {
+					name: field.name,
+					kind,
+					comment: field.comment,
+				});
+			}
+			tables.push(TableSchema {
+				relation: table.kind.is_some_and(|kind| kind.kind == "RELATION"),
+				name: table.name,
+				comment: table.comment,
+				fields,
+			});
+		}
+		Ok(Codegen {
+			tables,
+		})
+	}
+
+	/// Reads the schema of a database from a SurrealQL export
+	///
+	/// The export is imported into a temporary in-memory database, so it may
+	/// hold records as well as definitions.
+	#[cfg(feature = "kv-mem")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "kv-mem")))]
+	pub async fn from_export(export: &str) -> Result<Self> {
+		let db = Surreal::new::<crate::engine::local::Mem>(()).await?;
+		db.use_ns("codegen").use_db("codegen").await?;
+		db.query(export).await?.check()?;
+		Self::from_connection(&db).await
+	}
+
+	/// Generates the Rust source code of the types
+	pub fn generate(&self) -> String {
+		let mut generator = Generator::default();
+		for table in &self.tables {
+			let mut root = Node::default();
+			if table.relation {
+				for name in ["in", "out"] {
+					root.children.insert(
+						name.to_owned(),
+						Node {
+							kind: Some(Kind::Record(Vec::new())),
+							..Node::default()
+						},
+					);
+				}
+			}
+			for field in &table.fields {
+				// The id is always generated, and array elements are typed
+				// along with their array
+				if field.name == "id" || field.name.contains(['[', '*']) {
+					continue;
+				}
+				let mut node = &mut root;
+				for part in field.name.split('.') {
+					let part = part.trim_matches(['`', '⟨', '⟩']);
+					node = node.children.entry(part.to_owned()).or_default();
+				}
+				node.kind = field.kind.clone();
+				node.comment = field.comment.clone();
+			}
+			generator.structure(&pascal_case(&table.name), table.comment.as_deref(), true, &root);
+		}
+		let mut code = String::from(
+			"// This file is generated from the schema of a SurrealDB database.\n// Do not edit it by hand, as it will be overwritten.\n",
+		);
+		for item in generator.items {
+			code.push('\n');
+			code.push_str(&item);
+		}
+		code
+	}
+}
+
+/// Collects the types generated for a schema
+#[derive(Default)]
+struct Generator {
+	names: BTreeSet<String>,
+	items: Vec<String>,
+}
+
+impl Generator {
+	/// Reserves a type name, numbering it if it is already taken
+	fn name(&mut self, name: &str) -> String {
+		let mut unique = name.to_owned();
+		let mut n = 2;
+		while !self.names.insert(unique.clone()) {
+			unique = format!("{name}{n}");
+			n += 1;
+		}
+		unique
+	}
+
+	/// Generates a struct holding the fields nested within a node, and
+	/// returns its name
+	fn structure(&mut self, name: &str, comment: Option<&str>, id: bool, node: &Node) -> String {
+		let name = self.name(name);
+		// The types used by the struct are generated after it
+		let index = self.items.len();
+		self.items.push(String::new());
+		let mut code = String::new();
+		write_doc(&mut code, "", comment);
+		code.push_str(STRUCT_DERIVES);
+		let _ = writeln!(code, "\npub struct {name} {{");
+		if id {
+			let _ = writeln!(code, "\tpub id: {TYPES}::RecordId,");
+		}
+		for (field, child) in &node.children {
+			let owner = format!("{name}{}", pascal_case(field));
+			let ty = if !child.children.is_empty() {
+				let ty = self.structure(&owner, None, false, child);
+				if child.kind.as_ref().is_some_and(is_optional) {
+					format!("Option<{ty}>")
+				} else {
+					ty
+				}
+			} else if let Some(kind) = &child.kind {
+				self.rust_type(&owner, kind)
+			} else {
+				format!("{TYPES}::Value")
+			};
+			write_doc(&mut code, "\t", child.comment.as_deref());
+			let ident = field_ident(field);
+			let rename =
+				(ident.trim_start_matches("r#") != field).then(|| format!("rename = {field:?}"));
+			let optional = ty.starts_with("Option<");
+			let serde = rename
+				.iter()
+				.cloned()
+				.chain(
+					optional
+						.then(|| "default, skip_serializing_if = \"Option::is_none\"".to_owned()),
+				)
+				.collect::<Vec<_>>();
+			let surreal = rename
+				.into_iter()
+				.chain(optional.then(|| "default".to_owned()))
+				.collect::<Vec<_>>();
+			if !serde.is_empty() {
+				let _ = writeln!(code, "\t#[serde({})]", serde.join(", "));
+			}
+			if !surreal.is_empty() {
+				let _ = writeln!(code, "\t#[surreal({})]", surreal.join(", "));
+			}
+			let _ = writeln!(code, "\tpub {ident}: {ty},");
+		}
+		code.push_str("}\n");
+		self.items[index] = code;
+		name
+	}
+
+	/// Generates an enum of string values, and returns its name
+	fn enumeration(&mut self, name: &str, values: &[&str]) -> String {
+		let name = self.name(name);
+		let mut variants = BTreeSet::new();
+		let mut code = String::new();
+		code.push_str(ENUM_DERIVES);
+		let _ = writeln!(code, "\n#[surreal(untagged)]\npub enum {name} {{");
+		for value in values {
+			let variant = pascal_case(value);
+			let mut unique = variant.clone();
+			let mut n = 2;
+			while !variants.insert(unique.clone()) {
+				unique = format!("{variant}{n}");
+				n += 1;
+			}
+			let _ = writeln!(code, "\t#[serde(rename = {value:?})]");
+			let _ = writeln!(code, "\t#[surreal(value = {value:?})]");
+			let _ = writeln!(code, "\t{unique},");
+		}
+		code.push_str("}\n");
+		self.items.push(code);
+		name
+	}
+
+	/// Returns the Rust type of a kind, generating the types which it needs
+	fn rust_type(&mut self, owner: &str, kind: &Kind) -> String {
+		match kind {
+			Kind::Bool | Kind::Literal(KindLiteral::Bool(_)) => "bool".to_owned(),
+			Kind::Int | Kind::Literal(KindLiteral::Integer(_)) => "i64".to_owned(),
+			Kind::Float | Kind::Literal(KindLiteral::Float(_)) => "f64".to_owned(),
+			Kind::String | Kind::Literal(KindLiteral::String(_)) => "String".to_owned(),
+			Kind::Decimal | Kind::Literal(KindLiteral::Decimal(_)) => format!("{TYPES}::Decimal"),
+			Kind::Duration | Kind::Literal(KindLiteral::Duration(_)) => {
+				format!("{TYPES}::Duration")
+			}
+			Kind::Number => format!("{TYPES}::Number"),
+			Kind::Bytes => format!("{TYPES}::Bytes"),
+			Kind::Datetime => format!("{TYPES}::Datetime"),
+			Kind::Uuid => format!("{TYPES}::Uuid"),
+			Kind::Object => format!("{TYPES}::Object"),
+			Kind::Regex => format!("{TYPES}::Regex"),
+			Kind::Range => format!("{TYPES}::Range"),
+			Kind::Geometry(_) => format!("{TYPES}::Geometry"),
+			Kind::File(_) => format!("{TYPES}::File"),
+			Kind::Table(_) => format!("{TYPES}::Table"),
+			Kind::Record(_) => format!("{TYPES}::RecordId"),
+			Kind::Set(..) => format!("{TYPES}::Set"),
+			Kind::Array(inner, _) => format!("Vec<{}>", self.rust_type(owner, inner)),
+			Kind::Literal(KindLiteral::Object(fields)) => {
+				let node = Node {
+					children: fields
+						.iter()
+						.map(|(name, kind)| {
+							let node = Node {
+								kind: Some(kind.clone()),
+								..Node::default()
+							};
+							(name.clone(), node)
+						})
+						.collect(),
+					..Node::default()
+				};
+				self.structure(owner, None, false, &node)
+			}
+			Kind::Either(kinds) => {
+				let kinds = kinds.iter().filter(|k| !matches!(k, Kind::None)).collect::<Vec<_>>();
+				let values = kinds
+					.iter()
+					.map(|k| match k {
+						Kind::Literal(KindLiteral::String(v)) => Some(v.as_str()),
+						_ => None,
+					})
+					.collect::<Option<Vec<_>>>();
+				let ty = match (&kinds[..], values) {
+					([], _) => return format!("{TYPES}::Value"),
+					([kind], _) => self.rust_type(owner, kind),
+					(_, Some(values)) => self.enumeration(owner, &values),
+					(kinds, None) if kinds.iter().all(|k| matches!(k, Kind::Record(_))) => {
+						format!("{TYPES}::RecordId")
+					}
+					// Values of several kinds can only be held as a value
+					_ => return format!("{TYPES}::Value"),
+				};
+				if is_optional(kind) {
+					format!("Option<{ty}>")
+				} else {
+					ty
+				}
+			}
+			Kind::Any
+			| Kind::None
+			| Kind::Null
+			| Kind::Function(..)
+			| Kind::Literal(KindLiteral::Array(_)) => format!("{TYPES}::Value"),
+		}
+	}
+}
+
+/// Whether a kind allows a value to be `NONE`
+fn is_optional(kind: &Kind) -> bool {
+	matches!(kind, Kind::Either(kinds) if kinds.iter().any(|k| matches!(k, Kind::None)))
+}
+
+/// Writes a comment as a doc comment
+fn write_doc(code: &mut String, indent: &str, comment: Option<&str>) {
+	for line in comment.into_iter().flat_map(str::lines) {
+		let _ = writeln!(code, "{indent}/// {line}");
+	}
+}
+
+/// Converts a name to a `PascalCase` type name
+fn pascal_case(name: &str) -> String {
+	let mut ident = String::with_capacity(name.len());
+	for part in name.split(|c: char| !c.is_alphanumeric()) {
+		let mut chars = part.chars();
+		if let Some(first) = chars.next() {
+			ident.extend(first.to_uppercase());
+			ident.extend(chars);
+		}
+	}
+	if ident.is_empty() {
+		ident.push_str("Empty");
+	}
+	if ident.starts_with(|c: char| c.is_numeric()) {
+		ident.insert(0, 'V');
+	}
+	ident
+}
+
+/// Converts a name to a `snake_case` field name, escaping keywords
+fn field_ident(name: &str) -> String {
+	let mut ident = String::with_capacity(name.len());
+	let mut previous = '_';
+	for c in name.chars() {
+		if c.is_uppercase() {
+			if previous != '_' && !previous.is_uppercase() {
+				ident.push('_');
+			}
+			ident.extend(c.to_lowercase());
+		} else if c.is_alphanumeric() {
+			ident.push(c);
+		} else if previous != '_' {
+			ident.push('_');
+		}
+		previous = if c.is_alphanumeric() {
+			c
+		} else {
+			'_'
+		};
+	}
+	if ident.is_empty() || ident.starts_with(|c: char| c.is_numeric()) {
+		ident.insert(0, '_');
+	}
+	match ident.as_str() {
+		"self" | "super" | "crate" | "_" => format!("{ident}_"),
+		"as" | "async" | "await" | "break" | "const" | "continue" | "dyn" | "else" | "enum"
+		| "extern" | "false" | "fn" | "for" | "gen" | "if" | "impl" | "in" | "let" | "loop"
+		| "match" | "mod" | "move" | "mut" | "pub" | "ref" | "return" | "static" | "struct"
+		| "trait" | "true" | "type" | "unsafe" | "use" | "where" | "while" | "abstract"
+		| "become" | "box" | "do" | "final" | "macro" | "override" | "priv" | "try" | "typeof"
+		| "unsized" | "virtual" | "yield" => format!("r#{ident}"),
+		_ => ident,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn field(name: &str, kind: Option<&str>) -> FieldSchema {
+		FieldSchema {
+			name: name.to_owned(),
+			kind: kind.map(|kind| surrealdb_core::syn::kind(kind).unwrap().into()),
+			comment: None,
+		}
+	}
+
+	#[test]
+	fn converts_names_to_identifiers() {
+		assert_eq!(pascal_case("user_profile"), "UserProfile");
+		assert_eq!(pascal_case("in-progress"), "InProgress");
+		assert_eq!(pascal_case("2fa"), "V2fa");
+		assert_eq!(field_ident("firstName"), "first_name");
+		assert_eq!(field_ident("first-name"), "first_name");
+		assert_eq!(field_ident("type"), "r#type");
+		assert_eq!(field_ident("self"), "self_");
+		assert_eq!(field_ident("2fa"), "_2fa");
+	}
+
+	#[test]
+	fn generates_types_for_tables() {
+		let codegen = Codegen {
+			tables: vec![TableSchema {
+				name: "person".to_owned(),
+				relation: false,
+				comment: Some("A person".to_owned()),
+				fields: vec![
+					field("id", None),
+					field("name", Some("string")),
+					field("age", Some("option<int>")),
+					field("role", Some("'admin' | 'user'")),
+					field("company", Some("record<company>")),
+					field("tags", Some("array<string>")),
+					field("tags[*]", Some("string")),
+					field("address", Some("object")),
+					field("address.city", Some("string")),
+					field("type", None),
+				],
+			}],
+		};
+		let code = codegen.generate();
+		assert!(code.contains("/// A person\n"), "{code}");
+		assert!(code.contains("pub struct Person {\n\tpub id: surrealdb::types::RecordId,\n"));
+		assert!(code.contains("\tpub name: String,\n"));
+		assert!(code.contains(
+			"\t#[serde(default, skip_serializing_if = \"Option::is_none\")]\n\t#[surreal(default)]\n\tpub age: Option<i64>,\n"
+		));
+		assert!(code.contains("\tpub role: PersonRole,\n"));
+		assert!(code.contains("pub enum PersonRole {\n\t#[serde(rename = \"admin\")]\n\t#[surreal(value = \"admin\")]\n\tAdmin,\n"));
+		assert!(code.contains("\tpub company: surrealdb::types::RecordId,\n"));
+		assert!(code.contains("\tpub tags: Vec<String>,\n"));
+		assert!(code.contains("\tpub address: PersonAddress,\n"));
+		assert!(code.contains("pub struct PersonAddress {\n\tpub city: String,\n}\n"));
+		assert!(code.contains("\tpub r#type: surrealdb::types::Value,\n"));
+	}
+}
@@ -0,0 +1,42 @@
+//! The notifications of the live queries of a local engine, broadcast to the
+//! receivers returned by [`Surreal::notifications_broadcast`].
+
+use surrealdb_types::Notification;
+use tokio::sync::broadcast;
+
+#[allow(unused_imports, reason = "Used by the documentation.")]
+use crate::Surreal;
+use crate::opt::Config;
+
+/// The number of notifications kept for the slowest receiver, unless set with
+/// [`Config::notifications_broadcast_capacity`]
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Broadcasts every notification received from the datastore
+#[derive(Debug, Clone)]
+pub(crate) struct NotificationBroadcast {
+	sender: broadcast::Sender<Notification>,
+}
+
+impl NotificationBroadcast {
+	#[allow(dead_code, reason = "Used by the local engines.")]
+	pub(crate) fn new(config: &Config) -> Self {
+		let capacity = config.notifications_broadcast_capacity.unwrap_or(DEFAULT_CAPACITY);
+		Self {
+			sender: broadcast::channel(capacity.max(1)).0,
+		}
+	}
+
+	/// Sends a notification to every receiver, without cloning it when there
+	/// are none
+	#[allow(dead_code, reason = "Used by the local engines.")]
+	pub(crate) fn send(&self, notification: &Notification) {
+		if self.sender.receiver_count() > 0 {
+			self.sender.send(notification.clone()).ok();
+		}
+	}
+
+	pub(crate) fn subscribe(&self) -> broadcast::Receiver<Notification> {
+		self.sender.subscribe()
+	}
+}
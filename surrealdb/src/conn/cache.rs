@@ -0,0 +1,392 @@
+//! The client-side cache of the records selected by their id, used by the
+//! remote engines when a [`CachePolicy`] is configured.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_channel::Receiver;
+#[cfg(not(target_family = "wasm"))]
+use tokio::spawn;
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::Instant;
+use uuid::Uuid;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_futures::spawn_local as spawn;
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::Instant;
+
+use super::Command;
+use crate::opt::{CachePolicy, Config};
+use crate::types::{Action, Notification, RecordId, ToSql, Value, Variables};
+
+/// Identifies the result of selecting a record in a session
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+	session: Uuid,
+	record: String,
+	fields: Option<String>,
+}
+
+impl CacheKey {
+	pub(crate) fn new(session: Uuid, record: &RecordId, fields: Option<&str>) -> Self {
+		Self {
+			session,
+			record: Value::RecordId(record.clone()).to_sql(),
+			fields: fields.map(ToOwned::to_owned),
+		}
+	}
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+	table: String,
+	value: Value,
+	expires: Instant,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+	entries: HashMap<CacheKey, CacheEntry>,
+	/// The live queries reporting the changes to the tables cached by each
+	/// session, which are `None` while they are being started
+	watched: HashMap<(Uuid, String), Option<Uuid>>,
+	/// Incremented whenever entries are dropped, so that a select which
+	/// raced with a write does not store its result
+	generation: u64,
+}
+
+/// The client-side cache of a remote engine
+#[derive(Debug)]
+pub(crate) struct ClientCache {
+	policy: CachePolicy,
+	state: Mutex<CacheState>,
+}
+
+impl ClientCache {
+	/// Creates the cache configured for a connection, if any
+	pub(crate) fn new(config: &Config) -> Option<Arc<Self>> {
+		let policy = config.cache.clone()?;
+		if policy.ttl.is_zero() || policy.max_entries == 0 {
+			return None;
+		}
+		Some(Arc::new(Self {
+			policy,
+			state: Default::default(),
+		}))
+	}
+
+	fn state(&self) -> MutexGuard<'_, CacheState> {
+		self.state.lock().unwrap_or_else(|e| e.into_inner())
+	}
+
+	/// Checks if the records of a table are cached
+	pub(crate) fn covers(&self, table: &str) -> bool {
+		self.policy.tables.as_ref().is_none_or(|tables| tables.contains(table))
+	}
+
+	/// Returns the cached result of a select, if it has not expired
+	pub(crate) fn get(&self, key: &CacheKey) -> Option<Value> {
+		let mut state = self.state();
+		match state.entries.get(key) {
+			Some(entry) if entry.expires > Instant::now() => Some(entry.value.clone()),
+			Some(_) => {
+				state.entries.remove(key);
+				None
+			}
+			None => None,
+		}
+	}
+
+	/// Returns the current generation, to pass to [`ClientCache::insert`]
+	pub(crate) fn generation(&self) -> u64 {
+		self.state().generation
+	}
+
+	/// Stores the result of a select, unless entries were dropped since the
+	/// given generation was read
+	pub(crate) fn insert(&self, key: CacheKey, table: &str, value: Value, generation: u64) {
+		let now = Instant::now();
+		let mut state = self.state();
+		if state.generation != generation {
+			return;
+		}
+		if !state.entries.contains_key(&key) && state.entries.len() >= self.policy.max_entries {
+			state.entries.retain(|_, entry| entry.expires > now);
+			// Drop the entry closest to expiring to make room
+			if state.entries.len() >= self.policy.max_entries {
+				let oldest = state
+					.entries
+					.iter()
+					.min_by_key(|(_, entry)| entry.expires)
+					.map(|(key, _)| key.clone());
+				if let Some(oldest) = oldest {
+					state.entries.remove(&oldest);
+				}
+			}
+		}
+		state.entries.insert(
+			key,
+			CacheEntry {
+				table: table.to_owned(),
+				value,
+				expires: now + self.policy.ttl,
+			},
+		);
+	}
+
+	/// Drops the entries matching a predicate
+	fn invalidate(&self, predicate: impl Fn(&CacheKey, &CacheEntry) -> bool) {
+		let mut state = self.state();
+		state.generation += 1;
+		state.entries.retain(|key, entry| !predicate(key, entry));
+	}
+
+	/// Drops the entries which a command can make stale, and returns the live
+	/// queries of the session which no longer report the right changes
+	pub(crate) fn on_command(&self, session: Uuid, command: &Command) -> Vec<Uuid> {
+		match command {
+			Command::Query {
+				query,
+				variables,
+				..
+			}
+			| Command::IdempotentQuery {
+				query,
+				variables,
+				..
+			}
+			| Command::QueryBytes {
+				query,
+				variables,
+				..
+			} => {
+				if may_write(query) {
+					self.invalidate(|_, entry| references(query, variables, &entry.table));
+				}
+				Vec::new()
+			}
+			// The writes of these commands are not known
+			Command::Run {
+				..
+			}
+			| Command::Commit {
+				..
+			}
+			| Command::ImportFile {
+				..
+			}
+			| Command::ImportMl {
+				..
+			} => {
+				self.invalidate(|_, _| true);
+				Vec::new()
+			}
+			// Parameters can change what a session is allowed to select
+			Command::Set {
+				..
+			}
+			| Command::Unset {
+				..
+			} => {
+				self.invalidate(|key, _| key.session == session);
+				Vec::new()
+			}
+			// The session now selects from another database, or as another user
+			Command::Use {
+				..
+			}
+			| Command::Signup {
+				..
+			}
+			| Command::Signin {
+				..
+			}
+			| Command::Authenticate {
+				..
+			}
+			| Command::Refresh {
+				..
+			}
+			| Command::Invalidate
+			| Command::Impersonate {
+				..
+			}
+			| Command::StopImpersonating
+			| Command::Attach {
+				..
+			}
+			| Command::Detach {
+				..
+			} => {
+				self.invalidate(|key, _| key.session == session);
+				let mut state = self.state();
+				let mut killed = Vec::new();
+				state.watched.retain(|(s, _), id| {
+					if *s != session {
+						return true;
+					}
+					killed.extend(id.take());
+					false
+				});
+				killed
+			}
+			_ => Vec::new(),
+		}
+	}
+
+	/// Marks the table of a record as watched by a session, and returns
+	/// whether a live query has to be started to report its changes
+	pub(crate) fn start_watching(&self, session: Uuid, table: &str) -> bool {
+		if !self.policy.live_invalidation {
+			return false;
+		}
+		let mut state = self.state();
+		let key = (session, table.to_owned());
+		if state.watched.contains_key(&key) {
+			return false;
+		}
+		state.watched.insert(key, None);
+		true
+	}
+
+	/// Records the live query started for a table, or forgets the table when
+	/// it could not be started, so that it is tried again
+	///
+	/// Returns `false` when the session changed while the live query was
+	/// being started, in which case it has to be killed.
+	pub(crate) fn watching(&self, session: Uuid, table: &str, id: Option<Uuid>) -> bool {
+		let mut state = self.state();
+		let key = (session, table.to_owned());
+		match id {
+			Some(id) => match state.watched.get_mut(&key) {
+				Some(watched) => {
+					*watched = Some(id);
+					true
+				}
+				None => false,
+			},
+			None => {
+				state.watched.remove(&key);
+				true
+			}
+		}
+	}
+
+	/// Drops the entries of the records changed on a table, as reported by
+	/// the notifications of a live query
+	pub(crate) fn spawn_invalidation(
+		self: &Arc<Self>,
+		session: Uuid,
+		table: String,
+		id: Uuid,
+		notifications: Receiver<crate::Result<Notification>>,
+	) {
+		let cache = Arc::downgrade(self);
+		spawn(async move {
+			while let Ok(notification) = notifications.recv().await {
+				let Some(cache) = cache.upgrade() else {
+					return;
+				};
+				match notification {
+					Ok(notification) if notification.action != Action::Killed => {
+						let record = notification.record.to_sql();
+						cache.invalidate(|key, _| key.record == record);
+					}
+					_ => break,
+				}
+			}
+			// The changes to the table are no longer reported
+			if let Some(cache) = cache.upgrade() {
+				let mut state = cache.state();
+				let key = (session, table);
+				if state.watched.get(&key) == Some(&Some(id)) {
+					state.watched.remove(&key);
+				}
+				drop(state);
+				cache.invalidate(|_, entry| entry.table == key.1);
+			}
+		});
+	}
+}
+
+/// Checks if a query can write to the database
+///
+/// Only a single `SELECT` statement, without any statement which writes, is
+/// known not to write.
+fn may_write(query: &str) -> bool {
+	let query = query.trim_start();
+	let select = query.get(..6).is_some_and(|start| start.eq_ignore_ascii_case("SELECT"));
+	if !select || query.contains(';') {
+		return true;
+	}
+	query.split(|c: char| !c.is_ascii_alphabetic()).any(|word| {
+		["CREATE", "UPDATE", "UPSERT", "DELETE", "RELATE", "INSERT"]
+			.iter()
+			.any(|keyword| word.eq_ignore_ascii_case(keyword))
+	})
+}
+
+/// Checks if a query can refer to a table, by name or through its variables
+fn references(query: &str, variables: &Variables, table: &str) -> bool {
+	fn refers(value: &Value, table: &str) -> bool {
+		match value {
+			Value::Table(t) => t.as_str() == table,
+			Value::RecordId(id) => id.table.as_str() == table,
+			Value::Array(values) => values.iter().any(|v| refers(v, table)),
+			_ => false,
+		}
+	}
+	query.contains(table) || variables.iter().any(|(_, value)| refers(value, table))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::*;
+
+	#[test]
+	fn detects_queries_which_can_write() {
+		assert!(!may_write("SELECT * FROM $_record_id"));
+		assert!(!may_write("  select name FROM config:limits"));
+		assert!(may_write("SELECT * FROM config; DELETE config"));
+		assert!(may_write("SELECT (UPDATE config SET seen = true) FROM user"));
+		assert!(may_write("UPDATE $_record_id MERGE $_data"));
+		assert!(may_write("DEFINE TABLE config"));
+	}
+
+	#[test]
+	fn writes_drop_the_entries_of_their_tables() {
+		let config = Config::new().cache(CachePolicy::ttl(Duration::from_secs(60)));
+		let cache = ClientCache::new(&config).unwrap();
+		let session = Uuid::new_v4();
+		let limits = CacheKey::new(session, &RecordId::new("config", "limits"), None);
+		let tobie = CacheKey::new(session, &RecordId::new("user", "tobie"), None);
+		cache.insert(limits.clone(), "config", Value::Bool(true), cache.generation());
+		cache.insert(tobie.clone(), "user", Value::Bool(true), cache.generation());
+		// Reads do not drop any entry
+		let read = Command::Query {
+			txn: None,
+			query: "SELECT * FROM $_record_id".into(),
+			variables: Variables::new(),
+		};
+		cache.on_command(session, &read);
+		assert!(cache.get(&limits).is_some());
+		// Writes drop the entries of the tables they refer to
+		let mut variables = Variables::new();
+		variables
+			.insert("_record_id".to_owned(), Value::RecordId(RecordId::new("config", "limits")));
+		let write = Command::Query {
+			txn: None,
+			query: "UPDATE $_record_id MERGE { max: 10 }".into(),
+			variables,
+		};
+		let generation = cache.generation();
+		cache.on_command(session, &write);
+		assert!(cache.get(&limits).is_none());
+		assert!(cache.get(&tobie).is_some());
+		// A select which raced with the write is not stored
+		cache.insert(limits.clone(), "config", Value::Bool(true), generation);
+		assert!(cache.get(&limits).is_none());
+	}
+}
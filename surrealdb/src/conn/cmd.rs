@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use async_channel::Sender;
 use surrealdb_core::iam::token::Token;
@@ -7,6 +8,7 @@ use surrealdb_core::kvs::export::Config as DbExportConfig;
 use uuid::Uuid;
 
 use super::MlExportConfig;
+use crate::opt::{EncryptionKey, Format};
 use crate::types::{Array, Notification, Object, Value, Variables};
 
 #[derive(Debug, Clone)]
@@ -29,6 +31,10 @@ pub(crate) enum Command {
 		token: Token,
 	},
 	Invalidate,
+	Impersonate {
+		subject: Object,
+	},
+	StopImpersonating,
 	Begin,
 	Rollback {
 		txn: Uuid,
@@ -44,6 +50,18 @@ pub(crate) enum Command {
 		query: Cow<'static, str>,
 		variables: Variables,
 	},
+	IdempotentQuery {
+		query: Cow<'static, str>,
+		variables: Variables,
+		key: String,
+	},
+	QueryBytes {
+		txn: Option<Uuid>,
+		query: Cow<'static, str>,
+		variables: Variables,
+		format: Format,
+		bytes: Sender<crate::Result<Vec<u8>>>,
+	},
 	ExportFile {
 		path: PathBuf,
 		config: Option<DbExportConfig>,
@@ -62,12 +80,28 @@ pub(crate) enum Command {
 	},
 	ImportFile {
 		path: PathBuf,
+		resume: bool,
+		verify: bool,
+		decryption: Option<EncryptionKey>,
 	},
 	ImportMl {
 		path: PathBuf,
 	},
 	Health,
+	HealthReport,
 	Version,
+	Compatibility,
+	LockWrites {
+		id: Uuid,
+		wait: Duration,
+		max_hold: Duration,
+	},
+	UnlockWrites {
+		id: Uuid,
+	},
+	Snapshot {
+		name: String,
+	},
 	Set {
 		key: String,
 		value: Value,
@@ -94,3 +128,28 @@ pub(crate) enum Command {
 		args: Array,
 	},
 }
+
+impl Command {
+	/// Runs a query command with an idempotency key, if one is specified
+	pub(crate) fn with_idempotency_key(self, key: Option<String>) -> crate::Result<Command> {
+		match (self, key) {
+			(command, None) => Ok(command),
+			(
+				Command::Query {
+					txn: None,
+					query,
+					variables,
+				},
+				Some(key),
+			) => Ok(Command::IdempotentQuery {
+				query,
+				variables,
+				key,
+			}),
+			(_, Some(_)) => Err(crate::Error::validation(
+				"Idempotency keys can not be used within a transaction".to_string(),
+				None,
+			)),
+		}
+	}
+}
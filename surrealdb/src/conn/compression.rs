@@ -0,0 +1,40 @@
+//! The metrics of the compressed responses received by the HTTP engine, when
+//! compression is enabled with [`Config::http_compression`].
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::method::CompressionStats;
+use crate::opt::Config;
+
+/// Counts the bytes of the compressed responses of a connection
+#[derive(Debug, Default)]
+pub(crate) struct CompressionMetrics {
+	responses: AtomicU64,
+	compressed_bytes: AtomicU64,
+	decompressed_bytes: AtomicU64,
+}
+
+impl CompressionMetrics {
+	/// Creates the metrics of a connection, unless compression is disabled
+	#[allow(dead_code, reason = "Used by the HTTP engine on native targets.")]
+	pub(crate) fn new(config: &Config) -> Option<Arc<Self>> {
+		config.http_compression.unwrap_or(true).then(Default::default)
+	}
+
+	/// Records a response which was received compressed
+	#[allow(dead_code, reason = "Used by the HTTP engine on native targets.")]
+	pub(crate) fn record(&self, compressed: u64, decompressed: u64) {
+		self.responses.fetch_add(1, Ordering::Relaxed);
+		self.compressed_bytes.fetch_add(compressed, Ordering::Relaxed);
+		self.decompressed_bytes.fetch_add(decompressed, Ordering::Relaxed);
+	}
+
+	pub(crate) fn stats(&self) -> CompressionStats {
+		CompressionStats {
+			responses: self.responses.load(Ordering::Relaxed),
+			compressed_bytes: self.compressed_bytes.load(Ordering::Relaxed),
+			decompressed_bytes: self.decompressed_bytes.load(Ordering::Relaxed),
+		}
+	}
+}
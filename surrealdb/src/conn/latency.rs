@@ -0,0 +1,32 @@
+//! The round-trip time to the server of a remote engine, measured when
+//! connections are warmed up with [`Config::warm_connections`] and at every
+//! ping configured with [`Config::keepalive`].
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+#[allow(unused_imports, reason = "Used by the documentation.")]
+use crate::opt::Config;
+
+/// The latest round-trip time measured by a connection
+#[derive(Debug, Default)]
+pub(crate) struct LatencyMetrics {
+	/// The round-trip time in nanoseconds, or 0 before it is first measured
+	nanos: AtomicU64,
+}
+
+impl LatencyMetrics {
+	/// Records a round-trip time which was just measured
+	#[allow(dead_code, reason = "Used by the remote engines on native targets.")]
+	pub(crate) fn record(&self, latency: Duration) {
+		let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX).max(1);
+		self.nanos.store(nanos, Ordering::Relaxed);
+	}
+
+	pub(crate) fn latest(&self) -> Option<Duration> {
+		match self.nanos.load(Ordering::Relaxed) {
+			0 => None,
+			nanos => Some(Duration::from_nanos(nanos)),
+		}
+	}
+}
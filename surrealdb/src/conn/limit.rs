@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::timeout;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::timeout;
+
+use crate::opt::Config;
+use crate::types::QueryError;
+use crate::{Error, Result};
+
+/// Limits the number of requests a connection has in flight
+///
+/// Requests beyond [`Config::max_concurrent_queries`] wait for a running
+/// request to finish, for at most [`Config::query_queue_timeout`].
+#[derive(Debug, Default)]
+pub(crate) struct QueryLimiter {
+	permits: Option<Arc<Semaphore>>,
+	/// The maximum number of requests in flight
+	limit: usize,
+	queue_timeout: Option<Duration>,
+	/// The number of requests currently waiting for a permit
+	queued: AtomicUsize,
+}
+
+/// Decrements the queue depth when a queued request stops waiting, whether it
+/// got a permit, timed out, or was dropped
+struct Queued<'a>(&'a AtomicUsize);
+
+impl Drop for Queued<'_> {
+	fn drop(&mut self) {
+		self.0.fetch_sub(1, Ordering::Relaxed);
+	}
+}
+
+impl QueryLimiter {
+	pub(crate) fn new(config: &Config) -> Self {
+		let limit = config.max_concurrent_queries.map(|n| n.max(1)).unwrap_or_default();
+		Self {
+			permits: config.max_concurrent_queries.map(|_| Arc::new(Semaphore::new(limit))),
+			limit,
+			queue_timeout: config.query_queue_timeout,
+			queued: AtomicUsize::new(0),
+		}
+	}
+
+	/// Waits until the request is allowed to run
+	///
+	/// The returned permit must be held until the response has been received.
+	pub(crate) async fn acquire(&self) -> Result<Option<OwnedSemaphorePermit>> {
+		let Some(permits) = &self.permits else {
+			return Ok(None);
+		};
+		// Skip the queue accounting when a permit is free
+		if let Ok(permit) = Arc::clone(permits).try_acquire_owned() {
+			return Ok(Some(permit));
+		}
+		self.queued.fetch_add(1, Ordering::Relaxed);
+		let _queued = Queued(&self.queued);
+		let permit = Arc::clone(permits).acquire_owned();
+		let permit = match self.queue_timeout {
+			Some(duration) => timeout(duration, permit).await.map_err(|_| {
+				Error::query(
+					format!("The request waited longer than {duration:?} for a free query slot"),
+					QueryError::NotExecuted,
+				)
+			})?,
+			None => permit.await,
+		};
+		// The semaphore is never closed
+		Ok(permit.ok())
+	}
+
+	/// The number of requests waiting for a running request to finish
+	pub(crate) fn queued(&self) -> usize {
+		self.queued.load(Ordering::Relaxed)
+	}
+
+	/// The number of requests currently running, if the limit is enabled
+	pub(crate) fn running(&self) -> usize {
+		self.permits.as_ref().map(|p| self.limit - p.available_permits()).unwrap_or_default()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn queues_beyond_the_limit() {
+		let config =
+			Config::new().max_concurrent_queries(1).query_queue_timeout(Duration::from_millis(50));
+		let limiter = QueryLimiter::new(&config);
+		let permit = limiter.acquire().await.unwrap();
+		assert!(permit.is_some());
+		assert_eq!(limiter.running(), 1);
+		// The second request times out while the first one is running
+		let err = limiter.acquire().await.unwrap_err();
+		assert!(err.is_query(), "{err}");
+		assert_eq!(limiter.queued(), 0);
+		drop(permit);
+		assert!(limiter.acquire().await.unwrap().is_some());
+	}
+
+	#[tokio::test]
+	async fn unlimited_by_default() {
+		let limiter = QueryLimiter::new(&Config::new());
+		assert!(limiter.acquire().await.unwrap().is_none());
+		assert_eq!(limiter.running(), 0);
+	}
+}
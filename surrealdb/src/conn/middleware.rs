@@ -0,0 +1,144 @@
+use std::sync::{Arc, RwLock};
+
+use surrealdb_core::dbs::QueryResult;
+
+use super::Command;
+use crate::Result;
+use crate::opt::{Middleware, Request};
+
+/// The middleware registered on a connection
+#[derive(Default)]
+pub(crate) struct MiddlewareStack {
+	layers: RwLock<Vec<Arc<dyn Middleware>>>,
+}
+
+impl std::fmt::Debug for MiddlewareStack {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("MiddlewareStack").field("layers", &self.layers().len()).finish()
+	}
+}
+
+impl MiddlewareStack {
+	pub(crate) fn push(&self, middleware: Arc<dyn Middleware>) {
+		self.layers.write().unwrap_or_else(|e| e.into_inner()).push(middleware);
+	}
+
+	fn layers(&self) -> Vec<Arc<dyn Middleware>> {
+		self.layers.read().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	/// Passes a command through the middleware, in the order it was registered
+	pub(crate) fn on_request(&self, command: &mut Command) -> Result<()> {
+		for layer in self.layers() {
+			let mut request = match command {
+				Command::Query {
+					query,
+					variables,
+					..
+				}
+				| Command::IdempotentQuery {
+					query,
+					variables,
+					..
+				} => Request::Query {
+					query,
+					variables,
+				},
+				Command::Run {
+					name,
+					args,
+					..
+				} => Request::Run {
+					name,
+					args,
+				},
+				_ => Request::Other,
+			};
+			layer.on_request(&mut request)?;
+		}
+		Ok(())
+	}
+
+	/// Passes the successful results of a command through the middleware, in
+	/// the reverse order it was registered
+	pub(crate) fn on_response(&self, results: &mut [QueryResult]) -> Result<()> {
+		let layers = self.layers();
+		if layers.is_empty() {
+			return Ok(());
+		}
+		for result in results.iter_mut() {
+			if let Ok(value) = &mut result.result {
+				for layer in layers.iter().rev() {
+					layer.on_response(value)?;
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::borrow::Cow;
+
+	use surrealdb_core::dbs::QueryResultBuilder;
+
+	use super::*;
+	use crate::types::{Object, Value, Variables};
+
+	struct Prefix(&'static str);
+
+	impl Middleware for Prefix {
+		fn on_request(&self, request: &mut Request<'_>) -> Result<()> {
+			if let Request::Query {
+				query,
+				..
+			} = request
+			{
+				*query = Cow::Owned(query.replace("FROM ", &format!("FROM {}", self.0)));
+			}
+			Ok(())
+		}
+
+		fn on_response(&self, value: &mut Value) -> Result<()> {
+			if let Value::Object(object) = value {
+				object.insert("seen", format!("{}{}", self.0, object.len()));
+			}
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn middleware_runs_in_order() {
+		let stack = MiddlewareStack::default();
+		stack.push(Arc::new(Prefix("a_")));
+		stack.push(Arc::new(Prefix("b_")));
+
+		let mut command = Command::Query {
+			txn: None,
+			query: Cow::Borrowed("SELECT * FROM user"),
+			variables: Variables::new(),
+		};
+		stack.on_request(&mut command).unwrap();
+		let Command::Query {
+			query,
+			..
+		} = command
+		else {
+			unreachable!()
+		};
+		assert_eq!(query, "SELECT * FROM b_a_user");
+
+		// Responses pass through the last registered middleware first
+		let mut results = vec![
+			QueryResultBuilder::started_now()
+				.with_result(Ok(Value::Object(Object::new())))
+				.finish(),
+		];
+		stack.on_response(&mut results).unwrap();
+		let Ok(Value::Object(object)) = &results[0].result else {
+			unreachable!()
+		};
+		assert_eq!(object.get("seen"), Some(&Value::String("a_1".to_string())));
+	}
+}
@@ -1,4 +1,6 @@
+use std::borrow::Cow;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use async_channel::{Receiver, Sender};
 use surrealdb_core::dbs::QueryResult;
@@ -6,11 +8,23 @@ use uuid::Uuid;
 
 use crate::method::BoxFuture;
 use crate::opt::Endpoint;
-use crate::types::{SurrealValue, Value};
+use crate::types::{RecordId, SurrealValue, Table, Value, Variables};
 use crate::{Error, ExtraFeatures, Result, Surreal};
 
+mod broadcast;
+mod cache;
 pub(crate) mod cmd;
+mod compression;
+mod latency;
+mod limit;
+mod middleware;
+pub(crate) use broadcast::NotificationBroadcast;
+pub(crate) use cache::{CacheKey, ClientCache};
 pub(crate) use cmd::Command;
+pub(crate) use compression::CompressionMetrics;
+pub(crate) use latency::LatencyMetrics;
+pub(crate) use limit::QueryLimiter;
+pub(crate) use middleware::MiddlewareStack;
 
 use super::opt::Config;
 
@@ -19,6 +33,14 @@ use super::opt::Config;
 pub struct RequestData {
 	pub(crate) command: Command,
 	pub(crate) session_id: Uuid,
+	/// The trace id supplied for the request, if any
+	pub(crate) trace_id: Option<String>,
+}
+
+/// Generates a random trace id for a request, formatted like a W3C trace id
+#[cfg(any(feature = "protocol-http", feature = "protocol-ws"))]
+pub(crate) fn new_trace_id() -> String {
+	Uuid::new_v4().simple().to_string()
 }
 
 #[derive(Debug)]
@@ -37,6 +59,18 @@ pub struct Router {
 	#[allow(dead_code)]
 	pub(crate) config: Config,
 	pub(crate) features: HashSet<ExtraFeatures>,
+	pub(crate) limiter: Arc<QueryLimiter>,
+	pub(crate) middleware: Arc<MiddlewareStack>,
+	/// The client-side cache, only used by the remote engines
+	pub(crate) cache: Option<Arc<ClientCache>>,
+	/// The metrics of compressed responses, only used by the HTTP engine
+	pub(crate) compression: Option<Arc<CompressionMetrics>>,
+	/// The latest round-trip time to the server, only measured by the remote
+	/// engines
+	pub(crate) latency: Arc<LatencyMetrics>,
+	/// The notifications of every live query on the datastore, only
+	/// broadcast by the local engines
+	pub(crate) notifications: Option<NotificationBroadcast>,
 }
 
 impl Router {
@@ -44,6 +78,75 @@ impl Router {
 	pub(crate) fn send_command(
 		&self,
 		session_id: Uuid,
+		trace_id: Option<String>,
+		mut command: Command,
+	) -> BoxFuture<
+		'_,
+		Result<Receiver<std::result::Result<Vec<QueryResult>, surrealdb_types::Error>>>,
+	> {
+		Box::pin(async move {
+			self.middleware.on_request(&mut command)?;
+			if let Some(cache) = &self.cache {
+				for uuid in cache.on_command(session_id, &command) {
+					// The live query reports the changes seen by the previous session
+					self.dispatch(
+						session_id,
+						None,
+						Command::Kill {
+							uuid,
+						},
+					)
+					.await
+					.ok();
+				}
+			}
+			self.dispatch(session_id, trace_id, command).await
+		})
+	}
+
+	/// Sends a command to the engine without waiting for its response, for
+	/// use in `Drop`, where nothing can be awaited and there may not be a
+	/// runtime to spawn a task on.
+	///
+	/// The command is queued straight away when the engine has room for it,
+	/// and is otherwise sent from a task on the current runtime, if any.
+	pub(crate) fn send_detached(&self, session_id: Uuid, command: Command) {
+		// Nobody waits for the response, which the engine then discards
+		let (sender, _) = async_channel::bounded(1);
+		let route = Route {
+			request: RequestData {
+				command,
+				session_id,
+				trace_id: None,
+			},
+			response: sender,
+		};
+		let route = match self.sender.try_send(route) {
+			Ok(()) | Err(async_channel::TrySendError::Closed(_)) => return,
+			Err(async_channel::TrySendError::Full(route)) => route,
+		};
+		let sender = self.sender.clone();
+		let send = async move {
+			sender.send(route).await.ok();
+		};
+		#[cfg(not(target_family = "wasm"))]
+		match tokio::runtime::Handle::try_current() {
+			Ok(handle) => {
+				handle.spawn(send);
+			}
+			Err(_) => warn!("Could not send a command to the busy engine outside of a runtime"),
+		}
+		#[cfg(target_family = "wasm")]
+		wasm_bindgen_futures::spawn_local(send);
+	}
+
+	/// Sends a command to the engine, without passing it through the
+	/// middleware or the cache
+	#[allow(clippy::type_complexity)]
+	fn dispatch(
+		&self,
+		session_id: Uuid,
+		trace_id: Option<String>,
 		command: Command,
 	) -> BoxFuture<
 		'_,
@@ -55,6 +158,7 @@ impl Router {
 				request: RequestData {
 					command,
 					session_id,
+					trace_id,
 				},
 				response: sender,
 			};
@@ -81,6 +185,7 @@ impl Router {
 				)
 			})?;
 			let mut results = response?;
+			self.middleware.on_response(&mut results)?;
 
 			match results.len() {
 				0 => Ok(Value::None),
@@ -101,12 +206,14 @@ impl Router {
 		receiver: Receiver<std::result::Result<Vec<QueryResult>, surrealdb_types::Error>>,
 	) -> BoxFuture<'_, Result<Vec<QueryResult>>> {
 		Box::pin(async move {
-			receiver.recv().await.map_err(|_| {
+			let mut results = receiver.recv().await.map_err(|_| {
 				crate::Error::connection(
 					"Connection uninitialised".to_string(),
 					Some(crate::types::ConnectionError::Uninitialised),
 				)
-			})?
+			})??;
+			self.middleware.on_response(&mut results)?;
+			Ok(results)
 		})
 	}
 
@@ -116,7 +223,8 @@ impl Router {
 		R: SurrealValue,
 	{
 		Box::pin(async move {
-			let rx = self.send_command(session_id, command).await?;
+			let _permit = self.limiter.acquire().await?;
+			let rx = self.send_command(session_id, None, command).await?;
 			let value = self.recv_value(rx).await?;
 			// Handle single-element arrays that might be returned from operations like
 			// signup/signin
@@ -145,40 +253,10 @@ impl Router {
 		R: SurrealValue,
 	{
 		Box::pin(async move {
-			let rx = self.send_command(session_id, command).await?;
-			match self.recv_value(rx).await? {
-				Value::None | Value::Null => Ok(None),
-				Value::Array(array) => match array.len() {
-					// Empty array means no results
-					0 => Ok(None),
-					// Single-element array: extract and return the element
-					// This happens when operating on a record ID
-					1 => Ok(Some(
-						R::from_value(
-							array.into_iter().next().expect("array has exactly one element"),
-						)
-						.map_err(|e| {
-							crate::Error::serialization(
-								e.to_string(),
-								crate::types::SerializationError::Deserialization,
-							)
-						})?,
-					)),
-					// Multiple elements should not happen for operations expecting Option<T>
-					_ => Ok(Some(R::from_value(Value::Array(array)).map_err(|e| {
-						crate::Error::serialization(
-							e.to_string(),
-							crate::types::SerializationError::Deserialization,
-						)
-					})?)),
-				},
-				value => Ok(Some(R::from_value(value).map_err(|e| {
-					crate::Error::serialization(
-						e.to_string(),
-						crate::types::SerializationError::Deserialization,
-					)
-				})?)),
-			}
+			let _permit = self.limiter.acquire().await?;
+			let rx = self.send_command(session_id, None, command).await?;
+			let value = self.recv_value(rx).await?;
+			opt_from_value(value)
 		})
 	}
 
@@ -192,7 +270,8 @@ impl Router {
 		R: SurrealValue,
 	{
 		Box::pin(async move {
-			let rx = self.send_command(session_id, command).await?;
+			let _permit = self.limiter.acquire().await?;
+			let rx = self.send_command(session_id, None, command).await?;
 			match self.recv_value(rx).await? {
 				Value::None | Value::Null => Ok(Vec::new()),
 				Value::Array(array) => array
@@ -223,7 +302,8 @@ impl Router {
 		command: Command,
 	) -> BoxFuture<'_, Result<()>> {
 		Box::pin(async move {
-			let rx = self.send_command(session_id, command).await?;
+			let _permit = self.limiter.acquire().await?;
+			let rx = self.send_command(session_id, None, command).await?;
 			match self.recv_value(rx).await? {
 				Value::None | Value::Null => Ok(()),
 				Value::Array(array) if array.is_empty() => Ok(()),
@@ -241,19 +321,99 @@ impl Router {
 		command: Command,
 	) -> BoxFuture<'_, Result<Value>> {
 		Box::pin(async move {
-			let rx = self.send_command(session_id, command).await?;
+			let _permit = self.limiter.acquire().await?;
+			let rx = self.send_command(session_id, None, command).await?;
 			self.recv_value(rx).await
 		})
 	}
 
+	/// Execute the select of a single record, which is served from the client
+	/// cache while it holds the record
+	pub(crate) fn execute_record(
+		&self,
+		session_id: Uuid,
+		record: RecordId,
+		fields: Option<String>,
+		mut command: Command,
+	) -> BoxFuture<'_, Result<Value>> {
+		Box::pin(async move {
+			let cache = self.cache.as_ref().filter(|cache| cache.covers(record.table.as_str()));
+			let Some(cache) = cache else {
+				return self.execute_value(session_id, command).await;
+			};
+			let key = CacheKey::new(session_id, &record, fields.as_deref());
+			if let Some(value) = cache.get(&key) {
+				return Ok(value);
+			}
+			// Watch the changes to the table before the record is read, so
+			// that none of them is missed
+			if self.features.contains(&ExtraFeatures::LiveQueries)
+				&& cache.start_watching(session_id, record.table.as_str())
+			{
+				self.watch_table(cache, session_id, record.table.clone()).await;
+			}
+			let generation = cache.generation();
+			let _permit = self.limiter.acquire().await?;
+			self.middleware.on_request(&mut command)?;
+			let rx = self.dispatch(session_id, None, command).await?;
+			let value = self.recv_value(rx).await?;
+			cache.insert(key, record.table.as_str(), value.clone(), generation);
+			Ok(value)
+		})
+	}
+
+	/// Starts the live query which drops the cached records of a table when
+	/// they change
+	async fn watch_table(&self, cache: &Arc<ClientCache>, session_id: Uuid, table: Table) {
+		let started = async {
+			let mut variables = Variables::new();
+			variables.insert("_table".to_string(), Value::Table(table.clone()));
+			let command = Command::Query {
+				txn: None,
+				query: Cow::Borrowed("LIVE SELECT id FROM $_table"),
+				variables,
+			};
+			let rx = self.dispatch(session_id, None, command).await?;
+			let result = self.recv_value(rx).await?;
+			let id = crate::method::live::live_query_id(result)?;
+			let notifications = crate::method::live::register(self, id, session_id).await?;
+			Result::Ok((id, notifications))
+		};
+		match started.await {
+			Ok((id, notifications)) => {
+				if cache.watching(session_id, table.as_str(), Some(id)) {
+					cache.spawn_invalidation(session_id, table.into_string(), id, notifications);
+				} else {
+					self.dispatch(
+						session_id,
+						None,
+						Command::Kill {
+							uuid: id,
+						},
+					)
+					.await
+					.ok();
+				}
+			}
+			Err(error) => {
+				// The entries still expire, and the live query is retried
+				// when the next record of the table is cached
+				debug!("Failed to watch the changes to the cached table {table}: {error}");
+				cache.watching(session_id, table.as_str(), None);
+			}
+		}
+	}
+
 	/// Execute the `query` method
 	pub(crate) fn execute_query(
 		&self,
 		session_id: Uuid,
+		trace_id: Option<String>,
 		command: Command,
 	) -> BoxFuture<'_, Result<Vec<QueryResult>>> {
 		Box::pin(async move {
-			let rx = self.send_command(session_id, command).await?;
+			let _permit = self.limiter.acquire().await?;
+			let rx = self.send_command(session_id, trace_id, command).await?;
 			self.recv_results(rx).await
 		})
 	}
@@ -279,3 +439,41 @@ pub trait Sealed: Sized + Send + Sync + 'static {
 	where
 		Self: crate::Connection;
 }
+
+/// Converts the result of a method operating on a single record
+pub(crate) fn opt_from_value<R>(value: Value) -> Result<Option<R>>
+where
+	R: SurrealValue,
+{
+	match value {
+		Value::None | Value::Null => Ok(None),
+		Value::Array(array) => match array.len() {
+			// Empty array means no results
+			0 => Ok(None),
+			// Single-element array: extract and return the element
+			// This happens when operating on a record ID
+			1 => Ok(Some(
+				R::from_value(array.into_iter().next().expect("array has exactly one element"))
+					.map_err(|e| {
+						crate::Error::serialization(
+							e.to_string(),
+							crate::types::SerializationError::Deserialization,
+						)
+					})?,
+			)),
+			// Multiple elements should not happen for operations expecting Option<T>
+			_ => Ok(Some(R::from_value(Value::Array(array)).map_err(|e| {
+				crate::Error::serialization(
+					e.to_string(),
+					crate::types::SerializationError::Deserialization,
+				)
+			})?)),
+		},
+		value => Ok(Some(R::from_value(value).map_err(|e| {
+			crate::Error::serialization(
+				e.to_string(),
+				crate::types::SerializationError::Deserialization,
+			)
+		})?)),
+	}
+}
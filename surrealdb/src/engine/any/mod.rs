@@ -256,6 +256,39 @@ where
 	}
 }
 
+/// Checks that requests can be balanced across the servers of an endpoint
+fn check_replicas(endpoint: &Endpoint) -> Result<()> {
+	let is_ws = |url: &Url| matches!(url.scheme(), "ws" | "wss");
+	if endpoint.replicas.is_empty() || (is_ws(&endpoint.url) && endpoint.replicas.iter().all(is_ws))
+	{
+		return Ok(());
+	}
+	Err(Error::configuration(
+		"Requests can only be balanced across several WebSocket endpoints".to_owned(),
+		None,
+	))
+}
+
+impl<T, const N: usize> IntoEndpoint for [T; N] where T: Into<String> {}
+impl<T, const N: usize> into_endpoint::Sealed for [T; N]
+where
+	T: Into<String>,
+{
+	fn into_endpoint(self) -> Result<Endpoint> {
+		Endpoint::balanced(self.into_iter().map(|url| url.into().into_endpoint()))
+	}
+}
+
+impl<T> IntoEndpoint for Vec<T> where T: Into<String> {}
+impl<T> into_endpoint::Sealed for Vec<T>
+where
+	T: Into<String>,
+{
+	fn into_endpoint(self) -> Result<Endpoint> {
+		Endpoint::balanced(self.into_iter().map(|url| url.into().into_endpoint()))
+	}
+}
+
 /// A dynamic connection that supports any engine and allows you to pick at
 /// runtime
 #[derive(Debug, Clone)]
@@ -305,6 +338,9 @@ impl Surreal<Any> {
 /// // Connect to a remote endpoint
 /// let db = connect("wss://cloud.surrealdb.com").await?;
 ///
+/// // Balance requests across several servers
+/// let db = connect(["ws://a.example.com:8000", "ws://b.example.com:8000"]).await?;
+///
 /// // Connect using HTTP
 /// let db = connect("http://localhost:8000").await?;
 ///
@@ -429,4 +465,14 @@ mod tests {
 		db.query("INFO FOR NS").await.unwrap().check().expect("client should have access to NS");
 		db.query("INFO FOR DB").await.unwrap().check().expect("client should have access to DB");
 	}
+
+	#[tokio::test]
+	async fn balancing_requires_websocket_endpoints() {
+		let endpoint =
+			into_endpoint::Sealed::into_endpoint(["ws://a:8000", "ws://b:8000"]).unwrap();
+		assert_eq!(endpoint.replicas.len(), 1);
+		check_replicas(&endpoint).unwrap();
+		let err = connect(["memory", "ws://localhost:8000"]).await.unwrap_err();
+		assert!(err.to_string().contains("WebSocket"), "{err}");
+	}
 }
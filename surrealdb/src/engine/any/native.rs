@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use tokio::sync::watch;
 #[cfg(feature = "protocol-ws")]
@@ -9,7 +10,9 @@ use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 
 #[allow(unused_imports, reason = "Used by the DB engines.")]
 use crate::ExtraFeatures;
-use crate::conn::Router;
+#[cfg(feature = "protocol-http")]
+use crate::conn::CompressionMetrics;
+use crate::conn::{ClientCache, LatencyMetrics, NotificationBroadcast, QueryLimiter, Router};
 #[allow(unused_imports, reason = "Used by the DB engines.")]
 use crate::engine;
 use crate::engine::any::Any;
@@ -42,18 +45,33 @@ impl conn::Sealed for Any {
 			let config = address.config.clone();
 			let session_clone = session_clone.unwrap_or_else(SessionClone::new);
 			let mut features = HashSet::new();
+			// Only broadcast by the local engines
+			let notifications = NotificationBroadcast::new(&config);
+			let mut compression = None;
+			let latency = Arc::<LatencyMetrics>::default();
+
+			super::check_replicas(&address)?;
 
-			match EndpointKind::from(address.url.scheme()) {
+			let kind = EndpointKind::from(address.url.scheme());
+			// Only the remote engines are worth caching in front of
+			let remote = matches!(
+				kind,
+				EndpointKind::Http | EndpointKind::Https | EndpointKind::Ws | EndpointKind::Wss
+			);
+
+			match kind {
 				EndpointKind::Memory => {
 					#[cfg(feature = "kv-mem")]
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						tokio::spawn(engine::local::native::run_router(
 							address,
 							conn_tx,
 							route_rx,
 							session_clone.receiver.clone(),
+							notifications.clone(),
 						));
 						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??
 					}
@@ -70,11 +88,13 @@ impl conn::Sealed for Any {
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						tokio::spawn(engine::local::native::run_router(
 							address,
 							conn_tx,
 							route_rx,
 							session_clone.receiver.clone(),
+							notifications.clone(),
 						));
 						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??
 					}
@@ -91,11 +111,13 @@ impl conn::Sealed for Any {
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						tokio::spawn(engine::local::native::run_router(
 							address,
 							conn_tx,
 							route_rx,
 							session_clone.receiver.clone(),
+							notifications.clone(),
 						));
 						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??
 					}
@@ -111,11 +133,13 @@ impl conn::Sealed for Any {
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						tokio::spawn(engine::local::native::run_router(
 							address,
 							conn_tx,
 							route_rx,
 							session_clone.receiver.clone(),
+							notifications.clone(),
 						));
 						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??
 					}
@@ -134,13 +158,28 @@ impl conn::Sealed for Any {
 						let base_url = address.url;
 
 						#[cfg(any(feature = "native-tls", feature = "rustls"))]
-						let client = http::native::create_client(&base_url, address.config.tls_config.as_ref())
-							.await?;
+						let client = http::native::create_client(
+							&base_url,
+							config.keepalive,
+							address.config.tls_config.as_ref(),
+						)
+						.await?;
 						#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
-						let client = http::native::create_client(&base_url).await?;
+						let client = http::native::create_client(&base_url, config.keepalive).await?;
 
+						http::native::warm_up(
+							&client,
+							&base_url,
+							config.warm_connections,
+							&latency,
+						)
+						.await?;
+						compression = CompressionMetrics::new(&config);
 						tokio::spawn(http::native::run_router(
 							client,
+							compression.clone(),
+							config.keepalive,
+							latency.clone(),
 							base_url,
 							route_rx,
 							session_clone.receiver.clone(),
@@ -165,6 +204,8 @@ impl conn::Sealed for Any {
 						} = address.config.websocket;
 
 						features.insert(ExtraFeatures::LiveQueries);
+
+						features.insert(ExtraFeatures::Transactions);
 						let mut endpoint = address;
 						endpoint.url = endpoint
 							.url
@@ -181,20 +222,33 @@ impl conn::Sealed for Any {
 							.max_write_buffer_size(max_write_buffer_size)
 							.write_buffer_size(write_buffer_size)
 							.read_buffer_size(read_buffer_size);
-						let socket = engine::remote::ws::native::connect(
-							&endpoint,
-							Some(config),
-							maybe_connector.clone(),
-						)
-						.await?;
-						tokio::spawn(engine::remote::ws::native::run_router(
-							endpoint,
-							maybe_connector,
-							config,
-							socket,
-							route_rx,
-							session_clone.receiver.clone(),
-						));
+						if endpoint.replicas.is_empty() {
+							let socket = engine::remote::ws::native::connect(
+								&endpoint,
+								Some(config),
+								maybe_connector.clone(),
+							)
+							.await?;
+							tokio::spawn(engine::remote::ws::native::run_router(
+								endpoint,
+								maybe_connector,
+								config,
+								socket,
+								latency.clone(),
+								route_rx,
+								session_clone.receiver.clone(),
+							));
+						} else {
+							engine::remote::ws::native::connect_balanced(
+								endpoint,
+								maybe_connector,
+								config,
+								latency.clone(),
+								route_rx,
+								session_clone.receiver.clone(),
+							)
+							.await?;
+						}
 					}
 
 					#[cfg(not(feature = "protocol-ws"))]
@@ -211,7 +265,17 @@ impl conn::Sealed for Any {
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
 				features,
+				limiter: QueryLimiter::new(&config).into(),
+				middleware: Default::default(),
+				cache: if remote {
+					ClientCache::new(&config)
+				} else {
+					None
+				},
+				compression,
+				latency,
 				config,
+				notifications: (!remote).then_some(notifications),
 				sender: route_tx,
 			};
 
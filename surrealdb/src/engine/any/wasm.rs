@@ -6,7 +6,7 @@ use wasm_bindgen_futures::spawn_local;
 
 #[allow(unused_imports, reason = "Used by the DB engines.")]
 use crate::ExtraFeatures;
-use crate::conn::Router;
+use crate::conn::{ClientCache, NotificationBroadcast, QueryLimiter, Router};
 #[allow(unused_imports, reason = "Used by the DB engines.")]
 use crate::engine;
 use crate::engine::any::Any;
@@ -38,17 +38,30 @@ impl conn::Sealed for Any {
 			let config = address.config.clone();
 			let session_clone = session_clone.unwrap_or_else(SessionClone::new);
 			let mut features = HashSet::new();
+			// Only broadcast by the local engines
+			let notifications = NotificationBroadcast::new(&config);
 
-			match EndpointKind::from(address.url.scheme()) {
+			super::check_replicas(&address)?;
+
+			let kind = EndpointKind::from(address.url.scheme());
+			// Only the remote engines are worth caching in front of
+			let remote = matches!(
+				kind,
+				EndpointKind::Http | EndpointKind::Https | EndpointKind::Ws | EndpointKind::Wss
+			);
+
+			match kind {
 				EndpointKind::IndxDb => {
 					#[cfg(feature = "kv-indxdb")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						spawn_local(engine::local::wasm::run_router(
 							address,
 							conn_tx,
 							route_rx,
 							session_clone.receiver.clone(),
+							notifications.clone(),
 						));
 						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
 					}
@@ -63,11 +76,13 @@ impl conn::Sealed for Any {
 					#[cfg(feature = "kv-mem")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						spawn_local(engine::local::wasm::run_router(
 							address,
 							conn_tx,
 							route_rx,
 							session_clone.receiver.clone(),
+							notifications.clone(),
 						));
 						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
 					}
@@ -82,11 +97,13 @@ impl conn::Sealed for Any {
 					#[cfg(feature = "kv-rocksdb")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						spawn_local(engine::local::wasm::run_router(
 							address,
 							conn_tx,
 							route_rx,
 							session_clone.receiver.clone(),
+							notifications.clone(),
 						));
 						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
 					}
@@ -99,11 +116,13 @@ impl conn::Sealed for Any {
 					#[cfg(feature = "kv-surrealkv")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						spawn_local(engine::local::wasm::run_router(
 							address,
 							conn_tx,
 							route_rx,
 							session_clone.receiver.clone(),
+							notifications.clone(),
 						));
 						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
 					}
@@ -118,11 +137,13 @@ impl conn::Sealed for Any {
 					#[cfg(feature = "kv-tikv")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						spawn_local(engine::local::wasm::run_router(
 							address,
 							conn_tx,
 							route_rx,
 							session_clone.receiver.clone(),
+							notifications.clone(),
 						));
 						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
 					}
@@ -154,18 +175,28 @@ impl conn::Sealed for Any {
 					#[cfg(feature = "protocol-ws")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Transactions);
 						let mut endpoint = address;
 						endpoint.url = endpoint
 							.url
 							.join(engine::remote::ws::PATH)
 							.map_err(crate::std_error_to_types_error)?;
-						spawn_local(engine::remote::ws::wasm::run_router(
-							endpoint,
-							conn_tx,
-							route_rx,
-							session_clone.receiver.clone(),
-						));
-						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
+						if endpoint.replicas.is_empty() {
+							spawn_local(engine::remote::ws::wasm::run_router(
+								endpoint,
+								conn_tx,
+								route_rx,
+								session_clone.receiver.clone(),
+							));
+							conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
+						} else {
+							engine::remote::ws::wasm::connect_balanced(
+								endpoint,
+								route_rx,
+								session_clone.receiver.clone(),
+							)
+							.await?;
+						}
 					}
 
 					#[cfg(not(feature = "protocol-ws"))]
@@ -182,7 +213,17 @@ impl conn::Sealed for Any {
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
 				features,
+				limiter: QueryLimiter::new(&config).into(),
+				middleware: Default::default(),
+				cache: if remote {
+					ClientCache::new(&config)
+				} else {
+					None
+				},
+				compression: None,
+				latency: Default::default(),
 				config,
+				notifications: (!remote).then_some(notifications),
 				sender: route_tx,
 			};
 
@@ -0,0 +1,313 @@
+//! A cache of embedded datastores keyed by path
+//!
+//! See [`Surreal::open_many`] for details.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Mutex, OnceCell};
+use tokio::time::Instant;
+
+use crate::engine::local::Db;
+use crate::opt::{Config, IntoEndpoint};
+use crate::{Result, Surreal};
+
+type ConfigFn = dyn Fn(&str) -> Config + Send + Sync;
+
+/// An embedded instance, along with the last time it was handed out
+///
+/// The instance is opened outside of the lock on the cache, so that opening
+/// one slow datastore does not hold up requests for the others. Concurrent
+/// requests for the same path wait on the same cell, so the path is still
+/// only opened once.
+struct Entry {
+	db: Arc<OnceCell<Surreal<Db>>>,
+	last_used: Instant,
+}
+
+/// A set of embedded datastores opened on demand and keyed by path
+///
+/// Returned by [`Surreal::open_many`]. Each distinct path is opened at most
+/// once and the resulting client is cached, so repeated calls to
+/// [`Databases::get`] with the same path share the same underlying
+/// datastore. When more than [`Databases::max_open`] instances are open, the
+/// least recently used instance is closed to make room for the new one.
+///
+/// Closing an instance drops the cached client. The datastore itself shuts
+/// down once every clone previously returned by [`Databases::get`] has also
+/// been dropped.
+pub struct Databases<S> {
+	entries: Arc<Mutex<HashMap<String, Entry>>>,
+	max_open: usize,
+	idle_timeout: Option<Duration>,
+	config: Arc<ConfigFn>,
+	scheme: PhantomData<fn() -> S>,
+}
+
+impl<S> Clone for Databases<S> {
+	fn clone(&self) -> Self {
+		Self {
+			entries: Arc::clone(&self.entries),
+			max_open: self.max_open,
+			idle_timeout: self.idle_timeout,
+			config: Arc::clone(&self.config),
+			scheme: PhantomData,
+		}
+	}
+}
+
+impl<S> fmt::Debug for Databases<S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Databases")
+			.field("max_open", &self.max_open)
+			.field("idle_timeout", &self.idle_timeout)
+			.finish_non_exhaustive()
+	}
+}
+
+impl Surreal<Db> {
+	/// Opens and caches multiple embedded datastores behind one handle
+	///
+	/// This is designed for test harnesses and tools which manage many
+	/// isolated databases, such as one database per tenant or per test. Each
+	/// path is opened lazily the first time it is requested, and idle
+	/// instances are closed in least-recently-used order once the configured
+	/// limit is reached.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// use std::time::Duration;
+	/// use surrealdb::Surreal;
+	/// use surrealdb::engine::local::RocksDb;
+	/// use surrealdb::opt::Config;
+	///
+	/// let dbs = Surreal::open_many::<RocksDb>()
+	///     .max_open(8)
+	///     .idle_timeout(Duration::from_secs(300))
+	///     .config(|_path| Config::new().query_timeout(Duration::from_secs(5)));
+	///
+	/// let tenant = dbs.get("data/tenant-a").await?;
+	/// tenant.use_ns("main").use_db("main").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn open_many<S>() -> Databases<S>
+	where
+		(String, Config): IntoEndpoint<S, Client = Db>,
+	{
+		Databases {
+			entries: Arc::new(Mutex::new(HashMap::new())),
+			max_open: usize::MAX,
+			idle_timeout: None,
+			config: Arc::new(|_| Config::default()),
+			scheme: PhantomData,
+		}
+	}
+}
+
+impl<S> Databases<S>
+where
+	(String, Config): IntoEndpoint<S, Client = Db>,
+{
+	/// Sets the maximum number of instances kept open at the same time
+	///
+	/// A value of `0` is treated as `1`. Defaults to no limit.
+	pub fn max_open(mut self, max_open: usize) -> Self {
+		self.max_open = max_open.max(1);
+		self
+	}
+
+	/// Closes instances which have not been requested for the given duration
+	///
+	/// Idle instances are closed lazily, whenever [`Databases::get`] or
+	/// [`Databases::close_idle`] is called.
+	pub fn idle_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+		self.idle_timeout = timeout.into().filter(|x| !x.is_zero());
+		self
+	}
+
+	/// Sets the configuration used when opening each instance
+	///
+	/// The closure receives the path of the instance being opened, which
+	/// allows per-instance settings such as different timeouts or
+	/// capabilities.
+	pub fn config<F>(mut self, config: F) -> Self
+	where
+		F: Fn(&str) -> Config + Send + Sync + 'static,
+	{
+		self.config = Arc::new(config);
+		self
+	}
+
+	/// Returns the client for the given path, opening it if necessary
+	pub async fn get(&self, path: impl Into<String>) -> Result<Surreal<Db>> {
+		let path = path.into();
+		let now = Instant::now();
+		let cell = {
+			let mut entries = self.entries.lock().await;
+			self.evict_idle(&mut entries, now);
+			// Return the cached instance if there is one
+			if let Some(entry) = entries.get_mut(&path) {
+				entry.last_used = now;
+				Arc::clone(&entry.db)
+			} else {
+				// Make room for the new instance
+				while entries.len() >= self.max_open {
+					let Some(oldest) = entries
+						.iter()
+						.min_by_key(|(_, entry)| entry.last_used)
+						.map(|(k, _)| k.clone())
+					else {
+						break;
+					};
+					trace!("Closing least recently used datastore `{oldest}`");
+					entries.remove(&oldest);
+				}
+				let cell = Arc::new(OnceCell::new());
+				entries.insert(
+					path.clone(),
+					Entry {
+						db: Arc::clone(&cell),
+						last_used: now,
+					},
+				);
+				cell
+			}
+		};
+		// Open the instance with its own configuration, unless it is open
+		let res = cell
+			.get_or_try_init(|| async {
+				let config = (self.config)(&path);
+				Surreal::<Db>::new::<S>((path.clone(), config)).await
+			})
+			.await;
+		match res {
+			Ok(db) => Ok(db.clone()),
+			Err(e) => {
+				// Forget the instance which failed to open, so that it does
+				// not count towards the open instances
+				let mut entries = self.entries.lock().await;
+				if entries.get(&path).is_some_and(|entry| Arc::ptr_eq(&entry.db, &cell)) {
+					entries.remove(&path);
+				}
+				Err(e)
+			}
+		}
+	}
+
+	/// Closes the instance for the given path, returning whether it was open
+	pub async fn close(&self, path: &str) -> bool {
+		self.entries.lock().await.remove(path).is_some()
+	}
+
+	/// Closes all instances which have exceeded the idle timeout, returning
+	/// how many were closed
+	pub async fn close_idle(&self) -> usize {
+		let mut entries = self.entries.lock().await;
+		let before = entries.len();
+		self.evict_idle(&mut entries, Instant::now());
+		before - entries.len()
+	}
+
+	/// Closes every open instance
+	pub async fn close_all(&self) {
+		self.entries.lock().await.clear();
+	}
+
+	/// Returns the paths of all currently open instances
+	pub async fn open_paths(&self) -> Vec<String> {
+		let entries = self.entries.lock().await;
+		entries.iter().filter(|(_, entry)| entry.db.initialized()).map(|(k, _)| k.clone()).collect()
+	}
+
+	fn evict_idle(&self, entries: &mut HashMap<String, Entry>, now: Instant) {
+		if let Some(timeout) = self.idle_timeout {
+			entries.retain(|path, entry| {
+				let keep = now.duration_since(entry.last_used) < timeout;
+				if !keep {
+					trace!("Closing idle datastore `{path}`");
+				}
+				keep
+			});
+		}
+	}
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use temp_dir::TempDir;
+
+	use super::*;
+	use crate::engine::local::Mem;
+
+	fn path(dir: &TempDir, name: &str) -> String {
+		dir.child(name).display().to_string()
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn reuses_open_instances() {
+		let dir = TempDir::new().unwrap();
+		let dbs = Surreal::open_many::<Mem>();
+		let a = dbs.get(path(&dir, "a")).await.unwrap();
+		a.use_ns("test").use_db("test").await.unwrap();
+		a.query("CREATE person:one").await.unwrap().check().unwrap();
+		// The same path returns the same datastore
+		let again = dbs.get(path(&dir, "a")).await.unwrap();
+		again.use_ns("test").use_db("test").await.unwrap();
+		let mut res = again.query("SELECT VALUE id FROM person").await.unwrap();
+		let ids: Vec<crate::types::RecordId> = res.take(0).unwrap();
+		assert_eq!(ids.len(), 1);
+		// A different path returns an isolated datastore
+		let b = dbs.get(path(&dir, "b")).await.unwrap();
+		b.use_ns("test").use_db("test").await.unwrap();
+		let mut res = b.query("SELECT VALUE id FROM person").await.unwrap();
+		let ids: Vec<crate::types::RecordId> = res.take(0).unwrap();
+		assert!(ids.is_empty());
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn opens_each_path_once_when_requested_concurrently() {
+		let dir = TempDir::new().unwrap();
+		let dbs = Surreal::open_many::<Mem>();
+		let (a, again) = tokio::join!(dbs.get(path(&dir, "a")), dbs.get(path(&dir, "a")));
+		let (a, again) = (a.unwrap(), again.unwrap());
+		a.use_ns("test").use_db("test").await.unwrap();
+		a.query("CREATE person:one").await.unwrap().check().unwrap();
+		// Both requests share the same datastore
+		again.use_ns("test").use_db("test").await.unwrap();
+		let mut res = again.query("SELECT VALUE id FROM person").await.unwrap();
+		let ids: Vec<crate::types::RecordId> = res.take(0).unwrap();
+		assert_eq!(ids.len(), 1);
+		assert_eq!(dbs.open_paths().await, vec![path(&dir, "a")]);
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn evicts_least_recently_used() {
+		let dir = TempDir::new().unwrap();
+		let dbs = Surreal::open_many::<Mem>().max_open(2);
+		dbs.get(path(&dir, "a")).await.unwrap();
+		dbs.get(path(&dir, "b")).await.unwrap();
+		// Touch `a` so that `b` becomes the least recently used
+		dbs.get(path(&dir, "a")).await.unwrap();
+		dbs.get(path(&dir, "c")).await.unwrap();
+		let mut open = dbs.open_paths().await;
+		open.sort();
+		assert_eq!(open, vec![path(&dir, "a"), path(&dir, "c")]);
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn closes_idle_instances() {
+		let dir = TempDir::new().unwrap();
+		let dbs = Surreal::open_many::<Mem>().idle_timeout(Duration::from_millis(10));
+		dbs.get(path(&dir, "a")).await.unwrap();
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		assert_eq!(dbs.close_idle().await, 1);
+		assert!(dbs.open_paths().await.is_empty());
+	}
+}
@@ -148,6 +148,8 @@
 //! }
 //! ```
 
+#[cfg(not(target_family = "wasm"))]
+mod many;
 #[cfg(not(target_family = "wasm"))]
 pub(crate) mod native;
 #[cfg(target_family = "wasm")]
@@ -166,18 +168,28 @@ use async_channel::{Receiver, Sender};
 #[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 use futures::StreamExt;
 #[cfg(not(target_family = "wasm"))]
+use futures::TryStreamExt;
+#[cfg(not(target_family = "wasm"))]
+use futures::future::Either;
+#[cfg(not(target_family = "wasm"))]
 use futures::stream::poll_fn;
 use surrealdb_core::dbs::{QueryResult, QueryResultBuilder, Session};
 use surrealdb_core::iam;
 #[cfg(not(target_family = "wasm"))]
 use surrealdb_core::kvs::export::Config as DbExportConfig;
-use surrealdb_core::kvs::{Datastore, LockType, Transaction, TransactionType};
+#[cfg(not(target_family = "wasm"))]
+use surrealdb_core::kvs::export::verify_checksum;
+#[cfg(not(target_family = "wasm"))]
+use surrealdb_core::kvs::import::Config as DbImportConfig;
+use surrealdb_core::kvs::{Datastore, LockType, Transaction, TransactionType, WriteLock};
 #[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 use surrealdb_core::{
 	iam::{Action, ResourceKind, check::check_ns_db},
 	ml::storage::surml_file::SurMlFile,
 };
 use surrealdb_types::Error as TypesError;
+#[cfg(not(target_family = "wasm"))]
+use tokio::spawn;
 use tokio::sync::RwLock;
 #[cfg(not(target_family = "wasm"))]
 use tokio::{
@@ -185,18 +197,25 @@ use tokio::{
 	io::{self, AsyncReadExt, AsyncWriteExt},
 };
 #[cfg(not(target_family = "wasm"))]
-use tokio_util::bytes::BytesMut;
+use tokio_util::bytes::{Bytes, BytesMut};
 use uuid::Uuid;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_futures::spawn_local as spawn;
 
 use crate::conn::Command;
 #[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 use crate::conn::MlExportConfig;
 use crate::engine::SessionError;
-use crate::opt::IntoEndpoint;
 use crate::opt::auth::{AccessToken, RefreshToken, SecureToken, Token};
+#[cfg(not(target_family = "wasm"))]
+use crate::opt::decrypt_stream;
+use crate::opt::{Format, IntoEndpoint};
 use crate::types::{HashMap, Notification, SurrealValue, ToSql, Value, Variables};
 use crate::{Connect, Surreal};
 
+#[cfg(not(target_family = "wasm"))]
+pub use self::many::Databases;
+
 /// In-memory database
 ///
 /// # Examples
@@ -434,6 +453,7 @@ impl RouterState {
 					vars: RwLock::new(state.vars.read().await.clone()),
 					transactions: HashMap::new(),
 					live_queries: HashMap::new(),
+					write_locks: Default::default(),
 				}))
 			}
 			Some(Err(error)) => Err(error),
@@ -443,8 +463,11 @@ impl RouterState {
 	}
 
 	/// Handle a session being dropped.
-	fn handle_session_drop(&self, session_id: Uuid) {
+	async fn handle_session_drop(&self, session_id: Uuid) {
 		self.sessions.remove(&session_id);
+		if let Err(error) = self.kvs.remove_session_tables(session_id).await {
+			warn!("Failed to remove the session tables of session '{session_id}'; {error}");
+		}
 	}
 
 	/// Dispatch a session-lifecycle event to the appropriate handler.
@@ -455,7 +478,7 @@ impl RouterState {
 				old,
 				new,
 			} => self.handle_session_clone(old, new).await,
-			crate::SessionId::Drop(id) => self.handle_session_drop(id),
+			crate::SessionId::Drop(id) => self.handle_session_drop(id).await,
 		}
 	}
 
@@ -492,6 +515,10 @@ struct SessionState {
 	vars: RwLock<Variables>,
 	transactions: HashMap<Uuid, Arc<Transaction>>,
 	live_queries: HashMap<Uuid, Sender<crate::Result<Notification>>>,
+	/// Datastore write locks held by this session. Kept out of the concurrent
+	/// map, which defers dropping removed values, so that a lock is released
+	/// as soon as it is removed.
+	write_locks: std::sync::Mutex<std::collections::HashMap<Uuid, WriteLock>>,
 }
 
 impl SessionState {
@@ -503,6 +530,7 @@ impl SessionState {
 			vars: RwLock::new(Variables::default()),
 			transactions: HashMap::new(),
 			live_queries: HashMap::new(),
+			write_locks: Default::default(),
 		}
 	}
 }
@@ -590,6 +618,34 @@ where
 	})
 }
 
+/// Sends the rows of each statement of a query to `bytes`, encoded in the
+/// requested format, one line at a time
+async fn send_query_bytes<S>(results: S, format: Format, bytes: Sender<crate::Result<Vec<u8>>>)
+where
+	S: futures::Stream<Item = Result<QueryResult, TypesError>>,
+{
+	let mut results = std::pin::pin!(results);
+	while let Some(res) = futures::StreamExt::next(&mut results).await {
+		let res = match res {
+			Ok(res) => res,
+			Err(error) => {
+				bytes.send(Err(error)).await.ok();
+				break;
+			}
+		};
+		let lines = match format {
+			Format::Ndjson => surrealdb_core::rpc::format::ndjson::lines(res),
+		};
+		for line in lines {
+			let line = line.map_err(|e| crate::Error::internal(e.to_string()));
+			// Dropping the results stops the query once the output is no longer read
+			if bytes.send(line).await.is_err() {
+				return;
+			}
+		}
+	}
+}
+
 async fn kill_live_query(
 	kvs: &Datastore,
 	id: Uuid,
@@ -602,11 +658,34 @@ async fn kill_live_query(
 	Ok(results)
 }
 
+/// Updates a session parameter in the live queries started by the session, so
+/// that their notifications are filtered with the current value
+async fn update_live_query_param(
+	kvs: &Datastore,
+	state: &SessionState,
+	key: &str,
+	value: Value,
+) -> Result<(), crate::Error> {
+	let ids = state.live_queries.to_vec().into_iter().map(|(id, _)| id).collect();
+	kvs.update_live_query_param(ids, key, value).await.map_err(crate::std_error_to_types_error)
+}
+
 async fn router(
 	kvs: &Arc<Datastore>,
 	state: &SessionState,
 	command: Command,
 ) -> Result<Vec<QueryResult>, crate::Error> {
+	// The authentication can not change while impersonating a user
+	if matches!(
+		command,
+		Command::Signup { .. }
+			| Command::Signin { .. }
+			| Command::Authenticate { .. }
+			| Command::Refresh { .. }
+	) {
+		iam::impersonate::ensure_not_impersonating(&*state.session.read().await)
+			.map_err(surrealdb_core::err::anyhow_to_types_error)?;
+	}
 	match command {
 		Command::Use {
 			namespace,
@@ -735,11 +814,42 @@ async fn router(
 			};
 			Ok(vec![result])
 		}
+		Command::Impersonate {
+			subject,
+		} => {
+			let query_result = QueryResultBuilder::started_now();
+			let result = {
+				match iam::impersonate::impersonate(
+					kvs,
+					&mut *state.session.write().await,
+					subject.into(),
+				)
+				.await
+				{
+					Ok(_) => query_result.finish_with_result(Ok(Value::None)),
+					Err(error) => query_result
+						.finish_with_result(Err(surrealdb_core::err::anyhow_to_types_error(error))),
+				}
+			};
+			Ok(vec![result])
+		}
+		Command::StopImpersonating => {
+			let query_result = QueryResultBuilder::started_now();
+			let result = {
+				match iam::impersonate::stop(&mut *state.session.write().await) {
+					Ok(_) => query_result.finish_with_result(Ok(Value::None)),
+					Err(error) => query_result
+						.finish_with_result(Err(TypesError::internal(error.to_string()))),
+				}
+			};
+			Ok(vec![result])
+		}
 		Command::Begin => {
 			let query_result = QueryResultBuilder::started_now();
 			let result = match kvs.transaction(TransactionType::Write, LockType::Optimistic).await {
 				Ok(txn) => {
 					let id = Uuid::now_v7();
+					txn.enable_key_locks(id);
 					state.transactions.insert(id, Arc::new(txn));
 					query_result.finish_with_result(Ok(Value::Uuid(id.into())))
 				}
@@ -818,6 +928,56 @@ async fn router(
 
 			Ok(response)
 		}
+		Command::IdempotentQuery {
+			query,
+			variables,
+			key,
+		} => {
+			// Merge session vars with query vars
+			let mut vars = state.vars.read().await.clone();
+			vars.extend(variables);
+			// Run the query, or return the results of a previous run
+			let session = state.session.read().await;
+			Ok(kvs.execute_idempotent(query.as_ref(), &session, Some(vars), &key).await?)
+		}
+		Command::QueryBytes {
+			txn,
+			query,
+			variables,
+			format,
+			bytes,
+		} => {
+			// Merge session vars with query vars
+			let mut vars = state.vars.read().await.clone();
+			vars.extend(variables);
+			let session = state.session.read().await.clone();
+
+			if let Some(txn_id) = txn {
+				// Statements in a transaction are sent once the query completes
+				let Some(tx) = state.transactions.get(&txn_id) else {
+					return Err(TypesError::not_found(
+						"Transaction not found".to_string(),
+						Some(surrealdb_types::NotFoundError::Transaction),
+					));
+				};
+				let results =
+					kvs.execute_with_transaction(query.as_ref(), &session, Some(vars), tx).await?;
+				let results = futures::stream::iter(results.into_iter().map(Ok));
+				spawn(send_query_bytes(results, format, bytes));
+			} else {
+				// Otherwise each statement is sent as soon as it completes
+				let kvs = Arc::clone(kvs);
+				spawn(async move {
+					match kvs.execute_stream(query.as_ref(), &session, Some(vars)) {
+						Ok(results) => send_query_bytes(results, format, bytes).await,
+						Err(error) => {
+							bytes.send(Err(error)).await.ok();
+						}
+					}
+				});
+			}
+			Ok(vec![QueryResultBuilder::instant_none()])
+		}
 
 		#[cfg(target_family = "wasm")]
 		Command::ExportFile {
@@ -1008,6 +1168,9 @@ async fn router(
 		#[cfg(not(target_family = "wasm"))]
 		Command::ImportFile {
 			path,
+			resume,
+			verify,
+			decryption,
 		} => {
 			let query_result = QueryResultBuilder::started_now();
 			let file = match OpenOptions::new().read(true).open(&path).await {
@@ -1041,15 +1204,27 @@ async fn router(
 					Err(e) => Poll::Ready(Some(Err(anyhow::anyhow!("{}", e)))),
 				}
 			});
+			let stream = match decryption {
+				Some(key) => Either::Left(decrypt_stream(stream, key).map_ok(Bytes::from)),
+				None => Either::Right(stream),
+			};
+			// Verified exports are checked against their trailer before anything is imported
+			let stream = match verify {
+				true => Either::Left(verify_checksum(stream)),
+				false => Either::Right(stream),
+			};
 
-			let responses = kvs
-				.execute_import(
-					&*state.session.read().await,
-					Some(state.vars.read().await.clone()),
-					stream,
-				)
-				.await
-				.map_err(crate::std_error_to_types_error)?;
+			let session = state.session.read().await.clone();
+			let vars = Some(state.vars.read().await.clone());
+			let responses = if resume {
+				// Resumable imports are identified by the file they read from
+				let id = tokio::fs::canonicalize(&path).await.unwrap_or(path);
+				let config = DbImportConfig::new(id.display().to_string());
+				kvs.execute_resumable_import(&session, vars, stream, config).await
+			} else {
+				kvs.execute_import(&session, vars, stream).await
+			}
+			.map_err(crate::std_error_to_types_error)?;
 
 			for response in responses {
 				response.result?;
@@ -1118,7 +1293,75 @@ async fn router(
 
 			Ok(vec![query_result.finish()])
 		}
-		Command::Health => Ok(vec![QueryResultBuilder::instant_none()]),
+		Command::Health => {
+			let report = kvs.health_report().await;
+			if !report.is_healthy() {
+				return Err(crate::Error::internal(format!(
+					"The datastore is unhealthy: {}",
+					report.errors.join(", ")
+				)));
+			}
+			Ok(vec![QueryResultBuilder::instant_none()])
+		}
+		Command::HealthReport => {
+			let query_result = QueryResultBuilder::started_now();
+			let report = kvs.health_report().await;
+			let report = crate::method::HealthReport {
+				readable: report.readable,
+				writable: report.writable,
+				latency: report.latency,
+				heartbeat_age: report.heartbeat_age,
+				live_query_lag: report.live_query_lag,
+				corrupt_records: report.corrupt_records,
+				errors: report.errors,
+			};
+			Ok(vec![query_result.finish_with_result(Ok(report.into_value()))])
+		}
+		Command::LockWrites {
+			id,
+			wait,
+			max_hold,
+		} => {
+			let query_result = QueryResultBuilder::started_now();
+			// Only root owners may edit any kind of resource on the root, as
+			// editors are limited to specific kinds
+			kvs.check(
+				&*state.session.read().await,
+				iam::Action::Edit,
+				iam::ResourceKind::Any.on_root(),
+			)
+			.map_err(crate::std_error_to_types_error)?;
+			let lock =
+				kvs.lock_writes(wait, max_hold).await.map_err(crate::std_error_to_types_error)?;
+			state.write_locks.lock().unwrap_or_else(|e| e.into_inner()).insert(id, lock);
+			Ok(vec![query_result.finish()])
+		}
+		Command::UnlockWrites {
+			id,
+		} => {
+			let lock = state.write_locks.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
+			match lock {
+				Some(lock) if lock.is_held() => Ok(vec![QueryResultBuilder::instant_none()]),
+				_ => Err(crate::Error::query(
+					"The write lock was forcibly released after being held for too long"
+						.to_string(),
+					None,
+				)),
+			}
+		}
+		Command::Snapshot {
+			name,
+		} => {
+			let query_result = QueryResultBuilder::started_now();
+			kvs.check(
+				&*state.session.read().await,
+				iam::Action::Edit,
+				iam::ResourceKind::Any.on_root(),
+			)
+			.map_err(crate::std_error_to_types_error)?;
+			kvs.snapshot(&name).await.map_err(crate::std_error_to_types_error)?;
+			Ok(vec![query_result.finish()])
+		}
 		Command::Version => {
 			let query_result = QueryResultBuilder::started_now();
 			Ok(vec![
@@ -1127,6 +1370,15 @@ async fn router(
 				))),
 			])
 		}
+		Command::Compatibility => {
+			let query_result = QueryResultBuilder::started_now();
+			let storage = kvs.storage_version().await.map_err(crate::std_error_to_types_error)?;
+			let value = Value::from_t(crate::types::object! {
+				version: surrealdb_core::env::VERSION.to_string(),
+				storage: storage.map(i64::from),
+			});
+			Ok(vec![query_result.finish_with_result(Ok(value))])
+		}
 		Command::Set {
 			key,
 			value,
@@ -1136,10 +1388,11 @@ async fn router(
 				.map_err(|e| crate::Error::internal(e.to_string()))?;
 			// Need to compute because certain keys might not be allowed to be set and those
 			// should be rejected by an error.
-			match value {
+			match value.clone() {
 				Value::None => state.vars.write().await.remove(&key),
-				v => state.vars.write().await.insert(key, v),
+				v => state.vars.write().await.insert(key.clone(), v),
 			};
+			update_live_query_param(kvs, state, &key, value).await?;
 
 			Ok(vec![query_result.finish()])
 		}
@@ -1148,6 +1401,7 @@ async fn router(
 		} => {
 			let query_result = QueryResultBuilder::started_now();
 			state.vars.write().await.remove(&key);
+			update_live_query_param(kvs, state, &key, Value::None).await?;
 			Ok(vec![query_result.finish()])
 		}
 		Command::SubscribeLive {
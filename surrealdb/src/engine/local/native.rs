@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::task::Poll;
 
@@ -12,8 +13,9 @@ use surrealdb_core::options::EngineOptions;
 use surrealdb_types::Notification;
 use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
-use crate::conn::{self, Route, Router};
+use crate::conn::{self, NotificationBroadcast, QueryLimiter, Route, Router};
 use crate::engine::local::{Db, SessionError};
 use crate::engine::tasks;
 use crate::method::BoxFuture;
@@ -40,18 +42,39 @@ impl conn::Sealed for Db {
 			let config = address.config.clone();
 			let session_clone = session_clone.unwrap_or_else(SessionClone::new);
 
-			tokio::spawn(run_router(address, conn_tx, route_rx, session_clone.receiver.clone()));
+			let notifications = NotificationBroadcast::new(&config);
+			let router = run_router(
+				address,
+				conn_tx,
+				route_rx,
+				session_clone.receiver.clone(),
+				notifications.clone(),
+			);
+			match config.compute_threads {
+				// Run the datastore on a dedicated runtime, isolated from the caller
+				Some(threads) => spawn_compute_runtime(threads, router)?,
+				None => {
+					tokio::spawn(router);
+				}
+			}
 
 			conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::Backup);
 			features.insert(ExtraFeatures::LiveQueries);
+			features.insert(ExtraFeatures::Transactions);
 
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
 				features,
+				limiter: QueryLimiter::new(&config).into(),
+				middleware: Default::default(),
+				cache: None,
+				compression: None,
+				latency: Default::default(),
 				config,
+				notifications: Some(notifications),
 				sender: route_tx,
 			};
 
@@ -76,6 +99,9 @@ impl Surreal<Db> {
 		let (conn_tx, conn_rx) = async_channel::bounded::<Result<()>>(1);
 		let session_clone = SessionClone::new();
 		let recv = session_clone.receiver.clone();
+		let config = crate::opt::Config::default();
+		let broadcast = NotificationBroadcast::new(&config);
+		let sender = broadcast.clone();
 
 		tokio::spawn(async move {
 			conn_tx.send(Ok(())).await.ok();
@@ -87,7 +113,8 @@ impl Surreal<Db> {
 
 			let tasks = tasks::init(Arc::clone(&router_state.kvs), canceller.clone(), &engine);
 
-			router_loop(&router_state, canceller, tasks, route_rx, recv, notifications).await;
+			router_loop(&router_state, canceller, tasks, route_rx, recv, notifications, sender)
+				.await;
 
 			router_state.kvs.shutdown().await
 		});
@@ -97,11 +124,18 @@ impl Surreal<Db> {
 		let mut features = HashSet::new();
 		features.insert(ExtraFeatures::Backup);
 		features.insert(ExtraFeatures::LiveQueries);
+		features.insert(ExtraFeatures::Transactions);
 
 		let waiter = watch::channel(Some(WaitFor::Connection));
 		let router = Router {
 			features,
-			config: crate::opt::Config::default(),
+			limiter: Default::default(),
+			middleware: Default::default(),
+			cache: None,
+			compression: None,
+			latency: Default::default(),
+			config,
+			notifications: Some(broadcast),
 			sender: route_tx,
 		};
 
@@ -114,6 +148,7 @@ pub(crate) async fn run_router(
 	conn_tx: Sender<Result<()>>,
 	route_rx: Receiver<Route>,
 	session_rx: Receiver<SessionId>,
+	broadcast: NotificationBroadcast,
 ) {
 	let configured_root = match address.config.auth {
 		Level::Root => Some(Root {
@@ -128,13 +163,43 @@ pub(crate) async fn run_router(
 		_ => &address.path,
 	};
 
+	#[cfg(storage)]
+	let snapshot = address.config.snapshot;
+	#[cfg(not(storage))]
+	let snapshot: Option<String> = None;
+
+	#[cfg(storage)]
+	let temporary_directory = address.config.temporary_directory;
+	#[cfg(not(storage))]
+	let temporary_directory: Option<PathBuf> = None;
+
+	// Open a copy of the named snapshot instead of the datastore, if one was
+	// configured, so that the snapshot itself is never written to
+	let snapshot_copy = match &snapshot {
+		Some(name) => match snapshot_endpoint(
+			address.url.scheme(),
+			&address.path,
+			name,
+			temporary_directory.as_deref(),
+		) {
+			Ok(copy) => Some(copy),
+			Err(error) => {
+				conn_tx.send(Err(error)).await.ok();
+				return;
+			}
+		},
+		None => None,
+	};
+	let endpoint =
+		snapshot_copy.as_ref().map(|(endpoint, _)| endpoint.as_str()).unwrap_or(endpoint);
+
 	let builder = Datastore::builder()
 		.with_query_timeout(address.config.query_timeout)
 		.with_transaction_timeout(address.config.transaction_timeout)
 		.with_auth(configured_root.is_some());
 
 	#[cfg(storage)]
-	let builder = builder.with_temporary_directory(address.config.temporary_directory);
+	let builder = builder.with_temporary_directory(temporary_directory);
 
 	let (notify, builder) = if address.config.capabilities.allows_live_query_notifications() {
 		let (send, recv) =
@@ -144,30 +209,50 @@ pub(crate) async fn run_router(
 		(None, builder)
 	};
 
-	let builder = builder.with_capabilities(address.config.capabilities);
+	let capabilities = address.config.capabilities.with_guardrails(address.config.guardrails);
+	let builder = builder.with_capabilities(capabilities);
+
+	let builder = match address.config.blocking_threads {
+		Some(threads) => builder.with_blocking_threads(threads),
+		None => builder,
+	};
+
+	let builder = match address.config.core_pinning {
+		Some(enabled) => builder.with_core_pinning(enabled),
+		None => builder,
+	};
 
 	let kvs = match builder.build_with_path(endpoint).await {
 		Ok(kvs) => {
+			// Snapshots are opened read-only, before anything could be written
+			let kvs = kvs.with_read_only(snapshot.is_some());
 			if let Err(error) = kvs.check_version().await {
 				conn_tx.send(Err(crate::Error::internal(error.to_string()))).await.ok();
+				drop(kvs);
+				remove_snapshot_copy(snapshot_copy);
 				return;
 			};
-			if let Err(error) = kvs.bootstrap().await {
-				conn_tx.send(Err(crate::Error::internal(error.to_string()))).await.ok();
-				return;
-			}
-			// If a root user is specified, setup the initial datastore credentials
-			if let Some(root) = &configured_root
-				&& let Err(error) = kvs.initialise_credentials(&root.username, &root.password).await
-			{
-				conn_tx.send(Err(crate::Error::internal(error.to_string()))).await.ok();
-				return;
+			// Snapshots were taken of a datastore which was already set up
+			if snapshot.is_none() {
+				if let Err(error) = kvs.bootstrap().await {
+					conn_tx.send(Err(crate::Error::internal(error.to_string()))).await.ok();
+					return;
+				}
+				// If a root user is specified, setup the initial datastore credentials
+				if let Some(root) = &configured_root
+					&& let Err(error) =
+						kvs.initialise_credentials(&root.username, &root.password).await
+				{
+					conn_tx.send(Err(crate::Error::internal(error.to_string()))).await.ok();
+					return;
+				}
 			}
 			conn_tx.send(Ok(())).await.ok();
 			kvs
 		}
 		Err(error) => {
 			conn_tx.send(Err(crate::Error::internal(error.to_string()))).await.ok();
+			remove_snapshot_copy(snapshot_copy);
 			return;
 		}
 	};
@@ -192,11 +277,94 @@ pub(crate) async fn run_router(
 	if let Some(interval) = address.config.changefeed_gc_interval {
 		opt.changefeed_gc_interval = interval;
 	}
-	let tasks = tasks::init(Arc::clone(&router_state.kvs), canceller.clone(), &opt);
+	// The background tasks write to the datastore, so are not run on snapshots
+	let tasks = match snapshot {
+		Some(_) => tasks::Tasks::none(),
+		None => tasks::init(Arc::clone(&router_state.kvs), canceller.clone(), &opt),
+	};
 
-	router_loop(&router_state, canceller, tasks, route_rx, session_rx, notify).await;
+	router_loop(&router_state, canceller, tasks, route_rx, session_rx, notify, broadcast).await;
 
 	router_state.kvs.shutdown().await.ok();
+	drop(router_state);
+
+	remove_snapshot_copy(snapshot_copy);
+}
+
+/// Removes the copy of a snapshot which was opened, once it is closed
+fn remove_snapshot_copy(copy: Option<(String, PathBuf)>) {
+	if let Some((_, dir)) = copy
+		&& let Err(error) = std::fs::remove_dir_all(&dir)
+	{
+		warn!("Failed to remove the snapshot copy at {}: {error}", dir.display());
+	}
+}
+
+/// Runs the router on a dedicated multi-threaded runtime with the given number
+/// of worker threads, shutting the runtime down once the router has stopped
+fn spawn_compute_runtime(
+	threads: usize,
+	router: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+	let runtime = tokio::runtime::Builder::new_multi_thread()
+		.worker_threads(threads)
+		.thread_name("surrealdb-compute")
+		.enable_all()
+		.build()
+		.map_err(crate::std_error_to_types_error)?;
+	let handle = runtime.spawn(router);
+	// The runtime can not be dropped from within an async context
+	std::thread::Builder::new()
+		.name("surrealdb-compute-main".to_string())
+		.spawn(move || {
+			runtime.block_on(handle).ok();
+		})
+		.map_err(crate::std_error_to_types_error)?;
+	Ok(())
+}
+
+/// Copies the named snapshot of the datastore at `path` into a temporary
+/// directory, returning the endpoint and the directory of the copy
+fn snapshot_endpoint(
+	scheme: &str,
+	path: &str,
+	name: &str,
+	temporary_directory: Option<&Path>,
+) -> Result<(String, PathBuf)> {
+	match EndpointKind::from(scheme) {
+		EndpointKind::RocksDb | EndpointKind::SurrealKv => {}
+		_ => {
+			return Err(crate::Error::internal(
+				"The protocol or storage engine does not support snapshots".to_string(),
+			));
+		}
+	}
+	// Separate the query parameters, which also apply to the snapshot
+	let (endpoint, query) = match path.split_once('?') {
+		Some((endpoint, query)) => (endpoint, Some(query)),
+		None => (path, None),
+	};
+	let (scheme, path) = endpoint
+		.split_once("://")
+		.ok_or_else(|| crate::Error::internal(format!("Invalid endpoint: {endpoint}")))?;
+	let path = surrealdb_core::kvs::snapshot_path(path, name)
+		.map_err(|error| crate::Error::internal(error.to_string()))?;
+	if !path.is_dir() {
+		return Err(crate::Error::internal(format!("The snapshot '{name}' does not exist")));
+	}
+	// The storage engines write to the directory they open
+	let copy = temporary_directory
+		.map(Path::to_path_buf)
+		.unwrap_or_else(std::env::temp_dir)
+		.join(format!("surrealdb-snapshot-{}", Uuid::now_v7()));
+	// A copy which fails is removed by the copy itself
+	surrealdb_core::kvs::copy_snapshot(&path, &copy)
+		.map_err(|error| crate::Error::internal(error.to_string()))?;
+	let endpoint = match query {
+		Some(query) => format!("{scheme}://{}?{query}", copy.display()),
+		None => format!("{scheme}://{}", copy.display()),
+	};
+	Ok((endpoint, copy))
 }
 
 async fn router_loop(
@@ -206,6 +374,7 @@ async fn router_loop(
 	route_rx: Receiver<Route>,
 	session_rx: Receiver<SessionId>,
 	notification: Option<Receiver<Notification>>,
+	broadcast: NotificationBroadcast,
 ) {
 	let mut notifications = notification.map(Box::pin);
 	let mut notification_stream = poll_fn(move |cx| match &mut notifications {
@@ -260,6 +429,7 @@ async fn router_loop(
 				let Some(notification) = notification else {
 					continue
 				};
+				broadcast.send(&notification);
 				let Some(session_id) = notification.session.map(|x| x.into_inner()) else {
 					continue
 				};
@@ -12,7 +12,7 @@ use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 use wasm_bindgen_futures::spawn_local;
 
-use crate::conn::{self, Route, Router};
+use crate::conn::{self, NotificationBroadcast, QueryLimiter, Route, Router};
 use crate::engine::local::{Db, SessionError};
 use crate::engine::tasks;
 use crate::method::BoxFuture;
@@ -39,17 +39,31 @@ impl conn::Sealed for Db {
 			let config = address.config.clone();
 			let session_clone = session_clone.unwrap_or_else(SessionClone::new);
 
-			spawn_local(run_router(address, conn_tx, route_rx, session_clone.receiver.clone()));
+			let notifications = NotificationBroadcast::new(&config);
+			spawn_local(run_router(
+				address,
+				conn_tx,
+				route_rx,
+				session_clone.receiver.clone(),
+				notifications.clone(),
+			));
 
 			conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::LiveQueries);
+			features.insert(ExtraFeatures::Transactions);
 
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
 				features,
+				limiter: QueryLimiter::new(&config).into(),
+				middleware: Default::default(),
+				cache: None,
+				compression: None,
+				latency: Default::default(),
 				config,
+				notifications: Some(notifications),
 				sender: route_tx,
 			};
 
@@ -63,6 +77,7 @@ pub(crate) async fn run_router(
 	conn_tx: Sender<Result<()>>,
 	route_rx: Receiver<Route>,
 	session_rx: Receiver<SessionId>,
+	broadcast: NotificationBroadcast,
 ) {
 	let configured_root = match address.config.auth {
 		Level::Root => Some(Root {
@@ -85,6 +100,9 @@ pub(crate) async fn run_router(
 		(None, builder)
 	};
 
+	let capabilities = address.config.capabilities.with_guardrails(address.config.guardrails);
+	let builder = builder.with_capabilities(capabilities);
+
 	let kvs = match builder.build_with_path(&address.path).await {
 		Ok(kvs) => {
 			if let Err(error) = kvs.check_version().await {
@@ -190,6 +208,7 @@ pub(crate) async fn run_router(
 				let Some(notification) = notification else {
 					continue
 				};
+				broadcast.send(&notification);
 				let Some(session_id) = notification.session.map(|x| x.into_inner()) else {
 					continue
 				};
@@ -0,0 +1,137 @@
+//! Streaming decompression of the RPC responses of the HTTP engine.
+//!
+//! When compression is enabled, RPC requests ask the server for a zstd or
+//! gzip encoded response. The body is decompressed chunk by chunk as it is
+//! received, so that a large result set is never held both compressed and
+//! decompressed, and the decompressed bytes are then decoded as usual.
+
+use std::io::{self, Write};
+
+use futures::StreamExt;
+use reqwest::Response;
+use reqwest::header::{CONTENT_ENCODING, HeaderValue};
+use surrealdb_types::{ConnectionError, SerializationError};
+
+use crate::conn::CompressionMetrics;
+use crate::{Error, Result};
+
+/// The encodings accepted for RPC responses, in order of preference
+pub(super) const ACCEPT_ENCODING: HeaderValue = HeaderValue::from_static("zstd, gzip");
+
+enum Decoder {
+	Identity(Vec<u8>),
+	Gzip(flate2::write::GzDecoder<Vec<u8>>),
+	Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+}
+
+impl Decoder {
+	fn new(response: &Response) -> Result<Self> {
+		let Some(encoding) = response.headers().get(CONTENT_ENCODING) else {
+			return Ok(Self::Identity(Vec::new()));
+		};
+		match encoding.as_bytes() {
+			b"identity" => Ok(Self::Identity(Vec::new())),
+			b"gzip" => Ok(Self::Gzip(flate2::write::GzDecoder::new(Vec::new()))),
+			b"zstd" => Ok(Self::Zstd(
+				zstd::stream::write::Decoder::new(Vec::new()).map_err(decompression_error)?,
+			)),
+			other => Err(Error::connection(
+				format!(
+					"The server responded with an unsupported content encoding: {}",
+					String::from_utf8_lossy(other)
+				),
+				ConnectionError::ConnectionFailed,
+			)),
+		}
+	}
+
+	fn write(&mut self, chunk: &[u8]) -> io::Result<()> {
+		match self {
+			Self::Identity(body) => {
+				body.extend_from_slice(chunk);
+				Ok(())
+			}
+			Self::Gzip(decoder) => decoder.write_all(chunk),
+			Self::Zstd(decoder) => decoder.write_all(chunk),
+		}
+	}
+
+	fn finish(self) -> io::Result<Vec<u8>> {
+		match self {
+			Self::Identity(body) => Ok(body),
+			Self::Gzip(decoder) => decoder.finish(),
+			Self::Zstd(mut decoder) => {
+				decoder.flush()?;
+				Ok(decoder.into_inner())
+			}
+		}
+	}
+}
+
+/// Reads the body of a response, decompressing it as it is received
+///
+/// The bytes saved by a compressed response are added to the metrics of the
+/// connection.
+pub(super) async fn read_body(
+	response: Response,
+	metrics: Option<&CompressionMetrics>,
+) -> Result<Vec<u8>> {
+	let mut decoder = Decoder::new(&response)?;
+	let compressed = !matches!(decoder, Decoder::Identity(_));
+	let mut received = 0;
+	let mut chunks = response.bytes_stream();
+	while let Some(chunk) = chunks.next().await {
+		let chunk = chunk.map_err(crate::std_error_to_types_error)?;
+		received += chunk.len() as u64;
+		decoder.write(&chunk).map_err(decompression_error)?;
+	}
+	let body = decoder.finish().map_err(decompression_error)?;
+	if compressed && let Some(metrics) = metrics {
+		metrics.record(received, body.len() as u64);
+	}
+	Ok(body)
+}
+
+fn decompression_error(error: io::Error) -> Error {
+	Error::serialization(
+		format!("Failed to decompress the response of the server: {error}"),
+		SerializationError::Deserialization,
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn decode(mut decoder: Decoder, encoded: &[u8]) -> Vec<u8> {
+		// Feed the body in small chunks, as it would be received
+		for chunk in encoded.chunks(7) {
+			decoder.write(chunk).unwrap();
+		}
+		decoder.finish().unwrap()
+	}
+
+	#[test]
+	fn decompresses_in_chunks() {
+		let body = b"[{ id: person:1 }, { id: person:2 }]".repeat(100);
+
+		let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+		gzip.write_all(&body).unwrap();
+		let gzip = gzip.finish().unwrap();
+		assert!(gzip.len() < body.len());
+		let decoder = Decoder::Gzip(flate2::write::GzDecoder::new(Vec::new()));
+		assert_eq!(decode(decoder, &gzip), body);
+
+		let zstd = zstd::stream::encode_all(body.as_slice(), 0).unwrap();
+		assert!(zstd.len() < body.len());
+		let decoder = Decoder::Zstd(zstd::stream::write::Decoder::new(Vec::new()).unwrap());
+		assert_eq!(decode(decoder, &zstd), body);
+	}
+
+	#[test]
+	fn rejects_corrupt_bodies() {
+		let mut decoder = Decoder::Gzip(flate2::write::GzDecoder::new(Vec::new()));
+		let result = decoder.write(b"not gzip").and_then(|_| decoder.finish().map(drop));
+		assert!(result.is_err());
+	}
+}
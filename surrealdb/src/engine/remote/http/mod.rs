@@ -87,18 +87,29 @@
 //! For multi-node deployments without sticky session support at the infrastructure level,
 //! prefer WebSocket connections which maintain session affinity through persistent connections.
 
+#[cfg(not(target_family = "wasm"))]
+mod compression;
 #[cfg(not(target_family = "wasm"))]
 pub(crate) mod native;
 #[cfg(target_family = "wasm")]
 pub(crate) mod wasm;
 
 use std::marker::PhantomData;
+use std::ops::Deref;
 #[cfg(not(target_family = "wasm"))]
 use std::path::PathBuf;
+#[cfg(not(target_family = "wasm"))]
+use std::pin::pin;
 use std::sync::Arc;
 
 use futures::TryStreamExt;
+#[cfg(not(target_family = "wasm"))]
+use futures::future::Either;
+#[cfg(not(target_family = "wasm"))]
+use futures::{Stream, StreamExt};
 use reqwest::RequestBuilder;
+#[cfg(not(target_family = "wasm"))]
+use reqwest::header::ACCEPT_ENCODING;
 use reqwest::header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderValue};
 use serde::{Deserialize, Serialize};
 use surrealdb_core::dbs::{QueryResult, QueryResultBuilder};
@@ -119,17 +130,20 @@ use uuid::Uuid;
 #[cfg(target_family = "wasm")]
 use wasm_bindgen_futures::spawn_local;
 
-use crate::conn::{Command, RequestData};
+#[cfg(not(target_family = "wasm"))]
+use crate::conn::CompressionMetrics;
+use crate::conn::{Command, RequestData, new_trace_id};
 use crate::engine::SessionError;
 use crate::engine::remote::RouterRequest;
 use crate::headers::{AUTH_DB, AUTH_NS, DB, NS};
-use crate::opt::IntoEndpoint;
 use crate::opt::auth::{AccessToken, Token};
-use crate::types::{HashMap, SurrealValue, Value};
+#[cfg(not(target_family = "wasm"))]
+use crate::opt::{EncryptionKey, decrypt_stream};
+use crate::opt::{Format, IntoEndpoint};
+use crate::protocol::PATH as RPC_PATH;
+use crate::types::{HashMap, SurrealValue, ToSql, Value};
 use crate::{Connect, Error, Result, Surreal};
 
-const RPC_PATH: &str = "rpc";
-
 /// Per-session state for HTTP connections.
 /// Uses RwLock for headers and auth to allow concurrent request handling
 /// without cloning the entire state for each request.
@@ -169,6 +183,23 @@ impl SessionState {
 
 type SessionResult = std::result::Result<Arc<SessionState>, SessionError>;
 
+/// The HTTP client of a connection
+#[derive(Debug, Clone)]
+struct HttpClient {
+	inner: reqwest::Client,
+	/// The metrics of compressed responses, unless compression is disabled
+	#[cfg(not(target_family = "wasm"))]
+	compression: Option<Arc<CompressionMetrics>>,
+}
+
+impl Deref for HttpClient {
+	type Target = reqwest::Client;
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
+
 /// Router state for HTTP connections
 struct RouterState {
 	/// Per-session state (headers, auth for REST endpoints, replay commands)
@@ -177,14 +208,14 @@ struct RouterState {
 	/// On native platforms, this client is configured with a resolved address
 	/// via `reqwest::ClientBuilder::resolve()` to ensure all requests go to
 	/// the same server node, avoiding issues with DNS round-robin.
-	client: reqwest::Client,
+	client: HttpClient,
 	/// The base URL for the SurrealDB server
 	base_url: Url,
 }
 
 impl RouterState {
 	/// Creates a new RouterState with the given client and base URL
-	fn new(client: reqwest::Client, base_url: Url) -> Self {
+	fn new(client: HttpClient, base_url: Url) -> Self {
 		Self {
 			sessions: HashMap::new(),
 			client,
@@ -513,8 +544,69 @@ async fn export_bytes(request: RequestBuilder, bytes: BackupSender) -> Result<()
 	Ok(())
 }
 
+/// Sends the body of a newline-delimited response to `bytes` one line at a
+/// time, as the lines arrive
+async fn stream_lines(request: RequestBuilder, bytes: BackupSender) -> Result<()> {
+	let response = request
+		.send()
+		.await
+		.map_err(crate::std_error_to_types_error)?
+		.error_for_status()
+		.map_err(crate::std_error_to_types_error)?;
+
+	let future = async move {
+		let mut response = response.bytes_stream();
+		let mut buffer = Vec::new();
+		while let Ok(Some(b)) = response.try_next().await {
+			// The chunks of the response need not end on a line
+			let mut start = buffer.len();
+			buffer.extend_from_slice(&b);
+			while let Some(end) = buffer[start..].iter().position(|c| *c == b'\n') {
+				let line = buffer.drain(..=start + end).collect();
+				if bytes.send(Ok(line)).await.is_err() {
+					return;
+				}
+				start = 0;
+			}
+		}
+		if !buffer.is_empty() {
+			bytes.send(Ok(buffer)).await.ok();
+		}
+	};
+
+	#[cfg(not(target_family = "wasm"))]
+	tokio::spawn(future);
+
+	#[cfg(target_family = "wasm")]
+	spawn_local(future);
+
+	Ok(())
+}
+
+/// Reads a file in chunks, so that it can be decrypted or verified before it is sent
 #[cfg(not(target_family = "wasm"))]
-async fn import(request: RequestBuilder, path: PathBuf) -> Result<()> {
+fn file_chunks(file: tokio::fs::File) -> impl Stream<Item = Result<Vec<u8>>> {
+	futures::stream::unfold(Some(file), |file| async move {
+		let mut file = file?;
+		let mut buffer = vec![0; 4096];
+		match io::AsyncReadExt::read(&mut file, &mut buffer).await {
+			Ok(0) => None,
+			Ok(len) => {
+				buffer.truncate(len);
+				Some((Ok(buffer), Some(file)))
+			}
+			Err(error) => Some((Err(Error::internal(error.to_string())), None)),
+		}
+	})
+}
+
+#[cfg(not(target_family = "wasm"))]
+async fn import(
+	request: RequestBuilder,
+	path: PathBuf,
+	verify: bool,
+	decryption: Option<EncryptionKey>,
+) -> Result<()> {
 	let file = match OpenOptions::new().read(true).open(&path).await {
 		Ok(path) => path,
 		Err(error) => {
@@ -522,9 +614,30 @@ async fn import(request: RequestBuilder, path: PathBuf) -> Result<()> {
 		}
 	};
 
+	let body = match (decryption, verify) {
+		(None, false) => reqwest::Body::from(file),
+		// Encrypted exports are decrypted before they are sent to the server
+		(Some(key), false) => reqwest::Body::wrap_stream(decrypt_stream(file_chunks(file), key)),
+		// Verified exports are checked before they are sent, so that nothing is
+		// imported from an export which fails verification
+		(decryption, true) => {
+			let mut chunks = pin!(match decryption {
+				Some(key) => Either::Left(decrypt_stream(file_chunks(file), key)),
+				None => Either::Right(file_chunks(file)),
+			});
+			let mut export = Vec::new();
+			while let Some(chunk) = chunks.next().await {
+				export.extend_from_slice(&chunk?);
+			}
+			surrealdb_core::kvs::export::verify_trailer(&export)
+				.map_err(crate::std_error_to_types_error)?;
+			reqwest::Body::from(export)
+		}
+	};
+
 	let res = request
 		.header(ACCEPT, surrealdb_core::api::format::FLATBUFFERS)
-		.body(file)
+		.body(body)
 		.send()
 		.await
 		.map_err(crate::std_error_to_types_error)?;
@@ -600,12 +713,17 @@ pub(crate) async fn health(request: RequestBuilder) -> Result<()> {
 async fn send_request(
 	req: RouterRequest,
 	base_url: &Url,
-	client: &reqwest::Client,
+	client: &HttpClient,
 	headers: &HeaderMap,
 	auth: &Option<Auth>,
 ) -> Result<Vec<QueryResult>> {
 	let url = base_url.join(RPC_PATH).expect("valid RPC path");
 
+	// Identify the request in the server logs
+	let mut req = req;
+	if req.trace_id.is_none() {
+		req.trace_id = Some(new_trace_id());
+	}
 	let req_value = req.into_value();
 	let body = surrealdb_core::rpc::format::flatbuffers::encode(&req_value)
 		.map_err(|x| format!("Failed to serialize to flatbuffers: {x}"))
@@ -618,13 +736,21 @@ async fn send_request(
 	// Include auth header so the server can authenticate the request and maintain
 	// session state. This is essential for token-based auth flows where the server
 	// extracts namespace/database from JWT claims during authenticate().
-	let http_req = client.post(url).headers(headers.clone()).auth(auth).body(body);
+	#[cfg_attr(target_family = "wasm", expect(unused_mut))]
+	let mut http_req = client.post(url).headers(headers.clone()).auth(auth).body(body);
+	#[cfg(not(target_family = "wasm"))]
+	if client.compression.is_some() {
+		http_req = http_req.header(ACCEPT_ENCODING, compression::ACCEPT_ENCODING);
+	}
 	let response = http_req
 		.send()
 		.await
 		.map_err(crate::std_error_to_types_error)?
 		.error_for_status()
 		.map_err(crate::std_error_to_types_error)?;
+	#[cfg(not(target_family = "wasm"))]
+	let bytes = compression::read_body(response, client.compression.as_deref()).await?;
+	#[cfg(target_family = "wasm")]
 	let bytes = response.bytes().await.map_err(crate::std_error_to_types_error)?;
 
 	let response: DbResponse = surrealdb_core::rpc::format::flatbuffers::decode(&bytes)
@@ -647,7 +773,7 @@ async fn send_request(
 async fn refresh_token(
 	token: CoreToken,
 	base_url: &Url,
-	client: &reqwest::Client,
+	client: &HttpClient,
 	headers: &HeaderMap,
 	auth: &Option<Auth>,
 	session_id: Option<uuid::Uuid>,
@@ -671,10 +797,11 @@ async fn refresh_token(
 async fn router(
 	req: RequestData,
 	base_url: &Url,
-	client: &reqwest::Client,
+	client: &HttpClient,
 	session_state: &SessionState,
 ) -> Result<Vec<QueryResult>> {
 	let session_id = req.session_id;
+	let trace_id = req.trace_id;
 	match req.command {
 		Command::Use {
 			namespace,
@@ -1073,7 +1200,15 @@ async fn router(
 		#[cfg(not(target_family = "wasm"))]
 		Command::ImportFile {
 			path,
+			resume,
+			verify,
+			decryption,
 		} => {
+			if resume {
+				return Err(Error::internal(
+					"Resuming imports is not supported by the HTTP protocol".to_string(),
+				));
+			}
 			let req_path = base_url.join("import").map_err(crate::std_error_to_types_error)?;
 			let headers = session_state.headers.read().await;
 			let auth = session_state.auth.read().await;
@@ -1082,7 +1217,7 @@ async fn router(
 				.headers(headers.clone())
 				.auth(&auth)
 				.header(CONTENT_TYPE, "application/octet-stream");
-			import(request, path).await?;
+			import(request, path, verify, decryption).await?;
 			Ok(vec![QueryResultBuilder::instant_none()])
 		}
 		#[cfg(not(target_family = "wasm"))]
@@ -1101,7 +1236,7 @@ async fn router(
 				.headers(headers.clone())
 				.auth(&auth)
 				.header(CONTENT_TYPE, "application/octet-stream");
-			import(request, path).await?;
+			import(request, path, None).await?;
 			Ok(vec![QueryResultBuilder::instant_none()])
 		}
 		Command::SubscribeLive {
@@ -1110,18 +1245,77 @@ async fn router(
 			"The protocol or storage engine does not support live queries on this architecture"
 				.to_string(),
 		)),
-		Command::Query {
+		Command::LockWrites {
+			..
+		}
+		| Command::UnlockWrites {
+			..
+		} => Err(Error::internal(
+			"The protocol or storage engine does not support write locks".to_string(),
+		)),
+		Command::HealthReport => Err(Error::internal(
+			"The protocol or storage engine does not support health reports".to_string(),
+		)),
+		Command::Snapshot {
+			..
+		} => Err(Error::internal(
+			"The protocol or storage engine does not support snapshots".to_string(),
+		)),
+		Command::QueryBytes {
 			txn,
 			query,
 			variables,
+			format,
+			bytes,
 		} => {
-			let req = Command::Query {
-				txn,
-				query,
-				variables,
+			// Streamed output is served by the `/sql` endpoint, which has no
+			// access to the transactions and variables of the RPC session
+			if txn.is_some() {
+				return Err(Error::internal(
+					"Streaming query output is not supported within a transaction over HTTP"
+						.to_string(),
+				));
 			}
-			.into_router_request(None, Some(session_id))
-			.expect("command should convert to router request");
+			let accept = match format {
+				Format::Ndjson => surrealdb_core::api::format::NDJSON,
+			};
+			// The `/sql` endpoint only accepts string parameters, so the
+			// variables of the query are bound with `LET` statements instead
+			let mut sql = String::new();
+			for (key, value) in variables {
+				rpc::check_protected_param(&key)?;
+				if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+					return Err(Error::validation(
+						format!("Invalid variable name `{key}` for streamed query output"),
+						None,
+					));
+				}
+				sql.push_str(&format!("LET ${key} = {};\n", value.to_sql()));
+			}
+			sql.push_str(&query);
+			let req_path = base_url.join("sql").map_err(crate::std_error_to_types_error)?;
+			let headers = session_state.headers.read().await;
+			let auth = session_state.auth.read().await;
+			let request = client
+				.post(req_path)
+				.body(sql)
+				.headers(headers.clone())
+				.auth(&auth)
+				.header(CONTENT_TYPE, surrealdb_core::api::format::PLAIN)
+				.header(ACCEPT, accept);
+			stream_lines(request, bytes).await?;
+			Ok(vec![QueryResultBuilder::instant_none()])
+		}
+		cmd @ (Command::Query {
+			..
+		}
+		| Command::IdempotentQuery {
+			..
+		}) => {
+			let mut req = cmd
+				.into_router_request(None, Some(session_id))
+				.expect("command should convert to router request");
+			req.trace_id = trace_id;
 			send_request(
 				req,
 				base_url,
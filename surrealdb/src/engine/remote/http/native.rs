@@ -1,14 +1,16 @@
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_channel::Receiver;
 use reqwest::ClientBuilder;
 use surrealdb_types::ConnectionError;
 use tokio::sync::watch;
+use tokio::time::{self, Instant, Interval, MissedTickBehavior};
 use url::Url;
 
-use super::{Client, RouterState};
-use crate::conn::{Route, Router};
+use super::{Client, HttpClient, RouterState};
+use crate::conn::{ClientCache, CompressionMetrics, LatencyMetrics, QueryLimiter, Route, Router};
 use crate::engine::{SessionError, session_error_to_error};
 use crate::method::BoxFuture;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
@@ -26,6 +28,8 @@ use crate::{Error, ExtraFeatures, Result, SessionClone, SessionId, Surreal, conn
 /// # Arguments
 ///
 /// * `base_url` - The base URL of the SurrealDB server
+/// * `keepalive` - The interval of TCP keepalive probes, which also keeps idle
+///   connections in the pool
 /// * `tls_config` - Optional TLS configuration for HTTPS connections
 ///
 /// # Returns
@@ -33,6 +37,7 @@ use crate::{Error, ExtraFeatures, Result, SessionClone, SessionId, Surreal, conn
 /// A configured `reqwest::Client` pinned to a specific server IP address.
 pub(crate) async fn create_client(
 	base_url: &Url,
+	keepalive: Option<Duration>,
 	#[cfg(any(feature = "native-tls", feature = "rustls"))] tls_config: Option<&Tls>,
 ) -> Result<reqwest::Client> {
 	let headers = super::default_headers();
@@ -53,8 +58,19 @@ pub(crate) async fn create_client(
 	let mut last_error = None;
 
 	for addr in addrs {
-		#[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), expect(unused_mut))]
-		let mut builder = ClientBuilder::new().default_headers(headers.clone()).resolve(hostname, addr);
+		// Compressed responses are decompressed by the engine itself, so that
+		// the bytes saved can be measured
+		let mut builder = ClientBuilder::new()
+			.default_headers(headers.clone())
+			.resolve(hostname, addr)
+			.no_gzip()
+			.no_brotli()
+			.no_zstd()
+			.no_deflate();
+
+		if let Some(interval) = keepalive {
+			builder = builder.tcp_keepalive(interval).pool_idle_timeout(None);
+		}
 
 		#[cfg(any(feature = "native-tls", feature = "rustls"))]
 		if let Some(tls) = tls_config {
@@ -105,17 +121,30 @@ impl conn::Sealed for Client {
 			let base_url = address.url;
 
 			#[cfg(any(feature = "native-tls", feature = "rustls"))]
-			let client = create_client(&base_url, address.config.tls_config.as_ref()).await?;
+			let client = create_client(&base_url, config.keepalive, address.config.tls_config.as_ref())
+				.await?;
 			#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
-			let client = create_client(&base_url).await?;
+			let client = create_client(&base_url, config.keepalive).await?;
+
+			let latency = Arc::<LatencyMetrics>::default();
+			warm_up(&client, &base_url, config.warm_connections, &latency).await?;
 
 			let (route_tx, route_rx) = match capacity {
 				0 => async_channel::unbounded(),
 				capacity => async_channel::bounded(capacity),
 			};
 			let session_clone = session_clone.unwrap_or_else(SessionClone::new);
+			let compression = CompressionMetrics::new(&config);
 
-			tokio::spawn(run_router(client, base_url, route_rx, session_clone.receiver.clone()));
+			tokio::spawn(run_router(
+				client,
+				compression.clone(),
+				config.keepalive,
+				latency.clone(),
+				base_url,
+				route_rx,
+				session_clone.receiver.clone(),
+			));
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::Backup);
@@ -123,7 +152,13 @@ impl conn::Sealed for Client {
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
 				features,
+				limiter: QueryLimiter::new(&config).into(),
+				middleware: Default::default(),
+				cache: ClientCache::new(&config),
+				compression,
+				latency,
 				config,
+				notifications: None,
 				sender: route_tx,
 			};
 
@@ -132,13 +167,61 @@ impl conn::Sealed for Client {
 	}
 }
 
+/// Opens connections to the server ahead of the first requests, by sending
+/// concurrent requests to its health endpoint
+///
+/// Every request which is sent while the others are in flight takes a new
+/// connection, which stays in the pool of the client once it completes.
+pub(crate) async fn warm_up(
+	client: &reqwest::Client,
+	base_url: &Url,
+	connections: usize,
+	latency: &LatencyMetrics,
+) -> Result<()> {
+	let requests = (0..connections).map(|_| ping(client, base_url, latency));
+	futures::future::try_join_all(requests).await?;
+	Ok(())
+}
+
+/// Requests the health endpoint of the server, and records the round-trip
+/// time of the request
+async fn ping(client: &reqwest::Client, base_url: &Url, latency: &LatencyMetrics) -> Result<()> {
+	let url = base_url.join("health").map_err(crate::std_error_to_types_error)?;
+	let start = Instant::now();
+	super::health(client.get(url)).await?;
+	latency.record(start.elapsed());
+	Ok(())
+}
+
+/// Waits for the next keepalive ping, or forever when keepalive is disabled
+async fn next_ping(keepalive: &mut Option<Interval>) {
+	match keepalive {
+		Some(interval) => {
+			interval.tick().await;
+		}
+		None => std::future::pending().await,
+	}
+}
+
 pub(crate) async fn run_router(
 	client: reqwest::Client,
+	compression: Option<Arc<CompressionMetrics>>,
+	keepalive: Option<Duration>,
+	latency: Arc<LatencyMetrics>,
 	base_url: url::Url,
 	route_rx: Receiver<Route>,
 	session_rx: Receiver<SessionId>,
 ) {
+	let client = HttpClient {
+		inner: client,
+		compression,
+	};
 	let state = Arc::new(RouterState::new(client, base_url));
+	let mut keepalive = keepalive.map(|period| {
+		let mut interval = time::interval(period);
+		interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+		interval
+	});
 	loop {
 		tokio::select! {
 			biased;
@@ -208,6 +291,15 @@ pub(crate) async fn run_router(
 					route.response.send(db_result).await.ok();
 				});
 			}
+			_ = next_ping(&mut keepalive) => {
+				let state = Arc::clone(&state);
+				let latency = Arc::clone(&latency);
+				tokio::spawn(async move {
+					if let Err(error) = ping(&state.client, &state.base_url, &latency).await {
+						trace!("failed to ping the server; {error}");
+					}
+				});
+			}
 		}
 	}
 }
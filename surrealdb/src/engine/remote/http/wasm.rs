@@ -7,8 +7,8 @@ use reqwest::ClientBuilder;
 use tokio::sync::watch;
 use wasm_bindgen_futures::spawn_local;
 
-use super::{Client, RouterState};
-use crate::conn::{Route, Router};
+use super::{Client, HttpClient, RouterState};
+use crate::conn::{ClientCache, QueryLimiter, Route, Router};
 use crate::engine::{SessionError, session_error_to_error};
 use crate::method::BoxFuture;
 use crate::opt::{Endpoint, WaitFor};
@@ -42,7 +42,13 @@ impl conn::Sealed for Client {
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
 				features,
+				limiter: QueryLimiter::new(&config).into(),
+				middleware: Default::default(),
+				cache: ClientCache::new(&config),
+				compression: None,
+				latency: Default::default(),
 				config,
+				notifications: None,
 				sender: route_tx,
 			};
 
@@ -71,7 +77,9 @@ pub(crate) async fn run_router(
 	let client = match create_client(&base_url).await {
 		Ok(client) => {
 			conn_tx.send(Ok(())).await.ok();
-			client
+			HttpClient {
+				inner: client,
+			}
 		}
 		Err(error) => {
 			conn_tx.send(Err(error)).await.ok();
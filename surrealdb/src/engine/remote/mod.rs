@@ -106,7 +106,7 @@ use surrealdb_core::iam::token::Token;
 use uuid::Uuid;
 
 use crate::conn::cmd::Command;
-use crate::types::{Array, SurrealValue, Value};
+use crate::types::{Array, Object, SurrealValue, Value};
 
 /// A struct which will be serialized as a map to behave like the previously
 /// used BTreeMap.
@@ -121,6 +121,8 @@ pub(crate) struct RouterRequest {
 	pub(crate) txn: Option<Uuid>,
 	#[surreal(rename = "session")]
 	pub(crate) session_id: Option<Uuid>,
+	/// Correlates the request with the server logs for its processing
+	pub(crate) trace_id: Option<String>,
 }
 
 impl Command {
@@ -144,6 +146,7 @@ impl Command {
 					params: Some(Value::Array(Array::from(vec![namespace, database]))),
 					txn: None,
 					session_id,
+					trace_id: None,
 				}
 			}
 			Command::Signup {
@@ -154,6 +157,7 @@ impl Command {
 				params: Some(Value::Array(Array::from(vec![Value::from_t(credentials)]))),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Signin {
 				credentials,
@@ -163,6 +167,7 @@ impl Command {
 				params: Some(Value::Array(Array::from(vec![Value::from_t(credentials)]))),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Authenticate {
 				token,
@@ -181,6 +186,7 @@ impl Command {
 				}]))),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Refresh {
 				token,
@@ -192,6 +198,7 @@ impl Command {
 				params: Some(Value::Array(Array::from(vec![Value::from_t(token)]))),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Invalidate => RouterRequest {
 				id,
@@ -199,6 +206,25 @@ impl Command {
 				params: None,
 				txn: None,
 				session_id,
+				trace_id: None,
+			},
+			Command::Impersonate {
+				subject,
+			} => RouterRequest {
+				id,
+				method: "impersonate",
+				params: Some(Value::Array(Array::from(vec![Value::from_t(subject)]))),
+				txn: None,
+				session_id,
+				trace_id: None,
+			},
+			Command::StopImpersonating => RouterRequest {
+				id,
+				method: "stop_impersonating",
+				params: None,
+				txn: None,
+				session_id,
+				trace_id: None,
 			},
 			Command::Begin => RouterRequest {
 				id,
@@ -206,6 +232,7 @@ impl Command {
 				params: None,
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Commit {
 				txn,
@@ -215,6 +242,7 @@ impl Command {
 				params: Some(Value::Array(Array::from(vec![Value::Uuid(Uuid::from(txn))]))),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Rollback {
 				txn,
@@ -224,6 +252,7 @@ impl Command {
 				params: Some(Value::Array(Array::from(vec![Value::Uuid(Uuid::from(txn))]))),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Revoke {
 				token,
@@ -233,6 +262,7 @@ impl Command {
 				params: Some(Value::Array(Array::from(vec![token.into_value()]))),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Query {
 				txn,
@@ -247,9 +277,34 @@ impl Command {
 					params: Some(Value::Array(Array::from(params))),
 					txn,
 					session_id,
+					trace_id: None,
 				}
 			}
-			Command::ExportFile {
+			Command::IdempotentQuery {
+				query,
+				variables,
+				key,
+			} => {
+				let mut options = Object::new();
+				options.insert("idempotency_key", key);
+				let params: Vec<Value> = vec![
+					Value::String(query.into_owned()),
+					Value::Object(variables.into()),
+					Value::Object(options),
+				];
+				RouterRequest {
+					id,
+					method: "query",
+					params: Some(Value::Array(Array::from(params))),
+					txn: None,
+					session_id,
+					trace_id: None,
+				}
+			}
+			Command::QueryBytes {
+				..
+			}
+			| Command::ExportFile {
 				..
 			}
 			| Command::ExportBytes {
@@ -266,13 +321,24 @@ impl Command {
 			}
 			| Command::ImportMl {
 				..
-			} => return None,
+			}
+			| Command::LockWrites {
+				..
+			}
+			| Command::UnlockWrites {
+				..
+			}
+			| Command::Snapshot {
+				..
+			}
+			| Command::HealthReport => return None,
 			Command::Health => RouterRequest {
 				id,
 				method: "ping",
 				params: None,
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Version => RouterRequest {
 				id,
@@ -280,6 +346,15 @@ impl Command {
 				params: None,
 				txn: None,
 				session_id,
+				trace_id: None,
+			},
+			Command::Compatibility => RouterRequest {
+				id,
+				method: "compatibility",
+				params: None,
+				txn: None,
+				session_id,
+				trace_id: None,
 			},
 			Command::Set {
 				key,
@@ -290,6 +365,7 @@ impl Command {
 				params: Some(Value::from_t(vec![Value::from_t(key), value])),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Unset {
 				key,
@@ -299,6 +375,7 @@ impl Command {
 				params: Some(Value::from_t(vec![Value::from_t(key)])),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::SubscribeLive {
 				..
@@ -311,6 +388,7 @@ impl Command {
 				params: Some(Value::from_t(vec![Value::Uuid(Uuid::from(uuid))])),
 				txn: None,
 				session_id,
+				trace_id: None,
 			},
 			Command::Attach {
 				session_id,
@@ -320,6 +398,7 @@ impl Command {
 				params: None,
 				txn: None,
 				session_id: Some(session_id),
+				trace_id: None,
 			},
 			Command::Detach {
 				session_id,
@@ -329,6 +408,7 @@ impl Command {
 				params: None,
 				txn: None,
 				session_id: Some(session_id),
+				trace_id: None,
 			},
 			Command::Run {
 				name,
@@ -346,6 +426,7 @@ impl Command {
 					]))),
 					txn: None,
 					session_id,
+					trace_id: None,
 				}
 			}
 		};
@@ -359,6 +440,8 @@ impl Command {
 				| Command::Signin { .. }
 				| Command::Authenticate { .. }
 				| Command::Invalidate
+				| Command::Impersonate { .. }
+				| Command::StopImpersonating
 				| Command::Use { .. }
 				| Command::Set { .. }
 				| Command::Unset { .. }
@@ -472,6 +555,7 @@ mod test {
 			]))),
 			txn: Some(Uuid::new_v4()),
 			session_id: Some(Uuid::new_v4()),
+			trace_id: None,
 		};
 
 		assert_converts(
@@ -0,0 +1,722 @@
+//! Client-side load balancing across several servers
+//!
+//! A client which connects to more than one endpoint starts a router for each
+//! server, and a balancer in front of those routers. Commands which change the
+//! state of a session, such as `use` or `let`, are sent to every server, so
+//! that any of them can answer the next request of the session. The session
+//! fails the command if any healthy server fails it. Signing in or up runs on
+//! the home server of the session, which issues the token, and the other
+//! servers are then authenticated with that token.
+//!
+//! Transactions and live queries only exist on the server which started them,
+//! so they are routed to the home server of their session. The ids of the
+//! live queries are taken from the responses of the queries which start them,
+//! so that subscribing to a live query, or killing it, reaches the server
+//! which runs it even once the home server of the session has moved. Every
+//! other request is sent to a server picked by the [`LoadBalancing`] strategy.
+//!
+//! Each server is checked periodically, and servers which fail a check are
+//! not picked until they pass one again.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_channel::{Receiver, Sender};
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, StreamExt};
+use surrealdb_core::dbs::{QueryResult, QueryType};
+use surrealdb_core::iam::token::Token;
+use surrealdb_types::{ConnectionError, SurrealValue, Value};
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::{self, Instant, MissedTickBehavior};
+use url::Url;
+use uuid::Uuid;
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::Instant;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::{self as time, MissedTickBehavior};
+
+use crate::conn::{Command, RequestData, Route};
+use crate::engine::IntervalStream;
+use crate::opt::LoadBalancing;
+use crate::{Error, SessionId};
+
+/// How often each server is checked
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a server has to answer a check before it is skipped
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A server which requests can be routed to
+pub(crate) struct Backend {
+	/// The address of the server
+	url: Url,
+	/// The routes of the router connected to the server
+	routes: Sender<Route>,
+	/// The session events of the router connected to the server
+	sessions: Sender<SessionId>,
+	/// Whether the server passed its last check
+	healthy: bool,
+	/// How long the server took to answer its last check
+	latency: Option<Duration>,
+}
+
+impl Backend {
+	pub(crate) fn new(
+		url: Url,
+		routes: Sender<Route>,
+		sessions: Sender<SessionId>,
+		healthy: bool,
+	) -> Self {
+		Self {
+			url,
+			routes,
+			sessions,
+			healthy,
+			latency: None,
+		}
+	}
+
+	pub(crate) fn is_healthy(&self) -> bool {
+		self.healthy
+	}
+}
+
+/// The answer to a request
+type Answer = Result<Vec<QueryResult>, Error>;
+
+/// A request whose answer the balancer waits for, which resolves to the live
+/// queries the request started
+type Pending = BoxFuture<'static, Started>;
+
+/// The live queries started on a server within a session
+struct Started {
+	session_id: Uuid,
+	backend: usize,
+	lives: Vec<Uuid>,
+}
+
+/// Where a command is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+	/// Every server, as the command changes the state of the session
+	All,
+	/// The home server of the session, which issues a token that the other
+	/// servers are then authenticated with
+	Auth,
+	/// The server which runs a live query
+	Live(Uuid),
+	/// The home server of the session
+	Home,
+	/// The server picked by the strategy
+	Any,
+}
+
+impl Target {
+	fn of(command: &Command) -> Self {
+		match command {
+			Command::Signin {
+				..
+			}
+			| Command::Signup {
+				..
+			} => return Target::Auth,
+			Command::SubscribeLive {
+				uuid,
+				..
+			}
+			| Command::Kill {
+				uuid,
+			} => return Target::Live(*uuid),
+			_ => {}
+		}
+		if command.replayable() {
+			return Target::All;
+		}
+		match command {
+			Command::Begin
+			| Command::Commit {
+				..
+			}
+			| Command::Rollback {
+				..
+			} => Target::Home,
+			Command::Query {
+				txn,
+				query,
+				..
+			}
+			| Command::QueryBytes {
+				txn,
+				query,
+				..
+			} if txn.is_some() || may_start_live_query(query) => Target::Home,
+			Command::IdempotentQuery {
+				query,
+				..
+			} if may_start_live_query(query) => Target::Home,
+			_ => Target::Any,
+		}
+	}
+}
+
+/// Checks if a query may start a live query, whose notifications are only
+/// sent by the server which runs it. Queries which merely mention the keyword
+/// are routed to the home server too, which is harmless.
+fn may_start_live_query(query: &str) -> bool {
+	query
+		.split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+		.any(|word| word.eq_ignore_ascii_case("live"))
+}
+
+/// Routes requests across the routers of several servers
+pub(crate) struct Balancer {
+	strategy: LoadBalancing,
+	backends: Vec<Backend>,
+	/// The server which the round-robin strategy tries next
+	next: usize,
+	/// The server which each session runs its transactions and live queries on
+	homes: HashMap<Uuid, usize>,
+	/// The session and the server of each live query
+	lives: HashMap<Uuid, (Uuid, usize)>,
+	/// The session which health checks are sent within
+	probe: Option<Uuid>,
+}
+
+impl Balancer {
+	pub(crate) fn new(strategy: LoadBalancing, backends: Vec<Backend>) -> Self {
+		Self {
+			strategy,
+			backends,
+			next: 0,
+			homes: HashMap::new(),
+			lives: HashMap::new(),
+			probe: None,
+		}
+	}
+
+	/// Picks the server for a request which can run anywhere
+	fn pick(&mut self) -> usize {
+		let mut candidates =
+			(0..self.backends.len()).filter(|i| self.backends[*i].healthy).collect::<Vec<_>>();
+		// Requests wait for a server to reconnect when none are healthy
+		if candidates.is_empty() {
+			candidates = (0..self.backends.len()).collect();
+		}
+		match self.strategy {
+			LoadBalancing::RoundRobin => {
+				let i =
+					candidates.iter().copied().find(|i| *i >= self.next).unwrap_or(candidates[0]);
+				self.next = i + 1;
+				i
+			}
+			LoadBalancing::LowestLatency => candidates
+				.iter()
+				.copied()
+				.min_by_key(|i| self.backends[*i].latency.unwrap_or(Duration::MAX))
+				.unwrap_or(candidates[0]),
+			LoadBalancing::Failover => candidates[0],
+		}
+	}
+
+	/// Returns the home server of a session, picking a new one if the session
+	/// has none, or if its home is unhealthy while another server is not
+	fn home(&mut self, session_id: Uuid) -> usize {
+		if let Some(&i) = self.homes.get(&session_id)
+			&& (self.backends[i].healthy || !self.backends.iter().any(|b| b.healthy))
+		{
+			return i;
+		}
+		let i = self.pick();
+		self.homes.insert(session_id, i);
+		i
+	}
+
+	/// Returns the server which runs a live query, falling back to the home
+	/// server of the session for live queries the balancer has not seen
+	fn owner(&mut self, session_id: Uuid, uuid: Uuid) -> usize {
+		match self.lives.get(&uuid) {
+			Some(&(_, i)) => i,
+			None => self.home(session_id),
+		}
+	}
+
+	/// Records the live queries started by a request
+	fn started(&mut self, started: Started) {
+		for uuid in started.lives {
+			self.lives.insert(uuid, (started.session_id, started.backend));
+		}
+	}
+
+	/// Forwards a request to the routers of the servers it is meant for,
+	/// returning the answers to wait for, when the balancer has to read them
+	/// before the client does
+	async fn route(&mut self, route: Route) -> Option<Pending> {
+		let Route {
+			request: RequestData {
+				command,
+				session_id,
+				trace_id,
+			},
+			response,
+		} = route;
+		match Target::of(&command) {
+			Target::All => {
+				// Every server applies the command to its copy of the session
+				let home = self.home(session_id);
+				let mut answers = Vec::with_capacity(self.backends.len());
+				for i in 0..self.backends.len() {
+					let (sender, answer) = async_channel::bounded(1);
+					let request = RequestData {
+						command: command.clone(),
+						session_id,
+						trace_id: trace_id.clone(),
+					};
+					self.forward(i, request, sender).await;
+					let backend = &self.backends[i];
+					answers.push((backend.url.clone(), backend.healthy, answer));
+				}
+				Some(
+					gather(home, answers, response)
+						.map(move |_| Started {
+							session_id,
+							backend: home,
+							lives: Vec::new(),
+						})
+						.boxed(),
+				)
+			}
+			Target::Auth => {
+				let home = self.home(session_id);
+				let (sender, answer) = async_channel::bounded(1);
+				let request = RequestData {
+					command,
+					session_id,
+					trace_id: trace_id.clone(),
+				};
+				self.forward(home, request, sender).await;
+				let url = self.backends[home].url.clone();
+				let others = self
+					.backends
+					.iter()
+					.enumerate()
+					.filter(|(i, _)| *i != home)
+					.map(|(_, b)| (b.url.clone(), b.healthy, b.routes.clone()))
+					.collect();
+				Some(
+					authenticate((url, answer), others, session_id, trace_id, response)
+						.map(move |_| Started {
+							session_id,
+							backend: home,
+							lives: Vec::new(),
+						})
+						.boxed(),
+				)
+			}
+			Target::Live(uuid) => {
+				let i = self.owner(session_id, uuid);
+				if matches!(command, Command::Kill { .. }) {
+					self.lives.remove(&uuid);
+				}
+				let request = RequestData {
+					command,
+					session_id,
+					trace_id,
+				};
+				self.forward(i, request, response).await;
+				None
+			}
+			Target::Home => {
+				// Read the answer for the ids of any live queries it started
+				let i = self.home(session_id);
+				let (sender, answer) = async_channel::bounded(1);
+				let request = RequestData {
+					command,
+					session_id,
+					trace_id,
+				};
+				self.forward(i, request, sender).await;
+				let url = self.backends[i].url.clone();
+				Some(
+					track(url, answer, response)
+						.map(move |lives| Started {
+							session_id,
+							backend: i,
+							lives,
+						})
+						.boxed(),
+				)
+			}
+			Target::Any => {
+				let i = self.pick();
+				let request = RequestData {
+					command,
+					session_id,
+					trace_id,
+				};
+				self.forward(i, request, response).await;
+				None
+			}
+		}
+	}
+
+	async fn forward(
+		&self,
+		i: usize,
+		request: RequestData,
+		response: Sender<Result<Vec<QueryResult>, Error>>,
+	) {
+		let backend = &self.backends[i];
+		let route = Route {
+			request,
+			response,
+		};
+		let Err(unsent) = backend.routes.try_send(route) else {
+			return;
+		};
+		if unsent.into_inner().response.send(Err(stopped(&backend.url))).await.is_err() {
+			trace!("Receiver dropped");
+		}
+	}
+
+	/// Passes a session event on to the router of every server
+	fn session(&mut self, event: SessionId) {
+		match event {
+			SessionId::Initial(id)
+			| SessionId::Clone {
+				new: id,
+				..
+			} => {
+				self.probe.get_or_insert(id);
+			}
+			SessionId::Drop(id) => {
+				self.homes.remove(&id);
+				self.lives.retain(|_, (session_id, _)| *session_id != id);
+				if self.probe == Some(id) {
+					self.probe = None;
+				}
+			}
+		}
+		for backend in self.backends.iter() {
+			backend.sessions.try_send(event).ok();
+		}
+	}
+
+	/// Sends a health check to a server, and returns how long it took to
+	/// answer, if it answered in time
+	fn check(&self, i: usize) -> impl Future<Output = (usize, Option<Duration>)> + use<> {
+		let (response, answer) = async_channel::bounded(1);
+		let sent = self.probe.is_some_and(|session_id| {
+			let route = Route {
+				request: RequestData {
+					command: Command::Health,
+					session_id,
+					trace_id: None,
+				},
+				response,
+			};
+			self.backends[i].routes.try_send(route).is_ok()
+		});
+		let start = Instant::now();
+		async move {
+			if !sent {
+				return (i, None);
+			}
+			match time::timeout(HEALTH_CHECK_TIMEOUT, answer.recv()).await {
+				Ok(Ok(Ok(_))) => (i, Some(start.elapsed())),
+				_ => (i, None),
+			}
+		}
+	}
+
+	/// Records the outcome of a health check
+	fn checked(&mut self, i: usize, latency: Option<Duration>) {
+		let backend = &mut self.backends[i];
+		match (backend.healthy, latency) {
+			(true, None) => warn!("Skipping `{}` as it failed a health check", backend.url),
+			(false, Some(_)) => debug!("Using `{}` again as it passed a health check", backend.url),
+			_ => {}
+		}
+		backend.healthy = latency.is_some();
+		backend.latency = latency;
+	}
+}
+
+/// The error returned for a request which a router could not answer
+fn stopped(url: &Url) -> Error {
+	Error::connection(
+		format!("The router for `{url}` has stopped"),
+		ConnectionError::ConnectionFailed,
+	)
+}
+
+/// Waits for the answer of a server
+async fn answer(url: &Url, answer: Receiver<Answer>) -> Answer {
+	answer.recv().await.unwrap_or_else(|_| Err(stopped(url)))
+}
+
+/// Passes the answer of a request on to the client
+async fn respond(response: Sender<Answer>, answer: Answer) {
+	if response.send(answer).await.is_err() {
+		trace!("Receiver dropped");
+	}
+}
+
+/// Waits for every server to answer a command which changes the state of the
+/// session, answering with the result of the home server, or with the error
+/// of the first healthy server which failed the command. Servers which are
+/// skipped are brought up to date by their router once they reconnect.
+async fn gather(
+	home: usize,
+	answers: Vec<(Url, bool, Receiver<Answer>)>,
+	response: Sender<Answer>,
+) {
+	let mut result = None;
+	let mut error = None;
+	for (i, (url, healthy, receiver)) in answers.into_iter().enumerate() {
+		match answer(&url, receiver).await {
+			res if i == home => result = Some(res),
+			Err(e) if healthy => {
+				error.get_or_insert(e);
+			}
+			_ => {}
+		}
+	}
+	let result = match (result, error) {
+		(Some(Ok(_)), Some(e)) => Err(e),
+		(Some(result), _) => result,
+		(None, _) => Err(Error::internal(
+			"The home server of the session was not sent the command".to_owned(),
+		)),
+	};
+	respond(response, result).await;
+}
+
+/// Waits for the home server to sign the session in or up, then authenticates
+/// the session on every other server with the token it issued
+async fn authenticate(
+	(url, receiver): (Url, Receiver<Answer>),
+	others: Vec<(Url, bool, Sender<Route>)>,
+	session_id: Uuid,
+	trace_id: Option<String>,
+	response: Sender<Answer>,
+) {
+	let mut result = answer(&url, receiver).await;
+	let token = match &result {
+		Ok(results) => issued_token(results),
+		Err(_) => None,
+	};
+	if let Some(token) = token {
+		for (url, healthy, routes) in others {
+			let (sender, receiver) = async_channel::bounded(1);
+			let route = Route {
+				request: RequestData {
+					command: Command::Authenticate {
+						token: token.clone(),
+					},
+					session_id,
+					trace_id: trace_id.clone(),
+				},
+				response: sender,
+			};
+			let answer = match routes.try_send(route) {
+				Ok(()) => answer(&url, receiver).await,
+				Err(_) => Err(stopped(&url)),
+			};
+			if let Err(e) = answer
+				&& healthy && result.is_ok()
+			{
+				result = Err(e);
+			}
+		}
+	}
+	respond(response, result).await;
+}
+
+/// Reads the token issued by signing in or up
+fn issued_token(results: &[QueryResult]) -> Option<Token> {
+	let value = results.first()?.result.as_ref().ok()?;
+	Token::from_value(value.clone()).ok()
+}
+
+/// Waits for the answer to a request which runs on the home server of the
+/// session, returning the ids of the live queries it started
+async fn track(url: Url, receiver: Receiver<Answer>, response: Sender<Answer>) -> Vec<Uuid> {
+	let result = answer(&url, receiver).await;
+	let lives = match &result {
+		Ok(results) => started_lives(results),
+		Err(_) => Vec::new(),
+	};
+	respond(response, result).await;
+	lives
+}
+
+/// Reads the ids of the live queries started by the statements of a query
+fn started_lives(results: &[QueryResult]) -> Vec<Uuid> {
+	results
+		.iter()
+		.filter(|r| r.query_type == QueryType::Live)
+		.filter_map(|r| match &r.result {
+			Ok(Value::Uuid(uuid)) => Some(uuid.into_inner()),
+			_ => None,
+		})
+		.collect()
+}
+
+/// Routes the requests of a client across the routers of several servers,
+/// until the client is dropped
+pub(crate) async fn run_balancer(
+	mut balancer: Balancer,
+	route_rx: Receiver<Route>,
+	session_rx: Receiver<SessionId>,
+) {
+	let mut interval = time::interval(HEALTH_CHECK_INTERVAL);
+	interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+	let mut checker = IntervalStream::new(interval);
+	let mut checks = FuturesUnordered::new();
+	let mut pending = FuturesUnordered::new();
+
+	loop {
+		futures::select_biased! {
+			session = session_rx.recv().fuse() => {
+				let Ok(event) = session else {
+					break;
+				};
+				balancer.session(event);
+			}
+			route = route_rx.recv().fuse() => {
+				let Ok(route) = route else {
+					break;
+				};
+				// Pass on any session events which were sent before this request
+				while let Ok(event) = session_rx.try_recv() {
+					balancer.session(event);
+				}
+				if let Some(answer) = balancer.route(route).await {
+					pending.push(answer);
+				}
+			}
+			started = pending.select_next_some() => {
+				balancer.started(started);
+			}
+			(i, latency) = checks.select_next_some() => {
+				balancer.checked(i, latency);
+			}
+			_ = checker.next().fuse() => {
+				// Skip a round while the previous checks are still running
+				if checks.is_empty() && balancer.probe.is_some() {
+					for i in 0..balancer.backends.len() {
+						checks.push(balancer.check(i));
+					}
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::borrow::Cow;
+
+	use super::*;
+
+	fn balancer(strategy: LoadBalancing, backends: usize) -> Balancer {
+		let backends = (0..backends)
+			.map(|i| {
+				let url = Url::parse(&format!("ws://server-{i}:8000")).unwrap();
+				Backend::new(url, async_channel::unbounded().0, async_channel::unbounded().0, true)
+			})
+			.collect();
+		Balancer::new(strategy, backends)
+	}
+
+	fn query(query: &'static str, txn: Option<Uuid>) -> Command {
+		Command::Query {
+			txn,
+			query: Cow::Borrowed(query),
+			variables: Default::default(),
+		}
+	}
+
+	#[test]
+	fn commands_are_routed_by_their_effect_on_the_session() {
+		let use_db = Command::Use {
+			namespace: None,
+			database: Some("test".to_owned()),
+		};
+		assert_eq!(Target::of(&use_db), Target::All);
+		let signin = Command::Signin {
+			credentials: Default::default(),
+		};
+		assert_eq!(Target::of(&signin), Target::Auth);
+		let live = Uuid::new_v4();
+		assert_eq!(
+			Target::of(&Command::Kill {
+				uuid: live
+			}),
+			Target::Live(live)
+		);
+		assert_eq!(Target::of(&Command::Begin), Target::Home);
+		assert_eq!(Target::of(&query("LIVE SELECT * FROM person", None)), Target::Home);
+		assert_eq!(Target::of(&query("SELECT * FROM person", Some(Uuid::new_v4()))), Target::Home);
+		assert_eq!(Target::of(&query("SELECT * FROM person", None)), Target::Any);
+		assert_eq!(Target::of(&query("SELECT * FROM delivery", None)), Target::Any);
+	}
+
+	#[test]
+	fn strategies_skip_unhealthy_servers() {
+		let mut lb = balancer(LoadBalancing::RoundRobin, 3);
+		lb.checked(1, None);
+		assert_eq!((0..4).map(|_| lb.pick()).collect::<Vec<_>>(), [0, 2, 0, 2]);
+
+		let mut lb = balancer(LoadBalancing::LowestLatency, 3);
+		lb.checked(0, Some(Duration::from_millis(30)));
+		lb.checked(1, Some(Duration::from_millis(10)));
+		lb.checked(2, Some(Duration::from_millis(20)));
+		assert_eq!(lb.pick(), 1);
+		lb.checked(1, None);
+		assert_eq!(lb.pick(), 2);
+
+		let mut lb = balancer(LoadBalancing::Failover, 3);
+		assert_eq!(lb.pick(), 0);
+		lb.checked(0, None);
+		assert_eq!(lb.pick(), 1);
+		// Requests still go somewhere when every server is down
+		lb.checked(1, None);
+		lb.checked(2, None);
+		assert_eq!(lb.pick(), 0);
+	}
+
+	#[test]
+	fn sessions_stay_on_their_home_server() {
+		let mut lb = balancer(LoadBalancing::RoundRobin, 2);
+		let session = Uuid::new_v4();
+		let home = lb.home(session);
+		lb.pick();
+		assert_eq!(lb.home(session), home);
+		// The session moves once its home server is skipped
+		lb.checked(home, None);
+		assert_ne!(lb.home(session), home);
+	}
+
+	#[test]
+	fn live_queries_stay_on_their_server() {
+		let mut lb = balancer(LoadBalancing::RoundRobin, 2);
+		let session = Uuid::new_v4();
+		let home = lb.home(session);
+		let live = Uuid::new_v4();
+		lb.started(Started {
+			session_id: session,
+			backend: home,
+			lives: vec![live],
+		});
+		// The live query stays on the server which runs it once the session moves
+		lb.checked(home, None);
+		assert_ne!(lb.home(session), home);
+		assert_eq!(lb.owner(session, live), home);
+		assert_eq!(lb.owner(session, Uuid::new_v4()), lb.home(session));
+		lb.session(SessionId::Drop(session));
+		assert!(lb.lives.is_empty());
+	}
+}
@@ -4,6 +4,7 @@
 //! The core logic is shared between native and WASM platforms, with
 //! platform-specific implementations in the `native` and `wasm` submodules.
 
+mod balance;
 #[cfg(not(target_family = "wasm"))]
 pub(crate) mod native;
 #[cfg(target_family = "wasm")]
@@ -15,7 +16,7 @@ use std::sync::atomic::{AtomicI64, Ordering};
 use std::time::Duration;
 
 use async_channel::Sender;
-use futures::{Sink, SinkExt};
+use futures::{Sink, SinkExt, Stream, StreamExt};
 use surrealdb_core::dbs::{QueryResult, QueryResultBuilder};
 use surrealdb_core::iam::token::Token;
 use surrealdb_core::rpc::{DbResponse, DbResult};
@@ -24,18 +25,28 @@ use surrealdb_types::{
 	ValidationError,
 };
 use tokio::sync::RwLock;
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::timeout;
 use uuid::Uuid;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::timeout;
 
-use crate::conn::{Command, RequestData, Route};
+use crate::conn::{Command, RequestData, Route, new_trace_id};
 use crate::engine::remote::RouterRequest;
 use crate::engine::{SessionError, session_error_to_error};
 use crate::opt::IntoEndpoint;
+pub(crate) use crate::protocol::PATH;
 use crate::types::{Array, HashMap, Notification, Number, SurrealValue, Value};
 use crate::{Connect, Error, Surreal};
 
-pub(crate) const PATH: &str = "rpc";
 const PING_INTERVAL: Duration = Duration::from_secs(5);
 
+/// The id of the requests which the router sends on its own behalf
+const ROUTER_REQUEST_ID: i64 = -1;
+
+/// How long the router waits for the response to a request of its own
+const ROUTER_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
 // ============================================================================
 // Core Types
 // ============================================================================
@@ -178,6 +189,7 @@ where
 	let RequestData {
 		command,
 		session_id,
+		trace_id,
 	} = request;
 
 	// Get session state
@@ -230,11 +242,57 @@ where
 		} => {
 			session_state.live_queries.remove(uuid);
 		}
+		Command::LockWrites {
+			..
+		}
+		| Command::UnlockWrites {
+			..
+		} => {
+			let error = Error::internal(
+				"The protocol or storage engine does not support write locks".to_string(),
+			);
+			if response.send(Err(error)).await.is_err() {
+				trace!("Receiver dropped");
+			}
+			return HandleResult::Ok;
+		}
+		Command::QueryBytes {
+			..
+		} => {
+			let error = Error::internal(
+				"The protocol or storage engine does not support streaming query output"
+					.to_string(),
+			);
+			if response.send(Err(error)).await.is_err() {
+				trace!("Receiver dropped");
+			}
+			return HandleResult::Ok;
+		}
+		Command::HealthReport => {
+			let error = Error::internal(
+				"The protocol or storage engine does not support health reports".to_string(),
+			);
+			if response.send(Err(error)).await.is_err() {
+				trace!("Receiver dropped");
+			}
+			return HandleResult::Ok;
+		}
+		Command::Snapshot {
+			..
+		} => {
+			let error = Error::internal(
+				"The protocol or storage engine does not support snapshots".to_string(),
+			);
+			if response.send(Err(error)).await.is_err() {
+				trace!("Receiver dropped");
+			}
+			return HandleResult::Ok;
+		}
 		_ => {}
 	}
 
 	// Serialize the request
-	let Some(router_request) = command.clone().into_router_request(Some(id), Some(session_id))
+	let Some(mut router_request) = command.clone().into_router_request(Some(id), Some(session_id))
 	else {
 		response
 			.send(Err(Error::internal(
@@ -246,6 +304,9 @@ where
 		return HandleResult::Ok;
 	};
 
+	// Identify the request in the server logs
+	router_request.trace_id = Some(trace_id.unwrap_or_else(new_trace_id));
+
 	let message: M = serialize_request(router_request);
 
 	// Check message size
@@ -448,6 +509,7 @@ where
 					params: Some(Value::Array(Array::from(vec![token.into_value()]))),
 					txn: None,
 					session_id: Some(session_id),
+					trace_id: Some(new_trace_id()),
 				};
 				let message: M = serialize_request(refresh_request);
 
@@ -609,6 +671,136 @@ where
 	Ok(())
 }
 
+/// Send a request on behalf of the router, outside of any session, and wait
+/// for its result.
+///
+/// This is only used right after connecting, before any other request is
+/// sent on the connection, so any other message received is skipped.
+async fn router_request<M, S, E, St, SE>(
+	method: &'static str,
+	params: Option<Value>,
+	sink: &RwLock<S>,
+	stream: &RwLock<St>,
+) -> Option<Result<DbResult, TypesError>>
+where
+	M: WsMessage,
+	S: Sink<M, Error = E> + Unpin,
+	E: std::fmt::Debug,
+	St: Stream<Item = Result<M, SE>> + Unpin,
+{
+	let request = RouterRequest {
+		id: Some(ROUTER_REQUEST_ID),
+		method,
+		params,
+		txn: None,
+		session_id: None,
+		trace_id: Some(new_trace_id()),
+	};
+	if let Err(error) = send_message(sink, serialize_request::<M>(request)).await {
+		debug!("{:?}", error);
+		return None;
+	}
+	let response = async {
+		let mut stream = stream.write().await;
+		while let Some(Ok(message)) = stream.next().await {
+			let Some(binary) = message.as_binary() else {
+				continue;
+			};
+			let Ok(response) = DbResponse::from_bytes(binary) else {
+				continue;
+			};
+			if response.id == Some(Value::Number(Number::Int(ROUTER_REQUEST_ID))) {
+				return Some(response.result);
+			}
+		}
+		None
+	};
+	timeout(ROUTER_REQUEST_TIMEOUT, response).await.ok().flatten()
+}
+
+/// Request a token with which the server resumes the sessions of this
+/// connection after a reconnect.
+///
+/// Yields no token when the server does not support session resumption.
+async fn request_resume_token<M, S, E, St, SE>(
+	sink: &RwLock<S>,
+	stream: &RwLock<St>,
+) -> Option<String>
+where
+	M: WsMessage,
+	S: Sink<M, Error = E> + Unpin,
+	E: std::fmt::Debug,
+	St: Stream<Item = Result<M, SE>> + Unpin,
+{
+	match router_request::<M, S, E, St, SE>("resume_token", None, sink, stream).await {
+		Some(Ok(DbResult::Other(Value::String(token)))) => Some(token),
+		Some(Err(error)) => {
+			trace!("Session resumption is not available; {error}");
+			None
+		}
+		_ => None,
+	}
+}
+
+/// Resume the sessions of a previous connection on this connection, using
+/// the resumption token of the previous connection.
+///
+/// Yields the resumption token of this connection when the sessions were
+/// resumed, or nothing when they have to be replayed instead.
+async fn resume_sessions<M, S, E, St, SE>(
+	token: String,
+	sink: &RwLock<S>,
+	stream: &RwLock<St>,
+) -> Option<String>
+where
+	M: WsMessage,
+	S: Sink<M, Error = E> + Unpin,
+	E: std::fmt::Debug,
+	St: Stream<Item = Result<M, SE>> + Unpin,
+{
+	let params = Value::Array(Array::from(vec![Value::String(token)]));
+	match router_request::<M, S, E, St, SE>("resume", Some(params), sink, stream).await {
+		Some(Ok(DbResult::Other(Value::String(token)))) => Some(token),
+		Some(Err(error)) => {
+			trace!("Failed to resume the sessions; {error}");
+			None
+		}
+		_ => None,
+	}
+}
+
+/// Restore the state of all sessions after a reconnect.
+///
+/// The sessions are resumed on the server when the previous connection was
+/// given a resumption token, and replayed otherwise. Returns the resumption
+/// token of the new connection.
+async fn restore_sessions<M, S, E, St, SE>(
+	token: Option<String>,
+	sessions: &HashMap<Uuid, Result<Arc<SessionState>, SessionError>>,
+	sink: &RwLock<S>,
+	stream: &RwLock<St>,
+) -> Option<String>
+where
+	M: WsMessage,
+	S: Sink<M, Error = E> + Unpin,
+	E: std::fmt::Debug,
+	St: Stream<Item = Result<M, SE>> + Unpin,
+{
+	if let Some(token) = token
+		&& let Some(token) = resume_sessions::<M, S, E, St, SE>(token, sink, stream).await
+	{
+		trace!("Resumed the sessions on the server");
+		return Some(token);
+	}
+	// Replay state for ALL sessions
+	for (session_id, session_result) in sessions.to_vec() {
+		if let Ok(session_state) = session_result {
+			replay_session::<M, S, E>(session_id, &session_state, sink).await.ok();
+		}
+	}
+	request_resume_token::<M, S, E, St, SE>(sink, stream).await
+}
+
 /// Handle new session registration.
 async fn handle_session_initial<M, S, E>(
 	session_id: Uuid,
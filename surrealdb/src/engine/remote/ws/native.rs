@@ -8,7 +8,7 @@ use surrealdb_types::{ConnectionError, ValidationError};
 use tokio::net::TcpStream;
 use tokio::sync::{RwLock, watch};
 use tokio::time;
-use tokio::time::MissedTickBehavior;
+use tokio::time::{Instant, MissedTickBehavior};
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::error::Error as WsError;
@@ -18,11 +18,13 @@ use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 use tokio_tungstenite::{Connector, MaybeTlsStream, WebSocketStream};
 use uuid::Uuid;
 
+use super::balance::{Backend, Balancer, run_balancer};
 use super::{
 	HandleResult, PATH, PING_INTERVAL, SessionState, WsMessage, create_ping_message,
-	handle_response, handle_route, handle_session, replay_session, reset_sessions,
+	handle_response, handle_route, handle_session, request_resume_token, reset_sessions,
+	restore_sessions,
 };
-use crate::conn::{self, Route, Router};
+use crate::conn::{self, ClientCache, LatencyMetrics, QueryLimiter, Route, Router};
 use crate::engine::{IntervalStream, SessionError};
 use crate::method::BoxFuture;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
@@ -140,31 +142,51 @@ impl conn::Sealed for super::Client {
 				.max_write_buffer_size(address.config.websocket.max_write_buffer_size)
 				.write_buffer_size(address.config.websocket.write_buffer_size);
 
-			let socket = connect(&address, Some(ws_config), maybe_connector.clone()).await?;
-
 			let (route_tx, route_rx) = match capacity {
 				0 => async_channel::unbounded(),
 				capacity => async_channel::bounded(capacity),
 			};
 			let config = address.config.clone();
 			let session_clone = session_clone.unwrap_or_else(SessionClone::new);
-
-			tokio::spawn(run_router(
-				address,
-				maybe_connector,
-				ws_config,
-				socket,
-				route_rx,
-				session_clone.receiver.clone(),
-			));
+			let latency = Arc::<LatencyMetrics>::default();
+
+			if address.replicas.is_empty() {
+				let socket = connect(&address, Some(ws_config), maybe_connector.clone()).await?;
+				tokio::spawn(run_router(
+					address,
+					maybe_connector,
+					ws_config,
+					socket,
+					latency.clone(),
+					route_rx,
+					session_clone.receiver.clone(),
+				));
+			} else {
+				connect_balanced(
+					address,
+					maybe_connector,
+					ws_config,
+					latency.clone(),
+					route_rx,
+					session_clone.receiver.clone(),
+				)
+				.await?;
+			}
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::LiveQueries);
+			features.insert(ExtraFeatures::Transactions);
 
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
 				features,
+				limiter: QueryLimiter::new(&config).into(),
+				middleware: Default::default(),
+				cache: ClientCache::new(&config),
+				compression: None,
+				latency,
 				config,
+				notifications: None,
 				sender: route_tx,
 			};
 
@@ -173,6 +195,88 @@ impl conn::Sealed for super::Client {
 	}
 }
 
+/// Connects to every server of an endpoint, and balances the requests of the
+/// client across them
+///
+/// At least one of the servers has to be reachable. The others are connected
+/// to in the background, and are used once they pass a health check. The
+/// round-trip time is recorded from the pings to every server.
+pub(crate) async fn connect_balanced(
+	endpoint: Endpoint,
+	maybe_connector: Option<Connector>,
+	config: WebSocketConfig,
+	latency: Arc<LatencyMetrics>,
+	route_rx: Receiver<Route>,
+	session_rx: Receiver<SessionId>,
+) -> crate::Result<()> {
+	let mut urls = vec![endpoint.url.clone()];
+	for replica in endpoint.replicas.iter() {
+		let url = replica
+			.join(PATH)
+			.map_err(|e| Error::validation(e.to_string(), ValidationError::InvalidRequest))?;
+		urls.push(url);
+	}
+	let mut backends = Vec::with_capacity(urls.len());
+	let mut error = None;
+	for url in urls {
+		let mut server = endpoint.clone();
+		server.url = url.clone();
+		server.replicas = Vec::new();
+		let (routes, server_route_rx) = async_channel::unbounded();
+		let (sessions, server_session_rx) = async_channel::unbounded();
+		let connector = maybe_connector.clone();
+		let latency = latency.clone();
+		let healthy = match connect(&server, Some(config), connector.clone()).await {
+			Ok(socket) => {
+				tokio::spawn(run_router(
+					server,
+					connector,
+					config,
+					socket,
+					latency,
+					server_route_rx,
+					server_session_rx,
+				));
+				true
+			}
+			Err(err) => {
+				warn!("Failed to connect to `{url}`; {err}");
+				error = Some(err);
+				// Keep trying until the client is dropped
+				tokio::spawn(async move {
+					while !server_route_rx.is_closed() {
+						time::sleep(time::Duration::from_secs(1)).await;
+						if let Ok(socket) = connect(&server, Some(config), connector.clone()).await
+						{
+							run_router(
+								server,
+								connector,
+								config,
+								socket,
+								latency,
+								server_route_rx,
+								server_session_rx,
+							)
+							.await;
+							break;
+						}
+					}
+				});
+				false
+			}
+		};
+		backends.push(Backend::new(url, routes, sessions, healthy));
+	}
+	if let Some(error) = error
+		&& !backends.iter().any(Backend::is_healthy)
+	{
+		return Err(error);
+	}
+	let balancer = Balancer::new(endpoint.config.load_balancing, backends);
+	tokio::spawn(run_balancer(balancer, route_rx, session_rx));
+	Ok(())
+}
+
 // ============================================================================
 // Router State
 // ============================================================================
@@ -182,6 +286,8 @@ struct RouterState {
 	sessions: Sessions,
 	sink: RwLock<MessageSink>,
 	stream: RwLock<MessageStream>,
+	/// The token with which the server resumes the sessions after a reconnect
+	resume_token: RwLock<Option<String>>,
 }
 
 impl RouterState {
@@ -190,6 +296,7 @@ impl RouterState {
 			sessions: HashMap::new(),
 			sink: RwLock::new(sink),
 			stream: RwLock::new(stream),
+			resume_token: RwLock::new(None),
 		}
 	}
 
@@ -197,6 +304,25 @@ impl RouterState {
 		*self.sink.write().await = sink;
 		*self.stream.write().await = stream;
 	}
+
+	/// Requests a resumption token for the current connection
+	async fn request_resume_token(&self) {
+		let token = request_resume_token::<Message, _, _, _, _>(&self.sink, &self.stream).await;
+		*self.resume_token.write().await = token;
+	}
+
+	/// Resumes or replays the state of all sessions on the current connection
+	async fn restore_sessions(&self) {
+		let token = self.resume_token.write().await.take();
+		let token = restore_sessions::<Message, _, _, _, _>(
+			token,
+			&self.sessions,
+			&self.sink,
+			&self.stream,
+		)
+		.await;
+		*self.resume_token.write().await = token;
+	}
 }
 
 // ============================================================================
@@ -215,14 +341,7 @@ async fn router_reconnect(
 			Ok(s) => {
 				let (new_sink, new_stream) = s.split();
 				state.update_connection(new_sink, new_stream).await;
-				// Replay state for ALL sessions
-				for (session_id, session_result) in state.sessions.to_vec() {
-					if let Ok(session_state) = session_result {
-						replay_session::<Message, _, _>(session_id, &session_state, &state.sink)
-							.await
-							.ok();
-					}
-				}
+				state.restore_sessions().await;
 				trace!("Reconnected successfully");
 				break;
 			}
@@ -239,18 +358,28 @@ pub(crate) async fn run_router(
 	maybe_connector: Option<Connector>,
 	config: WebSocketConfig,
 	socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+	latency: Arc<LatencyMetrics>,
 	route_rx: Receiver<Route>,
 	session_rx: Receiver<SessionId>,
 ) {
-	let ping: Message = create_ping_message();
+	// With a keepalive, the server is pinged with WebSocket ping frames, so
+	// that the round-trip time can be measured from its pongs
+	let keepalive = endpoint.config.keepalive;
+	let ping: Message = match keepalive {
+		Some(_) => Message::Ping(Default::default()),
+		None => create_ping_message(),
+	};
 
 	let (socket_sink, socket_stream) = socket.split();
 	let state = Arc::new(RouterState::new(socket_sink, socket_stream));
+	state.request_resume_token().await;
 
 	'router: loop {
-		let mut interval = time::interval(PING_INTERVAL);
+		let mut interval = time::interval(keepalive.unwrap_or(PING_INTERVAL));
 		interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 		let mut pinger = IntervalStream::new(interval);
+		// When the ping frame awaiting a pong was sent
+		let mut ping_sent = None;
 
 		reset_sessions(&state.sessions).await;
 
@@ -300,6 +429,11 @@ pub(crate) async fn run_router(
 
 					match result {
 						Ok(message) => {
+							if let Message::Pong(_) = message
+								&& let Some(sent) = ping_sent.take()
+							{
+								latency.record(sent.elapsed());
+							}
 							match handle_response::<Message, _, _>(
 								&message, &state.sessions, &state.sink
 							).await {
@@ -327,11 +461,15 @@ pub(crate) async fn run_router(
 				}
 				_ = pinger.next() => {
 					trace!("Pinging the server");
+					let sent = Instant::now();
 					if let Err(error) = state.sink.write().await.send(ping.clone()).await {
 						trace!("failed to ping the server; {error:?}");
 						router_reconnect(&maybe_connector, &config, &state, &endpoint).await;
 						continue 'router;
 					}
+					if keepalive.is_some() {
+						ping_sent = Some(sent);
+					}
 				}
 			}
 		}
@@ -11,11 +11,13 @@ use wasm_bindgen_futures::spawn_local;
 use wasmtimer::tokio as time;
 use wasmtimer::tokio::MissedTickBehavior;
 
+use super::balance::{Backend, Balancer, run_balancer};
 use super::{
 	HandleResult, PATH, PING_INTERVAL, SessionState, WsMessage, create_ping_message,
-	handle_response, handle_route, handle_session, replay_session, reset_sessions,
+	handle_response, handle_route, handle_session, request_resume_token, reset_sessions,
+	restore_sessions,
 };
-use crate::conn::{self, Route, Router};
+use crate::conn::{self, ClientCache, QueryLimiter, Route, Router};
 use crate::engine::{IntervalStream, SessionError};
 use crate::method::BoxFuture;
 use crate::opt::{Endpoint, WaitFor};
@@ -75,17 +77,27 @@ impl conn::Sealed for super::Client {
 			let config = address.config.clone();
 			let session_clone = session_clone.unwrap_or_else(SessionClone::new);
 
-			spawn_local(run_router(address, conn_tx, route_rx, session_clone.receiver.clone()));
-
-			conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
+			if address.replicas.is_empty() {
+				spawn_local(run_router(address, conn_tx, route_rx, session_clone.receiver.clone()));
+				conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
+			} else {
+				connect_balanced(address, route_rx, session_clone.receiver.clone()).await?;
+			}
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::LiveQueries);
+			features.insert(ExtraFeatures::Transactions);
 
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
 				features,
+				limiter: QueryLimiter::new(&config).into(),
+				middleware: Default::default(),
+				cache: ClientCache::new(&config),
+				compression: None,
+				latency: Default::default(),
 				config,
+				notifications: None,
 				sender: route_tx,
 			};
 
@@ -94,6 +106,69 @@ impl conn::Sealed for super::Client {
 	}
 }
 
+/// Connects to every server of an endpoint, and balances the requests of the
+/// client across them
+///
+/// At least one of the servers has to be reachable. The others are connected
+/// to in the background, and are used once they pass a health check.
+pub(crate) async fn connect_balanced(
+	endpoint: Endpoint,
+	route_rx: Receiver<Route>,
+	session_rx: Receiver<SessionId>,
+) -> Result<()> {
+	let mut urls = vec![endpoint.url.clone()];
+	for replica in endpoint.replicas.iter() {
+		urls.push(replica.join(PATH).map_err(crate::std_error_to_types_error)?);
+	}
+	let mut backends = Vec::with_capacity(urls.len());
+	let mut error = None;
+	for url in urls {
+		let mut server = endpoint.clone();
+		server.url = url.clone();
+		server.replicas = Vec::new();
+		let (routes, server_route_rx) = async_channel::unbounded();
+		let (sessions, server_session_rx) = async_channel::unbounded();
+		let (conn_tx, conn_rx) = async_channel::bounded(1);
+		spawn_local(run_router(
+			server.clone(),
+			conn_tx,
+			server_route_rx.clone(),
+			server_session_rx.clone(),
+		));
+		let healthy = match conn_rx.recv().await.map_err(crate::std_error_to_types_error)? {
+			Ok(()) => true,
+			Err(err) => {
+				warn!("Failed to connect to `{url}`; {err}");
+				error = Some(err);
+				// Keep trying until the client is dropped
+				spawn_local(async move {
+					while !server_route_rx.is_closed() {
+						time::sleep(Duration::from_secs(1)).await;
+						let (conn_tx, _conn_rx) = async_channel::bounded(1);
+						run_router(
+							server.clone(),
+							conn_tx,
+							server_route_rx.clone(),
+							server_session_rx.clone(),
+						)
+						.await;
+					}
+				});
+				false
+			}
+		};
+		backends.push(Backend::new(url, routes, sessions, healthy));
+	}
+	if let Some(error) = error
+		&& !backends.iter().any(Backend::is_healthy)
+	{
+		return Err(error);
+	}
+	let balancer = Balancer::new(endpoint.config.load_balancing, backends);
+	spawn_local(run_balancer(balancer, route_rx, session_rx));
+	Ok(())
+}
+
 // ============================================================================
 // Router State
 // ============================================================================
@@ -103,6 +178,8 @@ struct RouterState {
 	sessions: Sessions,
 	sink: RwLock<MessageSink>,
 	stream: RwLock<MessageStream>,
+	/// The token with which the server resumes the sessions after a reconnect
+	resume_token: RwLock<Option<String>>,
 }
 
 impl RouterState {
@@ -111,6 +188,7 @@ impl RouterState {
 			sessions: HashMap::new(),
 			sink: RwLock::new(sink),
 			stream: RwLock::new(stream),
+			resume_token: RwLock::new(None),
 		}
 	}
 
@@ -118,6 +196,25 @@ impl RouterState {
 		*self.sink.write().await = sink;
 		*self.stream.write().await = stream;
 	}
+
+	/// Requests a resumption token for the current connection
+	async fn request_resume_token(&self) {
+		let token = request_resume_token::<Message, _, _, _, _>(&self.sink, &self.stream).await;
+		*self.resume_token.write().await = token;
+	}
+
+	/// Resumes or replays the state of all sessions on the current connection
+	async fn restore_sessions(&self) {
+		let token = self.resume_token.write().await.take();
+		let token = restore_sessions::<Message, _, _, _, _>(
+			token,
+			&self.sessions,
+			&self.sink,
+			&self.stream,
+		)
+		.await;
+		*self.resume_token.write().await = token;
+	}
 }
 
 // ============================================================================
@@ -135,15 +232,7 @@ async fn router_reconnect(state: &RouterState, endpoint: &Endpoint) {
 			Ok(socket) => {
 				let (new_sink, new_stream) = socket.split();
 				state.update_connection(new_sink, new_stream).await;
-
-				// Replay state for ALL sessions
-				for (session_id, session_result) in state.sessions.to_vec() {
-					if let Ok(session_state) = session_result {
-						replay_session::<Message, _, _>(session_id, &session_state, &state.sink)
-							.await
-							.ok();
-					}
-				}
+				state.restore_sessions().await;
 				trace!("Reconnected successfully");
 				break;
 			}
@@ -186,9 +275,10 @@ pub(crate) async fn run_router(
 
 	let (socket_sink, socket_stream) = socket.split();
 	let state = Arc::new(RouterState::new(socket_sink, socket_stream));
+	state.request_resume_token().await;
 
 	'router: loop {
-		let mut interval = time::interval(PING_INTERVAL);
+		let mut interval = time::interval(endpoint.config.keepalive.unwrap_or(PING_INTERVAL));
 		interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 		let mut pinger = IntervalStream::new(interval);
 
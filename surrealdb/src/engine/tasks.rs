@@ -36,6 +36,11 @@ enum NodeMembershipUpdateResult {
 pub struct Tasks(#[cfg_attr(target_family = "wasm", expect(dead_code))] Vec<Task>);
 
 impl Tasks {
+	/// No background tasks, for datastores which are opened read-only
+	#[cfg(not(target_family = "wasm"))]
+	pub fn none() -> Self {
+		Tasks(Vec::new())
+	}
 	#[cfg(target_family = "wasm")]
 	pub async fn resolve(self) -> Result<(), Error> {
 		Ok(())
@@ -67,8 +72,15 @@ pub fn init(dbs: Arc<Datastore>, canceller: CancellationToken, opts: &EngineOpti
 	let task8 = spawn_task_tikv_lock_cleanup(Arc::clone(&dbs), canceller.clone(), opts);
 	let task9 = spawn_task_reclaim_tombstones(Arc::clone(&dbs), canceller.clone(), opts);
 	let task10 = spawn_task_resume_index_builds(Arc::clone(&dbs), canceller.clone(), opts);
-	let task11 = spawn_task_live_query_router(dbs, canceller, opts);
-	Tasks(vec![task1, task2, task3, task4, task5, task6, task7, task8, task9, task10, task11])
+	let task11 = spawn_task_archival(Arc::clone(&dbs), canceller.clone(), opts);
+	let task12 = spawn_task_soft_delete_purge(Arc::clone(&dbs), canceller.clone(), opts);
+	let task13 = spawn_task_eviction(Arc::clone(&dbs), canceller.clone(), opts);
+	let task14 = spawn_task_scrub(Arc::clone(&dbs), canceller.clone(), opts);
+	let task15 = spawn_task_live_query_router(dbs, canceller, opts);
+	Tasks(vec![
+		task1, task2, task3, task4, task5, task6, task7, task8, task9, task10, task11, task12,
+		task13, task14, task15,
+	])
 }
 
 /// Spawns the per-node live-query router task.
@@ -410,6 +422,134 @@ fn spawn_task_event_processing(
 	}))
 }
 
+fn spawn_task_archival(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.archival_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running record archival every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.archival_process(interval).await {
+						error!("Error running record archival: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running record archival");
+	}))
+}
+
+fn spawn_task_soft_delete_purge(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.soft_delete_purge_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running soft delete purging every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.soft_delete_purge_process(interval).await {
+						error!("Error running soft delete purging: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running soft delete purging");
+	}))
+}
+
+fn spawn_task_eviction(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.eviction_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running eviction every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		//
+		let evict = async || {
+			if let Err(e) = dbs.eviction_process(interval).await {
+				error!("Error running eviction: {e}");
+			}
+		};
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Wake early when the evictable records exceed the memory limit
+				_ = dbs.eviction_trigger().notified() => evict().await,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => evict().await
+			}
+		}
+		trace!("Background task exited: Running eviction");
+	}))
+}
+
+fn spawn_task_scrub(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.scrub_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running record scrubbing every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.scrub_process(interval).await {
+						error!("Error running record scrubbing: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running record scrubbing");
+	}))
+}
+
 /// Spawns the periodic TiKV MVCC GC pass.
 ///
 /// On non-TiKV backends `Datastore::run_mvcc_gc` is a no-op and the task
@@ -24,12 +24,16 @@ compile_error!("The `ml` feature is not supported on Wasm.");
 #[macro_use]
 extern crate tracing;
 
+pub mod codegen;
 pub mod engine;
 #[doc(hidden)]
 #[cfg(feature = "protocol-http")]
 pub mod headers;
 pub mod method;
 pub mod opt;
+pub mod protocol;
+#[cfg(feature = "sync")]
+pub mod sync;
 
 mod conn;
 mod notification;
@@ -44,17 +48,41 @@ pub mod parse {
 	pub use surrealdb_core::syn::value;
 }
 
+/// Formatting of SurrealQL queries
+///
+/// # Examples
+///
+/// ```
+/// use surrealdb::syn::{FormatOptions, KeywordCase};
+///
+/// let options = FormatOptions {
+///     keyword_case: KeywordCase::Lower,
+///     ..Default::default()
+/// };
+/// let query = surrealdb::syn::format("SELECT * FROM person WHERE age>18", options).unwrap();
+/// assert_eq!(query, "select * from person where age > 18;");
+/// ```
+pub mod syn {
+	pub use surrealdb_core::syn::{FormatOptions, Indent, KeywordCase, format};
+}
+
+#[doc(inline)]
+pub use method::LiveHandle;
 #[doc(inline)]
 pub use method::Stats;
 #[doc(inline)]
 pub use method::Stream;
 #[doc(inline)]
 pub use method::query::IndexedResults;
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+#[doc(inline)]
+pub use surrealdb_macros::surql;
 #[doc(inline)]
 pub use surrealdb_types as types;
 
 #[doc(inline)]
-pub use crate::notification::Notification;
+pub use crate::notification::{Notification, NotificationError};
 
 /// A specialized `Result` type
 pub type Result<T> = std::result::Result<T, Error>;
@@ -65,8 +93,7 @@ use std::marker::PhantomData;
 use std::sync::{Arc, OnceLock};
 
 use async_channel::{Receiver, Sender};
-use method::BoxFuture;
-use semver::{Version, VersionReq};
+use method::{BoxFuture, CompatibilityIssue};
 #[doc(inline)]
 pub use surrealdb_types::Error;
 use surrealdb_types::NotAllowedError;
@@ -74,13 +101,11 @@ use tokio::sync::watch;
 use uuid::Uuid;
 
 use self::conn::Router;
-use self::opt::{Endpoint, EndpointKind, WaitFor};
+use self::opt::{CompatibilityCheck, Endpoint, EndpointKind, WaitFor};
 
 // Channel for waiters
 type Waiter = (watch::Sender<Option<WaitFor>>, watch::Receiver<Option<WaitFor>>);
 
-const SUPPORTED_VERSIONS: &str = ">=3.0.0-alpha.1, <4.0.0";
-
 /// Connection trait implemented by supported engines
 pub trait Connection: conn::Sealed {}
 
@@ -141,10 +166,9 @@ where
 		Box::pin(async move {
 			let endpoint = self.address?;
 			let endpoint_kind = EndpointKind::from(endpoint.url.scheme());
+			let check = endpoint.config.compatibility_check;
 			let client = Client::connect(endpoint, self.capacity, None).await?;
-			if endpoint_kind.is_remote() {
-				client.verify_server_version().await?;
-			}
+			client.verify_compatibility(check, endpoint_kind.is_remote()).await?;
 			// Both ends of the channel are still alive at this point
 			client.inner.waiter.0.send(Some(WaitFor::Connection)).ok();
 			Ok(client)
@@ -170,11 +194,10 @@ where
 			}
 			let endpoint = self.address?;
 			let endpoint_kind = EndpointKind::from(endpoint.url.scheme());
+			let check = endpoint.config.compatibility_check;
 			let session_clone = self.surreal.inner.session_clone.clone();
 			let client = Client::connect(endpoint, self.capacity, Some(session_clone)).await?;
-			if endpoint_kind.is_remote() {
-				client.verify_server_version().await?;
-			}
+			client.verify_compatibility(check, endpoint_kind.is_remote()).await?;
 			let router = client.inner.router.wait().clone();
 			self.surreal.inner.router.set(router).map_err(|_| {
 				Error::connection(
@@ -193,9 +216,10 @@ where
 pub(crate) enum ExtraFeatures {
 	Backup,
 	LiveQueries,
+	Transactions,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 #[allow(dead_code)]
 enum SessionId {
 	Initial(Uuid),
@@ -299,36 +323,48 @@ impl<C> Surreal<C>
 where
 	C: Connection,
 {
-	async fn check_server_version(&self, version: &Version) -> Result<()> {
-		// invalid version requirements should be caught during development
-		let req = VersionReq::parse(SUPPORTED_VERSIONS).expect("valid supported versions");
-		if !req.matches(version) {
-			return Err(Error::internal(format!(
-				"server version `{version}` does not match the range supported by the client `{SUPPORTED_VERSIONS}`"
-			)));
-		}
-
-		Ok(())
-	}
-
 	// If the server denies the `version` RPC method via its capabilities, skip
-	// the version check rather than failing to connect — the operator has
-	// explicitly opted out of exposing the version, and the rest of the
-	// connection is still usable.
-	async fn verify_server_version(&self) -> Result<()> {
-		match self.version().await {
-			Ok(mut version) => {
-				// we would like to be able to connect to pre-releases too
-				version.pre = Default::default();
-				self.check_server_version(&version).await
-			}
+	// the check rather than failing to connect — the operator has explicitly
+	// opted out of exposing the version, and the rest of the connection is
+	// still usable.
+	//
+	// Remote clients never touch the storage of the server, so a different
+	// storage format is only reported. Embedded engines read and write the
+	// datastore themselves, and are only checked for its storage format, as
+	// they always run the version of the client.
+	async fn verify_compatibility(&self, check: CompatibilityCheck, remote: bool) -> Result<()> {
+		if check == CompatibilityCheck::Skip {
+			return Ok(());
+		}
+		let mut report = match self.compatibility().await {
+			Ok(report) => report,
 			Err(e) if matches!(e.not_allowed_details(), Some(NotAllowedError::Method { .. })) => {
 				debug!(
-					"Skipping server version check; the `version` RPC method is denied by server capabilities"
+					"Skipping server compatibility check; the `version` RPC method is denied by server capabilities"
+				);
+				return Ok(());
+			}
+			Err(e) => return Err(e),
+		};
+		if remote {
+			if report.issues.contains(&CompatibilityIssue::StorageMismatch) {
+				warn!(
+					"The server stores data in storage format revision `{}`, while the client supports revision `{}`",
+					report.server_storage.unwrap_or_default(),
+					report.client_storage
 				);
+			}
+			report.issues.retain(|issue| *issue != CompatibilityIssue::StorageMismatch);
+		} else {
+			report.issues.retain(|issue| *issue == CompatibilityIssue::StorageMismatch);
+		}
+		match report.error() {
+			Some(e) if check == CompatibilityCheck::Enforce => Err(e),
+			Some(e) => {
+				warn!("Connecting to an incompatible server: {e}");
 				Ok(())
 			}
-			Err(e) => Err(e),
+			None => Ok(()),
 		}
 	}
 }
@@ -399,22 +435,3 @@ impl OnceLockExt for OnceLock<Router> {
 fn std_error_to_types_error(error: impl std::fmt::Display) -> Error {
 	Error::internal(error.to_string())
 }
-
-#[cfg(test)]
-mod tests {
-	use super::*;
-
-	#[test]
-	fn test_supported_versions() {
-		let req = VersionReq::parse(SUPPORTED_VERSIONS).expect("valid supported versions");
-		assert!(req.matches(&Version::parse("3.0.0-alpha.1").unwrap()));
-		assert!(req.matches(&Version::parse("3.0.0-beta.3").unwrap()));
-		assert!(req.matches(&Version::parse("3.0.0-rc.2").unwrap()));
-		assert!(req.matches(&Version::parse("3.0.0").unwrap()));
-		assert!(req.matches(&Version::parse("3.0.1").unwrap()));
-		assert!(req.matches(&Version::parse("3.9.0").unwrap()));
-
-		assert!(!req.matches(&Version::parse("2.9.0").unwrap()));
-		assert!(!req.matches(&Version::parse("4.0.0").unwrap()));
-	}
-}
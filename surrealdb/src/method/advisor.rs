@@ -0,0 +1,165 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::types::{SurrealValue, Variables};
+use crate::{Connection, Result, Surreal};
+
+/// Returned by [`Surreal::advisor`](crate::Surreal::advisor) to capture the
+/// workload of the selected database and recommend indexes for it
+#[derive(Debug)]
+pub struct Advisor<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<'r, C> Advisor<'r, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Advisor<'static, C> {
+		Advisor {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+
+	/// Starts capturing the shapes of the statements run against the
+	/// selected database, discarding any previously captured workload
+	///
+	/// The workload is captured by the node which runs the statements, until
+	/// the capture is stopped.
+	pub fn start(self) -> AdvisorCapture<'r, C> {
+		AdvisorCapture {
+			client: self.client,
+			query: "schema::advisor::start()",
+		}
+	}
+
+	/// Stops capturing the workload of the selected database, keeping the
+	/// workload captured so far
+	pub fn stop(self) -> AdvisorCapture<'r, C> {
+		AdvisorCapture {
+			client: self.client,
+			query: "schema::advisor::stop()",
+		}
+	}
+
+	/// Returns the indexes recommended for the captured workload, ranked by
+	/// their estimated benefit
+	///
+	/// Indexes which would serve statements already served by an existing
+	/// index are not recommended.
+	pub fn recommendations(self) -> IndexRecommendations<'r, C> {
+		IndexRecommendations {
+			client: self.client,
+		}
+	}
+}
+
+/// Returned by [`Advisor::start`] and [`Advisor::stop`], completing once the
+/// capture has been started or stopped
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct AdvisorCapture<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) query: &'static str,
+}
+
+impl<C> AdvisorCapture<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> AdvisorCapture<'static, C> {
+		AdvisorCapture {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for AdvisorCapture<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			router
+				.execute_unit(
+					self.client.session_id,
+					Command::Query {
+						txn: None,
+						query: Cow::Borrowed(self.query),
+						variables: Variables::new(),
+					},
+				)
+				.await
+		})
+	}
+}
+
+/// Returned by [`Advisor::recommendations`], yields the recommended indexes
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct IndexRecommendations<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> IndexRecommendations<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> IndexRecommendations<'static, C> {
+		IndexRecommendations {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for IndexRecommendations<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Vec<IndexRecommendation>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let mut response = self.client.query("schema::advisor::recommendations()").await?;
+			Ok(response.take::<Option<Vec<IndexRecommendation>>>(0)?.unwrap_or_default())
+		})
+	}
+}
+
+/// An index recommended for the captured workload, returned by
+/// [`Advisor::recommendations`]
+///
+/// The benefit and cost are estimated from the statistics of the table, so
+/// they are only useful to compare recommendations with each other.
+#[derive(Clone, Debug, Default, PartialEq, Eq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[non_exhaustive]
+pub struct IndexRecommendation {
+	/// The table to index
+	pub table: String,
+	/// The fields to index, in order
+	pub fields: Vec<String>,
+	/// The number of captured statements which the index would serve
+	pub queries: u64,
+	/// The estimated number of records which the captured statements scan
+	/// without the index
+	pub benefit: u64,
+	/// The estimated number of entries which the index would hold
+	pub cost: u64,
+	/// The statement which defines the index
+	pub statement: String,
+}
@@ -0,0 +1,355 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::StreamExt;
+use surrealdb_types::SerializationError;
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::sleep;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::sleep;
+
+use crate::method::BoxFuture;
+use crate::method::select::escape_field;
+use crate::method::version_stamp::VERSION_STAMP_QUERY;
+use crate::types::{SurrealValue, Value};
+use crate::{Connection, Error, Result, Surreal};
+
+/// The default interval between two reads of the changefeeds
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The default number of change sets read from a changefeed at once
+const DEFAULT_BATCH_SIZE: u32 = 1000;
+
+type KeyExtractor = Arc<dyn Fn(Value) -> Result<Value> + Send + Sync>;
+
+/// A table watched for changes, and the function which derives the cache key
+/// of each changed record
+#[derive(Clone)]
+struct Watch {
+	table: String,
+	key: KeyExtractor,
+}
+
+impl fmt::Debug for Watch {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Watch").field("table", &self.table).finish_non_exhaustive()
+	}
+}
+
+/// Returned by [`Surreal::cache_invalidation`](crate::Surreal::cache_invalidation)
+/// to build a stream of the cache keys invalidated by changes to tables.
+#[derive(Debug)]
+pub struct CacheInvalidation<'r, C: Connection> {
+	client: Cow<'r, Surreal<C>>,
+	watches: Vec<Watch>,
+	since: Option<u64>,
+	interval: Duration,
+	batch_size: u32,
+}
+
+impl<'r, C> CacheInvalidation<'r, C>
+where
+	C: Connection,
+{
+	pub(super) fn new(client: Cow<'r, Surreal<C>>) -> Self {
+		Self {
+			client,
+			watches: Vec::new(),
+			since: None,
+			interval: DEFAULT_INTERVAL,
+			batch_size: DEFAULT_BATCH_SIZE,
+		}
+	}
+
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> CacheInvalidation<'static, C> {
+		CacheInvalidation {
+			client: Cow::Owned(self.client.into_owned()),
+			watches: self.watches,
+			since: self.since,
+			interval: self.interval,
+			batch_size: self.batch_size,
+		}
+	}
+
+	/// Watches a table for changes
+	///
+	/// The table must be defined with a `CHANGEFEED`. The function derives the
+	/// cache key from each created, updated, or deleted record. Deleted records
+	/// only contain their `id`, unless the changefeed of the table was defined
+	/// with `INCLUDE ORIGINAL`.
+	pub fn watch<R, K, F>(mut self, table: impl Into<String>, key: F) -> Self
+	where
+		R: SurrealValue,
+		K: SurrealValue,
+		F: Fn(R) -> K + Send + Sync + 'static,
+	{
+		let key = move |record: Value| {
+			let record = R::from_value(record).map_err(|error| {
+				Error::serialization(error.to_string(), SerializationError::Deserialization)
+			})?;
+			Ok(key(record).into_value())
+		};
+		self.watches.push(Watch {
+			table: table.into(),
+			key: Arc::new(key),
+		});
+		self
+	}
+
+	/// Reads the changes made from the given versionstamp onwards
+	///
+	/// By default, only the changes made after the stream is started are read.
+	/// Passing the [`versionstamp`](Invalidation::versionstamp) of the last
+	/// handled invalidation resumes the stream where it left off.
+	pub const fn since(mut self, versionstamp: u64) -> Self {
+		self.since = Some(versionstamp);
+		self
+	}
+
+	/// Sets how long to wait between two reads of the changefeeds
+	pub const fn interval(mut self, interval: Duration) -> Self {
+		self.interval = interval;
+		self
+	}
+
+	/// Sets the maximum number of change sets read from a changefeed at once
+	pub const fn batch_size(mut self, batch_size: u32) -> Self {
+		self.batch_size = batch_size;
+		self
+	}
+
+	/// Starts reading the changefeeds of the watched tables, returning a
+	/// stream of the invalidated cache keys
+	///
+	/// The stream yields at most one [`Invalidation`] for each table every
+	/// time the changefeeds are read, with each key listed only once.
+	pub fn stream(self) -> BoxFuture<'r, Result<Invalidations>> {
+		Box::pin(async move {
+			let client = self.client.into_owned();
+			let since = match self.since {
+				Some(since) => since,
+				None => {
+					let mut response = client.query(VERSION_STAMP_QUERY).await?;
+					response.take::<Option<u64>>(0)?.ok_or_else(|| {
+						Error::internal("The database did not return a versionstamp".to_owned())
+					})?
+				}
+			};
+			let state = State {
+				cursor: since,
+				watches: self.watches,
+				interval: self.interval,
+				batch_size: self.batch_size,
+				pending: VecDeque::new(),
+				started: false,
+				client,
+			};
+			let stream = futures::stream::unfold(state, |mut state| async move {
+				let next = state.next().await;
+				Some((next, state))
+			});
+			Ok(Invalidations {
+				inner: Box::pin(stream),
+			})
+		})
+	}
+}
+
+/// The cache keys invalidated by the changes made to a table
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Invalidation {
+	/// The table which was changed
+	pub table: String,
+	/// The keys of the changed records, without duplicates
+	pub keys: Vec<Value>,
+	/// The versionstamp from which the next changes will be read
+	///
+	/// Storing this checkpoint, and passing it to
+	/// [`CacheInvalidation::since`] when restarting, ensures that no change
+	/// is missed.
+	pub versionstamp: u64,
+}
+
+/// A stream of [`Invalidation`]s, returned by [`CacheInvalidation::stream`]
+#[must_use = "streams do nothing unless polled"]
+pub struct Invalidations {
+	inner: Pin<Box<dyn futures::Stream<Item = Result<Invalidation>> + Send + Sync>>,
+}
+
+impl fmt::Debug for Invalidations {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Invalidations").finish_non_exhaustive()
+	}
+}
+
+impl futures::Stream for Invalidations {
+	type Item = Result<Invalidation>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.inner.poll_next_unpin(cx)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, None)
+	}
+}
+
+/// A set of changes read from a changefeed
+#[derive(Debug, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct ChangeSet {
+	versionstamp: u64,
+	changes: Vec<Value>,
+}
+
+struct State<C: Connection> {
+	client: Surreal<C>,
+	watches: Vec<Watch>,
+	/// The versionstamp from which the changefeeds are read next
+	cursor: u64,
+	interval: Duration,
+	batch_size: u32,
+	pending: VecDeque<Invalidation>,
+	started: bool,
+}
+
+impl<C> State<C>
+where
+	C: Connection,
+{
+	async fn next(&mut self) -> Result<Invalidation> {
+		loop {
+			if let Some(invalidation) = self.pending.pop_front() {
+				return Ok(invalidation);
+			}
+			if self.started {
+				sleep(self.interval).await;
+			}
+			self.started = true;
+			let invalidations = self.read().await?;
+			self.pending.extend(invalidations);
+		}
+	}
+
+	/// Reads the next changes of the watched tables
+	///
+	/// The changefeeds of all tables are read together, from a single cursor,
+	/// as the limit of a read applies to the changes of the whole database.
+	/// Reading each table with its own cursor would otherwise skip the changes
+	/// which were cut off by the limit.
+	async fn read(&mut self) -> Result<Vec<Invalidation>> {
+		let query =
+			format!("SHOW CHANGES FOR DATABASE SINCE {} LIMIT {}", self.cursor, self.batch_size);
+		let mut response = self.client.query(query).await?;
+		let mut sets: Vec<ChangeSet> = response.take(0)?;
+		let Some(last) = sets.last().map(|set| set.versionstamp) else {
+			return Ok(Vec::new());
+		};
+		// Each entry of a changefeed holds at least one change, so a read
+		// returning fewer changes than the limit was not cut off. Otherwise the
+		// last change set may be incomplete, and is read again next time,
+		// unless it is the only one and the cursor could not advance.
+		let count: usize = sets.iter().map(|set| set.changes.len()).sum();
+		if count >= self.batch_size as usize && sets.len() > 1 {
+			sets.pop();
+			self.cursor = last;
+		} else {
+			// Changes are read from the given versionstamp inclusively
+			self.cursor = last + 1;
+		}
+		let mut keys = vec![BTreeSet::new(); self.watches.len()];
+		for change in sets.into_iter().flat_map(|set| set.changes) {
+			let Some(record) = changed_record(change) else {
+				continue;
+			};
+			let Some(table) = record_table(&record) else {
+				continue;
+			};
+			for (watch, keys) in self.watches.iter().zip(keys.iter_mut()) {
+				if watch.table == table {
+					keys.insert((watch.key)(record.clone())?);
+				}
+			}
+		}
+		Ok(self
+			.watches
+			.iter()
+			.zip(keys)
+			.filter(|(_, keys)| !keys.is_empty())
+			.map(|(watch, keys)| Invalidation {
+				table: watch.table.clone(),
+				keys: keys.into_iter().collect(),
+				versionstamp: self.cursor,
+			})
+			.collect())
+	}
+}
+
+/// Returns the table of a record read from a changefeed
+fn record_table(record: &Value) -> Option<&str> {
+	match record {
+		Value::Object(record) => match record.get("id") {
+			Some(Value::RecordId(id)) => Some(id.table.as_str()),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+/// Returns the record changed by an entry of a changefeed, if the entry
+/// changed a record rather than the schema of the table
+fn changed_record(change: Value) -> Option<Value> {
+	let Value::Object(mut change) = change else {
+		return None;
+	};
+	// Changefeeds which include the original record store the current record
+	// alongside the patches which were applied
+	if let Some(current @ Value::Object(_)) = change.remove("current") {
+		return Some(current);
+	}
+	match change.remove("update") {
+		Some(record @ Value::Object(_)) => return Some(record),
+		Some(_) => return None,
+		None => {}
+	}
+	match change.remove("delete") {
+		Some(Value::Object(mut record)) => match record.remove("original") {
+			Some(original @ Value::Object(_)) => Some(original),
+			_ => Some(Value::Object(record)),
+		},
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{Object, RecordId};
+
+	fn record(id: i64) -> Value {
+		let mut record = Object::new();
+		record.insert("id".to_owned(), Value::RecordId(RecordId::new("product", id)));
+		Value::Object(record)
+	}
+
+	#[test]
+	fn changed_records_are_extracted() {
+		let change = |kind: &str, value: Value| {
+			let mut change = Object::new();
+			change.insert(kind.to_owned(), value);
+			Value::Object(change)
+		};
+		assert_eq!(changed_record(change("update", record(1))), Some(record(1)));
+		assert_eq!(changed_record(change("delete", record(2))), Some(record(2)));
+		assert_eq!(changed_record(change("define_table", record(3))), None);
+		assert_eq!(record_table(&record(4)), Some("product"));
+	}
+}
@@ -0,0 +1,179 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use surrealdb_types::{ConnectionError, NotAllowedError, NotFoundError};
+
+use crate::conn::Command;
+use crate::method::version::parse_version;
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::protocol::{self, SUPPORTED_VERSIONS};
+use crate::types::Value;
+use crate::{Connection, Error, Result, Surreal};
+
+/// The reason a client is not compatible with the server it is connected to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CompatibilityIssue {
+	/// The version of the server is outside of the range supported by the
+	/// client
+	UnsupportedServer,
+	/// The server stores data in a different revision of the storage format
+	/// than the one the client reads and writes
+	StorageMismatch,
+}
+
+/// A report on the compatibility of the client with the server it is connected
+/// to, returned by [`Surreal::compatibility`](crate::Surreal::compatibility)
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CompatibilityReport {
+	/// The version of this client
+	pub client_version: semver::Version,
+	/// The version of the server
+	pub server_version: semver::Version,
+	/// The range of server versions supported by the client
+	pub supported_versions: &'static str,
+	/// The revision of the storage format which the client reads and writes
+	pub client_storage: u16,
+	/// The revision of the storage format of the datastore, or `None` if the
+	/// server does not report it
+	pub server_storage: Option<u16>,
+	/// The reasons the client is not compatible with the server, if any
+	pub issues: Vec<CompatibilityIssue>,
+}
+
+impl CompatibilityReport {
+	fn new(server_version: semver::Version, server_storage: Option<u16>) -> Self {
+		let client_storage = surrealdb_core::env::STORAGE_VERSION;
+		let mut issues = Vec::new();
+		if protocol::check_version(&server_version).is_err() {
+			issues.push(CompatibilityIssue::UnsupportedServer);
+		}
+		if server_storage.is_some_and(|storage| storage != client_storage) {
+			issues.push(CompatibilityIssue::StorageMismatch);
+		}
+		Self {
+			// invalid package versions should be caught during development
+			client_version: env!("CARGO_PKG_VERSION").parse().expect("valid package version"),
+			server_version,
+			supported_versions: SUPPORTED_VERSIONS,
+			client_storage,
+			server_storage,
+			issues,
+		}
+	}
+
+	/// Checks whether the client can operate against the server
+	pub fn is_compatible(&self) -> bool {
+		self.issues.is_empty()
+	}
+
+	/// Returns the error describing why the client is not compatible with the
+	/// server, if it is not
+	pub fn error(&self) -> Option<Error> {
+		let reasons: Vec<String> = self
+			.issues
+			.iter()
+			.map(|issue| match issue {
+				CompatibilityIssue::UnsupportedServer => format!(
+					"server version `{}` does not match the range supported by the client `{}`",
+					self.server_version, self.supported_versions
+				),
+				CompatibilityIssue::StorageMismatch => format!(
+					"server storage format revision `{}` does not match the revision supported by the client `{}`",
+					self.server_storage.unwrap_or_default(),
+					self.client_storage
+				),
+			})
+			.collect();
+		if reasons.is_empty() {
+			return None;
+		}
+		Some(Error::connection(reasons.join("; "), ConnectionError::Incompatible))
+	}
+}
+
+/// Returned by [`Surreal::compatibility`](crate::Surreal::compatibility),
+/// yields a [`CompatibilityReport`] for the server.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Compatibility<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> Compatibility<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Compatibility<'static, C> {
+		Compatibility {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Compatibility<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<CompatibilityReport>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			let (mut server_version, server_storage) =
+				match router.execute_value(self.client.session_id, Command::Compatibility).await {
+					Ok(value) => {
+						let version = parse_version(value.get("version").clone())?;
+						let storage = match value.get("storage") {
+							Value::Number(storage) => {
+								storage.to_int().and_then(|s| u16::try_from(s).ok())
+							}
+							_ => None,
+						};
+						(version, storage)
+					}
+					// Servers which predate the method, or deny it, only report their
+					// version, which is still checked
+					Err(e)
+						if matches!(e.not_found_details(), Some(NotFoundError::Method { .. }))
+							|| matches!(
+								e.not_allowed_details(),
+								Some(NotAllowedError::Method { .. })
+							) =>
+					{
+						(self.client.version().await?, None)
+					}
+					Err(e) => return Err(e),
+				};
+			// we would like to be able to connect to pre-releases too
+			server_version.pre = Default::default();
+			Ok(CompatibilityReport::new(server_version, server_storage))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reports_every_issue() {
+		let report = CompatibilityReport::new(semver::Version::new(3, 1, 0), None);
+		assert!(report.is_compatible());
+		assert!(report.error().is_none());
+		let storage = surrealdb_core::env::STORAGE_VERSION;
+		let report = CompatibilityReport::new(semver::Version::new(3, 1, 0), Some(storage));
+		assert!(report.is_compatible());
+		let report = CompatibilityReport::new(semver::Version::new(4, 0, 0), Some(storage + 1));
+		assert_eq!(
+			report.issues,
+			[CompatibilityIssue::UnsupportedServer, CompatibilityIssue::StorageMismatch]
+		);
+		let error = report.error().unwrap();
+		assert_eq!(error.connection_details(), Some(&ConnectionError::Incompatible));
+	}
+}
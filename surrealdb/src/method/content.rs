@@ -5,24 +5,32 @@ use std::marker::PhantomData;
 use uuid::Uuid;
 
 use crate::conn::Command;
-use crate::method::{BoxFuture, OnceLockExt};
+use crate::method::{BoxFuture, OnceLockExt, WithOutcome};
 use crate::types::{SurrealValue, Value};
 use crate::{Connection, Result, Surreal};
 
 /// Payload from [`Update::content`](crate::method::Update::content),
 /// [`Upsert::content`](crate::method::Upsert::content), or
 /// [`Create::content`](crate::method::Create::content) (replaces the record body).
+///
+/// The `K` parameter is [`Upserting`] for the payload of an upsert, which
+/// can also report whether the record was created or updated.
 #[derive(Debug)]
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-pub struct Content<'r, C: Connection, R> {
+pub struct Content<'r, C: Connection, R, K = ()> {
 	#[allow(dead_code)]
 	pub(super) txn: Option<Uuid>,
 	pub(super) client: Cow<'r, Surreal<C>>,
 	pub(super) command: Result<Command>,
 	pub(super) response_type: PhantomData<R>,
+	pub(super) kind: PhantomData<K>,
 }
 
-impl<'r, C, R> Content<'r, C, R>
+/// Marks the [`Content`] of an [`Upsert`](crate::method::Upsert)
+#[derive(Debug)]
+pub struct Upserting;
+
+impl<'r, C, R, K> Content<'r, C, R, K>
 where
 	C: Connection,
 {
@@ -35,12 +43,13 @@ where
 			client,
 			command: f(),
 			response_type: PhantomData,
+			kind: PhantomData,
 		}
 	}
 
 	/// Converts to an owned type which can easily be moved to a different
 	/// thread
-	pub fn into_owned(self) -> Content<'static, C, R> {
+	pub fn into_owned(self) -> Content<'static, C, R, K> {
 		Content {
 			client: Cow::Owned(self.client.into_owned()),
 			..self
@@ -48,6 +57,21 @@ where
 	}
 }
 
+impl<'r, C, R> Content<'r, C, Option<R>, Upserting>
+where
+	C: Connection,
+{
+	/// Also reports whether the record was created or updated, as with
+	/// [`Upsert::with_outcome`](crate::method::Upsert::with_outcome)
+	pub fn with_outcome(self) -> WithOutcome<'r, C, R> {
+		WithOutcome {
+			client: self.client,
+			command: self.command,
+			response_type: PhantomData,
+		}
+	}
+}
+
 macro_rules! into_future {
 	($method:ident) => {
 		fn into_future(self) -> Self::IntoFuture {
@@ -64,7 +88,7 @@ macro_rules! into_future {
 	};
 }
 
-impl<'r, Client> IntoFuture for Content<'r, Client, Value>
+impl<'r, Client, K> IntoFuture for Content<'r, Client, Value, K>
 where
 	Client: Connection,
 {
@@ -74,7 +98,7 @@ where
 	into_future! {execute_value}
 }
 
-impl<'r, Client, R> IntoFuture for Content<'r, Client, Option<R>>
+impl<'r, Client, R, K> IntoFuture for Content<'r, Client, Option<R>, K>
 where
 	Client: Connection,
 	R: SurrealValue,
@@ -85,7 +109,7 @@ where
 	into_future! {execute_opt}
 }
 
-impl<'r, Client, R> IntoFuture for Content<'r, Client, Vec<R>>
+impl<'r, Client, R, K> IntoFuture for Content<'r, Client, Vec<R>, K>
 where
 	Client: Connection,
 	R: SurrealValue,
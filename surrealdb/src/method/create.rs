@@ -19,6 +19,7 @@ pub struct Create<'r, C: Connection, R> {
 	pub(super) txn: Option<Uuid>,
 	pub(super) client: Cow<'r, Surreal<C>>,
 	pub(super) resource: Result<Resource>,
+	pub(super) idempotency_key: Option<String>,
 	pub(super) response_type: PhantomData<R>,
 }
 
@@ -43,6 +44,19 @@ where
 			..self
 		}
 	}
+
+	/// Runs the create with an idempotency key, such as a UUID generated by
+	/// the client
+	///
+	/// The server records the result under the key, and returns it rather
+	/// than creating the record again when the same create is retried with
+	/// the same key, for instance after a network timeout. Keys expire after
+	/// a period configured on the server, and can not be used within a
+	/// transaction.
+	pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+		self.idempotency_key = Some(key.into());
+		self
+	}
 }
 
 macro_rules! into_future {
@@ -52,6 +66,7 @@ macro_rules! into_future {
 				txn,
 				client,
 				resource,
+				idempotency_key,
 				..
 			} = self;
 			Box::pin(async move {
@@ -66,7 +81,8 @@ macro_rules! into_future {
 					txn,
 					query: Cow::Owned(format!("CREATE {what}")),
 					variables,
-				};
+				}
+				.with_idempotency_key(idempotency_key)?;
 				router.$method(client.session_id, cmd).await
 			})
 		}
@@ -117,11 +133,12 @@ where
 			let what = what.for_sql_query(&mut variables)?;
 			variables.insert("_content".to_string(), content);
 
-			Ok(Command::Query {
+			Command::Query {
 				txn: self.txn,
 				query: Cow::Owned(format!("CREATE {what} CONTENT $_content")),
 				variables,
-			})
+			}
+			.with_idempotency_key(self.idempotency_key)
 		})
 	}
 }
@@ -156,11 +173,12 @@ where
 				}
 			};
 
-			Ok(Command::Query {
+			Command::Query {
 				txn: self.txn,
 				query,
 				variables,
-			})
+			}
+			.with_idempotency_key(self.idempotency_key)
 		})
 	}
 }
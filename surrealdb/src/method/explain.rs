@@ -0,0 +1,75 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use super::query::IntoVariables;
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::types::{Value, Variables};
+use crate::{Connection, Result, Surreal};
+
+/// A permission evaluation trace future
+///
+/// Returned by [`Surreal::explain_permissions`](crate::Surreal::explain_permissions)
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ExplainPermissions<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) query: Cow<'static, str>,
+	pub(super) variables: Result<Variables>,
+}
+
+impl<C> ExplainPermissions<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> ExplainPermissions<'static, C> {
+		ExplainPermissions {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Binds a parameter or parameters to the explained query
+	pub fn bind(self, vars: impl IntoVariables) -> Self {
+		let variables = match (self.variables, vars.into_variables()) {
+			(Ok(mut a), Ok(b)) => {
+				a.extend(b);
+				Ok(a)
+			}
+			(Err(e), _) | (_, Err(e)) => Err(e),
+		};
+		ExplainPermissions {
+			variables,
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for ExplainPermissions<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Value>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			router
+				.execute_value(
+					self.client.session_id,
+					Command::Query {
+						txn: None,
+						query: Cow::Owned(format!(
+							"EXPLAIN PERMISSIONS FORMAT JSON {}",
+							self.query.trim_end().trim_end_matches(';')
+						)),
+						variables: self.variables?,
+					},
+				)
+				.await
+		})
+	}
+}
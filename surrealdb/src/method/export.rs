@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 use std::future::IntoFuture;
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -12,6 +12,7 @@ use surrealdb_core::kvs::export::{Config as DbExportConfig, TableConfig};
 
 use crate::conn::{Command, MlExportConfig};
 use crate::method::{BoxFuture, ExportConfig as Config, Model, OnceLockExt};
+use crate::opt::{EncryptionKey, encrypt_stream};
 use crate::{Connection, Error, ExtraFeatures, Result, Surreal};
 
 /// Returned by [`Surreal::export`](crate::Surreal::export). File targets complete in place, while
@@ -23,6 +24,7 @@ pub struct Export<'r, C: Connection, R, T = ()> {
 	pub(super) target: R,
 	pub(super) ml_config: Option<MlExportConfig>,
 	pub(super) db_config: Option<DbExportConfig>,
+	pub(super) encryption: Option<EncryptionKey>,
 	pub(super) response: PhantomData<R>,
 	pub(super) export_type: PhantomData<T>,
 }
@@ -42,6 +44,7 @@ where
 				version: version.to_string(),
 			}),
 			db_config: self.db_config,
+			encryption: self.encryption,
 			response: self.response,
 			export_type: PhantomData,
 		}
@@ -55,6 +58,7 @@ where
 			ml_config: self.ml_config,
 			// Use default configuration options
 			db_config: Some(Default::default()),
+			encryption: self.encryption,
 			response: self.response,
 			export_type: PhantomData,
 		}
@@ -175,12 +179,63 @@ where
 		}
 		self
 	}
+
+	/// Whether to end the export with a trailer holding the number of records
+	/// exported from each table and a checksum of the export
+	///
+	/// The trailer is written by default, and is checked when the export is
+	/// imported with [`Import::verify`](crate::method::Import::verify).
+	pub fn checksum(mut self, checksum: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.checksum = checksum;
+		}
+		self
+	}
+
+	/// Export only the definitions of the database, without any records
+	///
+	/// The definitions are ordered so that the dump imports cleanly, for
+	/// example with tables defined before the views computed from them.
+	pub fn schema_only(mut self) -> Self {
+		self.db_config = self.db_config.map(DbExportConfig::schema_only);
+		self
+	}
+
+	/// Export only the records of the database, without any definitions
+	pub fn data_only(mut self) -> Self {
+		self.db_config = self.db_config.map(DbExportConfig::data_only);
+		self
+	}
 }
 
 impl<C, R, T> Export<'_, C, R, T>
 where
 	C: Connection,
 {
+	/// Encrypts the export with a passphrase or a 256-bit key
+	///
+	/// The export is encrypted with AES-256-GCM as it is produced, so that the
+	/// plain export is never written to disk. Pass the same key to
+	/// [`Import::decrypt_with`](crate::method::Import::decrypt_with) to restore
+	/// it. Machine learning model exports can not be encrypted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// db.export("backup.surql.enc").encrypt_with("correct horse battery staple").await?;
+	/// db.import("backup.surql.enc").decrypt_with("correct horse battery staple").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn encrypt_with(mut self, key: impl Into<EncryptionKey>) -> Self {
+		self.encryption = Some(key.into());
+		self
+	}
+
 	/// Converts to an owned type which can easily be moved to a different
 	/// thread
 	pub fn into_owned(self) -> Export<'static, C, R, T> {
@@ -209,6 +264,9 @@ where
 			}
 
 			if let Some(config) = self.ml_config {
+				if self.encryption.is_some() {
+					return Err(unsupported_ml_encryption());
+				}
 				return router
 					.execute_unit(
 						self.client.session_id,
@@ -220,15 +278,30 @@ where
 					.await;
 			}
 
+			let Some(key) = self.encryption else {
+				return router
+					.execute_unit(
+						self.client.session_id,
+						Command::ExportFile {
+							path: self.target,
+							config: self.db_config,
+						},
+					)
+					.await;
+			};
+
+			// Encrypted exports are streamed, and written once encrypted
+			let (tx, rx) = crate::channel::bounded(1);
 			router
 				.execute_unit(
 					self.client.session_id,
-					Command::ExportFile {
-						path: self.target,
+					Command::ExportBytes {
+						bytes: tx,
 						config: self.db_config,
 					},
 				)
-				.await
+				.await?;
+			write_file(&self.target, encrypt_stream(rx, &key)?).await
 		})
 	}
 }
@@ -251,11 +324,13 @@ where
 				));
 			}
 			let (tx, rx) = crate::channel::bounded(1);
-			let rx = Box::pin(rx);
 
 			tracing::info!("Exporting bytes");
 
 			if let Some(config) = self.ml_config {
+				if self.encryption.is_some() {
+					return Err(unsupported_ml_encryption());
+				}
 				router
 					.execute_unit(
 						self.client.session_id,
@@ -266,7 +341,7 @@ where
 					)
 					.await?;
 				return Ok(Backup {
-					rx,
+					rx: Box::pin(rx),
 				});
 			}
 
@@ -280,13 +355,53 @@ where
 				)
 				.await?;
 
+			let rx = match self.encryption {
+				Some(key) => encrypt_stream(rx, &key)?,
+				None => rx,
+			};
+
 			Ok(Backup {
-				rx,
+				rx: Box::pin(rx),
 			})
 		})
 	}
 }
 
+fn unsupported_ml_encryption() -> Error {
+	Error::internal("Machine learning model exports can not be encrypted".to_string())
+}
+
+/// Writes the chunks of an export to a file
+#[cfg(not(target_family = "wasm"))]
+async fn write_file(path: &Path, rx: Receiver<Result<Vec<u8>>>) -> Result<()> {
+	use tokio::io::AsyncWriteExt;
+
+	let mut file = tokio::fs::OpenOptions::new()
+		.write(true)
+		.create(true)
+		.truncate(true)
+		.open(path)
+		.await
+		.map_err(|e| Error::internal(format!("Failed to open `{}`: {e}", path.display())))?;
+	while let Ok(chunk) = rx.recv().await {
+		file.write_all(&chunk?)
+			.await
+			.map_err(|e| Error::internal(format!("Failed to write `{}`: {e}", path.display())))?;
+	}
+	file.flush()
+		.await
+		.map_err(|e| Error::internal(format!("Failed to write `{}`: {e}", path.display())))
+}
+
+#[cfg(target_family = "wasm")]
+async fn write_file(_: &Path, _: Receiver<Result<Vec<u8>>>) -> Result<()> {
+	Err(Error::internal(
+		"File-based export is not supported on this architecture. Use the byte-stream variants \
+		 (e.g. `db.export(()).await?`) instead."
+			.to_string(),
+	))
+}
+
 /// Byte chunks from [`Export`] when the destination is `()` (see
 /// [`Surreal::export`](crate::Surreal::export)).
 #[derive(Debug, Clone)]
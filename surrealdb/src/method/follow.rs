@@ -0,0 +1,489 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::IntoFuture;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::StreamExt;
+pub use surrealdb_core::kvs::export::{ExcludedTables, TableConfig};
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::sleep;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::sleep;
+
+use crate::method::BoxFuture;
+use crate::method::version_stamp::VERSION_STAMP_QUERY;
+use crate::types::{RecordId, SurrealValue, ToSql, Value};
+use crate::{Connection, Error, Result, Surreal};
+
+/// The default interval between two reads of the changefeeds
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The default number of change sets read at once
+const DEFAULT_BATCH_SIZE: u32 = 1000;
+
+/// Reads the structured definitions of the tables of the database
+const TABLES_QUERY: &str = "(INFO FOR DB STRUCTURE).tables";
+
+/// Configures a stream of the changes made to a database, returned by
+/// [`Surreal::follow`](crate::Surreal::follow)
+#[derive(Clone, Debug, Default)]
+pub struct ExportFollowConfig {
+	/// The tables whose changes are streamed
+	///
+	/// Only the changes of tables defined with a `CHANGEFEED`, or of every
+	/// table of a database defined with one, are recorded.
+	pub tables: TableConfig,
+	/// The versionstamp from which the changes are read, or `None` to only
+	/// read the changes made after the stream is started
+	pub since: Option<u64>,
+}
+
+/// Returned by [`Surreal::follow`](crate::Surreal::follow), yields a stream of
+/// the changes made to the selected database, as export statements.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Follow<'r, C: Connection> {
+	client: Cow<'r, Surreal<C>>,
+	config: ExportFollowConfig,
+	interval: Duration,
+	batch_size: u32,
+}
+
+impl<'r, C> Follow<'r, C>
+where
+	C: Connection,
+{
+	pub(super) fn new(client: Cow<'r, Surreal<C>>, config: ExportFollowConfig) -> Self {
+		Self {
+			client,
+			config,
+			interval: DEFAULT_INTERVAL,
+			batch_size: DEFAULT_BATCH_SIZE,
+		}
+	}
+
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Follow<'static, C> {
+		Follow {
+			client: Cow::Owned(self.client.into_owned()),
+			config: self.config,
+			interval: self.interval,
+			batch_size: self.batch_size,
+		}
+	}
+
+	/// Sets how long to wait between two reads of the changefeeds, once every
+	/// change has been streamed
+	pub const fn interval(mut self, interval: Duration) -> Self {
+		self.interval = interval;
+		self
+	}
+
+	/// Sets the maximum number of change sets read at once
+	pub const fn batch_size(mut self, batch_size: u32) -> Self {
+		self.batch_size = batch_size;
+		self
+	}
+}
+
+impl<'r, Client> IntoFuture for Follow<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<ExportChanges>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			if self.batch_size == 0 {
+				return Err(Error::validation(
+					"The batch size of a follow must be greater than 0".to_owned(),
+					None,
+				));
+			}
+			let client = self.client.into_owned();
+			let since = match self.config.since {
+				Some(since) => since,
+				None => {
+					let mut response = client.query(VERSION_STAMP_QUERY).await?;
+					response.take::<Option<u64>>(0)?.ok_or_else(|| {
+						Error::internal("The database did not return a versionstamp".to_owned())
+					})?
+				}
+			};
+			// The changes to a record are replayed according to the type of
+			// its table, which is kept up to date as tables are redefined
+			let mut response = client.query(TABLES_QUERY).await?;
+			let types = response
+				.take::<Vec<Value>>(0)?
+				.iter()
+				.filter_map(|definition| match definition.get("name") {
+					Value::String(name) => {
+						Some((name.clone(), TableType::from_definition(definition)))
+					}
+					_ => None,
+				})
+				.collect();
+			// Like an export, the stream starts by switching the importing
+			// session to import mode, so that events are not triggered again
+			let header = ExportChange {
+				versionstamp: since,
+				statements: vec!["OPTION IMPORT;".to_owned()],
+			};
+			let state = State {
+				client,
+				tables: self.config.tables,
+				types,
+				cursor: since,
+				interval: self.interval,
+				batch_size: self.batch_size,
+				pending: VecDeque::from([header]),
+				caught_up: false,
+			};
+			let stream = futures::stream::unfold(state, |mut state| async move {
+				let next = state.next().await;
+				Some((next, state))
+			});
+			Ok(ExportChanges {
+				inner: Box::pin(stream),
+			})
+		})
+	}
+}
+
+/// The statements replaying the changes committed in a single transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExportChange {
+	/// The versionstamp from which the next changes will be read
+	///
+	/// Storing this checkpoint once the statements have been applied, and
+	/// passing it as [`ExportFollowConfig::since`] when restarting, ensures
+	/// that no change is missed.
+	pub versionstamp: u64,
+	/// The SurrealQL statements replaying the changes, in the order they were
+	/// made
+	pub statements: Vec<String>,
+}
+
+impl fmt::Display for ExportChange {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for statement in &self.statements {
+			writeln!(f, "{statement}")?;
+		}
+		Ok(())
+	}
+}
+
+/// A stream of [`ExportChange`]s, returned by [`Follow`]
+///
+/// The stream never ends on its own, and stops reading the changefeeds once
+/// it is dropped.
+#[must_use = "streams do nothing unless polled"]
+pub struct ExportChanges {
+	inner: Pin<Box<dyn futures::Stream<Item = Result<ExportChange>> + Send + Sync>>,
+}
+
+impl fmt::Debug for ExportChanges {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ExportChanges").finish_non_exhaustive()
+	}
+}
+
+impl futures::Stream for ExportChanges {
+	type Item = Result<ExportChange>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.inner.poll_next_unpin(cx)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, None)
+	}
+}
+
+/// A set of changes read from the changefeeds
+#[derive(Debug, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct ChangeSet {
+	versionstamp: u64,
+	changes: Vec<Value>,
+}
+
+struct State<C: Connection> {
+	client: Surreal<C>,
+	tables: TableConfig,
+	/// The types of the tables of the database
+	types: HashMap<String, TableType>,
+	/// The versionstamp from which the changes are read next
+	cursor: u64,
+	interval: Duration,
+	batch_size: u32,
+	pending: VecDeque<ExportChange>,
+	/// Whether every change recorded so far has been read
+	caught_up: bool,
+}
+
+impl<C> State<C>
+where
+	C: Connection,
+{
+	async fn next(&mut self) -> Result<ExportChange> {
+		loop {
+			if let Some(change) = self.pending.pop_front() {
+				return Ok(change);
+			}
+			// Only wait once the changes which were already recorded have
+			// all been streamed
+			if self.caught_up {
+				sleep(self.interval).await;
+			}
+			self.caught_up = self.read().await?;
+		}
+	}
+
+	/// Reads the next batch of change sets, returning whether the batch was
+	/// the last one recorded so far
+	async fn read(&mut self) -> Result<bool> {
+		let query =
+			format!("SHOW CHANGES FOR DATABASE SINCE {} LIMIT {}", self.cursor, self.batch_size);
+		let mut response = self.client.query(query).await?;
+		let sets: Vec<ChangeSet> = response.take(0)?;
+		let exhausted = sets.len() < self.batch_size as usize;
+		for set in sets {
+			let mut statements = Vec::new();
+			for change in set.changes {
+				// Tables defined or altered while following change how the
+				// changes to their records are replayed from then on
+				let definition = change.get("define_table");
+				if let Value::String(name) = definition.get("name") {
+					self.types.insert(name.clone(), TableType::from_definition(definition));
+					continue;
+				}
+				let Some(change) = parse_change(change) else {
+					continue;
+				};
+				let table = change.id().table.as_str();
+				if self.tables.includes(table) {
+					let kind = self.types.get(table).copied().unwrap_or_default();
+					statements.extend(change.statements(kind));
+				}
+			}
+			// Changes are read from the given versionstamp inclusively
+			self.cursor = set.versionstamp + 1;
+			if !statements.is_empty() {
+				self.pending.push_back(ExportChange {
+					versionstamp: self.cursor,
+					statements,
+				});
+			}
+		}
+		Ok(exhausted)
+	}
+}
+
+/// The type of a table, which decides whether its records are edges
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum TableType {
+	/// A table which holds both records and edges
+	#[default]
+	Any,
+	/// A table which only holds records
+	Normal,
+	/// A table which only holds edges
+	Relation,
+}
+
+impl TableType {
+	/// Reads the type of a table from its structured definition
+	fn from_definition(definition: &Value) -> Self {
+		match definition.get("kind").get("kind") {
+			Value::String(kind) if kind == "NORMAL" => Self::Normal,
+			Value::String(kind) if kind == "RELATION" => Self::Relation,
+			_ => Self::Any,
+		}
+	}
+}
+
+/// A change made to a record, as read from a changefeed
+#[derive(Debug, PartialEq)]
+enum RecordChange {
+	/// The record was created or updated
+	Update {
+		id: RecordId,
+		record: Value,
+	},
+	/// The record was deleted
+	Delete {
+		id: RecordId,
+	},
+}
+
+impl RecordChange {
+	fn id(&self) -> &RecordId {
+		match self {
+			Self::Update {
+				id,
+				..
+			}
+			| Self::Delete {
+				id,
+			} => id,
+		}
+	}
+
+	/// Renders the change with the statements of an export
+	///
+	/// The records of a changefeed may already exist in the instance the
+	/// statements are imported into, so records are upserted rather than
+	/// inserted. Edges can not be upserted, and are replaced instead.
+	///
+	/// Whether a record is an edge is decided by the type of its table, as a
+	/// record of a normal table may have `in` and `out` fields of its own.
+	/// Only tables of any type hold both, where a record is taken to be an
+	/// edge if both of these fields are record ids.
+	fn statements(self, table: TableType) -> Vec<String> {
+		match self {
+			Self::Update {
+				id,
+				record,
+			} => {
+				let is_edge = match table {
+					TableType::Normal => false,
+					TableType::Relation => true,
+					TableType::Any => {
+						matches!(record.get("in"), Value::RecordId(_))
+							&& matches!(record.get("out"), Value::RecordId(_))
+					}
+				};
+				if is_edge {
+					vec![
+						format!("DELETE {};", id.to_sql()),
+						format!("INSERT RELATION [ {} ];", record.to_sql()),
+					]
+				} else {
+					vec![format!("UPSERT {} CONTENT {};", id.to_sql(), record.to_sql())]
+				}
+			}
+			Self::Delete {
+				id,
+			} => vec![format!("DELETE {};", id.to_sql())],
+		}
+	}
+}
+
+/// Reads an entry of a changefeed, if it changed a record rather than the
+/// schema of a table
+fn parse_change(change: Value) -> Option<RecordChange> {
+	let Value::Object(mut change) = change else {
+		return None;
+	};
+	// Changefeeds which include the original record store the current record
+	// alongside the patches which were applied
+	let record = match change.remove("current") {
+		Some(current @ Value::Object(_)) => Some(current),
+		_ => match change.remove("update") {
+			Some(record @ Value::Object(_)) => Some(record),
+			_ => None,
+		},
+	};
+	if let Some(record) = record {
+		return match record.get("id") {
+			Value::RecordId(id) => Some(RecordChange::Update {
+				id: id.clone(),
+				record,
+			}),
+			_ => None,
+		};
+	}
+	match change.remove("delete") {
+		Some(record) => match record.get("id") {
+			Value::RecordId(id) => Some(RecordChange::Delete {
+				id: id.clone(),
+			}),
+			_ => None,
+		},
+		None => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::Object;
+
+	fn change(kind: &str, value: Value) -> Value {
+		let mut change = Object::new();
+		change.insert(kind.to_owned(), value);
+		Value::Object(change)
+	}
+
+	fn record(fields: Vec<(&str, Value)>) -> Value {
+		let mut record = Object::new();
+		for (field, value) in fields {
+			record.insert(field.to_owned(), value);
+		}
+		Value::Object(record)
+	}
+
+	#[test]
+	fn changes_are_rendered_as_statements() {
+		let id = Value::RecordId(RecordId::new("person", 1));
+		let person = record(vec![("id", id.clone()), ("age", Value::from_t(42_i64))]);
+		let statements = parse_change(change("update", person)).unwrap().statements(TableType::Any);
+		assert_eq!(statements, ["UPSERT person:1 CONTENT { age: 42, id: person:1 };"]);
+
+		let edge = record(vec![
+			("id", Value::RecordId(RecordId::new("knows", 1))),
+			("in", id.clone()),
+			("out", Value::RecordId(RecordId::new("person", 2))),
+		]);
+		let statements =
+			parse_change(change("update", edge.clone())).unwrap().statements(TableType::Any);
+		assert_eq!(
+			statements,
+			[
+				"DELETE knows:1;",
+				"INSERT RELATION [ { id: knows:1, in: person:1, out: person:2 } ];"
+			]
+		);
+		// Records of normal tables are never edges, whatever their fields
+		let statements =
+			parse_change(change("update", edge)).unwrap().statements(TableType::Normal);
+		assert_eq!(
+			statements,
+			["UPSERT knows:1 CONTENT { id: knows:1, in: person:1, out: person:2 };"]
+		);
+		// Edges of relation tables are replaced, even without their fields
+		let link = record(vec![("id", Value::RecordId(RecordId::new("knows", 2)))]);
+		let statements =
+			parse_change(change("update", link)).unwrap().statements(TableType::Relation);
+		assert_eq!(statements, ["DELETE knows:2;", "INSERT RELATION [ { id: knows:2 } ];"]);
+
+		let deleted = record(vec![("id", id)]);
+		let statements =
+			parse_change(change("delete", deleted)).unwrap().statements(TableType::Any);
+		assert_eq!(statements, ["DELETE person:1;"]);
+
+		let table = record(vec![("name", Value::String("person".to_owned()))]);
+		assert_eq!(parse_change(change("define_table", table)), None);
+	}
+
+	#[test]
+	fn table_types_are_read_from_definitions() {
+		let kind = |kind: &str| {
+			record(vec![
+				("name", Value::String("knows".to_owned())),
+				("kind", record(vec![("kind", Value::String(kind.to_owned()))])),
+			])
+		};
+		assert_eq!(TableType::from_definition(&kind("RELATION")), TableType::Relation);
+		assert_eq!(TableType::from_definition(&kind("NORMAL")), TableType::Normal);
+		assert_eq!(TableType::from_definition(&kind("ANY")), TableType::Any);
+		assert_eq!(TableType::from_definition(&Value::None), TableType::Any);
+	}
+}
@@ -0,0 +1,319 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::IntoFuture;
+
+use crate::method::BoxFuture;
+use crate::types::{Kind, KindLiteral, RecordId, SurrealValue, ToSql, Value};
+use crate::{Connection, Error, Result, Surreal};
+
+/// The number of records created by a single query
+const BATCH_SIZE: u64 = 100;
+
+/// The number of consecutive batches which may fail to create any record
+/// before giving up, for example because every generated value fails a field
+/// assertion
+const MAX_FAILED_BATCHES: u32 = 10;
+
+/// The number of elements generated for arrays and sets
+const COLLECTION_LEN: u64 = 3;
+
+/// Returned by [`Surreal::generate`](crate::Surreal::generate) to insert
+/// random records which match the schema of a table.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Generate<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) table: String,
+	pub(super) count: u64,
+}
+
+impl<C> Generate<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Generate<'static, C> {
+		Generate {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Generate<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Vec<RecordId>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let mut response = self
+				.client
+				.query("INFO FOR TABLE type::table($table) STRUCTURE")
+				.bind(("table", self.table.clone()))
+				.await?;
+			let info = response.take::<Option<TableInfo>>(0)?.unwrap_or_default();
+			let statement = format!(
+				"CREATE type::table($table) CONTENT {} RETURN VALUE id;",
+				content(&self.table, &info)?
+			);
+			let mut ids = Vec::new();
+			let mut failed = 0;
+			let mut last_error = None;
+			while (ids.len() as u64) < self.count {
+				let batch = (self.count - ids.len() as u64).min(BATCH_SIZE) as usize;
+				let mut response = self
+					.client
+					.query(statement.repeat(batch))
+					.bind(("table", self.table.clone()))
+					.await?;
+				let created = ids.len();
+				// Records which fail an assertion or collide on a unique index
+				// are generated again in the next batch
+				for i in 0..batch {
+					match response.take::<Option<RecordId>>(i) {
+						Ok(Some(id)) => ids.push(id),
+						Ok(None) => {}
+						Err(e) => last_error = Some(e),
+					}
+				}
+				if ids.len() == created {
+					failed += 1;
+					if failed == MAX_FAILED_BATCHES {
+						return Err(last_error.unwrap_or_else(|| {
+							Error::internal("No records could be generated".to_owned())
+						}));
+					}
+				} else {
+					failed = 0;
+				}
+			}
+			Ok(ids)
+		})
+	}
+}
+
+/// The parts of the structure of a table which records are generated from
+#[derive(Debug, Default, SurrealValue)]
+struct TableInfo {
+	fields: Vec<FieldInfo>,
+	indexes: Vec<IndexInfo>,
+}
+
+#[derive(Debug, SurrealValue)]
+struct FieldInfo {
+	name: String,
+	kind: Option<String>,
+	value: Option<Value>,
+	computed: Option<Value>,
+}
+
+#[derive(Debug, SurrealValue)]
+struct IndexInfo {
+	cols: Vec<String>,
+	index: String,
+}
+
+/// A field, and the fields nested within it
+#[derive(Default)]
+struct Node {
+	kind: Option<Kind>,
+	unique: bool,
+	children: BTreeMap<String, Node>,
+}
+
+/// Builds the SurrealQL object from which each record is created
+fn content(table: &str, info: &TableInfo) -> Result<String> {
+	let unique = info
+		.indexes
+		.iter()
+		.filter(|ix| ix.index == "UNIQUE")
+		.flat_map(|ix| ix.cols.iter().map(String::as_str))
+		.collect::<BTreeSet<_>>();
+	let mut root = Node::default();
+	for field in &info.fields {
+		// The id is generated by the database, computed fields are calculated
+		// by it, and array elements are generated along with their array
+		if field.name == "id"
+			|| field.value.is_some()
+			|| field.computed.is_some()
+			|| field.name.contains(['[', '*'])
+		{
+			continue;
+		}
+		let mut node = &mut root;
+		for part in field.name.split('.') {
+			let part = part.trim_matches(['`', '⟨', '⟩']);
+			node = node.children.entry(part.to_owned()).or_default();
+		}
+		if let Some(kind) = &field.kind {
+			node.kind = Some(
+				surrealdb_core::syn::kind(kind)
+					.map_err(|e| {
+						Error::internal(format!("Invalid kind for field `{}`: {e}", field.name))
+					})?
+					.into(),
+			);
+		}
+		node.unique = unique.contains(field.name.as_str());
+	}
+	object(table, &root)
+}
+
+/// Generates an object from the fields nested within a node
+fn object(table: &str, node: &Node) -> Result<String> {
+	let mut entries = Vec::new();
+	for (name, child) in &node.children {
+		let value = if !child.children.is_empty() {
+			object(table, child)?
+		} else if let Some(kind) = &child.kind {
+			generate(table, kind, child.unique)?
+		} else {
+			// Fields without a kind can hold anything, and are left unset
+			continue;
+		};
+		entries.push(format!("{}: {value}", quote(name)));
+	}
+	Ok(format!("{{ {} }}", entries.join(", ")))
+}
+
+/// Generates a SurrealQL expression which evaluates to a random value of a
+/// kind. Unique values are drawn from a larger range.
+fn generate(table: &str, kind: &Kind, unique: bool) -> Result<String> {
+	let value = match kind {
+		Kind::Any | Kind::Object => "{}".to_owned(),
+		Kind::None => "NONE".to_owned(),
+		Kind::Null => "NULL".to_owned(),
+		Kind::Bool => "rand::bool()".to_owned(),
+		Kind::Bytes => "<bytes> rand::string(16)".to_owned(),
+		Kind::Datetime => "rand::time()".to_owned(),
+		Kind::Duration => "rand::duration(1s, 1d)".to_owned(),
+		Kind::Uuid => "rand::uuid()".to_owned(),
+		Kind::Int | Kind::Number if unique => "rand::int()".to_owned(),
+		Kind::Int | Kind::Number => "rand::int(0, 1000)".to_owned(),
+		Kind::Float if unique => "rand::float()".to_owned(),
+		Kind::Float => "rand::float(0, 1000)".to_owned(),
+		Kind::Decimal if unique => "<decimal> rand::float()".to_owned(),
+		Kind::Decimal => "<decimal> rand::float(0, 1000)".to_owned(),
+		Kind::String if unique => "rand::string(32)".to_owned(),
+		Kind::String => "rand::string(12)".to_owned(),
+		Kind::Table(tables) => {
+			let tb = tables.first().map_or(table, |tb| tb.as_str());
+			format!("type::table({})", quote(tb))
+		}
+		Kind::Record(tables) => {
+			let tb = tables.first().map_or(table, |tb| tb.as_str());
+			format!("type::record({}, rand::uuid())", quote(tb))
+		}
+		Kind::Either(kinds) => {
+			// Prefer a value over leaving optional fields empty
+			let values = kinds
+				.iter()
+				.filter(|k| !matches!(k, Kind::None | Kind::Null))
+				.filter_map(|k| generate(table, k, unique).ok())
+				.collect::<Vec<_>>();
+			match values.len() {
+				0 if kinds.iter().any(|k| matches!(k, Kind::None)) => "NONE".to_owned(),
+				0 if kinds.iter().any(|k| matches!(k, Kind::Null)) => "NULL".to_owned(),
+				0 => return Err(unsupported(kind)),
+				1 => values.into_iter().next().unwrap_or_default(),
+				_ => format!("rand::enum({})", values.join(", ")),
+			}
+		}
+		Kind::Array(inner, max) => format!("[{}]", elements(table, inner, *max)?),
+		Kind::Set(inner, max) => format!("<set> [{}]", elements(table, inner, *max)?),
+		Kind::Literal(KindLiteral::Array(kinds)) => {
+			let values =
+				kinds.iter().map(|k| generate(table, k, false)).collect::<Result<Vec<_>>>()?;
+			format!("[{}]", values.join(", "))
+		}
+		Kind::Literal(KindLiteral::Object(fields)) => {
+			let mut entries = Vec::new();
+			for (name, k) in fields {
+				entries.push(format!("{}: {}", quote(name), generate(table, k, false)?));
+			}
+			format!("{{ {} }}", entries.join(", "))
+		}
+		Kind::Literal(_) => kind.to_sql(),
+		Kind::Regex | Kind::Geometry(_) | Kind::Function(..) | Kind::Range | Kind::File(_) => {
+			return Err(unsupported(kind));
+		}
+	};
+	Ok(value)
+}
+
+/// Generates the elements of an array or set
+fn elements(table: &str, kind: &Kind, max: Option<u64>) -> Result<String> {
+	let len = max.map_or(COLLECTION_LEN, |max| max.min(COLLECTION_LEN));
+	let values = (0..len).map(|_| generate(table, kind, false)).collect::<Result<Vec<_>>>()?;
+	Ok(values.join(", "))
+}
+
+fn unsupported(kind: &Kind) -> Error {
+	Error::validation(format!("Cannot generate random values of type `{}`", kind.to_sql()), None)
+}
+
+/// Quotes a string as a SurrealQL string literal
+fn quote(value: &str) -> String {
+	serde_json::Value::from(value).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn kind(kind: &str) -> Kind {
+		surrealdb_core::syn::kind(kind).unwrap().into()
+	}
+
+	#[test]
+	fn generates_values_for_kinds() {
+		assert_eq!(generate("person", &kind("int"), false).unwrap(), "rand::int(0, 1000)");
+		assert_eq!(generate("person", &kind("int"), true).unwrap(), "rand::int()");
+		assert_eq!(generate("person", &kind("option<bool>"), false).unwrap(), "rand::bool()");
+		assert_eq!(
+			generate("person", &kind("record<company>"), false).unwrap(),
+			"type::record(\"company\", rand::uuid())"
+		);
+		assert_eq!(
+			generate("person", &kind("array<uuid, 2>"), false).unwrap(),
+			"[rand::uuid(), rand::uuid()]"
+		);
+		assert_eq!(
+			generate("person", &kind("'admin' | 'user'"), false).unwrap(),
+			"rand::enum('admin', 'user')"
+		);
+		generate("person", &kind("function"), false).unwrap_err();
+	}
+
+	#[test]
+	fn generates_nested_objects() {
+		let field = |name: &str, kind: &str| FieldInfo {
+			name: name.to_owned(),
+			kind: Some(kind.to_owned()),
+			value: None,
+			computed: None,
+		};
+		let info = TableInfo {
+			fields: vec![
+				field("id", "int"),
+				field("address", "object"),
+				field("address.city", "string"),
+				field("email", "string"),
+				field("tags", "array<string>"),
+				field("tags[*]", "string"),
+			],
+			indexes: vec![IndexInfo {
+				cols: vec!["email".to_owned()],
+				index: "UNIQUE".to_owned(),
+			}],
+		};
+		assert_eq!(
+			content("person", &info).unwrap(),
+			"{ \"address\": { \"city\": rand::string(12) }, \"email\": rand::string(32), \"tags\": [rand::string(12), rand::string(12), rand::string(12)] }"
+		);
+	}
+}
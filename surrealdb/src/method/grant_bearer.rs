@@ -0,0 +1,152 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use crate::method::BoxFuture;
+use crate::method::grants_for::GrantSubject;
+use crate::method::select::escape_field;
+use crate::types::{Datetime, SurrealValue, ToSql};
+use crate::{Connection, Error, Result, Surreal};
+
+/// Returned by [`Surreal::grant_bearer`](crate::Surreal::grant_bearer), issues
+/// a bearer grant for each of a list of subjects in a single transaction.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct GrantBearer<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) access: String,
+	pub(super) subjects: Vec<GrantSubject>,
+}
+
+impl<C> GrantBearer<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> GrantBearer<'static, C> {
+		GrantBearer {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Builds the statement which issues the grants
+	fn query(&self) -> Result<String> {
+		let mut users = Vec::new();
+		let mut records = Vec::new();
+		for subject in self.subjects.iter() {
+			match subject {
+				GrantSubject::User(name) => users.push(escape_field(name)),
+				GrantSubject::Record(id) => records.push(id.to_sql()),
+			}
+		}
+		let (kind, subjects) = match (users.is_empty(), records.is_empty()) {
+			(false, true) => ("USER", users),
+			(true, false) => ("RECORD", records),
+			(true, true) => {
+				return Err(Error::validation(
+					"At least one subject is required to issue grants".to_owned(),
+					None,
+				));
+			}
+			(false, false) => {
+				return Err(Error::validation(
+					"The subjects of the grants must be all users or all records".to_owned(),
+					None,
+				));
+			}
+		};
+		Ok(format!(
+			"ACCESS {} GRANT FOR {kind} IN [{}]",
+			escape_field(&self.access),
+			subjects.join(", ")
+		))
+	}
+}
+
+impl<'r, Client> IntoFuture for GrantBearer<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Vec<GrantOutcome>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let query = self.query()?;
+			let mut response = self.client.query(query).await?;
+			let entries: Vec<GrantEntry> = response.take(0)?;
+			Ok(entries.into_iter().map(GrantOutcome::from).collect())
+		})
+	}
+}
+
+/// The outcome of issuing a bearer grant for one subject
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum GrantOutcome {
+	/// The grant was issued
+	Issued(IssuedGrant),
+	/// The grant could not be issued, such as when the user does not exist
+	/// or the subject was rate limited
+	Failed {
+		/// The subject which was not granted access
+		subject: GrantSubject,
+		/// Why the grant could not be issued
+		error: String,
+	},
+}
+
+/// A bearer grant which was just issued
+///
+/// This is the only time the plaintext key of the grant is returned.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct IssuedGrant {
+	/// The identifier of the grant
+	pub id: String,
+	/// The access method which issued the grant
+	pub ac: String,
+	/// The subject which the grant was issued to
+	pub subject: GrantSubject,
+	/// The bearer key of the grant
+	pub key: String,
+	/// When the grant expires, if it does
+	pub expiration: Option<Datetime>,
+}
+
+/// An entry of the result of the statement which issues the grants
+#[derive(SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct GrantEntry {
+	ac: String,
+	subject: GrantSubject,
+	id: Option<String>,
+	expiration: Option<Datetime>,
+	grant: Option<GrantKey>,
+	error: Option<String>,
+}
+
+#[derive(SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct GrantKey {
+	key: String,
+}
+
+impl From<GrantEntry> for GrantOutcome {
+	fn from(entry: GrantEntry) -> Self {
+		match (entry.id, entry.grant, entry.error) {
+			(Some(id), Some(grant), None) => Self::Issued(IssuedGrant {
+				id,
+				ac: entry.ac,
+				subject: entry.subject,
+				key: grant.key,
+				expiration: entry.expiration,
+			}),
+			(.., error) => Self::Failed {
+				subject: entry.subject,
+				error: error.unwrap_or_else(|| "The grant was not issued".to_owned()),
+			},
+		}
+	}
+}
@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use crate::method::BoxFuture;
+use crate::method::select::escape_field;
+use crate::types::{Datetime, RecordId, SurrealValue, ToSql};
+use crate::{Connection, Result, Surreal};
+
+/// Returned by [`Surreal::grants_for`](crate::Surreal::grants_for), yields the
+/// access grants of a subject across every access method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct GrantsFor<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) subject: GrantSubject,
+}
+
+impl<C> GrantsFor<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> GrantsFor<'static, C> {
+		GrantsFor {
+			client: Cow::Owned(self.client.into_owned()),
+			subject: self.subject,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for GrantsFor<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Vec<GrantSummary>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let query = format!("ACCESS SHOW ALL FOR {}", self.subject.clause());
+			let mut response = self.client.query(query).await?;
+			response.take(0)
+		})
+	}
+}
+
+/// The subject which an access grant was issued to
+#[derive(Clone, Debug, PartialEq, Eq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[surreal(lowercase)]
+#[non_exhaustive]
+pub enum GrantSubject {
+	/// A system user, defined at the selected base
+	User(String),
+	/// A record
+	Record(RecordId),
+}
+
+impl GrantSubject {
+	/// A system user, defined at the selected base
+	pub fn user(name: impl Into<String>) -> Self {
+		Self::User(name.into())
+	}
+
+	/// Returns the `FOR` clause of the statement which shows the grants
+	fn clause(&self) -> String {
+		match self {
+			Self::User(name) => format!("USER {}", escape_field(name)),
+			Self::Record(id) => format!("RECORD {}", id.to_sql()),
+		}
+	}
+}
+
+impl From<RecordId> for GrantSubject {
+	fn from(id: RecordId) -> Self {
+		Self::Record(id)
+	}
+}
+
+/// A summary of an access grant
+///
+/// The secret of the grant is never included.
+#[derive(Clone, Debug, PartialEq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[non_exhaustive]
+pub struct GrantSummary {
+	/// The identifier of the grant
+	pub id: String,
+	/// The access method which issued the grant
+	pub ac: String,
+	/// The type of the grant, such as `bearer`
+	#[surreal(rename = "type")]
+	pub kind: String,
+	/// The subject which the grant was issued to
+	pub subject: GrantSubject,
+	/// When the grant was created
+	pub creation: Datetime,
+	/// When the grant expires, if it does
+	pub expiration: Option<Datetime>,
+	/// When the grant was revoked, if it was
+	pub revocation: Option<Datetime>,
+}
+
+impl GrantSummary {
+	/// Checks if the grant can still be used
+	pub fn is_active(&self) -> bool {
+		self.revocation.is_none()
+			&& self.expiration.as_ref().is_none_or(|exp| **exp > chrono::Utc::now())
+	}
+}
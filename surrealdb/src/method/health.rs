@@ -1,9 +1,11 @@
 use std::borrow::Cow;
 use std::future::IntoFuture;
+use std::time::Duration;
 
 use crate::conn::Command;
 use crate::method::{BoxFuture, OnceLockExt};
-use crate::{Connection, Result, Surreal};
+use crate::types::SurrealValue;
+use crate::{Connection, Error, Result, Surreal};
 
 /// Returned by [`Surreal::health`](crate::Surreal::health), completing successfully when the server
 /// reports healthy.
@@ -40,3 +42,73 @@ where
 		})
 	}
 }
+
+/// A report on the health of the datastore, returned by
+/// [`Surreal::health_report`](crate::Surreal::health_report)
+#[derive(Clone, Debug, Default, PartialEq, Eq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[non_exhaustive]
+pub struct HealthReport {
+	/// Whether the value written by the storage probe could be read back
+	pub readable: bool,
+	/// Whether the storage probe could write to the storage engine, or `None`
+	/// if the write was skipped because the datastore is locked for writes
+	pub writable: Option<bool>,
+	/// How long the storage probe took to complete
+	pub latency: Duration,
+	/// How long ago the node last refreshed its cluster heartbeat
+	pub heartbeat_age: Option<Duration>,
+	/// How long ago live query notifications were last delivered, when they
+	/// are delivered by the live query router
+	pub live_query_lag: Option<Duration>,
+	/// The number of records which the background scrubber has found not to
+	/// match their checksums, when record checksums are enabled
+	pub corrupt_records: u64,
+	/// The errors which were encountered while probing
+	pub errors: Vec<String>,
+}
+
+impl HealthReport {
+	/// Checks whether the storage engine could be read from and written to
+	pub fn is_healthy(&self) -> bool {
+		self.readable && self.writable != Some(false) && self.errors.is_empty()
+	}
+}
+
+/// Returned by [`Surreal::health_report`](crate::Surreal::health_report),
+/// yields a [`HealthReport`] for the datastore.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct HealthCheck<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> HealthCheck<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> HealthCheck<'static, C> {
+		HealthCheck {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for HealthCheck<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<HealthReport>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			let report =
+				router.execute_value(self.client.session_id, Command::HealthReport).await?;
+			HealthReport::from_value(report).map_err(|e| Error::internal(e.to_string()))
+		})
+	}
+}
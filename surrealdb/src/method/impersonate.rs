@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::types::Value;
+use crate::{Connection, Result, Surreal};
+
+/// Returned by [`Surreal::impersonate`](crate::Surreal::impersonate) to run
+/// the commands of a connection as another user.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Impersonate<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) subject: Value,
+}
+
+impl<C> Impersonate<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Impersonate<'static, C> {
+		Impersonate {
+			client: Cow::Owned(self.client.into_owned()),
+			subject: self.subject,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Impersonate<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		let Impersonate {
+			client,
+			subject,
+		} = self;
+		Box::pin(async move {
+			let router = client.inner.router.extract()?;
+			router
+				.execute_unit(
+					client.session_id,
+					Command::Impersonate {
+						subject: subject
+							.into_object()
+							.map_err(|e| crate::Error::internal(e.to_string()))?,
+					},
+				)
+				.await
+		})
+	}
+}
+
+/// Returned by [`Surreal::stop_impersonating`](crate::Surreal::stop_impersonating)
+/// to restore the authentication of the administrator.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct StopImpersonating<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> StopImpersonating<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> StopImpersonating<'static, C> {
+		StopImpersonating {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for StopImpersonating<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			router.execute_unit(self.client.session_id, Command::StopImpersonating).await
+		})
+	}
+}
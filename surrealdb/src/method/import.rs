@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use crate::conn::Command;
 use crate::method::{BoxFuture, Model, OnceLockExt};
+use crate::opt::EncryptionKey;
 use crate::{Connection, Error, ExtraFeatures, Result, Surreal};
 
 /// Returned by [`Surreal::import`](crate::Surreal::import) to restore from a `.surql` backup file.
@@ -14,6 +15,9 @@ pub struct Import<'r, C: Connection, T = ()> {
 	pub(super) client: Cow<'r, Surreal<C>>,
 	pub(super) file: PathBuf,
 	pub(super) is_ml: bool,
+	pub(super) resume: bool,
+	pub(super) verify: bool,
+	pub(super) decryption: Option<EncryptionKey>,
 	pub(super) import_type: PhantomData<T>,
 }
 
@@ -27,9 +31,94 @@ where
 			client: self.client,
 			file: self.file,
 			is_ml: true,
+			resume: false,
+			verify: false,
+			decryption: None,
 			import_type: PhantomData,
 		}
 	}
+
+	/// Makes the import resumable, continuing an interrupted import
+	///
+	/// The statements in the file are applied in transactions of many
+	/// statements, and the progress of the import is recorded as each
+	/// transaction commits. If an earlier resumable import of the same file
+	/// was interrupted, the statements it already applied are skipped,
+	/// provided they have not changed since. The import stops at the first
+	/// statement which fails, so that it can be resumed once the problem has
+	/// been fixed.
+	///
+	/// The file must not contain `BEGIN`, `COMMIT` or `CANCEL` statements.
+	/// Resuming imports is only supported by embedded datastores.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// db.import("backup.surql").resume().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn resume(self) -> Self {
+		Import {
+			resume: true,
+			..self
+		}
+	}
+
+	/// Verifies the export against its trailer before importing it
+	///
+	/// The whole export is read first, and nothing is imported if it has no
+	/// trailer, or if it was truncated or modified since it was exported.
+	/// Encrypted exports are verified once they have been decrypted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// db.import("backup.surql").verify().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn verify(self) -> Self {
+		Import {
+			verify: true,
+			..self
+		}
+	}
+
+	/// Decrypts an export which was encrypted with
+	/// [`Export::encrypt_with`](crate::method::Export::encrypt_with)
+	///
+	/// The export is decrypted as it is read. A wrong key is reported before
+	/// any statement is imported, while an export which was modified or
+	/// truncated is rejected when the modification is reached, so the import
+	/// should be combined with [`resume`](Self::resume) or a transaction
+	/// where a partial import must be avoided.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// db.import("backup.surql.enc").decrypt_with("correct horse battery staple").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn decrypt_with(self, key: impl Into<EncryptionKey>) -> Self {
+		Import {
+			decryption: Some(key.into()),
+			..self
+		}
+	}
 }
 
 impl<C, T> Import<'_, C, T>
@@ -79,6 +168,9 @@ where
 					self.client.session_id,
 					Command::ImportFile {
 						path: self.file,
+						resume: self.resume,
+						verify: self.verify,
+						decryption: self.decryption,
 					},
 				)
 				.await
@@ -6,11 +6,12 @@ use uuid::Uuid;
 
 use super::insert_relation::InsertRelation;
 use super::transaction::WithTransaction;
+use super::update_elements::field_path;
 use super::validate_data;
 use crate::conn::Command;
 use crate::method::{BoxFuture, Content, OnceLockExt};
 use crate::opt::Resource;
-use crate::types::{SurrealValue, Value, Variables};
+use crate::types::{SurrealValue, Value, Variables, object};
 use crate::{Connection, Error, Result, Surreal};
 
 /// Returned by [`Surreal::insert`](crate::Surreal::insert) for `INSERT` batches; use
@@ -21,9 +22,75 @@ pub struct Insert<'r, C: Connection, R> {
 	pub(super) txn: Option<Uuid>,
 	pub(super) client: Cow<'r, Surreal<C>>,
 	pub(super) resource: Result<Resource>,
+	pub(super) on_duplicate: Option<OnDuplicate>,
 	pub(super) response_type: PhantomData<R>,
 }
 
+/// The updates made by [`Insert::on_duplicate`] to the records which already
+/// exist
+#[derive(Debug, Default)]
+pub struct OnDuplicate {
+	assignments: Vec<(String, &'static str, Assignment)>,
+}
+
+#[derive(Debug)]
+enum Assignment {
+	Expression(String),
+	Value(Value),
+}
+
+impl OnDuplicate {
+	/// Sets a field to the result of a SurrealQL expression
+	///
+	/// The expression is evaluated against the existing record, and the record
+	/// which was to be inserted is available as `$input`.
+	pub fn set(mut self, field: impl Into<String>, expr: impl Into<String>) -> Self {
+		self.assignments.push((field.into(), "=", Assignment::Expression(expr.into())));
+		self
+	}
+
+	/// Sets a field to a value
+	pub fn set_value(mut self, field: impl Into<String>, value: impl SurrealValue) -> Self {
+		self.assignments.push((field.into(), "=", Assignment::Value(value.into_value())));
+		self
+	}
+
+	/// Adds a value to a field, as with `+=`
+	pub fn increment(mut self, field: impl Into<String>, value: impl SurrealValue) -> Self {
+		self.assignments.push((field.into(), "+=", Assignment::Value(value.into_value())));
+		self
+	}
+
+	/// Subtracts a value from a field, as with `-=`
+	pub fn decrement(mut self, field: impl Into<String>, value: impl SurrealValue) -> Self {
+		self.assignments.push((field.into(), "-=", Assignment::Value(value.into_value())));
+		self
+	}
+
+	/// Builds the `ON DUPLICATE KEY UPDATE` clause of the updates
+	fn clause(self, variables: &mut Variables) -> Result<String> {
+		if self.assignments.is_empty() {
+			return Err(Error::validation(
+				"At least one field must be updated on duplicate records".to_owned(),
+				None,
+			));
+		}
+		let mut sets = Vec::with_capacity(self.assignments.len());
+		for (i, (field, operator, assignment)) in self.assignments.into_iter().enumerate() {
+			let field = field_path(&field)?;
+			let value = match assignment {
+				Assignment::Expression(expr) => format!("({expr})"),
+				Assignment::Value(value) => {
+					variables.insert(format!("_duplicate_{i}"), value);
+					format!("$_duplicate_{i}")
+				}
+			};
+			sets.push(format!("{field} {operator} {value}"));
+		}
+		Ok(format!("ON DUPLICATE KEY UPDATE {}", sets.join(", ")))
+	}
+}
+
 impl<C, R> WithTransaction for Insert<'_, C, R>
 where
 	C: Connection,
@@ -46,6 +113,29 @@ where
 			..self
 		}
 	}
+
+	/// Updates the records which already exist, rather than failing to insert
+	/// them, as with `ON DUPLICATE KEY UPDATE`
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// use surrealdb::types::{Value, object};
+	///
+	/// // Counts the views of a page, starting from 1 for a new page
+	/// let _: Vec<Value> = db
+	///     .insert("views")
+	///     .on_duplicate(|u| u.increment("count", 1))
+	///     .content(object! { id: "home", count: 1 })
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_duplicate(mut self, update: impl FnOnce(OnDuplicate) -> OnDuplicate) -> Self {
+		self.on_duplicate = Some(update(OnDuplicate::default()));
+		self
+	}
 }
 
 macro_rules! into_future {
@@ -55,6 +145,7 @@ macro_rules! into_future {
 				txn,
 				client,
 				resource,
+				on_duplicate,
 				..
 			} = self;
 			Box::pin(async move {
@@ -70,7 +161,19 @@ macro_rules! into_future {
 						// CREATE accepts a table name expression and works without content
 						Cow::Owned(format!("CREATE {what}"))
 					}
-					Resource::RecordId(_) => Cow::Owned(format!("CREATE {what}")),
+					Resource::RecordId(record_id) => match on_duplicate {
+						// Only a record with a known id can already exist
+						Some(update) => {
+							let clause = update.clause(&mut variables)?;
+							variables.insert("_table".to_string(), Value::Table(record_id.table));
+							variables.insert(
+								"_data".to_string(),
+								Value::Object(object! { id: record_id.key }),
+							);
+							Cow::Owned(format!("INSERT INTO $_table $_data {clause}"))
+						}
+						None => Cow::Owned(format!("CREATE {what}")),
+					},
 					Resource::Object(_) => {
 						return Err(Error::internal(
 							"Insert queries on objects are not supported".to_string(),
@@ -157,9 +260,16 @@ where
 			let mut variables = Variables::new();
 			let what = what_resource.for_sql_query(&mut variables)?;
 
+			let clause = match self.on_duplicate {
+				Some(update) => Some(update.clause(&mut variables)?),
+				None => None,
+			};
+
 			let query = match what_resource {
 				Resource::Table(_) => {
-					if data.is_array() {
+					if let Some(clause) = clause {
+						Cow::Owned(format!("INSERT INTO {what} $_data {clause}"))
+					} else if data.is_array() {
 						Cow::Owned(format!("INSERT INTO {what} $_data"))
 					} else {
 						// Single object - use CREATE with CONTENT
@@ -178,7 +288,13 @@ where
 						x.insert("id".to_string(), record_id.key.into_value());
 					}
 
-					Cow::Owned(format!("CREATE {what} CONTENT $_data"))
+					match clause {
+						Some(clause) => {
+							variables.insert("_table".to_string(), Value::Table(record_id.table));
+							Cow::Owned(format!("INSERT INTO $_table $_data {clause}"))
+						}
+						None => Cow::Owned(format!("CREATE {what} CONTENT $_data")),
+					}
 				}
 				Resource::Object(_) => {
 					return Err(Error::internal(
@@ -230,8 +346,15 @@ where
 			let mut variables = Variables::new();
 			let what = what_resource.for_sql_query(&mut variables)?;
 
+			let clause = match self.on_duplicate {
+				Some(update) => update.clause(&mut variables)?,
+				None => String::new(),
+			};
+
 			let query = match what_resource {
-				Resource::Table(_) => Cow::Owned(format!("INSERT RELATION INTO {what} $_data;")),
+				Resource::Table(_) => {
+					Cow::Owned(format!("INSERT RELATION INTO {what} $_data {clause};"))
+				}
 				Resource::RecordId(record_id) => {
 					if data.is_array() {
 						return Err(Error::validation(
@@ -244,7 +367,7 @@ where
 						x.insert("id".to_string(), record_id.key.into_value());
 					}
 
-					Cow::Owned(format!("INSERT RELATION INTO {what} $_data RETURN AFTER"))
+					Cow::Owned(format!("INSERT RELATION INTO {what} $_data {clause} RETURN AFTER"))
 				}
 				Resource::Array(_) => {
 					return Err(Error::internal(
@@ -273,3 +396,26 @@ where
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn builds_the_on_duplicate_clause() {
+		let mut variables = Variables::new();
+		let update = OnDuplicate::default()
+			.set("count", "count + $input.count")
+			.increment("stats.seen", 1)
+			.set_value("name", "tobie");
+		assert_eq!(
+			update.clause(&mut variables).unwrap(),
+			"ON DUPLICATE KEY UPDATE `count` = (count + $input.count), \
+			 `stats`.`seen` += $_duplicate_1, `name` = $_duplicate_2"
+		);
+		assert_eq!(variables.get("_duplicate_1"), Some(&1.into_value()));
+		assert_eq!(variables.len(), 2);
+		assert!(OnDuplicate::default().clause(&mut variables).is_err());
+		assert!(OnDuplicate::default().set("stats..seen", "1").clause(&mut variables).is_err());
+	}
+}
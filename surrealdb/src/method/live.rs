@@ -17,10 +17,10 @@ use wasm_bindgen_futures::spawn_local as spawn;
 use crate::conn::{Command, Router};
 use crate::engine::any::Any;
 use crate::method::{BoxFuture, Live, OnceLockExt, Select};
-use crate::notification::Notification;
+use crate::notification::{Notification, NotificationError};
 use crate::opt::Resource;
 use crate::types::{
-	Action, Notification as CoreNotification, RecordId, SurrealValue, Value, Variables,
+	Action, Notification as CoreNotification, RecordId, SurrealValue, ToSql, Value,
 };
 use crate::{Connection, Error, ExtraFeatures, Result, Surreal};
 
@@ -31,6 +31,9 @@ where
 	let Select {
 		client,
 		resource,
+		fields,
+		filter,
+		variables: bound,
 		..
 	} = this;
 	Box::pin(async move {
@@ -45,21 +48,22 @@ where
 
 		let what_resource = resource?;
 
-		let mut variables = Variables::new();
+		let mut variables = bound;
 		let what = what_resource.for_sql_query(&mut variables)?;
+		let fields = fields.as_deref().unwrap_or("*");
 
 		// Generate the LIVE SELECT SQL based on resource type
-		let query = match what_resource {
+		let (mut query, has_condition) = match what_resource {
 			Resource::Table(table) => {
 				variables.insert("_table".to_string(), Value::Table(table));
-				format!("LIVE SELECT * FROM {what}")
+				(format!("LIVE SELECT {fields} FROM {what}"), false)
 			}
 			Resource::RecordId(record) => {
 				// For a specific record, we need to query the table with a WHERE clause
 				// because LIVE queries don't support record IDs directly
 				variables.insert("_table".to_string(), Value::Table(record.table.clone()));
 				variables.insert("_record_id".to_string(), Value::RecordId(record));
-				"LIVE SELECT * FROM $_table WHERE id = $_record_id".to_string()
+				(format!("LIVE SELECT {fields} FROM $_table WHERE id = $_record_id"), true)
 			}
 			Resource::Object(_) => {
 				return Err(Error::validation(
@@ -122,17 +126,32 @@ where
 
 				// Build final query
 				if conditions.is_empty() {
-					format!("LIVE SELECT * FROM {table_expr}")
+					(format!("LIVE SELECT {fields} FROM {table_expr}"), false)
 				} else {
-					format!("LIVE SELECT * FROM {table_expr} WHERE {}", conditions.join(" AND "))
+					let query = format!(
+						"LIVE SELECT {fields} FROM {table_expr} WHERE {}",
+						conditions.join(" AND ")
+					);
+					(query, true)
 				}
 			}
 		};
 
+		// Add the filter to the conditions of the query
+		if let Some(filter) = filter {
+			let keyword = if has_condition {
+				"AND"
+			} else {
+				"WHERE"
+			};
+			query = format!("{query} {keyword} ({filter})");
+		}
+
 		// Execute the LIVE SELECT query directly to get the UUID
 		let results = router
 			.execute_query(
 				client.session_id,
+				None,
 				Command::Query {
 					query: Cow::Owned(query),
 					txn: None,
@@ -146,29 +165,28 @@ where
 			Error::query("LIVE query returned no results".to_string(), QueryError::NotExecuted)
 		})?;
 
-		let id = match result.result? {
-			Value::Uuid(id) => *id,
-			Value::Array(mut arr) if arr.len() == 1 => match arr.pop() {
-				Some(Value::Uuid(id)) => *id,
-				_ => {
-					return Err(Error::internal(
-						"successful live query didn't return a uuid".to_string(),
-					));
-				}
-			},
-			other => {
-				return Err(Error::internal(format!(
-					"successful live query didn't return a uuid, got: {:?}",
-					other
-				)));
-			}
-		};
+		let id = live_query_id(result.result?)?;
 
 		let rx = register(router, id, client.session_id).await?;
 		Ok(Stream::new(Arc::clone(&client.inner).into(), id, Some(rx)))
 	})
 }
 
+/// Extracts the id of a live query from the result of its `LIVE SELECT`
+pub(crate) fn live_query_id(value: Value) -> Result<Uuid> {
+	match value {
+		Value::Uuid(id) => Ok(*id),
+		Value::Array(mut arr) if arr.len() == 1 => match arr.pop() {
+			Some(Value::Uuid(id)) => Ok(*id),
+			_ => Err(Error::internal("successful live query didn't return a uuid".to_string())),
+		},
+		other => Err(Error::internal(format!(
+			"successful live query didn't return a uuid, got: {:?}",
+			other
+		))),
+	}
+}
+
 pub(crate) async fn register(
 	router: &Router,
 	id: Uuid,
@@ -252,8 +270,62 @@ impl<R> Stream<R> {
 			response_type: PhantomData,
 		}
 	}
+
+	/// Returns the id of the live query
+	pub fn id(&self) -> crate::types::Uuid {
+		self.id.into()
+	}
+
+	/// Returns the number of notifications which have been received for the
+	/// live query but not yet read from the stream
+	///
+	/// A growing lag means that the stream is not being polled as fast as
+	/// notifications arrive.
+	pub fn lag(&self) -> usize {
+		self.rx.as_ref().map_or(0, |rx| rx.len())
+	}
+
+	/// Kills the live query, completing once the server has stopped it
+	///
+	/// Dropping the stream also kills the live query, but in the background,
+	/// so any error is discarded.
+	pub async fn kill(mut self) -> Result<()> {
+		// The live query no longer needs to be killed when the stream is dropped
+		if self.rx.take().is_none() {
+			return Ok(());
+		}
+		let router = self.client.inner.router.extract()?;
+		router
+			.execute_unit(
+				self.client.session_id,
+				Command::Kill {
+					uuid: self.id,
+				},
+			)
+			.await
+	}
+
+	/// Converts the stream into a [`Subscription`], which ends with
+	/// [`NotificationError::Killed`] when the live query is killed on the
+	/// server
+	pub fn subscription(mut self) -> Subscription<R> {
+		Subscription {
+			inner: Stream {
+				client: self.client.clone(),
+				id: self.id,
+				rx: self.rx.take(),
+				response_type: PhantomData,
+			},
+		}
+	}
 }
 
+/// A typed handle to a live query, which yields its notifications
+///
+/// The live query is killed when the handle is dropped, or explicitly with
+/// [`LiveHandle::kill`].
+pub type LiveHandle<R> = Stream<R>;
+
 macro_rules! poll_next {
 	($result:ident => $body:expr_2021) => {
 		fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
@@ -337,6 +409,123 @@ where
 	poll_next_and_convert! {}
 }
 
+/// Notifications from a live query, returned by [`Stream::subscription`]
+///
+/// A [`Stream`] simply ends when its live query is killed on the server, just
+/// as it does when the connection is lost. A subscription instead yields
+/// [`NotificationError::Killed`] as its last item, with the reason the live
+/// query was killed, so that the two can be told apart. Errors raised by the
+/// `WHERE` clause or the projection of the live query are yielded as
+/// [`NotificationError::Query`].
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct Subscription<R> {
+	inner: Stream<R>,
+}
+
+impl<R> Subscription<R> {
+	/// Returns the id of the live query
+	pub fn id(&self) -> crate::types::Uuid {
+		self.inner.id()
+	}
+
+	/// Kills the live query, completing once the server has stopped it
+	pub async fn kill(self) -> Result<()> {
+		self.inner.kill().await
+	}
+}
+
+macro_rules! poll_subscription {
+	($data:ty) => {
+		fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+			let stream = &mut self.as_mut().get_mut().inner;
+			let Some(ref mut rx) = stream.rx else {
+				return Poll::Ready(None);
+			};
+			let notification = match rx.poll_next_unpin(cx) {
+				Poll::Ready(Some(Ok(notification))) => notification,
+				Poll::Ready(Some(Err(error))) => {
+					return Poll::Ready(Some(Err(NotificationError::Error(error))));
+				}
+				Poll::Ready(None) => return Poll::Ready(None),
+				Poll::Pending => return Poll::Pending,
+			};
+			if notification.action == Action::Killed {
+				// Nothing follows, and the live query no longer needs to be
+				// killed when the subscription is dropped
+				stream.rx = None;
+			}
+			Poll::Ready(Some(subscription_item::<$data>(notification)))
+		}
+	};
+}
+
+impl futures::Stream for Subscription<Value> {
+	type Item = std::result::Result<Notification<Value>, NotificationError>;
+
+	poll_subscription!(Value);
+}
+
+impl<R> futures::Stream for Subscription<Option<R>>
+where
+	R: SurrealValue + Unpin,
+{
+	type Item = std::result::Result<Notification<R>, NotificationError>;
+
+	poll_subscription!(R);
+}
+
+impl<R> futures::Stream for Subscription<Vec<R>>
+where
+	R: SurrealValue + Unpin,
+{
+	type Item = std::result::Result<Notification<R>, NotificationError>;
+
+	poll_subscription!(R);
+}
+
+impl<R> futures::Stream for Subscription<Notification<R>>
+where
+	R: SurrealValue + Unpin,
+{
+	type Item = std::result::Result<Notification<R>, NotificationError>;
+
+	poll_subscription!(R);
+}
+
+fn subscription_item<R>(
+	notification: CoreNotification,
+) -> std::result::Result<Notification<R>, NotificationError>
+where
+	R: SurrealValue,
+{
+	match notification.action {
+		Action::Killed => Err(NotificationError::Killed {
+			reason: match notification.result {
+				Value::String(reason) => Some(reason),
+				_ => None,
+			},
+		}),
+		Action::Error => Err(NotificationError::Query {
+			message: match notification.result {
+				Value::String(message) => message,
+				other => other.to_sql(),
+			},
+		}),
+		action => match R::from_value(notification.result) {
+			Ok(data) => Ok(Notification {
+				query_id: notification.id,
+				action,
+				data,
+			}),
+			Err(error) => Err(NotificationError::Error(Error::serialization(
+				error.to_string(),
+				SerializationError::Deserialization,
+			))),
+		},
+	}
+}
+
 pub(crate) fn kill<Client>(client: &Surreal<Client>, uuid: Uuid)
 where
 	Client: Connection,
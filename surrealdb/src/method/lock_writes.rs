@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::{Connection, Result, Surreal};
+
+/// How long to wait for open write transactions to finish by default
+pub(super) const DEFAULT_WAIT: Duration = Duration::from_secs(10);
+
+/// How long a write lock may be held by default
+pub(super) const DEFAULT_MAX_HOLD: Duration = Duration::from_secs(60);
+
+/// Returned by [`Surreal::lock_writes`](crate::Surreal::lock_writes) to lock
+/// the datastore for writes.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct LockWrites<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) wait: Duration,
+	pub(super) max_hold: Duration,
+}
+
+impl<C> LockWrites<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> LockWrites<'static, C> {
+		LockWrites {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Sets how long to wait for open write transactions to finish
+	///
+	/// Defaults to 10 seconds.
+	pub fn wait(mut self, wait: Duration) -> Self {
+		self.wait = wait;
+		self
+	}
+
+	/// Sets how long the lock may be held before it is forcibly released
+	///
+	/// Defaults to 60 seconds.
+	pub fn max_hold(mut self, max_hold: Duration) -> Self {
+		self.max_hold = max_hold;
+		self
+	}
+}
+
+impl<'r, Client> IntoFuture for LockWrites<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<WriteLockGuard<Client>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			let id = Uuid::now_v7();
+			router
+				.execute_unit(
+					self.client.session_id,
+					Command::LockWrites {
+						id,
+						wait: self.wait,
+						max_hold: self.max_hold,
+					},
+				)
+				.await?;
+			Ok(WriteLockGuard {
+				client: self.client.into_owned(),
+				id,
+				released: false,
+			})
+		})
+	}
+}
+
+/// A lock preventing new write transactions on the datastore
+///
+/// Returned by [`Surreal::lock_writes`](crate::Surreal::lock_writes). The lock
+/// is released when the guard is dropped, or with [`WriteLockGuard::release`],
+/// which also reports whether the lock was still held.
+#[derive(Debug)]
+#[must_use = "the lock is released as soon as the guard is dropped"]
+pub struct WriteLockGuard<C: Connection> {
+	client: Surreal<C>,
+	id: Uuid,
+	released: bool,
+}
+
+impl<C> WriteLockGuard<C>
+where
+	C: Connection,
+{
+	/// Releases the lock, allowing write transactions to continue
+	///
+	/// Returns an error if the lock was forcibly released because it was held
+	/// for longer than its maximum hold duration.
+	pub async fn release(mut self) -> Result<()> {
+		self.released = true;
+		let router = self.client.inner.router.extract()?;
+		router
+			.execute_unit(
+				self.client.session_id,
+				Command::UnlockWrites {
+					id: self.id,
+				},
+			)
+			.await
+	}
+}
+
+impl<C> Drop for WriteLockGuard<C>
+where
+	C: Connection,
+{
+	fn drop(&mut self) {
+		if self.released {
+			return;
+		}
+		// The guard may be dropped outside of a runtime, so the release is
+		// sent without waiting for it
+		if let Ok(router) = self.client.inner.router.extract() {
+			router.send_detached(
+				self.client.session_id,
+				Command::UnlockWrites {
+					id: self.id,
+				},
+			);
+		}
+	}
+}
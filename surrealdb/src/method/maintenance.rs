@@ -0,0 +1,91 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::types::{Table, ToSql, Variables};
+use crate::{Connection, Result, Surreal};
+
+/// Returned by [`Surreal::maintenance`](crate::Surreal::maintenance) to run
+/// maintenance operations against the storage engine
+#[derive(Debug)]
+pub struct Maintenance<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<'r, C> Maintenance<'r, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Maintenance<'static, C> {
+		Maintenance {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+
+	/// Compacts the key range of a table in the storage engine
+	///
+	/// After large ranges of records have been deleted, scans over the table
+	/// still have to step over the deleted entries until the storage engine
+	/// compacts them away. This forces the compaction of the table, so that
+	/// the space is reclaimed and subsequent scans are fast again.
+	///
+	/// The table must exist in the selected namespace and database. Engines
+	/// which do not support compaction, such as the in-memory engine, return
+	/// an error.
+	pub fn compact_table(self, table: impl Into<Table>) -> CompactTable<'r, C> {
+		CompactTable {
+			client: self.client,
+			table: table.into(),
+		}
+	}
+}
+
+/// Returned by [`Maintenance::compact_table`], completing once the table has
+/// been compacted
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CompactTable<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) table: Table,
+}
+
+impl<C> CompactTable<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> CompactTable<'static, C> {
+		CompactTable {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for CompactTable<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			router
+				.execute_unit(
+					self.client.session_id,
+					Command::Query {
+						txn: None,
+						query: Cow::Owned(format!("ALTER TABLE {} COMPACT", self.table.to_sql())),
+						variables: Variables::new(),
+					},
+				)
+				.await
+		})
+	}
+}
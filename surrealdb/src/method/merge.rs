@@ -21,6 +21,7 @@ pub struct Merge<'r, C: Connection, D, R> {
 	pub(super) resource: Result<Resource>,
 	pub(super) content: D,
 	pub(super) upsert: bool,
+	pub(super) idempotency_key: Option<String>,
 	pub(super) response_type: PhantomData<R>,
 }
 
@@ -47,6 +48,7 @@ macro_rules! into_future {
 				resource,
 				content,
 				upsert,
+				idempotency_key,
 				..
 			} = self;
 			let content = content.into_value();
@@ -87,7 +89,8 @@ macro_rules! into_future {
 					txn,
 					query,
 					variables,
-				};
+				}
+				.with_idempotency_key(idempotency_key)?;
 
 				router.$method(client.session_id, cmd).await
 			})
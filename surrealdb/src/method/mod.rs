@@ -7,76 +7,132 @@ use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 use crate::opt::auth::{Credentials, Token};
-use crate::opt::{IntoEndpoint, IntoExportDestination, WaitFor, auth};
-use crate::types::{SurrealValue, Value, Variables};
+use crate::opt::{Fixture, IntoEndpoint, IntoExportDestination, Middleware, WaitFor, auth};
+use crate::types::{Object, RecordId, SurrealValue, Table, Value, Variables};
 use crate::{Connect, Connection, OnceLockExt, SessionClone, Surreal};
 
 pub(crate) mod live;
+mod lock_writes;
 pub(crate) mod query;
 
+mod advisor;
 mod authenticate;
 mod begin;
+mod cache_invalidation;
 mod cancel;
 mod commit;
+mod compatibility;
 mod content;
 mod create;
 mod delete;
+mod explain;
 mod export;
+mod follow;
+mod generate;
+mod grant_bearer;
+mod grants_for;
 mod health;
+mod impersonate;
 mod import;
 mod insert;
 mod insert_relation;
 mod invalidate;
+mod maintenance;
 mod merge;
+mod openapi;
 mod patch;
+mod query_bytes;
+mod relate_many;
 mod run;
+mod run_query;
+mod scan;
+mod schema_changes;
+mod seed;
 mod select;
 mod set;
 mod signin;
+mod signin_mfa;
 mod signup;
+mod snapshot;
+mod table_stats;
 mod transaction;
+mod traverse;
 mod unset;
 mod update;
+mod update_elements;
 mod upsert;
 mod use_db;
 mod use_defaults;
 mod use_ns;
+mod users;
 mod version;
+mod version_stamp;
 
 #[cfg(test)]
 mod tests;
 
+pub use advisor::{Advisor, AdvisorCapture, IndexRecommendation, IndexRecommendations};
 pub use authenticate::Authenticate;
 pub use begin::Begin;
+pub use cache_invalidation::{CacheInvalidation, Invalidation, Invalidations};
 pub use cancel::Cancel;
 pub use commit::Commit;
-pub use content::Content;
+pub use compatibility::{Compatibility, CompatibilityIssue, CompatibilityReport};
+pub use content::{Content, Upserting};
 pub use create::Create;
 pub use delete::Delete;
+pub use explain::ExplainPermissions;
 pub use export::{Backup, Export};
+pub use follow::{
+	ExcludedTables, ExportChange, ExportChanges, ExportFollowConfig, Follow, TableConfig,
+};
 use futures::Future;
-pub use health::Health;
+pub use generate::Generate;
+pub use grant_bearer::{GrantBearer, GrantOutcome, IssuedGrant};
+pub use grants_for::{GrantSubject, GrantSummary, GrantsFor};
+pub use health::{Health, HealthCheck, HealthReport};
+pub use impersonate::{Impersonate, StopImpersonating};
 pub use import::Import;
-pub use insert::Insert;
+pub use insert::{Insert, OnDuplicate};
 pub use invalidate::Invalidate;
-pub use live::Stream;
+pub use live::{LiveHandle, Stream, Subscription};
+pub use lock_writes::{LockWrites, WriteLockGuard};
+pub use maintenance::{CompactTable, Maintenance};
 pub use merge::Merge;
+pub use openapi::OpenApi;
 pub use patch::Patch;
-pub use query::{IntoVariables, Query, QueryStream};
+pub use query::{IntoQuery, IntoVariables, Query, QueryStream};
+pub use query_bytes::{FormattedQuery, QueryBytes};
+pub use relate_many::{EndpointPolicy, RelateMany, RelateOutcome};
 pub use run::{IntoFn, Run};
+pub use run_query::RunQuery;
+pub use scan::{Records, Scan};
+pub use schema_changes::{
+	LiveParams, ParamChange, SchemaAction, SchemaChange, SchemaChanges, SchemaKind,
+};
+pub use seed::Seed;
 pub use select::Select;
+pub(crate) use select::escape_field;
 pub use set::Set;
 pub use signin::Signin;
+pub use signin_mfa::{MfaChallenge, MfaSignin, SigninMfa};
 pub use signup::Signup;
-use tokio::sync::watch;
+pub use snapshot::Snapshot;
+use surrealdb_core::rpc::DbResultStats;
+pub use table_stats::{TableStatistics, TableStats};
+use tokio::sync::{broadcast, watch};
 pub use transaction::Transaction;
+pub use traverse::{IntoRecordId, TraversalPath, Traverse};
 pub use unset::Unset;
 pub use update::Update;
-pub use upsert::Upsert;
+pub use update_elements::UpdateElements;
+pub use upsert::{Upsert, WithOutcome};
 pub use use_db::UseDb;
 pub use use_defaults::UseDefaults;
 pub use use_ns::UseNs;
+pub use users::{CreateUser, Role, UserDuration, UserInfo, UserLevel, UserRequest, Users};
 pub use version::Version;
+pub use version_stamp::VersionStamp;
 
 use super::opt::{CreateResource, IntoResource};
 
@@ -96,16 +152,99 @@ pub struct ExportConfig;
 /// [`Surreal::select`](crate::Surreal::select).
 pub struct Live;
 
+/// Type-state marker for [`Select::with_total`](Select::with_total) on a [`Select`] from
+/// [`Surreal::select`](crate::Surreal::select).
+pub struct WithTotal;
+
+/// Type-state marker for [`Traverse::with_paths`](Traverse::with_paths) on a [`Traverse`] from
+/// [`Surreal::traverse`](crate::Surreal::traverse).
+pub struct WithPaths;
+
 /// Relation marker type
 pub struct Relation;
 
-/// Time elapsed for a single statement, paired by [`WithStats::take`](WithStats::take) after
-/// [`Query::with_stats`](crate::method::Query::with_stats).
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Execution statistics for a single statement, paired by [`WithStats::take`](WithStats::take)
+/// after [`Query::with_stats`](crate::method::Query::with_stats), or returned by
+/// [`IndexedResults::take_stats`](crate::IndexedResults::take_stats).
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub struct Stats {
 	/// The time taken to execute the query
 	pub execution_time: Option<Duration>,
+	/// The number of records read while executing the query, before any filtering
+	pub rows_scanned: u64,
+	/// The number of rows returned by the query
+	pub rows_returned: u64,
+	/// The names of the indexes read by the query
+	pub indexes_used: Vec<String>,
+	/// Whether the query read every record of a table instead of using an index
+	pub table_scan: bool,
+	/// The number of records an `UPSERT` query created
+	pub records_created: u64,
+	/// The number of records an `UPSERT` query updated, rather than created
+	pub records_updated: u64,
+	/// The most rows the query plan can return, if the planner could bound it
+	pub max_rows: Option<u64>,
+	/// Whether the query timed out with `RETURN PARTIAL`, returning only the
+	/// rows found before the timeout
+	pub truncated: bool,
+}
+
+impl From<DbResultStats> for Stats {
+	fn from(stats: DbResultStats) -> Self {
+		Stats {
+			execution_time: stats.execution_time,
+			rows_scanned: stats.rows_scanned,
+			rows_returned: stats.rows_returned,
+			indexes_used: stats.indexes_used,
+			table_scan: stats.table_scan,
+			records_created: stats.records_created,
+			records_updated: stats.records_updated,
+			max_rows: stats.max_rows,
+			truncated: stats.truncated,
+		}
+	}
+}
+
+/// The statistics of the compressed responses received by a connection,
+/// returned by [`Surreal::compression_stats`](crate::Surreal::compression_stats)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CompressionStats {
+	/// The number of responses which were received compressed
+	pub responses: u64,
+	/// The number of bytes received for those responses
+	pub compressed_bytes: u64,
+	/// The number of bytes those responses were decompressed to
+	pub decompressed_bytes: u64,
+}
+
+impl CompressionStats {
+	/// Returns the number of bytes which compression saved from being sent
+	pub fn bytes_saved(&self) -> u64 {
+		self.decompressed_bytes.saturating_sub(self.compressed_bytes)
+	}
+}
+
+/// A page of records along with the total number of matching records, returned by
+/// [`Select::with_total`](crate::method::Select::with_total).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Page<R> {
+	/// The records in this page
+	pub items: Vec<R>,
+	/// The number of records matched, ignoring the page bounds
+	pub total: u64,
+}
+
+/// Whether an upsert created a new record or updated an existing one, returned by
+/// [`Upsert::with_outcome`](crate::method::Upsert::with_outcome).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpsertOutcome {
+	/// The record did not exist, and was created
+	Created,
+	/// The record already existed, and was updated
+	Updated,
 }
 
 /// Wraps the [`Query`] output from [`Query::with_stats`](crate::method::Query::with_stats) so each
@@ -592,6 +731,56 @@ where
 		}
 	}
 
+	/// Signs in to a record access method defined `WITH MFA TOTP`
+	///
+	/// Records which are enrolled in the second factor with
+	/// `ACCESS ... GRANT TOTP FOR RECORD ...` receive a challenge, which is
+	/// completed with a code from their authenticator app. Other records are
+	/// signed in with their credentials alone.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::method::MfaSignin;
+	/// use surrealdb::opt::auth::Record;
+	/// use surrealdb::types::SurrealValue;
+	///
+	/// #[derive(Debug, SurrealValue)]
+	/// struct AuthParams {
+	///     email: String,
+	///     password: String,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # let code = String::new();
+	/// let signin = db.signin_mfa(Record {
+	///     namespace: "main".into(),
+	///     database: "main".into(),
+	///     access: "user_access".into(),
+	///     params: AuthParams {
+	///         email: "john.doe@example.com".into(),
+	///         password: "password123".into(),
+	///     },
+	/// }).await?;
+	///
+	/// let token = match signin {
+	///     MfaSignin::Complete(token) => token,
+	///     // Ask the user for the code shown by their authenticator app
+	///     MfaSignin::Challenge(challenge) => challenge.verify(code).await?,
+	/// };
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn signin_mfa(&'_ self, credentials: impl Credentials<auth::Signin>) -> SigninMfa<'_, C> {
+		SigninMfa {
+			client: Cow::Borrowed(self),
+			credentials: credentials.into_value(),
+		}
+	}
+
 	/// Invalidates the authentication for the current connection
 	///
 	/// # Examples
@@ -612,6 +801,71 @@ where
 		}
 	}
 
+	/// Runs the subsequent commands of the current connection as another user
+	///
+	/// This allows owners and editors at the root or namespace level to see
+	/// exactly what a record or system user sees, when supporting or debugging
+	/// an application. Only users within the administrator's own level can be
+	/// impersonated, and the start and end of the impersonation, as well as the
+	/// statements run in the meantime, are recorded in the audit events of the
+	/// server. The experimental `impersonation` capability must be enabled.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::opt::auth::{RecordUser, Root};
+	/// use surrealdb::types::RecordId;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.signin(Root {
+	///     username: "root".to_string(),
+	///     password: "root".to_string(),
+	/// })
+	/// .await?;
+	///
+	/// db.impersonate(RecordUser {
+	///     namespace: "namespace".to_string(),
+	///     database: "database".to_string(),
+	///     access: "user".to_string(),
+	///     id: RecordId::new("user", "tobie"),
+	/// })
+	/// .await?;
+	///
+	/// // Runs with the permissions of `user:tobie`
+	/// db.query("SELECT * FROM article").await?;
+	///
+	/// db.stop_impersonating().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn impersonate(&'_ self, subject: impl auth::Subject) -> Impersonate<'_, C> {
+		Impersonate {
+			client: Cow::Borrowed(self),
+			subject: subject.into_value(),
+		}
+	}
+
+	/// Stops impersonating a user, restoring the authentication of the
+	/// administrator
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.stop_impersonating().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn stop_impersonating(&'_ self) -> StopImpersonating<'_, C> {
+		StopImpersonating {
+			client: Cow::Borrowed(self),
+		}
+	}
+
 	/// Authenticates the current connection with a JWT token
 	///
 	/// # Examples
@@ -682,12 +936,15 @@ where
 	/// # }
 	/// ```
 	#[must_use = "queries do nothing unless you `.await` or poll them"]
-	pub fn query<'client>(&'client self, query: impl Into<Cow<'client, str>>) -> Query<'client, C> {
+	pub fn query<'client>(&'client self, query: impl IntoQuery<'client>) -> Query<'client, C> {
+		let (query, variables) = query.into_query();
 		Query {
 			txn: None,
 			client: Cow::Borrowed(self),
-			queries: vec![query.into()],
-			variables: Ok(Variables::new()),
+			queries: vec![query],
+			variables: Ok(variables),
+			version_stamp: false,
+			trace_id: None,
 		}
 	}
 
@@ -735,6 +992,58 @@ where
 			txn: None,
 			client: Cow::Borrowed(self),
 			resource: resource.into_resource(),
+			start: None,
+			limit: None,
+			fields: None,
+			filter: None,
+			variables: Variables::new(),
+			response_type: PhantomData,
+			query_type: PhantomData,
+		}
+	}
+
+	/// Walks the graph edges from a record
+	///
+	/// Each step follows the edges of a table, outgoing with
+	/// [`out`](Traverse::out), incoming with [`in_`](Traverse::in_) or in
+	/// either direction with [`both`](Traverse::both), and the records reached
+	/// by the last step are returned. Use [`with_paths`](Traverse::with_paths)
+	/// to also return the records visited on the way to each of them.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::method::TraversalPath;
+	/// use surrealdb::types::SurrealValue;
+	///
+	/// #[derive(SurrealValue)]
+	/// struct Post {
+	///     title: String,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// db.use_ns("main").use_db("main").await?;
+	///
+	/// // Select the posts authored by the people liked by `person:1`
+	/// let posts: Vec<Post> = db.traverse("person:1").out("likes").out("authored").limit(50).await?;
+	///
+	/// // Also return how each post was reached
+	/// let paths: Vec<TraversalPath<Post>> =
+	///     db.traverse("person:1").out("likes").out("authored").with_paths().await?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn traverse<R>(&'_ self, from: impl IntoRecordId) -> Traverse<'_, C, R> {
+		Traverse {
+			txn: None,
+			client: Cow::Borrowed(self),
+			from: from.into_record_id(),
+			steps: Vec::new(),
+			limit: None,
 			response_type: PhantomData,
 			query_type: PhantomData,
 		}
@@ -791,6 +1100,7 @@ where
 			txn: None,
 			client: Cow::Borrowed(self),
 			resource: resource.into_resource(),
+			idempotency_key: None,
 			response_type: PhantomData,
 		}
 	}
@@ -937,6 +1247,7 @@ where
 			txn: None,
 			client: Cow::Borrowed(self),
 			resource: resource.into_resource(),
+			on_duplicate: None,
 			response_type: PhantomData,
 		}
 	}
@@ -1255,6 +1566,7 @@ where
 			txn: None,
 			client: Cow::Borrowed(self),
 			resource: resource.into_resource(),
+			idempotency_key: None,
 			response_type: PhantomData,
 		}
 	}
@@ -1310,6 +1622,120 @@ where
 		}
 	}
 
+	/// Checks whether this client is compatible with the server
+	///
+	/// The report compares the version of the client with the version of the
+	/// server, and the revision of the storage format supported by the client
+	/// with the one stored in the datastore. This check runs when connecting,
+	/// in the same round trip as the version of the server is requested.
+	/// Remote clients refuse to connect to a server of an unsupported version,
+	/// and embedded engines refuse to open a datastore of a different storage
+	/// format, unless configured otherwise with
+	/// [`Config::compatibility_check`](crate::opt::Config::compatibility_check).
+	/// A remote server with a different storage format is only logged, as
+	/// remote clients never read its storage.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let report = db.compatibility().await?;
+	/// if let Some(error) = report.error() {
+	///     eprintln!("Running against an unsupported server: {error}");
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn compatibility(&'_ self) -> Compatibility<'_, C> {
+		Compatibility {
+			client: Cow::Borrowed(self),
+		}
+	}
+
+	/// Returns the current versionstamp of the selected database
+	///
+	/// Every transaction committed at or before the returned versionstamp is
+	/// visible, and versionstamps never decrease. This makes the value a
+	/// consistent cut point, for example to resume reading a changefeed with
+	/// `SHOW CHANGES FOR TABLE ... SINCE` or to bound an incremental export.
+	///
+	/// To capture the versionstamp alongside the results of a query, see
+	/// [`Query::with_version_stamp`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// let cut = db.version_stamp().await?;
+	/// let mut response = db.query(format!("SHOW CHANGES FOR TABLE person SINCE {cut}")).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn version_stamp(&'_ self) -> VersionStamp<'_, C> {
+		VersionStamp {
+			client: Cow::Borrowed(self),
+		}
+	}
+
+	/// Returns the statistics of a table in the selected database
+	///
+	/// The statistics include the estimated number of records in the table,
+	/// their approximate size, the number of entries of each index, and the
+	/// versionstamp of the latest write to the table. They are maintained
+	/// incrementally by the storage layer as records are written, so fetching
+	/// them does not scan the table.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// let stats = db.table_stats("person").await?;
+	/// println!("person has about {} records", stats.rows);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn table_stats(&'_ self, table: impl Into<String>) -> TableStats<'_, C> {
+		TableStats {
+			client: Cow::Borrowed(self),
+			table: table.into(),
+		}
+	}
+
+	/// Describes the HTTP key-value endpoints of the selected database as an
+	/// OpenAPI 3.1 document
+	///
+	/// The document is derived from the current schema: every table gets the
+	/// paths of its `/key` endpoints, and a component schema built from its
+	/// field definitions, so that clients in other languages can be generated
+	/// from it. The permissions of tables and fields are summarised in
+	/// `x-surrealdb-permissions` extensions.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// let document = db.openapi().await?;
+	/// std::fs::write("openapi.json", document.to_string()).unwrap();
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn openapi(&'_ self) -> OpenApi<'_, C> {
+		OpenApi {
+			client: Cow::Borrowed(self),
+		}
+	}
+
 	/// Runs a function
 	///
 	/// # Examples
@@ -1339,8 +1765,44 @@ where
 		}
 	}
 
+	/// Runs a query template defined with `DEFINE QUERY`
+	///
+	/// The arguments of the query are set by name, and are coerced to the
+	/// kinds they were declared with.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::types::RecordId;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // DEFINE QUERY recent_orders($user: record<user>) AS
+	/// //     SELECT VALUE id FROM order WHERE user = $user;
+	/// let orders: Vec<RecordId> = db
+	///     .run_query("recent_orders")
+	///     .arg("user", RecordId::new("user", "tobie"))
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn run_query<R>(&'_ self, name: impl Into<String>) -> RunQuery<'_, C, R> {
+		RunQuery {
+			client: Cow::Borrowed(self),
+			name: name.into(),
+			args: Object::new(),
+			response_type: PhantomData,
+		}
+	}
+
 	/// Checks whether the server is healthy or not
 	///
+	/// With the embedded engines, this probes the storage engine as
+	/// [`Surreal::health_report`] does, and errors if it is degraded.
+	///
 	/// # Examples
 	///
 	/// ```no_run
@@ -1357,73 +1819,38 @@ where
 		}
 	}
 
-	/// Wait for the selected event to happen before proceeding
-	pub async fn wait_for(&'_ self, event: WaitFor) {
-		let mut rx = self.inner.waiter.0.subscribe();
-		rx.wait_for(|current| match current {
-			// The connection hasn't been initialised yet.
-			None => false,
-			// The connection has been initialised. Only the connection even matches.
-			Some(WaitFor::Connection) => matches!(event, WaitFor::Connection),
-			// The database has been selected. Connection and database events both match.
-			Some(WaitFor::Database) => matches!(event, WaitFor::Connection | WaitFor::Database),
-		})
-		.await
-		.ok();
-	}
-
-	/// Dumps the database contents to a file
-	///
-	/// # Support
+	/// Probes the health of the datastore, and returns a report on it
 	///
-	/// Currently only supported by HTTP and the local engines. *Not* supported
-	/// on WebAssembly.
+	/// The storage engine is probed by writing a value and reading it back,
+	/// and the report includes how recently the background tasks of the node
+	/// last ran, so that orchestrators can detect degraded storage. Only
+	/// supported by the embedded engines.
 	///
 	/// # Examples
 	///
 	/// ```no_run
-	/// # use futures::StreamExt;
 	/// # #[tokio::main]
 	/// # async fn main() -> surrealdb::Result<()> {
 	/// # let db = surrealdb::engine::any::connect("mem://").await?;
-	/// // Select the namespace/database to use
-	/// db.use_ns("main").use_db("main").await?;
-	///
-	/// // Export to a file
-	/// db.export("backup.sql").await?;
-	///
-	/// // Export to a stream of bytes
-	/// let mut backup = db.export(()).await?;
-	/// while let Some(result) = backup.next().await {
-	///     match result {
-	///         Ok(bytes) => {
-	///             // Do something with the bytes received...
-	///         }
-	///         Err(error) => {
-	///             // Handle the export error
-	///         }
-	///     }
+	/// let report = db.health_report().await?;
+	/// if !report.is_healthy() {
+	///     eprintln!("The datastore is degraded: {:?}", report.errors);
 	/// }
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn export<R>(&'_ self, target: impl IntoExportDestination<R>) -> Export<'_, C, R> {
-		Export {
+	pub fn health_report(&'_ self) -> HealthCheck<'_, C> {
+		HealthCheck {
 			client: Cow::Borrowed(self),
-			target: target.into_export_destination(),
-			ml_config: None,
-			db_config: None,
-			response: PhantomData,
-			export_type: PhantomData,
 		}
 	}
 
-	/// Restores the database from a file
+	/// Runs a query and reports which permissions were evaluated while
+	/// running it
 	///
-	/// # Support
-	///
-	/// Currently only supported by HTTP and the local engines. *Not* supported
-	/// on WebAssembly.
+	/// The report lists the authentication of the current session, and every
+	/// table and field permission which was checked, along with how many rows
+	/// or fields it was evaluated for, and how many of those it denied.
 	///
 	/// # Examples
 	///
@@ -1431,24 +1858,865 @@ where
 	/// # #[tokio::main]
 	/// # async fn main() -> surrealdb::Result<()> {
 	/// # let db = surrealdb::engine::any::connect("mem://").await?;
-	/// // Select the namespace/database to use
-	/// db.use_ns("main").use_db("main").await?;
-	///
-	/// db.import("backup.sql").await?;
+	/// db.use_ns("ns").use_db("db").await?;
+	/// let report = db
+	///     .explain_permissions("SELECT * FROM post WHERE author = $author")
+	///     .bind(("author", "person:tobie"))
+	///     .await?;
+	/// println!("{report:?}");
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn import<P>(&'_ self, file: P) -> Import<'_, C>
-	where
-		P: AsRef<Path>,
-	{
-		Import {
+	pub fn explain_permissions(&'_ self, query: impl Into<String>) -> ExplainPermissions<'_, C> {
+		ExplainPermissions {
 			client: Cow::Borrowed(self),
-			file: file.as_ref().to_owned(),
-			is_ml: false,
+			query: Cow::Owned(query.into()),
+			variables: Ok(Variables::new()),
+		}
+	}
+
+	/// Captures the workload of the selected database, to recommend the
+	/// indexes which would serve it
+	///
+	/// While a capture is running, the shapes of the statements which filter
+	/// or order the records of a table are recorded. The recommendations rank
+	/// the indexes which would serve the captured statements, using the
+	/// statistics of each table to estimate their benefit and cost.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// db.advisor().start().await?;
+	/// // Run the workload of the application
+	/// db.advisor().stop().await?;
+	/// for recommendation in db.advisor().recommendations().await? {
+	///     println!("{}", recommendation.statement);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn advisor(&'_ self) -> Advisor<'_, C> {
+		Advisor {
+			client: Cow::Borrowed(self),
+		}
+	}
+
+	/// Runs maintenance operations against the storage engine
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("rocksdb://path/to/database").await?;
+	/// db.use_ns("ns").use_db("db").await?;
+	/// db.query("DELETE logs WHERE time < time::now() - 30d").await?;
+	/// db.maintenance().compact_table("logs").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn maintenance(&'_ self) -> Maintenance<'_, C> {
+		Maintenance {
+			client: Cow::Borrowed(self),
+		}
+	}
+
+	/// Locks the datastore for writes
+	///
+	/// Waits for the write transactions which are already running to finish,
+	/// then holds back new write transactions until the returned guard is
+	/// dropped or released. Reads continue as normal, which makes this useful
+	/// for taking consistent backups or coordinating a cut-over. The lock is
+	/// forcibly released once it has been held for its maximum duration, so a
+	/// lost guard can not block writes indefinitely.
+	///
+	/// Requires root owner permissions, as the lock affects every namespace and
+	/// database, and is only supported by the embedded engines. The lock is
+	/// released when the guard is dropped, even outside of an async runtime.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::time::Duration;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let lock = db.lock_writes().wait(Duration::from_secs(5)).max_hold(Duration::from_secs(30)).await?;
+	/// db.export("backup.surql").await?;
+	/// lock.release().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn lock_writes(&'_ self) -> LockWrites<'_, C> {
+		LockWrites {
+			client: Cow::Borrowed(self),
+			wait: lock_writes::DEFAULT_WAIT,
+			max_hold: lock_writes::DEFAULT_MAX_HOLD,
+		}
+	}
+
+	/// Creates a named storage-level snapshot of the datastore
+	///
+	/// The snapshot is created by the storage engine as a checkpoint, which
+	/// hard-links the data files rather than copying them, so it is nearly
+	/// instantaneous regardless of the size of the datastore. The snapshot is
+	/// stored next to the datastore, in the `{path}.snapshots/{name}`
+	/// directory, and can be opened read-only with
+	/// [`Config::from_snapshot`](crate::opt::Config::from_snapshot), for
+	/// example as a test fixture or to verify a backup.
+	///
+	/// Snapshot names may only contain letters, digits, `-` and `_`. Requires
+	/// root owner permissions, and is only supported by the embedded RocksDB
+	/// and SurrealKV engines.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::Surreal;
+	/// use surrealdb::engine::local::RocksDb;
+	/// use surrealdb::opt::Config;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// let db = Surreal::new::<RocksDb>("path/to/database").await?;
+	/// db.snapshot("fixture").await?;
+	///
+	/// // Open the snapshot read-only
+	/// let fixture = Surreal::new::<RocksDb>(("path/to/database", Config::from_snapshot("fixture"))).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn snapshot(&'_ self, name: impl Into<String>) -> Snapshot<'_, C> {
+		Snapshot {
+			client: Cow::Borrowed(self),
+			name: name.into(),
+		}
+	}
+
+	/// Returns the number of requests waiting for a free slot
+	///
+	/// Requests only wait when the connection was configured with
+	/// [`Config::max_concurrent_queries`](crate::opt::Config::max_concurrent_queries).
+	/// Returns `0` if the client is not connected.
+	pub fn queued_queries(&self) -> usize {
+		self.inner.router.get().map(|router| router.limiter.queued()).unwrap_or_default()
+	}
+
+	/// Returns the number of requests currently running
+	///
+	/// Requests are only counted when the connection was configured with
+	/// [`Config::max_concurrent_queries`](crate::opt::Config::max_concurrent_queries).
+	/// Returns `0` if the client is not connected.
+	pub fn running_queries(&self) -> usize {
+		self.inner.router.get().map(|router| router.limiter.running()).unwrap_or_default()
+	}
+
+	/// Returns the statistics of the compressed responses received so far
+	///
+	/// Responses are only compressed by the HTTP engine, when
+	/// [`Config::http_compression`](crate::opt::Config::http_compression) is
+	/// enabled. Returns `None` for the other engines, or if the client is not
+	/// connected.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("http://localhost:8000").await?;
+	/// if let Some(stats) = db.compression_stats() {
+	///     println!("{} bytes saved over {} responses", stats.bytes_saved(), stats.responses);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn compression_stats(&self) -> Option<CompressionStats> {
+		let router = self.inner.router.get()?;
+		router.compression.as_ref().map(|metrics| metrics.stats())
+	}
+
+	/// Returns the latest round-trip time to the server
+	///
+	/// The round-trip time is measured by the remote engines, while warming
+	/// up the connections configured with
+	/// [`Config::warm_connections`](crate::opt::Config::warm_connections), and
+	/// at every ping configured with
+	/// [`Config::keepalive`](crate::opt::Config::keepalive). Returns `None`
+	/// until it is first measured, for the embedded engines, or if the client
+	/// is not connected.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use std::time::Duration;
+	/// # use surrealdb::opt::Config;
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// let config = Config::new().keepalive(Duration::from_secs(10)).warm_connections(4);
+	/// let db = surrealdb::engine::any::connect(("http://localhost:8000", config)).await?;
+	/// if let Some(latency) = db.latency() {
+	///     println!("The server is {latency:?} away");
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn latency(&self) -> Option<Duration> {
+		self.inner.router.get()?.latency.latest()
+	}
+
+	/// Subscribes to the notifications of every live query on the datastore
+	///
+	/// Each receiver gets every notification sent by the datastore from now
+	/// on, for the live queries of any session, without having to consume a
+	/// [`Stream`](crate::method::Stream) per live query. A receiver which falls
+	/// behind by more than
+	/// [`Config::notifications_broadcast_capacity`](crate::opt::Config::notifications_broadcast_capacity)
+	/// notifications skips the oldest, and is told how many it missed with
+	/// [`RecvError::Lagged`](broadcast::error::RecvError::Lagged).
+	///
+	/// Notifications are only broadcast by the local engines. Returns `None`
+	/// for the remote engines, or if the client is not connected.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use tokio::sync::broadcast::error::RecvError;
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let mut notifications = db.notifications_broadcast().expect("a local engine");
+	/// loop {
+	///     match notifications.recv().await {
+	///         Ok(notification) => println!("{:?} {:?}", notification.action, notification.result),
+	///         Err(RecvError::Lagged(missed)) => println!("Missed {missed} notifications"),
+	///         Err(RecvError::Closed) => break,
+	///     }
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn notifications_broadcast(
+		&self,
+	) -> Option<broadcast::Receiver<crate::types::Notification>> {
+		let router = self.inner.router.get()?;
+		router.notifications.as_ref().map(|broadcast| broadcast.subscribe())
+	}
+
+	/// Registers middleware which intercepts requests and responses
+	///
+	/// The middleware applies to every request sent over this connection from
+	/// now on, including requests made by clones of this client. See
+	/// [`Middleware`](crate::opt::Middleware) for the order in which it runs.
+	///
+	/// Returns an error if the client is not connected yet.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::opt::{Middleware, Request};
+	///
+	/// // Prefix every query with the tenant it belongs to
+	/// struct Tenant(&'static str);
+	///
+	/// impl Middleware for Tenant {
+	///     fn on_request(&self, request: &mut Request<'_>) -> surrealdb::Result<()> {
+	///         if let Request::Query { variables, .. } = request {
+	///             variables.insert("tenant", self.0);
+	///         }
+	///         Ok(())
+	///     }
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.add_middleware(Tenant("acme"))?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn add_middleware(&self, middleware: impl Middleware) -> crate::Result<()> {
+		let router = self.inner.router.extract()?;
+		router.middleware.push(Arc::new(middleware));
+		Ok(())
+	}
+
+	/// Wait for the selected event to happen before proceeding
+	pub async fn wait_for(&'_ self, event: WaitFor) {
+		let mut rx = self.inner.waiter.0.subscribe();
+		rx.wait_for(|current| match current {
+			// The connection hasn't been initialised yet.
+			None => false,
+			// The connection has been initialised. Only the connection even matches.
+			Some(WaitFor::Connection) => matches!(event, WaitFor::Connection),
+			// The database has been selected. Connection and database events both match.
+			Some(WaitFor::Database) => matches!(event, WaitFor::Connection | WaitFor::Database),
+		})
+		.await
+		.ok();
+	}
+
+	/// Dumps the database contents to a file
+	///
+	/// # Support
+	///
+	/// Currently only supported by HTTP and the local engines. *Not* supported
+	/// on WebAssembly.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use futures::StreamExt;
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("main").use_db("main").await?;
+	///
+	/// // Export to a file
+	/// db.export("backup.sql").await?;
+	///
+	/// // Export to a stream of bytes
+	/// let mut backup = db.export(()).await?;
+	/// while let Some(result) = backup.next().await {
+	///     match result {
+	///         Ok(bytes) => {
+	///             // Do something with the bytes received...
+	///         }
+	///         Err(error) => {
+	///             // Handle the export error
+	///         }
+	///     }
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn export<R>(&'_ self, target: impl IntoExportDestination<R>) -> Export<'_, C, R> {
+		Export {
+			client: Cow::Borrowed(self),
+			target: target.into_export_destination(),
+			ml_config: None,
+			db_config: None,
+			encryption: None,
+			response: PhantomData,
+			export_type: PhantomData,
+		}
+	}
+
+	/// Restores the database from a file
+	///
+	/// # Support
+	///
+	/// Currently only supported by HTTP and the local engines. *Not* supported
+	/// on WebAssembly.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("main").use_db("main").await?;
+	///
+	/// db.import("backup.sql").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn import<P>(&'_ self, file: P) -> Import<'_, C>
+	where
+		P: AsRef<Path>,
+	{
+		Import {
+			client: Cow::Borrowed(self),
+			file: file.as_ref().to_owned(),
+			is_ml: false,
+			resume: false,
+			verify: false,
+			decryption: None,
 			import_type: PhantomData,
 		}
 	}
+
+	/// Loads a fixture into the current database, unless it was loaded before
+	///
+	/// The fixture is applied in a single transaction, and the SHA-256 hash of
+	/// its contents is recorded in the `_seed` table. Seeding the same fixture
+	/// again is a no-op, which makes this suitable for bootstrapping embedded
+	/// instances in integration tests and local development. Changing the
+	/// contents of a fixture makes it a new fixture, which will be applied on
+	/// top of any previous ones.
+	///
+	/// Resolves to `true` if the fixture was applied, or `false` if it had
+	/// already been applied. SurrealQL fixtures must not contain their own
+	/// transaction statements.
+	///
+	/// A path is read when the seed runs, with the format guessed from its
+	/// extension. Use [`Fixture`](crate::opt::Fixture) to embed a fixture in
+	/// the program instead.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// use surrealdb::opt::Fixture;
+	///
+	/// db.use_ns("test").use_db("test").await?;
+	///
+	/// // Load a fixture from disk
+	/// db.seed("fixtures/people.surql").await?;
+	///
+	/// // Load a fixture embedded in the binary
+	/// db.seed(Fixture::ndjson(r#"{"table": "person", "data": {"name": "Tobie"}}"#)).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn seed(&'_ self, fixture: impl Into<Fixture>) -> Seed<'_, C> {
+		Seed {
+			client: Cow::Borrowed(self),
+			fixture: fixture.into(),
+		}
+	}
+
+	/// Inserts random records which match the schema of a table
+	///
+	/// The fields, assertions and unique indexes of the table are read from
+	/// the database, and a random value of the defined type is generated for
+	/// each field which is not computed by the database. Records which fail an
+	/// assertion or collide on a unique index are generated again, so fields
+	/// with assertions which random values rarely satisfy should be given a
+	/// `DEFAULT` instead. Fields of a type which can not be generated, such as
+	/// geometries, cause the generation to fail.
+	///
+	/// This is intended for load tests and for exercising application logic
+	/// against realistic data. Resolves to the ids of the created records.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("test").use_db("test").await?;
+	///
+	/// db.query(
+	///     "
+	///     DEFINE TABLE person SCHEMAFULL;
+	///     DEFINE FIELD name ON person TYPE string;
+	///     DEFINE FIELD age ON person TYPE int ASSERT $value >= 0;
+	///     DEFINE FIELD email ON person TYPE string;
+	///     DEFINE INDEX email ON person FIELDS email UNIQUE;
+	///     ",
+	/// )
+	/// .await?;
+	///
+	/// let people = db.generate("person", 1000).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn generate(&'_ self, table: impl Into<String>, count: u64) -> Generate<'_, C> {
+		Generate {
+			client: Cow::Borrowed(self),
+			table: table.into(),
+			count,
+		}
+	}
+
+	/// Creates many graph edges in bulk
+	///
+	/// Each edge goes from a record, through an edge table, to another record,
+	/// with optional content. The edges are created in order, in transactions
+	/// of up to [`RelateMany::chunk_size`] edges, and an outcome is returned for
+	/// every edge in the same order. A failed edge rolls back the other edges
+	/// of its transaction, which are reported as failed too.
+	///
+	/// By default, an edge fails if either of its endpoints does not exist.
+	/// Use [`RelateMany::endpoints`] to skip such edges, or to not check them.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::method::{EndpointPolicy, RelateOutcome};
+	/// use surrealdb::types::{RecordId, SurrealValue};
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// let tobie = RecordId::new("person", "tobie");
+	/// let jaime = RecordId::new("person", "jaime");
+	/// let outcomes = db
+	///     .relate_many(vec![
+	///         (tobie.clone(), "knows", jaime.clone(), None),
+	///         (jaime, "likes", tobie, Some("surrealdb".into_value())),
+	///     ])
+	///     .endpoints(EndpointPolicy::Skip)
+	///     .await?;
+	/// for outcome in outcomes {
+	///     if let RelateOutcome::Failed(error) = outcome {
+	///         eprintln!("{error}");
+	///     }
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn relate_many(
+		&'_ self,
+		edges: Vec<(RecordId, &str, RecordId, Option<Value>)>,
+	) -> RelateMany<'_, C> {
+		RelateMany {
+			client: Cow::Borrowed(self),
+			edges: edges
+				.into_iter()
+				.map(|(from, table, to, data)| (from, Table::from(table), to, data))
+				.collect(),
+			chunk_size: relate_many::DEFAULT_CHUNK_SIZE,
+			endpoints: EndpointPolicy::default(),
+		}
+	}
+
+	/// Subscribes to the schema changes of the current database
+	///
+	/// The stream yields a [`SchemaChange`] whenever a table, field, index,
+	/// database access method, or param is defined, altered, or removed, once the
+	/// transaction making the change commits. This can be used to invalidate
+	/// anything derived from the schema, such as generated GraphQL schemas or
+	/// ORM metadata.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// let mut changes = db.schema_changes().stream().await?;
+	///
+	/// while let Some(change) = changes.next().await {
+	///     let change = change?;
+	///     println!("{:?} {:?} `{}`", change.action, change.kind, change.name);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn schema_changes(&'_ self) -> SchemaChanges<'_, C> {
+		SchemaChanges {
+			client: Cow::Borrowed(self),
+		}
+	}
+
+	/// Subscribes to the params of the current database
+	///
+	/// The stream yields a [`ParamChange`], carrying the new value of the
+	/// param, whenever a param is defined, altered, or removed with `DEFINE
+	/// PARAM`, `ALTER PARAM`, or `REMOVE PARAM`. This allows params used as
+	/// feature flags or configuration values to be followed without polling.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// let mut params = db.live_params().stream().await?;
+	///
+	/// while let Some(change) = params.next().await {
+	///     let change = change?;
+	///     println!("${} is now {:?}", change.name, change.value);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn live_params(&'_ self) -> LiveParams<'_, C> {
+		LiveParams {
+			client: Cow::Borrowed(self),
+		}
+	}
+
+	/// Invalidates application caches using the changefeeds of tables
+	///
+	/// Each watched table is paired with a function deriving the cache key of
+	/// a changed record. The changefeeds of the watched tables are read
+	/// periodically, and the stream yields the keys of the records which were
+	/// created, updated, or deleted since the last read. Every
+	/// [`Invalidation`] carries the versionstamp to resume from, so that a
+	/// restarted application can pass it to
+	/// [`since`](CacheInvalidation::since) without missing any change.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	/// use surrealdb::types::{RecordId, SurrealValue};
+	///
+	/// #[derive(SurrealValue)]
+	/// struct Product {
+	///     id: RecordId,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// db.query("DEFINE TABLE product CHANGEFEED 1d").await?;
+	///
+	/// let mut invalidations = db
+	///     .cache_invalidation()
+	///     .watch("product", |product: Product| product.id)
+	///     .stream()
+	///     .await?;
+	///
+	/// while let Some(invalidation) = invalidations.next().await {
+	///     let invalidation = invalidation?;
+	///     for key in invalidation.keys {
+	///         println!("Evicting {key:?} from the cache");
+	///     }
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cache_invalidation(&'_ self) -> CacheInvalidation<'_, C> {
+		CacheInvalidation::new(Cow::Borrowed(self))
+	}
+
+	/// Follows the changes made to the selected database, as a continuous
+	/// export
+	///
+	/// The changefeeds of the database are read periodically, and the stream
+	/// yields the statements replaying each committed transaction, in the
+	/// format of [`export`](Self::export). Piping the statements into another
+	/// instance, after importing an export taken at the same versionstamp,
+	/// keeps it up to date, for example to feed a replica or a data lake.
+	///
+	/// Only the tables selected by [`ExportFollowConfig::tables`] are
+	/// followed, and only their records: changes to the schema are skipped.
+	/// Every [`ExportChange`] carries the versionstamp to resume from, which
+	/// can be passed as [`ExportFollowConfig::since`] when restarting.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	/// use surrealdb::method::{ExcludedTables, ExportFollowConfig, TableConfig};
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// db.query("DEFINE DATABASE OVERWRITE database CHANGEFEED 1d").await?;
+	///
+	/// let mut changes = db
+	///     .follow(ExportFollowConfig {
+	///         tables: TableConfig::Exclude(ExcludedTables {
+	///             exclude: vec!["session".to_owned()],
+	///         }),
+	///         since: None,
+	///     })
+	///     .await?;
+	///
+	/// while let Some(change) = changes.next().await {
+	///     print!("{}", change?);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn follow(&'_ self, config: ExportFollowConfig) -> Follow<'_, C> {
+		Follow::new(Cow::Borrowed(self), config)
+	}
+
+	/// Streams every record of a table, in the order of their ids
+	///
+	/// The records are read in batches of [`batch_size`](Scan::batch_size),
+	/// so that the table never has to fit in memory, and every batch is read
+	/// within the same transaction, so that the stream sees the table as it
+	/// was when the scan started, regardless of concurrent writes. This makes
+	/// it suited to exports and ETL jobs which need a consistent dataset.
+	///
+	/// The transaction is held open until the stream is exhausted or dropped,
+	/// and is not exempt from any limit on how long a transaction may stay
+	/// open, such as the transaction timeout of the datastore, or the
+	/// transaction lifetime of the storage engine (five seconds on
+	/// FoundationDB). A scan which outlives such a limit fails with the error
+	/// of its transaction, after which the stream ends, so tables which take
+	/// longer to read should be read in several scans, or with
+	/// [`select`](Surreal::select) and a range of record ids.
+	///
+	/// Scans are not supported over HTTP, which has no client-side
+	/// transactions, and fail with a configuration error there.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	/// use surrealdb::types::{RecordId, SurrealValue};
+	///
+	/// #[derive(SurrealValue)]
+	/// struct Person {
+	///     id: RecordId,
+	///     name: String,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// let mut people = db.scan::<Person>("person").batch_size(1000).await?;
+	/// while let Some(person) = people.next().await {
+	///     let person = person?;
+	///     println!("{}", person.name);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn scan<T>(&'_ self, table: impl Into<String>) -> Scan<'_, C, T> {
+		Scan::new(Cow::Borrowed(self), table.into())
+	}
+
+	/// Lists the access grants of a subject, across every access method
+	///
+	/// The grants are looked up at the selected base: the database if one is
+	/// selected, otherwise the namespace, otherwise the root. The secrets of
+	/// the grants are never returned.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::method::GrantSubject;
+	/// use surrealdb::types::RecordId;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // The grants of a system user
+	/// for grant in db.grants_for(GrantSubject::user("tobie")).await? {
+	///     println!("{} was granted `{}` by `{}`", grant.id, grant.kind, grant.ac);
+	/// }
+	///
+	/// // The grants of a record
+	/// let grants = db.grants_for(RecordId::new("user", "jaime")).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn grants_for(&'_ self, subject: impl Into<GrantSubject>) -> GrantsFor<'_, C> {
+		GrantsFor {
+			client: Cow::Borrowed(self),
+			subject: subject.into(),
+		}
+	}
+
+	/// Issues a bearer grant for each of the given subjects, in a single
+	/// transaction
+	///
+	/// The grants are issued by the access method defined at the selected
+	/// base. The subjects must be all users or all records. A subject which
+	/// can not be granted access, such as a user which does not exist, is
+	/// reported without failing the grants of the other subjects. This is
+	/// the only time the plaintext keys of the grants are returned.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::method::{GrantOutcome, GrantSubject};
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// let subjects = ["tobie", "jaime"].map(GrantSubject::user);
+	/// for outcome in db.grant_bearer("api", subjects).await? {
+	///     match outcome {
+	///         GrantOutcome::Issued(grant) => println!("{:?} was issued {}", grant.subject, grant.key),
+	///         GrantOutcome::Failed { subject, error } => println!("{subject:?} failed: {error}"),
+	///         _ => {}
+	///     }
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn grant_bearer(
+		&'_ self,
+		access: impl Into<String>,
+		subjects: impl IntoIterator<Item = impl Into<GrantSubject>>,
+	) -> GrantBearer<'_, C> {
+		GrantBearer {
+			client: Cow::Borrowed(self),
+			access: access.into(),
+			subjects: subjects.into_iter().map(Into::into).collect(),
+		}
+	}
+
+	/// Manages the system users defined at the selected database, or at
+	/// another level chosen with [`Users::on`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::method::{Role, UserLevel};
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Create a user of the selected database
+	/// let user = db.users().create("tobie").password("secret").role(Role::Editor).await?;
+	///
+	/// // Replace the roles of the user
+	/// db.users().update_roles("tobie", [Role::Owner]).await?;
+	///
+	/// // List the root users
+	/// for user in db.users().on(UserLevel::Root).list().await? {
+	///     println!("{} has the roles {:?}", user.name, user.roles);
+	/// }
+	///
+	/// // Remove the user
+	/// db.users().delete("tobie").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn users(&'_ self) -> Users<'_, C> {
+		Users {
+			client: Cow::Borrowed(self),
+			level: UserLevel::Database,
+		}
+	}
+
+	/// Returns the definition of a system user of the selected database
+	///
+	/// Users defined at other levels are returned by [`Users::info`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// let user = db.info_for_user("tobie").await?;
+	/// println!("{} has the roles {:?}", user.name, user.roles);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn info_for_user(&'_ self, name: impl Into<String>) -> UserRequest<'_, C, UserInfo> {
+		self.users().info(name)
+	}
 }
 
 fn validate_data(data: &Value, error_message: &str) -> crate::Result<()> {
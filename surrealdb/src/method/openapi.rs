@@ -0,0 +1,51 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use crate::method::BoxFuture;
+use crate::types::Value;
+use crate::{Connection, Error, Result, Surreal};
+
+/// The statement used to describe the key-value endpoints of the database
+const OPENAPI_QUERY: &str = "RETURN schema::openapi()";
+
+/// Returned by [`Surreal::openapi`](crate::Surreal::openapi), yields the
+/// OpenAPI document describing the HTTP key-value endpoints of the tables of
+/// the selected database.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct OpenApi<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> OpenApi<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> OpenApi<'static, C> {
+		OpenApi {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for OpenApi<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<serde_json::Value>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let mut response = self.client.query(OPENAPI_QUERY).await?;
+			match response.take::<Value>(0)? {
+				Value::Object(document) => Ok(Value::Object(document).into_json_value()),
+				_ => Err(Error::internal(
+					"The database did not return the OpenAPI document".to_owned(),
+				)),
+			}
+		})
+	}
+}
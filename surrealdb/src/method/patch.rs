@@ -20,6 +20,7 @@ pub struct Patch<'r, C: Connection, R> {
 	pub(super) resource: Result<Resource>,
 	pub(super) patches: PatchOps,
 	pub(super) upsert: bool,
+	pub(super) idempotency_key: Option<String>,
 	pub(super) response_type: PhantomData<R>,
 }
 
@@ -46,6 +47,7 @@ macro_rules! into_future {
 				resource,
 				patches,
 				upsert,
+				idempotency_key,
 				..
 			} = self;
 			Box::pin(async move {
@@ -73,7 +75,8 @@ macro_rules! into_future {
 					txn,
 					query: Cow::Owned(format!("{operation} {what} PATCH $_patches RETURN AFTER")),
 					variables,
-				};
+				}
+				.with_idempotency_key(idempotency_key)?;
 
 				router.$method(client.session_id, cmd).await
 			})
@@ -126,6 +129,7 @@ where
 			resource,
 			patches,
 			upsert,
+			idempotency_key,
 			response_type,
 		} = self;
 		Patch {
@@ -134,6 +138,7 @@ where
 			resource,
 			patches: patches.push(patch.into()),
 			upsert,
+			idempotency_key,
 			response_type,
 		}
 	}
@@ -9,7 +9,7 @@ use futures::StreamExt;
 use futures::future::Either;
 use futures::stream::SelectAll;
 use indexmap::IndexMap;
-use surrealdb_core::dbs::QueryType;
+use surrealdb_core::dbs::{QueryResult, QueryType};
 use surrealdb_core::rpc::DbResultStats;
 use surrealdb_types::Error as TypesError;
 use uuid::Uuid;
@@ -17,9 +17,10 @@ use uuid::Uuid;
 use super::transaction::WithTransaction;
 use crate::conn::Command;
 use crate::method::live::Stream;
+use crate::method::version_stamp::VERSION_STAMP_QUERY;
 use crate::method::{BoxFuture, OnceLockExt, Stats, WithStats};
 use crate::notification::Notification;
-use crate::types::{SurrealValue, Value, Variables};
+use crate::types::{Kind, SurrealValue, Value, Variables};
 use crate::{Connection, Error, Result, Surreal, opt};
 
 /// Returned by [`Surreal::query`](crate::Surreal::query), resolving to [`IndexedResults`]
@@ -31,6 +32,8 @@ pub struct Query<'r, C: Connection> {
 	pub(crate) client: Cow<'r, Surreal<C>>,
 	pub(crate) queries: Vec<Cow<'r, str>>,
 	pub(crate) variables: Result<Variables>,
+	pub(crate) version_stamp: bool,
+	pub(crate) trace_id: Option<String>,
 }
 
 impl<C> WithTransaction for Query<'_, C>
@@ -43,6 +46,30 @@ where
 	}
 }
 
+/// Converts a value into the text of a query, along with any variables bound to it
+///
+/// Implemented for strings, and for the `Surql` queries built by the `surql!`
+/// macro of the `macros` feature.
+pub trait IntoQuery<'r> {
+	fn into_query(self) -> (Cow<'r, str>, Variables);
+}
+
+impl<'r, T> IntoQuery<'r> for T
+where
+	T: Into<Cow<'r, str>>,
+{
+	fn into_query(self) -> (Cow<'r, str>, Variables) {
+		(self.into(), Variables::new())
+	}
+}
+
+#[cfg(feature = "macros")]
+impl<'r> IntoQuery<'r> for opt::Surql {
+	fn into_query(self) -> (Cow<'r, str>, Variables) {
+		(self.query, self.variables)
+	}
+}
+
 pub trait IntoVariables {
 	fn into_variables(self) -> Result<Variables>;
 }
@@ -82,6 +109,8 @@ where
 			client: Cow::Owned(self.client.into_owned()),
 			queries: self.queries.into_iter().map(|q| Cow::Owned(q.into_owned())).collect(),
 			variables: self.variables,
+			version_stamp: self.version_stamp,
+			trace_id: self.trace_id,
 		}
 	}
 
@@ -110,10 +139,97 @@ where
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn query(mut self, query: impl Into<Cow<'r, str>>) -> Self {
-		self.queries.push(query.into());
+	pub fn query(mut self, query: impl IntoQuery<'r>) -> Self {
+		let (query, variables) = query.into_query();
+		self.queries.push(query);
+		if let Ok(vars) = &mut self.variables {
+			vars.extend(variables);
+		}
 		self
 	}
+
+	/// Captures the versionstamp of the database after the query has run
+	///
+	/// The versionstamp is read by a final statement executed after all of
+	/// the other statements, and is available from
+	/// [`IndexedResults::version_stamp`]. It does not count as a statement
+	/// of the query, so the indices of the results are unchanged.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let response = db.query("CREATE person SET name = 'Tobie'").with_version_stamp().await?;
+	/// let cut = response.version_stamp();
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_version_stamp(mut self) -> Self {
+		self.version_stamp = true;
+		self
+	}
+
+	/// Sets the trace id which identifies this query in the server logs
+	///
+	/// The trace id is sent along with the request and recorded on every span
+	/// and log line produced while the server processes it, so the query can
+	/// be correlated with the traces of the application. When no trace id is
+	/// set, a random one is generated for each request. It has no effect on
+	/// the embedded engines.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let response = db
+	///     .query("SELECT * FROM person")
+	///     .with_trace_id("4bf92f3577b34da6a3ce929d0e0e4736")
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+		self.trace_id = Some(trace_id.into());
+		self
+	}
+
+	/// Sets the format in which to stream the output of the query
+	///
+	/// Rather than resolving to [`IndexedResults`], the formatted query is
+	/// streamed as raw bytes with
+	/// [`FormattedQuery::stream_bytes`](super::FormattedQuery::stream_bytes),
+	/// for piping results into other tools without deserialising them.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	/// use surrealdb::opt::Format;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let mut stream = db
+	///     .query("SELECT * FROM person")
+	///     .format(Format::Ndjson)
+	///     .stream_bytes()
+	///     .await?;
+	/// while let Some(line) = stream.next().await {
+	///     std::io::Write::write_all(&mut std::io::stdout(), &line?).ok();
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn format(self, format: opt::Format) -> super::FormattedQuery<'r, C> {
+		super::FormattedQuery {
+			query: self,
+			format,
+		}
+	}
 }
 
 impl<'r, Client> IntoFuture for Query<'r, Client>
@@ -129,20 +245,24 @@ where
 			client,
 			queries,
 			variables,
+			version_stamp,
+			trace_id,
 		} = self;
 
 		Box::pin(async move {
 			// Extract the router from the client
 			let router = client.inner.router.extract()?;
-			let query = queries
-				.iter()
-				.map(|q| q.trim_end_matches(|c: char| c == ';' || c.is_whitespace()))
-				.collect::<Vec<_>>()
-				.join("; ");
+			let mut query = join_queries(&queries);
+			if version_stamp {
+				// Terminated on its own line, in case the query ends with a comment
+				query.push_str("\n;\n");
+				query.push_str(VERSION_STAMP_QUERY);
+			}
 
-			let results = router
+			let mut results = router
 				.execute_query(
 					client.session_id,
+					trace_id,
 					Command::Query {
 						query: Cow::Owned(query),
 						txn,
@@ -150,13 +270,19 @@ where
 					},
 				)
 				.await?;
+			let stamp = match version_stamp {
+				true => take_version_stamp(results.pop()),
+				false => None,
+			};
 
 			let mut indexed_results = IndexedResults::new();
+			indexed_results.version_stamp = stamp;
 
 			for (index, result) in results.into_iter().enumerate() {
 				let stats = DbResultStats::default()
 					.with_execution_time(result.time)
-					.with_query_type(result.query_type);
+					.with_query_type(result.query_type)
+					.with_query_stats(result.stats);
 
 				match result.query_type {
 					QueryType::Other => {
@@ -193,6 +319,21 @@ where
 	}
 }
 
+/// Joins the statements chained onto a query builder into a single query
+pub(super) fn join_queries(queries: &[Cow<'_, str>]) -> String {
+	queries
+		.iter()
+		.map(|q| q.trim_end_matches(|c: char| c == ';' || c.is_whitespace()))
+		.collect::<Vec<_>>()
+		.join("; ")
+}
+
+/// Extracts the versionstamp from the result of the trailing
+/// [`VERSION_STAMP_QUERY`] statement
+fn take_version_stamp(result: Option<QueryResult>) -> Option<u64> {
+	u64::from_value(result?.result.ok()?).ok()
+}
+
 impl<'r, Client> IntoFuture for WithStats<Query<'r, Client>>
 where
 	Client: Connection,
@@ -271,6 +412,8 @@ where
 			client: self.client,
 			queries: self.queries,
 			variables,
+			version_stamp: self.version_stamp,
+			trace_id: self.trace_id,
 		}
 	}
 }
@@ -281,6 +424,7 @@ where
 pub struct IndexedResults {
 	pub(crate) results: IndexMap<usize, (DbResultStats, std::result::Result<Value, TypesError>)>,
 	pub(crate) live_queries: IndexMap<usize, Result<Stream<Value>>>,
+	pub(crate) version_stamp: Option<u64>,
 }
 
 /// A `LIVE SELECT` stream from the `query` method
@@ -312,6 +456,7 @@ impl IndexedResults {
 		Self {
 			results: Default::default(),
 			live_queries: Default::default(),
+			version_stamp: None,
 		}
 	}
 
@@ -396,6 +541,73 @@ impl IndexedResults {
 		index.query_result(self)
 	}
 
+	/// Takes and returns records returned from the database, erroring on
+	/// fields which the target type does not capture
+	///
+	/// [`IndexedResults::take`] silently ignores any fields of a record which
+	/// are not present on the type it is deserialized into. This instead
+	/// returns an error naming every such field, which helps to catch schema
+	/// drift early. A result holding at most one record can be deserialized
+	/// into an `Option<T>`, like with [`IndexedResults::take`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::types::{RecordId, SurrealValue};
+	///
+	/// #[derive(Debug, SurrealValue)]
+	/// struct User {
+	///     id: RecordId,
+	///     name: String,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let mut response = db.query("SELECT * FROM user").await?;
+	/// // Errors if any user has fields other than `id` and `name`
+	/// let users: Vec<User> = response.take_strict(0)?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn take_strict<R>(&mut self, index: impl opt::QueryResult<Value>) -> Result<R>
+	where
+		R: SurrealValue,
+	{
+		let value = index.query_result(self)?;
+		// Unwrap a single record, as `take` does for an `Option<T>`
+		let collection = matches!(R::kind_of(), Kind::Any | Kind::Array(..) | Kind::Set(..));
+		let value = match value {
+			Value::Array(array) if array.len() <= 1 && !collection => {
+				array.into_vec().pop().unwrap_or_default()
+			}
+			value => value,
+		};
+		let deserialize = |value: Value| {
+			R::from_value(value).map_err(|e| {
+				Error::serialization(
+					e.to_string(),
+					crate::types::SerializationError::Deserialization,
+				)
+			})
+		};
+		// Convert the result back, to find which fields were not captured
+		let captured = deserialize(value.clone())?.into_value();
+		let mut unknown = Vec::new();
+		unknown_fields(&value, &captured, &mut String::new(), &mut unknown);
+		if !unknown.is_empty() {
+			return Err(Error::serialization(
+				format!(
+					"Found fields which are not present on `{}`: {}",
+					std::any::type_name::<R>(),
+					unknown.join(", ")
+				),
+				crate::types::SerializationError::Deserialization,
+			));
+		}
+		deserialize(value)
+	}
+
 	/// Takes and streams records returned from a `LIVE SELECT` query
 	///
 	/// This is the counterpart to [IndexedResults::take] used to stream the results
@@ -444,6 +656,82 @@ impl IndexedResults {
 		index.query_stream(self)
 	}
 
+	/// Returns the execution statistics of a statement
+	///
+	/// Unlike [`IndexedResults::take`], this leaves the result of the
+	/// statement in the response. Returns `None` when the index does not
+	/// correspond to a query statement.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let response = db.query("SELECT * FROM user WHERE email = 'john@example.com'").await?;
+	/// if let Some(stats) = response.take_stats(0) {
+	///     if stats.table_scan {
+	///         println!("Scanned {} rows without an index", stats.rows_scanned);
+	///     }
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn take_stats(&self, index: usize) -> Option<Stats> {
+		self.results.get(&index).map(|(stats, _)| Stats::from(stats.clone()))
+	}
+
+	/// Iterates over the execution statistics of every statement in the
+	/// response, along with the index of the statement
+	///
+	/// The results of the statements are left in the response.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let response = db
+	///     .query("SELECT * FROM user WHERE active = true")
+	///     .query("SELECT * FROM order WHERE user = user:john")
+	///     .await?;
+	/// for (index, stats) in response.iter_stats() {
+	///     println!(
+	///         "Statement {index}: {} rows scanned, {} returned (at most {:?})",
+	///         stats.rows_scanned, stats.rows_returned, stats.max_rows
+	///     );
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn iter_stats(&self) -> impl Iterator<Item = (usize, Stats)> + '_ {
+		self.results.iter().map(|(index, (stats, _))| (*index, Stats::from(stats.clone())))
+	}
+
+	/// Returns whether a statement timed out with `RETURN PARTIAL`, so that its
+	/// result only holds the rows found before the timeout
+	///
+	/// Returns `false` when the index does not correspond to a query statement.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let mut response = db.query("SELECT * FROM event TIMEOUT 2s RETURN PARTIAL").await?;
+	/// if response.is_partial(0) {
+	///     println!("The dashboard only shows some of the events");
+	/// }
+	/// let events: Vec<surrealdb::types::Value> = response.take(0)?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn is_partial(&self, index: usize) -> bool {
+		self.results.get(&index).is_some_and(|(stats, _)| stats.truncated)
+	}
+
 	/// Take all errors from the query response
 	///
 	/// The errors are keyed by the corresponding index of the statement that
@@ -527,6 +815,50 @@ impl IndexedResults {
 	pub fn num_statements(&self) -> usize {
 		self.results.len()
 	}
+
+	/// Returns the versionstamp of the database after the query ran
+	///
+	/// This is only available when the query was built with
+	/// [`Query::with_version_stamp`], and the versionstamp could be read. It
+	/// is not available when the query ended inside a transaction which was
+	/// cancelled or failed.
+	pub fn version_stamp(&self) -> Option<u64> {
+		self.version_stamp
+	}
+}
+
+/// Collects the paths of the fields in a value which are missing from the
+/// value it was converted into
+fn unknown_fields(value: &Value, captured: &Value, path: &mut String, unknown: &mut Vec<String>) {
+	match (value, captured) {
+		(Value::Object(value), Value::Object(captured)) => {
+			for (key, value) in value.iter() {
+				// Empty fields carry no data which could be lost
+				if matches!(value, Value::None | Value::Null) {
+					continue;
+				}
+				let len = path.len();
+				if !path.is_empty() {
+					path.push('.');
+				}
+				path.push_str(key);
+				match captured.get(key) {
+					Some(captured) => unknown_fields(value, captured, path, unknown),
+					None => unknown.push(path.clone()),
+				}
+				path.truncate(len);
+			}
+		}
+		(Value::Array(value), Value::Array(captured)) => {
+			for (i, (value, captured)) in value.iter().zip(captured.iter()).enumerate() {
+				let len = path.len();
+				path.push_str(&format!("[{i}]"));
+				unknown_fields(value, captured, path, unknown);
+				path.truncate(len);
+			}
+		}
+		_ => {}
+	}
 }
 
 impl WithStats<IndexedResults> {
@@ -593,10 +925,7 @@ impl WithStats<IndexedResults> {
 	where
 		R: SurrealValue,
 	{
-		let db_stats = index.stats(&self.0)?;
-		let stats = Stats {
-			execution_time: db_stats.execution_time,
-		};
+		let stats = Stats::from(index.stats(&self.0)?);
 		let result = index.query_result(&mut self.0);
 		Some((stats, result))
 	}
@@ -629,10 +958,7 @@ impl WithStats<IndexedResults> {
 		let mut errors = HashMap::with_capacity(keys.len());
 		for key in keys {
 			if let Some((db_stats, Err(error))) = self.0.results.swap_remove(&key) {
-				let stats = Stats {
-					execution_time: db_stats.execution_time,
-				};
-				errors.insert(key, (stats, error));
+				errors.insert(key, (Stats::from(db_stats), error));
 			}
 		}
 		errors
@@ -912,6 +1238,41 @@ mod tests {
 		assert_eq!(value, Value::String(article.title));
 	}
 
+	#[test]
+	fn take_strict() {
+		let article = Article {
+			title: "Lorem Ipsum".to_owned(),
+			body: "Lorem Ipsum Lorem Ipsum".to_owned(),
+		};
+		let value = article.clone().into_value();
+
+		// Every field is captured by the target type
+		let mut response = IndexedResults {
+			results: to_map(vec![Ok(Value::Array(vec![value.clone()].into()))]),
+			..IndexedResults::new()
+		};
+		let Some(taken): Option<Article> = response.take_strict(0).unwrap() else {
+			panic!("article not found");
+		};
+		assert_eq!(taken.body, article.body);
+
+		// The body is silently dropped by `take`
+		let mut response = IndexedResults {
+			results: to_map(vec![Ok(Value::Array(vec![value.clone()].into()))]),
+			..IndexedResults::new()
+		};
+		let summaries: Vec<Summary> = response.take(0).unwrap();
+		assert_eq!(summaries.len(), 1);
+
+		// But is reported by `take_strict`
+		let mut response = IndexedResults {
+			results: to_map(vec![Ok(Value::Array(vec![value].into()))]),
+			..IndexedResults::new()
+		};
+		let error = response.take_strict::<Vec<Summary>>(0).unwrap_err();
+		assert!(error.message().contains("[0].body"), "{}", error.message());
+	}
+
 	#[test]
 	fn take_key_multi() {
 		let article = Article {
@@ -0,0 +1,95 @@
+use std::borrow::Cow;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_channel::Receiver;
+use futures::{Stream, StreamExt};
+
+use super::query::join_queries;
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt, Query};
+use crate::opt::Format;
+use crate::{Connection, Result};
+
+/// Returned by [`Query::format`](crate::method::Query::format) to stream the
+/// output of a query in a given format.
+#[derive(Debug)]
+#[must_use = "formatted queries do nothing unless you call `stream_bytes`"]
+pub struct FormattedQuery<'r, C: Connection> {
+	pub(super) query: Query<'r, C>,
+	pub(super) format: Format,
+}
+
+impl<'r, C> FormattedQuery<'r, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> FormattedQuery<'static, C> {
+		FormattedQuery {
+			query: self.query.into_owned(),
+			format: self.format,
+		}
+	}
+
+	/// Runs the query, streaming its output as raw bytes
+	///
+	/// With [`Format::Ndjson`], each row of a statement result is written as
+	/// a JSON document on its own line, and each item of the stream is a
+	/// single line. The rows of each statement are streamed as soon as the
+	/// statement is complete, rather than once the whole query has run. A failed statement is written as a line with an
+	/// `ERR` status, while a query which can not run at all, for instance
+	/// because it can not be parsed, resolves to an error.
+	///
+	/// The local engines stop running the remaining statements once the
+	/// stream is dropped. The HTTP engine runs the query through the `/sql`
+	/// endpoint, so it can not be run within a transaction, and variables set
+	/// on the connection with [`Surreal::set`](crate::Surreal::set) are not
+	/// available to it. The WebSocket engine does not support streamed output.
+	pub fn stream_bytes(self) -> BoxFuture<'r, Result<QueryBytes>> {
+		let Query {
+			txn,
+			client,
+			queries,
+			variables,
+			..
+		} = self.query;
+		let format = self.format;
+		Box::pin(async move {
+			let router = client.inner.router.extract()?;
+			let (tx, rx) = crate::channel::bounded(1);
+			router
+				.execute_unit(
+					client.session_id,
+					Command::QueryBytes {
+						txn,
+						query: Cow::Owned(join_queries(&queries)),
+						variables: variables?,
+						format,
+						bytes: tx,
+					},
+				)
+				.await?;
+			Ok(QueryBytes {
+				rx: Box::pin(rx),
+			})
+		})
+	}
+}
+
+/// The output of a query streamed with
+/// [`FormattedQuery::stream_bytes`](crate::method::FormattedQuery::stream_bytes)
+#[derive(Debug, Clone)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct QueryBytes {
+	rx: Pin<Box<Receiver<Result<Vec<u8>>>>>,
+}
+
+impl Stream for QueryBytes {
+	type Item = Result<Vec<u8>>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.as_mut().rx.poll_next_unpin(cx)
+	}
+}
@@ -0,0 +1,182 @@
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::future::IntoFuture;
+
+use crate::method::BoxFuture;
+use crate::types::{RecordId, Table, ToSql, Value};
+use crate::{Connection, Error, Result, Surreal};
+
+/// The default number of edges created in a single transaction
+pub(super) const DEFAULT_CHUNK_SIZE: usize = 500;
+
+/// An edge to create, from a record through an edge table to another record
+pub(super) type Edge = (RecordId, Table, RecordId, Option<Value>);
+
+/// Returned by [`Surreal::relate_many`](crate::Surreal::relate_many) to create
+/// many graph edges in bulk.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RelateMany<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) edges: Vec<Edge>,
+	pub(super) chunk_size: usize,
+	pub(super) endpoints: EndpointPolicy,
+}
+
+/// How [`RelateMany`] treats edges whose endpoints do not exist
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EndpointPolicy {
+	/// Edges are created without checking their endpoints
+	Unchecked,
+	/// An edge whose endpoints do not exist fails, along with the rest of its
+	/// transaction
+	#[default]
+	Require,
+	/// An edge whose endpoints do not exist is skipped, and the rest of its
+	/// transaction is created
+	Skip,
+}
+
+/// The outcome of creating a single edge with [`RelateMany`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RelateOutcome {
+	/// The edge was created, with this id
+	Created(RecordId),
+	/// The edge was skipped, as one of its endpoints does not exist
+	Skipped,
+	/// The edge was not created, either because it failed or because another
+	/// edge in the same transaction failed
+	Failed(Error),
+}
+
+impl RelateOutcome {
+	/// Returns the id of the edge, if it was created
+	pub fn id(&self) -> Option<&RecordId> {
+		match self {
+			Self::Created(id) => Some(id),
+			_ => None,
+		}
+	}
+}
+
+impl<C> RelateMany<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> RelateMany<'static, C> {
+		RelateMany {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Sets the number of edges created in each transaction
+	///
+	/// Defaults to 500. A failed edge only rolls back the other edges of its
+	/// own transaction.
+	pub fn chunk_size(mut self, size: usize) -> Self {
+		self.chunk_size = size.max(1);
+		self
+	}
+
+	/// Sets how edges whose endpoints do not exist are treated
+	///
+	/// Defaults to [`EndpointPolicy::Require`].
+	pub fn endpoints(mut self, policy: EndpointPolicy) -> Self {
+		self.endpoints = policy;
+		self
+	}
+}
+
+impl<'r, Client> IntoFuture for RelateMany<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Vec<RelateOutcome>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let mut outcomes = Vec::with_capacity(self.edges.len());
+			let mut edges = self.edges.into_iter().peekable();
+			while edges.peek().is_some() {
+				let chunk = edges.by_ref().take(self.chunk_size).collect::<Vec<_>>();
+				let mut query = self.client.query(chunk_query(&chunk, self.endpoints));
+				for (i, (from, _, to, data)) in chunk.iter().enumerate() {
+					query = query.bind((format!("in_{i}"), from.clone()));
+					query = query.bind((format!("out_{i}"), to.clone()));
+					if let Some(data) = data {
+						query = query.bind((format!("data_{i}"), data.clone()));
+					}
+				}
+				let mut response = query.await?;
+				for i in 0..chunk.len() {
+					outcomes.push(match response.take::<Option<RecordId>>(i) {
+						Ok(Some(id)) => RelateOutcome::Created(id),
+						Ok(None) => RelateOutcome::Skipped,
+						Err(e) => RelateOutcome::Failed(e),
+					});
+				}
+			}
+			Ok(outcomes)
+		})
+	}
+}
+
+/// Builds the transaction which creates a chunk of edges, with one statement
+/// per edge
+fn chunk_query(chunk: &[Edge], endpoints: EndpointPolicy) -> String {
+	let mut query = String::from("BEGIN;\n");
+	for (i, (_, table, _, data)) in chunk.iter().enumerate() {
+		let mut relate = format!("RELATE $in_{i}->{}->$out_{i}", table.to_sql());
+		if data.is_some() {
+			write!(relate, " CONTENT $data_{i}").ok();
+		}
+		relate.push_str(" RETURN VALUE id");
+		let exists = format!("record::exists($in_{i}) AND record::exists($out_{i})");
+		match endpoints {
+			EndpointPolicy::Unchecked => writeln!(query, "{relate};"),
+			EndpointPolicy::Require => writeln!(
+				query,
+				"IF {exists} {{ {relate} }} ELSE {{ THROW 'The endpoints of the edge do not exist' }};"
+			),
+			EndpointPolicy::Skip => writeln!(query, "IF {exists} {{ {relate} }};"),
+		}
+		.ok();
+	}
+	query.push_str("COMMIT;");
+	query
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn edge(table: &str, data: Option<Value>) -> Edge {
+		(RecordId::new("person", "one"), Table::from(table), RecordId::new("person", "two"), data)
+	}
+
+	#[test]
+	fn builds_chunk_queries() {
+		let chunk = vec![edge("knows", None), edge("likes", Some(Value::Bool(true)))];
+		assert_eq!(
+			chunk_query(&chunk, EndpointPolicy::Unchecked),
+			"BEGIN;\nRELATE $in_0->knows->$out_0 RETURN VALUE id;\nRELATE $in_1->likes->$out_1 CONTENT $data_1 RETURN VALUE id;\nCOMMIT;"
+		);
+		assert_eq!(
+			chunk_query(&chunk[..1], EndpointPolicy::Skip),
+			"BEGIN;\nIF record::exists($in_0) AND record::exists($out_0) { RELATE $in_0->knows->$out_0 RETURN VALUE id };\nCOMMIT;"
+		);
+	}
+
+	#[test]
+	fn escapes_edge_tables() {
+		let chunk = vec![edge("knows->?; DELETE person", None)];
+		let query = chunk_query(&chunk, EndpointPolicy::Unchecked);
+		assert!(query.contains("->`knows->?; DELETE person`->"));
+	}
+}
@@ -0,0 +1,60 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+
+use crate::method::BoxFuture;
+use crate::types::{Object, SurrealValue, Value};
+use crate::{Connection, Result, Surreal};
+
+/// Returned by [`Surreal::run_query`](crate::Surreal::run_query) to run a query
+/// template defined with `DEFINE QUERY`
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RunQuery<'r, C: Connection, R> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) name: String,
+	pub(super) args: Object,
+	pub(super) response_type: PhantomData<R>,
+}
+
+impl<C, R> RunQuery<'_, C, R>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> RunQuery<'static, C, R> {
+		RunQuery {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Sets an argument of the query, by the name it was declared with
+	pub fn arg(mut self, name: impl Into<String>, value: impl SurrealValue) -> Self {
+		self.args.insert(name, value);
+		self
+	}
+}
+
+impl<'r, Client, R> IntoFuture for RunQuery<'r, Client, R>
+where
+	Client: Connection,
+	R: SurrealValue + Send + 'r,
+{
+	type Output = Result<R>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			// The name and the arguments are bound, so they are never parsed
+			let mut vars = Object::new();
+			vars.insert("name", self.name);
+			vars.insert("args", self.args);
+			let mut response =
+				self.client.query("query::run($name, $args)").bind(vars).await?.check()?;
+			let value: Value = response.take(0)?;
+			R::from_value(value)
+		})
+	}
+}
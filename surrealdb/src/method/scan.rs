@@ -0,0 +1,235 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::StreamExt;
+use surrealdb_types::SerializationError;
+
+use crate::conn::Command;
+use crate::method::select::escape_field;
+use crate::method::{BoxFuture, Transaction};
+use crate::types::{SurrealValue, Value};
+use crate::{Connection, Error, ExtraFeatures, Result, Surreal};
+
+/// The default number of records read at once
+const DEFAULT_BATCH_SIZE: u32 = 1000;
+
+/// Returned by [`Surreal::scan`](crate::Surreal::scan), yields a stream of
+/// every record of a table.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Scan<'r, C: Connection, T> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) table: String,
+	pub(super) batch_size: u32,
+	pub(super) response_type: PhantomData<T>,
+}
+
+impl<'r, C, T> Scan<'r, C, T>
+where
+	C: Connection,
+{
+	pub(super) fn new(client: Cow<'r, Surreal<C>>, table: String) -> Self {
+		Self {
+			client,
+			table,
+			batch_size: DEFAULT_BATCH_SIZE,
+			response_type: PhantomData,
+		}
+	}
+
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Scan<'static, C, T> {
+		Scan {
+			client: Cow::Owned(self.client.into_owned()),
+			table: self.table,
+			batch_size: self.batch_size,
+			response_type: PhantomData,
+		}
+	}
+
+	/// Sets the maximum number of records read from the table at once
+	///
+	/// Only one batch of records is held in memory at a time.
+	pub const fn batch_size(mut self, batch_size: u32) -> Self {
+		self.batch_size = batch_size;
+		self
+	}
+}
+
+impl<'r, Client, T> IntoFuture for Scan<'r, Client, T>
+where
+	Client: Connection,
+	T: SurrealValue + 'static,
+{
+	type Output = Result<Records<T>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			if self.batch_size == 0 {
+				return Err(Error::validation(
+					"The batch size of a scan must be greater than 0".to_owned(),
+					None,
+				));
+			}
+			let router = self.client.inner.router.extract()?;
+			if !router.features.contains(&ExtraFeatures::Transactions) {
+				return Err(Error::configuration(
+					"The protocol or storage engine does not support the client-side transactions a \
+					 scan reads the table in"
+						.to_string(),
+					None,
+				));
+			}
+			// Every batch is read within the same transaction, so that the
+			// stream sees the table as it was when the scan started
+			let txn = self.client.into_owned().begin().await?;
+			let state = State {
+				txn: Some(txn),
+				table: escape_field(&self.table),
+				batch_size: self.batch_size,
+				cursor: None,
+				exhausted: false,
+				pending: VecDeque::new(),
+			};
+			let stream = futures::stream::unfold(state, |mut state| async move {
+				state.next().await.map(|next| (next, state))
+			})
+			.map(|next| {
+				next.and_then(|record| {
+					T::from_value(record).map_err(|error| {
+						Error::serialization(error.to_string(), SerializationError::Deserialization)
+					})
+				})
+			});
+			Ok(Records {
+				inner: Box::pin(stream),
+			})
+		})
+	}
+}
+
+/// A stream of the records of a table, in the order of their ids, returned
+/// by [`Scan`]
+///
+/// The records are read within a single read snapshot, which is released once
+/// the stream is exhausted, fails, or is dropped.
+#[must_use = "streams do nothing unless polled"]
+pub struct Records<T> {
+	inner: Pin<Box<dyn futures::Stream<Item = Result<T>> + Send + Sync>>,
+}
+
+impl<T> fmt::Debug for Records<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Records").finish_non_exhaustive()
+	}
+}
+
+impl<T> futures::Stream for Records<T> {
+	type Item = Result<T>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.inner.poll_next_unpin(cx)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, None)
+	}
+}
+
+struct State<C: Connection> {
+	/// The transaction the table is read in, until the scan is finished
+	txn: Option<Transaction<C>>,
+	/// The escaped name of the table
+	table: String,
+	batch_size: u32,
+	/// The id of the last record which was read
+	cursor: Option<Value>,
+	/// Whether the last batch has been read
+	exhausted: bool,
+	pending: VecDeque<Value>,
+}
+
+impl<C> State<C>
+where
+	C: Connection,
+{
+	async fn next(&mut self) -> Option<Result<Value>> {
+		loop {
+			if let Some(record) = self.pending.pop_front() {
+				return Some(Ok(record));
+			}
+			if self.exhausted {
+				self.finish().await;
+				return None;
+			}
+			if let Err(error) = self.read().await {
+				self.exhausted = true;
+				self.finish().await;
+				return Some(Err(error));
+			}
+		}
+	}
+
+	/// Reads the next batch of records, after the last record which was read
+	async fn read(&mut self) -> Result<()> {
+		let Some(txn) = &self.txn else {
+			self.exhausted = true;
+			return Ok(());
+		};
+		let mut response = match &self.cursor {
+			Some(cursor) => {
+				let query = format!(
+					"SELECT * FROM {} WHERE id > $_start ORDER BY id LIMIT {}",
+					self.table, self.batch_size
+				);
+				txn.query(query).bind(("_start", cursor.clone())).await?
+			}
+			None => {
+				let query =
+					format!("SELECT * FROM {} ORDER BY id LIMIT {}", self.table, self.batch_size);
+				txn.query(query).await?
+			}
+		};
+		let records: Vec<Value> = response.take(0)?;
+		self.exhausted = records.len() < self.batch_size as usize;
+		if let Some(last) = records.last() {
+			self.cursor = Some(last.get("id").clone());
+		}
+		self.pending.extend(records);
+		Ok(())
+	}
+
+	/// Releases the transaction of the scan, which made no changes
+	async fn finish(&mut self) {
+		if let Some(txn) = self.txn.take() {
+			txn.cancel().await.ok();
+		}
+	}
+}
+
+impl<C> Drop for State<C>
+where
+	C: Connection,
+{
+	fn drop(&mut self) {
+		// The transaction is rolled back without waiting for the engine, as the
+		// stream may be dropped outside of an async runtime
+		if let Some(txn) = self.txn.take()
+			&& let Ok(router) = txn.client.inner.router.extract()
+		{
+			router.send_detached(
+				txn.client.session_id,
+				Command::Rollback {
+					txn: txn.id,
+				},
+			);
+		}
+	}
+}
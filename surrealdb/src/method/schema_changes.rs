@@ -0,0 +1,242 @@
+use std::borrow::Cow;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::StreamExt;
+use surrealdb_types::{ConfigurationError, QueryError, SerializationError};
+
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt, Stream, live};
+use crate::types::{Action, SurrealValue, Value, Variables};
+use crate::{Connection, Error, ExtraFeatures, Result, Surreal};
+
+/// Returned by [`Surreal::schema_changes`](crate::Surreal::schema_changes) to
+/// subscribe to the schema changes of the current database.
+#[derive(Debug)]
+pub struct SchemaChanges<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> SchemaChanges<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> SchemaChanges<'static, C> {
+		SchemaChanges {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+
+	/// Starts a `LIVE SCHEMA` query, returning a stream of the changes made to
+	/// the schema of the current database.
+	///
+	/// As with other live queries, the subscription is killed when the stream
+	/// is dropped.
+	pub fn stream(self) -> BoxFuture<'static, Result<Stream<SchemaChange>>> {
+		live_schema(self.client.into_owned())
+	}
+}
+
+/// Returned by [`Surreal::live_params`](crate::Surreal::live_params) to
+/// subscribe to the params of the current database.
+#[derive(Debug)]
+pub struct LiveParams<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> LiveParams<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> LiveParams<'static, C> {
+		LiveParams {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+
+	/// Starts a `LIVE SCHEMA` query, returning a stream of the changes made to
+	/// the params of the current database.
+	///
+	/// As with other live queries, the subscription is killed when the stream
+	/// is dropped.
+	pub fn stream(self) -> BoxFuture<'static, Result<Stream<ParamChange>>> {
+		live_schema(self.client.into_owned())
+	}
+}
+
+/// Starts a `LIVE SCHEMA` query, whose notifications are read as `R`
+fn live_schema<C, R>(client: Surreal<C>) -> BoxFuture<'static, Result<Stream<R>>>
+where
+	C: Connection,
+	R: 'static,
+{
+	Box::pin(async move {
+		let router = client.inner.router.extract()?;
+		if !router.features.contains(&ExtraFeatures::LiveQueries) {
+			return Err(Error::configuration(
+				"The protocol or storage engine does not support live queries on this architecture"
+					.to_string(),
+				ConfigurationError::LiveQueryNotSupported,
+			));
+		}
+		let results = router
+			.execute_query(
+				client.session_id,
+				None,
+				Command::Query {
+					query: Cow::Borrowed("LIVE SCHEMA"),
+					txn: None,
+					variables: Variables::new(),
+				},
+			)
+			.await?;
+		let result = results.into_iter().next().ok_or_else(|| {
+			Error::query("LIVE query returned no results".to_string(), QueryError::NotExecuted)
+		})?;
+		let id = match result.result? {
+			Value::Uuid(id) => *id,
+			other => {
+				return Err(Error::internal(format!(
+					"successful live query didn't return a uuid, got: {:?}",
+					other
+				)));
+			}
+		};
+		let rx = live::register(router, id, client.session_id).await?;
+		Ok(Stream::new(Arc::clone(&client.inner).into(), id, Some(rx)))
+	})
+}
+
+/// A change made to the schema of a database
+#[derive(Clone, Debug, PartialEq, Eq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[non_exhaustive]
+pub struct SchemaChange {
+	/// How the resource was changed
+	pub action: SchemaAction,
+	/// The kind of resource which was changed
+	pub kind: SchemaKind,
+	/// The name of the resource
+	pub name: String,
+	/// The table of a field or index
+	pub table: Option<String>,
+	/// The value of a param, unless it was removed
+	pub value: Option<Value>,
+}
+
+/// A change made to a param of a database
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParamChange {
+	/// How the param was changed
+	pub action: SchemaAction,
+	/// The name of the param, without the leading `$`
+	pub name: String,
+	/// The value of the param, unless it was removed
+	pub value: Option<Value>,
+}
+
+/// How a schema resource was changed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[surreal(untagged, lowercase)]
+#[non_exhaustive]
+pub enum SchemaAction {
+	/// The resource was defined
+	Define,
+	/// The resource was altered, or redefined with `OVERWRITE`
+	Alter,
+	/// The resource was removed
+	Remove,
+}
+
+/// The kind of a schema resource
+#[derive(Clone, Copy, Debug, PartialEq, Eq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[surreal(untagged, lowercase)]
+#[non_exhaustive]
+pub enum SchemaKind {
+	/// A table
+	Table,
+	/// A field of a table
+	Field,
+	/// An index of a table
+	Index,
+	/// A database access method
+	Access,
+	/// A database param
+	Param,
+}
+
+impl futures::Stream for Stream<SchemaChange> {
+	type Item = Result<SchemaChange>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let Some(ref mut rx) = self.as_mut().rx else {
+			return Poll::Ready(None);
+		};
+		match rx.poll_next_unpin(cx) {
+			Poll::Ready(Some(Ok(notification))) => match notification.action {
+				Action::Killed => Poll::Ready(None),
+				_ => Poll::Ready(Some(SchemaChange::from_value(notification.result).map_err(
+					|error| {
+						Error::serialization(error.to_string(), SerializationError::Deserialization)
+					},
+				))),
+			},
+			Poll::Ready(Some(Err(error))) => Poll::Ready(Some(Err(error))),
+			Poll::Ready(None) => Poll::Ready(None),
+			Poll::Pending => Poll::Pending,
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, None)
+	}
+}
+
+impl futures::Stream for Stream<ParamChange> {
+	type Item = Result<ParamChange>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let Some(ref mut rx) = self.as_mut().rx else {
+			return Poll::Ready(None);
+		};
+		// Skip the changes made to the rest of the schema
+		loop {
+			match rx.poll_next_unpin(cx) {
+				Poll::Ready(Some(Ok(notification))) => match notification.action {
+					Action::Killed => return Poll::Ready(None),
+					_ => match SchemaChange::from_value(notification.result) {
+						Ok(change) if change.kind == SchemaKind::Param => {
+							return Poll::Ready(Some(Ok(ParamChange {
+								action: change.action,
+								name: change.name,
+								value: change.value,
+							})));
+						}
+						Ok(_) => continue,
+						Err(error) => {
+							return Poll::Ready(Some(Err(Error::serialization(
+								error.to_string(),
+								SerializationError::Deserialization,
+							))));
+						}
+					},
+				},
+				Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error))),
+				Poll::Ready(None) => return Poll::Ready(None),
+				Poll::Pending => return Poll::Pending,
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, None)
+	}
+}
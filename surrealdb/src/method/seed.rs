@@ -0,0 +1,156 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use crate::method::BoxFuture;
+use crate::opt::{Fixture, FixtureFormat};
+use crate::{Connection, Error, Result, Surreal};
+
+/// The table in which applied fixtures are recorded
+const SEED_TABLE: &str = "_seed";
+
+/// Inserts the rows of an NDJSON fixture, bound as `$seed_rows`
+const NDJSON_QUERY: &str = "FOR $seed_row IN $seed_rows {
+	LET $seed_tb = type::table($seed_row.table);
+	INSERT INTO $seed_tb $seed_row.data;
+};";
+
+/// Returned by [`Surreal::seed`](crate::Surreal::seed) to apply a fixture at
+/// most once.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Seed<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) fixture: Fixture,
+}
+
+impl<C> Seed<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Seed<'static, C> {
+		Seed {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Seed<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<bool>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let (format, content, source) = load(self.fixture).await?;
+			// Skip fixtures which have already been applied
+			let mut response = self
+				.client
+				.query(
+					"RETURN record::exists(type::record($seed_table, crypto::sha256($seed_content)))",
+				)
+				.bind(("seed_table", SEED_TABLE.to_owned()))
+				.bind(("seed_content", content.clone()))
+				.await?;
+			if response.take::<Option<bool>>(0)?.unwrap_or_default() {
+				return Ok(false);
+			}
+			// Apply the fixture and record it in the same transaction, so that a
+			// failed fixture can be retried and a concurrent seed can not apply
+			// the same fixture twice
+			let (body, rows) = match format {
+				FixtureFormat::Surql => (content.clone(), None),
+				FixtureFormat::Ndjson => (NDJSON_QUERY.to_owned(), Some(parse_ndjson(&content)?)),
+			};
+			// The fixture is terminated on its own line, in case it ends with a
+			// comment or without a semicolon. Empty statements are ignored.
+			let query = format!(
+				"BEGIN TRANSACTION;\n{body}\n;\nCREATE type::record($seed_table, crypto::sha256($seed_content)) SET source = $seed_source, applied_at = time::now() RETURN NONE;\nCOMMIT TRANSACTION;"
+			);
+			let mut query = self
+				.client
+				.query(query)
+				.bind(("seed_table", SEED_TABLE.to_owned()))
+				.bind(("seed_content", content))
+				.bind(("seed_source", source));
+			if let Some(rows) = rows {
+				query = query.bind(("seed_rows", rows));
+			}
+			query.await?.check()?;
+			Ok(true)
+		})
+	}
+}
+
+/// Reads a fixture, returning its format, contents and a description of
+/// where it came from
+async fn load(fixture: Fixture) -> Result<(FixtureFormat, String, String)> {
+	match fixture {
+		Fixture::Embedded {
+			format,
+			content,
+		} => Ok((format, content.into_owned(), "embedded".to_owned())),
+		#[cfg(not(target_family = "wasm"))]
+		Fixture::File(path) => {
+			let content = tokio::fs::read_to_string(&path).await.map_err(|e| {
+				Error::internal(format!("Failed to read fixture `{}`: {e}", path.display()))
+			})?;
+			Ok((FixtureFormat::from_path(&path), content, path.display().to_string()))
+		}
+		#[cfg(target_family = "wasm")]
+		Fixture::File(_) => {
+			Err(Error::internal("Reading fixture files is not supported on WebAssembly".to_owned()))
+		}
+	}
+}
+
+/// Parses an NDJSON fixture into the rows to insert
+fn parse_ndjson(content: &str) -> Result<serde_json::Value> {
+	let mut rows = Vec::new();
+	for (i, line) in content.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() {
+			continue;
+		}
+		let invalid = |reason: String| {
+			Error::validation(format!("Invalid fixture on line {}: {reason}", i + 1), None)
+		};
+		let row: serde_json::Value =
+			serde_json::from_str(line).map_err(|e| invalid(e.to_string()))?;
+		if !row.get("table").is_some_and(serde_json::Value::is_string) {
+			return Err(invalid("expected a string `table` field".to_owned()));
+		}
+		if !row.get("data").is_some_and(|x| x.is_object() || x.is_array()) {
+			return Err(invalid("expected an object or array `data` field".to_owned()));
+		}
+		rows.push(row);
+	}
+	Ok(serde_json::Value::Array(rows))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_ndjson_fixtures() {
+		let rows = parse_ndjson(
+			"{\"table\": \"person\", \"data\": {\"id\": \"tobie\"}}\n\n{\"table\": \"person\", \"data\": [{}, {}]}\n",
+		)
+		.unwrap();
+		assert_eq!(rows.as_array().unwrap().len(), 2);
+	}
+
+	#[test]
+	fn rejects_invalid_ndjson_fixtures() {
+		let err =
+			parse_ndjson("{\"table\": \"person\", \"data\": {}}\n{\"data\": {}}").unwrap_err();
+		assert!(err.to_string().contains("line 2"), "{err}");
+		let err = parse_ndjson("not json").unwrap_err();
+		assert!(err.to_string().contains("line 1"), "{err}");
+	}
+}
@@ -5,11 +5,11 @@ use std::marker::PhantomData;
 use uuid::Uuid;
 
 use super::transaction::WithTransaction;
-use crate::conn::Command;
-use crate::method::{BoxFuture, Live, OnceLockExt};
+use crate::conn::{Command, opt_from_value};
+use crate::method::{BoxFuture, IntoQuery, IntoVariables, Live, OnceLockExt, Page, WithTotal};
 use crate::opt::Resource;
 use crate::types::{RecordIdKeyRange, SurrealValue, Value, Variables};
-use crate::{Connection, Result, Surreal};
+use crate::{Connection, Error, Result, Surreal};
 
 /// Returned by [`Surreal::select`](crate::Surreal::select) for `SELECT` (including
 /// [`Select::live`](Self::live) when using [`Live`](crate::method::Live)).
@@ -19,6 +19,11 @@ pub struct Select<'r, C: Connection, R, T = ()> {
 	pub(super) txn: Option<Uuid>,
 	pub(super) client: Cow<'r, Surreal<C>>,
 	pub(super) resource: Result<Resource>,
+	pub(super) start: Option<u64>,
+	pub(super) limit: Option<u64>,
+	pub(super) fields: Option<String>,
+	pub(super) filter: Option<String>,
+	pub(super) variables: Variables,
 	pub(super) response_type: PhantomData<R>,
 	pub(super) query_type: PhantomData<T>,
 }
@@ -47,33 +52,211 @@ where
 	}
 }
 
+impl<C, R> Select<'_, C, R>
+where
+	C: Connection,
+{
+	/// Only returns the given fields of the selected records
+	///
+	/// Each field is a path of field names separated by dots, such as `address.city`. A field
+	/// name followed by `[*]` selects the path within each element of an array, as in
+	/// `orders[*].total`. The fields are compiled into the projection of the `SELECT` statement,
+	/// so the rest of each record is never sent over the wire, and nested fields keep their
+	/// place in the returned records.
+	///
+	/// The `id` of each record is only returned if it is one of the fields.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[derive(serde::Deserialize, surrealdb::types::SurrealValue)]
+	/// # struct Person;
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # db.use_ns("main").use_db("main").await?;
+	/// // Returns records shaped like `{ name, address: { city }, orders: [{ total }] }`
+	/// let people: Vec<Person> =
+	///     db.select("person").fields(["name", "address.city", "orders[*].total"]).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn fields<I, F>(mut self, fields: I) -> Self
+	where
+		I: IntoIterator<Item = F>,
+		F: AsRef<str>,
+	{
+		match projection(fields) {
+			Ok(fields) => self.fields = Some(fields),
+			Err(error) => self.resource = Err(error),
+		}
+		self
+	}
+}
+
+/// The fields selected from each record, merged by their common prefixes
+#[derive(Debug, Default)]
+struct FieldTree(Vec<(String, Option<FieldTree>)>);
+
+impl FieldTree {
+	/// Adds a path to the tree, where `None` selects the whole field
+	fn insert(&mut self, name: &str, rest: &[&str]) {
+		let position = match self.0.iter().position(|(n, _)| n == name) {
+			Some(position) => position,
+			None => {
+				self.0.push((name.to_owned(), Some(FieldTree::default())));
+				self.0.len() - 1
+			}
+		};
+		let node = &mut self.0[position].1;
+		match rest.split_first() {
+			// The whole field supersedes any of its nested fields
+			None => *node = None,
+			Some((next, rest)) => {
+				if let Some(tree) = node {
+					tree.insert(next, rest);
+				}
+			}
+		}
+	}
+
+	/// Writes the nested fields as the parts of a destructuring
+	fn destructure(&self) -> String {
+		let parts: Vec<String> = self
+			.0
+			.iter()
+			.map(|(name, node)| match node {
+				None => escape_field(name),
+				Some(tree) => format!("{}.{{ {} }}", escape_field(name), tree.destructure()),
+			})
+			.collect();
+		parts.join(", ")
+	}
+}
+
+/// Compiles a list of field paths into the projection of a `SELECT` statement
+///
+/// Nested fields are selected with a destructuring, which also applies to each
+/// element of an array, and are aliased so that they keep their place in the
+/// returned records.
+fn projection<I, F>(fields: I) -> Result<String>
+where
+	I: IntoIterator<Item = F>,
+	F: AsRef<str>,
+{
+	let mut tree = FieldTree::default();
+	for field in fields {
+		let field = field.as_ref();
+		let invalid = || {
+			Error::validation(
+				format!("Invalid field path '{field}'"),
+				Some(crate::types::ValidationError::InvalidParams),
+			)
+		};
+		let mut path = Vec::new();
+		for part in field.split('.') {
+			let mut name = part;
+			while let Some(stripped) = name.strip_suffix("[*]") {
+				name = stripped;
+			}
+			if name.is_empty() || name.contains(['[', ']']) {
+				return Err(invalid());
+			}
+			path.push(name);
+		}
+		let (name, rest) = path.split_first().ok_or_else(invalid)?;
+		tree.insert(name, rest);
+	}
+	if tree.0.is_empty() {
+		return Err(Error::validation(
+			"At least one field must be selected".to_owned(),
+			Some(crate::types::ValidationError::InvalidParams),
+		));
+	}
+	let fields: Vec<String> = tree
+		.0
+		.iter()
+		.map(|(name, node)| {
+			let name = escape_field(name);
+			match node {
+				None => name,
+				Some(tree) => format!("{name}.{{ {} }} AS {name}", tree.destructure()),
+			}
+		})
+		.collect();
+	Ok(fields.join(", "))
+}
+
+/// Escapes a field name, so that keywords and other characters are always
+/// read as a name
+pub(crate) fn escape_field(name: &str) -> String {
+	let mut out = String::with_capacity(name.len() + 2);
+	out.push('`');
+	for c in name.chars() {
+		if matches!(c, '`' | '\\') {
+			out.push('\\');
+		}
+		out.push(c);
+	}
+	out.push('`');
+	out
+}
+
+/// Appends the page bounds of a select to the query
+fn page_clauses(start: Option<u64>, limit: Option<u64>) -> String {
+	let mut out = String::new();
+	if let Some(limit) = limit {
+		out.push_str(&format!(" LIMIT {limit}"));
+	}
+	if let Some(start) = start {
+		out.push_str(&format!(" START {start}"));
+	}
+	out
+}
+
 macro_rules! into_future {
-	($method:ident) => {
+	($method:ident $(, cached: $from_value:path)?) => {
 		fn into_future(self) -> Self::IntoFuture {
 			let Select {
 				txn,
 				client,
 				resource,
+				start,
+				limit,
+				fields,
 				..
 			} = self;
 			Box::pin(async move {
 				let router = client.inner.router.extract()?;
 
 				let what = resource?;
+				let fields = fields.as_deref().unwrap_or("*");
 
 				let mut variables = Variables::new();
-				let what = what.for_sql_query(&mut variables)?;
-
-				router
-					.$method(
-						client.session_id,
-						Command::Query {
-							txn,
-							query: Cow::Owned(format!("SELECT * FROM {what}")),
-							variables,
-						},
-					)
-					.await
+				let query = what.for_sql_query(&mut variables)?;
+				let command = Command::Query {
+					txn,
+					query: Cow::Owned(format!(
+						"SELECT {fields} FROM {query}{}",
+						page_clauses(start, limit)
+					)),
+					variables,
+				};
+
+				$(
+					// Records selected by their id outside of a transaction can
+					// be served from the client-side cache
+					if let (Resource::RecordId(record), None, None, None) =
+						(what, txn, start, limit)
+					{
+						let fields = Some(fields.to_owned());
+						let value =
+							router.execute_record(client.session_id, record, fields, command).await?;
+						return $from_value(value);
+					}
+				)?
+
+				router.$method(client.session_id, command).await
 			})
 		}
 	};
@@ -86,7 +269,7 @@ where
 	type Output = Result<Value>;
 	type IntoFuture = BoxFuture<'r, Self::Output>;
 
-	into_future! {execute_value}
+	into_future! {execute_value, cached: Ok}
 }
 
 impl<'r, Client, R> IntoFuture for Select<'r, Client, Option<R>>
@@ -97,7 +280,7 @@ where
 	type Output = Result<Option<R>>;
 	type IntoFuture = BoxFuture<'r, Self::Output>;
 
-	into_future! {execute_opt}
+	into_future! {execute_opt, cached: opt_from_value}
 }
 
 impl<'r, Client, R> IntoFuture for Select<'r, Client, Vec<R>>
@@ -122,7 +305,7 @@ where
 	}
 }
 
-impl<C, R> Select<'_, C, Vec<R>>
+impl<'r, C, R> Select<'r, C, Vec<R>>
 where
 	C: Connection,
 {
@@ -131,6 +314,124 @@ where
 		self.resource = self.resource.and_then(|x| x.with_range(range.into()));
 		self
 	}
+
+	/// Skips the given number of records
+	pub fn start(mut self, start: u64) -> Self {
+		self.start = Some(start);
+		self
+	}
+
+	/// Returns at most the given number of records
+	pub fn limit(mut self, limit: u64) -> Self {
+		self.limit = Some(limit);
+		self
+	}
+
+	/// Returns the total number of matching records along with the selected
+	/// page of records
+	///
+	/// The page and the total are read in the same statement, so they are
+	/// consistent with each other. The total ignores [`start`](Self::start)
+	/// and [`limit`](Self::limit), and is computed with a grouped `count()`,
+	/// which does not fetch the records themselves.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[derive(serde::Deserialize, surrealdb::types::SurrealValue)]
+	/// # struct Person;
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # db.use_ns("main").use_db("main").await?;
+	/// let page = db.select::<Vec<Person>>("person").start(20).limit(10).with_total().await?;
+	/// println!("Showing {} of {} people", page.items.len(), page.total);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_total(self) -> Select<'r, C, Vec<R>, WithTotal> {
+		Select {
+			txn: self.txn,
+			client: self.client,
+			resource: self.resource,
+			start: self.start,
+			limit: self.limit,
+			fields: self.fields,
+			filter: self.filter,
+			variables: self.variables,
+			response_type: self.response_type,
+			query_type: PhantomData,
+		}
+	}
+}
+
+impl<'r, Client, R> IntoFuture for Select<'r, Client, Vec<R>, WithTotal>
+where
+	Client: Connection,
+	R: SurrealValue,
+{
+	type Output = Result<Page<R>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		let Select {
+			txn,
+			client,
+			resource,
+			start,
+			limit,
+			fields,
+			..
+		} = self;
+		Box::pin(async move {
+			let router = client.inner.router.extract()?;
+
+			let what = resource?;
+			let fields = fields.as_deref().unwrap_or("*");
+
+			let mut variables = Variables::new();
+			let what = what.for_sql_query(&mut variables)?;
+
+			let value = router
+				.execute_value(
+					client.session_id,
+					Command::Query {
+						txn,
+						query: Cow::Owned(format!(
+							"RETURN {{ items: (SELECT {fields} FROM {what}{}), total: (SELECT count() FROM {what} GROUP ALL)[0].count ?? 0 }}",
+							page_clauses(start, limit)
+						)),
+						variables,
+					},
+				)
+				.await?;
+			page_from_value(value)
+		})
+	}
+}
+
+/// Extracts a [`Page`] from the object returned by a select with a total
+fn page_from_value<R: SurrealValue>(value: Value) -> Result<Page<R>> {
+	let invalid = || Error::internal("The database returned an invalid page".to_owned());
+	let Value::Object(mut object) = value else {
+		return Err(invalid());
+	};
+	let items = match object.remove("items") {
+		Some(Value::Array(items)) => items
+			.into_iter()
+			.map(|v| R::from_value(v).map_err(deserialize))
+			.collect::<Result<_>>()?,
+		_ => return Err(invalid()),
+	};
+	let total = object.remove("total").ok_or_else(invalid)?;
+	Ok(Page {
+		items,
+		total: u64::from_value(total).map_err(deserialize)?,
+	})
+}
+
+fn deserialize(e: impl std::fmt::Display) -> Error {
+	Error::serialization(e.to_string(), crate::types::SerializationError::Deserialization)
 }
 
 impl<'r, C, R> Select<'r, C, R>
@@ -190,8 +491,104 @@ where
 			txn: self.txn,
 			client: self.client,
 			resource: self.resource,
+			start: self.start,
+			limit: self.limit,
+			fields: self.fields,
+			filter: self.filter,
+			variables: self.variables,
 			response_type: self.response_type,
 			query_type: PhantomData,
 		}
 	}
 }
+
+impl<C, R> Select<'_, C, R, Live>
+where
+	C: Connection,
+{
+	/// Only sends notifications for records which match a condition
+	///
+	/// The condition is a single SurrealQL expression, as used in the `WHERE` clause of a `LIVE
+	/// SELECT` statement. Values should not be formatted into the condition, but passed as
+	/// parameters with [`Select::bind`], set on the session with
+	/// [`Surreal::set`](crate::Surreal::set), or interpolated with the `surql!` macro, which binds
+	/// them. A condition which is not a single expression is rejected.
+	///
+	/// Session parameters are resolved when each notification is sent, rather than when the live
+	/// query is started, so setting or unsetting a parameter on the session changes which
+	/// notifications are received from then on. This allows many subscribers to share the same
+	/// query, each with their own filter.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let mut stream = db
+	///     .select("msg")
+	///     .live()
+	///     .filter("room = $room")
+	///     .bind(("room", "general"))
+	///     .await?;
+	///
+	/// // Receive notifications for another room from now on
+	/// db.set("room", "random").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn filter<'q>(mut self, condition: impl IntoQuery<'q>) -> Self {
+		let (condition, variables) = condition.into_query();
+		match surrealdb_core::syn::condition(&condition) {
+			Ok(condition) => {
+				self.filter = Some(condition);
+				self.variables.extend(variables);
+			}
+			Err(e) => {
+				self.resource = Err(Error::validation(
+					format!("Invalid filter '{condition}': {e}"),
+					Some(crate::types::ValidationError::InvalidParams),
+				));
+			}
+		}
+		self
+	}
+
+	/// Binds the parameters used by the [filter](Select::filter) of the live query
+	pub fn bind(mut self, vars: impl IntoVariables) -> Self {
+		match vars.into_variables() {
+			Ok(vars) => self.variables.extend(vars),
+			Err(error) => self.resource = Err(error),
+		}
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn compiles_fields_into_a_projection() {
+		assert_eq!(
+			projection(["name", "address.city", "orders[*].total", "address.zip"]).unwrap(),
+			"`name`, `address`.{ `city`, `zip` } AS `address`, `orders`.{ `total` } AS `orders`"
+		);
+		assert_eq!(
+			projection(["a.b.c", "a.b", "a.d.e"]).unwrap(),
+			"`a`.{ `b`, `d`.{ `e` } } AS `a`"
+		);
+	}
+
+	#[test]
+	fn escapes_field_names() {
+		assert_eq!(projection(["value", "a` FROM x; --"]).unwrap(), "`value`, `a\\` FROM x; --`");
+	}
+
+	#[test]
+	fn rejects_invalid_field_paths() {
+		for fields in [vec![""], vec!["a..b"], vec!["orders[0].total"], vec![]] {
+			projection(fields).unwrap_err();
+		}
+	}
+}
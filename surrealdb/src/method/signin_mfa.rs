@@ -0,0 +1,130 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use surrealdb_types::{AuthError, NotAllowedError};
+
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::opt::auth::Token;
+use crate::types::{Object, Value};
+use crate::{Connection, Result, Surreal};
+
+/// Returned by [`Surreal::signin_mfa`](crate::Surreal::signin_mfa) for record
+/// credentials, when the record access method may require a second factor.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SigninMfa<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) credentials: Value,
+}
+
+impl<C> SigninMfa<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> SigninMfa<'static, C> {
+		SigninMfa {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+/// The outcome of [`Surreal::signin_mfa`](crate::Surreal::signin_mfa)
+#[derive(Debug)]
+pub enum MfaSignin<C: Connection> {
+	/// The signin is complete, as the record is not enrolled in a second factor
+	Complete(Token),
+	/// The signin has to be completed with a one-time code
+	Challenge(MfaChallenge<C>),
+}
+
+/// A signin waiting for the one-time code of the record which signed in
+///
+/// The challenge expires a few minutes after it was issued.
+#[derive(Debug)]
+pub struct MfaChallenge<C: Connection> {
+	client: Surreal<C>,
+	credentials: Object,
+	challenge: String,
+}
+
+impl<C> MfaChallenge<C>
+where
+	C: Connection,
+{
+	/// Returns the challenge issued by the server
+	pub fn challenge(&self) -> &str {
+		&self.challenge
+	}
+
+	/// Completes the signin with a code from the authenticator app of the
+	/// record
+	///
+	/// A code is only accepted once. On failure, the signin has to be started
+	/// again with [`Surreal::signin_mfa`](crate::Surreal::signin_mfa).
+	pub async fn verify(&self, code: impl Into<String>) -> Result<Token> {
+		let mut credentials = self.credentials.clone();
+		credentials.insert("challenge".to_owned(), Value::String(self.challenge.clone()));
+		credentials.insert("code".to_owned(), Value::String(code.into()));
+		let router = self.client.inner.router.extract()?;
+		router
+			.execute(
+				self.client.session_id,
+				Command::Signin {
+					credentials,
+				},
+			)
+			.await
+	}
+}
+
+impl<'r, Client> IntoFuture for SigninMfa<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<MfaSignin<Client>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		let SigninMfa {
+			client,
+			credentials,
+		} = self;
+		Box::pin(async move {
+			let credentials =
+				credentials.into_object().map_err(|e| crate::Error::internal(e.to_string()))?;
+			// The challenge is verified against the same access method
+			let mut scope = Object::new();
+			for key in ["ns", "db", "ac"] {
+				if let Some(value) = credentials.get(key) {
+					scope.insert(key.to_owned(), value.clone());
+				}
+			}
+			let router = client.inner.router.extract()?;
+			let res = router
+				.execute(
+					client.session_id,
+					Command::Signin {
+						credentials,
+					},
+				)
+				.await;
+			match res {
+				Ok(token) => Ok(MfaSignin::Complete(token)),
+				Err(e) => match e.not_allowed_details() {
+					Some(NotAllowedError::Auth(AuthError::MfaRequired {
+						challenge,
+					})) => Ok(MfaSignin::Challenge(MfaChallenge {
+						client: client.into_owned(),
+						credentials: scope,
+						challenge: challenge.clone(),
+					})),
+					_ => Err(e),
+				},
+			}
+		})
+	}
+}
@@ -0,0 +1,52 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::{Connection, Result, Surreal};
+
+/// A storage-level snapshot future
+///
+/// Returned by [`Surreal::snapshot`](crate::Surreal::snapshot)
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Snapshot<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) name: String,
+}
+
+impl<C> Snapshot<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Snapshot<'static, C> {
+		Snapshot {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Snapshot<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			router
+				.execute_unit(
+					self.client.session_id,
+					Command::Snapshot {
+						name: self.name,
+					},
+				)
+				.await
+		})
+	}
+}
@@ -0,0 +1,72 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::future::IntoFuture;
+
+use crate::method::BoxFuture;
+use crate::types::SurrealValue;
+use crate::{Connection, Error, Result, Surreal};
+
+/// The statement used to read the statistics of a table
+const TABLE_STATS_QUERY: &str = "RETURN schema::table::stats($table)";
+
+/// Returned by [`Surreal::table_stats`](crate::Surreal::table_stats), yields the statistics of a
+/// table.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TableStats<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) table: String,
+}
+
+impl<C> TableStats<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> TableStats<'static, C> {
+		TableStats {
+			client: Cow::Owned(self.client.into_owned()),
+			table: self.table,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for TableStats<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<TableStatistics>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let mut response =
+				self.client.query(TABLE_STATS_QUERY).bind(("table", self.table)).await?;
+			response.take::<Option<TableStatistics>>(0)?.ok_or_else(|| {
+				Error::internal("The database did not return the table statistics".to_owned())
+			})
+		})
+	}
+}
+
+/// The statistics of a table, returned by
+/// [`Surreal::table_stats`](crate::Surreal::table_stats)
+///
+/// The statistics are maintained incrementally as records are written, so they
+/// are estimates rather than exact counts.
+#[derive(Clone, Debug, Default, PartialEq, Eq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[non_exhaustive]
+pub struct TableStatistics {
+	/// The estimated number of records in the table
+	pub rows: u64,
+	/// The approximate encoded size of the records in the table, in bytes
+	pub bytes: u64,
+	/// The estimated number of entries of each standard and unique index of
+	/// the table, by index name
+	pub indexes: BTreeMap<String, u64>,
+	/// The versionstamp of the latest transaction which wrote to the table,
+	/// if any has since the statistics were first maintained
+	pub versionstamp: Option<u64>,
+}
@@ -26,6 +26,7 @@ async fn api() {
 
 	// health
 	let _: () = DB.health().await.unwrap();
+	let _: crate::method::HealthReport = DB.health_report().await.unwrap();
 
 	// invalidate
 	let _: () = DB.invalidate().await.unwrap();
@@ -117,6 +118,7 @@ async fn api() {
 	// select
 	let _: Vec<User> = DB.select(USER).await.unwrap();
 	let _: Option<User> = DB.select((USER, "john")).await.unwrap();
+	let _: Vec<User> = DB.select(USER).fields(["name", "address.city"]).await.unwrap();
 	let _: Vec<User> = DB.select(USER).range(..).await.unwrap();
 	let _: Vec<User> = DB.select(USER).range(.."john").await.unwrap();
 	let _: Vec<User> = DB.select(USER).range(..="john").await.unwrap();
@@ -152,6 +154,10 @@ async fn api() {
 		DB.update(USER).range("jane".."john").patch(PatchOp::remove("/name")).await.unwrap();
 	let _: Option<User> = DB.update((USER, "john")).patch(PatchOp::remove("/name")).await.unwrap();
 
+	// update elements
+	let _: Option<User> =
+		DB.update((USER, "john")).elements("tags", "$this = 'a'").set("name", "b").await.unwrap();
+
 	// delete
 	let _: Vec<User> = DB.delete(USER).await.unwrap();
 	let _: Option<User> = DB.delete((USER, "john")).await.unwrap();
@@ -165,6 +171,10 @@ async fn api() {
 
 	// version
 	let _: Version = DB.version().await.unwrap();
+
+	// compatibility
+	let report = DB.compatibility().await.unwrap();
+	assert_eq!(report.issues, [crate::method::CompatibilityIssue::UnsupportedServer]);
 }
 
 fn assert_send_sync(_: impl Send + Sync) {}
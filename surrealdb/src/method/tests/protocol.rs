@@ -6,7 +6,7 @@ use tokio::sync::watch;
 use url::Url;
 
 use super::server;
-use crate::conn::Router;
+use crate::conn::{QueryLimiter, Router};
 use crate::method::BoxFuture;
 use crate::opt::endpoint::into_endpoint;
 use crate::opt::{Endpoint, IntoEndpoint};
@@ -57,6 +57,12 @@ impl conn::Sealed for Client {
 			let router = Router {
 				features,
 				sender: route_tx,
+				limiter: QueryLimiter::new(&address.config).into(),
+				middleware: Default::default(),
+				cache: None,
+				compression: None,
+				latency: Default::default(),
+				notifications: None,
 				config: address.config,
 			};
 			server::mock(route_rx);
@@ -16,7 +16,7 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 			let query_result = QueryResultBuilder::started_now();
 
 			let query_result = match cmd {
-				Command::Invalidate | Command::Health => query_result,
+				Command::Invalidate | Command::StopImpersonating | Command::Health => query_result,
 				Command::Begin => {
 					query_result.with_result(Ok(Value::Uuid(uuid::Uuid::now_v7().into())))
 				}
@@ -28,6 +28,9 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				}
 				| Command::Revoke {
 					..
+				}
+				| Command::Impersonate {
+					..
 				} => query_result,
 				Command::Authenticate {
 					token,
@@ -46,9 +49,17 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				} => query_result.with_result(Ok(Value::String(
 					"c6c0e36c-e2cf-42cb-b2d5-75415249b261".to_string(),
 				))),
+				Command::HealthReport => query_result
+					.with_result(Ok(crate::method::HealthReport::default().into_value())),
 				Command::Version => {
 					query_result.with_result(Ok(Value::String("1.0.0".to_string())))
 				}
+				Command::Compatibility => {
+					query_result.with_result(Ok(Value::from_t(crate::types::object! {
+						version: "1.0.0",
+						storage: surrealdb_core::env::STORAGE_VERSION as i64,
+					})))
+				}
 				Command::Use {
 					..
 				} => query_result,
@@ -63,6 +74,12 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				} => query_result,
 				Command::Query {
 					..
+				}
+				| Command::IdempotentQuery {
+					..
+				}
+				| Command::QueryBytes {
+					..
 				} => query_result,
 				Command::Run {
 					..
@@ -90,6 +107,15 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				}
 				| Command::Detach {
 					..
+				}
+				| Command::LockWrites {
+					..
+				}
+				| Command::UnlockWrites {
+					..
+				}
+				| Command::Snapshot {
+					..
 				} => query_result,
 			};
 
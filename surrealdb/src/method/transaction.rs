@@ -1,8 +1,9 @@
-use std::borrow::Cow;
-
 use uuid::Uuid;
 
-use crate::method::{Cancel, Commit, Create, Delete, Insert, Query, Select, Update, Upsert};
+use crate::method::{
+	Cancel, Commit, Create, Delete, Insert, IntoQuery, IntoRecordId, Query, Select, Traverse,
+	Update, Upsert,
+};
 use crate::opt::{CreateResource, IntoResource};
 use crate::{Connection, Surreal};
 
@@ -37,7 +38,7 @@ where
 	}
 
 	/// See [Surreal::query]
-	pub fn query<'client>(&'client self, query: impl Into<Cow<'client, str>>) -> Query<'client, C> {
+	pub fn query<'client>(&'client self, query: impl IntoQuery<'client>) -> Query<'client, C> {
 		self.client.query(query).with_transaction(self.id)
 	}
 
@@ -46,6 +47,11 @@ where
 		self.client.select(resource).with_transaction(self.id)
 	}
 
+	/// See [Surreal::traverse]
+	pub fn traverse<R>(&'_ self, from: impl IntoRecordId) -> Traverse<'_, C, R> {
+		self.client.traverse(from).with_transaction(self.id)
+	}
+
 	/// See [Surreal::create]
 	pub fn create<R>(&'_ self, resource: impl CreateResource<R>) -> Create<'_, C, R> {
 		self.client.create(resource).with_transaction(self.id)
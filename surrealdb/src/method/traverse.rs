@@ -0,0 +1,391 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+
+use uuid::Uuid;
+
+use super::transaction::WithTransaction;
+use crate::conn::Command;
+use crate::method::{BoxFuture, OnceLockExt, WithPaths};
+use crate::opt::Direction;
+use crate::types::{RecordId, RecordIdKey, SurrealValue, Table, ToSql, Value, Variables};
+use crate::{Connection, Error, Result, Surreal};
+
+/// Returned by [`Surreal::traverse`](crate::Surreal::traverse) to walk the graph
+/// edges from a record (including [`Traverse::with_paths`] when using
+/// [`WithPaths`](crate::method::WithPaths)).
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Traverse<'r, C: Connection, R, T = ()> {
+	pub(super) txn: Option<Uuid>,
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) from: Result<RecordId>,
+	pub(super) steps: Vec<(Direction, Table)>,
+	pub(super) limit: Option<u64>,
+	pub(super) response_type: PhantomData<R>,
+	pub(super) query_type: PhantomData<T>,
+}
+
+/// A path through the graph, captured by [`Traverse::with_paths`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct TraversalPath<R> {
+	/// The records visited along the path, from the starting record up to
+	/// and including the record which was reached
+	pub path: Vec<RecordId>,
+	/// The record which was reached at the end of the path
+	pub record: R,
+}
+
+impl<C, R, T> WithTransaction for Traverse<'_, C, R, T>
+where
+	C: Connection,
+{
+	fn with_transaction(mut self, id: Uuid) -> Self {
+		self.txn = Some(id);
+		self
+	}
+}
+
+impl<C, R, T> Traverse<'_, C, R, T>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Traverse<'static, C, R, T> {
+		Traverse {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	fn step(mut self, direction: Direction, edge: impl Into<Table>) -> Self {
+		self.steps.push((direction, edge.into()));
+		self
+	}
+
+	/// Follows the outgoing edges of the given table, to the records they
+	/// point to (`->edge->?`)
+	pub fn out(self, edge: impl Into<Table>) -> Self {
+		self.step(Direction::Out, edge)
+	}
+
+	/// Follows the incoming edges of the given table, back to the records they
+	/// come from (`<-edge<-?`)
+	pub fn in_(self, edge: impl Into<Table>) -> Self {
+		self.step(Direction::In, edge)
+	}
+
+	/// Follows the edges of the given table in both directions (`<->edge<->?`)
+	pub fn both(self, edge: impl Into<Table>) -> Self {
+		self.step(Direction::Both, edge)
+	}
+
+	/// Limits the number of records, or paths, which are returned
+	pub fn limit(mut self, limit: u64) -> Self {
+		self.limit = Some(limit);
+		self
+	}
+}
+
+impl<'r, C, R> Traverse<'r, C, R>
+where
+	C: Connection,
+{
+	/// Captures the path taken to reach each record
+	///
+	/// Every record which is reached is returned as a [`TraversalPath`],
+	/// alongside the ids of the records visited on the way to it.
+	pub fn with_paths(self) -> Traverse<'r, C, R, WithPaths> {
+		Traverse {
+			txn: self.txn,
+			client: self.client,
+			from: self.from,
+			steps: self.steps,
+			limit: self.limit,
+			response_type: PhantomData,
+			query_type: PhantomData,
+		}
+	}
+}
+
+/// Renders a single traversal step as a graph expression
+fn arrow((direction, edge): &(Direction, Table)) -> String {
+	let edge = edge.to_sql();
+	match direction {
+		Direction::Out => format!("->{edge}->?"),
+		Direction::In => format!("<-{edge}<-?"),
+		Direction::Both => format!("<->{edge}<->?"),
+	}
+}
+
+/// Builds the query selecting the records reached by the steps
+fn records_query(steps: &[(Direction, Table)], limit: Option<u64>) -> String {
+	let arrows = steps.iter().map(arrow).collect::<String>();
+	let mut query = format!("SELECT * FROM $_from{arrows}");
+	if let Some(limit) = limit {
+		query.push_str(&format!(" LIMIT {limit}"));
+	}
+	query
+}
+
+/// Builds the query selecting every path taken by the steps, as an object
+/// holding the ids of the records visited along the path and the record which
+/// was reached, limited on the server to the given number of paths
+fn paths_query(steps: &[(Direction, Table)], limit: Option<u64>) -> String {
+	// The starting record begins every path, unless it is the record reached
+	let mut path = match steps {
+		[] => Vec::new(),
+		_ => vec!["$_from".to_owned()],
+	};
+	let paths = paths_expr(steps, "$_from", &mut path);
+	match limit {
+		Some(limit) => format!("RETURN array::slice({paths}, 0, {})", limit.min(i64::MAX as u64)),
+		None => format!("RETURN {paths}"),
+	}
+}
+
+/// Builds the expression collecting the paths taken by the steps from a
+/// record, below the ids of the records visited so far
+fn paths_expr(steps: &[(Direction, Table)], from: &str, path: &mut Vec<String>) -> String {
+	match steps {
+		[step, rest @ ..] if !rest.is_empty() => {
+			// Each intermediate record is bound to its own parameter, so that
+			// the paths below it can refer to it
+			let var = format!("$_step{}", path.len());
+			path.push(var.clone());
+			let inner = paths_expr(rest, &var, path);
+			path.pop();
+			format!(
+				"array::flatten((SELECT VALUE id FROM {from}{}).map(|{var}| {inner}))",
+				arrow(step)
+			)
+		}
+		_ => {
+			let arrows = steps.iter().map(arrow).collect::<String>();
+			let ids = path.iter().map(|x| format!("{x}, ")).collect::<String>();
+			format!(
+				"(SELECT * FROM {from}{arrows}).map(|$_record| ({{ path: [{ids}$_record.id], record: $_record }}))"
+			)
+		}
+	}
+}
+
+/// Splits the result of a paths query into the path and record of each path
+fn collect_paths(value: Value) -> Vec<(Vec<RecordId>, Value)> {
+	let Value::Array(paths) = value else {
+		return Vec::new();
+	};
+	paths
+		.into_iter()
+		.filter_map(|path| {
+			let Value::Object(mut path) = path else {
+				return None;
+			};
+			let Some(Value::Array(ids)) = path.remove("path") else {
+				return None;
+			};
+			let ids = ids
+				.into_iter()
+				.filter_map(|id| match id {
+					Value::RecordId(id) => Some(id),
+					_ => None,
+				})
+				.collect();
+			Some((ids, path.remove("record").unwrap_or(Value::None)))
+		})
+		.collect()
+}
+
+fn deserialize<R: SurrealValue>(value: Value) -> Result<R> {
+	R::from_value(value).map_err(|e| {
+		Error::serialization(e.to_string(), crate::types::SerializationError::Deserialization)
+	})
+}
+
+impl<'r, Client, R> IntoFuture for Traverse<'r, Client, R>
+where
+	Client: Connection,
+	R: SurrealValue,
+{
+	type Output = Result<Vec<R>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		let Traverse {
+			txn,
+			client,
+			from,
+			steps,
+			limit,
+			..
+		} = self;
+		Box::pin(async move {
+			let router = client.inner.router.extract()?;
+			let mut variables = Variables::new();
+			variables.insert("_from".to_string(), Value::RecordId(from?));
+			router
+				.execute_vec(
+					client.session_id,
+					Command::Query {
+						txn,
+						query: Cow::Owned(records_query(&steps, limit)),
+						variables,
+					},
+				)
+				.await
+		})
+	}
+}
+
+impl<'r, Client, R> IntoFuture for Traverse<'r, Client, R, WithPaths>
+where
+	Client: Connection,
+	R: SurrealValue,
+{
+	type Output = Result<Vec<TraversalPath<R>>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		let Traverse {
+			txn,
+			client,
+			from,
+			steps,
+			limit,
+			..
+		} = self;
+		Box::pin(async move {
+			let router = client.inner.router.extract()?;
+			let mut variables = Variables::new();
+			variables.insert("_from".to_string(), Value::RecordId(from?));
+			let value = router
+				.execute_value(
+					client.session_id,
+					Command::Query {
+						txn,
+						query: Cow::Owned(paths_query(&steps, limit)),
+						variables,
+					},
+				)
+				.await?;
+			collect_paths(value)
+				.into_iter()
+				.map(|(path, record)| {
+					Ok(TraversalPath {
+						path,
+						record: deserialize(record)?,
+					})
+				})
+				.collect()
+		})
+	}
+}
+
+/// Converts a value into the record id a traversal starts from
+pub trait IntoRecordId: into_record_id::Sealed {}
+
+impl IntoRecordId for RecordId {}
+impl into_record_id::Sealed for RecordId {
+	fn into_record_id(self) -> Result<RecordId> {
+		Ok(self)
+	}
+}
+
+impl IntoRecordId for &RecordId {}
+impl into_record_id::Sealed for &RecordId {
+	fn into_record_id(self) -> Result<RecordId> {
+		Ok(self.clone())
+	}
+}
+
+impl IntoRecordId for &str {}
+impl into_record_id::Sealed for &str {
+	fn into_record_id(self) -> Result<RecordId> {
+		surrealdb_core::syn::record_id(self).map_err(|e| {
+			Error::validation(
+				format!("Invalid record id '{self}': {e}"),
+				Some(crate::types::ValidationError::InvalidParams),
+			)
+		})
+	}
+}
+
+impl IntoRecordId for String {}
+impl into_record_id::Sealed for String {
+	fn into_record_id(self) -> Result<RecordId> {
+		self.as_str().into_record_id()
+	}
+}
+
+impl IntoRecordId for &String {}
+impl into_record_id::Sealed for &String {
+	fn into_record_id(self) -> Result<RecordId> {
+		self.as_str().into_record_id()
+	}
+}
+
+impl<T, I> IntoRecordId for (T, I)
+where
+	T: Into<Table>,
+	I: Into<RecordIdKey>,
+{
+}
+impl<T, I> into_record_id::Sealed for (T, I)
+where
+	T: Into<Table>,
+	I: Into<RecordIdKey>,
+{
+	fn into_record_id(self) -> Result<RecordId> {
+		Ok(RecordId::new(self.0, self.1))
+	}
+}
+
+mod into_record_id {
+	use crate::Result;
+	use crate::types::RecordId;
+
+	pub trait Sealed {
+		/// Handles the conversion of the starting record
+		fn into_record_id(self) -> Result<RecordId>;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn steps() -> Vec<(Direction, Table)> {
+		vec![(Direction::Out, Table::from("likes")), (Direction::In, Table::from("authored"))]
+	}
+
+	#[test]
+	fn builds_records_query() {
+		assert_eq!(
+			records_query(&steps(), Some(50)),
+			"SELECT * FROM $_from->likes->?<-authored<-? LIMIT 50"
+		);
+	}
+
+	#[test]
+	fn builds_paths_query() {
+		assert_eq!(
+			paths_query(&steps(), Some(50)),
+			"RETURN array::slice(array::flatten((SELECT VALUE id FROM $_from->likes->?).map(|$_step1| (SELECT * FROM $_step1<-authored<-?).map(|$_record| ({ path: [$_from, $_step1, $_record.id], record: $_record })))), 0, 50)"
+		);
+		assert_eq!(
+			paths_query(&[], None),
+			"RETURN (SELECT * FROM $_from).map(|$_record| ({ path: [$_record.id], record: $_record }))"
+		);
+	}
+
+	#[test]
+	fn escapes_edge_tables() {
+		let steps = vec![(Direction::Out, Table::from("likes->?; DELETE person"))];
+		assert_eq!(
+			records_query(&steps, None),
+			"SELECT * FROM $_from->`likes->?; DELETE person`->?"
+		);
+	}
+}
@@ -8,7 +8,7 @@ use uuid::Uuid;
 use super::transaction::WithTransaction;
 use super::validate_data;
 use crate::conn::Command;
-use crate::method::{BoxFuture, Content, Merge, OnceLockExt, Patch};
+use crate::method::{BoxFuture, Content, Merge, OnceLockExt, Patch, UpdateElements};
 use crate::opt::{PatchOps, Resource};
 use crate::types::{RecordId, RecordIdKeyRange, SurrealValue, Value, Variables};
 use crate::{Connection, Error, Result, Surreal};
@@ -22,6 +22,7 @@ pub struct Update<'r, C: Connection, R> {
 	pub(super) txn: Option<Uuid>,
 	pub(super) client: Cow<'r, Surreal<C>>,
 	pub(super) resource: Result<Resource>,
+	pub(super) idempotency_key: Option<String>,
 	pub(super) response_type: PhantomData<R>,
 }
 
@@ -47,6 +48,19 @@ where
 			..self
 		}
 	}
+
+	/// Runs the update with an idempotency key, such as a UUID generated by
+	/// the client
+	///
+	/// The server records the result under the key, and returns it rather
+	/// than applying the update again when the same update is retried with
+	/// the same key, for instance after a network timeout. Keys expire after
+	/// a period configured on the server, and can not be used within a
+	/// transaction.
+	pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+		self.idempotency_key = Some(key.into());
+		self
+	}
 }
 
 macro_rules! into_future {
@@ -56,6 +70,7 @@ macro_rules! into_future {
 				txn,
 				client,
 				resource,
+				idempotency_key,
 				..
 			} = self;
 			Box::pin(async move {
@@ -66,16 +81,13 @@ macro_rules! into_future {
 				let mut variables = Variables::new();
 				let what = what.for_sql_query(&mut variables)?;
 
-				router
-					.$method(
-						client.session_id,
-						Command::Query {
-							txn,
-							query: Cow::Owned(format!("UPDATE {what}")),
-							variables,
-						},
-					)
-					.await
+				let cmd = Command::Query {
+					txn,
+					query: Cow::Owned(format!("UPDATE {what}")),
+					variables,
+				}
+				.with_idempotency_key(idempotency_key)?;
+				router.$method(client.session_id, cmd).await
 			})
 		}
 	};
@@ -225,11 +237,12 @@ where
 				}
 			};
 
-			Ok(Command::Query {
+			Command::Query {
 				txn: self.txn,
 				query,
 				variables,
-			})
+			}
+			.with_idempotency_key(self.idempotency_key)
 		})
 	}
 
@@ -244,6 +257,42 @@ where
 			resource: self.resource,
 			content: data,
 			upsert: false,
+			idempotency_key: self.idempotency_key,
+			response_type: PhantomData,
+		}
+	}
+
+	/// Updates the elements of an array field which match a condition, in
+	/// place, rather than rewriting the whole array
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.update(("cart", "alice"))
+	///     .elements("items", "sku = $sku")
+	///     .bind(("sku", "x"))
+	///     .increment("qty", 1)
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn elements(
+		self,
+		array: impl Into<String>,
+		condition: impl Into<String>,
+	) -> UpdateElements<'r, C, R> {
+		UpdateElements {
+			txn: self.txn,
+			client: self.client,
+			resource: self.resource,
+			array: array.into(),
+			condition: condition.into(),
+			assignments: Vec::new(),
+			variables: Variables::new(),
+			idempotency_key: self.idempotency_key,
 			response_type: PhantomData,
 		}
 	}
@@ -257,6 +306,7 @@ where
 			client: self.client,
 			resource: self.resource,
 			upsert: false,
+			idempotency_key: self.idempotency_key,
 			response_type: PhantomData,
 		}
 	}
@@ -0,0 +1,194 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+
+use uuid::Uuid;
+
+use crate::conn::Command;
+use crate::method::{BoxFuture, IntoVariables, OnceLockExt, escape_field};
+use crate::opt::Resource;
+use crate::types::{SurrealValue, Value, Variables};
+use crate::{Connection, Error, Result, Surreal};
+
+/// Returned by [`Update::elements`](crate::method::Update::elements), updates
+/// the elements of an array which match a condition in place
+///
+/// Only the matched elements are changed, so concurrent updates of other
+/// elements of the same array are not overwritten.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct UpdateElements<'r, C: Connection, R> {
+	pub(super) txn: Option<Uuid>,
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) resource: Result<Resource>,
+	pub(super) array: String,
+	pub(super) condition: String,
+	pub(super) assignments: Vec<(String, &'static str, Value)>,
+	pub(super) variables: Variables,
+	pub(super) idempotency_key: Option<String>,
+	pub(super) response_type: PhantomData<R>,
+}
+
+impl<C, R> UpdateElements<'_, C, R>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> UpdateElements<'static, C, R> {
+		UpdateElements {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Sets a field of the matched elements
+	pub fn set(self, field: impl Into<String>, value: impl SurrealValue) -> Self {
+		self.assign(field, "=", value)
+	}
+
+	/// Adds a value to a field of the matched elements, as with `+=`
+	pub fn increment(self, field: impl Into<String>, value: impl SurrealValue) -> Self {
+		self.assign(field, "+=", value)
+	}
+
+	/// Subtracts a value from a field of the matched elements, as with `-=`
+	pub fn decrement(self, field: impl Into<String>, value: impl SurrealValue) -> Self {
+		self.assign(field, "-=", value)
+	}
+
+	/// Binds the parameters used by the condition which selects the elements
+	pub fn bind(mut self, vars: impl IntoVariables) -> Self {
+		match vars.into_variables() {
+			Ok(vars) => self.variables.extend(vars),
+			Err(error) => self.resource = Err(error),
+		}
+		self
+	}
+
+	fn assign(
+		mut self,
+		field: impl Into<String>,
+		operator: &'static str,
+		value: impl SurrealValue,
+	) -> Self {
+		self.assignments.push((field.into(), operator, value.into_value()));
+		self
+	}
+}
+
+/// Escapes each part of a dotted field path
+pub(super) fn field_path(path: &str) -> Result<String> {
+	if path.split('.').any(str::is_empty) {
+		return Err(Error::validation(format!("Invalid field path '{path}'"), None));
+	}
+	Ok(path.split('.').map(escape_field).collect::<Vec<_>>().join("."))
+}
+
+/// Builds the `UPDATE` statement which applies the assignments to the
+/// matched elements
+fn query(
+	what: &str,
+	array: &str,
+	condition: &str,
+	assignments: Vec<(String, &'static str, Value)>,
+	variables: &mut Variables,
+) -> Result<String> {
+	if assignments.is_empty() {
+		return Err(Error::validation(
+			"At least one field of the elements must be updated".to_owned(),
+			None,
+		));
+	}
+	let array = field_path(array)?;
+	let mut sets = Vec::with_capacity(assignments.len());
+	for (i, (field, operator, value)) in assignments.into_iter().enumerate() {
+		let field = field_path(&field)?;
+		variables.insert(format!("_element_{i}"), value);
+		sets.push(format!("{array}[WHERE {condition}].{field} {operator} $_element_{i}"));
+	}
+	Ok(format!("UPDATE {what} SET {}", sets.join(", ")))
+}
+
+macro_rules! into_future {
+	($method:ident) => {
+		fn into_future(self) -> Self::IntoFuture {
+			let UpdateElements {
+				txn,
+				client,
+				resource,
+				array,
+				condition,
+				assignments,
+				mut variables,
+				idempotency_key,
+				..
+			} = self;
+			Box::pin(async move {
+				let router = client.inner.router.extract()?;
+				let what = resource?.for_sql_query(&mut variables)?;
+				let query = query(what, &array, &condition, assignments, &mut variables)?;
+				let cmd = Command::Query {
+					txn,
+					query: Cow::Owned(query),
+					variables,
+				}
+				.with_idempotency_key(idempotency_key)?;
+				router.$method(client.session_id, cmd).await
+			})
+		}
+	};
+}
+
+impl<'r, Client> IntoFuture for UpdateElements<'r, Client, Value>
+where
+	Client: Connection,
+{
+	type Output = Result<Value>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	into_future! {execute_value}
+}
+
+impl<'r, Client, R> IntoFuture for UpdateElements<'r, Client, Option<R>>
+where
+	Client: Connection,
+	R: SurrealValue,
+{
+	type Output = Result<Option<R>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	into_future! {execute_opt}
+}
+
+impl<'r, Client, R> IntoFuture for UpdateElements<'r, Client, Vec<R>>
+where
+	Client: Connection,
+	R: SurrealValue,
+{
+	type Output = Result<Vec<R>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	into_future! {execute_vec}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn builds_the_update_of_the_matched_elements() {
+		let mut variables = Variables::new();
+		let assignments = vec![
+			("qty".to_owned(), "+=", 1i64.into_value()),
+			("price.amount".to_owned(), "=", 10i64.into_value()),
+		];
+		assert_eq!(
+			query("$_record_id", "items", "sku = $sku", assignments, &mut variables).unwrap(),
+			"UPDATE $_record_id SET `items`[WHERE sku = $sku].`qty` += $_element_0, \
+			 `items`[WHERE sku = $sku].`price`.`amount` = $_element_1"
+		);
+		assert!(query("$_table", "items", "true", Vec::new(), &mut variables).is_err());
+		assert!(field_path("items..qty").is_err());
+	}
+}
@@ -7,10 +7,10 @@ use uuid::Uuid;
 use super::transaction::WithTransaction;
 use super::validate_data;
 use crate::conn::Command;
-use crate::method::{BoxFuture, Content, Merge, OnceLockExt, Patch};
+use crate::method::{BoxFuture, Content, Merge, OnceLockExt, Patch, UpsertOutcome, Upserting};
 use crate::opt::{PatchOps, Resource};
-use crate::types::{RecordIdKeyRange, SurrealValue, Value, Variables};
-use crate::{Connection, Result, Surreal};
+use crate::types::{RecordIdKeyRange, SerializationError, SurrealValue, Value, Variables};
+use crate::{Connection, Error, Result, Surreal};
 
 /// Returned by [`Surreal::upsert`](crate::Surreal::upsert). Uses same content/merge/patch chaining
 /// as [`Update`](crate::method::Update).
@@ -111,6 +111,49 @@ where
 	into_future! {execute_vec}
 }
 
+impl<'r, C, R> Upsert<'r, C, Option<R>>
+where
+	C: Connection,
+{
+	/// Also reports whether the record was created or updated, so the caller
+	/// does not need to select the record beforehand
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::method::UpsertOutcome;
+	///
+	/// # #[derive(surrealdb::types::SurrealValue)]
+	/// # struct Person;
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let (outcome, person) =
+	///     db.upsert::<Option<Person>>(("person", "tobie")).with_outcome().await?;
+	/// if outcome == UpsertOutcome::Created {
+	///     println!("Added a new person");
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_outcome(self) -> WithOutcome<'r, C, R> {
+		let command = self.resource.and_then(|what| {
+			let mut variables = Variables::new();
+			let what = what.for_sql_query(&mut variables)?;
+			Ok(Command::Query {
+				txn: self.txn,
+				query: Cow::Owned(format!("UPSERT {what}")),
+				variables,
+			})
+		});
+		WithOutcome {
+			client: self.client,
+			command,
+			response_type: PhantomData,
+		}
+	}
+}
+
 impl<C> Upsert<'_, C, Value>
 where
 	C: Connection,
@@ -139,7 +182,7 @@ where
 	R: SurrealValue,
 {
 	/// Replaces the current document / record data with the specified data
-	pub fn content<D>(self, data: D) -> Content<'r, C, R>
+	pub fn content<D>(self, data: D) -> Content<'r, C, R, Upserting>
 	where
 		D: SurrealValue,
 	{
@@ -188,6 +231,7 @@ where
 			resource: self.resource,
 			content: data,
 			upsert: true,
+			idempotency_key: None,
 			response_type: PhantomData,
 		}
 	}
@@ -201,7 +245,74 @@ where
 			client: self.client,
 			resource: self.resource,
 			upsert: true,
+			idempotency_key: None,
 			response_type: PhantomData,
 		}
 	}
 }
+
+/// Returned by [`Upsert::with_outcome`] and [`Content::with_outcome`], resolving to the
+/// [`UpsertOutcome`] along with the record.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WithOutcome<'r, C: Connection, R> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) command: Result<Command>,
+	pub(super) response_type: PhantomData<R>,
+}
+
+impl<C, R> WithOutcome<'_, C, R>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> WithOutcome<'static, C, R> {
+		WithOutcome {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client, R> IntoFuture for WithOutcome<'r, Client, R>
+where
+	Client: Connection,
+	R: SurrealValue,
+{
+	type Output = Result<(UpsertOutcome, Option<R>)>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		let WithOutcome {
+			client,
+			command,
+			..
+		} = self;
+		Box::pin(async move {
+			let router = client.inner.router.extract()?;
+			let mut results = router.execute_query(client.session_id, None, command?).await?;
+			let Some(result) = results.pop() else {
+				return Err(Error::internal("The database returned no results".to_owned()));
+			};
+			// The statement stats count the records which were created
+			let outcome = match result.stats.records_created {
+				0 => UpsertOutcome::Updated,
+				_ => UpsertOutcome::Created,
+			};
+			let record = match result.result? {
+				Value::None | Value::Null => None,
+				Value::Array(array) if array.is_empty() => None,
+				Value::Array(array) if array.len() == 1 => {
+					array.into_iter().next().map(R::from_value).transpose().map_err(|e| {
+						Error::serialization(e.to_string(), SerializationError::Deserialization)
+					})?
+				}
+				value => Some(R::from_value(value).map_err(|e| {
+					Error::serialization(e.to_string(), SerializationError::Deserialization)
+				})?),
+			};
+			Ok((outcome, record))
+		})
+	}
+}
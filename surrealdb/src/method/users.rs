@@ -0,0 +1,385 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+
+use crate::method::BoxFuture;
+use crate::method::select::escape_field;
+use crate::types::{Duration, Kind, SurrealValue, ToSql, Value, kind};
+use crate::{Connection, Error, Result, Surreal};
+
+/// Returned by [`Surreal::users`](crate::Surreal::users) to manage the system
+/// users defined at a level
+///
+/// Users are managed at the selected database unless another level is chosen
+/// with [`on`](Self::on).
+#[derive(Debug)]
+pub struct Users<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) level: UserLevel,
+}
+
+impl<'r, C> Users<'r, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Users<'static, C> {
+		Users {
+			client: Cow::Owned(self.client.into_owned()),
+			level: self.level,
+		}
+	}
+
+	/// Manages the users defined at the given level
+	pub fn on(self, level: UserLevel) -> Self {
+		Users {
+			level,
+			..self
+		}
+	}
+
+	/// Defines a new user, which fails if the user already exists
+	///
+	/// The user is given the viewer role unless other roles are set.
+	pub fn create(self, name: impl Into<String>) -> CreateUser<'r, C> {
+		CreateUser {
+			client: self.client,
+			level: self.level,
+			name: name.into(),
+			password: None,
+			roles: Vec::new(),
+			comment: None,
+		}
+	}
+
+	/// Lists the users defined at the level
+	pub fn list(self) -> UserRequest<'r, C, Vec<UserInfo>> {
+		let query = format!("(INFO FOR {} STRUCTURE).users", self.level.info_target());
+		UserRequest::new(self.client, Ok(query))
+	}
+
+	/// Returns the definition of a user
+	pub fn info(self, name: impl Into<String>) -> UserRequest<'r, C, UserInfo> {
+		let query = validate_name(&name.into()).map(|name| self.level.info(&name));
+		UserRequest::new(self.client, query)
+	}
+
+	/// Replaces the roles of a user
+	pub fn update_roles(
+		self,
+		name: impl Into<String>,
+		roles: impl IntoIterator<Item = Role>,
+	) -> UserRequest<'r, C, UserInfo> {
+		let query = validate_name(&name.into()).and_then(|name| {
+			let roles = validate_roles(roles.into_iter().collect())?;
+			Ok(format!(
+				"ALTER USER {name} ON {} ROLES {roles}; {}",
+				self.level.base(),
+				self.level.info(&name)
+			))
+		});
+		UserRequest::new(self.client, query)
+	}
+
+	/// Removes a user, which fails if the user does not exist
+	pub fn delete(self, name: impl Into<String>) -> UserRequest<'r, C, ()> {
+		let query = validate_name(&name.into())
+			.map(|name| format!("REMOVE USER {name} ON {}", self.level.base()));
+		UserRequest::new(self.client, query)
+	}
+}
+
+/// Returned by [`Users::create`], defines a user and yields its definition
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CreateUser<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) level: UserLevel,
+	pub(super) name: String,
+	pub(super) password: Option<String>,
+	pub(super) roles: Vec<Role>,
+	pub(super) comment: Option<String>,
+}
+
+impl<C> CreateUser<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> CreateUser<'static, C> {
+		CreateUser {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Sets the password of the user, which is required
+	pub fn password(mut self, password: impl Into<String>) -> Self {
+		self.password = Some(password.into());
+		self
+	}
+
+	/// Adds a role to the user
+	pub fn role(mut self, role: Role) -> Self {
+		self.roles.push(role);
+		self
+	}
+
+	/// Adds several roles to the user
+	pub fn roles(mut self, roles: impl IntoIterator<Item = Role>) -> Self {
+		self.roles.extend(roles);
+		self
+	}
+
+	/// Sets a comment describing the user
+	pub fn comment(mut self, comment: impl Into<String>) -> Self {
+		self.comment = Some(comment.into());
+		self
+	}
+
+	/// Builds the statements which define the user and return its definition
+	fn query(&self) -> Result<String> {
+		let name = validate_name(&self.name)?;
+		let password = match self.password.as_deref() {
+			Some(password) if !password.is_empty() => password,
+			_ => {
+				return Err(Error::validation(
+					format!("A password is required to create the user '{}'", self.name),
+					None,
+				));
+			}
+		};
+		let mut query = format!(
+			"DEFINE USER {name} ON {} PASSWORD {}",
+			self.level.base(),
+			Value::String(password.to_owned()).to_sql()
+		);
+		if !self.roles.is_empty() {
+			let roles = validate_roles(self.roles.clone())?;
+			query.push_str(&format!(" ROLES {roles}"));
+		}
+		if let Some(comment) = &self.comment {
+			query.push_str(&format!(" COMMENT {}", Value::String(comment.clone()).to_sql()));
+		}
+		query.push_str(&format!("; {}", self.level.info(&name)));
+		Ok(query)
+	}
+}
+
+impl<'r, Client> IntoFuture for CreateUser<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<UserInfo>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		let query = self.query();
+		UserRequest::new(self.client, query).into_future()
+	}
+}
+
+/// Returned by the methods of [`Users`], runs a user management statement and
+/// yields its typed result
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct UserRequest<'r, C: Connection, R> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) query: Result<String>,
+	pub(super) response_type: PhantomData<R>,
+}
+
+impl<'r, C, R> UserRequest<'r, C, R>
+where
+	C: Connection,
+{
+	fn new(client: Cow<'r, Surreal<C>>, query: Result<String>) -> Self {
+		UserRequest {
+			client,
+			query,
+			response_type: PhantomData,
+		}
+	}
+
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> UserRequest<'static, C, R> {
+		UserRequest {
+			client: Cow::Owned(self.client.into_owned()),
+			query: self.query,
+			response_type: PhantomData,
+		}
+	}
+}
+
+impl<'r, Client, R> IntoFuture for UserRequest<'r, Client, R>
+where
+	Client: Connection,
+	R: SurrealValue + Send + 'r,
+{
+	type Output = Result<R>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let mut response = self.client.query(self.query?).await?.check()?;
+			// The result of the request is returned by the last statement
+			let last = response.num_statements().saturating_sub(1);
+			let value: Value = response.take(last)?;
+			R::from_value(value)
+		})
+	}
+}
+
+/// The level at which a system user is defined
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UserLevel {
+	/// A root user, with access to every namespace
+	Root,
+	/// A user of the selected namespace
+	Namespace,
+	/// A user of the selected database
+	#[default]
+	Database,
+}
+
+impl UserLevel {
+	/// Returns the base of the statements which manage users at this level
+	fn base(self) -> &'static str {
+		match self {
+			Self::Root => "ROOT",
+			Self::Namespace => "NAMESPACE",
+			Self::Database => "DATABASE",
+		}
+	}
+
+	/// Returns the target of the `INFO` statement which lists the users
+	fn info_target(self) -> &'static str {
+		match self {
+			Self::Root => "ROOT",
+			Self::Namespace => "NS",
+			Self::Database => "DB",
+		}
+	}
+
+	/// Returns the statement which shows the definition of a user
+	fn info(self, name: &str) -> String {
+		format!("INFO FOR USER {name} ON {} STRUCTURE", self.base())
+	}
+}
+
+/// A role which grants a system user its permissions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Role {
+	/// Can view resources
+	Viewer,
+	/// Can view and edit resources, but not users or access methods
+	Editor,
+	/// Can view and edit every resource, including users and access methods
+	Owner,
+}
+
+impl Role {
+	fn as_str(self) -> &'static str {
+		match self {
+			Self::Viewer => "Viewer",
+			Self::Editor => "Editor",
+			Self::Owner => "Owner",
+		}
+	}
+}
+
+impl SurrealValue for Role {
+	fn kind_of() -> Kind {
+		kind!(string)
+	}
+
+	fn into_value(self) -> Value {
+		Value::String(self.as_str().to_owned())
+	}
+
+	fn from_value(value: Value) -> Result<Self> {
+		let role = String::from_value(value)?;
+		// Roles are matched regardless of case, as they are when defined
+		match role.to_lowercase().as_str() {
+			"viewer" => Ok(Self::Viewer),
+			"editor" => Ok(Self::Editor),
+			"owner" => Ok(Self::Owner),
+			_ => Err(Error::internal(format!("Unknown role '{role}'"))),
+		}
+	}
+}
+
+/// The definition of a system user
+///
+/// The password hash of the user is never included.
+#[derive(Clone, Debug, PartialEq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[non_exhaustive]
+pub struct UserInfo {
+	/// The name of the user
+	pub name: String,
+	/// The roles of the user
+	pub roles: Vec<Role>,
+	/// How long the tokens and sessions of the user last
+	pub duration: UserDuration,
+	/// The comment describing the user, if it has one
+	pub comment: Option<String>,
+}
+
+/// How long the tokens and sessions of a system user last
+#[derive(Clone, Debug, Default, PartialEq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[non_exhaustive]
+pub struct UserDuration {
+	/// How long a token issued to the user lasts, if it expires
+	pub token: Option<Duration>,
+	/// How long a session of the user lasts, if it expires
+	pub session: Option<Duration>,
+}
+
+/// Checks and escapes the name of a user
+fn validate_name(name: &str) -> Result<String> {
+	if name.is_empty() {
+		return Err(Error::validation("The name of a user can not be empty".to_owned(), None));
+	}
+	Ok(escape_field(name))
+}
+
+/// Checks and joins the roles of a user
+fn validate_roles(mut roles: Vec<Role>) -> Result<String> {
+	if roles.is_empty() {
+		return Err(Error::validation("A user must have at least one role".to_owned(), None));
+	}
+	let mut seen = Vec::with_capacity(roles.len());
+	roles.retain(|role| {
+		let new = !seen.contains(role);
+		seen.push(*role);
+		new
+	});
+	Ok(roles.into_iter().map(Role::as_str).collect::<Vec<_>>().join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn builds_the_user_statements() {
+		assert_eq!(
+			UserLevel::Namespace.info("`tobie`"),
+			"INFO FOR USER `tobie` ON NAMESPACE STRUCTURE"
+		);
+		assert_eq!(
+			validate_roles(vec![Role::Owner, Role::Viewer, Role::Owner]).unwrap(),
+			"Owner, Viewer"
+		);
+		assert!(validate_roles(Vec::new()).is_err());
+		assert!(validate_name("").is_err());
+		assert_eq!(Role::from_value(Value::String("editor".to_owned())).unwrap(), Role::Editor);
+	}
+}
@@ -3,6 +3,7 @@ use std::future::IntoFuture;
 
 use crate::conn::Command;
 use crate::method::{BoxFuture, OnceLockExt};
+use crate::types::Value;
 use crate::{Connection, Error, Result, Surreal};
 
 /// Returned by [`Surreal::version`](crate::Surreal::version), yields the server version string.
@@ -36,11 +37,16 @@ where
 		Box::pin(async move {
 			let router = self.client.inner.router.extract()?;
 			let version = router.execute_value(self.client.session_id, Command::Version).await?;
-			let version = version.into_string().map_err(|e| Error::internal(e.to_string()))?;
-			let semantic = version.trim_start_matches("surrealdb-");
-			semantic
-				.parse()
-				.map_err(|_| Error::internal(format!("Invalid semantic version: \"{version}\"")))
+			parse_version(version)
 		})
 	}
 }
+
+/// Parses the version reported by a server
+pub(crate) fn parse_version(version: Value) -> Result<semver::Version> {
+	let version = version.into_string().map_err(|e| Error::internal(e.to_string()))?;
+	let semantic = version.trim_start_matches("surrealdb-");
+	semantic
+		.parse()
+		.map_err(|_| Error::internal(format!("Invalid semantic version: \"{version}\"")))
+}
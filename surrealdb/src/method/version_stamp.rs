@@ -0,0 +1,46 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use crate::method::BoxFuture;
+use crate::{Connection, Error, Result, Surreal};
+
+/// The statement used to read the current versionstamp of the database
+pub(crate) const VERSION_STAMP_QUERY: &str = "RETURN db::versionstamp()";
+
+/// Returned by [`Surreal::version_stamp`](crate::Surreal::version_stamp), yields the current
+/// versionstamp of the selected database.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct VersionStamp<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> VersionStamp<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> VersionStamp<'static, C> {
+		VersionStamp {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for VersionStamp<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<u64>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let mut response = self.client.query(VERSION_STAMP_QUERY).await?;
+			response.take::<Option<u64>>(0)?.ok_or_else(|| {
+				Error::internal("The database did not return a versionstamp".to_owned())
+			})
+		})
+	}
+}
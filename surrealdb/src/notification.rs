@@ -1,3 +1,6 @@
+use std::fmt;
+
+use crate::Error;
 use crate::types::{Action, Uuid};
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -7,3 +10,53 @@ pub struct Notification<R> {
 	pub action: Action,
 	pub data: R,
 }
+
+/// An item of a [`Subscription`](crate::method::Subscription) which is not a
+/// notification of a change
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum NotificationError {
+	/// The live query was killed on the server, for instance with `KILL` or by
+	/// the removal of its table
+	///
+	/// This is the last item of the subscription. The reason is `None` when the
+	/// server did not give one.
+	Killed {
+		reason: Option<String>,
+	},
+	/// The `WHERE` clause or the projection of the live query failed to
+	/// evaluate for a change
+	///
+	/// The live query keeps running, and later changes are still notified.
+	Query {
+		message: String,
+	},
+	/// A notification could not be received or deserialized
+	Error(Error),
+}
+
+impl fmt::Display for NotificationError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Killed {
+				reason: Some(reason),
+			} => write!(f, "The live query was killed: {reason}"),
+			Self::Killed {
+				reason: None,
+			} => write!(f, "The live query was killed"),
+			Self::Query {
+				message,
+			} => write!(f, "The live query failed: {message}"),
+			Self::Error(error) => fmt::Display::fmt(error, f),
+		}
+	}
+}
+
+impl std::error::Error for NotificationError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Error(error) => Some(error),
+			_ => None,
+		}
+	}
+}
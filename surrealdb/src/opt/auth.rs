@@ -4,7 +4,7 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-use crate::types::{Kind, Object, SurrealValue, Value, kind};
+use crate::types::{Kind, Object, RecordId, SurrealValue, Value, kind};
 
 /// A signup action
 #[derive(Debug)]
@@ -152,6 +152,47 @@ impl<P: SurrealValue> SurrealValue for Record<P> {
 
 impl<T, P> Credentials<T> for Record<P> where P: SurrealValue {}
 
+/// A user which can be impersonated with
+/// [`Surreal::impersonate`](crate::Surreal::impersonate)
+pub trait Subject: SurrealValue {}
+
+/// A system user to impersonate, defined on the root, a namespace, or a
+/// database
+#[derive(Debug, Clone, SurrealValue)]
+#[surreal(crate = "crate::types")]
+pub struct SystemUser {
+	/// The namespace of a namespace or database user
+	#[surreal(rename = "ns")]
+	pub namespace: Option<String>,
+	/// The database of a database user
+	#[surreal(rename = "db")]
+	pub database: Option<String>,
+	/// The username of the user
+	#[surreal(rename = "user")]
+	pub username: String,
+}
+
+impl Subject for SystemUser {}
+
+/// A record user to impersonate
+#[derive(Debug, Clone, SurrealValue)]
+#[surreal(crate = "crate::types")]
+pub struct RecordUser {
+	/// The namespace of the user
+	#[surreal(rename = "ns")]
+	pub namespace: String,
+	/// The database of the user
+	#[surreal(rename = "db")]
+	pub database: String,
+	/// The record access method of the user
+	#[surreal(rename = "ac")]
+	pub access: String,
+	/// The record of the user
+	pub id: RecordId,
+}
+
+impl Subject for RecordUser {}
+
 /// A token containing both access and optional refresh token for authentication.
 ///
 /// This struct represents the complete authentication token response from
@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// The policy of the client-side cache of a remote engine
+///
+/// The cache holds the results of selecting a single record by its id, such
+/// as `db.select(("config", "limits"))`, so that hot reference data is not
+/// fetched from the server on every read. Selects which run in a transaction
+/// are never cached.
+///
+/// An entry is dropped once its time to live has passed, or as soon as the
+/// record is changed: by a write sent through the same client, or through a
+/// live query on the table of the record, which the cache starts on the
+/// WebSocket engine. The HTTP engine does not support live queries, so changes
+/// made by other clients are only picked up when the entries expire.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use surrealdb::opt::{CachePolicy, Config};
+///
+/// let config = Config::new().cache(CachePolicy::ttl(Duration::from_secs(5)).tables(["config"]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachePolicy {
+	pub(crate) ttl: Duration,
+	pub(crate) tables: Option<HashSet<String>>,
+	pub(crate) max_entries: usize,
+	pub(crate) live_invalidation: bool,
+}
+
+impl CachePolicy {
+	/// Caches records for at most the given time to live
+	pub fn ttl(ttl: Duration) -> Self {
+		Self {
+			ttl,
+			tables: None,
+			max_entries: 1024,
+			live_invalidation: true,
+		}
+	}
+
+	/// Only caches the records of the given tables
+	///
+	/// The records of every table are cached unless this is set.
+	pub fn tables<I, T>(mut self, tables: I) -> Self
+	where
+		I: IntoIterator<Item = T>,
+		T: Into<String>,
+	{
+		self.tables = Some(tables.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Sets the maximum number of records held by the cache (default: 1024)
+	///
+	/// The entries closest to expiring are dropped to make room for new ones.
+	pub fn max_entries(mut self, max_entries: usize) -> Self {
+		self.max_entries = max_entries;
+		self
+	}
+
+	/// Sets whether entries are dropped when a live query reports a change to
+	/// their record (default: `true`)
+	pub fn live_invalidation(mut self, enabled: bool) -> Self {
+		self.live_invalidation = enabled;
+		self
+	}
+}
@@ -46,6 +46,10 @@ pub enum ExperimentalFeature {
 	Surrealism,
 	/// Enable the GQL query language.
 	Gql,
+	/// Enable the impersonation of users by administrators.
+	Impersonation,
+	/// Enable the type adapters registered with the datastore.
+	TypeAdapters,
 }
 
 /// Not public API
@@ -56,6 +60,8 @@ impl From<&ExperimentalFeature> for ExperimentalTarget {
 			ExperimentalFeature::Files => ExperimentalTarget::Files,
 			ExperimentalFeature::Surrealism => ExperimentalTarget::Surrealism,
 			ExperimentalFeature::Gql => ExperimentalTarget::Gql,
+			ExperimentalFeature::Impersonation => ExperimentalTarget::Impersonation,
+			ExperimentalFeature::TypeAdapters => ExperimentalTarget::TypeAdapters,
 		}
 	}
 }
@@ -442,6 +448,46 @@ impl Capabilities {
 		Ok(self)
 	}
 
+	/// Add a net target to the list of targets which can be granted to
+	/// functions
+	///
+	/// These targets are not reachable from queries, but can be granted to
+	/// individual functions with `DEFINE FUNCTION ... CAPABILITIES { net: [...] }`.
+	pub fn allow_function_net_target<S: AsRef<str>>(
+		&mut self,
+		func: S,
+	) -> Result<&mut Self, ParseNetTargetError> {
+		self.allow_function_net_target_str(func.as_ref())
+	}
+
+	/// Add a net target to the list of targets which can be granted to
+	/// functions
+	///
+	/// These targets are not reachable from queries, but can be granted to
+	/// individual functions with `DEFINE FUNCTION ... CAPABILITIES { net: [...] }`.
+	pub fn with_function_net_target_allowed<S: AsRef<str>>(
+		mut self,
+		func: S,
+	) -> Result<Self, ParseNetTargetError> {
+		self.allow_function_net_target(func)?;
+		Ok(self)
+	}
+
+	fn allow_function_net_target_str(&mut self, s: &str) -> Result<&mut Self, ParseNetTargetError> {
+		let target = s.parse()?;
+		match self.cap.allowed_function_network_targets_mut() {
+			Targets::None | Targets::All => {
+				let mut set = HashSet::new();
+				set.insert(target);
+				*self.cap.allowed_function_network_targets_mut() = Targets::Some(set);
+			}
+			Targets::Some(x) => {
+				x.insert(target);
+			}
+		}
+		Ok(self)
+	}
+
 	/// Add a net target to the deny lists
 	///
 	/// Adding a net target to the deny list overwrites previously set deny-all
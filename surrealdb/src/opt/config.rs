@@ -3,10 +3,26 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use surrealdb_core::dbs::Capabilities as CoreCapabilities;
+pub use surrealdb_core::dbs::Guardrails;
 use surrealdb_core::iam::Level;
 
+use crate::opt::CachePolicy;
 use crate::opt::capabilities::Capabilities;
-use crate::opt::websocket::WebsocketConfig;
+use crate::opt::websocket::{LoadBalancing, WebsocketConfig};
+
+/// How a client handles a server it is not compatible with, as
+/// reported by [`Surreal::compatibility`](crate::Surreal::compatibility)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CompatibilityCheck {
+	/// Refuse to connect to the server
+	#[default]
+	Enforce,
+	/// Log a warning, and connect to the server anyway
+	Warn,
+	/// Connect to the server without checking its compatibility
+	Skip,
+}
 
 /// Configuration for server connection, including: strictness, notifications,
 /// query_timeout, transaction_timeout
@@ -23,13 +39,28 @@ pub struct Config {
 	pub(crate) username: String,
 	pub(crate) password: String,
 	pub(crate) capabilities: CoreCapabilities,
+	pub(crate) guardrails: Guardrails,
 	pub(crate) websocket: WebsocketConfig,
+	pub(crate) load_balancing: LoadBalancing,
+	pub(crate) cache: Option<CachePolicy>,
 	#[cfg(storage)]
 	pub(crate) temporary_directory: Option<PathBuf>,
+	#[cfg(storage)]
+	pub(crate) snapshot: Option<String>,
 	pub(crate) node_membership_refresh_interval: Option<Duration>,
 	pub(crate) node_membership_check_interval: Option<Duration>,
 	pub(crate) node_membership_cleanup_interval: Option<Duration>,
 	pub(crate) changefeed_gc_interval: Option<Duration>,
+	pub(crate) max_concurrent_queries: Option<usize>,
+	pub(crate) query_queue_timeout: Option<Duration>,
+	pub(crate) blocking_threads: Option<usize>,
+	pub(crate) compute_threads: Option<usize>,
+	pub(crate) core_pinning: Option<bool>,
+	pub(crate) http_compression: Option<bool>,
+	pub(crate) keepalive: Option<Duration>,
+	pub(crate) warm_connections: usize,
+	pub(crate) notifications_broadcast_capacity: Option<usize>,
+	pub(crate) compatibility_check: CompatibilityCheck,
 }
 
 impl Config {
@@ -50,6 +81,28 @@ impl Config {
 		self
 	}
 
+	/// Limit the number of requests a connection runs at the same time
+	///
+	/// Requests beyond the limit wait in a queue until a running request
+	/// finishes, rather than being sent to the database straight away. This
+	/// applies to every request made through the connection, including
+	/// queries, CRUD methods and authentication. By default the number of
+	/// requests is not limited.
+	pub fn max_concurrent_queries(mut self, limit: impl Into<Option<usize>>) -> Self {
+		self.max_concurrent_queries = limit.into();
+		self
+	}
+
+	/// Set how long a request waits for a free slot when the connection is at
+	/// its [`max_concurrent_queries`](Self::max_concurrent_queries) limit
+	///
+	/// A request which waits longer fails without being sent to the database.
+	/// By default requests wait until a slot becomes free.
+	pub fn query_queue_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+		self.query_queue_timeout = timeout.into();
+		self
+	}
+
 	/// Set the query timeout of the config
 	pub fn query_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
 		self.query_timeout = timeout.into();
@@ -101,6 +154,28 @@ impl Config {
 		self
 	}
 
+	/// Set the guardrails for the database
+	///
+	/// Guardrails refuse entire classes of destructive statements, such as an
+	/// `UPDATE` or a `DELETE` on a whole table without a `WHERE` clause, or a
+	/// `REMOVE TABLE`, regardless of the permissions of the user running them.
+	/// A refused statement fails with a "not allowed" error. Guardrails only
+	/// apply to the embedded engines.
+	///
+	/// ```
+	/// use surrealdb::opt::{Config, Guardrails};
+	///
+	/// let config = Config::new().guardrails(Guardrails {
+	///     forbid_unfiltered_update_delete: true,
+	///     forbid_remove_table: true,
+	///     ..Default::default()
+	/// });
+	/// ```
+	pub fn guardrails(mut self, guardrails: Guardrails) -> Self {
+		self.guardrails = guardrails;
+		self
+	}
+
 	/// Set the WebSocket config
 	pub fn websocket(mut self, websocket: WebsocketConfig) -> crate::Result<Self> {
 		if websocket.max_write_buffer_size <= websocket.write_buffer_size {
@@ -110,12 +185,50 @@ impl Config {
 		Ok(self)
 	}
 
+	/// Set how requests are spread across the servers, when connecting to
+	/// several WebSocket endpoints
+	pub fn load_balancing(mut self, strategy: LoadBalancing) -> Self {
+		self.load_balancing = strategy;
+		self
+	}
+
+	/// Cache the records selected by their id on the client, when connected
+	/// to a remote engine
+	///
+	/// See [`CachePolicy`] for what is cached and when entries are dropped.
+	/// The cache is not used by the embedded engines.
+	pub fn cache(mut self, policy: CachePolicy) -> Self {
+		self.cache = Some(policy);
+		self
+	}
+
 	#[cfg(storage)]
 	pub fn temporary_directory(mut self, path: Option<PathBuf>) -> Self {
 		self.temporary_directory = path;
 		self
 	}
 
+	/// Create a config which opens a named snapshot of the datastore
+	///
+	/// The snapshot must have been created with
+	/// [`Surreal::snapshot`](crate::Surreal::snapshot). It is opened
+	/// read-only, so queries which write to the datastore fail, and the
+	/// background maintenance tasks are not run. A copy of the snapshot is
+	/// opened from the temporary directory, so the snapshot itself is never
+	/// modified. Only the embedded RocksDB and SurrealKV engines support
+	/// snapshots.
+	#[cfg(storage)]
+	pub fn from_snapshot(name: impl Into<String>) -> Self {
+		Self::new().snapshot(name)
+	}
+
+	/// Open a named snapshot of the datastore, see [`Config::from_snapshot`]
+	#[cfg(storage)]
+	pub fn snapshot(mut self, name: impl Into<String>) -> Self {
+		self.snapshot = Some(name.into());
+		self
+	}
+
 	/// Set the interval at which the database should run node maintenance tasks
 	pub fn node_membership_refresh_interval(
 		mut self,
@@ -145,4 +258,109 @@ impl Config {
 		self.changefeed_gc_interval = interval.into().filter(|x| !x.is_zero());
 		self
 	}
+
+	/// Set the number of threads which run blocking storage IO for the
+	/// embedded engines
+	///
+	/// The threadpool is shared by every embedded datastore in the process,
+	/// so only the configuration of the first datastore which is opened takes
+	/// effect. Values below 4 are ignored. By default the pool has one thread
+	/// per core, with at least 16 threads.
+	pub fn blocking_threads(mut self, threads: impl Into<Option<usize>>) -> Self {
+		self.blocking_threads = threads.into();
+		self
+	}
+
+	/// Set the number of threads which process queries for the embedded
+	/// engines
+	///
+	/// When set, queries and background tasks run on a dedicated runtime with
+	/// this many worker threads, isolating them from the runtime of the host
+	/// application. By default they run on the runtime which opened the
+	/// connection.
+	pub fn compute_threads(mut self, threads: impl Into<Option<usize>>) -> Self {
+		self.compute_threads = threads.into().filter(|x| *x > 0);
+		self
+	}
+
+	/// Set whether the blocking storage IO threads of the embedded engines
+	/// are pinned to dedicated cores
+	///
+	/// When enabled, one pinned thread is created per core, and the
+	/// [`blocking_threads`](Self::blocking_threads) setting is ignored. By
+	/// default threads are only pinned when there is one thread per core on a
+	/// host with at least 16 cores.
+	pub fn core_pinning(mut self, enabled: impl Into<Option<bool>>) -> Self {
+		self.core_pinning = enabled.into();
+		self
+	}
+
+	/// Set whether the HTTP engine asks the server to compress its responses
+	///
+	/// When enabled, responses are sent compressed with zstd or gzip, and are
+	/// decompressed as they are received. This saves bandwidth on large result
+	/// sets, at the cost of some CPU time on both ends. Small responses are
+	/// sent uncompressed by the server. The savings can be read with
+	/// [`Surreal::compression_stats`](crate::Surreal::compression_stats).
+	///
+	/// Compression is enabled by default. In the browser, compression is
+	/// negotiated by the browser itself, so this setting is ignored.
+	pub fn http_compression(mut self, enabled: bool) -> Self {
+		self.http_compression = Some(enabled);
+		self
+	}
+
+	/// Set the interval at which the remote engines ping the server, keeping
+	/// idle connections open and measuring the round-trip time
+	///
+	/// The WebSocket engine sends WebSocket ping frames instead of its default
+	/// health requests. The HTTP engine enables TCP keepalive, keeps idle
+	/// connections in its pool, and requests the health endpoint of the
+	/// server. The latest round-trip time can be read with
+	/// [`Surreal::latency`](crate::Surreal::latency). In the browser, pings
+	/// are sent as health requests and the round-trip time is not measured.
+	pub fn keepalive(mut self, interval: impl Into<Option<Duration>>) -> Self {
+		self.keepalive = interval.into().filter(|x| !x.is_zero());
+		self
+	}
+
+	/// Set the number of connections the HTTP engine opens to the server
+	/// before the client is returned
+	///
+	/// The connections are kept in the pool of the client, so that a burst of
+	/// concurrent requests does not wait for new connections to be established.
+	/// Idle connections are closed after 90 seconds, unless a
+	/// [`keepalive`](Self::keepalive) is set. The WebSocket engine sends every
+	/// request over a single connection per server, and the browser manages
+	/// its own connections, so this setting is ignored by them.
+	pub fn warm_connections(mut self, connections: usize) -> Self {
+		self.warm_connections = connections;
+		self
+	}
+
+	/// Set the number of notifications kept for the slowest receiver returned
+	/// by [`Surreal::notifications_broadcast`](crate::Surreal::notifications_broadcast)
+	///
+	/// A receiver which falls further behind skips the oldest notifications,
+	/// and is told how many it missed with
+	/// [`RecvError::Lagged`](tokio::sync::broadcast::error::RecvError::Lagged).
+	/// Defaults to 1024. Only the local engines broadcast notifications.
+	pub fn notifications_broadcast_capacity(mut self, capacity: usize) -> Self {
+		self.notifications_broadcast_capacity = Some(capacity);
+		self
+	}
+
+	/// Set how the engines handle a server which this client is not
+	/// compatible with
+	///
+	/// When connecting, the remote engines compare the version of the client
+	/// with the version of the server, while the embedded engines compare the
+	/// revision of the storage format of the client with the one stored in the
+	/// datastore. By default, the connection fails when they are not
+	/// compatible, rather than failing later with errors which are harder to
+	/// diagnose.
+	pub fn compatibility_check(mut self, check: CompatibilityCheck) -> Self {
+		self.compatibility_check = check;
+		self
+	}
 }
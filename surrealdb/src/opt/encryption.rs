@@ -0,0 +1,429 @@
+use std::fmt;
+use std::num::NonZeroU32;
+
+use async_channel::Receiver;
+use futures::{Stream, StreamExt};
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::{Error, Result};
+
+/// Identifies an encrypted export, followed by the version of the format
+const MAGIC: &[u8; 14] = b"SURREALDB-ENC\x01";
+
+/// The key was given directly
+const KDF_RAW: u8 = 0;
+
+/// The key was derived from a passphrase with PBKDF2-HMAC-SHA256
+const KDF_PBKDF2: u8 = 1;
+
+/// The number of PBKDF2 iterations used when encrypting with a passphrase
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// The largest number of PBKDF2 iterations accepted when decrypting, so that
+/// a crafted file can not make the import spin indefinitely
+const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+
+const SALT_LEN: usize = 16;
+
+const TAG_LEN: usize = 16;
+
+/// The random part of each nonce. The rest of the nonce is the number of the
+/// segment, and a byte marking the header or the last segment.
+const PREFIX_LEN: usize = NONCE_LEN - 5;
+
+/// The length of the header, before its authentication tag
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4 + SALT_LEN + PREFIX_LEN;
+
+/// The largest segment accepted when decrypting
+const MAX_SEGMENT_LEN: usize = 64 * 1024 * 1024;
+
+const FLAG_SEGMENT: u8 = 0;
+const FLAG_LAST: u8 = 1;
+const FLAG_HEADER: u8 = 2;
+
+/// The key used to encrypt an export, or to decrypt it on import
+///
+/// Exports are encrypted with AES-256-GCM. A passphrase is stretched into a
+/// key with PBKDF2-HMAC-SHA256 and a random salt, both of which are recorded
+/// in the header of the export. The header is authenticated, so that a wrong
+/// key is reported before anything is imported, and the export is split into
+/// authenticated segments, so that a truncated or modified export is rejected.
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum EncryptionKey {
+	/// A passphrase, from which the key is derived
+	Passphrase(String),
+	/// A 256-bit key
+	Key([u8; 32]),
+}
+
+impl fmt::Debug for EncryptionKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Passphrase(_) => f.write_str("Passphrase(..)"),
+			Self::Key(_) => f.write_str("Key(..)"),
+		}
+	}
+}
+
+impl From<&str> for EncryptionKey {
+	fn from(passphrase: &str) -> Self {
+		Self::Passphrase(passphrase.to_owned())
+	}
+}
+
+impl From<String> for EncryptionKey {
+	fn from(passphrase: String) -> Self {
+		Self::Passphrase(passphrase)
+	}
+}
+
+impl From<[u8; 32]> for EncryptionKey {
+	fn from(key: [u8; 32]) -> Self {
+		Self::Key(key)
+	}
+}
+
+impl EncryptionKey {
+	/// Derives the AES key, using the salt and iterations from the header
+	fn derive(&self, kdf: u8, iterations: u32, salt: &[u8]) -> Result<LessSafeKey> {
+		let mut key = [0u8; 32];
+		match (self, kdf) {
+			(Self::Key(k), KDF_RAW) => key = *k,
+			(Self::Passphrase(p), KDF_PBKDF2) => {
+				let iterations = NonZeroU32::new(iterations)
+					.filter(|i| i.get() <= MAX_PBKDF2_ITERATIONS)
+					.ok_or_else(|| invalid("The export has an invalid header"))?;
+				pbkdf2::derive(
+					pbkdf2::PBKDF2_HMAC_SHA256,
+					iterations,
+					salt,
+					p.as_bytes(),
+					&mut key,
+				);
+			}
+			(Self::Key(_), _) => {
+				return Err(invalid("The export was encrypted with a passphrase, not a key"));
+			}
+			(Self::Passphrase(_), _) => {
+				return Err(invalid("The export was encrypted with a key, not a passphrase"));
+			}
+		}
+		let key = UnboundKey::new(&AES_256_GCM, &key)
+			.map_err(|_| Error::internal("Failed to create the encryption key".to_owned()))?;
+		Ok(LessSafeKey::new(key))
+	}
+}
+
+fn invalid(message: &str) -> Error {
+	Error::validation(message.to_owned(), None)
+}
+
+fn nonce(prefix: &[u8; PREFIX_LEN], counter: u32, flag: u8) -> Nonce {
+	let mut nonce = [0u8; NONCE_LEN];
+	nonce[..PREFIX_LEN].copy_from_slice(prefix);
+	nonce[PREFIX_LEN..NONCE_LEN - 1].copy_from_slice(&counter.to_be_bytes());
+	nonce[NONCE_LEN - 1] = flag;
+	Nonce::assume_unique_for_key(nonce)
+}
+
+/// Encrypts an export as it is produced
+pub(crate) struct Encryptor {
+	key: LessSafeKey,
+	header: Vec<u8>,
+	prefix: [u8; PREFIX_LEN],
+	counter: u32,
+}
+
+impl Encryptor {
+	/// Creates an encryptor with a random salt and nonce prefix, returning
+	/// it along with the authenticated header which starts the export
+	pub(crate) fn new(key: &EncryptionKey) -> Result<(Self, Vec<u8>)> {
+		let rng = SystemRandom::new();
+		let mut salt = [0u8; SALT_LEN];
+		let mut prefix = [0u8; PREFIX_LEN];
+		rng.fill(&mut salt)
+			.and_then(|_| rng.fill(&mut prefix))
+			.map_err(|_| Error::internal("Failed to generate random bytes".to_owned()))?;
+		let (kdf, iterations) = match key {
+			EncryptionKey::Passphrase(_) => (KDF_PBKDF2, PBKDF2_ITERATIONS),
+			EncryptionKey::Key(_) => (KDF_RAW, 0),
+		};
+		let mut header = Vec::with_capacity(HEADER_LEN + TAG_LEN);
+		header.extend_from_slice(MAGIC);
+		header.push(kdf);
+		header.extend_from_slice(&iterations.to_be_bytes());
+		header.extend_from_slice(&salt);
+		header.extend_from_slice(&prefix);
+		let key = key.derive(kdf, iterations, &salt)?;
+		let tag = key
+			.seal_in_place_separate_tag(nonce(&prefix, 0, FLAG_HEADER), Aad::from(&header), &mut [])
+			.map_err(|_| Error::internal("Failed to encrypt the export".to_owned()))?;
+		let mut output = header.clone();
+		output.extend_from_slice(tag.as_ref());
+		let encryptor = Self {
+			key,
+			header,
+			prefix,
+			counter: 0,
+		};
+		Ok((encryptor, output))
+	}
+
+	/// Encrypts the next chunk of the export
+	pub(crate) fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>> {
+		self.segment(chunk, FLAG_SEGMENT)
+	}
+
+	/// Ends the export, so that a truncated export can be detected
+	pub(crate) fn finish(mut self) -> Result<Vec<u8>> {
+		self.segment(&[], FLAG_LAST)
+	}
+
+	fn segment(&mut self, chunk: &[u8], flag: u8) -> Result<Vec<u8>> {
+		let mut data = chunk.to_vec();
+		self.key
+			.seal_in_place_append_tag(
+				nonce(&self.prefix, self.counter, flag),
+				Aad::from(&self.header),
+				&mut data,
+			)
+			.map_err(|_| Error::internal("Failed to encrypt the export".to_owned()))?;
+		self.counter = self
+			.counter
+			.checked_add(1)
+			.ok_or_else(|| Error::internal("The export is too large to encrypt".to_owned()))?;
+		let mut output = Vec::with_capacity(4 + data.len());
+		output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+		output.extend_from_slice(&data);
+		Ok(output)
+	}
+}
+
+/// Decrypts an export as it is read
+pub(crate) struct Decryptor {
+	key: EncryptionKey,
+	state: Option<(LessSafeKey, Vec<u8>, [u8; PREFIX_LEN])>,
+	buffer: Vec<u8>,
+	counter: u32,
+	finished: bool,
+}
+
+impl Decryptor {
+	pub(crate) fn new(key: EncryptionKey) -> Self {
+		Self {
+			key,
+			state: None,
+			buffer: Vec::new(),
+			counter: 0,
+			finished: false,
+		}
+	}
+
+	/// Decrypts the next chunk of the export, returning the plaintext of
+	/// every segment which is now complete
+	pub(crate) fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>> {
+		self.buffer.extend_from_slice(chunk);
+		let mut output = Vec::new();
+		let mut pos = 0;
+		if self.state.is_none() {
+			if self.buffer.len() < HEADER_LEN + TAG_LEN {
+				return Ok(output);
+			}
+			self.state = Some(self.open_header()?);
+			pos = HEADER_LEN + TAG_LEN;
+		}
+		let Some((key, header, prefix)) = &self.state else {
+			return Ok(output);
+		};
+		while let Some(len) = self.buffer.get(pos..pos + 4) {
+			let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+			if !(TAG_LEN..=MAX_SEGMENT_LEN + TAG_LEN).contains(&len) || self.finished {
+				return Err(invalid("The export is corrupted"));
+			}
+			let Some(data) = self.buffer.get(pos + 4..pos + 4 + len) else {
+				break;
+			};
+			let mut data = data.to_vec();
+			// Only the last segment opens with the last segment flag
+			let plaintext = match key.open_in_place(
+				nonce(prefix, self.counter, FLAG_SEGMENT),
+				Aad::from(header),
+				&mut data,
+			) {
+				Ok(plaintext) => plaintext.to_vec(),
+				Err(_) => {
+					let mut data = self.buffer[pos + 4..pos + 4 + len].to_vec();
+					let plaintext = key
+						.open_in_place(
+							nonce(prefix, self.counter, FLAG_LAST),
+							Aad::from(header),
+							&mut data,
+						)
+						.map_err(|_| invalid("The export is corrupted"))?;
+					self.finished = true;
+					plaintext.to_vec()
+				}
+			};
+			output.extend_from_slice(&plaintext);
+			self.counter = self.counter.wrapping_add(1);
+			pos += 4 + len;
+		}
+		self.buffer.drain(..pos);
+		Ok(output)
+	}
+
+	/// Checks that the whole export was read
+	pub(crate) fn finish(&self) -> Result<()> {
+		if self.finished && self.buffer.is_empty() {
+			Ok(())
+		} else {
+			Err(invalid("The export is truncated"))
+		}
+	}
+
+	fn open_header(&self) -> Result<(LessSafeKey, Vec<u8>, [u8; PREFIX_LEN])> {
+		let header = &self.buffer[..HEADER_LEN];
+		if !header.starts_with(MAGIC) {
+			return Err(invalid("The export is not encrypted, or uses an unsupported format"));
+		}
+		let mut pos = MAGIC.len();
+		let kdf = header[pos];
+		pos += 1;
+		let iterations =
+			u32::from_be_bytes([header[pos], header[pos + 1], header[pos + 2], header[pos + 3]]);
+		pos += 4;
+		let salt = &header[pos..pos + SALT_LEN];
+		pos += SALT_LEN;
+		let mut prefix = [0u8; PREFIX_LEN];
+		prefix.copy_from_slice(&header[pos..pos + PREFIX_LEN]);
+		let key = self.key.derive(kdf, iterations, salt)?;
+		let mut tag = self.buffer[HEADER_LEN..HEADER_LEN + TAG_LEN].to_vec();
+		key.open_in_place(nonce(&prefix, 0, FLAG_HEADER), Aad::from(header), &mut tag)
+			.map_err(|_| invalid("The export could not be decrypted with the given key"))?;
+		Ok((key, header.to_vec(), prefix))
+	}
+}
+
+/// Encrypts the chunks of an export as they are received
+pub(crate) fn encrypt_stream(
+	rx: Receiver<Result<Vec<u8>>>,
+	key: &EncryptionKey,
+) -> Result<Receiver<Result<Vec<u8>>>> {
+	let (mut encryptor, header) = Encryptor::new(key)?;
+	let (tx, encrypted) = crate::channel::bounded(1);
+	let future = async move {
+		if tx.send(Ok(header)).await.is_err() {
+			return;
+		}
+		while let Ok(chunk) = rx.recv().await {
+			let chunk = chunk.and_then(|chunk| encryptor.update(&chunk));
+			let failed = chunk.is_err();
+			if tx.send(chunk).await.is_err() || failed {
+				return;
+			}
+		}
+		tx.send(encryptor.finish()).await.ok();
+	};
+
+	#[cfg(not(target_family = "wasm"))]
+	tokio::spawn(future);
+
+	#[cfg(target_family = "wasm")]
+	wasm_bindgen_futures::spawn_local(future);
+
+	Ok(encrypted)
+}
+
+/// Decrypts the chunks of an export as they are read
+pub(crate) fn decrypt_stream<S, B, E>(
+	stream: S,
+	key: EncryptionKey,
+) -> impl Stream<Item = std::result::Result<Vec<u8>, E>>
+where
+	S: Stream<Item = std::result::Result<B, E>>,
+	B: AsRef<[u8]>,
+	E: From<Error>,
+{
+	let state = Some((Box::pin(stream), Decryptor::new(key)));
+	futures::stream::unfold(state, |state| async move {
+		let (mut stream, mut decryptor) = state?;
+		loop {
+			match stream.next().await {
+				Some(Ok(chunk)) => match decryptor.update(chunk.as_ref()) {
+					// Wait for a whole segment before yielding anything
+					Ok(plain) if plain.is_empty() => continue,
+					Ok(plain) => return Some((Ok(plain), Some((stream, decryptor)))),
+					Err(error) => return Some((Err(error.into()), None)),
+				},
+				Some(Err(error)) => return Some((Err(error), None)),
+				None => return decryptor.finish().err().map(|error| (Err(error.into()), None)),
+			}
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn encrypt(key: &EncryptionKey, chunks: &[&[u8]]) -> Vec<u8> {
+		let (mut encryptor, mut output) = Encryptor::new(key).unwrap();
+		for chunk in chunks {
+			output.extend(encryptor.update(chunk).unwrap());
+		}
+		output.extend(encryptor.finish().unwrap());
+		output
+	}
+
+	fn decrypt(key: EncryptionKey, data: &[u8], chunk_size: usize) -> Result<Vec<u8>> {
+		let mut decryptor = Decryptor::new(key);
+		let mut output = Vec::new();
+		for chunk in data.chunks(chunk_size) {
+			output.extend(decryptor.update(chunk)?);
+		}
+		decryptor.finish()?;
+		Ok(output)
+	}
+
+	#[test]
+	fn round_trips_with_a_key() {
+		let key = EncryptionKey::from([7u8; 32]);
+		let data = encrypt(&key, &[b"DEFINE TABLE person;\n", b"", b"INSERT INTO person [];\n"]);
+		assert!(!data.windows(6).any(|w| w == b"person"));
+		// The export can be read in chunks of any size
+		for chunk_size in [1, 7, 4096] {
+			assert_eq!(
+				decrypt(key.clone(), &data, chunk_size).unwrap(),
+				b"DEFINE TABLE person;\nINSERT INTO person [];\n"
+			);
+		}
+	}
+
+	#[test]
+	fn round_trips_with_a_passphrase() {
+		let data = encrypt(&EncryptionKey::from("correct horse"), &[b"RETURN 1;"]);
+		assert_eq!(decrypt("correct horse".into(), &data, 64).unwrap(), b"RETURN 1;");
+		let err = decrypt("battery staple".into(), &data, 64).unwrap_err();
+		assert!(err.to_string().contains("given key"), "{err}");
+	}
+
+	#[test]
+	fn rejects_modified_exports() {
+		let key = EncryptionKey::from([1u8; 32]);
+		let data = encrypt(&key, &[b"RETURN 1;", b"RETURN 2;"]);
+		// A truncated export is rejected
+		let err = decrypt(key.clone(), &data[..data.len() - 20], 64).unwrap_err();
+		assert!(err.to_string().contains("truncated"), "{err}");
+		// A modified export is rejected
+		let mut modified = data.clone();
+		let last = modified.len() - 30;
+		modified[last] ^= 1;
+		decrypt(key.clone(), &modified, 64).unwrap_err();
+		// A plain export is rejected
+		let err = decrypt(key, &[b' '; 128], 64).unwrap_err();
+		assert!(err.to_string().contains("not encrypted"), "{err}");
+	}
+}
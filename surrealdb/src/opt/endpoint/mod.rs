@@ -30,6 +30,8 @@ pub struct Endpoint {
 	#[doc(hidden)]
 	pub path: String,
 	pub(crate) config: Config,
+	/// The addresses of the other servers which requests are balanced across
+	pub(crate) replicas: Vec<Url>,
 }
 
 impl Endpoint {
@@ -38,9 +40,25 @@ impl Endpoint {
 			url,
 			path: String::new(),
 			config: Default::default(),
+			replicas: Vec::new(),
 		}
 	}
 
+	/// Combines the endpoints of several servers, so that requests are
+	/// balanced across them. The configuration of the first endpoint is used
+	/// for every server.
+	pub(crate) fn balanced(endpoints: impl IntoIterator<Item = Result<Endpoint>>) -> Result<Self> {
+		let mut endpoints = endpoints.into_iter();
+		let Some(endpoint) = endpoints.next() else {
+			return Err(Error::configuration("No endpoints to connect to".to_owned(), None));
+		};
+		let mut endpoint = endpoint?;
+		for replica in endpoints {
+			endpoint.replicas.push(replica?.url);
+		}
+		Ok(endpoint)
+	}
+
 	#[doc(hidden)]
 	pub fn parse_kind(&self) -> Result<EndpointKind> {
 		match EndpointKind::from(self.url.scheme()) {
@@ -57,3 +57,63 @@ macro_rules! endpoints {
 }
 
 endpoints!(&str, &String, String, SocketAddr);
+
+macro_rules! balanced {
+	($($scheme:ty),*) => {
+		$(
+			impl<T, const N: usize> IntoEndpoint<$scheme> for [T; N] where T: into_endpoint::Sealed<$scheme> {}
+			impl<T, const N: usize> into_endpoint::Sealed<$scheme> for [T; N]
+			where
+				T: into_endpoint::Sealed<$scheme>,
+			{
+				type Client = Client;
+
+				fn into_endpoint(self) -> Result<Endpoint> {
+					Endpoint::balanced(self.into_iter().map(into_endpoint::Sealed::into_endpoint))
+				}
+			}
+
+			impl<T> IntoEndpoint<$scheme> for Vec<T> where T: into_endpoint::Sealed<$scheme> {}
+			impl<T> into_endpoint::Sealed<$scheme> for Vec<T>
+			where
+				T: into_endpoint::Sealed<$scheme>,
+			{
+				type Client = Client;
+
+				fn into_endpoint(self) -> Result<Endpoint> {
+					Endpoint::balanced(self.into_iter().map(into_endpoint::Sealed::into_endpoint))
+				}
+			}
+
+			impl<T, const N: usize> IntoEndpoint<$scheme> for ([T; N], Config) where T: into_endpoint::Sealed<$scheme> {}
+			impl<T, const N: usize> into_endpoint::Sealed<$scheme> for ([T; N], Config)
+			where
+				T: into_endpoint::Sealed<$scheme>,
+			{
+				type Client = Client;
+
+				fn into_endpoint(self) -> Result<Endpoint> {
+					let mut endpoint = into_endpoint::Sealed::<$scheme>::into_endpoint(self.0)?;
+					endpoint.config = self.1;
+					Ok(endpoint)
+				}
+			}
+
+			impl<T> IntoEndpoint<$scheme> for (Vec<T>, Config) where T: into_endpoint::Sealed<$scheme> {}
+			impl<T> into_endpoint::Sealed<$scheme> for (Vec<T>, Config)
+			where
+				T: into_endpoint::Sealed<$scheme>,
+			{
+				type Client = Client;
+
+				fn into_endpoint(self) -> Result<Endpoint> {
+					let mut endpoint = into_endpoint::Sealed::<$scheme>::into_endpoint(self.0)?;
+					endpoint.config = self.1;
+					Ok(endpoint)
+				}
+			}
+		)*
+	}
+}
+
+balanced!(Ws, Wss);
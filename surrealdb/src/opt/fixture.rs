@@ -0,0 +1,109 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// The format of a seed fixture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FixtureFormat {
+	/// A SurrealQL script, executed as-is
+	Surql,
+	/// Newline-delimited JSON, with one `{ "table": ..., "data": ... }` object
+	/// per line
+	Ndjson,
+}
+
+impl FixtureFormat {
+	/// Guesses the format of a fixture file from its extension
+	///
+	/// Files ending in `.ndjson` or `.jsonl` are treated as NDJSON, and
+	/// everything else as SurrealQL.
+	pub fn from_path(path: &Path) -> Self {
+		match path.extension().and_then(|x| x.to_str()) {
+			Some(ext)
+				if ext.eq_ignore_ascii_case("ndjson") || ext.eq_ignore_ascii_case("jsonl") =>
+			{
+				Self::Ndjson
+			}
+			_ => Self::Surql,
+		}
+	}
+}
+
+/// A set of records used to seed a database
+///
+/// Used with [`Surreal::seed`](crate::Surreal::seed). A fixture is either
+/// read from a file when the seed runs, or embedded in the binary, for
+/// example with [`include_str!`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Fixture {
+	/// A fixture read from a file on disk
+	File(PathBuf),
+	/// A fixture embedded in the program
+	Embedded {
+		/// The format of the fixture
+		format: FixtureFormat,
+		/// The contents of the fixture
+		content: Cow<'static, str>,
+	},
+}
+
+impl Fixture {
+	/// A fixture read from the file at `path`
+	///
+	/// The format is guessed from the extension of the file, see
+	/// [`FixtureFormat::from_path`].
+	pub fn file(path: impl AsRef<Path>) -> Self {
+		Self::File(path.as_ref().to_path_buf())
+	}
+
+	/// An embedded SurrealQL fixture
+	pub fn surql(content: impl Into<Cow<'static, str>>) -> Self {
+		Self::Embedded {
+			format: FixtureFormat::Surql,
+			content: content.into(),
+		}
+	}
+
+	/// An embedded NDJSON fixture
+	///
+	/// Each non-empty line must be a JSON object with a `table` field naming
+	/// the table to insert into, and a `data` field holding the record, or an
+	/// array of records, to insert.
+	pub fn ndjson(content: impl Into<Cow<'static, str>>) -> Self {
+		Self::Embedded {
+			format: FixtureFormat::Ndjson,
+			content: content.into(),
+		}
+	}
+}
+
+impl From<&Path> for Fixture {
+	fn from(path: &Path) -> Self {
+		Self::file(path)
+	}
+}
+
+impl From<PathBuf> for Fixture {
+	fn from(path: PathBuf) -> Self {
+		Self::File(path)
+	}
+}
+
+impl From<&PathBuf> for Fixture {
+	fn from(path: &PathBuf) -> Self {
+		Self::file(path)
+	}
+}
+
+impl From<&str> for Fixture {
+	fn from(path: &str) -> Self {
+		Self::file(path)
+	}
+}
+
+impl From<String> for Fixture {
+	fn from(path: String) -> Self {
+		Self::File(path.into())
+	}
+}
@@ -0,0 +1,8 @@
+/// The format of the bytes streamed by
+/// [`FormattedQuery::stream_bytes`](crate::method::FormattedQuery::stream_bytes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Format {
+	/// Newline-delimited JSON, with one row of a statement result per line
+	Ndjson,
+}
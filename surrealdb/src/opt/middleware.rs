@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+
+use crate::Result;
+use crate::types::{Array, Value, Variables};
+
+/// Intercepts the requests a client sends and the responses it receives
+///
+/// Middleware is registered with
+/// [`Surreal::add_middleware`](crate::Surreal::add_middleware) and applies to
+/// every request sent over the connection, including those made by clones of
+/// the client. Requests pass through middleware in the order it was
+/// registered, and responses pass through it in reverse order.
+///
+/// Returning an error from either hook fails the request with that error.
+///
+/// # Examples
+///
+/// Tag every query with a tracing id, and redact passwords from responses:
+///
+/// ```no_run
+/// use surrealdb::opt::{Middleware, Request};
+/// use surrealdb::types::Value;
+///
+/// struct Tracing;
+///
+/// impl Middleware for Tracing {
+///     fn on_request(&self, request: &mut Request<'_>) -> surrealdb::Result<()> {
+///         if let Request::Query { variables, .. } = request {
+///             variables.insert("trace_id", "7f3a9c");
+///         }
+///         Ok(())
+///     }
+///
+///     fn on_response(&self, value: &mut Value) -> surrealdb::Result<()> {
+///         if let Value::Object(object) = value {
+///             object.remove("password");
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// # let db = surrealdb::engine::any::connect("mem://").await?;
+/// db.add_middleware(Tracing)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+	/// Called before a request is sent to the database
+	fn on_request(&self, request: &mut Request<'_>) -> Result<()> {
+		let _ = request;
+		Ok(())
+	}
+
+	/// Called with the value of every successful result received from the
+	/// database, before it is returned to the caller
+	fn on_response(&self, value: &mut Value) -> Result<()> {
+		let _ = value;
+		Ok(())
+	}
+}
+
+/// A request about to be sent to the database, as seen by [`Middleware`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Request<'a> {
+	/// A SurrealQL query, which the query, select, create, update, upsert,
+	/// merge, patch, insert, relate and delete methods are all sent as
+	Query {
+		/// The SurrealQL query text
+		query: &'a mut Cow<'static, str>,
+		/// The variables bound to the query
+		variables: &'a mut Variables,
+	},
+	/// A function call made with [`Surreal::run`](crate::Surreal::run)
+	Run {
+		/// The name of the function
+		name: &'a mut String,
+		/// The arguments passed to the function
+		args: &'a mut Array,
+	},
+	/// Any other request, such as signing in or setting a parameter
+	Other,
+}
@@ -3,19 +3,34 @@
 pub mod auth;
 pub mod capabilities;
 
+mod cache;
 mod config;
+mod encryption;
 pub(crate) mod endpoint;
 mod export;
+mod fixture;
+mod format;
+mod middleware;
 pub(crate) mod query;
 mod resource;
+#[cfg(feature = "macros")]
+mod surql;
 mod tls;
 mod websocket;
 
+pub use cache::CachePolicy;
 pub use config::*;
+pub use encryption::EncryptionKey;
+pub(crate) use encryption::{decrypt_stream, encrypt_stream};
 pub use endpoint::*;
 pub use export::*;
+pub use fixture::*;
+pub use format::*;
+pub use middleware::*;
 pub use query::*;
 pub use resource::*;
+#[cfg(feature = "macros")]
+pub use surql::*;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
 pub use tls::*;
 pub use websocket::*;
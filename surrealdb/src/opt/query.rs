@@ -31,7 +31,7 @@ mod query_result {
 
 		/// Extracts the statistics from a query response
 		fn stats(&self, response: &super::QueryResponse) -> Option<DbResultStats> {
-			response.results.get(&0).map(|x| x.0)
+			response.results.get(&0).map(|x| x.0.clone())
 		}
 	}
 }
@@ -46,7 +46,7 @@ impl query_result::Sealed<Value> for usize {
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(self).map(|x| x.0)
+		response.results.get(self).map(|x| x.0.clone())
 	}
 }
 
@@ -84,7 +84,7 @@ where
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(self).map(|x| x.0)
+		response.results.get(self).map(|x| x.0.clone())
 	}
 }
 
@@ -105,7 +105,7 @@ impl query_result::Sealed<Value> for (usize, &str) {
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(&self.0).map(|x| x.0)
+		response.results.get(&self.0).map(|x| x.0.clone())
 	}
 }
 
@@ -155,7 +155,7 @@ where
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(&self.0).map(|x| x.0)
+		response.results.get(&self.0).map(|x| x.0.clone())
 	}
 }
 
@@ -181,7 +181,7 @@ where
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(self).map(|x| x.0)
+		response.results.get(self).map(|x| x.0.clone())
 	}
 }
 
@@ -224,7 +224,7 @@ where
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(&self.0).map(|x| x.0)
+		response.results.get(&self.0).map(|x| x.0.clone())
 	}
 }
 
@@ -250,7 +250,7 @@ where
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(self).map(|x| x.0)
+		response.results.get(self).map(|x| x.0.clone())
 	}
 }
 
@@ -293,7 +293,7 @@ where
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(&self.0).map(|x| x.0)
+		response.results.get(&self.0).map(|x| x.0.clone())
 	}
 }
 
@@ -319,7 +319,7 @@ where
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(self).map(|x| x.0)
+		response.results.get(self).map(|x| x.0.clone())
 	}
 }
 
@@ -362,7 +362,7 @@ where
 	}
 
 	fn stats(&self, response: &QueryResponse) -> Option<DbResultStats> {
-		response.results.get(&self.0).map(|x| x.0)
+		response.results.get(&self.0).map(|x| x.0.clone())
 	}
 }
 
@@ -14,7 +14,7 @@ pub struct QueryRange {
 }
 
 /// Direction for graph traversal or resource access.
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Direction {
 	/// Outgoing direction.
 	Out,
@@ -0,0 +1,62 @@
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::types::{Value, Variables};
+
+/// A SurrealQL query with bound parameters, built by the [`surql!`](crate::surql) macro
+///
+/// Pass it to [`Surreal::query`](crate::Surreal::query) or
+/// [`Query::query`](crate::method::Query::query) to run it. The query was
+/// checked for syntax errors when the program was compiled, and every
+/// interpolated value is sent as a parameter rather than as query text.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Surql {
+	pub(crate) query: Cow<'static, str>,
+	pub(crate) variables: Variables,
+}
+
+/// The prefix of the parameters holding interpolated values
+const PARAM_PREFIX: &str = "_surql_";
+
+/// Numbers the queries built by the macro, so that the parameters of queries
+/// chained on the same request do not replace each other
+static NEXT_QUERY: AtomicU64 = AtomicU64::new(0);
+
+impl Surql {
+	/// Used by the [`surql!`](crate::surql) macro. Not part of the public API.
+	///
+	/// Joins the text around the interpolations with parameters which are
+	/// unique to this query, binding the interpolated values to them.
+	#[doc(hidden)]
+	pub fn __from_parts(parts: &[&'static str], values: Vec<Value>) -> Self {
+		debug_assert_eq!(parts.len(), values.len() + 1);
+		let id = NEXT_QUERY.fetch_add(1, Ordering::Relaxed);
+		let mut query = String::new();
+		let mut variables = Variables::new();
+		for (i, (part, value)) in parts.iter().zip(values).enumerate() {
+			let name = format!("{PARAM_PREFIX}{id}_{i}");
+			query.push_str(part);
+			query.push('$');
+			query.push_str(&name);
+			variables.insert(name, value);
+		}
+		if let Some(last) = parts.last() {
+			query.push_str(last);
+		}
+		Self {
+			query: Cow::Owned(query),
+			variables,
+		}
+	}
+
+	/// The text of the query, with parameters in place of the interpolated values
+	pub fn query(&self) -> &str {
+		&self.query
+	}
+
+	/// The values bound to the parameters of the query
+	pub fn variables(&self) -> &Variables {
+		&self.variables
+	}
+}
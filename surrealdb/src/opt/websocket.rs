@@ -166,3 +166,39 @@ impl WebsocketConfig {
 		self
 	}
 }
+
+/// How requests are spread across the servers of a connection to several
+/// endpoints
+///
+/// Requests which depend on earlier requests, such as the statements of a
+/// transaction or the notifications of a live query, are always sent to the
+/// server which the session started them on. Servers which fail a health check
+/// are skipped until they pass one again.
+///
+/// # Examples
+///
+/// ```no_run
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::remote::ws::Ws;
+/// use surrealdb::opt::{Config, LoadBalancing};
+///
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// let config = Config::new().load_balancing(LoadBalancing::LowestLatency);
+/// let db = Surreal::new::<Ws>((["a.example.com:8000", "b.example.com:8000"], config)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LoadBalancing {
+	/// Send each request to the next server in turn
+	#[default]
+	RoundRobin,
+	/// Send each request to the server which answered its last health check
+	/// the fastest
+	LowestLatency,
+	/// Send every request to the first server which is healthy, only using
+	/// the other servers when it is not
+	Failover,
+}
@@ -0,0 +1,203 @@
+//! The RPC protocol spoken between clients and SurrealDB servers.
+//!
+//! This module exposes the message types, the serialisation formats, and the
+//! rules for negotiating a connection, so that alternative clients and proxies
+//! can be written in Rust against the same types which this SDK and the server
+//! use.
+//!
+//! # Connecting
+//!
+//! Requests are sent to the [`PATH`] endpoint of a server, either as the body
+//! of an HTTP `POST` request, or as messages over a WebSocket connection. A
+//! WebSocket client lists the [formats](Format) it can speak in the
+//! `Sec-WebSocket-Protocol` header, and the server picks the first entry of
+//! [`PROTOCOLS`] which the client offered (see [`negotiate`]). HTTP clients set
+//! the `Content-Type` and `Accept` headers instead.
+//!
+//! Once connected, a client should call the `version` method and check the
+//! result with [`check_version`], as the protocol may change between major
+//! versions of SurrealDB. The `compatibility` method returns the version along
+//! with the revision of the storage format stored in the datastore of the
+//! server, as `{ version, storage }`, in a single round trip.
+//!
+//! # Messages
+//!
+//! Every message is a single object, encoded in the negotiated format:
+//!
+//! - A [`Request`] carries an optional `id`, the `method` to call, its `params`,
+//!   and optionally the `session` and `txn` it runs within.
+//! - A [`DbResponse`] echoes the `id` and `session` of the request, and carries
+//!   either a `result` or an `error`. Live query notifications are sent as
+//!   responses without an `id`.
+//!
+//! # Example
+//!
+//! ```
+//! use surrealdb::protocol::{self, Format, Method, Request};
+//! use surrealdb::types::{Array, Value};
+//!
+//! let mut request = Request::new(Method::Query);
+//! request.id = Some(Value::from_t(1_i64));
+//! request.params = Array::from(vec![Value::from_t("RETURN 1")]);
+//! let bytes = protocol::encode_request(Format::Cbor, request)?;
+//! # let _ = bytes;
+//! # Ok::<(), surrealdb::Error>(())
+//! ```
+
+use semver::{Version, VersionReq};
+#[doc(inline)]
+pub use surrealdb_core::rpc::format::{Format, PROTOCOLS};
+use surrealdb_core::rpc::format::{cbor, flatbuffers, json};
+#[doc(inline)]
+pub use surrealdb_core::rpc::{DbResponse, DbResult, DbResultStats, Method, Request};
+use surrealdb_types::SerializationError;
+
+use crate::types::{SurrealValue, Value};
+use crate::{Error, Result};
+
+/// The path of the RPC endpoint on a server
+pub const PATH: &str = "rpc";
+
+/// The range of server versions which speak this version of the protocol
+pub const SUPPORTED_VERSIONS: &str = ">=3.0.0-alpha.1, <4.0.0";
+
+/// The default limit on the depth of nested values when decoding a message
+pub const RECURSION_LIMIT: usize = 100;
+
+/// Checks that a server speaks this version of the protocol
+///
+/// Pre-release servers are accepted as if they were the release they precede.
+pub fn check_version(version: &Version) -> Result<()> {
+	// invalid version requirements should be caught during development
+	let req = VersionReq::parse(SUPPORTED_VERSIONS).expect("valid supported versions");
+	let mut version = version.clone();
+	version.pre = Default::default();
+	if !req.matches(&version) {
+		return Err(Error::internal(format!(
+			"server version `{version}` does not match the range supported by the client `{SUPPORTED_VERSIONS}`"
+		)));
+	}
+	Ok(())
+}
+
+/// Picks the format of a WebSocket connection
+///
+/// The argument is the value of the `Sec-WebSocket-Protocol` header sent by
+/// the client. The format is chosen in the same way as the server does, by
+/// picking the first entry of [`PROTOCOLS`] which the client offered.
+pub fn negotiate(offered: &str) -> Option<Format> {
+	let offered: Vec<&str> = offered.split(',').map(str::trim).collect();
+	PROTOCOLS.into_iter().find(|p| offered.contains(p)).map(Format::from)
+}
+
+/// Encodes a value in the given format
+pub fn encode(format: Format, value: Value) -> Result<Vec<u8>> {
+	let res = match format {
+		Format::Json => json::encode(value),
+		Format::Cbor => cbor::encode(value),
+		Format::Flatbuffers => flatbuffers::encode(&value),
+		Format::Unsupported => return Err(unsupported(SerializationError::Serialization)),
+	};
+	res.map_err(|e| Error::serialization(e.to_string(), SerializationError::Serialization))
+}
+
+/// Decodes a value in the given format
+///
+/// The recursion limit bounds the depth of nested values, and is ignored by
+/// the flatbuffers format.
+pub fn decode(format: Format, bytes: &[u8], recursion_limit: usize) -> Result<Value> {
+	let res = match format {
+		Format::Json => json::decode(bytes, recursion_limit),
+		Format::Cbor => cbor::decode(bytes, recursion_limit),
+		Format::Flatbuffers => flatbuffers::decode(bytes),
+		Format::Unsupported => return Err(unsupported(SerializationError::Deserialization)),
+	};
+	res.map_err(|e| Error::serialization(e.to_string(), SerializationError::Deserialization))
+}
+
+/// Encodes a request in the given format
+pub fn encode_request(format: Format, request: Request) -> Result<Vec<u8>> {
+	encode(format, Value::Object(request.into_object()))
+}
+
+/// Decodes a request in the given format
+pub fn decode_request(format: Format, bytes: &[u8]) -> Result<Request> {
+	match decode(format, bytes, RECURSION_LIMIT)? {
+		Value::Object(obj) => Request::from_object(obj),
+		_ => Err(Error::serialization(
+			"An RPC request must be an object".to_owned(),
+			SerializationError::Deserialization,
+		)),
+	}
+}
+
+/// Encodes a response in the given format
+pub fn encode_response(format: Format, response: DbResponse) -> Result<Vec<u8>> {
+	encode(format, response.into_value())
+}
+
+/// Decodes a response in the given format
+pub fn decode_response(format: Format, bytes: &[u8]) -> Result<DbResponse> {
+	DbResponse::from_value(decode(format, bytes, RECURSION_LIMIT)?)
+}
+
+fn unsupported(details: SerializationError) -> Error {
+	Error::serialization("Unsupported RPC format".to_owned(), details)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::Array;
+
+	#[test]
+	fn test_supported_versions() {
+		for version in ["3.0.0-alpha.1", "3.0.0-beta.3", "3.0.0-rc.2", "3.0.0", "3.0.1", "3.9.0"] {
+			check_version(&Version::parse(version).unwrap()).unwrap();
+		}
+		for version in ["2.9.0", "4.0.0", "4.0.0-alpha.1"] {
+			check_version(&Version::parse(version).unwrap()).unwrap_err();
+		}
+	}
+
+	#[test]
+	fn test_negotiate() {
+		assert_eq!(negotiate("flatbuffers"), Some(Format::Flatbuffers));
+		assert_eq!(negotiate("cbor, json"), Some(Format::Json));
+		assert_eq!(negotiate("graphql-ws, cbor"), Some(Format::Cbor));
+		assert_eq!(negotiate("graphql-ws"), None);
+	}
+
+	#[test]
+	fn test_request_round_trip() {
+		for format in [Format::Json, Format::Cbor, Format::Flatbuffers] {
+			let mut request = Request::new(Method::Query);
+			request.id = Some(Value::from_t(1_i64));
+			request.params = Array::from(vec![Value::from_t("RETURN 1")]);
+			let bytes = encode_request(format, request).unwrap();
+			let request = decode_request(format, &bytes).unwrap();
+			assert_eq!(request.id, Some(Value::from_t(1_i64)), "{format:?}");
+			assert_eq!(request.method, Method::Query, "{format:?}");
+			assert_eq!(request.params, Array::from(vec![Value::from_t("RETURN 1")]), "{format:?}");
+		}
+		encode_request(Format::Unsupported, Request::new(Method::Ping)).unwrap_err();
+	}
+
+	#[test]
+	fn test_response_round_trip() {
+		for format in [Format::Cbor, Format::Flatbuffers] {
+			let response = DbResponse::success(
+				Some(Value::from_t(1_i64)),
+				None,
+				DbResult::Other(Value::from_t("3.0.0")),
+			);
+			let bytes = encode_response(format, response).unwrap();
+			let response = decode_response(format, &bytes).unwrap();
+			assert_eq!(response.id, Some(Value::from_t(1_i64)), "{format:?}");
+			match response.result {
+				Ok(DbResult::Other(value)) => assert_eq!(value, Value::from_t("3.0.0")),
+				other => panic!("unexpected result: {other:?}"),
+			}
+		}
+	}
+}
@@ -0,0 +1,517 @@
+//! Offline-first synchronisation between an embedded and a remote database.
+//!
+//! A [`SyncEngine`] keeps a set of tables in a local database, such as an
+//! IndexedDB database in the browser, in sync with the same tables in a remote
+//! SurrealDB server. Every change made to a synced table is recorded in an
+//! operation log (the `__oplog` table) by an event which
+//! [`SyncEngine::setup`] defines on both databases. Each round of
+//! synchronisation pushes the pending local operations to the remote database,
+//! and pulls the operations which were recorded remotely since the previous
+//! round. Local operations are only removed from the log once they have been
+//! pushed, so changes made while the remote database is unreachable are kept
+//! until connectivity returns.
+//!
+//! Every engine identifies itself with a client id, which is stored in the
+//! local database and recorded on the operations which the engine applies.
+//! The remote log is shared by every device: each engine reads it through its
+//! changefeed, from the versionstamp at which it stopped in the previous
+//! round, and skips the operations which it pushed itself. Remote operations
+//! are removed once they are older than the
+//! [`retention`](SyncEngine::retention) of the log.
+//!
+//! When a record was changed on both sides since the previous round, the
+//! [`ConflictResolution`] of the engine decides the state which both databases
+//! end up with.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use surrealdb::engine::any;
+//! use surrealdb::sync::{ConflictResolution, SyncEngine};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> surrealdb::Result<()> {
+//! let local = any::connect("mem://").await?;
+//! local.use_ns("app").use_db("app").await?;
+//! let remote = any::connect("wss://cloud.surrealdb.com").await?;
+//! remote.use_ns("app").use_db("app").await?;
+//!
+//! let engine = SyncEngine::new(local, remote)
+//!     .table("task")
+//!     .conflict(ConflictResolution::LastWriteWins)
+//!     .interval(Duration::from_secs(10));
+//! engine.setup().await?;
+//! // Synchronise in the background, retrying whenever the remote is unreachable
+//! engine.run().await;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::sleep;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::sleep;
+
+use uuid::Uuid;
+
+use crate::method::escape_field;
+use crate::types::{Datetime, RecordId, SurrealValue, Value};
+use crate::{Connection, Error, Result, Surreal};
+
+/// The table in which the changes to the synced tables are recorded
+const OPLOG: &str = "__oplog";
+
+/// The record which stores the client id of the engine, and its position in
+/// the remote oplog
+const STATE: &str = "__sync:state";
+
+/// The default interval between two rounds of synchronisation
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The default time for which operations are kept in the remote oplog
+const DEFAULT_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// The number of change sets read from the remote oplog at once
+const BATCH_SIZE: usize = 1000;
+
+/// Applies a batch of changes, recording the client which applied them on the
+/// operations logged by the changes, so that they are not synced back to it
+const APPLY_QUERY: &str = "
+	BEGIN;
+	FOR $change IN $changes {
+		IF $change.deleted {
+			DELETE $change.record;
+		} ELSE {
+			UPSERT $change.record CONTENT $change.value;
+		};
+	};
+	COMMIT;
+";
+
+type MergeFn = Arc<dyn Fn(&Conflict) -> Option<Value> + Send + Sync>;
+
+/// Decides the state of a record which was changed both locally and remotely
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub enum ConflictResolution {
+	/// Keeps the most recent change
+	///
+	/// Changes are ordered by the clock of the database which recorded them,
+	/// so the clocks of the devices and the server should be kept in sync.
+	#[default]
+	LastWriteWins,
+	/// Merges both changes with a custom function
+	///
+	/// The function returns the state of the record, or `None` if the record
+	/// should be deleted.
+	Merge(MergeFn),
+}
+
+impl ConflictResolution {
+	/// Merges conflicting changes with a custom function
+	pub fn merge<F>(merge: F) -> Self
+	where
+		F: Fn(&Conflict) -> Option<Value> + Send + Sync + 'static,
+	{
+		Self::Merge(Arc::new(merge))
+	}
+}
+
+impl fmt::Debug for ConflictResolution {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::LastWriteWins => f.write_str("LastWriteWins"),
+			Self::Merge(_) => f.write_str("Merge"),
+		}
+	}
+}
+
+/// A record which was changed both locally and remotely
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Conflict {
+	/// The record which was changed
+	pub record: RecordId,
+	/// The local state of the record, or `None` if it was deleted locally
+	pub local: Option<Value>,
+	/// When the record was last changed locally
+	pub local_at: Datetime,
+	/// The remote state of the record, or `None` if it was deleted remotely
+	pub remote: Option<Value>,
+	/// When the record was last changed remotely
+	pub remote_at: Datetime,
+}
+
+/// The outcome of a round of synchronisation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SyncReport {
+	/// The number of records changed in the remote database
+	pub pushed: usize,
+	/// The number of records changed in the local database
+	pub pulled: usize,
+	/// The number of records which were changed on both sides
+	pub conflicts: usize,
+}
+
+/// An operation recorded in an oplog
+#[derive(Debug, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct Operation {
+	id: RecordId,
+	record: RecordId,
+	tb: String,
+	value: Option<Value>,
+	deleted: bool,
+	at: Datetime,
+	/// The client which applied the change, unless it was made directly
+	origin: Option<String>,
+}
+
+/// The state of the engine, stored in the local database
+#[derive(Debug, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct SyncState {
+	client: String,
+	/// The versionstamp from which the remote oplog is read next
+	versionstamp: Option<u64>,
+}
+
+/// A set of changes read from the changefeed of the remote oplog
+#[derive(Debug, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct ChangeSet {
+	versionstamp: u64,
+	changes: Vec<Value>,
+}
+
+/// A change applied to a database
+#[derive(Debug, SurrealValue)]
+#[surreal(crate = "crate::types")]
+struct Change {
+	record: RecordId,
+	value: Option<Value>,
+	deleted: bool,
+}
+
+impl Change {
+	fn new(record: RecordId, value: Option<Value>) -> Self {
+		Self {
+			deleted: value.is_none(),
+			record,
+			value,
+		}
+	}
+}
+
+/// Synchronises tables between a local and a remote database
+///
+/// See the [module level documentation](self) for more details.
+#[derive(Debug)]
+pub struct SyncEngine<L: Connection, R: Connection> {
+	local: Surreal<L>,
+	remote: Surreal<R>,
+	tables: Vec<String>,
+	conflict: ConflictResolution,
+	interval: Duration,
+	retention: Duration,
+}
+
+impl<L, R> SyncEngine<L, R>
+where
+	L: Connection,
+	R: Connection,
+{
+	/// Creates an engine which syncs the local database with the remote one
+	///
+	/// Both clients must already have selected the namespace and database
+	/// which are synced.
+	pub fn new(local: Surreal<L>, remote: Surreal<R>) -> Self {
+		Self {
+			local,
+			remote,
+			tables: Vec::new(),
+			conflict: ConflictResolution::default(),
+			interval: DEFAULT_INTERVAL,
+			retention: DEFAULT_RETENTION,
+		}
+	}
+
+	/// Adds a table to the tables which are synced
+	pub fn table(mut self, table: impl Into<String>) -> Self {
+		self.tables.push(table.into());
+		self
+	}
+
+	/// Sets how records which were changed on both sides are resolved
+	pub fn conflict(mut self, conflict: ConflictResolution) -> Self {
+		self.conflict = conflict;
+		self
+	}
+
+	/// Sets how long [`run`](Self::run) waits between two rounds of
+	/// synchronisation
+	pub const fn interval(mut self, interval: Duration) -> Self {
+		self.interval = interval;
+		self
+	}
+
+	/// Sets how long operations are kept in the remote oplog
+	///
+	/// A device which has not synchronised for longer than this misses the
+	/// operations which were removed in the meantime, and has to be seeded
+	/// again from the remote database. Run [`setup`](Self::setup) again after
+	/// changing the retention.
+	pub const fn retention(mut self, retention: Duration) -> Self {
+		self.retention = retention;
+		self
+	}
+
+	/// Defines the oplog, and the events which record the changes to the
+	/// synced tables, on both databases
+	///
+	/// The first setup also generates the client id of the engine. This is
+	/// idempotent, and must be run again whenever a table is added to the
+	/// engine.
+	pub async fn setup(&self) -> Result<()> {
+		let query = self.setup_query();
+		self.local
+			.query(query.clone())
+			.query(format!("UPSERT {STATE} SET client = client ?? $client"))
+			.bind(("client", Uuid::now_v7().to_string()))
+			.await?
+			.check()?;
+		self.remote.query(query).await?.check()?;
+		Ok(())
+	}
+
+	fn setup_query(&self) -> String {
+		// The remote oplog is read through its changefeed, whose versionstamps
+		// follow the order in which the operations were committed
+		let mut query = format!(
+			"DEFINE TABLE OVERWRITE {OPLOG} SCHEMALESS CHANGEFEED {}s;
+			DEFINE INDEX IF NOT EXISTS at ON {OPLOG} FIELDS at;",
+			self.retention.as_secs().max(1)
+		);
+		for table in &self.tables {
+			query.push_str(&format!(
+				"DEFINE EVENT OVERWRITE __sync ON TABLE {} THEN {{
+					CREATE {OPLOG} CONTENT {{
+						record: $value.id,
+						tb: record::tb($value.id),
+						value: IF $event = 'DELETE' THEN NONE ELSE $after END,
+						deleted: $event = 'DELETE',
+						at: time::now(),
+						origin: $sync_origin,
+					}};
+				}};",
+				escape_field(table)
+			));
+		}
+		query
+	}
+
+	/// Reads the operations recorded in the remote oplog from a versionstamp
+	/// onwards, skipping those which the client applied itself, and returns
+	/// them along with the versionstamp from which to read next
+	async fn pull(&self, client: &str, mut cursor: u64) -> Result<(Vec<Operation>, u64)> {
+		let mut ops = Vec::new();
+		loop {
+			let query = format!("SHOW CHANGES FOR TABLE {OPLOG} SINCE {cursor} LIMIT {BATCH_SIZE}");
+			let sets: Vec<ChangeSet> = self.remote.query(query).await?.take(0)?;
+			let exhausted = sets.len() < BATCH_SIZE;
+			for set in sets {
+				for change in set.changes {
+					let Some(op) = logged_operation(change) else {
+						continue;
+					};
+					if op.origin.as_deref() != Some(client) && self.tables.contains(&op.tb) {
+						ops.push(op);
+					}
+				}
+				// Changes are read from the given versionstamp inclusively
+				cursor = set.versionstamp + 1;
+			}
+			if exhausted {
+				return Ok((ops, cursor));
+			}
+		}
+	}
+
+	/// Runs a single round of synchronisation
+	///
+	/// Fails without losing any change if either database can not be reached,
+	/// in which case the round can simply be retried later.
+	pub async fn sync(&self) -> Result<SyncReport> {
+		// Read the pending local operations
+		let mut response = self
+			.local
+			.query(format!("SELECT * FROM {OPLOG} WHERE tb IN $tables ORDER BY at"))
+			.query(format!("SELECT client, versionstamp FROM ONLY {STATE}"))
+			.bind(("tables", self.tables.clone()))
+			.await?;
+		let local: Vec<Operation> = response.take(0)?;
+		let state: Option<SyncState> = response.take(1)?;
+		let Some(state) = state else {
+			return Err(Error::validation(
+				"The sync engine must be set up before synchronising".to_owned(),
+				None,
+			));
+		};
+		// Read the remote operations recorded since the previous round
+		let (remote, cursor) = self.pull(&state.client, state.versionstamp.unwrap_or(0)).await?;
+		// Decide which changes to apply on each side. The local operations
+		// which were recorded by pulled changes are only forgotten.
+		let pushed_ops: Vec<RecordId> = local.iter().map(|op| op.id.clone()).collect();
+		let local =
+			local.into_iter().filter(|op| op.origin.as_deref() != Some(state.client.as_str()));
+		let mut local = latest(local.collect());
+		let mut report = SyncReport::default();
+		let mut to_local = Vec::new();
+		let mut to_remote = Vec::new();
+		for (record, theirs) in latest(remote) {
+			let Some(ours) = local.remove(&record) else {
+				to_local.push(Change::new(record, theirs.value));
+				continue;
+			};
+			report.conflicts += 1;
+			let conflict = Conflict {
+				record,
+				local: ours.value,
+				local_at: ours.at,
+				remote: theirs.value,
+				remote_at: theirs.at,
+			};
+			match &self.conflict {
+				ConflictResolution::LastWriteWins => {
+					if conflict.local_at >= conflict.remote_at {
+						to_remote.push(Change::new(conflict.record, conflict.local));
+					} else {
+						to_local.push(Change::new(conflict.record, conflict.remote));
+					}
+				}
+				ConflictResolution::Merge(merge) => {
+					let merged = merge(&conflict);
+					to_local.push(Change::new(conflict.record.clone(), merged.clone()));
+					to_remote.push(Change::new(conflict.record, merged));
+				}
+			}
+		}
+		to_remote.extend(local.into_iter().map(|(record, op)| Change::new(record, op.value)));
+		report.pushed = to_remote.len();
+		report.pulled = to_local.len();
+		// Push the local changes, and only then forget the pushed operations
+		if !to_remote.is_empty() {
+			apply(&self.remote, &state.client, to_remote).await?;
+		}
+		if !pushed_ops.is_empty() {
+			self.local.query("DELETE $ops").bind(("ops", pushed_ops)).await?.check()?;
+		}
+		// Pull the remote changes, and move past them in the remote oplog
+		if !to_local.is_empty() {
+			apply(&self.local, &state.client, to_local).await?;
+		}
+		self.local
+			.query(format!("UPDATE {STATE} SET versionstamp = $versionstamp"))
+			.bind(("versionstamp", cursor))
+			.await?
+			.check()?;
+		// Remove the remote operations which are past their retention
+		self.remote
+			.query(format!(
+				"DELETE {OPLOG} WHERE at < time::now() - {}s",
+				self.retention.as_secs().max(1)
+			))
+			.await?
+			.check()?;
+		Ok(report)
+	}
+
+	/// Synchronises the databases forever, every [`interval`](Self::interval)
+	///
+	/// Failed rounds, for example while the remote database is unreachable,
+	/// are logged and retried on the next interval.
+	pub async fn run(&self) {
+		loop {
+			match self.sync().await {
+				Ok(report) => trace!("Synchronised the local database: {report:?}"),
+				Err(error) => warn!("Failed to synchronise the local database: {error}"),
+			}
+			sleep(self.interval).await;
+		}
+	}
+}
+
+/// Applies a batch of changes to a database on behalf of a client
+async fn apply<C: Connection>(db: &Surreal<C>, client: &str, changes: Vec<Change>) -> Result<()> {
+	db.query(APPLY_QUERY)
+		.bind(("changes", changes))
+		.bind(("sync_origin", client.to_owned()))
+		.await?
+		.check()?;
+	Ok(())
+}
+
+/// Returns the operation logged by an entry of the changefeed of an oplog, if
+/// the entry recorded one rather than its removal
+fn logged_operation(change: Value) -> Option<Operation> {
+	let Value::Object(mut change) = change else {
+		return None;
+	};
+	match change.remove("update") {
+		Some(op @ Value::Object(_)) => Operation::from_value(op).ok(),
+		_ => None,
+	}
+}
+
+/// Collapses the operations of an oplog to the latest operation of each record
+fn latest(ops: Vec<Operation>) -> BTreeMap<RecordId, Operation> {
+	let mut latest = BTreeMap::new();
+	// Operations are ordered by time, so later operations replace earlier ones
+	for op in ops {
+		latest.insert(op.record.clone(), op);
+	}
+	latest
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn op(id: i64, record: i64, value: Option<i64>, at: i64) -> Operation {
+		Operation {
+			id: RecordId::new(OPLOG, id),
+			record: RecordId::new("task", record),
+			tb: "task".to_owned(),
+			deleted: value.is_none(),
+			value: value.map(Value::from_t),
+			at: Datetime::from_timestamp(at, 0).unwrap(),
+			origin: None,
+		}
+	}
+
+	#[test]
+	fn latest_operation_of_each_record_is_kept() {
+		let ops = vec![op(1, 1, Some(1), 1), op(2, 2, Some(2), 2), op(3, 1, None, 3)];
+		let latest = latest(ops);
+		assert_eq!(latest.len(), 2);
+		assert!(latest[&RecordId::new("task", 1)].deleted);
+		assert_eq!(latest[&RecordId::new("task", 2)].value, Some(Value::from_t(2)));
+	}
+
+	#[test]
+	fn removed_operations_are_not_logged() {
+		let mut change = crate::types::Object::new();
+		change.insert("update".to_owned(), op(1, 1, Some(1), 1).into_value());
+		let logged = logged_operation(Value::Object(change)).unwrap();
+		assert_eq!(logged.record, RecordId::new("task", 1));
+		let mut change = crate::types::Object::new();
+		change.insert("delete".to_owned(), op(1, 1, Some(1), 1).into_value());
+		assert!(logged_operation(Value::Object(change)).is_none());
+	}
+}
@@ -203,6 +203,121 @@ relate person:`a`->`friends2\`;\nDEFINE USER IF NOT EXISTS pwned ON ROOT PASSWOR
 	assert_eq!(export_text, export_text_2);
 }
 
+pub async fn export_schema_and_data_only(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	drop(permit);
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	db.query(
+		"
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD name ON person TYPE string;
+		DEFINE TABLE adults AS SELECT * FROM person WHERE age >= 18;
+		DEFINE FIELD age ON person TYPE int;
+		CREATE person:one SET name = 'Tobie', age = 35;
+		",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+
+	async fn collect(backup: surrealdb::method::Backup) -> String {
+		let bytes = backup
+			.fold(Vec::new(), |mut acc, x| async move {
+				acc.append(&mut x.unwrap());
+				acc
+			})
+			.await;
+		String::from_utf8(bytes).unwrap()
+	}
+
+	let schema = collect(db.export(()).with_config().schema_only().await.unwrap()).await;
+	let data = collect(db.export(()).with_config().data_only().await.unwrap()).await;
+
+	// Both dumps start with a manifest describing their contents
+	assert!(schema.contains(r#""contents":["schema"]"#), "{schema}");
+	assert!(data.contains(r#""contents":["data"]"#), "{data}");
+	// The schema dump holds no records, and defines tables before their views
+	assert!(!schema.contains("INSERT"), "{schema}");
+	let person = schema.find("DEFINE TABLE person").unwrap();
+	let adults = schema.find("DEFINE TABLE adults").unwrap();
+	assert!(person < adults, "{schema}");
+	// The data dump holds no definitions
+	assert!(!data.contains("DEFINE"), "{data}");
+	assert!(data.contains("INSERT"), "{data}");
+
+	// Both dumps import cleanly into an empty database
+	let dir = temp_dir::TempDir::new().unwrap();
+	let schema_path = dir.path().join("schema.surql");
+	let data_path = dir.path().join("data.surql");
+	std::fs::write(&schema_path, &schema).unwrap();
+	std::fs::write(&data_path, &data).unwrap();
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	db.import(schema_path).await.unwrap();
+	db.import(data_path).await.unwrap();
+	let mut response = db.query("SELECT VALUE name FROM person").await.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["Tobie".to_string()]);
+}
+
+pub async fn export_import_encrypted(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(Ulid::new().to_string()).use_db(&db_name).await.unwrap();
+	db.query("CREATE person:tobie SET name = 'Tobie'").await.unwrap().check().unwrap();
+	drop(permit);
+
+	let dir = temp_dir::TempDir::new().unwrap();
+	let file = dir.path().join("export.surql.enc");
+	db.export(&file).encrypt_with("correct horse battery staple").await.unwrap();
+
+	// Nothing in the export is readable without the passphrase
+	let bytes = std::fs::read(&file).unwrap();
+	assert!(!bytes.windows(5).any(|w| w == b"Tobie"));
+	assert!(!bytes.windows(6).any(|w| w == b"person"));
+
+	// The export can only be imported with the same passphrase
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	db.import(&file).decrypt_with("wrong passphrase").await.unwrap_err();
+	db.import(&file).decrypt_with("correct horse battery staple").await.unwrap();
+	let mut response = db.query("SELECT VALUE name FROM person").await.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["Tobie".to_string()]);
+}
+
+pub async fn export_import_verified(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(Ulid::new().to_string()).use_db(&db_name).await.unwrap();
+	db.query("CREATE person:tobie SET name = 'Tobie'").await.unwrap().check().unwrap();
+	drop(permit);
+
+	let dir = temp_dir::TempDir::new().unwrap();
+	let file = dir.path().join("export.surql");
+	db.export(&file).await.unwrap();
+	let export = std::fs::read_to_string(&file).unwrap();
+	assert!(export.lines().last().unwrap().starts_with("-- TRAILER: "), "{export}");
+
+	// An export which was modified is rejected before anything is imported
+	let modified = dir.path().join("modified.surql");
+	std::fs::write(&modified, export.replace("Tobie", "Jaime")).unwrap();
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	db.import(&modified).verify().await.unwrap_err();
+	let mut response = db.query("SELECT VALUE name FROM person").await.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert!(names.is_empty());
+
+	// An export which was not modified is imported
+	db.import(&file).verify().await.unwrap();
+	let mut response = db.query("SELECT VALUE name FROM person").await.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["Tobie".to_string()]);
+}
+
 define_include_tests!(backup => {
 	#[tokio::test]
 	export_import,
@@ -216,4 +331,13 @@ define_include_tests!(backup => {
 
 	#[tokio::test]
 	export_escaped_table_names,
+
+	#[tokio::test]
+	export_schema_and_data_only,
+
+	#[tokio::test]
+	export_import_encrypted,
+
+	#[tokio::test]
+	export_import_verified,
 });
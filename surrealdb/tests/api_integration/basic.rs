@@ -6,8 +6,12 @@ use std::time::Duration;
 
 use serde_json::json;
 use surrealdb::IndexedResults;
+use surrealdb::codegen::Codegen;
+use surrealdb::method::{EndpointPolicy, RelateOutcome, Role, UpsertOutcome};
 use surrealdb::opt::auth::{Database, Namespace, Record as RecordAccess, Token};
-use surrealdb::opt::{Config, PatchOp, PatchOps, Resource};
+use surrealdb::opt::{Config, Fixture, PatchOp, PatchOps, Resource};
+#[cfg(feature = "macros")]
+use surrealdb::surql;
 use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, Value, array, object};
 use surrealdb_core::syn;
 use surrealdb_types::Array;
@@ -443,6 +447,44 @@ pub async fn query_binds(new_db: impl CreateDb) {
 	assert_eq!(record.name, "John Doe");
 }
 
+#[cfg(feature = "macros")]
+pub async fn query_surql_template(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let name = "John Doe'; REMOVE TABLE user; --".to_string();
+	let age = 41;
+	let mut response = db
+		.query(surql!("CREATE user:john CONTENT {{ name: {name.clone()}, age: {age + 1} }}"))
+		.query(surql!("SELECT VALUE age FROM user WHERE name = {name}"))
+		.await
+		.unwrap();
+	let Some(record): Option<RecordName> = response.take(0).unwrap() else {
+		panic!("query returned no record");
+	};
+	// The interpolated value is bound as a parameter, not spliced into the query
+	assert_eq!(record.name, "John Doe'; REMOVE TABLE user; --");
+	let ages: Vec<i64> = response.take(1).unwrap();
+	assert_eq!(ages, vec![42]);
+	// The parameters of chained queries do not replace each other
+	let mut response = db
+		.query(surql!("RETURN {1}"))
+		.query(surql!("RETURN {2}"))
+		.query(surql!("RETURN '{{ {3} }}'"))
+		.await
+		.unwrap();
+	let one: Option<i64> = response.take(0).unwrap();
+	let two: Option<i64> = response.take(1).unwrap();
+	assert_eq!((one, two), (Some(1), Some(2)));
+	// Braces inside strings are not interpolated
+	let text: Option<String> = response.take(2).unwrap();
+	assert_eq!(text.as_deref(), Some("{{ {3} }}"));
+	let query = surql!("SELECT * FROM user WHERE age > {age}");
+	assert!(query.query().starts_with("SELECT * FROM user WHERE age > $_surql_"));
+	assert_eq!(query.variables().len(), 1);
+}
+
 pub async fn query_with_stats(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -457,9 +499,87 @@ pub async fn query_with_stats(new_db: impl CreateDb) {
 	// Second query statement
 	let (stats, result) = response.take(1).unwrap();
 	assert!(stats.execution_time > Some(Duration::ZERO));
+	assert_eq!(stats.rows_returned, 1);
+	assert!(stats.table_scan);
 	let _: Vec<ApiRecordId> = result.unwrap();
 }
 
+pub async fn query_stats_report_index_usage(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "
+		DEFINE INDEX email ON user FIELDS email UNIQUE;
+		CREATE user:1 SET email = 'a@example.com';
+		CREATE user:2 SET email = 'b@example.com';
+		SELECT * FROM user WHERE email = 'b@example.com';
+		SELECT * FROM user WHERE name = 'b';
+	";
+	let response = db.query(sql).await.unwrap();
+	let stats = response.take_stats(3).unwrap();
+	assert_eq!(stats.indexes_used, vec!["email".to_string()]);
+	assert!(!stats.table_scan);
+	assert_eq!(stats.rows_scanned, 1);
+	assert_eq!(stats.rows_returned, 1);
+	let stats = response.take_stats(4).unwrap();
+	assert!(stats.indexes_used.is_empty());
+	assert!(stats.table_scan);
+	assert_eq!(stats.rows_scanned, 2);
+	assert_eq!(stats.rows_returned, 0);
+	assert!(response.take_stats(5).is_none());
+}
+
+pub async fn query_iter_stats(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "
+		CREATE user:1, user:2, user:3;
+		SELECT * FROM user:1;
+		SELECT * FROM user;
+	";
+	let response = db.query(sql).await.unwrap();
+	let stats: Vec<_> = response.iter_stats().collect();
+	assert_eq!(stats.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![0, 1, 2]);
+	assert_eq!(stats[1].1.rows_returned, 1);
+	assert_eq!(stats[2].1.rows_returned, 3);
+	// The estimate is an upper bound on the rows returned
+	for (_, stats) in &stats {
+		assert!(stats.max_rows.is_none_or(|rows| rows >= stats.rows_returned));
+	}
+	// The results are left in the response
+	assert_eq!(response.num_statements(), 3);
+}
+
+pub async fn query_partial_results(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "
+		CREATE |user:1..10|;
+		SELECT * FROM user TIMEOUT 0ns RETURN PARTIAL;
+		SELECT * FROM user TIMEOUT 1m RETURN PARTIAL;
+		RETURN (SELECT * FROM user TIMEOUT 0ns RETURN PARTIAL);
+	";
+	let mut response = db.query(sql).await.unwrap();
+	assert!(!response.is_partial(0));
+	// The timed out statement returns the rows found so far, rather than an error
+	assert!(response.is_partial(1));
+	assert!(response.take_stats(1).unwrap().truncated);
+	let users: Vec<Value> = response.take(1).unwrap();
+	assert!(users.len() < 10);
+	// The statement which finished in time returns every row
+	assert!(!response.is_partial(2));
+	let users: Vec<Value> = response.take(2).unwrap();
+	assert_eq!(users.len(), 10);
+	// A timed out subquery does not mark the statement which contains it
+	assert!(!response.is_partial(3));
+	assert!(!response.take_stats(3).unwrap().truncated);
+}
+
 pub async fn query_chaining(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -659,6 +779,41 @@ pub async fn insert_thing(new_db: impl CreateDb) {
 	);
 }
 
+pub async fn insert_on_duplicate(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let count = async || -> Option<i64> {
+		db.query("RETURN views:home.count").await.unwrap().take(0).unwrap()
+	};
+	for _ in 0..3 {
+		let _: Vec<Value> = db
+			.insert("views")
+			.on_duplicate(|u| u.increment("count", 1))
+			.content(object! { id: "home", count: 1 })
+			.await
+			.unwrap();
+	}
+	assert_eq!(count().await, Some(3));
+	// Expressions can refer to the record which was to be inserted
+	let _: Option<Value> = db
+		.insert(("views", "home"))
+		.on_duplicate(|u| u.set("count", "count + $input.count"))
+		.content(object! { count: 10 })
+		.await
+		.unwrap();
+	assert_eq!(count().await, Some(13));
+	// A record id is enough to update an existing record
+	let _: Option<Value> =
+		db.insert(("views", "home")).on_duplicate(|u| u.decrement("count", 3)).await.unwrap();
+	assert_eq!(count().await, Some(10));
+	// There has to be something to update
+	let res: Result<Option<Value>, _> =
+		db.insert(("views", "home")).on_duplicate(|u| u).content(object! { count: 1 }).await;
+	res.unwrap_err();
+}
+
 pub async fn insert_relation_table(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -750,6 +905,76 @@ pub async fn binding_edges(new_db: impl CreateDb) {
 	);
 }
 
+pub async fn traverse(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "
+		CREATE person:1, person:2, person:3;
+		CREATE post:one SET name = 'one';
+		CREATE post:two SET name = 'two';
+		RELATE person:1->likes->person:2;
+		RELATE person:1->likes->person:3;
+		RELATE person:2->authored->post:one;
+		RELATE person:3->authored->post:two;
+	";
+	db.query(sql).await.unwrap().check().unwrap();
+	let mut posts: Vec<RecordBuf> =
+		db.traverse("person:1").out("likes").out("authored").await.unwrap();
+	posts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+	assert_eq!(
+		posts,
+		vec![
+			RecordBuf {
+				id: rid!("post:one"),
+				name: "one".to_owned(),
+			},
+			RecordBuf {
+				id: rid!("post:two"),
+				name: "two".to_owned(),
+			},
+		]
+	);
+	let posts: Vec<RecordBuf> =
+		db.traverse(("person", 1)).out("likes").out("authored").limit(1).await.unwrap();
+	assert_eq!(posts.len(), 1);
+	let people: Vec<ApiRecordId> = db.traverse("post:one").in_("authored").await.unwrap();
+	assert_eq!(people.len(), 1);
+	assert_eq!(people[0].id, RecordId::new("person", 2));
+	// Capture the path taken to each post
+	let mut paths = db
+		.traverse::<RecordBuf>("person:1")
+		.out("likes")
+		.out("authored")
+		.with_paths()
+		.await
+		.unwrap();
+	paths.sort_by(|a, b| a.record.partial_cmp(&b.record).unwrap());
+	assert_eq!(paths.len(), 2);
+	assert_eq!(
+		paths[0].path,
+		vec![RecordId::new("person", 1), RecordId::new("person", 2), rid!("post:one")]
+	);
+	assert_eq!(
+		paths[1].path,
+		vec![RecordId::new("person", 1), RecordId::new("person", 3), rid!("post:two")]
+	);
+	// The number of paths is limited on the server
+	let paths = db
+		.traverse::<RecordBuf>("person:1")
+		.out("likes")
+		.out("authored")
+		.with_paths()
+		.limit(1)
+		.await
+		.unwrap();
+	assert_eq!(paths.len(), 1);
+	assert_eq!(paths[0].path.len(), 3);
+	// Record ids are validated before anything is sent
+	db.traverse::<Value>("person").out("likes").await.unwrap_err();
+}
+
 pub async fn select_table(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -818,6 +1043,29 @@ pub async fn select_record_ranges(new_db: impl CreateDb) {
 	assert_eq!(array.len(), 2);
 }
 
+pub async fn select_with_total(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let table = "user";
+	for name in ["amos", "jane", "john", "zoey"] {
+		let _: Option<ApiRecordId> = db.create((table, name)).await.unwrap();
+	}
+	let users: Vec<ApiRecordId> = db.select(table).start(1).limit(2).await.unwrap();
+	assert_eq!(users.len(), 2);
+	let page = db.select::<Vec<ApiRecordId>>(table).start(1).limit(2).with_total().await.unwrap();
+	assert_eq!(page.items.len(), 2);
+	assert_eq!(page.items[0].id, rid!("user:jane"));
+	assert_eq!(page.total, 4);
+	let page = db.select::<Vec<ApiRecordId>>(table).range("jane"..).with_total().await.unwrap();
+	assert_eq!(page.items.len(), 3);
+	assert_eq!(page.total, 3);
+	let page = db.select::<Vec<ApiRecordId>>("empty").limit(2).with_total().await.unwrap();
+	assert!(page.items.is_empty());
+	assert_eq!(page.total, 0);
+}
+
 pub async fn select_records_order_by_start_limit(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -1360,6 +1608,42 @@ pub async fn upsert_patch_record_id(new_db: impl CreateDb) {
 	);
 }
 
+pub async fn upsert_with_outcome(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	// The first upsert creates the record
+	let (outcome, record): (_, Option<RecordName>) = db
+		.upsert(("user", "john"))
+		.content(RecordName {
+			name: "John".to_owned(),
+		})
+		.with_outcome()
+		.await
+		.unwrap();
+	assert_eq!(outcome, UpsertOutcome::Created);
+	assert_eq!(record.unwrap().name, "John");
+	// The second upsert updates the existing record
+	let (outcome, record): (_, Option<RecordName>) = db
+		.upsert(("user", "john"))
+		.content(RecordName {
+			name: "Johnny".to_owned(),
+		})
+		.with_outcome()
+		.await
+		.unwrap();
+	assert_eq!(outcome, UpsertOutcome::Updated);
+	assert_eq!(record.unwrap().name, "Johnny");
+	// The counts are also reported in the query stats
+	let response =
+		db.query("UPSERT user:john SET age = 30; UPSERT user:jane SET age = 25").await.unwrap();
+	let stats = response.take_stats(0).unwrap();
+	assert_eq!((stats.records_created, stats.records_updated), (0, 1));
+	let stats = response.take_stats(1).unwrap();
+	assert_eq!((stats.records_created, stats.records_updated), (1, 0));
+}
+
 #[allow(clippy::disallowed_names)]
 pub async fn patch_record_id_ops(new_db: impl CreateDb) {
 	#[derive(Debug, SurrealValue, PartialEq)]
@@ -1668,6 +1952,66 @@ pub async fn changefeed(new_db: impl CreateDb) {
 	);
 }
 
+pub async fn follow_changes(new_db: impl CreateDb) {
+	use futures::StreamExt;
+	use surrealdb::method::{ExcludedTables, ExportFollowConfig, TableConfig};
+
+	let (permit, db) = new_db.create_db(Config::new()).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	db.query("DEFINE TABLE person CHANGEFEED 1h; DEFINE TABLE session CHANGEFEED 1h")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+	let since = db.version_stamp().await.unwrap();
+	db.query(
+		"
+		CREATE person:1 SET age = 42;
+		CREATE session:1;
+		UPDATE person:1 SET age = 43;
+		DELETE person:1;
+	",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+
+	let config = ExportFollowConfig {
+		tables: TableConfig::Exclude(ExcludedTables {
+			exclude: vec!["session".to_owned()],
+		}),
+		since: Some(since),
+	};
+	let changes: Vec<_> = db.follow(config).await.unwrap().take(4).collect().await;
+	let changes: Vec<_> = changes.into_iter().map(Result::unwrap).collect();
+	let statements: Vec<&str> =
+		changes.iter().flat_map(|change| &change.statements).map(String::as_str).collect();
+	assert_eq!(
+		statements,
+		[
+			"OPTION IMPORT;",
+			"UPSERT person:1 CONTENT { age: 42, id: person:1 };",
+			"UPSERT person:1 CONTENT { age: 43, id: person:1 };",
+			"DELETE person:1;",
+		]
+	);
+	// Every change can be resumed from
+	assert!(changes.windows(2).all(|w| w[0].versionstamp < w[1].versionstamp));
+
+	// The statements can be imported into another database
+	let replica = format!("{}{}", changes[0], changes[1]);
+	db.use_db(Ulid::new().to_string()).await.unwrap();
+	db.query(replica).await.unwrap().check().unwrap();
+	let mut response = db.query("SELECT VALUE age FROM ONLY person:1").await.unwrap();
+	let age: Option<i64> = response.take(0).unwrap();
+	assert_eq!(age, Some(42));
+
+	// The batch size must not be zero
+	db.follow(ExportFollowConfig::default()).batch_size(0).await.unwrap_err();
+}
+
 pub async fn version(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -1675,6 +2019,15 @@ pub async fn version(new_db: impl CreateDb) {
 	db.version().await.unwrap();
 }
 
+pub async fn compatibility(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	drop(permit);
+	let report = db.compatibility().await.unwrap();
+	assert!(report.is_compatible(), "{:?}", report.error());
+	assert_eq!(report.server_storage, Some(report.client_storage));
+}
+
 pub async fn set_unset(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -1712,6 +2065,286 @@ pub async fn return_bool(new_db: impl CreateDb) {
 	assert_eq!(value, Value::Bool(false));
 }
 
+pub async fn seed_fixtures(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	let surql =
+		Fixture::surql("CREATE user:john SET name = 'John'; CREATE user:jane SET name = 'Jane'");
+	assert!(db.seed(surql.clone()).await.unwrap());
+	// Seeding the same fixture again is a no-op
+	assert!(!db.seed(surql).await.unwrap());
+
+	let ndjson = Fixture::ndjson(
+		r#"{"table": "user", "data": {"id": "adam", "name": "Adam"}}
+{"table": "user", "data": [{"id": "eve", "name": "Eve"}]}
+"#,
+	);
+	assert!(db.seed(ndjson.clone()).await.unwrap());
+	assert!(!db.seed(ndjson).await.unwrap());
+
+	let mut response = db.query("SELECT VALUE name FROM user ORDER BY name").await.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["Adam", "Eve", "Jane", "John"]);
+
+	// A failing fixture is rolled back and not recorded
+	let failing = Fixture::surql("CREATE user:failed SET name = 'Failed'; THROW 'boom'");
+	db.seed(failing.clone()).await.unwrap_err();
+	db.seed(failing).await.unwrap_err();
+	let mut response = db.query("SELECT VALUE name FROM user:failed").await.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert!(names.is_empty());
+}
+
+pub async fn generate_records(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	db.query(
+		"
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD name ON person TYPE string;
+		DEFINE FIELD age ON person TYPE int ASSERT $value >= 500;
+		DEFINE FIELD role ON person TYPE 'admin' | 'user';
+		DEFINE FIELD address ON person TYPE object;
+		DEFINE FIELD address.city ON person TYPE string;
+		DEFINE FIELD tags ON person TYPE array<string, 2>;
+		DEFINE FIELD nickname ON person TYPE option<string>;
+		DEFINE FIELD created ON person VALUE time::now() READONLY;
+		DEFINE FIELD email ON person TYPE string;
+		DEFINE INDEX email ON person FIELDS email UNIQUE;
+		",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+
+	let ids = db.generate("person", 150).await.unwrap();
+	assert_eq!(ids.len(), 150);
+
+	let mut response = db
+		.query("SELECT VALUE count() FROM person GROUP ALL")
+		.query(
+			"SELECT VALUE count() FROM person
+			WHERE age >= 500 AND role IN ['admin', 'user'] AND array::len(tags) == 2
+			GROUP ALL",
+		)
+		.query("array::len(array::distinct(SELECT VALUE email FROM person))")
+		.await
+		.unwrap();
+	let total: Option<i64> = response.take(0).unwrap();
+	assert_eq!(total, Some(150));
+	let valid: Option<i64> = response.take(1).unwrap();
+	assert_eq!(valid, Some(150));
+	let emails: Option<i64> = response.take(2).unwrap();
+	assert_eq!(emails, Some(150));
+
+	// Types which can not be generated are reported
+	db.query("DEFINE FIELD location ON person TYPE geometry<polygon>")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+	db.generate("person", 1).await.unwrap_err();
+}
+
+pub async fn version_stamp(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	let before = db.version_stamp().await.unwrap();
+	let response = db
+		.query("CREATE user:john SET name = 'John' RETURN NONE; -- trailing comment")
+		.with_version_stamp()
+		.await
+		.unwrap();
+	// The versionstamp statement is not part of the results
+	assert_eq!(response.num_statements(), 1);
+	let during = response.version_stamp().unwrap();
+	assert!(during > before);
+	assert!(db.version_stamp().await.unwrap() > during);
+	// Without opting in, no versionstamp is returned
+	let response = db.query("RETURN 1").await.unwrap();
+	assert_eq!(response.version_stamp(), None);
+}
+
+pub async fn relate_many(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	db.query("CREATE person:tobie, person:jaime").await.unwrap().check().unwrap();
+	let tobie = RecordId::new("person", "tobie");
+	let jaime = RecordId::new("person", "jaime");
+	let ghost = RecordId::new("person", "ghost");
+	let edges = || {
+		vec![
+			(tobie.clone(), "knows", jaime.clone(), Some(object! { since: 2020 }.into_value())),
+			(jaime.clone(), "knows", ghost.clone(), None),
+			(jaime.clone(), "likes", tobie.clone(), None),
+		]
+	};
+	// Missing endpoints fail the whole transaction by default
+	let outcomes = db.relate_many(edges()).await.unwrap();
+	assert_eq!(outcomes.len(), 3);
+	assert!(outcomes.iter().all(|o| matches!(o, RelateOutcome::Failed(_))));
+	// Smaller chunks limit the edges which are rolled back
+	let outcomes = db.relate_many(edges()).chunk_size(1).await.unwrap();
+	assert!(matches!(outcomes[0], RelateOutcome::Created(_)));
+	assert!(matches!(outcomes[1], RelateOutcome::Failed(_)));
+	assert!(matches!(outcomes[2], RelateOutcome::Created(_)));
+	// Missing endpoints can be skipped instead
+	let outcomes = db.relate_many(edges()).endpoints(EndpointPolicy::Skip).await.unwrap();
+	assert!(matches!(outcomes[1], RelateOutcome::Skipped));
+	let id = outcomes[0].id().unwrap().clone();
+	let mut response =
+		db.query("SELECT VALUE [in, out, since] FROM ONLY $id").bind(("id", id)).await.unwrap();
+	let edge: Option<Value> = response.take(0).unwrap();
+	assert_eq!(edge, Some(array![tobie.clone(), jaime.clone(), 2020].into_value()));
+	let mut response = db.query("count(SELECT * FROM knows, likes)").await.unwrap();
+	let count: Option<i64> = response.take(0).unwrap();
+	assert_eq!(count, Some(4));
+}
+
+pub async fn table_stats(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	db.table_stats("person").await.unwrap_err();
+	db.query("DEFINE TABLE person; DEFINE INDEX name ON person FIELDS name")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+	let stats = db.table_stats("person").await.unwrap();
+	assert_eq!(stats.rows, 0);
+	assert_eq!(stats.versionstamp, None);
+	let before = db.version_stamp().await.unwrap();
+	db.query("CREATE person:one SET name = 'Tobie'; CREATE person:two SET name = 'Jaime'")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+	let stats = db.table_stats("person").await.unwrap();
+	assert_eq!(stats.rows, 2);
+	assert!(stats.bytes > 0);
+	assert_eq!(stats.indexes.get("name"), Some(&2));
+	assert!(stats.versionstamp.unwrap() > before);
+	// Updates change the size, and deletes the number of records
+	db.query("UPDATE person:one SET name = 'Tobie Morgan Hitchcock'; DELETE person:two")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+	let after = db.table_stats("person").await.unwrap();
+	assert_eq!(after.rows, 1);
+	assert_eq!(after.indexes.get("name"), Some(&1));
+	assert!(after.versionstamp > stats.versionstamp);
+}
+
+pub async fn user_management(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	// A user can not be created without a password
+	db.users().create("tobie").role(Role::Editor).await.unwrap_err();
+	let user = db
+		.users()
+		.create("tobie")
+		.password("secret")
+		.role(Role::Editor)
+		.comment("Maintains the schema")
+		.await
+		.unwrap();
+	assert_eq!(user.name, "tobie");
+	assert_eq!(user.roles, vec![Role::Editor]);
+	assert_eq!(user.comment.as_deref(), Some("Maintains the schema"));
+	// Users can not be defined twice
+	db.users().create("tobie").password("secret").await.unwrap_err();
+	let user = db.users().update_roles("tobie", [Role::Owner, Role::Viewer]).await.unwrap();
+	assert_eq!(user.roles, vec![Role::Owner, Role::Viewer]);
+	assert_eq!(db.info_for_user("tobie").await.unwrap(), user);
+	let users = db.users().list().await.unwrap();
+	assert_eq!(users, vec![user]);
+	db.users().delete("tobie").await.unwrap();
+	assert!(db.users().list().await.unwrap().is_empty());
+	db.info_for_user("tobie").await.unwrap_err();
+}
+
+pub async fn run_query_template(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	db.query(
+		"
+		CREATE user:john SET name = 'John', age = 30;
+		CREATE user:adam SET name = 'Adam', age = 20;
+		DEFINE QUERY older_than($age: int) AS SELECT VALUE name FROM user WHERE age > $age ORDER BY name;
+		",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+	let names: Vec<String> = db.run_query("older_than").arg("age", 25).await.unwrap();
+	assert_eq!(names, vec!["John".to_owned()]);
+	let names: Vec<String> = db.run_query("older_than").arg("age", 10).await.unwrap();
+	assert_eq!(names, vec!["Adam".to_owned(), "John".to_owned()]);
+	// Arguments are coerced to the kind they were declared with
+	db.run_query::<Vec<String>>("older_than").arg("age", "old").await.unwrap_err();
+	// Unknown arguments and queries are rejected
+	db.run_query::<Vec<String>>("older_than").arg("name", "John").await.unwrap_err();
+	db.run_query::<Vec<String>>("younger_than").arg("age", 25).await.unwrap_err();
+}
+
+pub async fn codegen(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	db.query(
+		"
+		DEFINE TABLE person SCHEMAFULL COMMENT 'A person';
+		DEFINE FIELD name ON person TYPE string;
+		DEFINE FIELD nickname ON person TYPE option<string>;
+		DEFINE FIELD role ON person TYPE 'admin' | 'member';
+		DEFINE FIELD employer ON person TYPE option<record<company>>;
+		DEFINE TABLE works_at TYPE RELATION FROM person TO company;
+		",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+	let code = Codegen::from_connection(&db).await.unwrap().generate();
+	assert!(code.contains("/// A person\n"), "{code}");
+	assert!(
+		code.contains("pub struct Person {\n\tpub id: surrealdb::types::RecordId,\n"),
+		"{code}"
+	);
+	assert!(code.contains("\tpub employer: Option<surrealdb::types::RecordId>,\n"), "{code}");
+	assert!(code.contains("\tpub nickname: Option<String>,\n"), "{code}");
+	assert!(code.contains("\tpub role: PersonRole,\n"), "{code}");
+	assert!(code.contains("pub enum PersonRole {"), "{code}");
+	assert!(code.contains("pub struct WorksAt {"), "{code}");
+	assert!(code.contains("\tpub r#in: surrealdb::types::RecordId,\n"), "{code}");
+}
+
 pub async fn multi_take(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -1839,6 +2472,47 @@ pub async fn client_side_transactions(_new_db: impl CreateDb) {
 	// Client-side transactions are not supported on HTTP
 }
 
+#[cfg(not(feature = "protocol-http"))]
+pub async fn scan_table(new_db: impl CreateDb) {
+	use futures::StreamExt;
+
+	let (permit, db) = new_db.create_db(Config::new()).await;
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+	db.query(
+		"FOR $i IN 1..=25 { CREATE type::record('person', $i) SET name = 'Person ' + <string> $i }",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+	drop(permit);
+
+	let mut people = db.scan::<RecordBuf>("person").batch_size(10).await.unwrap();
+	let first = people.next().await.unwrap().unwrap();
+	assert_eq!(first.id, RecordId::new("person", 1_i64));
+	// Records written during the scan are not seen by it
+	db.query("CREATE person:100 SET name = 'Person 100'").await.unwrap().check().unwrap();
+	let mut ids = vec![first.id];
+	while let Some(person) = people.next().await {
+		ids.push(person.unwrap().id);
+	}
+	let expected: Vec<RecordId> = (1..=25_i64).map(|i| RecordId::new("person", i)).collect();
+	assert_eq!(ids, expected);
+
+	// A new scan sees them
+	let people: Vec<RecordBuf> =
+		db.scan::<RecordBuf>("person").await.unwrap().map(Result::unwrap).collect().await;
+	assert_eq!(people.len(), 26);
+
+	// The batch size must not be zero
+	db.scan::<RecordBuf>("person").batch_size(0).await.unwrap_err();
+}
+
+#[cfg(feature = "protocol-http")]
+pub async fn scan_table(_new_db: impl CreateDb) {
+	// Scans are read within a client-side transaction, which HTTP does not support
+}
+
 pub async fn refresh_tokens(new_db: impl CreateDb) {
 	let config = Config::new();
 	let (permit, db) = new_db.create_db(config).await;
@@ -1935,9 +2609,18 @@ define_include_tests!(basic => {
 	query_decimals,
 	#[test_log::test(tokio::test)]
 	query_binds,
+	#[cfg(feature = "macros")]
+	#[test_log::test(tokio::test)]
+	query_surql_template,
 	#[test_log::test(tokio::test)]
 	query_with_stats,
 	#[test_log::test(tokio::test)]
+	query_stats_report_index_usage,
+	#[test_log::test(tokio::test)]
+	query_iter_stats,
+	#[test_log::test(tokio::test)]
+	query_partial_results,
+	#[test_log::test(tokio::test)]
 	query_chaining,
 	#[test_log::test(tokio::test)]
 	mixed_results_query,
@@ -1956,16 +2639,22 @@ define_include_tests!(basic => {
 	#[test_log::test(tokio::test)]
 	insert_thing,
 	#[test_log::test(tokio::test)]
+	insert_on_duplicate,
+	#[test_log::test(tokio::test)]
 	insert_relation_table,
 	#[test_log::test(tokio::test)]
 	binding_edges,
 	#[test_log::test(tokio::test)]
+	traverse,
+	#[test_log::test(tokio::test)]
 	select_table,
 	#[test_log::test(tokio::test)]
 	select_record_id,
 	#[test_log::test(tokio::test)]
 	select_record_ranges,
 	#[test_log::test(tokio::test)]
+	select_with_total,
+	#[test_log::test(tokio::test)]
 	select_records_order_by_start_limit,
 	#[test_log::test(tokio::test)]
 	select_records_order_by,
@@ -1990,6 +2679,8 @@ define_include_tests!(basic => {
 	#[test_log::test(tokio::test)]
 	upsert_patch_record_id,
 	#[test_log::test(tokio::test)]
+	upsert_with_outcome,
+	#[test_log::test(tokio::test)]
 	patch_record_id_ops,
 	#[test_log::test(tokio::test)]
 	delete_table,
@@ -2000,17 +2691,39 @@ define_include_tests!(basic => {
 	#[test_log::test(tokio::test)]
 	changefeed,
 	#[test_log::test(tokio::test)]
+	follow_changes,
+	#[test_log::test(tokio::test)]
 	version,
 	#[test_log::test(tokio::test)]
+	compatibility,
+	#[test_log::test(tokio::test)]
 	set_unset,
 	#[test_log::test(tokio::test)]
 	return_bool,
 	#[test_log::test(tokio::test)]
+	seed_fixtures,
+	#[test_log::test(tokio::test)]
+	generate_records,
+	#[test_log::test(tokio::test)]
+	version_stamp,
+	#[test_log::test(tokio::test)]
+	relate_many,
+	#[test_log::test(tokio::test)]
+	table_stats,
+	#[test_log::test(tokio::test)]
+	user_management,
+	#[test_log::test(tokio::test)]
+	run_query_template,
+	#[test_log::test(tokio::test)]
+	codegen,
+	#[test_log::test(tokio::test)]
 	multi_take,
 	#[test_log::test(tokio::test)]
 	field_and_index_methods,
 	#[test_log::test(tokio::test)]
 	client_side_transactions,
 	#[test_log::test(tokio::test)]
+	scan_table,
+	#[test_log::test(tokio::test)]
 	refresh_tokens,
 });
@@ -14,10 +14,10 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use futures::{Stream, StreamExt};
-use surrealdb::method::QueryStream;
+use surrealdb::method::{QueryStream, SchemaAction, SchemaKind};
 use surrealdb::opt::{Config, Resource};
 use surrealdb::types::{Action, RecordId, SurrealValue, Value, object};
-use surrealdb::{Notification, Result};
+use surrealdb::{Notification, NotificationError, Result};
 use tokio::sync::RwLock;
 use tracing::info;
 use ulid::Ulid;
@@ -603,6 +603,221 @@ pub async fn live_select_returns_uuid(new_db: impl CreateDb) {
 	drop(permit);
 }
 
+pub async fn live_select_filter_session_param(new_db: impl CreateDb) {
+	#[derive(Debug, SurrealValue)]
+	struct Message {
+		room: String,
+	}
+
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let table = format!("table_{}", Ulid::new());
+	db.query(format!("DEFINE TABLE {table}")).await.unwrap();
+
+	// Start listening to a single room
+	let mut messages =
+		db.select(&table).live().filter("room = $room").bind(("room", "a")).await.unwrap();
+
+	// Only messages in the bound room are received
+	db.query(format!("CREATE {table} SET room = 'b'; CREATE {table} SET room = 'a'"))
+		.await
+		.unwrap();
+	let notification: Notification<Message> =
+		tokio::time::timeout(LQ_TIMEOUT, messages.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(notification.data.room, "a");
+
+	// The session parameter is resolved when each notification is sent
+	db.set("room", "b").await.unwrap();
+	db.query(format!("CREATE {table} SET room = 'a'; CREATE {table} SET room = 'b'"))
+		.await
+		.unwrap();
+	let notification: Notification<Message> =
+		tokio::time::timeout(LQ_TIMEOUT, messages.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(notification.data.room, "b");
+
+	// A filter which is not a single expression is rejected
+	let filter = format!("true; REMOVE TABLE {table}");
+	let res = db.select::<Vec<Message>>(&table).live().filter(filter).await;
+	res.unwrap_err();
+	db.query(format!("INFO FOR TABLE {table}")).await.unwrap().check().unwrap();
+
+	drop(permit);
+}
+
+pub async fn live_handle_lifecycle(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let table = format!("table_{}", Ulid::new());
+	db.query(format!("DEFINE TABLE {table}")).await.unwrap();
+
+	let mut handle = db.select(&table).live().await.unwrap();
+	assert_eq!(handle.lag(), 0);
+
+	// Notifications which are not read yet are counted as lag
+	db.query(format!("CREATE {table}; CREATE {table}")).await.unwrap().check().unwrap();
+	tokio::time::timeout(LQ_TIMEOUT, async {
+		while handle.lag() < 2 {
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	})
+	.await
+	.unwrap();
+
+	let notification: Notification<Value> = handle.next().await.unwrap().unwrap();
+	assert_eq!(notification.query_id, handle.id());
+	assert_eq!(handle.lag(), 1);
+
+	// Killing the handle waits for the live query to be stopped
+	handle.kill().await.unwrap();
+
+	drop(permit);
+}
+
+pub async fn live_subscription_killed(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let table = format!("table_{}", Ulid::new());
+	db.query(format!("DEFINE TABLE {table}")).await.unwrap();
+
+	let mut subscription = db.select(&table).live().await.unwrap().subscription();
+
+	db.query(format!("CREATE {table}")).await.unwrap().check().unwrap();
+	let notification: Notification<Value> =
+		tokio::time::timeout(LQ_TIMEOUT, subscription.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(notification.action, Action::Create);
+
+	// Removing the table kills the live query, which ends the subscription
+	db.query(format!("REMOVE TABLE {table}")).await.unwrap().check().unwrap();
+	let error = tokio::time::timeout(LQ_TIMEOUT, subscription.next()).await.unwrap().unwrap();
+	assert_eq!(
+		error.unwrap_err(),
+		NotificationError::Killed {
+			reason: Some("The table was removed".to_owned()),
+		}
+	);
+	assert!(subscription.next().await.is_none());
+
+	drop(permit);
+}
+
+pub async fn live_schema_changes(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let mut changes = db.schema_changes().stream().await.unwrap();
+
+	db.query("DEFINE TABLE person; DEFINE FIELD name ON person TYPE string")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+	let change = tokio::time::timeout(LQ_TIMEOUT, changes.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(change.action, SchemaAction::Define);
+	assert_eq!(change.kind, SchemaKind::Table);
+	assert_eq!(change.name, "person");
+	assert_eq!(change.table, None);
+	let change = tokio::time::timeout(LQ_TIMEOUT, changes.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(change.action, SchemaAction::Define);
+	assert_eq!(change.kind, SchemaKind::Field);
+	assert_eq!(change.name, "name");
+	assert_eq!(change.table.as_deref(), Some("person"));
+
+	// Changes are only sent once the transaction commits
+	db.query("BEGIN; DEFINE INDEX name ON person FIELDS name; CANCEL").await.unwrap();
+	db.query("ALTER TABLE person COMMENT 'people'").await.unwrap().check().unwrap();
+	let change = tokio::time::timeout(LQ_TIMEOUT, changes.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(change.action, SchemaAction::Alter);
+	assert_eq!(change.kind, SchemaKind::Table);
+
+	db.query("REMOVE TABLE person").await.unwrap().check().unwrap();
+	let change = tokio::time::timeout(LQ_TIMEOUT, changes.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(change.action, SchemaAction::Remove);
+	assert_eq!(change.kind, SchemaKind::Table);
+	assert_eq!(change.name, "person");
+
+	drop(permit);
+}
+
+pub async fn live_param_changes(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let mut params = db.live_params().stream().await.unwrap();
+
+	// Changes to the rest of the schema are skipped
+	db.query("DEFINE TABLE person; DEFINE PARAM $flag VALUE true").await.unwrap().check().unwrap();
+	let change = tokio::time::timeout(LQ_TIMEOUT, params.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(change.action, SchemaAction::Define);
+	assert_eq!(change.name, "flag");
+	assert_eq!(change.value, Some(Value::Bool(true)));
+
+	db.query("DEFINE PARAM OVERWRITE $flag VALUE false").await.unwrap().check().unwrap();
+	let change = tokio::time::timeout(LQ_TIMEOUT, params.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(change.action, SchemaAction::Alter);
+	assert_eq!(change.value, Some(Value::Bool(false)));
+
+	db.query("REMOVE PARAM $flag").await.unwrap().check().unwrap();
+	let change = tokio::time::timeout(LQ_TIMEOUT, params.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(change.action, SchemaAction::Remove);
+	assert_eq!(change.name, "flag");
+	assert_eq!(change.value, None);
+
+	drop(permit);
+}
+
+pub async fn live_notifications_broadcast(new_db: impl CreateDb) {
+	let config = Config::new().notifications_broadcast_capacity(1);
+	let (permit, db) = new_db.create_db(config).await;
+
+	// Only the local engines broadcast notifications
+	let Some(mut first) = db.notifications_broadcast() else {
+		drop(permit);
+		return;
+	};
+	let mut second = db.notifications_broadcast().unwrap();
+
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let table = format!("table_{}", Ulid::new());
+	db.query(format!("DEFINE TABLE {table}")).await.unwrap();
+	let _stream = db.select(Resource::from(&table)).live().await.unwrap();
+
+	// Every receiver gets every notification
+	db.query(format!("CREATE {table}:one")).await.unwrap().check().unwrap();
+	for receiver in [&mut first, &mut second] {
+		let notification =
+			tokio::time::timeout(LQ_TIMEOUT, receiver.recv()).await.unwrap().unwrap();
+		assert_eq!(notification.action, Action::Create);
+		assert_eq!(notification.record, RecordId::new(table.as_str(), "one").into_value());
+	}
+
+	// A receiver which falls behind skips the oldest notifications
+	db.query(format!("CREATE {table}:two")).await.unwrap().check().unwrap();
+	let notification = tokio::time::timeout(LQ_TIMEOUT, second.recv()).await.unwrap().unwrap();
+	assert_eq!(notification.record, RecordId::new(table.as_str(), "two").into_value());
+	db.query(format!("CREATE {table}:three")).await.unwrap().check().unwrap();
+	let notification = tokio::time::timeout(LQ_TIMEOUT, second.recv()).await.unwrap().unwrap();
+	assert_eq!(notification.record, RecordId::new(table.as_str(), "three").into_value());
+	assert!(matches!(first.recv().await, Err(tokio::sync::broadcast::error::RecvError::Lagged(1))));
+	let notification = first.recv().await.unwrap();
+	assert_eq!(notification.record, RecordId::new(table.as_str(), "three").into_value());
+
+	drop(permit);
+}
+
 define_include_tests!(live => {
 	#[test_log::test(tokio::test)]
 	live_select_table,
@@ -618,4 +833,16 @@ define_include_tests!(live => {
 	live_query_delete_notifications,
 	#[test_log::test(tokio::test)]
 	live_select_returns_uuid,
+	#[test_log::test(tokio::test)]
+	live_select_filter_session_param,
+	#[test_log::test(tokio::test)]
+	live_handle_lifecycle,
+	#[test_log::test(tokio::test)]
+	live_subscription_killed,
+	#[test_log::test(tokio::test)]
+	live_schema_changes,
+	#[test_log::test(tokio::test)]
+	live_param_changes,
+	#[test_log::test(tokio::test)]
+	live_notifications_broadcast,
 });
@@ -150,7 +150,9 @@ mod http {
 	use surrealdb::engine::remote::http::{Client, Http};
 	use surrealdb::opt::Config;
 	use surrealdb::opt::auth::Root;
+	use surrealdb::types::Value;
 	use tokio::sync::{Semaphore, SemaphorePermit};
+	use ulid::Ulid;
 
 	use super::{ROOT_PASS, ROOT_USER};
 
@@ -175,6 +177,40 @@ mod http {
 		drop(permit);
 	}
 
+	#[test_log::test(tokio::test)]
+	async fn large_results_are_compressed() {
+		let (permit, db) = new_db(Config::new()).await;
+		db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+		db.query("FOR $i IN 0..1000 { CREATE person SET name = 'Tobie', index = $i }")
+			.await
+			.unwrap()
+			.check()
+			.unwrap();
+		let before = db.compression_stats().unwrap();
+		let people: Vec<Value> = db.query("SELECT * FROM person").await.unwrap().take(0).unwrap();
+		assert_eq!(people.len(), 1000);
+		let after = db.compression_stats().unwrap();
+		assert!(after.responses > before.responses);
+		assert!(after.bytes_saved() > before.bytes_saved());
+		drop(permit);
+
+		// Responses are read as they are when compression is disabled
+		let (permit, db) = new_db(Config::new().http_compression(false)).await;
+		assert_eq!(db.compression_stats(), None);
+		drop(permit);
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn warm_connections_measure_latency() {
+		let (permit, db) = new_db(Config::new()).await;
+		assert_eq!(db.latency(), None);
+		drop(permit);
+
+		let (permit, db) = new_db(Config::new().warm_connections(4)).await;
+		assert!(db.latency().is_some());
+		drop(permit);
+	}
+
 	include_tests!(new_db => basic, serialisation, backup, session_isolation, run);
 }
 
@@ -186,6 +222,7 @@ mod http {
 mod ws {
 	use std::pin::pin;
 	use std::task::Poll;
+	use std::time::Duration;
 
 	use futures::poll;
 	use surrealdb::Surreal;
@@ -212,6 +249,18 @@ mod ws {
 		(permit, db)
 	}
 
+	#[test_log::test(tokio::test)]
+	async fn keepalive_pings_measure_latency() {
+		let (permit, db) = new_db(Config::new()).await;
+		assert_eq!(db.latency(), None);
+		drop(permit);
+
+		let (permit, db) = new_db(Config::new().keepalive(Duration::from_millis(100))).await;
+		tokio::time::sleep(Duration::from_millis(500)).await;
+		assert!(db.latency().is_some());
+		drop(permit);
+	}
+
 	#[test_log::test(tokio::test)]
 	async fn any_engine_can_connect() {
 		let permit = PERMITS.acquire().await.unwrap();
@@ -512,11 +561,245 @@ mod mem {
 		db.query(surql).await.unwrap().check().unwrap();
 	}
 
+	#[test_log::test(tokio::test)]
+	async fn impersonation() {
+		use surrealdb::opt::auth::{RecordUser, SystemUser};
+
+		let record_user = || RecordUser {
+			namespace: "test".to_string(),
+			database: "test".to_string(),
+			access: "user".to_string(),
+			id: RecordId::new("user", "one"),
+		};
+		// Impersonation is rejected by default
+		let (permit, db) = new_db(Config::new()).await;
+		drop(permit);
+		db.impersonate(record_user()).await.unwrap_err();
+		// Impersonation can be allowed
+		let capabilities = Capabilities::new()
+			.with_experimental_feature_allowed(ExperimentalFeature::Impersonation);
+		let (permit, db) = new_db(Config::new().capabilities(capabilities)).await;
+		drop(permit);
+		db.use_ns("test").use_db("test").await.unwrap();
+		db.query(
+			"
+			DEFINE ACCESS user ON DATABASE TYPE RECORD DURATION FOR SESSION 1h;
+			DEFINE USER viewer ON DATABASE PASSWORD 'secret' ROLES VIEWER;
+			DEFINE TABLE note PERMISSIONS FOR select WHERE owner = $auth FOR create FULL;
+			CREATE note:one SET owner = user:one;
+			CREATE note:two SET owner = user:two;
+			",
+		)
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+		// A record user only sees their own notes
+		db.impersonate(record_user()).await.unwrap();
+		let notes: Vec<ApiRecordId> = db.select("note").await.unwrap();
+		assert_eq!(notes.len(), 1);
+		// Impersonation can not be nested
+		db.impersonate(record_user()).await.unwrap_err();
+		db.stop_impersonating().await.unwrap();
+		let notes: Vec<ApiRecordId> = db.select("note").await.unwrap();
+		assert_eq!(notes.len(), 2);
+		// A database viewer can not create notes
+		db.impersonate(SystemUser {
+			namespace: Some("test".to_string()),
+			database: Some("test".to_string()),
+			username: "viewer".to_string(),
+		})
+		.await
+		.unwrap();
+		db.query("CREATE note:three").await.unwrap().check().unwrap_err();
+		db.stop_impersonating().await.unwrap();
+		db.query("CREATE note:three").await.unwrap().check().unwrap();
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn query_ndjson_output() {
+		use futures::TryStreamExt;
+		use surrealdb::opt::Format;
+
+		let (permit, db) = new_db(Config::new()).await;
+		drop(permit);
+		db.use_ns("test").use_db("test").await.unwrap();
+		let lines: Vec<Vec<u8>> = db
+			.query("CREATE person:one, person:two RETURN VALUE id; THROW 'boom'")
+			.query("RETURN $value")
+			.bind(("value", 3))
+			.format(Format::Ndjson)
+			.stream_bytes()
+			.await
+			.unwrap()
+			.try_collect()
+			.await
+			.unwrap();
+		// Each row is streamed as its own line
+		let lines: Vec<String> = lines.into_iter().map(|x| String::from_utf8(x).unwrap()).collect();
+		let output = lines.concat();
+		assert_eq!(lines.len(), 4, "{output}");
+		for line in &lines {
+			assert_eq!(line.matches('\n').count(), 1, "{output}");
+			assert!(line.ends_with('\n'), "{output}");
+		}
+		let lines: Vec<&str> = lines.iter().map(|x| x.trim_end()).collect();
+		assert_eq!(lines[0], r#""person:one""#);
+		assert_eq!(lines[1], r#""person:two""#);
+		assert!(lines[2].contains(r#""status":"ERR""#), "{output}");
+		assert_eq!(lines[3], "3");
+		// A query which can not be parsed fails as a whole
+		let mut stream =
+			db.query("SELEC * FROM person").format(Format::Ndjson).stream_bytes().await.unwrap();
+		stream.try_next().await.unwrap_err();
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn idempotent_create() {
+		use surrealdb::types::Object;
+
+		let (permit, db) = new_db(Config::new()).await;
+		drop(permit);
+		db.use_ns("test").use_db("test").await.unwrap();
+		let key = "c7c1a8f4-3f2e-4d55-9a43-0a7fb6f0d7e2";
+		let content = Object::from_iter([("name".to_owned(), "Tobie".to_owned())]);
+		let first: Option<ApiRecordId> =
+			db.create("person").content(content.clone()).idempotency_key(key).await.unwrap();
+		// A retry returns the original record rather than creating another
+		let retry: Option<ApiRecordId> =
+			db.create("person").content(content).idempotency_key(key).await.unwrap();
+		assert!(first.is_some());
+		assert_eq!(first, retry);
+		let mut response = db.query("count(SELECT * FROM person)").await.unwrap();
+		let count: Option<i64> = response.take(0).unwrap();
+		assert_eq!(count, Some(1));
+		// The key can not be reused for a different mutation
+		let other: Result<Vec<ApiRecordId>, _> =
+			db.update("person").merge(Object::new()).idempotency_key(key).await;
+		other.unwrap_err();
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn lock_writes() {
+		use std::time::Duration;
+
+		let (permit, db) = new_db(Config::new()).await;
+		drop(permit);
+		db.use_ns("test").use_db("test").await.unwrap();
+		db.query("CREATE item:one").await.unwrap().check().unwrap();
+		let lock = db.lock_writes().await.unwrap();
+		// Reads continue while writes wait for the lock to be released
+		let _: Option<ApiRecordId> = db.select(("item", "one")).await.unwrap();
+		let writer = db.clone();
+		let write = tokio::spawn(async move { writer.query("CREATE item:two").await });
+		tokio::time::sleep(Duration::from_millis(100)).await;
+		assert!(!write.is_finished());
+		lock.release().await.unwrap();
+		write.await.unwrap().unwrap().check().unwrap();
+		// A lock held for too long is forcibly released
+		let lock = db.lock_writes().max_hold(Duration::from_millis(50)).await.unwrap();
+		db.query("CREATE item:three").await.unwrap().check().unwrap();
+		lock.release().await.unwrap_err();
+	}
+
+	#[test_log::test]
+	fn lock_writes_guard_dropped_outside_runtime() {
+		let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+		let (db, lock) = runtime.block_on(async {
+			let (permit, db) = new_db(Config::new()).await;
+			drop(permit);
+			db.use_ns("test").use_db("test").await.unwrap();
+			let lock = db.lock_writes().await.unwrap();
+			(db, lock)
+		});
+		// Dropping the guard outside of the runtime releases the lock
+		drop(lock);
+		runtime.block_on(async {
+			db.query("CREATE item:one").await.unwrap().check().unwrap();
+		});
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn health_report() {
+		let (permit, db) = new_db(Config::new()).await;
+		drop(permit);
+		db.health().await.unwrap();
+		let report = db.health_report().await.unwrap();
+		assert!(report.is_healthy(), "{report:?}");
+		assert!(report.readable);
+		assert_eq!(report.writable, Some(true));
+		// The write probe is skipped while the datastore is locked for writes
+		let lock = db.lock_writes().await.unwrap();
+		let report = db.health_report().await.unwrap();
+		assert!(report.is_healthy(), "{report:?}");
+		assert_eq!(report.writable, None);
+		lock.release().await.unwrap();
+	}
+
+	#[cfg(feature = "sync")]
+	#[test_log::test(tokio::test)]
+	async fn sync_between_devices() {
+		use surrealdb::sync::{SyncEngine, SyncReport};
+
+		let remote = Surreal::new::<Mem>(()).await.unwrap();
+		let mut devices = Vec::new();
+		for _ in 0..2 {
+			let local = Surreal::new::<Mem>(()).await.unwrap();
+			local.use_ns("app").use_db("app").await.unwrap();
+			// Every clone is a new session, which selects the database again
+			let engine_local = local.clone();
+			engine_local.use_ns("app").use_db("app").await.unwrap();
+			let engine_remote = remote.clone();
+			engine_remote.use_ns("app").use_db("app").await.unwrap();
+			let engine = SyncEngine::new(engine_local, engine_remote).table("task");
+			engine.setup().await.unwrap();
+			devices.push((local, engine));
+		}
+		let [(a, sync_a), (b, sync_b)] = &devices[..] else {
+			unreachable!()
+		};
+		a.query("CREATE task:1 SET title = 'from a'").await.unwrap().check().unwrap();
+		b.query("CREATE task:2 SET title = 'from b'").await.unwrap().check().unwrap();
+		assert_eq!(sync_a.sync().await.unwrap().pushed, 1);
+		let report = sync_b.sync().await.unwrap();
+		assert_eq!((report.pushed, report.pulled), (1, 1));
+		let report = sync_a.sync().await.unwrap();
+		assert_eq!((report.pushed, report.pulled), (0, 1));
+		// The changes of each device reached the other one
+		for db in [a, b] {
+			let titles: Vec<String> = db
+				.query("SELECT VALUE title FROM task ORDER BY id")
+				.await
+				.unwrap()
+				.take(0)
+				.unwrap();
+			assert_eq!(titles, ["from a", "from b"]);
+		}
+		// Changes are not synced back to the device which made them
+		assert_eq!(sync_a.sync().await.unwrap(), SyncReport::default());
+		assert_eq!(sync_b.sync().await.unwrap(), SyncReport::default());
+		// Updates keep flowing in both directions
+		b.query("UPDATE task:1 SET title = 'edited by b'").await.unwrap().check().unwrap();
+		sync_b.sync().await.unwrap();
+		assert_eq!(sync_a.sync().await.unwrap().pulled, 1);
+		let title: Option<String> =
+			a.query("SELECT VALUE title FROM ONLY task:1").await.unwrap().take(0).unwrap();
+		assert_eq!(title.as_deref(), Some("edited by b"));
+		// The remote keeps the log of every pushed operation
+		remote.use_ns("app").use_db("app").await.unwrap();
+		let logged: Vec<i64> =
+			remote.query("SELECT VALUE count() FROM __oplog").await.unwrap().take(0).unwrap();
+		assert_eq!(logged.len(), 3);
+	}
+
 	include_tests!(new_db => basic, serialisation, live, backup, session_isolation, run);
 }
 
 #[cfg(feature = "kv-rocksdb")]
 mod rocksdb {
+	use std::collections::BTreeMap;
+	use std::path::{Path, PathBuf};
+
 	use surrealdb::Surreal;
 	use surrealdb::engine::local::{Db, RocksDb};
 	use surrealdb::opt::Config;
@@ -558,6 +841,77 @@ mod rocksdb {
 		}
 	}
 
+	#[test_log::test(tokio::test)]
+	async fn compact_table() {
+		let (permit, db) = new_db(Config::new()).await;
+		drop(permit);
+		db.use_ns("test").use_db("test").await.unwrap();
+		db.query("CREATE |log:1000| CONTENT { level: 'debug' }; CREATE log:keep")
+			.await
+			.unwrap()
+			.check()
+			.unwrap();
+		db.query("DELETE log WHERE level = 'debug'").await.unwrap().check().unwrap();
+		db.maintenance().compact_table("log").await.unwrap();
+		// The records which were not deleted remain after the compaction
+		let mut response = db.query("count(SELECT * FROM log)").await.unwrap();
+		let count: Option<i64> = response.take(0).unwrap();
+		assert_eq!(count, Some(1));
+		// Tables which do not exist can not be compacted
+		db.maintenance().compact_table("missing").await.unwrap_err();
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn snapshot() {
+		let path = TEMP_DIR.join(Ulid::new().to_string());
+		let root = Root {
+			username: ROOT_USER.to_string(),
+			password: ROOT_PASS.to_string(),
+		};
+		let db = Surreal::new::<RocksDb>((path.clone(), Config::new().user(root.clone())))
+			.await
+			.unwrap();
+		db.signin(root.clone()).await.unwrap();
+		db.use_ns("test").use_db("test").await.unwrap();
+		db.query("CREATE item:one").await.unwrap().check().unwrap();
+		db.snapshot("fixture").await.unwrap();
+		// Snapshot names are unique, and can not leave the snapshots directory
+		db.snapshot("fixture").await.unwrap_err();
+		db.snapshot("../fixture").await.unwrap_err();
+		// Changes after the snapshot are not included in it
+		db.query("CREATE item:two").await.unwrap().check().unwrap();
+		let dir = PathBuf::from(format!("{}.snapshots", path.display())).join("fixture");
+		let files = read_files(&dir);
+		let config = Config::from_snapshot("fixture").user(root.clone());
+		let snapshot = Surreal::new::<RocksDb>((path.clone(), config)).await.unwrap();
+		snapshot.signin(root.clone()).await.unwrap();
+		snapshot.use_ns("test").use_db("test").await.unwrap();
+		let mut response = snapshot.query("count(SELECT * FROM item)").await.unwrap();
+		let count: Option<i64> = response.take(0).unwrap();
+		assert_eq!(count, Some(1));
+		// The snapshot is opened read-only
+		snapshot.query("CREATE item:three").await.unwrap().check().unwrap_err();
+		// Opening and querying the snapshot leaves its files untouched
+		assert_eq!(read_files(&dir), files);
+		// Snapshots which do not exist can not be opened
+		let config = Config::from_snapshot("missing").user(root);
+		Surreal::new::<RocksDb>((path, config)).await.unwrap_err();
+	}
+
+	/// Reads the contents of every file in a directory, by path
+	fn read_files(dir: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+		let mut files = BTreeMap::new();
+		for entry in std::fs::read_dir(dir).unwrap() {
+			let path = entry.unwrap().path();
+			if path.is_dir() {
+				files.extend(read_files(&path));
+			} else {
+				files.insert(path.clone(), std::fs::read(&path).unwrap());
+			}
+		}
+		files
+	}
+
 	include_tests!(new_db => basic, serialisation, live, backup, session_isolation, run);
 }
 
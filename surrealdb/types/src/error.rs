@@ -26,6 +26,11 @@ mod code {
 	pub const QUERY_TIMEDOUT: i64 = -32004;
 	pub const QUERY_CANCELLED: i64 = -32005;
 	pub const QUERY_TRANSACTION_CONFLICT: i64 = -32009;
+	pub const QUERY_THROTTLED: i64 = -32010;
+	pub const QUERY_RATE_LIMITED: i64 = -32011;
+	pub const QUERY_DEADLOCK: i64 = -32012;
+	pub const QUERY_TOO_LARGE: i64 = -32013;
+	pub const QUERY_SCAN_LIMIT: i64 = -32014;
 	pub const THROWN: i64 = -32006;
 	pub const SERIALIZATION_ERROR: i64 = -32007;
 	pub const DESERIALIZATION_ERROR: i64 = -32008;
@@ -124,6 +129,9 @@ impl Error {
 					| AuthError::NotAllowed {
 						..
 					}
+					| AuthError::MfaRequired {
+						..
+					}
 					| AuthError::InvalidSignup => code::INVALID_AUTH,
 				},
 				NotAllowedError::Method {
@@ -189,6 +197,21 @@ impl Error {
 				} => code::QUERY_TIMEDOUT,
 				QueryError::Cancelled => code::QUERY_CANCELLED,
 				QueryError::TransactionConflict => code::QUERY_TRANSACTION_CONFLICT,
+				QueryError::ThrottleExceeded {
+					..
+				} => code::QUERY_THROTTLED,
+				QueryError::RateLimited {
+					..
+				} => code::QUERY_RATE_LIMITED,
+				QueryError::DeadlockDetected {
+					..
+				} => code::QUERY_DEADLOCK,
+				QueryError::TooLarge {
+					..
+				} => code::QUERY_TOO_LARGE,
+				QueryError::ScanLimitExceeded {
+					..
+				} => code::QUERY_SCAN_LIMIT,
 			})
 			.unwrap_or(code::INTERNAL_ERROR);
 		Self {
@@ -706,6 +729,12 @@ pub enum AuthError {
 	/// Signup failed.
 	#[surreal(skip_content)]
 	InvalidSignup,
+	/// The credentials were accepted, but a second factor is required to
+	/// complete the signin. Carries the challenge to verify with a one-time code.
+	MfaRequired {
+		/// The challenge which has to be sent back along with the code.
+		challenge: String,
+	},
 	/// Invalid role (IAM). Carries the role name.
 	InvalidRole {
 		/// Name of the invalid role.
@@ -881,6 +910,44 @@ pub enum QueryError {
 	/// Transaction conflict; the operation can be retried.
 	#[surreal(skip_content)]
 	TransactionConflict,
+	/// A write was rejected by the write throttle of a table; the operation
+	/// can be retried once the write rate has dropped.
+	ThrottleExceeded {
+		/// Name of the throttled table.
+		table: String,
+		/// Number of writes per second allowed on the table.
+		rate: u64,
+	},
+	/// The operation was rejected by a rate limit; it can be retried once the
+	/// retry-after duration has elapsed.
+	RateLimited {
+		/// Duration after which the operation can be retried.
+		retry_after: Duration,
+	},
+	/// A client-managed transaction gave up waiting for a lock held by another
+	/// transaction; the transaction should be cancelled and retried.
+	DeadlockDetected {
+		/// Description of the locked resource, such as the record which was
+		/// waited for.
+		resource: String,
+	},
+	/// A transaction would have written more than the storage engine accepts
+	/// in a single transaction; bulk statements marked `NON ATOMIC` are split
+	/// into several transactions instead.
+	TooLarge {
+		/// Number of bytes the transaction would have written.
+		size: u64,
+		/// Largest number of bytes the storage engine accepts.
+		limit: u64,
+	},
+	/// A statement scanned more rows than its scan budget allows; the budget
+	/// of a single `SELECT` can be raised with `SCAN LIMIT`.
+	ScanLimitExceeded {
+		/// Name of the operator which exceeded the budget, such as `TableScan`.
+		operator: String,
+		/// Number of rows the statement was allowed to scan.
+		limit: u64,
+	},
 }
 
 /// Already-exists reason for [`ErrorKind::AlreadyExists`] errors.
@@ -929,6 +996,8 @@ pub enum ConnectionError {
 	AlreadyConnected,
 	/// Connection or transport failed (e.g. network error, DNS failure, WebSocket error).
 	ConnectionFailed,
+	/// The server is not compatible with this version of the client.
+	Incompatible,
 }
 
 impl fmt::Display for Error {
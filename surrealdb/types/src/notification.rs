@@ -22,7 +22,16 @@ pub enum Action {
 	Update,
 	/// Record was deleted.
 	Delete,
+	/// Record was evicted from a table defined with `EVICT`.
+	///
+	/// Evictions are deletions made by the datastore, so the `result` field of
+	/// the accompanying [`Notification`] is the same as for [`Action::Delete`].
+	Evicted,
 	/// The live query was killed.
+	///
+	/// The `result` field of the accompanying [`Notification`] carries the
+	/// reason the live query was killed as a string, such as the removal of
+	/// its table. No further notifications are sent for the live query.
 	Killed,
 	/// The live query WHERE clause or projection raised an evaluation error.
 	///
@@ -39,6 +48,7 @@ impl Display for Action {
 			Action::Create => write!(f, "CREATE"),
 			Action::Update => write!(f, "UPDATE"),
 			Action::Delete => write!(f, "DELETE"),
+			Action::Evicted => write!(f, "EVICTED"),
 			Action::Killed => write!(f, "KILLED"),
 			Action::Error => write!(f, "ERROR"),
 		}
@@ -53,6 +63,7 @@ impl FromStr for Action {
 			"CREATE" => Ok(Action::Create),
 			"UPDATE" => Ok(Action::Update),
 			"DELETE" => Ok(Action::Delete),
+			"EVICTED" => Ok(Action::Evicted),
 			"KILLED" => Ok(Action::Killed),
 			"ERROR" => Ok(Action::Error),
 			_ => Err(crate::Error::validation(format!("Invalid action: {s}"), None)),
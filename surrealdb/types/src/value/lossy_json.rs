@@ -0,0 +1,384 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde_json::Value as JsonValue;
+
+use crate::{
+	Array, Bytes, Datetime, Decimal, Duration, Kind, Number, Object, RecordId, RecordIdKey, Set,
+	SurrealValue, Table, Uuid, Value,
+};
+
+/// A value which lost type information when it was converted into JSON
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonLoss {
+	/// The location of the value within the JSON document, as a JSON pointer
+	pub path: String,
+	/// The type of the value before it was converted
+	pub kind: Kind,
+}
+
+/// The values which lost type information when a value was converted into
+/// JSON, as returned by [`Value::to_json_lossy`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsonLossReport {
+	losses: Vec<JsonLoss>,
+}
+
+impl JsonLossReport {
+	/// Returns true if the conversion kept the type of every value
+	pub fn is_lossless(&self) -> bool {
+		self.losses.is_empty()
+	}
+
+	/// Returns the values which lost type information, in document order
+	pub fn losses(&self) -> &[JsonLoss] {
+		&self.losses
+	}
+}
+
+impl IntoIterator for JsonLossReport {
+	type Item = JsonLoss;
+	type IntoIter = std::vec::IntoIter<JsonLoss>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.losses.into_iter()
+	}
+}
+
+/// The declared types of the fields of a document, used to reconstruct typed
+/// values from JSON with [`Value::from_json_with_hints`]
+///
+/// Fields are named as in a `DEFINE FIELD` statement, so nested fields are
+/// separated with a `.`, and the elements of an array are named with `*`.
+///
+/// ```
+/// use surrealdb_types::{JsonHints, Kind};
+///
+/// let hints = JsonHints::new()
+/// 	.field("created_at", Kind::Datetime)
+/// 	.field("author", Kind::Record(vec!["person".into()]))
+/// 	.field("visits.*.duration", Kind::Duration);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsonHints {
+	fields: BTreeMap<String, Kind>,
+}
+
+impl JsonHints {
+	/// Creates an empty set of hints
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Declares the type of a field
+	pub fn field(mut self, name: impl Into<String>, kind: Kind) -> Self {
+		self.fields.insert(name.into(), kind);
+		self
+	}
+
+	/// Returns the declared type of a field
+	pub fn kind(&self, name: &str) -> Option<&Kind> {
+		self.fields.get(name)
+	}
+}
+
+impl<S: Into<String>> FromIterator<(S, Kind)> for JsonHints {
+	fn from_iter<I: IntoIterator<Item = (S, Kind)>>(iter: I) -> Self {
+		Self {
+			fields: iter.into_iter().map(|(name, kind)| (name.into(), kind)).collect(),
+		}
+	}
+}
+
+impl Value {
+	/// Converts the value into its JSON representation, along with a report of
+	/// the values which lost type information in the conversion.
+	///
+	/// The JSON is the same as returned by [`Value::into_json_value`]. Values
+	/// without a JSON counterpart, such as datetimes, durations and record ids,
+	/// are listed in the report with their location and original type.
+	pub fn to_json_lossy(&self) -> (JsonValue, JsonLossReport) {
+		let mut report = JsonLossReport::default();
+		collect_losses(self, &mut String::new(), &mut report.losses);
+		(self.clone().into_json_value(), report)
+	}
+
+	/// Converts a JSON value into a value, using the declared types of the
+	/// fields of the document to reconstruct the values which JSON can not
+	/// represent.
+	///
+	/// A string in a field declared as a datetime, duration, uuid, decimal,
+	/// record or table is parsed into that type, an array in a field declared
+	/// as a set or bytes is converted into that type, and `null` in an optional
+	/// field becomes `NONE`. A value which does not match its declared type is
+	/// left as is, in which case the database will report the mismatch.
+	pub fn from_json_with_hints(json: JsonValue, hints: &JsonHints) -> Value {
+		from_json(json, &mut String::new(), None, hints)
+	}
+}
+
+/// Records the values which have no JSON counterpart, with their JSON pointer
+fn collect_losses(value: &Value, path: &mut String, losses: &mut Vec<JsonLoss>) {
+	let lossy = match value {
+		Value::Null | Value::Bool(_) | Value::String(_) | Value::Array(_) | Value::Object(_) => {
+			false
+		}
+		Value::Number(Number::Int(_)) => false,
+		Value::Number(Number::Float(float)) => !float.is_finite(),
+		_ => true,
+	};
+	if lossy {
+		let kind = match value {
+			Value::Number(number) => number.kind(),
+			value => value.kind(),
+		};
+		losses.push(JsonLoss {
+			path: path.clone(),
+			kind,
+		});
+	}
+	let len = path.len();
+	match value {
+		Value::Array(array) => {
+			for (index, value) in array.iter().enumerate() {
+				path.push_str(&format!("/{index}"));
+				collect_losses(value, path, losses);
+				path.truncate(len);
+			}
+		}
+		Value::Set(set) => {
+			for (index, value) in set.iter().enumerate() {
+				path.push_str(&format!("/{index}"));
+				collect_losses(value, path, losses);
+				path.truncate(len);
+			}
+		}
+		Value::Object(object) => {
+			for (key, value) in object.iter() {
+				path.push('/');
+				path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+				collect_losses(value, path, losses);
+				path.truncate(len);
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Converts a JSON value at the named field, using the declared type of the
+/// field, or else the type inherited from its parent array
+fn from_json(
+	json: JsonValue,
+	name: &mut String,
+	inherited: Option<&Kind>,
+	hints: &JsonHints,
+) -> Value {
+	let kind = hints.kind(name).or(inherited);
+	let len = name.len();
+	match json {
+		JsonValue::Null => match kind {
+			Some(kind) if allows(kind, &Kind::None) && !allows(kind, &Kind::Null) => Value::None,
+			_ => Value::Null,
+		},
+		JsonValue::String(string) => {
+			kind.and_then(|kind| parse_string(&string, kind)).unwrap_or(Value::String(string))
+		}
+		JsonValue::Array(values) => {
+			if kind.is_some_and(|kind| allows(kind, &Kind::Bytes))
+				&& let Some(bytes) = values
+					.iter()
+					.map(|value| value.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+					.collect::<Option<Vec<u8>>>()
+			{
+				return Value::Bytes(Bytes::from(bytes));
+			}
+			let element = kind.and_then(element_kind);
+			if !name.is_empty() {
+				name.push('.');
+			}
+			name.push('*');
+			let values: Vec<Value> =
+				values.into_iter().map(|value| from_json(value, name, element, hints)).collect();
+			name.truncate(len);
+			if kind.is_some_and(|kind| candidates(kind).any(|kind| matches!(kind, Kind::Set(..)))) {
+				Value::Set(Set::from(values))
+			} else {
+				Value::Array(Array::from(values))
+			}
+		}
+		JsonValue::Object(fields) => {
+			let mut object = Object::new();
+			for (key, value) in fields {
+				if !name.is_empty() {
+					name.push('.');
+				}
+				name.push_str(&key);
+				let value = from_json(value, name, None, hints);
+				name.truncate(len);
+				object.insert(key, value);
+			}
+			Value::Object(object)
+		}
+		json => json.into_value(),
+	}
+}
+
+/// Returns the kinds which a value of the given kind may have
+fn candidates(kind: &Kind) -> Box<dyn Iterator<Item = &Kind> + '_> {
+	match kind {
+		Kind::Either(kinds) => Box::new(kinds.iter().flat_map(candidates)),
+		kind => Box::new(std::iter::once(kind)),
+	}
+}
+
+/// Returns true if a value of the given kind may have the expected kind
+fn allows(kind: &Kind, expected: &Kind) -> bool {
+	candidates(kind).any(|kind| kind == expected)
+}
+
+/// Returns the kind of the elements of an array or set kind
+fn element_kind(kind: &Kind) -> Option<&Kind> {
+	candidates(kind).find_map(|kind| match kind {
+		Kind::Array(element, _) | Kind::Set(element, _) => Some(element.as_ref()),
+		_ => None,
+	})
+}
+
+/// Parses a string which encodes a value of the given kind
+fn parse_string(string: &str, kind: &Kind) -> Option<Value> {
+	candidates(kind).find_map(|kind| match kind {
+		Kind::Datetime => Datetime::from_str(string).ok().map(Value::Datetime),
+		Kind::Duration => Duration::from_str(string).ok().map(Value::Duration),
+		Kind::Uuid => Uuid::from_str(string).ok().map(Value::Uuid),
+		Kind::Decimal | Kind::Number => {
+			Decimal::from_str(string).ok().map(|decimal| Value::Number(Number::Decimal(decimal)))
+		}
+		Kind::Table(tables) => {
+			let table = Table::new(unescape(string));
+			(tables.is_empty() || tables.contains(&table)).then_some(Value::Table(table))
+		}
+		Kind::Record(tables) => parse_record_id(string)
+			.filter(|record| record.is_table_type(tables))
+			.map(Value::RecordId),
+		_ => None,
+	})
+}
+
+/// Parses a record id as formatted by [`Value::into_json_value`]
+///
+/// Only number, string and uuid keys are supported.
+fn parse_record_id(string: &str) -> Option<RecordId> {
+	let (table, key) = split_record_id(string)?;
+	let key = if let Ok(number) = key.parse::<i64>() {
+		RecordIdKey::Number(number)
+	} else if let Some(uuid) = key.strip_prefix("u'").and_then(|key| key.strip_suffix('\'')) {
+		RecordIdKey::Uuid(Uuid::from_str(uuid).ok()?)
+	} else if key.starts_with(['[', '{']) || key.contains("..") {
+		return None;
+	} else {
+		RecordIdKey::String(unescape(key))
+	};
+	Some(RecordId::new(unescape(table), key))
+}
+
+/// Splits a record id at the `:` which follows its table name
+fn split_record_id(string: &str) -> Option<(&str, &str)> {
+	if let Some(rest) = string.strip_prefix('`') {
+		let mut escaped = false;
+		for (index, char) in rest.char_indices() {
+			match char {
+				'\\' if !escaped => escaped = true,
+				'`' if !escaped => {
+					let end = index + 2;
+					return string[end..].strip_prefix(':').map(|key| (&string[..end], key));
+				}
+				_ => escaped = false,
+			}
+		}
+		None
+	} else {
+		string.split_once(':')
+	}
+}
+
+/// Removes the backticks which escape an identifier or record key
+fn unescape(string: &str) -> String {
+	match string.strip_prefix('`').and_then(|string| string.strip_suffix('`')) {
+		Some(string) => string.replace("\\`", "`").replace("\\\\", "\\"),
+		None => string.to_owned(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::*;
+
+	#[test]
+	fn lossy_conversions_are_reported() {
+		let value = Value::from_t(crate::object! {
+			name: "Tobie",
+			created_at: Datetime::from_timestamp(0, 0).unwrap(),
+			author: RecordId::new("person", "tobie"),
+			tags: vec![Value::from_t(Duration::from_secs(60))],
+		});
+		let (json, report) = value.to_json_lossy();
+		assert_eq!(json, value.clone().into_json_value());
+		let losses: Vec<_> =
+			report.losses().iter().map(|loss| (loss.path.as_str(), loss.kind.clone())).collect();
+		assert_eq!(
+			losses,
+			vec![
+				("/author", Kind::Record(Vec::new())),
+				("/created_at", Kind::Datetime),
+				("/tags/0", Kind::Duration),
+			]
+		);
+		assert!(Value::from_t("plain").to_json_lossy().1.is_lossless());
+	}
+
+	#[test]
+	fn hints_reconstruct_typed_values() {
+		let hints = JsonHints::new()
+			.field("created_at", Kind::Datetime)
+			.field("author", Kind::Record(vec!["person".into()]))
+			.field("visits", Kind::Set(Box::new(Kind::Duration), None))
+			.field("deleted_at", Kind::option(Kind::Datetime))
+			.field("total", Kind::Decimal);
+		let json = json!({
+			"name": "Tobie",
+			"created_at": "1970-01-01T00:00:00Z",
+			"author": "person:tobie",
+			"visits": ["1m", "1h"],
+			"deleted_at": null,
+			"total": "12.50",
+		});
+		let value = Value::from_json_with_hints(json, &hints);
+		let Value::Object(object) = value else {
+			panic!("expected an object");
+		};
+		assert_eq!(object.get("name"), Some(&Value::from_t("Tobie")));
+		assert!(matches!(object.get("created_at"), Some(Value::Datetime(_))));
+		assert_eq!(object.get("author"), Some(&Value::RecordId(RecordId::new("person", "tobie"))));
+		assert!(matches!(object.get("visits"), Some(Value::Set(_))));
+		assert_eq!(object.get("deleted_at"), Some(&Value::None));
+		assert!(matches!(object.get("total"), Some(Value::Number(Number::Decimal(_)))));
+	}
+
+	#[test]
+	fn record_ids_round_trip() {
+		for record in [
+			RecordId::new("person", 1),
+			RecordId::new("person", "1"),
+			RecordId::new("person", "tobie smith"),
+			RecordId::new("a table", "tobie"),
+		] {
+			let json = Value::RecordId(record.clone()).into_json_value();
+			let JsonValue::String(string) = json else {
+				panic!("expected a string");
+			};
+			assert_eq!(parse_record_id(&string), Some(record));
+		}
+	}
+}
@@ -13,6 +13,8 @@ mod format;
 pub mod geometry;
 /// JSON value types for SurrealDB
 pub mod into_json;
+/// Lossy JSON conversions for SurrealDB values
+pub mod lossy_json;
 /// Numeric value types for SurrealDB
 pub mod number;
 /// Object value types for SurrealDB
@@ -42,6 +44,7 @@ pub use self::datetime::Datetime;
 pub use self::duration::Duration;
 pub use self::file::File;
 pub use self::geometry::Geometry;
+pub use self::lossy_json::{JsonHints, JsonLoss, JsonLossReport};
 pub use self::number::Number;
 pub use self::object::Object;
 pub use self::range::Range;
@@ -305,6 +305,32 @@ fn test_public_error_with_details() {
 	assert!(matches!(d, NotAllowedError::Auth(AuthError::TokenExpired)));
 }
 
+#[test]
+fn test_public_error_mfa_required_details() {
+	let err = Error::not_allowed(
+		"A second factor is required".to_string(),
+		AuthError::MfaRequired {
+			challenge: "surreal-mfa-abc".into(),
+		},
+	);
+	let val = err.into_value();
+	let Value::Object(ref obj) = val else {
+		panic!("Expected object, got {val:?}");
+	};
+	assert_eq!(obj.get("code"), Some(&Value::Number(Number::Int(-32002))));
+
+	// The challenge survives the round-trip
+	let parsed = Error::from_value(val).unwrap();
+	assert!(parsed.is_not_allowed());
+	let Some(NotAllowedError::Auth(AuthError::MfaRequired {
+		challenge,
+	})) = parsed.not_allowed_details()
+	else {
+		panic!("Expected MFA required details");
+	};
+	assert_eq!(challenge, "surreal-mfa-abc");
+}
+
 #[test]
 fn test_public_error_validation_details() {
 	let err =
@@ -1408,6 +1434,174 @@ fn test_error_wire_query_transaction_conflict() {
 	assert_eq!(parsed.query_details(), Some(&QueryError::TransactionConflict));
 }
 
+#[test]
+fn test_error_wire_query_throttle_exceeded() {
+	// Wire format:
+	// {
+	//   "code": -32010,
+	//   "message": "Write throttle exceeded",
+	//   "kind": "Query",
+	//   "details": { "kind": "ThrottleExceeded", "details": { "table": "logs", "rate": 1000 } }
+	// }
+	let err = Error::query(
+		"Write throttle exceeded".into(),
+		QueryError::ThrottleExceeded {
+			table: "logs".into(),
+			rate: 1000,
+		},
+	);
+	let val = err.into_value();
+
+	let Value::Object(ref obj) = val else {
+		panic!();
+	};
+	assert_eq!(obj.get("code"), Some(&Value::Number(Number::Int(-32010))));
+
+	let parsed = Error::from_value(val).unwrap();
+	assert!(parsed.is_query());
+	assert_eq!(
+		parsed.query_details(),
+		Some(&QueryError::ThrottleExceeded {
+			table: "logs".into(),
+			rate: 1000,
+		})
+	);
+}
+
+#[test]
+fn test_error_wire_query_rate_limited() {
+	// Wire format:
+	// {
+	//   "code": -32011,
+	//   "message": "Too many access grants",
+	//   "kind": "Query",
+	//   "details": { "kind": "RateLimited", "details": { "retry_after": { "secs": 30, "nanos": 0 } } }
+	// }
+	use std::time::Duration;
+
+	let err = Error::query(
+		"Too many access grants".into(),
+		QueryError::RateLimited {
+			retry_after: Duration::from_secs(30),
+		},
+	);
+	let val = err.into_value();
+
+	let Value::Object(ref obj) = val else {
+		panic!();
+	};
+	assert_eq!(obj.get("code"), Some(&Value::Number(Number::Int(-32011))));
+
+	let parsed = Error::from_value(val).unwrap();
+	assert!(parsed.is_query());
+	assert_eq!(
+		parsed.query_details(),
+		Some(&QueryError::RateLimited {
+			retry_after: Duration::from_secs(30),
+		})
+	);
+}
+
+#[test]
+fn test_error_wire_query_deadlock_detected() {
+	// Wire format:
+	// {
+	//   "code": -32012,
+	//   "message": "A deadlock was detected",
+	//   "kind": "Query",
+	//   "details": { "kind": "DeadlockDetected", "details": { "resource": "record account:one" } }
+	// }
+	let err = Error::query(
+		"A deadlock was detected".into(),
+		QueryError::DeadlockDetected {
+			resource: "record account:one".into(),
+		},
+	);
+	let val = err.into_value();
+
+	let Value::Object(ref obj) = val else {
+		panic!();
+	};
+	assert_eq!(obj.get("code"), Some(&Value::Number(Number::Int(-32012))));
+
+	let parsed = Error::from_value(val).unwrap();
+	assert!(parsed.is_query());
+	assert_eq!(
+		parsed.query_details(),
+		Some(&QueryError::DeadlockDetected {
+			resource: "record account:one".into(),
+		})
+	);
+}
+
+#[test]
+fn test_error_wire_query_too_large() {
+	// Wire format:
+	// {
+	//   "code": -32013,
+	//   "message": "The transaction is too large",
+	//   "kind": "Query",
+	//   "details": { "kind": "TooLarge", "details": { "size": 120000000, "limit": 104857600 } }
+	// }
+	let err = Error::query(
+		"The transaction is too large".into(),
+		QueryError::TooLarge {
+			size: 120_000_000,
+			limit: 104_857_600,
+		},
+	);
+	let val = err.into_value();
+
+	let Value::Object(ref obj) = val else {
+		panic!();
+	};
+	assert_eq!(obj.get("code"), Some(&Value::Number(Number::Int(-32013))));
+
+	let parsed = Error::from_value(val).unwrap();
+	assert!(parsed.is_query());
+	assert_eq!(
+		parsed.query_details(),
+		Some(&QueryError::TooLarge {
+			size: 120_000_000,
+			limit: 104_857_600,
+		})
+	);
+}
+
+#[test]
+fn test_error_wire_query_scan_limit_exceeded() {
+	// Wire format:
+	// {
+	//   "code": -32014,
+	//   "message": "The statement scanned more than 1000 rows",
+	//   "kind": "Query",
+	//   "details": { "kind": "ScanLimitExceeded", "details": { "operator": "TableScan", "limit": 1000 } }
+	// }
+	let err = Error::query(
+		"The statement scanned more than 1000 rows".into(),
+		QueryError::ScanLimitExceeded {
+			operator: "TableScan".into(),
+			limit: 1000,
+		},
+	);
+	let val = err.into_value();
+
+	let Value::Object(ref obj) = val else {
+		panic!();
+	};
+	assert_eq!(obj.get("code"), Some(&Value::Number(Number::Int(-32014))));
+
+	let parsed = Error::from_value(val).unwrap();
+	assert!(parsed.is_query());
+	assert_eq!(
+		parsed.query_details(),
+		Some(&QueryError::ScanLimitExceeded {
+			operator: "TableScan".into(),
+			limit: 1000,
+		})
+	);
+}
+
 #[test]
 fn test_error_wire_query_not_executed() {
 	// Wire format: